@@ -272,6 +272,8 @@ impl WorkloadSpec {
                     unparsed_create_cache_statement: None,
                     always: false,
                     concurrently: false,
+                    frontier_strategy: None,
+                    disable_predicate_pushdown: false,
                 };
 
                 let _ = conn
@@ -252,6 +252,7 @@ fn query_for_prepared_insert(
         },
         ignore: false,
         on_duplicate: None,
+        select: None,
     }
     .display(dialect)
     .to_string()
@@ -409,6 +410,7 @@ pub async fn load_to_backend(db: &mut Backend, mut spec: DatabaseGenerationSpec)
                 .collect(),
             ignore: false,
             on_duplicate: None,
+            select: None,
         };
 
         db.query(&insert.display(db.dialect()).to_string())
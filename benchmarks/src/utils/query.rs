@@ -215,6 +215,8 @@ impl ArbitraryQueryParameters {
             always: false,
             concurrently: false,
             unparsed_create_cache_statement: None,
+            frontier_strategy: None,
+            disable_predicate_pushdown: false,
         };
 
         conn.query_drop(create_cache_query.display(conn.dialect()).to_string())
@@ -157,6 +157,8 @@ async fn create_indices(
                 always: false,
                 concurrently: false,
                 unparsed_create_cache_statement: None,
+                frontier_strategy: None,
+                disable_predicate_pushdown: false,
             };
             conn.query_drop(create_cache.display(conn.dialect()).to_string())
                 .await?;
@@ -320,6 +322,7 @@ impl MultithreadBenchmark for WriteBenchmark {
                         .collect(),
                     ignore: false,
                     on_duplicate: None,
+                    select: None,
                 }
                 .display(dialect)
                 .to_string()
@@ -469,7 +469,7 @@ pub fn value_of_type(typ: &SqlType) -> DfValue {
         SqlType::VarBit(_) => DfValue::from(BitVec::new()),
         SqlType::Interval { .. } => unimplemented!(),
         SqlType::Array(_) => unimplemented!(),
-        SqlType::Other(_) => unimplemented!(),
+        SqlType::Geometry(_) | SqlType::Other(_) => unimplemented!(),
     }
 }
 
@@ -603,7 +603,7 @@ where
         SqlType::BigSerial => ((rng.gen::<u64>() + 1) as i64).into(),
         SqlType::Interval { .. } => unimplemented!(),
         SqlType::Array(_) => unimplemented!(),
-        SqlType::Other(_) => unimplemented!(),
+        SqlType::Geometry(_) | SqlType::Other(_) => unimplemented!(),
     }
 }
 
@@ -737,6 +737,6 @@ pub fn unique_value_of_type(typ: &SqlType, idx: u32) -> DfValue {
         SqlType::BigSerial => ((idx + 1) as i64).into(),
         SqlType::Interval { .. } => unimplemented!(),
         SqlType::Array(_) => unimplemented!(),
-        SqlType::Other(_) => unimplemented!(),
+        SqlType::Geometry(_) | SqlType::Other(_) => unimplemented!(),
     }
 }
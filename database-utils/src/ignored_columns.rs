@@ -0,0 +1,74 @@
+use std::collections::{HashMap, HashSet};
+
+use nom_sql::{Relation, SqlIdentifier};
+use readyset_errors::{ReadySetError, ReadySetResult};
+use serde::{Deserialize, Serialize};
+
+/// The set of columns of upstream tables which are never snapshotted or replicated, configured
+/// with --replication-columns-ignore.
+///
+/// Ignored columns remain part of the schema of their table in ReadySet, but are always
+/// replicated as `NULL`, and queries which reference them can't be cached.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IgnoredColumns {
+    columns: HashMap<Relation, HashSet<SqlIdentifier>>,
+}
+
+impl IgnoredColumns {
+    /// Parse a comma-separated list of fully qualified columns, of the form
+    /// `<schema>.<table>.<column>`
+    pub fn try_new(columns: &str) -> ReadySetResult<IgnoredColumns> {
+        let mut res = IgnoredColumns::default();
+        for column in columns.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+            match column.split('.').map(str::trim).collect::<Vec<_>>()[..] {
+                [schema, table, name]
+                    if !schema.is_empty() && !table.is_empty() && !name.is_empty() =>
+                {
+                    res.columns
+                        .entry(Relation {
+                            schema: Some(schema.into()),
+                            name: table.into(),
+                        })
+                        .or_default()
+                        .insert(name.into());
+                }
+                _ => {
+                    return Err(ReadySetError::ReplicationFailed(format!(
+                        "Ignored column {column} must be of the form <schema>.<table>.<column>"
+                    )))
+                }
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Returns true if no columns are ignored
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Returns the ignored columns of the given table, if any
+    pub fn for_table(&self, table: &Relation) -> Option<&HashSet<SqlIdentifier>> {
+        self.columns.get(table)
+    }
+
+    /// Returns true if the given column of the given table is ignored
+    pub fn contains(&self, table: &Relation, column: &str) -> bool {
+        self.for_table(table)
+            .is_some_and(|columns| columns.contains(column))
+    }
+
+    /// Returns the indices of the ignored columns among the given (ordered) columns of a table
+    pub fn indices_for_table(&self, table: &Relation, columns: &[SqlIdentifier]) -> Vec<usize> {
+        match self.for_table(table) {
+            Some(ignored) => columns
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| ignored.contains(*c))
+                .map(|(idx, _)| idx)
+                .collect(),
+            None => vec![],
+        }
+    }
+}
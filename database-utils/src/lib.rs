@@ -23,12 +23,22 @@ use crate::error::DatabaseURLParseError;
 
 mod connection;
 pub mod error;
+mod ignored_columns;
+mod tls;
+mod value_size_caps;
 
 pub use connection::{
     DatabaseConnection, DatabaseConnectionPool, DatabaseStatement, QueryResults,
     QueryableConnection, SimpleQueryResults, Transaction,
 };
 pub use error::DatabaseError;
+pub use ignored_columns::IgnoredColumns;
+pub use tls::UpstreamTlsConnector;
+pub use value_size_caps::ValueSizeCaps;
+
+/// The `server_id` a MySQL replicator registers with if no
+/// [`UpstreamConfig::replication_server_id`] is configured
+pub const DEFAULT_MYSQL_SERVER_ID: u32 = u32::MAX - 55;
 
 #[allow(missing_docs)] // If we add docs they get added into --help binary text which is confusing
 #[derive(Debug, Clone, Parser, PartialEq, Eq, Serialize, Deserialize)]
@@ -39,6 +49,29 @@ pub struct UpstreamConfig {
     #[serde(default)]
     pub upstream_db_url: Option<RedactedString>,
 
+    /// Additional upstream databases to replicate from, as a comma-separated list of
+    /// `<namespace>=<url>` pairs. Tables in schema `<schema>` of each additional upstream
+    /// database are replicated into schema `<namespace>_<schema>`, and can be queried alongside
+    /// the tables replicated from --upstream-db-url.
+    ///
+    /// Each additional upstream database is snapshotted and replicated independently, and must be
+    /// of the same type as the database at --upstream-db-url. Namespaces may only contain lower
+    /// case letters and numbers, and must be 16 characters or fewer.
+    #[arg(
+        long = "additional-upstream-db-url",
+        env = "ADDITIONAL_UPSTREAM_DB_URLS",
+        value_delimiter = ',',
+        hide = true
+    )]
+    #[serde(default)]
+    pub additional_upstreams: Vec<AdditionalUpstream>,
+
+    /// The namespace relations replicated from [`Self::upstream_db_url`] are mapped into, if this
+    /// configuration is for one of the [`Self::additional_upstreams`] of another configuration
+    #[arg(skip)]
+    #[serde(default)]
+    pub replication_namespace: Option<String>,
+
     /// Disable verification of SSL certificates supplied by the upstream database (postgres
     /// only, ignored for mysql). Ignored if `--upstream-db-url` is not passed.
     ///
@@ -57,6 +90,25 @@ pub struct UpstreamConfig {
     #[serde(default)]
     pub ssl_root_cert: Option<PathBuf>,
 
+    /// A path to a PKCS#12 archive containing the client certificate and private key that the
+    /// upstream connection will present, for databases which require clients to authenticate with
+    /// a certificate.
+    #[arg(long, env = "SSL_CLIENT_IDENTITY")]
+    #[serde(default)]
+    pub ssl_client_identity: Option<PathBuf>,
+
+    /// The password used to decrypt the archive at `--ssl-client-identity`, if it is encrypted.
+    #[arg(long, env = "SSL_CLIENT_IDENTITY_PASSWORD")]
+    #[serde(default)]
+    pub ssl_client_identity_password: Option<RedactedString>,
+
+    /// The server name to send via SNI and to verify the certificate supplied by the upstream
+    /// database against, instead of the host in `--upstream-db-url` (postgres only, ignored for
+    /// mysql). Useful when connecting to the upstream database via an IP address or a proxy.
+    #[arg(long, env = "SSL_SERVER_NAME")]
+    #[serde(default)]
+    pub ssl_server_name: Option<String>,
+
     /// Disable running DDL Streaming Replication Setup for PostgreSQL. If this flag is set
     /// the DDL Streaming Replication Setup SQL queries will need to be manually run on the
     /// primary server before streaming replication will start.
@@ -69,10 +121,10 @@ pub struct UpstreamConfig {
     /// This can be used to differentiate different ReadySet deployments connected to the same
     /// upstream DB.
     ///
-    /// This ends up being a suffix of the replication slot or resnapshot replication slot, which
-    /// have prefixes of 'readyset_' and 'readyset_resnapshot_', respectively. Since a replication
-    /// slot is limited by postgres to a length of 63 bytes, that means this server id must be 43
-    /// bytes or fewer.
+    /// This ends up being a suffix of the publication, replication slot, and resnapshot
+    /// replication slot, which have prefixes of 'readyset_', 'readyset_' and
+    /// 'readyset_resnapshot_', respectively. Since a replication slot is limited by postgres to a
+    /// length of 63 bytes, that means this server id must be 43 bytes or fewer.
     #[arg(long, env = "REPLICATION_SERVER_ID", hide = true, value_parser = parse_repl_server_id)]
     #[serde(default)]
     pub replication_server_id: Option<ReplicationServerId>,
@@ -110,6 +162,37 @@ pub struct UpstreamConfig {
     #[serde(default)]
     pub replication_tables_ignore: Option<RedactedString>,
 
+    /// Restricts the rows of individual tables that ReadySet snapshots and replicates to those
+    /// matching a filter expression, for example to only replicate the rows belonging to some
+    /// tenants of a multi-tenant database, or to skip soft-deleted rows.
+    ///
+    /// This option accepts a semicolon-separated list of `<schema>.<table>:<expression>`, where
+    /// `<expression>` is a SQL expression in terms of the columns of the table, such as
+    /// `public.orders:tenant_id = 42; public.users:deleted_at IS NULL`. Updates that move rows out
+    /// of the filter are only observed if the upstream database provides the full new row.
+    #[arg(long, env = "REPLICATION_ROW_FILTERS")]
+    #[serde(default)]
+    pub replication_row_filters: Option<RedactedString>,
+
+    /// Columns of upstream tables that ReadySet will never snapshot or replicate, such as columns
+    /// containing personally identifiable information or large blobs.
+    ///
+    /// This option accepts a comma-separated list of `<schema>.<table>.<column>`. Ignored columns
+    /// are replicated as `NULL`, and queries that reference them can't be cached. Columns that are
+    /// part of the primary key of their table can't be ignored.
+    #[arg(long, env = "REPLICATION_COLUMNS_IGNORE")]
+    #[serde(default)]
+    pub replication_columns_ignore: Option<RedactedString>,
+
+    /// Caps the size of the values of individual columns of upstream tables that ReadySet will
+    /// snapshot and replicate, to bound the memory used by tables with large BLOB or TEXT columns.
+    ///
+    /// This option accepts a comma-separated list of `<schema>.<table>.<column>=<bytes>`. Values
+    /// larger than the cap of their column are replicated as `NULL`, and a warning is logged.
+    #[arg(long, env = "REPLICATION_VALUE_SIZE_CAPS")]
+    #[serde(default)]
+    pub replication_value_size_caps: Option<String>,
+
     /// Sets the time (in seconds) between reports of progress snapshotting the database. A value
     /// of 0 disables reporting.
     #[arg(long, default_value = "30", hide = true)]
@@ -121,11 +204,62 @@ pub struct UpstreamConfig {
     #[serde(default = "default_max_parallel_snapshot_tables")]
     pub max_parallel_snapshot_tables: Option<usize>,
 
-    /// Sets the connection count for the pool that is used for replication and snapshotting.
+    /// Sets the connection count for the pool that is used for snapshotting. Replication events
+    /// are streamed over separate, dedicated connections.
     #[arg(long, default_value = "50", hide = true)]
     #[serde(default)]
     pub replication_pool_size: usize,
 
+    /// Sets the minimum number of connections kept open in the pool that is used for
+    /// snapshotting (mysql only, ignored for postgres). Values above `--replication-pool-size`
+    /// are capped at the pool size.
+    #[arg(long, default_value = "10", hide = true)]
+    #[serde(default = "default_snapshot_pool_min_size")]
+    pub snapshot_pool_min_size: usize,
+
+    /// Sets the time (in seconds) that a connection streaming replication events from the
+    /// upstream database may sit idle before TCP keepalives are sent on it. If unset, the
+    /// defaults of the database driver and operating system are used. Managed databases and the
+    /// proxies in front of them may close idle connections, interrupting replication from
+    /// databases that are written to infrequently.
+    #[arg(long, env = "STREAMING_TCP_KEEPALIVE_SECS", hide = true)]
+    #[serde(default)]
+    pub streaming_tcp_keepalive_secs: Option<u64>,
+
+    /// Sets the interval (in seconds) at which the upstream database is asked to send heartbeats
+    /// over the binlog connection while there are no new events to replicate (mysql only,
+    /// ignored for postgres). If neither events nor heartbeats are received for twice this
+    /// interval, the connection is presumed dead and replication is restarted. A value of 0
+    /// disables heartbeats.
+    #[arg(
+        long,
+        default_value = "30",
+        hide = true,
+        env = "REPLICATION_HEARTBEAT_INTERVAL_SECS"
+    )]
+    #[serde(default = "default_replication_heartbeat_interval_secs")]
+    pub replication_heartbeat_interval_secs: u16,
+
+    /// Sets the amount of WAL (in bytes) that the upstream database may retain for our
+    /// replication slot before we warn that replication is falling behind (postgres only, ignored
+    /// for mysql). A value of 0 disables the warning.
+    #[arg(
+        long,
+        default_value = "1073741824",
+        hide = true,
+        env = "REPLICATION_SLOT_LAG_WARNING_BYTES"
+    )]
+    #[serde(default = "default_replication_slot_lag_warning_bytes")]
+    pub replication_slot_lag_warning_bytes: u64,
+
+    /// Drop the replication slot on the upstream database when ReadySet shuts down gracefully, so
+    /// that the upstream database doesn't retain WAL for it while ReadySet isn't running
+    /// (postgres only, ignored for mysql). If set, ReadySet will perform a full snapshot of the
+    /// upstream database the next time it starts.
+    #[arg(long, env = "DROP_REPLICATION_SLOT_ON_SHUTDOWN")]
+    #[serde(default)]
+    pub drop_replication_slot_on_shutdown: bool,
+
     /// Allow ReadySet to start even if the file descriptor limit (ulimit -n) is below our minimum
     /// requirement.
     ///
@@ -168,6 +302,173 @@ impl UpstreamConfig {
         }
     }
 
+    /// Read the PKCS#12 archive at [`Self::ssl_client_identity`] path and try to parse it as a
+    /// client identity, decrypting it with [`Self::ssl_client_identity_password`]
+    pub async fn get_client_identity(&self) -> Option<ReadySetResult<native_tls::Identity>> {
+        let path = self.ssl_client_identity.as_ref()?;
+        let password = self.ssl_client_identity_password.as_deref().unwrap_or("");
+        Some(
+            tokio::fs::read(path)
+                .await
+                .map_err(ReadySetError::from)
+                .and_then(|identity| {
+                    native_tls::Identity::from_pkcs12(&identity, password)
+                        .map_err(|_| ReadySetError::InvalidClientIdentity)
+                }),
+        )
+    }
+
+    /// Build a connector for TLS connections to an upstream postgres database, trusting
+    /// [`Self::ssl_root_cert`], presenting [`Self::ssl_client_identity`], and verifying the
+    /// database against [`Self::ssl_server_name`]
+    pub async fn pg_tls_connector(&self) -> ReadySetResult<UpstreamTlsConnector> {
+        let mut builder = native_tls::TlsConnector::builder();
+        if self.disable_upstream_ssl_verification {
+            builder.danger_accept_invalid_certs(true);
+        }
+        if let Some(root_cert) = self.get_root_cert().await {
+            builder.add_root_certificate(root_cert?);
+        }
+        if let Some(identity) = self.get_client_identity().await {
+            builder.identity(identity?);
+        }
+        Ok(UpstreamTlsConnector::new(
+            builder.build()?,
+            self.ssl_server_name.clone(),
+        ))
+    }
+
+    /// Build the options for TLS connections to an upstream mysql database, trusting
+    /// [`Self::ssl_root_cert`] and presenting [`Self::ssl_client_identity`]. Returns `None` if
+    /// neither is configured.
+    pub fn mysql_ssl_opts(&self) -> Option<mysql::SslOpts> {
+        if self.ssl_root_cert.is_none() && self.ssl_client_identity.is_none() {
+            return None;
+        }
+
+        let mut ssl_opts = mysql::SslOpts::default();
+        if let Some(cert_path) = self.ssl_root_cert.clone() {
+            ssl_opts = ssl_opts.with_root_certs(vec![cert_path.into()]);
+        }
+        if let Some(identity_path) = self.ssl_client_identity.clone() {
+            let mut identity = mysql::ClientIdentity::new(identity_path.into());
+            if let Some(password) = self.ssl_client_identity_password.clone() {
+                identity = identity.with_password(String::from(password));
+            }
+            ssl_opts = ssl_opts.with_client_identity(Some(identity));
+        }
+        Some(ssl_opts)
+    }
+
+    /// Returns the time a streaming replication connection may sit idle before TCP keepalives
+    /// are sent on it, if keepalives are enabled
+    pub fn streaming_tcp_keepalive(&self) -> Option<Duration> {
+        self.streaming_tcp_keepalive_secs.map(Duration::from_secs)
+    }
+
+    /// Returns the interval at which the upstream database should send heartbeats over the binlog
+    /// connection, if heartbeats are enabled
+    pub fn replication_heartbeat_interval(&self) -> Option<Duration> {
+        (self.replication_heartbeat_interval_secs != 0)
+            .then(|| Duration::from_secs(self.replication_heartbeat_interval_secs.into()))
+    }
+
+    /// Returns the amount of WAL retained for our replication slot above which we warn that
+    /// replication is falling behind, if the warning is enabled
+    pub fn replication_slot_lag_warning_bytes(&self) -> Option<u64> {
+        (self.replication_slot_lag_warning_bytes != 0)
+            .then_some(self.replication_slot_lag_warning_bytes)
+    }
+
+    /// Returns a configuration for replicating from each of the [`Self::additional_upstreams`],
+    /// with all other options copied from this configuration.
+    ///
+    /// Table, row and column filters and value size caps only apply to [`Self::upstream_db_url`],
+    /// so they're cleared.
+    /// Replication slots and publications are named by the replication server id, so for postgres
+    /// the namespace is appended to it to keep the slots of different upstream databases on the
+    /// same server apart. For MySQL, every replica of a server must register with a distinct
+    /// numeric server id, so the server id of each additional upstream database is counted down
+    /// from the one used for [`Self::upstream_db_url`].
+    pub fn additional_upstream_configs(&self) -> ReadySetResult<Vec<UpstreamConfig>> {
+        self.additional_upstreams
+            .iter()
+            .enumerate()
+            .map(|(idx, upstream)| {
+                let database_type = upstream
+                    .url
+                    .parse::<DatabaseURL>()
+                    .map_err(|e| ReadySetError::UrlParseFailed(e.to_string()))?
+                    .database_type();
+                let replication_server_id = match database_type {
+                    DatabaseType::MySQL => {
+                        let base = match &self.replication_server_id {
+                            Some(server_id) => server_id.0.parse::<u32>().map_err(|_| {
+                                ReadySetError::ReplicationFailed(format!(
+                                    "{server_id} is an invalid server id--it must be a valid u32."
+                                ))
+                            })?,
+                            None => DEFAULT_MYSQL_SERVER_ID,
+                        };
+                        let server_id = u32::try_from(idx + 1)
+                            .ok()
+                            .and_then(|offset| base.checked_sub(offset))
+                            .filter(|server_id| *server_id != 0)
+                            .ok_or_else(|| {
+                                ReadySetError::ReplicationFailed(format!(
+                                    "Replication server id {base} is too small to assign a \
+                                     distinct server id to each additional upstream database"
+                                ))
+                            })?;
+                        Some(ReplicationServerId(server_id.to_string()))
+                    }
+                    DatabaseType::PostgreSQL => {
+                        let server_id = match &self.replication_server_id {
+                            Some(server_id) => format!("{server_id}_{}", upstream.namespace),
+                            None => upstream.namespace.clone(),
+                        };
+                        Some(parse_repl_server_id(&server_id).map_err(|e| {
+                            ReadySetError::ReplicationFailed(format!(
+                                "Invalid replication server id for upstream database in \
+                                 namespace {}: {e}",
+                                upstream.namespace
+                            ))
+                        })?)
+                    }
+                };
+
+                Ok(UpstreamConfig {
+                    upstream_db_url: Some(upstream.url.clone()),
+                    additional_upstreams: vec![],
+                    replication_namespace: Some(upstream.namespace.clone()),
+                    replication_server_id,
+                    replication_tables: None,
+                    replication_tables_ignore: None,
+                    replication_row_filters: None,
+                    replication_columns_ignore: None,
+                    replication_value_size_caps: None,
+                    ..self.clone()
+                })
+            })
+            .collect()
+    }
+
+    /// Parse the columns configured by [`Self::replication_columns_ignore`]
+    pub fn ignored_columns(&self) -> ReadySetResult<IgnoredColumns> {
+        match &self.replication_columns_ignore {
+            Some(columns) => IgnoredColumns::try_new(columns),
+            None => Ok(IgnoredColumns::default()),
+        }
+    }
+
+    /// Parse the size caps configured by [`Self::replication_value_size_caps`]
+    pub fn value_size_caps(&self) -> ReadySetResult<ValueSizeCaps> {
+        match &self.replication_value_size_caps {
+            Some(caps) => ValueSizeCaps::try_new(caps),
+            None => Ok(ValueSizeCaps::default()),
+        }
+    }
+
     pub fn from_url<S: AsRef<str>>(url: S) -> Self {
         UpstreamConfig {
             upstream_db_url: Some(url.as_ref().to_string().into()),
@@ -181,6 +482,45 @@ impl UpstreamConfig {
     }
 }
 
+/// An additional upstream database to replicate from, along with the namespace its relations are
+/// replicated into. Parsed from a string of the form `<namespace>=<url>`.
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub struct AdditionalUpstream {
+    pub namespace: String,
+    pub url: RedactedString,
+}
+
+impl FromStr for AdditionalUpstream {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (namespace, url) = s
+            .split_once('=')
+            .ok_or("Additional upstream databases must be of the form <namespace>=<url>")?;
+        let namespace = namespace.trim();
+
+        // The namespace ends up as a prefix of schema names, and as part of the names of
+        // replication slots, so keep it short and unambiguous
+        if namespace.is_empty() || namespace.len() > 16 {
+            return Err("Upstream database namespaces must be between 1 and 16 characters".into());
+        }
+        if namespace
+            .chars()
+            .any(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit()))
+        {
+            return Err(
+                "Upstream database namespaces may only contain lower case letters and numbers"
+                    .into(),
+            );
+        }
+
+        Ok(Self {
+            namespace: namespace.to_owned(),
+            url: url.trim().to_owned().into(),
+        })
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 pub struct ReplicationServerId(pub String);
 
@@ -230,6 +570,18 @@ fn default_max_parallel_snapshot_tables() -> Option<usize> {
     Some(max(1, num_cpus::get() - 1))
 }
 
+fn default_snapshot_pool_min_size() -> usize {
+    UpstreamConfig::default().snapshot_pool_min_size
+}
+
+fn default_replication_heartbeat_interval_secs() -> u16 {
+    UpstreamConfig::default().replication_heartbeat_interval_secs
+}
+
+fn default_replication_slot_lag_warning_bytes() -> u64 {
+    UpstreamConfig::default().replication_slot_lag_warning_bytes
+}
+
 fn default_status_update_interval_secs() -> u16 {
     UpstreamConfig::default().status_update_interval_secs
 }
@@ -242,15 +594,28 @@ impl Default for UpstreamConfig {
     fn default() -> Self {
         Self {
             upstream_db_url: Default::default(),
+            additional_upstreams: Default::default(),
+            replication_namespace: Default::default(),
             disable_upstream_ssl_verification: false,
             disable_setup_ddl_replication: false,
             replication_server_id: Default::default(),
             replicator_restart_timeout: Duration::from_secs(1),
             replication_tables: Default::default(),
             replication_tables_ignore: Default::default(),
+            replication_row_filters: Default::default(),
+            replication_columns_ignore: Default::default(),
+            replication_value_size_caps: Default::default(),
             snapshot_report_interval_secs: 30,
             ssl_root_cert: None,
+            ssl_client_identity: None,
+            ssl_client_identity_password: None,
+            ssl_server_name: None,
             replication_pool_size: 50,
+            snapshot_pool_min_size: 10,
+            streaming_tcp_keepalive_secs: None,
+            replication_heartbeat_interval_secs: 30,
+            replication_slot_lag_warning_bytes: 1024 * 1024 * 1024,
+            drop_replication_slot_on_shutdown: false,
             ignore_ulimit_check: false,
             status_update_interval_secs: 10,
             max_parallel_snapshot_tables: default_max_parallel_snapshot_tables(),
@@ -0,0 +1,43 @@
+use postgres_native_tls::{TlsConnector, TlsStream};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_postgres::tls::MakeTlsConnect;
+
+/// A [`MakeTlsConnect`] implementation for connections to an upstream postgres database, which
+/// behaves like [`postgres_native_tls::MakeTlsConnector`] except that it can be configured to use a
+/// fixed server name for SNI and certificate verification, rather than the host name being
+/// connected to.
+///
+/// This allows connecting to an upstream database via an address (such as an IP address or a
+/// proxy) which doesn't match the name on the database's certificate.
+#[derive(Clone)]
+pub struct UpstreamTlsConnector {
+    connector: native_tls::TlsConnector,
+    server_name: Option<String>,
+}
+
+impl UpstreamTlsConnector {
+    /// Construct a new [`UpstreamTlsConnector`] from the given native-tls connector, optionally
+    /// overriding the server name used for all connections
+    pub fn new(connector: native_tls::TlsConnector, server_name: Option<String>) -> Self {
+        Self {
+            connector,
+            server_name,
+        }
+    }
+}
+
+impl<S> MakeTlsConnect<S> for UpstreamTlsConnector
+where
+    S: AsyncRead + AsyncWrite + Unpin + 'static + Send,
+{
+    type Stream = TlsStream<S>;
+    type TlsConnect = TlsConnector;
+    type Error = native_tls::Error;
+
+    fn make_tls_connect(&mut self, domain: &str) -> Result<TlsConnector, native_tls::Error> {
+        Ok(TlsConnector::new(
+            self.connector.clone(),
+            self.server_name.as_deref().unwrap_or(domain),
+        ))
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use nom_sql::{Relation, SqlIdentifier};
+use readyset_errors::{ReadySetError, ReadySetResult};
+use serde::{Deserialize, Serialize};
+
+/// The maximum size, in bytes, of the values of columns of upstream tables that ReadySet will
+/// snapshot and replicate, configured with --replication-value-size-caps.
+///
+/// Values larger than the cap of their column are replicated as `NULL`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValueSizeCaps {
+    caps: HashMap<Relation, HashMap<SqlIdentifier, usize>>,
+}
+
+impl ValueSizeCaps {
+    /// Parse a comma-separated list of fully qualified columns and their size caps, of the form
+    /// `<schema>.<table>.<column>=<bytes>`
+    pub fn try_new(caps: &str) -> ReadySetResult<ValueSizeCaps> {
+        let mut res = ValueSizeCaps::default();
+        for cap in caps.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+            let invalid = || {
+                ReadySetError::ReplicationFailed(format!(
+                    "Value size cap {cap} must be of the form <schema>.<table>.<column>=<bytes>"
+                ))
+            };
+            let (column, size) = cap.split_once('=').ok_or_else(invalid)?;
+            let size = size.trim().parse::<usize>().map_err(|_| invalid())?;
+            match column.split('.').map(str::trim).collect::<Vec<_>>()[..] {
+                [schema, table, name]
+                    if !schema.is_empty() && !table.is_empty() && !name.is_empty() =>
+                {
+                    res.caps
+                        .entry(Relation {
+                            schema: Some(schema.into()),
+                            name: table.into(),
+                        })
+                        .or_default()
+                        .insert(name.into(), size);
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Returns true if no columns have a size cap
+    pub fn is_empty(&self) -> bool {
+        self.caps.is_empty()
+    }
+
+    /// Returns the size caps of the columns of the given table, if any
+    pub fn for_table(&self, table: &Relation) -> Option<&HashMap<SqlIdentifier, usize>> {
+        self.caps.get(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let caps =
+            ValueSizeCaps::try_new("public.t1.data = 1024, public.t1.thumb=16,s.t2.c=0").unwrap();
+        let t1 = caps
+            .for_table(&Relation {
+                schema: Some("public".into()),
+                name: "t1".into(),
+            })
+            .unwrap();
+        assert_eq!(t1.get("data"), Some(&1024));
+        assert_eq!(t1.get("thumb"), Some(&16));
+        assert_eq!(
+            caps.for_table(&Relation {
+                schema: Some("s".into()),
+                name: "t2".into(),
+            })
+            .unwrap()
+            .get("c"),
+            Some(&0)
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        ValueSizeCaps::try_new("public.t1.data").unwrap_err();
+        ValueSizeCaps::try_new("t1.data=10").unwrap_err();
+        ValueSizeCaps::try_new("public.t1.data=-1").unwrap_err();
+        ValueSizeCaps::try_new("public.t1.data=big").unwrap_err();
+    }
+}
@@ -18,6 +18,7 @@ macro_rules! non_null {
 
 pub(crate) mod builtins;
 mod json;
+mod spatial;
 
 fn eval_binary_op(op: BinaryOperator, left: &DfValue, right: &DfValue) -> ReadySetResult<DfValue> {
     use BinaryOperator::*;
@@ -41,8 +42,20 @@ fn eval_binary_op(op: BinaryOperator, left: &DfValue, right: &DfValue) -> ReadyS
         Subtract => Ok((non_null!(left) - non_null!(right))?),
         Multiply => Ok((non_null!(left) * non_null!(right))?),
         Divide => Ok((non_null!(left) / non_null!(right))?),
-        And => Ok((non_null!(left).is_truthy() && non_null!(right).is_truthy()).into()),
-        Or => Ok((non_null!(left).is_truthy() || non_null!(right).is_truthy()).into()),
+        // AND and OR follow SQL's three-valued logic: a NULL operand only makes the result NULL if
+        // the other operand doesn't already determine it
+        And => match (left, right) {
+            (DfValue::None, v) | (v, DfValue::None) if !v.is_none() && !v.is_truthy() => {
+                Ok(false.into())
+            }
+            (DfValue::None, _) | (_, DfValue::None) => Ok(DfValue::None),
+            (l, r) => Ok((l.is_truthy() && r.is_truthy()).into()),
+        },
+        Or => match (left, right) {
+            (DfValue::None, v) | (v, DfValue::None) if v.is_truthy() => Ok(true.into()),
+            (DfValue::None, _) | (_, DfValue::None) => Ok(DfValue::None),
+            (l, r) => Ok((l.is_truthy() || r.is_truthy()).into()),
+        },
         Equal => Ok((non_null!(left) == non_null!(right)).into()),
         Greater => Ok((non_null!(left) > non_null!(right)).into()),
         GreaterOrEqual => Ok((non_null!(left) >= non_null!(right)).into()),
@@ -315,6 +328,97 @@ impl Expr {
             }
         }
     }
+
+    /// Evaluate this expression against each of the given records, returning the results in the
+    /// same order.
+    ///
+    /// This is equivalent to calling [`Expr::eval`] on each record, except that an error
+    /// evaluating the expression against any one record is returned for the whole batch. Column
+    /// references, literals, and the operators applied to them are evaluated a column at a time,
+    /// so the dispatch on the structure of the expression is paid once per batch rather than once
+    /// per record, and subexpressions which don't reference any columns are only evaluated once.
+    pub fn eval_batch<D>(&self, records: &[&[D]]) -> ReadySetResult<Vec<DfValue>>
+    where
+        D: Borrow<DfValue>,
+    {
+        if records.is_empty() {
+            return Ok(vec![]);
+        }
+        Ok(match self.eval_batch_column(records)? {
+            BatchColumn::Constant(val) => vec![val; records.len()],
+            BatchColumn::Values(vals) => vals,
+        })
+    }
+
+    fn eval_batch_column<D>(&self, records: &[&[D]]) -> ReadySetResult<BatchColumn>
+    where
+        D: Borrow<DfValue>,
+    {
+        match self {
+            Expr::Literal { val, .. } => Ok(BatchColumn::Constant(val.clone())),
+            Expr::Column { index, .. } => records
+                .iter()
+                .map(|record| {
+                    record
+                        .get(*index)
+                        .map(|dt| dt.borrow().clone())
+                        .ok_or(ReadySetError::ProjectExprInvalidColumnIndex(*index))
+                })
+                .collect::<Result<_, _>>()
+                .map(BatchColumn::Values),
+            Expr::Op {
+                op, left, right, ..
+            } => {
+                let left = left.eval_batch_column(records)?;
+                let right = right.eval_batch_column(records)?;
+                if let (BatchColumn::Constant(left), BatchColumn::Constant(right)) = (&left, &right)
+                {
+                    return Ok(BatchColumn::Constant(eval_binary_op(*op, left, right)?));
+                }
+                (0..records.len())
+                    .map(|i| eval_binary_op(*op, left.get(i), right.get(i)))
+                    .collect::<Result<_, _>>()
+                    .map(BatchColumn::Values)
+            }
+            Expr::Not { expr, .. } => {
+                let not = |val: &DfValue| -> DfValue {
+                    match val {
+                        DfValue::None => DfValue::None,
+                        val => (!val.is_truthy()).into(),
+                    }
+                };
+                Ok(match expr.eval_batch_column(records)? {
+                    BatchColumn::Constant(val) => BatchColumn::Constant(not(&val)),
+                    BatchColumn::Values(vals) => {
+                        BatchColumn::Values(vals.iter().map(not).collect())
+                    }
+                })
+            }
+            _ => records
+                .iter()
+                .map(|record| self.eval(record))
+                .collect::<Result<_, _>>()
+                .map(BatchColumn::Values),
+        }
+    }
+}
+
+/// The result of evaluating an expression against a batch of records, as part of
+/// [`Expr::eval_batch`]
+enum BatchColumn {
+    /// The expression doesn't reference any columns, so it has the same value for every record
+    Constant(DfValue),
+    /// The value of the expression for each record in the batch
+    Values(Vec<DfValue>),
+}
+
+impl BatchColumn {
+    fn get(&self, i: usize) -> &DfValue {
+        match self {
+            BatchColumn::Constant(val) => val,
+            BatchColumn::Values(vals) => &vals[i],
+        }
+    }
 }
 
 #[cfg(test)]
@@ -379,6 +483,59 @@ mod tests {
         )
     }
 
+    #[test]
+    fn eval_batch_matches_eval() {
+        let greater = Op {
+            left: Box::new(column_with_type(0, DfType::Int)),
+            op: BinaryOperator::Greater,
+            right: Box::new(make_literal(1.into())),
+            ty: DfType::Bool,
+        };
+        let exprs = [
+            make_column(1),
+            make_literal(1.into()),
+            greater.clone(),
+            Not {
+                expr: Box::new(greater.clone()),
+                ty: DfType::Bool,
+            },
+            CaseWhen {
+                branches: vec![CaseWhenBranch {
+                    condition: greater,
+                    body: make_literal("big".into()),
+                }],
+                else_expr: Box::new(make_column(1)),
+                ty: DfType::Unknown,
+            },
+        ];
+        let rows: Vec<Vec<DfValue>> = vec![
+            vec![1.into(), "one".into()],
+            vec![2.into(), "two".into()],
+            vec![DfValue::None, "null".into()],
+        ];
+        let records = rows.iter().map(|row| row.as_slice()).collect::<Vec<_>>();
+
+        for expr in &exprs {
+            assert_eq!(
+                expr.eval_batch(&records).unwrap(),
+                rows.iter()
+                    .map(|row| expr.eval(row).unwrap())
+                    .collect::<Vec<_>>(),
+                "{expr}"
+            );
+        }
+    }
+
+    #[test]
+    fn eval_batch_invalid_column() {
+        let records: Vec<&[DfValue]> = vec![&[], &[]];
+        make_column(1).eval_batch(&records).unwrap_err();
+        assert!(make_column(1)
+            .eval_batch::<DfValue>(&[])
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn eval_cast_with_null_on_failure() {
         let expr = Expr::Cast {
@@ -1025,6 +1182,50 @@ mod tests {
         assert!(res.is_truthy());
     }
 
+    #[test]
+    fn eval_logical_ops_with_nulls() {
+        let cases = [
+            ("true AND null", DfValue::None),
+            ("false AND null", false.into()),
+            ("null AND false", false.into()),
+            ("null AND null", DfValue::None),
+            ("true OR null", true.into()),
+            ("null OR true", true.into()),
+            ("false OR null", DfValue::None),
+            ("null OR null", DfValue::None),
+            // `x NOT BETWEEN 1 AND NULL`, as rewritten by `RewriteBetween`
+            ("NOT (0 >= 1 AND 0 <= null)", true.into()),
+            ("NOT (2 >= 1 AND 2 <= null)", DfValue::None),
+        ];
+        for (expr, expected) in cases {
+            assert_eq!(
+                eval_expr(expr, nom_sql::Dialect::PostgreSQL),
+                expected,
+                "{expr}"
+            );
+        }
+    }
+
+    #[test]
+    fn eval_is_null() {
+        assert_eq!(
+            eval_expr("null IS NULL", nom_sql::Dialect::PostgreSQL),
+            true.into()
+        );
+        assert_eq!(
+            eval_expr("1 IS NULL", nom_sql::Dialect::PostgreSQL),
+            false.into()
+        );
+        assert_eq!(
+            eval_expr("null IS NOT NULL", nom_sql::Dialect::PostgreSQL),
+            false.into()
+        );
+        assert_eq!(
+            eval_expr("1 IS NOT NULL", nom_sql::Dialect::PostgreSQL),
+            true.into()
+        );
+    }
+
     #[test]
     fn like_null() {
         let expr = Expr::lower(
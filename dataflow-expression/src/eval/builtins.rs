@@ -4,13 +4,10 @@ use std::fmt::{self, Write};
 use std::ops::{Add, Div, Mul, Sub};
 use std::str::FromStr;
 
-use chrono::{
-    Datelike, LocalResult, Month, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Weekday,
-};
-use chrono_tz::Tz;
+use chrono::{Datelike, Month, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
 use itertools::Either;
 use mysql_time::MySqlTime;
-use readyset_data::{DfType, DfValue};
+use readyset_data::{DfType, DfValue, Interval, Timezone};
 use readyset_errors::{invalid_query_err, unsupported, ReadySetError, ReadySetResult};
 use readyset_util::math::integer_rnd;
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
@@ -19,6 +16,7 @@ use serde_json::Value as JsonValue;
 use test_strategy::Arbitrary;
 use vec1::Vec1;
 
+use crate::eval::spatial::Point;
 use crate::{BuiltinFunction, Expr};
 
 const NANOS_IN_MICRO: u32 = 1_000;
@@ -60,34 +58,29 @@ fn get_time_or_default(value: &DfValue, from_ty: &DfType) -> DfValue {
 /// Transforms a `[NaiveDateTime]` into a new one with a different timezone.
 /// The `[NaiveDateTime]` is interpreted as having the timezone specified by the
 /// `src` parameter, and then it's transformed to timezone specified by the `target` parameter.
+///
+/// Timezones can be given either as named timezones (eg `America/New_York`) or as offsets from UTC
+/// (eg `+10:00`).
 fn convert_tz(datetime: &NaiveDateTime, src: &str, target: &str) -> ReadySetResult<NaiveDateTime> {
     let mk_err = |message: &str| ReadySetError::ProjectExprBuiltInFunctionError {
         function: "convert_tz".to_owned(),
         message: message.to_owned(),
     };
 
-    let src_tz: Tz = src
+    let src_tz: Timezone = src
         .parse()
         .map_err(|_| mk_err("Failed to parse the source timezone"))?;
-    let target_tz: Tz = target
+    let target_tz: Timezone = target
         .parse()
         .map_err(|_| mk_err("Failed to parse the target timezone"))?;
 
-    let datetime_tz = match src_tz.from_local_datetime(datetime) {
-        LocalResult::Single(dt) => dt,
-        LocalResult::None => {
-            return Err(mk_err(
-                "Failed to transform the datetime to a different timezone",
-            ))
-        }
-        LocalResult::Ambiguous(_, _) => {
-            return Err(mk_err(
-                "Failed to transform the datetime to a different timezone",
-            ))
-        }
-    };
+    let datetime_tz = src_tz
+        .from_local_datetime(datetime)
+        .ok_or_else(|| mk_err("Failed to transform the datetime to a different timezone"))?;
 
-    Ok(datetime_tz.with_timezone(&target_tz).naive_local())
+    Ok(target_tz
+        .from_utc_datetime(&datetime_tz.naive_utc())
+        .naive_local())
 }
 
 fn day_of_week(date: &NaiveDate) -> u8 {
@@ -1033,13 +1026,44 @@ impl BuiltinFunction {
                     date_trunc(precision, datetime.naive_utc()).unwrap(),
                 ))
             }
+            BuiltinFunction::Point(x, y) => {
+                let x = f64::try_from(&non_null!(x.eval(record)?))?;
+                let y = f64::try_from(&non_null!(y.eval(record)?))?;
+                Ok(Point { srid: 0, x, y }.to_mysql_bytes().into())
+            }
+            BuiltinFunction::StGeomFromText(wkt, srid) => {
+                let wkt = non_null!(wkt.eval(record)?);
+                let srid = match srid {
+                    Some(srid) => u32::try_from(non_null!(srid.eval(record)?))?,
+                    None => 0,
+                };
+                Ok(Point::from_wkt(<&str>::try_from(&wkt)?, srid)?
+                    .to_mysql_bytes()
+                    .into())
+            }
+            BuiltinFunction::StDistance(g1, g2) => {
+                let g1 = Vec::<u8>::try_from(&non_null!(g1.eval(record)?))?;
+                let g2 = Vec::<u8>::try_from(&non_null!(g2.eval(record)?))?;
+                Ok(DfValue::Double(
+                    Point::from_mysql_bytes(&g1)?.distance(&Point::from_mysql_bytes(&g2)?)?,
+                ))
+            }
+            BuiltinFunction::Interval { quantity, unit } => {
+                let quantity = i64::try_from(&non_null!(quantity.eval(record)?))?;
+                Ok(Interval::from_quantity(quantity, *unit)?.into())
+            }
+            BuiltinFunction::Age(lhs, rhs) => {
+                let lhs = NaiveDateTime::try_from(&non_null!(lhs.eval(record)?))?;
+                let rhs = NaiveDateTime::try_from(&non_null!(rhs.eval(record)?))?;
+                Ok(Interval::age(lhs, rhs).into())
+            }
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use chrono::NaiveTime;
+    use chrono::{NaiveTime, TimeZone};
     use chrono_tz::{Asia, Atlantic};
     use lazy_static::lazy_static;
     use nom_sql::parse_expr;
@@ -1629,6 +1653,37 @@ mod tests {
         .unwrap_err();
     }
 
+    #[test]
+    fn convert_tz_offsets() {
+        let datetime = NaiveDate::from_ymd_opt(2004, 1, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+
+        assert_eq!(
+            super::convert_tz(&datetime, "+00:00", "+10:00").unwrap(),
+            NaiveDate::from_ymd_opt(2004, 1, 1)
+                .unwrap()
+                .and_hms_opt(22, 0, 0)
+                .unwrap()
+        );
+        assert_eq!(
+            super::convert_tz(&datetime, "-05:30", "UTC").unwrap(),
+            NaiveDate::from_ymd_opt(2004, 1, 1)
+                .unwrap()
+                .and_hms_opt(17, 30, 0)
+                .unwrap()
+        );
+        assert_eq!(
+            super::convert_tz(&datetime, "America/New_York", "+01:00").unwrap(),
+            NaiveDate::from_ymd_opt(2004, 1, 1)
+                .unwrap()
+                .and_hms_opt(18, 0, 0)
+                .unwrap()
+        );
+        super::convert_tz(&datetime, "+ab:cd", "UTC").unwrap_err();
+    }
+
     // NOTE(Fran): We have to be careful when testing timezones, as the time difference
     //   between two timezones might differ depending on the date (due to daylight savings
     //   or by historical changes).
@@ -1809,6 +1864,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn st_distance() {
+        assert_eq!(
+            eval_expr("st_distance(point(0, 0), point(3, 4))", MySQL),
+            DfValue::Double(5.0)
+        );
+        assert_eq!(
+            eval_expr(
+                "st_distance(st_geomfromtext('POINT(1 1)'), point(1, 3.5))",
+                MySQL
+            ),
+            DfValue::Double(2.5)
+        );
+        assert_eq!(
+            eval_expr("st_distance(point(0, 0), null)", MySQL),
+            DfValue::None
+        );
+        try_eval_expr(
+            "st_distance(st_geomfromtext('POINT(1 1)', 4326), point(1, 3))",
+            MySQL,
+        )
+        .unwrap_err();
+    }
+
     #[track_caller]
     fn date_format(time: &str, fmt: &str) -> DfValue {
         lazy_static! {
@@ -3101,4 +3180,92 @@ mod tests {
         test_with_null_string("{null,1,2,3,null,5}", "*,1,2,3,*,5");
         test_with_null_string("{{1,2},{3,4},{null,5}}", "1,2,3,4,*,5");
     }
+
+    fn datetime(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> DfValue {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, s)
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn date_add_and_sub() {
+        assert_eq!(
+            eval_expr("date_add('2024-01-31', INTERVAL 1 MONTH)", MySQL),
+            datetime(2024, 2, 29, 0, 0, 0)
+        );
+        assert_eq!(
+            eval_expr("date_sub('2024-03-01 10:00:00', INTERVAL 90 MINUTE)", MySQL),
+            datetime(2024, 3, 1, 8, 30, 0)
+        );
+        assert_eq!(
+            eval_expr("adddate('2024-12-31', 1)", MySQL),
+            datetime(2025, 1, 1, 0, 0, 0)
+        );
+        assert_eq!(
+            eval_expr("'2024-02-29 12:00:00' - INTERVAL 1 YEAR", MySQL),
+            datetime(2023, 2, 28, 12, 0, 0)
+        );
+        assert_eq!(
+            eval_expr("INTERVAL 2 DAY + '2024-02-28 12:00:00'", MySQL),
+            datetime(2024, 3, 1, 12, 0, 0)
+        );
+        assert_eq!(
+            eval_expr("date_add(null, INTERVAL 1 DAY)", MySQL),
+            DfValue::None
+        );
+    }
+
+    #[test]
+    fn interval_arithmetic() {
+        assert_eq!(
+            eval_expr(
+                "'2024-01-01 00:00:00'::timestamp + INTERVAL '1 day 2 hours'",
+                PostgreSQL
+            ),
+            datetime(2024, 1, 2, 2, 0, 0)
+        );
+        assert_eq!(
+            eval_expr("INTERVAL '1 day' + INTERVAL '2 hours'", PostgreSQL),
+            Interval::new(0, 1, 7_200_000_000).into()
+        );
+        assert_eq!(
+            eval_expr(
+                "'2024-03-01 00:00:00'::timestamp - '2024-02-28 12:00:00'::timestamp",
+                PostgreSQL
+            ),
+            Interval::new(0, 1, 43_200_000_000).into()
+        );
+
+        let ast = parse_expr(PostgreSQL, "INTERVAL '1 day' - '2024-01-01'::timestamp").unwrap();
+        Expr::lower(
+            ast,
+            Dialect::DEFAULT_POSTGRESQL,
+            crate::lower::tests::no_op_lower_context(),
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn interval_with_non_literal_quantity() {
+        let expr = parse_and_lower("date_add(c0, INTERVAL c1 HOUR)", MySQL);
+        assert_eq!(
+            expr.eval::<DfValue>(&["2024-01-01 00:00:00".into(), 36.into()])
+                .unwrap(),
+            datetime(2024, 1, 2, 12, 0, 0)
+        );
+    }
+
+    #[test]
+    fn age() {
+        assert_eq!(
+            eval_expr("age('2024-03-01', '2024-01-15')", PostgreSQL),
+            Interval::new(1, 15, 0).into()
+        );
+        assert_eq!(
+            eval_expr("age('2024-03-01', null)", PostgreSQL),
+            DfValue::None
+        );
+    }
 }
@@ -0,0 +1,201 @@
+//! Support for MySQL's spatial types.
+//!
+//! Spatial values are stored as opaque blobs in MySQL's [internal geometry format][format]: a
+//! 4-byte little-endian SRID, followed by the [WKB][] representation of the geometry. Currently
+//! only points are understood by the functions in this module.
+//!
+//! [format]: https://dev.mysql.com/doc/refman/8.0/en/gis-data-formats.html#gis-internal-format
+//! [WKB]: https://dev.mysql.com/doc/refman/8.0/en/gis-data-formats.html#gis-wkb-format
+
+use readyset_errors::{invalid_query_err, unsupported, ReadySetResult};
+
+/// The WKB type code for a point
+const WKB_POINT: u32 = 1;
+
+/// The length of a point in MySQL's internal format: a 4-byte SRID, a byte order byte, a 4-byte
+/// type code, and two 8-byte coordinates
+const POINT_LEN: usize = 4 + 1 + 4 + 8 + 8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Point {
+    pub(crate) srid: u32,
+    pub(crate) x: f64,
+    pub(crate) y: f64,
+}
+
+impl Point {
+    /// Decode a point from MySQL's internal geometry format
+    pub(crate) fn from_mysql_bytes(bytes: &[u8]) -> ReadySetResult<Self> {
+        if bytes.len() < 9 {
+            return Err(invalid_query_err!("Invalid GIS data"));
+        }
+        let srid = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+        let little_endian = match bytes[4] {
+            0 => false,
+            1 => true,
+            _ => return Err(invalid_query_err!("Invalid GIS data")),
+        };
+        let u32_at = |i: usize| {
+            let b: [u8; 4] = bytes[i..i + 4].try_into().unwrap();
+            if little_endian {
+                u32::from_le_bytes(b)
+            } else {
+                u32::from_be_bytes(b)
+            }
+        };
+        if u32_at(5) != WKB_POINT {
+            unsupported!("Only POINT geometries are supported");
+        }
+        if bytes.len() != POINT_LEN {
+            return Err(invalid_query_err!("Invalid GIS data"));
+        }
+        let f64_at = |i: usize| {
+            let b: [u8; 8] = bytes[i..i + 8].try_into().unwrap();
+            if little_endian {
+                f64::from_le_bytes(b)
+            } else {
+                f64::from_be_bytes(b)
+            }
+        };
+
+        Ok(Self {
+            srid,
+            x: f64_at(9),
+            y: f64_at(17),
+        })
+    }
+
+    /// Encode this point in MySQL's internal geometry format
+    pub(crate) fn to_mysql_bytes(self) -> Vec<u8> {
+        let mut res = Vec::with_capacity(POINT_LEN);
+        res.extend(self.srid.to_le_bytes());
+        res.push(1); // little-endian
+        res.extend(WKB_POINT.to_le_bytes());
+        res.extend(self.x.to_le_bytes());
+        res.extend(self.y.to_le_bytes());
+        res
+    }
+
+    /// Parse a point from its [WKT][] representation, eg `POINT(1 2)`
+    ///
+    /// [WKT]: https://dev.mysql.com/doc/refman/8.0/en/gis-data-formats.html#gis-wkt-format
+    pub(crate) fn from_wkt(wkt: &str, srid: u32) -> ReadySetResult<Self> {
+        let wkt = wkt.trim();
+        let Some(coords) = wkt
+            .get(..5)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("point"))
+            .map(|_| wkt[5..].trim())
+        else {
+            unsupported!("Only POINT geometries are supported");
+        };
+        let invalid = || invalid_query_err!("Invalid GIS data: {wkt}");
+        let coords = coords
+            .strip_prefix('(')
+            .and_then(|c| c.strip_suffix(')'))
+            .ok_or_else(invalid)?;
+        let mut coords = coords.split_whitespace().map(|c| c.parse::<f64>());
+        match (coords.next(), coords.next(), coords.next()) {
+            (Some(Ok(x)), Some(Ok(y)), None) => Ok(Self { srid, x, y }),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Returns the distance between this point and another point.
+    ///
+    /// Only points in a Cartesian spatial reference system (SRID 0) are supported; distances in
+    /// geographic spatial reference systems are computed on an ellipsoid by MySQL, which we don't
+    /// yet implement.
+    pub(crate) fn distance(&self, other: &Point) -> ReadySetResult<f64> {
+        if self.srid != other.srid {
+            return Err(invalid_query_err!(
+                "Binary geometry function st_distance given two geometries of different srids: \
+                 {} and {}",
+                self.srid,
+                other.srid
+            ));
+        }
+        if self.srid != 0 {
+            unsupported!("st_distance is only supported for geometries with SRID 0");
+        }
+        Ok((self.x - other.x).hypot(self.y - other.y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mysql_bytes_round_trip() {
+        let point = Point {
+            srid: 4326,
+            x: 1.5,
+            y: -2.0,
+        };
+        assert_eq!(
+            Point::from_mysql_bytes(&point.to_mysql_bytes()).unwrap(),
+            point
+        );
+    }
+
+    #[test]
+    fn from_mysql_bytes_big_endian() {
+        let mut bytes = vec![0, 0, 0, 0, 0];
+        bytes.extend(WKB_POINT.to_be_bytes());
+        bytes.extend(3.0f64.to_be_bytes());
+        bytes.extend(4.0f64.to_be_bytes());
+        assert_eq!(
+            Point::from_mysql_bytes(&bytes).unwrap(),
+            Point {
+                srid: 0,
+                x: 3.0,
+                y: 4.0
+            }
+        );
+    }
+
+    #[test]
+    fn from_mysql_bytes_invalid() {
+        Point::from_mysql_bytes(b"abc").unwrap_err();
+        Point::from_mysql_bytes(&[0; POINT_LEN - 1]).unwrap_err();
+    }
+
+    #[test]
+    fn from_wkt() {
+        assert_eq!(
+            Point::from_wkt("POINT(1 2)", 0).unwrap(),
+            Point {
+                srid: 0,
+                x: 1.0,
+                y: 2.0
+            }
+        );
+        assert_eq!(
+            Point::from_wkt(" point ( -1.5  2e3 ) ", 4326).unwrap(),
+            Point {
+                srid: 4326,
+                x: -1.5,
+                y: 2000.0
+            }
+        );
+        Point::from_wkt("POINT(1)", 0).unwrap_err();
+        Point::from_wkt("POINT(1 2 3)", 0).unwrap_err();
+        Point::from_wkt("LINESTRING(0 0, 1 1)", 0).unwrap_err();
+    }
+
+    #[test]
+    fn distance() {
+        let p1 = Point {
+            srid: 0,
+            x: 0.0,
+            y: 0.0,
+        };
+        let p2 = Point {
+            srid: 0,
+            x: 3.0,
+            y: 4.0,
+        };
+        assert_eq!(p1.distance(&p2).unwrap(), 5.0);
+        p1.distance(&Point { srid: 4326, ..p2 }).unwrap_err();
+    }
+}
@@ -11,6 +11,7 @@ use std::fmt::{self, Display, Formatter};
 
 pub use eval::builtins::DateTruncPrecision;
 use itertools::Itertools;
+use nom_sql::IntervalUnit;
 pub use readyset_data::Dialect;
 use readyset_data::{DfType, DfValue};
 use serde::{Deserialize, Serialize};
@@ -112,6 +113,29 @@ pub enum BuiltinFunction {
 
     /// [`date_trunc`](https://www.postgresql.org/docs/current/functions-datetime.html#FUNCTIONS-DATETIME-TRUNC)
     DateTrunc(Expr, Expr),
+
+    /// [`point`](https://dev.mysql.com/doc/refman/8.0/en/gis-mysql-specific-functions.html#function_point)
+    Point(Expr, Expr),
+
+    /// [`st_geomfromtext`](https://dev.mysql.com/doc/refman/8.0/en/gis-wkt-functions.html#function_st-geomfromtext)
+    ///
+    /// Only `POINT` geometries are currently supported.
+    StGeomFromText(Expr, Option<Expr>),
+
+    /// [`st_distance`](https://dev.mysql.com/doc/refman/8.0/en/spatial-relation-functions-object-shapes.html#function_st-distance)
+    ///
+    /// Only `POINT` geometries with SRID 0 are currently supported.
+    StDistance(Expr, Expr),
+
+    /// An `INTERVAL <quantity> <unit>` expression whose quantity is not a literal, and so can't be
+    /// folded into an interval value when the expression is lowered
+    Interval { quantity: Expr, unit: IntervalUnit },
+
+    /// [`age`](https://www.postgresql.org/docs/current/functions-datetime.html)
+    ///
+    /// Only the two-argument form is supported, since the single-argument form depends on the
+    /// current date.
+    Age(Expr, Expr),
 }
 
 impl BuiltinFunction {
@@ -146,6 +170,11 @@ impl BuiltinFunction {
             Least { .. } => "least",
             ArrayToString { .. } => "array_to_string",
             DateTrunc { .. } => "date_trunc",
+            Point { .. } => "point",
+            StGeomFromText { .. } => "st_geomfromtext",
+            StDistance { .. } => "st_distance",
+            Interval { .. } => "interval",
+            Age { .. } => "age",
         }
     }
 }
@@ -244,6 +273,17 @@ impl Display for BuiltinFunction {
             DateTrunc(field, source) => {
                 write!(f, "({}, {})", field, source)
             }
+            Point(x, y) => write!(f, "({x}, {y})"),
+            StGeomFromText(wkt, srid) => {
+                write!(f, "({wkt}")?;
+                if let Some(srid) = srid {
+                    write!(f, ", {srid}")?;
+                }
+                write!(f, ")")
+            }
+            StDistance(g1, g2) => write!(f, "({g1}, {g2})"),
+            Interval { quantity, unit } => write!(f, "({quantity} {unit})"),
+            Age(a, b) => write!(f, "({a}, {b})"),
         }
     }
 }
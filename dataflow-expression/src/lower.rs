@@ -2,13 +2,13 @@ use std::iter;
 
 use nom_sql::{
     BinaryOperator as SqlBinaryOperator, Column, DialectDisplay, Expr as AstExpr, FunctionExpr,
-    InValue, Relation, UnaryOperator,
+    InValue, IntervalUnit, Literal, Relation, UnaryOperator,
 };
 use readyset_data::dialect::SqlEngine;
-use readyset_data::{DfType, DfValue};
+use readyset_data::{DfType, DfValue, Interval, PgTypeCategory};
 use readyset_errors::{
-    internal, internal_err, invalid_query, invalid_query_err, unsupported, ReadySetError,
-    ReadySetResult,
+    internal, internal_err, invalid_query, invalid_query_err, unsupported, unsupported_err,
+    ReadySetError, ReadySetResult,
 };
 use readyset_util::redacted::Sensitive;
 use vec1::Vec1;
@@ -448,6 +448,43 @@ impl BuiltinFunction {
 
                 (Self::DateTrunc(precision, source), ret_type)
             }
+            "point" => (
+                Self::Point(
+                    cast(next_arg()?, DfType::Double),
+                    cast(next_arg()?, DfType::Double),
+                ),
+                DfType::Blob,
+            ),
+            "st_geomfromtext" | "st_pointfromtext" => (
+                Self::StGeomFromText(
+                    cast(next_arg()?, DfType::DEFAULT_TEXT),
+                    args.next().map(|srid| cast(srid, DfType::UnsignedInt)),
+                ),
+                DfType::Blob,
+            ),
+            "st_distance" => (Self::StDistance(next_arg()?, next_arg()?), DfType::Double),
+            "age" => {
+                let mut timestamp_arg = || -> ReadySetResult<Expr> {
+                    let arg = next_arg()?;
+                    Ok(match arg.ty() {
+                        DfType::Timestamp { .. } | DfType::TimestampTz { .. } => arg,
+                        _ => cast(
+                            arg,
+                            DfType::Timestamp {
+                                subsecond_digits: dialect.default_subsecond_digits(),
+                            },
+                        ),
+                    })
+                };
+                let lhs = timestamp_arg()?;
+                let rhs = timestamp_arg()
+                    .map_err(|_| unsupported_err!("AGE with a single argument is not supported"))?;
+                (Self::Age(lhs, rhs), DfType::Interval)
+            }
+            "now" | "current_timestamp" | "localtimestamp" | "localtime" | "sysdate"
+            | "utc_timestamp" | "current_date" | "curdate" | "utc_date" => unsupported!(
+                "{name}() is only supported in comparisons against a column in the WHERE clause"
+            ),
             _ => unsupported!("Function {name} does not exist"),
         };
 
@@ -583,6 +620,7 @@ impl BinaryOperator {
             HashArrow2 => Ok((Self::JsonKeyPathExtractText, false)),
             AtArrowRight => Ok((Self::JsonContains, false)),
             AtArrowLeft => Ok((Self::JsonContainedIn, false)),
+            AtAt => unsupported!("'@@' is only supported as a full-text search filter"),
         }
     }
 
@@ -620,8 +658,40 @@ impl BinaryOperator {
             }
         };
 
+        // PostgreSQL doesn't implicitly convert strings, booleans, or other non-numeric types to
+        // numbers for arithmetic, or anything but booleans to booleans for logical operators
+        let is_arithmetic_operand = |ty: &DfType| {
+            !matches!(
+                ty.pg_category(),
+                PgTypeCategory::Boolean
+                    | PgTypeCategory::String
+                    | PgTypeCategory::Array
+                    | PgTypeCategory::BitString
+                    | PgTypeCategory::UserDefined
+            )
+        };
+        let is_logical_operand = |ty: &DfType| ty.is_unknown() || ty.is_bool();
+
         use BinaryOperator::*;
         match self {
+            Add | Subtract | Multiply | Divide if dialect.engine() == SqlEngine::PostgreSQL => {
+                if !is_arithmetic_operand(left_type) {
+                    return error(Left, "a numeric type");
+                }
+                if !is_arithmetic_operand(right_type) {
+                    return error(Right, "a numeric type");
+                }
+                Ok((None, None))
+            }
+            And | Or if dialect.engine() == SqlEngine::PostgreSQL => {
+                if !is_logical_operand(left_type) {
+                    return error(Left, "BOOLEAN");
+                }
+                if !is_logical_operand(right_type) {
+                    return error(Right, "BOOLEAN");
+                }
+                Ok((None, None))
+            }
             Add | Subtract | Multiply | Divide | And | Or | Greater | GreaterOrEqual | Less
             | LessOrEqual | Is => match dialect.engine() {
                 SqlEngine::PostgreSQL => Ok((None, None)),
@@ -702,11 +772,23 @@ impl BinaryOperator {
     pub(crate) fn output_type(
         &self,
         left_type: &DfType,
-        _right_type: &DfType,
+        right_type: &DfType,
+        dialect: Dialect,
     ) -> ReadySetResult<DfType> {
-        // TODO: Maybe consider `right_type` in some cases too.
         // TODO: What is the correct return type for `And` and `Or`?
         match self {
+            // Arithmetic on two numbers in PostgreSQL yields the type that can represent both
+            // operands, eg `int + bigint` is a `bigint`
+            Self::Add | Self::Subtract | Self::Multiply | Self::Divide
+                if dialect.engine() == SqlEngine::PostgreSQL
+                    && left_type.pg_category() == PgTypeCategory::Numeric
+                    && right_type.pg_category() == PgTypeCategory::Numeric =>
+            {
+                Ok(left_type
+                    .unify(right_type)
+                    .unwrap_or_else(|| left_type.clone()))
+            }
+
             Self::Like
             | Self::ILike
             | Self::Equal
@@ -757,6 +839,25 @@ impl Expr {
                     .into_iter()
                     .map(|arg| Self::lower(arg, dialect, context.clone()))
                     .collect::<Result<Vec<_>, _>>()?;
+
+                let date_arithmetic_op = match fname.to_lowercase().as_str() {
+                    "date_add" | "adddate" => Some(BinaryOperator::Add),
+                    "date_sub" | "subdate" => Some(BinaryOperator::Subtract),
+                    _ => None,
+                };
+                if let Some(op) = date_arithmetic_op {
+                    let Ok([date, interval]) = <[Expr; 2]>::try_from(args) else {
+                        return Err(ReadySetError::ArityError(fname.to_string()));
+                    };
+                    // `DATE_ADD(date, n)` is shorthand for `DATE_ADD(date, INTERVAL n DAY)`
+                    let interval = if interval.ty().is_interval() {
+                        interval
+                    } else {
+                        Self::lower_interval(interval, IntervalUnit::Day)?
+                    };
+                    return Self::lower_date_arithmetic(op, date, interval, dialect);
+                }
+
                 let (func, ty) = BuiltinFunction::from_name_and_args(&fname, args, dialect)?;
                 Ok(Self::Call {
                     func: Box::new(func),
@@ -780,16 +881,28 @@ impl Expr {
 
                 Ok(Self::Call { func, ty })
             }
+            AstExpr::Call(FunctionExpr::Match { .. }) => {
+                unsupported!("MATCH ... AGAINST is only supported as a full-text search filter")
+            }
+            AstExpr::Call(FunctionExpr::Rank { .. }) => {
+                unsupported!(
+                    "Window functions are only supported in a subquery whose rank is compared \
+                     against a constant in the outer query"
+                )
+            }
             AstExpr::Call(call) => internal!(
                 "Unexpected (aggregate?) call node in project expression: {:?}",
                 Sensitive(&call)
             ),
             AstExpr::Literal(lit) => {
                 let is_string_literal = lit.is_string();
+                let is_bool_literal = matches!(lit, Literal::Boolean(_));
                 let val: DfValue = lit.try_into()?;
                 // TODO: Infer type from SQL
                 let ty = if is_string_literal && dialect.engine() == SqlEngine::PostgreSQL {
                     DfType::Unknown
+                } else if is_bool_literal && dialect.engine() == SqlEngine::PostgreSQL {
+                    DfType::Bool
                 } else {
                     val.infer_dataflow_type()
                 };
@@ -813,7 +926,19 @@ impl Expr {
                     unsupported!("'{op}' operator not implemented yet for MySQL");
                 }
 
-                let ty = op.output_type(left.ty(), right.ty())?;
+                if matches!(op, BinaryOperator::Add | BinaryOperator::Subtract)
+                    && (left.ty().is_interval()
+                        || right.ty().is_interval()
+                        || (op == BinaryOperator::Subtract
+                            && dialect.engine() == SqlEngine::PostgreSQL
+                            && [left.ty(), right.ty()].into_iter().all(|ty| {
+                                matches!(ty, DfType::Timestamp { .. } | DfType::TimestampTz { .. })
+                            })))
+                {
+                    return Self::lower_date_arithmetic(op, *left, *right, dialect);
+                }
+
+                let ty = op.output_type(left.ty(), right.ty(), dialect)?;
                 let (left_coerce_target, right_coerce_target) =
                     op.argument_type_coercions(left.ty(), right.ty(), dialect)?;
 
@@ -1021,6 +1146,25 @@ impl Expr {
                     ty,
                 })
             }
+            AstExpr::Interval {
+                value,
+                unit: Some(unit),
+            } => Self::lower_interval(Self::lower(*value, dialect, context)?, unit),
+            AstExpr::Interval { value, unit: None } => {
+                match Self::lower(*value, dialect, context)? {
+                    // Parse interval literals eagerly, so that malformed intervals are reported
+                    // when the query is planned rather than when it's executed
+                    Self::Literal { val, ty } => Ok(Self::Literal {
+                        val: val.coerce_to(&DfType::Interval, &ty)?,
+                        ty: DfType::Interval,
+                    }),
+                    expr => Ok(Self::Cast {
+                        expr: Box::new(expr),
+                        ty: DfType::Interval,
+                        null_on_failure: false,
+                    }),
+                }
+            }
             AstExpr::Row { .. } => unsupported!("Row expressions not currently supported"),
             AstExpr::Exists(_) => unsupported!("EXISTS not currently supported"),
             AstExpr::Variable(_) => unsupported!("Variables not currently supported"),
@@ -1033,6 +1177,116 @@ impl Expr {
         }
     }
 
+    /// Lower an `INTERVAL <quantity> <unit>` expression, folding it into an interval literal if
+    /// the quantity is itself a literal
+    fn lower_interval(quantity: Expr, unit: IntervalUnit) -> ReadySetResult<Self> {
+        if let Self::Literal { val, ty } = &quantity {
+            let val = if val.is_none() {
+                DfValue::None
+            } else {
+                let quantity = i64::try_from(&val.coerce_to(&DfType::BigInt, ty)?)?;
+                Interval::from_quantity(quantity, unit)?.into()
+            };
+            return Ok(Self::Literal {
+                val,
+                ty: DfType::Interval,
+            });
+        }
+
+        Ok(Self::Call {
+            func: Box::new(BuiltinFunction::Interval {
+                quantity: Self::Cast {
+                    expr: Box::new(quantity),
+                    ty: DfType::BigInt,
+                    null_on_failure: false,
+                },
+                unit,
+            }),
+            ty: DfType::Interval,
+        })
+    }
+
+    /// Lower the addition or subtraction of an interval to or from a date or time value (or to or
+    /// from another interval), or the subtraction of two timestamps, inferring the type of the
+    /// result from the type of the date or time operand.
+    fn lower_date_arithmetic(
+        op: BinaryOperator,
+        left: Expr,
+        right: Expr,
+        dialect: Dialect,
+    ) -> ReadySetResult<Self> {
+        let op_node = |left, right, ty| Self::Op {
+            op,
+            left: Box::new(left),
+            right: Box::new(right),
+            ty,
+        };
+
+        match (left.ty().is_interval(), right.ty().is_interval()) {
+            // interval ± interval, and timestamp - timestamp
+            (true, true) | (false, false) => return Ok(op_node(left, right, DfType::Interval)),
+            (true, false) if op == BinaryOperator::Subtract => {
+                invalid_query!("Cannot subtract a date or time value from an interval")
+            }
+            _ => {}
+        }
+
+        let interval_on_left = left.ty().is_interval();
+        let (datetime, interval) = if interval_on_left {
+            (right, left)
+        } else {
+            (left, right)
+        };
+
+        // MySQL keeps the result of adding whole days, months, or years to a DATE as a DATE
+        let whole_days = match &interval {
+            Self::Literal {
+                val: DfValue::Interval(interval),
+                ..
+            } => interval.microseconds() == 0,
+            Self::Call { func, .. } => matches!(
+                **func,
+                BuiltinFunction::Interval { unit, .. } if unit >= IntervalUnit::Day
+            ),
+            _ => false,
+        };
+        let ty = match datetime.ty() {
+            DfType::Date if whole_days && dialect.engine() == SqlEngine::MySQL => DfType::Date,
+            ty @ (DfType::DateTime { .. }
+            | DfType::Timestamp { .. }
+            | DfType::TimestampTz { .. }) => ty.clone(),
+            DfType::Time { .. } => {
+                unsupported!("Interval arithmetic on TIME values is not yet supported")
+            }
+            _ => {
+                let subsecond_digits = datetime
+                    .ty()
+                    .subsecond_digits()
+                    .unwrap_or_else(|| dialect.default_subsecond_digits());
+                match dialect.engine() {
+                    SqlEngine::PostgreSQL => DfType::Timestamp { subsecond_digits },
+                    SqlEngine::MySQL => DfType::DateTime { subsecond_digits },
+                }
+            }
+        };
+
+        let datetime = if *datetime.ty() == ty {
+            datetime
+        } else {
+            Self::Cast {
+                expr: Box::new(datetime),
+                ty: ty.clone(),
+                null_on_failure: false,
+            }
+        };
+
+        Ok(if interval_on_left {
+            op_node(interval, datetime, ty)
+        } else {
+            op_node(datetime, interval, ty)
+        })
+    }
+
     fn lower_op_any_or_all<C>(
         lhs: AstExpr,
         op: SqlBinaryOperator,
@@ -1065,7 +1319,7 @@ impl Expr {
             invalid_query!("op ANY/ALL (array) requires an array on the right-hand side")
         };
 
-        let ty = op.output_type(left.ty(), right_member_ty)?;
+        let ty = op.output_type(left.ty(), right_member_ty, dialect)?;
         if !ty.is_bool() {
             // localhost/noria=# select 1 + any('{1,2}');
             // ERROR:  42809: op ANY/ALL (array) requires operator to yield boolean
@@ -1622,6 +1876,76 @@ pub(crate) mod tests {
         assert_eq!(*result.ty(), DfType::Bool);
     }
 
+    fn lower_postgres_with_columns(expr: &str) -> ReadySetResult<Expr> {
+        let input = parse_expr(ParserDialect::PostgreSQL, expr).unwrap();
+        Expr::lower(
+            input,
+            Dialect::DEFAULT_POSTGRESQL,
+            resolve_columns(|c| match c.name.as_str() {
+                "i" => Ok((0, DfType::Int)),
+                "b" => Ok((1, DfType::BigInt)),
+                "t" => Ok((2, DfType::DEFAULT_TEXT)),
+                "d" => Ok((3, DfType::Double)),
+                "flag" => Ok((4, DfType::Bool)),
+                _ => internal!("what's this column?"),
+            }),
+        )
+    }
+
+    #[test]
+    fn postgres_arithmetic_unifies_numeric_types() {
+        assert_eq!(
+            *lower_postgres_with_columns("i + i").unwrap().ty(),
+            DfType::Int
+        );
+        assert_eq!(
+            *lower_postgres_with_columns("i + b").unwrap().ty(),
+            DfType::BigInt
+        );
+        assert_eq!(
+            *lower_postgres_with_columns("i * d").unwrap().ty(),
+            DfType::Double
+        );
+    }
+
+    #[test]
+    fn postgres_arithmetic_rejects_non_numeric_operands() {
+        let err = lower_postgres_with_columns("i + t").unwrap_err();
+        assert!(err.is_invalid_query(), "{err}");
+        assert!(
+            err.to_string().contains("right-side operand type Text"),
+            "{err}"
+        );
+
+        let err = lower_postgres_with_columns("flag - i").unwrap_err();
+        assert!(
+            err.to_string().contains("left-side operand type Bool"),
+            "{err}"
+        );
+
+        // Untyped string literals are still allowed, as they are coerced by postgres
+        lower_postgres_with_columns("i + '1'").unwrap();
+    }
+
+    #[test]
+    fn postgres_logical_operators_require_booleans() {
+        lower_postgres_with_columns("flag AND i = 1").unwrap();
+        lower_postgres_with_columns("flag OR true").unwrap();
+
+        let err = lower_postgres_with_columns("flag AND i").unwrap_err();
+        assert!(err.is_invalid_query(), "{err}");
+        assert!(err.to_string().contains("expected BOOLEAN"), "{err}");
+
+        // MySQL treats any value as a boolean
+        let input = parse_expr(ParserDialect::MySQL, "x AND 1").unwrap();
+        Expr::lower(
+            input,
+            Dialect::DEFAULT_MYSQL,
+            resolve_columns(|_| Ok((0, DfType::Int))),
+        )
+        .unwrap();
+    }
+
     #[test]
     fn lowered_json_op_expr_types() {
         for op in [
@@ -1786,7 +2110,12 @@ pub(crate) mod tests {
             #[track_caller]
             fn test_json_extract(op: BinaryOperator, left_type: DfType, output_type: DfType) {
                 assert_eq!(
-                    op.output_type(&left_type, &DfType::DEFAULT_TEXT).unwrap(),
+                    op.output_type(
+                        &left_type,
+                        &DfType::DEFAULT_TEXT,
+                        Dialect::DEFAULT_POSTGRESQL
+                    )
+                    .unwrap(),
                     output_type
                 );
             }
@@ -1798,8 +2127,12 @@ pub(crate) mod tests {
                 output_type: DfType,
             ) {
                 assert_eq!(
-                    op.output_type(&left_type, &DfType::Array(Box::new(DfType::DEFAULT_TEXT)))
-                        .unwrap(),
+                    op.output_type(
+                        &left_type,
+                        &DfType::Array(Box::new(DfType::DEFAULT_TEXT)),
+                        Dialect::DEFAULT_POSTGRESQL,
+                    )
+                    .unwrap(),
                     output_type
                 );
             }
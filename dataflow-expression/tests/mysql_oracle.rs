@@ -119,7 +119,7 @@ async fn example_exprs_eval_same_as_mysql() {
         "convert_tz('2004-01-01 12:00:00','GMT','MET')",
         "convert_tz('asdfadsf','asdf','MET')",
         "convert_tz('asdfadsf','asdf',null)",
-        // "convert_tz('2004-01-01 12:00:00','+00:00','+10:00')", TODO(ENG-2761)
+        "convert_tz('2004-01-01 12:00:00','+00:00','+10:00')",
         "dayofweek('2022-03-24')",
         "dayofweek('2022-03-24 12:00:00')",
         "dayofweek(null)",
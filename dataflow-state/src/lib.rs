@@ -5,6 +5,7 @@ mod keyed_state;
 mod memory_state;
 mod mk_key;
 mod persistent_state;
+mod shared_memory_state;
 mod single_state;
 
 use std::borrow::Cow;
@@ -33,6 +34,7 @@ pub use crate::memory_state::MemoryState;
 pub use crate::persistent_state::{
     DurabilityMode, PersistenceParameters, PersistentState, PersistentStateHandle, SnapshotMode,
 };
+pub use crate::shared_memory_state::{SharedMemoryState, SharedMemoryStates};
 
 /// Information about state evicted via a call to [`State::evict_bytes`]
 pub struct EvictBytesResult<'a> {
@@ -71,6 +73,9 @@ pub enum MaterializedNodeState {
     Persistent(PersistentState),
     /// A read handle to a [`PersistentState`] owned by another node.
     PersistentReadHandle(PersistentStateHandle),
+    /// A handle to a fully materialized [`MemoryState`] shared with the other shards of the same
+    /// node on the same worker.
+    SharedMemory(SharedMemoryState),
 }
 
 /// Enum representing whether a base table node was already initialized (and has a replication
@@ -263,8 +268,11 @@ impl MaterializedNodeState {
     /// replay
     pub fn set_replay_done(&mut self, replay_done: bool) {
         debug_assert!(!self.is_partial());
-        if let MaterializedNodeState::Memory(ms) = self {
-            ms.replay_done = replay_done;
+        match self {
+            MaterializedNodeState::Memory(ms) => ms.replay_done = replay_done,
+            MaterializedNodeState::SharedMemory(sms) => sms.set_replay_done(replay_done),
+            MaterializedNodeState::Persistent(_)
+            | MaterializedNodeState::PersistentReadHandle(_) => {}
         }
     }
 }
@@ -275,6 +283,7 @@ impl SizeOf for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.deep_size_of(),
             MaterializedNodeState::Persistent(ps) => ps.deep_size_of(),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.deep_size_of(),
+            MaterializedNodeState::SharedMemory(sms) => sms.deep_size_of(),
         }
     }
 
@@ -283,6 +292,7 @@ impl SizeOf for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.size_of(),
             MaterializedNodeState::Persistent(ps) => ps.size_of(),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.size_of(),
+            MaterializedNodeState::SharedMemory(sms) => sms.size_of(),
         }
     }
 
@@ -291,6 +301,7 @@ impl SizeOf for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.is_empty(),
             MaterializedNodeState::Persistent(ps) => ps.is_empty(),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.is_empty(),
+            MaterializedNodeState::SharedMemory(sms) => sms.is_empty(),
         }
     }
 }
@@ -301,6 +312,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.add_index(index, tags),
             MaterializedNodeState::Persistent(ps) => ps.add_index(index, tags),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.add_index(index, tags),
+            MaterializedNodeState::SharedMemory(sms) => sms.add_index(index, tags),
         }
     }
 
@@ -309,6 +321,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.add_weak_index(index),
             MaterializedNodeState::Persistent(ps) => ps.add_weak_index(index),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.add_weak_index(index),
+            MaterializedNodeState::SharedMemory(sms) => sms.add_weak_index(index),
         }
     }
 
@@ -317,6 +330,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.is_useful(),
             MaterializedNodeState::Persistent(ps) => ps.is_useful(),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.is_useful(),
+            MaterializedNodeState::SharedMemory(sms) => sms.is_useful(),
         }
     }
 
@@ -325,6 +339,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.is_partial(),
             MaterializedNodeState::Persistent(ps) => ps.is_partial(),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.is_partial(),
+            MaterializedNodeState::SharedMemory(sms) => sms.is_partial(),
         }
     }
 
@@ -333,6 +348,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.replay_done(),
             MaterializedNodeState::Persistent(ps) => ps.replay_done(),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.replay_done(),
+            MaterializedNodeState::SharedMemory(sms) => sms.replay_done(),
         }
     }
 
@@ -352,6 +368,9 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::PersistentReadHandle(rh) => {
                 rh.process_records(records, partial_tag, replication_offset)
             }
+            MaterializedNodeState::SharedMemory(sms) => {
+                sms.process_records(records, partial_tag, replication_offset)
+            }
         }
     }
 
@@ -360,6 +379,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.replication_offset(),
             MaterializedNodeState::Persistent(ps) => ps.replication_offset(),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.replication_offset(),
+            MaterializedNodeState::SharedMemory(sms) => sms.replication_offset(),
         }
     }
 
@@ -368,6 +388,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.persisted_up_to(),
             MaterializedNodeState::Persistent(ps) => ps.persisted_up_to(),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.persisted_up_to(),
+            MaterializedNodeState::SharedMemory(sms) => sms.persisted_up_to(),
         }
     }
 
@@ -376,6 +397,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.mark_filled(key, tag),
             MaterializedNodeState::Persistent(ps) => ps.mark_filled(key, tag),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.mark_filled(key, tag),
+            MaterializedNodeState::SharedMemory(sms) => sms.mark_filled(key, tag),
         }
     }
 
@@ -384,6 +406,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.mark_hole(key, tag),
             MaterializedNodeState::Persistent(ps) => ps.mark_hole(key, tag),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.mark_hole(key, tag),
+            MaterializedNodeState::SharedMemory(sms) => sms.mark_hole(key, tag),
         }
     }
 
@@ -392,6 +415,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.lookup(columns, key),
             MaterializedNodeState::Persistent(ps) => ps.lookup(columns, key),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.lookup(columns, key),
+            MaterializedNodeState::SharedMemory(sms) => sms.lookup(columns, key),
         }
     }
 
@@ -400,6 +424,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.lookup_range(columns, key),
             MaterializedNodeState::Persistent(ps) => ps.lookup_range(columns, key),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.lookup_range(columns, key),
+            MaterializedNodeState::SharedMemory(sms) => sms.lookup_range(columns, key),
         }
     }
 
@@ -408,6 +433,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.lookup_weak(columns, key),
             MaterializedNodeState::Persistent(ps) => ps.lookup_weak(columns, key),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.lookup_weak(columns, key),
+            MaterializedNodeState::SharedMemory(sms) => sms.lookup_weak(columns, key),
         }
     }
 
@@ -416,6 +442,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.as_persistent(),
             MaterializedNodeState::Persistent(ps) => ps.as_persistent(),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.as_persistent(),
+            MaterializedNodeState::SharedMemory(sms) => sms.as_persistent(),
         }
     }
 
@@ -424,6 +451,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.as_persistent_mut(),
             MaterializedNodeState::Persistent(ps) => ps.as_persistent_mut(),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.as_persistent_mut(),
+            MaterializedNodeState::SharedMemory(sms) => sms.as_persistent_mut(),
         }
     }
 
@@ -432,6 +460,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.key_count(),
             MaterializedNodeState::Persistent(ps) => ps.key_count(),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.key_count(),
+            MaterializedNodeState::SharedMemory(sms) => sms.key_count(),
         }
     }
 
@@ -440,6 +469,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.row_count(),
             MaterializedNodeState::Persistent(ps) => ps.row_count(),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.row_count(),
+            MaterializedNodeState::SharedMemory(sms) => sms.row_count(),
         }
     }
 
@@ -448,6 +478,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.all_records(),
             MaterializedNodeState::Persistent(ps) => ps.all_records(),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.all_records(),
+            MaterializedNodeState::SharedMemory(sms) => sms.all_records(),
         }
     }
 
@@ -456,6 +487,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.evict_bytes(bytes),
             MaterializedNodeState::Persistent(ps) => ps.evict_bytes(bytes),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.evict_bytes(bytes),
+            MaterializedNodeState::SharedMemory(sms) => sms.evict_bytes(bytes),
         }
     }
 
@@ -464,6 +496,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.evict_keys(tag, keys),
             MaterializedNodeState::Persistent(ps) => ps.evict_keys(tag, keys),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.evict_keys(tag, keys),
+            MaterializedNodeState::SharedMemory(sms) => sms.evict_keys(tag, keys),
         }
     }
 
@@ -472,6 +505,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.evict_random(tag, rng),
             MaterializedNodeState::Persistent(ps) => ps.evict_random(tag, rng),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.evict_random(tag, rng),
+            MaterializedNodeState::SharedMemory(sms) => sms.evict_random(tag, rng),
         }
     }
 
@@ -480,6 +514,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.clear(),
             MaterializedNodeState::Persistent(ps) => ps.clear(),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.clear(),
+            MaterializedNodeState::SharedMemory(sms) => sms.clear(),
         }
     }
 
@@ -488,6 +523,7 @@ impl State for MaterializedNodeState {
             MaterializedNodeState::Memory(ms) => ms.tear_down(),
             MaterializedNodeState::Persistent(ps) => ps.tear_down(),
             MaterializedNodeState::PersistentReadHandle(rh) => rh.tear_down(),
+            MaterializedNodeState::SharedMemory(sms) => sms.tear_down(),
         }
     }
 }
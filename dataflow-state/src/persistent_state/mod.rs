@@ -1609,6 +1609,33 @@ impl PersistentState {
         self.db.clone()
     }
 
+    /// Flush the RocksDB WAL and sync it to disk, so that all data written to this state so far
+    /// (along with its replication offset) is persisted, without waiting for the WAL flush thread.
+    ///
+    /// Used when shutting down gracefully, so that we don't have to re-replicate writes we've
+    /// already received once we restart.
+    pub fn flush_and_sync_wal(&self) -> ReadySetResult<()> {
+        // In snapshot mode writes bypass the WAL entirely, so there's nothing to flush
+        if self.snapshot_mode.is_enabled() {
+            return Ok(());
+        }
+
+        // Hold the write lock for the whole flush so that the WAL state can't change underneath us
+        let mut inner = self.db.inner_mut();
+        if matches!(inner.shared_state.wal_state, WalState::FlushedAndPersisted) {
+            return Ok(());
+        }
+
+        debug!(table = %self.name, "flushing and syncing WAL");
+        if let Err(error) = inner.db.flush_wal(true) {
+            error!(%error, table = %self.name, "failed to flush and sync WAL");
+            return Err(Error::from(error).into());
+        }
+        inner.shared_state.wal_state = WalState::FlushedAndPersisted;
+
+        Ok(())
+    }
+
     /// Adds a new primary index, assuming there are none present
     fn add_primary_index(&mut self, columns: &[usize], is_unique: bool) -> Result<()> {
         if self.db.inner().shared_state.indices.is_empty() {
@@ -3190,6 +3217,46 @@ mod tests {
         assert!(rh.do_lookup(&[0], &PointKey::Single(0.into())).is_some());
     }
 
+    #[test]
+    fn flush_and_sync_wal_persists_writes() {
+        let params = PersistenceParameters {
+            // Long enough that the WAL flush thread never runs during the test
+            wal_flush_interval_seconds: 3600,
+            ..Default::default()
+        };
+        let mut state = PersistentState::new(
+            String::from("flush_and_sync_wal_persists_writes"),
+            Vec::<Box<[usize]>>::new(),
+            &params,
+        )
+        .unwrap();
+        state.add_index(Index::hash_map(vec![0]), None);
+
+        let offset = ReplicationOffset::Postgres(PostgresPosition {
+            commit_lsn: 1.into(),
+            lsn: 0.into(),
+        });
+        state
+            .process_records(
+                &mut (0..10)
+                    .map(|n| Record::from(vec![n.into()]))
+                    .collect::<Records>(),
+                None,
+                Some(offset.clone()),
+            )
+            .unwrap();
+        assert!(matches!(
+            state.persisted_up_to().unwrap(),
+            PersistencePoint::UpTo(persisted) if persisted == offset
+        ));
+
+        state.flush_and_sync_wal().unwrap();
+        assert!(matches!(
+            state.persisted_up_to().unwrap(),
+            PersistencePoint::Persisted
+        ));
+    }
+
     mod lookup_range {
         use std::iter;
 
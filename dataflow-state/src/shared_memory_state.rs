@@ -0,0 +1,354 @@
+//! A fully materialized [`MemoryState`] shared between the shards of a node which run on the same
+//! worker.
+//!
+//! Each shard of a node sharded by a column only ever contains the rows whose values in that
+//! column are routed to it, so the shards of a node can store their rows in a single state rather
+//! than each paying for the overhead of its own. Every shard accesses the shared state through its
+//! own [`SharedMemoryState`] handle, which only ever returns (and clears) the rows in its shard's
+//! own partition, so the result of a lookup doesn't depend on which shards happen to share the
+//! state. Lookups from different shards only take a read lock on the state, so they can proceed
+//! concurrently.
+//!
+//! Only fully materialized state can be shared, since partial state tracks which keys are filled
+//! (and evicts them) per replay path, independently in each shard.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Weak};
+
+use common::{Record, Records, SizeOf, Tag};
+use parking_lot::{Mutex, RwLock};
+use readyset_client::debug::info::KeyCount;
+use readyset_client::internal::Index;
+use readyset_client::{shard_by, KeyComparison, PersistencePoint};
+use readyset_data::DfValue;
+use readyset_errors::ReadySetResult;
+use replication_offset::ReplicationOffset;
+
+use crate::{
+    AllRecords, EvictBytesResult, EvictKeysResult, EvictRandomResult, LookupResult, MemoryState,
+    PointKey, RangeKey, RangeLookupResult, RecordResult, State,
+};
+
+/// A single shard's handle to a [`MemoryState`] shared with the other shards of the same node on
+/// the same worker. See [the module documentation](self) for more information.
+///
+/// Dropping the handle removes the rows in its shard's partition from the shared state.
+pub struct SharedMemoryState {
+    state: Arc<RwLock<MemoryState>>,
+    /// The column the node is sharded by
+    shard_column: usize,
+    shard: usize,
+    num_shards: usize,
+    /// Whether *this shard* has received its full replay
+    replay_done: bool,
+    /// The number of rows in this shard's partition of the state, counted the same way as
+    /// [`MemoryState::row_count`]
+    rows: usize,
+}
+
+impl SharedMemoryState {
+    /// Returns whether the given row belongs to this handle's shard
+    fn in_shard(&self, row: &[DfValue]) -> bool {
+        row.get(self.shard_column)
+            .map_or(false, |v| shard_by(v, self.num_shards) == self.shard)
+    }
+
+    fn in_shard_result(&self, rs: RecordResult<'_>) -> RecordResult<'static> {
+        rs.into_iter()
+            .filter(|r| self.in_shard(r))
+            .map(|r| r.into_owned())
+            .collect()
+    }
+
+    /// Set whether this shard has received its full replay
+    pub fn set_replay_done(&mut self, replay_done: bool) {
+        self.replay_done = replay_done;
+    }
+}
+
+impl SizeOf for SharedMemoryState {
+    fn size_of(&self) -> u64 {
+        std::mem::size_of::<Self>() as u64
+    }
+
+    /// Each handle reports the share of the size of the shared state taken up by its own rows, so
+    /// that the state is only counted once in total
+    fn deep_size_of(&self) -> u64 {
+        let state = self.state.read();
+        match state.row_count() {
+            0 => 0,
+            total => state.deep_size_of() * self.rows as u64 / total as u64,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rows == 0
+    }
+}
+
+impl State for SharedMemoryState {
+    fn add_index(&mut self, index: Index, tags: Option<Vec<Tag>>) {
+        debug_assert!(tags.is_none(), "Partial state can't be shared");
+        self.state.write().add_index(index, tags)
+    }
+
+    fn add_weak_index(&mut self, index: Index) {
+        self.state.write().add_weak_index(index)
+    }
+
+    fn is_useful(&self) -> bool {
+        self.state.read().is_useful()
+    }
+
+    fn is_partial(&self) -> bool {
+        false
+    }
+
+    fn replay_done(&self) -> bool {
+        self.replay_done
+    }
+
+    fn process_records(
+        &mut self,
+        records: &mut Records,
+        partial_tag: Option<Tag>,
+        replication_offset: Option<ReplicationOffset>,
+    ) -> ReadySetResult<()> {
+        let mut state = self.state.write();
+        // The write lock is held throughout, so the change in the total row count is entirely down
+        // to this shard's records
+        let before = state.row_count();
+        state.process_records(records, partial_tag, replication_offset)?;
+        self.rows = (self.rows + state.row_count()).saturating_sub(before);
+        Ok(())
+    }
+
+    fn replication_offset(&self) -> Option<&ReplicationOffset> {
+        None
+    }
+
+    fn persisted_up_to(&self) -> ReadySetResult<PersistencePoint> {
+        Ok(PersistencePoint::Persisted)
+    }
+
+    fn mark_filled(&mut self, _: KeyComparison, _: Tag) {}
+
+    fn mark_hole(&mut self, _: &KeyComparison, _: Tag) {}
+
+    fn lookup<'a>(&'a self, columns: &[usize], key: &PointKey) -> LookupResult<'a> {
+        match self.state.read().lookup(columns, key) {
+            LookupResult::Some(rs) => LookupResult::Some(self.in_shard_result(rs)),
+            LookupResult::Missing => LookupResult::Missing,
+        }
+    }
+
+    fn lookup_range<'a>(&'a self, columns: &[usize], key: &RangeKey) -> RangeLookupResult<'a> {
+        match self.state.read().lookup_range(columns, key) {
+            RangeLookupResult::Some(rs) => RangeLookupResult::Some(self.in_shard_result(rs)),
+            RangeLookupResult::Missing(misses) => RangeLookupResult::Missing(misses),
+        }
+    }
+
+    fn lookup_weak<'a>(&'a self, columns: &[usize], key: &PointKey) -> Option<RecordResult<'a>> {
+        self.state
+            .read()
+            .lookup_weak(columns, key)
+            .map(|rs| self.in_shard_result(rs))
+    }
+
+    /// Counting the keys in only this shard's partition would mean scanning the whole shared
+    /// state, so this returns the number of rows in the partition instead
+    fn key_count(&self) -> KeyCount {
+        KeyCount::EstimatedRowCount(self.rows)
+    }
+
+    fn row_count(&self) -> usize {
+        self.rows
+    }
+
+    fn all_records(&self) -> AllRecords {
+        let mut records = self.state.read().all_records();
+        let rows = records.read().iter().filter(|r| self.in_shard(r)).collect();
+        AllRecords::Owned(rows)
+    }
+
+    fn evict_bytes(&mut self, _: usize) -> Option<EvictBytesResult> {
+        None
+    }
+
+    fn evict_keys(&mut self, _: Tag, _: &[KeyComparison]) -> Option<EvictKeysResult> {
+        None
+    }
+
+    fn evict_random<R: rand::Rng>(&mut self, _: Tag, _: &mut R) -> Option<EvictRandomResult> {
+        None
+    }
+
+    /// Remove all the rows in this shard's partition of the shared state, leaving the rows of all
+    /// the other shards
+    fn clear(&mut self) {
+        let mut state = self.state.write();
+        let mut records = state.all_records();
+        let mut removed = records
+            .read()
+            .iter()
+            .filter(|r| self.in_shard(r))
+            .map(Record::Negative)
+            .collect::<Records>();
+        // Removing rows from a fully materialized state can't fail
+        let _ = state.process_records(&mut removed, None, None);
+        self.rows = 0;
+    }
+
+    fn tear_down(self) -> ReadySetResult<()> {
+        Ok(())
+    }
+}
+
+impl Drop for SharedMemoryState {
+    fn drop(&mut self) {
+        // If this is the last handle the whole state is about to be dropped anyway
+        if Arc::strong_count(&self.state) > 1 && self.rows > 0 {
+            self.clear();
+        }
+    }
+}
+
+/// The states of nodes whose shards share a single materialization, owned by a worker and shared
+/// by all the domains it runs
+pub struct SharedMemoryStates<K>(Arc<Mutex<HashMap<K, Weak<RwLock<MemoryState>>>>>);
+
+impl<K> Default for SharedMemoryStates<K> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<K> Clone for SharedMemoryStates<K> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<K> SharedMemoryStates<K>
+where
+    K: Hash + Eq,
+{
+    /// Returns a handle for the given shard to the state identified by `key`, which is sharded by
+    /// `shard_column` into `num_shards` shards, creating the state if no other shard currently
+    /// holds a handle to it.
+    ///
+    /// Every shard sharing a state must use a different `shard`, so `key` must distinguish between
+    /// the replicas of a node.
+    pub fn join(
+        &self,
+        key: K,
+        shard_column: usize,
+        shard: usize,
+        num_shards: usize,
+    ) -> SharedMemoryState {
+        let mut states = self.0.lock();
+        states.retain(|_, state| state.strong_count() > 0);
+        let state = match states.get(&key).and_then(Weak::upgrade) {
+            Some(state) => state,
+            None => {
+                let state = Arc::new(RwLock::new(MemoryState::default()));
+                states.insert(key, Arc::downgrade(&state));
+                state
+            }
+        };
+
+        SharedMemoryState {
+            state,
+            shard_column,
+            shard,
+            num_shards,
+            replay_done: false,
+            rows: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::IndexType;
+
+    use super::*;
+
+    fn join(states: &SharedMemoryStates<usize>, key: usize, shard: usize) -> SharedMemoryState {
+        let mut state = states.join(key, 0, shard, 2);
+        state.add_index(Index::new(IndexType::HashMap, vec![1]), None);
+        state
+    }
+
+    fn insert(state: &mut SharedMemoryState, rows: &[(i32, i32)]) {
+        let mut records = rows
+            .iter()
+            .map(|(a, b)| vec![DfValue::from(*a), DfValue::from(*b)])
+            .collect::<Records>();
+        state.process_records(&mut records, None, None).unwrap();
+    }
+
+    fn lookup(state: &SharedMemoryState, key: i32) -> Vec<Vec<DfValue>> {
+        match state.lookup(&[1], &PointKey::Single(DfValue::from(key))) {
+            LookupResult::Some(rs) => rs.into_iter().map(|r| r.into_owned()).collect(),
+            LookupResult::Missing => panic!("Fully materialized state missed"),
+        }
+    }
+
+    #[test]
+    fn shards_only_see_own_rows() {
+        let states = SharedMemoryStates::default();
+        let mut shard0 = join(&states, 0, 0);
+        let mut shard1 = join(&states, 0, 1);
+        insert(&mut shard0, &[(2, 10), (4, 10)]);
+        insert(&mut shard1, &[(3, 10)]);
+
+        assert_eq!(shard0.row_count(), 2);
+        assert_eq!(shard1.row_count(), 1);
+        assert_eq!(lookup(&shard0, 10).len(), 2);
+        assert_eq!(
+            lookup(&shard1, 10),
+            vec![vec![DfValue::from(3), DfValue::from(10)]]
+        );
+    }
+
+    #[test]
+    fn dropping_clears_own_rows() {
+        let states = SharedMemoryStates::default();
+        let mut shard0 = join(&states, 0, 0);
+        let mut shard1 = join(&states, 0, 1);
+        insert(&mut shard0, &[(2, 10)]);
+        insert(&mut shard1, &[(3, 10)]);
+
+        drop(shard1);
+        let shard1 = join(&states, 0, 1);
+        assert!(lookup(&shard1, 10).is_empty());
+        assert_eq!(lookup(&shard0, 10).len(), 1);
+        assert_eq!(shard0.state.read().row_count(), 1);
+    }
+
+    #[test]
+    fn dropped_with_last_handle() {
+        let states = SharedMemoryStates::default();
+        let mut shard0 = join(&states, 0, 0);
+        insert(&mut shard0, &[(2, 10)]);
+        drop(shard0);
+
+        let shard0 = join(&states, 0, 0);
+        assert_eq!(shard0.state.read().row_count(), 0);
+    }
+
+    #[test]
+    fn separate_keys_separate_states() {
+        let states = SharedMemoryStates::default();
+        let mut replica0 = join(&states, 0, 0);
+        let mut replica1 = join(&states, 1, 0);
+        insert(&mut replica0, &[(2, 10)]);
+        insert(&mut replica1, &[(2, 10)]);
+
+        assert_eq!(lookup(&replica0, 10).len(), 1);
+        assert_eq!(lookup(&replica1, 10).len(), 1);
+    }
+}
@@ -11,12 +11,16 @@ use crate::myc::constants::{CapabilityFlags, Command as CommandByte, UTF8MB4_GEN
 #[derive(Debug)]
 pub struct ClientHandshake<'a> {
     pub capabilities: CapabilityFlags,
+    /// The MariaDB-specific capabilities requested by the client, which are always empty for
+    /// MySQL clients
+    pub mariadb_capabilities: u32,
     pub maxps: u32,
     pub charset: u16,
     pub username: &'a str,
     pub password: &'a [u8],
     pub database: Option<&'a str>,
     pub auth_plugin_name: Option<&'a str>,
+    pub connect_attributes: Vec<(&'a str, &'a str)>,
 }
 
 #[derive(Debug)]
@@ -26,6 +30,7 @@ pub struct ClientChangeUser<'a> {
     pub database: Option<&'a str>,
     pub charset: u16,
     pub auth_plugin_name: &'a str,
+    pub connect_attributes: Vec<(&'a str, &'a str)>,
 }
 
 /// Parse a "length-encoded integer" as specified by the [mysql binary protocol documentation][docs]
@@ -45,6 +50,15 @@ fn lenenc_int(i: &[u8]) -> IResult<&[u8], i64> {
     }
 }
 
+/// Parse a "length-encoded string" as specified by the [mysql binary protocol
+/// documentation][docs]
+///
+/// [docs]: https://dev.mysql.com/doc/internals/en/string.html#packet-Protocol::LengthEncodedString
+fn lenenc_str(i: &[u8]) -> IResult<&[u8], &str> {
+    let (i, len) = lenenc_int(i)?;
+    map_res(take(len as usize), parse_bytes_to_string)(i)
+}
+
 fn parse_bytes_to_string(i: &[u8]) -> Result<&str, nom::Err<nom::error::Error<&[u8]>>> {
     std::str::from_utf8(i).map_err(|e| {
         nom::Err::Error(nom::error::Error::from_external_error(
@@ -61,6 +75,21 @@ fn null_terminated_string(i: &[u8]) -> IResult<&[u8], &str> {
     Ok((i, res))
 }
 
+/// Parse the key-value connection attributes sent by clients with the `CLIENT_CONNECT_ATTRS`
+/// capability, which are prefixed with their total length in bytes
+fn connect_attributes(i: &[u8]) -> IResult<&[u8], Vec<(&str, &str)>> {
+    let (i, len) = lenenc_int(i)?;
+    let (i, mut attrs) = take(len as usize)(i)?;
+    let mut res = Vec::new();
+    while !attrs.is_empty() {
+        let (rest, key) = lenenc_str(attrs)?;
+        let (rest, value) = lenenc_str(rest)?;
+        res.push((key, value));
+        attrs = rest;
+    }
+    Ok((i, res))
+}
+
 /// Parse a COM_CHANGE_USER packet as specified by the [mysql binary protocol documentation][docs]
 /// [docs]: https://dev.mysql.com/doc/dev/mysql-server/latest/page_protocol_com_change_user.html
 pub fn change_user(
@@ -76,7 +105,7 @@ pub fn change_user(
             map(null_terminated_string, |s| s.as_bytes())(i)?
         };
     let (i, database) = map(null_terminated_string, Some)(i)?;
-    let (i, charset, auth_plugin_name) = if !i.is_empty() {
+    let (i, charset, auth_plugin_name, connect_attributes) = if !i.is_empty() {
         let (i, charset) = if client_capability_flags.contains(CapabilityFlags::CLIENT_PROTOCOL_41)
         {
             let (i, bytes) = take(2usize)(i)?;
@@ -91,9 +120,17 @@ pub fn change_user(
             } else {
                 (i, "")
             };
-        (i, charset, auth_plugin_name)
+        let (i, connect_attributes) = if client_capability_flags
+            .contains(CapabilityFlags::CLIENT_CONNECT_ATTRS)
+            && !i.is_empty()
+        {
+            connect_attributes(i)?
+        } else {
+            (i, Vec::new())
+        };
+        (i, charset, auth_plugin_name, connect_attributes)
     } else {
-        (i, UTF8MB4_GENERAL_CI, "")
+        (i, UTF8MB4_GENERAL_CI, "", Vec::new())
     };
 
     Ok((
@@ -104,6 +141,7 @@ pub fn change_user(
             database,
             charset,
             auth_plugin_name,
+            connect_attributes,
         },
     ))
 }
@@ -113,7 +151,10 @@ pub fn client_handshake(i: &[u8]) -> IResult<&[u8], ClientHandshake<'_>> {
     let (i, capabilities) = map(le_u32, CapabilityFlags::from_bits_truncate)(i)?;
     let (i, maxps) = le_u32(i)?;
     let (i, charset) = le_u8(i)?;
-    let (i, _) = take(23u8)(i)?;
+    let (i, _) = take(19u8)(i)?;
+    // MariaDB clients send their extended capabilities in the last 4 bytes of the filler, which
+    // MySQL clients leave zeroed
+    let (i, mariadb_capabilities) = le_u32(i)?;
     let (i, username) = null_terminated_string(i)?;
     let (i, password) =
         if capabilities.contains(CapabilityFlags::CLIENT_PLUGIN_AUTH_LENENC_CLIENT_DATA) {
@@ -138,20 +179,42 @@ pub fn client_handshake(i: &[u8]) -> IResult<&[u8], ClientHandshake<'_>> {
         (i, None)
     };
 
+    let (i, connect_attributes) =
+        if capabilities.contains(CapabilityFlags::CLIENT_CONNECT_ATTRS) && !i.is_empty() {
+            connect_attributes(i)?
+        } else {
+            (i, Vec::new())
+        };
+
     Ok((
         i,
         ClientHandshake {
             capabilities,
+            mariadb_capabilities,
             maxps,
             charset: charset.into(),
             username,
             password,
             database,
             auth_plugin_name,
+            connect_attributes,
         },
     ))
 }
 
+/// `COM_STMT_BULK_EXECUTE`, a MariaDB extension to the protocol which executes a prepared
+/// statement once for each of many sets of parameters sent in a single packet
+///
+/// <https://mariadb.com/kb/en/com_stmt_bulk_execute/>
+pub const COM_STMT_BULK_EXECUTE: u8 = 0xfa;
+
+/// Flag for `COM_STMT_BULK_EXECUTE` requesting a separate result for each set of parameters
+pub const STMT_BULK_FLAG_SEND_UNIT_RESULTS: u16 = 64;
+
+/// Flag for `COM_STMT_BULK_EXECUTE` indicating that the types of the parameters are sent before
+/// the first set of parameters
+pub const STMT_BULK_FLAG_SEND_TYPES_TO_SERVER: u16 = 128;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum Command<'a> {
     Query(&'a [u8]),
@@ -166,6 +229,11 @@ pub enum Command<'a> {
         stmt: u32,
         params: &'a [u8],
     },
+    BulkExecute {
+        stmt: u32,
+        flags: u16,
+        params: &'a [u8],
+    },
     SendLongData {
         stmt: u32,
         param: u16,
@@ -183,6 +251,19 @@ pub fn execute(i: &[u8]) -> IResult<&[u8], Command<'_>> {
     Ok((&[], Command::Execute { stmt, params: i }))
 }
 
+pub fn bulk_execute(i: &[u8]) -> IResult<&[u8], Command<'_>> {
+    let (i, stmt) = le_u32(i)?;
+    let (i, flags) = le_u16(i)?;
+    Ok((
+        &[],
+        Command::BulkExecute {
+            stmt,
+            flags,
+            params: i,
+        },
+    ))
+}
+
 pub fn send_long_data(i: &[u8]) -> IResult<&[u8], Command<'_>> {
     let (i, stmt) = le_u32(i)?;
     let (i, param) = le_u16(i)?;
@@ -223,6 +304,7 @@ pub fn parse(i: &[u8]) -> IResult<&[u8], Command<'_>> {
             Command::ResetStmtData,
         ),
         preceded(tag(&[CommandByte::COM_STMT_EXECUTE as u8]), execute),
+        preceded(tag(&[COM_STMT_BULK_EXECUTE]), bulk_execute),
         preceded(
             tag(&[CommandByte::COM_STMT_SEND_LONG_DATA as u8]),
             send_long_data,
@@ -275,11 +357,64 @@ mod tests {
         assert!(!handshake
             .capabilities
             .contains(CapabilityFlags::CLIENT_DEPRECATE_EOF));
+        assert_eq!(handshake.mariadb_capabilities, 0);
         assert_eq!(handshake.charset, UTF8_GENERAL_CI);
         assert_eq!(handshake.username, "jon");
         assert_eq!(handshake.maxps, 16777216);
     }
 
+    #[test]
+    fn it_parses_handshake_with_connect_attributes() {
+        let capabilities = CapabilityFlags::CLIENT_PROTOCOL_41
+            | CapabilityFlags::CLIENT_SECURE_CONNECTION
+            | CapabilityFlags::CLIENT_PLUGIN_AUTH
+            | CapabilityFlags::CLIENT_CONNECT_ATTRS;
+        let mut data = capabilities.bits().to_le_bytes().to_vec();
+        data.extend_from_slice(&16777216u32.to_le_bytes());
+        data.push(UTF8_GENERAL_CI as u8);
+        data.extend_from_slice(&[0; 23]);
+        data.extend_from_slice(b"jon\0");
+        data.push(0); // empty auth response
+        data.extend_from_slice(b"mysql_native_password\0");
+        let attrs = b"\x0c_client_name\x08libmysql\x0cprogram_name\x05mysql";
+        data.push(attrs.len() as u8);
+        data.extend_from_slice(attrs);
+
+        let (rest, handshake) = client_handshake(&data).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(handshake.username, "jon");
+        assert_eq!(handshake.auth_plugin_name, Some("mysql_native_password"));
+        assert_eq!(
+            handshake.connect_attributes,
+            vec![("_client_name", "libmysql"), ("program_name", "mysql")]
+        );
+    }
+
+    #[test]
+    fn it_parses_mariadb_handshake() {
+        let capabilities = CapabilityFlags::CLIENT_PROTOCOL_41
+            | CapabilityFlags::CLIENT_SECURE_CONNECTION
+            | CapabilityFlags::CLIENT_PLUGIN_AUTH;
+        let mut data = capabilities.bits().to_le_bytes().to_vec();
+        data.extend_from_slice(&16777216u32.to_le_bytes());
+        data.push(UTF8_GENERAL_CI as u8);
+        data.extend_from_slice(&[0; 19]);
+        data.extend_from_slice(
+            &crate::constants::MARIADB_CLIENT_STMT_BULK_OPERATIONS.to_le_bytes(),
+        );
+        data.extend_from_slice(b"jon\0");
+        data.push(0); // empty auth response
+        data.extend_from_slice(b"mysql_native_password\0");
+
+        let (rest, handshake) = client_handshake(&data).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            handshake.mariadb_capabilities,
+            crate::constants::MARIADB_CLIENT_STMT_BULK_OPERATIONS
+        );
+        assert_eq!(handshake.username, "jon");
+    }
+
     #[tokio::test]
     async fn it_parses_request() {
         let data = &[
@@ -317,6 +452,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_parses_bulk_execute() {
+        let data = &[
+            0xfa, 0x01, 0x00, 0x00, 0x00, 0x80, 0x00, 0x03, 0x00, 0x00, 0x01,
+        ];
+        let (_, cmd) = parse(data).unwrap();
+        assert_eq!(
+            cmd,
+            Command::BulkExecute {
+                stmt: 1,
+                flags: STMT_BULK_FLAG_SEND_TYPES_TO_SERVER,
+                params: &[0x03, 0x00, 0x00, 0x01],
+            }
+        );
+    }
+
     #[tokio::test]
     async fn it_parses_change_user() {
         let data = &[
@@ -58,3 +58,9 @@ pub const CLIENT_PLUGIN_AUTH: u32 = 0x00080000;
 
 pub const SSL_VERIFY_SERVER_CERT: u32 = 0x40000000;
 pub const REMEMBER_OPTIONS: u32 = 0x80000000;
+
+// MariaDB extended capabilities, sent in the last 4 bytes of the filler of the handshake packet to
+// clients which don't see `LONG_PASSWORD` (`CLIENT_MYSQL` in MariaDB) in the regular capabilities
+
+/// Supports `COM_STMT_BULK_EXECUTE`
+pub const MARIADB_CLIENT_STMT_BULK_OPERATIONS: u32 = 0x00000004;
@@ -172,7 +172,10 @@ use std::io;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use constants::{CLIENT_PLUGIN_AUTH, CONNECT_WITH_DB, PROTOCOL_41, RESERVED, SECURE_CONNECTION};
+use constants::{
+    CLIENT_PLUGIN_AUTH, CONNECT_ATTRS, CONNECT_WITH_DB, MARIADB_CLIENT_STMT_BULK_OPERATIONS,
+    PROTOCOL_41, RESERVED, SECURE_CONNECTION,
+};
 use error::{other_error, OtherErrorKind};
 use mysql_common::constants::CapabilityFlags;
 use readyset_adapter_types::{DeallocateId, ParsedCommand};
@@ -237,7 +240,7 @@ impl From<&mysql_async::Column> for Column {
 
 pub use crate::error::MsqlSrvError;
 pub use crate::errorcodes::ErrorKind;
-pub use crate::params::{ParamParser, ParamValue, Params};
+pub use crate::params::{BulkParamParser, ParamParser, ParamValue, Params};
 pub use crate::resultset::{InitWriter, QueryResultWriter, RowWriter, StatementMetaWriter};
 pub use crate::value::{ToMySqlValue, Value, ValueInner};
 
@@ -280,6 +283,28 @@ pub trait MySqlShim<W: AsyncWrite + Unpin + Send> {
         schema_cache: &mut HashMap<u32, CachedSchema>,
     ) -> io::Result<()>;
 
+    /// Called when the client executes a previously prepared statement once for each of many sets
+    /// of parameters at once, via MariaDB's `COM_STMT_BULK_EXECUTE`.
+    ///
+    /// The sets of parameters are given in `params`. A single response for all of the executions
+    /// should be given using the provided [`QueryResultWriter`](struct.QueryResultWriter.html).
+    ///
+    /// Defaults to responding with an error.
+    async fn on_bulk_execute(
+        &mut self,
+        id: u32,
+        params: BulkParamParser<'_>,
+        results: QueryResultWriter<'_, W>,
+    ) -> io::Result<()> {
+        let _ = (id, params);
+        results
+            .error(
+                ErrorKind::ER_UNKNOWN_COM_ERROR,
+                "COM_STMT_BULK_EXECUTE is unsupported".as_bytes(),
+            )
+            .await
+    }
+
     /// Called when the client wishes to deallocate resources associated with a previously prepared
     /// statement.
     async fn on_close(&mut self, stmt: DeallocateId);
@@ -297,12 +322,24 @@ pub trait MySqlShim<W: AsyncWrite + Unpin + Send> {
     /// Called when the client issues a reset command
     async fn on_reset(&mut self) -> io::Result<()>;
 
-    /// Called when client switches database.
+    /// Called when client switches database, either via `COM_INIT_DB` or by specifying a database
+    /// when connecting.
+    ///
+    /// The provided [`InitWriter`] should be used to tell the client whether the database was
+    /// switched successfully.
     async fn on_init(&mut self, _: &str, _: Option<InitWriter<'_, W>>) -> io::Result<()>;
 
     /// Called when client switches user.
     async fn on_change_user(&mut self, _: &str, _: &str, _: &str) -> io::Result<()>;
 
+    /// Called with the key-value connection attributes (such as `_client_name` or `program_name`)
+    /// sent by the client once it has authenticated or changed user.
+    ///
+    /// Defaults to doing nothing.
+    fn on_connect_attributes(&mut self, attributes: Vec<(String, String)>) {
+        let _ = attributes;
+    }
+
     /// Retrieve the password for the user with the given username, if any.
     ///
     /// If the user doesn't exist, return [`None`].
@@ -336,6 +373,9 @@ pub struct MySqlIntermediary<B, R: AsyncRead + Unpin, W: AsyncWrite + Unpin> {
     enable_statement_logging: bool,
     /// The capabilities of the client
     client_capabilities: CapabilityFlags,
+    /// The MariaDB-specific capabilities that both we and the client support, which are always
+    /// empty for MySQL clients
+    mariadb_capabilities: u32,
     /// Auth data sent to client
     auth_data: [u8; 20],
 }
@@ -381,6 +421,13 @@ where
     write_err(error_kind, msg, &mut w).await
 }
 
+fn owned_attributes(attributes: &[(&str, &str)]) -> Vec<(String, String)> {
+    attributes
+        .iter()
+        .map(|(key, value)| ((*key).to_owned(), (*value).to_owned()))
+        .collect()
+}
+
 #[derive(Default)]
 struct StatementData {
     long_data: HashMap<u16, Vec<u8>>,
@@ -388,8 +435,18 @@ struct StatementData {
     params: u16,
 }
 
-const CAPABILITIES: u32 =
-    PROTOCOL_41 | SECURE_CONNECTION | RESERVED | CLIENT_PLUGIN_AUTH | CONNECT_WITH_DB;
+const CAPABILITIES: u32 = PROTOCOL_41
+    | SECURE_CONNECTION
+    | RESERVED
+    | CLIENT_PLUGIN_AUTH
+    | CONNECT_WITH_DB
+    | CONNECT_ATTRS;
+
+/// MariaDB-specific capabilities, which MariaDB clients read from the handshake since we don't
+/// advertise `CLIENT_LONG_PASSWORD` in [`CAPABILITIES`], and which MySQL clients ignore. These are
+/// only enabled for clients which request them back in their handshake response, which MySQL
+/// clients never do.
+const MARIADB_CAPABILITIES: u32 = MARIADB_CLIENT_STMT_BULK_OPERATIONS;
 
 impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
     MySqlIntermediary<B, R, W>
@@ -411,12 +468,10 @@ impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
             schema_cache: HashMap::new(),
             enable_statement_logging,
             client_capabilities: CapabilityFlags::empty(),
+            mariadb_capabilities: 0,
             auth_data: [0; 20],
         };
-        if let (true, database) = mi.init().await? {
-            if let Some(database) = database {
-                mi.shim.on_init(&database, None).await?;
-            }
+        if mi.init().await? {
             mi.run().await?;
         }
         Ok(())
@@ -429,10 +484,12 @@ impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
     /// the HandshakeResponse packet that the client should send back to us. More packets may be
     /// sent and received as needed to complete authentication.
     ///
-    /// If no errors are encountered, the return value contains a tuple of a boolean to indicate
-    /// whether authentication was successful, and a database name if one was specified by the
-    /// client in the handshake response.
-    async fn init(&mut self) -> Result<(bool, Option<String>), io::Error> {
+    /// If the client specified a database in the handshake response, switching to that database
+    /// via [`MySqlShim::on_init`] takes the place of the final OK packet.
+    ///
+    /// If no errors are encountered, the return value indicates whether authentication was
+    /// successful.
+    async fn init(&mut self) -> Result<bool, io::Error> {
         let auth_data =
             generate_auth_data().map_err(|_| other_error(OtherErrorKind::AuthDataErr))?;
         self.auth_data = auth_data;
@@ -451,7 +508,8 @@ impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
         // We will add a \0 byte below so we need to account for that when sending the length, since
         // rust strings don't add the null terminator
         init_packet.extend_from_slice(&[(auth_data.len() + 1) as u8]);
-        init_packet.extend_from_slice(&[0x00; 10][..]); // filler
+        init_packet.extend_from_slice(&[0x00; 6][..]); // filler
+        init_packet.extend_from_slice(&MARIADB_CAPABILITIES.to_le_bytes());
         init_packet.extend_from_slice(&auth_data[8..]);
         init_packet.push(0);
         init_packet.extend_from_slice(AUTH_PLUGIN_NAME.as_bytes());
@@ -492,10 +550,12 @@ impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
         self.writer.set_seq(seq + 1);
 
         self.client_capabilities = handshake.capabilities;
+        self.mariadb_capabilities = handshake.mariadb_capabilities & MARIADB_CAPABILITIES;
         let username = handshake.username.to_owned();
         let password = handshake.password.to_vec();
         let database = handshake.database.map(String::from);
         let client_auth_plugin = handshake.auth_plugin_name.map(|s| s.to_owned());
+        let connect_attributes = owned_attributes(&handshake.connect_attributes);
 
         let handshake_password = if client_auth_plugin.iter().all(|apn| apn != AUTH_PLUGIN_NAME)
             // Some clients (at the very least certain versions of PHP's MySQL PDO library) send an
@@ -517,7 +577,7 @@ impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
                     &mut self.writer,
                 )
                 .await?;
-                return Ok((false, database));
+                return Ok(false);
             }
 
             debug!(
@@ -563,7 +623,18 @@ impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
 
         if auth_success {
             debug!(%username, "Successfully authenticated client");
-            writers::write_ok_packet(&mut self.writer, 0, 0, StatusFlags::empty()).await?;
+            self.shim.on_connect_attributes(connect_attributes);
+            match database {
+                Some(database) => {
+                    let w = InitWriter {
+                        writer: &mut self.writer,
+                    };
+                    self.shim.on_init(&database, Some(w)).await?;
+                }
+                None => {
+                    writers::write_ok_packet(&mut self.writer, 0, 0, StatusFlags::empty()).await?
+                }
+            }
         } else {
             debug!(%username, ?client_auth_plugin, "Received incorrect password");
             writers::write_err(
@@ -575,7 +646,7 @@ impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
         }
         self.writer.flush().await?;
 
-        Ok((auth_success, database))
+        Ok(auth_success)
     }
 
     async fn run(mut self) -> Result<(), io::Error> {
@@ -598,6 +669,7 @@ impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
                     Command::Query(_)
                         | Command::Prepare(_)
                         | Command::Execute { .. }
+                        | Command::BulkExecute { .. }
                         | Command::Init(_)
                 )
             {
@@ -642,6 +714,9 @@ impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
 
                     if auth_success {
                         debug!("Successfully authenticated client");
+                        self.shim.on_connect_attributes(owned_attributes(
+                            &change_user.connect_attributes,
+                        ));
                         match self
                             .shim
                             .on_change_user(
@@ -769,6 +844,39 @@ impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
                     }
                     state.long_data.clear();
                 }
+                Command::BulkExecute {
+                    stmt,
+                    flags,
+                    params,
+                } => {
+                    let state = stmts.get_mut(&stmt).ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("asked to bulk execute unknown statement {}", stmt),
+                        )
+                    })?;
+                    let w = QueryResultWriter::new(&mut self.writer, true);
+                    if self.mariadb_capabilities & MARIADB_CLIENT_STMT_BULK_OPERATIONS == 0 {
+                        w.error(
+                            ErrorKind::ER_UNKNOWN_COM_ERROR,
+                            "COM_STMT_BULK_EXECUTE requires a MariaDB client".as_bytes(),
+                        )
+                        .await?;
+                    } else if flags & commands::STMT_BULK_FLAG_SEND_UNIT_RESULTS != 0 {
+                        w.error(
+                            ErrorKind::ER_NOT_SUPPORTED_YET,
+                            "COM_STMT_BULK_EXECUTE with unit results is unsupported".as_bytes(),
+                        )
+                        .await?;
+                    } else {
+                        let params = params::BulkParamParser::new(
+                            params,
+                            flags & commands::STMT_BULK_FLAG_SEND_TYPES_TO_SERVER != 0,
+                            state,
+                        );
+                        self.shim.on_bulk_execute(stmt, params, w).await?;
+                    }
+                }
                 Command::SendLongData { stmt, param, data } => {
                     stmts
                         .get_mut(&stmt)
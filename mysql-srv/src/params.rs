@@ -144,3 +144,140 @@ impl<'a> Iterator for Params<'a> {
         }))
     }
 }
+
+/// Indicator preceding a parameter value in a `COM_STMT_BULK_EXECUTE` packet, signifying that the
+/// value follows
+const STMT_INDICATOR_NONE: u8 = 0;
+/// Indicator preceding a parameter value in a `COM_STMT_BULK_EXECUTE` packet, signifying that the
+/// value is NULL
+const STMT_INDICATOR_NULL: u8 = 1;
+
+/// A `BulkParamParser` decodes the sets of query parameters included in a client's
+/// `COM_STMT_BULK_EXECUTE` command given type information for the expected parameters.
+///
+/// Users should invoke [`rows`](struct.BulkParamParser.html#method.rows) to decode all of the
+/// provided sets of parameters.
+pub struct BulkParamParser<'a> {
+    pub(crate) params: u16,
+    pub(crate) send_types: bool,
+    pub(crate) bytes: &'a [u8],
+    pub(crate) bound_types: &'a mut Vec<(myc::constants::ColumnType, bool)>,
+}
+
+impl<'a> BulkParamParser<'a> {
+    pub(crate) fn new(input: &'a [u8], send_types: bool, stmt: &'a mut StatementData) -> Self {
+        BulkParamParser {
+            params: stmt.params,
+            send_types,
+            bytes: input,
+            bound_types: &mut stmt.bound_types,
+        }
+    }
+
+    /// Decode all of the sets of parameters provided by the client, in order
+    pub fn rows(mut self) -> Result<Vec<Vec<ParamValue<'a>>>, MsqlSrvError> {
+        let mut input = self.bytes;
+        if self.send_types {
+            self.bound_types.clear();
+            for _ in 0..self.params {
+                let (col, flag, rest) = match input {
+                    [col, flag, rest @ ..] => (*col, *flag, rest),
+                    _ => return Err(MsqlSrvError::IndexingError),
+                };
+                self.bound_types.push((
+                    myc::constants::ColumnType::try_from(col)?,
+                    (flag & 128) != 0,
+                ));
+                input = rest;
+            }
+        }
+
+        let mut rows = vec![];
+        // A statement without any parameters can't be bulk executed
+        while !input.is_empty() && self.params > 0 {
+            let mut row = Vec::with_capacity(self.params as usize);
+            for col in 0..self.params as usize {
+                let (coltype, unsigned) = *self
+                    .bound_types
+                    .get(col)
+                    .ok_or(MsqlSrvError::IndexingError)?;
+                let (indicator, rest) = input.split_first().ok_or(MsqlSrvError::IndexingError)?;
+                input = rest;
+                let value = match *indicator {
+                    STMT_INDICATOR_NONE => Value::parse_from(&mut input, coltype, unsigned)?,
+                    STMT_INDICATOR_NULL => Value::null(),
+                    _ => {
+                        return Err(MsqlSrvError::Unimplemented {
+                            operation: "DEFAULT and IGNORE parameters in COM_STMT_BULK_EXECUTE"
+                                .to_owned(),
+                        })
+                    }
+                };
+                row.push(ParamValue { value, coltype });
+            }
+            rows.push(row);
+        }
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ValueInner;
+
+    #[test]
+    fn bulk_params_with_types() {
+        let mut stmt = StatementData {
+            params: 2,
+            ..Default::default()
+        };
+        let input = [
+            // types: signed MYSQL_TYPE_LONG, MYSQL_TYPE_VAR_STRING
+            0x03, 0x00, 0xfd, 0x00, //
+            // row 1: 1, "a"
+            0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x01, b'a', //
+            // row 2: 2, NULL
+            0x00, 0x02, 0x00, 0x00, 0x00, 0x01,
+        ];
+        let rows = BulkParamParser::new(&input, true, &mut stmt)
+            .rows()
+            .unwrap();
+        let rows = rows
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|p| p.value.into_inner())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            vec![
+                vec![ValueInner::Int(1), ValueInner::Bytes(b"a")],
+                vec![ValueInner::Int(2), ValueInner::Null],
+            ]
+        );
+
+        // The types are remembered for later executions which don't send them
+        let input = [0x00, 0x03, 0x00, 0x00, 0x00, 0x01];
+        let rows = BulkParamParser::new(&input, false, &mut stmt)
+            .rows()
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert!(matches!(rows[0][0].value.into_inner(), ValueInner::Int(3)));
+        assert!(rows[0][1].value.is_null());
+    }
+
+    #[test]
+    fn bulk_params_default_indicator_unsupported() {
+        let mut stmt = StatementData {
+            params: 1,
+            ..Default::default()
+        };
+        let input = [0x03, 0x00, 0x02];
+        assert!(BulkParamParser::new(&input, true, &mut stmt)
+            .rows()
+            .is_err());
+    }
+}
@@ -10,7 +10,7 @@ use nom::branch::alt;
 use nom::bytes::complete::tag_no_case;
 use nom::combinator::{map, opt, value};
 use nom::multi::separated_list1;
-use nom::sequence::{preceded, terminated};
+use nom::sequence::{preceded, terminated, tuple};
 use nom_locate::LocatedSpan;
 use readyset_util::fmt::fmt_with;
 use serde::{Deserialize, Serialize};
@@ -98,6 +98,9 @@ pub enum AlterTableDefinition {
         name: SqlIdentifier,
         new_name: SqlIdentifier,
     },
+    RenameTable {
+        new_name: Relation,
+    },
     DropConstraint {
         name: SqlIdentifier,
         drop_behavior: Option<DropBehavior>,
@@ -144,11 +147,14 @@ impl DialectDisplay for AlterTableDefinition {
             Self::RenameColumn { name, new_name } => {
                 write!(
                     f,
-                    "RENAME COLUMN {} {}",
+                    "RENAME COLUMN {} TO {}",
                     dialect.quote_identifier(name),
                     dialect.quote_identifier(new_name)
                 )
             }
+            Self::RenameTable { new_name } => {
+                write!(f, "RENAME TO {}", new_name.display(dialect))
+            }
             Self::DropConstraint {
                 name,
                 drop_behavior,
@@ -309,6 +315,7 @@ fn change_column(
         let (i, name) = dialect.identifier()(i)?;
         let (i, _) = whitespace1(i)?;
         let (i, spec) = column_specification(dialect)(i)?;
+        let (i, _) = opt(preceded(whitespace1, column_position(dialect)))(i)?;
 
         Ok((i, AlterTableDefinition::ChangeColumn { name, spec }))
     }
@@ -317,18 +324,36 @@ fn change_column(
 fn modify_column(
     dialect: Dialect,
 ) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], AlterTableDefinition> {
-    // TODO: FIRST, AFTER col_name
     move |i| {
         let (i, _) = tag_no_case("modify")(i)?;
         let (i, _) = opt(preceded(whitespace1, tag_no_case("column")))(i)?;
         let (i, _) = whitespace1(i)?;
 
-        map(column_specification(dialect), |spec| {
+        let (i, spec) = column_specification(dialect)(i)?;
+        let (i, _) = opt(preceded(whitespace1, column_position(dialect)))(i)?;
+
+        Ok((
+            i,
             AlterTableDefinition::ChangeColumn {
                 name: spec.column.name.clone(),
                 spec,
-            }
-        })(i)
+            },
+        ))
+    }
+}
+
+/// Parse (and discard) the `FIRST` or `AFTER col_name` suffix of a MySQL `CHANGE` or `MODIFY`
+/// definition. Column order isn't tracked here, since changing the type of a column requires
+/// resnapshotting its table anyway.
+fn column_position(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], ()> {
+    move |i| {
+        alt((
+            value((), tag_no_case("first")),
+            value(
+                (),
+                tuple((tag_no_case("after"), whitespace1, dialect.identifier())),
+            ),
+        ))(i)
     }
 }
 
@@ -338,19 +363,16 @@ fn rename_column(
     move |i| {
         let (i, _) = tag_no_case("rename")(i)?;
         let (i, _) = whitespace1(i)?;
-        let (i, _) = tag_no_case("column")(i)?;
-        let (i, _) = whitespace1(i)?;
-
-        let (i, name) = dialect.identifier()(i)?;
-
-        let i = if dialect == Dialect::PostgreSQL {
-            let (i, _) = whitespace1(i)?;
-            let (i, _) = tag_no_case("to")(i)?;
-            i
+        // The `COLUMN` keyword is optional in PostgreSQL
+        let (i, _) = if dialect == Dialect::PostgreSQL {
+            map(opt(terminated(tag_no_case("column"), whitespace1)), |_| ())(i)?
         } else {
-            i
+            map(terminated(tag_no_case("column"), whitespace1), |_| ())(i)?
         };
 
+        let (i, name) = dialect.identifier()(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, _) = tag_no_case("to")(i)?;
         let (i, _) = whitespace1(i)?;
         let (i, new_name) = dialect.identifier()(i)?;
 
@@ -358,6 +380,30 @@ fn rename_column(
     }
 }
 
+fn rename_table(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], AlterTableDefinition> {
+    move |i| {
+        let (i, _) = tag_no_case("rename")(i)?;
+        let (i, _) = whitespace1(i)?;
+        // MySQL allows `RENAME [TO | AS] new_name`, but PostgreSQL requires the `TO`
+        let (i, _) = if dialect == Dialect::PostgreSQL {
+            map(terminated(tag_no_case("to"), whitespace1), |_| ())(i)?
+        } else {
+            map(
+                opt(terminated(
+                    alt((tag_no_case("to"), tag_no_case("as"))),
+                    whitespace1,
+                )),
+                |_| (),
+            )(i)?
+        };
+        let (i, new_name) = relation(dialect)(i)?;
+
+        Ok((i, AlterTableDefinition::RenameTable { new_name }))
+    }
+}
+
 fn drop_constraint(
     dialect: Dialect,
 ) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], AlterTableDefinition> {
@@ -420,6 +466,7 @@ fn alter_table_definition(
             change_column(dialect),
             modify_column(dialect),
             rename_column(dialect),
+            rename_table(dialect),
             drop_constraint(dialect),
             replica_identity(dialect),
         ))(i)
@@ -780,6 +827,81 @@ mod tests {
             );
         }
 
+        #[test]
+        fn alter_change_and_modify_with_position() {
+            let res = test_parse!(
+                alter_table_statement(Dialect::MySQL),
+                b"ALTER TABLE t CHANGE COLUMN f g INT FIRST, MODIFY h TEXT AFTER g"
+            );
+            assert_eq!(
+                res.definitions.unwrap(),
+                vec![
+                    AlterTableDefinition::ChangeColumn {
+                        name: "f".into(),
+                        spec: ColumnSpecification::new("g".into(), SqlType::Int(None)),
+                    },
+                    AlterTableDefinition::ChangeColumn {
+                        name: "h".into(),
+                        spec: ColumnSpecification::new("h".into(), SqlType::Text),
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn alter_rename_column() {
+            let res = test_parse!(
+                alter_table_statement(Dialect::MySQL),
+                b"ALTER TABLE t RENAME COLUMN x TO `y`"
+            );
+            assert_eq!(
+                res.definitions,
+                Ok(vec![AlterTableDefinition::RenameColumn {
+                    name: "x".into(),
+                    new_name: "y".into()
+                }])
+            );
+            assert_eq!(
+                res.display(Dialect::MySQL).to_string(),
+                "ALTER TABLE `t` RENAME COLUMN `x` TO `y`"
+            );
+        }
+
+        #[test]
+        fn alter_rename_table() {
+            for qstring in [
+                &b"ALTER TABLE t RENAME t2"[..],
+                b"ALTER TABLE t RENAME TO t2",
+                b"ALTER TABLE t RENAME AS t2",
+            ] {
+                let res = test_parse!(alter_table_statement(Dialect::MySQL), qstring);
+                assert_eq!(
+                    res.definitions,
+                    Ok(vec![AlterTableDefinition::RenameTable {
+                        new_name: "t2".into()
+                    }])
+                );
+                assert_eq!(
+                    res.display(Dialect::MySQL).to_string(),
+                    "ALTER TABLE `t` RENAME TO `t2`"
+                );
+            }
+
+            let res = test_parse!(
+                alter_table_statement(Dialect::MySQL),
+                b"ALTER TABLE db1.t RENAME TO db2.t"
+            );
+            assert_eq!(
+                res.definitions,
+                Ok(vec![AlterTableDefinition::RenameTable {
+                    new_name: Relation {
+                        schema: Some("db2".into()),
+                        name: "t".into()
+                    }
+                }])
+            );
+        }
+
         #[test]
         fn flarum_alter_2() {
             let qstring = b"alter table `posts_likes` add primary key `posts_likes_post_id_user_id_primary`(`post_id`, `user_id`)";
@@ -1298,5 +1420,37 @@ mod tests {
                 }]
             );
         }
+
+        #[test]
+        fn alter_table_rename_to() {
+            let res = test_parse!(
+                alter_table_statement(Dialect::PostgreSQL),
+                b"ALTER TABLE t RENAME TO t2"
+            );
+            assert_eq!(
+                res.definitions.unwrap(),
+                vec![AlterTableDefinition::RenameTable {
+                    new_name: "t2".into()
+                }]
+            );
+
+            let res = test_parse!(
+                alter_table_statement(Dialect::PostgreSQL),
+                b"ALTER TABLE t RENAME x TO y"
+            );
+            assert_eq!(
+                res.definitions.unwrap(),
+                vec![AlterTableDefinition::RenameColumn {
+                    name: "x".into(),
+                    new_name: "y".into()
+                }]
+            );
+
+            let res = test_parse!(
+                alter_table_statement(Dialect::PostgreSQL),
+                b"ALTER TABLE t RENAME t2"
+            );
+            res.definitions.unwrap_err();
+        }
     }
 }
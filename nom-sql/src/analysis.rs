@@ -54,7 +54,9 @@ impl<'a> ReferredColumnsIter<'a> {
                 self.exprs_to_visit.push(lhs);
                 self.visit_expr(rhs)
             }
-            Expr::UnaryOp { rhs: expr, .. } | Expr::Cast { expr, .. } => self.visit_expr(expr),
+            Expr::UnaryOp { rhs: expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::Interval { value: expr, .. } => self.visit_expr(expr),
             Expr::Exists { .. } => None,
             Expr::Between {
                 operand, min, max, ..
@@ -98,7 +100,17 @@ impl<'a> ReferredColumnsIter<'a> {
             Sum { expr, .. } => self.visit_expr(expr),
             Max(arg) => self.visit_expr(arg),
             Min(arg) => self.visit_expr(arg),
-            GroupConcat { expr, .. } => self.visit_expr(expr),
+            ApproxCountDistinct { expr, .. } => self.visit_expr(expr),
+            GroupConcat { expr, order_by, .. } => {
+                self.exprs_to_visit
+                    .extend(order_by.iter().flat_map(|o| &o.order_by).filter_map(|ob| {
+                        match &ob.field {
+                            FieldReference::Expr(e) => Some(e),
+                            FieldReference::Numeric(_) => None,
+                        }
+                    }));
+                self.visit_expr(expr)
+            }
             Call { arguments, .. } => arguments.first().and_then(|first_arg| {
                 if arguments.len() >= 2 {
                     self.exprs_to_visit.extend(arguments.iter().skip(1));
@@ -110,6 +122,25 @@ impl<'a> ReferredColumnsIter<'a> {
                 self.exprs_to_visit.extend(len.iter().map(|e| e.as_ref()));
                 self.visit_expr(string)
             }
+            Match { columns, query } => {
+                self.columns_to_visit.extend(columns.iter());
+                self.visit_expr(query)
+            }
+            Rank {
+                partition_by,
+                order_by,
+                ..
+            } => {
+                self.exprs_to_visit.extend(partition_by.iter());
+                self.exprs_to_visit
+                    .extend(order_by.iter().flat_map(|o| &o.order_by).filter_map(|ob| {
+                        match &ob.field {
+                            FieldReference::Expr(e) => Some(e),
+                            FieldReference::Numeric(_) => None,
+                        }
+                    }));
+                None
+            }
         }
     }
 
@@ -169,7 +200,9 @@ impl<'a> ReferredColumnsMut<'a> {
                 self.exprs_to_visit.push(lhs);
                 self.visit_expr(rhs)
             }
-            Expr::UnaryOp { rhs: expr, .. } | Expr::Cast { expr, .. } => self.visit_expr(expr),
+            Expr::UnaryOp { rhs: expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::Interval { value: expr, .. } => self.visit_expr(expr),
             Expr::Exists { .. } => None,
             Expr::Between {
                 operand, min, max, ..
@@ -209,7 +242,19 @@ impl<'a> ReferredColumnsMut<'a> {
             Sum { expr, .. } => self.visit_expr(expr),
             Max(arg) => self.visit_expr(arg),
             Min(arg) => self.visit_expr(arg),
-            GroupConcat { expr, .. } => self.visit_expr(expr),
+            ApproxCountDistinct { expr, .. } => self.visit_expr(expr),
+            GroupConcat { expr, order_by, .. } => {
+                self.exprs_to_visit.extend(
+                    order_by
+                        .iter_mut()
+                        .flat_map(|o| &mut o.order_by)
+                        .filter_map(|ob| match &mut ob.field {
+                            FieldReference::Expr(e) => Some(e),
+                            FieldReference::Numeric(_) => None,
+                        }),
+                );
+                self.visit_expr(expr)
+            }
             Call { arguments, .. } => arguments.split_first_mut().and_then(|(first_arg, args)| {
                 self.exprs_to_visit.extend(args);
                 self.visit_expr(first_arg)
@@ -221,6 +266,27 @@ impl<'a> ReferredColumnsMut<'a> {
                     .extend(len.iter_mut().map(|e| e.as_mut()));
                 self.visit_expr(string)
             }
+            Match { columns, query } => {
+                self.columns_to_visit.extend(columns.iter_mut());
+                self.visit_expr(query)
+            }
+            Rank {
+                partition_by,
+                order_by,
+                ..
+            } => {
+                self.exprs_to_visit.extend(partition_by.iter_mut());
+                self.exprs_to_visit.extend(
+                    order_by
+                        .iter_mut()
+                        .flat_map(|o| &mut o.order_by)
+                        .filter_map(|ob| match &mut ob.field {
+                            FieldReference::Expr(e) => Some(e),
+                            FieldReference::Numeric(_) => None,
+                        }),
+                );
+                None
+            }
         }
     }
 
@@ -344,8 +410,11 @@ pub fn is_aggregate(function: &FunctionExpr) -> bool {
         | FunctionExpr::Sum { .. }
         | FunctionExpr::Max(_)
         | FunctionExpr::Min(_)
+        | FunctionExpr::ApproxCountDistinct { .. }
         | FunctionExpr::GroupConcat { .. } => true,
         FunctionExpr::Substring { .. }
+        | FunctionExpr::Match { .. }
+        | FunctionExpr::Rank { .. }
         // For now, assume all "generic" function calls are not aggregates
         | FunctionExpr::Call { .. } => false,
     }
@@ -373,7 +442,9 @@ pub fn contains_aggregate(expr: &Expr) -> bool {
         | Expr::OpAny { lhs, rhs, .. }
         | Expr::OpSome { lhs, rhs, .. }
         | Expr::OpAll { lhs, rhs, .. } => contains_aggregate(lhs) || contains_aggregate(rhs),
-        Expr::UnaryOp { rhs: expr, .. } | Expr::Cast { expr, .. } => contains_aggregate(expr),
+        Expr::UnaryOp { rhs: expr, .. }
+        | Expr::Cast { expr, .. }
+        | Expr::Interval { value: expr, .. } => contains_aggregate(expr),
         Expr::Exists(_) => false,
         Expr::Between {
             operand, min, max, ..
@@ -447,9 +518,9 @@ impl Expr {
             | Expr::OpAll { lhs, rhs, .. } => {
                 Box::new(vec![lhs, rhs].into_iter().map(AsRef::as_ref)) as _
             }
-            Expr::UnaryOp { rhs: expr, .. } | Expr::Cast { expr, .. } => {
-                Box::new(iter::once(expr.as_ref())) as _
-            }
+            Expr::UnaryOp { rhs: expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::Interval { value: expr, .. } => Box::new(iter::once(expr.as_ref())) as _,
             Expr::CaseWhen {
                 branches,
                 else_expr,
@@ -18,14 +18,14 @@ use crate::transaction::{CommitStatement, RollbackStatement, StartTransactionSta
 use crate::{
     AlterColumnOperation, AlterTableDefinition, AlterTableStatement, CacheInner, CaseWhenBranch,
     Column, ColumnConstraint, ColumnSpecification, CommentStatement, CommonTableExpr,
-    CompoundSelectStatement, CreateCacheStatement, CreateTableStatement, CreateViewStatement,
-    DeallocateStatement, DeleteStatement, DropAllCachesStatement, DropAllProxiedQueriesStatement,
-    DropCacheStatement, DropTableStatement, DropViewStatement, ExplainStatement, Expr,
-    FieldDefinitionExpr, FieldReference, FunctionExpr, GroupByClause, InValue, InsertStatement,
-    JoinClause, JoinConstraint, JoinRightSide, Literal, OrderBy, OrderClause, Relation,
-    SelectSpecification, SelectStatement, SetNames, SetPostgresParameter, SetStatement,
-    SetVariables, ShowStatement, SqlIdentifier, SqlQuery, SqlType, TableExpr, TableExprInner,
-    TableKey, UpdateStatement, UseStatement,
+    CompoundSelectStatement, CreateCacheStatement, CreateIndexStatement, CreateTableStatement,
+    CreateViewStatement, DeallocateStatement, DeleteStatement, DropAllCachesStatement,
+    DropAllProxiedQueriesStatement, DropCacheStatement, DropIndexStatement, DropTableStatement,
+    DropViewStatement, ExplainStatement, Expr, FieldDefinitionExpr, FieldReference, FunctionExpr,
+    GroupByClause, InValue, InsertStatement, JoinClause, JoinConstraint, JoinRightSide, Literal,
+    OrderBy, OrderClause, Relation, SelectSpecification, SelectStatement, SetNames,
+    SetPostgresParameter, SetStatement, SetVariables, ShowStatement, SqlIdentifier, SqlQuery,
+    SqlType, TableExpr, TableExprInner, TableKey, UpdateStatement, UseStatement,
 };
 
 /// Each method of the `Visitor` trait is a hook to be potentially overridden when recursively
@@ -387,6 +387,20 @@ pub trait Visitor<'ast>: Sized {
         walk_drop_view_statement(self, drop_view_statement)
     }
 
+    fn visit_create_index_statement(
+        &mut self,
+        create_index_statement: &'ast CreateIndexStatement,
+    ) -> Result<(), Self::Error> {
+        walk_create_index_statement(self, create_index_statement)
+    }
+
+    fn visit_drop_index_statement(
+        &mut self,
+        drop_index_statement: &'ast DropIndexStatement,
+    ) -> Result<(), Self::Error> {
+        walk_drop_index_statement(self, drop_index_statement)
+    }
+
     fn visit_use_statement(
         &mut self,
         use_statement: &'ast UseStatement,
@@ -485,6 +499,7 @@ pub fn walk_expr<'ast, V: Visitor<'ast>>(
             Ok(())
         }
         Expr::Variable(var) => visitor.visit_variable(var),
+        Expr::Interval { value, .. } => visitor.visit_expr(value),
     }
 }
 
@@ -507,7 +522,16 @@ pub fn walk_function_expr<'ast, V: Visitor<'ast>>(
         FunctionExpr::Sum { expr, .. } => visitor.visit_expr(expr.as_ref()),
         FunctionExpr::Max(expr) => visitor.visit_expr(expr.as_ref()),
         FunctionExpr::Min(expr) => visitor.visit_expr(expr.as_ref()),
-        FunctionExpr::GroupConcat { expr, .. } => visitor.visit_expr(expr.as_ref()),
+        FunctionExpr::ApproxCountDistinct { expr, .. } => visitor.visit_expr(expr.as_ref()),
+        FunctionExpr::GroupConcat { expr, order_by, .. } => {
+            visitor.visit_expr(expr.as_ref())?;
+            // Don't call `visit_order_clause` here, since that's only for the top-level `ORDER BY`
+            // clause of a query, which can reference aliases
+            if let Some(order_by) = order_by {
+                walk_order_clause(visitor, order_by)?;
+            }
+            Ok(())
+        }
         FunctionExpr::Call { arguments, .. } => {
             for arg in arguments {
                 visitor.visit_expr(arg)?;
@@ -524,6 +548,26 @@ pub fn walk_function_expr<'ast, V: Visitor<'ast>>(
             }
             Ok(())
         }
+        FunctionExpr::Match { columns, query } => {
+            for column in columns {
+                visitor.visit_column(column)?;
+            }
+            visitor.visit_expr(query.as_ref())
+        }
+        FunctionExpr::Rank {
+            partition_by,
+            order_by,
+            ..
+        } => {
+            for expr in partition_by {
+                visitor.visit_expr(expr)?;
+            }
+            // Like GROUP_CONCAT, the `ORDER BY` of a window can't reference aliases
+            if let Some(order_by) = order_by {
+                walk_order_clause(visitor, order_by)?;
+            }
+            Ok(())
+        }
     }
 }
 
@@ -767,6 +811,10 @@ pub fn walk_create_table_statement<'a, V: Visitor<'a>>(
         }
     }
 
+    if let Some(select) = &create_table_statement.select {
+        visitor.visit_select_statement(select)?;
+    }
+
     Ok(())
 }
 
@@ -948,6 +996,7 @@ pub fn walk_alter_table_definition<'a, V: Visitor<'a>>(
         AlterTableDefinition::ChangeColumn { name: _, spec } => {
             visitor.visit_column_specification(spec)
         }
+        AlterTableDefinition::RenameTable { new_name } => visitor.visit_table(new_name),
         AlterTableDefinition::DropColumn {
             name: _,
             behavior: _,
@@ -991,6 +1040,10 @@ pub fn walk_insert_statement<'a, V: Visitor<'a>>(
         }
     }
 
+    if let Some(select) = &insert_statement.select {
+        visitor.visit_select_statement(select)?;
+    }
+
     if let Some(on_duplicate) = &insert_statement.on_duplicate {
         for (column, expr) in on_duplicate {
             visitor.visit_column(column)?;
@@ -1123,6 +1176,29 @@ pub fn walk_drop_view_statement<'a, V: Visitor<'a>>(
     Ok(())
 }
 
+pub fn walk_create_index_statement<'a, V: Visitor<'a>>(
+    visitor: &mut V,
+    create_index_statement: &'a CreateIndexStatement,
+) -> Result<(), V::Error> {
+    visitor.visit_table(&create_index_statement.table)?;
+    for column in &create_index_statement.columns {
+        visitor.visit_column(column)?;
+    }
+
+    Ok(())
+}
+
+pub fn walk_drop_index_statement<'a, V: Visitor<'a>>(
+    visitor: &mut V,
+    drop_index_statement: &'a DropIndexStatement,
+) -> Result<(), V::Error> {
+    if let Some(table) = &drop_index_statement.table {
+        visitor.visit_table(table)?;
+    }
+
+    Ok(())
+}
+
 pub fn walk_sql_query<'a, V: Visitor<'a>>(
     visitor: &mut V,
     sql_query: &'a SqlQuery,
@@ -1151,6 +1227,8 @@ pub fn walk_sql_query<'a, V: Visitor<'a>>(
             visitor.visit_drop_all_proxied_queries_statement(statement)
         }
         SqlQuery::DropView(statement) => visitor.visit_drop_view_statement(statement),
+        SqlQuery::CreateIndex(statement) => visitor.visit_create_index_statement(statement),
+        SqlQuery::DropIndex(statement) => visitor.visit_drop_index_statement(statement),
         SqlQuery::Use(statement) => visitor.visit_use_statement(statement),
         SqlQuery::Show(statement) => visitor.visit_show_statement(statement),
         SqlQuery::Explain(statement) => visitor.visit_explain_statement(statement),
@@ -18,14 +18,14 @@ use crate::transaction::{CommitStatement, RollbackStatement, StartTransactionSta
 use crate::{
     AlterColumnOperation, AlterTableDefinition, AlterTableStatement, CacheInner, CaseWhenBranch,
     Column, ColumnConstraint, ColumnSpecification, CommentStatement, CommonTableExpr,
-    CompoundSelectStatement, CreateCacheStatement, CreateTableStatement, CreateViewStatement,
-    DeallocateStatement, DeleteStatement, DropAllCachesStatement, DropAllProxiedQueriesStatement,
-    DropCacheStatement, DropTableStatement, DropViewStatement, ExplainStatement, Expr,
-    FieldDefinitionExpr, FieldReference, FunctionExpr, GroupByClause, InValue, InsertStatement,
-    JoinClause, JoinConstraint, JoinRightSide, Literal, OrderBy, OrderClause, Relation,
-    SelectSpecification, SelectStatement, SetNames, SetPostgresParameter, SetStatement,
-    SetVariables, ShowStatement, SqlIdentifier, SqlQuery, SqlType, TableExpr, TableExprInner,
-    TableKey, UpdateStatement, UseStatement,
+    CompoundSelectStatement, CreateCacheStatement, CreateIndexStatement, CreateTableStatement,
+    CreateViewStatement, DeallocateStatement, DeleteStatement, DropAllCachesStatement,
+    DropAllProxiedQueriesStatement, DropCacheStatement, DropIndexStatement, DropTableStatement,
+    DropViewStatement, ExplainStatement, Expr, FieldDefinitionExpr, FieldReference, FunctionExpr,
+    GroupByClause, InValue, InsertStatement, JoinClause, JoinConstraint, JoinRightSide, Literal,
+    OrderBy, OrderClause, Relation, SelectSpecification, SelectStatement, SetNames,
+    SetPostgresParameter, SetStatement, SetVariables, ShowStatement, SqlIdentifier, SqlQuery,
+    SqlType, TableExpr, TableExprInner, TableKey, UpdateStatement, UseStatement,
 };
 
 /// Each method of the `VisitorMut` trait is a hook to be potentially overridden when recursively
@@ -402,6 +402,20 @@ pub trait VisitorMut<'ast>: Sized {
         walk_drop_view_statement(self, drop_view_statement)
     }
 
+    fn visit_create_index_statement(
+        &mut self,
+        create_index_statement: &'ast mut CreateIndexStatement,
+    ) -> Result<(), Self::Error> {
+        walk_create_index_statement(self, create_index_statement)
+    }
+
+    fn visit_drop_index_statement(
+        &mut self,
+        drop_index_statement: &'ast mut DropIndexStatement,
+    ) -> Result<(), Self::Error> {
+        walk_drop_index_statement(self, drop_index_statement)
+    }
+
     fn visit_use_statement(
         &mut self,
         use_statement: &'ast mut UseStatement,
@@ -500,6 +514,7 @@ pub fn walk_expr<'ast, V: VisitorMut<'ast>>(
             Ok(())
         }
         Expr::Variable(var) => visitor.visit_variable(var),
+        Expr::Interval { value, .. } => visitor.visit_expr(value),
     }
 }
 
@@ -522,7 +537,16 @@ pub fn walk_function_expr<'ast, V: VisitorMut<'ast>>(
         FunctionExpr::Sum { expr, .. } => visitor.visit_expr(expr.as_mut()),
         FunctionExpr::Max(expr) => visitor.visit_expr(expr.as_mut()),
         FunctionExpr::Min(expr) => visitor.visit_expr(expr.as_mut()),
-        FunctionExpr::GroupConcat { expr, .. } => visitor.visit_expr(expr.as_mut()),
+        FunctionExpr::ApproxCountDistinct { expr, .. } => visitor.visit_expr(expr.as_mut()),
+        FunctionExpr::GroupConcat { expr, order_by, .. } => {
+            visitor.visit_expr(expr.as_mut())?;
+            // Don't call `visit_order_clause` here, since that's only for the top-level `ORDER BY`
+            // clause of a query, which can reference aliases
+            if let Some(order_by) = order_by {
+                walk_order_clause(visitor, order_by)?;
+            }
+            Ok(())
+        }
         FunctionExpr::Call { arguments, .. } => {
             for arg in arguments {
                 visitor.visit_expr(arg)?;
@@ -539,6 +563,26 @@ pub fn walk_function_expr<'ast, V: VisitorMut<'ast>>(
             }
             Ok(())
         }
+        FunctionExpr::Match { columns, query } => {
+            for column in columns {
+                visitor.visit_column(column)?;
+            }
+            visitor.visit_expr(query.as_mut())
+        }
+        FunctionExpr::Rank {
+            partition_by,
+            order_by,
+            ..
+        } => {
+            for expr in partition_by {
+                visitor.visit_expr(expr)?;
+            }
+            // Like GROUP_CONCAT, the `ORDER BY` of a window can't reference aliases
+            if let Some(order_by) = order_by {
+                walk_order_clause(visitor, order_by)?;
+            }
+            Ok(())
+        }
     }
 }
 
@@ -787,6 +831,10 @@ pub fn walk_create_table_statement<'a, V: VisitorMut<'a>>(
         }
     }
 
+    if let Some(select) = &mut create_table_statement.select {
+        visitor.visit_select_statement(select)?;
+    }
+
     Ok(())
 }
 
@@ -968,6 +1016,7 @@ pub fn walk_alter_table_definition<'a, V: VisitorMut<'a>>(
         AlterTableDefinition::ChangeColumn { name: _, spec } => {
             visitor.visit_column_specification(spec)
         }
+        AlterTableDefinition::RenameTable { new_name } => visitor.visit_table(new_name),
         AlterTableDefinition::DropColumn {
             name: _,
             behavior: _,
@@ -1011,6 +1060,10 @@ pub fn walk_insert_statement<'a, V: VisitorMut<'a>>(
         }
     }
 
+    if let Some(select) = &mut insert_statement.select {
+        visitor.visit_select_statement(select)?;
+    }
+
     if let Some(on_duplicate) = &mut insert_statement.on_duplicate {
         for (column, expr) in on_duplicate {
             visitor.visit_column(column)?;
@@ -1143,6 +1196,29 @@ pub fn walk_drop_view_statement<'a, V: VisitorMut<'a>>(
     Ok(())
 }
 
+pub fn walk_create_index_statement<'a, V: VisitorMut<'a>>(
+    visitor: &mut V,
+    create_index_statement: &'a mut CreateIndexStatement,
+) -> Result<(), V::Error> {
+    visitor.visit_table(&mut create_index_statement.table)?;
+    for column in &mut create_index_statement.columns {
+        visitor.visit_column(column)?;
+    }
+
+    Ok(())
+}
+
+pub fn walk_drop_index_statement<'a, V: VisitorMut<'a>>(
+    visitor: &mut V,
+    drop_index_statement: &'a mut DropIndexStatement,
+) -> Result<(), V::Error> {
+    if let Some(table) = &mut drop_index_statement.table {
+        visitor.visit_table(table)?;
+    }
+
+    Ok(())
+}
+
 pub fn walk_sql_query<'a, V: VisitorMut<'a>>(
     visitor: &mut V,
     sql_query: &'a mut SqlQuery,
@@ -1171,6 +1247,8 @@ pub fn walk_sql_query<'a, V: VisitorMut<'a>>(
             visitor.visit_drop_all_proxied_queries_statement(statement)
         }
         SqlQuery::DropView(statement) => visitor.visit_drop_view_statement(statement),
+        SqlQuery::CreateIndex(statement) => visitor.visit_create_index_statement(statement),
+        SqlQuery::DropIndex(statement) => visitor.visit_drop_index_statement(statement),
         SqlQuery::Use(statement) => visitor.visit_use_statement(statement),
         SqlQuery::Show(statement) => visitor.visit_show_statement(statement),
         SqlQuery::Explain(statement) => visitor.visit_explain_statement(statement),
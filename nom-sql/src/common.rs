@@ -21,9 +21,10 @@ use test_strategy::Arbitrary;
 use crate::column::Column;
 use crate::dialect::{Dialect, DialectDisplay};
 use crate::expression::expression;
+use crate::order::{order_clause, OrderClause};
 use crate::table::Relation;
 use crate::whitespace::{whitespace0, whitespace1};
-use crate::{Expr, FunctionExpr, Literal, NomSqlResult, SqlIdentifier};
+use crate::{Expr, FunctionExpr, Literal, NomSqlResult, RankFunction, SqlIdentifier};
 
 #[cfg(feature = "debug")]
 pub fn debug_print(tag: &str, i: &[u8]) {
@@ -422,8 +423,114 @@ fn group_concat_fx_helper(
 
 fn group_concat_fx(
     dialect: Dialect,
-) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], (Expr, Option<String>)> {
-    move |i| pair(expression(dialect), opt(group_concat_fx_helper(dialect)))(i)
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], (Expr, Option<OrderClause>, Option<String>)>
+{
+    move |i| {
+        tuple((
+            expression(dialect),
+            opt(order_clause(dialect)),
+            opt(group_concat_fx_helper(dialect)),
+        ))(i)
+    }
+}
+
+/// Parses the PostgreSQL `STRING_AGG(expr, 'separator' [ORDER BY ...])` aggregate function into a
+/// [`FunctionExpr::GroupConcat`]
+fn string_agg(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], FunctionExpr> {
+    move |i| {
+        let (i, _) = tag_no_case("string_agg")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag("(")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, expr) = expression(dialect)(i)?;
+        let (i, _) = ws_sep_comma(i)?;
+        let (i, separator) = map_res(dialect.string_literal(), String::from_utf8)(i)?;
+        let (i, order_by) = opt(order_clause(dialect))(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag(")")(i)?;
+
+        Ok((
+            i,
+            FunctionExpr::GroupConcat {
+                expr: Box::new(expr),
+                separator: Some(separator),
+                order_by,
+            },
+        ))
+    }
+}
+
+/// Parses the `APPROX_COUNT_DISTINCT(expr [, precision])` aggregate function into a
+/// [`FunctionExpr::ApproxCountDistinct`]
+fn approx_count_distinct(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], FunctionExpr> {
+    move |i| {
+        let (i, _) = tag_no_case("approx_count_distinct")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag("(")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, expr) = expression(dialect)(i)?;
+        let (i, precision) = opt(preceded(
+            ws_sep_comma,
+            map_res(
+                map_res(digit1, |i: LocatedSpan<&[u8]>| str::from_utf8(&i)),
+                u8::from_str,
+            ),
+        ))(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag(")")(i)?;
+
+        Ok((
+            i,
+            FunctionExpr::ApproxCountDistinct {
+                expr: Box::new(expr),
+                precision,
+            },
+        ))
+    }
+}
+
+/// Parses a ranking window function such as `ROW_NUMBER() OVER (PARTITION BY x ORDER BY y)` into
+/// a [`FunctionExpr::Rank`]
+fn rank_function(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], FunctionExpr> {
+    move |i| {
+        let (i, function) = alt((
+            map(tag_no_case("row_number"), |_| RankFunction::RowNumber),
+            map(tag_no_case("dense_rank"), |_| RankFunction::DenseRank),
+            map(tag_no_case("rank"), |_| RankFunction::Rank),
+        ))(i)?;
+        let (i, _) = tuple((whitespace0, tag("("), whitespace0, tag(")"), whitespace0))(i)?;
+        let (i, _) = tag_no_case("over")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag("(")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, partition_by) = opt(preceded(
+            tuple((
+                tag_no_case("partition"),
+                whitespace1,
+                tag_no_case("by"),
+                whitespace1,
+            )),
+            separated_list1(ws_sep_comma, expression(dialect)),
+        ))(i)?;
+        let (i, order_by) = opt(order_clause(dialect))(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag(")")(i)?;
+
+        Ok((
+            i,
+            FunctionExpr::Rank {
+                function,
+                partition_by: partition_by.unwrap_or_default(),
+                order_by,
+            },
+        ))
+    }
 }
 
 fn agg_fx_args(
@@ -505,6 +612,46 @@ fn substring(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[
     }
 }
 
+fn match_against(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], FunctionExpr> {
+    move |i| {
+        let (i, _) = tag_no_case("match")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, columns) = delimited(
+            terminated(tag("("), whitespace0),
+            separated_list1(ws_sep_comma, column_identifier_no_alias(dialect)),
+            preceded(whitespace0, tag(")")),
+        )(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag_no_case("against")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag("(")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, query) = expression(dialect)(i)?;
+        let (i, _) = opt(tuple((
+            whitespace1,
+            tag_no_case("in"),
+            whitespace1,
+            tag_no_case("natural"),
+            whitespace1,
+            tag_no_case("language"),
+            whitespace1,
+            tag_no_case("mode"),
+        )))(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag(")")(i)?;
+
+        Ok((
+            i,
+            FunctionExpr::Match {
+                columns,
+                query: Box::new(query),
+            },
+        ))
+    }
+}
+
 fn function_call(
     dialect: Dialect,
 ) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], FunctionExpr> {
@@ -576,12 +723,17 @@ pub fn function_expr(
                         ),
                     ),
                 ),
-                |(expr, separator)| FunctionExpr::GroupConcat {
+                |(expr, order_by, separator)| FunctionExpr::GroupConcat {
                     expr: Box::new(expr),
                     separator,
+                    order_by,
                 },
             ),
+            string_agg(dialect),
+            approx_count_distinct(dialect),
+            rank_function(dialect),
             substring(dialect),
+            match_against(dialect),
             function_call(dialect),
             function_call_without_parens,
         ))(i)
@@ -849,7 +1001,7 @@ pub fn field_reference_list(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{to_nom_result, SqlType};
+    use crate::{to_nom_result, OrderBy, OrderType, SqlType};
 
     fn test_opt_delimited_fn_call(i: &str) -> IResult<&[u8], &[u8]> {
         opt_delimited(tag("("), tag("abc"), tag(")"))(i.as_bytes())
@@ -892,6 +1044,7 @@ mod tests {
         let expected = FunctionExpr::GroupConcat {
             expr: Box::new(Expr::Column(Column::from("x"))),
             separator: Some(", ".to_owned()),
+            order_by: None,
         };
         let res = to_nom_result(function_expr(Dialect::MySQL)(LocatedSpan::new(qs)));
         assert_eq!(res.unwrap().1, expected);
@@ -900,21 +1053,166 @@ mod tests {
             test_parse!(function_expr(Dialect::MySQL), b"group_concat('a')"),
             FunctionExpr::GroupConcat {
                 expr: Box::new(Expr::Literal("a".into())),
-                separator: None
+                separator: None,
+                order_by: None,
             }
         );
         assert_eq!(
             test_parse!(function_expr(Dialect::MySQL), b"group_concat (a)"),
             FunctionExpr::GroupConcat {
                 expr: Box::new(Expr::Column("a".into())),
-                separator: None
+                separator: None,
+                order_by: None,
             }
         );
         assert_eq!(
             test_parse!(function_expr(Dialect::MySQL), b"group_concat ( a )"),
             FunctionExpr::GroupConcat {
                 expr: Box::new(Expr::Column("a".into())),
-                separator: None
+                separator: None,
+                order_by: None,
+            }
+        );
+    }
+
+    #[test]
+    fn group_concat_order_by() {
+        assert_eq!(
+            test_parse!(
+                function_expr(Dialect::MySQL),
+                b"group_concat(x ORDER BY y DESC, z separator '; ')"
+            ),
+            FunctionExpr::GroupConcat {
+                expr: Box::new(Expr::Column("x".into())),
+                separator: Some("; ".to_owned()),
+                order_by: Some(OrderClause {
+                    order_by: vec![
+                        OrderBy {
+                            field: FieldReference::Expr(Expr::Column("y".into())),
+                            order_type: Some(OrderType::OrderDescending),
+                            null_order: None,
+                        },
+                        OrderBy {
+                            field: FieldReference::Expr(Expr::Column("z".into())),
+                            order_type: None,
+                            null_order: None,
+                        },
+                    ],
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_string_agg() {
+        let expected = FunctionExpr::GroupConcat {
+            expr: Box::new(Expr::Column("x".into())),
+            separator: Some(", ".to_owned()),
+            order_by: None,
+        };
+        assert_eq!(
+            test_parse!(function_expr(Dialect::PostgreSQL), b"string_agg(x, ', ')"),
+            expected
+        );
+        assert_eq!(
+            test_parse!(
+                function_expr(Dialect::PostgreSQL),
+                b"STRING_AGG ( x , ', ' )"
+            ),
+            expected
+        );
+        assert_eq!(
+            test_parse!(
+                function_expr(Dialect::PostgreSQL),
+                b"string_agg(x, ', ' ORDER BY y)"
+            ),
+            FunctionExpr::GroupConcat {
+                order_by: Some(OrderClause {
+                    order_by: vec![OrderBy {
+                        field: FieldReference::Expr(Expr::Column("y".into())),
+                        order_type: None,
+                        null_order: None,
+                    }],
+                }),
+                ..expected
+            }
+        );
+    }
+
+    #[test]
+    fn parse_approx_count_distinct() {
+        assert_eq!(
+            test_parse!(function_expr(Dialect::MySQL), b"approx_count_distinct(x)"),
+            FunctionExpr::ApproxCountDistinct {
+                expr: Box::new(Expr::Column("x".into())),
+                precision: None,
+            }
+        );
+        let res = test_parse!(
+            function_expr(Dialect::PostgreSQL),
+            b"APPROX_COUNT_DISTINCT ( x , 10 )"
+        );
+        assert_eq!(
+            res,
+            FunctionExpr::ApproxCountDistinct {
+                expr: Box::new(Expr::Column("x".into())),
+                precision: Some(10),
+            }
+        );
+        assert_eq!(
+            res.display(Dialect::PostgreSQL).to_string(),
+            "approx_count_distinct(\"x\", 10)"
+        );
+    }
+
+    #[test]
+    fn parse_rank_function() {
+        let res = test_parse!(
+            function_expr(Dialect::MySQL),
+            b"ROW_NUMBER() OVER (PARTITION BY x, y ORDER BY z DESC)"
+        );
+        assert_eq!(
+            res,
+            FunctionExpr::Rank {
+                function: RankFunction::RowNumber,
+                partition_by: vec![Expr::Column("x".into()), Expr::Column("y".into())],
+                order_by: Some(OrderClause {
+                    order_by: vec![OrderBy {
+                        field: FieldReference::Expr(Expr::Column("z".into())),
+                        order_type: Some(OrderType::OrderDescending),
+                        null_order: None,
+                    }],
+                }),
+            }
+        );
+        assert_eq!(
+            res.display(Dialect::MySQL).to_string(),
+            "row_number() OVER (PARTITION BY `x`, `y` ORDER BY `z` DESC)"
+        );
+
+        assert_eq!(
+            test_parse!(
+                function_expr(Dialect::PostgreSQL),
+                b"dense_rank() over (order by z)"
+            ),
+            FunctionExpr::Rank {
+                function: RankFunction::DenseRank,
+                partition_by: vec![],
+                order_by: Some(OrderClause {
+                    order_by: vec![OrderBy {
+                        field: FieldReference::Expr(Expr::Column("z".into())),
+                        order_type: None,
+                        null_order: None,
+                    }],
+                }),
+            }
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::MySQL), b"rank() over ()"),
+            FunctionExpr::Rank {
+                function: RankFunction::Rank,
+                partition_by: vec![],
+                order_by: None,
             }
         );
     }
@@ -1075,6 +1373,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn match_against() {
+        let expected = FunctionExpr::Match {
+            columns: vec!["title".into(), "body".into()],
+            query: Box::new(Expr::Literal("quick fox".into())),
+        };
+        assert_eq!(
+            test_parse!(
+                function_expr(Dialect::MySQL),
+                b"MATCH (title, body) AGAINST ('quick fox')"
+            ),
+            expected
+        );
+        assert_eq!(
+            test_parse!(
+                function_expr(Dialect::MySQL),
+                b"match(title,body) against('quick fox' IN NATURAL LANGUAGE MODE)"
+            ),
+            expected
+        );
+        assert_eq!(
+            expected.display(Dialect::MySQL).to_string(),
+            "MATCH (`title`, `body`) AGAINST ('quick fox')"
+        );
+    }
+
     #[test]
     fn count_star() {
         assert_eq!(
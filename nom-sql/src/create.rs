@@ -23,8 +23,9 @@ use crate::common::{
 use crate::compound_select::{nested_compound_selection, CompoundSelectStatement};
 use crate::create_table_options::{table_options, CreateTableOption};
 use crate::expression::expression;
+use crate::literal::display_string_literal;
 use crate::order::{order_type, OrderType};
-use crate::select::{selection, SelectStatement};
+use crate::select::{nested_selection, selection, SelectStatement};
 use crate::table::{relation, Relation};
 use crate::whitespace::{whitespace0, whitespace1};
 use crate::{Dialect, DialectDisplay, NomSqlError, NomSqlResult, SqlIdentifier};
@@ -72,6 +73,11 @@ pub struct CreateTableStatement {
     /// If it failed to parse, this will be an `Err` with the remainder [`String`] that could not
     /// be parsed.
     pub options: Result<Vec<CreateTableOption>, String>,
+    /// The query used to populate the table, for `CREATE TABLE ... AS SELECT` statements.
+    ///
+    /// If this is set and no column definitions were given, `body` will be an empty
+    /// [`CreateTableBody`].
+    pub select: Option<Box<SelectStatement>>,
 }
 
 impl DialectDisplay for CreateTableStatement {
@@ -81,15 +87,19 @@ impl DialectDisplay for CreateTableStatement {
             if self.if_not_exists {
                 write!(f, "IF NOT EXISTS ")?;
             }
-            write!(f, "{} (", self.table.display(dialect))?;
+            write!(f, "{}", self.table.display(dialect))?;
 
-            match &self.body {
-                Ok(body) => write!(f, "{}", body.display(dialect))?,
-                Err(unparsed) => write!(f, "{unparsed}")?,
+            let has_body = !matches!(
+                &self.body,
+                Ok(CreateTableBody { fields, keys: None }) if fields.is_empty()
+            );
+            if has_body || self.select.is_none() {
+                match &self.body {
+                    Ok(body) => write!(f, " ({})", body.display(dialect))?,
+                    Err(unparsed) => write!(f, " ({unparsed})")?,
+                }
             }
 
-            write!(f, ")")?;
-
             match &self.options {
                 Ok(options) => {
                     for (i, option) in options.iter().enumerate() {
@@ -105,6 +115,10 @@ impl DialectDisplay for CreateTableStatement {
                 Err(unparsed) => write!(f, "{unparsed}")?,
             }
 
+            if let Some(select) = &self.select {
+                write!(f, " AS {}", select.display(dialect))?;
+            }
+
             Ok(())
         })
     }
@@ -205,14 +219,52 @@ impl DialectDisplay for CacheInner {
     }
 }
 
+/// Strategy for determining which (partial) materializations should be placed beyond the
+/// materialization frontier.
+///
+/// Note that no matter what this is set to, all nodes whose name starts with `SHALLOW_` will be
+/// placed beyond the frontier.
+///
+/// The strategy configured for the server applies to all queries by default, but can be
+/// overridden for the nodes added by an individual cache with `CREATE CACHE FRONTIER <strategy>`.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub enum FrontierStrategy {
+    /// Place no nodes beyond the frontier (this is the default).
+    #[default]
+    None,
+    /// Place all partial materializations beyond the frontier.
+    AllPartial,
+    /// Place all partial readers beyond the frontier.
+    Readers,
+    /// Place all nodes whose name contain the given string beyond the frontier.
+    Match(String),
+}
+
+impl DialectDisplay for FrontierStrategy {
+    fn display(&self, _dialect: Dialect) -> impl fmt::Display + '_ {
+        fmt_with(move |f| match self {
+            Self::None => write!(f, "NONE"),
+            Self::AllPartial => write!(f, "ALL_PARTIAL"),
+            Self::Readers => write!(f, "READERS"),
+            Self::Match(m) => {
+                write!(f, "MATCHING ")?;
+                display_string_literal(f, m)
+            }
+        })
+    }
+}
+
 /// Optional `CREATE CACHE` arguments. This struct is only used for parsing.
 #[derive(Default)]
 struct CreateCacheOptions {
     always: bool,
     concurrently: bool,
+    frontier_strategy: Option<FrontierStrategy>,
+    disable_predicate_pushdown: bool,
 }
 
-/// `CREATE CACHE [CONCURRENTLY] [ALWAYS] [<name>] FROM ...`
+/// `CREATE CACHE [CONCURRENTLY] [ALWAYS] [FRONTIER <strategy>] [DISABLE PREDICATE PUSHDOWN]
+/// [<name>] FROM ...`
 ///
 /// This is a non-standard ReadySet specific extension to SQL
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize, Arbitrary)]
@@ -234,6 +286,11 @@ pub struct CreateCacheStatement {
     pub always: bool,
     /// Whether the CREATE CACHE STATEMENT should block or run concurrently
     pub concurrently: bool,
+    /// Override of the server's frontier strategy for the nodes added for this cache
+    pub frontier_strategy: Option<FrontierStrategy>,
+    /// Whether to compile this cache's query without pushing filters up the graph past joins and
+    /// unions
+    pub disable_predicate_pushdown: bool,
 }
 
 impl DialectDisplay for CreateCacheStatement {
@@ -246,6 +303,12 @@ impl DialectDisplay for CreateCacheStatement {
             if self.always {
                 write!(f, "ALWAYS ")?;
             }
+            if let Some(frontier_strategy) = &self.frontier_strategy {
+                write!(f, "FRONTIER {} ", frontier_strategy.display(dialect))?;
+            }
+            if self.disable_predicate_pushdown {
+                write!(f, "DISABLE PREDICATE PUSHDOWN ")?;
+            }
             if let Some(name) = &self.name {
                 write!(f, "{} ", name.display(dialect))?;
             }
@@ -258,6 +321,55 @@ impl DialectDisplay for CreateCacheStatement {
     }
 }
 
+/// `CREATE [UNIQUE] INDEX [IF NOT EXISTS] [<name>] ON <table> [USING <type>] (<columns>)`
+///
+/// Any options following the column list (eg `WHERE` clauses for partial indexes in PostgreSQL,
+/// or `ALGORITHM` and `LOCK` options in MySQL) are ignored.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct CreateIndexStatement {
+    /// The name of the index. PostgreSQL allows the name to be omitted, in which case it's
+    /// generated by the database.
+    pub name: Option<SqlIdentifier>,
+    pub table: Relation,
+    pub columns: Vec<Column>,
+    pub unique: bool,
+    pub if_not_exists: bool,
+    pub index_type: Option<IndexType>,
+}
+
+impl DialectDisplay for CreateIndexStatement {
+    fn display(&self, dialect: Dialect) -> impl fmt::Display + '_ {
+        fmt_with(move |f| {
+            write!(f, "CREATE ")?;
+            if self.unique {
+                write!(f, "UNIQUE ")?;
+            }
+            write!(f, "INDEX ")?;
+            if self.if_not_exists {
+                write!(f, "IF NOT EXISTS ")?;
+            }
+            if let Some(name) = &self.name {
+                write!(f, "{} ", dialect.quote_identifier(name))?;
+            }
+            write!(f, "ON {} ", self.table.display(dialect))?;
+            // MySQL only accepts the index type after the column list, and PostgreSQL only
+            // accepts it before
+            if let (Some(index_type), Dialect::PostgreSQL) = (self.index_type, dialect) {
+                write!(f, "USING {index_type} ")?;
+            }
+            write!(
+                f,
+                "({})",
+                self.columns.iter().map(|c| c.display(dialect)).join(", ")
+            )?;
+            if let (Some(index_type), Dialect::MySQL) = (self.index_type, dialect) {
+                write!(f, " USING {index_type}")?;
+            }
+            Ok(())
+        })
+    }
+}
+
 // MySQL grammar element for index column definition (§13.1.18, index_col_name)
 #[allow(clippy::type_complexity)]
 pub fn index_col_name(
@@ -450,27 +562,70 @@ fn foreign_key(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<
             separated_list0(ws_sep_comma, column_identifier_no_alias(dialect))(i)?;
         let (i, _) = tag(")")(i)?;
 
-        // ON DELETE
-        let (i, on_delete) = opt(move |i| {
-            let (i, _) = whitespace0(i)?;
-            let (i, _) = tag_no_case("on")(i)?;
-            let (i, _) = whitespace1(i)?;
-            let (i, _) = tag_no_case("delete")(i)?;
-            let (i, _) = whitespace1(i)?;
-
-            referential_action(i)
-        })(i)?;
+        // MATCH { FULL | PARTIAL | SIMPLE }
+        let (i, _) = opt(tuple((
+            whitespace1,
+            tag_no_case("match"),
+            whitespace1,
+            alt((
+                tag_no_case("full"),
+                tag_no_case("partial"),
+                tag_no_case("simple"),
+            )),
+        )))(i)?;
+
+        // ON DELETE and ON UPDATE, in either order (PostgreSQL's `pg_get_constraintdef` emits ON
+        // UPDATE first)
+        let mut on_delete = None;
+        let mut on_update = None;
+        let mut i = i;
+        for _ in 0..2 {
+            let (rem, action) = opt(move |i| {
+                let (i, _) = whitespace0(i)?;
+                let (i, _) = tag_no_case("on")(i)?;
+                let (i, _) = whitespace1(i)?;
+                let (i, is_delete) = alt((
+                    map(tag_no_case("delete"), |_| true),
+                    map(tag_no_case("update"), |_| false),
+                ))(i)?;
+                let (i, _) = whitespace1(i)?;
+                let (i, action) = referential_action(i)?;
+                Ok((i, (is_delete, action)))
+            })(i)?;
+            i = rem;
+            match action {
+                Some((true, action)) if on_delete.is_none() => on_delete = Some(action),
+                Some((false, action)) if on_update.is_none() => on_update = Some(action),
+                Some(_) => {
+                    return Err(nom::Err::Error(NomSqlError::from_error_kind(
+                        i,
+                        ErrorKind::Tag,
+                    )))
+                }
+                None => break,
+            }
+        }
 
-        // ON UPDATE
-        let (i, on_update) = opt(move |i| {
-            let (i, _) = whitespace0(i)?;
-            let (i, _) = tag_no_case("on")(i)?;
-            let (i, _) = whitespace1(i)?;
-            let (i, _) = tag_no_case("update")(i)?;
-            let (i, _) = whitespace1(i)?;
+        // [NOT] DEFERRABLE [INITIALLY { DEFERRED | IMMEDIATE }]
+        let (i, _) = opt(tuple((
+            whitespace1,
+            opt(terminated(tag_no_case("not"), whitespace1)),
+            tag_no_case("deferrable"),
+            opt(tuple((
+                whitespace1,
+                tag_no_case("initially"),
+                whitespace1,
+                alt((tag_no_case("deferred"), tag_no_case("immediate"))),
+            ))),
+        )))(i)?;
 
-            referential_action(i)
-        })(i)?;
+        // NOT VALID
+        let (i, _) = opt(tuple((
+            whitespace1,
+            tag_no_case("not"),
+            whitespace1,
+            tag_no_case("valid"),
+        )))(i)?;
         debug_print("after foreign_key", &i);
 
         Ok((
@@ -642,16 +797,44 @@ pub fn create_table(
         let (i, table) = relation(dialect)(i)?;
         let (i, _) = whitespace0(i)?;
 
-        let (i, _) = tag("(")(i)?;
-        let (i, body) = parse_fallible(
-            create_table_body(dialect),
-            map(is_not(")"), |r: LocatedSpan<&[u8]>| *r),
-        )(i)?;
-        let (i, _) = tag(")")(i)?;
+        let (i, body) = opt(delimited(
+            tag("("),
+            parse_fallible(
+                create_table_body(dialect),
+                map(is_not(")"), |r: LocatedSpan<&[u8]>| *r),
+            ),
+            tag(")"),
+        ))(i)?;
         let (i, _) = whitespace0(i)?;
         let (i, options) = parse_fallible(table_options(dialect), until_statement_terminator)(i)?;
+        let (i, select) = opt(preceded(
+            tuple((whitespace0, opt(terminated(tag_no_case("as"), whitespace1)))),
+            nested_selection(dialect),
+        ))(i)?;
+        // MySQL writes `CREATE TABLE ... SELECT` statements to the binlog as a plain `CREATE TABLE`
+        // followed by the inserted rows, with this suffix when the statement is atomic
+        let (i, _) = opt(tuple((
+            whitespace1,
+            tag_no_case("start"),
+            whitespace1,
+            tag_no_case("transaction"),
+        )))(i)?;
         let (i, _) = statement_terminator(i)?;
 
+        let body = match body {
+            Some(body) => body,
+            None if select.is_some() => Ok(CreateTableBody {
+                fields: vec![],
+                keys: None,
+            }),
+            None => {
+                return Err(nom::Err::Error(NomSqlError::from_error_kind(
+                    i,
+                    ErrorKind::Tag,
+                )))
+            }
+        };
+
         Ok((
             i,
             CreateTableStatement {
@@ -659,6 +842,7 @@ pub fn create_table(
                 if_not_exists,
                 body,
                 options,
+                select: select.map(Box::new),
             },
         ))
     }
@@ -767,8 +951,30 @@ pub fn view_creation(
     }
 }
 
+/// Parse the strategy in a `FRONTIER <strategy>` option to `CREATE CACHE`
+fn frontier_strategy(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], FrontierStrategy> {
+    move |i| {
+        alt((
+            map(tag_no_case("none"), |_| FrontierStrategy::None),
+            map(tag_no_case("all_partial"), |_| FrontierStrategy::AllPartial),
+            map(tag_no_case("readers"), |_| FrontierStrategy::Readers),
+            map(
+                preceded(
+                    tuple((tag_no_case("matching"), whitespace1)),
+                    dialect.utf8_string_literal(),
+                ),
+                FrontierStrategy::Match,
+            ),
+        ))(i)
+    }
+}
+
 /// Extract the [`CreateCacheOption`] from a `CREATE CACHE statement.
-fn cached_query_options(mut i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], CreateCacheOptions> {
+fn cached_query_options(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], CreateCacheOptions> {
     // Create an error given the position
     fn error(i: LocatedSpan<&[u8]>) -> nom::Err<NomSqlError<&[u8]>> {
         nom::Err::Failure(NomSqlError::from_error_kind(i, ErrorKind::Permutation))
@@ -778,36 +984,70 @@ fn cached_query_options(mut i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Create
     enum Option {
         Always,
         Concurrently,
+        Frontier(FrontierStrategy),
+        DisablePredicatePushdown,
     }
 
-    let mut opts = CreateCacheOptions::default();
-
-    // Parse a subset of the options in any order. Ignore errors since all options are optional.
-    while let Ok((remaining, opt)) = alt((
-        map(tuple((tag_no_case("always"), whitespace1)), |_| {
-            Option::Always
-        }),
-        map(tuple((tag_no_case("concurrently"), whitespace1)), |_| {
-            Option::Concurrently
-        }),
-    ))(i)
-    {
-        // Error if the same option appears twice.
-        match opt {
-            Option::Always => {
-                if std::mem::replace(&mut opts.always, true) {
-                    return Err(error(i));
+    move |mut i| {
+        let mut opts = CreateCacheOptions::default();
+
+        // Parse a subset of the options in any order. Ignore errors since all options are optional.
+        while let Ok((remaining, opt)) = alt((
+            map(tuple((tag_no_case("always"), whitespace1)), |_| {
+                Option::Always
+            }),
+            map(tuple((tag_no_case("concurrently"), whitespace1)), |_| {
+                Option::Concurrently
+            }),
+            map(
+                tuple((
+                    tag_no_case("frontier"),
+                    whitespace1,
+                    frontier_strategy(dialect),
+                    whitespace1,
+                )),
+                |(_, _, strategy, _)| Option::Frontier(strategy),
+            ),
+            map(
+                tuple((
+                    tag_no_case("disable"),
+                    whitespace1,
+                    tag_no_case("predicate"),
+                    whitespace1,
+                    tag_no_case("pushdown"),
+                    whitespace1,
+                )),
+                |_| Option::DisablePredicatePushdown,
+            ),
+        ))(i)
+        {
+            // Error if the same option appears twice.
+            match opt {
+                Option::Always => {
+                    if std::mem::replace(&mut opts.always, true) {
+                        return Err(error(i));
+                    }
                 }
-            }
-            Option::Concurrently => {
-                if std::mem::replace(&mut opts.concurrently, true) {
-                    return Err(error(i));
+                Option::Concurrently => {
+                    if std::mem::replace(&mut opts.concurrently, true) {
+                        return Err(error(i));
+                    }
+                }
+                Option::Frontier(strategy) => {
+                    if opts.frontier_strategy.replace(strategy).is_some() {
+                        return Err(error(i));
+                    }
+                }
+                Option::DisablePredicatePushdown => {
+                    if std::mem::replace(&mut opts.disable_predicate_pushdown, true) {
+                        return Err(error(i));
+                    }
                 }
             }
+            i = remaining;
         }
-        i = remaining;
+        Ok((i, opts))
     }
-    Ok((i, opts))
 }
 
 /// Extract the [`SelectStatement`] or Query ID from a CREATE CACHE statement. Query ID is
@@ -833,7 +1073,7 @@ pub fn create_cached_query(
         let (i, _) = whitespace1(i)?;
         let (i, _) = tag_no_case("cache")(i)?;
         let (i, _) = whitespace1(i)?;
-        let (i, opts) = cached_query_options(i)?;
+        let (i, opts) = cached_query_options(dialect)(i)?;
         let (i, name) = opt(terminated(relation(dialect), whitespace1))(i)?;
         let (i, _) = tag_no_case("from")(i)?;
         let (i, _) = whitespace1(i)?;
@@ -847,6 +1087,71 @@ pub fn create_cached_query(
                 unparsed_create_cache_statement,
                 always: opts.always,
                 concurrently: opts.concurrently,
+                frontier_strategy: opts.frontier_strategy,
+                disable_predicate_pushdown: opts.disable_predicate_pushdown,
+            },
+        ))
+    }
+}
+
+/// Parse a [`CreateIndexStatement`]
+pub fn create_index(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], CreateIndexStatement> {
+    /*
+       MySQL:
+       CREATE [UNIQUE | FULLTEXT | SPATIAL] INDEX index_name
+       [index_type]
+       ON tbl_name (key_part,...)
+       [index_option]
+       [algorithm_option | lock_option] ...
+
+       PostgreSQL:
+       CREATE [ UNIQUE ] INDEX [ CONCURRENTLY ] [ [ IF NOT EXISTS ] name ] ON [ ONLY ] table_name
+       [ USING method ]
+       ( { column_name | ( expression ) } [ COLLATE collation ] [ opclass ] ... [, ...] )
+       [ INCLUDE ( column_name [, ...] ) ]
+       ...
+    */
+    move |i| {
+        let (i, _) = tag_no_case("create")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, unique) = map(opt(terminated(tag_no_case("unique"), whitespace1)), |u| {
+            u.is_some()
+        })(i)?;
+        let (i, _) = tag_no_case("index")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, _) = opt(terminated(tag_no_case("concurrently"), whitespace1))(i)?;
+        let (i, if_not_exists) = if_not_exists(i)?;
+        let (i, name) = opt(dialect.identifier())(i)?;
+        let (i, mut index_type) = opt(using_index)(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag_no_case("on")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, _) = opt(terminated(tag_no_case("only"), whitespace1))(i)?;
+        let (i, table) = relation(dialect)(i)?;
+        let (i, using) = opt(using_index)(i)?;
+        index_type = index_type.or(using);
+        let (i, _) = whitespace0(i)?;
+        let (i, columns) = delimited(
+            tag("("),
+            delimited(whitespace0, index_col_list(dialect), whitespace0),
+            tag(")"),
+        )(i)?;
+        let (i, using) = opt(using_index)(i)?;
+        index_type = index_type.or(using);
+        let (i, _) = until_statement_terminator(i)?;
+        let (i, _) = statement_terminator(i)?;
+
+        Ok((
+            i,
+            CreateIndexStatement {
+                name,
+                table,
+                columns,
+                unique,
+                if_not_exists,
+                index_type,
             },
         ))
     }
@@ -900,7 +1205,8 @@ mod tests {
                     ],
                     keys: None,
                 }),
-                options: Ok(vec![])
+                options: Ok(vec![]),
+                select: None,
             }
         );
     }
@@ -921,7 +1227,8 @@ mod tests {
                     ),],
                     keys: None,
                 }),
-                options: Ok(vec![])
+                options: Ok(vec![]),
+                select: None,
             }
         );
     }
@@ -945,7 +1252,8 @@ mod tests {
                     ),],
                     keys: None,
                 }),
-                options: Ok(vec![])
+                options: Ok(vec![]),
+                select: None,
             }
         );
     }
@@ -977,7 +1285,8 @@ mod tests {
                         columns: vec![Column::from("id")]
                     }]),
                 }),
-                options: Ok(vec![])
+                options: Ok(vec![]),
+                select: None,
             }
         );
 
@@ -1007,7 +1316,8 @@ mod tests {
                         index_type: None
                     },]),
                 }),
-                options: Ok(vec![])
+                options: Ok(vec![]),
+                select: None,
             }
         );
     }
@@ -1095,7 +1405,8 @@ mod tests {
                         }
                     ]),
                 }),
-                options: Ok(vec![CreateTableOption::AutoIncrement(1000)],)
+                options: Ok(vec![CreateTableOption::AutoIncrement(1000)],),
+                select: None,
             }
         )
     }
@@ -1161,7 +1472,8 @@ mod tests {
                         on_update: None,
                     },]),
                 }),
-                options: Ok(vec![CreateTableOption::AutoIncrement(10)],)
+                options: Ok(vec![CreateTableOption::AutoIncrement(10)],),
+                select: None,
             }
         )
     }
@@ -1227,11 +1539,36 @@ mod tests {
                         },
                     ]),
                 }),
-                options: Ok(vec![],)
+                options: Ok(vec![],),
+                select: None,
             }
         )
     }
 
+    #[test]
+    fn foreign_key_referential_actions_in_any_order() {
+        let res = test_parse!(
+            key_specification(Dialect::MySQL),
+            b"FOREIGN KEY (post_id) REFERENCES posts (id) ON UPDATE CASCADE ON DELETE SET NULL"
+        );
+        assert_eq!(
+            res,
+            TableKey::ForeignKey {
+                constraint_name: None,
+                index_name: None,
+                columns: vec!["post_id".into()],
+                target_table: "posts".into(),
+                target_columns: vec!["id".into()],
+                on_delete: Some(ReferentialAction::SetNull),
+                on_update: Some(ReferentialAction::Cascade),
+            }
+        );
+        test_parse_expect_err!(
+            key_specification(Dialect::MySQL),
+            b"FOREIGN KEY (post_id) REFERENCES posts (id) ON DELETE CASCADE ON DELETE SET NULL"
+        );
+    }
+
     /// Tests that UNIQUE KEY column constraint is parsed properly
     #[test]
     fn test_unique_key() {
@@ -1273,7 +1610,8 @@ mod tests {
                     ],
                     keys: None,
                 }),
-                options: Ok(vec![CreateTableOption::AutoIncrement(1001)],)
+                options: Ok(vec![CreateTableOption::AutoIncrement(1001)],),
+                select: None,
             }
         )
     }
@@ -1400,7 +1738,8 @@ mod tests {
                         }],
                         keys: None,
                     }),
-                    options: Ok(vec![],)
+                    options: Ok(vec![],),
+                    select: None,
                 }
             );
         }
@@ -1466,7 +1805,8 @@ mod tests {
                         ],
                         keys: None,
                     }),
-                    options: Ok(vec![],)
+                    options: Ok(vec![],),
+                    select: None,
                 }
             );
 
@@ -1498,7 +1838,8 @@ mod tests {
                         ],
                         keys: None,
                     }),
-                    options: Ok(vec![],)
+                    options: Ok(vec![],),
+                    select: None,
                 }
             );
         }
@@ -1668,6 +2009,69 @@ mod tests {
             );
         }
 
+        #[test]
+        fn create_cached_query_with_frontier_strategy() {
+            let stmt = test_parse!(
+                create_cached_query(Dialect::MySQL),
+                b"CREATE CACHE ALWAYS FRONTIER MATCHING 'q_' foo FROM SELECT id FROM users"
+            );
+            assert!(stmt.always);
+            assert_eq!(
+                stmt.frontier_strategy,
+                Some(FrontierStrategy::Match("q_".into()))
+            );
+            assert_eq!(stmt.name, Some("foo".into()));
+            assert_eq!(
+                stmt.display(Dialect::MySQL).to_string(),
+                "CREATE CACHE ALWAYS FRONTIER MATCHING 'q_' `foo` FROM SELECT `id` FROM `users`"
+            );
+
+            let stmt = test_parse!(
+                create_cached_query(Dialect::MySQL),
+                b"CREATE CACHE FRONTIER readers FROM SELECT id FROM users"
+            );
+            assert_eq!(stmt.frontier_strategy, Some(FrontierStrategy::Readers));
+            assert!(stmt.name.is_none());
+
+            // Caches can still be named `frontier`
+            let stmt = test_parse!(
+                create_cached_query(Dialect::MySQL),
+                b"CREATE CACHE frontier FROM SELECT id FROM users"
+            );
+            assert!(stmt.frontier_strategy.is_none());
+            assert_eq!(stmt.name, Some("frontier".into()));
+        }
+
+        #[test]
+        fn create_cached_query_disable_predicate_pushdown() {
+            let stmt = test_parse!(
+                create_cached_query(Dialect::MySQL),
+                b"CREATE CACHE ALWAYS DISABLE PREDICATE PUSHDOWN foo FROM SELECT id FROM users"
+            );
+            assert!(stmt.always);
+            assert!(stmt.disable_predicate_pushdown);
+            assert_eq!(stmt.name, Some("foo".into()));
+            assert_eq!(
+                stmt.display(Dialect::MySQL).to_string(),
+                "CREATE CACHE ALWAYS DISABLE PREDICATE PUSHDOWN `foo` FROM SELECT `id` FROM `users`"
+            );
+
+            let stmt = test_parse!(
+                create_cached_query(Dialect::PostgreSQL),
+                b"CREATE CACHE disable predicate pushdown FROM SELECT id FROM users"
+            );
+            assert!(stmt.disable_predicate_pushdown);
+            assert!(stmt.name.is_none());
+
+            // Caches can still be named `disable`
+            let stmt = test_parse!(
+                create_cached_query(Dialect::MySQL),
+                b"CREATE CACHE disable FROM SELECT id FROM users"
+            );
+            assert!(!stmt.disable_predicate_pushdown);
+            assert_eq!(stmt.name, Some("disable".into()));
+        }
+
         #[test]
         fn lobsters_indexes() {
             let qstring = "CREATE TABLE `comments` (
@@ -1739,7 +2143,8 @@ mod tests {
                     options: Ok(vec![
                         CreateTableOption::Engine(Some("InnoDB".to_string())),
                         CreateTableOption::Charset(CharsetName::Unquoted("utf8mb4".into())),
-                    ])
+                    ]),
+                    select: None,
                 }
             );
         }
@@ -1781,6 +2186,7 @@ mod tests {
                         keys: None,
                     }),
                     options: Ok(vec![CreateTableOption::Other]),
+                    select: None,
                 }
             );
         }
@@ -1889,6 +2295,86 @@ mod tests {
                   GROUP BY emp_no"
             );
         }
+
+        #[test]
+        fn create_index() {
+            let res = test_parse!(
+                create_index(Dialect::MySQL),
+                b"CREATE UNIQUE INDEX `idx` USING BTREE ON `t` (`a` DESC, b(10)) ALGORITHM = INPLACE"
+            );
+            assert_eq!(
+                res,
+                CreateIndexStatement {
+                    name: Some("idx".into()),
+                    table: Relation::from("t"),
+                    columns: vec![Column::from("a"), Column::from("b")],
+                    unique: true,
+                    if_not_exists: false,
+                    index_type: Some(IndexType::BTree),
+                }
+            );
+            assert_eq!(
+                res.display(Dialect::MySQL).to_string(),
+                "CREATE UNIQUE INDEX `idx` ON `t` (`a`, `b`) USING BTREE"
+            );
+        }
+
+        #[test]
+        fn create_table_select() {
+            let res = test_parse!(
+                create_table(Dialect::MySQL),
+                b"CREATE TABLE t2 (id int PRIMARY KEY) ENGINE=InnoDB SELECT id, name FROM t1"
+            );
+            assert_eq!(res.table, Relation::from("t2"));
+            assert_eq!(res.body.as_ref().unwrap().fields.len(), 1);
+            assert_eq!(
+                res.options,
+                Ok(vec![CreateTableOption::Engine(Some("InnoDB".into()))])
+            );
+            assert_eq!(
+                res.select,
+                Some(Box::new(test_parse!(
+                    selection(Dialect::MySQL),
+                    b"SELECT id, name FROM t1"
+                )))
+            );
+            assert_eq!(
+                res.display(Dialect::MySQL).to_string(),
+                "CREATE TABLE `t2` (`id` INT PRIMARY KEY) ENGINE=InnoDB \
+                 AS SELECT `id`, `name` FROM `t1`"
+            );
+        }
+
+        #[test]
+        fn create_table_as_without_columns() {
+            let res = test_parse!(
+                create_table(Dialect::MySQL),
+                b"CREATE TABLE t2 AS SELECT * FROM t1"
+            );
+            assert_eq!(res.table, Relation::from("t2"));
+            assert!(res.body.as_ref().unwrap().fields.is_empty());
+            assert!(res.select.is_some());
+            assert_eq!(
+                res.display(Dialect::MySQL).to_string(),
+                "CREATE TABLE `t2` AS SELECT * FROM `t1`"
+            );
+        }
+
+        #[test]
+        fn create_table_start_transaction() {
+            let res = test_parse!(
+                create_table(Dialect::MySQL),
+                b"CREATE TABLE `t2` (\n  `id` int DEFAULT NULL\n) START TRANSACTION"
+            );
+            assert_eq!(res.table, Relation::from("t2"));
+            assert_eq!(res.body.as_ref().unwrap().fields.len(), 1);
+            assert_eq!(res.select, None);
+        }
+
+        #[test]
+        fn create_table_without_body_or_select() {
+            test_parse_expect_err!(create_table(Dialect::MySQL), b"CREATE TABLE t2");
+        }
     }
 
     mod postgres {
@@ -1929,7 +2415,8 @@ mod tests {
                         }],
                         keys: None,
                     }),
-                    options: Ok(vec![])
+                    options: Ok(vec![]),
+                    select: None,
                 }
             );
         }
@@ -1950,7 +2437,8 @@ mod tests {
                         ),],
                         keys: None,
                     }),
-                    options: Ok(vec![])
+                    options: Ok(vec![]),
+                    select: None,
                 }
             );
         }
@@ -2023,7 +2511,8 @@ mod tests {
                         ],
                         keys: None,
                     }),
-                    options: Ok(vec![])
+                    options: Ok(vec![]),
+                    select: None,
                 }
             );
 
@@ -2056,6 +2545,7 @@ mod tests {
                         keys: None,
                     }),
                     options: Ok(vec![]),
+                    select: None,
                 }
             );
         }
@@ -2200,6 +2690,7 @@ mod tests {
                         CreateTableOption::Engine(Some("InnoDB".to_string())),
                         CreateTableOption::Charset(CharsetName::Unquoted("utf8mb4".into()))
                     ]),
+                    select: None,
                 }
             );
         }
@@ -2241,6 +2732,7 @@ mod tests {
                         keys: None,
                     }),
                     options: Ok(vec![CreateTableOption::Other]),
+                    select: None,
                 }
             );
         }
@@ -2298,6 +2790,89 @@ mod tests {
         )";
             create_table(Dialect::PostgreSQL)(LocatedSpan::new(qstring.as_bytes())).unwrap();
         }
+
+        #[test]
+        fn create_index() {
+            let res = test_parse!(
+                create_index(Dialect::PostgreSQL),
+                b"CREATE INDEX CONCURRENTLY IF NOT EXISTS \"idx\" ON ONLY public.t USING hash (a) WHERE a > 1"
+            );
+            assert_eq!(
+                res,
+                CreateIndexStatement {
+                    name: Some("idx".into()),
+                    table: Relation {
+                        schema: Some("public".into()),
+                        name: "t".into(),
+                    },
+                    columns: vec![Column::from("a")],
+                    unique: false,
+                    if_not_exists: true,
+                    index_type: Some(IndexType::Hash),
+                }
+            );
+            assert_eq!(
+                res.display(Dialect::PostgreSQL).to_string(),
+                "CREATE INDEX IF NOT EXISTS \"idx\" ON \"public\".\"t\" USING HASH (\"a\")"
+            );
+        }
+
+        #[test]
+        fn create_unnamed_index() {
+            let res = test_parse!(
+                create_index(Dialect::PostgreSQL),
+                b"create index on t (a, b);"
+            );
+            assert_eq!(res.name, None);
+            assert_eq!(res.table, Relation::from("t"));
+            assert_eq!(res.columns, vec![Column::from("a"), Column::from("b")]);
+        }
+
+        #[test]
+        fn create_table_as() {
+            let res = test_parse!(
+                create_table(Dialect::PostgreSQL),
+                b"CREATE TABLE IF NOT EXISTS t2 AS SELECT id FROM t1 WHERE id > 1;"
+            );
+            assert_eq!(res.table, Relation::from("t2"));
+            assert!(res.if_not_exists);
+            assert!(res.body.as_ref().unwrap().fields.is_empty());
+            assert_eq!(
+                res.select,
+                Some(Box::new(test_parse!(
+                    selection(Dialect::PostgreSQL),
+                    b"SELECT id FROM t1 WHERE id > 1"
+                )))
+            );
+            assert_eq!(
+                res.display(Dialect::PostgreSQL).to_string(),
+                "CREATE TABLE IF NOT EXISTS \"t2\" AS SELECT \"id\" FROM \"t1\" WHERE (\"id\" > 1)"
+            );
+        }
+
+        #[test]
+        fn foreign_key_from_constraintdef() {
+            let res = test_parse!(
+                key_specification(Dialect::PostgreSQL),
+                b"FOREIGN KEY (parent_id) REFERENCES public.parent(id) MATCH FULL \
+                  ON UPDATE RESTRICT ON DELETE CASCADE DEFERRABLE INITIALLY DEFERRED NOT VALID"
+            );
+            assert_eq!(
+                res,
+                TableKey::ForeignKey {
+                    constraint_name: None,
+                    index_name: None,
+                    columns: vec!["parent_id".into()],
+                    target_table: Relation {
+                        schema: Some("public".into()),
+                        name: "parent".into(),
+                    },
+                    target_columns: vec!["id".into()],
+                    on_delete: Some(ReferentialAction::Cascade),
+                    on_update: Some(ReferentialAction::Restrict),
+                }
+            );
+        }
     }
 
     #[test]
@@ -2430,6 +3005,7 @@ mod tests {
                         "utf8mb4_unicode_ci".into()
                     ))
                 ]),
+                select: None,
             }
         )
     }
@@ -2463,6 +3039,7 @@ mod tests {
                     CreateTableOption::Charset(CharsetName::Unquoted("utf8mb4".into())),
                     CreateTableOption::Collate(CollationName::Quoted("utf8mb4_unicode_ci".into()))
                 ]),
+                select: None,
             }
         )
     }
@@ -2564,7 +3141,8 @@ PRIMARY KEY (`id`));";
                     ),],
                     keys: None,
                 }),
-                options: Ok(vec![])
+                options: Ok(vec![]),
+                select: None,
             }
         );
     }
@@ -2597,6 +3175,7 @@ PRIMARY KEY (`id`));";
                     keys: None,
                 }),
                 options: Ok(vec![]),
+                select: None,
             }
         )
     }
@@ -17,7 +17,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::keywords::{sql_keyword, sql_keyword_or_builtin_function, POSTGRES_NOT_RESERVED};
-use crate::literal::{raw_string_literal, QuotingStyle};
+use crate::literal::{raw_dollar_quoted_string, raw_string_literal, QuotingStyle};
 use crate::select::{LimitClause, LimitValue};
 use crate::whitespace::whitespace0;
 use crate::{literal, Literal, NomSqlError, NomSqlResult, SqlIdentifier};
@@ -243,10 +243,13 @@ impl Dialect {
             // Currently we allow escape sequences in all string constants. If we support postgres'
             // standard_conforming_strings setting, then the below should be changed to check for
             // the presence of a preceding 'E' instead of matching and discarding the match result.
-            Dialect::PostgreSQL => preceded(
-                opt(tag_no_case("E")),
-                raw_string_literal(self.quoting_style()),
-            )(i),
+            Dialect::PostgreSQL => alt((
+                raw_dollar_quoted_string,
+                preceded(
+                    opt(tag_no_case("E")),
+                    raw_string_literal(self.quoting_style()),
+                ),
+            ))(i),
             Dialect::MySQL => preceded(
                 opt(alt((tag("_utf8mb4"), tag("_utf8"), tag("_binary")))),
                 raw_string_literal(self.quoting_style()),
@@ -571,6 +574,24 @@ mod tests {
             );
         }
 
+        #[test]
+        fn literal_string_dollar_quoted() {
+            let res = to_nom_result(Dialect::PostgreSQL.string_literal()(LocatedSpan::new(
+                b"$$it's a \\n $1 string$$",
+            )));
+            assert_eq!(res, Ok((&b""[..], b"it's a \\n $1 string".to_vec())));
+
+            let res = to_nom_result(Dialect::PostgreSQL.string_literal()(LocatedSpan::new(
+                b"$fn_body$ SELECT $$nested$$; $fn_body$",
+            )));
+            assert_eq!(res, Ok((&b""[..], b" SELECT $$nested$$; ".to_vec())));
+
+            // Placeholders aren't dollar-quoted strings
+            Dialect::PostgreSQL.string_literal()(LocatedSpan::new(b"$1$")).unwrap_err();
+            // Neither are unterminated strings
+            Dialect::PostgreSQL.string_literal()(LocatedSpan::new(b"$a$ foo $b$")).unwrap_err();
+        }
+
         #[test]
         fn bytes_parsing() {
             let res = to_nom_result(Dialect::PostgreSQL.bytes_literal()(LocatedSpan::new(
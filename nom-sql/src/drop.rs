@@ -5,13 +5,13 @@ use itertools::Itertools;
 use nom::bytes::complete::tag_no_case;
 use nom::combinator::{map, opt};
 use nom::multi::separated_list1;
-use nom::sequence::preceded;
+use nom::sequence::{preceded, terminated};
 use nom_locate::LocatedSpan;
 use readyset_util::fmt::fmt_with;
 use serde::{Deserialize, Serialize};
 use test_strategy::Arbitrary;
 
-use crate::common::{statement_terminator, ws_sep_comma};
+use crate::common::{statement_terminator, until_statement_terminator, ws_sep_comma};
 use crate::table::{relation, table_list, Relation};
 use crate::whitespace::whitespace1;
 use crate::{Dialect, DialectDisplay, NomSqlResult};
@@ -174,6 +174,65 @@ pub fn drop_view(
     }
 }
 
+/// `DROP INDEX [IF EXISTS] <name> [ON <table>]`
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct DropIndexStatement {
+    /// The name of the index. In PostgreSQL, indexes live in the same schema as their table, so
+    /// the name may be qualified with that schema.
+    pub name: Relation,
+    /// The table the index is on. Required in MySQL, and not allowed in PostgreSQL.
+    pub table: Option<Relation>,
+    pub if_exists: bool,
+}
+
+impl DialectDisplay for DropIndexStatement {
+    fn display(&self, dialect: Dialect) -> impl Display + '_ {
+        fmt_with(move |f| {
+            write!(f, "DROP INDEX ")?;
+            if self.if_exists {
+                write!(f, "IF EXISTS ")?;
+            }
+            write!(f, "{}", self.name.display(dialect))?;
+            if let Some(table) = &self.table {
+                write!(f, " ON {}", table.display(dialect))?;
+            }
+            Ok(())
+        })
+    }
+}
+
+pub fn drop_index(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], DropIndexStatement> {
+    move |i| {
+        let (i, _) = tag_no_case("drop")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, _) = tag_no_case("index")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, _) = opt(terminated(tag_no_case("concurrently"), whitespace1))(i)?;
+        let (i, if_exists) = if_exists(i)?;
+        let (i, name) = relation(dialect)(i)?;
+        let (i, table) = opt(|i| {
+            let (i, _) = whitespace1(i)?;
+            let (i, _) = tag_no_case("on")(i)?;
+            let (i, _) = whitespace1(i)?;
+            relation(dialect)(i)
+        })(i)?;
+        let (i, _) = restrict_cascade(i)?;
+        // Ignore MySQL's ALGORITHM and LOCK options
+        let (i, _) = until_statement_terminator(i)?;
+        let (i, _) = statement_terminator(i)?;
+        Ok((
+            i,
+            DropIndexStatement {
+                name,
+                table,
+                if_exists,
+            },
+        ))
+    }
+}
+
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize, Arbitrary)]
 pub struct DropAllCachesStatement {}
 
@@ -305,6 +364,26 @@ mod tests {
                 "DROP VIEW IF EXISTS `v1`, `v2`"
             );
         }
+
+        #[test]
+        fn drop_index() {
+            let res = test_parse!(
+                super::drop_index(Dialect::MySQL),
+                b"DROP INDEX `idx` ON `t` ALGORITHM = INPLACE;"
+            );
+            assert_eq!(
+                res,
+                DropIndexStatement {
+                    name: "idx".into(),
+                    table: Some("t".into()),
+                    if_exists: false,
+                }
+            );
+            assert_eq!(
+                res.display(Dialect::MySQL).to_string(),
+                "DROP INDEX `idx` ON `t`"
+            );
+        }
     }
 
     mod postgres {
@@ -332,5 +411,28 @@ mod tests {
                 "DROP VIEW IF EXISTS \"v1\", \"v2\""
             );
         }
+
+        #[test]
+        fn drop_index() {
+            let res = test_parse!(
+                super::drop_index(Dialect::PostgreSQL),
+                b"DROP INDEX CONCURRENTLY IF EXISTS public.idx CASCADE"
+            );
+            assert_eq!(
+                res,
+                DropIndexStatement {
+                    name: Relation {
+                        schema: Some("public".into()),
+                        name: "idx".into(),
+                    },
+                    table: None,
+                    if_exists: true,
+                }
+            );
+            assert_eq!(
+                res.display(Dialect::PostgreSQL).to_string(),
+                "DROP INDEX IF EXISTS \"public\".\"idx\""
+            );
+        }
     }
 }
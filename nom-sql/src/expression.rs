@@ -6,8 +6,8 @@ use derive_more::From;
 use itertools::Itertools;
 use nom::branch::alt;
 use nom::bytes::complete::{tag, tag_no_case};
-use nom::character::complete::char;
-use nom::combinator::{complete, map, opt, peek, value};
+use nom::character::complete::{char, satisfy};
+use nom::combinator::{complete, map, not, opt, peek, value};
 use nom::multi::{many0, many1, separated_list0, separated_list1};
 use nom::sequence::{delimited, pair, preceded, terminated, tuple};
 use nom::Parser;
@@ -20,13 +20,15 @@ use serde::{Deserialize, Serialize};
 use test_strategy::Arbitrary;
 
 use crate::common::{column_identifier_no_alias, function_expr, ws_sep_comma};
+use crate::dialect::is_sql_identifier;
 use crate::literal::{literal, Double, Float};
 use crate::select::nested_selection;
 use crate::set::{variable_scope_prefix, Variable};
 use crate::sql_type::{mysql_int_cast_targets, type_identifier};
 use crate::whitespace::{whitespace0, whitespace1};
 use crate::{
-    Column, Dialect, DialectDisplay, Literal, NomSqlResult, SelectStatement, SqlIdentifier, SqlType,
+    Column, Dialect, DialectDisplay, Literal, NomSqlResult, OrderClause, SelectStatement,
+    SqlIdentifier, SqlType,
 };
 
 /// Function call expressions
@@ -50,10 +52,19 @@ pub enum FunctionExpr {
     /// `MIN` aggregation
     Min(Box<Expr>),
 
-    /// `GROUP_CONCAT` aggregation
+    /// `APPROX_COUNT_DISTINCT` aggregation, which approximates the number of distinct non-null
+    /// values using a HyperLogLog sketch with `2^precision` registers
+    ApproxCountDistinct {
+        expr: Box<Expr>,
+        precision: Option<u8>,
+    },
+
+    /// `GROUP_CONCAT` aggregation in MySQL, or `STRING_AGG` aggregation in PostgreSQL
     GroupConcat {
         expr: Box<Expr>,
         separator: Option<String>,
+        /// Optional `ORDER BY` clause specifying the order to concatenate values within each group
+        order_by: Option<OrderClause>,
     },
 
     /// The SQL `SUBSTRING`/`SUBSTR` function.
@@ -71,6 +82,26 @@ pub enum FunctionExpr {
         len: Option<Box<Expr>>,
     },
 
+    /// MySQL full-text search function.
+    ///
+    /// The supported syntax is:
+    ///
+    /// `MATCH (col1, col2, ...) AGAINST (expr [IN NATURAL LANGUAGE MODE])`
+    Match {
+        columns: Vec<Column>,
+        query: Box<Expr>,
+    },
+
+    /// A ranking window function, such as `ROW_NUMBER() OVER (PARTITION BY x ORDER BY y)`.
+    ///
+    /// Only supported as a column of a subquery in the `FROM` clause whose rank is compared
+    /// against a constant in the outer query.
+    Rank {
+        function: RankFunction,
+        partition_by: Vec<Expr>,
+        order_by: Option<OrderClause>,
+    },
+
     /// Generic function call expression
     Call {
         name: SqlIdentifier,
@@ -78,6 +109,30 @@ pub enum FunctionExpr {
     },
 }
 
+/// The ranking window functions which can be used in a [`FunctionExpr::Rank`]
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, Hash, Serialize, Deserialize, Arbitrary,
+)]
+pub enum RankFunction {
+    /// `ROW_NUMBER()`, which numbers the rows of each partition consecutively, breaking ties
+    /// arbitrarily
+    RowNumber,
+    /// `RANK()`, which gives tied rows the same rank, and skips ranks after ties
+    Rank,
+    /// `DENSE_RANK()`, which gives tied rows the same rank, without skipping ranks after ties
+    DenseRank,
+}
+
+impl fmt::Display for RankFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RankFunction::RowNumber => write!(f, "row_number"),
+            RankFunction::Rank => write!(f, "rank"),
+            RankFunction::DenseRank => write!(f, "dense_rank"),
+        }
+    }
+}
+
 impl FunctionExpr {
     /// Returns an iterator over all the direct arguments passed to the given function call
     /// expression
@@ -89,11 +144,13 @@ impl FunctionExpr {
             | FunctionExpr::Sum { expr: arg, .. }
             | FunctionExpr::Max(arg)
             | FunctionExpr::Min(arg)
+            | FunctionExpr::ApproxCountDistinct { expr: arg, .. }
             | FunctionExpr::GroupConcat { expr: arg, .. } => {
                 concrete_iter!(iter::once(arg.as_ref()))
             }
-            FunctionExpr::CountStar => concrete_iter!(iter::empty()),
+            FunctionExpr::CountStar | FunctionExpr::Rank { .. } => concrete_iter!(iter::empty()),
             FunctionExpr::Call { arguments, .. } => concrete_iter!(arguments),
+            FunctionExpr::Match { query, .. } => concrete_iter!(iter::once(query.as_ref())),
             FunctionExpr::Substring { string, pos, len } => {
                 concrete_iter!(iter::once(string.as_ref())
                     .chain(pos.iter().map(|p| p.as_ref()))
@@ -124,17 +181,45 @@ impl DialectDisplay for FunctionExpr {
             FunctionExpr::Sum { expr, .. } => write!(f, "sum({})", expr.display(dialect)),
             FunctionExpr::Max(col) => write!(f, "max({})", col.display(dialect)),
             FunctionExpr::Min(col) => write!(f, "min({})", col.display(dialect)),
-            FunctionExpr::GroupConcat { expr, separator } => {
-                write!(f, "group_concat({}", expr.display(dialect),)?;
-                if let Some(separator) = separator {
+            FunctionExpr::ApproxCountDistinct { expr, precision } => {
+                write!(f, "approx_count_distinct({}", expr.display(dialect))?;
+                if let Some(precision) = precision {
+                    write!(f, ", {precision}")?;
+                }
+                write!(f, ")")
+            }
+            FunctionExpr::GroupConcat {
+                expr,
+                separator,
+                order_by,
+            } => match dialect {
+                Dialect::PostgreSQL => {
                     write!(
                         f,
-                        " separator '{}'",
-                        separator.replace('\'', "''").replace('\\', "\\\\")
+                        "string_agg({}, '{}'",
+                        expr.display(dialect),
+                        separator.as_deref().unwrap_or(",").replace('\'', "''")
                     )?;
+                    if let Some(order_by) = order_by {
+                        write!(f, " {}", order_by.display(dialect))?;
+                    }
+                    write!(f, ")")
                 }
-                write!(f, ")")
-            }
+                Dialect::MySQL => {
+                    write!(f, "group_concat({}", expr.display(dialect),)?;
+                    if let Some(order_by) = order_by {
+                        write!(f, " {}", order_by.display(dialect))?;
+                    }
+                    if let Some(separator) = separator {
+                        write!(
+                            f,
+                            " separator '{}'",
+                            separator.replace('\'', "''").replace('\\', "\\\\")
+                        )?;
+                    }
+                    write!(f, ")")
+                }
+            },
             FunctionExpr::Call { name, arguments } => {
                 write!(
                     f,
@@ -143,6 +228,12 @@ impl DialectDisplay for FunctionExpr {
                     arguments.iter().map(|arg| arg.display(dialect)).join(", ")
                 )
             }
+            FunctionExpr::Match { columns, query } => write!(
+                f,
+                "MATCH ({}) AGAINST ({})",
+                columns.iter().map(|c| c.display(dialect)).join(", "),
+                query.display(dialect)
+            ),
             FunctionExpr::Substring { string, pos, len } => {
                 write!(f, "substring({}", string.display(dialect))?;
 
@@ -156,6 +247,27 @@ impl DialectDisplay for FunctionExpr {
 
                 write!(f, ")")
             }
+            FunctionExpr::Rank {
+                function,
+                partition_by,
+                order_by,
+            } => {
+                write!(f, "{function}() OVER (")?;
+                if !partition_by.is_empty() {
+                    write!(
+                        f,
+                        "PARTITION BY {}",
+                        partition_by.iter().map(|e| e.display(dialect)).join(", ")
+                    )?;
+                    if order_by.is_some() {
+                        write!(f, " ")?;
+                    }
+                }
+                if let Some(order_by) = order_by {
+                    write!(f, "{}", order_by.display(dialect))?;
+                }
+                write!(f, ")")
+            }
         })
     }
 }
@@ -275,6 +387,12 @@ pub enum BinaryOperator {
     /// Postgres-specific JSONB operator. Behaves like [`BinaryOperator::AtArrowRight`] with
     /// switched sides for the operands.
     AtArrowLeft,
+
+    /// `@@`
+    ///
+    /// Postgres-specific full-text search operator. Takes a `tsvector` and a `tsquery` (in either
+    /// order) and determines whether the `tsvector` matches the `tsquery`.
+    AtAt,
 }
 
 impl BinaryOperator {
@@ -330,6 +448,7 @@ impl Display for BinaryOperator {
             Self::HashArrow2 => "#>>",
             Self::AtArrowRight => "@>",
             Self::AtArrowLeft => "<@",
+            Self::AtAt => "@@",
         };
         f.write_str(op)
     }
@@ -352,6 +471,38 @@ impl Display for UnaryOperator {
     }
 }
 
+/// The unit of an [interval expression](Expr::Interval), eg the `DAY` in `INTERVAL 1 DAY`
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize, Deserialize, Arbitrary,
+)]
+pub enum IntervalUnit {
+    Microsecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl Display for IntervalUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntervalUnit::Microsecond => write!(f, "MICROSECOND"),
+            IntervalUnit::Second => write!(f, "SECOND"),
+            IntervalUnit::Minute => write!(f, "MINUTE"),
+            IntervalUnit::Hour => write!(f, "HOUR"),
+            IntervalUnit::Day => write!(f, "DAY"),
+            IntervalUnit::Week => write!(f, "WEEK"),
+            IntervalUnit::Month => write!(f, "MONTH"),
+            IntervalUnit::Quarter => write!(f, "QUARTER"),
+            IntervalUnit::Year => write!(f, "YEAR"),
+        }
+    }
+}
+
 /// Right-hand side of IN
 #[derive(
     Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Serialize, Deserialize, From, Arbitrary,
@@ -500,6 +651,15 @@ pub enum Expr {
 
     /// A variable reference
     Variable(Variable),
+
+    /// An interval: `INTERVAL <value> <unit>` in MySQL, or `INTERVAL '<value>'` in PostgreSQL,
+    /// where the value is a string containing both quantities and units (eg `INTERVAL '1 day 2
+    /// hours'`)
+    #[from(ignore)]
+    Interval {
+        value: Box<Expr>,
+        unit: Option<IntervalUnit>,
+    },
 }
 
 impl DialectDisplay for Expr {
@@ -632,6 +792,13 @@ impl DialectDisplay for Expr {
                 write!(f, ")")
             }
             Expr::Variable(var) => write!(f, "{}", var.display(dialect)),
+            Expr::Interval { value, unit } => {
+                write!(f, "INTERVAL {}", value.display(dialect))?;
+                if let Some(unit) = unit {
+                    write!(f, " {unit}")?;
+                }
+                Ok(())
+            }
         })
     }
 }
@@ -692,8 +859,15 @@ impl Arbitrary for Expr {
                         .prop_map(|(expr, distinct)| FunctionExpr::Sum { expr, distinct }),
                     box_expr.clone().prop_map(FunctionExpr::Max),
                     box_expr.clone().prop_map(FunctionExpr::Min),
+                    (box_expr.clone(), option::of(4u8..=16)).prop_map(|(expr, precision)| {
+                        FunctionExpr::ApproxCountDistinct { expr, precision }
+                    }),
                     (box_expr.clone(), any::<Option<String>>()).prop_map(|(expr, separator)| {
-                        FunctionExpr::GroupConcat { expr, separator }
+                        FunctionExpr::GroupConcat {
+                            expr,
+                            separator,
+                            order_by: None,
+                        }
                     }),
                     (
                         box_expr.clone(),
@@ -751,6 +925,8 @@ impl Arbitrary for Expr {
                     any::<bool>(),
                 )
                     .prop_map(|(lhs, rhs, negated)| Expr::In { lhs, rhs, negated }),
+                (box_expr.clone(), any::<Option<IntervalUnit>>())
+                    .prop_map(|(value, unit)| Expr::Interval { value, unit }),
                 (box_expr, any::<SqlType>(), any::<bool>()).prop_map(
                     |(expr, ty, postgres_style)| {
                         Expr::Cast {
@@ -882,6 +1058,7 @@ fn binary_operator_no_and_or(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Binar
             map(tag("#>"), |_| BinaryOperator::HashArrow1),
         )),
         map(tag("#-"), |_| BinaryOperator::HashSubtract),
+        map(tag("@@"), |_| BinaryOperator::AtAt),
     ))(i)
 }
 
@@ -960,7 +1137,7 @@ fn primary(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8
 
     move |i| {
         let (i, lhs) = primary_inner(dialect)(i)?;
-        let (i, cast) = opt(move |i| {
+        let (i, casts) = many0(move |i| {
             let (i, _) = whitespace0(i)?;
             let (i, _) = tag("::")(i)?;
             let (i, _) = whitespace0(i)?;
@@ -981,11 +1158,9 @@ fn primary(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8
             Ok((i, OpSuffix(op, suffix, Box::new(TokenTree::Group(rhs)))))
         })(i)?;
 
-        let lhs = if let Some(ty) = cast {
-            TokenTree::PgsqlCast(Box::new(lhs), ty)
-        } else {
-            lhs
-        };
+        let lhs = casts
+            .into_iter()
+            .fold(lhs, |lhs, ty| TokenTree::PgsqlCast(Box::new(lhs), ty));
         Ok((
             i,
             match suffix {
@@ -1112,6 +1287,7 @@ where
             Infix(HashArrow2) => Affix::Infix(Precedence(8), Associativity::Left),
             Infix(AtArrowRight) => Affix::Infix(Precedence(8), Associativity::Left),
             Infix(AtArrowLeft) => Affix::Infix(Precedence(8), Associativity::Left),
+            Infix(AtAt) => Affix::Infix(Precedence(8), Associativity::Left),
             Infix(HashSubtract) => Affix::Infix(Precedence(8), Associativity::Left),
 
             Infix(Like) => Affix::Infix(Precedence(7), Associativity::Right),
@@ -1522,6 +1698,46 @@ fn row_expr_implicit(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlR
     }
 }
 
+fn interval_unit(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], IntervalUnit> {
+    terminated(
+        alt((
+            value(IntervalUnit::Microsecond, tag_no_case("microsecond")),
+            value(IntervalUnit::Second, tag_no_case("second")),
+            value(IntervalUnit::Minute, tag_no_case("minute")),
+            value(IntervalUnit::Hour, tag_no_case("hour")),
+            value(IntervalUnit::Day, tag_no_case("day")),
+            value(IntervalUnit::Week, tag_no_case("week")),
+            value(IntervalUnit::Month, tag_no_case("month")),
+            value(IntervalUnit::Quarter, tag_no_case("quarter")),
+            value(IntervalUnit::Year, tag_no_case("year")),
+        )),
+        // Don't parse a unit if it's a prefix of a larger identifier
+        not(peek(satisfy(|c| is_sql_identifier(c as _)))),
+    )(i)
+}
+
+fn interval_expr(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Expr> {
+    move |i| {
+        let (i, _) = tag_no_case("interval")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, value) = alt((
+            parenthesized_expr(dialect),
+            map(function_expr(dialect), Expr::Call),
+            map(literal(dialect), Expr::Literal),
+            map(column_identifier_no_alias(dialect), Expr::Column),
+        ))(i)?;
+        let (i, unit) = opt(preceded(whitespace1, interval_unit))(i)?;
+
+        Ok((
+            i,
+            Expr::Interval {
+                value: Box::new(value),
+                unit,
+            },
+        ))
+    }
+}
+
 // Expressions without (binary or unary) operators
 pub(crate) fn simple_expr(
     dialect: Dialect,
@@ -1534,6 +1750,7 @@ pub(crate) fn simple_expr(
             between_expr(dialect),
             row_expr_explicit(dialect),
             row_expr_implicit(dialect),
+            interval_expr(dialect),
             map(function_expr(dialect), Expr::Call),
             map(literal(dialect), Expr::Literal),
             case_when_expr(dialect),
@@ -1563,7 +1780,7 @@ mod tests {
     use test_strategy::proptest;
 
     use super::*;
-    use crate::{to_nom_result, Relation};
+    use crate::{to_nom_result, FieldReference, ItemPlaceholder, OrderBy, OrderType, Relation};
 
     #[test]
     fn column_then_column() {
@@ -1718,6 +1935,36 @@ mod tests {
             );
         }
 
+        #[test]
+        fn postgres_chained_cast() {
+            let res = expression(Dialect::PostgreSQL)(LocatedSpan::new(b"'1'::text::int"));
+            assert_eq!(
+                res.unwrap().1,
+                Expr::Cast {
+                    expr: Box::new(Expr::Cast {
+                        expr: Box::new(Expr::Literal(Literal::String("1".into()))),
+                        ty: SqlType::Text,
+                        postgres_style: true,
+                    }),
+                    ty: SqlType::Int(None),
+                    postgres_style: true,
+                }
+            );
+        }
+
+        #[test]
+        fn postgres_cast_dollar_quoted_string() {
+            let res = expression(Dialect::PostgreSQL)(LocatedSpan::new(b"$$[1,2]$$::json"));
+            assert_eq!(
+                res.unwrap().1,
+                Expr::Cast {
+                    expr: Box::new(Expr::Literal(Literal::String("[1,2]".into()))),
+                    ty: SqlType::Json,
+                    postgres_style: true,
+                }
+            );
+        }
+
         #[test]
         fn mysql_cast() {
             let res = expression(Dialect::MySQL)(LocatedSpan::new(br#"CAST(-128 AS UNSIGNED)"#));
@@ -2356,6 +2603,65 @@ mod tests {
     mod mysql {
         use super::*;
 
+        #[test]
+        fn interval() {
+            let res = test_parse!(expression(Dialect::MySQL), b"now() - INTERVAL 7 day");
+            assert_eq!(
+                res,
+                Expr::BinaryOp {
+                    lhs: Box::new(Expr::Call(FunctionExpr::Call {
+                        name: "now".into(),
+                        arguments: vec![]
+                    })),
+                    op: BinaryOperator::Subtract,
+                    rhs: Box::new(Expr::Interval {
+                        value: Box::new(Expr::Literal(7.into())),
+                        unit: Some(IntervalUnit::Day)
+                    })
+                }
+            );
+            assert_eq!(
+                res.display(Dialect::MySQL).to_string(),
+                "(now() - INTERVAL 7 DAY)"
+            );
+        }
+
+        #[test]
+        fn date_add_interval() {
+            let res = test_parse!(expression(Dialect::MySQL), b"DATE_ADD(t, INTERVAL ? HOUR)");
+            assert_eq!(
+                res,
+                Expr::Call(FunctionExpr::Call {
+                    name: "DATE_ADD".into(),
+                    arguments: vec![
+                        Expr::Column("t".into()),
+                        Expr::Interval {
+                            value: Box::new(Expr::Literal(Literal::Placeholder(
+                                ItemPlaceholder::QuestionMark
+                            ))),
+                            unit: Some(IntervalUnit::Hour)
+                        }
+                    ]
+                })
+            );
+        }
+
+        #[test]
+        fn interval_unit_requires_word_boundary() {
+            let (rem, res) = to_nom_result(expression(Dialect::MySQL)(LocatedSpan::new(
+                b"INTERVAL 1 days",
+            )))
+            .unwrap();
+            assert_eq!(
+                res,
+                Expr::Interval {
+                    value: Box::new(Expr::Literal(1.into())),
+                    unit: None
+                }
+            );
+            assert_eq!(rem, b" days");
+        }
+
         #[test]
         fn column_beginning_with_null() {
             let res = test_parse!(expression(Dialect::MySQL), b"nullable");
@@ -2459,7 +2765,8 @@ mod tests {
             assert_eq!(
                 FunctionExpr::GroupConcat {
                     expr: Box::new(Expr::Column("x".into())),
-                    separator: Some("a".into())
+                    separator: Some("a".into()),
+                    order_by: None,
                 }
                 .display(Dialect::MySQL)
                 .to_string(),
@@ -2468,7 +2775,8 @@ mod tests {
             assert_eq!(
                 FunctionExpr::GroupConcat {
                     expr: Box::new(Expr::Column("x".into())),
-                    separator: Some("'".into())
+                    separator: Some("'".into()),
+                    order_by: None,
                 }
                 .display(Dialect::MySQL)
                 .to_string(),
@@ -2477,12 +2785,29 @@ mod tests {
             assert_eq!(
                 FunctionExpr::GroupConcat {
                     expr: Box::new(Expr::Column("x".into())),
-                    separator: None
+                    separator: None,
+                    order_by: None,
                 }
                 .display(Dialect::MySQL)
                 .to_string(),
                 "group_concat(`x`)"
-            )
+            );
+            assert_eq!(
+                FunctionExpr::GroupConcat {
+                    expr: Box::new(Expr::Column("x".into())),
+                    separator: Some(", ".into()),
+                    order_by: Some(OrderClause {
+                        order_by: vec![OrderBy {
+                            field: FieldReference::Expr(Expr::Column("y".into())),
+                            order_type: Some(OrderType::OrderDescending),
+                            null_order: None,
+                        }],
+                    }),
+                }
+                .display(Dialect::MySQL)
+                .to_string(),
+                "group_concat(`x` ORDER BY `y` DESC separator ', ')"
+            );
         }
 
         mod precedence {
@@ -2637,6 +2962,56 @@ mod tests {
     mod postgres {
         use super::*;
 
+        #[test]
+        fn interval() {
+            let res = test_parse!(
+                expression(Dialect::PostgreSQL),
+                b"created_at > NOW() - interval '1 day 2 hours'"
+            );
+            assert_eq!(
+                res,
+                Expr::BinaryOp {
+                    lhs: Box::new(Expr::Column("created_at".into())),
+                    op: BinaryOperator::Greater,
+                    rhs: Box::new(Expr::BinaryOp {
+                        lhs: Box::new(Expr::Call(FunctionExpr::Call {
+                            name: "NOW".into(),
+                            arguments: vec![]
+                        })),
+                        op: BinaryOperator::Subtract,
+                        rhs: Box::new(Expr::Interval {
+                            value: Box::new(Expr::Literal("1 day 2 hours".into())),
+                            unit: None
+                        })
+                    })
+                }
+            );
+            assert_eq!(
+                res.display(Dialect::PostgreSQL).to_string(),
+                "(\"created_at\" > (NOW() - INTERVAL '1 day 2 hours'))"
+            );
+        }
+
+        #[test]
+        fn display_string_agg() {
+            assert_eq!(
+                FunctionExpr::GroupConcat {
+                    expr: Box::new(Expr::Column("x".into())),
+                    separator: Some(", ".into()),
+                    order_by: Some(OrderClause {
+                        order_by: vec![OrderBy {
+                            field: FieldReference::Expr(Expr::Column("y".into())),
+                            order_type: None,
+                            null_order: None,
+                        }],
+                    }),
+                }
+                .display(Dialect::PostgreSQL)
+                .to_string(),
+                "string_agg(\"x\", ', ' ORDER BY \"y\")"
+            );
+        }
+
         #[test]
         fn column_beginning_with_null() {
             let res = test_parse!(expression(Dialect::PostgreSQL), b"nullable");
@@ -3010,6 +3385,32 @@ mod tests {
                 );
             }
 
+            #[test]
+            fn at_at_operator() {
+                let cond = b"to_tsvector('simple', body) @@ to_tsquery('simple', 'cat & dog')";
+                let res = test_parse!(expression(Dialect::PostgreSQL), cond);
+                assert_eq!(
+                    res,
+                    Expr::BinaryOp {
+                        lhs: Box::new(Expr::Call(FunctionExpr::Call {
+                            name: "to_tsvector".into(),
+                            arguments: vec![
+                                Expr::Literal("simple".into()),
+                                Expr::Column("body".into()),
+                            ],
+                        })),
+                        op: BinaryOperator::AtAt,
+                        rhs: Box::new(Expr::Call(FunctionExpr::Call {
+                            name: "to_tsquery".into(),
+                            arguments: vec![
+                                Expr::Literal("simple".into()),
+                                Expr::Literal("cat & dog".into()),
+                            ],
+                        })),
+                    }
+                );
+            }
+
             #[test]
             fn complex_bracketing() {
                 let cond = "\"read_ribbons\".\"is_following\" = 1 \
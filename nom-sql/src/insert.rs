@@ -1,8 +1,9 @@
 use std::{fmt, str};
 
 use itertools::Itertools;
+use nom::branch::alt;
 use nom::bytes::complete::{tag, tag_no_case};
-use nom::combinator::opt;
+use nom::combinator::{map, opt};
 use nom::multi::separated_list1;
 use nom::sequence::{delimited, preceded, terminated, tuple};
 use nom_locate::LocatedSpan;
@@ -14,6 +15,7 @@ use crate::column::Column;
 use crate::common::{
     assignment_expr_list, field_list, statement_terminator, value_list, ws_sep_comma,
 };
+use crate::select::{nested_selection, SelectStatement};
 use crate::table::{relation, Relation};
 use crate::whitespace::{whitespace0, whitespace1};
 use crate::{Dialect, DialectDisplay, Expr, NomSqlResult};
@@ -23,6 +25,10 @@ pub struct InsertStatement {
     pub table: Relation,
     pub fields: Option<Vec<Column>>,
     pub data: Vec<Vec<Expr>>,
+    /// The query whose results should be inserted, for `INSERT ... SELECT` statements.
+    ///
+    /// If this is set, `data` will be empty.
+    pub select: Option<Box<SelectStatement>>,
     pub ignore: bool,
     pub on_duplicate: Option<Vec<(Column, Expr)>>,
 }
@@ -43,6 +49,10 @@ impl DialectDisplay for InsertStatement {
                 )?;
             }
 
+            if let Some(select) = &self.select {
+                return write!(f, " {}", select.display(dialect));
+            }
+
             write!(
                 f,
                 " VALUES {}",
@@ -89,15 +99,75 @@ fn on_duplicate(
     }
 }
 
+/// The action to take for rows which conflict with existing rows, specified either with MySQL's
+/// `ON DUPLICATE KEY UPDATE` or PostgreSQL's `ON CONFLICT`
+enum ConflictAction {
+    DoNothing,
+    DoUpdate(Vec<(Column, Expr)>),
+}
+
+/// Parse a PostgreSQL `ON CONFLICT` clause. The conflict target, if any, is ignored.
+fn on_conflict(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], ConflictAction> {
+    move |i| {
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag_no_case("on")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, _) = tag_no_case("conflict")(i)?;
+        let (i, _) = opt(preceded(
+            whitespace0,
+            alt((
+                map(
+                    delimited(
+                        terminated(tag("("), whitespace0),
+                        field_list(dialect),
+                        preceded(whitespace0, tag(")")),
+                    ),
+                    |_| (),
+                ),
+                map(
+                    tuple((
+                        tag_no_case("on"),
+                        whitespace1,
+                        tag_no_case("constraint"),
+                        whitespace1,
+                        dialect.identifier(),
+                    )),
+                    |_| (),
+                ),
+            )),
+        ))(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, _) = tag_no_case("do")(i)?;
+        let (i, _) = whitespace1(i)?;
+        alt((
+            map(tag_no_case("nothing"), |_| ConflictAction::DoNothing),
+            map(
+                preceded(
+                    tuple((
+                        tag_no_case("update"),
+                        whitespace1,
+                        tag_no_case("set"),
+                        whitespace1,
+                    )),
+                    assignment_expr_list(dialect),
+                ),
+                ConflictAction::DoUpdate,
+            ),
+        ))(i)
+    }
+}
+
 // Parse rule for a SQL insert query.
-// TODO(malte): support REPLACE, nested selection, DEFAULT VALUES
+// TODO(malte): support REPLACE, DEFAULT VALUES
 pub fn insertion(
     dialect: Dialect,
 ) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], InsertStatement> {
     move |i| {
         let (
             remaining_input,
-            (_, ignore_res, _, _, _, table, _, fields, _, _, data, on_duplicate, _),
+            (_, ignore_res, _, _, _, table, _, fields, (data, select), conflict_action, _),
         ) = tuple((
             tag_no_case("insert"),
             opt(preceded(whitespace1, tag_no_case("ignore"))),
@@ -107,13 +177,31 @@ pub fn insertion(
             relation(dialect),
             whitespace0,
             opt(fields(dialect)),
-            tag_no_case("values"),
-            whitespace0,
-            separated_list1(ws_sep_comma, data(dialect)),
-            opt(on_duplicate(dialect)),
+            alt((
+                map(
+                    preceded(
+                        terminated(tag_no_case("values"), whitespace0),
+                        separated_list1(ws_sep_comma, data(dialect)),
+                    ),
+                    |data| (data, None),
+                ),
+                map(nested_selection(dialect), |select| {
+                    (vec![], Some(Box::new(select)))
+                }),
+            )),
+            opt(alt((
+                map(on_duplicate(dialect), ConflictAction::DoUpdate),
+                on_conflict(dialect),
+            ))),
             statement_terminator,
         ))(i)?;
-        let ignore = ignore_res.is_some();
+        let (ignore, on_duplicate) = match conflict_action {
+            None => (ignore_res.is_some(), None),
+            Some(ConflictAction::DoNothing) => (true, None),
+            Some(ConflictAction::DoUpdate(assignments)) => {
+                (ignore_res.is_some(), Some(assignments))
+            }
+        };
 
         Ok((
             remaining_input,
@@ -121,6 +209,7 @@ pub fn insertion(
                 table,
                 fields,
                 data,
+                select,
                 ignore,
                 on_duplicate,
             },
@@ -151,7 +240,8 @@ mod tests {
                     Expr::Literal(Literal::Placeholder(ItemPlaceholder::QuestionMark))
                 ]],
                 on_duplicate: None,
-                ignore: false
+                ignore: false,
+                select: None,
             }
         );
     }
@@ -175,7 +265,8 @@ mod tests {
                     fields: None,
                     data: vec![vec![Expr::Literal(42.into()), Expr::Literal("test".into())]],
                     on_duplicate: None,
-                    ignore: false
+                    ignore: false,
+                    select: None,
                 }
             );
         }
@@ -202,7 +293,8 @@ mod tests {
                         }),
                     ],],
                     on_duplicate: None,
-                    ignore: false
+                    ignore: false,
+                    select: None,
                 }
             );
         }
@@ -219,7 +311,8 @@ mod tests {
                     fields: Some(vec![Column::from("id"), Column::from("name")]),
                     data: vec![vec![Expr::Literal(42.into()), Expr::Literal("test".into())]],
                     on_duplicate: None,
-                    ignore: false
+                    ignore: false,
+                    select: None,
                 }
             );
         }
@@ -237,7 +330,8 @@ mod tests {
                     fields: Some(vec![Column::from("id"), Column::from("name")]),
                     data: vec![vec![Expr::Literal(42.into()), Expr::Literal("test".into())]],
                     on_duplicate: None,
-                    ignore: false
+                    ignore: false,
+                    select: None,
                 }
             );
         }
@@ -258,6 +352,7 @@ mod tests {
                     data: vec![vec![Expr::Literal(42.into()), Expr::Literal("test".into())]],
                     on_duplicate: None,
                     ignore: false,
+                    select: None,
                 }
             );
         }
@@ -278,6 +373,7 @@ mod tests {
                     ],
                     on_duplicate: None,
                     ignore: false,
+                    select: None,
                 }
             );
         }
@@ -306,6 +402,7 @@ mod tests {
                         },
                     )]),
                     ignore: false,
+                    select: None,
                 }
             );
         }
@@ -323,6 +420,7 @@ mod tests {
                     data: vec![vec![Expr::Literal(42.into()), Expr::Literal("test".into())]],
                     on_duplicate: None,
                     ignore: false,
+                    select: None,
                 }
             );
         }
@@ -335,6 +433,37 @@ mod tests {
             let parsed_again = test_parse!(insertion(Dialect::MySQL), stringified.as_bytes());
             assert_eq!(parsed, parsed_again);
         }
+
+        #[test]
+        fn insert_select() {
+            let res = test_parse!(
+                insertion(Dialect::MySQL),
+                b"INSERT INTO users (id, name) SELECT id, name FROM old_users WHERE id > 10;"
+            );
+            assert_eq!(
+                res,
+                InsertStatement {
+                    table: Relation::from("users"),
+                    fields: Some(vec![Column::from("id"), Column::from("name")]),
+                    data: vec![],
+                    select: Some(Box::new(test_parse!(
+                        nested_selection(Dialect::MySQL),
+                        b"SELECT id, name FROM old_users WHERE id > 10"
+                    ))),
+                    ignore: false,
+                    on_duplicate: None,
+                }
+            );
+        }
+
+        #[test]
+        fn stringify_insert_select() {
+            let orig = b"INSERT INTO users (`id`, `key`) SELECT id, `key` FROM old_users;";
+            let parsed = test_parse!(insertion(Dialect::MySQL), orig);
+            let stringified = parsed.display(Dialect::MySQL).to_string();
+            let parsed_again = test_parse!(insertion(Dialect::MySQL), stringified.as_bytes());
+            assert_eq!(parsed, parsed_again);
+        }
     }
 
     mod postgres {
@@ -356,6 +485,7 @@ mod tests {
                     data: vec![vec![Expr::Literal(42.into()), Expr::Literal("test".into())]],
                     on_duplicate: None,
                     ignore: false,
+                    select: None,
                 }
             );
         }
@@ -381,6 +511,7 @@ mod tests {
                     ],],
                     on_duplicate: None,
                     ignore: false,
+                    select: None,
                 }
             );
         }
@@ -398,6 +529,7 @@ mod tests {
                     data: vec![vec![Expr::Literal(42.into()), Expr::Literal("test".into())]],
                     on_duplicate: None,
                     ignore: false,
+                    select: None,
                 }
             );
         }
@@ -416,6 +548,7 @@ mod tests {
                     data: vec![vec![Expr::Literal(42.into()), Expr::Literal("test".into())]],
                     on_duplicate: None,
                     ignore: false,
+                    select: None,
                 }
             );
         }
@@ -436,6 +569,7 @@ mod tests {
                     data: vec![vec![Expr::Literal(42.into()), Expr::Literal("test".into())]],
                     on_duplicate: None,
                     ignore: false,
+                    select: None,
                 }
             );
         }
@@ -455,7 +589,8 @@ mod tests {
                         vec![Expr::Literal(21.into()), Expr::Literal("test2".into())],
                     ],
                     ignore: false,
-                    on_duplicate: None
+                    on_duplicate: None,
+                    select: None,
                 }
             );
         }
@@ -483,7 +618,8 @@ mod tests {
                             rhs: Box::new(Expr::Literal(1.into()))
                         },
                     ),]),
-                    ignore: false
+                    ignore: false,
+                    select: None,
                 }
             );
         }
@@ -500,7 +636,83 @@ mod tests {
                     fields: Some(vec![Column::from("id"), Column::from("name")]),
                     data: vec![vec![Expr::Literal(42.into()), Expr::Literal("test".into())]],
                     ignore: false,
-                    on_duplicate: None
+                    on_duplicate: None,
+                    select: None,
+                }
+            );
+        }
+
+        #[test]
+        fn insert_select() {
+            let res = test_parse!(
+                insertion(Dialect::PostgreSQL),
+                b"INSERT INTO users SELECT id, name FROM old_users;"
+            );
+            assert_eq!(
+                res,
+                InsertStatement {
+                    table: Relation::from("users"),
+                    fields: None,
+                    data: vec![],
+                    select: Some(Box::new(test_parse!(
+                        nested_selection(Dialect::PostgreSQL),
+                        b"SELECT id, name FROM old_users"
+                    ))),
+                    ignore: false,
+                    on_duplicate: None,
+                }
+            );
+        }
+
+        #[test]
+        fn insert_on_conflict_do_nothing() {
+            for qstring in [
+                "INSERT INTO users (id, name) VALUES (42, 'test') ON CONFLICT DO NOTHING",
+                "INSERT INTO users (id, name) VALUES (42, 'test') ON CONFLICT (id) DO NOTHING",
+                "INSERT INTO users (id, name) VALUES (42, 'test') \
+                 ON CONFLICT ON CONSTRAINT users_pkey DO NOTHING",
+            ] {
+                let res = test_parse!(insertion(Dialect::PostgreSQL), qstring.as_bytes());
+                assert_eq!(
+                    res,
+                    InsertStatement {
+                        table: Relation::from("users"),
+                        fields: Some(vec![Column::from("id"), Column::from("name")]),
+                        data: vec![vec![Expr::Literal(42.into()), Expr::Literal("test".into())]],
+                        ignore: true,
+                        on_duplicate: None,
+                        select: None,
+                    }
+                );
+            }
+        }
+
+        #[test]
+        fn insert_on_conflict_do_update() {
+            let res = test_parse!(
+                insertion(Dialect::PostgreSQL),
+                b"INSERT INTO counters (id, hits) VALUES ($1, $2) \
+                  ON CONFLICT (id) DO UPDATE SET hits = counters.hits + 1;"
+            );
+            assert_eq!(
+                res,
+                InsertStatement {
+                    table: Relation::from("counters"),
+                    fields: Some(vec![Column::from("id"), Column::from("hits")]),
+                    data: vec![vec![
+                        Expr::Literal(Literal::Placeholder(ItemPlaceholder::DollarNumber(1))),
+                        Expr::Literal(Literal::Placeholder(ItemPlaceholder::DollarNumber(2)))
+                    ]],
+                    on_duplicate: Some(vec![(
+                        Column::from("hits"),
+                        Expr::BinaryOp {
+                            op: BinaryOperator::Add,
+                            lhs: Box::new(Expr::Column(Column::from("counters.hits"))),
+                            rhs: Box::new(Expr::Literal(1.into()))
+                        },
+                    )]),
+                    ignore: false,
+                    select: None,
                 }
             );
         }
@@ -21,20 +21,21 @@ pub use self::comment::CommentStatement;
 pub use self::common::{FieldDefinitionExpr, FieldReference, IndexType, TableKey};
 pub use self::compound_select::{CompoundSelectOperator, CompoundSelectStatement};
 pub use self::create::{
-    CacheInner, CreateCacheStatement, CreateTableBody, CreateTableStatement, CreateViewStatement,
-    SelectSpecification,
+    CacheInner, CreateCacheStatement, CreateIndexStatement, CreateTableBody, CreateTableStatement,
+    CreateViewStatement, FrontierStrategy, SelectSpecification,
 };
 pub use self::create_table_options::CreateTableOption;
 pub use self::deallocate::{DeallocateStatement, StatementIdentifier};
 pub use self::delete::DeleteStatement;
 pub use self::dialect::{Dialect, DialectDisplay};
 pub use self::drop::{
-    DropAllCachesStatement, DropAllProxiedQueriesStatement, DropCacheStatement, DropTableStatement,
-    DropViewStatement,
+    DropAllCachesStatement, DropAllProxiedQueriesStatement, DropCacheStatement, DropIndexStatement,
+    DropTableStatement, DropViewStatement,
 };
 pub use self::explain::ExplainStatement;
 pub use self::expression::{
-    BinaryOperator, CaseWhenBranch, Expr, FunctionExpr, InValue, UnaryOperator,
+    BinaryOperator, CaseWhenBranch, Expr, FunctionExpr, InValue, IntervalUnit, RankFunction,
+    UnaryOperator,
 };
 pub use self::insert::InsertStatement;
 pub use self::join::{JoinConstraint, JoinOperator, JoinRightSide};
@@ -54,12 +55,12 @@ pub use self::set::{
 };
 pub use self::show::ShowStatement;
 pub use self::sql_identifier::SqlIdentifier;
-pub use self::sql_type::{EnumVariants, SqlType, SqlTypeArbitraryOptions};
+pub use self::sql_type::{EnumVariants, GeometryType, SqlType, SqlTypeArbitraryOptions};
 pub use self::table::{
     replicator_table_list, NonReplicatedRelation, NotReplicatedReason, Relation, TableExpr,
     TableExprInner,
 };
-pub use self::transaction::StartTransactionStatement;
+pub use self::transaction::{IsolationLevel, StartTransactionStatement, TransactionMode};
 pub use self::update::UpdateStatement;
 pub use self::use_statement::UseStatement;
 
@@ -7,7 +7,7 @@ use bit_vec::BitVec;
 use eui48::{MacAddress, MacAddressFormat};
 use itertools::Itertools;
 use nom::branch::alt;
-use nom::bytes::complete::{is_not, tag, tag_no_case, take};
+use nom::bytes::complete::{is_not, tag, tag_no_case, take, take_until, take_while};
 use nom::character::complete::{char, digit0, digit1, satisfy};
 use nom::combinator::{map, map_parser, map_res, not, opt, peek, recognize};
 use nom::error::ErrorKind;
@@ -378,6 +378,7 @@ impl Literal {
             SqlType::Serial => any::<i32>().prop_map(Self::from).boxed(),
             SqlType::BigSerial => any::<i64>().prop_map(Self::from).boxed(),
             SqlType::Array(_) => unimplemented!("Arrays aren't implemented yet"),
+            SqlType::Geometry(_) => unimplemented!("Geometry types aren't implemented yet"),
             SqlType::Other(ty) => {
                 unimplemented!("Other({}) isn't implemented yet", ty.display_unquoted())
             }
@@ -504,6 +505,24 @@ fn raw_string_double_quoted(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Vec<u8
     raw_string_quoted(b"\"", b"\\\"")(i)
 }
 
+/// Parse the content of a PostgreSQL [dollar-quoted][] string constant, such as `$$foo$$` or
+/// `$tag$foo$tag$`. No escape sequences are processed within dollar-quoted strings.
+///
+/// [dollar-quoted]: https://www.postgresql.org/docs/current/sql-syntax-lexical.html#SQL-SYNTAX-DOLLAR-QUOTING
+pub(crate) fn raw_dollar_quoted_string(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Vec<u8>> {
+    let (i, delimiter) = recognize(tuple((
+        tag("$"),
+        opt(pair(
+            satisfy(|c| c.is_ascii_alphabetic() || c == '_'),
+            take_while(|c: u8| c.is_ascii_alphanumeric() || c == b'_'),
+        )),
+        tag("$"),
+    )))(i)?;
+    let (i, content) = take_until(*delimiter)(i)?;
+    let (i, _) = tag(*delimiter)(i)?;
+    Ok((i, content.to_vec()))
+}
+
 /// Specification for how string literals may be quoted
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum QuotingStyle {
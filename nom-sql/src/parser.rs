@@ -13,14 +13,15 @@ use crate::comment::{comment, CommentStatement};
 use crate::common::statement_terminator;
 use crate::compound_select::{simple_or_compound_selection, CompoundSelectStatement};
 use crate::create::{
-    create_cached_query, create_table, key_specification, view_creation, CreateCacheStatement,
-    CreateTableStatement, CreateViewStatement,
+    create_cached_query, create_index, create_table, key_specification, view_creation,
+    CreateCacheStatement, CreateIndexStatement, CreateTableStatement, CreateViewStatement,
 };
 use crate::deallocate::{deallocate, DeallocateStatement};
 use crate::delete::{deletion, DeleteStatement};
 use crate::drop::{
-    drop_all_caches, drop_all_proxied_queries, drop_cached_query, drop_table, drop_view,
-    DropAllProxiedQueriesStatement, DropCacheStatement, DropTableStatement, DropViewStatement,
+    drop_all_caches, drop_all_proxied_queries, drop_cached_query, drop_index, drop_table,
+    drop_view, DropAllProxiedQueriesStatement, DropCacheStatement, DropIndexStatement,
+    DropTableStatement, DropViewStatement,
 };
 use crate::explain::{explain_statement, ExplainStatement};
 use crate::expression::expression;
@@ -48,6 +49,7 @@ pub enum SqlQuery {
     CreateTable(CreateTableStatement),
     CreateView(CreateViewStatement),
     CreateCache(CreateCacheStatement),
+    CreateIndex(CreateIndexStatement),
     DropCache(DropCacheStatement),
     DropAllCaches(DropAllCachesStatement),
     DropAllProxiedQueries(DropAllProxiedQueriesStatement),
@@ -58,6 +60,7 @@ pub enum SqlQuery {
     Delete(DeleteStatement),
     DropTable(DropTableStatement),
     DropView(DropViewStatement),
+    DropIndex(DropIndexStatement),
     Update(UpdateStatement),
     Set(SetStatement),
     StartTransaction(StartTransactionStatement),
@@ -79,11 +82,13 @@ impl DialectDisplay for SqlQuery {
             Self::CreateTable(create) => write!(f, "{}", create.display(dialect)),
             Self::CreateView(create) => write!(f, "{}", create.display(dialect)),
             Self::CreateCache(create) => write!(f, "{}", create.display(dialect)),
+            Self::CreateIndex(create) => write!(f, "{}", create.display(dialect)),
             Self::DropCache(drop) => write!(f, "{}", drop.display(dialect)),
             Self::DropAllCaches(drop) => write!(f, "{}", drop),
             Self::Delete(delete) => write!(f, "{}", delete.display(dialect)),
             Self::DropTable(drop) => write!(f, "{}", drop.display(dialect)),
             Self::DropView(drop) => write!(f, "{}", drop.display(dialect)),
+            Self::DropIndex(drop) => write!(f, "{}", drop.display(dialect)),
             Self::Update(update) => write!(f, "{}", update.display(dialect)),
             Self::Set(set) => write!(f, "{}", set.display(dialect)),
             Self::AlterTable(alter) => write!(f, "{}", alter.display(dialect)),
@@ -128,12 +133,14 @@ impl SqlQuery {
             Self::CreateTable(_) => "CREATE TABLE",
             Self::CreateView(_) => "CREATE VIEW",
             Self::CreateCache(_) => "CREATE CACHE",
+            Self::CreateIndex(_) => "CREATE INDEX",
             Self::DropCache(_) => "DROP CACHE",
             Self::DropAllCaches(_) => "DROP ALL CACHES",
             Self::DropAllProxiedQueries(_) => "DROP ALL PROXIED QUERIES",
             Self::Delete(_) => "DELETE",
             Self::DropTable(_) => "DROP TABLE",
             Self::DropView(_) => "DROP VIEW",
+            Self::DropIndex(_) => "DROP INDEX",
             Self::Update(_) => "UPDATE",
             Self::Set(_) => "SET",
             Self::AlterTable(_) => "ALTER TABLE",
@@ -184,6 +191,8 @@ impl SqlQuery {
             | SqlQuery::Delete(_)
             | SqlQuery::DropTable(_)
             | SqlQuery::DropView(_)
+            | SqlQuery::CreateIndex(_)
+            | SqlQuery::DropIndex(_)
             | SqlQuery::Update(_)
             | SqlQuery::Set(_)
             | SqlQuery::StartTransaction(_)
@@ -247,6 +256,8 @@ fn sql_query_part2(
             // This does a more expensive clone of `i`, so process it last.
             map(create_cached_query(dialect), SqlQuery::CreateCache),
             map(comment(dialect), SqlQuery::Comment),
+            map(create_index(dialect), SqlQuery::CreateIndex),
+            map(drop_index(dialect), SqlQuery::DropIndex),
         ))(i)
     }
 }
@@ -319,6 +330,17 @@ mod tests {
         assert_eq!(res, SqlQuery::DropAllCaches(DropAllCachesStatement {}));
     }
 
+    #[test]
+    fn create_and_drop_index() {
+        let res = parse_query(Dialect::MySQL, "CREATE INDEX idx ON t (a, b);").unwrap();
+        assert!(matches!(res, SqlQuery::CreateIndex(_)));
+        assert_eq!(res.query_type(), "CREATE INDEX");
+
+        let res = parse_query(Dialect::PostgreSQL, "DROP INDEX idx").unwrap();
+        assert!(matches!(res, SqlQuery::DropIndex(_)));
+        assert_eq!(res.query_type(), "DROP INDEX");
+    }
+
     mod mysql {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
@@ -359,6 +381,7 @@ mod tests {
                 data: vec![vec![Expr::Literal(42.into()), Expr::Literal("test".into())]],
                 ignore: false,
                 on_duplicate: None,
+                select: None,
             });
             let mut h0 = DefaultHasher::new();
             let mut h1 = DefaultHasher::new();
@@ -567,6 +590,7 @@ mod tests {
                 data: vec![vec![Expr::Literal(42.into()), Expr::Literal("test".into())]],
                 ignore: false,
                 on_duplicate: None,
+                select: None,
             });
             let mut h0 = DefaultHasher::new();
             let mut h1 = DefaultHasher::new();
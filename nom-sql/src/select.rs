@@ -301,6 +301,7 @@ impl SelectStatement {
                         | FunctionExpr::Sum { .. }
                         | FunctionExpr::Max(_)
                         | FunctionExpr::Min(_)
+                        | FunctionExpr::ApproxCountDistinct { .. }
                         | FunctionExpr::GroupConcat { .. }
                 ),
                 Expr::NestedSelect(select) => select.contains_aggregate_select(),
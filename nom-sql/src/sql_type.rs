@@ -19,6 +19,7 @@ use proptest::sample::SizeRange;
 use proptest::strategy::{BoxedStrategy, Strategy};
 use readyset_util::fmt::fmt_with;
 use serde::{Deserialize, Serialize};
+use test_strategy::Arbitrary;
 use triomphe::ThinArc;
 
 use crate::common::{ws_sep_comma, Sign};
@@ -63,6 +64,38 @@ impl fmt::Display for IntervalFields {
     }
 }
 
+/// The kind of value stored in a column of one of MySQL's [spatial data types][mysql-spatial]
+///
+/// [mysql-spatial]: https://dev.mysql.com/doc/refman/8.0/en/spatial-type-overview.html
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize, Deserialize, Arbitrary,
+)]
+pub enum GeometryType {
+    Geometry,
+    Point,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    GeometryCollection,
+}
+
+impl fmt::Display for GeometryType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeometryType::Geometry => write!(f, "GEOMETRY"),
+            GeometryType::Point => write!(f, "POINT"),
+            GeometryType::LineString => write!(f, "LINESTRING"),
+            GeometryType::Polygon => write!(f, "POLYGON"),
+            GeometryType::MultiPoint => write!(f, "MULTIPOINT"),
+            GeometryType::MultiLineString => write!(f, "MULTILINESTRING"),
+            GeometryType::MultiPolygon => write!(f, "MULTIPOLYGON"),
+            GeometryType::GeometryCollection => write!(f, "GEOMETRYCOLLECTION"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum SqlType {
     Bool,
@@ -118,6 +151,8 @@ pub enum SqlType {
     Serial,
     BigSerial,
     Array(Box<SqlType>),
+    /// A MySQL spatial type
+    Geometry(GeometryType),
 
     /// Any other named type
     Other(Relation),
@@ -232,6 +267,7 @@ impl Arbitrary for SqlType {
                 Just(MediumText).boxed(),
                 Just(LongText).boxed(),
                 option::of(1..=6u16).prop_map(DateTime).boxed(),
+                any::<GeometryType>().prop_map(Geometry).boxed(),
             ]);
         }
 
@@ -412,6 +448,7 @@ impl DialectDisplay for SqlType {
                 SqlType::Serial => write!(f, "SERIAL"),
                 SqlType::BigSerial => write!(f, "BIGSERIAL"),
                 SqlType::Array(ref t) => write!(f, "{}[]", t.display(dialect)),
+                SqlType::Geometry(ty) => write!(f, "{ty}"),
                 SqlType::Other(ref t) => write!(f, "{}", t.display(dialect)),
             }
         })
@@ -943,11 +980,45 @@ fn type_identifier_part3(
             map(tag_no_case("bigserial"), |_| SqlType::BigSerial),
             map(tag_no_case("citext"), |_| SqlType::Citext),
             map(tag("\"char\""), |_| SqlType::QuotedChar),
+            map(geometry_type(dialect), SqlType::Geometry),
             map(other_type(dialect), SqlType::Other),
         ))(i)
     }
 }
 
+/// Parse one of MySQL's spatial data types. PostgreSQL's geometric types are distinct from these,
+/// and parse as [`SqlType::Other`].
+fn geometry_type(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], GeometryType> {
+    move |i| match dialect {
+        Dialect::MySQL => alt((
+            // Longer names need to come first, since they share prefixes with shorter ones
+            value(
+                GeometryType::GeometryCollection,
+                alt((
+                    tag_no_case("geometrycollection"),
+                    tag_no_case("geomcollection"),
+                )),
+            ),
+            value(GeometryType::Geometry, tag_no_case("geometry")),
+            value(
+                GeometryType::MultiLineString,
+                tag_no_case("multilinestring"),
+            ),
+            value(GeometryType::MultiPolygon, tag_no_case("multipolygon")),
+            value(GeometryType::MultiPoint, tag_no_case("multipoint")),
+            value(GeometryType::LineString, tag_no_case("linestring")),
+            value(GeometryType::Polygon, tag_no_case("polygon")),
+            value(GeometryType::Point, tag_no_case("point")),
+        ))(i),
+        Dialect::PostgreSQL => Err(nom::Err::Error(ParseError::from_error_kind(
+            i,
+            ErrorKind::IsNot,
+        ))),
+    }
+}
+
 fn other_type(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Relation> {
     move |i| match dialect {
         Dialect::PostgreSQL => relation(dialect)(i),
@@ -1139,6 +1210,29 @@ mod tests {
     mod mysql {
         use super::*;
 
+        #[test]
+        fn geometry_types() {
+            for (input, ty) in [
+                ("geometry", GeometryType::Geometry),
+                ("POINT", GeometryType::Point),
+                ("linestring", GeometryType::LineString),
+                ("polygon", GeometryType::Polygon),
+                ("multipoint", GeometryType::MultiPoint),
+                ("multilinestring", GeometryType::MultiLineString),
+                ("multipolygon", GeometryType::MultiPolygon),
+                ("geometrycollection", GeometryType::GeometryCollection),
+                ("geomcollection", GeometryType::GeometryCollection),
+            ] {
+                let res = test_parse!(type_identifier(Dialect::MySQL), input.as_bytes());
+                assert_eq!(res, SqlType::Geometry(ty));
+                assert_eq!(
+                    res.display(Dialect::MySQL).to_string(),
+                    ty.to_string(),
+                    "{input}"
+                );
+            }
+        }
+
         #[test]
         fn double_with_lens() {
             let qs = b"double(16,12)";
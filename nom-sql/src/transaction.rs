@@ -1,31 +1,92 @@
 use std::fmt;
 
+use itertools::Itertools;
 use nom::branch::alt;
 use nom::bytes::complete::tag_no_case;
-use nom::combinator::{map, opt};
-use nom::sequence::tuple;
+use nom::combinator::{map, opt, value};
+use nom::multi::separated_list1;
+use nom::sequence::{preceded, tuple};
 use nom_locate::LocatedSpan;
 use serde::{Deserialize, Serialize};
 use test_strategy::Arbitrary;
 
-use crate::common::statement_terminator;
+use crate::common::{statement_terminator, ws_sep_comma};
 use crate::whitespace::{whitespace0, whitespace1};
 use crate::{Dialect, NomSqlResult};
 
+/// The isolation level of a transaction
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl fmt::Display for IsolationLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IsolationLevel::ReadUncommitted => write!(f, "READ UNCOMMITTED"),
+            IsolationLevel::ReadCommitted => write!(f, "READ COMMITTED"),
+            IsolationLevel::RepeatableRead => write!(f, "REPEATABLE READ"),
+            IsolationLevel::Serializable => write!(f, "SERIALIZABLE"),
+        }
+    }
+}
+
+/// A characteristic of a transaction, given when starting it
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub enum TransactionMode {
+    /// `ISOLATION LEVEL <level>` (PostgreSQL only)
+    IsolationLevel(IsolationLevel),
+    /// `READ WRITE`
+    ReadWrite,
+    /// `READ ONLY`
+    ReadOnly,
+    /// `DEFERRABLE` or `NOT DEFERRABLE` (PostgreSQL only)
+    Deferrable(bool),
+    /// `WITH CONSISTENT SNAPSHOT` (MySQL only)
+    WithConsistentSnapshot,
+}
+
+impl fmt::Display for TransactionMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransactionMode::IsolationLevel(level) => write!(f, "ISOLATION LEVEL {level}"),
+            TransactionMode::ReadWrite => write!(f, "READ WRITE"),
+            TransactionMode::ReadOnly => write!(f, "READ ONLY"),
+            TransactionMode::Deferrable(true) => write!(f, "DEFERRABLE"),
+            TransactionMode::Deferrable(false) => write!(f, "NOT DEFERRABLE"),
+            TransactionMode::WithConsistentSnapshot => write!(f, "WITH CONSISTENT SNAPSHOT"),
+        }
+    }
+}
+
 // TODO(peter): Handle dialect differences.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize, Arbitrary)]
 pub enum StartTransactionStatement {
-    // TODO(ENG-2992): Implement optional fields for START TRANSACTION
-    Start,
-    Begin,
+    /// `START TRANSACTION [mode, ...]`
+    Start(Vec<TransactionMode>),
+    /// `BEGIN [mode, ...]` (modes are only supported by PostgreSQL)
+    Begin(Vec<TransactionMode>),
 }
 
 impl fmt::Display for StartTransactionStatement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            StartTransactionStatement::Start => write!(f, "START TRANSACTION"),
-            StartTransactionStatement::Begin => write!(f, "BEGIN"),
+        let modes = match self {
+            StartTransactionStatement::Start(modes) => {
+                write!(f, "START TRANSACTION")?;
+                modes
+            }
+            StartTransactionStatement::Begin(modes) => {
+                write!(f, "BEGIN")?;
+                modes
+            }
+        };
+        if !modes.is_empty() {
+            write!(f, " {}", modes.iter().join(", "))?;
         }
+        Ok(())
     }
 }
 
@@ -47,6 +108,90 @@ impl fmt::Display for RollbackStatement {
     }
 }
 
+fn isolation_level(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], IsolationLevel> {
+    alt((
+        value(
+            IsolationLevel::ReadUncommitted,
+            tuple((tag_no_case("read"), whitespace1, tag_no_case("uncommitted"))),
+        ),
+        value(
+            IsolationLevel::ReadCommitted,
+            tuple((tag_no_case("read"), whitespace1, tag_no_case("committed"))),
+        ),
+        value(
+            IsolationLevel::RepeatableRead,
+            tuple((tag_no_case("repeatable"), whitespace1, tag_no_case("read"))),
+        ),
+        value(IsolationLevel::Serializable, tag_no_case("serializable")),
+    ))(i)
+}
+
+fn read_mode(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], TransactionMode> {
+    preceded(
+        tuple((tag_no_case("read"), whitespace1)),
+        alt((
+            value(TransactionMode::ReadWrite, tag_no_case("write")),
+            value(TransactionMode::ReadOnly, tag_no_case("only")),
+        )),
+    )(i)
+}
+
+fn transaction_mode(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], TransactionMode> {
+    move |i| match dialect {
+        Dialect::MySQL => alt((
+            read_mode,
+            value(
+                TransactionMode::WithConsistentSnapshot,
+                tuple((
+                    tag_no_case("with"),
+                    whitespace1,
+                    tag_no_case("consistent"),
+                    whitespace1,
+                    tag_no_case("snapshot"),
+                )),
+            ),
+        ))(i),
+        Dialect::PostgreSQL => alt((
+            map(
+                preceded(
+                    tuple((
+                        tag_no_case("isolation"),
+                        whitespace1,
+                        tag_no_case("level"),
+                        whitespace1,
+                    )),
+                    isolation_level,
+                ),
+                TransactionMode::IsolationLevel,
+            ),
+            read_mode,
+            value(TransactionMode::Deferrable(true), tag_no_case("deferrable")),
+            value(
+                TransactionMode::Deferrable(false),
+                tuple((tag_no_case("not"), whitespace1, tag_no_case("deferrable"))),
+            ),
+        ))(i),
+    }
+}
+
+/// Parses an optional list of transaction modes following the statement which starts a
+/// transaction
+fn transaction_modes(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Vec<TransactionMode>> {
+    move |i| {
+        map(
+            opt(preceded(
+                whitespace1,
+                separated_list1(ws_sep_comma, transaction_mode(dialect)),
+            )),
+            |modes| modes.unwrap_or_default(),
+        )(i)
+    }
+}
+
 // Parse rule for a START TRANSACTION query.
 // TODO(peter): Handle dialect differences.
 pub fn start_transaction(
@@ -56,12 +201,15 @@ pub fn start_transaction(
         let (i, _) = whitespace0(i)?;
         let (i, stmt) = alt((
             map(
-                tuple((
-                    tag_no_case("start"),
-                    whitespace1,
-                    tag_no_case("transaction"),
-                )),
-                |_| StartTransactionStatement::Start,
+                preceded(
+                    tuple((
+                        tag_no_case("start"),
+                        whitespace1,
+                        tag_no_case("transaction"),
+                    )),
+                    transaction_modes(dialect),
+                ),
+                StartTransactionStatement::Start,
             ),
             begin(dialect),
         ))(i)?;
@@ -84,14 +232,17 @@ fn postgres_begin() -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], StartT
 {
     move |i| {
         map(
-            tuple((
-                tag_no_case("begin"),
-                opt(alt((
-                    tuple((whitespace1, tag_no_case("work"))),
-                    tuple((whitespace1, tag_no_case("transaction"))),
-                ))),
-            )),
-            |_| StartTransactionStatement::Begin,
+            preceded(
+                tuple((
+                    tag_no_case("begin"),
+                    opt(alt((
+                        tuple((whitespace1, tag_no_case("work"))),
+                        tuple((whitespace1, tag_no_case("transaction"))),
+                    ))),
+                )),
+                transaction_modes(Dialect::PostgreSQL),
+            ),
+            StartTransactionStatement::Begin,
         )(i)
     }
 }
@@ -103,7 +254,7 @@ fn mysql_begin() -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], StartTran
                 tag_no_case("begin"),
                 opt(tuple((whitespace1, tag_no_case("work")))),
             )),
-            |_| StartTransactionStatement::Begin,
+            |_| StartTransactionStatement::Begin(vec![]),
         )(i)
     }
 }
@@ -168,7 +319,7 @@ mod tests {
     fn start_transaction_dialect_agnostic(dialect: Dialect) {
         let qstring = "    START       TRANSACTION ;  ";
         let res = start_transaction(dialect)(LocatedSpan::new(qstring.as_bytes()));
-        assert_eq!(res.unwrap().1, StartTransactionStatement::Start,);
+        assert_eq!(res.unwrap().1, StartTransactionStatement::Start(vec![]),);
     }
 
     #[test]
@@ -183,17 +334,17 @@ mod tests {
         let res = start_transaction(Dialect::MySQL)(LocatedSpan::new(qstring.as_bytes()));
         assert!(res.is_err());
         let res = start_transaction(Dialect::PostgreSQL)(LocatedSpan::new(qstring.as_bytes()));
-        assert_eq!(res.unwrap().1, StartTransactionStatement::Begin,);
+        assert_eq!(res.unwrap().1, StartTransactionStatement::Begin(vec![]),);
     }
 
     fn begin_dialect_agnostic(dialect: Dialect) {
         let qstring = "    BEGIN       WORK;   ";
         let res = start_transaction(dialect)(LocatedSpan::new(qstring.as_bytes()));
-        assert_eq!(res.unwrap().1, StartTransactionStatement::Begin,);
+        assert_eq!(res.unwrap().1, StartTransactionStatement::Begin(vec![]),);
 
         let qstring = "    BEGIN;   ";
         let res = start_transaction(dialect)(LocatedSpan::new(qstring.as_bytes()));
-        assert_eq!(res.unwrap().1, StartTransactionStatement::Begin,);
+        assert_eq!(res.unwrap().1, StartTransactionStatement::Begin(vec![]),);
     }
 
     #[test]
@@ -202,6 +353,55 @@ mod tests {
         begin_dialect_agnostic(Dialect::PostgreSQL);
     }
 
+    #[test]
+    fn mysql_start_transaction_with_modes() {
+        let qstring = "START TRANSACTION WITH CONSISTENT SNAPSHOT, READ ONLY";
+        let res = start_transaction(Dialect::MySQL)(LocatedSpan::new(qstring.as_bytes()));
+        let stmt = res.unwrap().1;
+        assert_eq!(
+            stmt,
+            StartTransactionStatement::Start(vec![
+                TransactionMode::WithConsistentSnapshot,
+                TransactionMode::ReadOnly
+            ])
+        );
+        assert_eq!(stmt.to_string(), qstring);
+
+        let qstring = "START TRANSACTION ISOLATION LEVEL SERIALIZABLE";
+        let res = start_transaction(Dialect::MySQL)(LocatedSpan::new(qstring.as_bytes()));
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn postgres_begin_with_modes() {
+        let qstring =
+            "BEGIN TRANSACTION ISOLATION LEVEL REPEATABLE READ,READ WRITE , NOT DEFERRABLE;";
+        let res = start_transaction(Dialect::PostgreSQL)(LocatedSpan::new(qstring.as_bytes()));
+        let stmt = res.unwrap().1;
+        assert_eq!(
+            stmt,
+            StartTransactionStatement::Begin(vec![
+                TransactionMode::IsolationLevel(IsolationLevel::RepeatableRead),
+                TransactionMode::ReadWrite,
+                TransactionMode::Deferrable(false),
+            ])
+        );
+        assert_eq!(
+            stmt.to_string(),
+            "BEGIN ISOLATION LEVEL REPEATABLE READ, READ WRITE, NOT DEFERRABLE"
+        );
+
+        let qstring = "START TRANSACTION READ ONLY, DEFERRABLE";
+        let res = start_transaction(Dialect::PostgreSQL)(LocatedSpan::new(qstring.as_bytes()));
+        assert_eq!(
+            res.unwrap().1,
+            StartTransactionStatement::Start(vec![
+                TransactionMode::ReadOnly,
+                TransactionMode::Deferrable(true)
+            ])
+        );
+    }
+
     #[test]
     fn commit_complex() {
         let qstring = "    COMMIT";
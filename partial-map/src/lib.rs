@@ -3,7 +3,7 @@
 use std::borrow::Borrow;
 pub use std::collections::btree_map::{Iter, Keys, Range, Values, ValuesMut};
 use std::collections::{btree_map, BTreeMap};
-use std::fmt;
+use std::{fmt, ops};
 
 use merging_interval_tree::IntervalTreeSet;
 use readyset_util::ranges::{Bound, RangeBounds};
@@ -325,6 +325,19 @@ where
         }
     }
 
+    /// Returns an iterator over the keys and values in this map within `range`, in key order.
+    ///
+    /// Unlike [`range`](PartialMap::range), this does *not* consider whether the map contains all
+    /// of `range`, and just returns whichever keys in `range` are present.
+    pub fn present_range<R, Q>(&self, range: R) -> Range<K, V>
+    where
+        R: ops::RangeBounds<Q>,
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.range(range)
+    }
+
     /// Returns an iterator over the values in this map
     ///
     /// Note that this does *not* consider ranges, since iteration is not well-defined for certain
@@ -481,4 +494,17 @@ mod tests {
         map.insert_range((Bound::Included(1), Bound::Included(i32::MAX)));
         assert!(matches!(map.entry(2), Entry::Occupied(_)));
     }
+
+    #[test]
+    fn present_range_ignores_holes() {
+        let mut map: PartialMap<i32, i32> = PartialMap::new();
+        map.insert(1, 1);
+        map.insert(5, 5);
+        map.insert(9, 9);
+        assert!(map.range(&(2..20)).is_err());
+        assert_eq!(
+            map.present_range(2..).map(|(k, _)| *k).collect::<Vec<_>>(),
+            vec![5, 9]
+        );
+    }
 }
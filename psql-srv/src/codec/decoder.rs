@@ -8,7 +8,7 @@ use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZon
 use cidr::IpInet;
 use eui48::MacAddress;
 use postgres_types::{FromSql, Kind, Type};
-use readyset_data::{Array, Collation, PassThroughFormat};
+use readyset_data::{Array, Collation, Interval, PassThroughFormat};
 use rust_decimal::prelude::FromStr;
 use rust_decimal::Decimal;
 use tokio_util::codec::Decoder;
@@ -375,6 +375,7 @@ fn get_binary_value(src: &mut Bytes, t: &Type) -> Result<PsqlValue, Error> {
             Type::MACADDR => Ok(PsqlValue::MacAddress(MacAddress::from_sql(t, buf)?)),
             Type::INET => Ok(PsqlValue::Inet(IpInet::from_sql(t, buf)?)),
             Type::UUID => Ok(PsqlValue::Uuid(Uuid::from_sql(t, buf)?)),
+            Type::INTERVAL => Ok(PsqlValue::Interval(Interval::from_sql(t, buf)?)),
             Type::JSON => Ok(PsqlValue::Json(serde_json::Value::from_sql(t, buf)?)),
             Type::JSONB => Ok(PsqlValue::Jsonb(serde_json::Value::from_sql(t, buf)?)),
             Type::BIT => Ok(PsqlValue::Bit(BitVec::from_sql(t, buf)?)),
@@ -511,6 +512,10 @@ fn get_text_value(src: &mut Bytes, t: &Type) -> Result<PsqlValue, Error> {
         Type::UUID => Uuid::parse_str(text_str)
             .map_err(DecodeError::InvalidTextUuidValue)
             .map(PsqlValue::Uuid),
+        Type::INTERVAL => text_str
+            .parse::<Interval>()
+            .map_err(|e| DecodeError::InvalidTextIntervalValue(e.to_string()))
+            .map(PsqlValue::Interval),
         Type::JSON => serde_json::from_str::<serde_json::Value>(text_str)
             .map_err(DecodeError::InvalidTextJsonValue)
             .map(PsqlValue::Json),
@@ -1533,6 +1538,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_decode_text_interval() {
+        let mut buf = BytesMut::new();
+        buf.put_i32(14);
+        buf.extend_from_slice(b"1 day 02:00:00");
+        assert_eq!(
+            get_text_value(&mut buf.freeze(), &Type::INTERVAL).unwrap(),
+            PsqlValue::Interval(Interval::new(0, 1, 7_200_000_000))
+        );
+    }
+
     #[test]
     fn test_decode_text_json() {
         let json_str =
@@ -563,6 +563,9 @@ fn put_binary_value(val: PsqlValue, dst: &mut BytesMut) -> Result<(), Error> {
         PsqlValue::Uuid(u) => {
             u.to_sql(&Type::UUID, dst)?;
         }
+        PsqlValue::Interval(i) => {
+            i.to_sql(&Type::INTERVAL, dst)?;
+        }
         PsqlValue::Json(v) => {
             v.to_sql(&Type::JSON, dst)?;
         }
@@ -673,6 +676,7 @@ fn put_text_value(val: PsqlValue, dst: &mut BytesMut) -> Result<(), Error> {
         PsqlValue::MacAddress(m) => write!(dst, "{}", m.to_string(MacAddressFormat::HexString))?,
         PsqlValue::Inet(ip) => write!(dst, "{}", ip)?,
         PsqlValue::Uuid(u) => write!(dst, "{}", u)?,
+        PsqlValue::Interval(i) => write!(dst, "{}", i)?,
         PsqlValue::Json(v) => write!(dst, "{}", v)?,
         PsqlValue::Jsonb(v) => write!(dst, "{}", v)?,
         PsqlValue::Bit(bits) | PsqlValue::VarBit(bits) => write!(
@@ -708,6 +712,7 @@ mod tests {
     use eui48::MacAddress;
     use postgres::SimpleQueryRow;
     use postgres_protocol::message::backend::DataRowBody;
+    use readyset_data::Interval;
     use rust_decimal::Decimal;
     use tokio_postgres::OwnedField;
     use uuid::Uuid;
@@ -1479,6 +1484,17 @@ mod tests {
         assert_eq!(buf, exp);
     }
 
+    #[test]
+    fn test_encode_binary_interval() {
+        let mut buf = BytesMut::new();
+        let interval = Interval::new(14, 3, 14_706_000_000);
+        put_binary_value(PsqlValue::Interval(interval), &mut buf).unwrap();
+        let mut exp = BytesMut::new();
+        exp.put_i32(16);
+        interval.to_sql(&Type::INTERVAL, &mut exp).unwrap(); // add value
+        assert_eq!(buf, exp);
+    }
+
     #[test]
     fn test_encode_binary_json() {
         let mut buf = BytesMut::new();
@@ -1695,6 +1711,20 @@ mod tests {
         assert_eq!(buf, exp);
     }
 
+    #[test]
+    fn test_encode_text_interval() {
+        let mut buf = BytesMut::new();
+        put_text_value(
+            PsqlValue::Interval(Interval::new(14, 3, 14_706_000_000)),
+            &mut buf,
+        )
+        .unwrap();
+        let mut exp = BytesMut::new();
+        exp.put_i32(29); // length (placeholder)
+        exp.extend_from_slice(b"1 year 2 mons 3 days 04:05:06");
+        assert_eq!(buf, exp);
+    }
+
     #[test]
     fn test_encode_text_json() {
         let mut buf = BytesMut::new();
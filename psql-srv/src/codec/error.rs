@@ -55,6 +55,9 @@ pub enum DecodeError {
     #[error("invalid text uuid value: {0}")]
     InvalidTextUuidValue(uuid::Error),
 
+    #[error("invalid text interval value: {0}")]
+    InvalidTextIntervalValue(String),
+
     #[error("invalid text json value: {0}")]
     InvalidTextJsonValue(serde_json::Error),
 
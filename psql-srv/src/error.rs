@@ -66,6 +66,10 @@ pub enum Error {
 
     #[error(transparent)]
     PostgresError(#[from] tokio_postgres::error::Error),
+
+    /// An error which is reported to the client with the given SQLSTATE, and its message as-is
+    #[error("{message}")]
+    WithSqlState { sqlstate: SqlState, message: String },
 }
 
 impl From<Error> for BackendMessage {
@@ -89,6 +93,7 @@ impl From<Error> for BackendMessage {
             Error::UnsupportedMessage(_) => SqlState::FEATURE_NOT_SUPPORTED,
             Error::UnsupportedType(_) => SqlState::FEATURE_NOT_SUPPORTED,
             Error::Scram(_) => SqlState::PROTOCOL_VIOLATION,
+            Error::WithSqlState { ref sqlstate, .. } => sqlstate.clone(),
             Error::PostgresError(ref e) => {
                 if let Some(db_error) = e.as_db_error() {
                     return BackendMessage::ErrorResponse {
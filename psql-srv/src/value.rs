@@ -5,7 +5,7 @@ use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
 use cidr::IpInet;
 use eui48::MacAddress;
 use postgres_types::{FromSql, Kind, Type};
-use readyset_data::{Array, PassThroughFormat, Text};
+use readyset_data::{Array, Interval, PassThroughFormat, Text};
 use rust_decimal::Decimal;
 use uuid::Uuid;
 
@@ -34,6 +34,7 @@ pub enum PsqlValue {
     MacAddress(MacAddress),
     Inet(IpInet),
     Uuid(Uuid),
+    Interval(Interval),
     Json(serde_json::Value),
     Jsonb(serde_json::Value),
     Bit(BitVec),
@@ -101,6 +102,7 @@ impl<'a> FromSql<'a> for PsqlValue {
                 Type::MACADDR => MacAddress::from_sql(ty, raw).map(PsqlValue::MacAddress),
                 Type::INET => IpInet::from_sql(ty, raw).map(PsqlValue::Inet),
                 Type::UUID => Uuid::from_sql(ty, raw).map(PsqlValue::Uuid),
+                Type::INTERVAL => Interval::from_sql(ty, raw).map(PsqlValue::Interval),
                 Type::JSON => serde_json::Value::from_sql(ty, raw).map(PsqlValue::Json),
                 Type::JSONB => serde_json::Value::from_sql(ty, raw).map(PsqlValue::Jsonb),
                 Type::BIT => BitVec::from_sql(ty, raw).map(PsqlValue::Bit),
@@ -394,6 +394,7 @@ impl From<TableSpec> for CreateTableStatement {
                 }),
             }),
             options: Ok(vec![]),
+            select: None,
         }
     }
 }
@@ -1636,6 +1637,7 @@ impl QueryOperation {
                     GroupConcat => FunctionExpr::GroupConcat {
                         expr,
                         separator: Some(", ".to_owned()),
+                        order_by: None,
                     },
                     Max { .. } => FunctionExpr::Max(expr),
                     Min { .. } => FunctionExpr::Min(expr),
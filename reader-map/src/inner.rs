@@ -136,6 +136,23 @@ impl<K, V, S> Data<K, V, S> {
         }
     }
 
+    /// Returns an iterator over the keys present in `range`, in key order, or `None` if the map
+    /// is not a BTreeMap
+    pub(crate) fn present_range<R, Q>(
+        &'_ self,
+        range: R,
+    ) -> Option<partial_map::Range<'_, K, Values<V>>>
+    where
+        R: std::ops::RangeBounds<Q>,
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        match self {
+            Self::BTreeMap(map, ..) => Some(map.present_range(range)),
+            Self::HashMap(..) => None,
+        }
+    }
+
     pub(crate) fn add_range<R>(&mut self, range: R)
     where
         R: RangeBounds<K>,
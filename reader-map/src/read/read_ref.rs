@@ -85,6 +85,25 @@ where
         })
     }
 
+    /// Constructs an iterator over the key + valuesets present in a sub-range of keys in the map,
+    /// in key order, regardless of whether the map contains all of the range. Returns `None` if the
+    /// underlying map is not a
+    /// [`BTreeMap`](readyset_client::internal::IndexType::BTreeMap).
+    ///
+    /// Unlike [`range`](Self::range), reads through this iterator don't count as reads of the keys
+    /// for the purposes of eviction.
+    ///
+    /// Be careful with this function! While the iteration is ongoing, any writer that tries to
+    /// publish changes will block waiting on this reader to finish.
+    pub fn present_range<R, Q>(&self, range: R) -> Option<btree_map::Range<'_, K, Values<V>>>
+    where
+        R: std::ops::RangeBounds<Q>,
+        Q: Ord + ?Sized,
+        K: Borrow<Q>,
+    {
+        self.guard.data.present_range(range)
+    }
+
     /// Iterate over all keys in the map.
     ///
     /// Be careful with this function! While the iteration is ongoing, any writer that tries to
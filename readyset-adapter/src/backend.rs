@@ -69,10 +69,11 @@
 //! being too heavy handed.
 
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{self, Debug};
 use std::marker::PhantomData;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
@@ -89,6 +90,7 @@ use readyset_adapter_types::{DeallocateId, ParsedCommand};
 use readyset_client::consensus::{Authority, AuthorityControl, CacheDDLRequest};
 use readyset_client::consistency::Timestamp;
 use readyset_client::query::*;
+use readyset_client::recipe::{FrontierStrategy, QuerySource};
 use readyset_client::results::Results;
 use readyset_client::utils::retry_with_exponential_backoff;
 use readyset_client::{ColumnSchema, PlaceholderIdx, ViewCreateRequest};
@@ -96,7 +98,7 @@ pub use readyset_client_metrics::QueryDestination;
 use readyset_client_metrics::{
     recorded, EventType, QueryExecutionEvent, QueryLogMode, ReadysetExecutionEvent, SqlQueryType,
 };
-use readyset_data::{DfType, DfValue};
+use readyset_data::{DfType, DfValue, Timezone};
 use readyset_errors::ReadySetError::{self, PreparedStatementMissing};
 use readyset_errors::{internal, internal_err, unsupported, unsupported_err, ReadySetResult};
 use readyset_sql_passes::adapter_rewrites::{self, ProcessedQueryParams};
@@ -244,6 +246,12 @@ impl ProxyState {
         *self == ProxyState::InTransaction
     }
 
+    /// Returns true if statements proxied upstream run inside a transaction which is still open,
+    /// whether it was started explicitly or by turning autocommit off.
+    fn in_upstream_transaction(&self) -> bool {
+        matches!(self, Self::InTransaction | Self::AutocommitOff)
+    }
+
     /// Sets the autocommit state accordingly. If turning autocommit on, will set ProxyState to
     /// Fallback as long as current state is AutocommitOff.
     ///
@@ -290,6 +298,7 @@ pub struct BackendBuilder {
     metrics_handle: Option<MetricsHandle>,
     connections: Option<Arc<SkipSet<SocketAddr>>>,
     allow_cache_ddl: bool,
+    replication_paused: Option<Arc<AtomicBool>>,
 }
 
 impl Default for BackendBuilder {
@@ -313,6 +322,7 @@ impl Default for BackendBuilder {
             metrics_handle: None,
             connections: None,
             allow_cache_ddl: true,
+            replication_paused: None,
         }
     }
 }
@@ -339,6 +349,7 @@ impl BackendBuilder {
         } else {
             ProxyState::Never
         };
+        let replication_paused = self.replication_paused.filter(|_| upstream.is_some());
 
         if let Some(connections) = &self.connections {
             connections.insert(self.client_addr);
@@ -354,11 +365,14 @@ impl BackendBuilder {
             last_query: None,
             state: BackendState {
                 proxy_state,
+                replication_paused,
                 parsed_query_cache: LruCache::new(10_000.try_into().expect("10000 is not 0")),
                 prepared_statements: Default::default(),
                 query_status_cache,
                 ticket: self.ticket,
                 timestamp_client: self.timestamp_client,
+                timezone: Timezone::UTC,
+                incompatible_variables: HashSet::new(),
             },
             settings: BackendSettings {
                 slowlog: self.slowlog,
@@ -477,6 +491,16 @@ impl BackendBuilder {
         self
     }
 
+    /// Proxy all reads upstream, other than those for caches created with `ALWAYS`, whenever
+    /// `replication_paused` is set.
+    pub fn proxy_while_replication_paused(
+        mut self,
+        replication_paused: Option<Arc<AtomicBool>>,
+    ) -> Self {
+        self.replication_paused = replication_paused;
+        self
+    }
+
     pub fn metrics_handle(mut self, metrics_handle: Option<MetricsHandle>) -> Self {
         self.metrics_handle = metrics_handle;
         self
@@ -604,13 +628,17 @@ where
     DB: UpstreamDatabase,
 {
     proxy_state: ProxyState,
+    /// If set, whether replication from the upstream database is currently paused. Reads are
+    /// proxied upstream while it is, since results from ReadySet won't reflect upstream writes.
+    replication_paused: Option<Arc<AtomicBool>>,
     /// A cache of queries that we've seen, and their current state, used for processing
     query_status_cache: &'static QueryStatusCache,
     // a cache of all previously parsed queries
     parsed_query_cache: LruCache<String, SqlQuery>,
     // all queries previously prepared on noria or upstream, mapped by their ID.
     prepared_statements: Slab<PreparedStatement<DB>>,
-    /// Current RYW ticket. `None` if RYW is not enabled. This `ticket` will
+    /// Current RYW ticket. `None` if RYW is not enabled, or if the session has opted out of it
+    /// via `SetBehavior::SetReadYourWrites`. This `ticket` will
     /// be updated as the client makes writes so as to be an accurate low watermark timestamp
     /// required to make RYW-consistent reads. On reads, the client will pass in this ticket to be
     /// checked by noria view nodes.
@@ -619,6 +647,35 @@ where
     /// is responsible for creating accurate RYW timestamps/tickets based on writes made by the
     /// Backend client.
     timestamp_client: Option<TimestampClient>,
+    /// The timezone of the current session, which timestamps with a timezone are returned in
+    timezone: Timezone,
+    /// The names of the session variables which are currently set to values that ReadySet doesn't
+    /// return results consistent with. Reads are proxied upstream while any are.
+    incompatible_variables: HashSet<SqlIdentifier>,
+}
+
+impl<DB> BackendState<DB>
+where
+    DB: UpstreamDatabase,
+{
+    /// Returns true if a read should be proxied upstream unless it's been manually migrated with
+    /// the optional `ALWAYS` flag, either per our [`ProxyState`], because a session variable is set
+    /// to a value we don't support, or because replication is paused.
+    fn should_proxy(&self) -> bool {
+        self.proxy_state.should_proxy()
+            || !self.incompatible_variables.is_empty()
+            || self
+                .replication_paused
+                .as_ref()
+                .map_or(false, |paused| paused.load(Ordering::Relaxed))
+    }
+
+    /// Returns true if a read whose rewritten query still contains string literals (see
+    /// [`ProcessedQueryParams::has_string_literals`]) should be proxied upstream, since any
+    /// timestamps in those literals would be interpreted in UTC rather than the session timezone.
+    fn should_proxy_literals(&self, has_string_literals: bool) -> bool {
+        has_string_literals && !self.timezone.is_utc()
+    }
 }
 
 /// Settings that have no state and are constant for a given [`Backend`]
@@ -895,6 +952,9 @@ where
         if let Some(upstream) = &mut self.upstream {
             upstream.change_user(user, password, database).await?;
         }
+        if !database.is_empty() {
+            self.noria.set_schema_search_path(vec![database.into()]);
+        }
         Ok(())
     }
 
@@ -1409,6 +1469,34 @@ where
             .map(|r| QueryResult::Upstream(r))
     }
 
+    /// Execute a prepared write to `table` on the upstream, recording it in the session's RYW
+    /// `ticket`
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_upstream_ryw_write<'a>(
+        noria: &mut NoriaConnector,
+        upstream: &'a mut Option<DB>,
+        prep: &UpstreamPrepare<DB>,
+        params: &[DfValue],
+        exec_meta: DB::ExecMeta<'_>,
+        table: &Relation,
+        timestamp_client: &mut TimestampClient,
+        ticket: &mut Timestamp,
+        event: &mut QueryExecutionEvent,
+    ) -> Result<QueryResult<'a, DB>, DB::Error> {
+        let upstream = upstream.as_mut().ok_or_else(|| {
+            ReadySetError::Internal("This condition requires an upstream connector".to_string())
+        })?;
+
+        event.destination = Some(QueryDestination::Upstream);
+        let _t = event.start_upstream_timer();
+
+        let (query_result, identifier) = upstream
+            .execute_ryw_write(prep.statement_id, params, exec_meta)
+            .await?;
+        Self::record_ryw_write(noria, timestamp_client, ticket, table, identifier).await?;
+        Ok(QueryResult::Upstream(query_result))
+    }
+
     /// Execute on ReadySet, and if fails execute on upstream
     #[allow(clippy::too_many_arguments)] // meh.
     async fn execute_cascade<'a>(
@@ -1447,6 +1535,7 @@ where
                         ReadySetError::ReaderMissingKey
                             | ReadySetError::NoCacheForQuery
                             | ReadySetError::UnparseableQuery { .. }
+                            | ReadySetError::Overloaded { .. }
                     )
                 }) {
                     warn!(error = %noria_err,
@@ -1556,7 +1645,6 @@ where
     /// `params`.
     /// A [`QueryExecutionEvent`], is used to track metrics and behavior scoped to the
     /// execute operation.
-    // TODO(andrew, justin): add RYW support for executing prepared queries
     #[instrument(skip_all)]
     #[inline]
     pub async fn execute(
@@ -1660,7 +1748,12 @@ where
                 } else if always_readyset {
                     false
                 } else {
-                    is_recovering || self.state.proxy_state.should_proxy()
+                    is_recovering
+                        || self.state.should_proxy()
+                        || self.state.should_proxy_literals(matches!(
+                            cached_statement.prep.noria_biased(),
+                            SinglePrepareResult::Noria(prep) if prep.has_string_literals()
+                        ))
                 }
             }
         };
@@ -1678,7 +1771,48 @@ where
                         .query_status_cache
                         .inlined_cache_miss(cached_statement.as_view_request()?, params.to_vec())
                 }
-                Self::execute_upstream(upstream, prep, params, exec_meta, &mut event, false).await
+
+                // Writes are only ever prepared against the upstream if we have one, so this is
+                // the only place we need to update the ticket if RYW is enabled for this session.
+                // Writes inside a transaction aren't tracked, since they can't be committed
+                // individually.
+                let written_table =
+                    cached_statement
+                        .parsed_query
+                        .as_deref()
+                        .and_then(|query| match query {
+                            SqlQuery::Insert(InsertStatement { table, .. })
+                            | SqlQuery::Update(UpdateStatement { table, .. })
+                            | SqlQuery::Delete(DeleteStatement { table, .. }) => Some(table),
+                            _ => None,
+                        });
+                match (
+                    written_table,
+                    &mut self.state.timestamp_client,
+                    &mut self.state.ticket,
+                ) {
+                    (Some(table), Some(timestamp_client), Some(current_ticket))
+                        if cfg!(feature = "ryw")
+                            && !self.state.proxy_state.in_upstream_transaction() =>
+                    {
+                        Self::execute_upstream_ryw_write(
+                            noria,
+                            upstream,
+                            prep,
+                            params,
+                            exec_meta,
+                            table,
+                            timestamp_client,
+                            current_ticket,
+                            &mut event,
+                        )
+                        .await
+                    }
+                    _ => {
+                        Self::execute_upstream(upstream, prep, params, exec_meta, &mut event, false)
+                            .await
+                    }
+                }
             }
             PrepareResultInner::Both(.., uprep) if should_fallback => {
                 Self::execute_upstream(upstream, uprep, params, exec_meta, &mut event, false).await
@@ -1740,6 +1874,57 @@ where
         result
     }
 
+    /// Executes the prepared insert identified by `id` once for each of the given sets of
+    /// parameters, writing all of the resulting rows to ReadySet in a single batch.
+    ///
+    /// Returns `None` if the statement can't be executed as a batch, because it isn't an insert
+    /// prepared against ReadySet or it has an `ON DUPLICATE KEY UPDATE` clause. In that case the
+    /// caller should [`execute`](Self::execute) the statement once per set of parameters instead.
+    #[instrument(skip_all)]
+    pub async fn execute_insert_batch(
+        &mut self,
+        id: u32,
+        params: &[Vec<DfValue>],
+    ) -> Result<Option<QueryResult<'_, DB>>, DB::Error> {
+        self.last_query = None;
+        let cached_statement = self
+            .state
+            .prepared_statements
+            .get(id as _)
+            .ok_or(PreparedStatementMissing { statement_id: id })?;
+        let statement = match &cached_statement.prep.inner {
+            PrepareResultInner::Noria(noria_connector::PrepareResult::Insert {
+                statement, ..
+            }) if statement.on_duplicate.is_none() => statement,
+            _ => return Ok(None),
+        };
+
+        let mut event = QueryExecutionEvent::new(EventType::Execute);
+        event.query = cached_statement.parsed_query.clone();
+        event.query_id = cached_statement.query_id;
+        event.destination = Some(QueryDestination::Readyset);
+
+        let result = self
+            .noria
+            .execute_prepared_insert_batch(statement, params)
+            .await;
+        if let Err(e) = &result {
+            event.set_noria_error(e);
+        }
+
+        self.last_query = event.destination.map(|d| QueryInfo {
+            destination: d,
+            noria_error: event
+                .noria_error
+                .as_ref()
+                .map(|e| e.to_string())
+                .unwrap_or_default(),
+        });
+        log_query(self.query_log_sender.as_ref(), event, self.settings.slowlog);
+
+        Ok(Some(result?.into()))
+    }
+
     pub async fn remove_statement(&mut self, deallocate_id: DeallocateId) -> Result<(), DB::Error> {
         // in all cases, we need to call upstream.remove_statement(), but in the case
         // of a Numeric id and it's in self.state.prepared_statements, we need to use
@@ -1847,6 +2032,7 @@ where
 
     /// Forwards a `CREATE CACHE` request to ReadySet
     #[instrument(skip(self))]
+    #[allow(clippy::too_many_arguments)]
     async fn create_cached_query(
         &mut self,
         name: Option<&Relation>,
@@ -1854,6 +2040,8 @@ where
         override_schema_search_path: Option<Vec<SqlIdentifier>>,
         always: bool,
         concurrently: bool,
+        frontier_strategy: Option<FrontierStrategy>,
+        disable_predicate_pushdown: bool,
     ) -> ReadySetResult<noria_connector::QueryResult<'static>> {
         // If we have another query with the same name, drop that query first
         if let Some(name) = name {
@@ -1868,7 +2056,10 @@ where
             }
         }
         // Now migrate the new query
+        let original = stmt.clone();
         adapter_rewrites::process_query(&mut stmt, self.noria.rewrite_params())?;
+        let original_sql =
+            (stmt != original).then(|| original.display(self.settings.dialect).to_string());
         let migration_state = match self
             .noria
             .handle_create_cached_query(
@@ -1877,6 +2068,10 @@ where
                 override_schema_search_path,
                 always,
                 concurrently,
+                frontier_strategy,
+                disable_predicate_pushdown,
+                QuerySource::CreateCache,
+                original_sql,
             )
             .await
         {
@@ -2312,6 +2507,8 @@ where
                 always,
                 concurrently,
                 unparsed_create_cache_statement,
+                frontier_strategy,
+                disable_predicate_pushdown,
             }) => {
                 if !self.allow_cache_ddl {
                     unsupported!("{}", UNSUPPORTED_CACHE_DDL_MSG);
@@ -2364,7 +2561,15 @@ where
                 };
 
                 let res = self
-                    .create_cached_query(name.as_ref(), stmt, search_path, *always, *concurrently)
+                    .create_cached_query(
+                        name.as_ref(),
+                        stmt,
+                        search_path,
+                        *always,
+                        *concurrently,
+                        frontier_strategy.clone(),
+                        *disable_predicate_pushdown,
+                    )
                     .await;
                 // The extend_recipe may have failed, in which case we should remove our intention
                 // to create this cache. Extend recipe waits a bit and then returns an
@@ -2636,7 +2841,11 @@ where
         match adapter_rewrites::process_query(&mut q.statement, self.noria.rewrite_params()) {
             Ok(processed_query_params) => {
                 let s = self.state.query_status_cache.query_status(q);
-                let should_try = if self.state.proxy_state.should_proxy() {
+                let should_try = if self.state.should_proxy()
+                    || self
+                        .state
+                        .should_proxy_literals(processed_query_params.has_string_literals())
+                {
                     s.always
                 } else {
                     true
@@ -2666,6 +2875,9 @@ where
     /// If we have an upstream then we will pass valid set statements across to that upstream.
     /// If no upstream is present we will ignore the statement
     /// Disallowed set statements always produce an error
+    ///
+    /// Returns `true` if the statement only affects the state of this connection within ReadySet,
+    /// and so must not be passed on to the upstream.
     fn handle_set(
         noria: &mut NoriaConnector,
         upstream: Option<&mut &mut DB>,
@@ -2674,8 +2886,18 @@ where
         query: &str,
         set: &SetStatement,
         event: &mut QueryExecutionEvent,
-    ) -> Result<(), DB::Error> {
-        match Handler::handle_set_statement(set) {
+    ) -> Result<bool, DB::Error> {
+        let behavior = match Handler::handle_set_statement(set) {
+            // Without an upstream to proxy reads to, we can't return consistent results while a
+            // session variable is set to a value we don't support
+            SetBehavior::SetSessionVariables(changes)
+                if upstream.is_none() && changes.any_incompatible() =>
+            {
+                SetBehavior::Unsupported
+            }
+            behavior => behavior,
+        };
+        match behavior {
             SetBehavior::Unsupported => {
                 warn!(
                     // FIXME(REA-2168): Use correct dialect.
@@ -2725,8 +2947,60 @@ where
                 trace!(?search_path, "Setting search_path");
                 noria.set_schema_search_path(search_path);
             }
+            SetBehavior::SetSessionVariables(changes) => {
+                if let Some(timezone) = changes.timezone {
+                    trace!(%timezone, "Setting session timezone");
+                    state.timezone = timezone;
+                    noria.set_timezone(timezone);
+                }
+                for (name, compatible) in changes.compatibility {
+                    trace!(%name, compatible, "Setting session variable");
+                    if compatible {
+                        state.incompatible_variables.remove(&name);
+                    } else {
+                        state.incompatible_variables.insert(name);
+                    }
+                }
+            }
+            SetBehavior::SetReadYourWrites(enabled) => {
+                trace!(enabled, "Setting read-your-writes consistency");
+                if !enabled {
+                    state.ticket = None;
+                } else if state.ticket.is_none() {
+                    if state.timestamp_client.is_none() {
+                        unsupported!("Read-your-writes consistency is not enabled");
+                    }
+                    // Writes made while the session was opted out weren't tracked, so we can only
+                    // guarantee that reads observe writes made from here on
+                    state.ticket = Some(Timestamp::default());
+                }
+                return Ok(true);
+            }
         }
 
+        Ok(false)
+    }
+
+    /// Record a write to `table` which was committed upstream with the given identifier in the
+    /// session's RYW `ticket`, so that subsequent reads require a version of the data which
+    /// includes it
+    async fn record_ryw_write(
+        noria: &mut NoriaConnector,
+        timestamp_client: &mut TimestampClient,
+        ticket: &mut Timestamp,
+        table: &Relation,
+        identifier: String,
+    ) -> ReadySetResult<()> {
+        // TODO(andrew): Move table name to table index conversion to
+        // timestamp service https://app.clubhouse.io/readysettech/story/331
+        let index = noria.node_index_of(table.name.as_str()).await?;
+        let affected_tables = vec![WriteKey::TableIndex(index)];
+
+        let new_timestamp = timestamp_client
+            .append_write(WriteId::MySqlGtid(identifier), affected_tables)
+            .map_err(|e| internal_err!("{e}"))?;
+
+        *ticket = Timestamp::join(ticket, &new_timestamp);
         Ok(())
     }
 
@@ -2741,17 +3015,19 @@ where
         state: &mut BackendState<DB>,
     ) -> Result<QueryResult<'a, DB>, DB::Error> {
         match &query {
-            SqlQuery::Set(s) => Self::handle_set(
-                noria,
-                upstream.as_mut(),
-                settings,
-                state,
-                raw_query,
-                s,
-                event,
-            )?,
-            SqlQuery::Use(UseStatement { database }) => {
-                noria.set_schema_search_path(vec![database.clone()])
+            SqlQuery::Set(s) => {
+                if Self::handle_set(
+                    noria,
+                    upstream.as_mut(),
+                    settings,
+                    state,
+                    raw_query,
+                    s,
+                    event,
+                )? {
+                    event.destination = Some(QueryDestination::Readyset);
+                    return Ok(QueryResult::Noria(noria_connector::QueryResult::Empty));
+                }
             }
             _ => (),
         }
@@ -2769,35 +3045,22 @@ where
                         event.destination = Some(QueryDestination::Upstream);
                         let _t = event.start_upstream_timer();
 
-                        // Update ticket if RYW enabled
-                        let query_result = if cfg!(feature = "ryw") {
-                            if let Some(timestamp_service) = &mut state.timestamp_client {
+                        // Update ticket if RYW enabled for this session
+                        let query_result = match (&mut state.timestamp_client, &mut state.ticket) {
+                            (Some(timestamp_client), Some(ticket)) if cfg!(feature = "ryw") => {
                                 let (query_result, identifier) =
                                     upstream.handle_ryw_write(raw_query).await?;
-
-                                // TODO(andrew): Move table name to table index conversion to
-                                // timestamp service https://app.clubhouse.io/readysettech/story/331
-                                let index = noria.node_index_of(t.name.as_str()).await?;
-                                let affected_tables = vec![WriteKey::TableIndex(index)];
-
-                                let new_timestamp = timestamp_service
-                                    .append_write(WriteId::MySqlGtid(identifier), affected_tables)
-                                    .map_err(|e| internal_err!("{e}"))?;
-
-                                // TODO(andrew, justin): solidify error handling in client
-                                // https://app.clubhouse.io/readysettech/story/366
-                                let current_ticket = state.ticket.as_ref().ok_or_else(|| {
-                                    internal_err!("RYW enabled backends must have a current ticket")
-                                })?;
-
-                                state.ticket =
-                                    Some(Timestamp::join(current_ticket, &new_timestamp));
+                                Self::record_ryw_write(
+                                    noria,
+                                    timestamp_client,
+                                    ticket,
+                                    &t,
+                                    identifier,
+                                )
+                                .await?;
                                 Ok(query_result)
-                            } else {
-                                upstream.query(raw_query).await
                             }
-                        } else {
-                            upstream.query(raw_query).await
+                            _ => upstream.query(raw_query).await,
                         };
 
                         query_result.map(QueryResult::Upstream)
@@ -2809,11 +3072,22 @@ where
                     | SqlQuery::CreateTable(_)
                     | SqlQuery::DropTable(_)
                     | SqlQuery::DropView(_)
-                    | SqlQuery::AlterTable(_)
-                    | SqlQuery::Use(_) => {
+                    | SqlQuery::CreateIndex(_)
+                    | SqlQuery::DropIndex(_)
+                    | SqlQuery::AlterTable(_) => {
                         event.sql_type = SqlQueryType::Other;
                         upstream.query(raw_query).await.map(QueryResult::Upstream)
                     }
+                    SqlQuery::Use(UseStatement { database }) => {
+                        event.sql_type = SqlQueryType::Other;
+                        let res = upstream.query(raw_query).await;
+                        // Only switch the schema we resolve tables in once upstream has accepted
+                        // the new database
+                        if res.is_ok() {
+                            noria.set_schema_search_path(vec![database]);
+                        }
+                        res.map(QueryResult::Upstream)
+                    }
                     SqlQuery::RenameTable(_) => {
                         unsupported!("{} not yet supported", query.query_type());
                     }
@@ -2855,6 +3129,14 @@ where
                     SqlQuery::Select(_) => unreachable!("read path returns prior"),
                     // CREATE VIEW will still trigger migrations with explicit-migrations enabled
                     SqlQuery::CreateView(q) => noria.handle_create_view(q).await,
+                    SqlQuery::CreateTable(q) if q.select.is_some() => {
+                        // Without an upstream database, there's nothing to snapshot the rows
+                        // selected by the query from
+                        unsupported!(
+                            "CREATE TABLE ... AS SELECT is not supported without an upstream \
+                             database"
+                        );
+                    }
                     SqlQuery::CreateTable(q) => noria.handle_table_operation(q.clone()).await,
                     SqlQuery::AlterTable(q) => noria.handle_table_operation(q.clone()).await,
                     SqlQuery::DropTable(q) => noria.handle_table_operation(q.clone()).await,
@@ -2868,10 +3150,13 @@ where
                     // messages are dropped - we do not support transactions in noria standalone.
                     // We return an empty result set instead of an error to support test
                     // applications.
-                    SqlQuery::Set(_)
-                    | SqlQuery::Commit(_)
-                    | SqlQuery::Use(_)
-                    | SqlQuery::Comment(_) => Ok(noria_connector::QueryResult::Empty),
+                    SqlQuery::Set(_) | SqlQuery::Commit(_) | SqlQuery::Comment(_) => {
+                        Ok(noria_connector::QueryResult::Empty)
+                    }
+                    SqlQuery::Use(UseStatement { database }) => {
+                        noria.set_schema_search_path(vec![database.clone()]);
+                        Ok(noria_connector::QueryResult::Empty)
+                    }
                     _ => {
                         error!("unsupported query");
                         unsupported!("query type unsupported");
@@ -2969,10 +3254,16 @@ where
                 .await
                 .map(Into::into)
                 .map_err(Into::into),
-            // SET autocommit=1 needs to be handled explicitly or it will end up getting proxied in
-            // most cases.
+            // SET autocommit=1, SETs of session variables we track, and SETs which only affect
+            // ReadySet need to be handled explicitly or they will end up getting proxied in most
+            // cases.
             Ok(SqlQuery::Set(s))
-                if Handler::handle_set_statement(&s) == SetBehavior::SetAutocommit(true) =>
+                if matches!(
+                    Handler::handle_set_statement(&s),
+                    SetBehavior::SetAutocommit(true)
+                        | SetBehavior::SetSessionVariables(_)
+                        | SetBehavior::SetReadYourWrites(_)
+                ) =>
             {
                 Self::query_adhoc_non_select(
                     &mut self.noria,
@@ -3159,6 +3450,11 @@ where
     pub fn in_transaction(&self) -> bool {
         self.state.proxy_state.in_transaction()
     }
+
+    /// Returns the timezone of the current session
+    pub fn timezone(&self) -> Timezone {
+        self.state.timezone
+    }
 }
 
 impl<DB, Handler> Drop for Backend<DB, Handler>
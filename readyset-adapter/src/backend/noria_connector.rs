@@ -14,13 +14,13 @@ use readyset_client::consistency::Timestamp;
 use readyset_client::internal::LocalNodeIndex;
 use readyset_client::query::QueryId;
 use readyset_client::recipe::changelist::{Change, ChangeList, IntoChanges};
-use readyset_client::recipe::CacheExpr;
+use readyset_client::recipe::{CacheExpr, FrontierStrategy, QuerySource};
 use readyset_client::results::{ResultIterator, Results};
 use readyset_client::{
     ColumnSchema, GraphvizOptions, ReadQuery, ReaderAddress, ReaderHandle, ReadySetHandle,
-    SchemaType, Table, TableOperation, View, ViewCreateRequest, ViewQuery,
+    SchemaType, Table, View, ViewCreateRequest, ViewQuery,
 };
-use readyset_data::{DfType, DfValue, Dialect};
+use readyset_data::{DfType, DfValue, Dialect, Timezone};
 use readyset_errors::{
     internal_err, invariant_eq, table_err, unsupported, unsupported_err, ReadySetError,
     ReadySetResult,
@@ -161,6 +161,20 @@ pub enum PrepareResult {
     },
 }
 
+impl PrepareResult {
+    /// Returns true if this is a select whose rewritten query still contains string literals
+    /// which weren't replaced with placeholders. See
+    /// [`ProcessedQueryParams::has_string_literals`].
+    pub fn has_string_literals(&self) -> bool {
+        match self {
+            PrepareResult::Select { statement, .. } => {
+                statement.processed_query_params.has_string_literals()
+            }
+            _ => false,
+        }
+    }
+}
+
 /// A single row in the variable table associated with [`QueryResult::MetaVariables`].
 #[derive(Debug)]
 pub struct MetaVariable {
@@ -290,6 +304,9 @@ pub struct NoriaConnector {
     /// supports a multi-element schema search path, the concept of "currently connected database"
     /// in MySQL can be thought of as a schema search path that only has one element.
     schema_search_path: Vec<SqlIdentifier>,
+
+    /// The timezone of the current session, which timestamps in lookup keys are interpreted in
+    timezone: Timezone,
 }
 
 mod request_handler {
@@ -407,6 +424,7 @@ impl NoriaConnector {
             dialect,
             parse_dialect,
             schema_search_path,
+            timezone: Timezone::UTC,
         }
     }
 
@@ -560,6 +578,7 @@ impl NoriaConnector {
             .unwrap_or_else(|| AdapterRewriteParams {
                 server_supports_pagination: false,
                 server_supports_mixed_comparisons: false,
+                server_supports_post_lookup: false,
             })
     }
 
@@ -574,6 +593,9 @@ impl NoriaConnector {
         &mut self,
         q: &nom_sql::InsertStatement,
     ) -> ReadySetResult<QueryResult<'_>> {
+        if q.select.is_some() {
+            unsupported!("INSERT ... SELECT is not supported");
+        }
         let table = &q.table;
 
         // create a mutator if we don't have one for this table already
@@ -623,6 +645,9 @@ impl NoriaConnector {
         &mut self,
         mut statement: nom_sql::InsertStatement,
     ) -> ReadySetResult<PrepareResult> {
+        if statement.select.is_some() {
+            unsupported!("INSERT ... SELECT is not supported");
+        }
         trace!(table = %statement.table.name, "insert::access mutator");
         let mutator = self
             .inner
@@ -695,6 +720,26 @@ impl NoriaConnector {
         self.do_insert(q, rows).await
     }
 
+    /// Execute the prepared insert `q` once for each of the given sets of parameters, writing all
+    /// the resulting rows to the table in a single batch
+    pub(crate) async fn execute_prepared_insert_batch(
+        &mut self,
+        q: &InsertStatement,
+        params: &[Vec<DfValue>],
+    ) -> ReadySetResult<QueryResult<'_>> {
+        let table = &q.table;
+        let putter = self.inner.get_mut()?.get_noria_table(table).await?;
+        trace!("insert::extract schema");
+        let schema = putter
+            .schema()
+            .ok_or_else(|| internal_err!("no schema for table {}", table.display_unquoted()))?;
+        let mut rows = Vec::with_capacity(params.len());
+        for params in params {
+            rows.extend(utils::extract_insert(q, params, schema, self.dialect)?);
+        }
+        self.do_insert(q, rows).await
+    }
+
     pub(crate) async fn handle_delete(
         &mut self,
         q: &nom_sql::DeleteStatement,
@@ -939,15 +984,24 @@ impl NoriaConnector {
     pub fn schema_search_path(&self) -> &[SqlIdentifier] {
         self.schema_search_path.as_ref()
     }
+
+    /// Set the timezone of the current session
+    pub fn set_timezone(&mut self, timezone: Timezone) {
+        self.timezone = timezone;
+    }
 }
 
 impl NoriaConnector {
     /// This function handles CREATE CACHE statements. When explicit-migrations is enabled,
     /// this function is the only way to create a view in noria.
     ///
+    /// `source` and `original_sql` are recorded in the server's query log - `original_sql` should
+    /// be the SQL text of the query before it was rewritten by the adapter, if it was rewritten.
+    ///
     /// Returns Ok(Some(id)) if CREATE CACHE CONCURRENTLY is issued, where id is a unique identifier
     /// that can be used to query the status of the migration. Otherwise, returns Ok(None) on
     /// success and Err(_) on failure.
+    #[allow(clippy::too_many_arguments)]
     pub async fn handle_create_cached_query(
         &mut self,
         name: Option<&Relation>,
@@ -955,6 +1009,10 @@ impl NoriaConnector {
         override_schema_search_path: Option<Vec<SqlIdentifier>>,
         always: bool,
         concurrently: bool,
+        frontier_strategy: Option<FrontierStrategy>,
+        disable_predicate_pushdown: bool,
+        source: QuerySource,
+        original_sql: Option<String>,
     ) -> ReadySetResult<Option<u64>> {
         let name = name
             .cloned()
@@ -962,7 +1020,10 @@ impl NoriaConnector {
         let schema_search_path =
             override_schema_search_path.unwrap_or_else(|| self.schema_search_path.clone());
         let changelist = ChangeList::from_change(
-            Change::create_cache(name.clone(), statement.clone(), always),
+            Change::create_cache(name.clone(), statement.clone(), always)
+                .with_frontier_strategy(frontier_strategy)
+                .with_predicate_pushdown_disabled(disable_predicate_pushdown)
+                .with_provenance(source, original_sql),
             self.dialect,
         )
         .with_schema_search_path(schema_search_path.clone());
@@ -1053,7 +1114,8 @@ impl NoriaConnector {
                     // expensive, so we fall back to constructing a 'create cache' statement from a
                     // displayed version of the the SelectStatement we already parsed in this case
                     let changelist = ChangeList::from_change(
-                        Change::create_cache(qname.clone(), q.clone(), false),
+                        Change::create_cache(qname.clone(), q.clone(), false)
+                            .with_provenance(QuerySource::Automatic, None),
                         self.dialect,
                     )
                     .with_schema_search_path(search_path);
@@ -1239,11 +1301,11 @@ impl NoriaConnector {
                 }
             }
 
-            for (c, v) in default_value_columns.drain(..) {
+            for (c, v) in &default_value_columns {
                 let idx = schema
                     .fields
                     .iter()
-                    .position(|f| f.column == c)
+                    .position(|f| f.column == *c)
                     .ok_or_else(|| {
                         table_err(
                             table.clone(),
@@ -1251,8 +1313,8 @@ impl NoriaConnector {
                         )
                     })?;
                 // only use default value if query doesn't specify one
-                if !columns_specified.contains(&c) {
-                    buf[ri][idx] = v.try_into()?;
+                if !columns_specified.contains(c) {
+                    buf[ri][idx] = v.clone().try_into()?;
                 }
             }
 
@@ -1307,8 +1369,7 @@ impl NoriaConnector {
             r
         } else {
             trace!("insert::simple");
-            let buf: Vec<_> = buf.into_iter().map(TableOperation::Insert).collect();
-            let r = putter.perform_all(buf).await;
+            let r = putter.insert_many(buf).await;
             trace!("insert::simple::complete");
             r
         };
@@ -1529,6 +1590,7 @@ impl NoriaConnector {
             self.read_behavior,
             self.read_request_handler.as_mut(),
             self.dialect,
+            self.timezone,
         )
         .await;
 
@@ -1620,9 +1682,10 @@ fn build_view_query<'a>(
     ticket: Option<Timestamp>,
     read_behavior: ReadBehavior,
     dialect: Dialect,
+    timezone: Timezone,
 ) -> ReadySetResult<Option<(&'a mut ReaderHandle, ViewQuery)>> {
     let (limit, offset) = processed_query_params.limit_offset_params(params)?;
-    let raw_keys = processed_query_params.make_keys(params)?;
+    let raw_keys = processed_query_params.make_keys(params, dialect, timezone)?;
 
     getter.build_view_query(
         raw_keys,
@@ -1631,6 +1694,7 @@ fn build_view_query<'a>(
         ticket,
         read_behavior.is_blocking(),
         dialect,
+        timezone,
     )
 }
 
@@ -1652,6 +1716,7 @@ async fn do_read<'a>(
     read_behavior: ReadBehavior,
     read_request_handler: Option<&'a mut ReadRequestHandler>,
     dialect: Dialect,
+    timezone: Timezone,
 ) -> ReadySetResult<ReadResult<'a>> {
     let (reader_handle, vq) = match build_view_query(
         getter,
@@ -1660,6 +1725,7 @@ async fn do_read<'a>(
         ticket,
         read_behavior,
         dialect,
+        timezone,
     )? {
         Some(res) => res,
         None => return Err(ReadySetError::NoCacheForQuery),
@@ -24,7 +24,7 @@ use anyhow::anyhow;
 use clap::ValueEnum;
 
 pub use crate::backend::{Backend, BackendBuilder};
-pub use crate::query_handler::{QueryHandler, SetBehavior};
+pub use crate::query_handler::{QueryHandler, SessionVariableChanges, SetBehavior};
 pub use crate::status_reporter::{ReadySetStatus, ReadySetStatusReporter};
 pub use crate::upstream_database::{
     UpstreamConfig, UpstreamDatabase, UpstreamDestination, UpstreamPrepare,
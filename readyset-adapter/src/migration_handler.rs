@@ -13,6 +13,7 @@ use metrics::{counter, register_counter, Counter};
 use nom_sql::{DialectDisplay, Literal};
 use readyset_client::query::{MigrationState, Query, QueryId};
 use readyset_client::recipe::changelist::{Change, ChangeList};
+use readyset_client::recipe::QuerySource;
 use readyset_client::{PlaceholderIdx, ReadySetHandle, ViewCreateRequest};
 use readyset_client_metrics::recorded;
 use readyset_data::DfValue;
@@ -180,6 +181,10 @@ impl MigrationHandler {
                         Some(query.query().schema_search_path.clone()),
                         /* always */ false,
                         /* concurrently */ false,
+                        /* frontier_strategy */ None,
+                        /* disable_predicate_pushdown */ false,
+                        QuerySource::Automatic,
+                        None,
                     )
                     .await;
                 // Inform the query status cache of completed migrations
@@ -302,6 +307,10 @@ impl MigrationHandler {
                 Some(view_request.schema_search_path.clone()),
                 false,
                 false,
+                None,
+                false,
+                QuerySource::Automatic,
+                None,
             )
             .await?;
         Ok(())
@@ -1,4 +1,5 @@
 use nom_sql::{SqlIdentifier, SqlQuery};
+use readyset_data::Timezone;
 use readyset_errors::ReadySetResult;
 
 use crate::backend::noria_connector;
@@ -14,6 +15,31 @@ pub enum SetBehavior {
     SetAutocommit(bool),
     /// This `SET` statement represents the current schema search path being changed
     SetSearchPath(Vec<SqlIdentifier>),
+    /// This `SET` statement changes session variables which affect the results of queries. The
+    /// statement is still proxied upstream
+    SetSessionVariables(SessionVariableChanges),
+    /// This `SET` statement represents read-your-writes consistency being enabled or disabled for
+    /// the current session. This only affects ReadySet, so the statement is never proxied upstream
+    SetReadYourWrites(bool),
+}
+
+/// Changes made by a `SET` statement to the session variables which affect the results of queries
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionVariableChanges {
+    /// The new timezone of the session, which timestamps with a timezone are returned in
+    pub timezone: Option<Timezone>,
+    /// The (lowercase) names of the other variables which were set, along with whether they were
+    /// set to a value that ReadySet returns results consistent with. While any variable is set to
+    /// a value it doesn't, reads in the session are proxied upstream.
+    pub compatibility: Vec<(SqlIdentifier, bool)>,
+}
+
+impl SessionVariableChanges {
+    /// Returns true if any variable was set to a value ReadySet doesn't return results consistent
+    /// with
+    pub fn any_incompatible(&self) -> bool {
+        self.compatibility.iter().any(|(_, compatible)| !compatible)
+    }
 }
 
 impl SetBehavior {
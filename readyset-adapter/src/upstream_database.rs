@@ -190,6 +190,15 @@ pub trait UpstreamDatabase: Sized + Send {
     where
         S: AsRef<str> + Send + Sync + 'a;
 
+    /// Execute a prepared write statement with the given params, constructing and returning a RYW
+    /// ticket for the write
+    async fn execute_ryw_write<'a>(
+        &'a mut self,
+        statement_id: u32,
+        params: &[DfValue],
+        exec_meta: Self::ExecMeta<'_>,
+    ) -> Result<(Self::QueryResult<'a>, String), Self::Error>;
+
     /// Handle starting a transaction with the upstream database.
     async fn start_tx<'a>(
         &'a mut self,
@@ -350,6 +359,18 @@ where
         self.upstream().await?.handle_ryw_write(query).await
     }
 
+    async fn execute_ryw_write<'a>(
+        &'a mut self,
+        statement_id: u32,
+        params: &[DfValue],
+        exec_meta: Self::ExecMeta<'_>,
+    ) -> Result<(Self::QueryResult<'a>, String), Self::Error> {
+        self.upstream()
+            .await?
+            .execute_ryw_write(statement_id, params, exec_meta)
+            .await
+    }
+
     async fn start_tx<'a>(
         &'a mut self,
         stmt: &StartTransactionStatement,
@@ -2,7 +2,7 @@
 //! which ReadySet worker acts as the controller, which ReadySet workers exist, detecting failed
 //! workers which necessitate changes, and storing cluster wide global state.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::{self, Display};
 use std::net::SocketAddr;
 use std::str::FromStr;
@@ -36,9 +36,18 @@ use crate::ControllerDescriptor;
 // LeaderPayload must be Serialize + DeserializeOwned + PartialEq
 type LeaderPayload = ControllerDescriptor;
 
+/// Identifier for the persistent volume a worker stores base table data on, such as the name of
+/// the Kubernetes PersistentVolumeClaim mounted at its storage directory
 pub type VolumeId = String;
 pub type WorkerId = String;
 
+/// Key/value labels describing a worker, such as the availability zone it runs in
+pub type WorkerLabels = BTreeMap<String, String>;
+
+/// The label under which workers report the availability zone they run in. This matches the
+/// well-known label Kubernetes sets on nodes, so the zone can be copied over as-is.
+pub const ZONE_LABEL: &str = "topology.kubernetes.io/zone";
+
 const CACHE_DDL_REQUESTS_PATH: &str = "cache_ddl_requests";
 const PERSISTENT_STATS_PATH: &str = "persistent_stats";
 const SCHEMA_REPLICATION_OFFSET_PATH: &str = "schema_replication_offset";
@@ -97,6 +106,73 @@ pub struct WorkerSchedulingConfig {
     /// Configuration for how domains containing or not containing reader nodes may be scheduled
     /// onto this worker
     pub reader_nodes: NodeTypeSchedulingRestriction,
+    /// Labels describing this worker, such as the [zone][ZONE_LABEL] it runs in. Domains with
+    /// placement restrictions are only scheduled onto workers whose labels match the
+    /// restrictions' [`LabelSelector`]s.
+    #[serde(default)]
+    pub labels: WorkerLabels,
+}
+
+/// Parse a comma-separated list of `key=value` pairs into a set of [`WorkerLabels`]
+pub fn parse_labels(s: &str) -> Result<WorkerLabels, String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+        .map(|label| match label.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                Ok((key.trim().to_owned(), value.trim().to_owned()))
+            }
+            _ => Err(format!("Invalid label {label:?}, expected key=value")),
+        })
+        .collect()
+}
+
+/// A set of labels which a worker must have, each with the given value, to match the selector.
+///
+/// The empty selector matches every worker.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct LabelSelector(WorkerLabels);
+
+impl LabelSelector {
+    /// Returns `true` if the selector doesn't require any labels
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Require workers to have the label `key` with the given `value`, replacing any value
+    /// previously required for `key`
+    pub fn insert(&mut self, key: String, value: String) {
+        self.0.insert(key, value);
+    }
+
+    /// Returns `true` if a worker with the given `labels` matches this selector
+    pub fn matches(&self, labels: &WorkerLabels) -> bool {
+        self.0
+            .iter()
+            .all(|(key, value)| labels.get(key) == Some(value))
+    }
+
+    /// Returns `true` if no worker can match both this selector and `other`, because they require
+    /// different values for the same label
+    pub fn conflicts_with(&self, other: &LabelSelector) -> bool {
+        self.0
+            .iter()
+            .any(|(key, value)| other.0.get(key).map_or(false, |v| v != value))
+    }
+}
+
+impl From<WorkerLabels> for LabelSelector {
+    fn from(labels: WorkerLabels) -> Self {
+        Self(labels)
+    }
+}
+
+impl FromStr for LabelSelector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_labels(s).map(Self)
+    }
 }
 
 /// Initial registration request body, sent from workers to controllers.
@@ -399,3 +475,42 @@ impl Compressor {
         comp.0.finish().expect("Can't fail")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_label_selector() {
+        let selector: LabelSelector = "topology.kubernetes.io/zone=us-east-1a, tier = base"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            selector,
+            LabelSelector::from(WorkerLabels::from([
+                (ZONE_LABEL.to_owned(), "us-east-1a".to_owned()),
+                ("tier".to_owned(), "base".to_owned()),
+            ]))
+        );
+        assert!("".parse::<LabelSelector>().unwrap().is_empty());
+        "tier".parse::<LabelSelector>().unwrap_err();
+        "=base".parse::<LabelSelector>().unwrap_err();
+    }
+
+    #[test]
+    fn label_selector_matches() {
+        let selector: LabelSelector = "zone=a,tier=base".parse().unwrap();
+        assert!(selector.matches(&parse_labels("zone=a,tier=base,extra=1").unwrap()));
+        assert!(!selector.matches(&parse_labels("zone=a").unwrap()));
+        assert!(!selector.matches(&parse_labels("zone=b,tier=base").unwrap()));
+        assert!(LabelSelector::default().matches(&WorkerLabels::new()));
+    }
+
+    #[test]
+    fn label_selector_conflicts() {
+        let selector: LabelSelector = "zone=a,tier=base".parse().unwrap();
+        assert!(!selector.conflicts_with(&"zone=a,disk=ssd".parse().unwrap()));
+        assert!(selector.conflicts_with(&"zone=b".parse().unwrap()));
+        assert!(!selector.conflicts_with(&LabelSelector::default()));
+    }
+}
@@ -4,15 +4,17 @@ use std::future::Future;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::task::{Context, Poll};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 use futures_util::future;
 use hyper::client::HttpConnector;
-use nom_sql::{NonReplicatedRelation, Relation};
+use nom_sql::{Column, NonReplicatedRelation, Relation, SqlIdentifier};
 use parking_lot::RwLock;
 use petgraph::graph::NodeIndex;
+use readyset_data::DfValue;
 use readyset_errors::{
-    internal, internal_err, rpc_err, rpc_err_no_downcast, ReadySetError, ReadySetResult,
+    internal, internal_err, rpc_err, rpc_err_no_downcast, unsupported_err, ReadySetError,
+    ReadySetResult,
 };
 use readyset_sql_passes::adapter_rewrites::AdapterRewriteParams;
 use replication_offset::ReplicationOffsets;
@@ -23,19 +25,33 @@ use tower::ServiceExt;
 use tower_service::Service;
 use tracing::{debug, trace};
 use url::Url;
+use vec1::Vec1;
 
 use crate::consensus::{Authority, AuthorityControl};
-use crate::debug::info::{GraphInfo, MaterializationInfo, NodeSize};
+use crate::consistency::Timestamp;
+use crate::debug::info::{
+    GraphInfo, KeyTrace, MaterializationInfo, MigrationPlanSummary, NodeSize, SlowReplay,
+    ViewHotKeys, ViewMetrics, ViewReplayPaths,
+};
 use crate::debug::stats;
 use crate::internal::{DomainIndex, ReplicaAddress};
 use crate::metrics::MetricsDump;
 use crate::recipe::changelist::ChangeList;
-use crate::recipe::{CacheExpr, ExtendRecipeResult, ExtendRecipeSpec, MigrationStatus};
-use crate::status::ReadySetControllerStatus;
+use crate::recipe::{
+    CacheExpr, DeactivatedView, ExtendRecipeResult, ExtendRecipeSpec, MigrationProgress,
+    MigrationStatus, QueryLogEntry, RecipeVersion, RecipeVersionsInfo,
+};
+use crate::replication_namespace::ReplicationNamespace;
+use crate::results::ResultIterator;
+use crate::retry::RetryPolicy;
+use crate::runtime_config::{RuntimeConfigChange, RuntimeConfigUpdate};
+use crate::schema::SchemaRegistry;
+use crate::status::{ReadySetControllerStatus, RecoveryStatus, ReplicationPauseState};
 use crate::table::{PersistencePoint, Table, TableBuilder, TableRpc};
-use crate::view::{View, ViewBuilder, ViewRpc};
+use crate::view::{AsOf, View, ViewBuilder, ViewQuery, ViewRpc};
 use crate::{
-    ReplicationOffset, SingleKeyEviction, TableStatus, ViewCreateRequest, ViewFilter, ViewRequest,
+    ColumnProvenance, KeyComparison, ReplicationOffset, SingleKeyEviction, TableStatus,
+    ViewCreateRequest, ViewFilter, ViewRateLimit, ViewRequest, ViewResultLimit, ViewStaleReads,
 };
 
 mod rpc;
@@ -333,6 +349,8 @@ pub struct ReadySetHandle {
     views: Arc<Mutex<HashMap<(SocketAddr, usize), ViewRpc>>>,
     request_timeout: Option<Duration>,
     migration_timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    replication_namespace: ReplicationNamespace,
 }
 
 /// Define a simple RPC request wrapper for the controller, which queries the same RPC endpoint as
@@ -378,6 +396,8 @@ impl ReadySetHandle {
             }),
             request_timeout,
             migration_timeout,
+            retry_policy: Default::default(),
+            replication_namespace: Default::default(),
         }
     }
 
@@ -394,9 +414,33 @@ impl ReadySetHandle {
             handle: tower::util::Either::B(RawController::new(url, request_timeout)),
             request_timeout,
             migration_timeout,
+            retry_policy: Default::default(),
+            replication_namespace: Default::default(),
         }
     }
 
+    /// Set the [`RetryPolicy`] used for idempotent requests made through all [`View`]s and
+    /// [`Table`]s subsequently obtained from this handle.
+    ///
+    /// Returns an error if the policy is invalid.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) -> ReadySetResult<()> {
+        policy.validate()?;
+        self.retry_policy = policy;
+        Ok(())
+    }
+
+    /// Set the [`ReplicationNamespace`] of the upstream database that a replicator using this
+    /// handle is replicating from.
+    ///
+    /// All relations, changes and replication offsets subsequently sent or received by the
+    /// replication-related methods on this handle (such as [`Self::table`], [`Self::tables`],
+    /// [`Self::extend_recipe`] and [`Self::replication_offsets`]) are mapped between their names
+    /// in the upstream database and their names in ReadySet, and restricted to those belonging to
+    /// that upstream database.
+    pub fn set_replication_namespace(&mut self, replication_namespace: ReplicationNamespace) {
+        self.replication_namespace = replication_namespace;
+    }
+
     /// Check that the `ReadySetHandle` can accept another request.
     ///
     /// Note that this method _must_ return `Poll::Ready` before any other methods that return
@@ -456,7 +500,11 @@ impl ReadySetHandle {
     ///
     /// These have all been created in response to a `CREATE TABLE` statement in a recipe.
     pub async fn tables(&mut self) -> ReadySetResult<BTreeMap<Relation, NodeIndex>> {
-        self.simple_post_request("tables").await
+        let tables: BTreeMap<Relation, NodeIndex> = self.simple_post_request("tables").await?;
+        Ok(tables
+            .into_iter()
+            .filter_map(|(name, ni)| Some((self.replication_namespace.to_upstream(&name)?, ni)))
+            .collect())
     }
 
     /// Query the status of all known tables, including those not replicated by ReadySet
@@ -494,6 +542,14 @@ impl ReadySetHandle {
         self.simple_post_request("verbose_views").await
     }
 
+    simple_request!(
+        /// Describe the schemas of all known base tables and views, including the names, SQL types
+        /// and nullability of their columns and which of their columns are keys.
+        ///
+        /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+        schema() -> SchemaRegistry
+    );
+
     simple_request!(
         /// For each of the given list of queries, determine whether that query (or a semantically
         /// equivalent query) has been created as a `View`.
@@ -551,6 +607,52 @@ impl ReadySetHandle {
         })
     }
 
+    /// Look up each of the given keys in the corresponding view, returning the results in the same
+    /// order as `reads`, all read as of the same point so that they're consistent with each other.
+    ///
+    /// If `ticket` is given, each read waits until its view reflects all the writes covered by the
+    /// ticket, then returns the state of the view at the point it first did so, meaning every read
+    /// observes exactly the same set of writes. Otherwise, every read returns the state of its view
+    /// as of the same wall-clock time, which is only consistent up to how quickly each view
+    /// applies writes.
+    ///
+    /// Every view must have history retention configured with
+    /// [`set_view_history_retention`](Self::set_view_history_retention), otherwise the reads fail
+    /// with [`ReadySetError::ViewHistoryUnavailable`].
+    pub async fn consistent_read(
+        &mut self,
+        reads: Vec<(Relation, Vec<DfValue>)>,
+        ticket: Option<Timestamp>,
+    ) -> ReadySetResult<Vec<ResultIterator>> {
+        let as_of = match &ticket {
+            Some(ticket) => AsOf::Timestamp(ticket.clone()),
+            None => AsOf::Time(SystemTime::now()),
+        };
+
+        let mut lookups = Vec::with_capacity(reads.len());
+        for (name, key) in reads {
+            let mut view = self
+                .view(name.clone())
+                .await?
+                .into_reader_handle()
+                .ok_or_else(|| {
+                    unsupported_err!(
+                        "Consistent reads against reused view {} are not supported",
+                        name.display_unquoted()
+                    )
+                })?;
+            let key = Vec1::try_from_vec(key).map_err(|_| ReadySetError::EmptyKey)?;
+            let query = ViewQuery {
+                timestamp: ticket.clone(),
+                as_of: Some(as_of.clone()),
+                ..(vec![KeyComparison::Equal(key)], true).into()
+            };
+            lookups.push(async move { view.raw_lookup(query).await });
+        }
+
+        future::try_join_all(lookups).await
+    }
+
     /// Obtain a 'ViewBuilder' for a specific view that allows you to build a view.
     ///
     /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
@@ -597,8 +699,11 @@ impl ReadySetHandle {
             } else {
                 None
             };
-            let view_builder = self.view_builder(view_request).await?;
-            view_builder.build(replica, views).await
+            let view_builder = self.view_builder(view_request.clone()).await?;
+            let mut view = view_builder.build(replica, views).await?;
+            view.set_retry_policy(self.retry_policy.clone())?;
+            view.set_resolver(self.clone(), view_request);
+            Ok(view)
         }
     }
 
@@ -610,7 +715,7 @@ impl ReadySetHandle {
     where
         N: Into<Relation>,
     {
-        let name = name.into();
+        let name = self.replication_namespace.to_local(&name.into());
         async move {
             self.request_table(ControllerRequest::new(
                 "table_builder",
@@ -657,7 +762,11 @@ impl ReadySetHandle {
             match bincode::deserialize::<ReadySetResult<Option<TableBuilder>>>(&body)?
                 .map_err(|e| rpc_err_no_downcast("ReadySetHandle::table", e))?
             {
-                Some(tb) => Ok(Some(tb.build(domains).await)),
+                Some(tb) => {
+                    let mut table = tb.build(domains).await;
+                    table.set_retry_policy(self.retry_policy.clone())?;
+                    Ok(Some(table))
+                }
                 None => Ok(None),
             }
         }
@@ -689,6 +798,75 @@ impl ReadySetHandle {
         self.rpc("dry_run", request, self.migration_timeout)
     }
 
+    /// Plan the migration required to apply the given set of changes, including lowering any new
+    /// queries to dataflow, without applying it, and return a description of the nodes and
+    /// domains it would add and the replays it would perform.
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn plan_migration(
+        &mut self,
+        changes: ChangeList,
+    ) -> impl Future<Output = ReadySetResult<MigrationPlanSummary>> + '_ {
+        let request = ExtendRecipeSpec::from(changes);
+
+        self.rpc("plan_migration", request, self.migration_timeout)
+    }
+
+    /// Stage a set of view and cache additions and removals as a new version of the recipe,
+    /// without applying them. The changes are validated as if they were being applied, and an
+    /// error is returned without staging anything if they are invalid.
+    ///
+    /// Only `CREATE VIEW`, `CREATE CACHE` and drops of views and caches may be staged. The
+    /// returned version can later be applied atomically via [`Self::activate_recipe_version`].
+    pub fn stage_recipe_version(
+        &mut self,
+        changes: ChangeList,
+    ) -> impl Future<Output = ReadySetResult<RecipeVersion>> + '_ {
+        let request = ExtendRecipeSpec::from(changes);
+
+        self.rpc("stage_recipe_version", request, self.migration_timeout)
+    }
+
+    /// Apply all of the changes in a version of the recipe previously staged via
+    /// [`Self::stage_recipe_version`] in a single migration.
+    pub fn activate_recipe_version(
+        &mut self,
+        version: RecipeVersion,
+    ) -> impl Future<Output = ReadySetResult<()>> + '_ {
+        self.rpc("activate_recipe_version", version, self.migration_timeout)
+    }
+
+    /// Roll the recipe back to the given version, which must be either [`RecipeVersion`] `0` or a
+    /// version that has been activated, in a single migration. Views and caches added by all
+    /// versions activated since that version are removed, and views and caches removed by those
+    /// versions are restored.
+    pub fn rollback_to(
+        &mut self,
+        version: RecipeVersion,
+    ) -> impl Future<Output = ReadySetResult<()>> + '_ {
+        self.rpc("rollback_to", version, self.migration_timeout)
+    }
+
+    /// Deactivate the cache with the given name, removing its dataflow nodes to free the memory
+    /// used by their state while keeping its definition, so that it can later be re-created via
+    /// [`Self::reactivate_view`].
+    pub fn deactivate_view(
+        &mut self,
+        view: &Relation,
+    ) -> impl Future<Output = ReadySetResult<()>> + '_ {
+        self.rpc("deactivate_view", view, self.migration_timeout)
+    }
+
+    /// Re-create the previously deactivated cache with the given name from its definition,
+    /// migrating it using the given SQL dialect.
+    pub fn reactivate_view(
+        &mut self,
+        view: &Relation,
+        dialect: dataflow_expression::Dialect,
+    ) -> impl Future<Output = ReadySetResult<()>> + '_ {
+        self.rpc("reactivate_view", (view, dialect), self.migration_timeout)
+    }
+
     /// Extend the existing recipe with the given set of queries.
     ///
     /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
@@ -696,7 +874,8 @@ impl ReadySetHandle {
         &mut self,
         changes: ChangeList,
     ) -> impl Future<Output = ReadySetResult<()>> + '_ {
-        let request = ExtendRecipeSpec::from(changes);
+        let request =
+            ExtendRecipeSpec::from(self.replication_namespace.changelist_to_local(changes));
 
         async move {
             match self
@@ -722,6 +901,28 @@ impl ReadySetHandle {
         self.rpc::<_, MigrationStatus>("migration_status", migration_id, self.request_timeout)
     }
 
+    /// Query the progress of a pending migration identified by the given `migration_id`.
+    pub fn migration_progress(
+        &mut self,
+        migration_id: u64,
+    ) -> impl Future<Output = ReadySetResult<MigrationProgress>> + '_ {
+        self.rpc::<_, MigrationProgress>("migration_progress", migration_id, self.request_timeout)
+    }
+
+    /// Cancel a pending migration identified by the given `migration_id`.
+    ///
+    /// If the migration is still waiting to run, it is removed from the queue. If it's already
+    /// running, it's stopped at the next opportunity and any nodes it has already added to the
+    /// graph are removed. Either way, the migration's status becomes
+    /// [`MigrationStatus::Failed`] with [`ReadySetError::MigrationCancelled`]. Migrations which
+    /// have already finished are unaffected.
+    pub fn cancel_migration(
+        &mut self,
+        migration_id: u64,
+    ) -> impl Future<Output = ReadySetResult<()>> + '_ {
+        self.rpc("cancel_migration", migration_id, self.request_timeout)
+    }
+
     /// Asynchronous version of extend_recipe(). The Controller should immediately return an ID that
     /// can be used to query the migration status.
     pub fn extend_recipe_async(
@@ -751,7 +952,10 @@ impl ReadySetHandle {
     ) -> impl Future<Output = ReadySetResult<()>> + '_ {
         let request = ExtendRecipeSpec {
             require_leader_ready: false,
-            ..changes.into()
+            ..self
+                .replication_namespace
+                .changelist_to_local(changes)
+                .into()
         };
 
         self.rpc("extend_recipe", request, self.migration_timeout)
@@ -767,8 +971,9 @@ impl ReadySetHandle {
         require_leader_ready: bool,
     ) -> impl Future<Output = ReadySetResult<()>> + '_ {
         let request = ExtendRecipeSpec {
-            changes,
+            changes: self.replication_namespace.changelist_to_local(changes),
             replication_offset: Some(Cow::Borrowed(replication_offset)),
+            replication_namespace: self.replication_namespace.namespace().cloned(),
             require_leader_ready,
             concurrently: false,
         };
@@ -790,14 +995,38 @@ impl ReadySetHandle {
         remove_all_queries()
     );
 
-    simple_request!(
-        /// Set the replication offset for the schema, which is stored with the recipe.
-        ///
-        /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
-        set_schema_replication_offset(
-            replication_offset: Option<&ReplicationOffset>,
-        ) -> ()
-    );
+    /// Set the replication offset for the schema, which is stored with the recipe. If this handle
+    /// has a [`ReplicationNamespace`] for an additional upstream database, sets the offset for the
+    /// schema of that upstream database instead.
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn set_schema_replication_offset(
+        &mut self,
+        replication_offset: Option<&ReplicationOffset>,
+    ) -> impl Future<Output = ReadySetResult<()>> + '_ {
+        let namespace = self.replication_namespace.namespace().cloned();
+        let replication_offset = replication_offset.cloned();
+        async move {
+            match namespace {
+                None => {
+                    self.rpc(
+                        "set_schema_replication_offset",
+                        replication_offset,
+                        self.request_timeout,
+                    )
+                    .await
+                }
+                Some(namespace) => {
+                    self.rpc(
+                        "set_namespace_schema_replication_offset",
+                        (namespace, replication_offset),
+                        self.request_timeout,
+                    )
+                    .await
+                }
+            }
+        }
+    }
 
     simple_request!(
         /// Fetch a graphviz description of the dataflow graph.
@@ -872,21 +1101,211 @@ impl ReadySetHandle {
     );
 
     simple_request!(
-        /// Get the url of the current noria controller.
+        /// Report diagnostic information about how the given key is served by the view with the
+        /// given name, including which replay paths would be used to fill a miss on the key,
+        /// which nodes along those paths hold state, whether the key is currently materialized,
+        /// and timing information about the most recent miss on the key.
+        trace_key(view: Relation, key: Vec<DfValue>,) -> KeyTrace
+    );
+
+    simple_request!(
+        /// Report usage counters for the view with the given name, summed across all of its reader
+        /// shards and replicas: how many reads against the view hit and missed, how often keys were
+        /// evicted from it, and how long it took on average to fill a key after a miss.
+        view_metrics(view: &Relation) -> ViewMetrics
+    );
+
+    simple_request!(
+        /// Report the replay paths which can be used to fill misses in the view with the given
+        /// name, including those into partial materializations upstream of its reader, the domains
+        /// they traverse, and running totals of the rows and bytes replayed into the view. This can
+        /// be used to attribute the cost of misses to individual caches.
+        view_replay_paths(view: &Relation) -> ViewReplayPaths
+    );
+
+    simple_request!(
+        /// Report the keys read most often from the view with the given name, combined across all
+        /// of its reader shards and replicas, along with how many of the reads of each key missed.
+        /// Keys are tracked with a space-bounded heavy hitters sketch, so the counts for each key
+        /// are approximate.
+        view_hot_keys(view: &Relation) -> ViewHotKeys
+    );
+
+    simple_request!(
+        /// Replace the limits on the rate of reads against the view with the given name. Reads
+        /// which exceed the limits are rejected with [`ReadySetError::ViewThrottled`].
         ///
-        /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
-        controller_uri() -> Url
+        /// Passing [`ViewRateLimit::default()`] removes all limits on the view.
+        set_view_rate_limit(view: &Relation, limit: ViewRateLimit,) -> ()
+    );
+
+    simple_request!(
+        /// Set how long the view with the given name retains the changes made to it, allowing reads
+        /// against the view to be answered as of any time within that window using
+        /// [`ReaderHandle::lookup_at`](crate::ReaderHandle::lookup_at).
+        ///
+        /// Passing `None` disables as-of reads against the view.
+        set_view_history_retention(view: &Relation, retention: Option<Duration>,) -> ()
+    );
+
+    simple_request!(
+        /// Configure whether reads against the view with the given name may be answered with
+        /// last-known, possibly-stale values while keys are being replayed or while replication
+        /// from the upstream database is down, rather than blocking or erroring. Stale results are
+        /// flagged with [`ReadReplyStats::stale`](crate::ReadReplyStats::stale).
+        set_view_stale_reads(view: &Relation, stale_reads: ViewStaleReads,) -> ()
+    );
+
+    simple_request!(
+        /// Limit the number of rows and bytes returned by each read against the view with the given
+        /// name. Results exceeding the limit are cut short and flagged with
+        /// [`ReadReplyStats::truncated`](crate::ReadReplyStats::truncated).
+        set_view_result_limit(view: &Relation, limit: ViewResultLimit,) -> ()
     );
 
     simple_request!(
-        /// Get a set of all replication offsets for the entire system.
+        /// Set how long reads against the view with the given name wait for keys which miss to be
+        /// filled by upqueries before failing with [`ReadySetError::UpqueryTimeout`]. Reads can
+        /// override this with [`ViewQuery::upquery_timeout`](crate::ViewQuery::upquery_timeout).
         ///
-        /// See [the documentation for
-        /// PersistentState](::readyset_dataflow::state::persistent_state) for more information
-        /// about replication offsets.
-        replication_offsets() -> ReplicationOffsets
+        /// Passing `None` reverts the view to the server's default upquery timeout.
+        set_view_upquery_timeout(view: &Relation, timeout: Option<Duration>,) -> ()
+    );
+
+    simple_request!(
+        /// Maintain secondary indices on each of the given sets of columns of the (fully
+        /// materialized) view with the given name, replacing any existing secondary indices. The
+        /// view can then be looked up by those columns with
+        /// [`View::lookup_by_index`](crate::View::lookup_by_index).
+        set_view_secondary_indices(view: &Relation, indices: Vec<Vec<SqlIdentifier>>,) -> ()
+    );
+
+    simple_request!(
+        /// Get the most recent replays to fill misses in views which took longer than the
+        /// configured slow replay threshold, oldest first, along with the replay path each replay
+        /// took and how long each domain along that path spent handling it.
+        ///
+        /// Always empty unless the server was started with a slow replay threshold.
+        slow_replays() -> Vec<SlowReplay>
+    );
+
+    simple_request!(
+        /// Get the currently active version of the recipe, along with the versions which have been
+        /// activated and can be rolled back to, and the versions which are staged.
+        recipe_versions() -> RecipeVersionsInfo
+    );
+
+    simple_request!(
+        /// List the caches which have been deactivated, either explicitly or automatically for
+        /// having a low hit rate while memory was constrained, and can be reactivated.
+        deactivated_views() -> Vec<DeactivatedView>
+    );
+
+    simple_request!(
+        /// List all the cached queries currently installed in the recipe, in the order they were
+        /// installed, along with what caused each to be installed, its SQL before and after any
+        /// rewrites, and the rewrite decisions made for it.
+        list_queries() -> Vec<QueryLogEntry>
+    );
+
+    simple_request!(
+        /// Report where each output column of the view or cached query with the given name is
+        /// derived from: the base table columns its values are computed from, and the expressions
+        /// and aggregates applied to them along the way.
+        column_provenance(view: &Relation) -> Vec<ColumnProvenance>
+    );
+
+    simple_request!(
+        /// List the names of all views and cached queries which reference the given base table
+        /// column anywhere, either in their output or to compute their results (for example in a
+        /// filter or join key), and so would be affected by altering or dropping that column
+        /// upstream.
+        column_dependents(column: &Column) -> Vec<Relation>
     );
 
+    simple_request!(
+        /// Update a subset of the configuration of the deployment without restarting it,
+        /// propagating the update to all workers and domains. Returns an error without changing
+        /// anything if the update is invalid.
+        update_runtime_config(update: RuntimeConfigUpdate) -> ()
+    );
+
+    simple_request!(
+        /// Get the audit log of updates made to the runtime configuration of the deployment via
+        /// [`Self::update_runtime_config`] since the current controller became the leader, oldest
+        /// first.
+        runtime_config_changes() -> Vec<RuntimeConfigChange>
+    );
+
+    simple_request!(
+        /// Pause replication from the upstream database, for example to quiesce ReadySet during
+        /// maintenance on the upstream. Replication finishes applying any in-flight transaction
+        /// before pausing, and records the replication offset it paused at.
+        ///
+        /// Waits a bounded amount of time for replication to pause, then returns the resulting
+        /// [`ReplicationPauseState`]. If replication hasn't yet paused by then, this returns
+        /// [`ReplicationPauseState::Pausing`], and replication will still pause as soon as it can.
+        pause_replication() -> ReplicationPauseState
+    );
+
+    simple_request!(
+        /// Resume replication from the upstream database after it was paused via
+        /// [`Self::pause_replication`], picking up from the offset it was paused at.
+        resume_replication() -> ()
+    );
+
+    simple_request!(
+        /// Get whether replication from the upstream database is currently paused.
+        replication_pause_state() -> ReplicationPauseState
+    );
+
+    simple_request!(
+        /// Get the progress of recovering domains which were running on workers that have failed,
+        /// and how many workers have failed since the current leader was elected.
+        recovery_status() -> RecoveryStatus
+    );
+
+    simple_request!(
+        /// Request that the base table with the given name be dropped and snapshotted again from
+        /// scratch from the upstream database, for example after its contents in ReadySet have
+        /// diverged from the upstream. Returns once the request has been passed on to the
+        /// replicator, which drops the table and restarts replication to snapshot it. Any caches
+        /// which read from the table are dropped along with it, and must be recreated once the
+        /// table has been snapshotted.
+        resnapshot_table(table: &Relation) -> ()
+    );
+
+    simple_request!(
+        /// Stop scheduling domains onto the worker with the given URI, and move all the domain
+        /// replicas running on it onto other workers, for example before taking the worker down
+        /// for maintenance. Returns the number of domain replicas which were moved.
+        drain_worker(worker: &Url) -> usize
+    );
+
+    simple_request!(
+        /// Get the url of the current noria controller.
+        ///
+        /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+        controller_uri() -> Url
+    );
+
+    /// Get a set of all replication offsets for the entire system, restricted to those for the
+    /// upstream database identified by this handle's [`ReplicationNamespace`].
+    ///
+    /// See [the documentation for
+    /// PersistentState](::readyset_dataflow::state::persistent_state) for more information
+    /// about replication offsets.
+    pub fn replication_offsets(
+        &mut self,
+    ) -> impl Future<Output = ReadySetResult<ReplicationOffsets>> + '_ {
+        async move {
+            let offsets: ReplicationOffsets = self
+                .rpc("replication_offsets", (), self.request_timeout)
+                .await?;
+            Ok(self.replication_namespace.restrict_offsets(offsets))
+        }
+    }
+
     simple_request!(
         /// Each base table has an offset up to which data has been persisted to disk, and this
         /// method returns the minimum of those offsets. If no base tables have unpersisted data,
@@ -961,6 +1380,14 @@ impl ReadySetHandle {
         failpoint(name: String, action: String,) -> ()
     );
 
+    #[cfg(feature = "failure_injection")]
+    simple_request!(
+        /// Replace the set of faults injected into packets sent between domains on all workers.
+        ///
+        /// Passing an empty list of faults disables packet fault injection.
+        set_packet_faults(faults: Vec<crate::failpoints::PacketFault>) -> ()
+    );
+
     simple_request!(
         /// Notify the controller that a running domain replica has died
         domain_died(replica_address: ReplicaAddress) -> ()
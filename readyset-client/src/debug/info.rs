@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt::{self, Display};
 use std::ops::{AddAssign, Deref};
+use std::time::{Duration, SystemTime};
 
 use nom_sql::Relation;
 use petgraph::graph::NodeIndex;
@@ -8,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use url::Url;
 
 use crate::internal::*;
+use crate::KeyComparison;
 
 /// [`HashMap`] that has a pair of [`DomainIndex`] and [`usize`] as keys.
 /// Useful since it already implements the Serialization/Deserialization traits.
@@ -76,6 +78,355 @@ pub struct MaterializationInfo {
     pub indexes: HashSet<Index>,
 }
 
+/// Diagnostic information about how a single key in a view is served, returned by
+/// [`ReadySetHandle::trace_key`](crate::ReadySetHandle::trace_key)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyTrace {
+    /// The name of the view
+    pub view: Relation,
+    /// The index of the reader node for the view
+    pub reader: NodeIndex,
+    /// The domain containing the reader node
+    pub domain: DomainIndex,
+    /// Is the reader partially materialized?
+    pub partial: bool,
+    /// Is the key currently materialized in the reader?
+    pub materialized: bool,
+    /// The replay paths that would be used to fill a miss on the key in the reader
+    pub replay_paths: Vec<ReplayPathInfo>,
+    /// Timing information about the most recent miss on the key in the reader, if the reader has
+    /// recently missed on the key
+    pub last_miss: Option<KeyMissInfo>,
+}
+
+/// Information about a single replay path in the graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayPathInfo {
+    /// The tag identifying the replay path
+    pub tag: u32,
+    /// The nodes along the replay path, from the source of the replay to its target
+    pub segments: Vec<ReplayPathSegmentInfo>,
+}
+
+/// Information about a single node along a replay path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayPathSegmentInfo {
+    /// The index of the node
+    pub node_index: NodeIndex,
+    /// The node's name
+    pub node_name: Relation,
+    /// The domain containing the node
+    pub domain: DomainIndex,
+    /// Does the node hold materialized state?
+    pub materialized: bool,
+    /// Is the node's materialization partial?
+    pub partial: bool,
+}
+
+/// Timing information about a miss on a single key in a reader
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyMissInfo {
+    /// How long ago the reader missed on the key
+    pub elapsed: Duration,
+    /// How long it took to fill the key after the miss, or `None` if the replay to fill the key
+    /// has not yet completed
+    pub replay_duration: Option<Duration>,
+}
+
+/// Diagnostic information about a single key in a reader, as reported by the reader's domain
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReaderKeyTrace {
+    /// Is the key currently materialized in the reader?
+    pub materialized: bool,
+    /// Timing information about the most recent miss on the key in the reader, if any
+    pub last_miss: Option<KeyMissInfo>,
+}
+
+/// Usage counters for a single view, returned by
+/// [`ReadySetHandle::view_metrics`](crate::ReadySetHandle::view_metrics)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewMetrics {
+    /// The name of the view
+    pub view: Relation,
+    /// Usage counters for the view, summed across all of its reader shards and replicas
+    pub reader: ReaderMetrics,
+}
+
+impl ViewMetrics {
+    /// The average time it took to fill a key in the view after a miss, or `None` if no misses
+    /// have been filled yet
+    pub fn avg_replay_latency(&self) -> Option<Duration> {
+        self.reader.avg_replay_latency()
+    }
+}
+
+/// The replay paths which can be used to fill misses in a single view, and running totals of the
+/// replays which have filled them, returned by
+/// [`ReadySetHandle::view_replay_paths`](crate::ReadySetHandle::view_replay_paths)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewReplayPaths {
+    /// The name of the view
+    pub view: Relation,
+    /// The index of the reader node for the view
+    pub reader: NodeIndex,
+    /// The replay paths which target the view's reader, followed by the replay paths which target
+    /// partially materialized nodes along those paths (and so may be triggered by misses in the
+    /// reader), transitively
+    pub replay_paths: Vec<ReplayPathInfo>,
+    /// All the domains traversed by any of the replay paths, in ascending order
+    pub domains: Vec<DomainIndex>,
+    /// The number of keys which have been filled by replays after a miss in the view
+    pub replays: u64,
+    /// The total number of rows delivered to the view's reader by replays
+    pub replayed_rows: u64,
+    /// The total in-memory size, in bytes, of the rows delivered to the view's reader by replays
+    pub replayed_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReaderMetrics {
+    /// The number of reads which were served entirely from the reader's state
+    pub hits: u64,
+    /// The number of reads which missed on at least one key
+    pub misses: u64,
+    /// The number of times keys were evicted from the reader's state
+    pub evictions: u64,
+    /// The number of misses on keys which were coalesced into a replay of the same key which was
+    /// already in flight, rather than triggering another
+    pub coalesced_replays: u64,
+    /// The number of keys which have been filled by replays after a miss
+    pub replays: u64,
+    /// The total time it took to fill all of those keys after their misses
+    pub total_replay_time: Duration,
+    /// The total number of rows delivered to the reader by replays, including any which were
+    /// dropped because they were replayed to a key which had already been filled
+    #[serde(default)]
+    pub replayed_rows: u64,
+    /// The total in-memory size, in bytes, of the rows delivered to the reader by replays
+    #[serde(default)]
+    pub replayed_bytes: u64,
+}
+
+impl ReaderMetrics {
+    /// The average time it took to fill a key after a miss, or `None` if no misses have been filled
+    /// yet
+    pub fn avg_replay_latency(&self) -> Option<Duration> {
+        (self.replays > 0).then(|| {
+            Duration::from_nanos((self.total_replay_time.as_nanos() / self.replays as u128) as u64)
+        })
+    }
+}
+
+impl AddAssign for ReaderMetrics {
+    fn add_assign(&mut self, rhs: Self) {
+        self.hits += rhs.hits;
+        self.misses += rhs.misses;
+        self.evictions += rhs.evictions;
+        self.coalesced_replays += rhs.coalesced_replays;
+        self.replays += rhs.replays;
+        self.total_replay_time += rhs.total_replay_time;
+        self.replayed_rows += rhs.replayed_rows;
+        self.replayed_bytes += rhs.replayed_bytes;
+    }
+}
+
+/// A key which was read frequently from a reader, as tracked by the reader's heavy hitters sketch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotKey {
+    /// The key that was read
+    pub key: KeyComparison,
+    /// The number of times the key was read. This may overestimate the true number of reads of
+    /// the key by up to `overestimate`.
+    pub reads: u64,
+    /// The maximum amount by which `reads` may overestimate the number of reads of the key, for
+    /// keys which started being tracked after the sketch was full
+    pub overestimate: u64,
+    /// The number of reads of the key which missed, since the key started being tracked
+    pub misses: u64,
+}
+
+/// The most frequently read keys of a reader, as reported by the reader's domain
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReaderHotKeys {
+    /// The total number of key reads against the reader, including those of keys which aren't
+    /// tracked as hot keys
+    pub total_reads: u64,
+    /// The keys read most often from the reader, most frequent first
+    pub keys: Vec<HotKey>,
+}
+
+impl AddAssign for ReaderHotKeys {
+    /// Combine the hot keys of two shards or replicas of the same reader
+    fn add_assign(&mut self, rhs: Self) {
+        self.total_reads += rhs.total_reads;
+        for hot_key in rhs.keys {
+            match self.keys.iter_mut().find(|k| k.key == hot_key.key) {
+                Some(k) => {
+                    k.reads += hot_key.reads;
+                    k.overestimate += hot_key.overestimate;
+                    k.misses += hot_key.misses;
+                }
+                None => self.keys.push(hot_key),
+            }
+        }
+        self.keys.sort_by(|a, b| b.reads.cmp(&a.reads));
+    }
+}
+
+/// The most frequently read keys of a single view, returned by
+/// [`ReadySetHandle::view_hot_keys`](crate::ReadySetHandle::view_hot_keys)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewHotKeys {
+    /// The name of the view
+    pub view: Relation,
+    /// The hot keys of the view, combined across all of its reader shards and replicas
+    pub reader: ReaderHotKeys,
+}
+
+/// How long a single domain spent handling a partial replay before sending it on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayDomainTiming {
+    /// The domain that handled the replay
+    pub domain: DomainIndex,
+    /// The shard of the domain that handled the replay
+    pub shard: usize,
+    /// How long the domain spent handling the replay
+    pub duration: Duration,
+}
+
+/// A replay to fill a miss in a reader which took longer than the configured slow replay
+/// threshold, as reported by the reader's domain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReaderSlowReplay {
+    /// The index of the reader node that missed
+    pub reader: NodeIndex,
+    /// The name of the reader node that missed
+    pub view: Relation,
+    /// The key that was filled by the replay
+    pub key: KeyComparison,
+    /// The tag of the replay path that filled the key
+    pub tag: u32,
+    /// When the replay completed
+    pub completed_at: SystemTime,
+    /// How long it took to fill the key after the miss
+    pub duration: Duration,
+    /// How long each domain along the replay path spent handling the replay, in the order the
+    /// replay passed through them. Any time not accounted for here was spent waiting to be
+    /// handled or in transit between domains.
+    pub domain_timings: Vec<ReplayDomainTiming>,
+}
+
+/// A replay which took longer than the configured slow replay threshold, returned by
+/// [`ReadySetHandle::slow_replays`](crate::ReadySetHandle::slow_replays)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlowReplay {
+    /// The slow replay, as reported by the reader's domain
+    pub replay: ReaderSlowReplay,
+    /// The replay path used to fill the key, or `None` if the replay path no longer exists
+    pub path: Option<ReplayPathInfo>,
+}
+
+/// A description of the changes a migration would make to the dataflow graph, returned by
+/// [`ReadySetHandle::plan_migration`](crate::ReadySetHandle::plan_migration)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MigrationPlanSummary {
+    /// The nodes the migration would add to the graph
+    pub added_nodes: Vec<PlannedNode>,
+    /// The domains the migration would create
+    pub new_domains: Vec<PlannedDomain>,
+    /// The full replays the migration would perform to populate newly added, fully materialized
+    /// nodes
+    pub replays: Vec<PlannedReplay>,
+    /// Forecasts of the size of the state of each newly added, materialized node
+    pub size_forecasts: Vec<NodeSizeForecast>,
+}
+
+impl MigrationPlanSummary {
+    /// A rough estimate of how much new materialized state the migration would add, in bytes.
+    ///
+    /// This is the sum of the current sizes of the sources of all full replays the migration
+    /// would perform, so doesn't account for nodes that reduce the size of their input (such as
+    /// filters and aggregates), replays from nodes which are themselves new, or partially
+    /// materialized nodes, which start out empty and fill on demand.
+    pub fn estimated_new_state_bytes(&self) -> usize {
+        self.replays
+            .iter()
+            .filter_map(|replay| replay.estimated_size)
+            .map(|size| size.bytes.0)
+            .sum()
+    }
+
+    /// The total forecast size of the state of all the materialized nodes the migration would add,
+    /// in bytes.
+    ///
+    /// Unlike [`Self::estimated_new_state_bytes`], this accounts for the effect of each node on
+    /// the size of its input, and includes partially materialized nodes at the size they would
+    /// reach if every key were filled - so for caches which are only ever read for a subset of
+    /// their keys, this is an upper bound.
+    pub fn forecast_state_bytes(&self) -> usize {
+        self.size_forecasts
+            .iter()
+            .map(|forecast| forecast.estimated_size.bytes.0)
+            .sum()
+    }
+}
+
+/// A forecast of the size of the state of a node which would be materialized by a migration,
+/// estimated from the sizes of the base tables it reads from and samples of the cardinality of
+/// their columns
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeSizeForecast {
+    /// The index the node would have
+    pub node_index: NodeIndex,
+    /// The node's name
+    pub node_name: Relation,
+    /// Would the node's materialization be partial? If so, the forecast is of the size of the
+    /// node's state if every key were filled.
+    pub partial: bool,
+    /// The estimated number of rows and size in bytes of the node's state
+    pub estimated_size: NodeSize,
+}
+
+/// A node which would be added to the graph by a migration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedNode {
+    /// The index the node would have
+    pub node_index: NodeIndex,
+    /// The node's name
+    pub node_name: Relation,
+    /// A string description of the node
+    pub node_description: String,
+    /// The domain the node would be placed in
+    pub domain: DomainIndex,
+    /// Would the node hold materialized state?
+    pub materialized: bool,
+    /// Would the node's materialization be partial?
+    pub partial: bool,
+}
+
+/// A domain which would be created by a migration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedDomain {
+    /// The index the domain would have
+    pub domain: DomainIndex,
+    /// The number of shards the domain would have
+    pub num_shards: usize,
+    /// The number of replicas of each shard of the domain
+    pub num_replicas: usize,
+    /// The nodes which would be placed in the domain
+    pub nodes: Vec<NodeIndex>,
+}
+
+/// A full replay which would be performed by a migration to populate a newly materialized node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedReplay {
+    /// The replay path along which the replay would be performed
+    pub path: ReplayPathInfo,
+    /// The current size of the node at the source of the replay, or `None` if the source node
+    /// would itself be added by the migration
+    pub estimated_size: Option<NodeSize>,
+}
+
 impl Display for KeyCount {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
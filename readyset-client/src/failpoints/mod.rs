@@ -4,6 +4,12 @@
 //!
 //! See **[Failure Injection](../docs/src/failure_injection.md)** for much more details.
 
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::internal::DomainIndex;
+
 /// All requests to the authority will behave as if the Authority is down
 ///
 /// Currently only supports consul.
@@ -35,3 +41,52 @@ pub const START_INNER_POSTGRES: &str = "start-inner-postgres";
 pub const LOAD_CONTROLLER_STATE: &str = "load-controller-state";
 /// Injects a failpoint at the beginning of DfState::extend_recipe
 pub const EXTEND_RECIPE: &str = "extend-recipe";
+/// Injects a failure right before a domain handles a replay piece
+pub const DOMAIN_HANDLE_REPLAY: &str = "domain-handle-replay";
+/// Injects a failure right before a domain finishes a replay
+pub const DOMAIN_FINISH_REPLAY: &str = "domain-finish-replay";
+/// Injects a failure right before a domain seeds a partial replay
+pub const DOMAIN_SEED_REPLAY: &str = "domain-seed-replay";
+/// Injects a failure right before a domain sends a batch of packets to other domains
+pub const DOMAIN_SEND_PACKETS: &str = "domain-send-packets";
+
+/// What to do with a packet matched by a [`PacketFault`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PacketFaultAction {
+    /// Silently drop the packet
+    Drop,
+    /// Wait for the given duration before sending the packet
+    Delay(Duration),
+    /// Send the packet twice
+    Duplicate,
+}
+
+/// A fault to inject into packets sent from one domain to another.
+///
+/// Packet faults are configured on all workers via
+/// [`ReadySetHandle::set_packet_faults`](crate::ReadySetHandle::set_packet_faults), and are
+/// applied by each domain right before it sends packets to other domains.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PacketFault {
+    /// The name of the packet type to match, eg `"ReplayPiece"` or `"RequestPartialReplay"`. If
+    /// `None`, matches packets of all types.
+    pub packet_type: Option<String>,
+    /// The domain sending the packet. If `None`, matches packets sent by any domain.
+    pub from: Option<DomainIndex>,
+    /// The domain receiving the packet. If `None`, matches packets sent to any domain.
+    pub to: Option<DomainIndex>,
+    /// What to do with matched packets
+    pub action: PacketFaultAction,
+}
+
+impl PacketFault {
+    /// Returns true if this fault applies to a packet of the given type sent from the domain
+    /// `from` to the domain `to`
+    pub fn matches(&self, packet_type: &str, from: DomainIndex, to: DomainIndex) -> bool {
+        self.packet_type
+            .as_deref()
+            .map_or(true, |t| t == packet_type)
+            && self.from.map_or(true, |f| f == from)
+            && self.to.map_or(true, |t| t == to)
+    }
+}
@@ -1,4 +1,6 @@
+use std::collections::hash_map::DefaultHasher;
 use std::fmt::{self, Display};
+use std::hash::{Hash, Hasher};
 
 use serde::{Deserialize, Serialize};
 
@@ -75,3 +77,39 @@ impl Display for LocalNodeIndex {
         write!(f, "l{}", self.id)
     }
 }
+
+/// A stable identifier for a node in the dataflow graph.
+///
+/// Unlike [`petgraph::graph::NodeIndex`]es (which depend on the order nodes were added to the
+/// graph in) and node names (which include counters), stable identifiers are derived from the
+/// contents of a node and the stable identifiers of its ancestors, so the same query gets the same
+/// identifiers for its nodes if the graph is rebuilt. This makes them suitable for joining
+/// monitoring data across restarts and recoveries of the controller.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct StableNodeId(u64);
+
+impl From<u64> for StableNodeId {
+    fn from(id: u64) -> Self {
+        StableNodeId(id)
+    }
+}
+
+impl StableNodeId {
+    pub fn id(self) -> u64 {
+        self.0
+    }
+
+    /// Derive a new identifier from this one, used to disambiguate nodes which would otherwise
+    /// have the same identifier
+    pub fn rehash(self) -> StableNodeId {
+        let mut hasher = DefaultHasher::new();
+        self.0.hash(&mut hasher);
+        StableNodeId(hasher.finish())
+    }
+}
+
+impl Display for StableNodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
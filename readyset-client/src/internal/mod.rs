@@ -5,6 +5,6 @@ mod addressing;
 mod external;
 mod index;
 
-pub use self::addressing::{DomainIndex, LocalNodeIndex, ReplicaAddress};
+pub use self::addressing::{DomainIndex, LocalNodeIndex, ReplicaAddress, StableNodeId};
 pub use self::external::MaterializationStatus;
 pub use self::index::{Index, IndexType};
@@ -146,7 +146,9 @@
     bound_as_ref,
     box_into_inner,
     is_sorted,
-    impl_trait_in_assoc_type
+    impl_trait_in_assoc_type,
+    never_type,
+    exhaustive_patterns
 )]
 #![deny(macro_use_extern_crate)]
 #![deny(unused_extern_crates)]
@@ -258,6 +260,10 @@ pub mod consistency;
 mod controller;
 pub mod metrics;
 pub mod query;
+pub mod replication_namespace;
+pub mod retry;
+pub mod runtime_config;
+pub mod schema;
 pub mod status;
 mod table;
 pub mod utils;
@@ -281,8 +287,8 @@ use readyset_errors::{ReadySetError, ReadySetResult};
 use serde::{Deserialize, Serialize};
 use tokio::task_local;
 pub use view::{
-    ColumnBase, ColumnSchema, KeyColumnIdx, PlaceholderIdx, ReaderHandle, ViewPlaceholder,
-    ViewSchema,
+    ColumnBase, ColumnProvenance, ColumnSchema, KeyColumnIdx, PlaceholderIdx, ReaderHandle,
+    ViewPlaceholder, ViewSchema,
 };
 
 // FIXME(eta): get rid of these
@@ -366,8 +372,9 @@ pub use crate::table::{
     TableOperation, TableReplicationStatus, TableRequest, TableStatus,
 };
 pub use crate::view::{
-    KeyComparison, LookupResult, ReadQuery, ReadReply, ReadReplyBatch, ReadReplyStats, SchemaType,
-    View, ViewCreateRequest, ViewQuery,
+    AsOf, KeyComparison, LookupResult, ReadQuery, ReadReply, ReadReplyBatch, ReadReplyStats,
+    SchemaType, View, ViewCreateRequest, ViewQuery, ViewRateLimit, ViewResultLimit, ViewScanBatch,
+    ViewStaleReads,
 };
 
 pub mod builders {
@@ -455,6 +462,8 @@ pub fn shard_by(dt: &DfValue, shards: usize) -> usize {
         | DfValue::Double(_)
         | DfValue::Time(_)
         | DfValue::ByteArray(_)
+        | DfValue::Uuid(_)
+        | DfValue::Interval(_)
         | DfValue::Numeric(_)
         | DfValue::BitVector(_)
         | DfValue::Array(_)
@@ -31,6 +31,34 @@ pub mod recorded {
     /// | cache_name | The name of the cache associated with this replay.
     pub const DOMAIN_REPLAY_MISSES: &str = "readyset_domain.replay_misses";
 
+    /// Counter: The number of requests to replay keys which were coalesced into a replay of the
+    /// same key which was already in flight. Recorded at the domain when a reader requests replays
+    /// of keys it already requested, and when a replay misses on keys which are already being
+    /// replayed.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | cache_name | The name of the cache associated with this replay.
+    pub const DOMAIN_COALESCED_REPLAYS: &str = "readyset_domain.coalesced_replays";
+
+    /// Counter: The number of replays of keys in readers which the domain stopped tracking because
+    /// they were in flight for longer than the configured orphaned replay timeout without any reads
+    /// waiting for them.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | cache_name | The name of the cache associated with this reader.
+    pub const DOMAIN_RECLAIMED_REPLAYS: &str = "readyset_domain.reclaimed_replays";
+
+    /// Counter: The number of keys which were filled with no rows that were evicted from a reader,
+    /// either because they outlived the configured negative entry TTL or because the reader held
+    /// more than the configured maximum number of such keys.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | cache_name | The name of the cache associated with this reader.
+    pub const DOMAIN_NEGATIVE_ENTRY_EVICTIONS: &str = "readyset_domain.negative_entry_evictions";
+
     /// Histogram: The time in microseconds that a domain spends
     /// handling and forwarding a Message or Input packet. Recorded at
     /// the domain following handling each Message and Input packet.
@@ -155,6 +183,23 @@ pub mod recorded {
     /// is in progress, 0 otherwise.
     pub const CONTROLLER_MIGRATION_IN_PROGRESS: &str = "readyset_controller.migration_in_progress";
 
+    /// Gauge: The number of migrations requested via `extend_recipe` which are waiting in the
+    /// leader's queue for earlier migrations to finish running.
+    pub const CONTROLLER_MIGRATION_QUEUE_DEPTH: &str = "readyset_controller.migration_queue_depth";
+
+    /// Counter: The number of workers the controller has considered failed, either because their
+    /// session with the authority expired or because they stopped responding to the leader's
+    /// heartbeats.
+    pub const CONTROLLER_FAILED_WORKERS: &str = "readyset_controller.failed_workers";
+
+    /// Gauge: The number of domains which are not currently running on any worker, and are
+    /// waiting to be recovered.
+    pub const CONTROLLER_UNPLACED_DOMAINS: &str = "readyset_controller.unplaced_domains";
+
+    /// Counter: The number of views which have been automatically deactivated by the leader for
+    /// having a low hit rate while the materialized state in the graph exceeded its memory target.
+    pub const CONTROLLER_VIEWS_DEACTIVATED: &str = "readyset_controller.views_deactivated";
+
     /// Counter: The number of evicitons performed at a worker. Incremented each
     /// time `do_eviction` is called at the worker.
     pub const EVICTION_WORKER_EVICTIONS_REQUESTED: &str =
@@ -202,6 +247,14 @@ pub mod recorded {
     /// | cache_name | The name of the cache associated with this replay.
     pub const BASE_TABLE_LOOKUP_REQUESTS: &str = "readyset_base_table.lookup_requests";
 
+    /// Counter: The number of writes discarded by a base table because they were redelivered from
+    /// a replication offset the table had already applied.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | table_name | The name of the base table. |
+    pub const BASE_TABLE_DISCARDED_WRITES: &str = "readyset_base_table.discarded_writes";
+
     /// Counter: The number of packets dropped by an egress node.
     pub const EGRESS_NODE_DROPPED_PACKETS: &str = "readyset_egress.dropped_packets";
 
@@ -220,10 +273,94 @@ pub mod recorded {
     /// Counter: The number of times a query required at least a partial replay.
     pub const SERVER_VIEW_QUERY_MISS: &str = "readyset_server.view_query_result_miss";
 
+    /// Counter: The number of times a query was rejected because it exceeded a rate limit
+    /// configured for the view it was made against.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | cache_name | The name of the view that was read from |
+    /// | limit | The limit that was exceeded |
+    pub const SERVER_VIEW_QUERY_THROTTLED: &str = "readyset_server.view_query_throttled";
+
+    /// Counter: The number of times a query which missed was rejected because the domain that
+    /// would have had to fill the miss was overloaded.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | cache_name | The name of the view that was read from |
+    pub const SERVER_VIEW_QUERY_OVERLOADED: &str = "readyset_server.view_query_overloaded";
+
+    /// Counter: The number of times a query which missed failed because the keys it missed on
+    /// weren't filled by upqueries within its upquery timeout.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | cache_name | The name of the view that was read from |
+    pub const SERVER_VIEW_QUERY_UPQUERY_TIMEOUT: &str =
+        "readyset_server.view_query_upquery_timeout";
+
+    /// Counter: The number of times a query against a view with stale reads enabled was answered
+    /// with possibly-stale results, rather than blocking or erroring. Results are stale either
+    /// because the keys read were being replayed, or because replication from the upstream
+    /// database was down.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | cache_name | The name of the view that was read from |
+    /// | reason | `replaying` if keys were being replayed, or `replication_down` |
+    pub const SERVER_VIEW_QUERY_STALE: &str = "readyset_server.view_query_stale";
+
+    /// Counter: The number of times the results of a query against a view were cut short because
+    /// they exceeded the result limit configured for the view or for the query.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | cache_name | The name of the view that was read from |
+    pub const SERVER_VIEW_QUERY_TRUNCATED: &str = "readyset_server.view_query_truncated";
+
     /// Histogram: The amount of time in microseconds spent waiting for an upquery during a read
     /// request.
     pub const SERVER_VIEW_UPQUERY_DURATION: &str = "readyset_server.view_query_upquery_duration_us";
 
+    /// Counter: The number of times a query against a particular view was served entirely from
+    /// reader cache.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | cache_name | The name of the view |
+    pub const READER_VIEW_HITS: &str = "readyset_reader.view_hits";
+
+    /// Counter: The number of times a query against a particular view required at least a partial
+    /// replay.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | cache_name | The name of the view |
+    pub const READER_VIEW_MISSES: &str = "readyset_reader.view_misses";
+
+    /// Counter: The number of times keys were evicted from a particular view's reader state.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | cache_name | The name of the view |
+    pub const READER_VIEW_EVICTIONS: &str = "readyset_reader.view_evictions";
+
+    /// Counter: The number of misses on keys in a particular view's reader which were coalesced
+    /// into a replay of the same key which was already in flight, rather than triggering another.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | cache_name | The name of the view |
+    pub const READER_VIEW_COALESCED_REPLAYS: &str = "readyset_reader.view_coalesced_replays";
+
+    /// Histogram: The time in microseconds between a miss on a key in a particular view's reader
+    /// and that key being filled by a replay.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | cache_name | The name of the view |
+    pub const READER_VIEW_REPLAY_LATENCY: &str = "readyset_reader.view_replay_latency_us";
+
     /// Counter: The number of times a dataflow node type is added to the
     /// dataflow graph. Recorded at the time the new graph is committed.
     ///
@@ -247,6 +384,14 @@ pub mod recorded {
     /// | packet_type | The type of packet |
     pub const DOMAIN_PACKETS_QUEUED: &str = "readyset_domain.packets_queued";
 
+    /// Histogram: The time in microseconds that packets spend queued in a domain's event loop
+    /// before being handled. Only recorded for domains which prioritize replays over writes.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | priority | `replay` for upquery requests and partial replay pieces, `bulk` otherwise |
+    pub const DOMAIN_PACKET_QUEUE_WAIT_TIME: &str = "readyset_domain.packet_queue_wait_time_us";
+
     /// Histogram: The amount of time in microseconds an operator node spends handling a call to
     /// `Ingredient::on_input`.
     ///
@@ -262,6 +407,33 @@ pub mod recorded {
     /// | ntype | The operator node type. |
     pub const NODE_ON_INPUT_INVOCATIONS: &str = "readyset_domain.node_on_input_invocations";
 
+    /// Histogram: The time in microseconds a domain spends processing a single update forwarded
+    /// through a dataflow node. Only recorded if verbose domain metrics are enabled.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | ntype | The dataflow node type. |
+    /// | node_id | The [`StableNodeId`](crate::internal::StableNodeId) of the node. |
+    pub const DOMAIN_NODE_PROCESS_TIME: &str = "readyset_domain.node_process_time_us";
+
+    /// Histogram: The time in microseconds a domain spends processing a single partial replay
+    /// piece in a node along its replay path. Only recorded if replay profiling is enabled.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | cache_name | The name of the cache associated with this replay. |
+    /// | ntype | The dataflow node type. |
+    pub const DOMAIN_REPLAY_NODE_TIME: &str = "readyset_domain.replay_node_time_us";
+
+    /// Counter: The total time in microseconds domains spend processing partial replay pieces in
+    /// nodes along their replay paths. Only recorded if replay profiling is enabled.
+    ///
+    /// | Tag | Description |
+    /// | --- | ----------- |
+    /// | cache_name | The name of the cache associated with this replay. |
+    /// | ntype | The dataflow node type. |
+    pub const DOMAIN_TOTAL_REPLAY_NODE_TIME: &str = "readyset_domain.total_replay_node_time_us";
+
     /// Histogram: The time a snapshot takes to be performed.
     pub const REPLICATOR_SNAPSHOT_DURATION: &str = "readyset_replicator.snapshot_duration_us";
 
@@ -296,6 +468,11 @@ pub mod recorded {
     /// Gauge: The number of tables currently snapshotting
     pub const REPLICATOR_TABLES_SNAPSHOTTING: &str = "readyset_replicator.tables_snapshotting";
 
+    /// Gauge: The number of bytes of WAL the upstream postgres database is retaining for our
+    /// replication slot
+    pub const REPLICATOR_SLOT_RETAINED_WAL_BYTES: &str =
+        "readyset_replicator.slot_retained_wal_bytes";
+
     /// Counter: Number of failures encountered when following the replication
     /// log.
     pub const REPLICATOR_FAILURE: &str = "readyset_replicator.update_failure";
@@ -306,6 +483,14 @@ pub mod recorded {
     /// Counter: Number of replication actions performed successfully.
     pub const REPLICATOR_SUCCESS: &str = "readyset_replicator.update_success";
 
+    /// Counter: Number of values that were replicated as NULL because they were larger than the
+    /// size cap of their column.
+    pub const REPLICATOR_OVERSIZED_VALUES: &str = "readyset_replicator.oversized_values";
+
+    /// Counter: Number of replicated rows that were skipped because they contained values that
+    /// could not be decoded from the character set of their column.
+    pub const REPLICATOR_QUARANTINED_ROWS: &str = "readyset_replicator.quarantined_rows";
+
     /// Gauge: Indicates whether a server is the leader. Set to 1 when the
     /// server is leader, 0 for follower.
     pub const CONTROLLER_IS_LEADER: &str = "readyset_controller.is_leader";
@@ -16,6 +16,7 @@ use nom_sql::{DialectDisplay, Relation, SelectStatement, SqlIdentifier};
 use readyset_data::DfValue;
 use readyset_errors::ReadySetError;
 use readyset_sql_passes::anonymize::{Anonymize, Anonymizer};
+use readyset_sql_passes::Canonicalize;
 use readyset_util::fmt::fmt_with;
 use readyset_util::hash::hash;
 use serde::ser::{SerializeSeq, SerializeTuple};
@@ -25,9 +26,11 @@ use vec1::Vec1;
 use crate::{PlaceholderIdx, ViewCreateRequest};
 
 /// Uniquely identifies a SELECT statement that is in a particular form. In other words,
-/// `s1_query_id == s2_query_id` **only if** `s1 == s2`. This means that the unparsed,
-/// pre-adapter-rewrite version of a SELECT statement will not have the same `QueryId` as the
-/// parsed, rewritten version of the same SELECT statement.
+/// `s1_query_id == s2_query_id` **only if** `s1` and `s2` have the same
+/// [canonical form](readyset_sql_passes::Canonicalize), meaning that queries which differ only in
+/// formatting, the names of table aliases, or the case of function names have the same `QueryId`.
+/// This also means that the unparsed, pre-adapter-rewrite version of a SELECT statement will not
+/// have the same `QueryId` as the parsed, rewritten version of the same SELECT statement.
 ///
 /// `QueryId`s can be created from any type that implements [`Hash`] via
 /// [`QueryId::new`](struct.QueryId.html#method.new).
@@ -37,6 +40,8 @@ pub struct QueryId(u64);
 
 impl QueryId {
     pub fn from_select(stmt: &SelectStatement, schema_search_path: &[SqlIdentifier]) -> Self {
+        let mut stmt = stmt.clone();
+        stmt.canonicalize();
         QueryId(hash(&(stmt, schema_search_path)))
     }
 
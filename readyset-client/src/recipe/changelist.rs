@@ -6,8 +6,10 @@
 //! - `CREATE TABLE`
 //! - `CREATE CACHED QUERY`
 //! - `CREATE VIEW`
+//! - `CREATE INDEX`
 //! - `DROP CACHED QUERY`
 //! - `DROP TABLE`
+//! - `DROP INDEX`
 //! Said list of [`Change`]s are sorted in the same order as the queries came in. This guarantees
 //! that within the same request we can have queries like these:
 //! ```SQL
@@ -37,8 +39,9 @@ use std::hash::{Hash, Hasher};
 use dataflow_expression::Dialect;
 use nom_locate::LocatedSpan;
 use nom_sql::{
-    AlterTableStatement, CacheInner, CreateCacheStatement, CreateTableStatement,
-    CreateViewStatement, DropTableStatement, DropViewStatement, NonReplicatedRelation, Relation,
+    AlterTableDefinition, AlterTableStatement, CacheInner, CreateCacheStatement,
+    CreateIndexStatement, CreateTableStatement, CreateViewStatement, DialectDisplay,
+    DropIndexStatement, DropTableStatement, DropViewStatement, NonReplicatedRelation, Relation,
     SelectStatement, SqlIdentifier, SqlQuery,
 };
 use readyset_data::DfType;
@@ -49,6 +52,7 @@ use test_strategy::Arbitrary;
 use tracing::error;
 
 use crate::consensus::CacheDDLRequest;
+use crate::recipe::{FrontierStrategy, QuerySource};
 
 /// The specification for a list of changes that must be made
 /// to the MIR and dataflow graphs.
@@ -201,6 +205,8 @@ impl ChangeList {
                                 name,
                                 inner,
                                 always,
+                                frontier_strategy,
+                                disable_predicate_pushdown,
                                 ..
                             }) => {
                                 let statement = match inner {
@@ -223,9 +229,25 @@ impl ChangeList {
                                     name,
                                     statement,
                                     always,
+                                    frontier_strategy,
+                                    source: QuerySource::Api,
+                                    original_sql: None,
+                                    disable_predicate_pushdown,
+                                    standby: false,
                                 }))
                             }
                             SqlQuery::AlterTable(ats) => changes.push(Change::AlterTable(ats)),
+                            SqlQuery::RenameTable(rts) => {
+                                changes.extend(rts.ops.into_iter().map(|op| {
+                                    Change::AlterTable(AlterTableStatement {
+                                        table: op.from,
+                                        definitions: Ok(vec![AlterTableDefinition::RenameTable {
+                                            new_name: op.to,
+                                        }]),
+                                        only: false,
+                                    })
+                                }))
+                            }
                             SqlQuery::DropTable(dts) => {
                                 let if_exists = dts.if_exists;
                                 changes.extend(
@@ -244,6 +266,8 @@ impl ChangeList {
                                 name: dcs.name,
                                 if_exists: false,
                             }),
+                            SqlQuery::CreateIndex(cis) => changes.push(Change::CreateIndex(cis)),
+                            SqlQuery::DropIndex(dis) => changes.push(Change::DropIndex(dis)),
                             _ => unsupported!(
                                 "Only DDL statements supported in ChangeList (got {})",
                                 parsed.query_type()
@@ -327,6 +351,35 @@ pub struct CreateCache {
     /// If set to `true`, execution of this cache will bypass transaction handling in the
     /// adapter
     pub always: bool,
+    /// Strategy for determining which of the (partial) materializations added for this cache
+    /// should be placed beyond the materialization frontier. If not set, the strategy configured
+    /// for the server is used.
+    ///
+    /// Note that this only applies to nodes newly added for this cache - nodes reused from
+    /// existing caches retain their existing materializations.
+    #[serde(default)]
+    pub frontier_strategy: Option<FrontierStrategy>,
+    /// What caused this cache to be created, as recorded in the query log
+    #[serde(default)]
+    pub source: QuerySource,
+    /// The SQL text of the query as originally written, if `statement` was rewritten before being
+    /// sent to the server. Recorded in the query log.
+    #[serde(default)]
+    pub original_sql: Option<String>,
+    /// If set to `true`, filters in this cache's query won't be pushed up the graph past joins
+    /// and unions when compiling it to dataflow.
+    ///
+    /// This is an escape hatch for queries where the rewritten graph performs worse than (or
+    /// differs from) the graph as originally planned.
+    #[serde(default)]
+    pub disable_predicate_pushdown: bool,
+    /// If set to `true`, the domains containing this cache's reader are run with a warm standby
+    /// replica on a different worker, which is kept up to date with the same stream of updates as
+    /// the primary. If the worker running one of the replicas fails, reads against the cache fail
+    /// over to the other replica rather than waiting for the reader to be rescheduled and have its
+    /// state rebuilt via replays.
+    #[serde(default)]
+    pub standby: bool,
 }
 
 /// Metadata about a PostgreSQL table
@@ -394,6 +447,13 @@ pub enum Change {
         /// A specification for the change to make to the type
         change: AlterTypeChange,
     },
+    /// Record that an index exists in the upstream database on the given columns of a table.
+    ///
+    /// ReadySet doesn't maintain upstream indexes itself, but uses the indexed columns as hints
+    /// for which columns to index the table's base node by if it doesn't have a primary key.
+    CreateIndex(CreateIndexStatement),
+    /// Remove the hint previously recorded for an index via [`Change::CreateIndex`]
+    DropIndex(DropIndexStatement),
     /// Remove a relation from the graph.
     ///
     /// This could be one of:
@@ -422,9 +482,46 @@ impl Change {
             name: Some(name.into()),
             statement: Box::new(statement),
             always,
+            frontier_strategy: None,
+            source: QuerySource::default(),
+            original_sql: None,
+            disable_predicate_pushdown: false,
+            standby: false,
         })
     }
 
+    /// If this change creates a cache, record what caused the cache to be created and the SQL
+    /// text of the query before it was rewritten in the query log. Has no effect on other kinds of
+    /// change.
+    #[must_use]
+    pub fn with_provenance(mut self, source: QuerySource, original_sql: Option<String>) -> Self {
+        if let Change::CreateCache(create_cache) = &mut self {
+            create_cache.source = source;
+            create_cache.original_sql = original_sql;
+        }
+        self
+    }
+
+    /// If this change creates a cache, override the server's frontier strategy for the nodes added
+    /// for the cache. Has no effect on other kinds of change.
+    #[must_use]
+    pub fn with_frontier_strategy(mut self, frontier_strategy: Option<FrontierStrategy>) -> Self {
+        if let Change::CreateCache(create_cache) = &mut self {
+            create_cache.frontier_strategy = frontier_strategy;
+        }
+        self
+    }
+
+    /// If this change creates a cache, set whether to compile the cache's query without pushing
+    /// filters up the graph past joins and unions. Has no effect on other kinds of change.
+    #[must_use]
+    pub fn with_predicate_pushdown_disabled(mut self, disable_predicate_pushdown: bool) -> Self {
+        if let Change::CreateCache(create_cache) = &mut self {
+            create_cache.disable_predicate_pushdown = disable_predicate_pushdown;
+        }
+        self
+    }
+
     /// Return true if this change requires noria to resnapshot the database in order to properly
     /// update the schema
     pub fn requires_resnapshot(&self) -> bool {
@@ -437,6 +534,7 @@ impl Change {
                         | nom_sql::AlterTableDefinition::DropColumn { .. }
                         | nom_sql::AlterTableDefinition::ChangeColumn { .. }
                         | nom_sql::AlterTableDefinition::RenameColumn { .. }
+                        | nom_sql::AlterTableDefinition::RenameTable { .. }
                         | nom_sql::AlterTableDefinition::AddKey(_)
                         | nom_sql::AlterTableDefinition::DropConstraint { .. } => true,
                         nom_sql::AlterTableDefinition::ReplicaIdentity(_) => false,
@@ -469,15 +567,40 @@ impl Change {
                     },
                 ..
             } => true,
-            Change::CreateTable { .. }
-            | Change::CreateView(_)
+            // We can't know the rows that a `CREATE TABLE ... AS SELECT` statement inserted into
+            // the table, so the table has to be snapshotted
+            Change::CreateTable { statement, .. } => statement.select.is_some(),
+            Change::CreateView(_)
             | Change::CreateCache { .. }
             | Change::CreateType { .. }
+            | Change::CreateIndex(_)
+            | Change::DropIndex(_)
             | Change::Drop { .. }
             | Change::AddNonReplicatedRelation(_) => false,
         }
     }
 
+    /// Return true if this change renames a table or any of its columns.
+    ///
+    /// Renames still require resnapshotting the renamed table, but should be applied to ReadySet
+    /// first so that the views and caches which depend on the table can be remapped to the new
+    /// names rather than dropped.
+    pub fn is_rename(&self) -> bool {
+        match self {
+            Change::AlterTable(AlterTableStatement {
+                definitions: Ok(definitions),
+                ..
+            }) => definitions.iter().any(|def| match def {
+                AlterTableDefinition::RenameColumn { .. }
+                | AlterTableDefinition::RenameTable { .. } => true,
+                // MySQL's `CHANGE COLUMN` can rename the column as well as changing its definition
+                AlterTableDefinition::ChangeColumn { name, spec } => spec.column.name != *name,
+                _ => false,
+            }),
+            _ => false,
+        }
+    }
+
     /// Parse a `Change` from the given [`CacheDDLRequest`], using the encapsulated [`Dialect`] and
     /// schema search path for expression evaluation semantics. This function performs the adapter
     /// rewrites on the parsed query string before passing it to the server via `/extend_recipe`.
@@ -512,6 +635,8 @@ impl Change {
                         name,
                         inner,
                         always,
+                        frontier_strategy,
+                        disable_predicate_pushdown,
                         ..
                     }) => {
                         let mut statement = match inner {
@@ -529,15 +654,23 @@ impl Change {
                             Err(query) => return Err(ReadySetError::UnparseableQuery { query }),
                         };
 
+                        let original = statement.clone();
                         adapter_rewrites::process_query(
                             &mut statement,
                             adapter_rewrite_params
                         )?;
+                        let original_sql = (original != statement)
+                            .then(|| original.display(ddl_req.dialect.into()).to_string());
 
                         Change::CreateCache(CreateCache {
                             name,
                             statement,
                             always,
+                            frontier_strategy,
+                            source: QuerySource::CreateCache,
+                            original_sql,
+                            disable_predicate_pushdown,
+                            standby: false,
                         })
                     }
                     SqlQuery::DropCache(dcs) => Change::Drop {
@@ -691,6 +824,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_handles_indexes() {
+        let queries = "CREATE INDEX idx ON t (a, b);\nDROP INDEX idx ON t;";
+
+        let changelist = ChangeList::from_str(queries, Dialect::DEFAULT_MYSQL).unwrap();
+        match changelist.changes.as_slice() {
+            [Change::CreateIndex(create), Change::DropIndex(drop)] => {
+                assert_eq!(create.table, "t".into());
+                assert_eq!(
+                    create.columns,
+                    vec![nom_sql::Column::from("a"), nom_sql::Column::from("b")]
+                );
+                assert_eq!(drop.name, "idx".into());
+            }
+            changes => panic!("Unexpected changes: {changes:?}"),
+        }
+        assert!(!changelist.changes().any(Change::requires_resnapshot));
+    }
+
     mod requires_resnapshot {
         use super::*;
 
@@ -733,5 +885,61 @@ mod tests {
             };
             assert!(change.requires_resnapshot())
         }
+
+        #[test]
+        fn create_table() {
+            let changelist =
+                ChangeList::from_str("CREATE TABLE t (a int);", Dialect::DEFAULT_MYSQL).unwrap();
+            assert!(!changelist.changes().any(Change::requires_resnapshot));
+        }
+
+        #[test]
+        fn create_table_as_select() {
+            let changelist = ChangeList::from_str(
+                "CREATE TABLE t2 AS SELECT a FROM t;",
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap();
+            assert!(changelist.changes().all(Change::requires_resnapshot));
+        }
+
+        #[test]
+        fn rename_table() {
+            let changelist =
+                ChangeList::from_str("RENAME TABLE t1 TO t2, t3 TO t4;", Dialect::DEFAULT_MYSQL)
+                    .unwrap();
+            assert_eq!(changelist.changes.len(), 2);
+            match &changelist.changes[0] {
+                Change::AlterTable(stmt) => {
+                    assert_eq!(stmt.table, "t1".into());
+                    assert_eq!(
+                        stmt.definitions,
+                        Ok(vec![AlterTableDefinition::RenameTable {
+                            new_name: "t2".into()
+                        }])
+                    );
+                }
+                change => panic!("Unexpected change: {change:?}"),
+            }
+            assert!(changelist.changes().all(Change::requires_resnapshot));
+            assert!(changelist.changes().all(Change::is_rename));
+        }
+
+        #[test]
+        fn change_column() {
+            let changelist = ChangeList::from_str(
+                "ALTER TABLE t CHANGE COLUMN a b bigint;",
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap();
+            assert!(changelist.changes().all(Change::is_rename));
+
+            let changelist = ChangeList::from_str(
+                "ALTER TABLE t CHANGE COLUMN a a bigint; ALTER TABLE t MODIFY a bigint;",
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap();
+            assert!(!changelist.changes().any(Change::is_rename));
+        }
     }
 }
@@ -4,8 +4,12 @@ pub mod changelist;
 
 use std::borrow::Cow;
 use std::fmt::Display;
+use std::time::SystemTime;
 
-use nom_sql::{CacheInner, CreateCacheStatement, DialectDisplay, Relation, SelectStatement};
+pub use nom_sql::FrontierStrategy;
+use nom_sql::{
+    CacheInner, CreateCacheStatement, DialectDisplay, Relation, SelectStatement, SqlIdentifier,
+};
 use readyset_errors::ReadySetError;
 use readyset_util::fmt::fmt_with;
 use serde::{Deserialize, Serialize};
@@ -21,6 +25,10 @@ pub struct ExtendRecipeSpec<'a> {
     pub changes: ChangeList,
     /// Optional replication offset if recipe is installed from replication or binlog
     pub replication_offset: Option<Cow<'a, ReplicationOffset>>,
+    /// The namespace of the additional upstream database that `replication_offset` is an offset
+    /// in, or `None` if it's an offset in the primary upstream database
+    #[serde(default)]
+    pub replication_namespace: Option<SqlIdentifier>,
     /// Parameter that indicates if the leader is required to be ready before handling
     /// this RecipeSpec.
     ///
@@ -47,6 +55,7 @@ impl<'a> From<ChangeList> for ExtendRecipeSpec<'a> {
         Self {
             changes,
             replication_offset: None,
+            replication_namespace: None,
             require_leader_ready: true,
             concurrently: false,
         }
@@ -94,6 +103,186 @@ impl MigrationStatus {
     }
 }
 
+/// The phase of the work done by a migration that a [`MigrationProgress`] describes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum MigrationPhase {
+    /// The migration is waiting for migrations requested before it to finish running
+    Queued,
+    /// New queries are being lowered to MIR and dataflow, and the changes to the dataflow graph
+    /// are being planned
+    Lowering,
+    /// New domains are being started, and new nodes added to existing domains
+    DomainSetup,
+    /// State is being replayed into new fully materialized nodes
+    Replaying,
+    /// The migration has finished running, successfully or otherwise. Its result can be queried
+    /// using [`ReadySetHandle::migration_status`](crate::ReadySetHandle::migration_status)
+    Finished,
+}
+
+impl Display for MigrationPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationPhase::Queued => f.write_str("Queued"),
+            MigrationPhase::Lowering => f.write_str("Lowering"),
+            MigrationPhase::DomainSetup => f.write_str("Setting up domains"),
+            MigrationPhase::Replaying => f.write_str("Replaying"),
+            MigrationPhase::Finished => f.write_str("Finished"),
+        }
+    }
+}
+
+/// A snapshot of the progress of a migration requested via `extend_recipe`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MigrationProgress {
+    /// The phase of the migration that's currently running
+    pub phase: MigrationPhase,
+    /// The total number of full replays the migration needs to perform, or 0 if the migration
+    /// hasn't started replaying yet
+    pub replays_total: usize,
+    /// The number of full replays which have finished
+    pub replays_done: usize,
+    /// The number of rows replayed so far, across all full replays performed by the migration
+    pub rows_replayed: usize,
+}
+
+impl Default for MigrationProgress {
+    fn default() -> Self {
+        Self {
+            phase: MigrationPhase::Queued,
+            replays_total: 0,
+            replays_done: 0,
+            rows_replayed: 0,
+        }
+    }
+}
+
+impl Display for MigrationProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.phase)?;
+        if self.phase == MigrationPhase::Replaying {
+            write!(
+                f,
+                " ({}/{} replays done, {} rows replayed)",
+                self.replays_done, self.replays_total, self.rows_replayed
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Identifies a version of the set of views and caches in the recipe, as created by
+/// [`ReadySetHandle::stage_recipe_version`](crate::ReadySetHandle::stage_recipe_version).
+///
+/// Version `0` identifies the set of views and caches that existed before any versioned changes
+/// were activated.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct RecipeVersion(pub u64);
+
+impl Display for RecipeVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "v{}", self.0)
+    }
+}
+
+/// A version of the recipe which has been activated
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivatedRecipeVersion {
+    /// The version that was activated
+    pub version: RecipeVersion,
+    /// When the version was activated
+    pub activated_at: SystemTime,
+    /// The names of the views and caches added by activating the version
+    pub added: Vec<Relation>,
+    /// The names of the views and caches removed by activating the version
+    pub removed: Vec<Relation>,
+}
+
+/// Information about the versions of the recipe, returned by
+/// [`ReadySetHandle::recipe_versions`](crate::ReadySetHandle::recipe_versions)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecipeVersionsInfo {
+    /// The most recently activated version which hasn't been rolled back
+    pub active: RecipeVersion,
+    /// The versions which have been activated and can be rolled back, oldest first
+    pub activated: Vec<ActivatedRecipeVersion>,
+    /// The versions which have been staged but not yet activated
+    pub staged: Vec<RecipeVersion>,
+}
+
+/// A cache whose dataflow nodes have been removed to free memory, but whose definition has been
+/// kept so that it can be re-created via
+/// [`ReadySetHandle::reactivate_view`](crate::ReadySetHandle::reactivate_view). Returned by
+/// [`ReadySetHandle::deactivated_views`](crate::ReadySetHandle::deactivated_views)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeactivatedView {
+    /// The name of the cache
+    pub name: Relation,
+    /// When the cache was deactivated
+    pub deactivated_at: SystemTime,
+    /// The percentage of reads against the cache which hit during the interval before it was
+    /// automatically deactivated, or `None` if it was deactivated explicitly
+    pub hit_rate: Option<u8>,
+}
+
+/// What caused a cached query to be installed, as recorded in the query log
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuerySource {
+    /// Installed automatically by an adapter the first time it saw the query
+    Automatic,
+    /// Installed by an explicit `CREATE CACHE` statement
+    CreateCache,
+    /// Installed by a direct request to the controller
+    #[default]
+    Api,
+}
+
+impl Display for QuerySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuerySource::Automatic => write!(f, "automatic"),
+            QuerySource::CreateCache => write!(f, "CREATE CACHE"),
+            QuerySource::Api => write!(f, "API"),
+        }
+    }
+}
+
+/// A decision made when installing a cached query which changed the query or how it's answered,
+/// as recorded in the query log
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QueryRewrite {
+    /// The adapter rewrote the query before sending it to the server, for example to turn literals
+    /// into placeholders
+    Adapter,
+    /// The server rewrote the query into a normalized form before installing it
+    Server,
+    /// The query was identical to an existing cache, and was installed as an alias for that cache
+    AliasOf(Relation),
+    /// The positions of the query's placeholders weren't supported, so the query is answered by
+    /// existing caches with some of its placeholder values inlined
+    ReusesCaches(Vec<Relation>),
+}
+
+/// An entry in the log of installed cached queries, returned by
+/// [`ReadySetHandle::list_queries`](crate::ReadySetHandle::list_queries)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryLogEntry {
+    /// The name of the cached query
+    pub name: Relation,
+    /// What caused the query to be installed
+    pub source: QuerySource,
+    /// When the query was installed
+    pub installed_at: SystemTime,
+    /// The SQL text of the query as originally written, before any rewrites were applied
+    pub original_sql: String,
+    /// The SQL text of the query as installed, after all rewrites were applied
+    pub installed_sql: String,
+    /// The rewrite decisions made when installing the query
+    pub rewrites: Vec<QueryRewrite>,
+}
+
 /// The representation of a cache as it exists in the expression registry.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheExpr {
@@ -113,6 +302,8 @@ impl From<CacheExpr> for CreateCacheStatement {
             // already-migrated query
             concurrently: false,
             unparsed_create_cache_statement: None,
+            frontier_strategy: None,
+            disable_predicate_pushdown: false,
         }
     }
 }
@@ -0,0 +1,267 @@
+//! Support for replicating from multiple upstream databases into a single ReadySet deployment.
+//!
+//! Each additional upstream database is assigned a *namespace*, and relations in schema `s` of that
+//! upstream database are replicated into schema `<namespace>_s`. Relations in the primary upstream
+//! database are replicated unchanged. Since each replicator only ever sees the relations (and
+//! replication offsets) belonging to its own upstream database, base tables from different
+//! upstream databases can be snapshotted, resnapshotted and replicated independently, while still
+//! feeding the same dataflow graph.
+
+use nom_sql::analysis::visit_mut::{self, VisitorMut};
+use nom_sql::{Relation, SqlIdentifier, SqlType};
+use replication_offset::ReplicationOffsets;
+
+use crate::recipe::changelist::{Change, ChangeList};
+
+/// Identifies which upstream database a replicator is replicating from, and how the relations in
+/// that upstream database map to relations in ReadySet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplicationNamespace {
+    /// The primary upstream database, whose relations are replicated unchanged. The primary
+    /// upstream database owns all relations which aren't in the namespace of one of the given
+    /// additional upstream databases.
+    Primary {
+        /// The namespaces of all additional upstream databases
+        additional: Vec<SqlIdentifier>,
+    },
+    /// An additional upstream database, whose relations are replicated into the given namespace
+    Additional(SqlIdentifier),
+}
+
+impl Default for ReplicationNamespace {
+    fn default() -> Self {
+        Self::Primary { additional: vec![] }
+    }
+}
+
+/// Returns true if the given (ReadySet) schema belongs to the given namespace
+fn schema_in_namespace(schema: &str, namespace: &str) -> bool {
+    schema
+        .strip_prefix(namespace)
+        .is_some_and(|rest| rest.starts_with('_'))
+}
+
+impl ReplicationNamespace {
+    /// Returns the namespace of the upstream database, or `None` for the primary upstream database
+    pub fn namespace(&self) -> Option<&SqlIdentifier> {
+        match self {
+            Self::Primary { .. } => None,
+            Self::Additional(namespace) => Some(namespace),
+        }
+    }
+
+    /// Returns true if the given relation in ReadySet was replicated from this upstream database
+    pub fn owns(&self, relation: &Relation) -> bool {
+        let schema = relation.schema.as_deref();
+        match self {
+            Self::Primary { additional } => !additional
+                .iter()
+                .any(|ns| schema.is_some_and(|schema| schema_in_namespace(schema, ns))),
+            Self::Additional(ns) => schema.is_some_and(|schema| schema_in_namespace(schema, ns)),
+        }
+    }
+
+    /// Map the name of a schema in this upstream database to the name of the schema in ReadySet
+    pub fn schema_to_local(&self, schema: &SqlIdentifier) -> SqlIdentifier {
+        match self {
+            Self::Primary { .. } => schema.clone(),
+            Self::Additional(ns) => format!("{ns}_{schema}").into(),
+        }
+    }
+
+    /// Map a relation in this upstream database to the relation it's replicated into in ReadySet.
+    ///
+    /// Relations without a schema are left as-is, to be resolved against the (mapped) schema
+    /// search path.
+    pub fn to_local(&self, relation: &Relation) -> Relation {
+        Relation {
+            schema: relation.schema.as_ref().map(|s| self.schema_to_local(s)),
+            name: relation.name.clone(),
+        }
+    }
+
+    /// Map a relation in ReadySet back to the relation in this upstream database it was replicated
+    /// from, or return `None` if it wasn't replicated from this upstream database.
+    pub fn to_upstream(&self, relation: &Relation) -> Option<Relation> {
+        if !self.owns(relation) {
+            return None;
+        }
+
+        match self {
+            Self::Primary { .. } => Some(relation.clone()),
+            Self::Additional(ns) => Some(Relation {
+                schema: relation.schema.as_ref().map(|s| s[ns.len() + 1..].into()),
+                name: relation.name.clone(),
+            }),
+        }
+    }
+
+    /// Map all the relations referenced by the given [`ChangeList`] from this upstream database
+    /// into ReadySet
+    pub fn changelist_to_local(&self, mut changelist: ChangeList) -> ChangeList {
+        if self.namespace().is_none() {
+            return changelist;
+        }
+
+        for schema in &mut changelist.schema_search_path {
+            *schema = self.schema_to_local(schema);
+        }
+
+        let mut visitor = ToLocalVisitor(self);
+        for change in &mut changelist.changes {
+            let Ok(()) = match change {
+                Change::CreateTable { statement, .. } => {
+                    visitor.visit_create_table_statement(statement)
+                }
+                Change::AddNonReplicatedRelation(relation) => {
+                    relation.name = self.to_local(&relation.name);
+                    Ok(())
+                }
+                Change::CreateView(statement) => {
+                    statement.name = self.to_local(&statement.name);
+                    visitor.visit_create_view_statement(statement)
+                }
+                Change::CreateCache(create_cache) => {
+                    visitor.visit_select_statement(&mut create_cache.statement)
+                }
+                Change::AlterTable(statement) => visitor.visit_alter_table_statement(statement),
+                Change::CreateIndex(statement) => visitor.visit_create_index_statement(statement),
+                Change::DropIndex(statement) => {
+                    statement.name = self.to_local(&statement.name);
+                    visitor.visit_drop_index_statement(statement)
+                }
+                Change::CreateType { name, .. }
+                | Change::AlterType { name, .. }
+                | Change::Drop { name, .. } => {
+                    *name = self.to_local(name);
+                    Ok(())
+                }
+            };
+        }
+
+        changelist
+    }
+
+    /// Restrict the given set of replication offsets to the offsets for this upstream database,
+    /// mapping the names of tables back to their names in the upstream database
+    pub fn restrict_offsets(&self, offsets: ReplicationOffsets) -> ReplicationOffsets {
+        let ReplicationOffsets {
+            schema,
+            tables,
+            mut namespace_schemas,
+        } = offsets;
+
+        let schema = match self {
+            Self::Primary { .. } => schema,
+            Self::Additional(ns) => namespace_schemas.remove(ns),
+        };
+
+        ReplicationOffsets {
+            schema,
+            tables: tables
+                .into_iter()
+                .filter_map(|(table, offset)| Some((self.to_upstream(&table)?, offset)))
+                .collect(),
+            namespace_schemas: Default::default(),
+        }
+    }
+}
+
+struct ToLocalVisitor<'a>(&'a ReplicationNamespace);
+
+impl<'ast, 'a> VisitorMut<'ast> for ToLocalVisitor<'a> {
+    type Error = !;
+
+    fn visit_table(&mut self, table: &'ast mut Relation) -> Result<(), Self::Error> {
+        *table = self.0.to_local(table);
+        Ok(())
+    }
+
+    fn visit_sql_type(&mut self, sql_type: &'ast mut SqlType) -> Result<(), Self::Error> {
+        // Custom types are named by relations, too
+        if let SqlType::Other(ty) = sql_type {
+            *ty = self.0.to_local(ty);
+        }
+        visit_mut::walk_sql_type(self, sql_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dataflow_expression::Dialect;
+
+    use super::*;
+
+    fn shard() -> ReplicationNamespace {
+        ReplicationNamespace::Additional("shard2".into())
+    }
+
+    #[test]
+    fn relations_round_trip() {
+        let relation = Relation {
+            schema: Some("public".into()),
+            name: "t".into(),
+        };
+        let local = shard().to_local(&relation);
+        assert_eq!(local.schema.as_deref(), Some("shard2_public"));
+        assert_eq!(shard().to_upstream(&local), Some(relation));
+    }
+
+    #[test]
+    fn ownership() {
+        let primary = ReplicationNamespace::Primary {
+            additional: vec!["shard2".into()],
+        };
+        let primary_table = Relation {
+            schema: Some("public".into()),
+            name: "t".into(),
+        };
+        let shard_table = Relation {
+            schema: Some("shard2_public".into()),
+            name: "t".into(),
+        };
+        let other_table = Relation {
+            schema: Some("shard20_public".into()),
+            name: "t".into(),
+        };
+
+        assert!(primary.owns(&primary_table));
+        assert!(!primary.owns(&shard_table));
+        assert!(primary.owns(&other_table));
+        assert!(!shard().owns(&primary_table));
+        assert!(shard().owns(&shard_table));
+        assert!(!shard().owns(&other_table));
+        assert_eq!(primary.to_upstream(&shard_table), None);
+    }
+
+    #[test]
+    fn changelist_to_local() {
+        let mut changelist = ChangeList::from_str(
+            "CREATE TABLE public.t (id int PRIMARY KEY, status public.status);
+             DROP TABLE public.u;",
+            Dialect::DEFAULT_POSTGRESQL,
+        )
+        .unwrap();
+        changelist.schema_search_path = vec!["public".into()];
+
+        let changelist = shard().changelist_to_local(changelist);
+        assert_eq!(
+            changelist.schema_search_path,
+            vec![SqlIdentifier::from("shard2_public")]
+        );
+        match &changelist.changes[..] {
+            [Change::CreateTable { statement, .. }, Change::Drop { name, .. }] => {
+                assert_eq!(statement.table.schema.as_deref(), Some("shard2_public"));
+                assert_eq!(
+                    statement.body.as_ref().unwrap().fields[1].sql_type,
+                    SqlType::Other(Relation {
+                        schema: Some("shard2_public".into()),
+                        name: "status".into(),
+                    })
+                );
+                assert_eq!(name.schema.as_deref(), Some("shard2_public"));
+            }
+            changes => panic!("Unexpected changes: {changes:?}"),
+        }
+    }
+}
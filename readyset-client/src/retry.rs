@@ -0,0 +1,421 @@
+//! Automatic retries of idempotent requests made through [`View`](crate::View)s and
+//! [`Table`](crate::Table)s.
+//!
+//! By default, requests are never retried. A [`RetryPolicy`] can be configured for all handles
+//! created by a [`ReadySetHandle`](crate::ReadySetHandle) via
+//! [`ReadySetHandle::set_retry_policy`](crate::ReadySetHandle::set_retry_policy), or for a single
+//! handle via [`View::set_retry_policy`](crate::View::set_retry_policy) or
+//! [`Table::set_retry_policy`](crate::Table::set_retry_policy). Only requests which fail with a
+//! networking-related error are retried, after reconnecting to the workers the handle talks to.
+//!
+//! Only idempotent requests are retried:
+//!
+//! * All reads against views
+//! * Deletes by key, truncates, timestamps, and setting the replication offset or snapshot mode of
+//!   tables
+//!
+//! Inserts, updates, and deletes of individual rows are never retried, since a request which
+//! failed with a networking error may still have been applied.
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use readyset_errors::{bad_request_err, ReadySetError, ReadySetResult};
+use serde::{Deserialize, Serialize};
+
+/// Configuration for retrying idempotent requests made through a [`View`](crate::View) or
+/// [`Table`](crate::Table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// The maximum number of times to retry a single request. `0` disables retries.
+    pub max_retries: u32,
+    /// The amount of time to wait before the first retry of a request
+    pub initial_backoff: Duration,
+    /// The maximum amount of time to wait between retries of a request
+    pub max_backoff: Duration,
+    /// The factor to multiply the backoff by after each retry. Must be at least `1.0`.
+    pub backoff_multiplier: f64,
+    /// Whether to randomize the backoff, to avoid many clients retrying in lockstep. If `true`,
+    /// each backoff is chosen uniformly at random between half of and the full computed backoff.
+    pub jitter: bool,
+    /// Configuration for failing requests fast once many consecutive requests have failed, or
+    /// `None` to never fail fast
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+}
+
+impl Default for RetryPolicy {
+    /// The default policy never retries requests, and never fails fast
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+            backoff_multiplier: 2.0,
+            jitter: true,
+            circuit_breaker: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Construct a policy which retries requests up to `max_retries` times with the default
+    /// backoff
+    pub fn with_max_retries(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..Default::default()
+        }
+    }
+
+    /// Rejects backoff multipliers below `1.0`, an initial backoff above the maximum, and circuit
+    /// breakers with a failure threshold of zero
+    pub fn validate(&self) -> ReadySetResult<()> {
+        if self.backoff_multiplier.is_nan() || self.backoff_multiplier < 1.0 {
+            return Err(bad_request_err("backoff_multiplier must be at least 1.0"));
+        }
+        if self.initial_backoff > self.max_backoff {
+            return Err(bad_request_err(
+                "initial_backoff must not be greater than max_backoff",
+            ));
+        }
+        if self
+            .circuit_breaker
+            .as_ref()
+            .map_or(false, |cb| cb.failure_threshold == 0)
+        {
+            return Err(bad_request_err(
+                "circuit breaker failure_threshold must be greater than zero",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the amount of time to wait before making the given retry (counting from zero) of a
+    /// request, without jitter
+    fn base_backoff(&self, retry: u32) -> Duration {
+        let factor = self
+            .backoff_multiplier
+            .powi(retry.min(i32::MAX as u32) as i32);
+        self.initial_backoff
+            .mul_f64(factor.min(u32::MAX as f64))
+            .min(self.max_backoff)
+    }
+
+    /// Returns the amount of time to wait before making the given retry (counting from zero) of a
+    /// request
+    fn backoff(&self, retry: u32) -> Duration {
+        let base = self.base_backoff(retry);
+        if self.jitter && !base.is_zero() {
+            rand::thread_rng().gen_range(base / 2..=base)
+        } else {
+            base
+        }
+    }
+}
+
+/// Configuration for the circuit breaker of a [`View`](crate::View) or [`Table`](crate::Table).
+///
+/// Once `failure_threshold` consecutive requests through a handle have failed with a
+/// networking-related error, the circuit breaker *opens*, and all requests through the handle fail
+/// immediately with [`ReadySetError::CircuitOpen`] for `reset_timeout`. After that, a single
+/// request is let through as a trial - if it succeeds the circuit breaker closes again, and if it
+/// fails the circuit breaker stays open for another `reset_timeout`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// The number of consecutive failed requests after which to open the circuit breaker
+    pub failure_threshold: u32,
+    /// The amount of time to fail requests fast for once the circuit breaker opens
+    pub reset_timeout: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BreakerState {
+    /// Requests are allowed
+    Closed { consecutive_failures: u32 },
+    /// Requests fail fast until the given time
+    Open { until: Instant },
+    /// A single trial request was let through and hasn't finished yet. If it still hasn't finished
+    /// by the given time, another trial request is let through.
+    HalfOpen { until: Instant },
+}
+
+#[derive(Debug)]
+struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(BreakerState::Closed {
+                consecutive_failures: 0,
+            }),
+        }
+    }
+
+    /// Returns whether a request may be made
+    fn allow(&self) -> bool {
+        let now = Instant::now();
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut state = self.state.lock().unwrap();
+        match *state {
+            BreakerState::Closed { .. } => true,
+            BreakerState::Open { until } | BreakerState::HalfOpen { until } if now >= until => {
+                *state = BreakerState::HalfOpen {
+                    until: now + self.config.reset_timeout,
+                };
+                true
+            }
+            BreakerState::Open { .. } | BreakerState::HalfOpen { .. } => false,
+        }
+    }
+
+    fn record_success(&self) {
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut state = self.state.lock().unwrap();
+        *state = BreakerState::Closed {
+            consecutive_failures: 0,
+        };
+    }
+
+    fn record_failure(&self) {
+        let now = Instant::now();
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut state = self.state.lock().unwrap();
+        *state = match *state {
+            BreakerState::Closed {
+                consecutive_failures,
+            } if consecutive_failures + 1 < self.config.failure_threshold => BreakerState::Closed {
+                consecutive_failures: consecutive_failures + 1,
+            },
+            BreakerState::Open { until } => BreakerState::Open { until },
+            BreakerState::Closed { .. } | BreakerState::HalfOpen { .. } => BreakerState::Open {
+                until: now + self.config.reset_timeout,
+            },
+        };
+    }
+}
+
+/// The [`RetryPolicy`] of a single handle, along with the state of its circuit breaker, which is
+/// shared between all clones of the handle.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Retries {
+    policy: RetryPolicy,
+    breaker: Option<Arc<CircuitBreaker>>,
+}
+
+impl Retries {
+    pub(crate) fn new(policy: RetryPolicy) -> Self {
+        let breaker = policy
+            .circuit_breaker
+            .clone()
+            .map(|config| Arc::new(CircuitBreaker::new(config)));
+        Self { policy, breaker }
+    }
+
+    pub(crate) fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    /// Returns `true` if requests may be retried
+    pub(crate) fn enabled(&self) -> bool {
+        self.policy.max_retries > 0
+    }
+
+    /// Check that a request may be made against `target`, returning
+    /// [`ReadySetError::CircuitOpen`] if the circuit breaker is open
+    pub(crate) fn check_circuit<T>(&self, target: T) -> ReadySetResult<()>
+    where
+        T: FnOnce() -> String,
+    {
+        match &self.breaker {
+            Some(breaker) if !breaker.allow() => {
+                Err(ReadySetError::CircuitOpen { target: target() })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Record the result of the given attempt (counting from zero) of a request, and return the
+    /// amount of time to wait before retrying the request, or `None` if the request should not be
+    /// retried
+    pub(crate) fn after_attempt<T>(
+        &self,
+        attempt: u32,
+        result: &ReadySetResult<T>,
+    ) -> Option<Duration> {
+        match result {
+            Err(e) if e.is_networking_related() => {
+                if let Some(breaker) = &self.breaker {
+                    breaker.record_failure();
+                }
+                (attempt < self.policy.max_retries).then(|| self.policy.backoff(attempt))
+            }
+            _ => {
+                if let Some(breaker) = &self.breaker {
+                    breaker.record_success();
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Evaluate `$request` against `$handle` (which must have a `retries: Retries` field and a
+/// `reconnect` async method), retrying it according to the handle's [`RetryPolicy`].
+///
+/// `$target` is evaluated to build the name of the view or table for
+/// [`ReadySetError::CircuitOpen`].
+macro_rules! with_retries {
+    ($handle: expr, $target: expr, $request: expr) => {{
+        let mut attempt = 0;
+        loop {
+            $handle.retries.check_circuit(|| $target)?;
+            let result = $request;
+            match $handle.retries.after_attempt(attempt, &result) {
+                Some(backoff) => {
+                    tokio::time::sleep(backoff).await;
+                    $handle.reconnect().await;
+                    attempt += 1;
+                }
+                None => break result,
+            }
+        }
+    }};
+}
+
+pub(crate) use with_retries;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn networking_err() -> ReadySetResult<()> {
+        Err(ReadySetError::ServiceUnavailable)
+    }
+
+    #[test]
+    fn default_policy_never_retries() {
+        let retries = Retries::default();
+        assert_eq!(retries.after_attempt(0, &networking_err()), None);
+        retries.check_circuit(|| "t".into()).unwrap();
+    }
+
+    #[test]
+    fn only_retries_networking_errors() {
+        let retries = Retries::new(RetryPolicy::with_max_retries(2));
+        assert!(retries.after_attempt(0, &networking_err()).is_some());
+        assert!(retries.after_attempt(1, &networking_err()).is_some());
+        assert_eq!(retries.after_attempt(2, &networking_err()), None);
+        assert_eq!(
+            retries.after_attempt::<()>(0, &Err(ReadySetError::ReaderMissingKey)),
+            None
+        );
+        assert_eq!(retries.after_attempt(0, &Ok(())), None);
+    }
+
+    #[test]
+    fn backoff_grows_and_is_capped() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(50),
+            backoff_multiplier: 2.0,
+            jitter: false,
+            ..Default::default()
+        };
+        policy.validate().unwrap();
+        assert_eq!(policy.backoff(0), Duration::from_millis(10));
+        assert_eq!(policy.backoff(1), Duration::from_millis(20));
+        assert_eq!(policy.backoff(2), Duration::from_millis(40));
+        assert_eq!(policy.backoff(3), Duration::from_millis(50));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn jittered_backoff_is_within_bounds() {
+        let policy = RetryPolicy::default();
+        for retry in 0..10 {
+            let base = policy.base_backoff(retry);
+            let backoff = policy.backoff(retry);
+            assert!(backoff >= base / 2 && backoff <= base);
+        }
+    }
+
+    #[test]
+    fn invalid_policies() {
+        RetryPolicy {
+            backoff_multiplier: 0.5,
+            ..Default::default()
+        }
+        .validate()
+        .unwrap_err();
+        RetryPolicy {
+            backoff_multiplier: -1.0,
+            ..Default::default()
+        }
+        .validate()
+        .unwrap_err();
+
+        RetryPolicy {
+            initial_backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(1),
+            ..Default::default()
+        }
+        .validate()
+        .unwrap_err();
+
+        RetryPolicy {
+            circuit_breaker: Some(CircuitBreakerConfig {
+                failure_threshold: 0,
+                reset_timeout: Duration::from_secs(1),
+            }),
+            ..Default::default()
+        }
+        .validate()
+        .unwrap_err();
+    }
+
+    #[test]
+    fn circuit_breaker_opens_after_consecutive_failures() {
+        let retries = Retries::new(RetryPolicy {
+            circuit_breaker: Some(CircuitBreakerConfig {
+                failure_threshold: 2,
+                reset_timeout: Duration::from_secs(60),
+            }),
+            ..Default::default()
+        });
+
+        retries.after_attempt(0, &networking_err());
+        retries.after_attempt(0, &Ok(()));
+        retries.after_attempt(0, &networking_err());
+        retries.check_circuit(|| "t".into()).unwrap();
+
+        retries.after_attempt(0, &networking_err());
+        let err = retries.check_circuit(|| "t".into()).unwrap_err();
+        assert!(err.caused_by_circuit_open());
+
+        // Clones share the state of the circuit breaker
+        retries.clone().check_circuit(|| "t".into()).unwrap_err();
+    }
+
+    #[test]
+    fn circuit_breaker_closes_after_successful_trial() {
+        let retries = Retries::new(RetryPolicy {
+            circuit_breaker: Some(CircuitBreakerConfig {
+                failure_threshold: 1,
+                reset_timeout: Duration::ZERO,
+            }),
+            ..Default::default()
+        });
+
+        retries.after_attempt(0, &networking_err());
+        retries.check_circuit(|| "t".into()).unwrap();
+        retries.after_attempt(0, &networking_err());
+        retries.check_circuit(|| "t".into()).unwrap();
+        retries.after_attempt(0, &Ok(()));
+        retries.check_circuit(|| "t".into()).unwrap();
+        retries.check_circuit(|| "t".into()).unwrap();
+    }
+}
@@ -0,0 +1,143 @@
+//! Configuration which can be changed on a running deployment without restarting it.
+//!
+//! Updates are sent to the controller via
+//! [`ReadySetHandle::update_runtime_config`](crate::ReadySetHandle::update_runtime_config), which
+//! validates them and then propagates them to all workers and domains. Every update applied to a
+//! deployment is recorded in an audit log, which can be retrieved via
+//! [`ReadySetHandle::runtime_config_changes`](crate::ReadySetHandle::runtime_config_changes).
+use std::time::{Duration, SystemTime};
+
+use readyset_errors::{bad_request_err, ReadySetResult};
+use serde::{Deserialize, Serialize};
+
+/// A set of changes to the runtime configuration of a deployment.
+///
+/// Fields which are `None` are left unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeConfigUpdate {
+    /// The memory limit for each worker, in bytes. `Some(0)` removes the memory limit.
+    pub memory_limit: Option<usize>,
+    /// How often each worker compares its memory usage against its memory limit, and evicts if
+    /// it's over the limit
+    pub memory_check_frequency: Option<Duration>,
+    /// The amount of time to wait before timing out a view request. Only applies to view handles
+    /// created after the update.
+    pub view_request_timeout: Option<Duration>,
+    /// The amount of time to wait before timing out a table request. Only applies to table
+    /// handles created after the update.
+    pub table_request_timeout: Option<Duration>,
+    /// Whether to emit verbose metrics from domains
+    pub verbose_domain_metrics: Option<bool>,
+    /// Whether domains should update the metric tracking the in-memory size of materialized
+    /// state after every packet, rather than only when requested by the eviction worker
+    pub aggressively_update_state_sizes: Option<bool>,
+    /// The threshold over which replays to fill misses in readers are recorded as slow.
+    /// `Some(None)` disables tracking slow replays.
+    pub slow_replay_threshold: Option<Option<Duration>>,
+    /// Whether to log slow replays at the `WARN` level
+    pub log_slow_replays: Option<bool>,
+    /// Whether domains should record the time spent processing partial replays in each node along
+    /// their replay paths
+    #[serde(default)]
+    pub profile_replays: Option<bool>,
+}
+
+impl RuntimeConfigUpdate {
+    /// Returns `true` if this update doesn't change any configuration
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns `true` if this update changes any configuration held by domains
+    pub fn affects_domains(&self) -> bool {
+        self.verbose_domain_metrics.is_some()
+            || self.aggressively_update_state_sizes.is_some()
+            || self.slow_replay_threshold.is_some()
+            || self.log_slow_replays.is_some()
+            || self.profile_replays.is_some()
+    }
+
+    /// Returns `true` if this update changes any configuration held by workers
+    pub fn affects_workers(&self) -> bool {
+        self.memory_limit.is_some() || self.memory_check_frequency.is_some()
+    }
+
+    /// Rejects updates which don't change anything, and zero check frequencies or timeouts
+    pub fn validate(&self) -> ReadySetResult<()> {
+        if self.is_empty() {
+            return Err(bad_request_err(
+                "Runtime config update doesn't change anything",
+            ));
+        }
+
+        for (name, duration) in [
+            ("memory_check_frequency", self.memory_check_frequency),
+            ("view_request_timeout", self.view_request_timeout),
+            ("table_request_timeout", self.table_request_timeout),
+        ] {
+            if duration == Some(Duration::ZERO) {
+                return Err(bad_request_err(format!("{name} must be greater than zero")));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A single entry in the audit log of changes made to the runtime configuration of a deployment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeConfigChange {
+    /// When the change was applied
+    pub applied_at: SystemTime,
+    /// The change that was applied
+    pub update: RuntimeConfigUpdate,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_update_is_invalid() {
+        RuntimeConfigUpdate::default().validate().unwrap_err();
+    }
+
+    #[test]
+    fn zero_durations_are_invalid() {
+        RuntimeConfigUpdate {
+            memory_check_frequency: Some(Duration::ZERO),
+            ..Default::default()
+        }
+        .validate()
+        .unwrap_err();
+
+        RuntimeConfigUpdate {
+            view_request_timeout: Some(Duration::ZERO),
+            ..Default::default()
+        }
+        .validate()
+        .unwrap_err();
+    }
+
+    #[test]
+    fn disabling_slow_replays_is_valid() {
+        let update = RuntimeConfigUpdate {
+            slow_replay_threshold: Some(None),
+            ..Default::default()
+        };
+        update.validate().unwrap();
+        assert!(update.affects_domains());
+        assert!(!update.affects_workers());
+    }
+
+    #[test]
+    fn profiling_replays_affects_only_domains() {
+        let update = RuntimeConfigUpdate {
+            profile_replays: Some(true),
+            ..Default::default()
+        };
+        update.validate().unwrap();
+        assert!(update.affects_domains());
+        assert!(!update.affects_workers());
+    }
+}
@@ -0,0 +1,90 @@
+//! Descriptions of the schemas of the base tables and views known to ReadySet, as returned by
+//! [`ReadySetHandle::schema`](crate::ReadySetHandle::schema).
+//!
+//! These types serialize to JSON in a form intended to be consumed by external tooling, such as
+//! ORMs generating typed bindings against the cache.
+
+use nom_sql::{Relation, SqlIdentifier, SqlType};
+use readyset_data::DfType;
+use serde::{Deserialize, Serialize};
+
+/// The schemas of every base table and view known to ReadySet
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchemaRegistry {
+    /// The schemas of all base tables, ordered by name
+    pub tables: Vec<RelationSchema>,
+    /// The schemas of all views, ordered by name
+    pub views: Vec<RelationSchema>,
+}
+
+impl SchemaRegistry {
+    /// Returns the schema of the base table with the given name, if any
+    pub fn table(&self, name: &Relation) -> Option<&RelationSchema> {
+        self.tables.iter().find(|t| t.name == *name)
+    }
+
+    /// Returns the schema of the view with the given name, if any
+    pub fn view(&self, name: &Relation) -> Option<&RelationSchema> {
+        self.views.iter().find(|v| v.name == *name)
+    }
+}
+
+/// The schema of a single base table or view
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelationSchema {
+    /// The name of the table or view
+    pub name: Relation,
+    /// The columns of the table, or the columns returned by the view, in order
+    pub columns: Vec<ColumnDescription>,
+    /// The names of the key columns: the primary key of a base table, or the columns a view is
+    /// looked up by. Empty for tables without a primary key and for views without parameters.
+    ///
+    /// The key columns of a view are not necessarily among the columns it returns.
+    pub key_columns: Vec<SqlIdentifier>,
+}
+
+/// The description of a single column of a base table or view
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnDescription {
+    /// The name of the column
+    pub name: SqlIdentifier,
+    /// The SQL type of the column as declared in the upstream database. Always known for columns
+    /// of base tables; for columns of views, only known if the column's values are taken
+    /// directly from a column of a base table.
+    pub sql_type: Option<SqlType>,
+    /// The type ReadySet uses to represent values of the column
+    pub df_type: DfType,
+    /// Whether the column may contain `NULL`. Columns of views are only reported as non-nullable
+    /// if their values are taken directly from a non-nullable column of a base table and the view
+    /// contains no outer joins.
+    pub nullable: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_round_trip() {
+        let schema = SchemaRegistry {
+            tables: vec![RelationSchema {
+                name: "t".into(),
+                columns: vec![ColumnDescription {
+                    name: "x".into(),
+                    sql_type: Some(SqlType::Int(None)),
+                    df_type: DfType::Int,
+                    nullable: false,
+                }],
+                key_columns: vec!["x".into()],
+            }],
+            views: vec![],
+        };
+        let json = serde_json::to_string(&schema).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SchemaRegistry>(&json).unwrap(),
+            schema
+        );
+        assert!(schema.table(&"t".into()).is_some());
+        assert!(schema.view(&"t".into()).is_none());
+    }
+}
@@ -77,3 +77,52 @@ impl Display for SnapshotStatus {
         write!(f, "{}", s)
     }
 }
+
+/// Whether replication from the upstream database has been paused, returned by
+/// [`ReadySetHandle::replication_pause_state`](crate::ReadySetHandle::replication_pause_state).
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub enum ReplicationPauseState {
+    /// Replication is running normally.
+    #[default]
+    Running,
+    /// Replication has been asked to pause, and will do so once it has finished applying any
+    /// in-flight transaction.
+    Pausing,
+    /// Replication is paused, and will not apply any changes from the upstream database until it
+    /// is resumed.
+    Paused {
+        /// The replication offset that replication was paused at, which is where it will pick up
+        /// from when resumed.
+        offset: ReplicationOffset,
+    },
+}
+
+impl ReplicationPauseState {
+    /// Returns `true` if replication has been asked to pause, whether or not it has finished
+    /// doing so yet.
+    pub fn is_paused(&self) -> bool {
+        !matches!(self, ReplicationPauseState::Running)
+    }
+}
+
+/// The progress of recovering domains which were running on workers that have failed, returned
+/// by [`ReadySetHandle::recovery_status`](crate::ReadySetHandle::recovery_status).
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub struct RecoveryStatus {
+    /// The number of workers that have failed since the current leader was elected.
+    pub failed_workers: u64,
+    /// The total number of domains in the dataflow graph.
+    pub total_domains: usize,
+    /// The number of domains which are not currently running on any worker, and are waiting to be
+    /// rescheduled and have their state rebuilt.
+    pub unplaced_domains: usize,
+    /// Whether the leader is currently retrying recovery of unplaced domains in the background.
+    pub recovery_running: bool,
+}
+
+impl RecoveryStatus {
+    /// Returns `true` if every domain is running on a worker.
+    pub fn is_recovered(&self) -> bool {
+        self.unplaced_domains == 0
+    }
+}
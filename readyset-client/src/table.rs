@@ -34,6 +34,7 @@ use tracing::{debug_span, error, trace, trace_span, Span};
 use vec_map::VecMap;
 
 use crate::internal::*;
+use crate::retry::{with_retries, Retries, RetryPolicy};
 use crate::{consistency, Tagged, Tagger, CONNECTION_FROM_BASE};
 
 // TODO(justin): Make write propagation sample rate configurable.
@@ -333,28 +334,13 @@ impl TableBuilder {
 
             // one entry per shard so that we can send sharded requests in parallel even if
             // they happen to be targeting the same machine.
-            let mut rpcs = rpcs.lock().await;
+            let mut rpcs_guard = rpcs.lock().await;
             #[allow(clippy::significant_drop_in_scrutinee)]
-            let s = match rpcs.entry((addr, shardi)) {
+            let s = match rpcs_guard.entry((addr, shardi)) {
                 Entry::Occupied(e) => e.get().clone(),
-                Entry::Vacant(h) => {
-                    // TODO: maybe always use the same local port?
-                    let (c, w) = Buffer::pair(
-                        ConcurrencyLimit::new(
-                            Balance::new(make_table_discover(addr, self.table_request_timeout)),
-                            crate::PENDING_LIMIT,
-                        ),
-                        crate::BUFFER_TO_POOL,
-                    );
-                    use tracing_futures::Instrument;
-                    tokio::spawn(w.instrument(debug_span!(
-                        "table_worker",
-                        addr = %addr,
-                        shard = shardi
-                    )));
-                    h.insert(c.clone());
-                    c
-                }
+                Entry::Vacant(h) => h
+                    .insert(connect_shard(addr, shardi, self.table_request_timeout))
+                    .clone(),
             };
             conns.push(s);
         }
@@ -372,10 +358,31 @@ impl TableBuilder {
             shards: conns,
             last_trace_sample: Instant::now(),
             request_timeout: self.table_request_timeout,
+            rpcs,
+            retries: Default::default(),
         }
     }
 }
 
+/// Open a new pool of connections to the given shard of a base table at the given address
+fn connect_shard(addr: SocketAddr, shardi: usize, timeout: Duration) -> TableRpc {
+    // TODO: maybe always use the same local port?
+    let (c, w) = Buffer::pair(
+        ConcurrencyLimit::new(
+            Balance::new(make_table_discover(addr, timeout)),
+            crate::PENDING_LIMIT,
+        ),
+        crate::BUFFER_TO_POOL,
+    );
+    use tracing_futures::Instrument;
+    tokio::spawn(w.instrument(debug_span!(
+        "table_worker",
+        addr = %addr,
+        shard = shardi
+    )));
+    c
+}
+
 /// A `Table` is used to perform writes, deletes, and other operations to data in base tables.
 ///
 /// If you create multiple `Table` handles from a single `ReadySetHandle`, they may share
@@ -397,6 +404,10 @@ pub struct Table {
     shard_addrs: Vec<SocketAddr>,
     last_trace_sample: Instant,
     request_timeout: Duration,
+    /// The pool of connections shared by all handles built by the same `ReadySetHandle`, which
+    /// broken connections are replaced in when reconnecting
+    rpcs: Arc<Mutex<HashMap<(SocketAddr, usize), TableRpc>>>,
+    retries: Retries,
 }
 
 impl fmt::Debug for Table {
@@ -652,6 +663,7 @@ impl Table {
 }
 
 /// A request to the table service.
+#[derive(Clone)]
 pub enum TableRequest {
     /// A set of operations to apply on the table.
     TableOperations(Vec<TableOperation>),
@@ -850,6 +862,54 @@ impl Table {
             .map_err(|_| internal_err!("Timeout during table request"))?
     }
 
+    /// Make an idempotent request, retrying it according to this table's [`RetryPolicy`]
+    async fn idempotent_request(&mut self, r: TableRequest) -> ReadySetResult<()> {
+        with_retries!(self, self.target(), self.request(r.clone()).await)
+    }
+
+    /// Make an idempotent request with a timeout, retrying it according to this table's
+    /// [`RetryPolicy`]
+    async fn idempotent_request_with_timeout(&mut self, r: TableRequest) -> ReadySetResult<()> {
+        with_retries!(
+            self,
+            self.target(),
+            self.request_with_timeout(r.clone()).await
+        )
+    }
+
+    /// Returns the [`RetryPolicy`] used for requests made through this handle
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        self.retries.policy()
+    }
+
+    /// Set the [`RetryPolicy`] used for requests made through this handle, resetting its circuit
+    /// breaker.
+    ///
+    /// Only idempotent operations are retried: deletes by key, truncates, timestamp updates, and
+    /// setting the replication offset or snapshot mode.
+    ///
+    /// Returns an error if the policy is invalid.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) -> ReadySetResult<()> {
+        policy.validate()?;
+        self.retries = Retries::new(policy);
+        Ok(())
+    }
+
+    /// The name of this table, for use in errors
+    fn target(&self) -> String {
+        self.table_name.display_unquoted().to_string()
+    }
+
+    /// Replace the connections to all of this table's shards with new ones, both in this handle
+    /// and in the pool of connections shared with other handles
+    async fn reconnect(&mut self) {
+        let mut rpcs = self.rpcs.lock().await;
+        for (shardi, (shard, &addr)) in self.shards.iter_mut().zip(&self.shard_addrs).enumerate() {
+            *shard = connect_shard(addr, shardi, self.request_timeout);
+            rpcs.insert((addr, shardi), shard.clone());
+        }
+    }
+
     /// Insert a single row of data into this base table.
     pub async fn insert<V>(&mut self, u: V) -> ReadySetResult<()>
     where
@@ -892,7 +952,7 @@ impl Table {
     where
         I: Into<Vec<DfValue>>,
     {
-        self.request_with_timeout(TableRequest::TableOperations(vec![
+        self.idempotent_request_with_timeout(TableRequest::TableOperations(vec![
             TableOperation::DeleteByKey { key: key.into() },
         ]))
         .await
@@ -981,7 +1041,7 @@ impl Table {
 
     /// Delete all rows from this base table
     pub async fn truncate(&mut self) -> ReadySetResult<()> {
-        self.request_with_timeout(TableRequest::TableOperations(vec![
+        self.idempotent_request_with_timeout(TableRequest::TableOperations(vec![
             TableOperation::Truncate,
         ]))
         .await
@@ -989,7 +1049,8 @@ impl Table {
 
     /// Updates the timestamp of the base table in the data flow graph.
     pub async fn update_timestamp(&mut self, t: consistency::Timestamp) -> ReadySetResult<()> {
-        self.request_with_timeout(TableRequest::Timestamp(t)).await
+        self.idempotent_request_with_timeout(TableRequest::Timestamp(t))
+            .await
     }
 
     /// Set the replication offset for this table to the given value.
@@ -1004,7 +1065,7 @@ impl Table {
         &mut self,
         offset: ReplicationOffset,
     ) -> ReadySetResult<()> {
-        self.request(TableRequest::TableOperations(vec![
+        self.idempotent_request(TableRequest::TableOperations(vec![
             TableOperation::SetReplicationOffset(offset),
         ]))
         .await
@@ -1013,7 +1074,7 @@ impl Table {
     /// Enable or disable snapshot mode for this table. In snapshot mode compactions are disabled
     /// and writes don't go into WAL.
     pub async fn set_snapshot_mode(&mut self, snapshot: bool) -> ReadySetResult<()> {
-        self.request(TableRequest::TableOperations(vec![
+        self.idempotent_request(TableRequest::TableOperations(vec![
             TableOperation::SetSnapshotMode(snapshot),
         ]))
         .await
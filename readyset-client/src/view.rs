@@ -9,14 +9,14 @@ use std::net::SocketAddr;
 use std::ops::Range;
 use std::sync::Arc;
 use std::task::{Context, Poll};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use array2::Array2;
 use async_bincode::{AsyncBincodeStream, AsyncDestination};
 use dataflow_expression::{BinaryOperator as DfBinaryOperator, Dialect, Expr as DfExpr};
 use futures_util::future::TryFutureExt;
 use futures_util::stream::futures_unordered::FuturesUnordered;
-use futures_util::stream::{StreamExt, TryStreamExt};
+use futures_util::stream::{self, Stream, StreamExt, TryStreamExt};
 use futures_util::{future, ready};
 use nom_sql::{
     BinaryOperator, Column, ColumnConstraint, ColumnSpecification, ItemPlaceholder, Literal,
@@ -26,9 +26,12 @@ use petgraph::graph::NodeIndex;
 use proptest::arbitrary::Arbitrary;
 use rand::prelude::IteratorRandom;
 use rand::thread_rng;
-use readyset_data::{Bound, BoundedRange, DfType, DfValue, IntoBoundedRange, RangeBounds};
+use readyset_data::{
+    Bound, BoundedRange, DfType, DfValue, IntoBoundedRange, RangeBounds, Timezone,
+};
 use readyset_errors::{
-    internal, internal_err, rpc_err, unsupported, view_err, ReadySetError, ReadySetResult,
+    bad_request_err, internal, internal_err, rpc_err, unsupported, view_err, ReadySetError,
+    ReadySetResult,
 };
 use readyset_sql_passes::anonymize::{Anonymize, Anonymizer};
 use readyset_tracing::child_span;
@@ -44,7 +47,7 @@ use tower::buffer::Buffer;
 use tower::limit::concurrency::ConcurrencyLimit;
 use tower::timeout::Timeout;
 use tower_service::Service;
-use tracing::{debug_span, error, instrument, trace};
+use tracing::{debug, debug_span, error, instrument, trace};
 use tracing_futures::Instrument;
 use vec1::{vec1, Vec1};
 
@@ -52,7 +55,9 @@ pub(crate) mod results;
 
 use self::results::{ResultIterator, Results};
 use crate::consistency::Timestamp;
-use crate::{ReaderAddress, Tagged, Tagger};
+use crate::controller::ReadySetHandle;
+use crate::retry::{with_retries, Retries, RetryPolicy};
+use crate::{ReaderAddress, Tagged, Tagger, ViewFilter, ViewRequest};
 
 type Transport = AsyncBincodeStream<
     tokio::net::TcpStream,
@@ -107,6 +112,137 @@ impl ViewCreateRequest {
     }
 }
 
+/// Limits on the rate at which reads may be made against a single view, so that one client can't
+/// flood a shared deployment with misses that evict everyone else's state.
+///
+/// Limits are enforced independently by each shard and replica of the view's reader. Reads which
+/// would exceed a limit are rejected with [`ReadySetError::ViewThrottled`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewRateLimit {
+    /// The maximum number of reads per second. Bursts of up to this many reads at once are
+    /// allowed.
+    pub max_requests_per_second: Option<u32>,
+    /// The maximum number of reads which may be waiting for misses to be filled at once. Reads
+    /// which miss while this many reads are already waiting are rejected without triggering a
+    /// replay.
+    pub max_concurrent_misses: Option<u32>,
+}
+
+impl ViewRateLimit {
+    /// Returns `true` if this doesn't limit reads at all
+    pub fn is_unlimited(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Rejects a limit of zero requests per second
+    pub fn validate(&self) -> ReadySetResult<()> {
+        if self.max_requests_per_second == Some(0) {
+            return Err(bad_request_err(
+                "max_requests_per_second must be greater than zero",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Configuration for serving possibly-stale results from a single view instead of blocking or
+/// erroring, so that user-facing endpoints can degrade gracefully.
+///
+/// When enabled, each shard and replica of the view's reader retains the last-known values of
+/// keys which have been evicted. Reads of those keys are answered from the retained values while
+/// the keys are being replayed, and reads are answered from whatever the reader has while
+/// replication from the upstream database is down. Such replies are flagged with
+/// [`ReadReplyStats::stale`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewStaleReads {
+    /// Whether stale reads are enabled for the view
+    pub enabled: bool,
+    /// The maximum number of evicted keys to retain last-known values for, per shard and replica
+    /// of the view's reader. Once this many keys are retained, the oldest are discarded first.
+    pub max_retained_keys: usize,
+}
+
+impl Default for ViewStaleReads {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_retained_keys: Self::DEFAULT_MAX_RETAINED_KEYS,
+        }
+    }
+}
+
+impl ViewStaleReads {
+    /// The default value for [`max_retained_keys`](Self::max_retained_keys)
+    pub const DEFAULT_MAX_RETAINED_KEYS: usize = 10_000;
+
+    /// Returns a configuration enabling stale reads, retaining the default number of keys
+    pub fn enabled() -> Self {
+        Self {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    /// Rejects enabling stale reads without retaining any keys
+    pub fn validate(&self) -> ReadySetResult<()> {
+        if self.enabled && self.max_retained_keys == 0 {
+            return Err(bad_request_err(
+                "max_retained_keys must be greater than zero",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Limits on the size of the results of a single read against a view, so that reads of keys with
+/// huge numbers of rows can't allocate unbounded amounts of memory in the reader or the client.
+///
+/// Limits can be configured for a view with [`ReadySetHandle::set_view_result_limit`], and for a
+/// single read with [`ViewQuery::result_limit`]; if both are set, the tighter of each limit
+/// applies. Limits are enforced independently by each shard of the view's reader. Results which
+/// exceed a limit are cut short and flagged with [`ReadReplyStats::truncated`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewResultLimit {
+    /// The maximum number of rows to return
+    pub max_rows: Option<usize>,
+    /// The maximum size of the rows to return, in bytes, as measured by their serialized size
+    pub max_bytes: Option<usize>,
+}
+
+impl ViewResultLimit {
+    /// Returns `true` if this doesn't limit results at all
+    pub fn is_unlimited(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns the tighter of each of the limits in `self` and `other`
+    #[must_use]
+    pub fn min(&self, other: &Self) -> Self {
+        fn min(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            }
+        }
+
+        Self {
+            max_rows: min(self.max_rows, other.max_rows),
+            max_bytes: min(self.max_bytes, other.max_bytes),
+        }
+    }
+
+    /// Rejects limits of zero rows or zero bytes
+    pub fn validate(&self) -> ReadySetResult<()> {
+        if self.max_rows == Some(0) {
+            return Err(bad_request_err("max_rows must be greater than zero"));
+        }
+        if self.max_bytes == Some(0) {
+            return Err(bad_request_err("max_bytes must be greater than zero"));
+        }
+        Ok(())
+    }
+}
+
 /// Representation of how a key column in a [`View`] maps back to a placeholder in the original
 /// query
 #[derive(Hash, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -155,6 +291,20 @@ pub struct ColumnBase {
     pub attnum: Option<i16>,
 }
 
+/// Where the values of a single output column of a view are derived from, as returned by
+/// [`ReadySetHandle::column_provenance`](crate::ReadySetHandle::column_provenance)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnProvenance {
+    /// The name of the column in the output of the view
+    pub column: SqlIdentifier,
+    /// The columns of base tables which the values of the column are computed from
+    pub base_columns: Vec<Column>,
+    /// The SQL text of the expressions and aggregates which are applied to the base columns to
+    /// compute the column, outermost first. Empty if the column is a base column passed through
+    /// unchanged
+    pub expressions: Vec<String>,
+}
+
 /// Combines the specification for a columns with its base name
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ColumnSchema {
@@ -803,6 +953,15 @@ pub enum ReadQuery {
         /// Where to read from
         target: ReaderAddress,
     },
+    /// Read up to `limit` keys of a leaf view in key order, along with their rows
+    Scan {
+        /// Where to read from
+        target: ReaderAddress,
+        /// The first key to read, or `None` to start from the smallest key in the view
+        start: Option<Vec<DfValue>>,
+        /// The maximum number of keys to read
+        limit: usize,
+    },
 }
 
 /// The result of a lookup to a view.
@@ -842,6 +1001,12 @@ impl<D> LookupResult<D> {
 pub struct ReadReplyStats {
     /// The count of cache misses which have occurred
     pub cache_misses: u64,
+    /// Whether any of the results may be stale, because they were served from last-known values
+    /// while keys were being replayed or while replication from the upstream database was down.
+    /// See [`ViewStaleReads`].
+    pub stale: bool,
+    /// Whether the results were cut short because they exceeded a [`ViewResultLimit`]
+    pub truncated: bool,
 }
 
 impl ReadReplyStats {
@@ -850,10 +1015,23 @@ impl ReadReplyStats {
     pub fn merge(&self, other: &Self) -> Self {
         Self {
             cache_misses: self.cache_misses + other.cache_misses,
+            stale: self.stale || other.stale,
+            truncated: self.truncated || other.truncated,
         }
     }
 }
 
+/// A batch of the keys of a view in key order, along with the rows of the view for each key,
+/// returned by [`ReaderHandle::scan`]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct ViewScanBatch {
+    /// The keys in the batch in ascending order, each along with the rows of the view for that key
+    pub keys: Vec<(Vec<DfValue>, Vec<Vec<DfValue>>)>,
+    /// The smallest key in the view after the keys in this batch, to pass as the start key of the
+    /// next call to [`ReaderHandle::scan`], or `None` if there are no more keys in the view
+    pub next: Option<Vec<DfValue>>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub enum ReadReply<D = ReadReplyBatch> {
     /// A reply to a normal lookup request
@@ -862,6 +1040,8 @@ pub enum ReadReply<D = ReadReplyBatch> {
     Size(usize),
     // Read keys of view
     Keys(Vec<Vec<DfValue>>),
+    /// A reply to a scan request
+    Scan(ReadySetResult<ViewScanBatch>),
 }
 
 impl<D> ReadReply<D> {
@@ -975,33 +1155,13 @@ impl ReaderHandleBuilder {
 
             // one entry per shard so that we can send sharded requests in parallel even if
             // they happen to be targeting the same machine.
-            let mut rpcs = rpcs.lock().await;
+            let mut rpcs_guard = rpcs.lock().await;
             #[allow(clippy::significant_drop_in_scrutinee)]
-            let s = match rpcs.entry((shard_addr, shardi)) {
+            let s = match rpcs_guard.entry((shard_addr, shardi)) {
                 Entry::Occupied(e) => e.get().clone(),
-                Entry::Vacant(h) => {
-                    // TODO: maybe always use the same local port?
-                    let (c, w) = Buffer::pair(
-                        Timeout::new(
-                            ConcurrencyLimit::new(
-                                Balance::new(make_views_discover(
-                                    shard_addr,
-                                    self.view_request_timeout,
-                                )),
-                                crate::PENDING_LIMIT,
-                            ),
-                            self.view_request_timeout,
-                        ),
-                        crate::BUFFER_TO_POOL,
-                    );
-                    tokio::spawn(w.instrument(debug_span!(
-                        "view_worker",
-                        addr = %shard_addr,
-                        shard = shardi
-                    )));
-                    h.insert(c.clone());
-                    c
-                }
+                Entry::Vacant(h) => h
+                    .insert(connect_shard(shard_addr, shardi, self.view_request_timeout))
+                    .clone(),
             };
             conns.push(s);
         }
@@ -1019,10 +1179,35 @@ impl ReaderHandleBuilder {
                     self.name.display_unquoted()
                 )
             })?,
+            rpcs,
+            request_timeout: self.view_request_timeout,
+            retries: Default::default(),
+            resolver: None,
         })
     }
 }
 
+/// Open a new pool of connections to the given shard of a reader at the given address
+fn connect_shard(shard_addr: SocketAddr, shardi: usize, timeout: Duration) -> ViewRpc {
+    // TODO: maybe always use the same local port?
+    let (c, w) = Buffer::pair(
+        Timeout::new(
+            ConcurrencyLimit::new(
+                Balance::new(make_views_discover(shard_addr, timeout)),
+                crate::PENDING_LIMIT,
+            ),
+            timeout,
+        ),
+        crate::BUFFER_TO_POOL,
+    );
+    tokio::spawn(w.instrument(debug_span!(
+        "view_worker",
+        addr = %shard_addr,
+        shard = shardi
+    )));
+    c
+}
+
 impl ViewBuilder {
     /// Build a `View` from `ViewBuilder`. Wraps ReaderHandleBuilder::build().
     pub async fn build(
@@ -1068,6 +1253,53 @@ pub struct ReaderHandle {
     key_mapping: Vec<(ViewPlaceholder, KeyColumnIdx)>,
     shards: Vec1<ViewRpc>,
     shard_addrs: Vec<SocketAddr>,
+    /// The pool of connections shared by all handles built by the same `ReadySetHandle`, which
+    /// broken connections are replaced in when reconnecting
+    rpcs: Arc<Mutex<HashMap<(SocketAddr, usize), ViewRpc>>>,
+    request_timeout: Duration,
+    retries: Retries,
+    /// Used to follow the reader to its new location if its domain moves to another worker, or
+    /// `None` if the handle wasn't obtained from a [`ReadySetHandle`]
+    resolver: Option<ViewResolver>,
+}
+
+/// The information needed to look up the current location of the reader for a [`ReaderHandle`]
+/// from the controller
+#[derive(Clone)]
+struct ViewResolver {
+    controller: ReadySetHandle,
+    request: ViewRequest,
+}
+
+/// Returns true if `error`, returned from a request to a reader, may have been caused by the
+/// reader having moved to a different worker
+fn reader_may_have_moved(error: &ReadySetError) -> bool {
+    error.is_networking_related()
+        || error.caused_by_reader_not_found()
+        || error.caused_by_view_destroyed()
+}
+
+/// Returns the smaller of two optional keys, ignoring keys which are `None`
+fn min_key(a: Option<Vec<DfValue>>, b: Option<Vec<DfValue>>) -> Option<Vec<DfValue>> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Evaluate `$request` against `$handle`, retrying it according to the handle's [`RetryPolicy`].
+/// If it still fails with an error indicating that the reader may have moved to another worker,
+/// look up the reader's current location, and if it did move, send the request once more to its
+/// new location.
+macro_rules! with_re_resolution {
+    ($handle: expr, $request: expr) => {{
+        match with_retries!($handle, $handle.target(), $request) {
+            Err(e) if reader_may_have_moved(&e) && $handle.re_resolve().await => {
+                with_retries!($handle, $handle.target(), $request)
+            }
+            result => result,
+        }
+    }};
 }
 
 impl fmt::Debug for ReaderHandle {
@@ -1136,6 +1368,39 @@ pub struct ViewQuery {
     // TODO(justin): Verify reads block on timestamps once timestamps have a definition
     // with Ord.
     pub timestamp: Option<Timestamp>,
+    /// If set, answer the query against the state of the view as of this point in the past,
+    /// rather than its current state. Only supported for views with history retention configured,
+    /// and only for points within the retention window.
+    #[serde(default)]
+    pub as_of: Option<AsOf>,
+    /// If set, limits on the size of the results of this read, applied in addition to any
+    /// configured for the view. See [`ViewResultLimit`].
+    #[serde(default)]
+    pub result_limit: Option<ViewResultLimit>,
+    /// If set, look up `key_comparisons` (which must all be equality comparisons) in the
+    /// secondary index on these columns of the view, rather than in the view's own index. See
+    /// [`ReaderHandle::lookup_by_index`].
+    #[serde(default)]
+    pub secondary_index: Option<Vec<usize>>,
+    /// If set, the maximum amount of time to wait for keys which miss to be filled by upqueries
+    /// before failing the read with [`ReadySetError::UpqueryTimeout`], overriding both the timeout
+    /// configured for the view (see
+    /// [`ReadySetHandle::set_view_upquery_timeout`](crate::ReadySetHandle::set_view_upquery_timeout))
+    /// and the server's default. Keys which are filled after the read times out remain in the
+    /// view, so later reads of them hit.
+    #[serde(default)]
+    pub upquery_timeout: Option<Duration>,
+}
+
+/// A point in the past to answer a read against a [`View`] as of
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum AsOf {
+    /// The state of the view at the given wall-clock time
+    Time(SystemTime),
+    /// The state of the view at the point it first reflected all the writes covered by the given
+    /// [`Timestamp`]. Reads against several views as of the same timestamp observe the same set of
+    /// writes, so are consistent with each other.
+    Timestamp(Timestamp),
 }
 
 // TODO(andrew): consolidate From impls once RYW fully adopted
@@ -1150,6 +1415,10 @@ impl From<(Vec<KeyComparison>, bool, Option<Timestamp>)> for ViewQuery {
             offset: None,
             filter: None,
             timestamp: ticket,
+            as_of: None,
+            result_limit: None,
+            secondary_index: None,
+            upquery_timeout: None,
         }
     }
 }
@@ -1163,6 +1432,10 @@ impl From<(Vec<KeyComparison>, bool)> for ViewQuery {
             limit: None,
             offset: None,
             timestamp: None,
+            as_of: None,
+            result_limit: None,
+            secondary_index: None,
+            upquery_timeout: None,
         }
     }
 }
@@ -1238,7 +1511,14 @@ impl Service<ViewQuery> for ReaderHandle {
         span.in_scope(|| trace!("shard request"));
         let mut shard_queries = vec![Vec::new(); self.shards.len()];
         for comparison in query.key_comparisons.drain(..) {
-            for shard in comparison.shard_keys(self.shards.len()) {
+            // Views are sharded by their key, so rows with any given value in the columns of a
+            // secondary index may be in any shard
+            let shards = if query.secondary_index.is_some() {
+                (0..self.shards.len()).collect()
+            } else {
+                comparison.shard_keys(self.shards.len())
+            };
+            for shard in shards {
                 #[allow(clippy::indexing_slicing)]
                 // We built `shard_queries` to be the correct length, so it's safe to access
                 // it by index in this case.
@@ -1287,6 +1567,10 @@ impl Service<ViewQuery> for ReaderHandle {
                             limit: query.limit,
                             offset: query.offset,
                             timestamp: query.timestamp.clone(),
+                            as_of: query.as_of.clone(),
+                            result_limit: query.result_limit,
+                            secondary_index: query.secondary_index.clone(),
+                            upquery_timeout: query.upquery_timeout,
                         },
                     }));
 
@@ -1374,6 +1658,10 @@ impl ReaderHandle {
     /// Note that you must also continue to poll this `View` for the returned future to resolve.
     #[instrument(level = "info", skip(self))]
     pub async fn len(&mut self) -> ReadySetResult<usize> {
+        with_re_resolution!(self, self.len_once().await)
+    }
+
+    async fn len_once(&mut self) -> ReadySetResult<usize> {
         future::poll_fn(|cx| self.poll_ready(cx)).await?;
 
         let node = self.node;
@@ -1419,6 +1707,10 @@ impl ReaderHandle {
     /// Get the current keys of this view. For debugging only.
     #[instrument(level = "info", skip(self))]
     pub async fn keys(&mut self) -> ReadySetResult<Vec<Vec<DfValue>>> {
+        with_re_resolution!(self, self.keys_once().await)
+    }
+
+    async fn keys_once(&mut self) -> ReadySetResult<Vec<Vec<DfValue>>> {
         future::poll_fn(|cx| self.poll_ready(cx)).await?;
 
         let node = self.node;
@@ -1455,6 +1747,81 @@ impl ReaderHandle {
         Ok(vec)
     }
 
+    /// Read up to `batch_size` keys of this view in key order, starting at `start_key` (or the
+    /// smallest key in the view, if `None`), along with the rows of the view for each key. To walk
+    /// the whole view, pass the [`next`](ViewScanBatch::next) key of each batch as the `start_key`
+    /// of the following call, until it's `None`.
+    ///
+    /// Only keys which are currently cached are returned, and scanning never causes keys to be
+    /// replayed. Keys may be added or removed between batches, so a scan is not a consistent
+    /// snapshot of the view. Returns an error unless the reader for this view is ordered by key,
+    /// which is the case for views of queries with range parameters.
+    #[instrument(level = "info", skip(self))]
+    pub async fn scan(
+        &mut self,
+        start_key: Option<Vec<DfValue>>,
+        batch_size: usize,
+    ) -> ReadySetResult<ViewScanBatch> {
+        if batch_size == 0 {
+            return Err(bad_request_err("Scan batch size must be greater than 0"));
+        }
+        with_re_resolution!(self, self.scan_once(start_key.clone(), batch_size).await)
+    }
+
+    async fn scan_once(
+        &mut self,
+        start_key: Option<Vec<DfValue>>,
+        batch_size: usize,
+    ) -> ReadySetResult<ViewScanBatch> {
+        future::poll_fn(|cx| self.poll_ready(cx)).await?;
+
+        let node = self.node;
+        let name = self.name.clone();
+        let mut rsps = self
+            .shards
+            .iter_mut()
+            .enumerate()
+            .map(|(shardi, shard)| {
+                shard.call(Instrumented::from(Tagged::from(ReadQuery::Scan {
+                    target: ReaderAddress {
+                        node,
+                        name: name.clone(),
+                        shard: shardi,
+                    },
+                    start: start_key.clone(),
+                    limit: batch_size,
+                })))
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        // Each shard replies with its own first `batch_size` keys, so the first `batch_size` keys
+        // of the view are the first `batch_size` keys across all the replies
+        let mut keys = vec![];
+        let mut next: Option<Vec<DfValue>> = None;
+        while let Some(reply) = rsps
+            .next()
+            .await
+            .transpose()
+            .map_err(rpc_err!("View::scan"))?
+        {
+            if let ReadReply::Scan(batch) = reply.v {
+                let mut batch = batch?;
+                keys.append(&mut batch.keys);
+                next = min_key(next, batch.next);
+            } else {
+                unreachable!();
+            }
+        }
+
+        keys.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+        if keys.len() > batch_size {
+            let rest = keys.split_off(batch_size);
+            next = min_key(next, rest.into_iter().next().map(|(key, _)| key));
+        }
+
+        Ok(ViewScanBatch { keys, next })
+    }
+
     // TODO(andrew): consolidate RYW and normal reads into cohesive API once API design is settled.
     // RYW functionality currently added as duplicate methods so as not to disrupt current
     // reader usage until RYW is fully adopted
@@ -1464,14 +1831,145 @@ impl ReaderHandle {
     /// The method will block if the results are not yet available only when `block` is `true`.
     /// If `block` is false, misses will be returned as empty results. Any requested keys that have
     /// missing state will be backfilled (asynchronously if `block` is `false`).
+    ///
+    /// If the lookup fails with a networking-related error, it is retried according to this
+    /// handle's [`RetryPolicy`]. If the reader for this view has moved to a different worker, the
+    /// lookup is sent to the reader's new location.
     pub async fn raw_lookup(&mut self, query: ViewQuery) -> ReadySetResult<ResultIterator> {
-        future::poll_fn(|cx| self.poll_ready(cx)).await?;
-        match self.call(query).await? {
+        // Avoid cloning the query on the hot path if it can never be sent more than once
+        let may_resend = self.retries.enabled() || self.resolver.is_some();
+        let mut query = Some(query);
+        let result = with_re_resolution!(self, {
+            #[allow(clippy::unwrap_used)] // only ever taken if the query is never resent
+            let query = if may_resend {
+                query.clone().unwrap()
+            } else {
+                query.take().unwrap()
+            };
+            self.lookup_once(query).await
+        });
+        match result? {
             LookupResult::NonBlockingMiss => Err(ReadySetError::ReaderMissingKey),
             LookupResult::Results(results, _) => Ok(ResultIterator::owned(results)),
         }
     }
 
+    async fn lookup_once(&mut self, query: ViewQuery) -> ReadySetResult<LookupResult<Results>> {
+        future::poll_fn(|cx| self.poll_ready(cx)).await?;
+        self.call(query).await
+    }
+
+    /// Returns the [`RetryPolicy`] used for requests made through this handle
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        self.retries.policy()
+    }
+
+    /// Set the [`RetryPolicy`] used for requests made through this handle, resetting its circuit
+    /// breaker.
+    ///
+    /// Returns an error if the policy is invalid.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) -> ReadySetResult<()> {
+        policy.validate()?;
+        self.retries = Retries::new(policy);
+        Ok(())
+    }
+
+    /// The name of this view, for use in errors
+    fn target(&self) -> String {
+        self.name.display_unquoted().to_string()
+    }
+
+    /// Configure this handle to follow its reader to its new location if the reader's domain moves
+    /// to another worker, by looking the view up again from `controller` with `request`
+    pub(crate) fn set_resolver(&mut self, controller: ReadySetHandle, request: ViewRequest) {
+        self.resolver = Some(ViewResolver {
+            controller,
+            request,
+        });
+    }
+
+    /// Look up the current location of this view's reader from the controller, and if it has moved
+    /// (eg because its domain was rescheduled onto another worker), point this handle at the
+    /// reader's new location. Returns `true` if the reader moved.
+    async fn re_resolve(&mut self) -> bool {
+        let Some(mut resolver) = self.resolver.clone() else {
+            return false;
+        };
+
+        let view_builder = match resolver
+            .controller
+            .view_builder(resolver.request.clone())
+            .await
+        {
+            Ok(view_builder) => view_builder,
+            Err(error) => {
+                debug!(%error, view = %self.target(), "Failed to look up location of view");
+                return false;
+            }
+        };
+        let builder = match &view_builder {
+            ViewBuilder::Single(builder) => Some(builder),
+            ViewBuilder::MultipleReused(builders) => builders
+                .iter()
+                .map(|reused| &reused.builder)
+                .find(|builder| builder.name == self.name),
+        };
+        let Some(builder) = builder.filter(|builder| builder.name == self.name) else {
+            return false;
+        };
+
+        let still_running = builder.node == self.node
+            && builder.replica_shard_addrs.rows().any(|addrs| {
+                addrs
+                    .iter()
+                    .copied()
+                    .eq(self.shard_addrs.iter().copied().map(Some))
+            });
+        if still_running {
+            return false;
+        }
+
+        let replica = match resolver.request.filter {
+            Some(ViewFilter::Replica(replica)) => Some(replica),
+            _ => None,
+        };
+        let handle = match builder.build(replica, Arc::clone(&self.rpcs)).await {
+            Ok(handle) => handle,
+            Err(error) => {
+                debug!(%error, view = %self.target(), "Failed to connect to new location of view");
+                return false;
+            }
+        };
+
+        debug!(
+            view = %self.target(),
+            old_addrs = ?self.shard_addrs,
+            new_addrs = ?handle.shard_addrs,
+            "Reader for view moved"
+        );
+        *self = ReaderHandle {
+            retries: std::mem::take(&mut self.retries),
+            resolver: Some(resolver),
+            ..handle
+        };
+        true
+    }
+
+    /// Replace the connections to all of this view's shards with new ones, both in this handle and
+    /// in the pool of connections shared with other handles. If the view's reader has moved, this
+    /// connects to its new location instead.
+    async fn reconnect(&mut self) {
+        if self.re_resolve().await {
+            return;
+        }
+
+        let mut rpcs = self.rpcs.lock().await;
+        for (shardi, (shard, &addr)) in self.shards.iter_mut().zip(&self.shard_addrs).enumerate() {
+            *shard = connect_shard(addr, shardi, self.request_timeout);
+            rpcs.insert((addr, shardi), shard.clone());
+        }
+    }
+
     /// Retrieve the query results for the given parameter value.
     ///
     /// The method will block if the results are not yet available only when `block` is `true`.
@@ -1492,6 +1990,34 @@ impl ReaderHandle {
         self.raw_lookup((key_comparisons, block, None).into()).await
     }
 
+    /// Retrieve the rows of this view whose values in the given columns are equal to each of
+    /// `keys`, using the secondary index on exactly those columns. The index must have been
+    /// created with
+    /// [`ReadySetHandle::set_view_secondary_indices`](crate::ReadySetHandle::set_view_secondary_indices).
+    ///
+    /// Since secondary indices are only supported for fully materialized views, these lookups
+    /// never miss.
+    pub async fn lookup_by_index(
+        &mut self,
+        columns: &[&str],
+        keys: Vec<Vec1<DfValue>>,
+    ) -> ReadySetResult<ResultIterator> {
+        let secondary_index = columns
+            .iter()
+            .map(|column| {
+                self.columns
+                    .iter()
+                    .position(|c| c == column)
+                    .ok_or_else(|| ReadySetError::NoSuchColumn(column.to_string()))
+            })
+            .collect::<ReadySetResult<Vec<_>>>()?;
+        self.raw_lookup(ViewQuery {
+            secondary_index: Some(secondary_index),
+            ..(keys.into_iter().map(KeyComparison::Equal).collect(), true).into()
+        })
+        .await
+    }
+
     /// Retrieve the query results for the given parameter value.
     ///
     /// The method will block if the results are not yet available or do not have a timestamp
@@ -1527,6 +2053,125 @@ impl ReaderHandle {
             .await
     }
 
+    /// Retrieve the query results for the given parameter value, blocking if the results are not
+    /// yet available, but for no longer than `timeout`.
+    ///
+    /// If any of the keys miss and aren't filled by upqueries within `timeout`, the lookup fails
+    /// with [`ReadySetError::UpqueryTimeout`]. See [`ViewQuery::upquery_timeout`].
+    pub async fn lookup_with_timeout(
+        &mut self,
+        key: &[DfValue],
+        timeout: Duration,
+    ) -> ReadySetResult<ResultIterator> {
+        let key = Vec1::try_from_vec(key.into())
+            .map_err(|_| view_err(self.node, ReadySetError::EmptyKey))?;
+        self.multi_lookup_with_timeout(vec![KeyComparison::Equal(key)], timeout)
+            .await
+    }
+
+    /// Retrieve the query results for the given parameter values, blocking if the results are not
+    /// yet available, but for no longer than `timeout`.
+    ///
+    /// See [`lookup_with_timeout`](Self::lookup_with_timeout).
+    pub async fn multi_lookup_with_timeout(
+        &mut self,
+        key_comparisons: Vec<KeyComparison>,
+        timeout: Duration,
+    ) -> ReadySetResult<ResultIterator> {
+        self.raw_lookup(ViewQuery {
+            upquery_timeout: Some(timeout),
+            ..(key_comparisons, true).into()
+        })
+        .await
+    }
+
+    /// Retrieve the query results for the given parameter value as they were at `as_of`, blocking
+    /// if the results are not yet available.
+    ///
+    /// This requires history retention to have been configured for the view with
+    /// [`ReadySetHandle::set_view_history_retention`], and fails with
+    /// [`ReadySetError::ViewHistoryUnavailable`] if `as_of` is further in the past than the view
+    /// retains history for. Passing the same `as_of` to lookups against several views gives a
+    /// consistent snapshot across those views, as long as the views are fed by the same writes.
+    pub async fn lookup_at(
+        &mut self,
+        key: &[DfValue],
+        as_of: SystemTime,
+    ) -> ReadySetResult<ResultIterator> {
+        let key = Vec1::try_from_vec(key.into())
+            .map_err(|_| view_err(self.node, ReadySetError::EmptyKey))?;
+        self.multi_lookup_at(vec![KeyComparison::Equal(key)], as_of)
+            .await
+    }
+
+    /// Retrieve the query results for the given parameter values as they were at `as_of`,
+    /// blocking if the results are not yet available.
+    ///
+    /// See [`lookup_at`](Self::lookup_at) for the requirements on the view.
+    pub async fn multi_lookup_at(
+        &mut self,
+        key_comparisons: Vec<KeyComparison>,
+        as_of: SystemTime,
+    ) -> ReadySetResult<ResultIterator> {
+        self.raw_lookup(ViewQuery {
+            as_of: Some(AsOf::Time(as_of)),
+            ..(key_comparisons, true).into()
+        })
+        .await
+    }
+
+    /// Returns true if this view was created for a query without any parameters, meaning all of
+    /// its key columns were generated by ReadySet and every row lives under the same key.
+    pub fn is_unparameterized(&self) -> bool {
+        self.key_mapping
+            .iter()
+            .all(|(placeholder, _)| matches!(placeholder, ViewPlaceholder::Generated))
+    }
+
+    /// Stream every row in a view that was created for a query without any parameters, such as
+    /// `SELECT ... ORDER BY x LIMIT 10`, in pages of at most `page_size` rows.
+    ///
+    /// Each page is read from the reader separately, so neither the reader nor the caller ever
+    /// needs to hold more than one page of results at a time. Since pages are separate reads, rows
+    /// written to the view while the stream is being consumed may be missed or returned twice.
+    /// Returns an error if the view takes parameters, since those must be read via
+    /// [`Self::lookup`].
+    ///
+    /// Reads will block if the results are not yet available only when `block` is `true`.
+    pub fn read_all(
+        &mut self,
+        block: bool,
+        page_size: usize,
+    ) -> ReadySetResult<impl Stream<Item = ReadySetResult<Vec<Vec<DfValue>>>> + '_> {
+        if !self.is_unparameterized() {
+            return Err(view_err(
+                self.node,
+                ReadySetError::WrongKeyColumnCount(self.key_mapping.len(), 0),
+            ));
+        }
+
+        let page_size = page_size.max(1);
+        Ok(stream::try_unfold(
+            (self, Some(0)),
+            move |(this, offset)| async move {
+                let Some(offset) = offset else {
+                    return Ok(None);
+                };
+                let page = this
+                    .raw_lookup(ViewQuery {
+                        limit: Some(page_size),
+                        offset: Some(offset),
+                        ..(vec![generated_key()], block).into()
+                    })
+                    .await?
+                    .into_vec();
+                // A short page means we've reached the end of the view
+                let next = (page.len() == page_size).then_some(offset + page_size);
+                Ok(Some((page, (this, next))))
+            },
+        ))
+    }
+
     /// Build a [`ViewQuery`] for performing a lookup against this [`ReaderHandle`]
     #[allow(clippy::too_many_arguments)]
     fn build_view_query(
@@ -1538,14 +2183,15 @@ impl ReaderHandle {
         ticket: Option<Timestamp>,
         blocking_read: bool,
         dialect: Dialect,
+        timezone: Timezone,
     ) -> ReadySetResult<ViewQuery> {
         trace!("select::lookup");
 
         let (keys, filters) = if raw_keys.is_empty() {
-            let bogo = vec![vec1![DfValue::from(0i32)].into()];
-            (bogo, Vec::new())
+            (vec![generated_key()], Vec::new())
         } else {
-            let mut key_comparison_builder = KeyComparisonBuilder::new(self, key_remap, dialect)?;
+            let mut key_comparison_builder =
+                KeyComparisonBuilder::new(self, key_remap, dialect, timezone)?;
 
             let keys = raw_keys
                 .into_iter()
@@ -1569,10 +2215,20 @@ impl ReaderHandle {
             limit,
             offset,
             timestamp: ticket,
+            as_of: None,
+            result_limit: None,
+            secondary_index: None,
+            upquery_timeout: None,
         })
     }
 }
 
+/// The key under which all rows of an unparameterized view are stored. This must match the
+/// literal value projected as the "bogokey" column by MIR for queries without any parameters.
+fn generated_key() -> KeyComparison {
+    vec1![DfValue::from(0i32)].into()
+}
+
 struct KeyComparisonBuilder<'a> {
     mixed_binops: bool,
     filters: Vec<DfExpr>,
@@ -1581,6 +2237,8 @@ struct KeyComparisonBuilder<'a> {
     key_remap: Option<&'a HashMap<PlaceholderIdx, Literal>>,
     binop_to_use: BinaryOperator,
     dialect: Dialect,
+    /// The timezone of the session the keys were supplied in
+    timezone: Timezone,
 }
 
 impl<'a> KeyComparisonBuilder<'a> {
@@ -1588,6 +2246,7 @@ impl<'a> KeyComparisonBuilder<'a> {
         reader_handle: &'a ReaderHandle,
         key_remap: Option<&'a HashMap<PlaceholderIdx, Literal>>,
         dialect: Dialect,
+        timezone: Timezone,
     ) -> ReadySetResult<KeyComparisonBuilder<'a>> {
         let schema = reader_handle
             .schema()
@@ -1630,6 +2289,7 @@ impl<'a> KeyComparisonBuilder<'a> {
         Ok(Self {
             key_remap,
             dialect,
+            timezone,
             mixed_binops,
             binop_to_use,
             key_types,
@@ -1651,26 +2311,34 @@ impl<'a> KeyComparisonBuilder<'a> {
                 match remap.get(idx).ok_or_else(|| {
                     internal_err!("Key remapping for ReusedReaderHandle is missing indices")
                 })? {
-                    Literal::Placeholder(ItemPlaceholder::DollarNumber(idx)) => key
-                        .get(*idx as usize - 1)
-                        .ok_or_else(|| {
-                            internal_err!(
-                                "Key remapping for ReusedReaderHandle contains erroneous index"
-                            )
-                        })?
-                        .coerce_to(key_type, &DfType::Unknown)?,
+                    Literal::Placeholder(ItemPlaceholder::DollarNumber(idx)) => self
+                        .coerce_key_value(
+                            key.get(*idx as usize - 1).ok_or_else(|| {
+                                internal_err!(
+                                    "Key remapping for ReusedReaderHandle contains erroneous index"
+                                )
+                            })?,
+                            key_type,
+                        )?,
                     Literal::Placeholder(_) => {
                         internal!(
                                 "Key remapping for ReusedReaderHandle contains non-numbered placeholder"
                             )
                     }
-                    literal => DfValue::try_from(literal)?.coerce_to(key_type, &DfType::Unknown)?,
+                    literal => self.coerce_key_value(&DfValue::try_from(literal)?, key_type)?,
                 }
             }
-            None => key[*idx - 1].coerce_to(key_type, &DfType::Unknown)?,
+            None => self.coerce_key_value(&key[*idx - 1], key_type)?,
         })
     }
 
+    /// Coerce a value supplied by the client to the type of the key column it's compared against,
+    /// interpreting timestamps in the session's timezone
+    fn coerce_key_value(&self, value: &DfValue, key_type: &DfType) -> ReadySetResult<DfValue> {
+        self.timezone
+            .coerce_client_value(value, key_type, self.dialect)
+    }
+
     fn build_key(&mut self, raw_key: Cow<'_, [DfValue]>) -> ReadySetResult<Option<KeyComparison>> {
         let mut k = vec![];
         let mut bounds: Option<(Vec<DfValue>, Vec<DfValue>)> = if self.mixed_binops {
@@ -1861,6 +2529,7 @@ impl ReusedReaderHandle {
         ticket: Option<Timestamp>,
         blocking_read: bool,
         dialect: Dialect,
+        timezone: Timezone,
     ) -> ReadySetResult<Option<ViewQuery>> {
         // If any placeholders in our query correspond to inlined values in the migrated query,
         // verify that we are executing our query with these values.
@@ -1896,6 +2565,7 @@ impl ReusedReaderHandle {
                 ticket,
                 blocking_read,
                 dialect,
+                timezone,
             )
             .map(Some)
     }
@@ -1906,6 +2576,9 @@ impl View {
     /// [`View::MultipleReused`], then we will find a [`ReaderHandle`] for the first Reader that can
     /// satisfy the given keys or return None. Also returns a reference to the [`ReaderHandle`] to
     /// indicate which handle the [`ViewQuery`] corresponds to.
+    ///
+    /// Timestamps in `raw_keys` without an explicit offset are interpreted in the given session
+    /// `timezone`.
     #[allow(clippy::too_many_arguments)]
     pub fn build_view_query(
         &mut self,
@@ -1915,6 +2588,7 @@ impl View {
         ticket: Option<Timestamp>,
         blocking_read: bool,
         dialect: Dialect,
+        timezone: Timezone,
     ) -> ReadySetResult<Option<(&mut ReaderHandle, ViewQuery)>> {
         // If any placeholders in our query correspond to inlined values in the migrated query,
         // verify that we are executing our query with these values.
@@ -1928,6 +2602,7 @@ impl View {
                     ticket,
                     blocking_read,
                     dialect,
+                    timezone,
                 )
                 .map(|vq| Some((handle, vq))),
             View::MultipleReused(handles) => {
@@ -1940,6 +2615,7 @@ impl View {
                         ticket.clone(),
                         blocking_read,
                         dialect,
+                        timezone,
                     ) {
                         Ok(Some(vq)) => {
                             return Ok(Some((reused_handle.inner_mut(), vq)));
@@ -1962,6 +2638,39 @@ impl View {
         }
     }
 
+    /// Read up to `batch_size` keys of this view in key order, starting at `start_key`, along with
+    /// the rows of the view for each key. See [`ReaderHandle::scan`].
+    ///
+    /// Only supported for [`View::Single`], since the keys of a reused cache are not the keys of
+    /// this view.
+    pub async fn scan(
+        &mut self,
+        start_key: Option<Vec<DfValue>>,
+        batch_size: usize,
+    ) -> ReadySetResult<ViewScanBatch> {
+        match self {
+            View::Single(rh) => rh.scan(start_key, batch_size).await,
+            View::MultipleReused(_) => unsupported!("Cannot scan a view which reuses other caches"),
+        }
+    }
+
+    /// Retrieve the rows of this view whose values in the given columns are equal to each of
+    /// `keys`, using a secondary index. See [`ReaderHandle::lookup_by_index`].
+    ///
+    /// Only supported for [`View::Single`].
+    pub async fn lookup_by_index(
+        &mut self,
+        columns: &[&str],
+        keys: Vec<Vec1<DfValue>>,
+    ) -> ReadySetResult<ResultIterator> {
+        match self {
+            View::Single(rh) => rh.lookup_by_index(columns, keys).await,
+            View::MultipleReused(_) => {
+                unsupported!("Cannot look up a view which reuses other caches by secondary index")
+            }
+        }
+    }
+
     /// Returns a single ReaderHandle if Self is [`View::Single`]
     pub fn into_reader_handle(self) -> Option<ReaderHandle> {
         match self {
@@ -1970,6 +2679,38 @@ impl View {
         }
     }
 
+    /// Set the [`RetryPolicy`] used for requests made through all of the [`ReaderHandle`]s in this
+    /// view.
+    ///
+    /// Returns an error if the policy is invalid.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) -> ReadySetResult<()> {
+        match self {
+            View::Single(rh) => rh.set_retry_policy(policy),
+            View::MultipleReused(handles) => {
+                policy.validate()?;
+                for handle in handles {
+                    handle.inner_mut().set_retry_policy(policy.clone())?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Configure all of the [`ReaderHandle`]s in this view to follow their readers to their new
+    /// locations if their domains move to another worker
+    pub(crate) fn set_resolver(&mut self, controller: ReadySetHandle, request: ViewRequest) {
+        match self {
+            View::Single(rh) => rh.set_resolver(controller, request),
+            View::MultipleReused(handles) => {
+                for handle in handles {
+                    handle
+                        .inner_mut()
+                        .set_resolver(controller.clone(), request.clone());
+                }
+            }
+        }
+    }
+
     /// Returns a mut reference to a single ReaderHandle if Self is [`View::Single`]
     pub fn as_mut_reader_handle(&mut self) -> Option<&mut ReaderHandle> {
         match self {
@@ -2186,16 +2927,28 @@ mod tests {
                 key_mapping: key_map.to_vec(),
                 shards: Vec1::new(c), // Not used for test
                 shard_addrs: vec![],  // Not used for test
+                rpcs: Default::default(),
+                request_timeout: Duration::new(1, 0),
+                retries: Default::default(),
+                resolver: None,
             };
             let dataflow_dialect = match dialect {
                 Dialect::MySQL => DfDialect::DEFAULT_MYSQL,
                 Dialect::PostgreSQL => DfDialect::DEFAULT_POSTGRESQL,
             };
             let mut view = View::Single(reader_handle);
-            view.build_view_query(raw_keys, limit, offset, None, true, dataflow_dialect)
-                .unwrap()
-                .unwrap()
-                .1
+            view.build_view_query(
+                raw_keys,
+                limit,
+                offset,
+                None,
+                true,
+                dataflow_dialect,
+                Timezone::UTC,
+            )
+            .unwrap()
+            .unwrap()
+            .1
         }
 
         #[test]
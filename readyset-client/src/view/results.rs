@@ -318,7 +318,9 @@ impl ResultIterator {
                 .fold(None, |total, cur| match cur {
                     Some(stats) => Some(ReadReplyStats {
                         cache_misses: stats.cache_misses
-                            + total.map(|s| s.cache_misses).unwrap_or(0),
+                            + total.as_ref().map(|s| s.cache_misses).unwrap_or(0),
+                        stale: stats.stale || total.as_ref().map_or(false, |s| s.stale),
+                        truncated: stats.truncated || total.map_or(false, |s| s.truncated),
                     }),
                     None => total,
                 }),
@@ -1,7 +1,8 @@
 use ::readyset_client::metrics::{recorded, DumpedMetricValue};
-use ::readyset_client::recipe::changelist::ChangeList;
+use ::readyset_client::recipe::changelist::{Change, ChangeList, CreateCache};
 use ::readyset_client::{failpoints, get_metric};
 use database_utils::QueryableConnection;
+use nom_sql::parse_select_statement;
 use readyset_data::{DfValue, Dialect};
 use readyset_util::eventually;
 use rust_decimal::prelude::FromPrimitive;
@@ -403,6 +404,161 @@ async fn no_readers_worker_doesnt_get_readers() {
     deployment.teardown().await.unwrap();
 }
 
+#[clustertest]
+async fn view_follows_moved_reader() {
+    let mut deployment =
+        DeploymentBuilder::new(DatabaseType::MySQL, "ct_view_follows_moved_reader")
+            .add_server(ServerParams::default().no_readers())
+            .start()
+            .await
+            .unwrap();
+    let reader_server = deployment
+        .start_server(ServerParams::default().reader_only(), true)
+        .await
+        .unwrap();
+
+    deployment
+        .leader_handle()
+        .extend_recipe(
+            ChangeList::from_str(
+                "CREATE TABLE t (id int, val int);
+                 CREATE CACHE q FROM SELECT id, val FROM t WHERE id = ?;",
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let mut t = deployment.leader_handle().table("t").await.unwrap();
+    t.insert(vec![DfValue::from(1), DfValue::from(2)])
+        .await
+        .unwrap();
+
+    let mut view = deployment
+        .leader_handle()
+        .view("q")
+        .await
+        .unwrap()
+        .into_reader_handle()
+        .unwrap();
+    let old_addrs = view.shard_addrs().to_vec();
+    eventually! {
+        view.lookup(&[1.into()], true).await.unwrap().into_vec()
+            == vec![vec![DfValue::from(1), DfValue::from(2)]]
+    }
+
+    // Move the reader onto a new worker by killing the one it's running on
+    deployment
+        .start_server(ServerParams::default().reader_only(), true)
+        .await
+        .unwrap();
+    deployment.kill_server(&reader_server, true).await.unwrap();
+
+    // The existing view handle should follow the reader to its new location once it's been
+    // rescheduled, without needing to be recreated
+    eventually!(attempts: 120, {
+        view.lookup(&[1.into()], true)
+            .await
+            .map_or(false, |res| {
+                res.into_vec() == vec![vec![DfValue::from(1), DfValue::from(2)]]
+            })
+    });
+    assert_ne!(view.shard_addrs(), old_addrs);
+
+    deployment.teardown().await.unwrap();
+}
+
+#[clustertest]
+async fn standby_reader_fails_over() {
+    let mut deployment =
+        DeploymentBuilder::new(DatabaseType::MySQL, "ct_standby_reader_fails_over")
+            .add_server(ServerParams::default().no_readers())
+            .start()
+            .await
+            .unwrap();
+    let mut reader_servers = vec![];
+    for _ in 0..2 {
+        reader_servers.push(
+            deployment
+                .start_server(ServerParams::default().reader_only(), true)
+                .await
+                .unwrap(),
+        );
+    }
+
+    let lh = deployment.leader_handle();
+    lh.extend_recipe(
+        ChangeList::from_str("CREATE TABLE t (id int, val int);", Dialect::DEFAULT_MYSQL).unwrap(),
+    )
+    .await
+    .unwrap();
+    lh.extend_recipe(ChangeList::from_change(
+        Change::CreateCache(CreateCache {
+            name: Some("q".into()),
+            statement: Box::new(
+                parse_select_statement(
+                    nom_sql::Dialect::MySQL,
+                    "SELECT id, val FROM t WHERE id = ?",
+                )
+                .unwrap(),
+            ),
+            always: false,
+            frontier_strategy: None,
+            source: Default::default(),
+            original_sql: None,
+            disable_predicate_pushdown: false,
+            standby: true,
+        }),
+        Dialect::DEFAULT_MYSQL,
+    ))
+    .await
+    .unwrap();
+
+    let mut t = lh.table("t").await.unwrap();
+    t.insert(vec![DfValue::from(1), DfValue::from(2)])
+        .await
+        .unwrap();
+
+    // The reader should be running on both reader-only workers, even though reader replication
+    // isn't enabled for the deployment
+    let mut replica_addrs = vec![];
+    for replica in 0..2 {
+        let mut view = lh
+            .view_with_replica("q", replica)
+            .await
+            .unwrap()
+            .into_reader_handle()
+            .unwrap();
+        eventually! {
+            view.lookup(&[1.into()], true).await.unwrap().into_vec()
+                == vec![vec![DfValue::from(1), DfValue::from(2)]]
+        }
+        replica_addrs.push(view.shard_addrs().to_vec());
+    }
+    assert_ne!(replica_addrs[0], replica_addrs[1]);
+
+    let mut view = lh.view("q").await.unwrap().into_reader_handle().unwrap();
+    deployment
+        .kill_server(&reader_servers[0], true)
+        .await
+        .unwrap();
+
+    // Whichever replica the view was reading from, it should keep being able to read from the
+    // replica on the remaining worker, which already has the data. There's nowhere else to
+    // reschedule the killed replica to, so this can only succeed by failing over to the standby.
+    eventually! {
+        view.lookup(&[1.into()], true)
+            .await
+            .map_or(false, |res| {
+                res.into_vec() == vec![vec![DfValue::from(1), DfValue::from(2)]]
+            })
+    }
+    assert!(replica_addrs.contains(&view.shard_addrs().to_vec()));
+
+    deployment.teardown().await.unwrap();
+}
+
 #[clustertest]
 async fn server_and_adapter_auto_restart() {
     let mut deployment = DeploymentBuilder::new(DatabaseType::MySQL, "ct_adapter_restart")
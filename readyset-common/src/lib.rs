@@ -27,6 +27,8 @@ impl SizeOf for DfValue {
             DfValue::Text(ref t) => size_of_val(t) as u64 + t.as_bytes().len() as u64,
             DfValue::BitVector(ref t) => size_of_val(t) as u64 + (t.len() as u64 + 7) / 8,
             DfValue::ByteArray(ref t) => size_of_val(t) as u64 + t.len() as u64,
+            DfValue::Uuid(ref u) => size_of_val(u.as_ref()) as u64,
+            DfValue::Interval(ref i) => size_of_val(i.as_ref()) as u64,
             _ => 0u64,
         };
 
@@ -43,6 +43,15 @@ pub enum Collation {
     /// [PostgreSQL `CITEXT` type](https://www.postgresql.org/docs/current/citext.html) with the
     /// locale set to `en_US.utf8`.
     Citext,
+
+    /// The case-insensitive MySQL UTF-8 collations.
+    ///
+    /// This collation corresponds to the case-insensitive collations of MySQL's `utf8mb4` and
+    /// `utf8mb3` character sets, such as `utf8mb4_general_ci` (the default prior to MySQL 8.0)
+    /// and `utf8mb4_0900_ai_ci` (the default in MySQL 8.0). Strings are compared by their
+    /// lowercased characters; accent-insensitivity and `PAD SPACE` semantics are not (yet)
+    /// supported.
+    Utf8Ci,
 }
 
 impl Display for Collation {
@@ -50,6 +59,7 @@ impl Display for Collation {
         match self {
             Self::Utf8 => write!(f, "utf-8"),
             Self::Citext => write!(f, "citext"),
+            Self::Utf8Ci => write!(f, "utf-8 (case-insensitive)"),
         }
     }
 }
@@ -64,7 +74,7 @@ impl Collation {
     pub(crate) fn normalize(self, s: &str) -> Cow<str> {
         match self {
             Collation::Utf8 => s.into(),
-            Collation::Citext => s.to_lowercase().into(),
+            Collation::Citext | Collation::Utf8Ci => s.to_lowercase().into(),
         }
     }
 
@@ -75,7 +85,7 @@ impl Collation {
     {
         match self {
             Collation::Utf8 => s.hash(state),
-            Collation::Citext => s.to_lowercase().hash(state),
+            Collation::Citext | Collation::Utf8Ci => s.to_lowercase().hash(state),
         }
     }
 
@@ -83,7 +93,7 @@ impl Collation {
     pub(crate) fn compare_strs(self, s1: &str, s2: &str) -> Ordering {
         match self {
             Collation::Utf8 => s1.cmp(s2),
-            Collation::Citext => s1
+            Collation::Citext | Collation::Utf8Ci => s1
                 .chars()
                 .map(|c| c.to_lowercase())
                 .cmp_by(s2.chars().map(|c| c.to_lowercase()), |c1, c2| c1.cmp(c2)),
@@ -97,6 +107,37 @@ impl Collation {
     pub fn is_utf8(&self) -> bool {
         matches!(self, Self::Utf8)
     }
+
+    /// Returns the collation corresponding to the MySQL collation with the given name (as
+    /// specified in a `COLLATE` clause), or `None` if the collation isn't supported.
+    ///
+    /// Binary and case-sensitive collations (those ending in `_bin` or `_cs`) compare strings
+    /// bytewise, and case-insensitive collations (those ending in `_ci`) map to [`Utf8Ci`].
+    ///
+    /// [`Utf8Ci`]: Collation::Utf8Ci
+    pub fn from_mysql_collation(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        if name == "binary" || name.ends_with("_bin") || name.ends_with("_cs") {
+            Some(Self::Utf8)
+        } else if name.ends_with("_ci") {
+            Some(Self::Utf8Ci)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the collation corresponding to the default collation of the MySQL character set
+    /// with the given name (as specified in a `CHARACTER SET` clause).
+    ///
+    /// The default collation of every MySQL character set other than `binary` is
+    /// case-insensitive.
+    pub fn from_mysql_charset(name: &str) -> Self {
+        if name.eq_ignore_ascii_case("binary") {
+            Self::Utf8
+        } else {
+            Self::Utf8Ci
+        }
+    }
 }
 
 #[cfg(test)]
@@ -170,4 +211,46 @@ mod tests {
         citext_strings_less("A", "b");
         citext_strings_less("a", "B");
     }
+
+    #[test]
+    fn utf8_ci_equal() {
+        assert_eq!(
+            Collation::Utf8Ci.compare_strs("Hello", "hELLO"),
+            Ordering::Equal
+        );
+        assert_eq!(Collation::Utf8Ci.compare_strs("a", "B"), Ordering::Less);
+        assert_ne!(Collation::Utf8Ci.compare_strs("a", "á"), Ordering::Equal);
+    }
+
+    #[test]
+    fn mysql_collation_names() {
+        assert_eq!(
+            Collation::from_mysql_collation("utf8mb4_general_ci"),
+            Some(Collation::Utf8Ci)
+        );
+        assert_eq!(
+            Collation::from_mysql_collation("UTF8MB4_0900_AI_CI"),
+            Some(Collation::Utf8Ci)
+        );
+        assert_eq!(
+            Collation::from_mysql_collation("utf8mb4_bin"),
+            Some(Collation::Utf8)
+        );
+        assert_eq!(
+            Collation::from_mysql_collation("utf8mb4_0900_as_cs"),
+            Some(Collation::Utf8)
+        );
+        assert_eq!(
+            Collation::from_mysql_collation("binary"),
+            Some(Collation::Utf8)
+        );
+        assert_eq!(Collation::from_mysql_collation("ucs2_persian"), None);
+    }
+
+    #[test]
+    fn mysql_charset_names() {
+        assert_eq!(Collation::from_mysql_charset("utf8mb4"), Collation::Utf8Ci);
+        assert_eq!(Collation::from_mysql_charset("latin1"), Collation::Utf8Ci);
+        assert_eq!(Collation::from_mysql_charset("BINARY"), Collation::Utf8);
+    }
 }
@@ -158,6 +158,7 @@ pub(crate) fn coerce_f64(val: f64, to_ty: &DfType, from_ty: &DfType) -> ReadySet
         | DfType::MacAddr
         | DfType::Inet
         | DfType::Uuid
+        | DfType::Interval
         | DfType::Bit(_)
         | DfType::VarBit(_)
         | DfType::Array(_) => Err(err("not allowed")),
@@ -248,6 +249,7 @@ pub(crate) fn coerce_decimal(
         | DfType::MacAddr
         | DfType::Inet
         | DfType::Uuid
+        | DfType::Interval
         | DfType::Bit(_)
         | DfType::VarBit(_)
         | DfType::Array(_) => Err(ReadySetError::DfValueConversionError {
@@ -0,0 +1,737 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use bytes::{Buf, BufMut, BytesMut};
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime, Timelike};
+use nom_sql::IntervalUnit;
+use proptest::arbitrary::Arbitrary;
+use proptest::strategy::{BoxedStrategy, Strategy};
+use readyset_errors::{invalid_query_err, ReadySetError, ReadySetResult};
+use serde::{Deserialize, Serialize};
+use tokio_postgres::types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+const MICROSECONDS_PER_SECOND: i64 = 1_000_000;
+const MICROSECONDS_PER_MINUTE: i64 = 60 * MICROSECONDS_PER_SECOND;
+const MICROSECONDS_PER_HOUR: i64 = 60 * MICROSECONDS_PER_MINUTE;
+const MICROSECONDS_PER_DAY: i64 = 24 * MICROSECONDS_PER_HOUR;
+
+/// The number of days in a month, for the purposes of comparing intervals and converting
+/// fractional months into days
+const DAYS_PER_MONTH: i64 = 30;
+
+/// A span of time, as produced by `INTERVAL` expressions or by subtracting timestamps with `AGE`.
+///
+/// Like in PostgreSQL, intervals are made up of separate months, days, and microseconds fields,
+/// since the number of days in a month varies: adding `INTERVAL '1 month'` to a timestamp moves to
+/// the same day of the following month, no matter how many days that month has. When *ordering*
+/// intervals, months are treated as 30 days and days as 24 hours, so that eg `INTERVAL '1 month'`
+/// sorts before `INTERVAL '31 days'`.
+///
+/// Unlike in PostgreSQL, intervals with the same total length but different fields (such as
+/// `INTERVAL '1 month'` and `INTERVAL '30 days'`) are *not* considered equal, so that equality
+/// agrees with the serialized representation of intervals (which is used for keys in persistent
+/// state).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Interval {
+    months: i32,
+    days: i32,
+    microseconds: i64,
+}
+
+fn out_of_range() -> ReadySetError {
+    invalid_query_err!("interval out of range")
+}
+
+/// Returns the number of days in the given month
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|d| d.pred_opt())
+        .map(|d| d.day())
+        .unwrap_or(31)
+}
+
+impl Interval {
+    /// Construct a new interval from its months, days, and microseconds
+    pub fn new(months: i32, days: i32, microseconds: i64) -> Self {
+        Self {
+            months,
+            days,
+            microseconds,
+        }
+    }
+
+    /// Construct an interval of `quantity` of the given `unit`, as in MySQL's `INTERVAL 3 DAY`
+    pub fn from_quantity(quantity: i64, unit: IntervalUnit) -> ReadySetResult<Self> {
+        let micros = |per: i64| quantity.checked_mul(per).ok_or_else(out_of_range);
+        let narrow = |n: Option<i64>| {
+            n.and_then(|n| i32::try_from(n).ok())
+                .ok_or_else(out_of_range)
+        };
+        Ok(match unit {
+            IntervalUnit::Microsecond => Self::new(0, 0, quantity),
+            IntervalUnit::Second => Self::new(0, 0, micros(MICROSECONDS_PER_SECOND)?),
+            IntervalUnit::Minute => Self::new(0, 0, micros(MICROSECONDS_PER_MINUTE)?),
+            IntervalUnit::Hour => Self::new(0, 0, micros(MICROSECONDS_PER_HOUR)?),
+            IntervalUnit::Day => Self::new(0, narrow(Some(quantity))?, 0),
+            IntervalUnit::Week => Self::new(0, narrow(quantity.checked_mul(7))?, 0),
+            IntervalUnit::Month => Self::new(narrow(Some(quantity))?, 0, 0),
+            IntervalUnit::Quarter => Self::new(narrow(quantity.checked_mul(3))?, 0, 0),
+            IntervalUnit::Year => Self::new(narrow(quantity.checked_mul(12))?, 0, 0),
+        })
+    }
+
+    /// Returns the months part of this interval
+    pub fn months(&self) -> i32 {
+        self.months
+    }
+
+    /// Returns the days part of this interval
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    /// Returns the microseconds part of this interval
+    pub fn microseconds(&self) -> i64 {
+        self.microseconds
+    }
+
+    /// Returns true if this interval is zero-length
+    pub fn is_zero(&self) -> bool {
+        self.months == 0 && self.days == 0 && self.microseconds == 0
+    }
+
+    /// Returns the total length of this interval in microseconds, treating months as 30 days
+    fn total_microseconds(&self) -> i128 {
+        (i128::from(self.months) * i128::from(DAYS_PER_MONTH) + i128::from(self.days))
+            * i128::from(MICROSECONDS_PER_DAY)
+            + i128::from(self.microseconds)
+    }
+
+    /// Negate this interval, returning `None` on overflow
+    pub fn checked_neg(&self) -> Option<Self> {
+        Some(Self {
+            months: self.months.checked_neg()?,
+            days: self.days.checked_neg()?,
+            microseconds: self.microseconds.checked_neg()?,
+        })
+    }
+
+    /// Add two intervals field-by-field, returning `None` on overflow
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(Self {
+            months: self.months.checked_add(other.months)?,
+            days: self.days.checked_add(other.days)?,
+            microseconds: self.microseconds.checked_add(other.microseconds)?,
+        })
+    }
+
+    /// Subtract two intervals field-by-field, returning `None` on overflow
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.checked_add(&other.checked_neg()?)
+    }
+
+    /// Add this interval to the given datetime, returning `None` if the result is out of range.
+    ///
+    /// Months are added first, clamping to the last day of the month if necessary (so
+    /// `2024-01-31 + INTERVAL '1 month'` is `2024-02-29`), followed by days and then microseconds.
+    pub fn add_to(&self, dt: NaiveDateTime) -> Option<NaiveDateTime> {
+        let months = Months::new(self.months.unsigned_abs());
+        let dt = if self.months >= 0 {
+            dt.checked_add_months(months)?
+        } else {
+            dt.checked_sub_months(months)?
+        };
+        dt.checked_add_signed(Duration::days(self.days.into()))?
+            .checked_add_signed(Duration::microseconds(self.microseconds))
+    }
+
+    /// Subtract this interval from the given datetime, returning `None` if the result is out of
+    /// range
+    pub fn sub_from(&self, dt: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.checked_neg()?.add_to(dt)
+    }
+
+    /// Subtract `rhs` from `lhs`, with the semantics of PostgreSQL's `timestamp - timestamp`
+    /// operator: the result is made up of only days and microseconds, never months. Returns `None`
+    /// if the result is out of range.
+    pub fn between(lhs: NaiveDateTime, rhs: NaiveDateTime) -> Option<Self> {
+        let micros = lhs.signed_duration_since(rhs).num_microseconds()?;
+        Some(Self {
+            months: 0,
+            days: i32::try_from(micros / MICROSECONDS_PER_DAY).ok()?,
+            microseconds: micros % MICROSECONDS_PER_DAY,
+        })
+    }
+
+    /// Subtract `rhs` from `lhs`, producing a "symbolic" result that uses years and months rather
+    /// than just days, with the semantics of PostgreSQL's [`AGE`][pg-docs] function.
+    ///
+    /// For example, the age of `2024-03-15` relative to `2023-01-20` is `1 year 1 mon 26 days`.
+    ///
+    /// [pg-docs]: https://www.postgresql.org/docs/current/functions-datetime.html
+    pub fn age(lhs: NaiveDateTime, rhs: NaiveDateTime) -> Self {
+        let negative = lhs < rhs;
+        let (later, earlier) = if negative { (rhs, lhs) } else { (lhs, rhs) };
+        let field = |f: fn(&NaiveDateTime) -> u32| i64::from(f(&later)) - i64::from(f(&earlier));
+
+        let mut micros = i64::from(later.nanosecond().min(999_999_999) / 1000)
+            - i64::from(earlier.nanosecond().min(999_999_999) / 1000);
+        let mut seconds = field(|dt| dt.second());
+        let mut minutes = field(|dt| dt.minute());
+        let mut hours = field(|dt| dt.hour());
+        let mut days = field(|dt| dt.day());
+        let mut months = field(|dt| dt.month());
+        let mut years = i64::from(later.year()) - i64::from(earlier.year());
+
+        // Propagate negative fields into the next larger field, borrowing days according to the
+        // length of the month of the earlier datetime
+        if micros < 0 {
+            micros += MICROSECONDS_PER_SECOND;
+            seconds -= 1;
+        }
+        if seconds < 0 {
+            seconds += 60;
+            minutes -= 1;
+        }
+        if minutes < 0 {
+            minutes += 60;
+            hours -= 1;
+        }
+        if hours < 0 {
+            hours += 24;
+            days -= 1;
+        }
+        if days < 0 {
+            days += i64::from(days_in_month(earlier.year(), earlier.month()));
+            months -= 1;
+        }
+        if months < 0 {
+            months += 12;
+            years -= 1;
+        }
+
+        // The difference between any two valid `NaiveDateTime`s is well within range
+        let res = Self {
+            months: (years * 12 + months) as i32,
+            days: days as i32,
+            microseconds: hours * MICROSECONDS_PER_HOUR
+                + minutes * MICROSECONDS_PER_MINUTE
+                + seconds * MICROSECONDS_PER_SECOND
+                + micros,
+        };
+        if negative {
+            Self {
+                months: -res.months,
+                days: -res.days,
+                microseconds: -res.microseconds,
+            }
+        } else {
+            res
+        }
+    }
+}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interval {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Intervals with the same total length are ordered by their fields, to be consistent with
+        // `Eq`
+        self.total_microseconds()
+            .cmp(&other.total_microseconds())
+            .then_with(|| self.months.cmp(&other.months))
+            .then_with(|| self.days.cmp(&other.days))
+    }
+}
+
+/// Formats the interval in PostgreSQL's default (`postgres`) output style, eg `1 year 2 mons 3
+/// days 04:05:06.5`
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut is_zero = true;
+        let mut is_before = false;
+        let mut write_part = |f: &mut fmt::Formatter<'_>, value: i64, unit: &str| -> fmt::Result {
+            if value == 0 {
+                return Ok(());
+            }
+            write!(
+                f,
+                "{}{}{value} {unit}{}",
+                if is_zero { "" } else { " " },
+                if is_before && value > 0 { "+" } else { "" },
+                if value != 1 { "s" } else { "" }
+            )?;
+            is_before = value < 0;
+            is_zero = false;
+            Ok(())
+        };
+        write_part(f, i64::from(self.months / 12), "year")?;
+        write_part(f, i64::from(self.months % 12), "mon")?;
+        write_part(f, self.days.into(), "day")?;
+
+        if is_zero || self.microseconds != 0 {
+            let micros = self.microseconds.unsigned_abs();
+            let hours = micros / MICROSECONDS_PER_HOUR as u64;
+            let minutes = micros / MICROSECONDS_PER_MINUTE as u64 % 60;
+            let seconds = micros / MICROSECONDS_PER_SECOND as u64 % 60;
+            let fraction = micros % MICROSECONDS_PER_SECOND as u64;
+            write!(
+                f,
+                "{}{}{hours:02}:{minutes:02}:{seconds:02}",
+                if is_zero { "" } else { " " },
+                if self.microseconds < 0 {
+                    "-"
+                } else if is_before {
+                    "+"
+                } else {
+                    ""
+                }
+            )?;
+            if fraction != 0 {
+                write!(f, ".{}", format!("{fraction:06}").trim_end_matches('0'))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A quantity in an interval string, split into its whole and fractional parts (which always have
+/// the same sign)
+#[derive(Clone, Copy)]
+struct Quantity {
+    whole: i128,
+    fraction: f64,
+}
+
+impl Quantity {
+    /// Multiply this quantity by `factor`, rounding the result to a whole number
+    fn round(self, factor: i64) -> i128 {
+        self.whole * i128::from(factor) + (self.fraction * factor as f64).round() as i128
+    }
+}
+
+/// Fields of an interval being parsed, which are allowed to temporarily exceed the range of the
+/// final interval
+#[derive(Default)]
+struct IntervalParts {
+    months: i128,
+    days: i128,
+    microseconds: i128,
+}
+
+impl IntervalParts {
+    /// Add a (possibly fractional) number of days, with fractional days cascading into
+    /// microseconds
+    fn add_days(&mut self, days: Quantity) {
+        self.days += days.whole;
+        self.microseconds += (days.fraction * MICROSECONDS_PER_DAY as f64).round() as i128;
+    }
+
+    fn add(&mut self, quantity: Quantity, unit: &str) -> Option<()> {
+        match unit {
+            "microsecond" | "microseconds" | "us" | "usec" | "usecs" => {
+                self.microseconds += quantity.round(1)
+            }
+            "millisecond" | "milliseconds" | "ms" | "msec" | "msecs" => {
+                self.microseconds += quantity.round(1000)
+            }
+            "second" | "seconds" | "s" | "sec" | "secs" => {
+                self.microseconds += quantity.round(MICROSECONDS_PER_SECOND)
+            }
+            "minute" | "minutes" | "m" | "min" | "mins" => {
+                self.microseconds += quantity.round(MICROSECONDS_PER_MINUTE)
+            }
+            "hour" | "hours" | "h" | "hr" | "hrs" => {
+                self.microseconds += quantity.round(MICROSECONDS_PER_HOUR)
+            }
+            "day" | "days" | "d" => self.add_days(quantity),
+            "week" | "weeks" | "w" => {
+                let days = quantity.fraction * 7.0;
+                self.add_days(Quantity {
+                    whole: quantity.whole * 7 + days.trunc() as i128,
+                    fraction: days.fract(),
+                })
+            }
+            "month" | "months" | "mon" | "mons" => {
+                // Fractional months cascade into days, assuming 30 days per month
+                self.months += quantity.whole;
+                let days = quantity.fraction * DAYS_PER_MONTH as f64;
+                self.add_days(Quantity {
+                    whole: days.trunc() as i128,
+                    fraction: days.fract(),
+                })
+            }
+            // Fractional parts of units larger than months are rounded to a whole number of months
+            "year" | "years" | "y" | "yr" | "yrs" => self.months += quantity.round(12),
+            "decade" | "decades" => self.months += quantity.round(12 * 10),
+            "century" | "centuries" => self.months += quantity.round(12 * 100),
+            "millennium" | "millennia" | "millenniums" => self.months += quantity.round(12 * 1000),
+            _ => return None,
+        }
+        Some(())
+    }
+}
+
+/// Parses intervals in PostgreSQL's input syntax, such as `1 day 2 hours`, `1 year 2 mons 3 days
+/// 04:05:06`, `@ 3 days ago`, or `-1:30`. Numbers without a unit are interpreted as seconds.
+impl FromStr for Interval {
+    type Err = ReadySetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || invalid_query_err!("invalid input syntax for type interval: \"{s}\"");
+        let mut parts = IntervalParts::default();
+        let mut ago = false;
+        let mut any = false;
+
+        let mut rest = s.trim_start();
+        while !rest.is_empty() {
+            if let Some(r) = rest.strip_prefix('@') {
+                rest = r.trim_start();
+                continue;
+            }
+
+            let word_len = rest
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rest.len());
+            if word_len > 0 {
+                if !rest[..word_len].eq_ignore_ascii_case("ago") || ago {
+                    return Err(invalid());
+                }
+                ago = true;
+                rest = rest[word_len..].trim_start();
+                continue;
+            }
+
+            // A (possibly signed, possibly fractional) number
+            let (negative, r) = match rest.as_bytes()[0] {
+                b'-' => (true, &rest[1..]),
+                b'+' => (false, &rest[1..]),
+                _ => (false, rest),
+            };
+            let digits_len = r.find(|c: char| !c.is_ascii_digit()).unwrap_or(r.len());
+            let whole = if digits_len == 0 {
+                0
+            } else {
+                r[..digits_len].parse::<i128>().map_err(|_| invalid())?
+            };
+            let r = &r[digits_len..];
+            let sign = if negative { -1 } else { 1 };
+
+            if let Some(r) = r.strip_prefix(':') {
+                // A time of the form `hh:mm[:ss[.ffffff]]`
+                if digits_len == 0 {
+                    return Err(invalid());
+                }
+                let time_len = r
+                    .find(|c: char| !c.is_ascii_digit() && c != ':' && c != '.')
+                    .unwrap_or(r.len());
+                let mut fields = r[..time_len].splitn(2, ':');
+                let minutes = fields
+                    .next()
+                    .filter(|m| !m.is_empty())
+                    .and_then(|m| m.parse::<i128>().ok())
+                    .ok_or_else(invalid)?;
+                let seconds = match fields.next() {
+                    Some(s) => s.parse::<f64>().map_err(|_| invalid())?,
+                    None => 0.0,
+                };
+                if minutes >= 60 || seconds >= 60.0 {
+                    return Err(invalid());
+                }
+                parts.microseconds += sign
+                    * (whole * i128::from(MICROSECONDS_PER_HOUR)
+                        + minutes * i128::from(MICROSECONDS_PER_MINUTE)
+                        + (seconds * MICROSECONDS_PER_SECOND as f64).round() as i128);
+                rest = r[time_len..].trim_start();
+                any = true;
+                continue;
+            }
+
+            let fraction_len = match r.strip_prefix('.') {
+                Some(f) => 1 + f.find(|c: char| !c.is_ascii_digit()).unwrap_or(f.len()),
+                None => 0,
+            };
+            if digits_len == 0 && fraction_len <= 1 {
+                return Err(invalid());
+            }
+            let fraction = if fraction_len > 1 {
+                format!("0{}", &r[..fraction_len])
+                    .parse::<f64>()
+                    .map_err(|_| invalid())?
+            } else {
+                0.0
+            };
+            let quantity = Quantity {
+                whole: sign * whole,
+                fraction: sign as f64 * fraction,
+            };
+
+            let r = r[fraction_len..].trim_start();
+            let unit_len = r
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(r.len());
+            let unit = r[..unit_len].to_ascii_lowercase();
+            if unit.is_empty() || unit == "ago" {
+                parts.add(quantity, "second");
+                rest = r;
+            } else {
+                parts.add(quantity, &unit).ok_or_else(invalid)?;
+                rest = r[unit_len..].trim_start();
+            }
+            any = true;
+        }
+
+        if !any {
+            return Err(invalid());
+        }
+
+        let res = Self {
+            months: parts.months.try_into().map_err(|_| out_of_range())?,
+            days: parts.days.try_into().map_err(|_| out_of_range())?,
+            microseconds: parts.microseconds.try_into().map_err(|_| out_of_range())?,
+        };
+        if ago {
+            res.checked_neg().ok_or_else(out_of_range)
+        } else {
+            Ok(res)
+        }
+    }
+}
+
+impl ToSql for Interval {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        out.put_i64(self.microseconds);
+        out.put_i32(self.days);
+        out.put_i32(self.months);
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::INTERVAL
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Interval {
+    fn from_sql(_: &Type, mut raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        if raw.len() != 16 {
+            return Err("invalid message length: interval size mismatch".into());
+        }
+        Ok(Self {
+            microseconds: raw.get_i64(),
+            days: raw.get_i32(),
+            months: raw.get_i32(),
+        })
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::INTERVAL
+    }
+}
+
+impl Arbitrary for Interval {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Interval>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            -120_000i32..120_000,
+            -3_650_000i32..3_650_000,
+            -MICROSECONDS_PER_DAY * 1000..MICROSECONDS_PER_DAY * 1000,
+        )
+            .prop_map(|(months, days, microseconds)| Interval::new(months, days, microseconds))
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_strategy::proptest;
+
+    use super::*;
+
+    fn dt(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, s)
+            .unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        #[track_caller]
+        fn check(s: &str, months: i32, days: i32, microseconds: i64) {
+            let res = s.parse::<Interval>().unwrap();
+            assert_eq!(
+                (res.months, res.days, res.microseconds),
+                (months, days, microseconds),
+                "{s}"
+            );
+        }
+
+        check("1 day", 0, 1, 0);
+        check("7 days", 0, 7, 0);
+        check("1 day 2 hours", 0, 1, 2 * MICROSECONDS_PER_HOUR);
+        check("2 HOURS 30 minutes", 0, 0, 150 * MICROSECONDS_PER_MINUTE);
+        check("1 year 2 mons 3 days", 14, 3, 0);
+        check("1.5 years", 18, 0, 0);
+        check("1.5 months", 1, 15, 0);
+        check("1.5 days", 0, 1, 12 * MICROSECONDS_PER_HOUR);
+        check("2 weeks", 0, 14, 0);
+        check("1day", 0, 1, 0);
+        check("@ 3 days ago", 0, -3, 0);
+        check("-1 day +2 hours", 0, -1, 2 * MICROSECONDS_PER_HOUR);
+        check("04:05:06", 0, 0, 14_706 * MICROSECONDS_PER_SECOND);
+        check("-1:30", 0, 0, -90 * MICROSECONDS_PER_MINUTE);
+        check(
+            "1 day 00:00:01.5",
+            0,
+            1,
+            MICROSECONDS_PER_SECOND + MICROSECONDS_PER_SECOND / 2,
+        );
+        check("10", 0, 0, 10 * MICROSECONDS_PER_SECOND);
+        check("100 ms", 0, 0, 100_000);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        "".parse::<Interval>().unwrap_err();
+        "day".parse::<Interval>().unwrap_err();
+        "1 fortnight".parse::<Interval>().unwrap_err();
+        "1:75".parse::<Interval>().unwrap_err();
+        "99999999999 years".parse::<Interval>().unwrap_err();
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Interval::new(0, 7, 0).to_string(), "7 days");
+        assert_eq!(Interval::new(0, 1, 0).to_string(), "1 day");
+        assert_eq!(
+            Interval::new(14, 3, 14_706 * MICROSECONDS_PER_SECOND).to_string(),
+            "1 year 2 mons 3 days 04:05:06"
+        );
+        assert_eq!(
+            Interval::new(0, 1, 2 * MICROSECONDS_PER_HOUR).to_string(),
+            "1 day 02:00:00"
+        );
+        assert_eq!(
+            Interval::new(0, -1, 2 * MICROSECONDS_PER_HOUR).to_string(),
+            "-1 days +02:00:00"
+        );
+        assert_eq!(
+            Interval::new(0, 0, -90 * MICROSECONDS_PER_MINUTE).to_string(),
+            "-01:30:00"
+        );
+        assert_eq!(Interval::new(0, 0, 1_500_000).to_string(), "00:00:01.5");
+        assert_eq!(Interval::default().to_string(), "00:00:00");
+    }
+
+    #[proptest]
+    fn display_parse_round_trip(interval: Interval) {
+        let parsed = interval.to_string().parse::<Interval>().unwrap();
+        assert_eq!(
+            (parsed.months, parsed.days, parsed.microseconds),
+            (interval.months, interval.days, interval.microseconds)
+        );
+    }
+
+    #[test]
+    fn from_quantity() {
+        assert_eq!(
+            Interval::from_quantity(3, IntervalUnit::Quarter).unwrap(),
+            Interval::new(9, 0, 0)
+        );
+        assert_eq!(
+            Interval::from_quantity(-2, IntervalUnit::Hour).unwrap(),
+            Interval::new(0, 0, -2 * MICROSECONDS_PER_HOUR)
+        );
+        Interval::from_quantity(i64::MAX, IntervalUnit::Day).unwrap_err();
+    }
+
+    #[test]
+    fn comparison() {
+        assert!(Interval::new(1, 0, 0) < Interval::new(0, 31, 0));
+        assert!(Interval::new(1, 0, 0) > Interval::new(0, 29, 0));
+        assert_ne!(Interval::new(1, 0, 0), Interval::new(0, 30, 0));
+        assert!(Interval::new(0, 1, 1) > Interval::new(0, 0, MICROSECONDS_PER_DAY));
+        assert!(Interval::new(-1, 0, 0) < Interval::default());
+    }
+
+    #[test]
+    fn add_to_datetime() {
+        assert_eq!(
+            Interval::new(1, 0, 0).add_to(dt(2024, 1, 31, 12, 0, 0)),
+            Some(dt(2024, 2, 29, 12, 0, 0))
+        );
+        assert_eq!(
+            Interval::new(0, 1, 2 * MICROSECONDS_PER_HOUR).add_to(dt(2024, 12, 31, 23, 0, 0)),
+            Some(dt(2025, 1, 2, 1, 0, 0))
+        );
+        assert_eq!(
+            Interval::new(0, 7, 0).sub_from(dt(2024, 3, 3, 0, 0, 0)),
+            Some(dt(2024, 2, 25, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn age() {
+        assert_eq!(
+            Interval::age(dt(2001, 4, 10, 0, 0, 0), dt(1957, 6, 13, 0, 0, 0)).to_string(),
+            "43 years 9 mons 27 days"
+        );
+        assert_eq!(
+            Interval::age(dt(2024, 3, 15, 0, 0, 0), dt(2023, 1, 20, 0, 0, 0)).to_string(),
+            "1 year 1 mon 26 days"
+        );
+        assert_eq!(
+            Interval::age(dt(2023, 1, 20, 0, 0, 0), dt(2024, 3, 15, 0, 0, 0)).to_string(),
+            "-1 years -1 mons -26 days"
+        );
+        assert_eq!(
+            Interval::age(dt(2024, 1, 1, 1, 0, 0), dt(2023, 12, 31, 23, 30, 0)).to_string(),
+            "01:30:00"
+        );
+    }
+
+    #[test]
+    fn between() {
+        assert_eq!(
+            Interval::between(dt(2024, 3, 10, 12, 0, 0), dt(2024, 3, 1, 11, 0, 0))
+                .unwrap()
+                .to_string(),
+            "9 days 01:00:00"
+        );
+        assert_eq!(
+            Interval::between(dt(2024, 3, 1, 10, 0, 0), dt(2024, 3, 2, 12, 0, 0))
+                .unwrap()
+                .to_string(),
+            "-1 days -02:00:00"
+        );
+    }
+
+    #[test]
+    fn postgres_binary_round_trip() {
+        let interval = Interval::new(14, -3, 14_706 * MICROSECONDS_PER_SECOND);
+        let mut buf = BytesMut::new();
+        interval.to_sql(&Type::INTERVAL, &mut buf).unwrap();
+        assert_eq!(buf.len(), 16);
+        let res = Interval::from_sql(&Type::INTERVAL, &buf).unwrap();
+        assert_eq!(
+            (res.months, res.days, res.microseconds),
+            (interval.months, interval.days, interval.microseconds)
+        );
+    }
+}
@@ -21,7 +21,7 @@ use mysql_time::MySqlTime;
 use nom_sql::{DialectDisplay, Double, Float, Literal, SqlType};
 use postgres_types::Format;
 use readyset_errors::{internal, invalid_query_err, unsupported, ReadySetError, ReadySetResult};
-use readyset_util::arbitrary::{arbitrary_decimal, arbitrary_duration};
+use readyset_util::arbitrary::{arbitrary_decimal, arbitrary_duration, arbitrary_uuid};
 use readyset_util::redacted::Sensitive;
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use rust_decimal::Decimal;
@@ -36,10 +36,12 @@ pub mod dialect;
 mod r#enum;
 mod float;
 mod integer;
+mod interval;
 mod ranges;
 mod serde;
 mod text;
 mod timestamp;
+mod timezone;
 mod r#type;
 
 pub use ndarray::{ArrayD, IxDyn};
@@ -49,11 +51,13 @@ use proptest::prelude::*;
 pub use crate::array::Array;
 pub use crate::collation::Collation;
 pub use crate::dialect::Dialect;
+pub use crate::interval::Interval;
 pub use crate::r#type::{DfType, PgEnumMetadata, PgTypeCategory};
 pub use crate::ranges::{Bound, BoundedRange, IntoBoundedRange, RangeBounds};
 pub use crate::serde::TextRef;
 pub use crate::text::{Text, TinyText};
 pub use crate::timestamp::{TimestampTz, TIMESTAMP_FORMAT, TIMESTAMP_PARSE_FORMAT};
+pub use crate::timezone::Timezone;
 
 type JsonObject = serde_json::Map<String, JsonValue>;
 
@@ -66,6 +70,17 @@ const MAX_SECONDS_DATETIME_OFFSET: i32 = 85_940;
 /// Which sets `NUMERIC_SPECIAL` and `NUMERIC_NAN`
 const NUMERIC_NAN: &[u8] = &[0, 0, 0, 0, 192, 0, 0, 0];
 
+/// Parse a UUID from a string value, which can be either its textual representation, or its 16-byte
+/// binary representation (since MySQL sends the values of `BINARY(16)` columns as strings whenever
+/// they're valid UTF-8)
+pub(crate) fn parse_uuid(s: &str) -> Result<Uuid, uuid::Error> {
+    if s.len() == 16 {
+        Uuid::from_slice(s.as_bytes())
+    } else {
+        s.parse()
+    }
+}
+
 /// Used to wrap arbitrary Postgres types which aren't natively supported, enabling more extensive
 /// proxying support
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -126,6 +141,12 @@ pub enum DfValue {
     Array(Arc<Array>),
     /// Container type for arbitrary unserialized, unsupported types
     PassThrough(Arc<PassThrough>),
+    // NOTE: Using an `Arc` rather than storing the 16 bytes inline to keep the `DfValue` type 16
+    // bytes long
+    /// A UUID, stored in its 16-byte binary representation
+    Uuid(Arc<Uuid>),
+    /// An interval, made up of a number of months, days, and microseconds
+    Interval(Arc<Interval>),
     /// A sentinel maximal value.
     ///
     /// This value is always greater than all other [`DfValue`]s, except itself.
@@ -177,6 +198,8 @@ impl fmt::Display for DfValue {
             DfValue::PassThrough(ref p) => {
                 write!(f, "[{}:{:x?}]", p.ty.name(), p.data)
             }
+            DfValue::Uuid(ref u) => write!(f, "{}", u),
+            DfValue::Interval(ref i) => write!(f, "{}", i),
             DfValue::Max => f.write_str("MAX"),
         }
     }
@@ -247,6 +270,8 @@ impl DfValue {
                 format: PassThroughFormat::Binary,
                 data: [].into(),
             })),
+            DfValue::Uuid(_) => DfValue::from(Uuid::nil()),
+            DfValue::Interval(_) => DfValue::from(Interval::new(i32::MIN, i32::MIN, i64::MIN)),
             DfValue::Max => DfValue::None,
         }
     }
@@ -269,6 +294,8 @@ impl DfValue {
             DfValue::UnsignedInt(_) => DfValue::UnsignedInt(u64::max_value()),
             DfValue::Time(_) => DfValue::Time(MySqlTime::max_value()),
             DfValue::Numeric(_) => DfValue::from(Decimal::MAX),
+            DfValue::Uuid(_) => DfValue::from(Uuid::from_bytes([u8::MAX; 16])),
+            DfValue::Interval(_) => DfValue::from(Interval::new(i32::MAX, i32::MAX, i64::MAX)),
             DfValue::TinyText(_)
             | DfValue::Text(_)
             | DfValue::ByteArray(_)
@@ -316,6 +343,16 @@ impl DfValue {
         matches!(*self, DfValue::ByteArray(_))
     }
 
+    /// Checks if this value is of a UUID data type.
+    pub fn is_uuid(&self) -> bool {
+        matches!(*self, DfValue::Uuid(_))
+    }
+
+    /// Checks if this value is of an interval data type.
+    pub fn is_interval(&self) -> bool {
+        matches!(*self, DfValue::Interval(_))
+    }
+
     /// Checks if this value is of a PostgreSQL array.
     pub fn is_array(&self) -> bool {
         matches!(*self, DfValue::Array(_))
@@ -352,6 +389,8 @@ impl DfValue {
             DfValue::ByteArray(ref array) => !array.is_empty(),
             DfValue::Numeric(ref d) => !d.is_zero(),
             DfValue::BitVector(ref bits) => !bits.is_empty(),
+            DfValue::Uuid(ref u) => !u.is_nil(),
+            DfValue::Interval(ref i) => !i.is_zero(),
             // Truthiness only matters for mysql, and mysql doesn't have arrays, so we can kind of
             // pick whatever we want here - but it makes the most sense to try to limit falsiness to
             // only the things that mysql considers falsey
@@ -418,6 +457,11 @@ impl DfValue {
             Self::ByteArray(_) => Some(ByteArray),
             Self::Numeric(_) => Some(Numeric(None)),
             Self::BitVector(_) => Some(VarBit(None)),
+            Self::Uuid(_) => Some(Uuid),
+            Self::Interval(_) => Some(Interval {
+                fields: None,
+                precision: None,
+            }),
             // TODO: Once this returns DfType instead of SqlType, an empty array and an array of
             // null should be Array(Unknown) not Unknown.
             Self::Array(vs) => Some(SqlType::Array(Box::new(
@@ -453,6 +497,8 @@ impl DfValue {
             Self::ByteArray(_) => Blob,
             Self::Numeric(_) => DfType::DEFAULT_NUMERIC,
             Self::BitVector(_) => VarBit(None),
+            Self::Uuid(_) => Uuid,
+            Self::Interval(_) => Interval,
             Self::Array(array) => Array(Box::new(
                 array
                     .values()
@@ -586,7 +632,36 @@ impl DfValue {
                 },
                 _ => Err(mk_err()),
             },
-            DfValue::ByteArray(_) | DfValue::Max => Err(mk_err()),
+            DfValue::ByteArray(bytes) => match to_ty {
+                DfType::Uuid => Uuid::from_slice(bytes).map(DfValue::from).map_err(|e| {
+                    ReadySetError::DfValueConversionError {
+                        src_type: from_ty.to_string(),
+                        target_type: to_ty.to_string(),
+                        details: e.to_string(),
+                    }
+                }),
+                _ => Err(mk_err()),
+            },
+            DfValue::Uuid(u) => match to_ty {
+                DfType::Text(collation)
+                | DfType::Char(_, collation)
+                | DfType::VarChar(_, collation) => {
+                    Ok(DfValue::from_str_and_collation(&u.to_string(), *collation))
+                }
+                DfType::Blob | DfType::Binary(_) | DfType::VarBinary(_) => {
+                    Ok(DfValue::from(u.as_bytes().to_vec()))
+                }
+                _ => Err(mk_err()),
+            },
+            DfValue::Interval(i) => match to_ty {
+                DfType::Text(collation)
+                | DfType::Char(_, collation)
+                | DfType::VarChar(_, collation) => {
+                    Ok(DfValue::from_str_and_collation(&i.to_string(), *collation))
+                }
+                _ => Err(mk_err()),
+            },
+            DfValue::Max => Err(mk_err()),
             DfValue::PassThrough(ref p) => Err(ReadySetError::DfValueConversionError {
                 src_type: format!("PassThrough[{}]", p.ty),
                 target_type: to_ty.to_string(),
@@ -607,8 +682,17 @@ impl DfValue {
                 .unwrap_or(DfValue::Int(0));
         } else if (col_ty.is_array() && col_ty.innermost_array_type().is_enum())
             || col_ty.is_citext()
+            || col_ty.is_uuid()
         {
             *self = self.coerce_to(col_ty, &DfType::Unknown)?;
+        } else if let Some(collation) = col_ty.collation() {
+            // Text values are compared and hashed according to their collation, so they have to
+            // be stored with the collation of their column to be looked up and grouped correctly
+            if let Some((s, value_collation)) = self.as_str_and_collation() {
+                if value_collation != collation {
+                    *self = DfValue::from_str_and_collation(s, collation);
+                }
+            }
         }
 
         Ok(())
@@ -662,14 +746,15 @@ impl DfValue {
         }
     }
 
-    /// If `self` is [`DfValue::Text`], [`DfValue::TinyText`] or [`DfValue::ByteArray`], return a
-    /// reference to the underlying [`Array`], otherwise return a
+    /// If `self` is [`DfValue::Text`], [`DfValue::TinyText`], [`DfValue::ByteArray`] or
+    /// [`DfValue::Uuid`], return a reference to the underlying bytes, otherwise return a
     /// [`ReadySetError::DfValueConversionError`] for all other [`DfValue`] variants.
     pub fn as_bytes(&self) -> ReadySetResult<&[u8]> {
         match self {
             DfValue::Text(text) => Ok(text.as_bytes()),
             DfValue::TinyText(text) => Ok(text.as_bytes()),
             DfValue::ByteArray(bytes) => Ok(bytes.as_ref()),
+            DfValue::Uuid(u) => Ok(u.as_bytes()),
             _ => Err(ReadySetError::DfValueConversionError {
                 src_type: match self.sql_type() {
                     Some(ty) => ty.display(nom_sql::Dialect::MySQL).to_string(),
@@ -816,6 +901,15 @@ impl PartialEq for DfValue {
                     .map(|other_t: MySqlTime| t.eq(&other_t))
                     .unwrap_or(false)
             }
+            (&DfValue::Text(..) | &DfValue::TinyText(..), DfValue::Uuid(u)) => {
+                // this unwrap should be safe because no error path in try_from for &str on Text or
+                // TinyText
+                #[allow(clippy::unwrap_used)]
+                let a = <&str>::try_from(self).unwrap();
+                a.parse()
+                    .map(|other_u: Uuid| other_u.eq(u.as_ref()))
+                    .unwrap_or(false)
+            }
             (&DfValue::Int(a), &DfValue::Int(b)) => a == b,
             (&DfValue::UnsignedInt(a), &DfValue::UnsignedInt(b)) => a == b,
             (&DfValue::UnsignedInt(..), &DfValue::Int(..))
@@ -863,7 +957,7 @@ impl PartialEq for DfValue {
             (DfValue::Numeric(da), DfValue::Numeric(db)) => da == db,
             (&DfValue::Numeric(_), &DfValue::Float(_) | &DfValue::Double(_)) => other == self,
             (
-                &DfValue::Time(_) | &DfValue::TimestampTz(_),
+                &DfValue::Time(_) | &DfValue::TimestampTz(_) | &DfValue::Uuid(_),
                 &DfValue::Text(..) | &DfValue::TinyText(..),
             ) => other == self,
             (&DfValue::TimestampTz(tsa), &DfValue::TimestampTz(tsb)) => tsa == tsb,
@@ -871,6 +965,8 @@ impl PartialEq for DfValue {
             (DfValue::ByteArray(array_a), DfValue::ByteArray(array_b)) => {
                 array_a.as_ref() == array_b.as_ref()
             }
+            (DfValue::Uuid(ua), DfValue::Uuid(ub)) => ua == ub,
+            (DfValue::Interval(ia), DfValue::Interval(ib)) => ia == ib,
             (DfValue::BitVector(bits_a), DfValue::BitVector(bits_b)) => {
                 bits_a.as_ref() == bits_b.as_ref()
             }
@@ -925,8 +1021,19 @@ impl Ord for DfValue {
                 a.map(|t: MySqlTime| t.cmp(other_t))
                     .unwrap_or(Ordering::Greater)
             }
+            (&DfValue::Text(..) | &DfValue::TinyText(..), DfValue::Uuid(other_u)) => {
+                // this unwrap should be safe because no error path in try_from for &str on Text or
+                // TinyText
+                #[allow(clippy::unwrap_used)]
+                let a = <&str>::try_from(self).unwrap();
+                // Text which isn't a valid UUID sorts by kind, like any other pair of values of
+                // different types
+                a.parse()
+                    .map(|u: Uuid| u.cmp(other_u))
+                    .unwrap_or(Ordering::Less)
+            }
             (
-                &DfValue::Time(_) | &DfValue::TimestampTz(_),
+                &DfValue::Time(_) | &DfValue::TimestampTz(_) | &DfValue::Uuid(_),
                 &DfValue::Text(..) | &DfValue::TinyText(..),
             ) => other.cmp(self).reverse(),
             (&DfValue::Int(a), &DfValue::Int(b)) => a.cmp(&b),
@@ -1025,6 +1132,8 @@ impl Ord for DfValue {
             (&DfValue::Numeric(_), &DfValue::Int(..))
             | (&DfValue::Numeric(_), &DfValue::UnsignedInt(..)) => other.cmp(self).reverse(),
             (DfValue::ByteArray(array_a), DfValue::ByteArray(array_b)) => array_a.cmp(array_b),
+            (DfValue::Uuid(ua), DfValue::Uuid(ub)) => ua.cmp(ub),
+            (DfValue::Interval(ia), DfValue::Interval(ib)) => ia.cmp(ib),
             (DfValue::BitVector(bits_a), DfValue::BitVector(bits_b)) => bits_a.cmp(bits_b),
             (DfValue::Array(vs_a), DfValue::Array(vs_b)) => vs_a.cmp(vs_b),
 
@@ -1058,6 +1167,8 @@ impl Hash for DfValue {
             DfValue::TimestampTz(ts) => ts.hash(state),
             DfValue::Time(ref t) => t.hash(state),
             DfValue::ByteArray(ref array) => array.hash(state),
+            DfValue::Uuid(ref u) => u.hash(state),
+            DfValue::Interval(ref i) => i.hash(state),
             DfValue::Numeric(ref d) => d.hash(state),
             DfValue::BitVector(ref bits) => bits.hash(state),
             DfValue::Array(ref vs) => vs.hash(state),
@@ -1296,6 +1407,8 @@ impl TryFrom<DfValue> for Literal {
             DfValue::Numeric(ref d) => Ok(Literal::Numeric(d.mantissa(), d.scale())),
             DfValue::BitVector(ref bits) => Ok(Literal::BitVector(bits.as_ref().to_bytes())),
             DfValue::Array(_) => unsupported!("Arrays not implemented yet"),
+            DfValue::Uuid(ref u) => Ok(Literal::String(u.to_string())),
+            DfValue::Interval(ref i) => Ok(Literal::String(i.to_string())),
             DfValue::PassThrough(_) => internal!("PassThrough has no representation as a literal"),
             DfValue::Max => internal!("MAX has no representation as a literal"),
         }
@@ -1320,6 +1433,18 @@ impl From<Vec<u8>> for DfValue {
     }
 }
 
+impl From<Uuid> for DfValue {
+    fn from(u: Uuid) -> Self {
+        DfValue::Uuid(Arc::new(u))
+    }
+}
+
+impl From<Interval> for DfValue {
+    fn from(i: Interval) -> Self {
+        DfValue::Interval(Arc::new(i))
+    }
+}
+
 impl From<NaiveDate> for DfValue {
     fn from(dt: NaiveDate) -> Self {
         DfValue::TimestampTz(dt.into())
@@ -1405,6 +1530,7 @@ impl<'a> TryFrom<&'a DfValue> for Vec<u8> {
             DfValue::Text(ref t) => Ok(t.as_bytes().to_vec()),
             DfValue::TinyText(ref tt) => Ok(tt.as_str().as_bytes().to_vec()),
             DfValue::ByteArray(ref array) => Ok(array.as_ref().clone()),
+            DfValue::Uuid(ref u) => Ok(u.as_bytes().to_vec()),
             _ => Err(Self::Error::DfValueConversionError {
                 src_type: "DfValue".to_string(),
                 target_type: "Vec<u8>".to_string(),
@@ -1438,6 +1564,7 @@ impl TryFrom<DfValue> for Vec<u8> {
             DfValue::Text(t) => Ok(t.as_bytes().to_vec()),
             DfValue::TinyText(tt) => Ok(tt.as_str().as_bytes().to_vec()),
             DfValue::ByteArray(bytes) => Ok(bytes.as_ref().clone()),
+            DfValue::Uuid(u) => Ok(u.as_bytes().to_vec()),
             _ => Err(Self::Error::DfValueConversionError {
                 src_type: match data.sql_type() {
                     Some(ty) => ty.display(nom_sql::Dialect::MySQL).to_string(),
@@ -1801,6 +1928,16 @@ impl ToSql for DfValue {
                     })
                     .and_then(|m| m.to_sql(ty, out))
             }
+            (Self::Text(_) | Self::TinyText(_), &Type::INTERVAL) => <&str>::try_from(self)
+                .unwrap()
+                .parse::<Interval>()
+                .map_err(|e| {
+                    Box::<dyn Error + Send + Sync>::from(format!(
+                        "Could not convert Text into an interval: {}",
+                        e
+                    ))
+                })
+                .and_then(|i| i.to_sql(ty, out)),
             (Self::Text(_) | Self::TinyText(_), &Type::JSON | &Type::JSONB) => {
                 serde_json::from_str::<serde_json::Value>(<&str>::try_from(self).unwrap())
                     .map_err(|e| {
@@ -1820,6 +1957,14 @@ impl ToSql for DfValue {
             (Self::TimestampTz(x), &Type::TIMESTAMP) => x.to_chrono().naive_local().to_sql(ty, out),
             (Self::TimestampTz(ref ts), _) => ts.to_chrono().to_sql(ty, out),
             (Self::Time(x), _) => NaiveTime::from(*x).to_sql(ty, out),
+            (Self::Uuid(u), &Type::TEXT | &Type::VARCHAR | &Type::BPCHAR | &Type::NAME) => {
+                u.to_string().to_sql(ty, out)
+            }
+            (Self::Uuid(u), _) => u.as_ref().to_sql(ty, out),
+            (Self::Interval(i), &Type::TEXT | &Type::VARCHAR | &Type::BPCHAR | &Type::NAME) => {
+                i.to_string().to_sql(ty, out)
+            }
+            (Self::Interval(i), _) => i.as_ref().to_sql(ty, out),
             (Self::ByteArray(ref array), _) => array.as_ref().to_sql(ty, out),
             (Self::BitVector(ref bits), _) => bits.as_ref().to_sql(ty, out),
             (Self::Array(ref array), _) => array.as_ref().to_sql(ty, out),
@@ -1906,7 +2051,8 @@ impl<'a> FromSql<'a> for DfValue {
                     MacAddress::from_sql(ty, raw)?.to_string(MacAddressFormat::HexString),
                 )),
                 Type::INET => Ok(DfValue::from(IpInet::from_sql(ty, raw)?.to_string())),
-                Type::UUID => Ok(DfValue::from(Uuid::from_sql(ty, raw)?.to_string())),
+                Type::UUID => mk_from_sql!(Uuid),
+                Type::INTERVAL => mk_from_sql!(Interval),
                 Type::JSON | Type::JSONB => {
                     let raw = match (ty, raw) {
                         (&Type::JSONB, []) => {
@@ -2012,6 +2158,10 @@ impl TryFrom<&DfValue> for mysql_common::value::Value {
                 val.microseconds(),
             )),
             DfValue::ByteArray(array) => Ok(Value::Bytes(array.as_ref().clone())),
+            // MySQL has no UUID type, so send UUIDs in their binary representation, matching the
+            // `BINARY(16)` columns we store them for
+            DfValue::Uuid(u) => Ok(Value::Bytes(u.as_bytes().to_vec())),
+            DfValue::Interval(_) => internal!("MySQL does not support interval types"),
             DfValue::PassThrough(_) => {
                 internal!("DfValue::PassThrough to MySQL Value type is not implemented")
             }
@@ -2126,7 +2276,17 @@ impl<'a, 'b> Add<&'b DfValue> for &'a DfValue {
     type Output = ReadySetResult<DfValue>;
 
     fn add(self, other: &'b DfValue) -> Self::Output {
-        Ok(arithmetic_operation!(+, checked_add, self, other))
+        // Date arithmetic returns NULL on overflow, the same as integer arithmetic
+        match (self, other) {
+            (DfValue::TimestampTz(ts), DfValue::Interval(i))
+            | (DfValue::Interval(i), DfValue::TimestampTz(ts)) => Ok(ts
+                .checked_add_interval(i)
+                .map_or(DfValue::None, DfValue::TimestampTz)),
+            (DfValue::Interval(a), DfValue::Interval(b)) => {
+                Ok(a.checked_add(b).map_or(DfValue::None, DfValue::from))
+            }
+            _ => Ok(arithmetic_operation!(+, checked_add, self, other)),
+        }
     }
 }
 
@@ -2134,7 +2294,20 @@ impl<'a, 'b> Sub<&'b DfValue> for &'a DfValue {
     type Output = ReadySetResult<DfValue>;
 
     fn sub(self, other: &'b DfValue) -> Self::Output {
-        Ok(arithmetic_operation!(-, checked_sub, self, other))
+        match (self, other) {
+            (DfValue::TimestampTz(ts), DfValue::Interval(i)) => Ok(ts
+                .checked_sub_interval(i)
+                .map_or(DfValue::None, DfValue::TimestampTz)),
+            (DfValue::TimestampTz(a), DfValue::TimestampTz(b)) => Ok(Interval::between(
+                a.to_chrono().naive_utc(),
+                b.to_chrono().naive_utc(),
+            )
+            .map_or(DfValue::None, DfValue::from)),
+            (DfValue::Interval(a), DfValue::Interval(b)) => {
+                Ok(a.checked_sub(b).map_or(DfValue::None, DfValue::from))
+            }
+            _ => Ok(arithmetic_operation!(-, checked_sub, self, other)),
+        }
     }
 }
 
@@ -2199,6 +2372,8 @@ impl Arbitrary for DfValue {
                     }))
                 })
                 .boxed(),
+            Some(DfValueKind::Uuid) => arbitrary_uuid().prop_map(DfValue::from).boxed(),
+            Some(DfValueKind::Interval) => any::<Interval>().prop_map(DfValue::from).boxed(),
             None => prop_oneof![
                 Just(DfValue::None),
                 Just(DfValue::Max),
@@ -2213,7 +2388,9 @@ impl Arbitrary for DfValue {
                     .prop_map(DfValue::Time),
                 any::<Vec<u8>>().prop_map(|b| DfValue::ByteArray(Arc::new(b))),
                 arbitrary_decimal().prop_map(DfValue::from),
-                any::<Array>().prop_map(DfValue::from)
+                any::<Array>().prop_map(DfValue::from),
+                arbitrary_uuid().prop_map(DfValue::from),
+                any::<Interval>().prop_map(DfValue::from)
             ]
             .boxed(),
         }
@@ -2228,7 +2405,7 @@ mod arbitrary {
     use proptest::arbitrary::any;
     use proptest::collection::vec;
     use proptest::strategy::{Just, Strategy};
-    use readyset_util::arbitrary::arbitrary_decimal;
+    use readyset_util::arbitrary::{arbitrary_decimal, arbitrary_uuid};
 
     use crate::array::Array;
     use crate::{DfType, DfValue, TimestampTz};
@@ -2313,12 +2490,13 @@ mod arbitrary {
             Some(DfType::Enum { variants, .. }) => proptest::sample::select(variants.to_vec())
                 .prop_map(DfValue::from)
                 .boxed(),
+            Some(DfType::Uuid) => arbitrary_uuid().prop_map(DfValue::from).boxed(),
+            Some(DfType::Interval) => any::<crate::Interval>().prop_map(DfValue::from).boxed(),
             Some(DfType::Unknown) | None => Just(DfValue::None).boxed(),
             // These are ignored for now
             Some(DfType::Jsonb)
             | Some(DfType::Json)
             | Some(DfType::MacAddr)
-            | Some(DfType::Uuid)
             | Some(DfType::Inet) => Just(DfValue::None).boxed(),
         }
     }
@@ -2447,8 +2625,12 @@ mod tests {
                 if t.to_chrono().naive_local().date().year() < 1000
                     || t.to_chrono().naive_local().date().year() > 9999 =>
                 false,
-            DfValue::ByteArray(_) | DfValue::BitVector(_) | DfValue::Array(_) | DfValue::Max =>
-                false,
+            DfValue::ByteArray(_)
+            | DfValue::BitVector(_)
+            | DfValue::Array(_)
+            | DfValue::Uuid(_)
+            | DfValue::Interval(_)
+            | DfValue::Max => false,
             _ => true,
         });
 
@@ -2691,6 +2873,35 @@ mod tests {
         assert_eq!((&DfValue::Int(2) - &DfValue::from(1)).unwrap(), 1.into());
     }
 
+    #[test]
+    fn interval_arithmetic() {
+        let ts = |s: &str| DfValue::TimestampTz(s.parse().unwrap());
+        let day = DfValue::from(Interval::new(0, 1, 0));
+
+        assert_eq!(
+            (&ts("2024-02-28 12:00:00") + &day).unwrap(),
+            ts("2024-02-29 12:00:00")
+        );
+        assert_eq!(
+            (&day + &ts("2024-02-28 12:00:00")).unwrap(),
+            ts("2024-02-29 12:00:00")
+        );
+        assert_eq!(
+            (&ts("2024-03-01 12:00:00") - &day).unwrap(),
+            ts("2024-02-29 12:00:00")
+        );
+        assert_eq!(
+            (&ts("2024-03-10 12:00:00") - &ts("2024-03-01 11:00:00")).unwrap(),
+            DfValue::from(Interval::new(0, 9, 3_600_000_000))
+        );
+        assert_eq!(
+            (&day + &day).unwrap(),
+            DfValue::from(Interval::new(0, 2, 0))
+        );
+        assert_eq!((&day - &day).unwrap(), DfValue::from(Interval::default()));
+        assert!((&DfValue::Int(1) + &day).is_err());
+    }
+
     #[test]
     fn multiply_data_types() {
         assert_arithmetic!(*, 2, 1, 2);
@@ -3167,6 +3378,36 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn uuid_roundtrip() -> Result<(), Box<dyn Error + Sync + Send>> {
+        let uuid = Uuid::parse_str("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11")?;
+        let mut raw = BytesMut::new();
+        uuid.to_sql(&Type::UUID, &mut raw)?;
+
+        let value = DfValue::from_sql(&Type::UUID, &raw)?;
+        assert_eq!(value, DfValue::from(uuid));
+        assert_eq!(value.to_string(), "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11");
+
+        let mut received = BytesMut::new();
+        value.to_sql(&Type::UUID, &mut received)?;
+        assert_eq!(raw, received);
+
+        assert_eq!(
+            mysql_common::value::Value::try_from(&value)?,
+            mysql_common::value::Value::Bytes(uuid.as_bytes().to_vec())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn uuid_ordering() {
+        let low = DfValue::from(Uuid::parse_str("00000000-0000-0000-0000-0000000000ff").unwrap());
+        let high = DfValue::from(Uuid::parse_str("ff000000-0000-0000-0000-000000000000").unwrap());
+        assert!(low < high);
+        assert_eq!(high, DfValue::from("FF000000-0000-0000-0000-000000000000"));
+        assert!(DfValue::from("ff000000-0000-0000-0000-000000000001") > high);
+    }
+
     #[test]
     fn deeply_nested_json_roundtrip() -> Result<(), Box<dyn Error + Sync + Send>> {
         let nesting_level = 1_000_000;
@@ -3476,6 +3717,24 @@ mod tests {
         assert_eq!(numeric2.cmp(&int1), Ordering::Less);
     }
 
+    #[test]
+    fn maybe_coerce_for_table_op_collation() {
+        let mut val = DfValue::from("ABC");
+        val.maybe_coerce_for_table_op(&DfType::VarChar(255, Collation::Utf8Ci))
+            .unwrap();
+        assert_eq!(val.as_str_and_collation(), Some(("ABC", Collation::Utf8Ci)));
+        assert_eq!(
+            val,
+            DfValue::from_str_and_collation("abc", Collation::Utf8Ci)
+        );
+
+        let mut val = DfValue::from_str_and_collation("ABC", Collation::Utf8Ci);
+        val.maybe_coerce_for_table_op(&DfType::Text(Collation::Utf8))
+            .unwrap();
+        assert_eq!(val.as_str_and_collation(), Some(("ABC", Collation::Utf8)));
+        assert_ne!(val, DfValue::from("abc"));
+    }
+
     #[test]
     fn array_sql_type() {
         let arr = DfValue::from(vec![DfValue::None, DfValue::from(1)]);
@@ -3770,9 +4029,38 @@ mod tests {
 
         #[test]
         fn text_to_uuid() {
-            let input = DfValue::from(uuid::Uuid::new_v4().to_string());
+            let uuid = uuid::Uuid::new_v4();
+            let input = DfValue::from(uuid.to_string());
             let result = input.coerce_to(&DfType::Uuid, &DfType::Unknown).unwrap();
-            assert_eq!(input, result);
+            assert_eq!(result, DfValue::from(uuid));
+            assert!(result.is_uuid());
+        }
+
+        #[test]
+        fn uuid_to_text_and_bytes() {
+            let uuid = uuid::Uuid::new_v4();
+            let input = DfValue::from(uuid);
+            assert_eq!(
+                input
+                    .coerce_to(&DfType::DEFAULT_TEXT, &DfType::Uuid)
+                    .unwrap()
+                    .as_str()
+                    .unwrap(),
+                uuid.to_string()
+            );
+            assert_eq!(
+                input.coerce_to(&DfType::Binary(16), &DfType::Uuid).unwrap(),
+                DfValue::from(uuid.as_bytes().to_vec())
+            );
+            assert_eq!(
+                DfValue::from(uuid.as_bytes().to_vec())
+                    .coerce_to(&DfType::Uuid, &DfType::Binary(16))
+                    .unwrap(),
+                input
+            );
+            DfValue::from(vec![1u8, 2, 3])
+                .coerce_to(&DfType::Uuid, &DfType::Blob)
+                .unwrap_err();
         }
 
         macro_rules! bool_conversion {
@@ -11,8 +11,9 @@ use serde::{Deserialize, Deserializer, Serialize};
 use serde_bytes::{ByteBuf, Bytes};
 use strum::VariantNames;
 use strum_macros::{EnumString, EnumVariantNames, FromRepr};
+use uuid::Uuid;
 
-use crate::{Array, Collation, DfValue, Text, TimestampTz, TinyText};
+use crate::{Array, Collation, DfValue, Interval, Text, TimestampTz, TinyText};
 
 impl DfValue {
     /// Version number for the current implementations of [`serde::Deserialize`] and
@@ -55,6 +56,10 @@ impl DfValue {
             DfValue::Numeric(Arc::new(Decimal::MAX)),
             DfValue::BitVector(Arc::new(BitVec::from_bytes(b"aaaaaaaaa"))),
             DfValue::Array(Arc::new(Array::from(vec![DfValue::from("aaaaaaaaa")]))),
+            DfValue::Uuid(Arc::new(
+                "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11".parse().unwrap(),
+            )),
+            DfValue::from(Interval::new(14, 3, 14_706_000_000)),
             DfValue::Max,
         ]
     }
@@ -74,6 +79,8 @@ enum Variant {
     TimestampTz,
     Array,
     Max,
+    Uuid,
+    Interval,
 }
 
 enum TextOrTinyText {
@@ -148,6 +155,14 @@ impl serde::ser::Serialize for DfValue {
                 serialize_variant(serializer, Variant::TimestampTz, &(ts, extra))
             }
             DfValue::Array(vs) => serialize_variant(serializer, Variant::Array, &vs),
+            DfValue::Uuid(u) => {
+                serialize_variant(serializer, Variant::Uuid, Bytes::new(u.as_bytes()))
+            }
+            DfValue::Interval(i) => serialize_variant(
+                serializer,
+                Variant::Interval,
+                &(i.months(), i.days(), i.microseconds()),
+            ),
             DfValue::PassThrough(v) => Err(serde::ser::Error::custom(format_args!(
                 "PassThrough value of type {} not supported in dataflow graph",
                 v.ty
@@ -182,7 +197,7 @@ impl<'de> Deserialize<'de> for DfValue {
                 } else {
                     Err(serde::de::Error::invalid_value(
                         serde::de::Unexpected::Unsigned(val),
-                        &"variant index 0 <= i < 11",
+                        &"variant index 0 <= i < 14",
                     ))
                 }
             }
@@ -286,6 +301,17 @@ impl<'de> Deserialize<'de> for DfValue {
                     (Variant::Max, variant) => {
                         VariantAccess::unit_variant(variant).map(|_| DfValue::Max)
                     }
+                    (Variant::Uuid, variant) => VariantAccess::newtype_variant::<ByteBuf>(variant)
+                        .and_then(|v| {
+                            Uuid::from_slice(&v)
+                                .map(DfValue::from)
+                                .map_err(|_| serde::de::Error::invalid_length(v.len(), &"16 bytes"))
+                        }),
+                    (Variant::Interval, variant) => {
+                        VariantAccess::newtype_variant::<(i32, i32, i64)>(variant).map(
+                            |(months, days, micros)| Interval::new(months, days, micros).into(),
+                        )
+                    }
                 }
             }
         }
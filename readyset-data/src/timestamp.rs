@@ -4,13 +4,14 @@ use std::hash::Hash;
 use std::str::FromStr;
 
 use chrono::{
-    DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike,
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+    Timelike,
 };
 use proptest::arbitrary::Arbitrary;
 use readyset_errors::{internal_err, ReadySetError, ReadySetResult};
 use serde::{Deserialize, Serialize};
 
-use crate::{DfType, DfValue};
+use crate::{DfType, DfValue, Interval};
 
 /// The format for timestamps when parsed as text
 pub const TIMESTAMP_PARSE_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
@@ -99,6 +100,12 @@ impl TimestampTz {
         self.extra[2] |= TimestampTz::DATE_FLAG
     }
 
+    /// Mark this timestamp as containing a time as well as a date
+    #[inline(always)]
+    fn clear_date_only(&mut self) {
+        self.extra[2] &= !TimestampTz::DATE_FLAG
+    }
+
     /// Return the timezone offset from UTC in seconds
     #[inline(always)]
     fn get_offset(&self) -> i32 {
@@ -279,6 +286,33 @@ impl TimestampTz {
         self.into()
     }
 
+    /// Add the given interval to this timestamp, in the timestamp's own timezone offset, returning
+    /// [`None`] if the result is out of range.
+    ///
+    /// If this timestamp is a date and the interval moves it away from midnight, the result is a
+    /// datetime, as in `DATE_ADD('2024-01-01', INTERVAL 1 HOUR)`.
+    pub fn checked_add_interval(&self, interval: &Interval) -> Option<Self> {
+        self.map_local(|dt| interval.add_to(dt))
+    }
+
+    /// Subtract the given interval from this timestamp, in the timestamp's own timezone offset,
+    /// returning [`None`] if the result is out of range.
+    pub fn checked_sub_interval(&self, interval: &Interval) -> Option<Self> {
+        self.map_local(|dt| interval.sub_from(dt))
+    }
+
+    fn map_local(&self, f: impl FnOnce(NaiveDateTime) -> Option<NaiveDateTime>) -> Option<Self> {
+        let local = f(self.to_chrono().naive_local())?;
+        let mut res = TimestampTz {
+            datetime: local.checked_sub_signed(Duration::seconds(self.get_offset().into()))?,
+            extra: self.extra,
+        };
+        if local.time() != NaiveTime::MIN {
+            res.clear_date_only();
+        }
+        Some(res)
+    }
+
     // MySQL can cast a timestamp into a signed/unsigned integer
     // where the fields up to seconds are decimal digits. i.e.
     // +--------------------------------------------------------------+
@@ -348,14 +382,15 @@ impl TimestampTz {
     pub(crate) fn coerce_to(&self, to_ty: &DfType) -> ReadySetResult<DfValue> {
         match *to_ty {
             DfType::Timestamp { subsecond_digits } => {
-                // Conversion into timestamp without tz.
-                let mut ts: TimestampTz = self.to_chrono().naive_local().into();
+                // Conversion into timestamp without tz. Timestamps with a timezone are converted
+                // to their UTC time, since UTC is the timezone ReadySet evaluates queries in.
+                let mut ts: TimestampTz = self.to_chrono().naive_utc().into();
                 ts.set_subsecond_digits(subsecond_digits as u8);
                 Ok(DfValue::TimestampTz(ts))
             }
             DfType::TimestampTz { subsecond_digits } => {
-                // TODO: when converting into a timestamp with tz on postgres should apply
-                // local tz, but what is local for noria?
+                // Timestamps without a timezone are interpreted as being in UTC, which leaves the
+                // instant in time they represent unchanged.
                 let mut ts_tz = *self;
                 ts_tz.set_offset(0);
                 ts_tz.set_subsecond_digits(subsecond_digits as u8);
@@ -448,6 +483,7 @@ impl TimestampTz {
             | DfType::MacAddr
             | DfType::Inet
             | DfType::Uuid
+            | DfType::Interval
             | DfType::Bit(_)
             | DfType::VarBit(_)
             | DfType::Array(_) => Err(ReadySetError::DfValueConversionError {
@@ -635,6 +671,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn timestamp_tz_coercion() {
+        let ts = DfValue::TimestampTz(TimestampTz::from_str("2004-10-19 10:23:54+02").unwrap());
+
+        assert_eq!(
+            ts.coerce_to(
+                &DfType::Timestamp {
+                    subsecond_digits: 0
+                },
+                &DfType::Unknown
+            )
+            .unwrap(),
+            DfValue::from(
+                chrono::NaiveDate::from_ymd_opt(2004, 10, 19)
+                    .unwrap()
+                    .and_hms_opt(8, 23, 54)
+                    .unwrap()
+            )
+        );
+
+        let converted = ts
+            .coerce_to(
+                &DfType::TimestampTz {
+                    subsecond_digits: 0,
+                },
+                &DfType::Unknown,
+            )
+            .unwrap();
+        assert_eq!(converted, ts);
+        assert_eq!(converted.to_string(), "2004-10-19 08:23:54+00:00");
+    }
+
     #[test]
     fn timestamp_from_str() {
         assert_eq!(
@@ -753,4 +821,36 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn add_interval() {
+        let date = TimestampTz::from(NaiveDate::from_ymd_opt(2024, 1, 31).unwrap());
+        assert_eq!(
+            date.checked_add_interval(&Interval::new(1, 0, 0))
+                .unwrap()
+                .to_string(),
+            "2024-02-29"
+        );
+        assert_eq!(
+            date.checked_add_interval(&Interval::new(0, 0, 3_600_000_000))
+                .unwrap()
+                .to_string(),
+            "2024-01-31 01:00:00"
+        );
+        assert_eq!(
+            date.checked_sub_interval(&Interval::new(0, 7, 0))
+                .unwrap()
+                .to_string(),
+            "2024-01-24"
+        );
+
+        // Intervals are added in the timestamp's own offset
+        let ts = TimestampTz::from_str("2024-03-31 02:00:00+05").unwrap();
+        assert_eq!(
+            ts.checked_add_interval(&Interval::new(1, 0, 0))
+                .unwrap()
+                .to_string(),
+            "2024-04-30 02:00:00+05:00"
+        );
+    }
 }
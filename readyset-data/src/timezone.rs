@@ -0,0 +1,258 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, FixedOffset, LocalResult, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use readyset_errors::{invalid_query_err, ReadySetError, ReadySetResult};
+
+use crate::dialect::SqlEngine;
+use crate::{DfType, DfValue, Dialect};
+
+/// A timezone, as specified in the session timezone of a connection (`SET timezone` in
+/// PostgreSQL, `SET time_zone` in MySQL) or in the arguments to `CONVERT_TZ`.
+///
+/// Timezones can either be a named zone from the IANA timezone database, such as
+/// `America/New_York`, or a fixed offset from UTC, such as `+10:00`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timezone {
+    /// A named timezone, whose offset from UTC can vary over time (eg due to daylight savings)
+    Named(Tz),
+    /// A fixed offset from UTC
+    Offset(FixedOffset),
+}
+
+impl Default for Timezone {
+    fn default() -> Self {
+        Self::UTC
+    }
+}
+
+impl fmt::Display for Timezone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Timezone::Named(tz) => write!(f, "{tz}"),
+            Timezone::Offset(offset) => write!(f, "{offset}"),
+        }
+    }
+}
+
+impl FromStr for Timezone {
+    type Err = ReadySetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with(['+', '-']) {
+            s.parse::<FixedOffset>()
+                .map(Self::Offset)
+                .map_err(|_| invalid_query_err!("Invalid timezone offset: {s}"))
+        } else {
+            s.parse::<Tz>()
+                .map(Self::Named)
+                .map_err(|_| invalid_query_err!("Unknown timezone: {s}"))
+        }
+    }
+}
+
+impl Timezone {
+    /// The UTC timezone
+    pub const UTC: Timezone = Timezone::Named(Tz::UTC);
+
+    /// Returns true if this timezone is always at a zero offset from UTC
+    pub fn is_utc(&self) -> bool {
+        match self {
+            Timezone::Named(tz) => matches!(tz, Tz::UTC | Tz::Etc__UTC | Tz::GMT | Tz::Etc__GMT),
+            Timezone::Offset(offset) => offset.local_minus_utc() == 0,
+        }
+    }
+
+    /// Returns the given UTC datetime, at this timezone's offset from UTC at that time
+    pub fn from_utc_datetime(&self, utc: &NaiveDateTime) -> DateTime<FixedOffset> {
+        match self {
+            Timezone::Named(tz) => tz.from_utc_datetime(utc).fixed_offset(),
+            Timezone::Offset(offset) => offset.from_utc_datetime(utc),
+        }
+    }
+
+    /// Interprets the given datetime as a local time in this timezone. Returns `None` if the local
+    /// time doesn't exist or is ambiguous in this timezone (eg due to a daylight savings
+    /// transition).
+    pub fn from_local_datetime(&self, local: &NaiveDateTime) -> Option<DateTime<FixedOffset>> {
+        let res = match self {
+            Timezone::Named(tz) => tz.from_local_datetime(local).map(|dt| dt.fixed_offset()),
+            Timezone::Offset(offset) => offset.from_local_datetime(local),
+        };
+        match res {
+            LocalResult::Single(dt) => Some(dt),
+            LocalResult::None | LocalResult::Ambiguous(..) => None,
+        }
+    }
+
+    /// Coerces a value supplied by a client in a session with this timezone to the given type, so
+    /// that it can be compared against values of a column of that type.
+    ///
+    /// Values of MySQL `TIMESTAMP` and PostgreSQL `timestamptz` columns are stored in UTC, but
+    /// clients supply timestamps without an explicit offset in their session timezone, so those
+    /// are converted to UTC first. Returns an error if such a timestamp doesn't exist or is
+    /// ambiguous in this timezone.
+    pub fn coerce_client_value(
+        &self,
+        value: &DfValue,
+        to_ty: &DfType,
+        dialect: Dialect,
+    ) -> ReadySetResult<DfValue> {
+        let stored_in_utc = match to_ty {
+            DfType::TimestampTz { .. } => true,
+            DfType::Timestamp { .. } => dialect.engine() == SqlEngine::MySQL,
+            _ => false,
+        };
+        if self.is_utc() || !stored_in_utc || value.is_none() {
+            return value.coerce_to(to_ty, &DfType::Unknown);
+        }
+
+        let subsecond_digits = to_ty.subsecond_digits().unwrap_or_default();
+        let local =
+            match value.coerce_to(&DfType::DateTime { subsecond_digits }, &DfType::Unknown)? {
+                DfValue::TimestampTz(ts) if !ts.has_timezone() => ts.to_chrono().naive_local(),
+                _ => return value.coerce_to(to_ty, &DfType::Unknown),
+            };
+        let datetime = self.from_local_datetime(&local).ok_or_else(|| {
+            invalid_query_err!(
+                "Timestamp {local} does not exist or is ambiguous in timezone {self}"
+            )
+        })?;
+
+        DfValue::from(datetime).coerce_to(to_ty, &DfType::Unknown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            "America/New_York".parse::<Timezone>().unwrap(),
+            Timezone::Named(Tz::America__New_York)
+        );
+        assert_eq!(
+            "+10:00".parse::<Timezone>().unwrap(),
+            Timezone::Offset(FixedOffset::east_opt(10 * 3600).unwrap())
+        );
+        assert_eq!(
+            "-03:30".parse::<Timezone>().unwrap(),
+            Timezone::Offset(FixedOffset::west_opt(3 * 3600 + 1800).unwrap())
+        );
+        "Not/A_Timezone".parse::<Timezone>().unwrap_err();
+        "+ab:cd".parse::<Timezone>().unwrap_err();
+    }
+
+    #[test]
+    fn is_utc() {
+        assert!(Timezone::UTC.is_utc());
+        assert!("Etc/UTC".parse::<Timezone>().unwrap().is_utc());
+        assert!("+00:00".parse::<Timezone>().unwrap().is_utc());
+        assert!(!"+01:00".parse::<Timezone>().unwrap().is_utc());
+        assert!(!"Europe/London".parse::<Timezone>().unwrap().is_utc());
+    }
+
+    #[test]
+    fn from_local_datetime_dst_gap() {
+        let tz = "America/New_York".parse::<Timezone>().unwrap();
+        let nonexistent = NaiveDate::from_ymd_opt(2023, 3, 12)
+            .unwrap()
+            .and_hms_opt(2, 30, 0)
+            .unwrap();
+        assert_eq!(tz.from_local_datetime(&nonexistent), None);
+    }
+
+    #[test]
+    fn from_utc_datetime() {
+        let tz = "America/New_York".parse::<Timezone>().unwrap();
+        let utc = NaiveDate::from_ymd_opt(2023, 7, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let local = tz.from_utc_datetime(&utc);
+        assert_eq!(local.naive_utc(), utc);
+        assert_eq!(
+            local.naive_local(),
+            NaiveDate::from_ymd_opt(2023, 7, 1)
+                .unwrap()
+                .and_hms_opt(8, 0, 0)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn coerce_client_value() {
+        let tz = "America/New_York".parse::<Timezone>().unwrap();
+        let local = DfValue::from("2023-07-01 08:00:00");
+        let utc = NaiveDate::from_ymd_opt(2023, 7, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+
+        // MySQL `TIMESTAMP` columns are stored in UTC
+        let res = tz
+            .coerce_client_value(
+                &local,
+                &DfType::Timestamp {
+                    subsecond_digits: 0,
+                },
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap();
+        assert_eq!(NaiveDateTime::try_from(&res).unwrap(), utc);
+
+        // As are PostgreSQL `timestamptz` columns
+        let res = tz
+            .coerce_client_value(
+                &local,
+                &DfType::TimestampTz {
+                    subsecond_digits: 6,
+                },
+                Dialect::DEFAULT_POSTGRESQL,
+            )
+            .unwrap();
+        assert_eq!(NaiveDateTime::try_from(&res).unwrap(), utc);
+
+        // PostgreSQL `timestamp` and MySQL `DATETIME` columns have no timezone
+        let res = tz
+            .coerce_client_value(
+                &local,
+                &DfType::Timestamp {
+                    subsecond_digits: 6,
+                },
+                Dialect::DEFAULT_POSTGRESQL,
+            )
+            .unwrap();
+        assert_eq!(
+            NaiveDateTime::try_from(&res).unwrap(),
+            utc - chrono::Duration::hours(4)
+        );
+
+        // Timestamps with an explicit offset are left alone
+        let res = tz
+            .coerce_client_value(
+                &DfValue::from("2023-07-01 12:00:00+00"),
+                &DfType::TimestampTz {
+                    subsecond_digits: 6,
+                },
+                Dialect::DEFAULT_POSTGRESQL,
+            )
+            .unwrap();
+        assert_eq!(NaiveDateTime::try_from(&res).unwrap(), utc);
+
+        tz.coerce_client_value(
+            &DfValue::from("2023-03-12 02:30:00"),
+            &DfType::Timestamp {
+                subsecond_digits: 0,
+            },
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap_err();
+    }
+}
@@ -173,6 +173,11 @@ pub enum DfType {
     /// [PostgreSQL `uuid`](https://www.postgresql.org/docs/current/datatype-uuid.html).
     Uuid,
 
+    /// [PostgreSQL `interval`](https://www.postgresql.org/docs/current/datatype-datetime.html#DATATYPE-INTERVAL-INPUT).
+    ///
+    /// Interval field restrictions and precision are not tracked.
+    Interval,
+
     /// Enum types
     Enum {
         variants: EnumVariants,
@@ -278,6 +283,9 @@ impl DfType {
             QuotedChar => Self::TinyInt,
 
             Blob | TinyBlob | MediumBlob | LongBlob | ByteArray => Self::Blob,
+            // Spatial types are stored as opaque blobs, in MySQL's internal geometry format (a
+            // 4-byte SRID followed by the geometry's WKB representation)
+            Geometry(_) => Self::Blob,
             VarBinary(len) => Self::VarBinary(len),
             Binary(len) => Self::Binary(len.unwrap_or(1)),
 
@@ -301,8 +309,8 @@ impl DfType {
             TimestampTz => Self::TimestampTz {
                 subsecond_digits: dialect.default_subsecond_digits(),
             },
-            Interval { .. } => unsupported!("Unsupported type: INTERVAL"),
-            Uuid => unsupported!("Unsupported type: Uuid"),
+            Interval { .. } => Self::Interval,
+            Uuid => Self::Uuid,
             MacAddr => unsupported!("Unsupported type: MacAddr"),
             Inet => unsupported!("Unsupported type: Inet"),
             Citext => Self::Text(Collation::Citext),
@@ -357,6 +365,7 @@ impl DfType {
             | DfType::Time { .. }
             | DfType::Timestamp { .. }
             | DfType::TimestampTz { .. } => PgTypeCategory::DateTime,
+            DfType::Interval => PgTypeCategory::Timespan,
             DfType::MacAddr | DfType::Inet => PgTypeCategory::NetworkAddress,
             DfType::Uuid | DfType::Enum { .. } | DfType::Json | DfType::Jsonb => {
                 PgTypeCategory::UserDefined
@@ -423,6 +432,41 @@ impl DfType {
         matches!(self, DfType::Text(Collation::Citext))
     }
 
+    /// Returns the collation of this type, if it's any `text` type
+    #[inline]
+    pub fn collation(&self) -> Option<Collation> {
+        match self {
+            Self::Text(collation) | Self::VarChar(_, collation) | Self::Char(_, collation) => {
+                Some(*collation)
+            }
+            _ => None,
+        }
+    }
+
+    /// If this is any `text` type, returns the same type with the given collation. Otherwise,
+    /// returns this type unchanged.
+    #[must_use]
+    pub fn with_collation(self, collation: Collation) -> Self {
+        match self {
+            Self::Text(_) => Self::Text(collation),
+            Self::VarChar(len, _) => Self::VarChar(len, collation),
+            Self::Char(len, _) => Self::Char(len, collation),
+            ty => ty,
+        }
+    }
+
+    /// Returns `true` if this is the UUID type.
+    #[inline]
+    pub fn is_uuid(&self) -> bool {
+        matches!(self, DfType::Uuid)
+    }
+
+    /// Returns `true` if this is the INTERVAL type.
+    #[inline]
+    pub fn is_interval(&self) -> bool {
+        matches!(self, DfType::Interval)
+    }
+
     /// Returns `true` if this is the JSON type in MySQL or PostgreSQL.
     #[inline]
     pub fn is_json(&self) -> bool {
@@ -594,6 +638,7 @@ impl Arbitrary for DfType {
             Just(DfType::MacAddr),
             Just(DfType::Inet),
             Just(DfType::Uuid),
+            Just(DfType::Interval),
             (
                 any_with::<EnumVariants>((".{0, 32}", (0..=20).into())),
                 proptest::option::of(any::<PgEnumMetadata>())
@@ -639,6 +684,174 @@ pub enum PgTypeCategory {
     Unknown,
 }
 
+impl DfType {
+    /// Returns the number of bits in, and the signedness of, this type if it's any `*int` type
+    fn int_width(&self) -> Option<(u8, bool)> {
+        match self {
+            Self::TinyInt => Some((8, true)),
+            Self::UnsignedTinyInt => Some((8, false)),
+            Self::SmallInt => Some((16, true)),
+            Self::UnsignedSmallInt => Some((16, false)),
+            Self::Int => Some((32, true)),
+            Self::UnsignedInt => Some((32, false)),
+            Self::BigInt => Some((64, true)),
+            Self::UnsignedBigInt => Some((64, false)),
+            _ => None,
+        }
+    }
+
+    /// Returns the `*int` type with the given number of bits and signedness
+    fn int_of_width(bits: u8, signed: bool) -> Self {
+        match (bits, signed) {
+            (0..=8, true) => Self::TinyInt,
+            (0..=8, false) => Self::UnsignedTinyInt,
+            (9..=16, true) => Self::SmallInt,
+            (9..=16, false) => Self::UnsignedSmallInt,
+            (17..=32, true) => Self::Int,
+            (17..=32, false) => Self::UnsignedInt,
+            (_, true) => Self::BigInt,
+            (_, false) => Self::UnsignedBigInt,
+        }
+    }
+
+    /// Returns the precision and scale of a `numeric` type which can hold every value of this
+    /// type, if it's any `*int` type or a `numeric` type
+    fn numeric_precision(&self) -> Option<(u16, u8)> {
+        match self {
+            Self::Numeric { prec, scale } => Some((*prec, *scale)),
+            Self::TinyInt | Self::UnsignedTinyInt => Some((3, 0)),
+            Self::SmallInt | Self::UnsignedSmallInt => Some((5, 0)),
+            Self::Int | Self::UnsignedInt => Some((10, 0)),
+            Self::BigInt => Some((19, 0)),
+            Self::UnsignedBigInt => Some((20, 0)),
+            _ => None,
+        }
+    }
+
+    /// Returns a type which every value of both this type and `other` can be converted to without
+    /// losing information, for combining values of the two types in the same column (such as in
+    /// the branches of a `UNION`), or `None` if there's no such type.
+    ///
+    /// [`DfType::Unknown`] (the type of `NULL` literals) unifies with every type.
+    pub fn unify(&self, other: &Self) -> Option<Self> {
+        use DfType::*;
+
+        if self == other || other.is_unknown() {
+            return Some(self.clone());
+        }
+        if self.is_unknown() {
+            return Some(other.clone());
+        }
+
+        match (self, other) {
+            _ if self.is_any_int() && other.is_any_int() => {
+                let (self_bits, self_signed) = self.int_width()?;
+                let (other_bits, other_signed) = other.int_width()?;
+                if self_signed == other_signed {
+                    return Some(Self::int_of_width(self_bits.max(other_bits), self_signed));
+                }
+                // The signed type needs to be wider than the unsigned type to hold all its values
+                let unsigned_bits = if self_signed { other_bits } else { self_bits };
+                if unsigned_bits >= 64 {
+                    Some(Numeric { prec: 20, scale: 0 })
+                } else {
+                    Some(Self::int_of_width(
+                        self_bits.max(other_bits).max(unsigned_bits * 2),
+                        true,
+                    ))
+                }
+            }
+            (Numeric { .. }, _) | (_, Numeric { .. })
+                if !self.is_any_float() && !other.is_any_float() =>
+            {
+                let (self_prec, self_scale) = self.numeric_precision()?;
+                let (other_prec, other_scale) = other.numeric_precision()?;
+                let scale = self_scale.max(other_scale);
+                let integer_digits = self_prec
+                    .saturating_sub(u16::from(self_scale))
+                    .max(other_prec.saturating_sub(u16::from(other_scale)));
+                Some(Numeric {
+                    prec: integer_digits + u16::from(scale),
+                    scale,
+                })
+            }
+            _ if (self.is_any_float() || self.numeric_precision().is_some())
+                && (other.is_any_float() || other.numeric_precision().is_some()) =>
+            {
+                Some(Double)
+            }
+            _ if self.is_any_text() && other.is_any_text() => {
+                let collation = self.collation()?;
+                if other.collation() != Some(collation) {
+                    return None;
+                }
+                match (self, other) {
+                    (Char(l1, _), Char(l2, _)) => Some(Char((*l1).max(*l2), collation)),
+                    (Char(l1, _) | VarChar(l1, _), Char(l2, _) | VarChar(l2, _)) => {
+                        Some(VarChar((*l1).max(*l2), collation))
+                    }
+                    _ => Some(Text(collation)),
+                }
+            }
+            (Binary(l1), Binary(l2)) => Some(Binary((*l1).max(*l2))),
+            (Binary(l1) | VarBinary(l1), Binary(l2) | VarBinary(l2)) => {
+                Some(VarBinary((*l1).max(*l2)))
+            }
+            (Binary(_) | VarBinary(_) | Blob, Binary(_) | VarBinary(_) | Blob) => Some(Blob),
+            (Bit(l1), Bit(l2)) => Some(VarBit(Some((*l1).max(*l2)))),
+            (Bit(l1) | VarBit(Some(l1)), Bit(l2) | VarBit(Some(l2))) => {
+                Some(VarBit(Some((*l1).max(*l2))))
+            }
+            (Bit(_) | VarBit(_), Bit(_) | VarBit(_)) => Some(VarBit(None)),
+            (
+                DateTime {
+                    subsecond_digits: d1,
+                },
+                DateTime {
+                    subsecond_digits: d2,
+                },
+            ) => Some(DateTime {
+                subsecond_digits: (*d1).max(*d2),
+            }),
+            (
+                Time {
+                    subsecond_digits: d1,
+                },
+                Time {
+                    subsecond_digits: d2,
+                },
+            ) => Some(Time {
+                subsecond_digits: (*d1).max(*d2),
+            }),
+            (
+                Timestamp {
+                    subsecond_digits: d1,
+                },
+                Timestamp {
+                    subsecond_digits: d2,
+                },
+            ) => Some(Timestamp {
+                subsecond_digits: (*d1).max(*d2),
+            }),
+            (
+                TimestampTz {
+                    subsecond_digits: d1,
+                },
+                TimestampTz {
+                    subsecond_digits: d2,
+                },
+            ) => Some(TimestampTz {
+                subsecond_digits: (*d1).max(*d2),
+            }),
+            // Dates can be widened to any type which includes a date
+            (Date, DateTime { .. } | Timestamp { .. } | TimestampTz { .. }) => Some(other.clone()),
+            (DateTime { .. } | Timestamp { .. } | TimestampTz { .. }, Date) => Some(self.clone()),
+            (Json, Jsonb) | (Jsonb, Json) => Some(Json),
+            _ => None,
+        }
+    }
+}
+
 /// Test helpers.
 #[cfg(test)]
 impl DfType {
@@ -682,6 +895,7 @@ impl fmt::Display for DfType {
             | Self::Inet
             | Self::MacAddr
             | Self::Uuid
+            | Self::Interval
             | Self::Json
             | Self::Jsonb => write!(f, "{kind:?}"),
 
@@ -742,4 +956,96 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn unify_ints() {
+        assert_eq!(DfType::Int.unify(&DfType::BigInt), Some(DfType::BigInt));
+        assert_eq!(
+            DfType::TinyInt.unify(&DfType::SmallInt),
+            Some(DfType::SmallInt)
+        );
+        assert_eq!(
+            DfType::UnsignedInt.unify(&DfType::UnsignedTinyInt),
+            Some(DfType::UnsignedInt)
+        );
+        assert_eq!(
+            DfType::UnsignedInt.unify(&DfType::Int),
+            Some(DfType::BigInt)
+        );
+        assert_eq!(
+            DfType::UnsignedSmallInt.unify(&DfType::TinyInt),
+            Some(DfType::Int)
+        );
+        assert_eq!(
+            DfType::UnsignedBigInt.unify(&DfType::BigInt),
+            Some(DfType::Numeric { prec: 20, scale: 0 })
+        );
+    }
+
+    #[test]
+    fn unify_numbers() {
+        assert_eq!(DfType::Int.unify(&DfType::Double), Some(DfType::Double));
+        assert_eq!(DfType::Float.unify(&DfType::Double), Some(DfType::Double));
+        assert_eq!(
+            DfType::Numeric { prec: 5, scale: 2 }.unify(&DfType::Numeric { prec: 6, scale: 1 }),
+            Some(DfType::Numeric { prec: 7, scale: 2 })
+        );
+        assert_eq!(
+            DfType::Int.unify(&DfType::Numeric { prec: 5, scale: 2 }),
+            Some(DfType::Numeric { prec: 12, scale: 2 })
+        );
+        assert_eq!(
+            DfType::Numeric { prec: 5, scale: 2 }.unify(&DfType::Float),
+            Some(DfType::Double)
+        );
+    }
+
+    #[test]
+    fn unify_text() {
+        assert_eq!(
+            DfType::VarChar(10, Collation::Utf8).unify(&DfType::VarChar(20, Collation::Utf8)),
+            Some(DfType::VarChar(20, Collation::Utf8))
+        );
+        assert_eq!(
+            DfType::Char(30, Collation::Utf8).unify(&DfType::VarChar(20, Collation::Utf8)),
+            Some(DfType::VarChar(30, Collation::Utf8))
+        );
+        assert_eq!(
+            DfType::VarChar(10, Collation::Utf8).unify(&DfType::DEFAULT_TEXT),
+            Some(DfType::DEFAULT_TEXT)
+        );
+        assert_eq!(
+            DfType::DEFAULT_TEXT.unify(&DfType::Text(Collation::Citext)),
+            None
+        );
+    }
+
+    #[test]
+    fn unify_unknown() {
+        assert_eq!(DfType::Unknown.unify(&DfType::Int), Some(DfType::Int));
+        assert_eq!(DfType::Date.unify(&DfType::Unknown), Some(DfType::Date));
+    }
+
+    #[test]
+    fn unify_incompatible() {
+        assert_eq!(DfType::Int.unify(&DfType::DEFAULT_TEXT), None);
+        assert_eq!(DfType::Bool.unify(&DfType::Date), None);
+        assert_eq!(DfType::Uuid.unify(&DfType::Blob), None);
+    }
+
+    #[test]
+    fn uuid_types() {
+        let from_sql_type = |ty: &SqlType, dialect| DfType::from_sql_type(ty, dialect, |_| None);
+
+        assert_eq!(
+            from_sql_type(&SqlType::Uuid, Dialect::DEFAULT_POSTGRESQL).unwrap(),
+            DfType::Uuid
+        );
+        // MySQL `BINARY(16)` columns are only stored as UUIDs if they opt in to it, which is
+        // handled when creating the column
+        assert_eq!(
+            from_sql_type(&SqlType::Binary(Some(16)), Dialect::DEFAULT_MYSQL).unwrap(),
+            DfType::Binary(16)
+        );
+    }
 }
@@ -0,0 +1,445 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use common::Record;
+use readyset_client::consistency::Timestamp;
+use readyset_client::results::{SharedResults, SharedRows};
+use readyset_client::{AsOf, KeyComparison};
+use readyset_data::DfValue;
+
+/// A batch of deltas published to a reader
+struct Published {
+    /// The time the batch was published
+    at: SystemTime,
+    /// The timestamp of the reader once the batch was published
+    timestamp: Timestamp,
+    deltas: Vec<Record>,
+}
+
+struct HistoryInner {
+    /// How long to retain deltas for, or `None` if history is disabled
+    retention: Option<Duration>,
+    /// The earliest time that reads can be rewound to. This is the later of the time history was
+    /// enabled and the time of the most recent batch of deltas that was discarded.
+    available_since: SystemTime,
+    /// The timestamp of the reader as of `available_since`
+    base_timestamp: Timestamp,
+    /// Batches of deltas published to the reader, oldest first
+    published: VecDeque<Published>,
+    /// Deltas which have been applied to the reader but not yet published, and so aren't yet
+    /// visible to reads
+    pending: Vec<Record>,
+    /// The most recent timestamp set on the reader, which may not be published yet
+    timestamp: Timestamp,
+}
+
+impl HistoryInner {
+    /// Discard all deltas which are older than the retention period
+    fn prune(&mut self, now: SystemTime) {
+        let Some(retention) = self.retention else {
+            return;
+        };
+        let Some(cutoff) = now.checked_sub(retention) else {
+            return;
+        };
+        while self
+            .published
+            .front()
+            .map_or(false, |batch| batch.at < cutoff)
+        {
+            #[allow(clippy::unwrap_used)] // just checked there's a front element
+            let batch = self.published.pop_front().unwrap();
+            self.available_since = self.available_since.max(batch.at);
+            self.base_timestamp = batch.timestamp;
+        }
+    }
+
+    /// Returns the timestamp of the most recently published state of the reader
+    fn published_timestamp(&self) -> &Timestamp {
+        self.published
+            .back()
+            .map_or(&self.base_timestamp, |batch| &batch.timestamp)
+    }
+
+    /// Returns the number of batches, newest first, which must be undone to rewind the reader to
+    /// `as_of`, or `None` if history doesn't reach back as far as `as_of`
+    fn batches_since(&self, as_of: &AsOf) -> Option<usize> {
+        match as_of {
+            AsOf::Time(time) => (*time >= self.available_since).then(|| {
+                self.published
+                    .iter()
+                    .rev()
+                    .take_while(|batch| batch.at > *time)
+                    .count()
+            }),
+            AsOf::Timestamp(timestamp) => {
+                // If the reader already satisfied the timestamp when history starts, we can't tell
+                // how many writes after the timestamp it had already seen
+                if self.base_timestamp.satisfies(timestamp) {
+                    return None;
+                }
+                // Rewind to the first state of the reader which satisfied the timestamp
+                let first = self
+                    .published
+                    .iter()
+                    .position(|batch| batch.timestamp.satisfies(timestamp))?;
+                Some(self.published.len() - first - 1)
+            }
+        }
+    }
+}
+
+/// A short window of the deltas applied to a single shard of a reader, used to answer reads
+/// against the state of the reader as of some time or [`Timestamp`] in the past. Shared between the
+/// reader's [`SingleReadHandle`](super::SingleReadHandle)s (which rewind reads using the history)
+/// and its [`WriteHandle`](super::WriteHandle) (which records deltas as they're applied).
+pub struct ReadHistory {
+    /// Whether history is enabled, so that readers without history can skip taking the lock on
+    /// `inner` for every write
+    enabled: AtomicBool,
+    inner: Mutex<HistoryInner>,
+}
+
+impl ReadHistory {
+    pub(crate) fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            inner: Mutex::new(HistoryInner {
+                retention: None,
+                available_since: SystemTime::now(),
+                base_timestamp: Timestamp::default(),
+                published: VecDeque::new(),
+                pending: Vec::new(),
+                timestamp: Timestamp::default(),
+            }),
+        }
+    }
+
+    /// Set how long to retain deltas for, or disable history entirely if `retention` is `None`
+    pub(crate) fn set_retention(&self, retention: Option<Duration>) {
+        let now = SystemTime::now();
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut inner = self.inner.lock().unwrap();
+        if retention.is_none() {
+            inner.published.clear();
+            inner.pending.clear();
+        } else if inner.retention.is_none() {
+            // History is only complete from the point it's enabled
+            inner.available_since = now;
+            inner.base_timestamp = inner.timestamp.clone();
+        }
+        inner.retention = retention;
+        inner.prune(now);
+        self.enabled.store(retention.is_some(), Ordering::Relaxed);
+    }
+
+    /// Returns how long deltas are retained for, or `None` if history is disabled
+    pub fn retention(&self) -> Option<Duration> {
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        self.inner.lock().unwrap().retention
+    }
+
+    /// Record a batch of deltas which is about to be applied to the reader. The deltas are only
+    /// considered when rewinding reads once they're [published](Self::publish).
+    ///
+    /// This must be called with *all* regular (non-replay) deltas that reach the reader, including
+    /// those for keys which are currently missing, since those keys might be filled before a read
+    /// is rewound past the deltas.
+    pub(crate) fn record(&self, records: &[Record]) {
+        if records.is_empty() || !self.enabled.load(Ordering::Relaxed) {
+            return;
+        }
+
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        self.inner
+            .lock()
+            .unwrap()
+            .pending
+            .extend_from_slice(records);
+    }
+
+    /// Record a new timestamp set on the reader, to take effect on the next
+    /// [publish](Self::publish).
+    ///
+    /// Unlike deltas, timestamps are tracked even while history is disabled, so that the timestamp
+    /// of the reader is known once history is enabled.
+    pub(crate) fn set_timestamp(&self, timestamp: Timestamp) {
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut inner = self.inner.lock().unwrap();
+        inner.timestamp = timestamp;
+    }
+
+    /// Mark all recorded deltas and timestamps as visible to reads as of now, which must be called
+    /// whenever the reader's state is published
+    pub(crate) fn publish(&self) {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let now = SystemTime::now();
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut inner = self.inner.lock().unwrap();
+        if !inner.pending.is_empty() || inner.timestamp != *inner.published_timestamp() {
+            let batch = Published {
+                at: now,
+                timestamp: inner.timestamp.clone(),
+                deltas: std::mem::take(&mut inner.pending),
+            };
+            inner.published.push_back(batch);
+        }
+        inner.prune(now);
+    }
+
+    /// Rewind `results`, the result of looking up `keys` in the current state of a reader whose
+    /// key is formed from `key_columns`, to the state of the reader as of `as_of`, by undoing all
+    /// the deltas published to the reader after that point.
+    ///
+    /// When rewinding to a [`Timestamp`], the results reflect the state of the reader at the point
+    /// it first satisfied that timestamp, so rewinding several readers to the same timestamp gives
+    /// results which are consistent with each other.
+    ///
+    /// Returns `None` if history is disabled, or doesn't reach back as far as `as_of`.
+    pub fn rewind(
+        &self,
+        results: SharedResults,
+        keys: &[KeyComparison],
+        key_columns: &[usize],
+        as_of: &AsOf,
+    ) -> Option<SharedResults> {
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let inner = self.inner.lock().unwrap();
+        inner.retention?;
+        let undo = inner.batches_since(as_of)?;
+
+        // The net number of copies of each row matching `keys` added to the reader since `as_of`
+        let mut net: HashMap<&[DfValue], isize> = HashMap::new();
+        for batch in inner.published.iter().rev().take(undo) {
+            for record in &batch.deltas {
+                let matches = keys
+                    .iter()
+                    .any(|key| key.contains(key_columns.iter().map(|&col| &record[col])));
+                if matches {
+                    *net.entry(record.rec()).or_default() +=
+                        if record.is_positive() { 1 } else { -1 };
+                }
+            }
+        }
+        if net.is_empty() {
+            return Some(results);
+        }
+
+        // Remove rows which were added since `as_of`, leaving the remaining rows in each set in the
+        // same order
+        let mut rewound = SharedResults::with_capacity(results.len());
+        for rows in results {
+            let added_since = |row: &[DfValue]| net.get(row).map_or(false, |&n| n > 0);
+            if rows.iter().any(|row| added_since(row)) {
+                let kept = rows
+                    .iter()
+                    .filter(|row| match net.get_mut(&row[..]) {
+                        Some(n) if *n > 0 => {
+                            *n -= 1;
+                            false
+                        }
+                        _ => true,
+                    })
+                    .cloned()
+                    .collect();
+                rewound.push(SharedRows::new(kept));
+            } else {
+                rewound.push(rows);
+            }
+        }
+
+        // Restore rows which were removed since `as_of`, each in its own set so that every set
+        // stays sorted
+        for (row, n) in net {
+            if n < 0 {
+                for _ in 0..n.unsigned_abs() {
+                    rewound.push(SharedRows::new(std::iter::once(row.into()).collect()));
+                }
+            }
+        }
+
+        Some(rewound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use readyset_client::internal::LocalNodeIndex;
+    use readyset_client::results::Row;
+    use vec1::vec1;
+
+    use super::*;
+
+    fn rows(rows: &[[i32; 2]]) -> SharedRows {
+        SharedRows::new(
+            rows.iter()
+                .map(|row| row.iter().map(|v| DfValue::from(*v)).collect::<Row>())
+                .collect(),
+        )
+    }
+
+    fn flatten(results: SharedResults) -> Vec<Vec<DfValue>> {
+        let mut rows = results
+            .iter()
+            .flat_map(|rows| rows.iter().map(|row| row.to_vec()))
+            .collect::<Vec<_>>();
+        rows.sort();
+        rows
+    }
+
+    fn key(k: i32) -> Vec<KeyComparison> {
+        vec![KeyComparison::Equal(vec1![DfValue::from(k)])]
+    }
+
+    fn record(positive: bool, row: [i32; 2]) -> Record {
+        (row.iter().map(|v| DfValue::from(*v)).collect(), positive).into()
+    }
+
+    fn timestamp(t: u64) -> Timestamp {
+        Timestamp {
+            map: [(LocalNodeIndex::make(0), t)].into_iter().collect(),
+        }
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let history = ReadHistory::new();
+        history.record(&[record(true, [1, 1])]);
+        history.publish();
+        assert!(history
+            .rewind(
+                Default::default(),
+                &key(1),
+                &[0],
+                &AsOf::Time(SystemTime::now() + Duration::from_secs(1))
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn rewinds_deltas() {
+        let history = ReadHistory::new();
+        history.set_retention(Some(Duration::from_secs(60)));
+        let start = SystemTime::now();
+
+        history.record(&[
+            record(true, [1, 2]),
+            record(false, [1, 1]),
+            record(true, [2, 2]),
+        ]);
+        history.publish();
+        let current = SharedResults::from_iter([rows(&[[1, 2], [1, 3]])]);
+
+        assert_eq!(
+            flatten(
+                history
+                    .rewind(current.clone(), &key(1), &[0], &AsOf::Time(start))
+                    .unwrap()
+            ),
+            flatten(SharedResults::from_iter([rows(&[[1, 1], [1, 3]])]))
+        );
+
+        // Rewinding to after the deltas leaves the results unchanged
+        assert_eq!(
+            flatten(
+                history
+                    .rewind(
+                        current.clone(),
+                        &key(1),
+                        &[0],
+                        &AsOf::Time(SystemTime::now() + Duration::from_secs(1))
+                    )
+                    .unwrap()
+            ),
+            flatten(current.clone())
+        );
+
+        // History doesn't reach back before it was enabled
+        assert!(history
+            .rewind(
+                current,
+                &key(1),
+                &[0],
+                &AsOf::Time(start - Duration::from_secs(1))
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn ignores_unpublished_deltas() {
+        let history = ReadHistory::new();
+        history.set_retention(Some(Duration::from_secs(60)));
+        let start = SystemTime::now();
+        history.record(&[record(false, [1, 1])]);
+
+        let current = SharedResults::from_iter([rows(&[[1, 1]])]);
+        assert_eq!(
+            flatten(
+                history
+                    .rewind(current.clone(), &key(1), &[0], &AsOf::Time(start))
+                    .unwrap()
+            ),
+            flatten(current)
+        );
+    }
+
+    #[test]
+    fn rewinds_to_timestamp() {
+        let history = ReadHistory::new();
+        history.set_timestamp(timestamp(1));
+        history.publish();
+        history.set_retention(Some(Duration::from_secs(60)));
+
+        history.record(&[record(true, [1, 2])]);
+        history.publish();
+        history.set_timestamp(timestamp(2));
+        history.publish();
+        history.record(&[record(true, [1, 3])]);
+        history.publish();
+        history.set_timestamp(timestamp(3));
+        history.publish();
+
+        let current = SharedResults::from_iter([rows(&[[1, 1], [1, 2], [1, 3]])]);
+        assert_eq!(
+            flatten(
+                history
+                    .rewind(
+                        current.clone(),
+                        &key(1),
+                        &[0],
+                        &AsOf::Timestamp(timestamp(2))
+                    )
+                    .unwrap()
+            ),
+            flatten(SharedResults::from_iter([rows(&[[1, 1], [1, 2]])]))
+        );
+
+        // The reader already satisfied the timestamp when history was enabled
+        assert!(history
+            .rewind(current, &key(1), &[0], &AsOf::Timestamp(timestamp(1)))
+            .is_none());
+    }
+
+    #[test]
+    fn prunes_old_deltas() {
+        let history = ReadHistory::new();
+        history.set_retention(Some(Duration::ZERO));
+        let start = SystemTime::now();
+        history.record(&[record(true, [1, 1])]);
+        history.publish();
+        history.record(&[record(true, [1, 2])]);
+        history.publish();
+
+        assert!(history
+            .rewind(Default::default(), &key(1), &[0], &AsOf::Time(start))
+            .is_none());
+
+        history.set_retention(None);
+        assert_eq!(history.retention(), None);
+    }
+}
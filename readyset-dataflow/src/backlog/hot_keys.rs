@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use readyset_client::debug::info::{HotKey, ReaderHotKeys};
+use readyset_client::KeyComparison;
+
+/// The maximum number of keys tracked by a [`HotKeys`] sketch
+const HOT_KEYS_CAPACITY: usize = 128;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct KeyCounter {
+    reads: u64,
+    overestimate: u64,
+    misses: u64,
+}
+
+#[derive(Debug, Default)]
+struct HotKeysInner {
+    total_reads: u64,
+    counters: HashMap<KeyComparison, KeyCounter>,
+}
+
+/// A space-bounded sketch of the keys read most often from a single shard of a reader, using the
+/// "Space-Saving" algorithm of Metwally et al., "Efficient Computation of Frequent and Top-k
+/// Elements in Data Streams" (ICDT 2005).
+///
+/// At most [`HOT_KEYS_CAPACITY`] keys are tracked at once. Once the sketch is full, a read of a
+/// new key replaces the key with the fewest reads, and inherits its count of reads. Any key read
+/// more than `total_reads / HOT_KEYS_CAPACITY` times is guaranteed to be tracked.
+#[derive(Debug, Default)]
+pub(super) struct HotKeys {
+    inner: Mutex<HotKeysInner>,
+}
+
+impl HotKeys {
+    /// Record a read of `keys`, of which the keys in `missed` were not present in the reader.
+    ///
+    /// Reads which find the sketch locked by another read aren't recorded, so that tracking hot
+    /// keys never blocks reads.
+    pub(super) fn record<'a, I>(&self, keys: &[KeyComparison], missed: I)
+    where
+        I: IntoIterator<Item = &'a KeyComparison>,
+    {
+        let Ok(mut inner) = self.inner.try_lock() else {
+            return;
+        };
+
+        for key in keys {
+            inner.total_reads += 1;
+            if let Some(counter) = inner.counters.get_mut(key) {
+                counter.reads += 1;
+                continue;
+            }
+
+            let counter = if inner.counters.len() < HOT_KEYS_CAPACITY {
+                KeyCounter {
+                    reads: 1,
+                    ..Default::default()
+                }
+            } else {
+                let Some(min_key) = inner
+                    .counters
+                    .iter()
+                    .min_by_key(|(_, counter)| counter.reads)
+                    .map(|(key, _)| key.clone())
+                else {
+                    continue;
+                };
+                let min_reads = inner
+                    .counters
+                    .remove(&min_key)
+                    .map_or(0, |counter| counter.reads);
+                KeyCounter {
+                    reads: min_reads + 1,
+                    overestimate: min_reads,
+                    misses: 0,
+                }
+            };
+            inner.counters.insert(key.clone(), counter);
+        }
+
+        for key in missed {
+            if let Some(counter) = inner.counters.get_mut(key) {
+                counter.misses += 1;
+            }
+        }
+    }
+
+    /// Returns the keys currently tracked by the sketch, most frequent first
+    pub(super) fn snapshot(&self) -> ReaderHotKeys {
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let inner = self.inner.lock().unwrap();
+        let mut keys = inner
+            .counters
+            .iter()
+            .map(|(key, counter)| HotKey {
+                key: key.clone(),
+                reads: counter.reads,
+                overestimate: counter.overestimate,
+                misses: counter.misses,
+            })
+            .collect::<Vec<_>>();
+        keys.sort_by(|a, b| b.reads.cmp(&a.reads));
+        ReaderHotKeys {
+            total_reads: inner.total_reads,
+            keys,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use readyset_data::DfValue;
+    use vec1::vec1;
+
+    use super::*;
+
+    fn key(k: i32) -> KeyComparison {
+        KeyComparison::Equal(vec1![DfValue::from(k)])
+    }
+
+    #[test]
+    fn counts_reads_and_misses() {
+        let hot_keys = HotKeys::default();
+        hot_keys.record(&[key(1), key(2)], &[key(2)]);
+        hot_keys.record(&[key(1)], &[]);
+
+        let snapshot = hot_keys.snapshot();
+        assert_eq!(snapshot.total_reads, 3);
+        assert_eq!(
+            snapshot.keys,
+            vec![
+                HotKey {
+                    key: key(1),
+                    reads: 2,
+                    overestimate: 0,
+                    misses: 0,
+                },
+                HotKey {
+                    key: key(2),
+                    reads: 1,
+                    overestimate: 0,
+                    misses: 1,
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn bounded_and_keeps_heavy_hitters() {
+        let hot_keys = HotKeys::default();
+        for i in 0..10_000 {
+            // Key 0 is read as often as all the other keys combined
+            hot_keys.record(&[key(0), key(i)], &[]);
+        }
+
+        let snapshot = hot_keys.snapshot();
+        assert_eq!(snapshot.total_reads, 20_000);
+        assert_eq!(snapshot.keys.len(), HOT_KEYS_CAPACITY);
+        assert_eq!(snapshot.keys[0].key, key(0));
+        assert!(snapshot.keys[0].reads >= 10_000);
+    }
+}
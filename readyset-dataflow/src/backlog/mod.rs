@@ -1,19 +1,25 @@
 use std::borrow::Cow;
 use std::cmp::Ordering;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering as AtomicOrdering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
 
 use ahash::RandomState;
 use common::SizeOf;
 use dataflow_expression::{PostLookup, ReaderProcessing};
+use metrics::{register_counter, register_histogram, Counter, Histogram};
 use nom_sql::Relation;
 use reader_map::{EvictionQuantity, EvictionStrategy};
 use readyset_client::consistency::Timestamp;
-use readyset_client::results::SharedResults;
-use readyset_client::KeyComparison;
+use readyset_client::debug::info::{ReaderHotKeys, ReaderMetrics};
+use readyset_client::metrics::recorded;
+use readyset_client::results::{SharedResults, SharedRows};
+use readyset_client::{AsOf, KeyComparison, ViewRateLimit, ViewResultLimit, ViewStaleReads};
 use readyset_data::Bound;
 use vec1::Vec1;
 
 pub use self::multir::LookupError;
+use crate::domain::DomainLoad;
 use crate::prelude::*;
 
 /// The kind of reader update notification, currently the eviction epoch of the writer
@@ -23,8 +29,20 @@ pub type ReaderUpdatedNotifier = tokio::sync::broadcast::Receiver<ReaderNotifica
 /// The type we can send reader update notifications
 pub(crate) type ReaderUpdatedSender = tokio::sync::broadcast::Sender<ReaderNotification>;
 
-pub(crate) trait Trigger =
-    Fn(&mut dyn Iterator<Item = KeyComparison>, Relation) -> bool + 'static + Send + Sync;
+pub(crate) trait Trigger = Fn(&mut dyn Iterator<Item = KeyComparison>, Relation, ReplayRequest) -> bool
+    + 'static
+    + Send
+    + Sync;
+
+/// What a [`Trigger`] asks the domain of a reader to do with the replays of the keys it's given
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReplayRequest {
+    /// Replay the keys into the reader
+    Trigger,
+    /// Stop tracking the replays of the keys, which were triggered earlier but are no longer
+    /// being waited for, so that the next request for any of them triggers a new replay
+    Release,
+}
 
 /// Allocate a new end-user facing result table.
 ///
@@ -139,6 +157,15 @@ fn new_inner(
 
     let (notifier, receiver) = tokio::sync::broadcast::channel(1);
     let partial = trigger.is_some();
+    let stats = Arc::new(ReaderStats::default());
+    let throttle = Arc::new(ReadThrottle::new());
+    let history = Arc::new(ReadHistory::new());
+    let stale = Arc::new(StaleValues::new());
+    let replays = Arc::new(InFlightReplays::default());
+    let result_limit = Arc::new(ResultLimit::new());
+    let upquery_timeout = Arc::new(UpqueryTimeout::new());
+    let waiters = Arc::new(ReplayWaiters::default());
+    let secondary = Arc::new(SecondaryIndices::new(pre_processing.clone()));
     let w = WriteHandle {
         partial,
         replay_done: partial,
@@ -149,6 +176,16 @@ fn new_inner(
         mem_size: 0,
         notifier,
         eviction_epoch: 0,
+        stats: Arc::clone(&stats),
+        throttle: Arc::clone(&throttle),
+        history: Arc::clone(&history),
+        stale: Arc::clone(&stale),
+        replays: Arc::clone(&replays),
+        result_limit: Arc::clone(&result_limit),
+        upquery_timeout: Arc::clone(&upquery_timeout),
+        waiters: Arc::clone(&waiters),
+        secondary: Arc::clone(&secondary),
+        negative: NegativeEntries::default(),
     };
 
     let r = SingleReadHandle {
@@ -158,13 +195,189 @@ fn new_inner(
         post_lookup: post_processing,
         receiver,
         eviction_epoch: 0,
+        stats,
+        throttle,
+        history,
+        stale,
+        replays,
+        result_limit,
+        upquery_timeout,
+        waiters,
+        secondary,
+        unavailable: Arc::new(AtomicBool::new(false)),
     };
 
     (r, w)
 }
 
+mod history;
+mod hot_keys;
 mod multir;
 mod multiw;
+mod negative;
+mod replays;
+mod result_limit;
+mod secondary;
+mod stale;
+mod throttle;
+mod upquery_timeout;
+mod waiters;
+
+pub use self::history::ReadHistory;
+use self::hot_keys::HotKeys;
+use self::negative::NegativeEntries;
+pub(crate) use self::negative::NegativeEntryLimits;
+use self::replays::InFlightReplays;
+use self::result_limit::ResultLimit;
+pub use self::secondary::SecondaryIndices;
+pub use self::stale::StaleValues;
+pub use self::throttle::{MissPermit, ReadThrottle};
+use self::upquery_timeout::UpqueryTimeout;
+pub use self::waiters::ReplayWaitGuard;
+use self::waiters::ReplayWaiters;
+
+/// Handles to the metrics recorded for a single view in the metrics recorder, tagged with the
+/// view's name
+struct ViewCounters {
+    hits: Counter,
+    misses: Counter,
+    evictions: Counter,
+    coalesced_replays: Counter,
+    replay_latency: Histogram,
+}
+
+impl ViewCounters {
+    fn new(name: &Relation) -> Self {
+        let name = name.display_unquoted().to_string();
+        Self {
+            hits: register_counter!(recorded::READER_VIEW_HITS, "cache_name" => name.clone()),
+            misses: register_counter!(recorded::READER_VIEW_MISSES, "cache_name" => name.clone()),
+            evictions: register_counter!(
+                recorded::READER_VIEW_EVICTIONS,
+                "cache_name" => name.clone()
+            ),
+            coalesced_replays: register_counter!(
+                recorded::READER_VIEW_COALESCED_REPLAYS,
+                "cache_name" => name.clone()
+            ),
+            replay_latency: register_histogram!(
+                recorded::READER_VIEW_REPLAY_LATENCY,
+                "cache_name" => name
+            ),
+        }
+    }
+}
+
+/// Usage counters for a single shard of a reader, shared between its [`SingleReadHandle`]s (which
+/// record hits and misses) and its [`WriteHandle`] (which records evictions and replays).
+#[derive(Default)]
+pub struct ReaderStats {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    coalesced_replays: AtomicU64,
+    replays: AtomicU64,
+    total_replay_time_us: AtomicU64,
+    replayed_rows: AtomicU64,
+    replayed_bytes: AtomicU64,
+    /// The keys read most often from the reader
+    hot_keys: HotKeys,
+    /// Per-view metrics, registered by the first read against the reader since that's the first
+    /// place the view's name is known. Evictions and replays can only happen after a read has
+    /// missed, so nothing is lost by not recording them before then.
+    counters: OnceLock<ViewCounters>,
+}
+
+impl ReaderStats {
+    /// Record that a read against the view with the given name was served entirely from the
+    /// reader's state
+    pub fn record_hit(&self, name: &Relation) {
+        self.hits.fetch_add(1, AtomicOrdering::Relaxed);
+        self.counters
+            .get_or_init(|| ViewCounters::new(name))
+            .hits
+            .increment(1);
+    }
+
+    /// Record that a read against the view with the given name missed on at least one key
+    pub fn record_miss(&self, name: &Relation) {
+        self.misses.fetch_add(1, AtomicOrdering::Relaxed);
+        self.counters
+            .get_or_init(|| ViewCounters::new(name))
+            .misses
+            .increment(1);
+    }
+
+    /// Record a read of `keys` from the reader, of which the keys in `missed` were not present in
+    /// its state
+    pub fn record_key_reads<'a, I>(&self, keys: &[KeyComparison], missed: I)
+    where
+        I: IntoIterator<Item = &'a KeyComparison>,
+    {
+        self.hot_keys.record(keys, missed)
+    }
+
+    fn record_eviction(&self) {
+        self.evictions.fetch_add(1, AtomicOrdering::Relaxed);
+        if let Some(counters) = self.counters.get() {
+            counters.evictions.increment(1);
+        }
+    }
+
+    /// Record that misses on `n` keys in the view with the given name were coalesced into replays
+    /// which were already in flight
+    fn record_coalesced_replays(&self, name: &Relation, n: usize) {
+        self.coalesced_replays
+            .fetch_add(n as u64, AtomicOrdering::Relaxed);
+        self.counters
+            .get_or_init(|| ViewCounters::new(name))
+            .coalesced_replays
+            .increment(n as u64);
+    }
+
+    /// Record that a key was filled by a replay `latency` after the reader missed on it
+    pub(crate) fn record_replay(&self, latency: Duration) {
+        let latency_us = latency.as_micros() as u64;
+        self.replays.fetch_add(1, AtomicOrdering::Relaxed);
+        self.total_replay_time_us
+            .fetch_add(latency_us, AtomicOrdering::Relaxed);
+        if let Some(counters) = self.counters.get() {
+            counters.replay_latency.record(latency_us as f64);
+        }
+    }
+
+    /// Record that the given rows were delivered to the reader by a replay, whether or not they
+    /// ended up being added to its state
+    pub(crate) fn record_replayed_rows(&self, rows: &[Record]) {
+        self.replayed_rows
+            .fetch_add(rows.len() as u64, AtomicOrdering::Relaxed);
+        self.replayed_bytes.fetch_add(
+            rows.iter().map(|r| r.deep_size_of()).sum::<u64>(),
+            AtomicOrdering::Relaxed,
+        );
+    }
+
+    /// Take a snapshot of the current values of all the counters
+    pub fn metrics(&self) -> ReaderMetrics {
+        ReaderMetrics {
+            hits: self.hits.load(AtomicOrdering::Relaxed),
+            misses: self.misses.load(AtomicOrdering::Relaxed),
+            evictions: self.evictions.load(AtomicOrdering::Relaxed),
+            coalesced_replays: self.coalesced_replays.load(AtomicOrdering::Relaxed),
+            replays: self.replays.load(AtomicOrdering::Relaxed),
+            total_replay_time: Duration::from_micros(
+                self.total_replay_time_us.load(AtomicOrdering::Relaxed),
+            ),
+            replayed_rows: self.replayed_rows.load(AtomicOrdering::Relaxed),
+            replayed_bytes: self.replayed_bytes.load(AtomicOrdering::Relaxed),
+        }
+    }
+
+    /// Take a snapshot of the keys read most often from the reader
+    pub fn hot_keys(&self) -> ReaderHotKeys {
+        self.hot_keys.snapshot()
+    }
+}
 
 fn key_to_single(k: Key) -> Cow<DfValue> {
     assert_eq!(k.len(), 1);
@@ -187,6 +400,27 @@ pub(crate) struct WriteHandle {
     notifier: ReaderUpdatedSender,
     /// How many eviction rounds this handle had
     eviction_epoch: usize,
+    /// Usage counters for the reader, shared with its read handles
+    stats: Arc<ReaderStats>,
+    /// Rate limits for reads against the reader, shared with its read handles
+    throttle: Arc<ReadThrottle>,
+    /// Recent deltas applied to the reader, shared with its read handles
+    history: Arc<ReadHistory>,
+    /// Last-known values of keys evicted from the reader, shared with its read handles
+    stale: Arc<StaleValues>,
+    /// Keys with replays in flight, shared with its read handles
+    replays: Arc<InFlightReplays>,
+    /// Limits on the size of results of reads against the reader, shared with its read handles
+    result_limit: Arc<ResultLimit>,
+    /// How long reads against the reader wait for misses to be filled, shared with its read
+    /// handles
+    upquery_timeout: Arc<UpqueryTimeout>,
+    /// Reads waiting for keys which missed to be filled, shared with its read handles
+    waiters: Arc<ReplayWaiters>,
+    /// Indices on columns of the reader other than its key, shared with its read handles
+    secondary: Arc<SecondaryIndices>,
+    /// Keys which were filled with no rows, tracked so that they can be expired and bounded
+    negative: NegativeEntries,
 }
 
 type Key<'a> = Cow<'a, [DfValue]>;
@@ -235,6 +469,9 @@ impl<'a> MutWriteHandleEntry<'a> {
             .read()
             .get(&self.key)
             .map(|rs| {
+                if self.handle.stale.is_enabled() {
+                    self.handle.stale.retain(self.key.to_vec(), rs.clone());
+                }
                 rs.iter().map(SizeOf::deep_size_of).sum::<u64>() as usize
                     + self.key_value_size(&self.key)
             })
@@ -290,6 +527,8 @@ impl WriteHandle {
 
     pub(crate) fn swap(&mut self) {
         self.handle.refresh();
+        self.history.publish();
+        self.secondary.publish();
     }
 
     pub(crate) fn len(&self) -> usize {
@@ -303,7 +542,13 @@ impl WriteHandle {
     where
         I: IntoIterator<Item = Record>,
     {
-        let mem_delta = self.handle.add(&self.index.columns, self.cols, rs);
+        let mem_delta = if self.secondary.is_enabled() {
+            let rs = rs.into_iter().collect::<Vec<_>>();
+            self.secondary.record(&rs);
+            self.handle.add(&self.index.columns, self.cols, rs)
+        } else {
+            self.handle.add(&self.index.columns, self.cols, rs)
+        };
         match mem_delta.cmp(&0) {
             Ordering::Greater => {
                 self.mem_size += mem_delta as usize;
@@ -315,6 +560,7 @@ impl WriteHandle {
         }
     }
     pub(crate) fn set_timestamp(&mut self, t: Timestamp) {
+        self.history.set_timestamp(t.clone());
         self.handle.set_timestamp(t);
     }
 
@@ -332,7 +578,7 @@ impl WriteHandle {
                 self.mem_size
             );
 
-            self.handle.evict(request)
+            self.handle.evict(request, &self.stale)
         } else {
             (0, None)
         };
@@ -388,6 +634,7 @@ impl WriteHandle {
 
     pub(crate) fn mark_filled(&mut self, key: KeyComparison) -> ReadySetResult<()> {
         invariant_eq!(key.len(), self.index.len());
+        self.stale.discard(&key);
 
         #[allow(clippy::unreachable)] // Documented invariant.
         let range = match (self.index.index_type, &key) {
@@ -418,6 +665,8 @@ impl WriteHandle {
     /// Increment the eviction epoch, and notify readers
     pub(crate) fn notify_readers_of_eviction(&mut self) -> ReadySetResult<()> {
         self.eviction_epoch += 1;
+        self.stats.record_eviction();
+        self.replays.release_all();
         self.notify_readers()
     }
 
@@ -434,6 +683,141 @@ impl WriteHandle {
         self.index.index_type
     }
 
+    /// Record that the replays of `keys` have completed, so that the next miss on any of them
+    /// triggers a new replay rather than waiting on this one
+    pub(crate) fn finish_replays<'a, I>(&self, keys: I)
+    where
+        I: IntoIterator<Item = &'a KeyComparison>,
+    {
+        self.replays.release(keys)
+    }
+
+    /// Returns whether any reads are still waiting for `key` to be filled
+    pub(crate) fn has_waiters(&self, key: &KeyComparison) -> bool {
+        self.waiters.is_waiting(key)
+    }
+
+    /// Replace the limits on the negative entries (keys filled with no rows) cached in the reader
+    pub(crate) fn set_negative_entry_limits(&mut self, limits: NegativeEntryLimits) {
+        self.negative.set_limits(limits)
+    }
+
+    /// Track any of `keys`, which have just been filled by replays, that were filled with no rows
+    /// as negative entries. Must be called after the fills have been [swapped](Self::swap) in.
+    pub(crate) fn track_negative_entries<'a, I>(&mut self, keys: I)
+    where
+        I: IntoIterator<Item = &'a KeyComparison>,
+    {
+        if !self.negative.is_tracking() {
+            return;
+        }
+        let now = Instant::now();
+        let reader = self.handle.read();
+        for key in keys {
+            let Some(key) = key.equal() else {
+                continue;
+            };
+            if reader.get(key.as_vec()).map_or(false, |rs| rs.is_empty()) {
+                self.negative.track(key.to_vec(), now);
+            }
+        }
+    }
+
+    /// Evict the negative entries which have outlived their TTL or exceed the maximum number of
+    /// negative entries, unless rows have since been written to them. Returns the number of keys
+    /// evicted; if any were, the caller must [swap](Self::swap) and notify readers of the eviction.
+    pub(crate) fn evict_negative_entries(&mut self) -> usize {
+        let expired = self.negative.pop_expired(Instant::now());
+        if expired.is_empty() {
+            return 0;
+        }
+        let reader = self.handle.read();
+        let still_empty = expired
+            .into_iter()
+            .filter(|key| reader.get(key).map_or(false, |rs| rs.is_empty()))
+            .collect::<Vec<_>>();
+        for key in &still_empty {
+            self.mut_with_key(key.as_slice()).mark_hole();
+        }
+        still_empty.len()
+    }
+
+    /// Returns the time at which the next negative entry in the reader outlives its TTL, if any
+    pub(crate) fn next_negative_entry_expiry(&self) -> Option<Instant> {
+        self.negative.next_expiry()
+    }
+
+    /// Usage counters for the reader
+    pub(crate) fn stats(&self) -> &ReaderStats {
+        &self.stats
+    }
+
+    /// Replace the rate limits enforced for reads against the reader
+    pub(crate) fn set_rate_limit(&self, limit: ViewRateLimit) {
+        self.throttle.set_limit(limit)
+    }
+
+    /// Set how long deltas applied to the reader are retained for as-of reads, or disable as-of
+    /// reads if `retention` is `None`
+    pub(crate) fn set_history_retention(&self, retention: Option<Duration>) {
+        self.history.set_retention(retention)
+    }
+
+    /// Configure whether reads against the reader may be answered with possibly-stale values
+    pub(crate) fn set_stale_reads(&self, stale_reads: ViewStaleReads) {
+        self.stale.set_config(stale_reads)
+    }
+
+    /// Replace the limits on the size of results of reads against the reader
+    pub(crate) fn set_result_limit(&self, limit: ViewResultLimit) {
+        self.result_limit.set(limit)
+    }
+
+    /// Set how long reads against the reader wait for misses to be filled by upqueries, or revert
+    /// to the server's default if `timeout` is `None`
+    pub(crate) fn set_upquery_timeout(&self, timeout: Option<Duration>) {
+        self.upquery_timeout.set(timeout)
+    }
+
+    /// Share the worker-wide flag tracking whether replication from the upstream database is
+    /// healthy with the reader's read handles
+    pub(crate) fn share_replication_health(&self, healthy: Arc<AtomicBool>) {
+        self.stale.share_replication_health(healthy)
+    }
+
+    /// Share the load of the domain running the reader with the reader's read handles, so that
+    /// reads which miss can be shed while the domain is overloaded
+    pub(crate) fn share_domain_load(&self, load: Arc<DomainLoad>) {
+        self.throttle.share_domain_load(load)
+    }
+
+    /// Replace the secondary indices on the reader with indices on each of the given sets of
+    /// columns, built from the reader's current contents. Only supported for fully materialized
+    /// readers, since the rows of a partially materialized reader are incomplete.
+    pub(crate) fn set_secondary_indices(&mut self, columns: Vec<Vec<usize>>) -> ReadySetResult<()> {
+        if self.partial && !columns.is_empty() {
+            unsupported!("Secondary indices are only supported on fully materialized readers");
+        }
+        if let Some(column) = columns.iter().flatten().find(|c| **c >= self.cols) {
+            return Err(bad_request_err(format!(
+                "Cannot index column {column} of a reader with {} columns",
+                self.cols
+            )));
+        }
+
+        // Publish everything applied so far, so the indices can be built from the published rows
+        self.swap();
+        self.secondary
+            .set_indices(columns, self.handle.read().rows());
+        Ok(())
+    }
+
+    /// Record a batch of regular (non-replay) deltas which is about to be applied to the reader,
+    /// to be made visible to as-of reads on the next [`swap`](Self::swap)
+    pub(crate) fn record_history(&self, records: &[Record]) {
+        self.history.record(records)
+    }
+
     pub(crate) fn set_replay_done(&mut self, replay_done: bool) {
         debug_assert!(!self.is_partial());
         self.replay_done = replay_done;
@@ -470,6 +854,28 @@ pub struct SingleReadHandle {
     receiver: ReaderUpdatedNotifier,
     /// Caches the eviction epoch of the associated [`WriteHandle`]
     eviction_epoch: usize,
+    /// Usage counters for the reader, shared with its [`WriteHandle`]
+    stats: Arc<ReaderStats>,
+    /// Rate limits for reads against the reader, shared with its [`WriteHandle`]
+    throttle: Arc<ReadThrottle>,
+    /// Recent deltas applied to the reader, shared with its [`WriteHandle`]
+    history: Arc<ReadHistory>,
+    /// Last-known values of keys evicted from the reader, shared with its [`WriteHandle`]
+    stale: Arc<StaleValues>,
+    /// Keys with replays in flight, shared with its [`WriteHandle`]
+    replays: Arc<InFlightReplays>,
+    /// Limits on the size of results of reads against the reader, shared with its [`WriteHandle`]
+    result_limit: Arc<ResultLimit>,
+    /// How long reads against the reader wait for misses to be filled, shared with its
+    /// [`WriteHandle`]
+    upquery_timeout: Arc<UpqueryTimeout>,
+    /// Reads waiting for keys which missed to be filled, shared with its [`WriteHandle`]
+    waiters: Arc<ReplayWaiters>,
+    /// Indices on columns of the reader other than its key, shared with its [`WriteHandle`]
+    secondary: Arc<SecondaryIndices>,
+    /// Set once the domain maintaining the reader has failed, shared between all clones of the
+    /// handle
+    unavailable: Arc<AtomicBool>,
 }
 
 impl Clone for SingleReadHandle {
@@ -481,6 +887,16 @@ impl Clone for SingleReadHandle {
             post_lookup: self.post_lookup.clone(),
             receiver: self.receiver.resubscribe(),
             eviction_epoch: self.eviction_epoch,
+            stats: Arc::clone(&self.stats),
+            throttle: Arc::clone(&self.throttle),
+            history: Arc::clone(&self.history),
+            stale: Arc::clone(&self.stale),
+            replays: Arc::clone(&self.replays),
+            result_limit: Arc::clone(&self.result_limit),
+            upquery_timeout: Arc::clone(&self.upquery_timeout),
+            waiters: Arc::clone(&self.waiters),
+            secondary: Arc::clone(&self.secondary),
+            unavailable: Arc::clone(&self.unavailable),
         }
     }
 }
@@ -497,6 +913,10 @@ impl std::fmt::Debug for SingleReadHandle {
 
 impl SingleReadHandle {
     /// Trigger a replay of a missing key from a partially materialized view.
+    ///
+    /// Keys which already have a replay in flight are coalesced into that replay rather than
+    /// triggering another, so that many reads missing on the same hot key result in a single
+    /// replay. All the reads are woken up once it completes.
     pub fn trigger<I>(&self, keys: I, name: Relation) -> bool
     where
         I: Iterator<Item = KeyComparison>,
@@ -506,10 +926,34 @@ impl SingleReadHandle {
             "tried to trigger a replay for a fully materialized view"
         );
 
-        let mut it = keys;
+        let claimed = self.replays.claim(keys);
+        if claimed.coalesced > 0 {
+            self.stats
+                .record_coalesced_replays(&name, claimed.coalesced);
+        }
+        if claimed.keys.is_empty() {
+            return true;
+        }
 
-        // trigger a replay to populate
-        (*self.trigger.as_ref().unwrap())(&mut it, name)
+        let trigger = self.trigger.as_ref().unwrap();
+        // replays which have been in flight for too long may have been lost, so make sure the
+        // domain doesn't deduplicate the new requests into them
+        let triggered = (claimed.retriggered.is_empty()
+            || trigger(
+                &mut claimed.retriggered.iter().cloned(),
+                name.clone(),
+                ReplayRequest::Release,
+            ))
+            // trigger a replay to populate
+            && trigger(
+                &mut claimed.keys.iter().cloned(),
+                name,
+                ReplayRequest::Trigger,
+            );
+        if !triggered {
+            self.replays.release(&claimed.keys);
+        }
+        triggered
     }
 
     /// Returns None if this handle is not ready, Some(true) if this handle fully contains the given
@@ -562,6 +1006,24 @@ impl SingleReadHandle {
         self.handle.keys()
     }
 
+    /// Returns up to `limit` of the keys in the reader which are at or after `start` (or all keys,
+    /// if `start` is `None`), in key order, along with their rows.
+    ///
+    /// Only keys which are currently materialized in the reader are returned, and scanning never
+    /// triggers replays. Returns an error if the reader's index isn't ordered by key.
+    pub fn scan(
+        &self,
+        start: Option<&[DfValue]>,
+        limit: usize,
+    ) -> ReadySetResult<Vec<(Vec<DfValue>, SharedRows)>> {
+        match self.handle.scan(start, limit) {
+            Ok(Some(keys)) => Ok(keys),
+            Ok(None) => unsupported!("Scanning a reader requires an ordered (BTreeMap) index"),
+            Err(reader_map::Error::NotPublished) => Err(ReadySetError::ViewNotYetAvailable),
+            Err(reader_map::Error::Destroyed) => Err(ReadySetError::ViewDestroyed),
+        }
+    }
+
     pub fn timestamp(&self) -> Option<Timestamp> {
         self.handle.timestamp()
     }
@@ -571,6 +1033,105 @@ impl SingleReadHandle {
         self.handle.was_dropped()
     }
 
+    /// Mark the reader as unavailable because the domain maintaining it has failed, so that reads
+    /// against it are rejected rather than waiting on upqueries which will never be answered. The
+    /// reader stays unavailable until it's replaced by the recovered domain.
+    pub fn mark_unavailable(&self) {
+        self.unavailable.store(true, AtomicOrdering::Release);
+    }
+
+    /// Returns true if the domain maintaining the reader has failed (see
+    /// [`mark_unavailable`](Self::mark_unavailable))
+    pub fn is_unavailable(&self) -> bool {
+        self.unavailable.load(AtomicOrdering::Acquire)
+    }
+
+    /// Usage counters for the reader
+    pub fn stats(&self) -> &ReaderStats {
+        &self.stats
+    }
+
+    /// Rate limits for reads against the reader
+    pub fn throttle(&self) -> &Arc<ReadThrottle> {
+        &self.throttle
+    }
+
+    /// Indices on columns of the reader other than its key
+    pub fn secondary_indices(&self) -> &SecondaryIndices {
+        &self.secondary
+    }
+
+    /// The limits on the size of results of reads against the reader
+    pub fn result_limit(&self) -> ViewResultLimit {
+        self.result_limit.get()
+    }
+
+    /// How long reads against the reader wait for misses to be filled by upqueries, if configured
+    /// for the view
+    pub fn upquery_timeout(&self) -> Option<Duration> {
+        self.upquery_timeout.get()
+    }
+
+    /// Give up waiting on the replays of `keys`, because the read which triggered them timed out,
+    /// so that the next miss on any of them triggers a new replay rather than being coalesced
+    /// into one which may be stuck, either here or in the reader's domain
+    pub fn abandon_replays(&self, keys: Vec<KeyComparison>, name: Relation) {
+        self.replays.release(&keys);
+        if let Some(trigger) = &self.trigger {
+            trigger(&mut keys.into_iter(), name, ReplayRequest::Release);
+        }
+    }
+
+    /// Register a read as waiting for `keys`, which missed, to be filled, until the returned guard
+    /// is dropped. This lets the reader's domain tell replays which are still wanted apart from
+    /// those which were abandoned by their reads.
+    pub fn wait_for<'a, I>(&self, keys: I) -> ReplayWaitGuard
+    where
+        I: IntoIterator<Item = &'a KeyComparison>,
+    {
+        waiters::register(&self.waiters, keys)
+    }
+
+    /// Support for answering reads against the reader with possibly-stale values
+    pub fn stale_values(&self) -> &StaleValues {
+        &self.stale
+    }
+
+    /// Look up `keys`, answering each key which misses with the last-known value retained for it
+    /// when it was evicted. Returns `None` if stale reads aren't enabled, or if any key misses and
+    /// has no retained value.
+    pub fn get_multi_stale(&self, keys: &[KeyComparison]) -> Option<SharedResults> {
+        if !self.stale.is_enabled() {
+            return None;
+        }
+
+        keys.iter()
+            .map(|key| match self.get_multi(std::slice::from_ref(key)) {
+                Ok(results) => Some(results),
+                Err(_) => self
+                    .stale
+                    .get(key)
+                    .map(|rows| std::iter::once(rows).collect()),
+            })
+            .try_fold(SharedResults::new(), |mut acc, results| {
+                acc.extend(results?);
+                Some(acc)
+            })
+    }
+
+    /// Rewind `results`, the result of looking up `keys` in this reader, to the state of the
+    /// reader as of `as_of`. Returns `None` if the reader doesn't retain history as far back as
+    /// `as_of`.
+    pub fn rewind(
+        &self,
+        results: SharedResults,
+        keys: &[KeyComparison],
+        as_of: &AsOf,
+    ) -> Option<SharedResults> {
+        self.history
+            .rewind(results, keys, &self.index.columns, as_of)
+    }
+
     pub fn eviction_epoch(&mut self) -> usize {
         while !self.receiver.is_empty() {
             if let Ok(epoch) = self.receiver.try_recv() {
@@ -622,6 +1183,20 @@ mod tests {
         assert_eq!(r.get(&a[0..1]).unwrap()[0], a);
     }
 
+    #[test]
+    fn unavailable_shared_between_clones() {
+        let (r, _w) = new(1, Index::hash_map(vec![0]), ReaderProcessing::default());
+        let r2 = r.clone();
+        assert!(!r2.is_unavailable());
+
+        r.mark_unavailable();
+        assert!(r2.is_unavailable());
+
+        // A new reader, as created by the recovered domain, starts out available
+        let (r3, _w3) = new(1, Index::hash_map(vec![0]), ReaderProcessing::default());
+        assert!(!r3.is_unavailable());
+    }
+
     #[test]
     fn busybusybusy() {
         use std::thread;
@@ -746,7 +1321,7 @@ mod tests {
         let (r, mut w) = new_partial(
             1,
             Index::hash_map(vec![0]),
-            |_: &mut dyn Iterator<Item = KeyComparison>, _| true,
+            |_: &mut dyn Iterator<Item = KeyComparison>, _, _| true,
             EvictionKind::Random,
             ReaderProcessing::default(),
         );
@@ -763,6 +1338,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn scan() {
+        let (r, mut w) = new(2, Index::btree_map(vec![0]), ReaderProcessing::default());
+        w.add((0..10).map(|i| Record::Positive(vec![i.into(), (i * 2).into()])));
+        w.swap();
+
+        let keys = |batch: Vec<(Vec<DfValue>, SharedRows)>| {
+            batch.into_iter().map(|(k, _)| k).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            keys(r.scan(None, 3).unwrap()),
+            vec![vec![0.into()], vec![1.into()], vec![2.into()]]
+        );
+        let batch = r.scan(Some(&[8.into()]), 3).unwrap();
+        assert_eq!(keys(batch.clone()), vec![vec![8.into()], vec![9.into()]]);
+        assert_eq!(batch[0].1[0], vec![8.into(), 16.into()].into_boxed_slice());
+
+        let (r, mut w) = new(2, Index::hash_map(vec![0]), ReaderProcessing::default());
+        w.swap();
+        r.scan(None, 3).unwrap_err();
+    }
+
+    #[test]
+    fn secondary_indices() {
+        let (r, mut w) = new(2, Index::hash_map(vec![0]), ReaderProcessing::default());
+        w.add((0..4).map(|i| Record::Positive(vec![i.into(), (i % 2).into()])));
+        w.set_secondary_indices(vec![vec![1]]).unwrap();
+
+        let lookup = |y: i32| {
+            r.secondary_indices()
+                .lookup(&[1], &[vec![y.into()]])
+                .unwrap()
+                .remove(0)
+                .len()
+        };
+        assert_eq!(lookup(0), 2);
+
+        w.add(vec![Record::Positive(vec![4.into(), 0.into()])]);
+        assert_eq!(lookup(0), 2);
+        w.swap();
+        assert_eq!(lookup(0), 3);
+
+        let (_, mut w) = new_partial(
+            2,
+            Index::hash_map(vec![0]),
+            |_: &mut dyn Iterator<Item = KeyComparison>, _, _| true,
+            EvictionKind::Random,
+            ReaderProcessing::default(),
+        );
+        w.set_secondary_indices(vec![vec![1]]).unwrap_err();
+    }
+
     mod mark_filled {
         use super::*;
 
@@ -771,7 +1398,7 @@ mod tests {
             let (r, mut w) = new_partial(
                 1,
                 Index::hash_map(vec![0]),
-                |_: &mut dyn Iterator<Item = KeyComparison>, _| true,
+                |_: &mut dyn Iterator<Item = KeyComparison>, _, _| true,
                 EvictionKind::Random,
                 ReaderProcessing::default(),
             );
@@ -790,7 +1417,7 @@ mod tests {
             let (r, mut w) = new_partial(
                 1,
                 Index::btree_map(vec![0]),
-                |_: &mut dyn Iterator<Item = KeyComparison>, _| true,
+                |_: &mut dyn Iterator<Item = KeyComparison>, _, _| true,
                 EvictionKind::Random,
                 ReaderProcessing::default(),
             );
@@ -820,7 +1447,7 @@ mod tests {
             let (r, mut w) = new_partial(
                 1,
                 Index::btree_map(vec![0]),
-                |_: &mut dyn Iterator<Item = KeyComparison>, _| true,
+                |_: &mut dyn Iterator<Item = KeyComparison>, _, _| true,
                 EvictionKind::Random,
                 ReaderProcessing::default(),
             );
@@ -841,7 +1468,7 @@ mod tests {
             let (r, mut w) = new_partial(
                 1,
                 Index::btree_map(vec![0]),
-                |_: &mut dyn Iterator<Item = KeyComparison>, _| true,
+                |_: &mut dyn Iterator<Item = KeyComparison>, _, _| true,
                 EvictionKind::Random,
                 ReaderProcessing::default(),
             );
@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 use std::collections::HashSet;
 use std::convert::TryInto;
+use std::ops::Bound;
 
 use ahash::RandomState;
 use common::DfValue;
@@ -109,6 +110,59 @@ impl Handle {
         }
     }
 
+    /// Returns all the rows in the map, across all keys
+    pub(super) fn rows(&self) -> Vec<Box<[DfValue]>> {
+        let rows: Vec<Vec<Box<[DfValue]>>> = match *self {
+            Handle::Single(ref h) => h.map_into(|_, v| v.as_ref().to_vec()),
+            Handle::Many(ref h) => h.map_into(|_, v| v.as_ref().to_vec()),
+        };
+        rows.into_iter().flatten().collect()
+    }
+
+    /// Returns up to `limit` of the keys present in the map which are at or after `start` (or all
+    /// keys, if `start` is `None`), in key order, along with their rows. Keys which are present
+    /// but have no rows are skipped.
+    ///
+    /// Returns `Ok(None)` if the map isn't ordered by key, and so can't be scanned.
+    pub(super) fn scan(
+        &self,
+        start: Option<&[DfValue]>,
+        limit: usize,
+    ) -> reader_map::Result<Option<Vec<(Vec<DfValue>, SharedRows)>>> {
+        match *self {
+            Handle::Single(ref h) => {
+                let map = h.enter()?;
+                let start = match start.and_then(|k| k.first()) {
+                    Some(start) => Bound::Included(start),
+                    None => Bound::Unbounded,
+                };
+                Ok(map.present_range((start, Bound::Unbounded)).map(|range| {
+                    range
+                        .filter(|(_, v)| !v.as_ref().is_empty())
+                        .take(limit)
+                        .map(|(k, v)| (vec![k.clone()], v.as_ref().clone()))
+                        .collect()
+                }))
+            }
+            Handle::Many(ref h) => {
+                let map = h.enter()?;
+                let start = match start {
+                    Some(start) => Bound::Included(start),
+                    None => Bound::Unbounded,
+                };
+                Ok(map
+                    .present_range::<_, [DfValue]>((start, Bound::Unbounded))
+                    .map(|range| {
+                        range
+                            .filter(|(_, v)| !v.as_ref().is_empty())
+                            .take(limit)
+                            .map(|(k, v)| (k.clone(), v.as_ref().clone()))
+                            .collect()
+                    }))
+            }
+        }
+    }
+
     fn get_multi_single_handle<'a, T, F: Fn() -> T>(
         handle: &HandleSingle,
         keys: &'a [KeyComparison],
@@ -5,7 +5,7 @@ use readyset_client::consistency::Timestamp;
 use readyset_data::Bound;
 use readyset_util::ranges::RangeBounds;
 
-use super::{key_to_single, Key};
+use super::{key_to_single, Key, StaleValues};
 use crate::prelude::*;
 
 pub(super) enum Handle {
@@ -89,15 +89,24 @@ impl Handle {
     }
 
     /// Evict keys that were selected by the assigned eviction strategy from the state. The amount
-    /// of keys evicted will be ceil(len() * ratio).
+    /// of keys evicted will be ceil(len() * ratio). The last-known values of the evicted keys are
+    /// retained in `stale`, if stale reads are enabled.
     ///
     /// Returns the number of bytes evicted, and if passed an EvictionQuantity::SingleKey, returns
     /// the key that was evicted.
-    pub fn evict(&mut self, keys_to_evict: EvictionQuantity) -> (u64, Option<Vec<DfValue>>) {
+    pub fn evict(
+        &mut self,
+        keys_to_evict: EvictionQuantity,
+        stale: &StaleValues,
+    ) -> (u64, Option<Vec<DfValue>>) {
         let base_value_size = self.base_value_size() as u64;
+        let retain_stale = stale.is_enabled();
         match *self {
             Handle::Single(ref mut h) => {
                 let (bytes, key) = h.evict_keys(keys_to_evict, |k, v| {
+                    if retain_stale {
+                        stale.retain(vec![k.clone()], v.as_ref().clone());
+                    }
                     // Each row's state is composed of: The key, the set of Values in the row
                     // (DfValues) and the bytes required to hold the Row data
                     // structure.
@@ -109,6 +118,9 @@ impl Handle {
                 (bytes, key.map(|k| vec![k]))
             }
             Handle::Many(ref mut h) => h.evict_keys(keys_to_evict, |k, v| {
+                if retain_stale {
+                    stale.retain(k.clone(), v.as_ref().clone());
+                }
                 k.deep_size_of() + v.iter().map(|r| r.deep_size_of()).sum::<u64>() + base_value_size
             }),
         }
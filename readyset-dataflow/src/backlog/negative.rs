@@ -0,0 +1,179 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use readyset_data::DfValue;
+
+/// Limits on the negative entries cached in a single shard of a reader. A negative entry is a key
+/// which was filled by a replay that found no rows upstream; such keys are kept in the reader as
+/// empty entries so that repeated lookups for them are answered without another replay.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct NegativeEntryLimits {
+    /// How long a negative entry is cached before it's evicted, if it's still empty by then
+    pub(crate) ttl: Option<Duration>,
+    /// The maximum number of negative entries cached at once, beyond which the oldest entries are
+    /// evicted
+    pub(crate) max_entries: Option<usize>,
+}
+
+impl NegativeEntryLimits {
+    /// Returns `true` if negative entries are neither expired nor bounded
+    pub(crate) fn is_unlimited(&self) -> bool {
+        self.ttl.is_none() && self.max_entries.is_none()
+    }
+}
+
+/// The negative entries in a single shard of a reader, tracked by its
+/// [`WriteHandle`](super::WriteHandle) so that they can be expired and bounded.
+///
+/// Entries aren't untracked when rows are later written to their keys; instead, the write handle
+/// checks that a key is still empty before evicting it, so that keys which now exist upstream
+/// stay in the reader.
+#[derive(Default)]
+pub(crate) struct NegativeEntries {
+    limits: NegativeEntryLimits,
+    /// The time each tracked key was filled
+    filled: HashMap<Vec<DfValue>, Instant>,
+    /// Keys in `filled` along with the time they were filled, oldest first. May contain outdated
+    /// entries for keys which have since been filled again, which are skipped.
+    order: VecDeque<(Vec<DfValue>, Instant)>,
+}
+
+impl NegativeEntries {
+    /// Replace the limits on negative entries, untracking all entries if there are no limits
+    pub(crate) fn set_limits(&mut self, limits: NegativeEntryLimits) {
+        self.limits = limits;
+        if limits.is_unlimited() {
+            self.filled.clear();
+            self.order.clear();
+        }
+    }
+
+    /// Returns `true` if negative entries are being tracked
+    pub(crate) fn is_tracking(&self) -> bool {
+        !self.limits.is_unlimited()
+    }
+
+    /// Start tracking `key`, which was just filled with no rows
+    pub(crate) fn track(&mut self, key: Vec<DfValue>, now: Instant) {
+        if !self.is_tracking() {
+            return;
+        }
+        self.filled.insert(key.clone(), now);
+        self.order.push_back((key, now));
+    }
+
+    /// Stop tracking, and return, all keys which have outlived the TTL as of `now` along with the
+    /// oldest keys beyond the maximum number of entries
+    pub(crate) fn pop_expired(&mut self, now: Instant) -> Vec<Vec<DfValue>> {
+        let mut expired = vec![];
+        while let Some((key, filled_at)) = self.order.front() {
+            if self.filled.get(key) != Some(filled_at) {
+                self.order.pop_front();
+                continue;
+            }
+            let over_ttl = self.limits.ttl.map_or(false, |ttl| {
+                now.saturating_duration_since(*filled_at) >= ttl
+            });
+            let over_max = self
+                .limits
+                .max_entries
+                .map_or(false, |max| self.filled.len() > max);
+            if !over_ttl && !over_max {
+                break;
+            }
+            if let Some((key, _)) = self.order.pop_front() {
+                self.filled.remove(&key);
+                expired.push(key);
+            }
+        }
+        expired
+    }
+
+    /// Returns the time at which the oldest tracked key outlives the TTL, if any
+    pub(crate) fn next_expiry(&self) -> Option<Instant> {
+        let ttl = self.limits.ttl?;
+        self.order
+            .iter()
+            .find(|(key, filled_at)| self.filled.get(key) == Some(filled_at))
+            .map(|(_, filled_at)| *filled_at + ttl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(v: i32) -> Vec<DfValue> {
+        vec![DfValue::from(v)]
+    }
+
+    #[test]
+    fn unlimited_by_default() {
+        let mut negative = NegativeEntries::default();
+        let now = Instant::now();
+        negative.track(key(1), now);
+        assert_eq!(negative.filled.len(), 0);
+        assert!(negative
+            .pop_expired(now + Duration::from_secs(3600))
+            .is_empty());
+        assert!(negative.next_expiry().is_none());
+    }
+
+    #[test]
+    fn expires_after_ttl() {
+        let mut negative = NegativeEntries::default();
+        negative.set_limits(NegativeEntryLimits {
+            ttl: Some(Duration::from_secs(10)),
+            max_entries: None,
+        });
+        let now = Instant::now();
+        negative.track(key(1), now);
+        negative.track(key(2), now + Duration::from_secs(5));
+        assert_eq!(negative.next_expiry(), Some(now + Duration::from_secs(10)));
+
+        assert!(negative
+            .pop_expired(now + Duration::from_secs(9))
+            .is_empty());
+        assert_eq!(
+            negative.pop_expired(now + Duration::from_secs(10)),
+            vec![key(1)]
+        );
+        assert_eq!(negative.next_expiry(), Some(now + Duration::from_secs(15)));
+    }
+
+    #[test]
+    fn refilled_keys_expire_from_latest_fill() {
+        let mut negative = NegativeEntries::default();
+        negative.set_limits(NegativeEntryLimits {
+            ttl: Some(Duration::from_secs(10)),
+            max_entries: None,
+        });
+        let now = Instant::now();
+        negative.track(key(1), now);
+        negative.track(key(1), now + Duration::from_secs(5));
+        assert_eq!(negative.filled.len(), 1);
+        assert!(negative
+            .pop_expired(now + Duration::from_secs(10))
+            .is_empty());
+        assert_eq!(
+            negative.pop_expired(now + Duration::from_secs(15)),
+            vec![key(1)]
+        );
+    }
+
+    #[test]
+    fn evicts_oldest_beyond_max() {
+        let mut negative = NegativeEntries::default();
+        negative.set_limits(NegativeEntryLimits {
+            ttl: None,
+            max_entries: Some(2),
+        });
+        let now = Instant::now();
+        for i in 0..3 {
+            negative.track(key(i), now);
+        }
+        assert_eq!(negative.pop_expired(now), vec![key(0)]);
+        assert_eq!(negative.filled.len(), 2);
+        assert!(negative.next_expiry().is_none());
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use readyset_client::KeyComparison;
+
+/// How long a replay can be in flight before misses on the same key trigger it again, in case the
+/// replay was lost. Replays which are triggered again are released in the reader's domain first, so
+/// that the domain doesn't deduplicate them into the replay that may have been lost.
+const RETRIGGER_AFTER: Duration = Duration::from_secs(5);
+
+/// The replays claimed by [`InFlightReplays::claim`]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ClaimedReplays {
+    /// The keys whose replays need to be triggered
+    pub(crate) keys: Vec<KeyComparison>,
+    /// The subset of `keys` whose replays were already triggered longer than [`RETRIGGER_AFTER`]
+    /// ago, and so need to be released in the reader's domain before they're triggered again
+    pub(crate) retriggered: Vec<KeyComparison>,
+    /// The number of keys which were coalesced into a replay already in flight
+    pub(crate) coalesced: usize,
+}
+
+/// The keys for which replays have been triggered in a single shard of a reader and not yet
+/// completed, shared between its [`SingleReadHandle`](super::SingleReadHandle)s (which coalesce
+/// misses on keys with a replay in flight rather than triggering another) and its
+/// [`WriteHandle`](super::WriteHandle) (which releases keys once their replays complete).
+///
+/// Only equality keys are tracked; replays of ranges are always triggered, and deduplicated by
+/// the reader's domain.
+#[derive(Default)]
+pub(crate) struct InFlightReplays {
+    /// The time each key's replay was triggered
+    keys: Mutex<HashMap<KeyComparison, Instant>>,
+}
+
+impl InFlightReplays {
+    /// Claim the replays of `keys`, returning the keys whose replays need to be triggered
+    pub(crate) fn claim<I>(&self, keys: I) -> ClaimedReplays
+    where
+        I: Iterator<Item = KeyComparison>,
+    {
+        let now = Instant::now();
+        let mut claimed = ClaimedReplays::default();
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut in_flight = self.keys.lock().unwrap();
+        for key in keys {
+            if key.is_equal() {
+                match in_flight.get(&key) {
+                    Some(triggered) if now.duration_since(*triggered) < RETRIGGER_AFTER => {
+                        claimed.coalesced += 1;
+                        continue;
+                    }
+                    Some(_) => claimed.retriggered.push(key.clone()),
+                    None => {}
+                }
+                in_flight.insert(key.clone(), now);
+            }
+            claimed.keys.push(key);
+        }
+        claimed
+    }
+
+    /// Release `keys`, either because their replays have completed or because they couldn't be
+    /// triggered, so that the next miss on them triggers a new replay
+    pub(crate) fn release<'a, I>(&self, keys: I)
+    where
+        I: IntoIterator<Item = &'a KeyComparison>,
+    {
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut in_flight = self.keys.lock().unwrap();
+        if in_flight.is_empty() {
+            return;
+        }
+        for key in keys {
+            in_flight.remove(key);
+        }
+    }
+
+    /// Release all keys. Called when keys are evicted from the reader, since a key may have been
+    /// filled and then evicted again without its replay being released.
+    pub(crate) fn release_all(&self) {
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        self.keys.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use readyset_data::DfValue;
+    use vec1::vec1;
+
+    use super::*;
+
+    fn key(v: i32) -> KeyComparison {
+        KeyComparison::Equal(vec1![DfValue::from(v)])
+    }
+
+    #[test]
+    fn coalesces_keys_in_flight() {
+        let replays = InFlightReplays::default();
+        let claimed = replays.claim([key(1), key(2)].into_iter());
+        assert_eq!(claimed.keys, vec![key(1), key(2)]);
+        assert_eq!(claimed.coalesced, 0);
+
+        let claimed = replays.claim([key(1), key(3)].into_iter());
+        assert_eq!(claimed.keys, vec![key(3)]);
+        assert_eq!(claimed.coalesced, 1);
+
+        replays.release(&[key(1)]);
+        let claimed = replays.claim([key(1), key(2)].into_iter());
+        assert_eq!(claimed.keys, vec![key(1)]);
+        assert_eq!(claimed.coalesced, 1);
+        assert!(claimed.retriggered.is_empty());
+
+        replays.release_all();
+        let claimed = replays.claim([key(2), key(3)].into_iter());
+        assert_eq!(claimed.keys, vec![key(2), key(3)]);
+    }
+
+    #[test]
+    fn retriggers_keys_in_flight_too_long() {
+        let replays = InFlightReplays::default();
+        replays.claim([key(1), key(2)].into_iter());
+        replays
+            .keys
+            .lock()
+            .unwrap()
+            .insert(key(1), Instant::now() - RETRIGGER_AFTER);
+
+        let claimed = replays.claim([key(1), key(2)].into_iter());
+        assert_eq!(claimed.keys, vec![key(1)]);
+        assert_eq!(claimed.retriggered, vec![key(1)]);
+        assert_eq!(claimed.coalesced, 1);
+
+        // The retriggered replay is in flight again
+        let claimed = replays.claim([key(1)].into_iter());
+        assert!(claimed.keys.is_empty());
+        assert_eq!(claimed.coalesced, 1);
+    }
+
+    #[test]
+    fn never_coalesces_ranges() {
+        let replays = InFlightReplays::default();
+        let range = KeyComparison::from(vec1![DfValue::from(1)]..vec1![DfValue::from(5)]);
+        for _ in 0..2 {
+            let claimed = replays.claim(std::iter::once(range.clone()));
+            assert_eq!(claimed.keys, vec![range.clone()]);
+            assert_eq!(claimed.coalesced, 0);
+        }
+    }
+}
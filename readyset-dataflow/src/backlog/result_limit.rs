@@ -0,0 +1,63 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use readyset_client::ViewResultLimit;
+
+/// The [`ViewResultLimit`] configured for a single shard of a reader, shared between its
+/// [`SingleReadHandle`](super::SingleReadHandle)s (which enforce the limit when replying to reads)
+/// and its [`WriteHandle`](super::WriteHandle) (through which the domain configures the limit).
+pub(crate) struct ResultLimit {
+    /// The configured limit on rows, or [`usize::MAX`] if rows aren't limited
+    max_rows: AtomicUsize,
+    /// The configured limit on bytes, or [`usize::MAX`] if bytes aren't limited
+    max_bytes: AtomicUsize,
+}
+
+impl ResultLimit {
+    pub(crate) fn new() -> Self {
+        Self {
+            max_rows: AtomicUsize::new(usize::MAX),
+            max_bytes: AtomicUsize::new(usize::MAX),
+        }
+    }
+
+    /// Replace the configured limit
+    pub(crate) fn set(&self, limit: ViewResultLimit) {
+        self.max_rows
+            .store(limit.max_rows.unwrap_or(usize::MAX), Ordering::Relaxed);
+        self.max_bytes
+            .store(limit.max_bytes.unwrap_or(usize::MAX), Ordering::Relaxed);
+    }
+
+    /// Returns the configured limit
+    pub(crate) fn get(&self) -> ViewResultLimit {
+        let limit = |v: &AtomicUsize| Some(v.load(Ordering::Relaxed)).filter(|v| *v != usize::MAX);
+        ViewResultLimit {
+            max_rows: limit(&self.max_rows),
+            max_bytes: limit(&self.max_bytes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited_by_default() {
+        assert!(ResultLimit::new().get().is_unlimited());
+    }
+
+    #[test]
+    fn set_and_get() {
+        let limit = ResultLimit::new();
+        let configured = ViewResultLimit {
+            max_rows: Some(10),
+            max_bytes: None,
+        };
+        limit.set(configured);
+        assert_eq!(limit.get(), configured);
+
+        limit.set(ViewResultLimit::default());
+        assert!(limit.get().is_unlimited());
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, RwLock};
+
+use common::Record;
+use dataflow_expression::PreInsertion;
+use partial_map::InsertionOrder;
+use readyset_client::results::{SharedResults, SharedRows};
+use readyset_data::DfValue;
+
+/// A secondary index on a set of columns of a reader other than its key, mapping each value of
+/// those columns to the rows with that value
+struct SecondaryIndex {
+    columns: Vec<usize>,
+    rows: HashMap<Vec<DfValue>, SharedRows>,
+}
+
+impl SecondaryIndex {
+    fn new(columns: Vec<usize>) -> Self {
+        Self {
+            columns,
+            rows: HashMap::new(),
+        }
+    }
+
+    /// Returns the value of the indexed columns in `row`, or `None` if the row is too short
+    fn key(&self, row: &[DfValue]) -> Option<Vec<DfValue>> {
+        self.columns.iter().map(|c| row.get(*c).cloned()).collect()
+    }
+
+    fn insert(&mut self, row: Box<[DfValue]>, order: &PreInsertion) {
+        let Some(key) = self.key(&row) else {
+            return;
+        };
+        let rows = SharedRows::make_mut(self.rows.entry(key).or_default());
+        // Keep rows in the same order as the reader does, so that results can be merged across
+        // keys the same way as results from the reader's own index
+        let (Ok(pos) | Err(pos)) = order.get_insertion_order(rows, &row);
+        rows.insert(pos, row);
+    }
+
+    fn remove(&mut self, row: &[DfValue]) {
+        let Some(key) = self.key(row) else {
+            return;
+        };
+        let Some(shared) = self.rows.get_mut(&key) else {
+            return;
+        };
+        let rows = SharedRows::make_mut(shared);
+        if let Some(pos) = rows.iter().position(|r| **r == *row) {
+            rows.remove(pos);
+        }
+        if rows.is_empty() {
+            self.rows.remove(&key);
+        }
+    }
+}
+
+/// The secondary indices on a single shard of a fully materialized reader, which allow reads to
+/// look up the rows of the reader by columns other than its key. Shared between the reader's
+/// [`SingleReadHandle`](super::SingleReadHandle)s (which look up rows in the indices) and its
+/// [`WriteHandle`](super::WriteHandle) (which keeps the indices up to date as deltas are applied).
+///
+/// Like the reader itself, deltas are only visible to lookups in the indices once they're
+/// [published](Self::publish).
+pub struct SecondaryIndices {
+    /// Whether any secondary indices are configured, so that readers without any can skip taking
+    /// the lock on `pending` for every write
+    enabled: AtomicBool,
+    /// The order rows are kept in for each key of the reader
+    order: PreInsertion,
+    /// Deltas which have been applied to the reader but not yet published
+    pending: Mutex<Vec<Record>>,
+    indices: RwLock<Vec<SecondaryIndex>>,
+}
+
+impl SecondaryIndices {
+    pub(crate) fn new(order: PreInsertion) -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            order,
+            pending: Default::default(),
+            indices: Default::default(),
+        }
+    }
+
+    /// Replace the secondary indices with indices on each of the given sets of columns, built from
+    /// `rows`, the current published rows of the reader
+    pub(crate) fn set_indices<I>(&self, columns: Vec<Vec<usize>>, rows: I)
+    where
+        I: IntoIterator<Item = Box<[DfValue]>>,
+    {
+        let mut indices = columns
+            .into_iter()
+            .map(SecondaryIndex::new)
+            .collect::<Vec<_>>();
+        if !indices.is_empty() {
+            for row in rows {
+                for index in &mut indices {
+                    index.insert(row.clone(), &self.order);
+                }
+            }
+        }
+
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        self.pending.lock().unwrap().clear();
+        self.enabled.store(!indices.is_empty(), Ordering::Relaxed);
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut current = self.indices.write().unwrap();
+        *current = indices;
+    }
+
+    /// Returns true if any secondary indices are configured
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Returns the sets of columns which are indexed
+    pub fn columns(&self) -> Vec<Vec<usize>> {
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let indices = self.indices.read().unwrap();
+        indices.iter().map(|index| index.columns.clone()).collect()
+    }
+
+    /// Record a batch of deltas which is about to be applied to the reader, to be applied to the
+    /// indices once they're [published](Self::publish)
+    pub(crate) fn record(&self, records: &[Record]) {
+        if !self.is_enabled() {
+            return;
+        }
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        self.pending.lock().unwrap().extend(records.iter().cloned());
+    }
+
+    /// Apply all recorded deltas to the indices, making them visible to lookups
+    pub(crate) fn publish(&self) {
+        if !self.is_enabled() {
+            return;
+        }
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+        if pending.is_empty() {
+            return;
+        }
+
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut indices = self.indices.write().unwrap();
+        for record in pending {
+            for index in indices.iter_mut() {
+                match &record {
+                    Record::Positive(row) => index.insert(row.clone().into(), &self.order),
+                    Record::Negative(row) => index.remove(row),
+                }
+            }
+        }
+    }
+
+    /// Look up the rows with each of `keys` in the index on exactly `columns`, or return `None` if
+    /// there's no index on those columns
+    pub fn lookup(&self, columns: &[usize], keys: &[Vec<DfValue>]) -> Option<SharedResults> {
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let indices = self.indices.read().unwrap();
+        let index = indices.iter().find(|index| index.columns == columns)?;
+        Some(
+            keys.iter()
+                .map(|key| {
+                    // NULL can never compare equal to anything
+                    if key.iter().any(|v| v.is_none()) {
+                        return Default::default();
+                    }
+                    index.rows.get(key).cloned().unwrap_or_default()
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(x: i32, y: i32) -> Vec<DfValue> {
+        vec![x.into(), y.into()]
+    }
+
+    #[test]
+    fn maintained_by_deltas() {
+        let indices = SecondaryIndices::new(PreInsertion::default());
+        indices.set_indices(vec![vec![1]], vec![row(1, 10).into(), row(2, 10).into()]);
+        assert_eq!(indices.columns(), vec![vec![1]]);
+
+        let lookup = |y: i32| {
+            indices.lookup(&[1], &[vec![y.into()]]).unwrap()[0]
+                .iter()
+                .map(|r| r.to_vec())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(lookup(10), vec![row(1, 10), row(2, 10)]);
+
+        indices.record(&[
+            Record::Negative(row(1, 10)),
+            Record::Positive(row(1, 20)),
+            Record::Positive(row(3, 20)),
+        ]);
+        // Not visible until published
+        assert_eq!(lookup(20), Vec::<Vec<DfValue>>::new());
+
+        indices.publish();
+        assert_eq!(lookup(10), vec![row(2, 10)]);
+        assert_eq!(lookup(20), vec![row(1, 20), row(3, 20)]);
+        assert!(indices.lookup(&[0], &[vec![1.into()]]).is_none());
+    }
+}
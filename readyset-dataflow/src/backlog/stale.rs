@@ -0,0 +1,208 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use readyset_client::results::SharedRows;
+use readyset_client::{KeyComparison, ViewStaleReads};
+use readyset_data::DfValue;
+
+/// The last-known values of keys evicted from a reader, bounded to a maximum number of keys
+#[derive(Default)]
+struct Retained {
+    values: HashMap<Vec<DfValue>, SharedRows>,
+    /// Keys in `values`, oldest first
+    order: VecDeque<Vec<DfValue>>,
+}
+
+impl Retained {
+    /// Discard the oldest keys until at most `max` are retained
+    fn truncate(&mut self, max: usize) {
+        while self.values.len() > max {
+            let Some(key) = self.order.pop_front() else {
+                break;
+            };
+            self.values.remove(&key);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.values.clear();
+        self.order.clear();
+    }
+}
+
+/// Support for the [`ViewStaleReads`] configured for a single shard of a reader, shared between its
+/// [`SingleReadHandle`](super::SingleReadHandle)s (which answer reads with stale values) and its
+/// [`WriteHandle`](super::WriteHandle) (which retains the values of evicted keys until they're
+/// filled again).
+pub struct StaleValues {
+    /// Whether stale reads are enabled, so that readers without stale reads can skip taking the
+    /// lock on `retained` on every eviction and fill
+    enabled: AtomicBool,
+    retained: Mutex<Retained>,
+    /// The configured stale reads settings
+    config: Mutex<ViewStaleReads>,
+    /// Whether replication from the upstream database is healthy, shared with every other reader
+    /// on the same worker. Unset for readers which aren't run by a worker, which are always
+    /// considered healthy.
+    replication_healthy: OnceLock<Arc<AtomicBool>>,
+}
+
+impl StaleValues {
+    pub(crate) fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            retained: Default::default(),
+            config: Default::default(),
+            replication_healthy: OnceLock::new(),
+        }
+    }
+
+    /// Replace the stale reads settings, discarding retained values if stale reads are disabled
+    pub(crate) fn set_config(&self, config: ViewStaleReads) {
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut retained = self.retained.lock().unwrap();
+        if config.enabled {
+            retained.truncate(config.max_retained_keys);
+        } else {
+            retained.clear();
+        }
+        self.enabled.store(config.enabled, Ordering::Relaxed);
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut current = self.config.lock().unwrap();
+        *current = config;
+    }
+
+    /// Returns the stale reads settings currently in effect
+    pub fn config(&self) -> ViewStaleReads {
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let config = self.config.lock().unwrap();
+        *config
+    }
+
+    /// Returns `true` if reads may be answered with stale values
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Share the worker-wide flag tracking whether replication from the upstream database is
+    /// healthy. Only the first flag shared takes effect.
+    pub(crate) fn share_replication_health(&self, healthy: Arc<AtomicBool>) {
+        let _ = self.replication_healthy.set(healthy);
+    }
+
+    /// Returns `true` unless replication from the upstream database is known to be down
+    pub fn replication_healthy(&self) -> bool {
+        self.replication_healthy
+            .get()
+            .map_or(true, |healthy| healthy.load(Ordering::Relaxed))
+    }
+
+    /// Retain `rows`, the last-known value of `key`, which is being evicted from the reader
+    pub(crate) fn retain(&self, key: Vec<DfValue>, rows: SharedRows) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let max = self.config().max_retained_keys;
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut retained = self.retained.lock().unwrap();
+        if retained.values.insert(key.clone(), rows).is_none() {
+            retained.order.push_back(key);
+        }
+        retained.truncate(max);
+    }
+
+    /// Discard any values retained for keys covered by `key`, which has been filled in the reader
+    pub(crate) fn discard(&self, key: &KeyComparison) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut retained = self.retained.lock().unwrap();
+        if retained.values.is_empty() {
+            return;
+        }
+        let discarded = match key {
+            KeyComparison::Equal(k) => retained.values.remove(k.as_vec()).is_some(),
+            KeyComparison::Range(_) => {
+                let len = retained.values.len();
+                retained.values.retain(|k, _| !key.contains(k));
+                retained.values.len() != len
+            }
+        };
+        if discarded {
+            let Retained { values, order } = &mut *retained;
+            order.retain(|k| values.contains_key(k));
+        }
+    }
+
+    /// Returns the last-known value retained for `key`, if any. Only equality keys are retained.
+    pub fn get(&self, key: &KeyComparison) -> Option<SharedRows> {
+        if !self.is_enabled() {
+            return None;
+        }
+
+        let key = key.equal()?;
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let retained = self.retained.lock().unwrap();
+        retained.values.get(key.as_vec()).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use readyset_client::results::Row;
+    use vec1::vec1;
+
+    use super::*;
+
+    fn rows(v: i32) -> SharedRows {
+        SharedRows::new(std::iter::once(Row::from(vec![DfValue::from(v)])).collect())
+    }
+
+    fn key(v: i32) -> KeyComparison {
+        KeyComparison::Equal(vec1![DfValue::from(v)])
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let stale = StaleValues::new();
+        assert!(!stale.is_enabled());
+        assert!(stale.replication_healthy());
+        stale.retain(vec![1.into()], rows(1));
+        assert!(stale.get(&key(1)).is_none());
+    }
+
+    #[test]
+    fn retains_until_filled() {
+        let stale = StaleValues::new();
+        stale.set_config(ViewStaleReads::enabled());
+        stale.retain(vec![1.into()], rows(1));
+        stale.retain(vec![2.into()], rows(2));
+        assert_eq!(stale.get(&key(1)), Some(rows(1)));
+
+        stale.discard(&key(1));
+        assert!(stale.get(&key(1)).is_none());
+        assert_eq!(stale.get(&key(2)), Some(rows(2)));
+
+        stale.set_config(ViewStaleReads::default());
+        assert!(stale.get(&key(2)).is_none());
+    }
+
+    #[test]
+    fn discards_oldest_keys() {
+        let stale = StaleValues::new();
+        stale.set_config(ViewStaleReads {
+            enabled: true,
+            max_retained_keys: 2,
+        });
+        for i in 0..3 {
+            stale.retain(vec![i.into()], rows(i));
+        }
+        assert!(stale.get(&key(0)).is_none());
+        assert_eq!(stale.get(&key(1)), Some(rows(1)));
+        assert_eq!(stale.get(&key(2)), Some(rows(2)));
+    }
+}
@@ -0,0 +1,215 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use readyset_client::ViewRateLimit;
+use readyset_errors::RateLimit;
+
+use crate::domain::DomainLoad;
+
+/// A token bucket holding up to `rate` tokens, refilled continuously at `rate` tokens per second
+struct TokenBucket {
+    rate: u32,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u32) -> Self {
+        Self {
+            rate,
+            tokens: rate as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take a token from the bucket, returning `false` if the bucket is empty
+    fn try_take(&mut self) -> bool {
+        let now = Instant::now();
+        let rate = self.rate as f64;
+        self.tokens =
+            (self.tokens + now.duration_since(self.last_refill).as_secs_f64() * rate).min(rate);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Enforcement of the [`ViewRateLimit`] configured for a single shard of a reader, shared between
+/// its [`SingleReadHandle`](super::SingleReadHandle)s (which check reads against the limit) and its
+/// [`WriteHandle`](super::WriteHandle) (through which the domain configures the limit).
+pub struct ReadThrottle {
+    /// Whether requests per second are limited, so that unlimited readers can skip taking the lock
+    /// on `requests` on every read
+    limit_requests: AtomicBool,
+    /// The configured limit on requests per second, or `None` if requests per second aren't
+    /// limited
+    requests: Mutex<Option<TokenBucket>>,
+    /// The configured limit on concurrent misses, or [`u32::MAX`] if concurrent misses aren't
+    /// limited
+    max_concurrent_misses: AtomicU32,
+    /// The number of reads currently waiting for misses to be filled
+    concurrent_misses: AtomicU32,
+    /// The load of the domain running the reader. Unset for readers which aren't run by a domain,
+    /// or which are fully materialized and so never need the domain to fill misses.
+    domain_load: OnceLock<Arc<DomainLoad>>,
+}
+
+impl ReadThrottle {
+    pub(crate) fn new() -> Self {
+        Self {
+            limit_requests: AtomicBool::new(false),
+            requests: Default::default(),
+            max_concurrent_misses: AtomicU32::new(u32::MAX),
+            concurrent_misses: AtomicU32::new(0),
+            domain_load: OnceLock::new(),
+        }
+    }
+
+    /// Share the load of the domain running the reader. Only the first load shared takes effect.
+    pub(crate) fn share_domain_load(&self, load: Arc<DomainLoad>) {
+        let _ = self.domain_load.set(load);
+    }
+
+    /// Replace the limits enforced by this throttle
+    pub(crate) fn set_limit(&self, limit: ViewRateLimit) {
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut requests = self.requests.lock().unwrap();
+        *requests = match (limit.max_requests_per_second, requests.take()) {
+            // Don't reset the bucket if the rate didn't change
+            (Some(rate), Some(bucket)) if bucket.rate == rate => Some(bucket),
+            (rate, _) => rate.map(TokenBucket::new),
+        };
+        self.limit_requests
+            .store(requests.is_some(), Ordering::Relaxed);
+        self.max_concurrent_misses.store(
+            limit.max_concurrent_misses.unwrap_or(u32::MAX),
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Returns the limits currently enforced by this throttle
+    pub fn limit(&self) -> ViewRateLimit {
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let requests = self.requests.lock().unwrap();
+        let max_concurrent_misses = self.max_concurrent_misses.load(Ordering::Relaxed);
+        ViewRateLimit {
+            max_requests_per_second: requests.as_ref().map(|bucket| bucket.rate),
+            max_concurrent_misses: (max_concurrent_misses != u32::MAX)
+                .then_some(max_concurrent_misses),
+        }
+    }
+
+    /// Check whether a new read may be made against the reader, returning the limit that would
+    /// be exceeded if not
+    pub fn check_request(&self) -> Result<(), RateLimit> {
+        if !self.limit_requests.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        match &mut *self.requests.lock().unwrap() {
+            Some(bucket) if !bucket.try_take() => Err(RateLimit::RequestsPerSecond),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check whether the domain running the reader is too far behind to fill misses in a timely
+    /// fashion, returning how long the client should wait before retrying the read if so
+    pub fn check_overload(&self) -> Result<(), Duration> {
+        self.domain_load.get().map_or(Ok(()), |load| load.check())
+    }
+
+    /// Reserve a slot for a read which missed to wait for its misses to be filled, returning the
+    /// limit that would be exceeded if there are no slots available. The slot is released when the
+    /// returned [`MissPermit`] is dropped.
+    pub fn acquire_miss(self: &Arc<Self>) -> Result<MissPermit, RateLimit> {
+        let max = self.max_concurrent_misses.load(Ordering::Relaxed);
+        self.concurrent_misses
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |cur| {
+                (cur < max).then_some(cur + 1)
+            })
+            .map_err(|_| RateLimit::ConcurrentMisses)?;
+        Ok(MissPermit(Arc::clone(self)))
+    }
+}
+
+/// A slot reserved by a read which is waiting for misses to be filled, released on drop
+pub struct MissPermit(Arc<ReadThrottle>);
+
+impl Drop for MissPermit {
+    fn drop(&mut self) {
+        self.0.concurrent_misses.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited_by_default() {
+        let throttle = Arc::new(ReadThrottle::new());
+        assert!(throttle.limit().is_unlimited());
+        for _ in 0..100 {
+            throttle.check_request().unwrap();
+        }
+        let _permits = (0..100)
+            .map(|_| throttle.acquire_miss().unwrap())
+            .collect::<Vec<_>>();
+    }
+
+    #[test]
+    fn limits_requests_per_second() {
+        let throttle = ReadThrottle::new();
+        throttle.set_limit(ViewRateLimit {
+            max_requests_per_second: Some(3),
+            max_concurrent_misses: None,
+        });
+        for _ in 0..3 {
+            throttle.check_request().unwrap();
+        }
+        assert_eq!(throttle.check_request(), Err(RateLimit::RequestsPerSecond));
+
+        throttle.set_limit(ViewRateLimit::default());
+        throttle.check_request().unwrap();
+    }
+
+    #[test]
+    fn limits_concurrent_misses() {
+        let throttle = Arc::new(ReadThrottle::new());
+        let limit = ViewRateLimit {
+            max_requests_per_second: None,
+            max_concurrent_misses: Some(2),
+        };
+        throttle.set_limit(limit);
+        assert_eq!(throttle.limit(), limit);
+
+        let first = throttle.acquire_miss().unwrap();
+        let _second = throttle.acquire_miss().unwrap();
+        assert!(matches!(
+            throttle.acquire_miss(),
+            Err(RateLimit::ConcurrentMisses)
+        ));
+
+        drop(first);
+        throttle.acquire_miss().unwrap();
+    }
+
+    #[test]
+    fn sheds_misses_when_domain_overloaded() {
+        let throttle = ReadThrottle::new();
+        throttle.check_overload().unwrap();
+
+        let load = Arc::new(DomainLoad::new(Some(1), None));
+        throttle.share_domain_load(Arc::clone(&load));
+        throttle.check_overload().unwrap();
+        load.record_queued_packets(1);
+        assert!(throttle.check_overload().is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// The upquery timeout configured for a single shard of a reader, shared between its
+/// [`SingleReadHandle`](super::SingleReadHandle)s (which time out reads waiting on misses) and its
+/// [`WriteHandle`](super::WriteHandle) (through which the domain configures the timeout).
+pub(crate) struct UpqueryTimeout {
+    /// The configured timeout in milliseconds, or [`u64::MAX`] if the server's default applies
+    millis: AtomicU64,
+}
+
+impl UpqueryTimeout {
+    pub(crate) fn new() -> Self {
+        Self {
+            millis: AtomicU64::new(u64::MAX),
+        }
+    }
+
+    /// Replace the configured timeout
+    pub(crate) fn set(&self, timeout: Option<Duration>) {
+        let millis = timeout.map_or(u64::MAX, |timeout| {
+            u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX - 1)
+        });
+        self.millis.store(millis, Ordering::Relaxed);
+    }
+
+    /// Returns the configured timeout, if any
+    pub(crate) fn get(&self) -> Option<Duration> {
+        Some(self.millis.load(Ordering::Relaxed))
+            .filter(|millis| *millis != u64::MAX)
+            .map(Duration::from_millis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unset_by_default() {
+        assert_eq!(UpqueryTimeout::new().get(), None);
+    }
+
+    #[test]
+    fn set_and_get() {
+        let timeout = UpqueryTimeout::new();
+        timeout.set(Some(Duration::from_millis(250)));
+        assert_eq!(timeout.get(), Some(Duration::from_millis(250)));
+
+        timeout.set(None);
+        assert_eq!(timeout.get(), None);
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use readyset_client::KeyComparison;
+
+/// The number of reads waiting for each key which missed in a single shard of a reader to be
+/// filled, shared between its [`SingleReadHandle`](super::SingleReadHandle)s (which register
+/// reads that block on misses) and its [`WriteHandle`](super::WriteHandle) (through which the
+/// domain checks whether the replays it's tracking are still wanted by any reads).
+///
+/// Only equality keys are tracked, since replays of ranges are split into replays of the parts of
+/// the range which missed by the reader's domain.
+#[derive(Default)]
+pub(crate) struct ReplayWaiters {
+    keys: Mutex<HashMap<KeyComparison, usize>>,
+}
+
+impl ReplayWaiters {
+    /// Returns whether any reads are waiting for `key` to be filled
+    pub(crate) fn is_waiting(&self, key: &KeyComparison) -> bool {
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        self.keys.lock().unwrap().contains_key(key)
+    }
+
+    fn unregister(&self, keys: &[KeyComparison]) {
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut waiting = self.keys.lock().unwrap();
+        for key in keys {
+            if let Some(n) = waiting.get_mut(key) {
+                *n -= 1;
+                if *n == 0 {
+                    waiting.remove(key);
+                }
+            }
+        }
+    }
+}
+
+/// Register a read as waiting for `keys` to be filled, until the returned [`ReplayWaitGuard`] is
+/// dropped
+pub(crate) fn register<'a, I>(waiters: &Arc<ReplayWaiters>, keys: I) -> ReplayWaitGuard
+where
+    I: IntoIterator<Item = &'a KeyComparison>,
+{
+    let keys = keys
+        .into_iter()
+        .filter(|key| key.is_equal())
+        .cloned()
+        .collect::<Vec<_>>();
+    if !keys.is_empty() {
+        #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+        let mut waiting = waiters.keys.lock().unwrap();
+        for key in &keys {
+            *waiting.entry(key.clone()).or_default() += 1;
+        }
+    }
+    ReplayWaitGuard {
+        waiters: Arc::clone(waiters),
+        keys,
+    }
+}
+
+/// A read waiting for keys which missed to be filled, which stops waiting when dropped - either
+/// because the read completed, or because it was abandoned (for example, because the client
+/// which issued it disconnected)
+pub struct ReplayWaitGuard {
+    waiters: Arc<ReplayWaiters>,
+    keys: Vec<KeyComparison>,
+}
+
+impl Drop for ReplayWaitGuard {
+    fn drop(&mut self) {
+        if !self.keys.is_empty() {
+            self.waiters.unregister(&self.keys);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use readyset_data::DfValue;
+    use vec1::vec1;
+
+    use super::*;
+
+    fn key(v: i32) -> KeyComparison {
+        KeyComparison::Equal(vec1![DfValue::from(v)])
+    }
+
+    #[test]
+    fn waiting_until_all_guards_dropped() {
+        let waiters = Arc::new(ReplayWaiters::default());
+        let first = register(&waiters, &[key(1), key(2)]);
+        let second = register(&waiters, &[key(1)]);
+        assert!(waiters.is_waiting(&key(1)));
+        assert!(waiters.is_waiting(&key(2)));
+        assert!(!waiters.is_waiting(&key(3)));
+
+        drop(first);
+        assert!(waiters.is_waiting(&key(1)));
+        assert!(!waiters.is_waiting(&key(2)));
+
+        drop(second);
+        assert!(!waiters.is_waiting(&key(1)));
+    }
+
+    #[test]
+    fn ranges_not_tracked() {
+        let waiters = Arc::new(ReplayWaiters::default());
+        let range = KeyComparison::from(vec1![DfValue::from(1)]..vec1![DfValue::from(5)]);
+        let _guard = register(&waiters, std::iter::once(&range));
+        assert!(!waiters.is_waiting(&range));
+    }
+}
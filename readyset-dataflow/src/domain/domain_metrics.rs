@@ -9,6 +9,7 @@ use metrics::{counter, gauge, histogram};
 use nom_sql::Relation;
 use readyset_client::metrics::recorded;
 
+use crate::node::Node;
 use crate::{Packet, PacketDiscriminants};
 
 /// Contains handles to the various metrics collected for a domain.
@@ -129,6 +130,61 @@ impl DomainMetrics {
         );
     }
 
+    pub(super) fn inc_coalesced_replays(&mut self, cache_name: &Relation, n: usize) {
+        if n > 0 {
+            counter!(
+                recorded::DOMAIN_COALESCED_REPLAYS,
+                n as u64,
+                "cache_name" => cache_name_to_string(cache_name)
+            );
+        }
+    }
+
+    pub(super) fn inc_reclaimed_replays(&mut self, cache_name: &Relation, n: usize) {
+        counter!(
+            recorded::DOMAIN_RECLAIMED_REPLAYS,
+            n as u64,
+            "cache_name" => cache_name_to_string(cache_name)
+        );
+    }
+
+    pub(super) fn inc_negative_entry_evictions(&mut self, cache_name: &Relation, n: usize) {
+        counter!(
+            recorded::DOMAIN_NEGATIVE_ENTRY_EVICTIONS,
+            n as u64,
+            "cache_name" => cache_name_to_string(cache_name)
+        );
+    }
+
+    pub(super) fn rec_replay_node_time(&self, cache_name: &Relation, node: &Node, time: Duration) {
+        counter!(
+            recorded::DOMAIN_TOTAL_REPLAY_NODE_TIME,
+            time.as_micros() as u64,
+            "cache_name" => cache_name_to_string(cache_name),
+            "ntype" => node.node_type_string()
+        );
+
+        histogram!(
+            recorded::DOMAIN_REPLAY_NODE_TIME,
+            time.as_micros() as f64,
+            "cache_name" => cache_name_to_string(cache_name),
+            "ntype" => node.node_type_string()
+        );
+    }
+
+    pub(super) fn rec_node_process_time(&self, node: &Node, time: Duration) {
+        if self.verbose {
+            if let Some(node_id) = node.stable_id() {
+                histogram!(
+                    recorded::DOMAIN_NODE_PROCESS_TIME,
+                    time.as_micros() as f64,
+                    "ntype" => node.node_type_string(),
+                    "node_id" => node_id.to_string()
+                );
+            }
+        }
+    }
+
     pub(super) fn inc_packets_sent(&mut self, packet: &Packet) {
         let discriminant: PacketDiscriminants = packet.into();
         let packet_type: &'static str = discriminant.into();
@@ -0,0 +1,117 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// The minimum amount of time that reads against an overloaded domain ask clients to wait before
+/// retrying
+const MIN_RETRY_AFTER: Duration = Duration::from_millis(100);
+
+/// Tracks how far behind a domain is on handling the packets it has received, so that readers in
+/// the domain can reject reads which miss with
+/// [`ReadySetError::Overloaded`](readyset_errors::ReadySetError::Overloaded) instead of waiting on
+/// replays which the domain won't get to in a timely fashion. Shared between the domain (which
+/// records how many packets it has queued) and its partially materialized readers.
+///
+/// A domain is considered overloaded if it has at least
+/// [`Config::overload_queue_depth`](super::Config::overload_queue_depth) packets queued, or if it
+/// has had packets queued continuously for longer than
+/// [`Config::overload_lag`](super::Config::overload_lag).
+pub struct DomainLoad {
+    max_queued_packets: Option<usize>,
+    max_lag: Option<Duration>,
+    /// The point in time that `busy_since_us` is measured from
+    epoch: Instant,
+    /// The number of packets the domain currently has queued
+    queued_packets: AtomicUsize,
+    /// The number of microseconds after `epoch`, plus one, at which the domain last went from
+    /// having no packets queued to having some, or 0 if the domain has no packets queued
+    busy_since_us: AtomicU64,
+}
+
+impl DomainLoad {
+    pub(crate) fn new(max_queued_packets: Option<usize>, max_lag: Option<Duration>) -> Self {
+        Self {
+            max_queued_packets,
+            max_lag,
+            epoch: Instant::now(),
+            queued_packets: AtomicUsize::new(0),
+            busy_since_us: AtomicU64::new(0),
+        }
+    }
+
+    /// Record the number of packets the domain currently has queued
+    pub(crate) fn record_queued_packets(&self, queued_packets: usize) {
+        self.queued_packets.store(queued_packets, Ordering::Relaxed);
+        if queued_packets == 0 {
+            self.busy_since_us.store(0, Ordering::Relaxed);
+        } else if self.busy_since_us.load(Ordering::Relaxed) == 0 {
+            let now_us = self.epoch.elapsed().as_micros() as u64 + 1;
+            self.busy_since_us.store(now_us, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns how long the domain has had packets queued for continuously, which is roughly how
+    /// far behind it is on handling the packets it has received
+    pub fn lag(&self) -> Duration {
+        match self.busy_since_us.load(Ordering::Relaxed) {
+            0 => Duration::ZERO,
+            busy_since_us => {
+                let busy_since = self.epoch + Duration::from_micros(busy_since_us - 1);
+                busy_since.elapsed()
+            }
+        }
+    }
+
+    /// Check whether the domain is overloaded, returning how long clients should wait before
+    /// retrying reads which would need the domain to fill a miss if so
+    pub fn check(&self) -> Result<(), Duration> {
+        let lag = self.lag();
+        let too_many_packets = self.max_queued_packets.map_or(false, |max| {
+            self.queued_packets.load(Ordering::Relaxed) >= max
+        });
+        let too_far_behind = self.max_lag.map_or(false, |max| lag > max);
+        if too_many_packets || too_far_behind {
+            Err(lag.max(MIN_RETRY_AFTER))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn never_overloaded_without_thresholds() {
+        let load = DomainLoad::new(None, None);
+        load.record_queued_packets(1_000_000);
+        load.check().unwrap();
+    }
+
+    #[test]
+    fn overloaded_by_queue_depth() {
+        let load = DomainLoad::new(Some(10), None);
+        load.record_queued_packets(9);
+        load.check().unwrap();
+        load.record_queued_packets(10);
+        assert!(load.check().unwrap_err() >= MIN_RETRY_AFTER);
+        load.record_queued_packets(0);
+        load.check().unwrap();
+    }
+
+    #[test]
+    fn overloaded_by_lag() {
+        let load = DomainLoad::new(None, Some(Duration::from_millis(10)));
+        load.record_queued_packets(1);
+        load.check().unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        // Still busy, so the lag keeps growing
+        load.record_queued_packets(1);
+        assert!(load.check().is_err());
+        assert!(load.lag() >= Duration::from_millis(20));
+
+        load.record_queued_packets(0);
+        assert_eq!(load.lag(), Duration::ZERO);
+        load.check().unwrap();
+    }
+}
@@ -1,5 +1,8 @@
 pub(crate) mod channel;
 mod domain_metrics;
+mod load;
+mod pending_replays;
+mod record_pool;
 mod replay_paths;
 
 use std::borrow::Cow;
@@ -18,17 +21,22 @@ use dataflow_state::{
     BaseTableState, EvictBytesResult, EvictKeysResult, EvictRandomResult, MaterializedNodeState,
     PointKey, RangeKey, RangeLookupResult,
 };
-use failpoint_macros::failpoint;
+use failpoint_macros::{failpoint, set_failpoint};
 use futures_util::future::FutureExt;
 use futures_util::stream::StreamExt;
 use futures_util::TryFutureExt;
+use indexmap::IndexMap;
 pub use internal::{DomainIndex, ReplicaAddress};
 use merging_interval_tree::IntervalTreeSet;
 use metrics::{counter, histogram};
 use nom_sql::Relation;
 use petgraph::graph::NodeIndex;
 use readyset_alloc::StdThreadBuildWrapper;
-use readyset_client::debug::info::KeyCount;
+use readyset_client::debug::info::{
+    KeyCount, KeyMissInfo, ReaderKeyTrace, ReaderSlowReplay, ReplayDomainTiming,
+};
+#[cfg(feature = "failure_injection")]
+use readyset_client::failpoints;
 use readyset_client::internal::{self, Index};
 use readyset_client::metrics::recorded;
 use readyset_client::{KeyComparison, PersistencePoint, ReaderAddress};
@@ -46,18 +54,25 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{debug, error, info, info_span, trace, warn, Instrument};
 use vec1::Vec1;
 
+pub use self::load::DomainLoad;
+use self::pending_replays::PendingReaderReplays;
+#[cfg(feature = "bench")]
+pub use self::record_pool::bench as record_pool_bench;
+use self::record_pool::RecordPool;
 pub(crate) use self::replay_paths::ReplayPath;
 use self::replay_paths::{Destination, ReplayPathSpec, ReplayPaths, Target};
 use crate::domain::channel::{ChannelCoordinator, DomainReceiver, DomainSender};
 use crate::node::special::EgressTx;
 use crate::node::{NodeProcessingResult, ProcessEnv};
+use crate::ops::parallel::OperatorWorkers;
 use crate::payload::{
     EvictRequest, MaterializedState, PacketDiscriminants, PrepareStateKind, PrettyReplayPath,
     ReplayPieceContext, SourceSelection,
 };
 use crate::prelude::*;
 use crate::processing::ColumnMiss;
-use crate::{backlog, DomainRequest, Readers};
+use crate::profile::StateProfile;
+use crate::{backlog, DomainRequest, Readers, SharedStates};
 
 /// A stub for the cache name used for domain metrics that are emitted during a migration.
 const MIGRATION_CACHE_NAME_STUB: &str = "migration";
@@ -82,10 +97,123 @@ pub struct Config {
     /// Whether to emit verbose metrics for the domain.
     #[serde(default)]
     pub verbose_metrics: bool,
+
+    /// The maximum number of distinct values tracked per group by MIN and MAX nodes, which allows
+    /// them to handle deletion of the current extremum without re-scanning the whole group.
+    #[serde(default = "default_max_tracked_extremum_values")]
+    pub max_tracked_extremum_values: usize,
+
+    /// If set, replays to fill misses in readers which take longer than this amount of time
+    /// (measured from the miss to the fill) are recorded in a log of slow replays, along with a
+    /// breakdown of how long each domain along the replay path spent handling the replay.
+    #[serde(default)]
+    pub slow_replay_threshold: Option<time::Duration>,
+
+    /// If set to `true`, replays which exceed [`Config::slow_replay_threshold`] are also logged at
+    /// the `WARN` level.
+    #[serde(default)]
+    pub log_slow_replays: bool,
+
+    /// If set, keys in partially materialized readers which were filled with no rows (because
+    /// they don't exist upstream) are evicted after this amount of time, unless rows have since
+    /// been written to them. If unset, such keys are only evicted along with other keys when the
+    /// reader is over its memory limit.
+    #[serde(default)]
+    pub negative_entry_ttl: Option<time::Duration>,
+
+    /// If set, the maximum number of keys filled with no rows which are kept in each partially
+    /// materialized reader at once, beyond which the oldest such keys are evicted.
+    #[serde(default)]
+    pub max_negative_entries: Option<usize>,
+
+    /// If set to `true`, the event loop running the domain handles upquery requests and partial
+    /// replay pieces ahead of queued writes, other than writes which must be handled first to
+    /// preserve the ordering of updates along a replay path.
+    #[serde(default)]
+    pub prioritize_replays: bool,
+
+    /// If [`Config::prioritize_replays`] is set, the maximum number of replay packets to handle in
+    /// a row while writes are queued, after which a single write is handled, so that writes aren't
+    /// starved by a steady stream of replays.
+    #[serde(default = "default_max_consecutive_replay_packets")]
+    pub max_consecutive_replay_packets: usize,
+
+    /// If set, the domain is considered overloaded while it has at least this many packets queued,
+    /// and reads which miss in its partially materialized readers are rejected with
+    /// [`ReadySetError::Overloaded`] rather than waiting for the miss to be filled.
+    #[serde(default)]
+    pub overload_queue_depth: Option<usize>,
+
+    /// If set, the domain is considered overloaded once it has had packets queued continuously for
+    /// longer than this amount of time, and reads which miss in its partially materialized readers
+    /// are rejected with [`ReadySetError::Overloaded`] rather than waiting for the miss to be
+    /// filled.
+    #[serde(default)]
+    pub overload_lag: Option<time::Duration>,
+
+    /// If set, replays to fill misses in partially materialized readers which have been in flight
+    /// for longer than this amount of time without any reads still waiting for them (for example,
+    /// because the clients which issued those reads disconnected) are no longer tracked by the
+    /// domain or the reader, so that the next miss on the same keys triggers a new replay rather
+    /// than waiting on one which may never complete. If unset, replays are tracked until they
+    /// complete.
+    #[serde(default)]
+    pub orphaned_replay_timeout: Option<time::Duration>,
+
+    /// If set to `true`, the time spent processing partial replays in each node along their
+    /// replay paths is recorded in metrics, aggregated by cache and node type, to help find out
+    /// which operators a slow cache's replays spend their time in.
+    #[serde(default)]
+    pub profile_replays: bool,
+
+    /// The maximum number of emptied record buffers the domain holds on to, to reuse their
+    /// allocations for the data of the packets it copies when forwarding writes to more than one
+    /// child. If set to 0, a new buffer is allocated for every copied packet. Only has an effect
+    /// if the `record_pool` feature is enabled.
+    #[serde(default = "default_record_pool_size")]
+    pub record_pool_size: usize,
+
+    /// If set to `true`, the shards of a fully materialized node which is sharded by a column
+    /// share a single materialization with the other shards of the same replica of that node
+    /// which run on the same worker, rather than each keeping its own copy.
+    #[serde(default)]
+    pub share_full_state_between_shards: bool,
+
+    /// The number of worker threads the domain starts to process large packets through operators
+    /// which handle each record independently of all the others (filters and projections) in
+    /// parallel. Each packet is split into contiguous ranges of records whose results are emitted
+    /// in order, so records with the same key are still emitted in the order in which they were
+    /// received. If set to 0 or 1, all processing happens on the domain's own thread.
+    #[serde(default)]
+    pub operator_threads: usize,
+}
+
+fn default_max_tracked_extremum_values() -> usize {
+    crate::ops::grouped::extremum::DEFAULT_MAX_TRACKED_EXTREMUM_VALUES
+}
+
+/// The default value for [`Config::max_consecutive_replay_packets`]
+pub const DEFAULT_MAX_CONSECUTIVE_REPLAY_PACKETS: usize = 16;
+
+fn default_max_consecutive_replay_packets() -> usize {
+    DEFAULT_MAX_CONSECUTIVE_REPLAY_PACKETS
+}
+
+/// The default value for [`Config::record_pool_size`]
+pub const DEFAULT_RECORD_POOL_SIZE: usize = 64;
+
+fn default_record_pool_size() -> usize {
+    DEFAULT_RECORD_POOL_SIZE
 }
 
 const BATCH_SIZE: usize = 256;
 
+/// The maximum number of reader misses for which to retain timing information for diagnostics
+const MAX_TRACKED_READER_MISSES: usize = 1024;
+
+/// The maximum number of slow replays to retain in each domain's slow replay log
+const MAX_TRACKED_SLOW_REPLAYS: usize = 128;
+
 #[derive(Debug)]
 enum DomainMode {
     Forwarding,
@@ -251,6 +379,22 @@ struct Waiting {
     redos: HashMap<Hole, HashSet<Redo>>,
 }
 
+impl Waiting {
+    /// Stop waiting to re-do all the replays for which `released` returns true. The holes they
+    /// were waiting for are kept even if no redos are left waiting for them, since the replays
+    /// filling them are still in flight.
+    fn release_redos<F>(&mut self, mut released: F)
+    where
+        F: FnMut(&Redo) -> bool,
+    {
+        let Self { holes, redos } = self;
+        holes.retain(|redo, _| !released(redo));
+        for waiting in redos.values_mut() {
+            waiting.retain(|redo| holes.contains_key(redo));
+        }
+    }
+}
+
 /// Data structure representing the set of keys that have been requested by a reader.
 ///
 /// As an optimization, this structure is backed by either a [`HashSet`] if the reader's index is a
@@ -436,6 +580,8 @@ impl DomainBuilder {
         readers: Readers,
         channel_coordinator: Arc<ChannelCoordinator>,
         state_size: Arc<AtomicUsize>,
+        replication_healthy: Arc<AtomicBool>,
+        shared_states: SharedStates,
         init_state_tx: Sender<MaterializedState>,
     ) -> Domain {
         // initially, all nodes are not ready
@@ -445,6 +591,26 @@ impl DomainBuilder {
             .map(|n| n.borrow().local_addr())
             .collect();
 
+        let operator_workers = if self.config.operator_threads > 1 {
+            match OperatorWorkers::new(self.config.operator_threads, self.address()) {
+                Ok(workers) => Some(workers),
+                Err(error) => {
+                    warn!(
+                        %error,
+                        "Failed to start operator worker threads; processing on the domain thread"
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        if let Some(workers) = &operator_workers {
+            for n in self.nodes.values() {
+                n.borrow_mut().set_operator_workers(workers);
+            }
+        }
+
         Domain {
             index: self.index,
             shard: self.shard,
@@ -458,7 +624,7 @@ impl DomainBuilder {
                 .iter()
                 .filter_map(|(n, node)| {
                     node.borrow()
-                        .initial_auxiliary_state()
+                        .initial_auxiliary_state(self.config.max_tracked_extremum_values)
                         .map(|state| (n, state))
                 })
                 .collect(),
@@ -468,7 +634,16 @@ impl DomainBuilder {
             not_ready,
             mode: DomainMode::Forwarding,
             waiting: Default::default(),
+            full_replay_rows: Default::default(),
             reader_triggered: Default::default(),
+            reader_misses: Default::default(),
+            pending_reader_replays: PendingReaderReplays::new(self.config.orphaned_replay_timeout),
+            record_pool: RecordPool::new(if cfg!(feature = "record_pool") {
+                self.config.record_pool_size
+            } else {
+                0
+            }),
+            operator_workers,
             replay_paths: Default::default(),
 
             ingress_inject: Default::default(),
@@ -481,6 +656,8 @@ impl DomainBuilder {
             delayed_for_self: Default::default(),
 
             state_size,
+            replication_healthy,
+            shared_states,
             total_time: Timer::new(),
             total_ptime: Timer::new(),
             wait_time: Timer::new(),
@@ -495,6 +672,22 @@ impl DomainBuilder {
             metrics: domain_metrics::DomainMetrics::new(self.config.verbose_metrics),
 
             eviction_kind: self.config.eviction_kind,
+            max_tracked_extremum_values: self.config.max_tracked_extremum_values,
+            slow_replay_threshold: self.config.slow_replay_threshold,
+            log_slow_replays: self.config.log_slow_replays,
+            profile_replays: self.config.profile_replays,
+            share_full_state_between_shards: self.config.share_full_state_between_shards,
+            negative_entry_limits: backlog::NegativeEntryLimits {
+                ttl: self.config.negative_entry_ttl,
+                max_entries: self.config.max_negative_entries,
+            },
+            prioritize_replays: self.config.prioritize_replays,
+            max_consecutive_replay_packets: self.config.max_consecutive_replay_packets,
+            load: Arc::new(DomainLoad::new(
+                self.config.overload_queue_depth,
+                self.config.overload_lag,
+            )),
+            slow_replays: Default::default(),
             remapped_keys: Default::default(),
 
             init_state_tx,
@@ -502,6 +695,71 @@ impl DomainBuilder {
     }
 }
 
+/// Timing information about a miss on a single key in a reader, retained for diagnostics
+#[derive(Clone, Copy, Debug)]
+struct ReaderMiss {
+    missed_at: time::Instant,
+    replay_duration: Option<time::Duration>,
+}
+
+/// Timing information about the most recent misses on keys in readers, in order of when the misses
+/// occurred. Bounded to [`MAX_TRACKED_READER_MISSES`] entries.
+#[derive(Debug, Default)]
+struct ReaderMisses(IndexMap<(LocalNodeIndex, KeyComparison), ReaderMiss>);
+
+impl ReaderMisses {
+    /// Record that the given reader node missed on the given keys
+    fn record_misses(&mut self, node: LocalNodeIndex, keys: &[KeyComparison]) {
+        let now = time::Instant::now();
+        for key in keys {
+            // Remove any previous entry first, so that the new one moves to the back of the map
+            self.0.shift_remove(&(node, key.clone()));
+            self.0.insert(
+                (node, key.clone()),
+                ReaderMiss {
+                    missed_at: now,
+                    replay_duration: None,
+                },
+            );
+        }
+        while self.0.len() > MAX_TRACKED_READER_MISSES {
+            self.0.shift_remove_index(0);
+        }
+    }
+
+    /// Record that the given keys were filled in the given reader node, returning how long it took
+    /// to fill each of the keys we had recorded a miss for
+    fn record_fills<'a, I>(
+        &mut self,
+        node: LocalNodeIndex,
+        keys: I,
+    ) -> Vec<(&'a KeyComparison, time::Duration)>
+    where
+        I: IntoIterator<Item = &'a KeyComparison>,
+    {
+        let mut fills = vec![];
+        for key in keys {
+            if let Some(miss) = self.0.get_mut(&(node, key.clone())) {
+                if miss.replay_duration.is_none() {
+                    let duration = miss.missed_at.elapsed();
+                    miss.replay_duration = Some(duration);
+                    fills.push((key, duration));
+                }
+            }
+        }
+        fills
+    }
+
+    /// Returns timing information about the most recent miss on the given key in the given reader
+    /// node, if any
+    fn last_miss(&self, node: LocalNodeIndex, key: KeyComparison) -> Option<KeyMissInfo> {
+        self.0.get(&(node, key)).map(|miss| KeyMissInfo {
+            elapsed: miss.missed_at.elapsed(),
+            replay_duration: miss.replay_duration,
+        })
+    }
+}
+
 #[derive(Clone, Debug)]
 struct TimedPurge {
     time: time::Instant,
@@ -665,6 +923,10 @@ pub struct Domain {
     mode: DomainMode,
     waiting: NodeMap<Waiting>,
 
+    /// Number of rows received so far by each node in this domain which is (or was) the target of
+    /// a full replay, used to report the progress of migrations
+    full_replay_rows: NodeMap<usize>,
+
     remapped_keys: RemappedKeys,
 
     /// Replay paths that go through this domain
@@ -674,6 +936,21 @@ pub struct Domain {
     /// node
     reader_triggered: NodeMap<RequestedKeys>,
 
+    /// Timing information about the most recent misses on keys in readers in this domain, used for
+    /// diagnostics
+    reader_misses: ReaderMisses,
+
+    /// When replays of keys in readers in this domain were last requested, used to stop tracking
+    /// replays which no reads are waiting for anymore. See [`Config::orphaned_replay_timeout`]
+    pending_reader_replays: PendingReaderReplays,
+
+    /// Buffers left behind by fully consumed packets, reused for the data of copied packets. See
+    /// [`Config::record_pool_size`]
+    record_pool: RecordPool,
+
+    /// Workers for operators to process large packets on. See [`Config::operator_threads`]
+    operator_workers: Option<OperatorWorkers>,
+
     /// Queue of purge operations to be performed on reader nodes at some point in the future, used
     /// as part of the implementation of materialization frontiers
     ///
@@ -689,6 +966,11 @@ pub struct Domain {
     delayed_for_self: VecDeque<Packet>,
 
     state_size: Arc<AtomicUsize>,
+    /// Whether replication from the upstream database is currently healthy, shared by all the
+    /// domains run by the worker running this domain, and with the readers in this domain
+    replication_healthy: Arc<AtomicBool>,
+    /// The states shared between the shards of nodes on the worker running this domain
+    shared_states: SharedStates,
     total_time: Timer<SimpleTracker, RealTime>,
     total_ptime: Timer<SimpleTracker, ThreadTime>,
     wait_time: Timer<SimpleTracker, RealTime>,
@@ -708,6 +990,28 @@ pub struct Domain {
 
     metrics: domain_metrics::DomainMetrics,
     eviction_kind: crate::EvictionKind,
+    /// See [`Config::max_tracked_extremum_values`]
+    max_tracked_extremum_values: usize,
+    /// See [`Config::slow_replay_threshold`]
+    slow_replay_threshold: Option<time::Duration>,
+    /// See [`Config::log_slow_replays`]
+    log_slow_replays: bool,
+    /// See [`Config::profile_replays`]
+    profile_replays: bool,
+    /// See [`Config::share_full_state_between_shards`]
+    share_full_state_between_shards: bool,
+    /// See [`Config::negative_entry_ttl`] and [`Config::max_negative_entries`]
+    negative_entry_limits: backlog::NegativeEntryLimits,
+    /// See [`Config::prioritize_replays`]
+    prioritize_replays: bool,
+    /// See [`Config::max_consecutive_replay_packets`]
+    max_consecutive_replay_packets: usize,
+    /// How far behind the domain is on handling the packets it has received, shared with the
+    /// partially materialized readers in this domain
+    load: Arc<DomainLoad>,
+    /// The most recent replays to readers in this domain which took longer than
+    /// `slow_replay_threshold`, oldest first. Bounded to [`MAX_TRACKED_SLOW_REPLAYS`] entries.
+    slow_replays: VecDeque<ReaderSlowReplay>,
 
     /// This channel is used to notify the replica that a base node has its persistent state
     /// initialized.
@@ -781,6 +1085,17 @@ async fn initialize_state(
         })
 }
 
+/// Returns the [`DataflowContext`] for an error which occurred while processing a packet in the
+/// given node, along the replay path with the given tag if the packet was a replay
+fn node_context(node: &Node, tag: Option<Tag>) -> DataflowContext {
+    DataflowContext {
+        node: Some(node.global_addr()),
+        operator: Some(node.node_type_string()),
+        tag: tag.map(u32::from),
+        ..Default::default()
+    }
+}
+
 impl Domain {
     /// Return the unique index for this domain
     pub fn index(&self) -> DomainIndex {
@@ -927,6 +1242,8 @@ impl Domain {
         // on!)
         let mut needed_replays: HashMap<(Target, Vec<usize>), Vec<KeyComparison>> =
             Default::default();
+        // The number of misses coalesced into replays which were already in flight
+        let mut coalesced = 0;
 
         for (replay_key, miss_key) in missed_keys {
             let miss = ColumnMiss {
@@ -996,6 +1313,7 @@ impl Domain {
                         Entry::Occupied(e) => {
                             // we have already requested backfill of this key
                             // remember to notify this Redo when backfill completes
+                            coalesced += 1;
                             if e.into_mut().insert(redo.clone()) {
                                 // this Redo should wait for this backfill to complete before
                                 // redoing
@@ -1017,6 +1335,7 @@ impl Domain {
         }
 
         self.waiting.insert(miss_in, w);
+        self.metrics.inc_coalesced_replays(&cache_name, coalesced);
 
         for ((target, columns), keys) in needed_replays {
             self.find_tags_and_replay(
@@ -1220,29 +1539,33 @@ impl Domain {
         let (mut m, evictions) = {
             #[allow(clippy::indexing_slicing)] // we checked the node exists already
             let mut n = self.nodes[me].borrow_mut();
+            let start = time::Instant::now();
             self.process_times.start(me);
             self.process_ptimes.start(me);
             let mut m = Some(m);
             let NodeProcessingResult {
                 misses, captured, ..
-            } = n.process(
-                &mut m,
-                None,
-                None,
-                true,
-                ProcessEnv {
-                    state: &mut self.state,
-                    reader_write_handles: &mut self.reader_write_handles,
-                    nodes: &self.nodes,
-                    executor,
-                    shard: self.shard,
-                    replica: self.replica,
-                    auxiliary_node_states: &mut self.auxiliary_node_states,
-                },
-            )?;
+            } = n
+                .process(
+                    &mut m,
+                    None,
+                    None,
+                    true,
+                    ProcessEnv {
+                        state: &mut self.state,
+                        reader_write_handles: &mut self.reader_write_handles,
+                        nodes: &self.nodes,
+                        executor,
+                        shard: self.shard,
+                        replica: self.replica,
+                        auxiliary_node_states: &mut self.auxiliary_node_states,
+                    },
+                )
+                .map_err(|e| e.in_dataflow(node_context(&n, None)))?;
             assert_eq!(captured.len(), 0);
             self.process_ptimes.stop();
             self.process_times.stop();
+            self.metrics.rec_node_process_time(&n, start.elapsed());
 
             if m.is_none() {
                 // no need to deal with our children if we're not sending them anything
@@ -1337,13 +1660,15 @@ impl Domain {
 
         // We checked it's Some above, it's only an Option so we can take()
         #[allow(clippy::unwrap_used)]
-        match m.as_ref().unwrap() {
-            m @ &Packet::Message { .. } if m.is_empty() => {
-                // no need to deal with our children if we're not sending them anything
+        match m.as_mut().unwrap() {
+            Packet::Message { data, .. } if data.is_empty() => {
+                // no need to deal with our children if we're not sending them anything, but hold
+                // on to the buffer for the next packet we need to copy
+                self.record_pool.recycle(mem::take(data));
                 return Ok(());
             }
-            &Packet::Message { .. } => {}
-            &Packet::ReplayPiece { .. } => {
+            Packet::Message { .. } => {}
+            Packet::ReplayPiece { .. } => {
                 internal!("Replay should never go through dispatch.");
             }
             m => {
@@ -1361,7 +1686,9 @@ impl Domain {
             let mut m = if i == nchildren - 1 {
                 m.take().unwrap()
             } else {
-                m.as_ref().map(|m| m.clone_data()).unwrap()
+                m.as_ref()
+                    .map(|m| m.clone_data_with(|data| self.record_pool.clone_records(data)))
+                    .unwrap()
             };
 
             #[allow(clippy::indexing_slicing)] // see NOTE above
@@ -1435,7 +1762,7 @@ impl Domain {
             }
             p.link_mut().dst = childi;
 
-            self.handle_packet(p, executor)?;
+            self.process_packet(p, executor)?;
         }
         Ok(())
     }
@@ -1447,9 +1774,12 @@ impl Domain {
     ) -> ReadySetResult<Option<Vec<u8>>> {
         trace!(?req, "processing domain request");
         let ret = match req {
-            DomainRequest::AddNode { node, parents } => {
+            DomainRequest::AddNode { mut node, parents } => {
+                if let Some(workers) = &self.operator_workers {
+                    node.set_operator_workers(workers);
+                }
                 let addr = node.local_addr();
-                let aux_state = node.initial_auxiliary_state();
+                let aux_state = node.initial_auxiliary_state(self.max_tracked_extremum_values);
                 self.not_ready.insert(addr);
 
                 for p in parents {
@@ -1478,9 +1808,19 @@ impl Domain {
                     };
                     self.auxiliary_node_states.remove(node);
                     self.reader_write_handles.remove(node);
+                    self.full_replay_rows.remove(node);
                     trace!(local = node.id(), "node removed");
                 }
 
+                // If we were in the middle of a full replay to one of the removed nodes (because
+                // the migration that added it was cancelled), stop waiting for it to finish
+                if let DomainMode::Replaying { to, .. } = self.mode {
+                    if nodes.contains(&to) {
+                        debug!(local = to.id(), "replay target removed, abandoning replay");
+                        self.mode = DomainMode::Forwarding;
+                    }
+                }
+
                 for node in nodes {
                     for cn in self.nodes.iter_mut() {
                         cn.1.borrow_mut().try_remove_child(node);
@@ -1566,6 +1906,24 @@ impl Domain {
 
                 Ok(None)
             }
+            DomainRequest::RemoveEgressTx {
+                egress_node,
+                ingress_node,
+            } => {
+                let mut n = self
+                    .nodes
+                    .get(egress_node)
+                    .ok_or_else(|| ReadySetError::NoSuchNode(egress_node.id()))?
+                    .borrow_mut();
+
+                n.as_mut_egress()
+                    .ok_or(ReadySetError::InvalidNodeType {
+                        node_index: egress_node.id(),
+                        expected_type: NodeType::Egress,
+                    })?
+                    .remove_tx(ingress_node);
+                Ok(None)
+            }
             DomainRequest::AddEgressTag {
                 egress_node,
                 tag,
@@ -1655,10 +2013,22 @@ impl Domain {
                         weak_indices,
                     } => {
                         if !self.state.contains_key(node) {
-                            self.state.insert(
-                                node,
-                                MaterializedNodeState::Memory(MemoryState::default()),
-                            );
+                            let n = self.nodes[node].borrow();
+                            let state = match (n.sharded_by(), self.shard) {
+                                (Sharding::ByColumn(col, nshards), Some(shard))
+                                    if self.share_full_state_between_shards =>
+                                {
+                                    MaterializedNodeState::SharedMemory(self.shared_states.join(
+                                        (n.global_addr(), self.replica),
+                                        col,
+                                        shard,
+                                        nshards,
+                                    ))
+                                }
+                                _ => MaterializedNodeState::Memory(MemoryState::default()),
+                            };
+                            drop(n);
+                            self.state.insert(node, state);
                         }
                         let state = self.state.get_mut(node).unwrap();
                         for index in strict_indices {
@@ -1699,6 +2069,7 @@ impl Domain {
                         struct Misses {
                             misses: Vec<KeyComparison>,
                             cache_name: Relation,
+                            request: backlog::ReplayRequest,
                         }
 
                         let txs = (0..num_shards)
@@ -1716,11 +2087,21 @@ impl Domain {
                                 let cols = index.columns.clone();
                                 tokio::spawn(
                                     UnboundedReceiverStream::new(rx)
-                                        .map(move |misses| Packet::RequestReaderReplay {
-                                            keys: misses.misses,
-                                            cols: cols.clone(),
-                                            node,
-                                            cache_name: misses.cache_name,
+                                        .map(move |misses| match misses.request {
+                                            backlog::ReplayRequest::Trigger => {
+                                                Packet::RequestReaderReplay {
+                                                    keys: misses.misses,
+                                                    cols: cols.clone(),
+                                                    node,
+                                                    cache_name: misses.cache_name,
+                                                }
+                                            }
+                                            backlog::ReplayRequest::Release => {
+                                                Packet::ReleaseReaderReplays {
+                                                    node,
+                                                    keys: misses.misses,
+                                                }
+                                            }
                                         })
                                         .map(Ok)
                                         .forward(sender)
@@ -1741,17 +2122,25 @@ impl Domain {
                         #[allow(clippy::unwrap_used)] // checked it was a reader above
                         let r = n.as_mut_reader().unwrap();
 
-                        let (r_part, w_part) = backlog::new_partial(
+                        let (r_part, mut w_part) = backlog::new_partial(
                             num_columns,
                             index,
-                            move |misses: &mut dyn Iterator<Item = KeyComparison>, cache_name| {
+                            move |misses: &mut dyn Iterator<Item = KeyComparison>,
+                                  cache_name,
+                                  request| {
                                 if num_shards == 1 {
                                     let misses = misses.collect::<Vec<_>>();
                                     if misses.is_empty() {
                                         return true;
                                     }
                                     #[allow(clippy::indexing_slicing)] // just checked len is 1
-                                    txs[0].send(Misses { misses, cache_name }).is_ok()
+                                    txs[0]
+                                        .send(Misses {
+                                            misses,
+                                            cache_name,
+                                            request,
+                                        })
+                                        .is_ok()
                                 } else {
                                     let mut per_shard = HashMap::new();
                                     for miss in misses {
@@ -1773,6 +2162,7 @@ impl Domain {
                                             .send(Misses {
                                                 misses: keys,
                                                 cache_name: cache_name.clone(),
+                                                request,
                                             })
                                             .is_ok()
                                     })
@@ -1781,6 +2171,14 @@ impl Domain {
                             self.eviction_kind,
                             r.reader_processing().clone(),
                         );
+                        w_part.set_rate_limit(r.rate_limit());
+                        w_part.set_history_retention(r.history_retention());
+                        w_part.set_stale_reads(r.stale_reads());
+                        w_part.set_result_limit(r.result_limit());
+                        w_part.set_upquery_timeout(r.upquery_timeout());
+                        w_part.share_replication_health(Arc::clone(&self.replication_healthy));
+                        w_part.share_domain_load(Arc::clone(&self.load));
+                        w_part.set_negative_entry_limits(self.negative_entry_limits);
 
                         let shard = *self.shard.as_ref().unwrap_or(&0);
                         // TODO(ENG-838): Don't recreate every single node on leader failure.
@@ -1829,8 +2227,14 @@ impl Domain {
                                     expected_type: NodeType::Reader,
                                 })?;
 
-                        let (r_part, w_part) =
+                        let (r_part, mut w_part) =
                             backlog::new(num_columns, index, r.reader_processing().clone());
+                        w_part.set_rate_limit(r.rate_limit());
+                        w_part.set_history_retention(r.history_retention());
+                        w_part.set_stale_reads(r.stale_reads());
+                        w_part.set_result_limit(r.result_limit());
+                        w_part.set_secondary_indices(r.secondary_indices().to_vec())?;
+                        w_part.share_replication_health(Arc::clone(&self.replication_healthy));
 
                         let shard = *self.shard.as_ref().unwrap_or(&0);
                         // TODO(ENG-838): Don't recreate every single node on leader failure.
@@ -2362,8 +2766,18 @@ impl Domain {
                 }
                 Ok(Some(bincode::serialize(&res)?))
             }
+            DomainRequest::ProfileState { node, max_rows } => {
+                let state = self.state.get(node).ok_or_else(|| {
+                    internal_err!("Asked to profile the state of non-materialized node {node}")
+                })?;
+                // The sample is whichever rows the state happens to iterate over first, which for
+                // persistent state is in order of the hash of the primary key
+                let mut all_records = state.all_records();
+                let profile = StateProfile::new(all_records.read().iter().take(max_rows));
+                Ok(Some(bincode::serialize(&profile)?))
+            }
             DomainRequest::Packet(pkt) => {
-                self.handle_packet(pkt, executor)?;
+                self.process_packet(pkt, executor)?;
                 Ok(None)
             }
             DomainRequest::QueryReplayDone { node } => {
@@ -2382,6 +2796,10 @@ impl Domain {
                     });
                 Ok(Some(bincode::serialize(&ret)?))
             }
+            DomainRequest::QueryReplayProgress { node } => {
+                let ret = self.full_replay_rows.get(node).copied().unwrap_or(0);
+                Ok(Some(bincode::serialize(&ret)?))
+            }
             DomainRequest::GeneratedColumns { node, index, tag } => {
                 // Record that these columns are generated...
                 self.replay_paths
@@ -2411,6 +2829,156 @@ impl Domain {
                 let key = self.handle_eviction(req, executor)?;
                 Ok(Some(bincode::serialize(&key)?))
             }
+            DomainRequest::TraceReaderKey { node, key } => {
+                let w = self.reader_write_handles.get_mut(node).ok_or_else(|| {
+                    internal_err!("key trace requested for non-materialized reader")
+                })?;
+                let materialized = w.contains(&key) == Ok(true);
+                let last_miss = self.reader_misses.last_miss(node, key);
+                Ok(Some(bincode::serialize(&ReaderKeyTrace {
+                    materialized,
+                    last_miss,
+                })?))
+            }
+            DomainRequest::ReaderMetrics { node } => {
+                let w = self.reader_write_handles.get(node).ok_or_else(|| {
+                    internal_err!("metrics requested for non-materialized reader")
+                })?;
+                Ok(Some(bincode::serialize(&w.stats().metrics())?))
+            }
+            DomainRequest::ReaderHotKeys { node } => {
+                let w = self.reader_write_handles.get(node).ok_or_else(|| {
+                    internal_err!("hot keys requested for non-materialized reader")
+                })?;
+                Ok(Some(bincode::serialize(&w.stats().hot_keys())?))
+            }
+            DomainRequest::SetReaderRateLimit { node, limit } => {
+                let mut n = self
+                    .nodes
+                    .get(node)
+                    .ok_or_else(|| ReadySetError::NoSuchNode(node.id()))?
+                    .borrow_mut();
+                let r = n
+                    .as_mut_reader()
+                    .ok_or_else(|| ReadySetError::InvalidNodeType {
+                        node_index: node.id(),
+                        expected_type: NodeType::Reader,
+                    })?;
+                r.set_rate_limit(limit);
+                if let Some(w) = self.reader_write_handles.get(node) {
+                    w.set_rate_limit(limit);
+                }
+                Ok(None)
+            }
+            DomainRequest::SetReaderHistoryRetention { node, retention } => {
+                let mut n = self
+                    .nodes
+                    .get(node)
+                    .ok_or_else(|| ReadySetError::NoSuchNode(node.id()))?
+                    .borrow_mut();
+                let r = n
+                    .as_mut_reader()
+                    .ok_or_else(|| ReadySetError::InvalidNodeType {
+                        node_index: node.id(),
+                        expected_type: NodeType::Reader,
+                    })?;
+                r.set_history_retention(retention);
+                if let Some(w) = self.reader_write_handles.get(node) {
+                    w.set_history_retention(retention);
+                }
+                Ok(None)
+            }
+            DomainRequest::SetReaderStaleReads { node, stale_reads } => {
+                let mut n = self
+                    .nodes
+                    .get(node)
+                    .ok_or_else(|| ReadySetError::NoSuchNode(node.id()))?
+                    .borrow_mut();
+                let r = n
+                    .as_mut_reader()
+                    .ok_or_else(|| ReadySetError::InvalidNodeType {
+                        node_index: node.id(),
+                        expected_type: NodeType::Reader,
+                    })?;
+                r.set_stale_reads(stale_reads);
+                if let Some(w) = self.reader_write_handles.get(node) {
+                    w.set_stale_reads(stale_reads);
+                }
+                Ok(None)
+            }
+            DomainRequest::SetReaderResultLimit { node, result_limit } => {
+                let mut n = self
+                    .nodes
+                    .get(node)
+                    .ok_or_else(|| ReadySetError::NoSuchNode(node.id()))?
+                    .borrow_mut();
+                let r = n
+                    .as_mut_reader()
+                    .ok_or_else(|| ReadySetError::InvalidNodeType {
+                        node_index: node.id(),
+                        expected_type: NodeType::Reader,
+                    })?;
+                r.set_result_limit(result_limit);
+                if let Some(w) = self.reader_write_handles.get(node) {
+                    w.set_result_limit(result_limit);
+                }
+                Ok(None)
+            }
+            DomainRequest::SetReaderUpqueryTimeout { node, timeout } => {
+                let mut n = self
+                    .nodes
+                    .get(node)
+                    .ok_or_else(|| ReadySetError::NoSuchNode(node.id()))?
+                    .borrow_mut();
+                let r = n
+                    .as_mut_reader()
+                    .ok_or_else(|| ReadySetError::InvalidNodeType {
+                        node_index: node.id(),
+                        expected_type: NodeType::Reader,
+                    })?;
+                r.set_upquery_timeout(timeout);
+                if let Some(w) = self.reader_write_handles.get(node) {
+                    w.set_upquery_timeout(timeout);
+                }
+                Ok(None)
+            }
+            DomainRequest::SetReaderSecondaryIndices { node, indices } => {
+                let mut n = self
+                    .nodes
+                    .get(node)
+                    .ok_or_else(|| ReadySetError::NoSuchNode(node.id()))?
+                    .borrow_mut();
+                let r = n
+                    .as_mut_reader()
+                    .ok_or_else(|| ReadySetError::InvalidNodeType {
+                        node_index: node.id(),
+                        expected_type: NodeType::Reader,
+                    })?;
+                if let Some(w) = self.reader_write_handles.get_mut(node) {
+                    w.set_secondary_indices(indices.clone())?;
+                }
+                r.set_secondary_indices(indices);
+                Ok(None)
+            }
+            DomainRequest::SlowReplays => Ok(Some(bincode::serialize(&self.slow_replays)?)),
+            DomainRequest::UpdateRuntimeConfig(update) => {
+                if let Some(verbose) = update.verbose_domain_metrics {
+                    self.metrics = domain_metrics::DomainMetrics::new(verbose);
+                }
+                if let Some(aggressive) = update.aggressively_update_state_sizes {
+                    self.aggressively_update_state_sizes = aggressive;
+                }
+                if let Some(threshold) = update.slow_replay_threshold {
+                    self.slow_replay_threshold = threshold;
+                }
+                if let Some(log) = update.log_slow_replays {
+                    self.log_slow_replays = log;
+                }
+                if let Some(profile) = update.profile_replays {
+                    self.profile_replays = profile;
+                }
+                Ok(None)
+            }
         };
 
         // What we just did might have done things like insert into `self.delayed_for_self`, so
@@ -2418,7 +2986,7 @@ impl Domain {
         //
         // Not doing this leads to complete insanity, as things just don't replay sometimes and
         // you aren't sure why.
-        self.handle_packet(Packet::Spin, executor)?;
+        self.process_packet(Packet::Spin, executor)?;
         ret
     }
 
@@ -2446,6 +3014,7 @@ impl Domain {
                 }
             }
             Packet::ReplayPiece { ref cache_name, .. } => {
+                set_failpoint!(failpoints::DOMAIN_HANDLE_REPLAY);
                 let start = time::Instant::now();
                 let cache_name = cache_name.clone();
                 self.total_replay_time.start();
@@ -2519,7 +3088,14 @@ impl Domain {
                     .reader_triggered
                     .entry(node)
                     .or_insert_with(|| RequestedKeys::new(reader_index_type));
+                let requested = keys.len();
+                // Keys which were already requested are requested again, so any reads coalesced
+                // into their replays keep them from being considered orphaned
+                self.pending_reader_replays.record_requests(node, &keys);
                 already_requested.extend(&mut keys);
+                self.metrics
+                    .inc_coalesced_replays(&cache_name, requested - keys.len());
+                self.reader_misses.record_misses(node, &keys);
                 if !keys.is_empty() {
                     self.find_tags_and_replay(
                         keys,
@@ -2542,6 +3118,7 @@ impl Domain {
                 ..
             } => {
                 trace!(%tag, ?keys, "got replay request");
+                set_failpoint!(failpoints::DOMAIN_SEED_REPLAY);
                 let start = time::Instant::now();
                 let cache_name = cache_name.clone();
                 self.total_replay_time.start();
@@ -2555,6 +3132,7 @@ impl Domain {
                 node,
                 cache_name,
             } => {
+                set_failpoint!(failpoints::DOMAIN_FINISH_REPLAY);
                 let start = time::Instant::now();
                 self.total_replay_time.start();
                 self.finish_replay(tag, node, &cache_name, executor)?;
@@ -2562,6 +3140,10 @@ impl Domain {
                 self.metrics
                     .rec_finish_replay_time(&cache_name, start.elapsed());
             }
+            Packet::ReleaseReaderReplays { node, keys } => {
+                trace!(%node, ?keys, "Releasing abandoned reader replays");
+                self.release_reader_replays(node, &keys);
+            }
             Packet::Spin => {
                 // spinning as instructed
             }
@@ -2607,6 +3189,92 @@ impl Domain {
         Ok(())
     }
 
+    /// Evict keys which were filled with no rows from readers once they've outlived
+    /// [`Config::negative_entry_ttl`]
+    fn expire_negative_entries(&mut self) -> ReadySetResult<()> {
+        for (node, wh) in self.reader_write_handles.iter_mut() {
+            let evicted = wh.evict_negative_entries();
+            if evicted > 0 {
+                wh.swap();
+                wh.notify_readers_of_eviction()?;
+                #[allow(clippy::indexing_slicing)] // nodes in reader_write_handles are in self
+                let name = self.nodes[node].borrow().name().clone();
+                self.metrics.inc_negative_entry_evictions(&name, evicted);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stop tracking replays to readers which have been in flight for longer than
+    /// [`Config::orphaned_replay_timeout`] without any reads waiting for them, releasing them in
+    /// the reader so that the next miss on the same keys triggers a new replay
+    fn reclaim_orphaned_replays(&mut self) {
+        let reader_write_handles = &self.reader_write_handles;
+        let orphaned = self.pending_reader_replays.take_orphaned(|node, key| {
+            reader_write_handles
+                .get(node)
+                .map_or(false, |wh| wh.has_waiters(key))
+        });
+
+        for (node, keys) in orphaned {
+            // The reader may have been removed since the replays were requested
+            if !self.reader_write_handles.contains_key(node) {
+                continue;
+            }
+            self.release_reader_replays(node, &keys);
+
+            #[allow(clippy::indexing_slicing)] // nodes in reader_write_handles are in self
+            let name = self.nodes[node].borrow().name().clone();
+            debug!(
+                reader = %name.display_unquoted(),
+                num_keys = keys.len(),
+                "Reclaimed replays with no reads waiting for them"
+            );
+            self.metrics.inc_reclaimed_replays(&name, keys.len());
+        }
+    }
+
+    /// Stop tracking the replays of `keys` into the reader `node`, which no reads are waiting for
+    /// any more, so that the next request for any of them triggers a new replay rather than being
+    /// deduplicated into one which may be stuck.
+    ///
+    /// This also drops any redos of those replays which are waiting for holes upstream of the
+    /// reader to be filled, and any requests to replay those keys into the reader which are
+    /// queued to be handled by this domain. Replays of those keys which are already in flight
+    /// from other domains still fill the reader when they arrive.
+    fn release_reader_replays(&mut self, node: LocalNodeIndex, keys: &[KeyComparison]) {
+        if let Some(requested) = self.reader_triggered.get_mut(node) {
+            for key in keys {
+                requested.remove(key);
+            }
+        }
+
+        let released: HashSet<&KeyComparison> = keys.iter().collect();
+        let replay_paths = &self.replay_paths;
+        let into_reader = |tag: Tag| {
+            replay_paths
+                .get(tag)
+                .map_or(false, |path| path.last_segment().node == node)
+        };
+        for waiting in self.waiting.values_mut() {
+            waiting
+                .release_redos(|redo| into_reader(redo.tag) && released.contains(&redo.replay_key));
+        }
+        self.delayed_for_self.retain_mut(|packet| match packet {
+            Packet::RequestPartialReplay { tag, keys, .. } if into_reader(*tag) => {
+                keys.retain(|key| !released.contains(key));
+                !keys.is_empty()
+            }
+            _ => true,
+        });
+
+        self.pending_reader_replays.record_fills(node, keys);
+        if let Some(wh) = self.reader_write_handles.get(node) {
+            wh.finish_replays(keys);
+        }
+    }
+
     fn seed_row(&self, source: LocalNodeIndex, row: Cow<[DfValue]>) -> ReadySetResult<Record> {
         if let Some(&(start, ref defaults)) = self.ingress_inject.get(source) {
             let mut v = Vec::with_capacity(start + defaults.len());
@@ -2860,6 +3528,7 @@ impl Domain {
                         unishard, // if we are the only source, only one path
                         requesting_shard,
                         requesting_replica,
+                        timings: vec![],
                     },
                     data: records.into(),
                     cache_name,
@@ -2895,6 +3564,8 @@ impl Domain {
         let mut finished = None;
         let mut need_replay = Vec::new();
         let mut finished_partial = 0;
+        // only pay for timing replays if someone is going to look at the timings
+        let replay_started = self.slow_replay_threshold.map(|_| time::Instant::now());
 
         // this loop is just here so we have a way of giving up the borrow of self.replay_paths
         #[allow(clippy::never_loop)]
@@ -2952,6 +3623,9 @@ impl Domain {
                 );
             } else {
                 debug!(num = data.len(), "replaying batch");
+                if notify_done {
+                    *self.full_replay_rows.entry(path.last().node).or_default() += data.len();
+                }
             }
 
             // let's collect some information about the destination of this replay
@@ -3193,22 +3867,43 @@ impl Domain {
                     }
                 }
 
+                // if this node is about to send the replay on to another domain, record how long
+                // we spent handling it
+                if n.is_sender() {
+                    if let (Some(started), Some(timings)) =
+                        (replay_started, replay_context!(m, timings))
+                    {
+                        timings.push(ReplayDomainTiming {
+                            domain: self.index,
+                            shard: self.shard.unwrap_or(0),
+                            duration: started.elapsed(),
+                        });
+                    }
+                }
+
                 // process the current message in this node
-                let process_result = n.process(
-                    &mut m,
-                    cols,
-                    Some(rp),
-                    false,
-                    ProcessEnv {
-                        state: &mut self.state,
-                        reader_write_handles: &mut self.reader_write_handles,
-                        nodes: &self.nodes,
-                        executor: ex,
-                        shard: self.shard,
-                        replica: self.replica,
-                        auxiliary_node_states: &mut self.auxiliary_node_states,
-                    },
-                )?;
+                let process_started = self.profile_replays.then(time::Instant::now);
+                let process_result = n
+                    .process(
+                        &mut m,
+                        cols,
+                        Some(rp),
+                        false,
+                        ProcessEnv {
+                            state: &mut self.state,
+                            reader_write_handles: &mut self.reader_write_handles,
+                            nodes: &self.nodes,
+                            executor: ex,
+                            shard: self.shard,
+                            replica: self.replica,
+                            auxiliary_node_states: &mut self.auxiliary_node_states,
+                        },
+                    )
+                    .map_err(|e| e.in_dataflow(node_context(&n, Some(tag))))?;
+                if let Some(started) = process_started {
+                    self.metrics
+                        .rec_replay_node_time(&cache_name, &n, started.elapsed());
+                }
 
                 let misses = process_result.unique_misses();
 
@@ -3241,7 +3936,20 @@ impl Domain {
                         // we filled a hole! swap the reader.
                         if let Some(wh) = self.reader_write_handles.get_mut(segment.node) {
                             wh.swap();
-                            wh.notify_readers()?;
+                            if let Some(backfill_keys) = &backfill_keys {
+                                wh.finish_replays(backfill_keys);
+                                wh.track_negative_entries(backfill_keys);
+                            }
+                            // enforce the maximum number of negative entries right away, rather
+                            // than waiting for the next timeout
+                            let evicted = wh.evict_negative_entries();
+                            if evicted > 0 {
+                                wh.swap();
+                                wh.notify_readers_of_eviction()?;
+                                self.metrics.inc_negative_entry_evictions(n.name(), evicted);
+                            } else {
+                                wh.notify_readers()?;
+                            }
                         }
 
                         // and also unmark the replay request
@@ -3252,6 +3960,58 @@ impl Domain {
                                 }
                             }
                         }
+                        if let Some(backfill_keys) = &backfill_keys {
+                            self.pending_reader_replays
+                                .record_fills(segment.node, backfill_keys);
+                            let fills =
+                                self.reader_misses.record_fills(segment.node, backfill_keys);
+                            if let Some(wh) = self.reader_write_handles.get(segment.node) {
+                                for (_, duration) in &fills {
+                                    wh.stats().record_replay(*duration);
+                                }
+                            }
+
+                            if let (Some(threshold), Some(started)) =
+                                (self.slow_replay_threshold, replay_started)
+                            {
+                                let mut domain_timings = replay_context!(m, timings)
+                                    .map(|timings| timings.clone())
+                                    .unwrap_or_default();
+                                domain_timings.push(ReplayDomainTiming {
+                                    domain: self.index,
+                                    shard: self.shard.unwrap_or(0),
+                                    duration: started.elapsed(),
+                                });
+                                for (key, duration) in fills {
+                                    if duration < threshold {
+                                        continue;
+                                    }
+                                    let replay = ReaderSlowReplay {
+                                        reader: n.global_addr(),
+                                        view: n.name().clone(),
+                                        key: key.clone(),
+                                        tag: u32::from(tag),
+                                        completed_at: time::SystemTime::now(),
+                                        duration,
+                                        domain_timings: domain_timings.clone(),
+                                    };
+                                    if self.log_slow_replays {
+                                        warn!(
+                                            view = %replay.view.display_unquoted(),
+                                            key = ?Sensitive(&replay.key),
+                                            %tag,
+                                            ?duration,
+                                            domain_timings = ?replay.domain_timings,
+                                            "Slow replay"
+                                        );
+                                    }
+                                    self.slow_replays.push_back(replay);
+                                    if self.slow_replays.len() > MAX_TRACKED_SLOW_REPLAYS {
+                                        self.slow_replays.pop_front();
+                                    }
+                                }
+                            }
+                        }
                     }
                 }
 
@@ -3791,15 +4551,15 @@ impl Domain {
                         });
                 }
 
-                if !waiting.holes.is_empty() {
-                    // there are still holes, so there must still be pending redos
-                    assert!(!waiting.redos.is_empty());
+                if !waiting.redos.is_empty() {
+                    // there are still holes filling, though if all the redos waiting on them were
+                    // released there may not be any redos left
 
                     // restore Waiting in case seeding triggers more replays
                     self.waiting.insert(dst, waiting);
                 } else {
                     // there are no more holes that are filling, so there can't be more redos
-                    assert!(waiting.redos.is_empty());
+                    assert!(waiting.holes.is_empty());
                 }
                 return Ok(());
             } else if for_keys.is_some() {
@@ -3951,6 +4711,7 @@ impl Domain {
             shard: Option<usize>,
             replica: usize,
             state: &mut StateMap,
+            auxiliary_node_states: &mut AuxiliaryNodeStateMap,
             reader_write_handles: &mut NodeMap<backlog::WriteHandle>,
             nodes: &DomainNodes,
             remapped_keys: &mut RemappedKeys,
@@ -4003,6 +4764,12 @@ impl Domain {
                         #[allow(clippy::indexing_slicing)] // nodes in replay paths must exist
                         if let Some(result) = state[dest.node].evict_keys(tag, &keys) {
                             bytes_freed += result.bytes_freed;
+                            #[allow(clippy::indexing_slicing)] // nodes in replay paths must exist
+                            nodes[dest.node].borrow().evict_auxiliary_state(
+                                auxiliary_node_states,
+                                &result.index.columns,
+                                &keys,
+                            );
                             #[allow(clippy::unwrap_used)]
                             // we can only evict from partial replay paths, so we must have a
                             // partial key
@@ -4016,6 +4783,7 @@ impl Domain {
                                 shard,
                                 replica,
                                 state,
+                                auxiliary_node_states,
                                 reader_write_handles,
                                 nodes,
                                 remapped_keys,
@@ -4023,8 +4791,15 @@ impl Domain {
                         }
                     }
                     TriggerEndpoint::Start(_) => {
-                        if let Some(result) = state[path.source.unwrap()].evict_keys(tag, &keys) {
+                        let source = path.source.unwrap();
+                        if let Some(result) = state[source].evict_keys(tag, &keys) {
                             bytes_freed += result.bytes_freed;
+                            #[allow(clippy::indexing_slicing)] // nodes in replay paths must exist
+                            nodes[source].borrow().evict_auxiliary_state(
+                                auxiliary_node_states,
+                                &result.index.columns,
+                                &keys,
+                            );
                         }
                     }
                     _ => (),
@@ -4182,6 +4957,11 @@ impl Domain {
                             .collect::<ReadySetResult<Vec<_>>>()?;
 
                         freed += bytes_freed;
+                        n.evict_auxiliary_state(
+                            &mut self.auxiliary_node_states,
+                            &index.columns,
+                            &keys,
+                        );
                         if !keys.is_empty() {
                             let index = index.clone();
                             freed += trigger_downstream_evictions(
@@ -4194,6 +4974,7 @@ impl Domain {
                                 self.shard,
                                 self.replica,
                                 &mut self.state,
+                                &mut self.auxiliary_node_states,
                                 &mut self.reader_write_handles,
                                 &self.nodes,
                                 &mut self.remapped_keys,
@@ -4265,6 +5046,11 @@ impl Domain {
                         #[allow(clippy::indexing_slicing)] // came from replay paths
                         if let Some(result) = self.state[destination].evict_keys(tag, &keys) {
                             let mut freed = result.bytes_freed;
+                            self.nodes[destination].borrow().evict_auxiliary_state(
+                                &mut self.auxiliary_node_states,
+                                &result.index.columns,
+                                &keys,
+                            );
                             freed += trigger_downstream_evictions(
                                 &index,
                                 &keys[..],
@@ -4275,6 +5061,7 @@ impl Domain {
                                 self.shard,
                                 self.replica,
                                 &mut self.state,
+                                &mut self.auxiliary_node_states,
                                 &mut self.reader_write_handles,
                                 &self.nodes,
                                 &mut self.remapped_keys,
@@ -4368,6 +5155,11 @@ impl Domain {
                             };
                             let key = KeyComparison::try_from(key_evicted.clone())
                                 .map_err(|_| internal_err!("Empty key evicted"))?;
+                            n.evict_auxiliary_state(
+                                &mut self.auxiliary_node_states,
+                                &index.columns,
+                                std::slice::from_ref(&key),
+                            );
 
                             let index = index.clone();
                             let freed = trigger_downstream_evictions(
@@ -4380,6 +5172,7 @@ impl Domain {
                                 self.shard,
                                 self.replica,
                                 &mut self.state,
+                                &mut self.auxiliary_node_states,
                                 &mut self.reader_write_handles,
                                 &self.nodes,
                                 &mut self.remapped_keys,
@@ -4410,6 +5203,43 @@ impl Domain {
         }
     }
 
+    /// See [`Config::prioritize_replays`]
+    pub fn prioritize_replays(&self) -> bool {
+        self.prioritize_replays
+    }
+
+    /// See [`Config::max_consecutive_replay_packets`]
+    pub fn max_consecutive_replay_packets(&self) -> usize {
+        self.max_consecutive_replay_packets
+    }
+
+    /// Record the number of packets this domain currently has queued to be handled, to detect when
+    /// it's overloaded (see [`Config::overload_queue_depth`] and [`Config::overload_lag`])
+    pub fn record_queued_packets(&self, queued_packets: usize) {
+        self.load.record_queued_packets(queued_packets)
+    }
+
+    /// Returns `true` if this domain is in the middle of a full replay, is waiting on the results
+    /// of any upqueries, or has packets queued to be handled by itself
+    pub fn has_in_flight_replays(&self) -> bool {
+        !matches!(self.mode, DomainMode::Forwarding)
+            || !self.waiting.is_empty()
+            || self.reader_triggered.values().any(|keys| !keys.is_empty())
+            || !self.delayed_for_self.is_empty()
+    }
+
+    /// Flush and sync the WAL of the persistent state of every base table in this domain, so that
+    /// all writes processed so far and their replication offsets are persisted to disk
+    pub fn flush_persistent_state(&self) -> ReadySetResult<()> {
+        for (idx, state) in self.state.iter() {
+            if let Some(state) = state.as_persistent() {
+                trace!(local = %idx, "flushing persistent state");
+                state.flush_and_sync_wal()?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn update_state_sizes(&mut self) {
         let mut reader_size: u64 = 0;
         let total: u64 = self
@@ -4512,18 +5342,27 @@ impl Domain {
             .collect()
     }
 
-    /// If there is a pending timed purge, return the duration until it needs
-    /// to happen
+    /// If there is a pending timed purge, negative entry expiry, or check for orphaned replays,
+    /// return the duration until it needs to happen
     pub fn next_poll_duration(&mut self) -> Option<time::Duration> {
         // when do we need to be woken up again?
         let now = time::Instant::now();
-        self.timed_purges.front().map(|tp| {
-            if tp.time > now {
-                tp.time - now
-            } else {
-                time::Duration::from_millis(0)
-            }
-        })
+        let negative_entry_expiry = if self.negative_entry_limits.ttl.is_some() {
+            self.reader_write_handles
+                .values()
+                .filter_map(|wh| wh.next_negative_entry_expiry())
+                .min()
+        } else {
+            None
+        };
+        self.timed_purges
+            .front()
+            .map(|tp| tp.time)
+            .into_iter()
+            .chain(negative_entry_expiry)
+            .chain(self.pending_reader_replays.next_check())
+            .min()
+            .map(|time| time.saturating_duration_since(now))
     }
 
     /// Handle a single message for this domain
@@ -4532,6 +5371,20 @@ impl Domain {
         &mut self,
         packet: Packet,
         executor: &mut dyn Executor,
+    ) -> ReadySetResult<()> {
+        self.process_packet(packet, executor).map_err(|e| {
+            e.in_dataflow(DataflowContext {
+                domain: Some(self.index.index()),
+                shard: self.shard,
+                ..Default::default()
+            })
+        })
+    }
+
+    fn process_packet(
+        &mut self,
+        packet: Packet,
+        executor: &mut dyn Executor,
     ) -> ReadySetResult<()> {
         if self.wait_time.is_running() {
             self.wait_time.stop();
@@ -4566,6 +5419,18 @@ impl Domain {
             self.handle_timed_purges()?;
         }
 
+        if self.negative_entry_limits.ttl.is_some() {
+            self.expire_negative_entries()?;
+        }
+
+        if self
+            .pending_reader_replays
+            .next_check()
+            .map_or(false, |check| check <= time::Instant::now())
+        {
+            self.reclaim_orphaned_replays();
+        }
+
         if self.aggressively_update_state_sizes {
             self.update_state_sizes();
         }
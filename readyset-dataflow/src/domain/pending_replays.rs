@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use readyset_client::KeyComparison;
+
+use crate::prelude::*;
+
+/// The time at which replays of keys were last requested by partially materialized readers in a
+/// domain, used to find replays which have been in flight for longer than
+/// [`Config::orphaned_replay_timeout`](super::Config::orphaned_replay_timeout) without any reads
+/// still waiting for them, so that the domain can stop tracking them.
+///
+/// Only equality keys are tracked, since those are the only keys for which readers track the
+/// reads waiting on them.
+#[derive(Debug, Default)]
+pub(super) struct PendingReaderReplays {
+    /// If unset, no replays are tracked
+    timeout: Option<Duration>,
+    requested: HashMap<(LocalNodeIndex, KeyComparison), Instant>,
+    /// The next time any of the replays in `requested` may have exceeded the timeout
+    next_check: Option<Instant>,
+}
+
+impl PendingReaderReplays {
+    pub(super) fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            timeout,
+            ..Default::default()
+        }
+    }
+
+    /// Record that replays of the given keys were requested by the given reader node, either for
+    /// the first time or again by a read which was coalesced into a replay already in flight
+    pub(super) fn record_requests<'a, I>(&mut self, node: LocalNodeIndex, keys: I)
+    where
+        I: IntoIterator<Item = &'a KeyComparison>,
+    {
+        let Some(timeout) = self.timeout else {
+            return;
+        };
+        let now = Instant::now();
+        for key in keys.into_iter().filter(|key| key.is_equal()) {
+            self.requested.insert((node, key.clone()), now);
+        }
+        if self.next_check.is_none() && !self.requested.is_empty() {
+            self.next_check = Some(now + timeout);
+        }
+    }
+
+    /// Record that the given keys were filled in the given reader node, or that their replays were
+    /// released
+    pub(super) fn record_fills<'a, I>(&mut self, node: LocalNodeIndex, keys: I)
+    where
+        I: IntoIterator<Item = &'a KeyComparison>,
+    {
+        if self.requested.is_empty() {
+            return;
+        }
+        for key in keys {
+            self.requested.remove(&(node, key.clone()));
+        }
+    }
+
+    /// Returns the next time [`Self::take_orphaned`] needs to be called, if any replays are
+    /// pending
+    pub(super) fn next_check(&self) -> Option<Instant> {
+        self.next_check
+    }
+
+    /// Stop tracking, and return, all the replays which were last requested longer than the
+    /// timeout ago and for which `has_waiters` returns false. Replays which still have reads
+    /// waiting for them are considered requested again as of now.
+    pub(super) fn take_orphaned<F>(
+        &mut self,
+        mut has_waiters: F,
+    ) -> HashMap<LocalNodeIndex, Vec<KeyComparison>>
+    where
+        F: FnMut(LocalNodeIndex, &KeyComparison) -> bool,
+    {
+        let mut orphaned: HashMap<LocalNodeIndex, Vec<KeyComparison>> = HashMap::new();
+        let Some(timeout) = self.timeout else {
+            return orphaned;
+        };
+        let now = Instant::now();
+        let mut oldest: Option<Instant> = None;
+        self.requested.retain(|(node, key), requested_at| {
+            if now.duration_since(*requested_at) >= timeout {
+                if !has_waiters(*node, key) {
+                    orphaned.entry(*node).or_default().push(key.clone());
+                    return false;
+                }
+                *requested_at = now;
+            }
+            oldest = Some(oldest.map_or(*requested_at, |oldest| oldest.min(*requested_at)));
+            true
+        });
+        self.next_check = oldest.map(|oldest| oldest + timeout);
+        orphaned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use readyset_data::DfValue;
+    use vec1::vec1;
+
+    use super::*;
+
+    fn key(v: i32) -> KeyComparison {
+        KeyComparison::Equal(vec1![DfValue::from(v)])
+    }
+
+    #[test]
+    fn disabled_without_timeout() {
+        let mut pending = PendingReaderReplays::new(None);
+        pending.record_requests(LocalNodeIndex::make(0), &[key(1)]);
+        assert_eq!(pending.next_check(), None);
+        assert!(pending.take_orphaned(|_, _| false).is_empty());
+    }
+
+    #[test]
+    fn takes_orphaned_replays() {
+        let node = LocalNodeIndex::make(0);
+        let mut pending = PendingReaderReplays::new(Some(Duration::ZERO));
+        pending.record_requests(node, &[key(1), key(2), key(3)]);
+        assert!(pending.next_check().is_some());
+        pending.record_fills(node, &[key(3)]);
+
+        let orphaned = pending.take_orphaned(|_, k| *k == key(2));
+        assert_eq!(orphaned.get(&node), Some(&vec![key(1)]));
+        assert!(pending.next_check().is_some());
+
+        let orphaned = pending.take_orphaned(|_, _| false);
+        assert_eq!(orphaned.get(&node), Some(&vec![key(2)]));
+        assert_eq!(pending.next_check(), None);
+    }
+}
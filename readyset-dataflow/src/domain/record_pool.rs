@@ -0,0 +1,131 @@
+use crate::prelude::*;
+
+/// Buffers with a capacity above this many records aren't returned to the pool, so that a single
+/// large packet (for example a full replay) doesn't keep its buffer alive for the lifetime of the
+/// domain
+const MAX_POOLED_CAPACITY: usize = 4096;
+
+/// A pool of emptied record buffers, used by a domain to recycle the allocations of the data of
+/// packets which have been fully consumed (by readers, or by operators which emitted nothing) into
+/// the data of the packets it copies when forwarding a write to more than one child.
+///
+/// Packets themselves are moved by value rather than boxed, so their record buffers are the only
+/// per-packet allocations there are to pool.
+///
+/// A pool with a size of 0 never holds on to any buffers, falling back to plain allocation for
+/// every packet.
+#[derive(Debug, Default)]
+pub(super) struct RecordPool {
+    buffers: Vec<Records>,
+    /// The maximum number of buffers to hold on to
+    size: usize,
+}
+
+impl RecordPool {
+    pub(super) fn new(size: usize) -> Self {
+        Self {
+            buffers: Vec::with_capacity(size),
+            size,
+        }
+    }
+
+    /// Returns an empty buffer with room for at least `capacity` records, reusing a pooled buffer
+    /// if there is one
+    pub(super) fn take(&mut self, capacity: usize) -> Records {
+        match self.buffers.pop() {
+            Some(mut buffer) => {
+                buffer.reserve(capacity);
+                buffer
+            }
+            None => Vec::with_capacity(capacity).into(),
+        }
+    }
+
+    /// Clone `records` into a buffer taken from the pool
+    pub(super) fn clone_records(&mut self, records: &Records) -> Records {
+        let mut buffer = self.take(records.len());
+        buffer.extend(records.iter().cloned());
+        buffer
+    }
+
+    /// Return the allocation of `records` to the pool, dropping any records left in it
+    pub(super) fn recycle(&mut self, mut records: Records) {
+        if self.buffers.len() < self.size
+            && records.capacity() > 0
+            && records.capacity() <= MAX_POOLED_CAPACITY
+        {
+            records.clear();
+            self.buffers.push(records);
+        }
+    }
+}
+
+#[cfg(feature = "bench")]
+pub mod bench {
+    use criterion::measurement::Measurement;
+    use criterion::{black_box, BenchmarkId, Criterion};
+
+    use super::*;
+
+    /// Forward a packet of each size to two children, one of which gets a copy of its data, and
+    /// consume both copies the way a reader would
+    pub fn fanout_records<M: Measurement>(c: &mut Criterion<M>) {
+        let mut group = c.benchmark_group("fanout_records");
+        for size in [1, 16, 128, 1024] {
+            let records: Records = (0..size)
+                .map(|i| vec![DfValue::from(i as i64), DfValue::from(format!("row {i}"))])
+                .collect();
+            for pool_size in [0, 64] {
+                let mut pool = RecordPool::new(pool_size);
+                let name = if pool_size == 0 { "plain" } else { "pooled" };
+                group.bench_with_input(BenchmarkId::new(name, size), &records, |b, records| {
+                    b.iter(|| {
+                        let mut copy = pool.clone_records(records);
+                        black_box(copy.drain(..).count());
+                        pool.recycle(copy);
+                    })
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(n: i32) -> Records {
+        (0..n).map(|i| vec![DfValue::from(i)]).collect()
+    }
+
+    #[test]
+    fn reuses_recycled_buffers() {
+        let mut pool = RecordPool::new(1);
+        let mut buffer = pool.clone_records(&records(10));
+        assert_eq!(buffer, records(10));
+        let ptr = buffer.as_ptr();
+        buffer.truncate(2);
+        pool.recycle(buffer);
+
+        let buffer = pool.take(5);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn bounded_by_size() {
+        let mut pool = RecordPool::new(1);
+        pool.recycle(records(1));
+        pool.recycle(records(1));
+        pool.recycle(Vec::with_capacity(MAX_POOLED_CAPACITY + 1).into());
+        assert_eq!(pool.buffers.len(), 1);
+    }
+
+    #[test]
+    fn disabled_with_size_zero() {
+        let mut pool = RecordPool::new(0);
+        pool.recycle(records(3));
+        assert!(pool.buffers.is_empty());
+    }
+}
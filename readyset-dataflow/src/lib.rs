@@ -30,6 +30,7 @@ pub mod node;
 pub mod ops;
 pub mod payload; // it makes me _really_ sad that this has to be pub
 pub mod prelude;
+pub mod profile;
 pub mod utils;
 
 mod domain;
@@ -43,7 +44,10 @@ use std::sync::{Arc, Mutex};
 use readyset_client::ReaderAddress;
 use serde::{Deserialize, Serialize};
 
-pub use crate::backlog::{LookupError, ReaderUpdatedNotifier, SingleReadHandle};
+pub use crate::backlog::{
+    LookupError, MissPermit, ReadHistory, ReadThrottle, ReaderStats, ReaderUpdatedNotifier,
+    ReplayWaitGuard, SingleReadHandle, StaleValues,
+};
 
 /// A [`ReaderMap`] maps a [`ReaderAddress`] to the [`SingleReadHandle`] to access the reader at
 /// that address.
@@ -52,6 +56,11 @@ pub use crate::backlog::{LookupError, ReaderUpdatedNotifier, SingleReadHandle};
 pub struct ReaderMap(HashMap<ReaderAddress, SingleReadHandle>);
 pub type Readers = Arc<Mutex<ReaderMap>>;
 
+/// The fully materialized states shared between the co-located shards of nodes when
+/// [`DomainConfig::share_full_state_between_shards`] is set, keyed by the global index of the node
+/// and the replica of its domain. Owned by a worker and shared by all the domains it runs.
+pub type SharedStates = dataflow_state::SharedMemoryStates<(petgraph::graph::NodeIndex, usize)>;
+
 pub type DomainConfig = domain::Config;
 
 pub use dataflow_expression::{
@@ -63,10 +72,16 @@ pub use dataflow_state::{
 };
 
 pub use crate::domain::channel::{ChannelCoordinator, DomainReceiver, DomainSender, DualTcpStream};
-pub use crate::domain::{Domain, DomainBuilder, DomainIndex};
+#[cfg(feature = "bench")]
+pub use crate::domain::record_pool_bench;
+pub use crate::domain::{
+    Domain, DomainBuilder, DomainIndex, DEFAULT_MAX_CONSECUTIVE_REPLAY_PACKETS,
+    DEFAULT_RECORD_POOL_SIZE,
+};
 pub use crate::node_map::NodeMap;
 pub use crate::payload::{DomainRequest, Packet, PacketDiscriminants};
 pub use crate::processing::LookupIndex;
+pub use crate::profile::StateProfile;
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum Sharding {
@@ -171,6 +171,10 @@ impl Node {
                 }
                 None => format!("{} / -", idx.index()),
             };
+            let addr = match self.stable_id {
+                Some(id) => format!("{} / {}", addr, id),
+                None => addr,
+            };
 
             match self.inner {
                 NodeType::Source => s.push_str("(source)"),
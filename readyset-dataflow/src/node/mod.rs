@@ -1,12 +1,23 @@
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 
-use nom_sql::{ColumnSpecification, Relation, SqlIdentifier};
+use lazy_static::lazy_static;
+use nom_sql::{ColumnConstraint, ColumnSpecification, Relation, SqlIdentifier};
 use readyset_client::consistency::Timestamp;
-use readyset_data::{DfType, Dialect};
+use readyset_client::KeyComparison;
+use readyset_data::dialect::SqlEngine;
+use readyset_data::{Collation, DfType, Dialect};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+use crate::ops::fulltext::FullTextSearchState;
 use crate::ops::grouped::aggregate::AggregatorState;
+use crate::ops::grouped::approx_distinct::ApproxCountDistinctState;
 use crate::ops::grouped::concat::GroupConcatState;
+use crate::ops::grouped::extremum::ExtremumState;
+use crate::ops::grouped::EvictedGroups;
+use crate::ops::parallel::OperatorWorkers;
 use crate::ops::{self};
 use crate::prelude::*;
 use crate::processing::LookupIndex;
@@ -27,6 +38,12 @@ mod debug;
 #[cfg(feature = "bench")]
 pub use process::bench;
 
+/// Marker which, anywhere in the `COMMENT` of a MySQL `BINARY(16)` column, opts that column in to
+/// storing its values as [`DfType::Uuid`]s rather than as raw bytes. Since MySQL has no native UUID
+/// type, UUIDs are conventionally stored in `BINARY(16)` columns (eg via `UUID_TO_BIN`), but we
+/// can't tell those columns apart from ones holding arbitrary binary data.
+pub const MYSQL_UUID_COLUMN_MARKER: &str = "readyset:uuid";
+
 // NOTE(jfrg): the migration code should probably move into the dataflow crate...
 // it is the reason why so much stuff here is pub
 
@@ -59,11 +76,33 @@ impl Column {
     where
         F: Fn(Relation) -> Option<DfType>,
     {
-        Ok(Self::new(
-            spec.column.name,
-            DfType::from_sql_type(&spec.sql_type, dialect, resolve_type)?,
-            spec.column.table,
-        ))
+        let mut ty = DfType::from_sql_type(&spec.sql_type, dialect, resolve_type)?;
+        if dialect.engine() == SqlEngine::MySQL && ty.is_any_text() {
+            // MySQL text columns compare according to their collation, which is either given
+            // explicitly or is the default collation of the column's character set
+            let collation = spec.constraints.iter().find_map(|c| match c {
+                ColumnConstraint::Collation(name) => Collation::from_mysql_collation(name),
+                _ => None,
+            });
+            let charset_collation = spec.constraints.iter().find_map(|c| match c {
+                ColumnConstraint::CharacterSet(name) => Some(Collation::from_mysql_charset(name)),
+                _ => None,
+            });
+            if let Some(collation) = collation.or(charset_collation) {
+                ty = ty.with_collation(collation);
+            }
+        }
+        if dialect.engine() == SqlEngine::MySQL
+            && ty == DfType::Binary(16)
+            && spec
+                .comment
+                .as_deref()
+                .is_some_and(|comment| comment.contains(MYSQL_UUID_COLUMN_MARKER))
+        {
+            ty = DfType::Uuid;
+        }
+
+        Ok(Self::new(spec.column.name, ty, spec.column.table))
     }
 
     /// Column name
@@ -116,6 +155,11 @@ pub struct Node {
     // We skip serde since we don't want the state of the node, just the configuration.
     #[serde(skip)]
     timestamps: HashMap<LocalNodeIndex, Timestamp>,
+
+    /// The stable identifier assigned to this node when it was added to the graph, if any. See
+    /// [`StableNodeId`].
+    #[serde(default)]
+    stable_id: Option<StableNodeId>,
 }
 
 // constructors
@@ -141,6 +185,7 @@ impl Node {
 
             sharded_by: Sharding::None,
             timestamps: HashMap::new(),
+            stable_id: None,
         }
     }
 
@@ -152,13 +197,14 @@ impl Node {
         Self::new(name, self.columns.clone(), n)
     }
 
-    /// Duplicates the existing node, clearing the index, taken flag, and timestamps
+    /// Duplicates the existing node, clearing the index, taken flag, timestamps, and stable id
     /// Used to create fully materialized duplicates of partially materialized nodes
     pub fn duplicate(&self) -> Node {
         Self {
             index: None,
             taken: false,
             timestamps: HashMap::new(),
+            stable_id: None,
             ..self.clone()
         }
     }
@@ -191,6 +237,24 @@ impl DanglingDomainNode {
 pub enum AuxiliaryNodeState {
     Aggregation(AggregatorState),
     Concat(GroupConcatState),
+    ApproxCountDistinct(ApproxCountDistinctState),
+    Extremum(ExtremumState),
+    FullTextSearch(FullTextSearchState),
+}
+
+impl AuxiliaryNodeState {
+    /// Forget any state kept for the given groups, which were evicted from the node's
+    /// materialized state
+    pub(crate) fn evict_groups(&mut self, evicted: &EvictedGroups) {
+        match self {
+            AuxiliaryNodeState::Aggregation(state) => state.evict_groups(evicted),
+            AuxiliaryNodeState::Concat(state) => state.evict_groups(evicted),
+            AuxiliaryNodeState::ApproxCountDistinct(state) => state.evict_groups(evicted),
+            AuxiliaryNodeState::Extremum(state) => state.evict_groups(evicted),
+            // Full-text search nodes are always fully materialized, so never evict anything
+            AuxiliaryNodeState::FullTextSearch(_) => {}
+        }
+    }
 }
 
 // external parts of Ingredient
@@ -363,15 +427,31 @@ impl Node {
         self.inner.description(detailed)
     }
 
-    pub fn initial_auxiliary_state(&self) -> Option<AuxiliaryNodeState> {
+    /// Construct the initial [`AuxiliaryNodeState`] for this node, if it needs any.
+    ///
+    /// `max_tracked_extremum_values` bounds the number of values per group tracked by extremum
+    /// nodes (see [`ExtremumState`]).
+    pub fn initial_auxiliary_state(
+        &self,
+        max_tracked_extremum_values: usize,
+    ) -> Option<AuxiliaryNodeState> {
         match &self.inner {
             NodeType::Internal(no) => match no {
                 NodeOperator::Aggregation(_) => {
                     Some(AuxiliaryNodeState::Aggregation(Default::default()))
                 }
                 NodeOperator::Concat(_) => Some(AuxiliaryNodeState::Concat(Default::default())),
-                NodeOperator::Extremum(_)
-                | NodeOperator::Join(_)
+                NodeOperator::ApproxCountDistinct(_) => {
+                    Some(AuxiliaryNodeState::ApproxCountDistinct(Default::default()))
+                }
+                NodeOperator::Extremum(_) => Some(AuxiliaryNodeState::Extremum(
+                    ExtremumState::new(max_tracked_extremum_values),
+                )),
+                NodeOperator::FullTextSearch(_) => {
+                    Some(AuxiliaryNodeState::FullTextSearch(Default::default()))
+                }
+                NodeOperator::Join(_)
+                | NodeOperator::Latest(_)
                 | NodeOperator::Paginate(_)
                 | NodeOperator::Project(_)
                 | NodeOperator::Union(_)
@@ -388,6 +468,40 @@ impl Node {
             | NodeType::Dropped => None,
         }
     }
+
+    /// Forget any [`AuxiliaryNodeState`] kept by this node for the given `keys`, which were
+    /// evicted from its index on `columns`
+    pub(crate) fn evict_auxiliary_state(
+        &self,
+        auxiliary_node_states: &mut AuxiliaryNodeStateMap,
+        columns: &[usize],
+        keys: &[KeyComparison],
+    ) {
+        if keys.is_empty() {
+            return;
+        }
+        let Some(auxiliary_node_state) = auxiliary_node_states.get_mut(self.local_addr()) else {
+            return;
+        };
+        let group_len = match self.as_internal() {
+            Some(NodeOperator::Aggregation(op)) => op.group_by().len(),
+            Some(NodeOperator::Extremum(op)) => op.group_by().len(),
+            Some(NodeOperator::Concat(op)) => op.group_by().len(),
+            Some(NodeOperator::ApproxCountDistinct(op)) => op.group_by().len(),
+            _ => return,
+        };
+        auxiliary_node_state.evict_groups(&EvictedGroups::new(group_len, columns, keys));
+    }
+
+    /// Allow this node to process large packets on the given pool of workers, if it's an operator
+    /// which handles each record independently of all the others
+    pub(crate) fn set_operator_workers(&mut self, workers: &OperatorWorkers) {
+        match self.as_mut_internal() {
+            Some(NodeOperator::Filter(f)) => f.set_workers(workers.clone()),
+            Some(NodeOperator::Project(p)) => p.set_workers(workers.clone()),
+            _ => {}
+        }
+    }
 }
 
 // publicly accessible attributes
@@ -413,6 +527,41 @@ impl Node {
     pub fn node_type_string(&self) -> String {
         self.inner.to_string()
     }
+
+    /// Returns the stable identifier assigned to this node, if it has been assigned one yet
+    pub fn stable_id(&self) -> Option<StableNodeId> {
+        self.stable_id
+    }
+
+    pub fn set_stable_id(&mut self, id: StableNodeId) {
+        self.stable_id = Some(id);
+    }
+
+    /// Derive a [`StableNodeId`] for this node from its contents and the stable identifiers of
+    /// its ancestors, `parents`.
+    ///
+    /// The contents which are hashed deliberately exclude anything that depends on the order nodes
+    /// were added to the graph in: node indices, and the counters included in the names of the
+    /// nodes generated for queries. This means that identifiers aren't necessarily unique (eg for
+    /// two identical projections of the same parent), so the caller is responsible for resolving
+    /// collisions via [`StableNodeId::rehash`].
+    pub fn content_id(&self, parents: &[StableNodeId]) -> StableNodeId {
+        lazy_static! {
+            static ref NODE_COUNTER_RE: Regex = Regex::new("_n[0-9]+").unwrap();
+        }
+
+        let mut hasher = DefaultHasher::new();
+        NODE_COUNTER_RE
+            .replace_all(&self.name.display_unquoted().to_string(), "")
+            .hash(&mut hasher);
+        self.inner.to_string().hash(&mut hasher);
+        for column in &self.columns {
+            column.name().hash(&mut hasher);
+            column.ty().to_string().hash(&mut hasher);
+        }
+        parents.hash(&mut hasher);
+        hasher.finish().into()
+    }
 }
 
 // events
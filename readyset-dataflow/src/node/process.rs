@@ -259,6 +259,7 @@ impl Node {
                                     requesting_shard,
                                     requesting_replica,
                                     unishard,
+                                    ..
                                 },
                             ..
                         } => {
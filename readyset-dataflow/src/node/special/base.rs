@@ -6,6 +6,7 @@ use std::convert::TryFrom;
 use dataflow_state::{MaterializedNodeState, PointKey, SnapshotMode};
 use itertools::Itertools;
 use nom_sql::Relation;
+use readyset_client::metrics::recorded;
 use readyset_client::{Modification, Operation, TableOperation};
 use readyset_data::{DfValue, DfValueKind};
 use readyset_errors::ReadySetResult;
@@ -13,7 +14,7 @@ use readyset_util::redacted::Sensitive;
 use readyset_util::Indices;
 use replication_offset::ReplicationOffset;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, error, trace};
+use tracing::{debug, error, trace, warn};
 use vec_map::VecMap;
 
 use crate::node::Column;
@@ -65,6 +66,10 @@ impl From<Records> for BaseWrite {
 pub struct Base {
     primary_key: Option<Box<[usize]>>,
     unique_keys: Vec<Box<[usize]>>,
+    /// Sets of columns which are indexed in the upstream database, used to choose which columns
+    /// to index this base by if it has no primary key
+    #[serde(default)]
+    index_hints: Vec<Box<[usize]>>,
 
     defaults: Vec<DfValue>,
     dropped: Vec<usize>,
@@ -103,6 +108,30 @@ impl Base {
         self
     }
 
+    /// Set the sets of columns which are indexed in the upstream database for this base. If the
+    /// base has no primary key, it will be indexed by the first of these.
+    pub fn with_index_hints<C: AsRef<[usize]>, K: IntoIterator<Item = C>>(
+        mut self,
+        index_hints: K,
+    ) -> Self {
+        self.set_index_hints(index_hints);
+        self
+    }
+
+    /// Replace the sets of columns which are indexed in the upstream database for this base. See
+    /// [`with_index_hints`](Self::with_index_hints)
+    pub fn set_index_hints<C: AsRef<[usize]>, K: IntoIterator<Item = C>>(
+        &mut self,
+        index_hints: K,
+    ) {
+        self.index_hints = index_hints.into_iter().map(|c| c.as_ref().into()).collect();
+    }
+
+    /// Return the sets of columns which are indexed in the upstream database for this base
+    pub fn index_hints(&self) -> &[Box<[usize]>] {
+        &self.index_hints
+    }
+
     pub fn primary_key(&self) -> Option<&[usize]> {
         self.primary_key.as_deref()
     }
@@ -240,6 +269,21 @@ impl Base {
             None => internal!("base nodes must always be materialized"),
         };
 
+        let discarded = discard_applied_ops(&mut ops, db.replication_offset())?;
+        if discarded > 0 {
+            warn!(
+                table = %name.display_unquoted(),
+                discarded,
+                replication_offset = ?db.replication_offset(),
+                "Discarding writes which were already applied to base table"
+            );
+            metrics::counter!(
+                recorded::BASE_TABLE_DISCARDED_WRITES,
+                discarded as u64,
+                "table_name" => name.display_unquoted().to_string()
+            );
+        }
+
         let key_cols = match &self.primary_key {
             Some(key) if !ops.is_empty() => key.as_ref(),
             _ => return self.process_unkeyed(db, ops),
@@ -442,10 +486,12 @@ impl Base {
     }
 
     pub(in crate::node) fn suggest_indexes(&self, n: NodeIndex) -> HashMap<NodeIndex, LookupIndex> {
-        if let Some(pk) = &self.primary_key {
+        // Without a primary key, index by the columns of an upstream index rather than letting the
+        // materialization planner make up an index
+        if let Some(key) = self.primary_key.as_ref().or(self.index_hints.first()) {
             HashMap::from([(
                 n,
-                LookupIndex::Strict(Index::hash_map(pk.as_ref().to_vec())),
+                LookupIndex::Strict(Index::hash_map(key.as_ref().to_vec())),
             )])
         } else {
             HashMap::new()
@@ -458,6 +504,7 @@ impl Default for Base {
         Base {
             primary_key: None,
             unique_keys: Vec::new(),
+            index_hints: Vec::new(),
             defaults: Vec::new(),
             dropped: Vec::new(),
             unmodified: true,
@@ -479,6 +526,50 @@ fn key_val(i: usize, col: usize, r: &TableOperation) -> Option<&DfValue> {
     }
 }
 
+/// Remove the operations in `ops` which were already applied to the base table, given the
+/// replication offset `applied` up to which it has applied writes, and return the number of
+/// operations removed.
+///
+/// Writes from replication are followed by a [`TableOperation::SetReplicationOffset`] for the
+/// position of those writes in the replication log, so any operations preceding an offset at or
+/// below `applied` are being redelivered (for example, after the replicator reconnects) and must
+/// not be applied again - in keyless tables, doing so would duplicate rows.
+fn discard_applied_ops(
+    ops: &mut Vec<TableOperation>,
+    applied: Option<&ReplicationOffset>,
+) -> ReadySetResult<usize> {
+    let Some(applied) = applied else {
+        return Ok(0);
+    };
+
+    // Offsets increase through a batch, so the operations which were already applied are all
+    // those up to the last offset at or below `applied`
+    let mut applied_up_to = 0;
+    for (i, op) in ops.iter().enumerate() {
+        if let TableOperation::SetReplicationOffset(offset) = op {
+            if offset.try_partial_cmp(applied)?.is_le() {
+                applied_up_to = i + 1;
+            }
+        }
+    }
+
+    if applied_up_to == 0 {
+        return Ok(0);
+    }
+
+    let mut discarded = 0;
+    let mut i = 0;
+    ops.retain(|op| {
+        let keep = i >= applied_up_to || matches!(op, TableOperation::SetSnapshotMode(_));
+        if !keep && !matches!(op, TableOperation::SetReplicationOffset(_)) {
+            discarded += 1;
+        }
+        i += 1;
+        keep
+    });
+    Ok(discarded)
+}
+
 fn key_of<'a>(key_cols: &'a [usize], r: &'a TableOperation) -> impl Iterator<Item = &'a DfValue> {
     key_cols
         .iter()
@@ -698,6 +789,29 @@ mod tests {
         assert!(b.unmodified);
     }
 
+    #[test]
+    fn suggests_index_hint_without_primary_key() {
+        let n = NodeIndex::new(0);
+        let index = |b: &Base| match b.suggest_indexes(n).remove(&n) {
+            Some(LookupIndex::Strict(index)) => Some(index.columns),
+            _ => None,
+        };
+
+        assert_eq!(index(&Base::new()), None);
+        assert_eq!(
+            index(&Base::new().with_index_hints([vec![2, 1], vec![0]])),
+            Some(vec![2, 1])
+        );
+        assert_eq!(
+            index(
+                &Base::new()
+                    .with_primary_key([0])
+                    .with_index_hints([vec![2, 1]])
+            ),
+            Some(vec![0])
+        );
+    }
+
     mod process {
         use dataflow_state::MaterializedNodeState;
         use readyset_data::DfType;
@@ -1127,6 +1241,54 @@ mod tests {
             );
         }
 
+        #[test]
+        fn redelivered_writes_unkeyed() {
+            use replication_offset::postgres::PostgresPosition;
+
+            let offset =
+                |lsn: i64| ReplicationOffset::from(PostgresPosition::commit_end(lsn.into()));
+
+            let mut b = Base::new();
+            let ni = LocalNodeIndex::make(0u32);
+            let mut state = MaterializedNodeState::Memory(MemoryState::default());
+            state
+                .process_records(&mut Records::default(), None, Some(offset(2)))
+                .unwrap();
+
+            let mut state_map = NodeMap::new();
+            state_map.insert(ni, state);
+
+            let table = Relation {
+                name: "test".into(),
+                schema: None,
+            };
+            let res = b
+                .process_ops(
+                    ni,
+                    &[],
+                    vec![
+                        TableOperation::Insert(vec![1.into()]),
+                        TableOperation::SetReplicationOffset(offset(1)),
+                        TableOperation::Insert(vec![2.into()]),
+                        TableOperation::SetReplicationOffset(offset(2)),
+                        TableOperation::Insert(vec![3.into()]),
+                        TableOperation::SetReplicationOffset(offset(3)),
+                    ],
+                    &state_map,
+                    SnapshotMode::SnapshotModeDisabled,
+                    table,
+                )
+                .unwrap();
+            assert_eq!(
+                res,
+                BaseWrite {
+                    records: vec![Record::Positive(vec![3.into()])].into(),
+                    replication_offset: Some(offset(3)),
+                    set_snapshot_mode: None
+                }
+            );
+        }
+
         #[test]
         fn citext_coercion() {
             use readyset_data::{Collation, TinyText};
@@ -90,6 +90,14 @@ impl Egress {
         self.packet_filter.add_for_filtering(target);
     }
 
+    /// Stop sending to the given ingress node (in all shards of its domain), and forget about any
+    /// replay paths which pass through it.
+    pub fn remove_tx(&mut self, ingress: NodeIndex) {
+        self.txs.retain(|_, tx| tx.node != ingress);
+        self.tags.retain(|_, dst| *dst != ingress);
+        self.packet_filter.remove_for_filtering(ingress);
+    }
+
     pub fn add_tag(&mut self, tag: Tag, dst: NodeIndex) {
         self.tags.insert(tag, dst);
     }
@@ -43,6 +43,12 @@ impl PacketFilter {
         self.requested_keys.entry(target).or_default();
     }
 
+    /// Removes a node from the filter list, along with all the keys requested for it. Used when the
+    /// target node is removed from the graph.
+    pub fn remove_for_filtering(&mut self, target: NodeIndex) {
+        self.requested_keys.remove(&target);
+    }
+
     /// Processes a given [`Packet`], provided with some contextual information.
     /// The processing modifies the packet in-place.
     ///
@@ -284,6 +290,7 @@ mod test {
                     requesting_shard: 0,
                     requesting_replica: 0,
                     unishard: false,
+                    timings: vec![],
                 },
                 cache_name: "test".into(),
             };
@@ -596,6 +603,7 @@ mod test {
                     requesting_shard: 0,
                     requesting_replica: 0,
                     unishard: false,
+                    timings: vec![],
                 }
             } else {
                 ReplayPieceContext::Full {
@@ -1,10 +1,12 @@
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 use dataflow_expression::ReaderProcessing;
 use failpoint_macros::failpoint;
 use metrics::histogram;
 use readyset_client::metrics::recorded;
-use readyset_client::{KeyColumnIdx, ViewPlaceholder};
+use readyset_client::{
+    KeyColumnIdx, ViewPlaceholder, ViewRateLimit, ViewResultLimit, ViewStaleReads,
+};
 use serde::{Deserialize, Serialize};
 use tracing::{trace, warn};
 
@@ -25,6 +27,39 @@ pub struct Reader {
     ///
     /// The data is stored in this manner instead of in a Hashmap to support ordered iteration.
     placeholder_map: Vec<(ViewPlaceholder, KeyColumnIdx)>,
+
+    /// Limits on the rate of reads against this reader, applied to each of its shards
+    #[serde(default)]
+    rate_limit: ViewRateLimit,
+
+    /// How long each shard of this reader retains the deltas applied to it for as-of reads, or
+    /// `None` if as-of reads aren't supported
+    #[serde(default)]
+    history_retention: Option<Duration>,
+
+    /// Whether reads against this reader may be answered with last-known, possibly-stale values,
+    /// applied to each of its shards
+    #[serde(default)]
+    stale_reads: ViewStaleReads,
+
+    /// Limits on the size of results of reads against this reader, applied to each of its shards
+    #[serde(default)]
+    result_limit: ViewResultLimit,
+
+    /// How long reads against this reader wait for misses to be filled by upqueries, applied to
+    /// each of its shards, or `None` to use the server's default
+    #[serde(default)]
+    upquery_timeout: Option<Duration>,
+
+    /// Sets of (non-key) columns of this reader to maintain secondary indices on, so that it can
+    /// be looked up by those columns. Only supported for fully materialized readers.
+    #[serde(default)]
+    secondary_indices: Vec<Vec<usize>>,
+
+    /// Whether the domain containing this reader should be run with a warm standby replica on a
+    /// different worker, for fast failover if the worker running the reader fails
+    #[serde(default)]
+    standby: bool,
 }
 
 impl Clone for Reader {
@@ -34,6 +69,13 @@ impl Clone for Reader {
             reader_processing: self.reader_processing.clone(),
             index: self.index.clone(),
             placeholder_map: self.placeholder_map.clone(),
+            rate_limit: self.rate_limit,
+            history_retention: self.history_retention,
+            stale_reads: self.stale_reads,
+            result_limit: self.result_limit,
+            upquery_timeout: self.upquery_timeout,
+            secondary_indices: self.secondary_indices.clone(),
+            standby: self.standby,
         }
     }
 }
@@ -45,6 +87,13 @@ impl Reader {
             reader_processing,
             index: None,
             placeholder_map: Default::default(),
+            rate_limit: Default::default(),
+            history_retention: None,
+            stale_reads: Default::default(),
+            result_limit: Default::default(),
+            upquery_timeout: None,
+            secondary_indices: Vec::new(),
+            standby: false,
         }
     }
 
@@ -60,6 +109,13 @@ impl Reader {
             reader_processing: self.reader_processing.clone(),
             index: self.index.clone(),
             placeholder_map: self.placeholder_map.clone(),
+            rate_limit: self.rate_limit,
+            history_retention: self.history_retention,
+            stale_reads: self.stale_reads,
+            result_limit: self.result_limit,
+            upquery_timeout: self.upquery_timeout,
+            secondary_indices: self.secondary_indices.clone(),
+            standby: self.standby,
         }
     }
 
@@ -115,6 +171,84 @@ impl Reader {
         self.placeholder_map.as_ref()
     }
 
+    /// Returns the limits on the rate of reads against this reader
+    pub fn rate_limit(&self) -> ViewRateLimit {
+        self.rate_limit
+    }
+
+    /// Set the limits on the rate of reads against this reader. This only takes effect for
+    /// shards of the reader created after it's set.
+    pub fn set_rate_limit(&mut self, rate_limit: ViewRateLimit) {
+        self.rate_limit = rate_limit;
+    }
+
+    /// Returns how long each shard of this reader retains deltas for as-of reads
+    pub fn history_retention(&self) -> Option<Duration> {
+        self.history_retention
+    }
+
+    /// Set how long each shard of this reader retains deltas for as-of reads. This only takes
+    /// effect for shards of the reader created after it's set.
+    pub fn set_history_retention(&mut self, history_retention: Option<Duration>) {
+        self.history_retention = history_retention;
+    }
+
+    /// Returns whether reads against this reader may be answered with possibly-stale values
+    pub fn stale_reads(&self) -> ViewStaleReads {
+        self.stale_reads
+    }
+
+    /// Set whether reads against this reader may be answered with possibly-stale values. This only
+    /// takes effect for shards of the reader created after it's set.
+    pub fn set_stale_reads(&mut self, stale_reads: ViewStaleReads) {
+        self.stale_reads = stale_reads;
+    }
+
+    /// Returns the limits on the size of results of reads against this reader
+    pub fn result_limit(&self) -> ViewResultLimit {
+        self.result_limit
+    }
+
+    /// Set the limits on the size of results of reads against this reader. This only takes effect
+    /// for shards of the reader created after it's set.
+    pub fn set_result_limit(&mut self, result_limit: ViewResultLimit) {
+        self.result_limit = result_limit;
+    }
+
+    /// Returns how long reads against this reader wait for misses to be filled by upqueries, if
+    /// configured
+    pub fn upquery_timeout(&self) -> Option<Duration> {
+        self.upquery_timeout
+    }
+
+    /// Set how long reads against this reader wait for misses to be filled by upqueries. This only
+    /// takes effect for shards of the reader created after it's set.
+    pub fn set_upquery_timeout(&mut self, upquery_timeout: Option<Duration>) {
+        self.upquery_timeout = upquery_timeout;
+    }
+
+    /// Returns the sets of columns of this reader which secondary indices are maintained on
+    pub fn secondary_indices(&self) -> &[Vec<usize>] {
+        &self.secondary_indices
+    }
+
+    /// Set the sets of columns of this reader to maintain secondary indices on. This only takes
+    /// effect for shards of the reader created after it's set.
+    pub fn set_secondary_indices(&mut self, secondary_indices: Vec<Vec<usize>>) {
+        self.secondary_indices = secondary_indices;
+    }
+
+    /// Returns whether the domain containing this reader should be run with a warm standby replica
+    pub fn standby(&self) -> bool {
+        self.standby
+    }
+
+    /// Set whether the domain containing this reader should be run with a warm standby replica.
+    /// This only takes effect if it's set before the reader's domain is first scheduled.
+    pub fn set_standby(&mut self, standby: bool) {
+        self.standby = standby;
+    }
+
     #[allow(clippy::unreachable)]
     #[failpoint("reader-handle-packet")]
     pub(in crate::node) fn process(
@@ -137,11 +271,19 @@ impl Reader {
                 }
             },
         );
+        if !m.is_regular() {
+            // count everything the replay shipped here, before dropping duplicates, since that's
+            // what the replay cost
+            state.stats().record_replayed_rows(m.mut_data());
+        }
         // make sure we don't fill a partial materialization
         // hole with incomplete (i.e., non-replay) state.
         if m.is_regular() {
             let data = m.mut_data();
             trace!(?data, "reader received regular message");
+            // Record deltas before dropping those that hit holes, since the holes might be filled
+            // before a read is rewound past them
+            state.record_history(data);
             if state.is_partial() {
                 data.retain(|row| {
                     match state.contains_record(&row[..]) {
@@ -201,7 +343,8 @@ impl Reader {
             });
         }
 
-        state.add(m.take_data());
+        // drain rather than take the data, so that the domain can reuse the packet's buffer
+        state.add(m.mut_data().drain(..));
 
         if swap {
             // TODO: avoid doing the pointer swap if we didn't modify anything (inc. ts)
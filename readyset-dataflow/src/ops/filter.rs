@@ -6,6 +6,8 @@ use readyset_errors::ReadySetResult;
 use serde::{Deserialize, Serialize};
 use tracing::error;
 
+use crate::ops::parallel::{OperatorWorkers, MIN_PARALLEL_RECORDS};
+use crate::ops::MIN_BATCH_SIZE;
 use crate::prelude::*;
 use crate::processing::{ColumnSource, IngredientLookupResult, LookupIndex, LookupMode};
 
@@ -17,6 +19,9 @@ use crate::processing::{ColumnSource, IngredientLookupResult, LookupIndex, Looku
 pub struct Filter {
     src: IndexPair,
     expression: Expr,
+    /// Workers to filter large packets on, if the domain running this node has any
+    #[serde(skip)]
+    workers: Option<OperatorWorkers>,
 }
 
 impl Filter {
@@ -25,30 +30,29 @@ impl Filter {
         Filter {
             src: src.into(),
             expression,
+            workers: None,
         }
     }
-}
 
-impl Ingredient for Filter {
-    fn ancestors(&self) -> Vec<NodeIndex> {
-        vec![self.src.as_global()]
+    /// Filter large packets on the given workers
+    pub(crate) fn set_workers(&mut self, workers: OperatorWorkers) {
+        self.workers = Some(workers);
     }
 
-    impl_replace_sibling!(src);
-
-    fn on_commit(&mut self, _: NodeIndex, remap: &HashMap<NodeIndex, IndexPair>) {
-        self.src.remap(remap);
-    }
+    fn filter(&self, rs: Records) -> Records {
+        if rs.len() >= MIN_BATCH_SIZE {
+            let records = rs.iter().map(|r| r.rec()).collect::<Vec<_>>();
+            // If evaluating the expression fails for any record, fall back to evaluating it a
+            // record at a time below, so that only the records it fails for are filtered out
+            if let Ok(conditions) = self.expression.eval_batch(&records) {
+                return rs
+                    .into_iter()
+                    .zip(conditions)
+                    .filter_map(|(r, condition)| condition.is_truthy().then_some(r))
+                    .collect();
+            }
+        }
 
-    fn on_input(
-        &mut self,
-        _: LocalNodeIndex,
-        rs: Records,
-        _: &ReplayContext,
-        _: &DomainNodes,
-        _: &StateMap,
-        _: &mut AuxiliaryNodeStateMap,
-    ) -> ReadySetResult<ProcessingResult> {
         let mut results = Vec::new();
         let mut log_error_once_flag = false;
         for r in rs {
@@ -70,9 +74,39 @@ impl Ingredient for Filter {
                 results.push(r);
             }
         }
+        results.into()
+    }
+}
+
+impl Ingredient for Filter {
+    fn ancestors(&self) -> Vec<NodeIndex> {
+        vec![self.src.as_global()]
+    }
+
+    impl_replace_sibling!(src);
+
+    fn on_commit(&mut self, _: NodeIndex, remap: &HashMap<NodeIndex, IndexPair>) {
+        self.src.remap(remap);
+    }
+
+    fn on_input(
+        &mut self,
+        _: LocalNodeIndex,
+        rs: Records,
+        _: &ReplayContext,
+        _: &DomainNodes,
+        _: &StateMap,
+        _: &mut AuxiliaryNodeStateMap,
+    ) -> ReadySetResult<ProcessingResult> {
+        let results = match &self.workers {
+            Some(workers) if rs.len() >= MIN_PARALLEL_RECORDS => {
+                workers.process(rs, |rs| self.filter(rs))
+            }
+            _ => self.filter(rs),
+        };
 
         Ok(ProcessingResult {
-            results: results.into(),
+            results,
             ..Default::default()
         })
     }
@@ -201,6 +235,46 @@ mod tests {
         assert_eq!(g.narrow_one_row(left.clone(), false), vec![left].into());
     }
 
+    #[test]
+    fn it_forwards_batch() {
+        let mut g = setup(false, None);
+
+        let rows = (0..MIN_BATCH_SIZE as i32 * 2)
+            .map(|i| vec![i.into(), if i % 3 == 0 { "a" } else { "b" }.into()])
+            .collect::<Vec<Vec<DfValue>>>();
+        let expected = rows
+            .iter()
+            .filter(|row| row[1] == "a".into())
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(g.narrow_one(rows, false), expected.into());
+    }
+
+    #[test]
+    fn it_forwards_parallel() {
+        let mut g = setup(false, None);
+        let workers = OperatorWorkers::new(
+            4,
+            ReplicaAddress {
+                domain_index: 0.into(),
+                shard: 0,
+                replica: 0,
+            },
+        )
+        .unwrap();
+        g.node_mut().set_operator_workers(&workers);
+
+        let rows = (0..MIN_PARALLEL_RECORDS as i32 * 2 + 1)
+            .map(|i| vec![i.into(), if i % 3 == 0 { "a" } else { "b" }.into()])
+            .collect::<Vec<Vec<DfValue>>>();
+        let expected = rows
+            .iter()
+            .filter(|row| row[1] == "a".into())
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(g.narrow_one(rows, false), expected.into());
+    }
+
     #[test]
     fn it_forwards_mfilter() {
         let mut g = setup(
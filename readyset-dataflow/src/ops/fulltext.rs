@@ -0,0 +1,577 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap};
+
+use itertools::Itertools;
+use readyset_client::internal;
+use readyset_errors::{internal, ReadySetResult};
+use serde::{Deserialize, Serialize};
+
+use crate::node::AuxiliaryNodeState;
+use crate::prelude::*;
+use crate::processing::{ColumnSource, LookupIndex};
+
+/// Split the given text into the lowercased, alphanumeric terms used by full-text search.
+///
+/// This is the tokenizer used both when indexing rows and when parsing the search terms out of a
+/// query, so that the two always agree on what constitutes a term.
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| term.to_lowercase())
+}
+
+/// Returns the relevance score of a row with the given occurrences of each search term
+fn score(occurrences: &[(&str, usize)]) -> usize {
+    occurrences.iter().map(|(_, count)| count).sum()
+}
+
+/// Append the given relevance score to a row, as it's emitted by a [`FullTextSearch`] node
+fn scored(mut row: Vec<DfValue>, score: usize) -> Vec<DfValue> {
+    row.push(DfValue::Double(score as f64));
+    row
+}
+
+/// The inverted index for a single group of a [`FullTextSearch`] node.
+#[derive(Debug, Default)]
+struct GroupIndex {
+    /// Map from search term, to the number of occurrences of that term in each matching row
+    postings: HashMap<String, HashMap<Vec<DfValue>, usize>>,
+    /// The relevance score and number of copies of each matching row
+    rows: HashMap<Vec<DfValue>, (usize, usize)>,
+    /// Every matching row, ordered by score (descending) and then by the row itself
+    ranking: BTreeSet<(Reverse<usize>, Vec<DfValue>)>,
+}
+
+impl GroupIndex {
+    fn insert(&mut self, row: Vec<DfValue>, occurrences: &[(&str, usize)]) {
+        let score = score(occurrences);
+        let (_, copies) = self.rows.entry(row.clone()).or_insert((score, 0));
+        *copies += 1;
+        if *copies > 1 {
+            return;
+        }
+
+        for (term, count) in occurrences {
+            if *count > 0 {
+                self.postings
+                    .entry((*term).to_owned())
+                    .or_default()
+                    .insert(row.clone(), *count);
+            }
+        }
+        self.ranking.insert((Reverse(score), row));
+    }
+
+    /// Remove a copy of the given row, returning `false` if we weren't indexing it
+    fn remove(&mut self, row: Vec<DfValue>, occurrences: &[(&str, usize)]) -> bool {
+        let score = match self.rows.get_mut(&row) {
+            Some((_, copies)) if *copies > 1 => {
+                *copies -= 1;
+                return true;
+            }
+            Some((score, _)) => *score,
+            None => return false,
+        };
+
+        self.rows.remove(&row);
+        for (term, count) in occurrences {
+            if *count == 0 {
+                continue;
+            }
+            if let Some(rows) = self.postings.get_mut(*term) {
+                rows.remove(&row);
+                if rows.is_empty() {
+                    self.postings.remove(*term);
+                }
+            }
+        }
+        self.ranking.remove(&(Reverse(score), row));
+        true
+    }
+
+    /// Returns the `limit` highest-ranked rows in this group, with their scores appended
+    fn top(&self, limit: usize) -> Vec<Vec<DfValue>> {
+        self.ranking
+            .iter()
+            .flat_map(|(Reverse(score), row)| {
+                let copies = self.rows.get(row).map_or(0, |(_, copies)| *copies);
+                std::iter::repeat((row, *score)).take(copies)
+            })
+            .take(limit)
+            .map(|(row, score)| scored(row.clone(), score))
+            .collect()
+    }
+}
+
+/// Auxiliary state for a [`FullTextSearch`] node, which is owned by a Domain.
+///
+/// Holds an inverted index over the rows matching the search, for each group.
+#[derive(Debug, Default)]
+pub struct FullTextSearchState {
+    groups: HashMap<Vec<DfValue>, GroupIndex>,
+}
+
+/// FullTextSearch filters the rows of its parent down to those whose text columns contain the
+/// search terms, and appends a relevance score column to each matching row.
+///
+/// The relevance score of a row is the total number of occurrences of the search terms within its
+/// text columns. Matching rows are kept in an inverted index per group, which is maintained
+/// incrementally as rows are inserted and deleted. If a `limit` is given, only the `limit`
+/// highest-scoring rows of each group are emitted, ordered by score (descending) and then by the
+/// row itself to break ties.
+///
+/// Since the search terms are fixed when the node is created, only rows matching the search are
+/// ever indexed, and the score of a row never changes once it's indexed. A search with no terms
+/// (for example, one whose query only contained punctuation or stopwords) matches nothing.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FullTextSearch {
+    src: IndexPair,
+
+    /// The index of this node
+    our_index: Option<IndexPair>,
+
+    /// The index of the relevance score column we append to our parent's columns
+    ///
+    /// Set during [`Ingredient::on_connected`]
+    score_col: Option<usize>,
+
+    /// The indices of the text columns to search
+    columns: Vec<usize>,
+
+    /// The (already tokenized) terms to search for
+    terms: Vec<String>,
+
+    /// If true, rows must contain *every* search term to match, rather than *any* search term
+    match_all: bool,
+
+    /// The list of column indices that we're grouping by.
+    group_by: Vec<usize>,
+
+    /// The maximum number of results per group, if any.
+    limit: Option<usize>,
+}
+
+impl FullTextSearch {
+    /// Construct a new FullTextSearch operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - this operator's ancestor
+    /// * `columns` - the text columns to search
+    /// * `terms` - the search terms, which will be tokenized with [`tokenize`]
+    /// * `match_all` - whether rows must contain all of the search terms, rather than any of them
+    /// * `group_by` - the columns that this operator is keyed on
+    /// * `limit` - the maximum number of results per group, if any
+    pub fn new(
+        src: NodeIndex,
+        columns: Vec<usize>,
+        terms: Vec<String>,
+        match_all: bool,
+        group_by: Vec<usize>,
+        limit: Option<usize>,
+    ) -> Self {
+        let terms = terms
+            .iter()
+            .flat_map(|term| tokenize(term))
+            .unique()
+            .collect();
+
+        FullTextSearch {
+            src: src.into(),
+            our_index: None,
+            score_col: None,
+            columns,
+            terms,
+            match_all,
+            group_by,
+            limit,
+        }
+    }
+
+    /// Returns the index of the relevance score column
+    ///
+    /// # Panics
+    ///
+    /// Panics if called before [`Ingredient::on_connected`]
+    fn score_column(&self) -> usize {
+        self.score_col
+            .expect("score_column called before Ingredient::on_connected")
+    }
+
+    /// Count the occurrences of each of our search terms in the text columns of the given row,
+    /// returning [`None`] if the row does not match the search
+    fn occurrences(&self, row: &[DfValue]) -> Option<Vec<(&str, usize)>> {
+        if self.terms.is_empty() {
+            return None;
+        }
+
+        let mut counts: Vec<(&str, usize)> = self.terms.iter().map(|t| (t.as_str(), 0)).collect();
+        for text in self.columns.iter().filter_map(|c| row.get(*c)?.as_str()) {
+            for token in tokenize(text) {
+                if let Some((_, count)) = counts.iter_mut().find(|(t, _)| *t == token) {
+                    *count += 1;
+                }
+            }
+        }
+
+        let matches = if self.match_all {
+            counts.iter().all(|(_, count)| *count > 0)
+        } else {
+            counts.iter().any(|(_, count)| *count > 0)
+        };
+        matches.then_some(counts)
+    }
+}
+
+impl Ingredient for FullTextSearch {
+    fn ancestors(&self) -> Vec<NodeIndex> {
+        vec![self.src.as_global()]
+    }
+
+    impl_replace_sibling!(src);
+
+    fn on_connected(&mut self, graph: &Graph) {
+        self.score_col = Some(graph[self.src.as_global()].columns().len());
+    }
+
+    fn on_commit(&mut self, us: NodeIndex, remap: &HashMap<NodeIndex, IndexPair>) {
+        self.src.remap(remap);
+        self.our_index = Some(remap[&us]);
+    }
+
+    fn on_input(
+        &mut self,
+        from: LocalNodeIndex,
+        rs: Records,
+        _replay: &ReplayContext,
+        _nodes: &DomainNodes,
+        _state: &StateMap,
+        auxiliary_node_states: &mut AuxiliaryNodeStateMap,
+    ) -> ReadySetResult<ProcessingResult> {
+        debug_assert_eq!(from, *self.src);
+
+        if rs.is_empty() {
+            return Ok(ProcessingResult {
+                results: rs,
+                ..Default::default()
+            });
+        }
+
+        let us = self.our_index.unwrap();
+        let fts_state = match auxiliary_node_states.get_mut(*us) {
+            Some(AuxiliaryNodeState::FullTextSearch(ref mut fts_state)) => fts_state,
+            Some(_) => internal!("Incorrect auxiliary state for FullTextSearch node"),
+            None => internal!("Missing auxiliary state for FullTextSearch node"),
+        };
+
+        let mut out = Vec::new();
+        // If we're limiting the number of results per group, the output of each group touched by
+        // this batch before we started processing it
+        let mut before: HashMap<Vec<DfValue>, Vec<Vec<DfValue>>> = HashMap::new();
+
+        for r in rs {
+            let group = self
+                .group_by
+                .iter()
+                .map(|c| r.get(*c).cloned().ok_or(ReadySetError::InvalidRecordLength))
+                .collect::<ReadySetResult<Vec<_>>>()?;
+
+            let occurrences = match self.occurrences(&r) {
+                Some(occurrences) => occurrences,
+                None => continue,
+            };
+
+            if let Some(limit) = self.limit {
+                before.entry(group.clone()).or_insert_with(|| {
+                    fts_state
+                        .groups
+                        .get(&group)
+                        .map(|index| index.top(limit))
+                        .unwrap_or_default()
+                });
+            }
+
+            let (row, positive) = r.extract();
+            if positive {
+                fts_state
+                    .groups
+                    .entry(group)
+                    .or_default()
+                    .insert(row.clone(), &occurrences);
+                if self.limit.is_none() {
+                    out.push(Record::Positive(scored(row, score(&occurrences))));
+                }
+            } else if let Some(index) = fts_state.groups.get_mut(&group) {
+                if index.remove(row.clone(), &occurrences) && self.limit.is_none() {
+                    out.push(Record::Negative(scored(row, score(&occurrences))));
+                }
+                if index.rows.is_empty() {
+                    fts_state.groups.remove(&group);
+                }
+            }
+        }
+
+        // Since the score of a row never changes, only the top of the ranking of each group we
+        // touched needs to be compared to find the rows entering or leaving the results
+        for (group, before) in before {
+            let after = match (fts_state.groups.get(&group), self.limit) {
+                (Some(index), Some(limit)) => index.top(limit),
+                _ => vec![],
+            };
+
+            let mut diff: HashMap<Vec<DfValue>, isize> = HashMap::new();
+            for row in before {
+                *diff.entry(row).or_default() -= 1;
+            }
+            for row in after {
+                *diff.entry(row).or_default() += 1;
+            }
+
+            for (row, count) in diff {
+                let record = if count < 0 {
+                    Record::Negative
+                } else {
+                    Record::Positive
+                };
+                out.extend(
+                    std::iter::repeat(row)
+                        .take(count.unsigned_abs())
+                        .map(record),
+                );
+            }
+        }
+
+        Ok(ProcessingResult {
+            results: out.into(),
+            ..Default::default()
+        })
+    }
+
+    fn suggest_indexes(&self, this: NodeIndex) -> HashMap<NodeIndex, LookupIndex> {
+        // We always need to be materialized (see `requires_full_materialization`), so if we don't
+        // have any group columns index on the score column instead
+        let index_cols = if self.group_by.is_empty() {
+            vec![self.score_column()]
+        } else {
+            self.group_by.clone()
+        };
+
+        HashMap::from([(
+            this,
+            LookupIndex::Strict(internal::Index::hash_map(index_cols)),
+        )])
+    }
+
+    fn column_source(&self, cols: &[usize]) -> ColumnSource {
+        if cols.contains(&self.score_column()) {
+            ColumnSource::RequiresFullReplay(vec1![self.src.as_global()])
+        } else {
+            ColumnSource::exact_copy(self.src.as_global(), cols.into())
+        }
+    }
+
+    fn description(&self, detailed: bool) -> String {
+        if !detailed {
+            return String::from("FullTextSearch");
+        }
+
+        format!(
+            "FullTextSearch {}[{}] ⊇ [{}] γ[{}]{}",
+            if self.match_all { "all" } else { "any" },
+            self.columns.iter().join(", "),
+            self.terms.iter().join(", "),
+            self.group_by.iter().join(", "),
+            self.limit
+                .map(|limit| format!(" k={}", limit))
+                .unwrap_or_default()
+        )
+    }
+
+    fn is_selective(&self) -> bool {
+        true
+    }
+
+    // The inverted index in our auxiliary state has to see every row of our parent, so we can
+    // never be partially materialized
+    fn requires_full_materialization(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops;
+
+    fn setup(match_all: bool, limit: Option<usize>) -> (ops::test::MockGraph, IndexPair) {
+        let mut g = ops::test::MockGraph::new();
+        let s = g.add_base("source", &["id", "group", "body"]);
+        g.set_op(
+            "fts",
+            &["id", "group", "body", "score"],
+            FullTextSearch::new(
+                s.as_global(),
+                vec![2],
+                vec!["Quick fox".into()],
+                match_all,
+                vec![1],
+                limit,
+            ),
+            true,
+        );
+        (g, s)
+    }
+
+    fn row(id: i32, body: &str) -> Vec<DfValue> {
+        vec![id.into(), "g".into(), body.into()]
+    }
+
+    fn scored(mut row: Vec<DfValue>, score: f64) -> Vec<DfValue> {
+        row.push(DfValue::Double(score));
+        row
+    }
+
+    #[test]
+    fn tokenizes() {
+        assert_eq!(
+            tokenize("The quick, brown FOX!").collect::<Vec<_>>(),
+            vec!["the", "quick", "brown", "fox"]
+        );
+    }
+
+    #[test]
+    fn it_matches_any_term() {
+        let (mut g, _) = setup(false, None);
+
+        let rs = g.narrow_one_row(row(1, "the quick brown fox"), true);
+        assert_eq!(
+            rs,
+            vec![(scored(row(1, "the quick brown fox"), 2.0), true)].into()
+        );
+
+        let rs = g.narrow_one_row(row(2, "a fox and another fox"), true);
+        assert_eq!(
+            rs,
+            vec![(scored(row(2, "a fox and another fox"), 2.0), true)].into()
+        );
+
+        let rs = g.narrow_one_row(row(3, "a lazy dog"), true);
+        assert!(rs.is_empty());
+    }
+
+    #[test]
+    fn it_matches_all_terms() {
+        let (mut g, _) = setup(true, None);
+
+        let rs = g.narrow_one_row(row(1, "the quick brown fox"), true);
+        assert_eq!(
+            rs,
+            vec![(scored(row(1, "the quick brown fox"), 2.0), true)].into()
+        );
+
+        let rs = g.narrow_one_row(row(2, "a fox and another fox"), true);
+        assert!(rs.is_empty());
+    }
+
+    #[test]
+    fn it_removes_deleted_rows() {
+        let (mut g, _) = setup(false, None);
+
+        g.narrow_one_row(row(1, "quick"), true);
+        let rs = g.narrow_one_row((row(1, "quick"), false), true);
+        assert_eq!(rs, vec![(scored(row(1, "quick"), 1.0), false)].into());
+
+        // deleting a row that doesn't match does nothing
+        let rs = g.narrow_one_row((row(2, "slow"), false), true);
+        assert!(rs.is_empty());
+    }
+
+    #[test]
+    fn it_keeps_top_ranked() {
+        let (mut g, _) = setup(false, Some(2));
+
+        g.narrow_one_row(row(1, "fox"), true);
+        g.narrow_one_row(row(2, "quick fox"), true);
+
+        // outranks row 1, which drops out of the results
+        let rs = g.narrow_one_row(row(3, "quick quick fox"), true);
+        assert_eq!(rs.len(), 2);
+        assert!(rs.has_positive(&scored(row(3, "quick quick fox"), 3.0)[..]));
+        assert!(rs.has_negative(&scored(row(1, "fox"), 1.0)[..]));
+
+        // ranks below everything we're already emitting
+        let rs = g.narrow_one_row(row(4, "fox"), true);
+        assert!(rs.is_empty());
+
+        // deleting a row from the results brings back the next-highest ranked row
+        let rs = g.narrow_one_row((row(2, "quick fox"), false), true);
+        assert_eq!(rs.len(), 2);
+        assert!(rs.has_negative(&scored(row(2, "quick fox"), 2.0)[..]));
+        assert!(rs.has_positive(&scored(row(1, "fox"), 1.0)[..]));
+    }
+
+    #[test]
+    fn it_ignores_rows_outside_the_limit() {
+        let (mut g, _) = setup(false, Some(1));
+
+        g.narrow_one_row(row(1, "quick fox"), true);
+        let rs = g.narrow_one_row(row(2, "fox"), true);
+        assert!(rs.is_empty());
+        let rs = g.narrow_one_row((row(2, "fox"), false), true);
+        assert!(rs.is_empty());
+
+        // a second copy of a row in the results takes up the rest of the limit
+        let rs = g.narrow_one_row(row(1, "quick fox"), true);
+        assert!(rs.is_empty());
+        let rs = g.narrow_one_row((row(1, "quick fox"), false), true);
+        assert!(rs.is_empty());
+    }
+
+    #[test]
+    fn it_matches_nothing_without_terms() {
+        let mut g = ops::test::MockGraph::new();
+        let s = g.add_base("source", &["id", "group", "body"]);
+        g.set_op(
+            "fts",
+            &["id", "group", "body", "score"],
+            FullTextSearch::new(
+                s.as_global(),
+                vec![2],
+                vec!["!?".into()],
+                true,
+                vec![1],
+                None,
+            ),
+            true,
+        );
+
+        let rs = g.narrow_one_row(row(1, "quick fox"), true);
+        assert!(rs.is_empty());
+        let rs = g.narrow_one_row(row(2, ""), true);
+        assert!(rs.is_empty());
+    }
+
+    #[test]
+    fn it_suggests_indices() {
+        let (g, _) = setup(false, None);
+        let res = g.node().suggest_indexes(g.node_index().as_global());
+        assert_eq!(res.len(), 1);
+        assert_eq!(
+            res[&g.node_index().as_global()],
+            LookupIndex::Strict(internal::Index::hash_map(vec![1]))
+        );
+    }
+
+    #[test]
+    fn column_source() {
+        let (g, s) = setup(false, None);
+        assert_eq!(
+            g.node().column_source(&[0, 1]),
+            ColumnSource::exact_copy(s.as_global(), vec![0, 1])
+        );
+        assert_eq!(
+            g.node().column_source(&[3]),
+            ColumnSource::RequiresFullReplay(vec1![s.as_global()])
+        );
+    }
+}
@@ -1,27 +1,66 @@
-use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
-use std::hash::{Hash, Hasher};
 
+use nom_sql::OrderType;
 pub use nom_sql::{BinaryOperator, Literal, SqlType};
 use readyset_data::{Collation, DfType};
 use readyset_errors::{invariant, ReadySetResult};
 use serde::{Deserialize, Serialize};
 
 use crate::node::AuxiliaryNodeState;
-use crate::ops::grouped::{GroupedOperation, GroupedOperator};
+use crate::ops::grouped::{
+    group_hash, group_values_hash, EvictedGroups, GroupHash, GroupedOperation, GroupedOperator,
+};
 use crate::prelude::*;
 
 /// Supported aggregation operators.
+///
+/// Generic over the type used to reference columns, so that the same type can be used both in MIR
+/// (where columns are referenced by name) and in dataflow (where they're referenced by index).
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub enum Aggregation {
+pub enum Aggregation<Column = usize> {
     /// Count the number of non-null values.
     Count,
     /// Sum the value of the `over` column for all records of each group.
     Sum,
-    /// Average the value of the `over` column. Maintains count and sum in HashMap
+    /// Average the value of the `over` column. Maintains count and sum in auxiliary state, and
+    /// divides when emitting.
     Avg,
-    /// Concatenates using the given separator between values.
-    GroupConcat { separator: String },
+    /// Concatenates using the given separator between values, ordered within each group by the
+    /// given columns (or in the order the values arrived in, if `order_by` is empty).
+    GroupConcat {
+        separator: String,
+        order_by: Vec<(Column, OrderType)>,
+    },
+    /// Approximates the number of distinct non-null values using a HyperLogLog sketch with
+    /// `2^precision` registers.
+    ApproxCountDistinct { precision: u8 },
+}
+
+impl<Column> Aggregation<Column> {
+    /// Transform all column references in self by applying a function
+    pub fn map_columns<F, C2, E>(self, mut f: F) -> Result<Aggregation<C2>, E>
+    where
+        F: FnMut(Column) -> Result<C2, E>,
+    {
+        Ok(match self {
+            Aggregation::Count => Aggregation::Count,
+            Aggregation::Sum => Aggregation::Sum,
+            Aggregation::Avg => Aggregation::Avg,
+            Aggregation::GroupConcat {
+                separator,
+                order_by,
+            } => Aggregation::GroupConcat {
+                separator,
+                order_by: order_by
+                    .into_iter()
+                    .map(|(col, ot)| Ok((f(col)?, ot)))
+                    .collect::<Result<_, E>>()?,
+            },
+            Aggregation::ApproxCountDistinct { precision } => {
+                Aggregation::ApproxCountDistinct { precision }
+            }
+        })
+    }
 }
 
 impl Aggregation {
@@ -38,7 +77,7 @@ impl Aggregation {
         over_col_ty: &DfType,
     ) -> ReadySetResult<GroupedOperator<Aggregator>> {
         let out_ty = match &self {
-            Aggregation::Count { .. } => DfType::BigInt,
+            Aggregation::Count { .. } | Aggregation::ApproxCountDistinct { .. } => DfType::BigInt,
             // The SUM() and AVG() functions return a DECIMAL value for exact-value arguments
             // (integer or DECIMAL), and a DOUBLE value for approximate-value arguments (FLOAT or
             // DOUBLE).
@@ -98,30 +137,44 @@ pub struct NumericalDiff {
     group_hash: GroupHash,
 }
 
-pub type GroupHash = u64;
-
-/// For storing (Count, Sum) in additional state for Average.
+/// Running (Count, Sum) pair kept in auxiliary state for Average.
+///
+/// The sum is kept in the same type as the output column (so exact-value arguments accumulate as
+/// `DECIMAL` rather than drifting through a float), and the division is only performed when a new
+/// value is emitted for the group.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct AverageDataPair {
-    count: DfValue,
+    count: i64,
     sum: DfValue,
 }
 
 impl AverageDataPair {
-    fn apply_diff(&mut self, d: NumericalDiff) -> ReadySetResult<DfValue> {
+    fn new(zero: DfValue) -> Self {
+        AverageDataPair {
+            count: 0,
+            sum: zero,
+        }
+    }
+
+    fn apply_diff(&mut self, d: NumericalDiff) -> ReadySetResult<()> {
         if d.positive {
             self.sum = (&self.sum + &d.value)?;
-            self.count = (&self.count + &DfValue::Int(1))?;
+            self.count += 1;
         } else {
             self.sum = (&self.sum - &d.value)?;
-            self.count = (&self.count - &DfValue::Int(1))?;
+            self.count -= 1;
         }
+        Ok(())
+    }
 
-        if self.count > DfValue::Int(0) {
-            &self.sum / &self.count
-        } else {
-            Ok(DfValue::Double(0.0))
+    /// Compute the average for this pair, coerced to `out_ty`, or `None` if the group has no
+    /// non-null values left.
+    fn average(&self, out_ty: &DfType) -> ReadySetResult<Option<DfValue>> {
+        if self.count <= 0 {
+            return Ok(None);
         }
+        let avg = (&self.sum / &DfValue::from(self.count))?;
+        Ok(Some(avg.coerce_to(out_ty, &DfType::Unknown)?))
     }
 }
 
@@ -131,19 +184,25 @@ pub struct AggregatorState {
     count_sum_map: HashMap<GroupHash, AverageDataPair>,
 }
 
-impl Aggregator {
-    fn group_hash(&self, rec: &[DfValue]) -> GroupHash {
-        let mut hasher = DefaultHasher::new();
-        for &col in self.group.iter() {
-            #[allow(clippy::indexing_slicing)]
-            // When the Aggregator is constructed, it is constructed with a group by that is
-            // derived from existing columns. If we lack a column in the record, then something has
-            // gone horrible wrong and we should panic.
-            rec[col].hash(&mut hasher)
+impl AggregatorState {
+    /// Forget the (count, sum) pairs kept for the given groups, which were evicted from the node's
+    /// materialized state
+    pub(crate) fn evict_groups(&mut self, evicted: &EvictedGroups) {
+        match evicted {
+            EvictedGroups::Exact(groups) => {
+                for group in groups {
+                    self.count_sum_map.remove(&group_values_hash(group.iter()));
+                }
+            }
+            // We only have the hashes of the groups we're tracking, so we can't tell which of them
+            // were evicted - forget all of them, and recompute any group that's still
+            // materialized the next time it's written to
+            EvictedGroups::Matching { .. } => self.count_sum_map.clear(),
         }
-        hasher.finish()
     }
+}
 
+impl Aggregator {
     fn new_data(&self) -> ReadySetResult<DfValue> {
         match &self.out_ty {
             DfType::BigInt => Ok(DfValue::Int(Default::default())),
@@ -172,7 +231,7 @@ impl GroupedOperation for Aggregator {
     }
 
     fn to_diff(&self, r: &[DfValue], pos: bool) -> ReadySetResult<Self::Diff> {
-        let group_hash = self.group_hash(r);
+        let group_hash = group_hash(&self.group, r);
         Ok(NumericalDiff {
             #[allow(clippy::indexing_slicing)]
             // When the Aggregator is constructed, it is constructed with an over that is
@@ -206,23 +265,52 @@ impl GroupedOperation for Aggregator {
             }
         };
 
-        let count_sum_map = match auxiliary_node_state {
-            Some(AuxiliaryNodeState::Aggregation(ref mut aggregator_state)) => {
-                &mut aggregator_state.count_sum_map
+        if let Aggregation::Avg = self.op {
+            let count_sum_map = match auxiliary_node_state {
+                Some(AuxiliaryNodeState::Aggregation(ref mut aggregator_state)) => {
+                    &mut aggregator_state.count_sum_map
+                }
+                Some(_) => internal!("Incorrect auxiliary state for Aggregation node"),
+                None => internal!("Missing auxiliary state for Aggregation node"),
+            };
+
+            let mut diffs = diffs.peekable();
+            let group_hash = match diffs.peek() {
+                Some(diff) => diff.group_hash,
+                None => return Ok(Some(current.cloned().unwrap_or(DfValue::None))),
+            };
+
+            // The pair is only valid if it produced the value currently emitted for the group. If
+            // the group has no current value (it's new, or it's being recomputed after being
+            // evicted) any pair left behind for it is stale, so start again from an exact zero; if
+            // the current value doesn't match the pair, our state has diverged from our parent's,
+            // so throw the pair away and recompute the group from scratch.
+            let pair = count_sum_map.remove(&group_hash);
+            let mut pair = match (current, pair) {
+                (None, _) | (Some(DfValue::None), None) => AverageDataPair::new(self.new_data()?),
+                (Some(current), Some(pair))
+                    if pair.average(&self.out_ty)?.as_ref() == Some(current) =>
+                {
+                    pair
+                }
+                (Some(_), _) => return Ok(None),
+            };
+            for diff in diffs {
+                if diff.value.is_none() {
+                    continue;
+                }
+                pair.apply_diff(diff)?;
             }
-            Some(_) => internal!("Incorrect auxiliary state for Aggregation node"),
-            None => internal!("Missing auxiliary state for Aggregation node"),
-        };
 
-        let mut apply_avg = |_curr, diff: Self::Diff| -> ReadySetResult<DfValue> {
-            count_sum_map
-                .entry(diff.group_hash)
-                .or_insert(AverageDataPair {
-                    sum: DfValue::Double(0.0),
-                    count: DfValue::Int(0),
-                })
-                .apply_diff(diff)
-        };
+            // Only divide once all the diffs for the group have been folded into the pair, and
+            // drop the pair entirely once the group has no non-null values left so that a later
+            // insert starts again from an exact zero.
+            let avg = pair.average(&self.out_ty)?;
+            if avg.is_some() {
+                count_sum_map.insert(group_hash, pair);
+            }
+            return Ok(Some(avg.unwrap_or(DfValue::None)));
+        }
 
         let apply_diff =
             |curr: ReadySetResult<DfValue>, diff: Self::Diff| -> ReadySetResult<DfValue> {
@@ -233,10 +321,14 @@ impl GroupedOperation for Aggregator {
                 match self.op {
                     Aggregation::Count { .. } => apply_count(curr?, diff),
                     Aggregation::Sum => apply_sum(curr?, diff),
-                    Aggregation::Avg => apply_avg(curr?, diff),
-                    Aggregation::GroupConcat { separator: _ } => internal!(
+                    Aggregation::Avg => internal!("Averages are handled separately above"),
+                    Aggregation::GroupConcat { .. } => internal!(
                         "GroupConcats are separate from the other aggregations in the dataflow."
                     ),
+                    Aggregation::ApproxCountDistinct { .. } => internal!(
+                        "ApproxCountDistincts are separate from the other aggregations in the \
+                         dataflow."
+                    ),
                 }
             };
 
@@ -251,9 +343,12 @@ impl GroupedOperation for Aggregator {
                 Aggregation::Count { .. } => "+".to_owned(),
                 Aggregation::Sum => "𝛴".to_owned(),
                 Aggregation::Avg => "Avg".to_owned(),
-                Aggregation::GroupConcat { separator: ref s } => {
+                Aggregation::GroupConcat {
+                    separator: ref s, ..
+                } => {
                     format!("||({})", s)
                 }
+                Aggregation::ApproxCountDistinct { .. } => "≈|*|".to_owned(),
             };
         }
 
@@ -261,7 +356,14 @@ impl GroupedOperation for Aggregator {
             Aggregation::Count { .. } => "|*|".to_owned(),
             Aggregation::Sum => format!("𝛴({})", self.over),
             Aggregation::Avg => format!("Avg({})", self.over),
-            Aggregation::GroupConcat { separator: ref s } => format!("||({}, {})", s, self.over),
+            Aggregation::GroupConcat {
+                separator: ref s, ..
+            } => {
+                format!("||({}, {})", s, self.over)
+            }
+            Aggregation::ApproxCountDistinct { precision } => {
+                format!("≈|{}|({})", self.over, precision)
+            }
         };
         let group_cols = self
             .group
@@ -297,7 +399,9 @@ impl GroupedOperation for Aggregator {
     }
 
     fn can_lose_state(&self) -> bool {
-        false
+        // AVG keeps a running (count, sum) pair per group in auxiliary state, which has to be
+        // rebuilt from our parent if it's lost or doesn't match our current value
+        matches!(self.op, Aggregation::Avg)
     }
 }
 
@@ -1066,6 +1170,136 @@ mod tests {
         }
     }
 
+    /// Testing AVG over an exact-value column under heavy insert/delete churn. The running sum
+    /// is kept as a `DECIMAL`, so the emitted average must be exact no matter how many times the
+    /// group has been updated.
+    #[test]
+    fn avg_exact_under_churn() {
+        let mut g = ops::test::MockGraph::new();
+        let s = g.add_base("source", &["x", "y"]);
+        g.set_op(
+            "identity",
+            &["x", "ys"],
+            Aggregation::Avg
+                .over(s.as_global(), 1, &[0], &DfType::Int)
+                .unwrap(),
+            true,
+        );
+        let numeric = |v: f64| {
+            DfValue::try_from(v)
+                .unwrap()
+                .coerce_to(&DfType::DEFAULT_NUMERIC, &DfType::Double)
+                .unwrap()
+        };
+        let last_positive = |rs: Records| {
+            rs.into_iter()
+                .filter_map(|r| match r {
+                    Record::Positive(r) => Some(r),
+                    Record::Negative(_) => None,
+                })
+                .last()
+        };
+
+        // Insert 1..=1000, then churn each value out and back in one at a time
+        let u = (1..=1000)
+            .map(|i| (vec![1.into(), i.into()], true))
+            .collect::<Vec<_>>();
+        let out = last_positive(g.narrow_one(u, true)).unwrap();
+        assert_eq!(out[1], numeric(500.5));
+        for i in 1..=1000 {
+            g.narrow_one_row((vec![1.into(), i.into()], false), true);
+            g.narrow_one_row((vec![1.into(), i.into()], true), true);
+        }
+
+        // Remove all the even values; the odd values 1..=999 average to exactly 500
+        let u = (1..=1000)
+            .filter(|i| i % 2 == 0)
+            .map(|i| (vec![1.into(), i.into()], false))
+            .collect::<Vec<_>>();
+        let out = last_positive(g.narrow_one(u, true)).unwrap();
+        assert_eq!(out[1], numeric(500.0));
+        assert_eq!(out[2], 500.into());
+
+        // Removing everything else retracts the group entirely
+        let u = (1..=1000)
+            .filter(|i| i % 2 == 1)
+            .map(|i| (vec![1.into(), i.into()], false))
+            .collect::<Vec<_>>();
+        let rs = g.narrow_one(u, true);
+        assert_eq!(rs.len(), 1);
+        assert!(last_positive(rs).is_none());
+
+        // ...and a later insert starts again from an exact zero
+        let out = last_positive(g.narrow_one_row(vec![1.into(), 3.into()], true)).unwrap();
+        assert_eq!(out[1], numeric(3.0));
+    }
+
+    /// AVG ignores NULLs, and is NULL for a group with no non-null values
+    #[test]
+    fn avg_of_nulls() {
+        let mut c = setup(Aggregation::Avg, true);
+
+        let rs = c.narrow_one_row(vec![1.into(), DfValue::None], true);
+        assert_eq!(rs, vec![vec![1.into(), DfValue::None, 1.into()]].into());
+
+        let rs = c.narrow_one_row(vec![1.into(), 4.into()], true);
+        assert_eq!(
+            rs,
+            vec![
+                (vec![1.into(), DfValue::None, 1.into()], false),
+                (
+                    vec![1.into(), DfValue::try_from(4.0).unwrap(), 2.into()],
+                    true
+                )
+            ]
+            .into()
+        );
+
+        let rs = c.narrow_one_row((vec![1.into(), 4.into()], false), true);
+        assert_eq!(
+            rs,
+            vec![
+                (
+                    vec![1.into(), DfValue::try_from(4.0).unwrap(), 2.into()],
+                    false
+                ),
+                (vec![1.into(), DfValue::None, 1.into()], true)
+            ]
+            .into()
+        );
+    }
+
+    /// AVG rebuilds its running sum and count when its group is recomputed, rather than folding
+    /// the group's values into whatever was left behind before the group was evicted
+    #[test]
+    fn avg_rebuilds_lost_state() {
+        let avg = Aggregator {
+            op: Aggregation::Avg,
+            over: 1,
+            group: vec![0],
+            over_else: None,
+            out_ty: DfType::Double,
+        };
+        let mut state = AuxiliaryNodeState::Aggregation(Default::default());
+        let mut apply = |current: Option<&DfValue>, rows: &[(i32, bool)]| {
+            let mut diffs = rows
+                .iter()
+                .map(|(v, pos)| avg.to_diff(&[1.into(), (*v).into()], *pos).unwrap());
+            avg.apply(current, &mut diffs, Some(&mut state)).unwrap()
+        };
+        let three = DfValue::try_from(3.0).unwrap();
+
+        assert_eq!(apply(None, &[(2, true), (4, true)]), Some(three.clone()));
+        // The group was evicted, and is being recomputed from all of its rows
+        assert_eq!(apply(None, &[(2, true), (4, true)]), Some(three.clone()));
+        assert_eq!(
+            apply(Some(&three), &[(6, true)]),
+            Some(DfValue::try_from(4.0).unwrap())
+        );
+        // A current value that doesn't match our state asks for the group to be recomputed
+        assert_eq!(apply(Some(&three), &[(6, true)]), None);
+    }
+
     #[test]
     fn it_suggests_indices() {
         let me = 1.into();
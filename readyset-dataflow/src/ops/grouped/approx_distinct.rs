@@ -0,0 +1,460 @@
+//! Approximate count of distinct values, using HyperLogLog sketches
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use readyset_data::DfType;
+use readyset_errors::{internal, invalid_query, ReadySetResult};
+use readyset_util::Indices;
+use serde::{Deserialize, Serialize};
+
+use crate::node::{AuxiliaryNodeState, Node};
+use crate::ops::grouped::{EvictedGroups, GroupedOperation, GroupedOperator};
+use crate::prelude::*;
+
+/// The smallest supported precision for [`ApproxCountDistinct`]
+pub const MIN_PRECISION: u8 = 4;
+/// The largest supported precision for [`ApproxCountDistinct`]
+pub const MAX_PRECISION: u8 = 16;
+/// The precision used for [`ApproxCountDistinct`] if none is specified, giving a standard error of
+/// about 1.6% using 4KiB of state per group
+pub const DEFAULT_PRECISION: u8 = 12;
+
+/// A [`Hasher`] with a fixed algorithm and no random state, so that the same value always hashes to
+/// the same register of a [`Sketch`], regardless of the process, platform, or version of Rust.
+///
+/// Bytes are hashed with 64-bit FNV-1a, and the result is passed through the finalizer from
+/// MurmurHash3, since HyperLogLog relies on the high bits of the hash being well distributed.
+#[derive(Debug, Clone, Copy)]
+struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    // Integers are always hashed as little-endian, and `usize`s as 64 bits, so that hashes don't
+    // depend on the platform
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64)
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16)
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32)
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64)
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128)
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as u64)
+    }
+
+    fn finish(&self) -> u64 {
+        let mut h = self.0;
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xff51afd7ed558ccd);
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xc4ceb9fe1a85ec53);
+        h ^= h >> 33;
+        h
+    }
+}
+
+/// A HyperLogLog sketch, which estimates the number of distinct values inserted into it using
+/// `2^precision` single-byte registers.
+///
+/// Sketches can't have values removed from them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sketch {
+    precision: u8,
+    registers: Vec<u8>,
+}
+
+impl Sketch {
+    /// Construct a new, empty sketch with `2^precision` registers
+    pub fn new(precision: u8) -> Self {
+        Self {
+            precision,
+            registers: vec![0; 1 << precision],
+        }
+    }
+
+    /// Insert a value into the sketch
+    pub fn insert(&mut self, value: &DfValue) {
+        let mut hasher = StableHasher::default();
+        value.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        // The first `precision` bits of the hash pick a register, and the register records the
+        // largest position of the first set bit seen in the remaining bits
+        let register = (hash >> (64 - self.precision)) as usize;
+        let rest = hash << self.precision;
+        let rank = (rest.leading_zeros() as u8).min(64 - self.precision) + 1;
+        #[allow(clippy::indexing_slicing)] // register is less than 2^precision
+        let reg = &mut self.registers[register];
+        *reg = (*reg).max(rank);
+    }
+
+    /// Estimate the number of distinct values which have been inserted into the sketch
+    pub fn estimate(&self) -> i64 {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|r| 2f64.powi(-i32::from(*r)))
+            .sum();
+        let raw = alpha * m * m / sum;
+
+        // Use linear counting for small cardinalities, where the raw estimate is badly biased
+        let zeros = self.registers.iter().filter(|r| **r == 0).count();
+        let estimate = if raw <= 2.5 * m && zeros > 0 {
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        };
+        estimate.round() as i64
+    }
+}
+
+/// `ApproxCountDistinct` implements the `APPROX_COUNT_DISTINCT` SQL aggregate function, which
+/// estimates the number of distinct non-null values in each group by maintaining a [`Sketch`] of
+/// the values in that group in auxiliary state.
+///
+/// Since values can't be removed from a sketch, deleting a value from a group (or finding that the
+/// stored sketch for a group no longer matches the current value) causes the sketch for that group
+/// to be rebuilt from the operator's parent.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ApproxCountDistinct {
+    /// Which column to aggregate.
+    source_col: usize,
+    /// The columns to group by.
+    group_by: Vec<usize>,
+    /// The number of bits of each value's hash used to pick a register in the sketch
+    precision: u8,
+}
+
+impl ApproxCountDistinct {
+    /// Construct a new `ApproxCountDistinct`, estimating the number of distinct values of
+    /// `source_col` using sketches with `2^precision` registers.
+    pub fn new(
+        src: NodeIndex,
+        source_col: usize,
+        group_by: Vec<usize>,
+        precision: u8,
+    ) -> ReadySetResult<GroupedOperator<ApproxCountDistinct>> {
+        if !(MIN_PRECISION..=MAX_PRECISION).contains(&precision) {
+            invalid_query!(
+                "APPROX_COUNT_DISTINCT precision must be between {} and {}, got {}",
+                MIN_PRECISION,
+                MAX_PRECISION,
+                precision
+            );
+        }
+        Ok(GroupedOperator::new(
+            src,
+            ApproxCountDistinct {
+                source_col,
+                group_by,
+                precision,
+            },
+        ))
+    }
+}
+
+pub struct ApproxCountDistinctDiff {
+    value: DfValue,
+    is_positive: bool,
+    group_by: Vec<DfValue>,
+}
+
+impl GroupedOperation for ApproxCountDistinct {
+    type Diff = ApproxCountDistinctDiff;
+
+    fn setup(&mut self, _: &Node) -> ReadySetResult<()> {
+        Ok(())
+    }
+
+    fn group_by(&self) -> &[usize] {
+        &self.group_by
+    }
+
+    fn to_diff(&self, record: &[DfValue], is_positive: bool) -> ReadySetResult<Self::Diff> {
+        let value = record
+            .get(self.source_col)
+            .ok_or(ReadySetError::InvalidRecordLength)?
+            .clone();
+        let group_by = record
+            .cloned_indices(self.group_by.iter().cloned())
+            .map_err(|_| ReadySetError::InvalidRecordLength)?;
+        Ok(ApproxCountDistinctDiff {
+            value,
+            is_positive,
+            group_by,
+        })
+    }
+
+    fn apply(
+        &self,
+        current: Option<&DfValue>,
+        diffs: &mut dyn Iterator<Item = Self::Diff>,
+        auxiliary_node_state: Option<&mut AuxiliaryNodeState>,
+    ) -> ReadySetResult<Option<DfValue>> {
+        let sketches = match auxiliary_node_state {
+            Some(AuxiliaryNodeState::ApproxCountDistinct(ref mut state)) => &mut state.sketches,
+            Some(_) => internal!("Incorrect auxiliary state for ApproxCountDistinct node"),
+            None => internal!("Missing auxiliary state for ApproxCountDistinct node"),
+        };
+
+        let mut diffs = diffs.peekable();
+        let Some(first_diff) = diffs.peek() else {
+            // Rebuilding a group which no longer has any rows
+            return Ok(Some(0.into()));
+        };
+        let group = first_diff.group_by.clone();
+
+        let mut sketch = match (current, sketches.remove(&group)) {
+            (Some(current), Some((estimate, sketch))) if *current == DfValue::from(estimate) => {
+                sketch
+            }
+            // If the sketch doesn't match the current value (or we don't have one at all), it
+            // needs to be rebuilt
+            (Some(_), _) => return Ok(None),
+            (None, _) => Sketch::new(self.precision),
+        };
+
+        for ApproxCountDistinctDiff {
+            value,
+            is_positive,
+            group_by,
+        } in diffs
+        {
+            if group_by != group {
+                internal!("ApproxCountDistinct got diffs for more than one group");
+            }
+            if value.is_none() {
+                continue;
+            }
+            if !is_positive {
+                return Ok(None);
+            }
+            sketch.insert(&value);
+        }
+
+        let estimate = sketch.estimate();
+        sketches.insert(group, (estimate, sketch));
+        Ok(Some(estimate.into()))
+    }
+
+    fn description(&self, detailed: bool) -> String {
+        if !detailed {
+            return "≈|*|".into();
+        }
+
+        format!(
+            "≈|{}|({}) γ{:?}",
+            self.source_col, self.precision, self.group_by
+        )
+    }
+
+    fn over_column(&self) -> usize {
+        self.source_col
+    }
+
+    fn output_col_type(&self) -> DfType {
+        DfType::BigInt
+    }
+
+    fn empty_value(&self) -> Option<DfValue> {
+        Some(0.into())
+    }
+
+    fn emit_empty(&self) -> bool {
+        self.group_by.is_empty()
+    }
+
+    fn can_lose_state(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Default)]
+/// Auxiliary State for a single ApproxCountDistinct Node, which is owned by a Domain.
+pub struct ApproxCountDistinctState {
+    /// The sketch for each group, along with the estimate we last emitted for that group
+    sketches: HashMap<Vec<DfValue>, (i64, Sketch)>,
+}
+
+impl ApproxCountDistinctState {
+    /// Forget the sketches kept for the given groups, which were evicted from the node's
+    /// materialized state
+    pub(crate) fn evict_groups(&mut self, evicted: &EvictedGroups) {
+        match evicted {
+            EvictedGroups::Exact(groups) => {
+                for group in groups {
+                    self.sketches.remove(*group);
+                }
+            }
+            EvictedGroups::Matching { .. } => {
+                self.sketches.retain(|group, _| !evicted.contains(group))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops;
+
+    fn setup_with_base(mat: bool) -> (ops::test::MockGraph, IndexPair) {
+        let mut g = ops::test::MockGraph::new();
+        let s = g.add_base("source", &["x", "y"]);
+
+        let c = ApproxCountDistinct::new(s.as_global(), 1, vec![0], DEFAULT_PRECISION).unwrap();
+
+        g.set_op("approx", &["x", "ys"], c, mat);
+        (g, s)
+    }
+
+    fn setup(mat: bool) -> ops::test::MockGraph {
+        setup_with_base(mat).0
+    }
+
+    fn estimate_for(rs: &Records, group: i32) -> Option<DfValue> {
+        rs.iter().find_map(|r| match r {
+            Record::Positive(r) if r[0] == group.into() => Some(r[1].clone()),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn it_describes() {
+        let c = setup(true);
+        assert_eq!(c.node().description(true), "≈|1|(12) γ[0]");
+    }
+
+    #[test]
+    fn rejects_invalid_precision() {
+        let s = NodeIndex::new(0);
+        ApproxCountDistinct::new(s, 1, vec![0], MIN_PRECISION - 1).unwrap_err();
+        ApproxCountDistinct::new(s, 1, vec![0], MAX_PRECISION + 1).unwrap_err();
+    }
+
+    #[test]
+    fn sketch_estimates() {
+        let mut sketch = Sketch::new(DEFAULT_PRECISION);
+        for _ in 0..3 {
+            for i in 0..10_000 {
+                sketch.insert(&i.into());
+            }
+        }
+        let estimate = sketch.estimate();
+        assert!(
+            (9_500..=10_500).contains(&estimate),
+            "estimate {estimate} too far from 10000"
+        );
+    }
+
+    #[test]
+    fn sketch_hash_is_stable() {
+        let hash = |value: DfValue| {
+            let mut hasher = StableHasher::default();
+            value.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(1.into()), hash(1.into()));
+        assert_ne!(hash(1.into()), hash(2.into()));
+
+        // The same values should always end up in the same registers
+        let mut a = Sketch::new(DEFAULT_PRECISION);
+        let mut b = Sketch::new(DEFAULT_PRECISION);
+        for i in 0..1000 {
+            a.insert(&i.into());
+            b.insert(&(999 - i).into());
+        }
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn it_forwards() {
+        let mut c = setup(true);
+
+        let rs = c.narrow_one_row(vec![1.into(), 1.into()], true);
+        assert_eq!(estimate_for(&rs, 1), Some(1.into()));
+
+        // Duplicates and nulls don't change the estimate
+        let rs = c.narrow_one(
+            vec![
+                (vec![1.into(), 1.into()], true),
+                (vec![1.into(), DfValue::None], true),
+            ],
+            true,
+        );
+        assert!(rs.is_empty());
+
+        let rs = c.narrow_one_row(vec![1.into(), 2.into()], true);
+        assert_eq!(estimate_for(&rs, 1), Some(2.into()));
+
+        let rs = c.narrow_one_row(vec![2.into(), 1.into()], true);
+        assert_eq!(estimate_for(&rs, 2), Some(1.into()));
+    }
+
+    #[test]
+    fn negative_rebuilds_from_parent() {
+        let (mut c, s) = setup_with_base(true);
+
+        c.seed(s, vec![1.into(), 1.into()]);
+        c.narrow_one_row(vec![1.into(), 1.into()], true);
+        c.seed(s, vec![1.into(), 2.into()]);
+        let rs = c.narrow_one_row(vec![1.into(), 2.into()], true);
+        assert_eq!(estimate_for(&rs, 1), Some(2.into()));
+
+        // Values can't be removed from a sketch, so removing one should rebuild the group from
+        // the rows still in the parent
+        c.unseed(s);
+        c.seed(s, vec![1.into(), 1.into()]);
+        let rs = c.narrow_one_row((vec![1.into(), 2.into()], false), true);
+        assert_eq!(estimate_for(&rs, 1), Some(1.into()));
+    }
+}
@@ -1,17 +1,20 @@
 //! Kinda (s)crappy group_concat() implementation
 
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt::Write;
 
 use common::DfValue;
+use itertools::Itertools;
+use nom_sql::OrderType;
 use readyset_data::{Collation, DfType};
 use readyset_errors::invariant_eq;
 use readyset_util::Indices;
 use serde::{Deserialize, Serialize};
 
 use crate::node::{AuxiliaryNodeState, Node};
-use crate::ops::grouped::{GroupedOperation, GroupedOperator};
+use crate::ops::grouped::{EvictedGroups, GroupedOperation, GroupedOperator};
 use crate::prelude::*;
 
 /// The last stored state for a given group.
@@ -19,8 +22,9 @@ use crate::prelude::*;
 struct LastState {
     /// The string representation we last emitted for this group.
     string_repr: String,
-    /// A vector containing the actual data
-    data: Vec<DfValue>,
+    /// A vector containing the actual data, along with the values of the columns we're ordering
+    /// by for each value, kept sorted by those values.
+    data: Vec<(Vec<DfValue>, DfValue)>,
 }
 
 impl Default for LastState {
@@ -35,6 +39,10 @@ impl Default for LastState {
 /// `GroupConcat` partially implements the `GROUP_CONCAT` SQL aggregate function, which
 /// aggregates a set of arbitrary `DfValue`s into a string representation separated by
 /// a user-defined separator.
+///
+/// Values within each group are concatenated in the order given by `order_by`, or in the order
+/// they were received if `order_by` is empty. Consistent with both MySQL and PostgreSQL, `NULL`
+/// values are skipped, and groups containing only `NULL` values are aggregated to `NULL`.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct GroupConcat {
     /// Which column to aggregate.
@@ -43,6 +51,9 @@ pub struct GroupConcat {
     group_by: Vec<usize>,
     /// The user-defined separator.
     separator: String,
+    /// The columns to order values by within each group, and the direction to order them in.
+    #[serde(default)]
+    order_by: Vec<(usize, OrderType)>,
 }
 
 fn concat_fmt<F: Write>(f: &mut F, dt: &DfValue) -> ReadySetResult<()> {
@@ -58,12 +69,13 @@ fn concat_fmt<F: Write>(f: &mut F, dt: &DfValue) -> ReadySetResult<()> {
 
 impl GroupConcat {
     /// Construct a new `GroupConcat`, aggregating the provided `source_col` and separating
-    /// aggregated data with the provided `separator`.
+    /// aggregated data with the provided `separator`, in the order given by `order_by`.
     pub fn new(
         src: NodeIndex,
         source_col: usize,
         group_by: Vec<usize>,
         separator: String,
+        order_by: Vec<(usize, OrderType)>,
     ) -> ReadySetResult<GroupedOperator<GroupConcat>> {
         Ok(GroupedOperator::new(
             src,
@@ -71,13 +83,24 @@ impl GroupConcat {
                 source_col,
                 group_by,
                 separator,
+                order_by,
             },
         ))
     }
+
+    /// Compare two sets of values for the `order_by` columns
+    fn cmp_order_keys(&self, a: &[DfValue], b: &[DfValue]) -> Ordering {
+        self.order_by
+            .iter()
+            .zip(a.iter().zip(b))
+            .map(|((_, ot), (a, b))| ot.apply(a.cmp(b)))
+            .fold(Ordering::Equal, Ordering::then)
+    }
 }
 
 pub struct ConcatDiff {
     value: DfValue,
+    order_key: Vec<DfValue>,
     is_positive: bool,
     group_by: Vec<DfValue>,
 }
@@ -98,12 +121,16 @@ impl GroupedOperation for GroupConcat {
             .get(self.source_col)
             .ok_or(ReadySetError::InvalidRecordLength)?
             .clone();
+        let order_key = record
+            .cloned_indices(self.order_by.iter().map(|(col, _)| *col))
+            .map_err(|_| ReadySetError::InvalidRecordLength)?;
         // We need this to figure out which state to use.
         let group_by = record
             .cloned_indices(self.group_by.iter().cloned())
             .map_err(|_| ReadySetError::InvalidRecordLength)?;
         Ok(ConcatDiff {
             value,
+            order_key,
             is_positive,
             group_by,
         })
@@ -148,45 +175,56 @@ impl GroupedOperation for GroupConcat {
         };
         for ConcatDiff {
             value,
+            order_key,
             is_positive,
             group_by,
         } in diffs
         {
             invariant_eq!(group_by, group);
+            if value.is_none() {
+                continue;
+            }
+
             if is_positive {
-                prev_state.data.push(value);
+                // Insert after all values which compare equal, so that values with the same order
+                // key stay in the order they were received
+                let pos = prev_state.data.partition_point(|(k, _)| {
+                    self.cmp_order_keys(k, &order_key) != Ordering::Greater
+                });
+                prev_state.data.insert(pos, (order_key, value));
             } else {
-                let item_pos = prev_state
+                match prev_state
                     .data
                     .iter()
-                    .rposition(|x| x == &value)
-                    .ok_or_else(|| {
-                        #[cfg(feature = "display literals")]
-                        {
-                            internal_err!(
-                                "group_concat couldn't remove {:?} from {:?}",
-                                value,
-                                prev_state.data
-                            )
-                        }
-                        #[cfg(not(feature = "display literals"))]
-                        internal_err!("group_concat couldn't remove value from data")
-                    })?;
-                prev_state.data.remove(item_pos);
+                    .rposition(|(k, v)| v == &value && k == &order_key)
+                {
+                    Some(item_pos) => {
+                        prev_state.data.remove(item_pos);
+                    }
+                    // If we can't find the value we're removing, our state has diverged from our
+                    // parent's, so throw it away and recompute the group from scratch
+                    None => return Ok(None),
+                }
             }
         }
-        // what I *really* want here is Haskell's "intercalate" ~eta
+
         let mut out_str = String::new();
-        for (i, piece) in prev_state.data.iter().enumerate() {
-            // TODO(eta): not unwrap, maybe
-            concat_fmt(&mut out_str, piece)?;
-            if i < prev_state.data.len() - 1 {
+        for (i, (_, piece)) in prev_state.data.iter().enumerate() {
+            if i > 0 {
                 write!(&mut out_str, "{}", self.separator).unwrap();
             }
+            concat_fmt(&mut out_str, piece)?;
         }
-        prev_state.string_repr = out_str.clone();
+        // Consistent with both MySQL and PostgreSQL, a group with no non-`NULL` values concatenates
+        // to `NULL` rather than the empty string
+        let out = if prev_state.data.is_empty() {
+            DfValue::None
+        } else {
+            out_str.clone().into()
+        };
+        prev_state.string_repr = out_str;
         last_state.insert(group, prev_state);
-        Ok(Some(out_str.into()))
+        Ok(Some(out))
     }
 
     fn description(&self, detailed: bool) -> String {
@@ -194,10 +232,20 @@ impl GroupedOperation for GroupConcat {
             return "CONCAT2".into();
         }
 
-        format!(
-            "||({}, {:?}) γ{:?}",
-            self.source_col, self.separator, self.group_by
-        )
+        let mut desc = format!("||({}, {:?}", self.source_col, self.separator);
+        if !self.order_by.is_empty() {
+            write!(
+                &mut desc,
+                " ORDER BY {}",
+                self.order_by
+                    .iter()
+                    .map(|(col, ot)| format!("{col} {ot}"))
+                    .join(", ")
+            )
+            .unwrap();
+        }
+        write!(&mut desc, ") γ{:?}", self.group_by).unwrap();
+        desc
     }
 
     fn over_column(&self) -> usize {
@@ -223,6 +271,23 @@ pub struct GroupConcatState {
     last_state: HashMap<Vec<DfValue>, LastState>,
 }
 
+impl GroupConcatState {
+    /// Forget the state kept for the given groups, which were evicted from the node's
+    /// materialized state
+    pub(crate) fn evict_groups(&mut self, evicted: &EvictedGroups) {
+        match evicted {
+            EvictedGroups::Exact(groups) => {
+                for group in groups {
+                    self.last_state.remove(*group);
+                }
+            }
+            EvictedGroups::Matching { .. } => {
+                self.last_state.retain(|group, _| !evicted.contains(group))
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unreachable)]
 mod tests {
@@ -233,7 +298,7 @@ mod tests {
         let mut g = ops::test::MockGraph::new();
         let s = g.add_base("source", &["x", "y"]);
 
-        let c = GroupConcat::new(s.as_global(), 1, vec![0], String::from("#")).unwrap();
+        let c = GroupConcat::new(s.as_global(), 1, vec![0], String::from("#"), vec![]).unwrap();
 
         g.set_op("concat", &["x", "ys"], c, mat);
         g
@@ -398,6 +463,90 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn it_orders_within_groups() {
+        let mut g = ops::test::MockGraph::new();
+        let s = g.add_base("source", &["x", "y", "z"]);
+        let c = GroupConcat::new(
+            s.as_global(),
+            1,
+            vec![0],
+            String::from(", "),
+            vec![(2, OrderType::OrderDescending)],
+        )
+        .unwrap();
+        g.set_op("concat", &["x", "ys"], c, true);
+
+        let rs = g.narrow_one(
+            vec![
+                (vec![1.into(), "a".into(), 1.into()], true),
+                (vec![1.into(), "b".into(), 3.into()], true),
+                (vec![1.into(), DfValue::None, 4.into()], true),
+                (vec![1.into(), "c".into(), 2.into()], true),
+            ],
+            true,
+        );
+        assert_eq!(
+            rs,
+            vec![Record::Positive(vec![1.into(), "b, c, a".into(), 4.into()])].into()
+        );
+
+        // deleting a value from the middle of the group should retract the old value and emit the
+        // new one, preserving the order of the remaining values
+        let rs = g.narrow_one_row((vec![1.into(), "c".into(), 2.into()], false), true);
+        assert_eq!(
+            rs,
+            vec![
+                Record::Negative(vec![1.into(), "b, c, a".into(), 4.into()]),
+                Record::Positive(vec![1.into(), "b, a".into(), 3.into()]),
+            ]
+            .into()
+        );
+
+        let rs = g.narrow_one_row(vec![1.into(), "d".into(), 3.into()], true);
+        assert_eq!(
+            rs,
+            vec![
+                Record::Negative(vec![1.into(), "b, a".into(), 3.into()]),
+                Record::Positive(vec![1.into(), "b, d, a".into(), 4.into()]),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn it_emits_null_for_all_null_groups() {
+        let mut c = setup(true);
+
+        let rs = c.narrow_one_row(vec![1.into(), DfValue::None], true);
+        assert_eq!(
+            rs,
+            vec![Record::Positive(vec![1.into(), DfValue::None, 1.into()])].into()
+        );
+
+        // a non-NULL value replaces the NULL
+        let rs = c.narrow_one_row(vec![1.into(), 2.into()], true);
+        assert_eq!(
+            rs,
+            vec![
+                Record::Negative(vec![1.into(), DfValue::None, 1.into()]),
+                Record::Positive(vec![1.into(), "2".into(), 2.into()]),
+            ]
+            .into()
+        );
+
+        // and removing it again goes back to NULL
+        let rs = c.narrow_one_row((vec![1.into(), 2.into()], false), true);
+        assert_eq!(
+            rs,
+            vec![
+                Record::Negative(vec![1.into(), "2".into(), 2.into()]),
+                Record::Positive(vec![1.into(), DfValue::None, 1.into()]),
+            ]
+            .into()
+        );
+    }
+
     #[test]
     fn it_suggests_indices() {
         let me = 1.into();
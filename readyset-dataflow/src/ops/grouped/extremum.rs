@@ -1,11 +1,19 @@
+use std::collections::{BTreeMap, HashMap};
+
 use readyset_data::DfType;
 use readyset_errors::{invariant, ReadySetResult};
 use serde::{Deserialize, Serialize};
 
 use crate::node::AuxiliaryNodeState;
-use crate::ops::grouped::{GroupedOperation, GroupedOperator};
+use crate::ops::grouped::{
+    group_hash, group_values_hash, EvictedGroups, GroupHash, GroupedOperation, GroupedOperator,
+};
 use crate::prelude::*;
 
+/// The default for the maximum number of distinct values tracked per group by extremum operators.
+/// See [`ExtremumState`].
+pub const DEFAULT_MAX_TRACKED_EXTREMUM_VALUES: usize = 32;
+
 /// Supported kinds of extremum operators.
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub enum Extremum {
@@ -40,6 +48,14 @@ impl Extremum {
             },
         )
     }
+
+    /// Returns true if `a` is strictly more extreme than `b` for this operation
+    fn is_more_extreme(&self, a: &DfValue, b: &DfValue) -> bool {
+        match self {
+            Extremum::Min => a < b,
+            Extremum::Max => a > b,
+        }
+    }
 }
 
 /// `ExtremumOperator` implements a Dataflow node that performs common aggregation operations such
@@ -54,6 +70,10 @@ impl Extremum {
 /// incoming record. The output record is constructed by concatenating the columns identifying the
 /// group, and appending the aggregated value. For example, for a sum with `self.over == 1`, a
 /// previous sum of `3`, and an incoming record with `[a, 1, x]`, the output would be `[a, x, 4]`.
+///
+/// To avoid having to re-scan the whole group from the parent every time the current extremum is
+/// deleted, the operator keeps the most extreme values of each group in its [`ExtremumState`], and
+/// only falls back to a full re-scan once all of those have been deleted.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExtremumOperator {
     op: Extremum,
@@ -67,8 +87,106 @@ pub enum DiffType {
     None,
 }
 
+/// Diff type for extremum operators
+pub struct ExtremumDiff {
+    diff: DiffType,
+    /// Hash of the values of the group by columns, used to find the group's tracked values
+    group_hash: GroupHash,
+}
+
+/// The most extreme values in the `over` column of a single group, as an ordered multiset.
+///
+/// If `bound` is set, some values in the group are no longer tracked, and all of those values are
+/// at most as extreme as `bound`. Since values are only ever dropped from the least extreme end,
+/// the most extreme tracked value is the extremum of the group as long as any values are tracked.
+#[derive(Debug, Default)]
+struct GroupExtremes {
+    values: BTreeMap<DfValue, usize>,
+    bound: Option<DfValue>,
+}
+
+impl GroupExtremes {
+    fn most_extreme(&self, op: &Extremum) -> Option<&DfValue> {
+        match op {
+            Extremum::Min => self.values.keys().next(),
+            Extremum::Max => self.values.keys().next_back(),
+        }
+    }
+
+    fn insert(&mut self, value: DfValue, op: &Extremum, max_tracked_values: usize) {
+        if let Some(bound) = &self.bound {
+            if !op.is_more_extreme(&value, bound) {
+                // Not tracked, but still covered by the bound
+                return;
+            }
+        }
+
+        *self.values.entry(value).or_insert(0) += 1;
+        if self.values.len() > max_tracked_values {
+            let dropped = match op {
+                Extremum::Min => self.values.pop_last(),
+                Extremum::Max => self.values.pop_first(),
+            };
+            self.bound = dropped.map(|(v, _)| v);
+        }
+    }
+
+    fn remove(&mut self, value: &DfValue) {
+        if let Some(count) = self.values.get_mut(value) {
+            *count -= 1;
+            if *count == 0 {
+                self.values.remove(value);
+            }
+        }
+        // Otherwise, the value was one of the untracked values beyond `bound`
+    }
+}
+
+/// Auxiliary State for an Extremum node, which is owned by a Domain.
+///
+/// Tracks up to `max_tracked_values` distinct values for each group, so that deleting the current
+/// extremum of a group can usually be handled without an upquery for all records in the group.
+#[derive(Debug)]
+pub struct ExtremumState {
+    groups: HashMap<GroupHash, GroupExtremes>,
+    max_tracked_values: usize,
+}
+
+impl ExtremumState {
+    /// Create a new, empty `ExtremumState` that tracks up to `max_tracked_values` distinct values
+    /// per group
+    pub fn new(max_tracked_values: usize) -> Self {
+        Self {
+            groups: Default::default(),
+            max_tracked_values: max_tracked_values.max(1),
+        }
+    }
+
+    /// Forget the values tracked for the given groups, which were evicted from the node's
+    /// materialized state
+    pub(crate) fn evict_groups(&mut self, evicted: &EvictedGroups) {
+        match evicted {
+            EvictedGroups::Exact(groups) => {
+                for group in groups {
+                    self.groups.remove(&group_values_hash(group.iter()));
+                }
+            }
+            // We only have the hashes of the groups we're tracking, so we can't tell which of them
+            // were evicted. Since the tracked values for a group are only trusted if they agree
+            // with its current value, forgetting all of them is always safe.
+            EvictedGroups::Matching { .. } => self.groups.clear(),
+        }
+    }
+}
+
+impl Default for ExtremumState {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_TRACKED_EXTREMUM_VALUES)
+    }
+}
+
 impl GroupedOperation for ExtremumOperator {
-    type Diff = DiffType;
+    type Diff = ExtremumDiff;
 
     fn setup(&mut self, parent: &Node) -> ReadySetResult<()> {
         invariant!(
@@ -85,56 +203,77 @@ impl GroupedOperation for ExtremumOperator {
     fn to_diff(&self, r: &[DfValue], pos: bool) -> ReadySetResult<Self::Diff> {
         #[allow(clippy::indexing_slicing)] // Invariant documented.
         let v = &r[self.over];
-        if let DfValue::None = *v {
-            Ok(DiffType::None)
+        let diff = if let DfValue::None = *v {
+            DiffType::None
         } else if pos {
-            Ok(DiffType::Insert(v.clone()))
+            DiffType::Insert(v.clone())
         } else {
-            Ok(DiffType::Remove(v.clone()))
-        }
+            DiffType::Remove(v.clone())
+        };
+        Ok(ExtremumDiff {
+            diff,
+            group_hash: group_hash(&self.group, r),
+        })
     }
 
     fn apply(
         &self,
         current: Option<&DfValue>,
         diffs: &mut dyn Iterator<Item = Self::Diff>,
-        _auxiliary_node_state: Option<&mut AuxiliaryNodeState>,
+        auxiliary_node_state: Option<&mut AuxiliaryNodeState>,
     ) -> ReadySetResult<Option<DfValue>> {
-        // Extreme values are those that are at least as extreme as the current min/max (if any).
-        // let mut is_extreme_value : Box<dyn Fn(i64) -> bool> = Box::new(|_|true);
-        let mut extreme_values: Vec<DfValue> = vec![];
-        if let Some(d) = current {
-            extreme_values.push(d.clone());
-        }
+        let state = match auxiliary_node_state {
+            Some(AuxiliaryNodeState::Extremum(ref mut extremum_state)) => extremum_state,
+            Some(_) => internal!("Incorrect auxiliary state for Extremum node"),
+            None => internal!("Missing auxiliary state for Extremum node"),
+        };
+
+        let max_tracked_values = state.max_tracked_values;
+        let mut diffs = diffs.peekable();
+        let group_hash = match diffs.peek() {
+            Some(diff) => diff.group_hash,
+            None => return Ok(current.cloned()),
+        };
 
-        let is_extreme_value = |x: &DfValue| {
-            if let Some(n) = current {
-                match self.op {
-                    Extremum::Max => x >= n,
-                    Extremum::Min => x <= n,
+        let extremes = state.groups.entry(group_hash).or_default();
+        match current {
+            // No current value means the group is being built from scratch (possibly after having
+            // been evicted), so anything we were tracking for it is stale
+            None => *extremes = Default::default(),
+            Some(DfValue::None) if extremes.values.is_empty() && extremes.bound.is_none() => {}
+            Some(DfValue::None) => *extremes = Default::default(),
+            Some(v) if extremes.most_extreme(&self.op) == Some(v) => {}
+            // We don't know what we were tracking for this group (or it's out of date), but we do
+            // know that the current value is the most extreme one in the group
+            Some(v) => {
+                *extremes = GroupExtremes {
+                    values: BTreeMap::from([(v.clone(), 1)]),
+                    bound: Some(v.clone()),
                 }
-            } else {
-                true
             }
-        };
+        }
 
         for d in diffs {
-            match d {
-                DiffType::Insert(v) if is_extreme_value(&v) => extreme_values.push(v),
-                DiffType::Remove(v) if is_extreme_value(&v) => {
-                    if let Some(i) = extreme_values.iter().position(|x| *x == v) {
-                        extreme_values.swap_remove(i);
-                    }
-                }
-                _ => {}
-            };
+            match d.diff {
+                DiffType::Insert(v) => extremes.insert(v, &self.op, max_tracked_values),
+                DiffType::Remove(v) => extremes.remove(&v),
+                DiffType::None => {}
+            }
         }
 
-        let extreme = match self.op {
-            Extremum::Min => extreme_values.into_iter().min(),
-            Extremum::Max => extreme_values.into_iter().max(),
+        let extreme = match extremes.most_extreme(&self.op) {
+            Some(v) => Some(v.clone()),
+            // We've deleted every value we were tracking, but there are more values in the group
+            // that we weren't - we need to re-scan the group to find the new extremum
+            None if extremes.bound.is_some() => None,
+            // There are no non-null values left in the group
+            None => Some(DfValue::None),
         };
 
+        if extremes.values.is_empty() {
+            state.groups.remove(&group_hash);
+        }
+
         Ok(extreme)
     }
 
@@ -176,6 +315,9 @@ impl GroupedOperation for ExtremumOperator {
 #[cfg(test)]
 #[allow(clippy::panic, clippy::unreachable)]
 mod tests {
+    use readyset_client::KeyComparison;
+    use readyset_data::Bound;
+
     use super::*;
     use crate::{ops, LookupIndex};
 
@@ -357,6 +499,115 @@ mod tests {
         assert!(out.is_empty());
     }
 
+    #[test]
+    fn it_forwards_next_maximum_after_deleting_maximum() {
+        let mut c = setup(Extremum::Max, true);
+        let key = 1;
+
+        let u = (1..=10)
+            .map(|i| (vec![key.into(), i.into()], true))
+            .collect::<Vec<_>>();
+        c.narrow_one(u, true);
+
+        // Deleting the max should fall back to the next largest tracked value, without needing to
+        // re-scan the group
+        let rs = c.narrow_one_row((vec![key.into(), 10.into()], false), true);
+        assert_eq!(
+            rs,
+            vec![
+                (
+                    vec![DfValue::from(key), DfValue::from(10), DfValue::from(10)],
+                    false
+                ),
+                (
+                    vec![DfValue::from(key), DfValue::from(9), DfValue::from(9)],
+                    true
+                ),
+            ]
+            .into()
+        );
+
+        // Deleting a duplicate of the max should keep the max
+        c.narrow_one_row(vec![key.into(), 9.into()], true);
+        let rs = c.narrow_one_row((vec![key.into(), 9.into()], false), true);
+        assert_eq!(
+            rs,
+            vec![
+                (
+                    vec![DfValue::from(key), DfValue::from(9), DfValue::from(10)],
+                    false
+                ),
+                (
+                    vec![DfValue::from(key), DfValue::from(9), DfValue::from(9)],
+                    true
+                ),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn group_extremes_respects_bound() {
+        let op = Extremum::Min;
+        let mut extremes = GroupExtremes::default();
+        for i in [5, 3, 4, 1, 2] {
+            extremes.insert(i.into(), &op, 3);
+        }
+        assert_eq!(extremes.values.len(), 3);
+        assert_eq!(extremes.bound, Some(DfValue::from(4)));
+        assert_eq!(extremes.most_extreme(&op), Some(&DfValue::from(1)));
+
+        // Values beyond the bound aren't tracked
+        extremes.insert(6.into(), &op, 3);
+        assert!(!extremes.values.contains_key(&DfValue::from(6)));
+
+        extremes.remove(&1.into());
+        extremes.remove(&2.into());
+        assert_eq!(extremes.most_extreme(&op), Some(&DfValue::from(3)));
+
+        // Values more extreme than the bound are still tracked once it's set
+        extremes.insert(0.into(), &op, 3);
+        assert_eq!(extremes.most_extreme(&op), Some(&DfValue::from(0)));
+
+        extremes.remove(&0.into());
+        extremes.remove(&3.into());
+        assert_eq!(extremes.most_extreme(&op), None);
+        assert!(extremes.bound.is_some());
+    }
+
+    #[test]
+    fn it_forgets_evicted_groups() {
+        let max = ExtremumOperator {
+            op: Extremum::Max,
+            over: 1,
+            group: vec![0],
+        };
+        let mut state = AuxiliaryNodeState::Extremum(Default::default());
+        for group in 1..=3 {
+            let mut diffs = [3, 5]
+                .into_iter()
+                .map(|v| max.to_diff(&[group.into(), v.into()], true).unwrap());
+            max.apply(None, &mut diffs, Some(&mut state)).unwrap();
+        }
+        let tracked_groups = |state: &AuxiliaryNodeState| match state {
+            AuxiliaryNodeState::Extremum(state) => state.groups.len(),
+            _ => unreachable!(),
+        };
+        assert_eq!(tracked_groups(&state), 3);
+
+        let keys = [KeyComparison::Equal(vec1![1.into()])];
+        state.evict_groups(&EvictedGroups::new(1, &[0], &keys));
+        assert_eq!(tracked_groups(&state), 2);
+
+        // We can't tell which groups a range of keys covered, so we have to forget all of them
+        let keys = [KeyComparison::Range((
+            Bound::Included(vec1![2.into()]),
+            Bound::Excluded(vec1![3.into()]),
+        ))];
+        state.evict_groups(&EvictedGroups::new(1, &[0], &keys));
+        assert_eq!(tracked_groups(&state), 0);
+    }
+
     #[test]
     fn it_suggests_indices() {
         let me = 1.into();
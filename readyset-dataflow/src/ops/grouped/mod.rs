@@ -1,9 +1,12 @@
 use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::convert::TryInto;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 use dataflow_state::PointKey;
+use readyset_client::KeyComparison;
 use readyset_data::DfType;
 use readyset_errors::{internal_err, ReadySetResult};
 use serde::{Deserialize, Serialize};
@@ -14,9 +17,89 @@ use crate::processing::{ColumnSource, IngredientLookupResult, LookupIndex, Looku
 
 // pub mod latest;
 pub mod aggregate;
+pub mod approx_distinct;
 pub mod concat;
 pub mod extremum;
 
+/// Hash of the values of the group by columns of a record, used by grouped operations to key their
+/// auxiliary state by group.
+pub type GroupHash = u64;
+
+/// Compute the [`GroupHash`] for the values of the `group_by` columns in `rec`.
+pub(crate) fn group_hash(group_by: &[usize], rec: &[DfValue]) -> GroupHash {
+    group_values_hash(group_by.iter().map(|&col| {
+        #[allow(clippy::indexing_slicing)]
+        // Grouped operators are constructed with a group by that is derived from existing
+        // columns. If we lack a column in the record, then something has gone horribly wrong and
+        // we should panic.
+        &rec[col]
+    }))
+}
+
+/// Compute the [`GroupHash`] for a group, given the values of its group by columns in order.
+pub(crate) fn group_values_hash<'a, I>(values: I) -> GroupHash
+where
+    I: IntoIterator<Item = &'a DfValue>,
+{
+    let mut hasher = DefaultHasher::new();
+    for value in values {
+        value.hash(&mut hasher)
+    }
+    hasher.finish()
+}
+
+/// The groups of a grouped operator which were evicted from its materialized state.
+///
+/// Grouped operators emit the values of their group by columns first, in order, so keys evicted
+/// from an index on exactly those columns each identify a single group.
+pub(crate) enum EvictedGroups<'a> {
+    /// The values of the group by columns of each of the evicted groups
+    Exact(Vec<&'a [DfValue]>),
+    /// The keys evicted from an index on `columns`, which may each match any number of groups
+    Matching {
+        columns: &'a [usize],
+        keys: &'a [KeyComparison],
+    },
+}
+
+impl<'a> EvictedGroups<'a> {
+    /// Construct the [`EvictedGroups`] for the given `keys`, evicted from the index on `columns`
+    /// of a grouped operator with `group_len` group by columns
+    pub(crate) fn new(group_len: usize, columns: &'a [usize], keys: &'a [KeyComparison]) -> Self {
+        if columns.iter().copied().eq(0..group_len) {
+            if let Some(groups) = keys
+                .iter()
+                .map(|key| key.equal().map(|key| key.as_slice()))
+                .collect::<Option<Vec<_>>>()
+            {
+                return Self::Exact(groups);
+            }
+        }
+
+        Self::Matching { columns, keys }
+    }
+
+    /// Returns true if the group with the given values for its group by columns was evicted.
+    ///
+    /// If the evicted keys were on columns other than the group by columns we can't tell, so we
+    /// assume it was.
+    pub(crate) fn contains(&self, group: &[DfValue]) -> bool {
+        match self {
+            Self::Exact(groups) => groups.iter().any(|evicted| *evicted == group),
+            Self::Matching { columns, keys } => {
+                match columns
+                    .iter()
+                    .map(|col| group.get(*col))
+                    .collect::<Option<Vec<_>>>()
+                {
+                    Some(values) => keys.iter().any(|key| key.contains(values.iter().copied())),
+                    None => true,
+                }
+            }
+        }
+    }
+}
+
 /// Trait for implementing operations that collapse a group of records into a single record.
 ///
 /// Implementors of this trait can be used as nodes in a `flow::FlowGraph` by wrapping them in a
@@ -134,6 +217,11 @@ impl<T: GroupedOperation> GroupedOperator<T> {
     pub fn output_col_type(&self) -> DfType {
         self.inner.output_col_type()
     }
+
+    /// Returns the columns in the parent node that this operator groups by
+    pub fn group_by(&self) -> &[usize] {
+        &self.group_by
+    }
 }
 
 /// Extract a copy of all values in the record being targeted by the group
@@ -115,6 +115,16 @@ impl Join {
         }
     }
 
+    /// Returns the kind of join this node performs
+    pub fn kind(&self) -> &JoinType {
+        &self.kind
+    }
+
+    /// Returns the join key, as pairs of columns in the left and right parents respectively
+    pub fn on(&self) -> &[(usize, usize)] {
+        &self.on
+    }
+
     fn on_left(&self) -> Vec<usize> {
         self.on.iter().map(|(l, _)| *l).collect()
     }
@@ -330,6 +340,18 @@ impl Ingredient for Join {
 
         let mut ret: Vec<Record> = Vec::with_capacity(rs.len());
 
+        // For a left join with updates from the right side, whether we have to emit/delete NULL
+        // rows depends on how the whole batch changed the row count for each key, and records
+        // with the same key aren't necessarily adjacent in the batch
+        let mut right_count_diffs: HashMap<Vec<DfValue>, isize> = HashMap::new();
+        if self.kind == JoinType::Left && !from_left {
+            for rec in rs.iter() {
+                *right_count_diffs
+                    .entry(from_key.iter().map(|i| rec[*i].clone()).collect())
+                    .or_default() += if rec.is_positive() { 1 } else { -1 };
+            }
+        }
+
         let grouped_records = rs
             .into_iter()
             .group_by(|rec| from_key.iter().map(|i| rec[*i].clone()).collect::<Vec<_>>());
@@ -432,6 +454,9 @@ impl Ingredient for Join {
                 }
             };
 
+            // Only handle the NULL rows for the first group with this key
+            let right_count_diff = right_count_diffs.remove(&join_key);
+
             if is_replay && !nulls {
                 lookups.push(Lookup {
                     on: other,
@@ -444,12 +469,9 @@ impl Ingredient for Join {
 
             let other_rows = other_records.collect::<Result<Vec<_>, _>>()?;
 
-            let mut rc_diff = 0isize;
             for r in group {
                 let (row, positive) = r.extract();
 
-                rc_diff += if positive { 1 } else { -1 };
-
                 if other_rows.is_empty() {
                     if self.kind == JoinType::Left && from_left {
                         // left join, got a thing from left, no rows in right == NULL
@@ -468,7 +490,7 @@ impl Ingredient for Join {
 
             // For a left join with updates from the right side, we also have to emit/delete NULL
             // rows if row count changed to/from zero
-            if let Some(new_rc) = new_right_count {
+            if let (Some(new_rc), Some(rc_diff)) = (new_right_count, right_count_diff) {
                 let old_rc = new_rc as isize - rc_diff;
                 if new_rc == 0 && old_rc != 0 {
                     for other in other_rows.iter() {
@@ -840,6 +862,43 @@ mod tests {
         assert_eq!(rs.len(), 0);
     }
 
+    #[test]
+    fn right_updates_with_interleaved_keys() {
+        let (mut j, l, r) = setup();
+        let l_a1 = vec![1.into(), "a".into()];
+        let l_b2 = vec![2.into(), "b".into()];
+
+        let r_x1 = vec![1.into(), "x".into()];
+        let r_y1 = vec![1.into(), "y".into()];
+        let r_z2 = vec![2.into(), "z".into()];
+
+        j.seed(l, l_a1.clone());
+        j.one_row(l, l_a1, false);
+        j.seed(l, l_b2.clone());
+        j.one_row(l, l_b2, false);
+
+        // both keys go from zero to non-zero right rows, so each NULL row should be retracted
+        // exactly once, even though the records for key 1 aren't adjacent
+        j.seed(r, r_x1.clone());
+        j.seed(r, r_z2.clone());
+        j.seed(r, r_y1.clone());
+        let rs = j.one(
+            r,
+            vec![
+                (r_x1, true).into(),
+                (r_z2, true).into(),
+                (r_y1, true).into(),
+            ],
+            false,
+        );
+        assert_eq!(rs.len(), 5);
+        assert!(rs.has_positive(&[1.into(), "a".into(), "x".into()][..]));
+        assert!(rs.has_positive(&[1.into(), "a".into(), "y".into()][..]));
+        assert!(rs.has_positive(&[2.into(), "b".into(), "z".into()][..]));
+        assert!(rs.has_negative(&[1.into(), "a".into(), DfValue::None][..]));
+        assert!(rs.has_negative(&[2.into(), "b".into(), DfValue::None][..]));
+    }
+
     #[test]
     fn nulls_from_left() {
         let (mut j, l, r) = setup();
@@ -0,0 +1,367 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+use dataflow_state::PointKey;
+use itertools::Itertools;
+use readyset_client::internal;
+use readyset_errors::{internal, internal_err, ReadySetResult};
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::processing::{ColumnSource, IngredientLookupResult, LookupIndex, LookupMode};
+
+/// Latest provides an operator that emits only the most recent row for each group, as determined
+/// by the value of a timestamp (or other monotonically increasing) column.
+///
+/// Rows with a later value in the `over` column replace the current row for their group, and
+/// deleting the current row for a group causes the group to be recomputed from the parent. Ties in
+/// the `over` column are broken by comparing the rest of the row, so that which row is emitted for
+/// a group doesn't depend on the order rows arrive in. `NULL` compares less than every other value,
+/// so rows with a `NULL` timestamp are only emitted if no other rows exist for their group.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Latest {
+    src: IndexPair,
+
+    // some cache state
+    our_index: Option<IndexPair>,
+
+    /// The columns that this operator is keyed on
+    group_by: Vec<usize>,
+    /// The column used to determine which row is the most recent
+    over: usize,
+}
+
+impl Latest {
+    /// Construct a new latest operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - this operator's ancestor
+    /// * `group_by` - the columns that this operator is keyed on
+    /// * `over` - the column used to determine which row is the most recent
+    pub fn new(src: NodeIndex, group_by: Vec<usize>, over: usize) -> Self {
+        Latest {
+            src: src.into(),
+            our_index: None,
+            group_by,
+            over,
+        }
+    }
+
+    /// Returns the columns that this operator is keyed on
+    pub fn group_by(&self) -> &[usize] {
+        &self.group_by
+    }
+
+    /// Returns the column used to determine which row is the most recent
+    pub fn over(&self) -> usize {
+        self.over
+    }
+
+    /// Compare two rows by recency, breaking ties by comparing the rest of the row
+    fn cmp_rows(&self, a: &[DfValue], b: &[DfValue]) -> Ordering {
+        a.get(self.over)
+            .cmp(&b.get(self.over))
+            .then_with(|| a.cmp(b))
+    }
+
+    /// Project the columns we are grouping by out of the given record
+    fn project_group(&self, rec: &[DfValue]) -> ReadySetResult<Vec<DfValue>> {
+        self.group_by
+            .iter()
+            .map(|&col| {
+                rec.get(col)
+                    .cloned()
+                    .ok_or_else(|| internal_err!("Record too short for latest group column"))
+            })
+            .collect()
+    }
+
+    /// Look up the most recent row in our parent for the given group
+    fn lookup_latest(
+        &self,
+        group_key: &[DfValue],
+        nodes: &DomainNodes,
+        state: &StateMap,
+    ) -> ReadySetResult<Option<Vec<DfValue>>> {
+        match self.lookup(
+            *self.src,
+            &self.group_by,
+            &PointKey::from(group_key.iter().cloned()),
+            nodes,
+            state,
+            LookupMode::Strict,
+        )? {
+            IngredientLookupResult::Miss => {
+                internal!("We shouldn't have been able to get this record if the parent would miss")
+            }
+            IngredientLookupResult::Records(rs) => {
+                let mut latest: Option<Vec<DfValue>> = None;
+                for row in rs {
+                    let row = row?;
+                    if latest
+                        .as_ref()
+                        .map_or(true, |l| self.cmp_rows(&row, l) == Ordering::Greater)
+                    {
+                        latest = Some(row.into_owned());
+                    }
+                }
+                Ok(latest)
+            }
+        }
+    }
+}
+
+impl Ingredient for Latest {
+    fn ancestors(&self) -> Vec<NodeIndex> {
+        vec![self.src.as_global()]
+    }
+
+    impl_replace_sibling!(src);
+
+    fn on_commit(&mut self, us: NodeIndex, remap: &HashMap<NodeIndex, IndexPair>) {
+        // who's our parent really?
+        self.src.remap(remap);
+
+        // who are we?
+        self.our_index = Some(remap[&us]);
+    }
+
+    fn on_input(
+        &mut self,
+        from: LocalNodeIndex,
+        rs: Records,
+        replay: &ReplayContext,
+        nodes: &DomainNodes,
+        state: &StateMap,
+        _auxiliary_node_states: &mut AuxiliaryNodeStateMap,
+    ) -> ReadySetResult<ProcessingResult> {
+        debug_assert_eq!(from, *self.src);
+
+        if rs.is_empty() {
+            return Ok(ProcessingResult {
+                results: rs,
+                ..Default::default()
+            });
+        }
+
+        let us = self.our_index.unwrap();
+        let db = state.get(*us).ok_or_else(|| {
+            internal_err!("latest operators must have their own state materialized")
+        })?;
+
+        let mut out = Vec::new();
+        let mut misses = Vec::new();
+        let mut lookups = Vec::new();
+
+        // Process all the records for each group together, so that we look up each group at most
+        // once. The sort is stable, so records within each group stay in the order they arrived.
+        let mut keyed = rs
+            .into_iter()
+            .map(|r| -> ReadySetResult<_> { Ok((self.project_group(r.rec())?, r)) })
+            .collect::<ReadySetResult<Vec<_>>>()?;
+        keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for (group_key, group_rs) in &keyed.into_iter().group_by(|(key, _)| key.clone()) {
+            let current = match db.lookup(
+                &self.group_by[..],
+                &PointKey::from(group_key.iter().cloned()),
+            ) {
+                LookupResult::Some(rows) => {
+                    if replay.is_partial() {
+                        lookups.push(Lookup {
+                            on: *us,
+                            cols: self.group_by.clone(),
+                            key: group_key.clone().try_into().expect("Empty group"),
+                        });
+                    }
+                    rows.into_iter().next().map(|row| row.into_owned())
+                }
+                LookupResult::Missing => {
+                    misses.extend(group_rs.map(|(_, r)| {
+                        Miss::builder()
+                            .on(*us)
+                            .lookup_idx(self.group_by.clone())
+                            .lookup_key(self.group_by.clone())
+                            .replay(replay)
+                            .record(r.into_row())
+                            .build()
+                    }));
+                    continue;
+                }
+            };
+
+            let mut latest = current.clone();
+            let mut recompute = false;
+            for (_, r) in group_rs {
+                match r {
+                    Record::Positive(row) => {
+                        if latest
+                            .as_ref()
+                            .map_or(true, |l| self.cmp_rows(&row, l) == Ordering::Greater)
+                        {
+                            latest = Some(row);
+                        }
+                    }
+                    Record::Negative(row) => {
+                        if latest.as_ref() == Some(&row) {
+                            // We don't know what the next most recent row is, so we have to ask
+                            // our parent. Its state already reflects the rest of this batch, so
+                            // there's no need to look at any more records for this group.
+                            recompute = true;
+                            break;
+                        }
+                    }
+                }
+            }
+
+            if recompute {
+                latest = self.lookup_latest(&group_key, nodes, state)?;
+                if replay.is_partial() {
+                    lookups.push(Lookup {
+                        on: *self.src,
+                        cols: self.group_by.clone(),
+                        key: group_key.clone().try_into().expect("Empty group"),
+                    });
+                }
+            }
+
+            if latest != current {
+                out.extend(current.map(Record::Negative));
+                out.extend(latest.map(Record::Positive));
+            }
+        }
+
+        Ok(ProcessingResult {
+            results: out.into(),
+            lookups,
+            misses,
+        })
+    }
+
+    fn suggest_indexes(&self, this: NodeIndex) -> HashMap<NodeIndex, LookupIndex> {
+        HashMap::from([
+            (
+                this,
+                LookupIndex::Strict(internal::Index::hash_map(self.group_by.clone())),
+            ),
+            (
+                self.src.as_global(),
+                LookupIndex::Strict(internal::Index::hash_map(self.group_by.clone())),
+            ),
+        ])
+    }
+
+    fn column_source(&self, cols: &[usize]) -> ColumnSource {
+        ColumnSource::exact_copy(self.src.as_global(), cols.into())
+    }
+
+    fn description(&self, detailed: bool) -> String {
+        if !detailed {
+            return String::from("⧖");
+        }
+
+        format!("⧖ γ[{}] o[{}]", self.group_by.iter().join(", "), self.over)
+    }
+
+    fn is_selective(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops;
+
+    fn setup() -> (ops::test::MockGraph, IndexPair) {
+        let mut g = ops::test::MockGraph::new();
+        let s = g.add_base("source", &["x", "y", "ts"]);
+
+        g.set_op(
+            "latest",
+            &["x", "y", "ts"],
+            Latest::new(s.as_global(), vec![1], 2),
+            true,
+        );
+        (g, s)
+    }
+
+    #[test]
+    fn it_describes() {
+        let (g, _) = setup();
+        assert_eq!(g.node().description(true), "⧖ γ[1] o[2]");
+    }
+
+    #[test]
+    fn it_keeps_latest() {
+        let (mut g, _) = setup();
+        let ni = g.node().local_addr();
+
+        let r1: Vec<DfValue> = vec![1.into(), "a".into(), 1.into()];
+        let r2: Vec<DfValue> = vec![2.into(), "a".into(), 2.into()];
+        let r0: Vec<DfValue> = vec![3.into(), "a".into(), 0.into()];
+        let b1: Vec<DfValue> = vec![4.into(), "b".into(), 1.into()];
+
+        let emit = g.narrow_one_row(r1.clone(), true);
+        assert_eq!(emit, vec![r1.clone()].into());
+
+        let emit = g.narrow_one_row(r2.clone(), true);
+        assert_eq!(emit, vec![(r1, false), (r2, true)].into());
+
+        // Older rows don't replace the latest row
+        let emit = g.narrow_one_row(r0, true);
+        assert!(emit.is_empty());
+
+        // Groups are independent
+        let emit = g.narrow_one_row(b1.clone(), true);
+        assert_eq!(emit, vec![b1].into());
+
+        assert_eq!(g.states[ni].row_count(), 2);
+    }
+
+    #[test]
+    fn it_queries_parent_on_deletes() {
+        let (mut g, s) = setup();
+
+        let r1: Vec<DfValue> = vec![1.into(), "a".into(), 1.into()];
+        let r2: Vec<DfValue> = vec![2.into(), "a".into(), 2.into()];
+        let r3: Vec<DfValue> = vec![3.into(), "a".into(), 3.into()];
+
+        // fill the parent (but not with 3 since we'll delete it)
+        g.seed(s, r1.clone());
+        g.seed(s, r2.clone());
+
+        g.narrow_one_row(r1.clone(), true);
+        g.narrow_one_row(r2.clone(), true);
+        g.narrow_one_row(r3.clone(), true);
+
+        // Deleting a row which isn't the latest doesn't change anything
+        let emit = g.narrow_one_row((r1, false), true);
+        assert!(emit.is_empty());
+
+        // Deleting the latest row brings back the next most recent row
+        let emit = g.narrow_one_row((r3.clone(), false), true);
+        assert_eq!(emit, vec![(r3, false), (r2, true)].into());
+    }
+
+    #[test]
+    fn ties_broken_by_row() {
+        let (mut g, _) = setup();
+
+        // Whichever order tied rows arrive in, the same row is emitted
+        let a1: Vec<DfValue> = vec![1.into(), "a".into(), 5.into()];
+        let a2: Vec<DfValue> = vec![2.into(), "a".into(), 5.into()];
+        let emit = g.narrow_one_row(a2.clone(), true);
+        assert_eq!(emit, vec![a2].into());
+        let emit = g.narrow_one_row(a1, true);
+        assert!(emit.is_empty());
+
+        let b1: Vec<DfValue> = vec![1.into(), "b".into(), 5.into()];
+        let b2: Vec<DfValue> = vec![2.into(), "b".into(), 5.into()];
+        g.narrow_one_row(b1.clone(), true);
+        let emit = g.narrow_one_row(b2.clone(), true);
+        assert_eq!(emit, vec![(b1, false), (b2, true)].into());
+    }
+}
@@ -12,15 +12,19 @@ use serde::{Deserialize, Serialize};
 use crate::prelude::*;
 
 pub mod filter;
+pub mod fulltext;
 pub mod grouped;
 pub mod identity;
 pub mod join;
+pub mod latest;
 pub mod paginate;
+pub(crate) mod parallel;
 pub mod project;
 pub mod topk;
 pub mod union;
 pub(crate) mod utils;
 
+use crate::ops::grouped::approx_distinct::ApproxCountDistinct;
 use crate::ops::grouped::concat::GroupConcat;
 use crate::processing::{
     ColumnMiss, ColumnSource, IngredientLookupResult, LookupIndex, LookupMode,
@@ -42,6 +46,11 @@ impl Side {
     }
 }
 
+/// The minimum number of records in a packet for operators which evaluate expressions to evaluate
+/// them a column at a time with [`Expr::eval_batch`](dataflow_expression::Expr::eval_batch),
+/// rather than a record at a time. Below this, setting up the batch costs more than it saves.
+pub(crate) const MIN_BATCH_SIZE: usize = 16;
+
 #[derive(Clone, Serialize, Deserialize, From)]
 #[allow(clippy::large_enum_variant)]
 pub enum NodeOperator {
@@ -50,13 +59,16 @@ pub enum NodeOperator {
     Aggregation(grouped::GroupedOperator<grouped::aggregate::Aggregator>),
     Extremum(grouped::GroupedOperator<grouped::extremum::ExtremumOperator>),
     Concat(grouped::GroupedOperator<GroupConcat>),
+    ApproxCountDistinct(grouped::GroupedOperator<ApproxCountDistinct>),
     Join(join::Join),
+    Latest(latest::Latest),
     Paginate(paginate::Paginate),
     Project(project::Project),
     Union(union::Union),
     Identity(identity::Identity),
     Filter(filter::Filter),
     TopK(topk::TopK),
+    FullTextSearch(fulltext::FullTextSearch),
 }
 
 impl ToString for NodeOperator {
@@ -65,13 +77,16 @@ impl ToString for NodeOperator {
             NodeOperator::Aggregation(_) => "Aggregation",
             NodeOperator::Extremum(_) => "Extremum",
             NodeOperator::Concat(_) => "Concat",
+            NodeOperator::ApproxCountDistinct(_) => "ApproxCountDistinct",
             NodeOperator::Join(_) => "Join",
+            NodeOperator::Latest(_) => "Latest",
             NodeOperator::Paginate(_) => "Paginate",
             NodeOperator::Project(_) => "Project",
             NodeOperator::Union(_) => "Union",
             NodeOperator::Identity(_) => "Identity",
             NodeOperator::Filter(_) => "Filter",
             NodeOperator::TopK(_) => "TopK",
+            NodeOperator::FullTextSearch(_) => "FullTextSearch",
         }
         .to_string()
     }
@@ -83,13 +98,16 @@ macro_rules! impl_ingredient_fn_mut {
             NodeOperator::Aggregation(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Extremum(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Concat(ref mut i) => i.$fn($($arg),*),
+            NodeOperator::ApproxCountDistinct(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Join(ref mut i) => i.$fn($($arg),*),
+            NodeOperator::Latest(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Paginate(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Project(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Union(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Identity(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Filter(ref mut i) => i.$fn($($arg),*),
             NodeOperator::TopK(ref mut i) => i.$fn($($arg),*),
+            NodeOperator::FullTextSearch(ref mut i) => i.$fn($($arg),*),
         }
     }
 }
@@ -100,13 +118,16 @@ macro_rules! impl_ingredient_fn_ref {
             NodeOperator::Aggregation(ref i) => i.$fn($($arg),*),
             NodeOperator::Extremum(ref i) => i.$fn($($arg),*),
             NodeOperator::Concat(ref i) => i.$fn($($arg),*),
+            NodeOperator::ApproxCountDistinct(ref i) => i.$fn($($arg),*),
             NodeOperator::Join(ref i) => i.$fn($($arg),*),
+            NodeOperator::Latest(ref i) => i.$fn($($arg),*),
             NodeOperator::Paginate(ref i) => i.$fn($($arg),*),
             NodeOperator::Project(ref i) => i.$fn($($arg),*),
             NodeOperator::Union(ref i) => i.$fn($($arg),*),
             NodeOperator::Identity(ref i) => i.$fn($($arg),*),
             NodeOperator::Filter(ref i) => i.$fn($($arg),*),
             NodeOperator::TopK(ref i) => i.$fn($($arg),*),
+            NodeOperator::FullTextSearch(ref i) => i.$fn($($arg),*),
         }
     }
 }
@@ -229,6 +250,107 @@ impl Ingredient for NodeOperator {
     }
 }
 
+#[cfg(feature = "bench")]
+pub mod bench {
+    use criterion::{black_box, BatchSize, BenchmarkId, Criterion};
+    use dataflow_expression::utils::{column_with_type, make_column, make_literal};
+    use dataflow_expression::{BinaryOperator, Expr};
+    use readyset_data::DfType;
+
+    use super::*;
+
+    /// The numbers of records per packet to benchmark operators with, from a single write to the
+    /// batches seen under write-heavy load
+    const PACKET_SIZES: [usize; 4] = [1, 16, 128, 1024];
+
+    fn records(n: usize) -> Records {
+        (0..n)
+            .map(|i| {
+                vec![
+                    DfValue::from(i as i64),
+                    DfValue::from((i % 7) as i64),
+                    DfValue::from(format!("row {i}")),
+                ]
+            })
+            .collect()
+    }
+
+    fn bench_packets<I>(c: &mut Criterion, name: &str, mut op: I)
+    where
+        I: Ingredient,
+    {
+        let src = LocalNodeIndex::make(0);
+        let mut group = c.benchmark_group(name);
+        for size in PACKET_SIZES {
+            group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+                b.iter_batched(
+                    || records(size),
+                    |rs| {
+                        black_box(
+                            op.on_input(
+                                src,
+                                rs,
+                                &ReplayContext::None,
+                                &Default::default(),
+                                &Default::default(),
+                                &mut Default::default(),
+                            )
+                            .unwrap(),
+                        )
+                    },
+                    BatchSize::SmallInput,
+                )
+            });
+        }
+        group.finish();
+    }
+
+    fn source() -> (NodeIndex, HashMap<NodeIndex, IndexPair>) {
+        let src = NodeIndex::new(0);
+        let mut pair = IndexPair::from(src);
+        pair.set_local(LocalNodeIndex::make(0));
+        (src, HashMap::from([(src, pair)]))
+    }
+
+    /// Filter packets of records on a comparison between a column and a literal
+    pub fn filter_batch(c: &mut Criterion) {
+        let (src, remap) = source();
+        let mut filter = filter::Filter::new(
+            src,
+            Expr::Op {
+                left: Box::new(column_with_type(1, DfType::BigInt)),
+                op: BinaryOperator::Equal,
+                right: Box::new(make_literal(3.into())),
+                ty: DfType::Bool,
+            },
+        );
+        filter.on_commit(NodeIndex::new(1), &remap);
+        bench_packets(c, "filter", filter);
+    }
+
+    /// Project packets of records to a permutation of their columns along with an arithmetic
+    /// expression and a literal
+    pub fn project_batch(c: &mut Criterion) {
+        let (src, remap) = source();
+        let mut project = project::Project::new(
+            src,
+            vec![
+                make_column(2),
+                make_column(0),
+                Expr::Op {
+                    left: Box::new(column_with_type(0, DfType::BigInt)),
+                    op: BinaryOperator::Add,
+                    right: Box::new(column_with_type(1, DfType::BigInt)),
+                    ty: DfType::BigInt,
+                },
+                make_literal("constant".into()),
+            ],
+        );
+        project.on_commit(NodeIndex::new(1), &remap);
+        bench_packets(c, "project", project);
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::panic, clippy::unreachable)]
 pub mod test {
@@ -239,6 +361,7 @@ pub mod test {
     use petgraph::graph::NodeIndex;
 
     use crate::node;
+    use crate::ops::grouped::extremum::DEFAULT_MAX_TRACKED_EXTREMUM_VALUES;
     use crate::prelude::*;
     use crate::processing::LookupIndex;
     use crate::utils::make_columns;
@@ -286,7 +409,7 @@ pub mod test {
             use crate::node::special::Base;
             let i = Base::new().with_default_values(defaults);
             let node = Node::new(name, make_columns(fields), i);
-            if let Some(s) = node.initial_auxiliary_state() {
+            if let Some(s) = node.initial_auxiliary_state(DEFAULT_MAX_TRACKED_EXTREMUM_VALUES) {
                 self.auxiliary_node_states.insert(node.local_addr(), s);
             }
             let global = self.graph.add_node(node);
@@ -322,7 +445,7 @@ pub mod test {
 
             let i: NodeOperator = i.into();
             let node = Node::new(name, make_columns(fields), i);
-            let aux_state = node.initial_auxiliary_state();
+            let aux_state = node.initial_auxiliary_state(DEFAULT_MAX_TRACKED_EXTREMUM_VALUES);
             let global = self.graph.add_node(node);
             let local = LocalNodeIndex::make(self.remap.len() as u32);
             if let Some(aux_state) = aux_state {
@@ -0,0 +1,109 @@
+//! Processing of large packets through operators which handle each record independently of all
+//! the others (filters and projections) on a small pool of worker threads per domain, rather than
+//! only on the thread running the domain.
+
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+
+use crate::prelude::*;
+
+/// The minimum number of records in a packet for it to be processed on [`OperatorWorkers`].
+/// Below this, handing the packet off to the workers costs more than it saves.
+pub(crate) const MIN_PARALLEL_RECORDS: usize = 1024;
+
+/// A pool of worker threads shared by the operators in a domain, configured via
+/// [`Config::operator_threads`](crate::domain::Config::operator_threads)
+#[derive(Clone)]
+pub struct OperatorWorkers(Arc<ThreadPool>);
+
+impl Debug for OperatorWorkers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OperatorWorkers")
+            .field("threads", &self.0.current_num_threads())
+            .finish()
+    }
+}
+
+impl OperatorWorkers {
+    /// Start a pool of `threads` workers for the operators in the domain with the given address
+    pub(crate) fn new(
+        threads: usize,
+        address: ReplicaAddress,
+    ) -> Result<Self, ThreadPoolBuildError> {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(move |i| format!("{address}-op-{i}"))
+            .build()?;
+        Ok(Self(Arc::new(pool)))
+    }
+
+    /// Split `records` into contiguous chunks, one per worker, apply `f` to each chunk on the
+    /// workers, and concatenate the results in the order of the chunks they came from.
+    ///
+    /// Since each chunk is a contiguous range of the packet, and `f` is expected to preserve the
+    /// order of the records within it, records with the same key are emitted in the same order in
+    /// which they were received.
+    pub(crate) fn process<F>(&self, records: Records, f: F) -> Records
+    where
+        F: Fn(Records) -> Records + Sync,
+    {
+        let chunk_size = records.len().div_ceil(self.0.current_num_threads());
+        let mut records = records.into_iter();
+        let chunks = std::iter::from_fn(|| {
+            let chunk = records.by_ref().take(chunk_size).collect::<Records>();
+            (!chunk.is_empty()).then_some(chunk)
+        })
+        .collect::<Vec<_>>();
+
+        let results = self
+            .0
+            .install(|| chunks.into_par_iter().map(&f).collect::<Vec<_>>());
+        let mut out = Vec::with_capacity(results.iter().map(|rs| rs.len()).sum());
+        for rs in results {
+            out.extend(rs);
+        }
+        out.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(n: i32) -> Records {
+        (0..n).map(|i| vec![DfValue::from(i)]).collect()
+    }
+
+    fn workers(threads: usize) -> OperatorWorkers {
+        let address = ReplicaAddress {
+            domain_index: 0.into(),
+            shard: 0,
+            replica: 0,
+        };
+        OperatorWorkers::new(threads, address).unwrap()
+    }
+
+    #[test]
+    fn process_preserves_order() {
+        let workers = workers(4);
+        let results = workers.process(records(1001), |rs| {
+            rs.into_iter()
+                .filter(|r| r[0] != DfValue::from(500))
+                .collect()
+        });
+        let expected = (0..1001)
+            .filter(|i| *i != 500)
+            .map(|i| vec![DfValue::from(i)])
+            .collect::<Records>();
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn process_empty() {
+        let workers = workers(2);
+        assert!(workers.process(Records::default(), |rs| rs).is_empty());
+    }
+}
@@ -8,6 +8,8 @@ use readyset_errors::ReadySetResult;
 use serde::{Deserialize, Serialize};
 use tracing::error;
 
+use crate::ops::parallel::{OperatorWorkers, MIN_PARALLEL_RECORDS};
+use crate::ops::MIN_BATCH_SIZE;
 use crate::prelude::*;
 use crate::processing::{ColumnSource, IngredientLookupResult, LookupIndex, LookupMode};
 
@@ -19,6 +21,9 @@ pub struct Project {
     emit: Vec<Expr>,
     src: IndexPair,
     cols: usize,
+    /// Workers to project large packets on, if the domain running this node has any
+    #[serde(skip)]
+    workers: Option<OperatorWorkers>,
 }
 
 impl Project {
@@ -29,8 +34,59 @@ impl Project {
             src: src.into(),
             cols: 0,
             us: None,
+            workers: None,
         }
     }
+
+    /// Project large packets on the given workers
+    pub(crate) fn set_workers(&mut self, workers: OperatorWorkers) {
+        self.workers = Some(workers);
+    }
+
+    fn project(&self, mut rs: Records) -> Records {
+        if rs.len() >= MIN_BATCH_SIZE {
+            let records = rs.iter().map(|r| r.rec()).collect::<Vec<_>>();
+            let columns = self
+                .emit
+                .iter()
+                .map(|expr| expr.eval_batch(&records))
+                .collect::<ReadySetResult<Vec<_>>>();
+            // If evaluating any of the expressions fails for any record, fall back to evaluating
+            // them a record at a time below, so that only the values they fail for are NULL
+            if let Ok(columns) = columns {
+                let mut columns = columns.into_iter().map(Vec::into_iter).collect::<Vec<_>>();
+                for r in &mut *rs {
+                    **r = columns
+                        .iter_mut()
+                        .map(|column| column.next().unwrap_or(DfValue::None))
+                        .collect();
+                }
+                return rs;
+            }
+        }
+
+        let mut log_error_once_flag = false;
+        for r in &mut *rs {
+            **r = self
+                .emit
+                .iter()
+                .map(|expr| match expr.eval(r) {
+                    Ok(val) => val,
+                    // TODO (REA-2964): Handle expression eval errors
+                    Err(error) => {
+                        // only log this error once per on_input call
+                        if !log_error_once_flag {
+                            error!(%error, "Error evaluating project expression");
+                            log_error_once_flag = true;
+                        }
+                        DfValue::None
+                    }
+                })
+                .collect();
+        }
+
+        rs
+    }
 }
 
 impl Ingredient for Project {
@@ -95,35 +151,22 @@ impl Ingredient for Project {
     fn on_input(
         &mut self,
         from: LocalNodeIndex,
-        mut rs: Records,
+        rs: Records,
         _: &ReplayContext,
         _: &DomainNodes,
         _: &StateMap,
         _: &mut AuxiliaryNodeStateMap,
     ) -> ReadySetResult<ProcessingResult> {
         debug_assert_eq!(from, *self.src);
-        let mut log_error_once_flag = false;
-        for r in &mut *rs {
-            **r = self
-                .emit
-                .iter()
-                .map(|expr| match expr.eval(r) {
-                    Ok(val) => val,
-                    // TODO (REA-2964): Handle expression eval errors
-                    Err(error) => {
-                        // only log this error once per on_input call
-                        if !log_error_once_flag {
-                            error!(%error, "Error evaluating project expression");
-                            log_error_once_flag = true;
-                        }
-                        DfValue::None
-                    }
-                })
-                .collect();
-        }
+        let results = match &self.workers {
+            Some(workers) if rs.len() >= MIN_PARALLEL_RECORDS => {
+                workers.process(rs, |rs| self.project(rs))
+            }
+            _ => self.project(rs),
+        };
 
         Ok(ProcessingResult {
-            results: rs,
+            results,
             ..Default::default()
         })
     }
@@ -319,6 +362,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_forwards_arithmetic_batch() {
+        let mut p = setup_column_arithmetic(BinaryOperator::Add);
+        let rows = (0..(MIN_BATCH_SIZE as i32 * 2))
+            .map(|i| vec![i.into(), (i * 10).into()])
+            .collect::<Vec<Vec<DfValue>>>();
+        let expected = (0..(MIN_BATCH_SIZE as i32 * 2))
+            .map(|i| vec![i.into(), (i * 10).into(), (i * 11).into()])
+            .collect::<Vec<Vec<DfValue>>>();
+        assert_eq!(p.narrow_one(rows, false), expected.into());
+    }
+
     #[test]
     fn it_forwards_multiplication_arithmetic() {
         let mut p = setup_column_arithmetic(BinaryOperator::Multiply);
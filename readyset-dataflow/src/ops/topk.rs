@@ -2,8 +2,8 @@ use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap};
 use std::convert::TryInto;
-use std::mem;
 use std::num::NonZeroUsize;
+use std::{fmt, mem};
 
 use dataflow_state::PointKey;
 use itertools::Itertools;
@@ -66,6 +66,31 @@ impl<'topk, 'state> PartialEq<[DfValue]> for CurrentRecord<'topk, 'state> {
 
 impl<'topk, 'state> Eq for CurrentRecord<'topk, 'state> {}
 
+/// How a [`TopK`] treats rows which tie with each other in its order
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TopKMode {
+    /// Emit exactly `k` rows per group (or every row, if the group has fewer than `k`), breaking
+    /// ties arbitrarily. Matches `ROW_NUMBER() <= k`.
+    #[default]
+    RowNumber,
+    /// Emit the first `k` rows of each group, plus every row which ties with the last of them, as
+    /// with `FETCH FIRST k ROWS WITH TIES`. Matches `RANK() <= k`.
+    WithTies,
+    /// Emit every row whose values for the order columns are among the first `k` distinct values
+    /// of those columns in the group. Matches `DENSE_RANK() <= k`.
+    DenseRank,
+}
+
+impl fmt::Display for TopKMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TopKMode::RowNumber => write!(f, "row_number"),
+            TopKMode::WithTies => write!(f, "with_ties"),
+            TopKMode::DenseRank => write!(f, "dense_rank"),
+        }
+    }
+}
+
 /// TopK provides an operator that will produce the top k elements for each group.
 ///
 /// Positives are generally fast to process, while negative records can trigger expensive backwards
@@ -83,6 +108,10 @@ pub struct TopK {
 
     order: Order,
     k: usize,
+
+    /// How rows which tie in `order` are treated
+    #[serde(default)]
+    mode: TopKMode,
 }
 
 impl TopK {
@@ -93,12 +122,15 @@ impl TopK {
     /// * `src` - this operator's ancestor
     /// * `order` - The list of columns to compute top k over
     /// * `group_by` - the columns that this operator is keyed on
-    /// * `k` - the maximum number of results per group.
+    /// * `k` - the maximum number of results (or, for [`TopKMode::DenseRank`], distinct values of
+    ///   the order columns) per group, not counting ties for [`TopKMode::WithTies`].
+    /// * `mode` - how rows which tie in `order` are treated
     pub fn new(
         src: NodeIndex,
         order: Vec<(usize, OrderType)>,
         group_by: Vec<usize>,
         k: usize,
+        mode: TopKMode,
     ) -> Self {
         TopK {
             src: src.into(),
@@ -106,9 +138,25 @@ impl TopK {
             group_by,
             order: order.into(),
             k,
+            mode,
         }
     }
 
+    /// Returns the columns in the parent node that this operator groups by
+    pub fn group_by(&self) -> &[usize] {
+        &self.group_by
+    }
+
+    /// Returns the maximum number of results this operator emits per group
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns how this operator treats rows which tie in its order
+    pub fn mode(&self) -> TopKMode {
+        self.mode
+    }
+
     /// Project the columns we are grouping by out of the given record
     fn project_group<'rec, R>(&self, rec: &'rec R) -> ReadySetResult<Vec<&'rec DfValue>>
     where
@@ -224,6 +272,233 @@ impl TopK {
         }
         Ok(lookup)
     }
+
+    /// Returns true if `rows`, which must be sorted from best to worst, are enough to determine the
+    /// contents of the group, meaning any row worse than all of them can't be in the top k
+    fn is_full(&self, rows: &[Cow<[DfValue]>]) -> bool {
+        match self.mode {
+            TopKMode::DenseRank => {
+                rows.iter()
+                    .dedup_by(|a, b| self.order.cmp(a, b) == Ordering::Equal)
+                    .count()
+                    >= self.k
+            }
+            TopKMode::RowNumber | TopKMode::WithTies => rows.len() >= self.k,
+        }
+    }
+
+    /// Returns the number of rows at the start of `rows`, which must be sorted from best to worst,
+    /// which are in the top k of the group according to `self.mode`. Only used for the modes which
+    /// include ties.
+    fn num_with_ties(&self, rows: &[Cow<[DfValue]>]) -> usize {
+        let Some(mut kth) = self.k.checked_sub(1) else {
+            return 0;
+        };
+        if self.mode == TopKMode::DenseRank {
+            // Find the first row with the kth distinct value
+            let mut distinct = 0;
+            let first_of_value = rows.iter().enumerate().position(|(i, row)| {
+                #[allow(clippy::indexing_slicing)] // i > 0
+                if i == 0 || self.order.cmp(row, &rows[i - 1]) != Ordering::Equal {
+                    distinct += 1;
+                }
+                distinct == self.k
+            });
+            match first_of_value {
+                Some(i) => kth = i,
+                None => return rows.len(),
+            }
+        }
+        let Some(kth_row) = rows.get(kth) else {
+            return rows.len();
+        };
+        #[allow(clippy::indexing_slicing)] // kth < rows.len()
+        let num_tied = rows[kth..]
+            .iter()
+            .take_while(|row| self.order.cmp(row, kth_row) == Ordering::Equal)
+            .count();
+        kth + num_tied
+    }
+
+    /// Process a batch of records, sorted by group, for the modes which include ties
+    fn on_input_with_ties(
+        &self,
+        rs: &[Record],
+        replay: &ReplayContext,
+        nodes: &DomainNodes,
+        state: &StateMap,
+    ) -> ReadySetResult<ProcessingResult> {
+        let us = self.our_index.unwrap();
+        let db = state.get(*us).ok_or_else(|| {
+            internal_err!("topk operators must have their own state materialized")
+        })?;
+
+        let mut out = Vec::new();
+        let mut misses = Vec::new();
+        let mut lookups = Vec::new();
+
+        let keyed = rs
+            .iter()
+            .map(|r| -> ReadySetResult<_> {
+                let key = self
+                    .project_group(r.rec())?
+                    .into_iter()
+                    .cloned()
+                    .collect::<Vec<_>>();
+                Ok((key, r))
+            })
+            .collect::<ReadySetResult<Vec<_>>>()?;
+        for (group_key, group_rs) in &keyed.into_iter().group_by(|(key, _)| key.clone()) {
+            let group_rs = group_rs.map(|(_, r)| r).collect::<Vec<_>>();
+            let original = match db.lookup(
+                &self.group_by[..],
+                &PointKey::from(group_key.iter().cloned()),
+            ) {
+                LookupResult::Some(local_records) => {
+                    if replay.is_partial() {
+                        lookups.push(Lookup {
+                            on: *us,
+                            cols: self.group_by.clone(),
+                            key: group_key.clone().try_into().expect("Empty group"),
+                        });
+                    }
+                    local_records.into_iter().collect::<Vec<_>>()
+                }
+                LookupResult::Missing => {
+                    misses.extend(group_rs.into_iter().map(|r| {
+                        Miss::builder()
+                            .on(*us)
+                            .lookup_idx(self.group_by.clone())
+                            .lookup_key(self.group_by.clone())
+                            .replay(replay)
+                            .record(r.row().clone())
+                            .build()
+                    }));
+                    continue;
+                }
+            };
+
+            if let Some(lookup) = self
+                .process_group_with_ties(&mut out, original, &group_rs, &group_key, state, nodes)?
+            {
+                if replay.is_partial() {
+                    lookups.push(lookup);
+                }
+            }
+        }
+
+        Ok(ProcessingResult {
+            results: out.into(),
+            lookups,
+            misses,
+        })
+    }
+
+    /// Process all the records in `rs` for a single group, for the modes which include ties.
+    ///
+    /// Unlike in [`TopKMode::RowNumber`] mode, which rows are in the top k can depend on every row
+    /// which ties with the worst of them, so rather than tracking which rows are new we recompute
+    /// the contents of the group and emit the difference from `original`, the rows we previously
+    /// emitted for the group.
+    ///
+    /// The rows we've emitted for a group always include *every* row in our parent which is at
+    /// least as good as the worst of them, so as long as that's still enough rows to fill the
+    /// group after processing `rs`, we don't need to look at our parent. Otherwise (for example,
+    /// if a row tied for the last place in the group is deleted) we refill the group from our
+    /// parent.
+    fn process_group_with_ties<'state>(
+        &self,
+        out: &mut Vec<Record>,
+        original: Vec<Cow<'state, [DfValue]>>,
+        rs: &[&Record],
+        group_key: &[DfValue],
+        state: &'state StateMap,
+        nodes: &DomainNodes,
+    ) -> ReadySetResult<Option<Lookup>> {
+        let best_first = |a: &Cow<[DfValue]>, b: &Cow<[DfValue]>| self.order.cmp(b, a);
+
+        let mut current = original.clone();
+        current.sort_by(best_first);
+        let was_full = self.is_full(&current);
+        let boundary = current.last().map(|row| row.to_vec());
+
+        for r in rs {
+            match r {
+                Record::Positive(row) => current.push(Cow::Owned(row.clone())),
+                Record::Negative(row) => {
+                    if let Some(pos) = current.iter().position(|c| **c == **row) {
+                        current.remove(pos);
+                    }
+                }
+            }
+        }
+        current.sort_by(best_first);
+
+        let mut lookup = None;
+        if let Some(boundary) = boundary.filter(|_| was_full) {
+            // Rows worse than the previous worst row in the group might not be the best such rows
+            // in our parent, so only the rows at least as good as it count towards filling the
+            // group
+            let known = current
+                .iter()
+                .take_while(|row| self.order.cmp(row, &boundary) != Ordering::Less)
+                .count();
+            #[allow(clippy::indexing_slicing)] // known <= current.len()
+            let still_full = self.is_full(&current[..known]);
+            if !still_full {
+                match self.lookup(
+                    *self.src,
+                    &self.group_by,
+                    &PointKey::from(group_key.iter().cloned()),
+                    nodes,
+                    state,
+                    LookupMode::Strict,
+                )? {
+                    IngredientLookupResult::Miss => {
+                        internal!(
+                            "We shouldn't have been able to get this record if the parent would miss"
+                        )
+                    }
+                    IngredientLookupResult::Records(rs) => {
+                        current = rs.collect::<Result<Vec<_>, _>>()?;
+                        current.sort_by(best_first);
+                        lookup = Some(Lookup {
+                            on: *self.src,
+                            cols: self.group_by.clone(),
+                            key: group_key.to_vec().try_into().expect("Empty group"),
+                        });
+                    }
+                }
+            }
+        }
+
+        current.truncate(self.num_with_ties(&current));
+
+        // Emit the difference between the rows we previously emitted and the new contents of the
+        // group
+        let mut removed = original;
+        let mut added = vec![];
+        for row in current {
+            match removed.iter().position(|r| *r == row) {
+                Some(pos) => {
+                    removed.swap_remove(pos);
+                }
+                None => added.push(row),
+            }
+        }
+        out.extend(
+            removed
+                .into_iter()
+                .map(|row| Record::Negative(row.into_owned())),
+        );
+        out.extend(
+            added
+                .into_iter()
+                .map(|row| Record::Positive(row.into_owned())),
+        );
+
+        Ok(lookup)
+    }
 }
 
 impl Ingredient for TopK {
@@ -270,6 +545,10 @@ impl Ingredient for TopK {
                 .cmp(&self.project_group(&***b).unwrap_or_default())
         });
 
+        if self.mode != TopKMode::RowNumber {
+            return self.on_input_with_ties(&rs, replay, nodes, state);
+        }
+
         let us = self.our_index.unwrap();
         let db = state.get(*us).ok_or_else(|| {
             internal_err!("topk operators must have their own state materialized")
@@ -452,9 +731,15 @@ impl Ingredient for TopK {
             return String::from("TopK");
         }
 
+        let mode = if self.mode == TopKMode::RowNumber {
+            String::new()
+        } else {
+            format!(" ({})", self.mode)
+        };
         format!(
-            "TopK k={} γ[{}] o[{}]",
+            "TopK k={}{} γ[{}] o[{}]",
             self.k,
+            mode,
             self.group_by.iter().join(", "),
             self.order
         )
@@ -483,7 +768,7 @@ mod tests {
         g.set_op(
             "topk",
             &["x", "y", "z"],
-            TopK::new(s.as_global(), cmp_rows, vec![1], 3),
+            TopK::new(s.as_global(), cmp_rows, vec![1], 3, TopKMode::RowNumber),
             true,
         );
         (g, s)
@@ -821,4 +1106,127 @@ mod tests {
         let emit = g.narrow_one(vec![(ra3.clone(), false), (ra0, true)], true);
         assert_eq!(emit, vec![(ra3, false), (ra1, true)].into());
     }
+
+    fn setup_with_mode(mode: TopKMode) -> (ops::test::MockGraph, IndexPair) {
+        let mut g = ops::test::MockGraph::new();
+        let s = g.add_base("source", &["x", "y", "z"]);
+
+        g.set_op(
+            "topk",
+            &["x", "y", "z"],
+            TopK::new(
+                s.as_global(),
+                vec![(2, OrderType::OrderAscending)],
+                vec![1],
+                2,
+                mode,
+            ),
+            true,
+        );
+        (g, s)
+    }
+
+    fn sorted(rs: Records) -> Vec<Record> {
+        let mut rs: Vec<Record> = rs.into();
+        rs.sort();
+        rs
+    }
+
+    #[test]
+    fn with_ties_includes_ties() {
+        let (mut g, _) = setup_with_mode(TopKMode::WithTies);
+        let ni = g.node().local_addr();
+
+        let r10: Vec<DfValue> = vec![1.into(), "z".into(), 10.into()];
+        let r9a: Vec<DfValue> = vec![2.into(), "z".into(), 9.into()];
+        let r9b: Vec<DfValue> = vec![3.into(), "z".into(), 9.into()];
+        let r8: Vec<DfValue> = vec![4.into(), "z".into(), 8.into()];
+        let r11: Vec<DfValue> = vec![5.into(), "z".into(), 11.into()];
+
+        g.narrow_one_row(r10, true);
+        g.narrow_one_row(r9a.clone(), true);
+        let emit = g.narrow_one_row(r9b.clone(), true);
+        assert_eq!(emit, vec![r9b.clone()].into());
+        assert_eq!(g.states[ni].row_count(), 3);
+
+        let emit = g.narrow_one_row(r8, true);
+        assert!(emit.is_empty());
+
+        // A new best row pushes out all the rows tied for second place
+        let emit = g.narrow_one_row(r11.clone(), true);
+        assert_eq!(
+            sorted(emit),
+            sorted(vec![(r11, true), (r9a, false), (r9b, false)].into())
+        );
+        assert_eq!(g.states[ni].row_count(), 2);
+    }
+
+    #[test]
+    fn with_ties_refills_after_deleting_tied_row() {
+        let (mut g, s) = setup_with_mode(TopKMode::WithTies);
+
+        let r10: Vec<DfValue> = vec![1.into(), "z".into(), 10.into()];
+        let r9a: Vec<DfValue> = vec![2.into(), "z".into(), 9.into()];
+        let r9b: Vec<DfValue> = vec![3.into(), "z".into(), 9.into()];
+        let r8: Vec<DfValue> = vec![4.into(), "z".into(), 8.into()];
+
+        for r in [&r10, &r9a, &r9b, &r8] {
+            g.seed(s, r.clone());
+            g.narrow_one_row(r.clone(), true);
+        }
+
+        // Deleting one of the tied rows still leaves enough rows to fill the group, so we don't
+        // need to look at the parent
+        let emit = g.narrow_one_row((r9a.clone(), false), true);
+        assert_eq!(emit, vec![(r9a, false)].into());
+
+        // But deleting the best row leaves only one row we know about, so the group should be
+        // refilled from the parent
+        g.unseed(s);
+        g.seed(s, r9b);
+        g.seed(s, r8.clone());
+        let emit = g.narrow_one_row((r10.clone(), false), true);
+        assert_eq!(sorted(emit), sorted(vec![(r10, false), (r8, true)].into()));
+    }
+
+    #[test]
+    fn dense_rank_keeps_k_distinct_values() {
+        let (mut g, _) = setup_with_mode(TopKMode::DenseRank);
+        let ni = g.node().local_addr();
+
+        let r10a: Vec<DfValue> = vec![1.into(), "z".into(), 10.into()];
+        let r10b: Vec<DfValue> = vec![2.into(), "z".into(), 10.into()];
+        let r9a: Vec<DfValue> = vec![3.into(), "z".into(), 9.into()];
+        let r9b: Vec<DfValue> = vec![4.into(), "z".into(), 9.into()];
+        let r8: Vec<DfValue> = vec![5.into(), "z".into(), 8.into()];
+        let r11: Vec<DfValue> = vec![6.into(), "z".into(), 11.into()];
+
+        g.narrow_one(
+            vec![
+                (r10a, true),
+                (r10b, true),
+                (r9a.clone(), true),
+                (r9b.clone(), true),
+                (r8, true),
+            ],
+            true,
+        );
+        assert_eq!(g.states[ni].row_count(), 4);
+
+        let emit = g.narrow_one_row(r11.clone(), true);
+        assert_eq!(
+            sorted(emit),
+            sorted(vec![(r11, true), (r9a, false), (r9b, false)].into())
+        );
+        assert_eq!(g.states[ni].row_count(), 3);
+    }
+
+    #[test]
+    fn describes_mode() {
+        let (g, _) = setup_with_mode(TopKMode::DenseRank);
+        assert_eq!(
+            g.node().description(true),
+            "TopK k=2 (dense_rank) γ[1] o[<2]"
+        );
+    }
 }
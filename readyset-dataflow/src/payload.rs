@@ -1,10 +1,15 @@
 use std::collections::HashSet;
 use std::fmt::{self, Display};
+use std::time::Duration;
 
 use dataflow_state::MaterializedNodeState;
 use itertools::Itertools;
 use nom_sql::Relation;
-use readyset_client::{self, KeyComparison, PacketData, PacketTrace};
+use readyset_client::debug::info::ReplayDomainTiming;
+use readyset_client::runtime_config::RuntimeConfigUpdate;
+use readyset_client::{
+    self, KeyComparison, PacketData, PacketTrace, ViewRateLimit, ViewResultLimit, ViewStaleReads,
+};
 use readyset_data::DfType;
 use serde::{Deserialize, Serialize};
 use strum_macros::{EnumCount, EnumDiscriminants, EnumIter, IntoStaticStr};
@@ -178,6 +183,12 @@ pub enum ReplayPieceContext {
         requesting_replica: usize,
         /// Is this replay coming from a single shard in the source domain?
         unishard: bool,
+        /// How long each domain the replay has passed through so far spent handling it.
+        ///
+        /// Only recorded if a [slow replay threshold][] is configured.
+        ///
+        /// [slow replay threshold]: crate::DomainConfig::slow_replay_threshold
+        timings: Vec<ReplayDomainTiming>,
     },
     /// Context for a full replay
     Full {
@@ -301,6 +312,15 @@ pub enum DomainRequest {
         replication: SenderReplication,
     },
 
+    /// Tell an egress node that the given ingress node in another domain has been removed, and
+    /// that it should stop sending to it
+    RemoveEgressTx {
+        /// The local index of the egress node we're informing about changes
+        egress_node: LocalNodeIndex,
+        /// The global index of the ingress node that was removed
+        ingress_node: NodeIndex,
+    },
+
     /// Tell an egress node about a new tag that will pass through it, and the ingress node in the
     /// next domain that will receive replays along that tag
     AddEgressTag {
@@ -385,6 +405,12 @@ pub enum DomainRequest {
         node: LocalNodeIndex,
     },
 
+    /// Query the number of rows a domain has received so far as part of a full replay to the
+    /// given node.
+    QueryReplayProgress {
+        node: LocalNodeIndex,
+    },
+
     /// Sent to instruct a domain that a particular node should be considered ready to process
     /// updates.
     Ready {
@@ -407,6 +433,15 @@ pub enum DomainRequest {
     /// bytes
     RequestNodeSizes,
 
+    /// Profile the distribution of the values in each column of up to `max_rows` rows of the state
+    /// of the given node, to estimate the cardinality of its columns.
+    ///
+    /// Returns a [`StateProfile`](crate::StateProfile)
+    ProfileState {
+        node: LocalNodeIndex,
+        max_rows: usize,
+    },
+
     /// Process the packet, as per usual
     Packet(Packet),
 
@@ -431,6 +466,77 @@ pub enum DomainRequest {
 
     /// Requests an eviction from state within this Domain.
     Evict(EvictRequest),
+
+    /// Request diagnostic information about a single key in the given reader node.
+    ///
+    /// Returns a [`ReaderKeyTrace`](readyset_client::debug::info::ReaderKeyTrace)
+    TraceReaderKey {
+        node: LocalNodeIndex,
+        key: KeyComparison,
+    },
+
+    /// Request the usage counters for the given reader node.
+    ///
+    /// Returns a [`ReaderMetrics`](readyset_client::debug::info::ReaderMetrics)
+    ReaderMetrics {
+        node: LocalNodeIndex,
+    },
+
+    /// Request the keys read most often from the given reader node.
+    ///
+    /// Returns a [`ReaderHotKeys`](readyset_client::debug::info::ReaderHotKeys)
+    ReaderHotKeys {
+        node: LocalNodeIndex,
+    },
+
+    /// Replace the limits on the rate of reads against the given reader node.
+    SetReaderRateLimit {
+        node: LocalNodeIndex,
+        limit: ViewRateLimit,
+    },
+
+    /// Set how long the given reader node retains the deltas applied to it for as-of reads, or
+    /// disable as-of reads against it if `retention` is `None`.
+    SetReaderHistoryRetention {
+        node: LocalNodeIndex,
+        retention: Option<Duration>,
+    },
+
+    /// Configure whether reads against the given reader node may be answered with last-known,
+    /// possibly-stale values.
+    SetReaderStaleReads {
+        node: LocalNodeIndex,
+        stale_reads: ViewStaleReads,
+    },
+
+    /// Replace the limits on the size of results of reads against the given reader node.
+    SetReaderResultLimit {
+        node: LocalNodeIndex,
+        result_limit: ViewResultLimit,
+    },
+
+    /// Set how long reads against the given reader node wait for misses to be filled by upqueries,
+    /// or revert to the server's default if `timeout` is `None`.
+    SetReaderUpqueryTimeout {
+        node: LocalNodeIndex,
+        timeout: Option<Duration>,
+    },
+
+    /// Replace the sets of columns of the given (fully materialized) reader node which secondary
+    /// indices are maintained on, building the new indices from the reader's current contents.
+    SetReaderSecondaryIndices {
+        node: LocalNodeIndex,
+        indices: Vec<Vec<usize>>,
+    },
+
+    /// Request the most recent replays to readers in this domain which took longer than the
+    /// configured slow replay threshold.
+    ///
+    /// Returns a `Vec<`[`ReaderSlowReplay`](readyset_client::debug::info::ReaderSlowReplay)`>`
+    SlowReplays,
+
+    /// Apply the domain-level parts of an update to the runtime configuration of the deployment
+    UpdateRuntimeConfig(RuntimeConfigUpdate),
 }
 
 /// The primary unit of communication between nodes in the dataflow graph.
@@ -499,6 +605,14 @@ pub enum Packet {
         cache_name: Relation,
     },
 
+    /// Tell a domain that no reads are waiting for the replays of a set of keys into a Reader any
+    /// more (eg because the reads timed out), so that the next request for any of those keys
+    /// triggers a new replay rather than being deduplicated into one which may be stuck.
+    ReleaseReaderReplays {
+        node: LocalNodeIndex,
+        keys: Vec<KeyComparison>,
+    },
+
     /// A packet used solely to drive the event loop forward.
     Spin,
 
@@ -620,6 +734,14 @@ impl Packet {
     }
 
     pub(crate) fn clone_data(&self) -> Self {
+        self.clone_data_with(Records::clone)
+    }
+
+    /// Like [`Self::clone_data`], but copying the packet's data with `clone_records`
+    pub(crate) fn clone_data_with<F>(&self, clone_records: F) -> Self
+    where
+        F: FnOnce(&Records) -> Records,
+    {
         match *self {
             Packet::Message {
                 link,
@@ -627,7 +749,7 @@ impl Packet {
                 ref trace,
             } => Packet::Message {
                 link,
-                data: data.clone(),
+                data: clone_records(data),
                 trace: trace.clone(),
             },
             Packet::ReplayPiece {
@@ -639,7 +761,7 @@ impl Packet {
             } => Packet::ReplayPiece {
                 link,
                 tag,
-                data: data.clone(),
+                data: clone_records(data),
                 context: context.clone(),
                 cache_name: cache_name.clone(),
             },
@@ -670,6 +792,7 @@ impl ToString for Packet {
             Packet::Input { .. } => "Input",
             Packet::Message { .. } => "Message",
             Packet::RequestReaderReplay { .. } => "RequestReaderReplay",
+            Packet::ReleaseReaderReplays { .. } => "ReleaseReaderReplays",
             Packet::RequestPartialReplay { .. } => "RequestPartialReplay",
             Packet::ReplayPiece { .. } => "ReplayPiece",
             Packet::Timestamp { .. } => "Timestamp",
@@ -689,6 +812,9 @@ impl fmt::Debug for Packet {
             Packet::RequestReaderReplay { ref keys, .. } => {
                 write!(f, "Packet::RequestReaderReplay({:?})", keys)
             }
+            Packet::ReleaseReaderReplays { ref keys, .. } => {
+                write!(f, "Packet::ReleaseReaderReplays({:?})", keys)
+            }
             Packet::RequestPartialReplay { ref tag, .. } => {
                 write!(f, "Packet::RequestPartialReplay({:?})", tag)
             }
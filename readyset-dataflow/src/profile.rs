@@ -0,0 +1,257 @@
+//! Profiles of the distribution of the values in the state of a node, computed from a sample of its
+//! rows. See [`DomainRequest::ProfileState`](crate::DomainRequest::ProfileState).
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use readyset_data::DfValue;
+use serde::{Deserialize, Serialize};
+
+/// The number of most frequent values to record in [`ColumnProfile::hot_keys`]
+const NUM_HOT_KEYS: usize = 8;
+
+/// The number of counters in each row of a [`CountMinSketch`]
+const SKETCH_WIDTH: usize = 128;
+
+/// The number of rows of counters in a [`CountMinSketch`]
+const SKETCH_DEPTH: usize = 4;
+
+/// A [count-min sketch][0] of the number of times each value occurred in a column, which can
+/// overestimate but never underestimate the frequency of a value.
+///
+/// Sketches are only comparable (and mergeable) with sketches built by the same version of
+/// ReadySet, since the hash of a value can change between versions.
+///
+/// [0]: https://en.wikipedia.org/wiki/Count%E2%80%93min_sketch
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CountMinSketch {
+    /// `SKETCH_DEPTH` rows of `SKETCH_WIDTH` counters each, in row-major order
+    counters: Vec<u32>,
+}
+
+impl Default for CountMinSketch {
+    fn default() -> Self {
+        Self {
+            counters: vec![0; SKETCH_WIDTH * SKETCH_DEPTH],
+        }
+    }
+}
+
+impl CountMinSketch {
+    /// Returns the index of the counter for `value` in the given row of the sketch
+    fn counter(row: usize, value: &DfValue) -> usize {
+        let mut hasher = DefaultHasher::new();
+        row.hash(&mut hasher);
+        value.hash(&mut hasher);
+        row * SKETCH_WIDTH + (hasher.finish() as usize) % SKETCH_WIDTH
+    }
+
+    /// Record `count` more occurrences of `value`
+    pub fn insert(&mut self, value: &DfValue, count: u32) {
+        for row in 0..SKETCH_DEPTH {
+            if let Some(counter) = self.counters.get_mut(Self::counter(row, value)) {
+                *counter = counter.saturating_add(count);
+            }
+        }
+    }
+
+    /// Estimate the number of times `value` occurred
+    pub fn estimate(&self, value: &DfValue) -> u32 {
+        (0..SKETCH_DEPTH)
+            .filter_map(|row| self.counters.get(Self::counter(row, value)))
+            .copied()
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Add all the occurrences recorded in `other` to this sketch
+    pub fn merge(&mut self, other: &CountMinSketch) {
+        for (x, y) in self.counters.iter_mut().zip(&other.counters) {
+            *x = x.saturating_add(*y);
+        }
+    }
+}
+
+/// The distribution of the values in a single column of a sample of rows
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnProfile {
+    /// The number of distinct values in the column
+    pub distinct: usize,
+    /// The number of values which occurred exactly once in the column
+    pub singletons: usize,
+    /// The number of NULL values in the column
+    pub nulls: usize,
+    /// The smallest non-NULL value in the column
+    pub min: Option<DfValue>,
+    /// The largest non-NULL value in the column
+    pub max: Option<DfValue>,
+    /// The most frequently occurring values in the column along with the number of times they
+    /// occurred, most frequent first
+    pub hot_keys: Vec<(DfValue, usize)>,
+    /// A sketch of the number of times each value occurred in the column
+    pub sketch: CountMinSketch,
+}
+
+impl ColumnProfile {
+    fn new(counts: HashMap<DfValue, usize>) -> Self {
+        let mut profile = ColumnProfile {
+            distinct: counts.len(),
+            singletons: counts.values().filter(|n| **n == 1).count(),
+            nulls: counts.get(&DfValue::None).copied().unwrap_or(0),
+            ..Default::default()
+        };
+
+        for (value, count) in &counts {
+            profile
+                .sketch
+                .insert(value, (*count).try_into().unwrap_or(u32::MAX));
+            if value.is_none() {
+                continue;
+            }
+            if profile.min.as_ref().map_or(true, |min| value < min) {
+                profile.min = Some(value.clone());
+            }
+            if profile.max.as_ref().map_or(true, |max| value > max) {
+                profile.max = Some(value.clone());
+            }
+        }
+
+        let mut hot_keys = counts.into_iter().collect::<Vec<_>>();
+        hot_keys.sort_by(|(v1, n1), (v2, n2)| n2.cmp(n1).then_with(|| v1.cmp(v2)));
+        hot_keys.truncate(NUM_HOT_KEYS);
+        profile.hot_keys = hot_keys;
+
+        profile
+    }
+
+    /// Combine this profile with the profile of the same column in a disjoint set of rows.
+    ///
+    /// Since the values in the two sets of rows may overlap, this slightly overestimates the
+    /// number of distinct and singleton values unless the rows were partitioned by this column.
+    fn merge(&mut self, other: ColumnProfile) {
+        self.distinct += other.distinct;
+        self.singletons += other.singletons;
+        self.nulls += other.nulls;
+        self.min = match (self.min.take(), other.min) {
+            (Some(x), Some(y)) => Some(x.min(y)),
+            (x, y) => x.or(y),
+        };
+        self.max = match (self.max.take(), other.max) {
+            (Some(x), Some(y)) => Some(x.max(y)),
+            (x, y) => x.or(y),
+        };
+        self.sketch.merge(&other.sketch);
+
+        let mut hot_keys: HashMap<DfValue, usize> = HashMap::new();
+        for (value, count) in self.hot_keys.drain(..).chain(other.hot_keys) {
+            *hot_keys.entry(value).or_default() += count;
+        }
+        let mut hot_keys = hot_keys.into_iter().collect::<Vec<_>>();
+        hot_keys.sort_by(|(v1, n1), (v2, n2)| n2.cmp(n1).then_with(|| v1.cmp(v2)));
+        hot_keys.truncate(NUM_HOT_KEYS);
+        self.hot_keys = hot_keys;
+    }
+}
+
+/// A profile of the distribution of the values in each column of a sample of the rows of a node's
+/// state
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateProfile {
+    /// The number of rows in the sample
+    pub rows: usize,
+    /// The profile of each column of the sample, in column order
+    pub columns: Vec<ColumnProfile>,
+}
+
+impl StateProfile {
+    /// Build a profile of the given rows
+    pub fn new<I>(rows: I) -> Self
+    where
+        I: IntoIterator<Item = Vec<DfValue>>,
+    {
+        let mut counts: Vec<HashMap<DfValue, usize>> = vec![];
+        let mut num_rows = 0;
+        for row in rows {
+            if counts.len() < row.len() {
+                counts.resize_with(row.len(), Default::default);
+            }
+            for (count, value) in counts.iter_mut().zip(row) {
+                *count.entry(value).or_default() += 1;
+            }
+            num_rows += 1;
+        }
+
+        StateProfile {
+            rows: num_rows,
+            columns: counts.into_iter().map(ColumnProfile::new).collect(),
+        }
+    }
+
+    /// Combine this profile with the profile of a disjoint set of rows with the same columns, such
+    /// as the rows of a different shard of the same node
+    pub fn merge(&mut self, other: StateProfile) {
+        self.rows += other.rows;
+        let mut other_columns = other.columns.into_iter();
+        for (column, other) in self.columns.iter_mut().zip(other_columns.by_ref()) {
+            column.merge(other);
+        }
+        self.columns.extend(other_columns);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(values: &[DfValue]) -> StateProfile {
+        StateProfile::new(values.iter().map(|v| vec![v.clone()]))
+    }
+
+    #[test]
+    fn profile_column() {
+        let profile = profile(&[
+            DfValue::from(3),
+            DfValue::from(1),
+            DfValue::None,
+            DfValue::from(3),
+            DfValue::from(2),
+            DfValue::from(3),
+        ]);
+        assert_eq!(profile.rows, 6);
+        let column = &profile.columns[0];
+        assert_eq!(column.distinct, 4);
+        assert_eq!(column.singletons, 3);
+        assert_eq!(column.nulls, 1);
+        assert_eq!(column.min, Some(DfValue::from(1)));
+        assert_eq!(column.max, Some(DfValue::from(3)));
+        assert_eq!(column.hot_keys[0], (DfValue::from(3), 3));
+        assert!(column.sketch.estimate(&DfValue::from(3)) >= 3);
+    }
+
+    #[test]
+    fn merge_profiles() {
+        let mut profile1 = profile(&[DfValue::from(1), DfValue::from(1)]);
+        let profile2 = profile(&[DfValue::from(1), DfValue::from(5), DfValue::None]);
+        profile1.merge(profile2);
+
+        assert_eq!(profile1.rows, 5);
+        let column = &profile1.columns[0];
+        assert_eq!(column.nulls, 1);
+        assert_eq!(column.min, Some(DfValue::from(1)));
+        assert_eq!(column.max, Some(DfValue::from(5)));
+        assert_eq!(column.hot_keys[0], (DfValue::from(1), 3));
+        assert!(column.sketch.estimate(&DfValue::from(1)) >= 3);
+    }
+
+    #[test]
+    fn sketch_never_underestimates() {
+        let mut sketch = CountMinSketch::default();
+        for i in 0..1000 {
+            sketch.insert(&DfValue::from(i), (i % 7) as u32);
+        }
+        for i in 0..1000 {
+            assert!(sketch.estimate(&DfValue::from(i)) >= (i % 7) as u32);
+        }
+    }
+}
@@ -4,7 +4,8 @@
 pub mod rpc;
 
 use std::error::Error;
-use std::io;
+use std::time::Duration;
+use std::{fmt, io, iter};
 
 use derive_more::Display;
 use nom_sql::{DialectDisplay, Relation, SelectStatement};
@@ -59,6 +60,74 @@ pub enum NodeType {
     Sharder,
 }
 
+/// The rate limit on a view that was exceeded by a read, for use in
+/// [`ReadySetError::ViewThrottled`]
+#[derive(Eq, PartialEq, Serialize, Deserialize, Debug, Display, Clone, Copy)]
+pub enum RateLimit {
+    /// The maximum number of reads per second
+    #[display(fmt = "requests per second")]
+    RequestsPerSecond,
+    /// The maximum number of reads waiting on misses at once
+    #[display(fmt = "concurrent misses")]
+    ConcurrentMisses,
+}
+
+/// Where in the dataflow graph an error occurred, for use in [`ReadySetError::InDataflow`].
+///
+/// Each field is only known if the error passed through the part of the code that knows about
+/// it. For example, errors which occur while processing a replay have a `tag`, but errors which
+/// occur while processing a regular write don't.
+///
+/// This is rendered as space-separated `key=value` pairs of only the known fields, such as
+/// `domain=3 shard=0 node=12 operator="Internal (Join)" tag=5`, so that it can be parsed out of
+/// logs.
+#[derive(Eq, PartialEq, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DataflowContext {
+    /// The index of the domain
+    pub domain: Option<usize>,
+    /// The shard of the domain, if the domain is sharded
+    pub shard: Option<usize>,
+    /// The global index of the node
+    pub node: Option<NodeIndex>,
+    /// The kind of the node
+    pub operator: Option<String>,
+    /// The tag of the replay path
+    pub tag: Option<u32>,
+}
+
+impl DataflowContext {
+    /// Fill in any fields of `self` which aren't known from `other`
+    fn fill_from(&mut self, other: DataflowContext) {
+        self.domain = self.domain.or(other.domain);
+        self.shard = self.shard.or(other.shard);
+        self.node = self.node.or(other.node);
+        self.operator = self.operator.take().or(other.operator);
+        self.tag = self.tag.or(other.tag);
+    }
+}
+
+impl fmt::Display for DataflowContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut fields = Vec::new();
+        if let Some(domain) = self.domain {
+            fields.push(format!("domain={domain}"));
+        }
+        if let Some(shard) = self.shard {
+            fields.push(format!("shard={shard}"));
+        }
+        if let Some(node) = self.node {
+            fields.push(format!("node={}", node.index()));
+        }
+        if let Some(operator) = &self.operator {
+            fields.push(format!("operator={operator:?}"));
+        }
+        if let Some(tag) = self.tag {
+            fields.push(format!("tag={tag}"));
+        }
+        write!(f, "{}", fields.join(" "))
+    }
+}
+
 /// General error type to be used across all of the ReadySet codebase.
 #[derive(Eq, PartialEq, Serialize, Deserialize, Error, Debug, Clone)]
 pub enum ReadySetError {
@@ -72,6 +141,16 @@ pub enum ReadySetError {
         error: Box<ReadySetError>,
     },
 
+    /// An error which occurred at a particular place in the dataflow graph
+    #[error("{source} [{context}]")]
+    InDataflow {
+        /// Where in the dataflow graph the error occurred
+        context: DataflowContext,
+        /// The original error
+        #[source]
+        source: Box<ReadySetError>,
+    },
+
     /// The query is invalid
     #[error("The provided query is invalid: {0}")]
     InvalidQuery(String),
@@ -275,6 +354,14 @@ pub enum ReadySetError {
         schema: Option<String>,
     },
 
+    /// A query was made referencing a column that is excluded from replication by the
+    /// `--replication-columns-ignore` option
+    #[error(
+        "Column '{column}' of table '{table}' is not being replicated by ReadySet, since it is \
+         ignored by the rule '{table}.{column}' in --replication-columns-ignore"
+    )]
+    ColumnNotReplicated { table: String, column: String },
+
     /// A view is not yet available.
     #[error("view not yet available")]
     ViewNotYetAvailable,
@@ -283,6 +370,10 @@ pub enum ReadySetError {
     #[error("view destroyed")]
     ViewDestroyed,
 
+    /// A read was made against a view whose domain has failed, and is being recovered
+    #[error("View {0} is unavailable while the domain maintaining it recovers from a failure")]
+    ViewUnavailable(String),
+
     /// A view couldn't be found.
     #[error("Could not find view {0}")]
     ViewNotFound(String),
@@ -323,6 +414,41 @@ pub enum ReadySetError {
     #[error("Upquery timeout")]
     UpqueryTimeout,
 
+    /// A read against a view was rejected because it would have exceeded one of the rate limits
+    /// configured for that view.
+    #[error("Read against view {view} throttled: exceeded limit on {limit}")]
+    ViewThrottled {
+        /// The name of the view
+        view: String,
+        /// The limit that was exceeded
+        limit: RateLimit,
+    },
+
+    /// A read against a view as of some time in the past was rejected because the view doesn't
+    /// retain history as far back as that time, or doesn't retain history at all.
+    #[error("View {view} does not retain history as far back as the requested time")]
+    ViewHistoryUnavailable {
+        /// The name of the view
+        view: String,
+    },
+
+    /// A request was not sent because too many consecutive requests through the same handle
+    /// recently failed, and the handle's circuit breaker is open.
+    #[error("Not sending request to {target}: too many consecutive requests failed")]
+    CircuitOpen {
+        /// The name of the view or table the request was made against
+        target: String,
+    },
+
+    /// A read was rejected because it would have had to wait for a domain which is too far behind
+    /// on handling the packets it has received to fill a miss. The read should be retried after
+    /// `retry_after`, or sent to the upstream database instead.
+    #[error("ReadySet is overloaded, retry after {retry_after:?}")]
+    Overloaded {
+        /// How long the client should wait before retrying the read
+        retry_after: Duration,
+    },
+
     /// The query specified an empty lookup key.
     #[error("the query specified an empty lookup key")]
     EmptyKey,
@@ -640,9 +766,16 @@ pub enum ReadySetError {
     #[error("Fatal error requires full resnapshot")]
     FullResnapshotNeeded,
 
+    /// Replication was stopped because it was paused by a request to the controller
+    #[error("Replication paused")]
+    ReplicationPaused,
+
     #[error("Root certificate must be a valid DER or PEM encoded certificate")]
     InvalidRootCertificate,
 
+    #[error("Client identity must be a valid PKCS#12 archive")]
+    InvalidClientIdentity,
+
     /// Error when a node couldn't be found in MIR.
     #[error("Could not find MIR node {index}")]
     MirNodeNotFound { index: usize },
@@ -679,6 +812,10 @@ pub enum ReadySetError {
     #[error("Unknown migration: {0}")]
     UnknownMigration(u64),
 
+    /// A migration was cancelled before it finished running
+    #[error("Migration was cancelled")]
+    MigrationCancelled,
+
     /// Error interacting with a Consul server
     #[error("Consul error: {0}")]
     ConsulError(String),
@@ -720,6 +857,37 @@ impl ReadySetError {
         }
     }
 
+    /// Record where in the dataflow graph this error occurred. If this error already has a
+    /// [`DataflowContext`], any fields of it which aren't known yet are filled in from `context`
+    /// rather than wrapping the error again.
+    pub fn in_dataflow(self, context: DataflowContext) -> Self {
+        match self {
+            ReadySetError::InDataflow {
+                context: mut existing,
+                source,
+            } => {
+                existing.fill_from(context);
+                ReadySetError::InDataflow {
+                    context: existing,
+                    source,
+                }
+            }
+            error => ReadySetError::InDataflow {
+                context,
+                source: Box::new(error),
+            },
+        }
+    }
+
+    /// Returns the [`DataflowContext`] of where in the dataflow graph this error, or any of its
+    /// causes, occurred, if known
+    pub fn dataflow_context(&self) -> Option<&DataflowContext> {
+        self.find_map_cause(|e| match e {
+            Self::InDataflow { context, .. } => Some(context),
+            _ => None,
+        })
+    }
+
     /// Returns true if any of the causes of the error satisfy the given predicate
     pub fn any_cause<F>(&self, f: F) -> bool
     where
@@ -735,6 +903,16 @@ impl ReadySetError {
                 .any(move |e| e.any_cause(f.clone()))
     }
 
+    /// Returns an iterator over this error followed by each of the errors that (transitively)
+    /// caused it, outermost first
+    pub fn causes(&self) -> impl Iterator<Item = &Self> {
+        iter::successors(Some(self), |&e| {
+            e.source()
+                .and_then(|e| e.downcast_ref::<Box<ReadySetError>>())
+                .map(|e| e.as_ref())
+        })
+    }
+
     fn find_map_cause<'a, F, R: 'a>(&'a self, f: F) -> Option<R>
     where
         F: Fn(&'a Self) -> Option<R>,
@@ -842,6 +1020,35 @@ impl ReadySetError {
         self.any_cause(|e| matches!(e, Self::ViewDestroyed))
     }
 
+    /// Returns true if the error either *is* [`ReaderNotFound`], or was *caused by*
+    /// [`ReaderNotFound`]
+    pub fn caused_by_reader_not_found(&self) -> bool {
+        self.any_cause(|e| matches!(e, Self::ReaderNotFound))
+    }
+
+    /// Returns true if the error either *is* [`ViewThrottled`], or was *caused by*
+    /// [`ViewThrottled`]
+    pub fn caused_by_view_throttled(&self) -> bool {
+        self.any_cause(|e| matches!(e, Self::ViewThrottled { .. }))
+    }
+
+    /// Returns true if the error either *is* [`ViewHistoryUnavailable`], or was *caused by*
+    /// [`ViewHistoryUnavailable`]
+    pub fn caused_by_view_history_unavailable(&self) -> bool {
+        self.any_cause(|e| matches!(e, Self::ViewHistoryUnavailable { .. }))
+    }
+
+    /// Returns true if the error either *is* [`CircuitOpen`], or was *caused by*
+    /// [`CircuitOpen`]
+    pub fn caused_by_circuit_open(&self) -> bool {
+        self.any_cause(|e| matches!(e, Self::CircuitOpen { .. }))
+    }
+
+    /// Returns true if the error either *is* [`Overloaded`], or was *caused by* [`Overloaded`]
+    pub fn caused_by_overloaded(&self) -> bool {
+        self.any_cause(|e| matches!(e, Self::Overloaded { .. }))
+    }
+
     /// Returns true if the error either *is* [`SerializationFailed`], or was *caused by*
     /// [`SerializationFailed`]
     pub fn caused_by_serialization_failed(&self) -> bool {
@@ -1263,3 +1470,68 @@ mod test {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dataflow_context_display() {
+        let error = ReadySetError::Internal("oops".into()).in_dataflow(DataflowContext {
+            node: Some(NodeIndex::new(12)),
+            operator: Some("Internal (Join)".into()),
+            tag: Some(5),
+            ..Default::default()
+        });
+        assert_eq!(
+            error.to_string(),
+            "Internal error: oops [node=12 operator=\"Internal (Join)\" tag=5]"
+        );
+    }
+
+    #[test]
+    fn in_dataflow_fills_existing_context() {
+        let error = ReadySetError::Internal("oops".into())
+            .in_dataflow(DataflowContext {
+                node: Some(NodeIndex::new(12)),
+                ..Default::default()
+            })
+            .in_dataflow(DataflowContext {
+                domain: Some(3),
+                shard: Some(0),
+                node: Some(NodeIndex::new(1)),
+                ..Default::default()
+            });
+        assert_eq!(
+            error.dataflow_context(),
+            Some(&DataflowContext {
+                domain: Some(3),
+                shard: Some(0),
+                node: Some(NodeIndex::new(12)),
+                ..Default::default()
+            })
+        );
+        assert!(matches!(
+            error.causes().nth(1),
+            Some(ReadySetError::Internal(_))
+        ));
+    }
+
+    #[test]
+    fn dataflow_context_survives_rpc() {
+        let error = rpc_err_no_downcast(
+            "domain request",
+            ReadySetError::Internal("oops".into()).in_dataflow(DataflowContext {
+                domain: Some(3),
+                ..Default::default()
+            }),
+        );
+        let roundtripped: ReadySetError =
+            bincode::deserialize(&bincode::serialize(&error).unwrap()).unwrap();
+        assert_eq!(roundtripped, error);
+        assert_eq!(
+            roundtripped.dataflow_context().and_then(|c| c.domain),
+            Some(3)
+        );
+    }
+}
@@ -447,6 +447,8 @@ impl TryFrom<DfValue> for Value {
             DfValue::TimestampTz(ref ts) => Ok(Value::Date(ts.to_chrono().naive_utc())),
             DfValue::Time(t) => Ok(Value::Time(t)),
             DfValue::ByteArray(t) => Ok(Value::ByteArray(t.as_ref().clone())),
+            DfValue::Uuid(ref u) => Ok(Value::Text(u.to_string())),
+            DfValue::Interval(ref i) => Ok(Value::Text(i.to_string())),
             DfValue::Numeric(ref d) => Ok(Value::Numeric(*d.as_ref())),
             DfValue::BitVector(ref b) => Ok(Value::BitVector(b.as_ref().clone())),
             DfValue::Array(_) => bail!("Arrays not supported"),
@@ -108,6 +108,8 @@ fn is_ddl(query: &SqlQuery) -> bool {
         | SqlQuery::CreateView(_)
         | SqlQuery::DropTable(_)
         | SqlQuery::DropView(_)
+        | SqlQuery::CreateIndex(_)
+        | SqlQuery::DropIndex(_)
         | SqlQuery::AlterTable(_)
         | SqlQuery::RenameTable(_)
         | SqlQuery::Use(_)
@@ -270,6 +270,7 @@ impl Seed {
                         .collect(),
                     ignore: false,
                     on_duplicate: None,
+                    select: None,
                 }
             })
             .collect::<Vec<_>>();
@@ -7,8 +7,11 @@
 use std::iter;
 use std::ops::{Deref, DerefMut, Index, IndexMut};
 
+use dataflow::ops::grouped::aggregate::Aggregation;
+use dataflow::ops::grouped::extremum::Extremum;
 use itertools::Itertools;
 use nom_sql::analysis::ReferredColumns;
+use nom_sql::DialectDisplay;
 use petgraph::stable_graph::StableGraph;
 use petgraph::visit::{Bfs, EdgeRef, Reversed};
 use petgraph::{Directed, Direction};
@@ -17,7 +20,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::node::node_inner::ProjectExpr;
 use crate::node::{MirNode, MirNodeInner};
-use crate::{Column as MirColumn, DfNodeIndex, Ix, NodeIndex, PAGE_NUMBER_COL};
+use crate::{
+    Column as MirColumn, DfNodeIndex, Ix, NodeIndex, FULL_TEXT_SCORE_COL, PAGE_NUMBER_COL,
+};
 
 type Graph = StableGraph<MirNode, usize, Directed, Ix>;
 
@@ -176,9 +181,26 @@ impl MirGraph {
     /// from its parent.
     pub fn referenced_columns(&self, node: NodeIndex) -> Vec<MirColumn> {
         match &self.graph[node].inner {
-            MirNodeInner::Aggregation { on, group_by, .. }
-            | MirNodeInner::Extremum { on, group_by, .. } => {
-                // Aggregates need the group_by columns and the "over" column
+            MirNodeInner::Aggregation {
+                on, group_by, kind, ..
+            } => {
+                // Aggregates need the group_by columns, the "over" column, and any columns they
+                // order by
+                let mut columns = group_by.clone();
+                if !columns.contains(on) {
+                    columns.push(on.clone());
+                }
+                if let Aggregation::GroupConcat { order_by, .. } = kind {
+                    for (col, _) in order_by {
+                        if !columns.contains(col) {
+                            columns.push(col.clone());
+                        }
+                    }
+                }
+                columns
+            }
+            MirNodeInner::Extremum { on, group_by, .. } => {
+                // Extrema need the group_by columns and the "over" column
                 let mut columns = group_by.clone();
                 if !columns.contains(on) {
                     columns.push(on.clone());
@@ -267,7 +289,8 @@ impl MirGraph {
             | MirNodeInner::ViewKey { .. }
             | MirNodeInner::Leaf { .. }
             | MirNodeInner::Identity
-            | MirNodeInner::TopK { .. } => parent_columns(),
+            | MirNodeInner::TopK { .. }
+            | MirNodeInner::Latest { .. } => parent_columns(),
             MirNodeInner::AliasTable { table } => parent_columns()
                 .iter()
                 .map(|c| MirColumn {
@@ -324,6 +347,10 @@ impl MirGraph {
                 .into_iter()
                 .chain(iter::once(MirColumn::named(&*PAGE_NUMBER_COL)))
                 .collect(),
+            MirNodeInner::FullTextSearch { .. } => parent_columns()
+                .into_iter()
+                .chain(iter::once(MirColumn::named(&*FULL_TEXT_SCORE_COL)))
+                .collect(),
             MirNodeInner::Distinct { group_by } => group_by
                 .iter()
                 .cloned()
@@ -415,6 +442,115 @@ impl MirGraph {
         }
     }
 
+    /// Traces the given column of the given node back through the node's ancestors, returning the
+    /// columns of base tables which its values are computed from, and the SQL text of the
+    /// expressions and aggregates applied to those columns along the way, outermost first.
+    pub fn column_provenance(
+        &self,
+        node: NodeIndex,
+        column: &MirColumn,
+    ) -> (Vec<nom_sql::Column>, Vec<String>) {
+        let mut base_columns: Vec<nom_sql::Column> = vec![];
+        let mut expressions: Vec<String> = vec![];
+        let mut visited: Vec<(NodeIndex, MirColumn)> = vec![];
+        let mut to_visit = vec![(node, column.clone())];
+
+        // Continue tracing `column` in each of the parents of `node` which provides it
+        let trace_in_parents =
+            |node: NodeIndex, column: &MirColumn, to_visit: &mut Vec<(NodeIndex, MirColumn)>| {
+                for parent in self.sorted_ancestors(node) {
+                    if let Some(idx) = self.find_source_for_child_column(parent, column) {
+                        if let Some(c) = self.columns(parent).into_iter().nth(idx) {
+                            to_visit.push((parent, c));
+                        }
+                    }
+                }
+            };
+
+        while let Some((node, column)) = to_visit.pop() {
+            if visited.contains(&(node, column.clone())) {
+                continue;
+            }
+            visited.push((node, column.clone()));
+
+            let Ok(idx) = self.column_id_for_column(node, &column) else {
+                continue;
+            };
+            match &self.graph[node].inner {
+                MirNodeInner::Base { column_specs, .. } => {
+                    if let Some(spec) = column_specs.get(idx) {
+                        let base_column = nom_sql::Column {
+                            name: spec.column.name.clone(),
+                            table: Some(self.graph[node].name().clone()),
+                        };
+                        if !base_columns.contains(&base_column) {
+                            base_columns.push(base_column);
+                        }
+                    }
+                }
+                MirNodeInner::Project { emit } => match emit.get(idx) {
+                    Some(ProjectExpr::Column(c)) => trace_in_parents(node, c, &mut to_visit),
+                    Some(ProjectExpr::Expr { expr, .. }) => {
+                        expressions.push(expr.display(nom_sql::Dialect::MySQL).to_string());
+                        for c in expr.referred_columns() {
+                            trace_in_parents(node, &MirColumn::from(c), &mut to_visit);
+                        }
+                    }
+                    None => {}
+                },
+                MirNodeInner::Aggregation {
+                    on,
+                    output_column,
+                    kind,
+                    ..
+                } if *output_column == column => {
+                    let function = match kind {
+                        Aggregation::Count => "COUNT",
+                        Aggregation::Sum => "SUM",
+                        Aggregation::Avg => "AVG",
+                        Aggregation::GroupConcat { .. } => "GROUP_CONCAT",
+                        Aggregation::ApproxCountDistinct { .. } => "APPROX_COUNT_DISTINCT",
+                    };
+                    expressions.push(format!("{function}({})", on.name));
+                    trace_in_parents(node, on, &mut to_visit);
+                }
+                MirNodeInner::Extremum {
+                    on,
+                    output_column,
+                    kind,
+                    ..
+                } if *output_column == column => {
+                    let function = match kind {
+                        Extremum::Min => "MIN",
+                        Extremum::Max => "MAX",
+                    };
+                    expressions.push(format!("{function}({})", on.name));
+                    trace_in_parents(node, on, &mut to_visit);
+                }
+                MirNodeInner::Union { emit, .. } => {
+                    // Each parent of a union contributes the column at the same position in its
+                    // own emit list
+                    for (parent, parent_emit) in self.sorted_ancestors(node).zip(emit) {
+                        if let Some(c) = parent_emit.get(idx) {
+                            to_visit.push((parent, c.clone()));
+                        }
+                    }
+                }
+                MirNodeInner::AliasTable { .. } => {
+                    // Alias table nodes only change the table of their parent's columns
+                    for parent in self.sorted_ancestors(node) {
+                        if let Some(c) = self.columns(parent).into_iter().nth(idx) {
+                            to_visit.push((parent, c));
+                        }
+                    }
+                }
+                _ => trace_in_parents(node, &column, &mut to_visit),
+            }
+        }
+
+        (base_columns, expressions)
+    }
+
     /// Add a new column to the set of emitted columns for this node
     pub fn add_column(&mut self, node: NodeIndex, c: MirColumn) -> ReadySetResult<()> {
         if !self.graph[node].inner.add_column(c.clone())? {
@@ -48,6 +48,7 @@ pub use column::Column;
 use lazy_static::lazy_static;
 use nom_sql::SqlIdentifier;
 use petgraph::csr::IndexType;
+pub use rewrite::RewriteOptions;
 use serde::{Deserialize, Serialize};
 
 mod column;
@@ -108,4 +109,14 @@ lazy_static! {
     ///
     /// [`Paginate`]: node::node_inner::MirNodeInner::Paginate
     pub static ref PAGE_NUMBER_COL: SqlIdentifier = "__page_number".into();
+
+    /// The column used by the [`FullTextSearch`] node for its relevance score
+    ///
+    /// [`FullTextSearch`]: node::node_inner::MirNodeInner::FullTextSearch
+    pub static ref FULL_TEXT_SCORE_COL: SqlIdentifier = "__full_text_score".into();
+
+    /// The column projected into queries that need a key or a group but don't have one - the key
+    /// of the view for a query without any parameters, or the group of a TopK over a whole query.
+    /// Every row has the same value (`0`) in this column, hence "bogokey" (from "bogus key").
+    pub static ref BOGOKEY_COL: SqlIdentifier = "__bogokey".into();
 }
@@ -13,7 +13,7 @@ pub mod node_inner;
 
 /// Helper enum to avoid having separate `make_aggregation_node` and `make_extremum_node` functions
 pub enum GroupedNodeType {
-    Aggregation(ops::grouped::aggregate::Aggregation),
+    Aggregation(ops::grouped::aggregate::Aggregation<crate::Column>),
     Extremum(ops::grouped::extremum::Extremum),
 }
 
@@ -370,6 +370,15 @@ mod tests {
                 )]),
                 group_by: vec![Column::new(Some("base"), "b")],
                 limit: 3,
+                mode: ops::topk::TopKMode::RowNumber,
+            })
+        }
+
+        #[test]
+        fn latest() {
+            same_columns_as_parent(MirNodeInner::Latest {
+                group_by: vec![Column::new(Some("base"), "b")],
+                over: Column::new(Some("base"), "a"),
             })
         }
 
@@ -392,6 +401,24 @@ mod tests {
             )
         }
 
+        #[test]
+        fn full_text_search() {
+            has_columns_single_parent(
+                MirNodeInner::FullTextSearch {
+                    columns: vec![Column::new(Some("base"), "a")],
+                    terms: vec!["quick".into(), "fox".into()],
+                    match_all: false,
+                    group_by: vec![Column::new(Some("base"), "b")],
+                    limit: Some(3),
+                },
+                vec![
+                    Column::new(Some("base"), "a"),
+                    Column::new(Some("base"), "b"),
+                    Column::named("__full_text_score"),
+                ],
+            )
+        }
+
         #[test]
         fn join_aggregates() {
             let mut graph = MirGraph::new();
@@ -3,6 +3,7 @@ use std::fmt::{self, Debug, Display};
 use common::{DfValue, IndexType};
 use dataflow::ops::grouped::aggregate::Aggregation;
 use dataflow::ops::grouped::extremum::Extremum;
+use dataflow::ops::topk::TopKMode;
 use dataflow::ops::union;
 use dataflow::PostLookupAggregates;
 use derive_more::From;
@@ -84,7 +85,7 @@ pub enum MirNodeInner {
         /// column
         output_column: Column,
         /// Which aggregate function we are computing
-        kind: Aggregation,
+        kind: Aggregation<Column>,
     },
     /// Base node in the graph, corresponding to a snapshot of a full table in the upstream
     /// database.
@@ -271,8 +272,42 @@ pub enum MirNodeInner {
         /// Set of columns that are indexed to form a unique grouping of results
         group_by: Vec<Column>,
         /// Numeric literal that determines the number of results stored per group. Taken from the
-        /// LIMIT clause
+        /// LIMIT clause, or from the constant a window function's rank is compared against
         limit: usize,
+        /// How rows which tie in `order` are treated
+        mode: TopKMode,
+    },
+    /// Node which emits only the most recent row per group, as determined by the value of a
+    /// timestamp (or other monotonically increasing) column.
+    ///
+    /// Converted to [`Latest`] when lowering to dataflow.
+    ///
+    /// [`Latest`]: dataflow::ops::latest::Latest
+    Latest {
+        /// Set of columns that are indexed to form a unique grouping of results
+        group_by: Vec<Column>,
+        /// The column used to determine which row is the most recent
+        over: Column,
+    },
+    /// Node which filters its input rows down to those whose text columns contain a set of search
+    /// terms, then emits an extra relevance score column (which will always have a name given by
+    /// [`FULL_TEXT_SCORE_COL`]) for each matching row.
+    ///
+    /// Converted to [`FullTextSearch`] when lowering to dataflow.
+    ///
+    /// [`FULL_TEXT_SCORE_COL`]: crate::FULL_TEXT_SCORE_COL
+    /// [`FullTextSearch`]: dataflow::ops::fulltext::FullTextSearch
+    FullTextSearch {
+        /// The text columns to search
+        columns: Vec<Column>,
+        /// The search terms
+        terms: Vec<String>,
+        /// If true, rows must contain all of the search terms rather than any of them
+        match_all: bool,
+        /// Set of columns that are indexed to form a unique grouping of results
+        group_by: Vec<Column>,
+        /// If set, only the `limit` highest-scoring rows per group are emitted
+        limit: Option<usize>,
     },
     /// Node which emits only distinct rows per some group.
     ///
@@ -300,7 +335,10 @@ pub enum MirNodeInner {
         /// Whether or not this leaf node was already lowered to dataflow or not.
         lowered_to_df: bool,
 
-        /// Optional set of columns and direction to order the results of lookups to this leaf
+        /// Optional set of columns and direction to order the results of lookups to this leaf.
+        ///
+        /// Readers keep the rows for each key sorted in this order as they're inserted, so that
+        /// lookups can return rows in order without sorting them after the fact.
         order_by: Option<Vec<(Column, OrderType)>>,
         /// Optional limit for the set of results to lookups to this leaf
         limit: Option<usize>,
@@ -381,6 +419,14 @@ impl MirNodeInner {
                 group_by.push(c);
                 Ok(true)
             }
+            MirNodeInner::Latest { group_by, .. } => {
+                group_by.push(c);
+                Ok(true)
+            }
+            MirNodeInner::FullTextSearch { group_by, .. } => {
+                group_by.push(c);
+                Ok(true)
+            }
             _ => Ok(false),
         }
     }
@@ -415,9 +461,26 @@ impl MirNodeInner {
                     Aggregation::Count { .. } => format!("|*|({})", on.name.as_str()),
                     Aggregation::Sum => format!("𝛴({})", on.name.as_str()),
                     Aggregation::Avg => format!("AVG({})", on.name.as_str()),
-                    Aggregation::GroupConcat { separator: ref s } => {
+                    Aggregation::GroupConcat {
+                        separator: ref s,
+                        ref order_by,
+                    } if !order_by.is_empty() => format!(
+                        "||([{}], \"{}\" ORDER BY {})",
+                        on.name.as_str(),
+                        s.as_str(),
+                        order_by
+                            .iter()
+                            .map(|(c, ot)| format!("{} {}", c.name.as_str(), ot))
+                            .join(", ")
+                    ),
+                    Aggregation::GroupConcat {
+                        separator: ref s, ..
+                    } => {
                         format!("||([{}], \"{}\")", on.name.as_str(), s.as_str())
                     }
+                    Aggregation::ApproxCountDistinct { precision } => {
+                        format!("≈|{}|({})", on.name.as_str(), precision)
+                    }
                 };
                 let group_cols = group_by
                     .iter()
@@ -564,9 +627,33 @@ impl MirNodeInner {
             MirNodeInner::TopK {
                 ref order,
                 ref limit,
+                mode,
+                ..
+            } => {
+                format!("TopK [k: {}, {}, {:?}]", limit, mode, order)
+            }
+            MirNodeInner::Latest {
+                ref group_by,
+                ref over,
+            } => {
+                format!(
+                    "Latest [γ: {}, over: {}]",
+                    group_by.iter().map(|c| c.name.as_str()).join(", "),
+                    over.name
+                )
+            }
+            MirNodeInner::FullTextSearch {
+                ref columns,
+                ref terms,
+                ref limit,
                 ..
             } => {
-                format!("TopK [k: {}, {:?}]", limit, order)
+                format!(
+                    "FullTextSearch [{} ⊇ {:?}, limit: {:?}]",
+                    columns.iter().map(|c| c.name.clone()).join(", "),
+                    terms,
+                    limit
+                )
             }
             MirNodeInner::Union {
                 ref emit,
@@ -4,6 +4,7 @@ use itertools::Itertools;
 use nom_sql::{Relation, SqlIdentifier};
 use petgraph::visit::{Bfs, EdgeRef, IntoNodeReferences, Reversed};
 use petgraph::Direction;
+use readyset_client::ColumnProvenance;
 use readyset_errors::{internal_err, ReadySetError, ReadySetResult};
 
 use crate::graph::MirGraph;
@@ -76,6 +77,51 @@ impl<'a> MirQuery<'a> {
             .collect()
     }
 
+    /// Returns where the values of each of the fields returned by this query are derived from, in
+    /// the same order as [`Self::fields`]
+    pub fn column_provenance(&self) -> Vec<ColumnProvenance> {
+        self.graph
+            .columns(self.leaf)
+            .into_iter()
+            .map(|column| {
+                let (base_columns, expressions) = self.graph.column_provenance(self.leaf, &column);
+                ColumnProvenance {
+                    column: column.name,
+                    base_columns,
+                    expressions,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns all the columns of base tables which are referenced anywhere in this query, whether
+    /// they're returned by the query or only used to compute its results (eg in filters or join
+    /// keys)
+    pub fn referenced_base_columns(&self) -> Vec<nom_sql::Column> {
+        let mut base_columns = vec![];
+        for (node, _) in self.node_references() {
+            let referenced = self.graph.referenced_columns(node);
+            for parent in self.graph.neighbors_directed(node, Direction::Incoming) {
+                let parent_columns = self.graph.columns(parent);
+                for column in &referenced {
+                    let Some(parent_column) = self
+                        .graph
+                        .find_source_for_child_column(parent, column)
+                        .and_then(|idx| parent_columns.get(idx))
+                    else {
+                        continue;
+                    };
+                    for base_column in self.graph.column_provenance(parent, parent_column).0 {
+                        if !base_columns.contains(&base_column) {
+                            base_columns.push(base_column);
+                        }
+                    }
+                }
+            }
+        }
+        base_columns
+    }
+
     pub fn is_root(&self, node: NodeIndex) -> bool {
         self.graph[node].is_owned_by(&self.name)
             && self
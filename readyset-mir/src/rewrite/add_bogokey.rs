@@ -8,7 +8,7 @@ use tracing::trace;
 use crate::node::node_inner::ProjectExpr;
 use crate::node::{MirNode, MirNodeInner};
 use crate::query::MirQuery;
-use crate::Column;
+use crate::{Column, BOGOKEY_COL};
 
 /// If the given query has a Leaf but doesn't have any keys, create a key for it by adding a new
 /// node to the query that projects out a constant literal value (a "bogokey", from "bogus key") and
@@ -30,8 +30,9 @@ pub(crate) fn add_bogokey_if_necessary(query: &mut MirQuery<'_>) -> ReadySetResu
 
     // Find the node we're going to insert the bogokey project node above
     //
-    // Usually this'll be the first leaf project node, but in the case of topk or paginate with an
-    // empty group_by we insert above those instead, since those both happen to need a group_by.
+    // Usually this'll be the first leaf project node, but in the case of topk, latest, or paginate
+    // with an empty group_by we insert above those instead, since those all happen to need a
+    // group_by.
     let mut node_to_insert_above = query.leaf();
     while let Some(parent) = query
         .ancestors(node_to_insert_above)?
@@ -42,6 +43,7 @@ pub(crate) fn add_bogokey_if_necessary(query: &mut MirQuery<'_>) -> ReadySetResu
                 || matches!(
                     inner,
                     MirNodeInner::TopK { group_by, .. }
+                    | MirNodeInner::Latest { group_by, .. }
                     | MirNodeInner::Paginate { group_by, .. }
                     if group_by.is_empty()
                 )
@@ -71,7 +73,7 @@ pub(crate) fn add_bogokey_if_necessary(query: &mut MirQuery<'_>) -> ReadySetResu
                     .map(ProjectExpr::Column)
                     .chain(iter::once(ProjectExpr::Expr {
                         expr: Expr::Literal(0.into()),
-                        alias: "bogokey".into(),
+                        alias: BOGOKEY_COL.clone(),
                     }))
                     .collect(),
             },
@@ -80,13 +82,16 @@ pub(crate) fn add_bogokey_if_necessary(query: &mut MirQuery<'_>) -> ReadySetResu
     trace!(?bogo_project, "Added new bogokey project node");
 
     if let MirNodeInner::Leaf { keys, .. } = &mut query.leaf_node_mut().inner {
-        keys.push((Column::named("bogokey"), ViewPlaceholder::Generated))
+        keys.push((
+            Column::named(BOGOKEY_COL.clone()),
+            ViewPlaceholder::Generated,
+        ))
     }
 
-    if let MirNodeInner::TopK { group_by, .. } =
+    if let MirNodeInner::TopK { group_by, .. } | MirNodeInner::Latest { group_by, .. } =
         &mut query.get_node_mut(node_to_insert_above).unwrap().inner
     {
-        group_by.push(Column::named("bogokey"))
+        group_by.push(Column::named(BOGOKEY_COL.clone()))
     }
 
     Ok(())
@@ -96,6 +101,7 @@ pub(crate) fn add_bogokey_if_necessary(query: &mut MirQuery<'_>) -> ReadySetResu
 #[allow(clippy::panic)]
 mod tests {
     use common::IndexType;
+    use dataflow::ops::topk::TopKMode;
     use nom_sql::{BinaryOperator, ColumnSpecification, Literal, Relation, SqlType};
     use petgraph::visit::EdgeRef;
     use petgraph::Direction;
@@ -164,7 +170,7 @@ mod tests {
                     ProjectExpr::Expr {
                         expr: Expr::Literal(Literal::Integer(0)),
                         alias
-                    } if alias == "bogokey"
+                    } if *alias == *BOGOKEY_COL
                 )))
             }
             _ => panic!("bogo project node should be a Project"),
@@ -205,6 +211,7 @@ mod tests {
                 order: None,
                 group_by: vec![],
                 limit: 3,
+                mode: TopKMode::RowNumber,
             },
         ));
         mir_graph[topk].add_owner(query_name.clone());
@@ -242,7 +249,7 @@ mod tests {
                         ProjectExpr::Expr {
                             expr: Expr::Literal(Literal::Integer(0)),
                             alias
-                        } if alias == "bogokey"
+                        } if *alias == *BOGOKEY_COL
                     )),
                     "{emit:?}"
                 )
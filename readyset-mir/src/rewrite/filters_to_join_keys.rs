@@ -62,7 +62,9 @@ pub(crate) fn convert_filters_to_join_keys(query: &mut MirQuery<'_>) -> ReadySet
                 | MirNodeInner::Extremum { group_by, .. }
                 | MirNodeInner::Distinct { group_by, .. }
                 | MirNodeInner::Paginate { group_by, .. }
-                | MirNodeInner::TopK { group_by, .. } => {
+                | MirNodeInner::TopK { group_by, .. }
+                | MirNodeInner::Latest { group_by, .. }
+                | MirNodeInner::FullTextSearch { group_by, .. } => {
                     if !(group_by.contains(&c1) && group_by.contains(&c2)) {
                         trace!(
                             "Columns in filter not in group_by of ancestor grouped node; can't \
@@ -9,16 +9,36 @@ mod fuse;
 mod predicate_pushup;
 mod pull_columns;
 mod pull_keys;
+mod topk_pushup;
+
+/// Options controlling which of the optional rewrite passes are run by [`MirQuery::rewrite`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RewriteOptions {
+    /// Whether to push filters up the graph, past joins and unions and towards the base tables
+    /// (defaults to `true`)
+    pub push_filters_up: bool,
+}
+
+impl Default for RewriteOptions {
+    fn default() -> Self {
+        Self {
+            push_filters_up: true,
+        }
+    }
+}
 
 impl<'a> MirQuery<'a> {
     /// Run a set of rewrite and optimization passes on this [`MirQuery`], and returns the modified
     /// query.
-    pub fn rewrite(mut self) -> ReadySetResult<Self> {
+    pub fn rewrite(mut self, options: RewriteOptions) -> ReadySetResult<Self> {
         pull_keys::pull_view_keys_to_leaf(&mut self)?;
         decorrelate::eliminate_dependent_joins(&mut self)?;
         add_bogokey::add_bogokey_if_necessary(&mut self)?;
-        predicate_pushup::push_filters_up(&mut self)?;
+        if options.push_filters_up {
+            predicate_pushup::push_filters_up(&mut self)?;
+        }
         filters_to_join_keys::convert_filters_to_join_keys(&mut self)?;
+        topk_pushup::push_topk_up(&mut self)?;
         pull_columns::pull_all_required_columns(&mut self)?;
         fuse::fuse_project_nodes(&mut self)?;
         fuse::fuse_filter_nodes(&mut self)?;
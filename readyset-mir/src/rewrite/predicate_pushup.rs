@@ -1,12 +1,12 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 use nom_sql::analysis::{ReferredColumns, ReferredColumnsMut};
 use nom_sql::{Expr, SqlIdentifier};
 use petgraph::Direction;
-use readyset_errors::{invariant_eq, unsupported_err, ReadySetResult};
+use readyset_errors::{internal, invariant_eq, unsupported_err, ReadySetResult};
 use tracing::{instrument, trace};
 
-use crate::node::MirNodeInner;
+use crate::node::{MirNode, MirNodeInner};
 use crate::query::MirQuery;
 use crate::{Column, NodeIndex};
 
@@ -15,6 +15,8 @@ fn commutes_with(conditions: &Expr, inner: &MirNodeInner) -> bool {
         MirNodeInner::Aggregation { group_by, .. }
         | MirNodeInner::Paginate { group_by, .. }
         | MirNodeInner::TopK { group_by, .. }
+        | MirNodeInner::Latest { group_by, .. }
+        | MirNodeInner::FullTextSearch { group_by, .. }
         | MirNodeInner::Distinct { group_by, .. }
         | MirNodeInner::Extremum { group_by, .. } => conditions
             .referred_columns()
@@ -23,6 +25,9 @@ fn commutes_with(conditions: &Expr, inner: &MirNodeInner) -> bool {
         MirNodeInner::Filter { .. }
         | MirNodeInner::Identity
         | MirNodeInner::Join { .. }
+        // Filters can only be pushed into the left-hand side of left joins, which is checked in
+        // `plan_push_filter`
+        | MirNodeInner::LeftJoin { .. }
         | MirNodeInner::JoinAggregates
         | MirNodeInner::DependentJoin { .. }
         | MirNodeInner::ViewKey { .. }
@@ -31,9 +36,7 @@ fn commutes_with(conditions: &Expr, inner: &MirNodeInner) -> bool {
         | MirNodeInner::AliasTable { .. }
         | MirNodeInner::Leaf { .. } => true,
 
-        MirNodeInner::Base { .. }
-        | MirNodeInner::LeftJoin { .. }
-        | MirNodeInner::DependentLeftJoin { .. } => false,
+        MirNodeInner::Base { .. } | MirNodeInner::DependentLeftJoin { .. } => false,
     }
 }
 
@@ -98,6 +101,15 @@ fn plan_push_filter(
             query.get_node(new_parent).unwrap().inner,
             MirNodeInner::AliasTable { .. }
         );
+        // Rows on the left-hand side of a left join which don't match any rows on the right-hand
+        // side are still emitted (with nulls for the right-hand side's columns), so filters can
+        // only be pushed into the left-hand side
+        let left_join_lhs = matches!(
+            query.get_node(new_parent).unwrap().inner,
+            MirNodeInner::LeftJoin { .. }
+        )
+        .then(|| ancestors.first().copied())
+        .flatten();
 
         let mut candidates = Vec::with_capacity(ancestors.len());
         for n in ancestors.into_iter() {
@@ -136,6 +148,13 @@ fn plan_push_filter(
 
         match candidates.as_slice() {
             [] => done!(),
+            [candidate] if left_join_lhs.is_some() && left_join_lhs != Some(*candidate) => {
+                trace!(
+                    ancestor = %candidate.index(),
+                    "Can't push filter into the right-hand side of a left join"
+                );
+                done!()
+            }
             [candidate] => {
                 trace!(ancestor = %candidate.index(), "Considering ancestor");
                 new_child = new_parent;
@@ -192,12 +211,97 @@ fn map_columns_above_alias_table(
     Ok(())
 }
 
+/// If the parent of the filter node at `filter_idx` is a union, replace the filter with a copy of
+/// the filter above each of the union's parents (with the filter's columns mapped to the columns
+/// that union emits from that parent), and return the indices of the new filter nodes.
+fn push_filter_through_union(
+    query: &mut MirQuery<'_>,
+    filter_idx: NodeIndex,
+) -> ReadySetResult<Vec<NodeIndex>> {
+    let ancestors = query.ancestors(filter_idx)?;
+    let [union] = ancestors[..] else {
+        return Ok(vec![]);
+    };
+    let MirNodeInner::Union { emit, .. } = &query.get_node(union).unwrap().inner else {
+        return Ok(vec![]);
+    };
+    let MirNodeInner::Filter { conditions } = &query.get_node(filter_idx).unwrap().inner else {
+        internal!("push_filter_through_union called on a non-filter node");
+    };
+
+    if query
+        .graph
+        .edges_directed(union, Direction::Outgoing)
+        .count()
+        > 1
+    {
+        trace!(
+            union = %union.index(),
+            "Can't push past union with more than one outgoing edge"
+        );
+        return Ok(vec![]);
+    }
+
+    let union_parents = query.ancestors(union)?;
+    if union_parents.len() != emit.len() {
+        // Some of the union's parents aren't in this query
+        return Ok(vec![]);
+    }
+
+    // The union's columns are the columns it emits from its first parent (see
+    // `MirGraph::columns`), so map each column in the filter to the column at the same position
+    // in the columns emitted from each parent
+    let mut parent_conditions = Vec::with_capacity(emit.len());
+    for parent_emit in emit {
+        let mut conditions = conditions.clone();
+        for column in conditions.referred_columns_mut() {
+            let Some(pos) = emit[0].iter().position(|c| c == &*column) else {
+                trace!(?column, "Filter column not emitted by union");
+                return Ok(vec![]);
+            };
+            column.name = parent_emit[pos].name.clone();
+            column.table = parent_emit[pos].table.clone();
+        }
+        parent_conditions.push(conditions);
+    }
+
+    trace!(
+        filter = %filter_idx.index(),
+        union = %union.index(),
+        "Pushing filter through union"
+    );
+    let filter_node = query
+        .remove_node(filter_idx)?
+        .expect("Filter came from query");
+    union_parents
+        .into_iter()
+        .zip(parent_conditions)
+        .enumerate()
+        .map(|(i, (parent, conditions))| {
+            query.splice(
+                parent,
+                union,
+                MirNode::new(
+                    format!("{}_{i}", filter_node.name().display_unquoted()).into(),
+                    MirNodeInner::Filter { conditions },
+                ),
+            )
+        })
+        .collect()
+}
+
 /// Push as many filter nodes as high as possible up the graph, to keep the inputs to expensive
-/// nodes like joins as small as possible
+/// nodes like joins as small as possible.
+///
+/// Filters are pushed past inner joins into whichever side provides all of their columns, into the
+/// left-hand side of left joins, and into every parent of unions.
 #[instrument(level = "trace", skip_all)]
 pub(crate) fn push_filters_up(query: &mut MirQuery<'_>) -> ReadySetResult<()> {
-    for filter_idx in query.topo_nodes() {
-        let node = query.get_node(filter_idx).unwrap();
+    let mut filters = VecDeque::from(query.topo_nodes());
+    while let Some(mut filter_idx) = filters.pop_front() {
+        let Some(node) = query.get_node(filter_idx) else {
+            continue;
+        };
         let MirNodeInner::Filter { conditions } = &node.inner else {
             continue;
         };
@@ -213,8 +317,11 @@ pub(crate) fn push_filters_up(query: &mut MirQuery<'_>) -> ReadySetResult<()> {
                 conditions: new_conditions,
             };
 
-            query.splice(new_parent, new_child, filter_node)?;
+            filter_idx = query.splice(new_parent, new_child, filter_node)?;
         }
+
+        // Any filters we push through a union might be able to be pushed further up the graph
+        filters.extend(push_filter_through_union(query, filter_idx)?);
     }
 
     Ok(())
@@ -426,4 +533,221 @@ mod tests {
             .find_edge(filter, alias_1)
             .expect("Filter should be a direct parent of alias_1");
     }
+
+    fn int_column(name: &str) -> ColumnSpecification {
+        ColumnSpecification {
+            column: nom_sql::Column::from(name),
+            sql_type: SqlType::Int(None),
+            constraints: vec![],
+            comment: None,
+        }
+    }
+
+    /// Build a query that looks like `SELECT t1.a, t1.b, t2.c FROM t1 LEFT JOIN t2 ON t1.a = t2.a
+    /// WHERE <filter_column> = 1`, and return the query name, the graph, and the indices of `t1`,
+    /// `t2`, the join, the filter, and the leaf
+    fn left_join_query(name: &str, filter_column: &str) -> (Relation, MirGraph, [NodeIndex; 5]) {
+        let query_name = Relation::from(name);
+        let mut graph = MirGraph::new();
+
+        let t1 = graph.add_node(MirNode::new(
+            "t1".into(),
+            MirNodeInner::Base {
+                column_specs: vec![int_column("t1.a"), int_column("t1.b")],
+                primary_key: None,
+                unique_keys: Default::default(),
+            },
+        ));
+        graph[t1].add_owner(query_name.clone());
+
+        let t2 = graph.add_node(MirNode::new(
+            "t2".into(),
+            MirNodeInner::Base {
+                column_specs: vec![int_column("t2.a"), int_column("t2.c")],
+                primary_key: None,
+                unique_keys: Default::default(),
+            },
+        ));
+        graph[t2].add_owner(query_name.clone());
+
+        let join = graph.add_node(MirNode::new(
+            "join".into(),
+            MirNodeInner::LeftJoin {
+                on: vec![(Column::new(Some("t1"), "a"), Column::new(Some("t2"), "a"))],
+                project: vec![
+                    Column::new(Some("t1"), "a"),
+                    Column::new(Some("t1"), "b"),
+                    Column::new(Some("t2"), "c"),
+                ],
+            },
+        ));
+        graph[join].add_owner(query_name.clone());
+        graph.add_edge(t1, join, 0);
+        graph.add_edge(t2, join, 1);
+
+        let filter = graph.add_node(MirNode::new(
+            "filter".into(),
+            MirNodeInner::Filter {
+                conditions: Expr::BinaryOp {
+                    lhs: Box::new(Expr::Column(filter_column.into())),
+                    op: BinaryOperator::Equal,
+                    rhs: Box::new(Expr::Literal(1.into())),
+                },
+            },
+        ));
+        graph[filter].add_owner(query_name.clone());
+        graph.add_edge(join, filter, 0);
+
+        let leaf = graph.add_node(MirNode::new(
+            "q".into(),
+            MirNodeInner::leaf(vec![], IndexType::HashMap),
+        ));
+        graph[leaf].add_owner(query_name.clone());
+        graph.add_edge(filter, leaf, 0);
+
+        (query_name, graph, [t1, t2, join, filter, leaf])
+    }
+
+    #[test]
+    fn left_pred_below_left_join() {
+        readyset_tracing::init_test_logging();
+        let (query_name, mut graph, [t1, _t2, join, filter, leaf]) =
+            left_join_query("left_pred_below_left_join", "t1.b");
+        let mut query = MirQuery::new(query_name, leaf, &mut graph);
+
+        push_filters_up(&mut query).unwrap();
+        eprintln!("{}", query.to_graphviz());
+
+        query
+            .get_node(filter)
+            .expect("Filter should still be in graph");
+        query
+            .graph
+            .find_edge(t1, filter)
+            .expect("Filter should be a direct child of t1");
+        query
+            .graph
+            .find_edge(filter, join)
+            .expect("Filter should be a direct parent of join");
+        assert_eq!(
+            query
+                .graph
+                .edge_weight(query.graph.find_edge(filter, join).unwrap()),
+            Some(&0),
+            "Filter should be on the left-hand side of the join"
+        );
+    }
+
+    #[test]
+    fn right_pred_not_below_left_join() {
+        readyset_tracing::init_test_logging();
+        let (query_name, mut graph, [t1, t2, join, filter, leaf]) =
+            left_join_query("right_pred_not_below_left_join", "t2.c");
+        let mut query = MirQuery::new(query_name, leaf, &mut graph);
+
+        push_filters_up(&mut query).unwrap();
+        eprintln!("{}", query.to_graphviz());
+
+        query
+            .graph
+            .find_edge(join, filter)
+            .expect("Filter should still be a direct child of join");
+        query
+            .graph
+            .find_edge(t1, join)
+            .expect("t1 should still be a direct parent of join");
+        query
+            .graph
+            .find_edge(t2, join)
+            .expect("t2 should still be a direct parent of join");
+    }
+
+    #[test]
+    fn pred_through_union() {
+        readyset_tracing::init_test_logging();
+        let query_name = Relation::from("pred_through_union");
+        let mut graph = MirGraph::new();
+
+        let t1 = graph.add_node(MirNode::new(
+            "t1".into(),
+            MirNodeInner::Base {
+                column_specs: vec![int_column("t1.a"), int_column("t1.b")],
+                primary_key: None,
+                unique_keys: Default::default(),
+            },
+        ));
+        graph[t1].add_owner(query_name.clone());
+
+        let t2 = graph.add_node(MirNode::new(
+            "t2".into(),
+            MirNodeInner::Base {
+                column_specs: vec![int_column("t2.c"), int_column("t2.d")],
+                primary_key: None,
+                unique_keys: Default::default(),
+            },
+        ));
+        graph[t2].add_owner(query_name.clone());
+
+        let union = graph.add_node(MirNode::new(
+            "union".into(),
+            MirNodeInner::Union {
+                emit: vec![
+                    vec![Column::new(Some("t1"), "a"), Column::new(Some("t1"), "b")],
+                    vec![Column::new(Some("t2"), "c"), Column::new(Some("t2"), "d")],
+                ],
+                duplicate_mode: dataflow::ops::union::DuplicateMode::UnionAll,
+            },
+        ));
+        graph[union].add_owner(query_name.clone());
+        graph.add_edge(t1, union, 0);
+        graph.add_edge(t2, union, 1);
+
+        let filter = graph.add_node(MirNode::new(
+            "filter".into(),
+            MirNodeInner::Filter {
+                conditions: Expr::BinaryOp {
+                    lhs: Box::new(Expr::Column("t1.b".into())),
+                    op: BinaryOperator::Equal,
+                    rhs: Box::new(Expr::Literal(1.into())),
+                },
+            },
+        ));
+        graph[filter].add_owner(query_name.clone());
+        graph.add_edge(union, filter, 0);
+
+        let leaf = graph.add_node(MirNode::new(
+            "q".into(),
+            MirNodeInner::leaf(vec![], IndexType::HashMap),
+        ));
+        graph[leaf].add_owner(query_name.clone());
+        graph.add_edge(filter, leaf, 0);
+
+        let mut query = MirQuery::new(query_name, leaf, &mut graph);
+
+        push_filters_up(&mut query).unwrap();
+        eprintln!("{}", query.to_graphviz());
+
+        query
+            .graph
+            .find_edge(union, leaf)
+            .expect("Union should be a direct parent of the leaf");
+        for (parent, column) in [(t1, "t1.b"), (t2, "t2.d")] {
+            let parent_filter = query
+                .graph
+                .neighbors_directed(union, Direction::Incoming)
+                .find(|&n| query.graph.find_edge(parent, n).is_some())
+                .expect("Union should have a parent which is a child of the base table");
+            let MirNodeInner::Filter { conditions } = &query.graph[parent_filter].inner else {
+                panic!("Parent of union should be a filter");
+            };
+            assert_eq!(
+                *conditions,
+                Expr::BinaryOp {
+                    lhs: Box::new(Expr::Column(column.into())),
+                    op: BinaryOperator::Equal,
+                    rhs: Box::new(Expr::Literal(1.into())),
+                }
+            );
+        }
+    }
 }
@@ -0,0 +1,350 @@
+use itertools::Itertools;
+use petgraph::Direction;
+use readyset_errors::ReadySetResult;
+use tracing::{instrument, trace};
+
+use crate::node::MirNodeInner;
+use crate::query::MirQuery;
+use crate::{Column, NodeIndex};
+
+/// Returns true if the node at `node` is known to never emit more than one row for any given
+/// value of `columns`, because those columns cover a primary key or unique key of the base table
+/// the node reads from.
+///
+/// Only nodes which preserve the uniqueness of their parent's rows (and don't rename columns) are
+/// looked through on the way up to the base table.
+fn columns_are_unique(query: &MirQuery, node: NodeIndex, columns: &[&Column]) -> bool {
+    match &query.graph[node].inner {
+        MirNodeInner::Base {
+            primary_key,
+            unique_keys,
+            ..
+        } => primary_key.iter().chain(unique_keys.iter()).any(|key| {
+            key.iter()
+                .all(|key_col| columns.iter().any(|c| c.name == key_col.name))
+        }),
+        MirNodeInner::AliasTable { .. } | MirNodeInner::Filter { .. } | MirNodeInner::Identity => {
+            match query
+                .graph
+                .neighbors_directed(node, Direction::Incoming)
+                .exactly_one()
+            {
+                Ok(parent) => columns_are_unique(query, parent, columns),
+                Err(_) => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Determine whether the TopK node at `topk_idx` can be moved above its parent join, returning
+/// the new parent for the TopK node and the join it should be spliced above if so.
+fn plan_push_topk(
+    query: &MirQuery,
+    topk_idx: NodeIndex,
+) -> ReadySetResult<Option<(NodeIndex, NodeIndex)>> {
+    let MirNodeInner::TopK {
+        order, group_by, ..
+    } = &query.get_node(topk_idx).unwrap().inner
+    else {
+        return Ok(None);
+    };
+
+    let ancestors = query.ancestors(topk_idx)?;
+    let [join] = ancestors[..] else {
+        return Ok(None);
+    };
+
+    // Only left joins are considered, since an inner join drops rows from the left side that
+    // don't match any rows on the right side - if that happens after the limit has already been
+    // applied, we'd emit fewer rows than the limit even though more rows would have matched.
+    let MirNodeInner::LeftJoin { on, .. } = &query.get_node(join).unwrap().inner else {
+        return Ok(None);
+    };
+
+    if query
+        .graph
+        .edges_directed(join, Direction::Outgoing)
+        .count()
+        > 1
+    {
+        trace!(join = %join.index(), "Can't push TopK past join with more than one outgoing edge");
+        return Ok(None);
+    }
+
+    let join_ancestors = query.ancestors(join)?;
+    let [left, right] = join_ancestors[..] else {
+        return Ok(None);
+    };
+
+    if !order
+        .iter()
+        .flatten()
+        .map(|(col, _)| col)
+        .chain(group_by)
+        .all(|col| query.graph.provides_column(left, col))
+    {
+        trace!(
+            topk = %topk_idx.index(),
+            "TopK depends on columns not provided by the left side of the join"
+        );
+        return Ok(None);
+    }
+
+    // The join has to be N:1 - every row on the left side of the join must be emitted exactly
+    // once, in the same order, for the TopK to commute with it
+    let right_join_columns = on.iter().map(|(_, r)| r).collect::<Vec<_>>();
+    if !columns_are_unique(query, right, &right_join_columns) {
+        trace!(
+            join = %join.index(),
+            "Join columns on the right side of the join are not known to be unique"
+        );
+        return Ok(None);
+    }
+
+    trace!(
+        topk = %topk_idx.index(),
+        new_parent = %left.index(),
+        "Pushing TopK"
+    );
+    Ok(Some((left, join)))
+}
+
+/// Push TopK nodes above left joins when both the ordering and the grouping of the TopK only
+/// depend on columns from the left side of the join, and the join is N:1 (the join columns on the
+/// right side cover a primary or unique key). This limits the rows on the left side of the join
+/// before they're joined, which can significantly reduce the amount of state we need to keep
+/// around for queries like "the latest N rows per entity, joined with a dimension table".
+#[instrument(level = "trace", skip_all)]
+pub(crate) fn push_topk_up(query: &mut MirQuery<'_>) -> ReadySetResult<()> {
+    for topk_idx in query.topo_nodes() {
+        if let Some((new_parent, join)) = plan_push_topk(query, topk_idx)? {
+            let topk_node = query.remove_node(topk_idx)?.expect("TopK came from query");
+
+            query.splice(new_parent, join, topk_node)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use common::IndexType;
+    use dataflow::ops::topk::TopKMode;
+    use nom_sql::{ColumnSpecification, OrderType, Relation, SqlType};
+
+    use super::*;
+    use crate::graph::MirGraph;
+    use crate::node::MirNode;
+    use crate::visualize::GraphViz;
+
+    fn column_spec(name: &str) -> ColumnSpecification {
+        ColumnSpecification {
+            column: nom_sql::Column::from(name),
+            sql_type: SqlType::Int(None),
+            constraints: vec![],
+            comment: None,
+        }
+    }
+
+    struct TopKQuery {
+        graph: MirGraph,
+        query_name: Relation,
+        t1: NodeIndex,
+        join: NodeIndex,
+        topk: NodeIndex,
+        leaf: NodeIndex,
+    }
+
+    /// Build a query that looks like:
+    ///
+    /// ```sql
+    /// SELECT t1.a, t1.b, t2.d FROM t1 [LEFT] JOIN t2 ON t1.c = t2.c
+    /// WHERE t1.a = ? ORDER BY <order_col> DESC LIMIT 3
+    /// ```
+    ///
+    /// where `t2.c` is the primary key of `t2` if `right_unique` is true
+    fn build_query(
+        query_name: &str,
+        left_join: bool,
+        order_col: Column,
+        right_unique: bool,
+    ) -> TopKQuery {
+        let query_name = Relation::from(query_name);
+        let mut graph = MirGraph::new();
+
+        let t1 = graph.add_node(MirNode::new(
+            "t1".into(),
+            MirNodeInner::Base {
+                column_specs: vec![
+                    column_spec("t1.a"),
+                    column_spec("t1.b"),
+                    column_spec("t1.c"),
+                ],
+                primary_key: Some([Column::from("b")].into()),
+                unique_keys: Default::default(),
+            },
+        ));
+        graph[t1].add_owner(query_name.clone());
+
+        let t2 = graph.add_node(MirNode::new(
+            "t2".into(),
+            MirNodeInner::Base {
+                column_specs: vec![column_spec("t2.c"), column_spec("t2.d")],
+                primary_key: right_unique.then(|| [Column::new(Some("t2"), "c")].into()),
+                unique_keys: Default::default(),
+            },
+        ));
+        graph[t2].add_owner(query_name.clone());
+
+        let on = vec![(Column::new(Some("t1"), "c"), Column::new(Some("t2"), "c"))];
+        let project = vec![
+            Column::new(Some("t1"), "a"),
+            Column::new(Some("t1"), "b"),
+            Column::new(Some("t1"), "c"),
+            Column::new(Some("t2"), "d"),
+        ];
+        let join = graph.add_node(MirNode::new(
+            "join".into(),
+            if left_join {
+                MirNodeInner::LeftJoin { on, project }
+            } else {
+                MirNodeInner::Join { on, project }
+            },
+        ));
+        graph[join].add_owner(query_name.clone());
+        graph.add_edge(t1, join, 0);
+        graph.add_edge(t2, join, 1);
+
+        let topk = graph.add_node(MirNode::new(
+            "topk".into(),
+            MirNodeInner::TopK {
+                order: Some(vec![(order_col, OrderType::OrderDescending)]),
+                group_by: vec![Column::new(Some("t1"), "a")],
+                limit: 3,
+                mode: TopKMode::RowNumber,
+            },
+        ));
+        graph[topk].add_owner(query_name.clone());
+        graph.add_edge(join, topk, 0);
+
+        let leaf = graph.add_node(MirNode::new(
+            "q".into(),
+            MirNodeInner::leaf(vec![], IndexType::HashMap),
+        ));
+        graph[leaf].add_owner(query_name.clone());
+        graph.add_edge(topk, leaf, 0);
+
+        TopKQuery {
+            graph,
+            query_name,
+            t1,
+            join,
+            topk,
+            leaf,
+        }
+    }
+
+    #[test]
+    fn topk_above_n_to_1_left_join() {
+        readyset_tracing::init_test_logging();
+        let TopKQuery {
+            mut graph,
+            query_name,
+            t1,
+            join,
+            leaf,
+            ..
+        } = build_query(
+            "topk_above_n_to_1_left_join",
+            true,
+            Column::new(Some("t1"), "b"),
+            true,
+        );
+        let mut query = MirQuery::new(query_name, leaf, &mut graph);
+
+        push_topk_up(&mut query).unwrap();
+        eprintln!("{}", query.to_graphviz());
+
+        let topk = query
+            .graph
+            .node_indices()
+            .find(|&n| matches!(query.graph[n].inner, MirNodeInner::TopK { .. }))
+            .expect("TopK should still be in graph");
+        query
+            .graph
+            .find_edge(t1, topk)
+            .expect("TopK should be a direct child of t1");
+        query
+            .graph
+            .find_edge(topk, join)
+            .expect("TopK should be a direct parent of join");
+        query
+            .graph
+            .find_edge(join, leaf)
+            .expect("Join should be a direct parent of the leaf");
+        assert_eq!(
+            query
+                .graph
+                .edge_weight(query.graph.find_edge(topk, join).unwrap()),
+            Some(&0),
+            "TopK should be on the left side of the join"
+        );
+    }
+
+    fn assert_not_pushed(query_name: &str, left_join: bool, order_col: Column, right_unique: bool) {
+        readyset_tracing::init_test_logging();
+        let TopKQuery {
+            mut graph,
+            query_name,
+            t1,
+            join,
+            topk,
+            leaf,
+        } = build_query(query_name, left_join, order_col, right_unique);
+        let mut query = MirQuery::new(query_name, leaf, &mut graph);
+
+        push_topk_up(&mut query).unwrap();
+        eprintln!("{}", query.to_graphviz());
+
+        query
+            .graph
+            .find_edge(join, topk)
+            .expect("TopK should still be a direct child of join");
+        query
+            .graph
+            .find_edge(t1, join)
+            .expect("t1 should still be a direct parent of join");
+    }
+
+    #[test]
+    fn topk_not_above_inner_join() {
+        assert_not_pushed(
+            "topk_not_above_inner_join",
+            false,
+            Column::new(Some("t1"), "b"),
+            true,
+        );
+    }
+
+    #[test]
+    fn topk_ordered_by_right_side_not_pushed() {
+        assert_not_pushed(
+            "topk_ordered_by_right_side_not_pushed",
+            true,
+            Column::new(Some("t2"), "d"),
+            true,
+        );
+    }
+
+    #[test]
+    fn topk_not_above_n_to_m_join() {
+        assert_not_pushed(
+            "topk_not_above_n_to_m_join",
+            true,
+            Column::new(Some("t1"), "b"),
+            false,
+        );
+    }
+}
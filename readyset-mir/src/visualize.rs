@@ -161,9 +161,12 @@ impl GraphViz for MirNodeInner {
                     AggregationKind::Count { .. } => format!("\\|*\\|({})", on),
                     AggregationKind::Sum => format!("𝛴({})", on),
                     AggregationKind::Avg => format!("AVG({})", on),
-                    AggregationKind::GroupConcat { separator: s } => {
+                    AggregationKind::GroupConcat { separator: s, .. } => {
                         format!("\\|\\|({}, \\\"{}\\\")", on, s)
                     }
+                    AggregationKind::ApproxCountDistinct { precision } => {
+                        format!("≈\\|{}\\|({})", on, precision)
+                    }
                 };
                 let group_cols = group_by.iter().join(", ");
                 write!(f, "{} | γ: {}", op_string, group_cols)
@@ -332,6 +335,7 @@ impl GraphViz for MirNodeInner {
             MirNodeInner::TopK {
                 ref order,
                 ref limit,
+                mode,
                 ..
             } => {
                 let order = order
@@ -343,7 +347,37 @@ impl GraphViz for MirNodeInner {
                             .join(", ")
                     })
                     .unwrap_or_else(|| "".into());
-                write!(f, "TopK [k: {}; {}]", limit, order)
+                write!(f, "TopK [k: {}; {}; {}]", limit, mode, order)
+            }
+            MirNodeInner::Latest {
+                ref group_by,
+                ref over,
+            } => {
+                write!(
+                    f,
+                    "⧖ | γ: {}; over: {}",
+                    group_by.iter().map(|c| c.name.as_str()).join(", "),
+                    over.name
+                )
+            }
+            MirNodeInner::FullTextSearch {
+                ref columns,
+                ref terms,
+                match_all,
+                ref limit,
+                ..
+            } => {
+                write!(
+                    f,
+                    "FullTextSearch [{} ⊇ {} {}",
+                    columns.iter().map(|c| c.name.as_str()).join(", "),
+                    if *match_all { "all" } else { "any" },
+                    terms.iter().join(" "),
+                )?;
+                if let Some(limit) = limit {
+                    write!(f, "; k: {}", limit)?;
+                }
+                write!(f, "]")
             }
             MirNodeInner::Union {
                 ref emit,
@@ -0,0 +1,63 @@
+//! This module contains a [`criterion`] benchmark which compares inserting rows into a table via
+//! the MySQL adapter one row at a time against inserting the same number of rows with a single
+//! multi-row `INSERT` statement, which is written to the base table as one batch.
+//!
+//! To run these benchmarks:
+//!
+//! ```notrust
+//! $ cargo criterion -p readyset-mysql --bench inserts
+//! ```
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use mysql_async::prelude::Queryable;
+use readyset_client_test_helpers::mysql_helpers::MySQLAdapter;
+use readyset_client_test_helpers::{sleep, TestBuilder};
+use tokio::runtime::Runtime;
+
+const ROWS: usize = 1000;
+
+fn benchmark_inserts(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let (opts, _handle, shutdown_tx) = rt.block_on(TestBuilder::default().build::<MySQLAdapter>());
+    let mut conn = rt.block_on(mysql_async::Conn::new(opts)).unwrap();
+    rt.block_on(async {
+        conn.query_drop("CREATE TABLE t (x int)").await.unwrap();
+        sleep().await;
+    });
+
+    let mut group = c.benchmark_group("insert");
+    group.throughput(Throughput::Elements(ROWS as u64));
+
+    group.bench_function("row by row", |b| {
+        b.iter(|| {
+            rt.block_on(async {
+                for x in 0..ROWS {
+                    conn.exec_drop("INSERT INTO t (x) VALUES (?)", (x,))
+                        .await
+                        .unwrap();
+                }
+            })
+        })
+    });
+
+    let query = format!(
+        "INSERT INTO t (x) VALUES {}",
+        (0..ROWS)
+            .map(|x| format!("({x})"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    group.bench_function("batched", |b| {
+        b.iter(|| rt.block_on(conn.query_drop(&query)).unwrap())
+    });
+
+    group.finish();
+
+    rt.block_on(async {
+        drop(conn);
+        shutdown_tx.shutdown().await;
+    });
+}
+
+criterion_group!(benches, benchmark_inserts);
+criterion_main!(benches);
@@ -22,12 +22,12 @@ use readyset_adapter::backend::{
 };
 use readyset_adapter::upstream_database::LazyUpstream;
 use readyset_adapter_types::DeallocateId;
-use readyset_data::{DfType, DfValue, DfValueKind};
+use readyset_data::{DfType, DfValue, DfValueKind, Timezone};
 use readyset_errors::{internal, ReadySetError};
 use readyset_util::redacted::Sensitive;
 use streaming_iterator::StreamingIterator;
 use tokio::io::{self, AsyncWrite};
-use tracing::{error, info, trace};
+use tracing::{debug, error, info, trace};
 use upstream::StatementMeta;
 
 use crate::constants::DEFAULT_CHARACTER_SET;
@@ -59,6 +59,7 @@ async fn write_column<W: AsyncWrite + Unpin>(
     c: &DfValue,
     cs: &mysql_srv::Column,
     ty: &DfType,
+    timezone: Timezone,
 ) -> Result<(), Error> {
     let conv_error = || ReadySetError::DfValueConversionError {
         src_type: format!("{:?}", DfValueKind::from(c)),
@@ -89,20 +90,8 @@ async fn write_column<W: AsyncWrite + Unpin>(
                 rw.write_col(i as isize)
             }
         }
-        DfValue::Text(ref t) => {
-            if ty.is_binary() {
-                rw.write_col(BinaryDisplay(c.as_bytes()?).to_string())
-            } else {
-                rw.write_col(t.as_str())
-            }
-        }
-        DfValue::TinyText(ref t) => {
-            if ty.is_binary() {
-                rw.write_col(BinaryDisplay(c.as_bytes()?).to_string())
-            } else {
-                rw.write_col(t.as_str())
-            }
-        }
+        DfValue::Text(ref t) => rw.write_col(t.as_str()),
+        DfValue::TinyText(ref t) => rw.write_col(t.as_str()),
         ref dt @ (DfValue::Float(..) | DfValue::Double(..)) => match cs.coltype {
             mysql_srv::ColumnType::MYSQL_TYPE_DECIMAL
             | mysql_srv::ColumnType::MYSQL_TYPE_NEWDECIMAL => {
@@ -141,13 +130,21 @@ async fn write_column<W: AsyncWrite + Unpin>(
             | mysql_srv::ColumnType::MYSQL_TYPE_DATETIME2
             | mysql_srv::ColumnType::MYSQL_TYPE_TIMESTAMP
             | mysql_srv::ColumnType::MYSQL_TYPE_TIMESTAMP2 => {
-                rw.write_col(ts.to_chrono().naive_local())
+                let datetime = ts.to_chrono().naive_local();
+                // `TIMESTAMP` values are stored in UTC, but are returned in the session timezone
+                if matches!(ty, DfType::Timestamp { .. }) && !timezone.is_utc() {
+                    rw.write_col(timezone.from_utc_datetime(&datetime).naive_local())
+                } else {
+                    rw.write_col(datetime)
+                }
             }
             ColumnType::MYSQL_TYPE_DATE => rw.write_col(ts.to_chrono().naive_local().date()),
             _ => return Err(conv_error())?,
         },
         DfValue::Time(ref t) => rw.write_col(t),
-        DfValue::ByteArray(ref bytes) => rw.write_col(BinaryDisplay(bytes.as_ref()).to_string()),
+        // Binary values are sent to clients as their raw bytes, the same as MySQL does
+        DfValue::ByteArray(ref bytes) => rw.write_col(bytes.as_slice()),
+        DfValue::Uuid(ref u) => rw.write_col(BinaryDisplay(u.as_bytes()).to_string()),
         // These types are PostgreSQL specific
         DfValue::Array(_) => {
             internal!("Cannot write MySQL column: MySQL does not support arrays")
@@ -155,6 +152,9 @@ async fn write_column<W: AsyncWrite + Unpin>(
         DfValue::BitVector(_) => {
             internal!("Cannot write MySQL column: MySQL does not support bit vectors")
         }
+        DfValue::Interval(_) => {
+            internal!("Cannot write MySQL column: MySQL does not support intervals")
+        }
         DfValue::PassThrough(_) => {
             internal!("Cannot write MySQL column: PassThrough types aren't supported for MySQL")
         }
@@ -362,6 +362,7 @@ macro_rules! handle_error {
 async fn handle_readyset_result<'a, W>(
     result: noria_connector::QueryResult<'a>,
     writer: QueryResultWriter<'_, W>,
+    timezone: Timezone,
 ) -> io::Result<()>
 where
     W: AsyncWrite + Unpin,
@@ -397,7 +398,7 @@ where
                         .map(|cs| cs.column_type.clone())
                         .unwrap_or_default();
 
-                    if let Err(e) = write_column(&mut rw, val, c, &ty).await {
+                    if let Err(e) = write_column(&mut rw, val, c, &ty, timezone).await {
                         return handle_column_write_err(e, rw).await;
                     }
                 }
@@ -462,12 +463,13 @@ where
 async fn handle_execute_result<'a, W>(
     result: Result<QueryResult<'a, LazyUpstream<MySqlUpstream>>, Error>,
     writer: QueryResultWriter<'_, W>,
+    timezone: Timezone,
 ) -> io::Result<()>
 where
     W: AsyncWrite + Unpin,
 {
     match result {
-        Ok(QueryResult::Noria(result)) => handle_readyset_result(result, writer).await,
+        Ok(QueryResult::Noria(result)) => handle_readyset_result(result, writer, timezone).await,
         Ok(QueryResult::Upstream(result)) => handle_upstream_result(result, writer).await,
         Ok(QueryResult::UpstreamBufferedInMemory(..)) => handle_error!(
             Error::ReadySet(readyset_errors::unsupported_err!(
@@ -488,13 +490,14 @@ where
 async fn handle_query_result<'a, W>(
     result: Result<QueryResult<'a, LazyUpstream<MySqlUpstream>>, Error>,
     writer: QueryResultWriter<'_, W>,
+    timezone: Timezone,
 ) -> QueryResultsResponse
 where
     W: AsyncWrite + Unpin,
 {
     match result {
         Ok(QueryResult::Parser(command)) => QueryResultsResponse::Command(command),
-        res => QueryResultsResponse::IoResult(handle_execute_result(res, writer).await),
+        res => QueryResultsResponse::IoResult(handle_execute_result(res, writer, timezone).await),
     }
 }
 
@@ -633,6 +636,7 @@ where
             info!(target: "client_statement", "Execute: {{id: {id}, params: {:?}}}", value_params)
         }
 
+        let timezone = self.noria.timezone();
         match self.execute(id, &value_params, ()).await {
             Ok(QueryResult::Noria(noria_connector::QueryResult::Select { mut rows, schema })) => {
                 let CachedSchema {
@@ -667,7 +671,7 @@ where
                 while let Some(row) = rows.next() {
                     for (c, ty, val) in izip!(mysql_schema.iter(), column_types.iter(), row.iter())
                     {
-                        if let Err(e) = write_column(&mut rw, val, c, ty).await {
+                        if let Err(e) = write_column(&mut rw, val, c, ty, timezone).await {
                             return handle_column_write_err(e, rw).await;
                         };
                     }
@@ -675,34 +679,109 @@ where
                 }
                 rw.finish().await
             }
-            execute_result => handle_execute_result(execute_result, results).await,
+            execute_result => handle_execute_result(execute_result, results, timezone).await,
         }
     }
 
-    async fn on_init(&mut self, database: &str, w: Option<InitWriter<'_, W>>) -> io::Result<()> {
+    async fn on_bulk_execute(
+        &mut self,
+        id: u32,
+        params: mysql_srv::BulkParamParser<'_>,
+        results: QueryResultWriter<'_, W>,
+    ) -> io::Result<()> {
+        let params_result = params.rows().map_err(Error::from).and_then(|rows| {
+            rows.into_iter()
+                .map(|row| {
+                    row.into_iter()
+                        .map(|pval| mysql_value_to_dataflow_value(pval.value).map_err(Error::from))
+                        .collect::<Result<Vec<DfValue>, Error>>()
+                })
+                .collect::<Result<Vec<_>, Error>>()
+        });
+
+        let value_params = match params_result {
+            Ok(r) => r,
+            Err(e) => {
+                error!(err = %e, "encountered error parsing bulk execute params");
+                return results
+                    .error(e.error_kind(), e.to_string().as_bytes())
+                    .await;
+            }
+        };
+
         if self.enable_statement_logging {
-            info!(target: "client_statement", "database: {database}");
+            info!(target: "client_statement", "Bulk execute: {{id: {id}, params: {:?}}}", value_params)
         }
-        match self.set_database(database).await {
-            Ok(()) => {
-                if let Some(w) = w {
-                    w.ok().await
-                } else {
-                    Ok(())
+
+        // Inserts into ReadySet are written to the table as a single batch
+        let timezone = self.noria.timezone();
+        match self.execute_insert_batch(id, &value_params).await {
+            Ok(None) => {}
+            Ok(Some(result)) => return handle_execute_result(Ok(result), results, timezone).await,
+            Err(e) => return handle_error!(e, results),
+        }
+
+        // Otherwise, execute the statement once for each set of parameters, reporting the total
+        // number of affected rows
+        let mut num_rows_affected = 0;
+        let mut first_inserted_id = 0;
+        let mut status_flags = None;
+        for params in &value_params {
+            let (rows, inserted_id) = match self.execute(id, params, ()).await {
+                Ok(QueryResult::Noria(noria_connector::QueryResult::Insert {
+                    num_rows_inserted,
+                    first_inserted_id,
+                })) => (num_rows_inserted, first_inserted_id),
+                Ok(QueryResult::Noria(noria_connector::QueryResult::Update {
+                    num_rows_updated,
+                    last_inserted_id,
+                })) => (num_rows_updated, last_inserted_id),
+                Ok(QueryResult::Noria(noria_connector::QueryResult::Delete {
+                    num_rows_deleted,
+                })) => (num_rows_deleted, 0),
+                Ok(QueryResult::Upstream(upstream::QueryResult::WriteResult {
+                    num_rows_affected,
+                    last_inserted_id,
+                    status_flags: flags,
+                })) => {
+                    status_flags = Some(flags);
+                    (num_rows_affected, last_inserted_id)
                 }
-            }
-            Err(e) => {
-                if let Some(w) = w {
-                    w.error(
-                        mysql_srv::ErrorKind::ER_UNKNOWN_ERROR,
-                        e.to_string().as_bytes(),
-                    )
-                    .await
-                } else {
-                    Ok(())
+                Ok(_) => {
+                    return results
+                        .error(
+                            mysql_srv::ErrorKind::ER_NOT_SUPPORTED_YET,
+                            "COM_STMT_BULK_EXECUTE is only supported for writes".as_bytes(),
+                        )
+                        .await
                 }
+                Err(e) => return handle_error!(e, results),
+            };
+            num_rows_affected += rows;
+            if first_inserted_id == 0 {
+                first_inserted_id = inserted_id;
             }
         }
+        results
+            .completed(num_rows_affected, first_inserted_id, status_flags)
+            .await
+    }
+
+    async fn on_init(&mut self, database: &str, w: Option<InitWriter<'_, W>>) -> io::Result<()> {
+        if self.enable_statement_logging {
+            info!(target: "client_statement", "database: {database}");
+        }
+        match (self.set_database(database).await, w) {
+            (Ok(()), Some(w)) => w.ok().await,
+            // Pass on errors from upstream (such as `ER_BAD_DB_ERROR` for a database that doesn't
+            // exist) to the client as-is
+            (Err(e), Some(w)) => handle_error!(e, w),
+            (_, None) => Ok(()),
+        }
+    }
+
+    fn on_connect_attributes(&mut self, attributes: Vec<(String, String)>) {
+        debug!(?attributes, "Received connection attributes");
     }
 
     async fn on_change_user(
@@ -740,8 +819,9 @@ where
             info!(target: "client_statement", "Query: {query}");
         }
 
+        let timezone = self.noria.timezone();
         let query_result = self.query(query).await;
-        return handle_query_result(query_result, results).await;
+        return handle_query_result(query_result, results, timezone).await;
     }
 
     fn password_for_username(&self, username: &str) -> Option<Vec<u8>> {
@@ -1,6 +1,6 @@
 use std::io;
 
-use mysql_srv::MsqlSrvError;
+use mysql_srv::{ErrorKind, MsqlSrvError};
 use readyset_adapter::upstream_database::IsFatalError;
 use readyset_errors::ReadySetError;
 use thiserror::Error;
@@ -20,34 +20,55 @@ pub enum Error {
     Io(#[from] io::Error),
 }
 
+/// Returns the MySQL error code that most closely corresponds to the given error on its own, if
+/// there is one
+fn readyset_error_kind(error: &ReadySetError) -> Option<ErrorKind> {
+    use ReadySetError::*;
+    Some(match error {
+        UnparseableQuery { .. } => ErrorKind::ER_PARSE_ERROR,
+        TableNotFound { .. }
+        | TableNotReplicated { .. }
+        | ViewNotFound(_)
+        | ViewNotFoundForQuery { .. }
+        | ViewNotFoundInWorkers { .. } => ErrorKind::ER_NO_SUCH_TABLE,
+        ViewAlreadyExists(_) => ErrorKind::ER_TABLE_EXISTS_ERROR,
+        NoSuchColumn(_) | NonExistentColumn { .. } => ErrorKind::ER_BAD_FIELD_ERROR,
+        NonNullable { .. } => ErrorKind::ER_BAD_NULL_ERROR,
+        ExprNotInGroupBy { .. } => ErrorKind::ER_WRONG_FIELD_WITH_GROUP,
+        WrongColumnCount(..) => ErrorKind::ER_WRONG_VALUE_COUNT,
+        DfValueConversionError { .. } => ErrorKind::ER_TRUNCATED_WRONG_VALUE,
+        Unsupported(_) | UnsupportedPlaceholders { .. } => ErrorKind::ER_NOT_SUPPORTED_YET,
+        PreparedStatementMissing { .. } => ErrorKind::ER_UNKNOWN_STMT_HANDLER,
+        UpqueryTimeout | ViewUnavailable(_) => ErrorKind::ER_QUERY_INTERRUPTED,
+        Overloaded { .. } | ViewThrottled { .. } | CircuitOpen { .. } => {
+            ErrorKind::ER_TOO_MANY_CONCURRENT_TRXS
+        }
+        ServerShuttingDown => ErrorKind::ER_SERVER_SHUTDOWN,
+        Internal(_) => ErrorKind::ER_INTERNAL_ERROR,
+        _ => return None,
+    })
+}
+
 impl Error {
     /// Transforms each error to the closest mysql error.
     /// Sometimes, there is not a good one and UNKNOWN is used.
-    pub fn error_kind(&self) -> mysql_srv::ErrorKind {
-        // TODO(peter): Implement the rest of this error translation logic.
-        /*
-        match self {
-            MySqlError(_) => ErrorKind::ER_UNKNOWN_ERROR,
-            MySqlAsyncError(_) => ErrorKind::ER_UNKNOWN_ERROR,
-            NoriaReadError(_) => ErrorKind::ER_ERROR_ON_READ,
-            NoriaWriteError(_) => ErrorKind::ER_ERROR_ON_WRITE,
-            NoriaRecipeError(_) => ErrorKind::ER_UNKNOWN_ERROR,
-            ParseError(_) => ErrorKind::ER_PARSE_ERROR,
-            IOError(_) => ErrorKind::ER_IO_WRITE_ERROR,
-            UnimplementedError(_) => ErrorKind::ER_NOT_SUPPORTED_YET,
-            UnsupportedError(_) => ErrorKind::ER_NOT_SUPPORTED_YET,
-            Internal(_) => ErrorKind::ER_INTERNAL_ERROR,
-            MissingPreparedStatement => ErrorKind::ER_NEED_REPREPARE,
-        }
-         */
+    ///
+    /// Errors returned by ReadySet are mapped according to the outermost of their causes which
+    /// has a corresponding MySQL error code, so that eg a missing table is reported as such even
+    /// if the error was returned by a remote request.
+    pub fn error_kind(&self) -> ErrorKind {
         match self {
+            Self::ReadySet(e) => e
+                .causes()
+                .find_map(readyset_error_kind)
+                .unwrap_or(ErrorKind::ER_UNKNOWN_ERROR),
             Self::MySql(mysql_async::Error::Server(e)) => e.code.into(),
             Self::MySql(_) => {
                 // TODO(peter): We need to translate these to appropriate
                 // mysql error codes. Currently mysql_async is only used by fallback.
-                mysql_srv::ErrorKind::ER_UNKNOWN_ERROR
+                ErrorKind::ER_UNKNOWN_ERROR
             }
-            _ => mysql_srv::ErrorKind::ER_UNKNOWN_ERROR,
+            _ => ErrorKind::ER_UNKNOWN_ERROR,
         }
     }
 }
@@ -57,3 +78,39 @@ impl IsFatalError for Error {
         matches!(self, Self::MySql(e) if e.is_fatal())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn readyset_error_kinds() {
+        assert_eq!(
+            Error::from(ReadySetError::TableNotFound {
+                name: "t".into(),
+                schema: None,
+            })
+            .error_kind(),
+            ErrorKind::ER_NO_SUCH_TABLE
+        );
+        assert_eq!(
+            Error::from(ReadySetError::Overloaded {
+                retry_after: Duration::from_millis(10)
+            })
+            .error_kind(),
+            ErrorKind::ER_TOO_MANY_CONCURRENT_TRXS
+        );
+        assert_eq!(
+            Error::from(ReadySetError::EmptyKey).error_kind(),
+            ErrorKind::ER_UNKNOWN_ERROR
+        );
+    }
+
+    #[test]
+    fn wrapped_readyset_error_kind() {
+        let error = ReadySetError::ViewNotFound("q".into()).context("while looking up view");
+        assert_eq!(Error::from(error).error_kind(), ErrorKind::ER_NO_SUCH_TABLE);
+    }
+}
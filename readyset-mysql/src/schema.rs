@@ -49,7 +49,8 @@ pub(crate) fn convert_column(col: &ColumnSchema) -> ReadySetResult<mysql_srv::Co
         DfType::TimestampTz { .. } => {
             unsupported!("MySQL does not support the timestamp with time zone type")
         }
-        DfType::Binary(_) => {
+        // UUIDs are stored for `BINARY(16)` columns which opt in to it
+        DfType::Binary(_) | DfType::Uuid => {
             // TODO(aspen): I don't know if this is right
             colflags |= mysql_srv::ColumnFlags::BINARY_FLAG;
             MYSQL_TYPE_STRING
@@ -69,7 +70,7 @@ pub(crate) fn convert_column(col: &ColumnSchema) -> ReadySetResult<mysql_srv::Co
         DfType::Numeric { .. } => MYSQL_TYPE_DECIMAL,
         DfType::MacAddr => unsupported!("MySQL does not support the MACADDR type"),
         DfType::Inet => unsupported!("MySQL does not support the INET type"),
-        DfType::Uuid => unsupported!("MySQL does not support the UUID type"),
+        DfType::Interval => unsupported!("MySQL does not support the INTERVAL type"),
         DfType::Jsonb => unsupported!("MySQL does not support the JSONB type"),
         DfType::Bit(size) => {
             if size <= 64 {
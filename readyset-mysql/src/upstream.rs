@@ -7,7 +7,7 @@ use futures_util::Stream;
 use mysql_async::consts::{CapabilityFlags, StatusFlags};
 use mysql_async::prelude::Queryable;
 use mysql_async::{
-    ChangeUserOpts, Column, Conn, Opts, OptsBuilder, ResultSetStream, Row, SslOpts, TxOpts,
+    ChangeUserOpts, Column, Conn, Opts, OptsBuilder, ResultSetStream, Row, Transaction, TxOpts,
     UrlError,
 };
 use nom_sql::{SqlIdentifier, StartTransactionStatement};
@@ -169,8 +169,7 @@ impl MySqlUpstream {
         let mut opts =
             Opts::from_url(url).map_err(|e: UrlError| Error::MySql(mysql_async::Error::Url(e)))?;
 
-        if let Some(cert_path) = upstream_config.ssl_root_cert.clone() {
-            let ssl_opts = SslOpts::default().with_root_certs(vec![cert_path.into()]);
+        if let Some(ssl_opts) = upstream_config.mysql_ssl_opts() {
             opts = OptsBuilder::from_opts(opts).ssl_opts(ssl_opts).into();
         }
 
@@ -208,6 +207,30 @@ impl MySqlUpstream {
         let prepared_statements = HashMap::new();
         Ok((conn, prepared_statements))
     }
+
+    /// Commit the given transaction, which has just run a single write, returning the result of the
+    /// write along with the GTID of the transaction
+    async fn commit_ryw_write<'a>(
+        transaction: Transaction<'_>,
+    ) -> Result<(QueryResult<'a>, String), Error> {
+        let affected_rows = transaction.affected_rows();
+        let last_insert_id = transaction.last_insert_id();
+        let status_flags = transaction.status();
+        let txid = transaction.commit_returning_gtid().await.map_err(|e| {
+            internal_err!(
+                "Error obtaining GTID from MySQL for RYW-enabled commit: {}",
+                e
+            )
+        })?;
+        Ok((
+            QueryResult::WriteResult {
+                num_rows_affected: affected_rows,
+                last_inserted_id: last_insert_id.unwrap_or(0),
+                status_flags,
+            },
+            txid,
+        ))
+    }
 }
 
 #[async_trait]
@@ -366,24 +389,29 @@ impl UpstreamDatabase for MySqlUpstream {
             error!("Could not execute query in mysql : {:?}", e);
             e
         })?;
+        Self::commit_ryw_write(transaction).await
+    }
 
-        let affected_rows = transaction.affected_rows();
-        let last_insert_id = transaction.last_insert_id();
-        let status_flags = transaction.status();
-        let txid = transaction.commit_returning_gtid().await.map_err(|e| {
-            internal_err!(
-                "Error obtaining GTID from MySQL for RYW-enabled commit: {}",
+    /// Executes the given prepared statement on the mysql backend.
+    async fn execute_ryw_write<'a>(
+        &'a mut self,
+        id: u32,
+        params: &[DfValue],
+        _exec_meta: Self::ExecMeta<'_>,
+    ) -> Result<(Self::QueryResult<'a>, String), Error> {
+        let params = dt_to_value_params(params)?;
+        let statement = self.prepared_statements.get(&id).ok_or(Error::ReadySet(
+            ReadySetError::PreparedStatementMissing { statement_id: id },
+        ))?;
+        let mut transaction = self.conn.start_transaction(TxOpts::default()).await?;
+        transaction
+            .exec_drop(statement, params)
+            .await
+            .map_err(|e| {
+                error!("Could not execute statement in mysql : {:?}", e);
                 e
-            )
-        })?;
-        Ok((
-            QueryResult::WriteResult {
-                num_rows_affected: affected_rows,
-                last_inserted_id: last_insert_id.unwrap_or(0),
-                status_flags,
-            },
-            txid,
-        ))
+            })?;
+        Self::commit_ryw_write(transaction).await
     }
 
     async fn start_tx<'a>(
@@ -1,12 +1,14 @@
 use mysql_async::prelude::*;
-use mysql_async::ChangeUserOpts;
+use mysql_async::{ChangeUserOpts, OptsBuilder};
 use readyset_adapter::backend::UnsupportedSetMode;
 use readyset_adapter::BackendBuilder;
 use readyset_client::query::QueryId;
+use readyset_client::status::ReplicationPauseState;
 use readyset_client_metrics::QueryDestination;
 use readyset_client_test_helpers::mysql_helpers::{last_query_info, MySQLAdapter};
 use readyset_client_test_helpers::{self, sleep, TestBuilder};
 use readyset_server::Handle;
+use readyset_util::eventually;
 use readyset_util::shutdown::ShutdownSender;
 use serial_test::serial;
 use test_utils::{skip_flaky_finder, slow};
@@ -731,6 +733,166 @@ async fn transaction_proxies() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+#[slow]
+#[skip_flaky_finder]
+async fn transaction_with_modes_proxies() {
+    let (opts, _handle, shutdown_tx) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+
+    conn.query_drop("CREATE TABLE t (x int)").await.unwrap();
+    sleep().await;
+
+    conn.query_drop("CREATE CACHE FROM SELECT * FROM t")
+        .await
+        .unwrap();
+
+    conn.query_drop("START TRANSACTION WITH CONSISTENT SNAPSHOT, READ ONLY;")
+        .await
+        .unwrap();
+    conn.query_drop("SELECT * FROM t;").await.unwrap();
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Upstream
+    );
+    // The transaction must have been started upstream with its modes intact
+    assert!(conn
+        .query_drop("INSERT INTO t (x) VALUES (1)")
+        .await
+        .is_err());
+
+    conn.query_drop("ROLLBACK;").await.unwrap();
+
+    conn.query_drop("SELECT * FROM t;").await.unwrap();
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Readyset
+    );
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+#[slow]
+async fn incompatible_session_variables_proxy_reads() {
+    let (opts, _handle, shutdown_tx) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+
+    conn.query_drop("CREATE TABLE t (x int)").await.unwrap();
+    conn.query_drop("INSERT INTO t (x) VALUES (1)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    conn.query_drop("CREATE CACHE FROM SELECT x FROM t")
+        .await
+        .unwrap();
+
+    // Variables which don't affect results are just proxied upstream
+    conn.query_drop("SET SESSION foreign_key_checks = 0")
+        .await
+        .unwrap();
+    conn.query_drop("SELECT x FROM t").await.unwrap();
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Readyset
+    );
+
+    conn.query_drop("SET NAMES utf8mb4 COLLATE utf8mb4_unicode_ci")
+        .await
+        .unwrap();
+    conn.query_drop("SET sql_mode = 'ANSI_QUOTES'")
+        .await
+        .unwrap();
+    assert_eq!(
+        conn.query_first::<(i32,), _>("SELECT \"x\" FROM \"t\"")
+            .await
+            .unwrap()
+            .unwrap()
+            .0,
+        1,
+    );
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Upstream
+    );
+
+    conn.query_drop(
+        "SET sql_mode = 'ONLY_FULL_GROUP_BY,STRICT_TRANS_TABLES,NO_ZERO_IN_DATE,NO_ZERO_DATE'",
+    )
+    .await
+    .unwrap();
+    conn.query_drop("SELECT x FROM t").await.unwrap();
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Readyset
+    );
+
+    // We can't know whether any other variable affects results, so once one is set all reads go
+    // upstream
+    conn.query_drop("SET SESSION div_precision_increment = 8")
+        .await
+        .unwrap();
+    conn.query_drop("SELECT x FROM t").await.unwrap();
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Upstream
+    );
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+#[slow]
+async fn ping_and_switch_database() {
+    let (opts, _handle, shutdown_tx) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts.clone()).await.unwrap();
+    conn.ping().await.unwrap();
+
+    conn.query_drop("CREATE TABLE t (x int)").await.unwrap();
+    conn.query_drop("INSERT INTO t (x) VALUES (1)")
+        .await
+        .unwrap();
+    sleep().await;
+    conn.query_drop("CREATE CACHE FROM SELECT x FROM t")
+        .await
+        .unwrap();
+
+    // Switching to a database that doesn't exist fails with the upstream error, and leaves the
+    // current database as-is
+    match conn.query_drop("USE readyset_no_such_database").await {
+        Err(mysql_async::Error::Server(e)) => assert_eq!(e.code, 1049), // ER_BAD_DB_ERROR
+        res => panic!("Expected ER_BAD_DB_ERROR, got {res:?}"),
+    }
+    assert_eq!(
+        conn.query_first::<(i32,), _>("SELECT x FROM t")
+            .await
+            .unwrap()
+            .unwrap()
+            .0,
+        1
+    );
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Readyset
+    );
+
+    // Same for connecting with a database that doesn't exist
+    let res = mysql_async::Conn::new(
+        OptsBuilder::from_opts(opts).db_name(Some("readyset_no_such_database")),
+    )
+    .await;
+    match res {
+        Err(mysql_async::Error::Server(e)) => assert_eq!(e.code, 1049),
+        res => panic!("Expected ER_BAD_DB_ERROR, got {:?}", res.map(|_| ())),
+    }
+
+    shutdown_tx.shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 #[serial]
 #[slow]
@@ -741,7 +903,7 @@ async fn valid_sql_parsing_failed_shows_proxied() {
     // supported by the parser, but if this test is failing, that may no longer be the case and
     // this the query should be replaced with another one that isn't supported, or a more
     // complicated way of testing this needs to be devised
-    let q = "CREATE TABLE t1 (id polygon);".to_string();
+    let q = "CREATE TABLE t1 (id year);".to_string();
     let _ = conn.query_drop(q.clone()).await;
 
     let proxied_queries = conn
@@ -1044,3 +1206,55 @@ async fn it_change_user() {
 
     shutdown_tx.shutdown().await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+#[slow]
+async fn pause_replication_between_transactions() {
+    let (opts, mut handle, shutdown_tx) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+
+    conn.query_drop("CREATE TABLE t (x int)").await.unwrap();
+    conn.query_drop("INSERT INTO t (x) VALUES (1)")
+        .await
+        .unwrap();
+    sleep().await;
+    conn.query_drop("CREATE CACHE FROM SELECT x FROM t")
+        .await
+        .unwrap();
+
+    handle.pause_replication().await.unwrap();
+    eventually!(run_test: {
+        handle.replication_pause_state().await.unwrap()
+    }, then_assert: |state| {
+        assert!(matches!(state, ReplicationPauseState::Paused { .. }))
+    });
+
+    let mut tx = conn
+        .start_transaction(mysql_async::TxOpts::new())
+        .await
+        .unwrap();
+    tx.query_drop("INSERT INTO t (x) VALUES (2)").await.unwrap();
+    tx.query_drop("INSERT INTO t (x) VALUES (3)").await.unwrap();
+    tx.commit().await.unwrap();
+    sleep().await;
+
+    // None of the transaction should have been applied while replication is paused
+    let rows: Vec<i32> = conn.query("SELECT x FROM t").await.unwrap();
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Readyset
+    );
+    assert_eq!(rows, vec![1]);
+
+    handle.resume_replication().await.unwrap();
+    eventually!(run_test: {
+        let mut rows: Vec<i32> = conn.query("SELECT x FROM t").await.unwrap();
+        rows.sort();
+        rows
+    }, then_assert: |rows| {
+        assert_eq!(rows, vec![1, 2, 3])
+    });
+
+    shutdown_tx.shutdown().await;
+}
@@ -1378,6 +1378,231 @@ async fn exec_insert_multiple() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn insert_multiple_with_defaults() {
+    let (opts, _handle, shutdown_tx) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE t (id int, x int DEFAULT 7)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    conn.query_drop("INSERT INTO t (id) VALUES (1), (2)")
+        .await
+        .unwrap();
+    assert_eq!(conn.affected_rows(), 2);
+    conn.exec_drop("INSERT INTO t (id) VALUES (?), (?)", (3, 4))
+        .await
+        .unwrap();
+    assert_eq!(conn.affected_rows(), 2);
+
+    let mut res = conn
+        .query::<(i32, i32), _>("SELECT id, x FROM t")
+        .await
+        .unwrap();
+    res.sort();
+    assert_eq!(res, vec![(1, 7), (2, 7), (3, 7), (4, 7)]);
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn batched_insert() {
+    const ROWS: usize = 1000;
+
+    let (opts, _handle, shutdown_tx) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE t (x int)").await.unwrap();
+    sleep().await;
+
+    for x in 0..ROWS {
+        conn.exec_drop("INSERT INTO t (x) VALUES (?)", (x,))
+            .await
+            .unwrap();
+    }
+
+    let query = format!(
+        "INSERT INTO t (x) VALUES {}",
+        (ROWS..ROWS * 2)
+            .map(|x| format!("({x})"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    conn.query_drop(query).await.unwrap();
+    assert_eq!(conn.affected_rows(), ROWS as u64);
+
+    let res = conn
+        .query_first::<usize, _>("SELECT count(*) FROM t")
+        .await
+        .unwrap();
+    assert_eq!(res, Some(ROWS * 2));
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn blob_values_are_binary() {
+    let (opts, _handle, shutdown_tx) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE t (id int, data blob, PRIMARY KEY(id))")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let data = vec![0u8, 0xff, b'a', 0x80];
+    conn.exec_drop("INSERT INTO t (id, data) VALUES (?, ?)", (1, data.clone()))
+        .await
+        .unwrap();
+    sleep().await;
+
+    let res = conn
+        .exec_first::<(i32, Vec<u8>), _, _>("SELECT id, data FROM t WHERE id = ?", (1,))
+        .await
+        .unwrap();
+    assert_eq!(res, Some((1, data.clone())));
+
+    let res = conn
+        .query_first::<(i32, Vec<u8>), _>("SELECT id, data FROM t WHERE id = 1")
+        .await
+        .unwrap();
+    assert_eq!(res, Some((1, data)));
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn case_insensitive_collation_grouping() {
+    let (opts, _handle, shutdown_tx) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop(
+        "CREATE TABLE t (id int, name varchar(255), code varchar(255) COLLATE utf8mb4_bin, \
+         PRIMARY KEY(id)) DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_general_ci",
+    )
+    .await
+    .unwrap();
+    sleep().await;
+
+    conn.query_drop(
+        "INSERT INTO t (id, name, code) VALUES (1, 'abc', 'abc'), (2, 'ABC', 'ABC'), \
+         (3, 'def', 'def')",
+    )
+    .await
+    .unwrap();
+    sleep().await;
+
+    let mut res = conn
+        .query::<(i64,), _>("SELECT count(*) FROM t GROUP BY name")
+        .await
+        .unwrap();
+    res.sort();
+    assert_eq!(res, vec![(1,), (2,)]);
+
+    let mut res = conn
+        .query::<(i64,), _>("SELECT count(*) FROM t GROUP BY code")
+        .await
+        .unwrap();
+    res.sort();
+    assert_eq!(res, vec![(1,), (1,), (1,)]);
+
+    let mut res = conn
+        .exec::<(i32,), _, _>("SELECT id FROM t WHERE name = ?", ("aBc",))
+        .await
+        .unwrap();
+    res.sort();
+    assert_eq!(res, vec![(1,), (2,)]);
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn case_insensitive_collation_writes() {
+    let (opts, _handle, shutdown_tx) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop(
+        "CREATE TABLE t (name varchar(255), val int, PRIMARY KEY(name)) \
+         DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_general_ci",
+    )
+    .await
+    .unwrap();
+    sleep().await;
+
+    conn.query_drop("INSERT INTO t (name, val) VALUES ('ABC', 1)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let res = conn
+        .exec::<(i32,), _, _>("SELECT val FROM t WHERE name = ?", ("abc",))
+        .await
+        .unwrap();
+    assert_eq!(res, vec![(1,)]);
+
+    conn.query_drop("UPDATE t SET val = 2 WHERE name = 'aBc'")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let res = conn
+        .exec::<(i32,), _, _>("SELECT val FROM t WHERE name = ?", ("ABC",))
+        .await
+        .unwrap();
+    assert_eq!(res, vec![(2,)]);
+
+    conn.query_drop("DELETE FROM t WHERE name = 'abc'")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let res = conn
+        .exec::<(i32,), _, _>("SELECT val FROM t WHERE name = ?", ("Abc",))
+        .await
+        .unwrap();
+    assert!(res.is_empty());
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn uuid_binary_columns() {
+    let (opts, _handle, shutdown_tx) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop(
+        "CREATE TABLE t (id BINARY(16) COMMENT 'readyset:uuid', raw BINARY(16), \
+         PRIMARY KEY(id))",
+    )
+    .await
+    .unwrap();
+    sleep().await;
+
+    let uuid = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+    let bytes = vec![
+        0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0,
+        0xc8,
+    ];
+    // Only the column marked as holding UUIDs parses their textual representation
+    conn.exec_drop(
+        "INSERT INTO t (id, raw) VALUES (?, ?)",
+        (uuid, bytes.clone()),
+    )
+    .await
+    .unwrap();
+    sleep().await;
+
+    let res = conn
+        .exec::<(Vec<u8>, Vec<u8>), _, _>("SELECT id, raw FROM t WHERE id = ?", (uuid,))
+        .await
+        .unwrap();
+    assert_eq!(res, vec![(bytes.clone(), bytes.clone())]);
+
+    let res = conn
+        .exec::<(Vec<u8>,), _, _>("SELECT raw FROM t WHERE id = ?", (bytes.clone(),))
+        .await
+        .unwrap();
+    assert_eq!(res, vec![(bytes,)]);
+
+    shutdown_tx.shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 #[ignore]
 async fn design_doc_topk_with_preload() {
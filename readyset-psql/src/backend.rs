@@ -13,7 +13,7 @@ use psql_srv as ps;
 use readyset_adapter::backend as cl;
 use readyset_adapter::upstream_database::LazyUpstream;
 use readyset_adapter_types::DeallocateId;
-use readyset_data::DfValue;
+use readyset_data::{DfValue, Timezone};
 use thiserror::Error;
 use tokio_postgres::SimpleQueryMessage;
 
@@ -147,7 +147,9 @@ impl ps::PsqlBackend for Backend {
     }
 
     async fn on_query(&mut self, query: &str) -> Result<ps::QueryResponse<Resultset>, ps::Error> {
-        self.query(query).await?.try_into()
+        let response = self.query(query).await?.try_into()?;
+        // Read the timezone after running the query, since the query might have changed it
+        Ok(with_timezone(response, self.inner.timezone()))
     }
 
     async fn on_prepare(
@@ -170,9 +172,12 @@ impl ps::PsqlBackend for Backend {
             .iter()
             .map(|p| ParamRef(p).try_into())
             .collect::<Result<Vec<DfValue>, ps::Error>>()?;
-        self.execute(statement_id, &params, result_transfer_formats)
+        let timezone = self.inner.timezone();
+        let response = self
+            .execute(statement_id, &params, result_transfer_formats)
             .await?
-            .try_into()
+            .try_into()?;
+        Ok(with_timezone(response, timezone))
     }
 
     async fn on_close(&mut self, statement_id: DeallocateId) -> Result<(), ps::Error> {
@@ -217,6 +222,21 @@ impl ps::PsqlBackend for Backend {
     }
 }
 
+/// Returns the given response with the values of any `timestamptz` columns in its results converted
+/// to the given session timezone
+fn with_timezone(
+    response: ps::QueryResponse<Resultset>,
+    timezone: Timezone,
+) -> ps::QueryResponse<Resultset> {
+    match response {
+        ps::QueryResponse::Select { schema, resultset } => ps::QueryResponse::Select {
+            schema,
+            resultset: resultset.with_timezone(timezone),
+        },
+        response => response,
+    }
+}
+
 /// A simple wrapper around a request parameter `psql_srv::PsqlValue` reference, facilitiating
 /// conversion to `DfValue`.
 pub struct ParamRef<'a>(pub &'a PsqlValue);
@@ -249,7 +269,8 @@ impl TryFrom<ParamRef<'_>> for DfValue {
             PsqlValue::ByteArray(b) => Ok(DfValue::ByteArray(Arc::new(b.clone()))),
             PsqlValue::MacAddress(m) => Ok(DfValue::from(m.to_string(MacAddressFormat::HexString))),
             PsqlValue::Inet(ip) => Ok(DfValue::from(ip.to_string())),
-            PsqlValue::Uuid(uuid) => Ok(DfValue::from(uuid.to_string())),
+            PsqlValue::Uuid(uuid) => Ok(DfValue::from(*uuid)),
+            PsqlValue::Interval(i) => Ok(DfValue::from(*i)),
             PsqlValue::Json(v) | PsqlValue::Jsonb(v) => Ok(DfValue::from(v.to_string())),
             PsqlValue::Bit(bits) | PsqlValue::VarBit(bits) => Ok(DfValue::from(bits.clone())),
             PsqlValue::Array(arr, _) => Ok(DfValue::from(arr.clone())),
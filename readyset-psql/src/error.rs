@@ -4,6 +4,7 @@ use psql_srv as ps;
 use readyset_adapter::upstream_database::IsFatalError;
 use readyset_errors::ReadySetError;
 use thiserror::Error;
+use tokio_postgres::error::SqlState;
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -17,6 +18,37 @@ pub enum Error {
     Io(#[from] io::Error),
 }
 
+/// Returns the SQLSTATE that most closely corresponds to the given error on its own, if there is
+/// one
+fn readyset_error_sqlstate(error: &ReadySetError) -> Option<SqlState> {
+    use ReadySetError::*;
+    Some(match error {
+        UnparseableQuery { .. } => SqlState::SYNTAX_ERROR,
+        TableNotFound { .. }
+        | TableNotReplicated { .. }
+        | ViewNotFound(_)
+        | ViewNotFoundForQuery { .. }
+        | ViewNotFoundInWorkers { .. } => SqlState::UNDEFINED_TABLE,
+        ViewAlreadyExists(_) => SqlState::DUPLICATE_TABLE,
+        NoSuchColumn(_) | NonExistentColumn { .. } => SqlState::UNDEFINED_COLUMN,
+        NonNullable { .. } => SqlState::NOT_NULL_VIOLATION,
+        ExprNotInGroupBy { .. } => SqlState::GROUPING_ERROR,
+        DfValueConversionError { .. } => SqlState::DATATYPE_MISMATCH,
+        Unsupported(_) | UnsupportedPlaceholders { .. } => SqlState::FEATURE_NOT_SUPPORTED,
+        PreparedStatementMissing { .. } => SqlState::UNDEFINED_PSTATEMENT,
+        UpqueryTimeout | ViewUnavailable(_) => SqlState::QUERY_CANCELED,
+        Overloaded { .. } | ViewThrottled { .. } | CircuitOpen { .. } => {
+            SqlState::INSUFFICIENT_RESOURCES
+        }
+        ServerShuttingDown => SqlState::ADMIN_SHUTDOWN,
+        Internal(_) => SqlState::INTERNAL_ERROR,
+        _ => return None,
+    })
+}
+
+/// Errors returned by ReadySet are reported with the SQLSTATE of the outermost of their causes
+/// which has a corresponding SQLSTATE, so that eg a missing table is reported as such even if the
+/// error was returned by a remote request.
 impl From<Error> for ps::Error {
     fn from(e: Error) -> Self {
         use Error::*;
@@ -27,7 +59,13 @@ impl From<Error> for ps::Error {
                 ps::Error::MissingPreparedStatement(statement_id.to_string())
             }
             ReadySet(ReadySetError::Unsupported(s)) => ps::Error::Unsupported(s),
-            ReadySet(e) => ps::Error::Unknown(e.to_string()),
+            ReadySet(e) => match e.causes().find_map(readyset_error_sqlstate) {
+                Some(sqlstate) => ps::Error::WithSqlState {
+                    sqlstate,
+                    message: e.to_string(),
+                },
+                None => ps::Error::Unknown(e.to_string()),
+            },
             PostgreSql(e) => e.into(),
         }
     }
@@ -40,3 +78,50 @@ impl IsFatalError for Error {
         matches!(self, Self::PostgreSql(e) if e.is_closed())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sqlstate(error: ReadySetError) -> SqlState {
+        match ps::Error::from(Error::from(error)) {
+            ps::Error::WithSqlState { sqlstate, .. } => sqlstate,
+            e => panic!("Expected an error with a SQLSTATE, got {e:?}"),
+        }
+    }
+
+    #[test]
+    fn readyset_error_sqlstates() {
+        assert_eq!(
+            sqlstate(ReadySetError::TableNotFound {
+                name: "t".into(),
+                schema: Some("public".into()),
+            }),
+            SqlState::UNDEFINED_TABLE
+        );
+        assert_eq!(
+            sqlstate(ReadySetError::UpqueryTimeout),
+            SqlState::QUERY_CANCELED
+        );
+        assert!(matches!(
+            ps::Error::from(Error::from(ReadySetError::EmptyKey)),
+            ps::Error::Unknown(_)
+        ));
+    }
+
+    #[test]
+    fn wrapped_readyset_error_sqlstate() {
+        let error = ReadySetError::Unsupported("x".into()).context("while migrating");
+        let message = error.to_string();
+        match ps::Error::from(Error::from(error)) {
+            ps::Error::WithSqlState {
+                sqlstate,
+                message: m,
+            } => {
+                assert_eq!(sqlstate, SqlState::FEATURE_NOT_SUPPORTED);
+                assert_eq!(m, message);
+            }
+            e => panic!("Expected an error with a SQLSTATE, got {e:?}"),
+        }
+    }
+}
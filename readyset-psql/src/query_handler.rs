@@ -8,7 +8,8 @@ use nom_sql::{
 };
 use readyset_adapter::backend::noria_connector::QueryResult;
 use readyset_adapter::backend::{noria_connector, SelectSchema};
-use readyset_adapter::{QueryHandler, SetBehavior};
+use readyset_adapter::{QueryHandler, SessionVariableChanges, SetBehavior};
+use readyset_data::Timezone;
 use readyset_errors::ReadySetResult;
 
 enum AllowedParameterValue {
@@ -44,272 +45,11 @@ impl AllowedParameterValue {
 }
 
 lazy_static! {
-    /// The set of parameters that we can safely proxy upstream with *any* value, as we've
-    /// determined that they don't change the semantics of queries in a way that would matter for us
-    static ref ALLOWED_PARAMETERS_ANY_VALUE: HashSet<&'static str> =
-        HashSet::from([
-            "autovacuum",
-            "autovacuum_analyze_scale_factor",
-            "autovacuum_analyze_threshold",
-            "autovacuum_freeze_max_age",
-            "autovacuum_max_workers",
-            "autovacuum_multixact_freeze_max_age",
-            "autovacuum_naptime",
-            "autovacuum_vacuum_cost_delay",
-            "autovacuum_vacuum_cost_limit",
-            "autovacuum_vacuum_scale_factor",
-            "autovacuum_vacuum_threshold",
-            "default_text_search_config",
-            "server_encoding",
-            "timezone_abbreviations",
-            "dynamic_library_path",
-            "gin_fuzzy_search_limit",
-            "tcp_keepalives_count",
-            "tcp_keepalives_idle",
-            "tcp_keepalives_interval",
-            "local_preload_libraries",
-            "session_preload_libraries",
-            "shared_preload_libraries",
-            "check_function_bodies",
-            "default_tablespace",
-            "default_transaction_deferrable",
-            "default_transaction_isolation",
-            "default_transaction_read_only",
-            "gin_pending_list_limit",
-            "idle_in_transaction_session_timeout",
-            "lock_timeout",
-            "session_replication_role",
-            "statement_timeout",
-            "temp_tablespaces",
-            "transaction_deferrable",
-            "transaction_isolation",
-            "transaction_read_only",
-            "vacuum_freeze_min_age",
-            "vacuum_freeze_table_age",
-            "vacuum_multixact_freeze_min_age",
-            "vacuum_multixact_freeze_table_age",
-            "xmlbinary",
-            "xmloption",
-            "bonjour",
-            "bonjour_name",
-            "listen_addresses",
-            "max_connections",
-            "port",
-            "superuser_reserved_connections",
-            "unix_socket_directories",
-            "unix_socket_group",
-            "unix_socket_permissions",
-            "authentication_timeout",
-            "db_user_namespace",
-            "krb_caseins_users",
-            "krb_server_keyfile",
-            "password_encryption",
-            "row_security",
-            "ssl",
-            "ssl_ca_file",
-            "ssl_cert_file",
-            "ssl_ciphers",
-            "ssl_crl_file",
-            "ssl_dh_params_file",
-            "ssl_ecdh_curve",
-            "ssl_key_file",
-            "ssl_prefer_server_ciphers",
-            "allow_system_table_mods",
-            "ignore_checksum_failure",
-            "ignore_system_indexes",
-            "post_auth_delay",
-            "pre_auth_delay",
-            "trace_notify",
-            "trace_recovery_messages",
-            "trace_sort",
-            "wal_consistency_checking",
-            "zero_damaged_pages",
-            "exit_on_error",
-            "restart_after_crash",
-            "config_file",
-            "data_directory",
-            "external_pid_file",
-            "hba_file",
-            "ident_file",
-            "deadlock_timeout",
-            "max_locks_per_transaction",
-            "max_pred_locks_per_page",
-            "max_pred_locks_per_relation",
-            "max_pred_locks_per_transaction",
-            "block_size",
-            "data_checksums",
-            "debug_assertions",
-            "integer_datetimes",
-            "max_function_args",
-            "max_identifier_length",
-            "max_index_keys",
-            "segment_size",
-            "server_version",
-            "server_version_num",
-            "wal_block_size",
-            "wal_segment_size",
-            "cluster_name",
-            "update_process_title",
-            "geqo",
-            "geqo_effort",
-            "geqo_generations",
-            "geqo_pool_size",
-            "geqo_seed",
-            "geqo_selection_bias",
-            "geqo_threshold",
-            "constraint_exclusion",
-            "cursor_tuple_fraction",
-            "default_statistics_target",
-            "force_parallel_mode",
-            "from_collapse_limit",
-            "join_collapse_limit",
-            "cpu_index_tuple_cost",
-            "cpu_operator_cost",
-            "cpu_tuple_cost",
-            "effective_cache_size",
-            "min_parallel_index_scan_size",
-            "min_parallel_table_scan_size",
-            "parallel_setup_cost",
-            "parallel_tuple_cost",
-            "random_page_cost",
-            "seq_page_cost",
-            "enable_bitmapscan",
-            "enable_gathermerge",
-            "enable_hashagg",
-            "enable_hashjoin",
-            "enable_indexonlyscan",
-            "enable_indexscan",
-            "enable_material",
-            "enable_mergejoin",
-            "enable_nestloop",
-            "enable_seqscan",
-            "enable_sort",
-            "enable_tidscan",
-            "track_commit_timestamp",
-            "synchronous_standby_names",
-            "vacuum_defer_cleanup_age",
-            "max_replication_slots",
-            "max_wal_senders",
-            "wal_keep_segments",
-            "wal_sender_timeout",
-            "hot_standby",
-            "hot_standby_feedback",
-            "max_standby_archive_delay",
-            "max_standby_streaming_delay",
-            "wal_receiver_status_interval",
-            "wal_receiver_timeout",
-            "wal_retrieve_retry_interval",
-            "max_logical_replication_workers",
-            "max_sync_workers_per_subscription",
-            "application_name",
-            "debug_pretty_print",
-            "debug_print_parse",
-            "debug_print_plan",
-            "debug_print_rewritten",
-            "log_autovacuum_min_duration",
-            "log_checkpoints",
-            "log_connections",
-            "log_disconnections",
-            "log_duration",
-            "log_error_verbosity",
-            "log_hostname",
-            "log_line_prefix",
-            "log_lock_waits",
-            "log_replication_commands",
-            "log_statement",
-            "log_temp_files",
-            "log_timezone",
-            "client_min_messages",
-            "log_min_duration_statement",
-            "log_min_error_statement",
-            "log_min_messages",
-            "event_source",
-            "log_destination",
-            "log_directory",
-            "log_file_mode",
-            "log_filename",
-            "log_rotation_age",
-            "log_rotation_size",
-            "log_truncate_on_rotation",
-            "logging_collector",
-            "syslog_facility",
-            "syslog_ident",
-            "syslog_sequence_numbers",
-            "syslog_split_messages",
-            "backend_flush_after",
-            "effective_io_concurrency",
-            "max_parallel_workers",
-            "max_parallel_workers_per_gather",
-            "max_worker_processes",
-            "old_snapshot_threshold",
-            "bgwriter_delay",
-            "bgwriter_flush_after",
-            "bgwriter_lru_maxpages",
-            "bgwriter_lru_multiplier",
-            "vacuum_cost_delay",
-            "vacuum_cost_limit",
-            "vacuum_cost_page_dirty",
-            "vacuum_cost_page_hit",
-            "vacuum_cost_page_miss",
-            "temp_file_limit",
-            "max_files_per_process",
-            "autovacuum_work_mem",
-            "dynamic_shared_memory_type",
-            "huge_pages",
-            "maintenance_work_mem",
-            "max_prepared_transactions",
-            "max_stack_depth",
-            "replacement_sort_tuples",
-            "shared_buffers",
-            "temp_buffers",
-            "track_activity_query_size",
-            "work_mem",
-            "log_executor_stats",
-            "log_parser_stats",
-            "log_planner_stats",
-            "log_statement_stats",
-            "stats_temp_directory",
-            "track_activities",
-            "track_counts",
-            "track_functions",
-            "track_io_timing",
-            "default_with_oids",
-            "escape_string_warning",
-            "lo_compat_privileges",
-            "operator_precedence_warning",
-            "quote_all_identifiers",
-            "synchronize_seqscans",
-            "archive_command",
-            "archive_mode",
-            "archive_timeout",
-            "checkpoint_completion_target",
-            "checkpoint_flush_after",
-            "checkpoint_timeout",
-            "checkpoint_warning",
-            "max_wal_size",
-            "min_wal_size",
-            "commit_delay",
-            "commit_siblings",
-            "fsync",
-            "full_page_writes",
-            "synchronous_commit",
-            "wal_buffers",
-            "wal_compression",
-            "wal_level",
-            "wal_log_hints",
-            "wal_sync_method",
-            "wal_writer_delay",
-            "wal_writer_flush_after",
-
-            // This parameter *would* matter, if we supported intervals - once we do we should move
-            // this to WITH_VALUE and specify the value for the interval style we actually use
-            "intervalstyle",
-
-            // Similarly this parameter *would* matter if we supported arrays, and once we do we
-            // should move this to WITH_VALUE
-            "array_nulls",
-        ]);
-
+    /// Parameters which affect the semantics of queries, mapped to the values ReadySet returns
+    /// results consistent with.
+    ///
+    /// `intervalstyle` is only allowed to be `postgres`, since that's the only style we format
+    /// intervals in (see the `Display` impl for [`readyset_data::Interval`]).
     static ref ALLOWED_PARAMETERS_WITH_VALUE: HashMap<&'static str, AllowedParameterValue> =
         HashMap::from([
             ("client_encoding", AllowedParameterValue::one_of([
@@ -318,13 +58,11 @@ lazy_static! {
                 PostgresParameterValue::literal("UTF8"),
                 PostgresParameterValue::literal("unicode"),
             ])),
-            ("timezone", AllowedParameterValue::literal("UTC")),
             ("datestyle", AllowedParameterValue::one_of([
                 PostgresParameterValue::literal("ISO"),
                 PostgresParameterValue::identifier("iso"),
             ])),
             ("extra_float_digits", AllowedParameterValue::literal(1)),
-            ("TimeZone",  AllowedParameterValue::literal("Etc/UTC")),
             ("bytea_output",  AllowedParameterValue::literal("hex")),
             ("transform_null_equals", AllowedParameterValue::literal(false)),
             ("backslash_quote", AllowedParameterValue::one_of([
@@ -335,7 +73,33 @@ lazy_static! {
                 PostgresParameterValue::literal(true),
                 PostgresParameterValue::identifier("on"),
             ])),
+            ("intervalstyle", AllowedParameterValue::one_of([
+                PostgresParameterValue::literal("postgres"),
+                PostgresParameterValue::identifier("postgres"),
+            ])),
+            ("array_nulls", AllowedParameterValue::one_of([
+                PostgresParameterValue::literal(true),
+                PostgresParameterValue::identifier("on"),
+            ])),
         ]);
+
+    /// Parameters which are known not to affect the results of queries, so setting them is proxied
+    /// upstream as-is. Setting any parameter which is in neither this set nor
+    /// [`ALLOWED_PARAMETERS_WITH_VALUE`] (other than to its default) proxies all reads in the
+    /// session upstream, since we can't know whether ReadySet's results would be consistent with
+    /// it.
+    static ref PARAMETERS_WITHOUT_EFFECT: HashSet<&'static str> = HashSet::from([
+        "application_name",
+        "client_min_messages",
+        "idle_in_transaction_session_timeout",
+        "idle_session_timeout",
+        "lock_timeout",
+        "statement_timeout",
+        "tcp_keepalives_count",
+        "tcp_keepalives_idle",
+        "tcp_keepalives_interval",
+        "work_mem",
+    ]);
 }
 
 /// PostgreSQL flavor of [`QueryHandler`].
@@ -355,11 +119,6 @@ impl QueryHandler for PostgreSqlQueryHandler {
 
     fn handle_set_statement(stmt: &SetStatement) -> SetBehavior {
         match stmt {
-            SetStatement::PostgresParameter(SetPostgresParameter { name, .. })
-                if ALLOWED_PARAMETERS_ANY_VALUE.contains(name.to_ascii_lowercase().as_str()) =>
-            {
-                SetBehavior::Proxy
-            }
             SetStatement::PostgresParameter(SetPostgresParameter { name, value, .. }) => match name
                 .as_str()
             {
@@ -393,17 +152,62 @@ impl QueryHandler for PostgreSqlQueryHandler {
 
                     SetBehavior::SetSearchPath(search_path)
                 }
+                tz if tz.eq_ignore_ascii_case("timezone") => {
+                    let timezone = match value {
+                        // We always report UTC as the server's timezone on startup
+                        SetPostgresParameterValue::Default => Some(Timezone::UTC),
+                        SetPostgresParameterValue::Value(PostgresParameterValue::Single(
+                            PostgresParameterValueInner::Identifier(name),
+                        )) => name.parse().ok(),
+                        SetPostgresParameterValue::Value(PostgresParameterValue::Single(
+                            PostgresParameterValueInner::Literal(Literal::String(name)),
+                        )) if !name.starts_with(['+', '-']) => name.parse().ok(),
+                        // Postgres interprets numeric offsets and POSIX-style timezone strings
+                        // with the opposite sign convention to ISO 8601 offsets, so we don't try
+                        // to support them
+                        _ => None,
+                    };
+
+                    SetBehavior::SetSessionVariables(SessionVariableChanges {
+                        timezone,
+                        compatibility: vec![("timezone".into(), timezone.is_some())],
+                    })
+                }
                 _ => {
-                    if let Some(allowed_value) = ALLOWED_PARAMETERS_WITH_VALUE.get(name.as_str()) {
-                        SetBehavior::proxy_if(allowed_value.set_value_is_allowed(value))
-                    } else {
-                        SetBehavior::Unsupported
+                    let name = name.to_ascii_lowercase();
+                    match ALLOWED_PARAMETERS_WITH_VALUE.get(name.as_str()) {
+                        Some(allowed_value) => {
+                            SetBehavior::SetSessionVariables(SessionVariableChanges {
+                                compatibility: vec![(
+                                    name.into(),
+                                    allowed_value.set_value_is_allowed(value),
+                                )],
+                                ..Default::default()
+                            })
+                        }
+                        None if PARAMETERS_WITHOUT_EFFECT.contains(name.as_str()) => {
+                            SetBehavior::Proxy
+                        }
+                        None => SetBehavior::SetSessionVariables(SessionVariableChanges {
+                            compatibility: vec![(
+                                name.into(),
+                                *value == SetPostgresParameterValue::Default,
+                            )],
+                            ..Default::default()
+                        }),
                     }
                 }
             },
-            SetStatement::Names(SetNames { charset, .. }) => SetBehavior::proxy_if(
-                charset.to_lowercase() == "utf8" || charset.to_lowercase() == "utf-8",
-            ),
+            SetStatement::Names(SetNames { charset, .. }) => {
+                SetBehavior::SetSessionVariables(SessionVariableChanges {
+                    compatibility: vec![(
+                        "client_encoding".into(),
+                        charset.eq_ignore_ascii_case("utf8")
+                            || charset.eq_ignore_ascii_case("utf-8"),
+                    )],
+                    ..Default::default()
+                })
+            }
             _ => SetBehavior::Unsupported,
         }
     }
@@ -429,24 +233,63 @@ mod tests {
         )
     }
 
+    fn sets_variable(statement: &str, name: &str, compatible: bool) {
+        assert_eq!(
+            PostgreSqlQueryHandler::handle_set_statement(&parse_set_statement(statement)),
+            SetBehavior::SetSessionVariables(SessionVariableChanges {
+                compatibility: vec![(name.into(), compatible)],
+                ..Default::default()
+            })
+        )
+    }
+
+    #[test]
+    fn parameter_without_effect_proxied() {
+        is_proxy("SET application_name = 'readyset'");
+        is_proxy("SET statement_timeout = 1000");
+    }
+
+    #[test]
+    fn unknown_parameter_incompatible() {
+        sets_variable("SET lc_numeric = 'de_DE'", "lc_numeric", false);
+        sets_variable("SET lc_monetary = 'C'", "lc_monetary", false);
+        sets_variable("SET lc_monetary TO DEFAULT", "lc_monetary", true);
+    }
+
+    #[test]
+    fn intervalstyle_postgres_allowed() {
+        sets_variable("SET intervalstyle = 'postgres'", "intervalstyle", true);
+        sets_variable("SET IntervalStyle = iso_8601", "intervalstyle", false);
+    }
+
     #[test]
     fn standard_conforming_strings_on_allowed() {
-        is_proxy("SET standard_conforming_strings = on");
+        sets_variable(
+            "SET standard_conforming_strings = on",
+            "standard_conforming_strings",
+            true,
+        );
+        sets_variable(
+            "SET standard_conforming_strings = off",
+            "standard_conforming_strings",
+            false,
+        );
     }
 
     #[test]
     fn client_encoding_utf8_allowed() {
-        is_proxy("SET client_encoding = 'UTF8'");
+        sets_variable("SET client_encoding = 'UTF8'", "client_encoding", true);
+        sets_variable("SET client_encoding = 'LATIN1'", "client_encoding", false);
     }
 
     #[test]
     fn set_name_utf8_variations() {
         // The Postgres documentation lists "UTF8" as a supported Character Set. However, the
         // Postgres server also accepts "UTF-8" and "utf-8" as correct Character Set names.
-        is_proxy("SET names 'UTF8'");
-        is_proxy("SET names 'UTF-8'");
-        is_proxy("SET names 'utf8'");
-        is_proxy("SET names 'utf-8'");
+        sets_variable("SET names 'UTF8'", "client_encoding", true);
+        sets_variable("SET names 'UTF-8'", "client_encoding", true);
+        sets_variable("SET names 'utf8'", "client_encoding", true);
+        sets_variable("SET names 'utf-8'", "client_encoding", true);
     }
 
     #[test]
@@ -466,6 +309,48 @@ mod tests {
         );
     }
 
+    mod timezone {
+        use super::*;
+
+        fn sets_timezone(stmt: &str, timezone: &str) {
+            assert_eq!(
+                PostgreSqlQueryHandler::handle_set_statement(&parse_set_statement(stmt)),
+                SetBehavior::SetSessionVariables(SessionVariableChanges {
+                    timezone: Some(timezone.parse().unwrap()),
+                    compatibility: vec![("timezone".into(), true)],
+                })
+            );
+        }
+
+        fn is_incompatible(stmt: &str) {
+            sets_variable(stmt, "timezone", false);
+        }
+
+        #[test]
+        fn utc() {
+            sets_timezone("SET timezone = 'UTC'", "UTC");
+            sets_timezone("SET TimeZone = 'Etc/UTC'", "Etc/UTC");
+        }
+
+        #[test]
+        fn named() {
+            sets_timezone("SET timezone = 'America/New_York'", "America/New_York");
+            sets_timezone("SET timezone TO \"Europe/London\"", "Europe/London");
+        }
+
+        #[test]
+        fn default() {
+            sets_timezone("SET timezone TO DEFAULT", "UTC");
+        }
+
+        #[test]
+        fn incompatible() {
+            is_incompatible("SET timezone = 'Not/A_Timezone'");
+            is_incompatible("SET timezone = '+10:00'");
+            is_incompatible("SET timezone = -7");
+        }
+    }
+
     mod search_path {
         use super::*;
 
@@ -6,6 +6,7 @@ use futures::{ready, Stream};
 use ps::{PsqlSrvRow, PsqlValue};
 use psql_srv as ps;
 use readyset_client::results::ResultIterator;
+use readyset_data::{DfValue, Timezone};
 use tokio_postgres::types::Type;
 use tokio_postgres::{GenericResult, ResultStream, SimpleQueryMessage, SimpleQueryStream};
 
@@ -34,6 +35,10 @@ pub struct Resultset {
 
     /// The data types of the projected fields for each row.
     project_field_types: Arc<Vec<Type>>,
+
+    /// The timezone of the session, which values of `timestamptz` columns returned from ReadySet
+    /// are converted to.
+    timezone: Timezone,
 }
 
 impl Resultset {
@@ -41,6 +46,7 @@ impl Resultset {
         Self {
             results: ResultsetInner::Empty,
             project_field_types: Arc::new(vec![]),
+            timezone: Timezone::UTC,
         }
     }
 
@@ -60,6 +66,7 @@ impl Resultset {
         Ok(Resultset {
             results: ResultsetInner::ReadySet(Box::new(results.into_iter())),
             project_field_types,
+            timezone: Timezone::UTC,
         })
     }
 
@@ -74,6 +81,7 @@ impl Resultset {
                 stream,
             },
             project_field_types: Arc::new(schema),
+            timezone: Timezone::UTC,
         }
     }
 
@@ -87,8 +95,14 @@ impl Resultset {
                 stream,
             },
             project_field_types: Arc::new(vec![]),
+            timezone: Timezone::UTC,
         }
     }
+
+    /// Convert the values of `timestamptz` columns returned from ReadySet to the given timezone
+    pub fn with_timezone(self, timezone: Timezone) -> Self {
+        Self { timezone, ..self }
+    }
 }
 
 impl Stream for Resultset {
@@ -96,13 +110,30 @@ impl Stream for Resultset {
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let project_field_types = self.project_field_types.clone();
+        let timezone = self.timezone;
         let next = match &mut self.get_mut().results {
             ResultsetInner::Empty => None,
             ResultsetInner::ReadySet(i) => i.next().map(|values| {
                 values
                     .into_iter()
                     .zip(project_field_types.iter())
-                    .map(|(value, col_type)| PsqlValue::try_from(TypedDfValue { value, col_type }))
+                    .map(|(value, col_type)| {
+                        // Timestamps with a timezone are stored in UTC, but should be returned in
+                        // the session timezone
+                        let value = match (col_type, value) {
+                            (&Type::TIMESTAMPTZ, DfValue::TimestampTz(ts))
+                                if !timezone.is_utc() =>
+                            {
+                                DfValue::TimestampTz(
+                                    timezone
+                                        .from_utc_datetime(&ts.to_chrono().naive_utc())
+                                        .into(),
+                                )
+                            }
+                            (_, value) => value,
+                        };
+                        PsqlValue::try_from(TypedDfValue { value, col_type })
+                    })
                     .collect::<Result<Vec<_>, _>>()
                     .map(PsqlSrvRow::ValueVec)
             }),
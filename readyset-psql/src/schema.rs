@@ -63,7 +63,7 @@ pub fn type_to_pgsql(col_type: &DfType) -> Result<pgsql::types::Type, Error> {
         DfType::Unknown => Ok(Type::TEXT), // The default type for "unknown" in pgsql is TEXT
         DfType::Bool => Ok(Type::BOOL),
         DfType::Char(..) => Ok(Type::BPCHAR),
-        DfType::VarChar(_, Collation::Utf8) => Ok(Type::VARCHAR),
+        DfType::VarChar(_, Collation::Utf8 | Collation::Utf8Ci) => Ok(Type::VARCHAR),
         DfType::VarChar(_, Collation::Citext) => {
             // TODO: use the right CITEXT type
             Ok(Type::VARCHAR)
@@ -73,7 +73,7 @@ pub fn type_to_pgsql(col_type: &DfType) -> Result<pgsql::types::Type, Error> {
         DfType::SmallInt => Ok(Type::INT2),
         DfType::Float => Ok(Type::FLOAT4),
         DfType::Double => Ok(Type::FLOAT8),
-        DfType::Text(Collation::Utf8) => Ok(Type::TEXT),
+        DfType::Text(Collation::Utf8 | Collation::Utf8Ci) => Ok(Type::TEXT),
         DfType::Text(Collation::Citext) => Ok(Type::TEXT), // TODO: use the right CITEXT type
         DfType::Timestamp { .. } => Ok(Type::TIMESTAMP),
         DfType::TimestampTz { .. } => Ok(Type::TIMESTAMPTZ),
@@ -107,6 +107,7 @@ pub fn type_to_pgsql(col_type: &DfType) -> Result<pgsql::types::Type, Error> {
         DfType::MacAddr => Ok(Type::MACADDR),
         DfType::Inet => Ok(Type::INET),
         DfType::Uuid => Ok(Type::UUID),
+        DfType::Interval => Ok(Type::INTERVAL),
         DfType::Bit(_) => Ok(Type::BIT),
         DfType::VarBit(_) => Ok(Type::VARBIT),
         DfType::Array(box DfType::Unknown) => {
@@ -115,7 +116,9 @@ pub fn type_to_pgsql(col_type: &DfType) -> Result<pgsql::types::Type, Error> {
         }
         DfType::Array(box DfType::Bool) => Ok(Type::BOOL_ARRAY),
         DfType::Array(box DfType::Char(..)) => Ok(Type::BPCHAR_ARRAY),
-        DfType::Array(box DfType::VarChar(_, Collation::Utf8)) => Ok(Type::VARCHAR_ARRAY),
+        DfType::Array(box DfType::VarChar(_, Collation::Utf8 | Collation::Utf8Ci)) => {
+            Ok(Type::VARCHAR_ARRAY)
+        }
         DfType::Array(box DfType::VarChar(_, Collation::Citext)) => {
             // TODO: use the right CITEXT type
             Ok(Type::VARCHAR_ARRAY)
@@ -125,7 +128,9 @@ pub fn type_to_pgsql(col_type: &DfType) -> Result<pgsql::types::Type, Error> {
         DfType::Array(box DfType::SmallInt) => Ok(Type::INT2_ARRAY),
         DfType::Array(box DfType::Float) => Ok(Type::FLOAT4_ARRAY),
         DfType::Array(box DfType::Double) => Ok(Type::FLOAT8_ARRAY),
-        DfType::Array(box DfType::Text(Collation::Utf8)) => Ok(Type::TEXT_ARRAY),
+        DfType::Array(box DfType::Text(Collation::Utf8 | Collation::Utf8Ci)) => {
+            Ok(Type::TEXT_ARRAY)
+        }
         DfType::Array(box DfType::Text(Collation::Citext)) => {
             // TODO: use the right CITEXT_ARRAY type
             Ok(Type::TEXT_ARRAY)
@@ -171,6 +176,7 @@ pub fn type_to_pgsql(col_type: &DfType) -> Result<pgsql::types::Type, Error> {
         DfType::Array(box DfType::MacAddr) => Ok(Type::MACADDR_ARRAY),
         DfType::Array(box DfType::Inet) => Ok(Type::INET_ARRAY),
         DfType::Array(box DfType::Uuid) => Ok(Type::UUID_ARRAY),
+        DfType::Array(box DfType::Interval) => Ok(Type::INTERVAL_ARRAY),
         DfType::Array(box DfType::Bit(_)) => Ok(Type::BIT_ARRAY),
         DfType::Array(box DfType::VarBit(_)) => Ok(Type::VARBIT_ARRAY),
         DfType::Array(box DfType::Array(_)) => unsupported_type!(),
@@ -181,17 +181,7 @@ impl UpstreamDatabase for PostgreSqlUpstream {
 
         let pg_config = pgsql::Config::from_str(url)?;
         let user = pg_config.get_user().map(|s| s.to_owned());
-        let connector = {
-            let mut builder = native_tls::TlsConnector::builder();
-            if upstream_config.disable_upstream_ssl_verification {
-                builder.danger_accept_invalid_certs(true);
-            }
-            if let Some(cert) = upstream_config.get_root_cert().await {
-                builder.add_root_certificate(cert?);
-            }
-            builder.build().unwrap() // Never returns an error
-        };
-        let tls = postgres_native_tls::MakeTlsConnector::new(connector);
+        let tls = upstream_config.pg_tls_connector().await?;
         let span = info_span!(
             "Connecting to PostgreSQL upstream",
             host = ?pg_config.get_hosts(),
@@ -334,6 +324,15 @@ impl UpstreamDatabase for PostgreSqlUpstream {
         unsupported!("Read-Your-Write not yet implemented for PostgreSQL")
     }
 
+    async fn execute_ryw_write<'a>(
+        &'a mut self,
+        _statement_id: u32,
+        _params: &[DfValue],
+        _exec_meta: &'_ [TransferFormat],
+    ) -> Result<(Self::QueryResult<'a>, String), Error> {
+        unsupported!("Read-Your-Write not yet implemented for PostgreSQL")
+    }
+
     async fn execute<'a>(
         &'a mut self,
         statement_id: u32,
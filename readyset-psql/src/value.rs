@@ -6,7 +6,7 @@ use postgres_types::Kind;
 use ps::util::type_is_oid;
 use ps::PsqlValue;
 use psql_srv as ps;
-use readyset_data::DfValue;
+use readyset_data::{DfValue, Interval};
 use rust_decimal::Decimal;
 use tokio_postgres::types::Type;
 use tracing::{error, trace};
@@ -87,6 +87,9 @@ impl<'a> TryFrom<TypedDfValue<'a>> for PsqlValue {
             (&Type::BYTEA, DfValue::ByteArray(b)) => Ok(PsqlValue::ByteArray(
                 std::sync::Arc::try_unwrap(b).unwrap_or_else(|v| v.as_ref().to_vec()),
             )),
+            (&Type::BYTEA, ref d @ (DfValue::Text(_) | DfValue::TinyText(_))) => Ok(
+                PsqlValue::ByteArray(<&str>::try_from(d).unwrap().as_bytes().to_vec()),
+            ),
             (&Type::MACADDR, DfValue::Text(m)) => Ok(PsqlValue::MacAddress(
                 MacAddress::parse_str(m.as_str())
                     .map_err(|e| ps::Error::ParseError(e.to_string()))?,
@@ -97,9 +100,19 @@ impl<'a> TryFrom<TypedDfValue<'a>> for PsqlValue {
                     .parse::<IpInet>()
                     .map_err(|e| ps::Error::ParseError(e.to_string()))?,
             )),
+            (&Type::UUID, DfValue::Uuid(u)) => Ok(PsqlValue::Uuid(*u)),
             (&Type::UUID, DfValue::Text(u)) => Ok(PsqlValue::Uuid(
                 Uuid::parse_str(u.as_str()).map_err(|e| ps::Error::ParseError(e.to_string()))?,
             )),
+            (&Type::INTERVAL, DfValue::Interval(i)) => Ok(PsqlValue::Interval(**i)),
+            (&Type::INTERVAL, dt @ (DfValue::Text(_) | DfValue::TinyText(_))) => {
+                Ok(PsqlValue::Interval(
+                    <&str>::try_from(&dt)
+                        .unwrap()
+                        .parse::<Interval>()
+                        .map_err(|e| ps::Error::ParseError(e.to_string()))?,
+                ))
+            }
             (&Type::JSON, ref d @ (DfValue::Text(_) | DfValue::TinyText(_))) => {
                 Ok(PsqlValue::Json(
                     <&str>::try_from(d)
@@ -185,6 +198,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn text_bytea() {
+        let val = TypedDfValue {
+            col_type: &Type::BYTEA,
+            value: DfValue::from("abc"),
+        };
+        assert_eq!(
+            PsqlValue::try_from(val).unwrap(),
+            PsqlValue::ByteArray(b"abc".to_vec())
+        );
+    }
+
     #[test]
     fn tiny_text_text() {
         let val = TypedDfValue {
@@ -1,5 +1,13 @@
 use criterion::{criterion_group, criterion_main};
 use dataflow::node::bench::unique_misses;
+use dataflow::ops::bench::{filter_batch, project_batch};
+use dataflow::record_pool_bench::fanout_records;
 
-criterion_group!(benches, unique_misses);
+criterion_group!(
+    benches,
+    unique_misses,
+    filter_batch,
+    project_batch,
+    fanout_records
+);
 criterion_main!(benches);
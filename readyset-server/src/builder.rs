@@ -8,8 +8,8 @@ use std::time::{self, Duration};
 use database_utils::{ReplicationServerId, UpstreamConfig};
 use dataflow::PersistenceParameters;
 use readyset_client::consensus::{
-    Authority, LocalAuthority, LocalAuthorityStore, NodeTypeSchedulingRestriction,
-    WorkerSchedulingConfig,
+    Authority, LabelSelector, LocalAuthority, LocalAuthorityStore, NodeTypeSchedulingRestriction,
+    WorkerLabels, WorkerSchedulingConfig, ZONE_LABEL,
 };
 use readyset_telemetry_reporter::TelemetrySender;
 use readyset_util::shutdown::{self, ShutdownSender};
@@ -17,7 +17,7 @@ use tracing::info;
 
 use crate::controller::replication::ReplicationStrategy;
 use crate::handle::Handle;
-use crate::{Config, FrontierStrategy, ReuseConfigType, VolumeId};
+use crate::{Config, FrontierStrategy, ReuseConfigType, ViewEvictionConfig, VolumeId};
 
 /// Used to construct a worker.
 #[derive(Clone)]
@@ -95,13 +95,59 @@ impl Builder {
         builder.set_background_recovery_interval(Duration::from_secs(
             opts.background_recovery_interval_seconds,
         ));
+        builder.set_worker_heartbeat_interval(Duration::from_secs(
+            opts.worker_heartbeat_interval_seconds,
+        ));
+        builder
+            .set_worker_failure_timeout(Duration::from_secs(opts.worker_failure_timeout_seconds));
+        builder.set_max_ready_replication_lag(Duration::from_secs(
+            opts.max_ready_replication_lag_seconds,
+        ));
+        builder.set_shutdown_deadline(Duration::from_secs(opts.shutdown_deadline_seconds));
+        builder
+            .set_table_profile_interval(Duration::from_secs(opts.table_profile_interval_seconds));
+        builder.set_view_eviction(opts.view_eviction_memory_target.map(|memory_target| {
+            ViewEvictionConfig {
+                interval: Duration::from_secs(opts.view_eviction_interval_seconds),
+                memory_target,
+                min_hit_rate: opts.view_eviction_min_hit_rate,
+            }
+        }));
+
+        builder.set_isolate_domain_panics(opts.isolate_domain_panics);
 
         builder.set_replication_strategy(opts.domain_replication_options.into());
         builder.set_verbose_domain_metrics(opts.verbose_domain_metrics);
+        builder.set_max_tracked_extremum_values(opts.max_tracked_extremum_values);
+        builder.set_slow_replay_threshold(opts.slow_replay_threshold_ms.map(Duration::from_millis));
+        builder.set_log_slow_replays(opts.log_slow_replays);
+        builder.set_profile_replays(opts.profile_replays);
+        builder.set_record_pool_size(opts.record_pool_size);
+        builder.set_operator_threads(opts.operator_threads);
+        builder.set_share_full_state_between_shards(opts.share_full_state_between_shards);
+        builder.set_negative_entry_ttl(opts.negative_entry_ttl_seconds.map(Duration::from_secs));
+        builder.set_max_negative_entries(opts.max_negative_entries);
+        builder.set_prioritize_replays(opts.prioritize_replays);
+        builder.set_max_consecutive_replay_packets(opts.max_consecutive_replay_packets);
+        builder.set_overload_queue_depth(opts.overload_queue_depth);
+        builder.set_overload_lag(opts.overload_lag_ms.map(Duration::from_millis));
+        builder.set_orphaned_replay_timeout(
+            opts.orphaned_replay_timeout_seconds
+                .map(Duration::from_secs),
+        );
 
         if let Some(volume_id) = opts.volume_id {
             builder.set_volume_id(volume_id);
         }
+        if let Some(labels) = opts.worker_labels {
+            builder.set_worker_labels(labels);
+        }
+        if let Some(zone) = opts.zone {
+            builder.set_zone(zone);
+        }
+        if let Some(selector) = opts.base_table_worker_selector {
+            builder.set_base_table_worker_selector(selector);
+        }
 
         let persistence_params = PersistenceParameters::new(
             opts.durability,
@@ -258,6 +304,36 @@ impl Builder {
         self.config.background_recovery_interval = background_recovery_interval;
     }
 
+    /// Set the value of [`Config::worker_heartbeat_interval`]
+    pub fn set_worker_heartbeat_interval(&mut self, worker_heartbeat_interval: Duration) {
+        self.config.worker_heartbeat_interval = worker_heartbeat_interval;
+    }
+
+    /// Set the value of [`Config::worker_failure_timeout`]
+    pub fn set_worker_failure_timeout(&mut self, worker_failure_timeout: Duration) {
+        self.config.worker_failure_timeout = worker_failure_timeout;
+    }
+
+    /// Set the value of [`Config::max_ready_replication_lag`]
+    pub fn set_max_ready_replication_lag(&mut self, max_ready_replication_lag: Duration) {
+        self.config.max_ready_replication_lag = max_ready_replication_lag;
+    }
+
+    /// Set the value of [`Config::shutdown_deadline`]
+    pub fn set_shutdown_deadline(&mut self, shutdown_deadline: Duration) {
+        self.config.shutdown_deadline = shutdown_deadline;
+    }
+
+    /// Set the value of [`Config::table_profile_interval`]
+    pub fn set_table_profile_interval(&mut self, table_profile_interval: Duration) {
+        self.config.table_profile_interval = table_profile_interval;
+    }
+
+    /// Set the value of [`Config::view_eviction`]
+    pub fn set_view_eviction(&mut self, view_eviction: Option<ViewEvictionConfig>) {
+        self.config.view_eviction = view_eviction;
+    }
+
     /// Set the value of [`DomainConfig::aggressively_update_state_sizes`][0]. See the documentation
     /// of that field for more information
     ///
@@ -319,12 +395,37 @@ impl Builder {
         self.domain_scheduling_config.volume_id = Some(volume_id);
     }
 
+    /// Adds the given labels to the labels describing this server, replacing the values of any
+    /// labels which were already set.
+    pub fn set_worker_labels(&mut self, labels: WorkerLabels) {
+        self.domain_scheduling_config.labels.extend(labels);
+    }
+
+    /// Configures the availability zone this server runs in, as its [`ZONE_LABEL`] label.
+    pub fn set_zone(&mut self, zone: String) {
+        self.domain_scheduling_config
+            .labels
+            .insert(ZONE_LABEL.to_owned(), zone);
+    }
+
+    /// Set the value of [`Config::base_table_worker_selector`]. See the documentation of that
+    /// field for more information.
+    pub fn set_base_table_worker_selector(&mut self, selector: LabelSelector) {
+        self.config.base_table_worker_selector = selector;
+    }
+
     /// Set the value of [`Config::abort_on_task_failure`]. See the documentation of that field for
     /// more information.
     pub fn set_abort_on_task_failure(&mut self, abort_on_task_failure: bool) {
         self.config.abort_on_task_failure = abort_on_task_failure;
     }
 
+    /// Set the value of [`Config::isolate_domain_panics`]. See the documentation of that field for
+    /// more information.
+    pub fn set_isolate_domain_panics(&mut self, isolate_domain_panics: bool) {
+        self.config.isolate_domain_panics = isolate_domain_panics;
+    }
+
     /// Sets the value of [`Config::upquery_timeout`]. See documentation of that field for more
     /// information.
     pub fn set_upquery_timeout(&mut self, value: std::time::Duration) {
@@ -343,6 +444,90 @@ impl Builder {
         self.config.domain_config.verbose_metrics = value;
     }
 
+    /// Sets the value of [`Config::domain_config::max_tracked_extremum_values`]. See documentation
+    /// of that field for more information.
+    pub fn set_max_tracked_extremum_values(&mut self, value: usize) {
+        self.config.domain_config.max_tracked_extremum_values = value;
+    }
+
+    /// Sets the value of [`Config::domain_config::slow_replay_threshold`]. See documentation of
+    /// that field for more information.
+    pub fn set_slow_replay_threshold(&mut self, value: Option<std::time::Duration>) {
+        self.config.domain_config.slow_replay_threshold = value;
+    }
+
+    /// Sets the value of [`Config::domain_config::log_slow_replays`]. See documentation of
+    /// that field for more information.
+    pub fn set_log_slow_replays(&mut self, value: bool) {
+        self.config.domain_config.log_slow_replays = value;
+    }
+
+    /// Sets the value of [`Config::domain_config::profile_replays`]. See documentation of
+    /// that field for more information.
+    pub fn set_profile_replays(&mut self, value: bool) {
+        self.config.domain_config.profile_replays = value;
+    }
+
+    /// Sets the value of [`Config::domain_config::record_pool_size`]. See documentation of
+    /// that field for more information.
+    pub fn set_record_pool_size(&mut self, value: usize) {
+        self.config.domain_config.record_pool_size = value;
+    }
+
+    /// Sets the value of [`Config::domain_config::operator_threads`]. See documentation of
+    /// that field for more information.
+    pub fn set_operator_threads(&mut self, value: usize) {
+        self.config.domain_config.operator_threads = value;
+    }
+
+    /// Sets the value of [`Config::domain_config::share_full_state_between_shards`]. See
+    /// documentation of that field for more information.
+    pub fn set_share_full_state_between_shards(&mut self, value: bool) {
+        self.config.domain_config.share_full_state_between_shards = value;
+    }
+
+    /// Sets the value of [`Config::domain_config::negative_entry_ttl`]. See documentation of
+    /// that field for more information.
+    pub fn set_negative_entry_ttl(&mut self, value: Option<std::time::Duration>) {
+        self.config.domain_config.negative_entry_ttl = value;
+    }
+
+    /// Sets the value of [`Config::domain_config::max_negative_entries`]. See documentation of
+    /// that field for more information.
+    pub fn set_max_negative_entries(&mut self, value: Option<usize>) {
+        self.config.domain_config.max_negative_entries = value;
+    }
+
+    /// Sets the value of [`Config::domain_config::prioritize_replays`]. See documentation of
+    /// that field for more information.
+    pub fn set_prioritize_replays(&mut self, value: bool) {
+        self.config.domain_config.prioritize_replays = value;
+    }
+
+    /// Sets the value of [`Config::domain_config::max_consecutive_replay_packets`]. See
+    /// documentation of that field for more information.
+    pub fn set_max_consecutive_replay_packets(&mut self, value: usize) {
+        self.config.domain_config.max_consecutive_replay_packets = value;
+    }
+
+    /// Sets the value of [`Config::domain_config::overload_queue_depth`]. See documentation of
+    /// that field for more information.
+    pub fn set_overload_queue_depth(&mut self, value: Option<usize>) {
+        self.config.domain_config.overload_queue_depth = value;
+    }
+
+    /// Sets the value of [`Config::domain_config::overload_lag`]. See documentation of that field
+    /// for more information.
+    pub fn set_overload_lag(&mut self, value: Option<std::time::Duration>) {
+        self.config.domain_config.overload_lag = value;
+    }
+
+    /// Sets the value of [`Config::domain_config::orphaned_replay_timeout`]. See documentation of
+    /// that field for more information.
+    pub fn set_orphaned_replay_timeout(&mut self, value: Option<std::time::Duration>) {
+        self.config.domain_config.orphaned_replay_timeout = value;
+    }
+
     /// Sets the value of [`Config::domain_config::table_request_timeout`]. See documentation of
     /// that field for more information.
     pub fn set_table_request_timeout(&mut self, value: std::time::Duration) {
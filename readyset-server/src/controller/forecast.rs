@@ -0,0 +1,365 @@
+//! Forecasting of the size of the state of the nodes a migration would materialize, before the
+//! migration is run, as part of [`DfState::plan_migration`](super::state::DfState::plan_migration).
+//!
+//! The number of rows in each node is estimated from the number of rows in its parents, starting
+//! from the row counts of the base tables, using a simple rule for each kind of operator:
+//!
+//! * Filters keep a fixed fraction ([`FILTER_SELECTIVITY`]) of their input
+//! * Grouped and latest operators emit one row per distinct value of their group columns
+//! * TopK operators emit up to `k` rows per distinct value of their group columns
+//! * Inner joins emit `|L|·|R| / max(d(L), d(R))` rows, where `d` is the number of distinct values
+//!   of the join key in each parent and rows with a NULL join key are excluded from `|L|` and
+//!   `|R|`. Left joins emit at least one row per row in their left parent.
+//! * Unions emit all the rows of all of their parents
+//! * All other operators emit one row per input row
+//!
+//! The number of distinct values and NULLs in a column are estimated from the [`StateProfile`] of
+//! the base table the column's values are copied from, if any (see the
+//! [`profiler`](super::profiler) module). Columns whose values are computed by an operator are
+//! assumed to be distinct and non-NULL in every row.
+//!
+//! The size of a row is estimated from the average size of the values in the columns of the base
+//! tables the node reads from.
+
+use std::collections::HashMap;
+
+use dataflow::ops::join::JoinType;
+use dataflow::ops::NodeOperator;
+use dataflow::prelude::*;
+use dataflow::StateProfile;
+use readyset_client::debug::info::{KeyCount, NodeMaterializedSize, NodeSize};
+
+/// The fraction of its input that a filter is assumed to emit
+const FILTER_SELECTIVITY: f64 = 0.5;
+
+/// The size assumed for values in columns of base tables which are empty
+const DEFAULT_VALUE_BYTES: f64 = 16.0;
+
+/// What we know about the contents of a base table
+#[derive(Debug, Clone, Default)]
+pub(super) struct BaseTableStats {
+    /// The size of the base table, as reported by its domain
+    pub(super) size: NodeSize,
+    /// A profile of the data in the base table's columns, if one could be taken
+    pub(super) profile: Option<StateProfile>,
+}
+
+impl BaseTableStats {
+    fn rows(&self) -> f64 {
+        let rows = match self.size.key_count {
+            KeyCount::ExactKeyCount(n) | KeyCount::EstimatedRowCount(n) => n,
+            KeyCount::ExternalMaterialization => 0,
+        };
+        // The row counts of persistent base tables are estimates, which can be lower than the
+        // number of rows we actually saw
+        rows.max(self.profile.as_ref().map_or(0, |profile| profile.rows)) as f64
+    }
+
+    /// Estimate the number of distinct values in the given column of the base table
+    fn distinct(&self, column: usize) -> f64 {
+        let rows = self.rows();
+        match &self.profile {
+            Some(profile) => match profile.columns.get(column) {
+                Some(col) => estimate_distinct(profile.rows, col.distinct, col.singletons, rows),
+                None => rows,
+            },
+            None => rows,
+        }
+    }
+
+    /// Estimate the fraction of the values in the given column of the base table which are NULL
+    fn null_fraction(&self, column: usize) -> f64 {
+        match &self.profile {
+            Some(profile) if profile.rows > 0 => profile
+                .columns
+                .get(column)
+                .map_or(0.0, |col| col.nulls as f64 / profile.rows as f64),
+            _ => 0.0,
+        }
+    }
+}
+
+/// Estimate the number of distinct values in a column of `total_rows` rows from the number of
+/// `distinct` values, of which `singletons` occurred exactly once, in a sample of `sample_rows` of
+/// those rows, using the "Duj1" estimator of Haas et al., "Sampling-Based Estimation of the Number
+/// of Distinct Values of an Attribute" (VLDB 1995):
+///
+/// ```text
+/// n·d / (n - f1 + f1·n/N)
+/// ```
+pub(super) fn estimate_distinct(
+    sample_rows: usize,
+    distinct: usize,
+    singletons: usize,
+    total_rows: f64,
+) -> f64 {
+    let (n, d, f1) = (sample_rows as f64, distinct as f64, singletons as f64);
+    if sample_rows == 0 || n >= total_rows {
+        // The sample was the whole table
+        return d;
+    }
+    let estimate = (n * d) / (n - f1 + f1 * n / total_rows);
+    estimate.clamp(d, total_rows)
+}
+
+/// The estimated contents of a single node
+#[derive(Debug, Clone, Copy)]
+struct Estimate {
+    rows: f64,
+    /// The average size of a single value in one of the node's columns
+    value_bytes: f64,
+}
+
+/// Forecasts the size of the state of nodes in a graph. See the [module docs](self) for more
+/// information.
+pub(super) struct Forecaster<'a> {
+    graph: &'a Graph,
+    base_tables: &'a HashMap<NodeIndex, BaseTableStats>,
+    estimates: HashMap<NodeIndex, Estimate>,
+}
+
+impl<'a> Forecaster<'a> {
+    pub(super) fn new(
+        graph: &'a Graph,
+        base_tables: &'a HashMap<NodeIndex, BaseTableStats>,
+    ) -> Self {
+        Self {
+            graph,
+            base_tables,
+            estimates: Default::default(),
+        }
+    }
+
+    /// Forecast the number of rows in and size of the state of the given node
+    pub(super) fn forecast(&mut self, ni: NodeIndex) -> NodeSize {
+        let estimate = self.estimate(ni);
+        #[allow(clippy::indexing_slicing)] // nodes passed in come from the graph
+        let num_columns = self.graph[ni].columns().len() as f64;
+        NodeSize {
+            key_count: KeyCount::EstimatedRowCount(estimate.rows.round() as usize),
+            bytes: NodeMaterializedSize(
+                (estimate.rows * num_columns * estimate.value_bytes).round() as usize,
+            ),
+        }
+    }
+
+    fn parents(&self, ni: NodeIndex) -> Vec<NodeIndex> {
+        #[allow(clippy::indexing_slicing)] // nodes passed in come from the graph
+        let node = &self.graph[ni];
+        match node.ancestors() {
+            // Use the ancestors of internal nodes, so that the parents of joins are in order
+            Ok(ancestors) => ancestors,
+            Err(_) => self
+                .graph
+                .neighbors_directed(ni, petgraph::EdgeDirection::Incoming)
+                .collect(),
+        }
+    }
+
+    fn rows(&mut self, ni: NodeIndex) -> f64 {
+        self.estimate(ni).rows
+    }
+
+    fn estimate(&mut self, ni: NodeIndex) -> Estimate {
+        if let Some(estimate) = self.estimates.get(&ni) {
+            return *estimate;
+        }
+
+        let graph = self.graph;
+        #[allow(clippy::indexing_slicing)] // nodes passed in come from the graph
+        let node = &graph[ni];
+        let estimate = if node.is_source() {
+            Estimate {
+                rows: 0.0,
+                value_bytes: DEFAULT_VALUE_BYTES,
+            }
+        } else if node.is_base() {
+            let stats = self.base_tables.get(&ni).cloned().unwrap_or_default();
+            let rows = stats.rows();
+            let num_columns = node.columns().len() as f64;
+            let value_bytes = if rows > 0.0 && num_columns > 0.0 {
+                stats.size.bytes.0 as f64 / rows / num_columns
+            } else {
+                DEFAULT_VALUE_BYTES
+            };
+            Estimate { rows, value_bytes }
+        } else {
+            let parents = self.parents(ni);
+            let parent_estimates = parents
+                .iter()
+                .map(|p| self.estimate(*p))
+                .collect::<Vec<_>>();
+            let value_bytes = if parent_estimates.is_empty() {
+                DEFAULT_VALUE_BYTES
+            } else {
+                parent_estimates.iter().map(|e| e.value_bytes).sum::<f64>()
+                    / parent_estimates.len() as f64
+            };
+            let parent_rows = parent_estimates.first().map_or(0.0, |e| e.rows);
+
+            let rows = match (node.as_internal(), parents.as_slice()) {
+                (Some(NodeOperator::Filter(_)), _) => parent_rows * FILTER_SELECTIVITY,
+                (Some(NodeOperator::Aggregation(op)), [parent]) => {
+                    self.distinct_group(*parent, op.group_by())
+                }
+                (Some(NodeOperator::Extremum(op)), [parent]) => {
+                    self.distinct_group(*parent, op.group_by())
+                }
+                (Some(NodeOperator::Concat(op)), [parent]) => {
+                    self.distinct_group(*parent, op.group_by())
+                }
+                (Some(NodeOperator::ApproxCountDistinct(op)), [parent]) => {
+                    self.distinct_group(*parent, op.group_by())
+                }
+                (Some(NodeOperator::TopK(op)), [parent]) => {
+                    (self.distinct_group(*parent, op.group_by()) * op.k() as f64).min(parent_rows)
+                }
+                (Some(NodeOperator::Latest(op)), [parent]) => {
+                    self.distinct_group(*parent, op.group_by())
+                }
+                (Some(NodeOperator::Join(op)), [left, right]) => {
+                    let (left_key, right_key): (Vec<_>, Vec<_>) = op.on().iter().copied().unzip();
+                    let left_rows = self.rows(*left);
+                    let right_rows = self.rows(*right);
+                    // Rows with a NULL join key never match any rows on the other side
+                    let left_matchable = left_rows * (1.0 - self.null_fraction(*left, &left_key));
+                    let right_matchable =
+                        right_rows * (1.0 - self.null_fraction(*right, &right_key));
+                    let key_distinct = self
+                        .distinct_group(*left, &left_key)
+                        .max(self.distinct_group(*right, &right_key))
+                        .max(1.0);
+                    let rows = left_matchable * right_matchable / key_distinct;
+                    match op.kind() {
+                        JoinType::Inner => rows,
+                        JoinType::Left => rows.max(left_rows),
+                    }
+                }
+                (Some(NodeOperator::Union(_)), _) => parent_estimates.iter().map(|e| e.rows).sum(),
+                _ => parent_rows,
+            };
+
+            Estimate { rows, value_bytes }
+        };
+
+        self.estimates.insert(ni, estimate);
+        estimate
+    }
+
+    /// Estimate the number of distinct values of the given column of the given node
+    fn distinct(&mut self, ni: NodeIndex, column: usize) -> f64 {
+        let rows = self.rows(ni);
+        let graph = self.graph;
+        #[allow(clippy::indexing_slicing)] // nodes passed in come from the graph
+        let node = &graph[ni];
+
+        if node.is_base() {
+            return self
+                .base_tables
+                .get(&ni)
+                .map_or(rows, |stats| stats.distinct(column))
+                .min(rows);
+        }
+
+        if !node.is_internal() {
+            // Ingress, egress, reader and sharder nodes pass their input through unchanged
+            return match self.parents(ni).as_slice() {
+                [parent] => self.distinct(*parent, column).min(rows),
+                _ => rows,
+            };
+        }
+
+        match node.column_source(&[column]) {
+            ColumnSource::ExactCopy(ColumnRef {
+                node: parent,
+                columns,
+            }) => match columns.first() {
+                Some(col) => self.distinct(parent, *col).min(rows),
+                None => rows,
+            },
+            ColumnSource::Union(refs) => refs
+                .iter()
+                .filter_map(|r| Some((r.node, *r.columns.first()?)))
+                .map(|(parent, col)| self.distinct(parent, col))
+                .sum::<f64>()
+                .min(rows),
+            // Assume computed values are all distinct
+            ColumnSource::GeneratedFromColumns(_) | ColumnSource::RequiresFullReplay(_) => rows,
+        }
+    }
+
+    /// Find the base table column that the values of the given column of the given node are copied
+    /// from, if any
+    fn base_column(&self, ni: NodeIndex, column: usize) -> Option<(NodeIndex, usize)> {
+        let node = self.graph.node_weight(ni)?;
+        if node.is_base() {
+            return Some((ni, column));
+        }
+        if !node.is_internal() {
+            return match self.parents(ni).as_slice() {
+                [parent] => self.base_column(*parent, column),
+                _ => None,
+            };
+        }
+        match node.column_source(&[column]) {
+            ColumnSource::ExactCopy(ColumnRef {
+                node: parent,
+                columns,
+            }) => self.base_column(parent, *columns.first()?),
+            _ => None,
+        }
+    }
+
+    /// Estimate the fraction of the rows of the given node in which any of the given columns are
+    /// NULL, assuming the columns are independent
+    fn null_fraction(&self, ni: NodeIndex, columns: &[usize]) -> f64 {
+        let non_null = columns
+            .iter()
+            .filter_map(|col| self.base_column(ni, *col))
+            .map(|(base, col)| {
+                1.0 - self
+                    .base_tables
+                    .get(&base)
+                    .map_or(0.0, |stats| stats.null_fraction(col))
+            })
+            .product::<f64>();
+        1.0 - non_null
+    }
+
+    /// Estimate the number of distinct combinations of values of the given columns of the given
+    /// node, assuming the columns are independent
+    fn distinct_group(&mut self, ni: NodeIndex, columns: &[usize]) -> f64 {
+        let rows = self.rows(ni);
+        if columns.is_empty() {
+            // Grouping by nothing emits a single row
+            return rows.min(1.0);
+        }
+        columns
+            .iter()
+            .map(|col| self.distinct(ni, *col))
+            .product::<f64>()
+            .min(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_distinct_from_whole_table() {
+        assert_eq!(estimate_distinct(100, 10, 0, 100.0), 10.0);
+    }
+
+    #[test]
+    fn estimate_distinct_all_unique() {
+        // Every sampled value occurred once, so assume the column is unique
+        assert_eq!(estimate_distinct(100, 100, 100, 10_000.0), 10_000.0);
+    }
+
+    #[test]
+    fn estimate_distinct_no_singletons() {
+        // Every sampled value occurred more than once, so assume we've seen all the values
+        assert_eq!(estimate_distinct(100, 5, 0, 10_000.0), 5.0);
+    }
+}
@@ -8,7 +8,8 @@
 )]
 
 use std::collections::{HashMap, HashSet};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::iter;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
@@ -18,16 +19,24 @@ use failpoint_macros::failpoint;
 use futures::future::Fuse;
 use futures::{Future, FutureExt};
 use hyper::Method;
-use metrics::gauge;
-use nom_sql::Relation;
+use metrics::{counter, gauge};
+use nom_sql::{Column, Relation, SqlIdentifier};
 use readyset_client::consensus::{Authority, AuthorityControl};
 use readyset_client::debug::stats::PersistentStats;
 use readyset_client::internal::ReplicaAddress;
 use readyset_client::metrics::recorded;
 use readyset_client::recipe::{ExtendRecipeResult, ExtendRecipeSpec, MigrationStatus};
-use readyset_client::status::{ReadySetControllerStatus, SnapshotStatus};
-use readyset_client::{GraphvizOptions, SingleKeyEviction, ViewCreateRequest, WorkerDescriptor};
-use readyset_errors::{internal_err, ReadySetError, ReadySetResult};
+use readyset_client::replication_namespace::ReplicationNamespace;
+use readyset_client::runtime_config::RuntimeConfigUpdate;
+use readyset_client::status::{
+    ReadySetControllerStatus, RecoveryStatus, ReplicationPauseState, SnapshotStatus,
+};
+use readyset_client::{
+    GraphvizOptions, SingleKeyEviction, ViewCreateRequest, ViewRateLimit, ViewResultLimit,
+    ViewStaleReads, WorkerDescriptor,
+};
+use readyset_data::DfValue;
+use readyset_errors::{bad_request_err, internal_err, ReadySetError, ReadySetResult};
 use readyset_telemetry_reporter::TelemetrySender;
 use readyset_util::futures::abort_on_panic;
 use readyset_util::shutdown::ShutdownReceiver;
@@ -39,11 +48,14 @@ use slotmap::{DefaultKey, Key, KeyData, SlotMap};
 use tokio::select;
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use tokio::sync::{watch, Mutex};
-use tokio::task::JoinHandle;
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
+use crate::controller::migration_queue::{MigrationQueue, MigrationResult, MigrationTracker};
+use crate::controller::profiler::TableProfiler;
 use crate::controller::state::{DfState, DfStateHandle};
+use crate::controller::view_eviction::{ViewEvictionConfig, ViewEvictor};
+use crate::controller::worker_monitor::{WorkerMonitor, WorkerMonitorConfig};
 use crate::controller::{ControllerState, Worker, WorkerIdentifier};
 use crate::worker::WorkerRequestKind;
 
@@ -51,9 +63,18 @@ use crate::worker::WorkerRequestKind;
 /// let it run in the background and return [`ExtendRecipeResult::Pending`].
 const EXTEND_RECIPE_MAX_SYNC_TIME: Duration = Duration::from_secs(5);
 
+/// Maximum amount of time for a `pause_replication` request to wait for the replicator to finish
+/// any in-flight transaction and pause, before returning [`ReplicationPauseState::Pausing`].
+const PAUSE_REPLICATION_MAX_WAIT_TIME: Duration = Duration::from_secs(3);
+
 /// A handle to a migration running in the background. Used as part of
 /// [`Leader::running_migrations`].
-type RunningMigration = Fuse<JoinHandle<ReadySetResult<()>>>;
+struct RunningMigration {
+    /// Future which resolves to the result of the migration once it's finished
+    result: Fuse<MigrationResult>,
+    /// Tracker used to query the progress of the migration, and to cancel it
+    tracker: MigrationTracker,
+}
 
 /// The ReadySet leader, responsible for making control-plane decisions for the whole of a ReadySet
 /// cluster.
@@ -77,19 +98,49 @@ pub struct Leader {
     background_recovery_interval: Duration,
     /// Are we currently trying to run recovery in the background?
     background_recovery_running: Arc<AtomicBool>,
+    /// Configuration for the heartbeats we send to workers to detect when they've failed
+    worker_monitor_config: WorkerMonitorConfig,
+    /// Channel used by the worker monitor to notify the controller of workers which have stopped
+    /// responding to heartbeats
+    failed_workers_tx: UnboundedSender<Vec<WorkerIdentifier>>,
+    /// Number of workers that have failed since we became the leader
+    failed_workers: AtomicU64,
+    /// Interval on which to profile the data in every base table
+    table_profile_interval: Duration,
+    /// Configuration for automatically deactivating caches with a low hit rate while memory is
+    /// constrained, if enabled
+    view_eviction: Option<ViewEvictionConfig>,
+    /// Whether replication from the upstream database was healthy as of the last time the
+    /// replicator reported on it, sent to all workers so that their readers can flag stale results
+    replication_healthy: AtomicBool,
 
     /// Whether to log statements received by the replicators
     replicator_statement_logging: bool,
     /// Configuration for the replicator
     pub(super) replicator_config: UpstreamConfig,
+    /// Whether replication has been paused via a request to `/pause_replication`. Shared with the
+    /// replication task, which watches it to know when to pause, and records the offset it paused
+    /// at here.
+    replication_pause_state: Arc<watch::Sender<ReplicationPauseState>>,
+    /// Channel used to send messages to the replicator for the primary upstream database, such as
+    /// requests to resnapshot a table via `/resnapshot_table`
+    controller_tx: UnboundedSender<ControllerMessage>,
+    /// Channels used to send messages to the replicators for each of the additional upstream
+    /// databases, along with the namespace each one replicates into
+    additional_controller_txs: Vec<(ReplicationNamespace, UnboundedSender<ControllerMessage>)>,
     /// A client to the current authority.
     pub(super) authority: Arc<Authority>,
 
+    /// Queue of migrations requested via `/extend_recipe`, which are run one at a time in the
+    /// order they were received.
+    migration_queue: MigrationQueue,
+
     /// A map of currently running migrations.
     ///
     /// Requests to `/extend_recipe` run for at least [`EXTEND_RECIPE_MAX_SYNC_TIME`], after which
-    /// a handle to the running migration is placed here, where it can be queried via an rpc to
-    /// `/migration_status`.
+    /// a handle to the running migration is placed here, where its result can be queried via an
+    /// rpc to `/migration_status`, its progress via `/migration_progress`, and where it can be
+    /// cancelled via `/cancel_migration`.
     running_migrations: Mutex<SlotMap<DefaultKey, RunningMigration>>,
 
     /// A channel that will be notified if a background task for the controller fails
@@ -128,6 +179,34 @@ impl Leader {
             warn!(%error, "Failed to persist stats in the Authority");
         }
 
+        tokio::spawn(
+            WorkerMonitor::new(
+                self.worker_monitor_config,
+                Arc::downgrade(&self.dataflow_state_handle),
+                self.failed_workers_tx.clone(),
+            )
+            .run(shutdown_rx.clone()),
+        );
+
+        tokio::spawn(
+            TableProfiler::new(
+                self.table_profile_interval,
+                Arc::downgrade(&self.dataflow_state_handle),
+            )
+            .run(shutdown_rx.clone()),
+        );
+
+        if let Some(config) = self.view_eviction {
+            tokio::spawn(
+                ViewEvictor::new(
+                    config,
+                    Arc::downgrade(&self.dataflow_state_handle),
+                    Arc::clone(&self.authority),
+                )
+                .run(shutdown_rx.clone()),
+            );
+        }
+
         // When the controller becomes the leader, we need to read updates
         // from the binlog.
         self.start_replication_task(
@@ -150,7 +229,7 @@ impl Leader {
         notification_channel: UnboundedSender<ReplicatorMessage>,
         controller_channel: UnboundedReceiver<ControllerMessage>,
         telemetry_sender: TelemetrySender,
-        mut shutdown_rx: ShutdownReceiver,
+        shutdown_rx: ShutdownReceiver,
     ) {
         if self.replicator_config.upstream_db_url.is_none() {
             // Controller must be notified that snapshot is completed even though we don't have an
@@ -163,10 +242,110 @@ impl Leader {
             return;
         }
 
+        let additional_configs = match self.replicator_config.additional_upstream_configs() {
+            Ok(configs) => configs,
+            Err(error) => {
+                let _ = notification_channel.send(ReplicatorMessage::UnrecoverableError(error));
+                return;
+            }
+        };
+
+        let primary_namespace = ReplicationNamespace::Primary {
+            additional: additional_configs
+                .iter()
+                .filter_map(|config| config.replication_namespace.as_deref())
+                .map(SqlIdentifier::from)
+                .collect(),
+        };
+
+        if additional_configs.is_empty() {
+            self.spawn_replicator(
+                self.replicator_config.clone(),
+                primary_namespace,
+                notification_channel,
+                controller_channel,
+                telemetry_sender,
+                shutdown_rx,
+            );
+            return;
+        }
+
+        // Each replicator snapshots independently, but the controller should only be told that
+        // snapshotting is done once all the upstream databases have been snapshotted
+        let num_replicators = additional_configs.len() + 1;
+        let (replicator_tx, mut replicator_rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut snapshotted = HashSet::new();
+            while let Some((idx, message)) = replicator_rx.recv().await {
+                if let ReplicatorMessage::SnapshotDone = message {
+                    snapshotted.insert(idx);
+                    if snapshotted.len() < num_replicators {
+                        continue;
+                    }
+                }
+                if notification_channel.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let configs = iter::once((self.replicator_config.clone(), primary_namespace)).chain(
+            additional_configs.into_iter().map(|config| {
+                let namespace = config.replication_namespace.as_deref().unwrap_or_default();
+                let namespace = ReplicationNamespace::Additional(namespace.into());
+                (config, namespace)
+            }),
+        );
+        let mut primary_controller_channel = Some(controller_channel);
+        for (idx, (config, namespace)) in configs.enumerate() {
+            let controller_channel = match primary_controller_channel.take() {
+                Some(controller_channel) => controller_channel,
+                None => {
+                    let (controller_tx, controller_rx) = mpsc::unbounded_channel();
+                    self.additional_controller_txs
+                        .push((namespace.clone(), controller_tx));
+                    controller_rx
+                }
+            };
+            let (notification_tx, mut notification_rx) = mpsc::unbounded_channel();
+            let replicator_tx = replicator_tx.clone();
+            tokio::spawn(async move {
+                while let Some(message) = notification_rx.recv().await {
+                    if replicator_tx.send((idx, message)).is_err() {
+                        break;
+                    }
+                }
+            });
+
+            self.spawn_replicator(
+                config,
+                namespace,
+                notification_tx,
+                controller_channel,
+                telemetry_sender.clone(),
+                shutdown_rx.clone(),
+            );
+        }
+    }
+
+    /// Spawn a task which replicates from the upstream database configured in `config` into the
+    /// given namespace, restarting replication on any recoverable error
+    fn spawn_replicator(
+        &self,
+        config: UpstreamConfig,
+        namespace: ReplicationNamespace,
+        notification_channel: UnboundedSender<ReplicatorMessage>,
+        mut controller_channel: UnboundedReceiver<ControllerMessage>,
+        telemetry_sender: TelemetrySender,
+        mut shutdown_rx: ShutdownReceiver,
+    ) {
         let authority = Arc::clone(&self.authority);
-        let replicator_restart_timeout = self.replicator_config.replicator_restart_timeout;
-        let config = self.replicator_config.clone();
+        let replicator_restart_timeout = config.replicator_restart_timeout;
+        let release_slot_config = config
+            .drop_replication_slot_on_shutdown
+            .then(|| config.clone());
         let replicator_statement_logging = self.replicator_statement_logging;
+        let pause_state = Arc::clone(&self.replication_pause_state);
 
         // The replication task ideally won't panic, but if it does and we arent replicating, that
         // will mean the data we return, will be more and more stale, and the transaction logs on
@@ -178,14 +357,16 @@ impl Leader {
                 // resnapshot to capture changes made to replication-tables.
                 let mut server_startup = true;
                 loop {
-                    let noria: readyset_client::ReadySetHandle =
+                    let mut noria: readyset_client::ReadySetHandle =
                         readyset_client::ReadySetHandle::new(Arc::clone(&authority)).await;
+                    noria.set_replication_namespace(namespace.clone());
 
                     match replicators::NoriaAdapter::start(
                         noria,
                         config.clone(),
                         &notification_channel,
-                        &controller_channel,
+                        &mut controller_channel,
+                        &pause_state,
                         telemetry_sender.clone(),
                         server_startup,
                         replicator_statement_logging,
@@ -202,6 +383,15 @@ impl Leader {
                             }
                             break;
                         }
+                        Err(ReadySetError::ReplicationPaused) => {
+                            info!("Replication paused, waiting for it to be resumed");
+                            // Won't error, since we hold a reference to the sender
+                            let _ = pause_state
+                                .subscribe()
+                                .wait_for(|state| *state == ReplicationPauseState::Running)
+                                .await;
+                            info!("Resuming replication");
+                        }
                         Err(error) => {
                             // On each replication error we wait for `replicator_restart_timeout`
                             // then try again
@@ -222,9 +412,16 @@ impl Leader {
                 }
             };
 
-            tokio::select! {
-                _ = replication_future => {},
-                _ = shutdown_rx.recv() => {},
+            let shut_down = tokio::select! {
+                _ = replication_future => false,
+                _ = shutdown_rx.recv() => true,
+            };
+
+            // The replicator has been dropped by now, closing its replication connection
+            if let Some(config) = release_slot_config.filter(|_| shut_down) {
+                if let Err(error) = replicators::release_replication_slot(&config).await {
+                    warn!(%error, "Failed to drop replication slot on shutdown");
+                }
             }
         }));
     }
@@ -347,6 +544,133 @@ impl Leader {
                 };
                 return_serialized!(res);
             }
+            (&Method::POST, "/update_runtime_config") => {
+                let update: RuntimeConfigUpdate = bincode::deserialize(&body)?;
+                update.validate()?;
+                // Persist the update before sending it anywhere, so that workers and domains are
+                // never running with configuration that a new leader wouldn't know about. If
+                // propagating the update fails partway, sending it again is harmless.
+                {
+                    let mut writer = self.dataflow_state_handle.write().await;
+                    writer.as_mut().record_runtime_config_update(update.clone());
+                    self.dataflow_state_handle.commit(writer, authority).await?;
+                }
+                info!(?update, "Updated runtime config");
+
+                self.dataflow_state_handle
+                    .read()
+                    .await
+                    .propagate_runtime_config_update(&update)
+                    .await?;
+                return_serialized!(());
+            }
+            (&Method::GET | &Method::POST, "/runtime_config_changes") => {
+                let ds = self.dataflow_state_handle.read().await;
+                return_serialized!(ds.runtime_config_changes);
+            }
+            (&Method::POST, "/pause_replication") => {
+                if self.replicator_config.upstream_db_url.is_none() {
+                    return Err(bad_request_err(
+                        "Cannot pause replication without an upstream database",
+                    ));
+                }
+                self.set_replication_paused(true, authority).await?;
+                let mut pause_rx = self.replication_pause_state.subscribe();
+                self.replication_pause_state.send_if_modified(|state| {
+                    if *state == ReplicationPauseState::Running {
+                        *state = ReplicationPauseState::Pausing;
+                        true
+                    } else {
+                        false
+                    }
+                });
+                let res = match tokio::time::timeout(
+                    PAUSE_REPLICATION_MAX_WAIT_TIME,
+                    pause_rx
+                        .wait_for(|state| matches!(state, ReplicationPauseState::Paused { .. })),
+                )
+                .await
+                {
+                    Ok(Ok(state)) => state.clone(),
+                    _ => self.replication_pause_state.borrow().clone(),
+                };
+                return_serialized!(res);
+            }
+            (&Method::POST, "/resume_replication") => {
+                self.set_replication_paused(false, authority).await?;
+                self.replication_pause_state.send_if_modified(|state| {
+                    if state.is_paused() {
+                        *state = ReplicationPauseState::Running;
+                        true
+                    } else {
+                        false
+                    }
+                });
+                return_serialized!(());
+            }
+            (&Method::GET | &Method::POST, "/replication_pause_state") => {
+                return_serialized!(*self.replication_pause_state.borrow());
+            }
+            (&Method::POST, "/resnapshot_table") => {
+                require_leader_ready()?;
+                if self.replicator_config.upstream_db_url.is_none() {
+                    return Err(bad_request_err(
+                        "Cannot resnapshot a table without an upstream database",
+                    ));
+                }
+                let table: Relation = bincode::deserialize(&body)?;
+                if !self
+                    .dataflow_state_handle
+                    .read()
+                    .await
+                    .tables()
+                    .contains_key(&table)
+                {
+                    return Err(ReadySetError::TableNotFound {
+                        name: table.name.clone().into(),
+                        schema: table.schema.clone().map(Into::into),
+                    });
+                }
+                // Replicators refer to tables by their names in their own upstream database
+                let (table, controller_tx) = self
+                    .additional_controller_txs
+                    .iter()
+                    .find_map(|(namespace, tx)| Some((namespace.to_upstream(&table)?, tx)))
+                    .unwrap_or((table, &self.controller_tx));
+                controller_tx
+                    .send(ControllerMessage::ResnapshotTable { table })
+                    .map_err(|_| internal_err!("Replicator is not running"))?;
+                return_serialized!(());
+            }
+            (&Method::POST, "/drain_worker") => {
+                require_leader_ready()?;
+                let worker: WorkerIdentifier = bincode::deserialize(&body)?;
+                return_serialized!(self.drain_worker(&worker).await?);
+            }
+            (&Method::GET | &Method::POST, "/recovery_status") => {
+                let ds = self.dataflow_state_handle.read().await;
+                return_serialized!(RecoveryStatus {
+                    failed_workers: self.failed_workers.load(Ordering::Acquire),
+                    total_domains: ds.domain_nodes.len(),
+                    unplaced_domains: ds.unplaced_domain_nodes().len(),
+                    recovery_running: self.background_recovery_running.load(Ordering::Acquire),
+                });
+            }
+            #[cfg(feature = "failure_injection")]
+            (&Method::POST, "/set_packet_faults") => {
+                let faults: Vec<readyset_client::failpoints::PacketFault> =
+                    bincode::deserialize(&body)?;
+                let res: Result<(), ReadySetError> = {
+                    let ds = self.dataflow_state_handle.read().await;
+                    for (_, worker) in ds.workers.iter() {
+                        worker
+                            .rpc::<()>(WorkerRequestKind::SetPacketFaults(faults.clone()))
+                            .await?;
+                    }
+                    Ok(())
+                };
+                return_serialized!(res);
+            }
             (&Method::GET | &Method::POST, "/version") => {
                 return_serialized!(RELEASE_VERSION);
             }
@@ -373,6 +697,14 @@ impl Leader {
                 let ds = self.dataflow_state_handle.read().await;
                 return_serialized!(ds.verbose_views())
             }
+            (&Method::GET, "/schema") => {
+                let ds = self.dataflow_state_handle.read().await;
+                Ok(serde_json::to_vec(&ds.schema()?)?)
+            }
+            (&Method::POST, "/schema") => {
+                let ds = self.dataflow_state_handle.read().await;
+                return_serialized!(ds.schema()?)
+            }
             (&Method::POST, "/view_names") => {
                 let (queries, dialect): (Vec<ViewCreateRequest>, _) = bincode::deserialize(&body)?;
                 gauge!(
@@ -525,6 +857,17 @@ impl Leader {
                 state_copy.extend_recipe(body, true).await?;
                 return_serialized!(ExtendRecipeResult::Done);
             }
+            (&Method::POST, "/plan_migration") => {
+                let body: ExtendRecipeSpec = bincode::deserialize(&body)?;
+                if body.require_leader_ready {
+                    require_leader_ready()?;
+                }
+                let mut state_copy: DfState = {
+                    let reader = self.dataflow_state_handle.read().await;
+                    reader.clone()
+                };
+                return_serialized!(state_copy.plan_migration(body.changes).await?);
+            }
             (&Method::GET | &Method::POST, "/adapter_rewrite_params") => {
                 let ds = self.dataflow_state_handle.read().await;
                 let supports = ds.recipe.adapter_rewrite_params();
@@ -537,6 +880,87 @@ impl Leader {
                 return_serialized!(key);
             }
 
+            (&Method::POST, "/trace_key") => {
+                let (view, key): (Relation, Vec<DfValue>) = bincode::deserialize(&body)?;
+                let ds = self.dataflow_state_handle.read().await;
+                return_serialized!(ds.trace_key(&view, key).await?);
+            }
+            (&Method::POST, "/view_metrics") => {
+                let view: Relation = bincode::deserialize(&body)?;
+                let ds = self.dataflow_state_handle.read().await;
+                return_serialized!(ds.view_metrics(&view).await?);
+            }
+            (&Method::POST, "/view_replay_paths") => {
+                let view: Relation = bincode::deserialize(&body)?;
+                let ds = self.dataflow_state_handle.read().await;
+                return_serialized!(ds.view_replay_paths(&view).await?);
+            }
+            (&Method::POST, "/view_hot_keys") => {
+                let view: Relation = bincode::deserialize(&body)?;
+                let ds = self.dataflow_state_handle.read().await;
+                return_serialized!(ds.view_hot_keys(&view).await?);
+            }
+            (&Method::POST, "/set_view_rate_limit") => {
+                let (view, limit): (Relation, ViewRateLimit) = bincode::deserialize(&body)?;
+                let mut writer = self.dataflow_state_handle.write().await;
+                writer.as_mut().set_view_rate_limit(&view, limit).await?;
+                self.dataflow_state_handle.commit(writer, authority).await?;
+                return_serialized!(());
+            }
+            (&Method::POST, "/set_view_history_retention") => {
+                let (view, retention): (Relation, Option<Duration>) = bincode::deserialize(&body)?;
+                let mut writer = self.dataflow_state_handle.write().await;
+                writer
+                    .as_mut()
+                    .set_view_history_retention(&view, retention)
+                    .await?;
+                self.dataflow_state_handle.commit(writer, authority).await?;
+                return_serialized!(());
+            }
+            (&Method::POST, "/set_view_stale_reads") => {
+                let (view, stale_reads): (Relation, ViewStaleReads) = bincode::deserialize(&body)?;
+                let mut writer = self.dataflow_state_handle.write().await;
+                writer
+                    .as_mut()
+                    .set_view_stale_reads(&view, stale_reads)
+                    .await?;
+                self.dataflow_state_handle.commit(writer, authority).await?;
+                return_serialized!(());
+            }
+            (&Method::POST, "/set_view_result_limit") => {
+                let (view, limit): (Relation, ViewResultLimit) = bincode::deserialize(&body)?;
+                let mut writer = self.dataflow_state_handle.write().await;
+                writer.as_mut().set_view_result_limit(&view, limit).await?;
+                self.dataflow_state_handle.commit(writer, authority).await?;
+                return_serialized!(());
+            }
+            (&Method::POST, "/set_view_upquery_timeout") => {
+                let (view, timeout): (Relation, Option<Duration>) = bincode::deserialize(&body)?;
+                let mut writer = self.dataflow_state_handle.write().await;
+                writer
+                    .as_mut()
+                    .set_view_upquery_timeout(&view, timeout)
+                    .await?;
+                self.dataflow_state_handle.commit(writer, authority).await?;
+                return_serialized!(());
+            }
+            (&Method::POST, "/set_view_secondary_indices") => {
+                let (view, indices): (Relation, Vec<Vec<SqlIdentifier>>) =
+                    bincode::deserialize(&body)?;
+                let mut writer = self.dataflow_state_handle.write().await;
+                let reader = writer.as_mut().set_view_secondary_indices(&view, indices)?;
+                self.dataflow_state_handle.commit(writer, authority).await?;
+                // Only tell the domains about the new indices once they've been persisted, so
+                // that they're never maintained on a reader without being recorded in the
+                // dataflow state
+                let ds = self.dataflow_state_handle.read().await;
+                ds.send_reader_secondary_indices(reader).await?;
+                return_serialized!(());
+            }
+            (&Method::GET | &Method::POST, "/slow_replays") => {
+                let ds = self.dataflow_state_handle.read().await;
+                return_serialized!(ds.slow_replays().await?);
+            }
             (&Method::GET, "/flush_partial") => {
                 let ret = {
                     let mut writer = self.dataflow_state_handle.write().await;
@@ -553,20 +977,17 @@ impl Leader {
                 }
                 let concurrently = body.concurrently;
                 let ret = {
-                    // Start the migration running in the background
-                    let dataflow_state_handle = Arc::clone(&self.dataflow_state_handle);
-                    let authority = Arc::clone(authority);
-                    let mut migration = tokio::spawn(async move {
-                        let mut writer = dataflow_state_handle.write().await;
-                        writer.as_mut().extend_recipe(body, false).await?;
-                        dataflow_state_handle.commit(writer, &authority).await?;
-                        Ok(())
-                    })
-                    .fuse();
+                    // Queue the migration to run in the background, after any migrations
+                    // requested before it have finished
+                    let (tracker, result) = self.migration_queue.enqueue(body);
+                    let mut migration = RunningMigration {
+                        result: result.fuse(),
+                        tracker,
+                    };
 
                     // If a non-blocking migration is requested, return immediately. We do not want
                     // to handle this case inside the select!, with a timeout of 0, in the (very
-                    // unlikely) event that the migration task yields first.
+                    // unlikely) event that the migration finishes first.
                     if concurrently {
                         let mut running_migrations = self.running_migrations.lock().await;
                         let migration_id = running_migrations.insert(migration);
@@ -576,7 +997,7 @@ impl Leader {
                         // EXTEND_RECIPE_MAX_SYNC_TIME), or we place it in `self.running_migrations`
                         // and return a `Pending` result.
                         select! {
-                            res = &mut migration => {
+                            res = &mut migration.result => {
                                 res.map_err(|e| internal_err!("{e}"))?.map(|_| ExtendRecipeResult::Done)
                             }
                             _ = sleep(EXTEND_RECIPE_MAX_SYNC_TIME) => {
@@ -594,11 +1015,11 @@ impl Leader {
                 let migration_key = DefaultKey::from(KeyData::from_ffi(migration_id));
                 let ret = {
                     let mut running_migrations = self.running_migrations.lock().await;
-                    let mut migration: &mut RunningMigration = running_migrations
+                    let migration: &mut RunningMigration = running_migrations
                         .get_mut(migration_key)
                         .ok_or_else(|| ReadySetError::UnknownMigration(migration_id))?;
 
-                    match (&mut migration).now_or_never() {
+                    match (&mut migration.result).now_or_never() {
                         None => MigrationStatus::Pending,
                         Some(res) => {
                             // Migration is done, remove it from the map
@@ -614,6 +1035,32 @@ impl Leader {
                 };
                 return_serialized!(ret)
             }
+            (&Method::POST, "/migration_progress") => {
+                let migration_id: u64 = bincode::deserialize(&body)?;
+                let migration_key = DefaultKey::from(KeyData::from_ffi(migration_id));
+                let ret = self
+                    .running_migrations
+                    .lock()
+                    .await
+                    .get(migration_key)
+                    .ok_or_else(|| ReadySetError::UnknownMigration(migration_id))?
+                    .tracker
+                    .progress();
+                return_serialized!(ret)
+            }
+            (&Method::POST, "/cancel_migration") => {
+                let migration_id: u64 = bincode::deserialize(&body)?;
+                let migration_key = DefaultKey::from(KeyData::from_ffi(migration_id));
+                self.running_migrations
+                    .lock()
+                    .await
+                    .get(migration_key)
+                    .ok_or_else(|| ReadySetError::UnknownMigration(migration_id))?
+                    .tracker
+                    .cancel();
+                info!(%migration_id, "Cancelling migration");
+                return_serialized!(())
+            }
             (&Method::POST, "/remove_query") => {
                 require_leader_ready()?;
                 let query_name = bincode::deserialize(&body)?;
@@ -629,6 +1076,71 @@ impl Leader {
                 self.dataflow_state_handle.commit(writer, authority).await?;
                 return_serialized!(ReadySetResult::Ok(()));
             }
+            (&Method::POST, "/stage_recipe_version") => {
+                let body: ExtendRecipeSpec = bincode::deserialize(&body)?;
+                if body.require_leader_ready {
+                    require_leader_ready()?;
+                }
+                let mut writer = self.dataflow_state_handle.write().await;
+                let version = writer.as_mut().stage_recipe_version(body.changes).await?;
+                self.dataflow_state_handle.commit(writer, authority).await?;
+                return_serialized!(version);
+            }
+            (&Method::POST, "/activate_recipe_version") => {
+                require_leader_ready()?;
+                let version = bincode::deserialize(&body)?;
+                let mut writer = self.dataflow_state_handle.write().await;
+                writer.as_mut().activate_recipe_version(version).await?;
+                self.dataflow_state_handle.commit(writer, authority).await?;
+                return_serialized!(());
+            }
+            (&Method::POST, "/rollback_to") => {
+                require_leader_ready()?;
+                let version = bincode::deserialize(&body)?;
+                let mut writer = self.dataflow_state_handle.write().await;
+                writer.as_mut().rollback_recipe_to(version).await?;
+                self.dataflow_state_handle.commit(writer, authority).await?;
+                return_serialized!(());
+            }
+            (&Method::POST, "/deactivate_view") => {
+                require_leader_ready()?;
+                let view: Relation = bincode::deserialize(&body)?;
+                let mut writer = self.dataflow_state_handle.write().await;
+                writer.as_mut().deactivate_view(&view, None).await?;
+                self.dataflow_state_handle.commit(writer, authority).await?;
+                info!(view = %view.display_unquoted(), "Deactivated view");
+                return_serialized!(());
+            }
+            (&Method::POST, "/reactivate_view") => {
+                require_leader_ready()?;
+                let (view, dialect): (Relation, _) = bincode::deserialize(&body)?;
+                let mut writer = self.dataflow_state_handle.write().await;
+                writer.as_mut().reactivate_view(&view, dialect).await?;
+                self.dataflow_state_handle.commit(writer, authority).await?;
+                return_serialized!(());
+            }
+            (&Method::GET | &Method::POST, "/deactivated_views") => {
+                let ds = self.dataflow_state_handle.read().await;
+                return_serialized!(ds.deactivated_views());
+            }
+            (&Method::GET | &Method::POST, "/recipe_versions") => {
+                let ds = self.dataflow_state_handle.read().await;
+                return_serialized!(ds.recipe_versions());
+            }
+            (&Method::GET | &Method::POST, "/list_queries") => {
+                let ds = self.dataflow_state_handle.read().await;
+                return_serialized!(ds.list_queries());
+            }
+            (&Method::POST, "/column_provenance") => {
+                let view: Relation = bincode::deserialize(&body)?;
+                let ds = self.dataflow_state_handle.read().await;
+                return_serialized!(ds.column_provenance(&view)?);
+            }
+            (&Method::POST, "/column_dependents") => {
+                let column: Column = bincode::deserialize(&body)?;
+                let ds = self.dataflow_state_handle.read().await;
+                return_serialized!(ds.column_dependents(&column));
+            }
             (&Method::POST, "/set_schema_replication_offset") => {
                 let body: Option<ReplicationOffset> = bincode::deserialize(&body)?;
                 let mut writer = self.dataflow_state_handle.write().await;
@@ -636,6 +1148,16 @@ impl Leader {
                 self.dataflow_state_handle.commit(writer, authority).await?;
                 return_serialized!(ReadySetResult::Ok(()));
             }
+            (&Method::POST, "/set_namespace_schema_replication_offset") => {
+                let (namespace, offset): (SqlIdentifier, Option<ReplicationOffset>) =
+                    bincode::deserialize(&body)?;
+                let mut writer = self.dataflow_state_handle.write().await;
+                writer
+                    .as_mut()
+                    .set_namespace_schema_replication_offset(namespace, offset);
+                self.dataflow_state_handle.commit(writer, authority).await?;
+                return_serialized!(ReadySetResult::Ok(()));
+            }
             (&Method::POST, "/remove_node") => {
                 require_leader_ready()?;
                 let body = bincode::deserialize(&body)?;
@@ -668,7 +1190,13 @@ impl Leader {
                 ..
             } = desc;
 
-            info!(%worker_uri, %reader_addr, "received registration payload from worker");
+            info!(
+                %worker_uri,
+                %reader_addr,
+                volume_id = ?domain_scheduling_config.volume_id,
+                labels = ?domain_scheduling_config.labels,
+                "received registration payload from worker"
+            );
 
             let ws = Worker::new(
                 worker_uri.clone(),
@@ -698,6 +1226,19 @@ impl Leader {
                 );
             }
 
+            // And whether replication is healthy, for views with stale reads enabled
+            let healthy = self.replication_healthy.load(Ordering::Acquire);
+            if let Err(e) = ws
+                .rpc::<()>(WorkerRequestKind::SetReplicationHealthy(healthy))
+                .await
+            {
+                error!(
+                    %worker_uri,
+                    %e,
+                    "Worker could not be reached and was not updated on replication health",
+                );
+            }
+
             ds.workers.insert(worker_uri.clone(), ws);
             ds.read_addrs.insert(worker_uri, reader_addr);
 
@@ -770,10 +1311,17 @@ impl Leader {
         let ds = writer.as_mut();
 
         // Remove references to the worker from any internal state, collecting downstream domains to
-        // kill
+        // kill. The same worker can be reported as failed both by the authority and by the worker
+        // monitor, so skip any workers we've already removed.
         let mut downstream_domains = HashSet::new();
         for wi in failed {
+            if !ds.workers.contains_key(&wi) {
+                debug!(worker = %wi, "Ignoring failure of worker that was already removed");
+                continue;
+            }
             warn!(worker = %wi, "handling failure of worker");
+            self.failed_workers.fetch_add(1, Ordering::AcqRel);
+            counter!(recorded::CONTROLLER_FAILED_WORKERS, 1);
             for di in ds.remove_worker(&wi) {
                 downstream_domains.extend(ds.downstream_domains(di.domain_index)?);
             }
@@ -787,6 +1335,11 @@ impl Leader {
             ds.kill_domains(downstream_domains).await?;
         }
 
+        gauge!(
+            recorded::CONTROLLER_UNPLACED_DOMAINS,
+            ds.unplaced_domain_nodes().len() as f64
+        );
+
         if !ds.all_replicas_placed()
             && !self
                 .background_recovery_running
@@ -825,6 +1378,10 @@ impl Leader {
                     let mut writer = dataflow_state_handle.write().await;
                     let ds = writer.as_mut();
                     let domain_nodes = ds.unplaced_domain_nodes();
+                    gauge!(
+                        recorded::CONTROLLER_UNPLACED_DOMAINS,
+                        domain_nodes.len() as f64
+                    );
                     if domain_nodes.is_empty() {
                         // All replicas placed! Stop trying to recover
                         break;
@@ -850,6 +1407,87 @@ impl Leader {
             .await
     }
 
+    /// Stop scheduling domains onto the given worker, and move all the domain replicas running on
+    /// it onto other workers, returning the number of domain replicas which were moved. As when a
+    /// worker fails, all domains downstream of the moved replicas are restarted along with them.
+    ///
+    /// The worker stays registered, but no domains are scheduled onto it until it re-registers.
+    async fn drain_worker(&self, worker: &WorkerIdentifier) -> ReadySetResult<usize> {
+        let mut writer = self.dataflow_state_handle.write().await;
+        let ds = writer.as_mut();
+
+        ds.workers
+            .get_mut(worker)
+            .ok_or_else(|| bad_request_err(format!("Unknown worker {worker}")))?
+            .draining = true;
+
+        let drained = ds
+            .domains
+            .values()
+            .flat_map(|dh| dh.assignments().filter(|(_, wi)| *wi == worker))
+            .map(|(addr, _)| addr)
+            .collect::<Vec<_>>();
+
+        let mut domains = HashSet::new();
+        for addr in &drained {
+            domains.insert(addr.domain_index);
+            domains.extend(ds.downstream_domains(addr.domain_index)?);
+        }
+
+        if !domains.is_empty() {
+            info!(
+                %worker,
+                num_replicas = drained.len(),
+                num_domains = domains.len(),
+                "Draining worker"
+            );
+            ds.kill_domains(domains.iter().copied()).await?;
+
+            #[allow(clippy::indexing_slicing)] // Internal data structure invariant
+            let domain_nodes: HashMap<_, HashSet<_>> = domains
+                .into_iter()
+                .map(|d| (d, ds.domain_nodes[&d].values().copied().collect()))
+                .collect();
+            let dmp = ds.plan_recovery(&domain_nodes).await?;
+            if !dmp.failed_placement().is_empty() {
+                warn!(
+                    %worker,
+                    num_unplaced_domains = dmp.failed_placement().len(),
+                    "Could not move some domains off of drained worker"
+                );
+            }
+            dmp.apply(ds).await?;
+        }
+
+        self.dataflow_state_handle
+            .commit(writer, &self.authority)
+            .await?;
+        Ok(drained.len())
+    }
+
+    /// Record whether replication from the upstream database is healthy, and inform all workers
+    /// so that reads against views with stale reads enabled are flagged as stale while it isn't.
+    /// Workers which can't be reached are updated when they next register.
+    pub(super) async fn set_replication_healthy(&self, healthy: bool) {
+        if self.replication_healthy.swap(healthy, Ordering::AcqRel) == healthy {
+            return;
+        }
+
+        let ds = self.dataflow_state_handle.read().await;
+        for (worker_uri, worker) in ds.workers.iter() {
+            if let Err(error) = worker
+                .rpc::<()>(WorkerRequestKind::SetReplicationHealthy(healthy))
+                .await
+            {
+                warn!(
+                    %worker_uri,
+                    %error,
+                    "Worker could not be reached and was not updated on replication health",
+                );
+            }
+        }
+    }
+
     pub(super) async fn handle_failed_domain(&self, addr: ReplicaAddress) -> ReadySetResult<()> {
         // It's important that this happens in the background not just for parallelism /
         // performance, but because the worker thread blocks on this RPC completing before it can
@@ -919,6 +1557,22 @@ impl Leader {
         Ok(())
     }
 
+    /// Persist whether replication has been paused by an operator, so that a new leader picks up
+    /// where we left off. This is done before signalling the replication task, so that we never
+    /// pause (or resume) replication without a new leader knowing about it.
+    async fn set_replication_paused(
+        &self,
+        paused: bool,
+        authority: &Arc<Authority>,
+    ) -> ReadySetResult<()> {
+        let mut writer = self.dataflow_state_handle.write().await;
+        if writer.as_ref().replication_paused != paused {
+            writer.as_mut().replication_paused = paused;
+            self.dataflow_state_handle.commit(writer, authority).await?;
+        }
+        Ok(())
+    }
+
     /// Spawn a background task which notifies the controller if it fails or panics
     async fn spawn_background_task<F>(&self, fut: F)
     where
@@ -953,10 +1607,24 @@ impl Leader {
         replicator_config: UpstreamConfig,
         worker_request_timeout: Duration,
         background_recovery_interval: Duration,
+        worker_heartbeat_interval: Duration,
+        worker_failure_timeout: Duration,
+        failed_workers_tx: UnboundedSender<Vec<WorkerIdentifier>>,
+        controller_tx: UnboundedSender<ControllerMessage>,
     ) -> Self {
         assert_ne!(state.config.min_workers, 0);
 
+        // If replication was paused under a previous leader, pause it again as soon as the
+        // replication task starts
+        let (replication_pause_state, _) =
+            watch::channel(if state.dataflow_state.replication_paused {
+                ReplicationPauseState::Pausing
+            } else {
+                ReplicationPauseState::Running
+            });
         let dataflow_state_handle = Arc::new(DfStateHandle::new(state.dataflow_state));
+        let migration_queue =
+            MigrationQueue::new(Arc::clone(&dataflow_state_handle), Arc::clone(&authority));
 
         Leader {
             dataflow_state_handle,
@@ -966,10 +1634,23 @@ impl Leader {
 
             replicator_statement_logging,
             replicator_config,
+            replication_pause_state: Arc::new(replication_pause_state),
+            controller_tx,
+            additional_controller_txs: vec![],
             authority,
             worker_request_timeout,
             background_recovery_interval,
             background_recovery_running: Arc::new(AtomicBool::new(false)),
+            worker_monitor_config: WorkerMonitorConfig {
+                heartbeat_interval: worker_heartbeat_interval,
+                failure_timeout: worker_failure_timeout,
+            },
+            failed_workers_tx,
+            failed_workers: AtomicU64::new(0),
+            table_profile_interval: state.config.table_profile_interval,
+            view_eviction: state.config.view_eviction,
+            replication_healthy: AtomicBool::new(true),
+            migration_queue,
             running_migrations: Default::default(),
             background_task_failed,
             running_recovery: None,
@@ -186,10 +186,7 @@ pub fn assign(dataflow_state: &mut DfState, new_nodes: &[NodeIndex]) -> ReadySet
                                 // same domain. Otherwise, the domain would not be placed
                                 // on a valid server.
                                 (Some(new_node), Some(existing_node)) => {
-                                    // A server can only have one worker_volume, as a
-                                    // result, these two nodes should require the same
-                                    // worker_volume.
-                                    new_node.worker_volume == existing_node.worker_volume
+                                    new_node.compatible_with(existing_node)
                                 }
                                 // If we have placement restrictions, don't place the node
                                 // in a domain without. We technically can if the worker
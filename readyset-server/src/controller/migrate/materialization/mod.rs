@@ -11,6 +11,7 @@ use bimap::BiHashMap;
 use dataflow::prelude::*;
 use dataflow::{DomainRequest, LookupIndex};
 use petgraph::graph::NodeIndex;
+pub use readyset_client::recipe::FrontierStrategy;
 use readyset_errors::{internal, internal_err, invariant, ReadySetError, ReadySetResult};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info_span, trace};
@@ -28,24 +29,6 @@ pub(crate) struct InvalidEdge {
     pub child: NodeIndex,
 }
 
-/// Strategy for determining which (partial) materializations should be placed beyond the
-/// materialization frontier.
-///
-/// Note that no matter what this is set to, all nodes whose name starts with `SHALLOW_` will be
-/// placed beyond the frontier.
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Default)]
-pub enum FrontierStrategy {
-    /// Place no nodes beyond the frontier (this is the default).
-    #[default]
-    None,
-    /// Place all partial materializations beyond the frontier.
-    AllPartial,
-    /// Place all partial readers beyond the frontier.
-    Readers,
-    /// Place all nodes whose name contain the given string beyond the frontier.
-    Match(String),
-}
-
 #[derive(Debug)]
 enum IndexObligation {
     /// An obligation to index a particular set of columns with a particular index type in a node.
@@ -158,6 +141,11 @@ pub(in crate::controller) struct Materializations {
     #[serde(skip)]
     partial: HashSet<NodeIndex>,
 
+    /// Overrides of [`Config::frontier_strategy`] for individual nodes being added in the current
+    /// migration.
+    #[serde(skip)]
+    frontier_strategy_overrides: HashMap<NodeIndex, FrontierStrategy>,
+
     pub(in crate::controller) tag_generator: usize,
 
     pub(crate) config: Config,
@@ -180,6 +168,8 @@ impl Materializations {
 
             partial: HashSet::default(),
 
+            frontier_strategy_overrides: HashMap::default(),
+
             tag_generator: 0,
 
             config: Default::default(),
@@ -191,6 +181,23 @@ impl Materializations {
         self.config = config;
     }
 
+    /// Set the per-node overrides of the configured frontier strategy to use for all future
+    /// materializations
+    pub(in crate::controller) fn set_frontier_strategy_overrides(
+        &mut self,
+        overrides: HashMap<NodeIndex, FrontierStrategy>,
+    ) {
+        self.frontier_strategy_overrides = overrides;
+    }
+
+    /// Returns the frontier strategy to use for the given node, taking per-node overrides into
+    /// account
+    fn frontier_strategy(&self, ni: NodeIndex) -> &FrontierStrategy {
+        self.frontier_strategy_overrides
+            .get(&ni)
+            .unwrap_or(&self.config.frontier_strategy)
+    }
+
     /// Does this partial node have a fully materialized duplicate?
     pub(in crate::controller) fn get_redundant(&self, idx: &NodeIndex) -> Option<&NodeIndex> {
         self.redundant_partial.get(idx)
@@ -203,6 +210,19 @@ impl Materializations {
     ) {
         self.redundant_partial.extend(new_duplicates);
     }
+
+    /// Forget about the materializations and replay paths of the given nodes, which have been
+    /// removed from the graph
+    pub(in crate::controller) fn remove_nodes(&mut self, removed: &HashSet<NodeIndex>) {
+        for ni in removed {
+            self.have.remove(ni);
+            self.had.remove(ni);
+            self.partial.remove(ni);
+            self.paths.remove(ni);
+        }
+        self.redundant_partial
+            .retain(|partial, full| !removed.contains(partial) && !removed.contains(full));
+    }
 }
 
 impl Materializations {
@@ -234,6 +254,28 @@ impl Materializations {
         self.have.get(&ni)
     }
 
+    /// Add a new index on the given columns to the state of the existing base node `ni`, for
+    /// example because an index was created on its table in the upstream database after the base
+    /// node was added.
+    pub(in crate::controller) fn add_base_index(
+        &mut self,
+        graph: &mut Graph,
+        ni: NodeIndex,
+        index: Index,
+        dmp: &mut DomainMigrationPlan,
+    ) -> ReadySetResult<()> {
+        #[allow(clippy::indexing_slicing)] // ni must be in the graph
+        if !graph[ni].is_base() {
+            internal!("add_base_index called with non-base node {}", ni.index());
+        }
+        if self.have.entry(ni).or_default().insert(index.clone()) {
+            debug!(node = %ni.index(), ?index, "adding index to existing base");
+            self.added.entry(ni).or_default().insert(index);
+            self.commit(graph, &HashSet::new(), dmp)?;
+        }
+        Ok(())
+    }
+
     /// Is the given node partially materialized?
     ///
     /// Note that this method returns `false` if the node is fully materialized, *or* if it's not
@@ -688,6 +730,7 @@ impl Materializations {
 
         // Mark nodes as beyond the frontier as dictated by the strategy
         for &ni in new {
+            let frontier_strategy = self.frontier_strategy(ni);
             #[allow(clippy::unwrap_used)] // graph must contain nodes in new
             let n = graph.node_weight_mut(ni).unwrap();
 
@@ -700,7 +743,7 @@ impl Materializations {
             // name matching, we don't do that since MIR will sometimes place the name of identity
             // nodes and the like. It's up to the user to make sure they don't match node names
             // that are, say, above a full materialization.
-            if let FrontierStrategy::Match(ref m) = self.config.frontier_strategy {
+            if let FrontierStrategy::Match(ref m) = frontier_strategy {
                 n.purge = n.purge || n.name().name.contains(m);
                 continue;
             }
@@ -714,9 +757,9 @@ impl Materializations {
                 continue;
             }
 
-            if let FrontierStrategy::AllPartial = self.config.frontier_strategy {
+            if let FrontierStrategy::AllPartial = frontier_strategy {
                 n.purge = true;
-            } else if let FrontierStrategy::Readers = self.config.frontier_strategy {
+            } else if let FrontierStrategy::Readers = frontier_strategy {
                 n.purge = n.purge || n.is_reader();
             }
         }
@@ -29,9 +29,15 @@
 //! of a much larger problem (such as nodes being partitioned), seeing as the only things that
 //! happen during application are domains being spun up and messages being sent.
 //!
+//! The one exception is a migration which is cancelled (via its
+//! [`MigrationTracker`](crate::controller::migration_queue::MigrationTracker)) during the
+//! apply stage, in which case the domains started and nodes added so far are removed again before
+//! the migration fails.
+//!
 //! Beware, Here be slightly smaller dragons™
 
 use std::collections::{hash_map, BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use array2::Array2;
@@ -40,27 +46,35 @@ use dataflow::prelude::*;
 use dataflow::{node, DomainRequest, ReaderProcessing};
 use metrics::{counter, histogram};
 use nom_sql::Relation;
+use readyset_client::debug::info::{
+    MigrationPlanSummary, NodeSize, PlannedDomain, PlannedNode, PlannedReplay,
+};
 use readyset_client::metrics::recorded;
+use readyset_client::recipe::MigrationPhase;
 use readyset_client::{KeyColumnIdx, ViewPlaceholder};
 use readyset_data::{DfType, Dialect};
 use tokio::time::sleep;
 use tokio_retry::strategy::ExponentialBackoff;
 use tracing::{debug, debug_span, error, info, info_span, instrument, trace};
 
-use crate::controller::migrate::materialization::InvalidEdge;
+use crate::controller::domain_handle::DomainHandle;
+use crate::controller::migrate::materialization::{FrontierStrategy, InvalidEdge};
 use crate::controller::migrate::node_changes::{MigrationNodeChanges, NodeChanges};
 use crate::controller::migrate::scheduling::Scheduler;
 use crate::controller::state::DfState;
-use crate::controller::WorkerIdentifier;
+use crate::controller::{Worker, WorkerIdentifier};
 
 pub(crate) mod assignment;
 mod augmentation;
 pub(crate) mod materialization;
 pub(in crate::controller) mod node_changes;
+mod observer;
 pub(in crate::controller) mod routing;
 pub(in crate::controller) mod scheduling;
 mod sharding;
 
+pub use self::observer::MigrationObserver;
+
 /// The base delay used when sending follow up requests to a domain, for the exponential backoff
 /// strategy
 const DOMAIN_REQUEST_DELAY_BASE_BACKOFF_MS: u64 = 2;
@@ -134,6 +148,11 @@ impl StoredDomainRequest {
 
                 invariant!(self.shard.is_none()); // QueryReplayDone isn't ever sent to just one shard
 
+                let rows_before = mainline.migration_tracker.as_ref().map(|tracker| {
+                    tracker.set_phase(MigrationPhase::Replaying);
+                    tracker.progress().rows_replayed
+                });
+
                 let mut spins = 0;
                 let mut non_completed_replicas: BTreeSet<_> = match placed_replicas(self.domain)? {
                     Some(replicas) => replicas.into_iter().collect(),
@@ -181,6 +200,17 @@ impl StoredDomainRequest {
                         break;
                     }
 
+                    if let (Some(tracker), Some(rows_before)) =
+                        (&mainline.migration_tracker, rows_before)
+                    {
+                        let rows = replayed_rows(dom, node, &mainline.workers).await?;
+                        tracker.update(|progress| progress.rows_replayed = rows_before + rows);
+                        tracker.check_cancelled()?;
+                    }
+                    if let Some(observer) = &mainline.migration_observer {
+                        observer.replay_waiting(self.domain, node)?;
+                    }
+
                     spins += 1;
                     if spins == 10 {
                         info!(
@@ -191,6 +221,19 @@ impl StoredDomainRequest {
                     }
                     std::thread::sleep(Duration::from_millis(200));
                 }
+
+                if let (Some(tracker), Some(rows_before)) =
+                    (&mainline.migration_tracker, rows_before)
+                {
+                    let rows = replayed_rows(dom, node, &mainline.workers).await?;
+                    tracker.update(|progress| {
+                        progress.rows_replayed = rows_before + rows;
+                        progress.replays_done += 1;
+                    });
+                }
+                if let Some(observer) = &mainline.migration_observer {
+                    observer.replay_finished(self.domain, node)?;
+                }
             }
             DomainRequest::RemoveNodes { .. } => {
                 match dom.send_to_healthy::<()>(self.req, &mainline.workers).await {
@@ -275,6 +318,108 @@ impl StoredDomainRequest {
     }
 }
 
+/// Returns the number of rows the given domain has received so far as part of the full replay to
+/// `node`, summed across all shards of the domain
+async fn replayed_rows(
+    dom: &DomainHandle,
+    node: LocalNodeIndex,
+    workers: &HashMap<WorkerIdentifier, Worker>,
+) -> ReadySetResult<usize> {
+    Ok(dom
+        .send_to_healthy::<usize>(DomainRequest::QueryReplayProgress { node }, workers)
+        .await?
+        .rows()
+        .map(|replicas| replicas.iter().flatten().copied().max().unwrap_or(0))
+        .sum())
+}
+
+/// Changes made to the running domains while applying a [`DomainMigrationPlan`], recorded so that
+/// they can be undone if the migration is cancelled before it finishes.
+#[derive(Default)]
+struct AppliedChanges {
+    /// Domains which were started by the plan
+    placed_domains: HashSet<DomainIndex>,
+    /// Nodes which were added to domains which were already running, in the order they were added
+    added_nodes: Vec<(DomainIndex, LocalNodeIndex)>,
+    /// Egress nodes in domains which were already running which were told to send to a new
+    /// ingress node
+    egress_txs: Vec<(DomainIndex, LocalNodeIndex, NodeIndex)>,
+}
+
+impl AppliedChanges {
+    /// Record that the given request is about to be sent to a domain
+    fn record(&mut self, req: &StoredDomainRequest) {
+        if self.placed_domains.contains(&req.domain) {
+            // Removed wholesale when the domain is shut down
+            return;
+        }
+
+        match &req.req {
+            DomainRequest::AddNode { node, .. } => {
+                self.added_nodes.push((req.domain, node.local_addr()));
+            }
+            DomainRequest::AddEgressTx {
+                egress_node,
+                ingress_node: (ingress_node, _),
+                ..
+            } => {
+                self.egress_txs
+                    .push((req.domain, *egress_node, *ingress_node));
+            }
+            _ => {}
+        }
+    }
+
+    /// Undo all the recorded changes, leaving the running domains as they were before the plan
+    /// was applied.
+    ///
+    /// Any indices or replay paths added to existing nodes by the plan are left in place, since
+    /// they're harmless once all the nodes that need them are gone.
+    async fn undo(self, mainline: &mut DfState) -> ReadySetResult<()> {
+        let no_placed_replicas = HashMap::new();
+
+        for (domain, egress_node, ingress_node) in self.egress_txs {
+            StoredDomainRequest {
+                domain,
+                shard: None,
+                req: DomainRequest::RemoveEgressTx {
+                    egress_node,
+                    ingress_node,
+                },
+            }
+            .apply(mainline, &no_placed_replicas)
+            .await?;
+        }
+
+        // Nodes are added parents-first, so removing them in reverse order removes them
+        // leaves-first
+        let mut nodes_per_domain: HashMap<DomainIndex, Vec<LocalNodeIndex>> = HashMap::new();
+        for (domain, node) in self.added_nodes.into_iter().rev() {
+            nodes_per_domain.entry(domain).or_default().push(node);
+        }
+        for (domain, nodes) in nodes_per_domain {
+            StoredDomainRequest {
+                domain,
+                shard: None,
+                req: DomainRequest::RemoveNodes { nodes },
+            }
+            .apply(mainline, &no_placed_replicas)
+            .await?;
+        }
+
+        if !self.placed_domains.is_empty() {
+            mainline
+                .kill_domains(self.placed_domains.iter().copied())
+                .await?;
+            for domain in self.placed_domains {
+                mainline.domains.remove(&domain);
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// A request to place a new domain, corresponding to the arguments passed to
 /// [`Leader::place_domain`].
 ///
@@ -334,12 +479,16 @@ pub struct DomainMigrationPlan {
     failed_placement: Vec<ReplicaAddress>,
     /// A map of valid domain indices to the settings for that domain.
     domains: HashMap<DomainIndex, DomainSettings>,
+    /// Domains which no longer have any nodes, to shut down after all stored requests have been
+    /// sent.
+    kill: HashSet<DomainIndex>,
 }
 
 /// A set of stored data sufficient to apply a migration.
 pub struct MigrationPlan<'df> {
     dataflow_state: &'df mut DfState,
     dmp: DomainMigrationPlan,
+    observer: Option<Arc<dyn MigrationObserver>>,
 }
 
 impl<'df> MigrationPlan<'df> {
@@ -353,6 +502,7 @@ impl<'df> MigrationPlan<'df> {
         let MigrationPlan {
             dataflow_state,
             dmp,
+            observer,
         } = self;
 
         debug!(
@@ -363,11 +513,16 @@ impl<'df> MigrationPlan<'df> {
 
         let start = Instant::now();
 
-        match dmp.apply(dataflow_state).await {
+        dataflow_state.migration_observer = observer;
+        let res = dmp.apply(dataflow_state).await;
+        dataflow_state.migration_observer = None;
+
+        match res {
             Ok(_) => {
                 debug!(ms = %start.elapsed().as_millis(), "migration plan applied");
                 Ok(())
             }
+            Err(ReadySetError::MigrationCancelled) => Err(ReadySetError::MigrationCancelled),
             Err(e) => {
                 error!(error = %e, "migration plan apply failed");
                 Err(ReadySetError::MigrationApplyFailed {
@@ -376,6 +531,90 @@ impl<'df> MigrationPlan<'df> {
             }
         }
     }
+
+    /// Describe the changes this plan would make to the graph, without applying it.
+    ///
+    /// `first_new_node` is the index of the first node added by the migration, and `node_sizes`
+    /// holds the sizes of the materialized nodes in the graph before the migration, which are used
+    /// to estimate the size of the replays the migration would perform.
+    pub fn summarize(
+        &self,
+        first_new_node: NodeIndex,
+        node_sizes: &HashMap<NodeIndex, NodeSize>,
+    ) -> MigrationPlanSummary {
+        let dataflow_state = &*self.dataflow_state;
+
+        let added_nodes = dataflow_state
+            .ingredients
+            .node_indices()
+            .filter(|&ni| ni >= first_new_node)
+            .filter_map(|ni| {
+                #[allow(clippy::indexing_slicing)] // came from node_indices()
+                let node = &dataflow_state.ingredients[ni];
+                (!node.is_dropped()).then(|| PlannedNode {
+                    node_index: ni,
+                    node_name: node.name().clone(),
+                    node_description: node.description(true),
+                    domain: node.domain(),
+                    materialized: node.is_reader()
+                        || dataflow_state.materializations.indexes_for(ni).is_some(),
+                    partial: dataflow_state.materializations.is_partial(ni),
+                })
+            })
+            .collect();
+
+        let new_domains = self
+            .dmp
+            .place
+            .iter()
+            .map(|place| {
+                let (num_shards, num_replicas) = place.shard_replica_workers.shape();
+                PlannedDomain {
+                    domain: place.idx,
+                    num_shards,
+                    num_replicas,
+                    nodes: place.nodes.clone(),
+                }
+            })
+            .collect();
+
+        let replays = self
+            .dmp
+            .stored
+            .iter()
+            .filter_map(|stored| match stored.req {
+                DomainRequest::StartReplay { tag, .. } => Some(tag),
+                _ => None,
+            })
+            .filter_map(|tag| {
+                let target = dataflow_state
+                    .materializations
+                    .paths
+                    .iter()
+                    .find(|(_, paths)| paths.contains_left(&tag))
+                    .map(|(target, _)| *target)?;
+                let path = dataflow_state.replay_path_info(target, tag)?;
+                let estimated_size = path
+                    .segments
+                    .first()
+                    .and_then(|source| node_sizes.get(&source.node_index))
+                    .copied();
+                Some(PlannedReplay {
+                    path,
+                    estimated_size,
+                })
+            })
+            .collect();
+
+        MigrationPlanSummary {
+            added_nodes,
+            new_domains,
+            replays,
+            // Forecasting requires sampling base tables, which is done by the caller once the plan
+            // has been summarized
+            size_forecasts: vec![],
+        }
+    }
 }
 
 impl DomainMigrationPlan {
@@ -387,6 +626,7 @@ impl DomainMigrationPlan {
             mode,
             domains,
             failed_placement: vec![],
+            kill: HashSet::new(),
         }
     }
 
@@ -411,6 +651,12 @@ impl DomainMigrationPlan {
         });
     }
 
+    /// Enqueues the given domain to be shut down and removed, once all other requests in the plan
+    /// have been sent.
+    pub fn kill_domain(&mut self, idx: DomainIndex) {
+        self.kill.insert(idx);
+    }
+
     /// Mark that a given replica could not be placed because because no worker was available for it
     /// to run on
     pub fn replica_failed_placement(&mut self, replica: ReplicaAddress) {
@@ -442,7 +688,38 @@ impl DomainMigrationPlan {
 
     /// Apply all stored changes using the given controller object, placing new domains and sending
     /// messages added since the last time this method was called.
+    ///
+    /// If the migration being applied is cancelled partway through, all the changes made to the
+    /// running domains so far are undone before returning [`ReadySetError::MigrationCancelled`].
     pub async fn apply(self, mainline: &mut DfState) -> ReadySetResult<()> {
+        if let Some(tracker) = &mainline.migration_tracker {
+            let replays_total = self
+                .stored
+                .iter()
+                .filter(|req| matches!(req.req, DomainRequest::QueryReplayDone { .. }))
+                .count();
+            tracker.update(|progress| {
+                progress.phase = MigrationPhase::DomainSetup;
+                progress.replays_total = replays_total;
+            });
+        }
+
+        let mut applied = AppliedChanges::default();
+        match self.apply_inner(mainline, &mut applied).await {
+            Err(ReadySetError::MigrationCancelled) => {
+                info!("Migration cancelled; removing the domains and nodes it added");
+                applied.undo(mainline).await?;
+                Err(ReadySetError::MigrationCancelled)
+            }
+            res => res,
+        }
+    }
+
+    async fn apply_inner(
+        self,
+        mainline: &mut DfState,
+        applied: &mut AppliedChanges,
+    ) -> ReadySetResult<()> {
         // First, tell all the workers to run the domains
         //
         // While we're doing this, we also maintain a map of all the domains' shard replicas which
@@ -466,13 +743,17 @@ impl DomainMigrationPlan {
             }
 
             let handle = mainline
-                .place_domain(place.idx, place.shard_replica_workers, place.nodes)
+                .place_domain(place.idx, place.shard_replica_workers, place.nodes.clone())
                 .await?;
 
             match mainline.domains.entry(place.idx) {
                 hash_map::Entry::Occupied(mut e) => e.get_mut().merge(handle),
                 hash_map::Entry::Vacant(e) => {
                     e.insert(handle);
+                    applied.placed_domains.insert(place.idx);
+                    if let Some(observer) = &mainline.migration_observer {
+                        observer.domain_created(place.idx, &place.nodes)?;
+                    }
                 }
             }
         }
@@ -491,6 +772,16 @@ impl DomainMigrationPlan {
         };
         let mut retry_strategy = create_exponential_backoff();
         while let Some(req) = stored.pop_front() {
+            if let Some(tracker) = &mainline.migration_tracker {
+                tracker.check_cancelled()?;
+            }
+
+            if let (Some(observer), DomainRequest::StartReplay { tag, from, .. }) =
+                (&mainline.migration_observer, &req.req)
+            {
+                observer.replay_started(req.domain, *tag, *from)?;
+            }
+            applied.record(&req);
             if let Some(req) = req.apply(mainline, &just_placed_shard_replicas).await? {
                 // Initializing base table nodes might take a lot of time, so we try to wait using
                 // an exponential backoff strategy.
@@ -505,6 +796,16 @@ impl DomainMigrationPlan {
         }
 
         debug!("successfully sent all domain messages for this migration!");
+
+        if !self.kill.is_empty() {
+            mainline.kill_domains(self.kill.iter().copied()).await?;
+            for domain in self.kill {
+                mainline.domains.remove(&domain);
+                mainline.domain_nodes.remove(&domain);
+                mainline.domain_node_index_pairs.remove(&domain);
+            }
+        }
+
         Ok(())
     }
 
@@ -567,6 +868,7 @@ impl DomainMigrationPlan {
         self.place.extend(other.place);
         self.stored.extend(other.stored);
         self.domains.extend(other.domains);
+        self.kill.extend(other.kill);
     }
 
     /// Returns list of domains which could not be placed because no worker was available for them
@@ -603,6 +905,61 @@ fn topo_order(dataflow_state: &DfState, nodes: &HashSet<NodeIndex>) -> Vec<NodeI
     topo_list
 }
 
+/// Assign a [`StableNodeId`] to every node in the graph which doesn't have one yet, going in
+/// topological order so that the identifiers of each node's ancestors are always known.
+///
+/// Since a node's identifier is derived from its contents, two nodes can end up with the same
+/// identifier (eg a fully materialized duplicate of a partially materialized node); the node
+/// which is assigned its identifier later is disambiguated by rehashing.
+fn assign_stable_ids(dataflow_state: &mut DfState) {
+    let source = dataflow_state.source;
+    let graph = &mut dataflow_state.ingredients;
+    let mut used = graph
+        .node_weights()
+        .filter_map(|n| n.stable_id())
+        .collect::<HashSet<_>>();
+
+    let mut order = Vec::with_capacity(graph.node_count());
+    let mut topo = petgraph::visit::Topo::new(&*graph);
+    while let Some(ni) = topo.next(&*graph) {
+        order.push(ni);
+    }
+
+    for ni in order {
+        #[allow(clippy::indexing_slicing)] // came from the graph
+        let node = &graph[ni];
+        if ni == source || node.is_dropped() || node.stable_id().is_some() {
+            continue;
+        }
+
+        // The order of the parents of internal nodes matters (eg for the two sides of a join),
+        // but otherwise they're sorted so that the identifier doesn't depend on node indices
+        #[allow(clippy::indexing_slicing)] // came from the graph
+        let parents = match node.ancestors() {
+            Ok(ancestors) => ancestors
+                .into_iter()
+                .filter_map(|parent| graph[parent].stable_id())
+                .collect::<Vec<_>>(),
+            Err(_) => {
+                let mut parents = graph
+                    .neighbors_directed(ni, petgraph::EdgeDirection::Incoming)
+                    .filter(|&parent| parent != source)
+                    .filter_map(|parent| graph[parent].stable_id())
+                    .collect::<Vec<_>>();
+                parents.sort();
+                parents
+            }
+        };
+
+        let mut id = node.content_id(&parents);
+        while !used.insert(id) {
+            id = id.rehash();
+        }
+        #[allow(clippy::indexing_slicing)] // came from the graph
+        graph[ni].set_stable_id(id);
+    }
+}
+
 #[derive(Clone)]
 pub(super) enum ColumnChange {
     Add(Column, DfValue),
@@ -672,10 +1029,25 @@ pub struct Migration<'df> {
     pub(super) dataflow_state: &'df mut DfState,
     pub(in crate::controller) changes: MigrationNodeChanges,
     pub(super) columns: Vec<(NodeIndex, ColumnChange)>,
-    pub(super) readers: HashMap<NodeIndex, NodeIndex>,
+    /// New indices to add to the state of existing base nodes
+    base_indices: HashMap<NodeIndex, Index>,
+    /// Readers added in this migration, keyed by the node they read from and their name, since a
+    /// node can have multiple named readers (each with their own index)
+    pub(super) readers: HashMap<(NodeIndex, Option<Relation>), NodeIndex>,
     pub(super) worker: Option<WorkerIdentifier>,
     pub(super) dialect: Dialect,
 
+    /// Strategy for placing materializations beyond the frontier that overrides the global one for
+    /// all nodes added while it is set.
+    frontier_strategy: Option<FrontierStrategy>,
+    /// Overrides of the global frontier strategy for nodes added in this migration
+    frontier_strategy_overrides: HashMap<NodeIndex, FrontierStrategy>,
+    /// Whether readers added while this is set should have their domains run with a warm standby
+    /// replica
+    standby_readers: bool,
+    /// Observer to notify of the progress of the migration as it's committed
+    observer: Option<Arc<dyn MigrationObserver>>,
+
     pub(super) start: Instant,
 }
 
@@ -685,13 +1057,47 @@ impl<'df> Migration<'df> {
             dataflow_state,
             changes: Default::default(),
             columns: Default::default(),
+            base_indices: Default::default(),
             readers: Default::default(),
             worker: None,
             dialect,
+            frontier_strategy: None,
+            frontier_strategy_overrides: Default::default(),
+            standby_readers: false,
+            observer: None,
             start: Instant::now(),
         }
     }
 
+    /// Register an observer to be notified as domains are created, replays start and finish, and
+    /// the migration completes once it's committed. See [`MigrationObserver`] for more
+    /// information.
+    pub fn set_observer(&mut self, observer: Arc<dyn MigrationObserver>) {
+        self.observer = Some(observer);
+    }
+
+    /// Override the global frontier strategy for all nodes added to the graph by this migration
+    /// until this method is called again, or reset to the global frontier strategy if `None` is
+    /// passed.
+    pub(crate) fn set_frontier_strategy(&mut self, frontier_strategy: Option<FrontierStrategy>) {
+        self.frontier_strategy = frontier_strategy;
+    }
+
+    /// Set whether readers added to the graph by this migration should have their domains run with
+    /// a warm standby replica on a different worker, until this method is called again.
+    pub(crate) fn set_standby_readers(&mut self, standby_readers: bool) {
+        self.standby_readers = standby_readers;
+    }
+
+    /// Record that the given newly-added node should use the current frontier strategy override,
+    /// if any
+    fn record_frontier_strategy(&mut self, ni: NodeIndex) {
+        if let Some(frontier_strategy) = &self.frontier_strategy {
+            self.frontier_strategy_overrides
+                .insert(ni, frontier_strategy.clone());
+        }
+    }
+
     /// Add the given `Ingredient` to the dataflow graph.
     ///
     /// The returned identifier can later be used to refer to the added ingredient.
@@ -725,6 +1131,7 @@ impl<'df> Migration<'df> {
 
         // keep track of the fact that it's new
         self.changes.add_node(ni);
+        self.record_frontier_strategy(ni);
         // insert it into the graph
         for parent in parents {
             self.dataflow_state.ingredients.add_edge(parent, ni, ());
@@ -858,8 +1265,47 @@ impl<'df> Migration<'df> {
         Ok(())
     }
 
+    /// Set the sets of columns which are indexed in the upstream database for the given base node.
+    ///
+    /// Index hints are used when choosing the index for a base node's own state. If the base node
+    /// already exists and this changes that index (for example, because the base has no primary
+    /// key and previously had no hints), the new index is added to the base's existing state when
+    /// the migration is committed.
+    pub fn set_base_index_hints(
+        &mut self,
+        node_index: NodeIndex,
+        index_hints: Vec<Vec<usize>>,
+    ) -> ReadySetResult<()> {
+        let node = self
+            .dataflow_state
+            .ingredients
+            .node_weight_mut(node_index)
+            .ok_or_else(|| ReadySetError::NoSuchNode(node_index.index()))?;
+        let previous_index = node.suggest_indexes(node_index).remove(&node_index);
+        node.get_base_mut()
+            .ok_or_else(|| ReadySetError::NoSuchNode(node_index.index()))?
+            .set_index_hints(index_hints);
+        let index = node.suggest_indexes(node_index).remove(&node_index);
+
+        // Base nodes added in this migration aren't materialized yet, and get their index when
+        // they are
+        if index != previous_index
+            && self
+                .dataflow_state
+                .materializations
+                .indexes_for(node_index)
+                .is_some()
+        {
+            if let Some(index) = index {
+                self.base_indices.insert(node_index, index.into_index());
+            }
+        }
+        Ok(())
+    }
+
     /// Ensure that a reader node exists as a child of `n`, optionally with the given name and set
-    /// of post-lookup operations, returning the index of that reader.
+    /// of post-lookup operations, returning the index of that reader. Readers with different names
+    /// can exist as children of the same node, each with their own index.
     fn ensure_reader_for(
         &mut self,
         n: NodeIndex,
@@ -867,27 +1313,23 @@ impl<'df> Migration<'df> {
         reader_processing: ReaderProcessing,
     ) -> NodeIndex {
         use std::collections::hash_map::Entry;
-        match self.readers.entry(n) {
+        match self.readers.entry((n, name.clone())) {
             Entry::Occupied(ni) => {
                 let ni = *ni.into_mut();
                 debug_assert!(
-                    {
-                        let node = &self.dataflow_state.ingredients[ni];
-                        let name_matches = name.map_or(true, |n| *node.name() == n);
-                        name_matches
-                            && *node
-                                .as_reader()
-                                .expect("non-reader in readers")
-                                .reader_processing()
-                                == reader_processing
-                    },
+                    *self.dataflow_state.ingredients[ni]
+                        .as_reader()
+                        .expect("non-reader in readers")
+                        .reader_processing()
+                        == reader_processing,
                     "existing reader state doesn't meet requirements"
                 );
                 ni
             }
             Entry::Vacant(e) => {
                 // make a reader
-                let r = node::special::Reader::new(n, reader_processing);
+                let mut r = node::special::Reader::new(n, reader_processing);
+                r.set_standby(self.standby_readers);
 
                 #[allow(clippy::indexing_slicing)] // NodeIndex must exist in ingredients
                 let mut r = if let Some(name) = name {
@@ -901,6 +1343,7 @@ impl<'df> Migration<'df> {
                 let r = self.dataflow_state.ingredients.add_node(r);
                 self.dataflow_state.ingredients.add_edge(n, r, ());
                 self.changes.add_node(r);
+                self.record_frontier_strategy(r);
                 *e.insert(r)
             }
         }
@@ -964,6 +1407,16 @@ impl<'df> Migration<'df> {
     /// Build a `MigrationPlan` for this migration, and apply it if the planning stage succeeds.
     pub(super) async fn commit(self, dry_run: bool) -> ReadySetResult<()> {
         let start = self.start;
+        let observer = self.observer.clone();
+        let res = self.commit_inner(dry_run).await;
+        if let Some(observer) = observer {
+            observer.migration_finished(&res, start.elapsed());
+        }
+        res
+    }
+
+    async fn commit_inner(self, dry_run: bool) -> ReadySetResult<()> {
+        let start = self.start;
 
         let plan = self
             .plan()
@@ -974,6 +1427,9 @@ impl<'df> Migration<'df> {
         if dry_run {
             return Ok(());
         }
+        if let Some(tracker) = &plan.dataflow_state.migration_tracker {
+            tracker.check_cancelled()?;
+        }
         plan.apply().await?;
 
         debug!(
@@ -1009,6 +1465,10 @@ impl<'df> Migration<'df> {
         let mut dropped = 0;
         let columns = self.columns;
         let worker = self.worker;
+        let observer = self.observer;
+        dataflow_state
+            .materializations
+            .set_frontier_strategy_overrides(self.frontier_strategy_overrides);
         for change in self.changes.into_iter() {
             match change {
                 NodeChanges::Add(new_nodes) => {
@@ -1022,6 +1482,19 @@ impl<'df> Migration<'df> {
             }
         }
 
+        dataflow_state
+            .materializations
+            .set_frontier_strategy_overrides(Default::default());
+
+        for (ni, index) in self.base_indices {
+            dataflow_state.materializations.add_base_index(
+                &mut dataflow_state.ingredients,
+                ni,
+                index,
+                &mut dmp,
+            )?;
+        }
+
         // We have successfully made a valid graph! Now we can inform the dmp of all the
         // changes
         inform_col_changes(&mut dmp, &columns, &dataflow_state.ingredients)?;
@@ -1040,6 +1513,7 @@ impl<'df> Migration<'df> {
         Ok(MigrationPlan {
             dataflow_state,
             dmp,
+            observer,
         })
     }
 }
@@ -1119,6 +1593,10 @@ fn plan_add_nodes(
     }
     let mut swapped = swapped0;
     loop {
+        // Give all the new nodes (including any duplicates added by the last iteration) stable
+        // identifiers before they're sent to their domains
+        assign_stable_ids(dataflow_state);
+
         let mut sorted_new = new_nodes.iter().collect::<Vec<_>>();
         sorted_new.sort();
 
@@ -1429,16 +1907,98 @@ fn plan_add_nodes(
 
 fn plan_drop_nodes(
     dataflow_state: &mut DfState,
-    removals: HashSet<NodeIndex>,
+    mut removals: HashSet<NodeIndex>,
 ) -> ReadySetResult<DomainMigrationPlan> {
     let mut dmp = DomainMigrationPlan::new(
         DomainMigrationMode::Extend,
         dataflow_state.domain_settings(),
     );
+    prune_orphaned_nodes(&dataflow_state.ingredients, &mut removals);
+
+    // Egress nodes which are staying around need to stop sending to any ingress nodes which are
+    // being removed
+    #[allow(clippy::indexing_slicing)] // nodes come from ingredients
+    for &ni in &removals {
+        if !dataflow_state.ingredients[ni].is_ingress() {
+            continue;
+        }
+        for parent in dataflow_state
+            .ingredients
+            .neighbors_directed(ni, petgraph::EdgeDirection::Incoming)
+        {
+            let parent_node = &dataflow_state.ingredients[parent];
+            if parent_node.is_egress() && !removals.contains(&parent) && !parent_node.is_dropped() {
+                dmp.add_message(
+                    parent_node.domain(),
+                    DomainRequest::RemoveEgressTx {
+                        egress_node: parent_node.local_addr(),
+                        ingress_node: ni,
+                    },
+                )?;
+            }
+        }
+    }
+
+    let affected_domains = removals
+        .iter()
+        .filter_map(|&ni| {
+            let node = dataflow_state.ingredients.node_weight(ni)?;
+            node.has_domain().then(|| node.domain())
+        })
+        .collect::<HashSet<_>>();
+
     remove_nodes(dataflow_state, &mut dmp, &removals)?;
+    dataflow_state.materializations.remove_nodes(&removals);
+
+    // Any domains which no longer have any nodes in them can be shut down entirely
+    #[allow(clippy::indexing_slicing)] // nodes come from ingredients
+    let live_domains = dataflow_state
+        .ingredients
+        .node_indices()
+        .map(|ni| &dataflow_state.ingredients[ni])
+        .filter(|node| !node.is_dropped() && !node.is_source() && node.has_domain())
+        .map(|node| node.domain())
+        .collect::<HashSet<_>>();
+    for domain in affected_domains.difference(&live_domains) {
+        debug!(domain = %domain.index(), "Domain is empty after node removals; shutting it down");
+        dmp.kill_domain(*domain);
+    }
+
     Ok(dmp)
 }
 
+/// Extend the given set of nodes to be removed with any ingress, egress, and sharder nodes which
+/// would be left without any children once those nodes are removed. These nodes are never
+/// represented in MIR, so they aren't tracked by the reference counting done there when a query
+/// is removed.
+fn prune_orphaned_nodes(graph: &Graph, removals: &mut HashSet<NodeIndex>) {
+    let mut to_check = removals.iter().copied().collect::<Vec<_>>();
+    while let Some(ni) = to_check.pop() {
+        for parent in graph.neighbors_directed(ni, petgraph::EdgeDirection::Incoming) {
+            #[allow(clippy::indexing_slicing)] // nodes come from graph
+            let parent_node = &graph[parent];
+            if removals.contains(&parent)
+                || parent_node.is_dropped()
+                || !(parent_node.is_ingress()
+                    || parent_node.is_egress()
+                    || parent_node.is_sharder())
+            {
+                continue;
+            }
+
+            #[allow(clippy::indexing_slicing)] // nodes come from graph
+            let orphaned = graph
+                .neighbors_directed(parent, petgraph::EdgeDirection::Outgoing)
+                .all(|child| removals.contains(&child) || graph[child].is_dropped());
+            if orphaned {
+                trace!(node = %parent.index(), "Pruning orphaned node");
+                removals.insert(parent);
+                to_check.push(parent);
+            }
+        }
+    }
+}
+
 fn remove_nodes(
     dataflow_state: &mut DfState,
     dmp: &mut DomainMigrationPlan,
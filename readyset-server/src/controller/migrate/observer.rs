@@ -0,0 +1,55 @@
+//! Hooks which allow applications embedding ReadySet to follow the progress of migrations as
+//! they're committed.
+
+use std::time::Duration;
+
+use dataflow::prelude::*;
+
+/// Callbacks invoked while a [`Migration`](super::Migration) is being committed, registered with
+/// [`Migration::set_observer`](super::Migration::set_observer).
+///
+/// This allows applications which drive migrations themselves (via the [`manual`](crate::manual)
+/// module) to report the progress of long-running migrations, or to enforce a timeout on them.
+/// Returning [`ReadySetError::MigrationCancelled`] from any of the callbacks which return a result
+/// cancels the migration, removing any domains and nodes it has added so far; any other error
+/// fails the migration as-is.
+///
+/// All callbacks default to doing nothing.
+pub trait MigrationObserver: Send + Sync {
+    /// Called once the new domain `domain`, containing the given nodes, has been started
+    fn domain_created(&self, domain: DomainIndex, nodes: &[NodeIndex]) -> ReadySetResult<()> {
+        let _ = (domain, nodes);
+        Ok(())
+    }
+
+    /// Called right before the replay along the path identified by `tag` is started from the node
+    /// `from` in `domain`
+    fn replay_started(
+        &self,
+        domain: DomainIndex,
+        tag: Tag,
+        from: LocalNodeIndex,
+    ) -> ReadySetResult<()> {
+        let _ = (domain, tag, from);
+        Ok(())
+    }
+
+    /// Called periodically while waiting for the full replay to the node `node` in `domain` to
+    /// finish
+    fn replay_waiting(&self, domain: DomainIndex, node: LocalNodeIndex) -> ReadySetResult<()> {
+        let _ = (domain, node);
+        Ok(())
+    }
+
+    /// Called once the full replay to the node `node` in `domain` has finished
+    fn replay_finished(&self, domain: DomainIndex, node: LocalNodeIndex) -> ReadySetResult<()> {
+        let _ = (domain, node);
+        Ok(())
+    }
+
+    /// Called once the migration has finished, successfully or otherwise, with its result and the
+    /// total time it took to plan and apply
+    fn migration_finished(&self, result: &ReadySetResult<()>, elapsed: Duration) {
+        let _ = (result, elapsed);
+    }
+}
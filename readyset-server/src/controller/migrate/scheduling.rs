@@ -2,9 +2,11 @@
 //!
 //! The domain scheduling algorithm, which is currently quite simplistic, works as follows:
 //!
-//! 1. We filter the set of workers in the cluster by two criteria: a. The worker must be healhty,
-//!    and b. The worker can be [configured to only run reader nodes][reader_only], in which case
-//!    only domains that contain a reader node can run on that worker
+//! 1. We filter the set of workers in the cluster by three criteria: a. The worker must be healhty,
+//!    and not draining, b. The worker can be [configured to only run reader nodes][reader_only], in
+//!    which case only domains that contain a reader node can run on that worker, and c. Domains
+//!    that contain base tables can only run on workers whose labels match the [base table worker
+//!    selector][selector]
 //! 2. Migrations can optionally [be restricted to a single worker][worker] - if so, all replicas of
 //!    all shards of all domains within the migration will be scheduled to that worker, *if* it's
 //!    valid
@@ -18,6 +20,7 @@
 //! [reader_only]: Worker::reader_only
 //! [worker]: Migration::worker
 //! [placement restrictions]: DomainPlacementRestriction
+//! [selector]: crate::Config::base_table_worker_selector
 
 use std::collections::{HashMap, HashSet};
 
@@ -37,9 +40,10 @@ fn worker_meets_restrictions(
     worker: &Worker,
     restrictions: &[&DomainPlacementRestriction],
 ) -> bool {
+    let config = &worker.domain_scheduling_config;
     restrictions
         .iter()
-        .all(|r| r.worker_volume == worker.domain_scheduling_config.volume_id)
+        .all(|r| r.worker_volume == config.volume_id && r.label_selector.matches(&config.labels))
 }
 
 /// Statistics about the domains scheduled onto a worker
@@ -73,7 +77,7 @@ impl<'state> Scheduler<'state> {
         let valid_workers = dataflow_state
             .workers
             .iter()
-            .filter(|(_, w)| w.healthy)
+            .filter(|(_, w)| w.healthy && !w.draining)
             .filter(|(wi, _)| worker.iter().all(|target_worker| *target_worker == **wi))
             .collect();
 
@@ -145,13 +149,23 @@ impl<'state> Scheduler<'state> {
             invariant_eq!(num_replicas, 1);
         }
 
-        let workers = self.valid_workers.iter().filter(|(_, worker)| {
-            match worker.domain_scheduling_config.reader_nodes {
-                NodeTypeSchedulingRestriction::None => true,
-                NodeTypeSchedulingRestriction::OnlyWithNodeType => is_reader_domain,
-                NodeTypeSchedulingRestriction::NeverWithNodeType => !is_reader_domain,
-            }
-        });
+        let workers = self
+            .valid_workers
+            .iter()
+            .filter(
+                |(_, worker)| match worker.domain_scheduling_config.reader_nodes {
+                    NodeTypeSchedulingRestriction::None => true,
+                    NodeTypeSchedulingRestriction::OnlyWithNodeType => is_reader_domain,
+                    NodeTypeSchedulingRestriction::NeverWithNodeType => !is_reader_domain,
+                },
+            )
+            .filter(|(_, worker)| {
+                !is_base_table_domain
+                    || self
+                        .dataflow_state
+                        .base_table_worker_selector
+                        .matches(&worker.domain_scheduling_config.labels)
+            });
 
         let mut res = Vec::with_capacity(num_shards);
         for shard in 0..num_shards {
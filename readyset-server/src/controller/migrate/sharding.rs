@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use dataflow::prelude::*;
 use dataflow::{node, ops};
+use mir::BOGOKEY_COL;
 use petgraph::graph::NodeIndex;
 use readyset_errors::{internal, invariant, invariant_eq, ReadySetResult};
 use tracing::{debug, error, info_span, trace};
@@ -42,7 +43,7 @@ pub fn shard(
                 .key()
                 .and_then(|c| {
                     if c.len() == 1 {
-                        if graph[node].columns()[c[0]].name() == "bogokey" {
+                        if graph[node].columns()[c[0]].name() == BOGOKEY_COL.as_str() {
                             Some(Sharding::ForcedNone)
                         } else {
                             Some(Sharding::ByColumn(c[0], sharding_factor))
@@ -133,7 +134,7 @@ pub fn shard(
             assert_eq!(want_sharding.len(), 1);
             let want_sharding = want_sharding[0];
 
-            if graph[node].columns()[want_sharding].name() == "bogokey" {
+            if graph[node].columns()[want_sharding].name() == BOGOKEY_COL.as_str() {
                 debug!("de-sharding node that operates on bogokey");
                 for (ni, s) in input_shardings.iter_mut() {
                     reshard(new, &mut swaps, graph, *ni, node, Sharding::ForcedNone)?;
@@ -0,0 +1,182 @@
+//! A queue for migrations requested by clients of the leader via `extend_recipe`.
+//!
+//! Migrations requested concurrently (for example, by several adapters creating caches at the same
+//! time) are run one at a time, in the order they were requested, by a single background task.
+//! Each request gets its own completion future, which resolves to the result of running *that*
+//! request's changes, and a [`MigrationTracker`] which can be used to follow the migration's
+//! progress or to cancel it.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use metrics::gauge;
+use parking_lot::Mutex;
+use readyset_client::consensus::Authority;
+use readyset_client::metrics::recorded;
+use readyset_client::recipe::{ExtendRecipeSpec, MigrationPhase, MigrationProgress};
+use readyset_errors::{internal_err, ReadySetError, ReadySetResult};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, warn};
+
+use crate::controller::state::DfStateHandle;
+
+/// A future which resolves to the result of a queued migration once it has finished running.
+///
+/// Resolves to an error if the migration was dropped from the queue before it could run, which
+/// happens if the leader is shutting down.
+pub(super) type MigrationResult = oneshot::Receiver<ReadySetResult<()>>;
+
+/// A handle to a single migration requested via the [`MigrationQueue`], used to report the
+/// migration's progress while it runs and to request that it be cancelled.
+///
+/// Cloning a tracker returns another handle to the same migration.
+#[derive(Clone, Default)]
+pub(super) struct MigrationTracker {
+    progress: Arc<Mutex<MigrationProgress>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl MigrationTracker {
+    /// Returns a snapshot of the current progress of the migration
+    pub(super) fn progress(&self) -> MigrationProgress {
+        *self.progress.lock()
+    }
+
+    /// Update the progress of the migration with the given function
+    pub(super) fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut MigrationProgress),
+    {
+        f(&mut self.progress.lock())
+    }
+
+    /// Record that the migration has moved on to the given phase
+    pub(super) fn set_phase(&self, phase: MigrationPhase) {
+        self.update(|progress| progress.phase = phase)
+    }
+
+    /// Request that the migration be cancelled. The migration stops the next time it checks for
+    /// cancellation via [`Self::check_cancelled`]
+    pub(super) fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release)
+    }
+
+    /// Returns [`ReadySetError::MigrationCancelled`] if the migration has been cancelled
+    pub(super) fn check_cancelled(&self) -> ReadySetResult<()> {
+        if self.cancelled.load(Ordering::Acquire) {
+            Err(ReadySetError::MigrationCancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+struct QueuedMigration {
+    spec: ExtendRecipeSpec<'static>,
+    tracker: MigrationTracker,
+    done: oneshot::Sender<ReadySetResult<()>>,
+}
+
+/// A handle to the queue of migrations waiting to be run by the leader.
+///
+/// Dropping the queue stops the background task once it's finished with the migration it's
+/// currently running, and fails any migrations still waiting in the queue.
+pub(super) struct MigrationQueue {
+    tx: mpsc::UnboundedSender<QueuedMigration>,
+    /// Number of migrations which have been enqueued but have not started running yet
+    depth: Arc<AtomicUsize>,
+}
+
+impl MigrationQueue {
+    /// Create a new, empty migration queue, spawning a background task to run migrations against
+    /// the given dataflow state.
+    ///
+    /// Must be called from within the context of a tokio runtime.
+    pub(super) fn new(
+        dataflow_state_handle: Arc<DfStateHandle>,
+        authority: Arc<Authority>,
+    ) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let depth = Arc::new(AtomicUsize::new(0));
+        gauge!(recorded::CONTROLLER_MIGRATION_QUEUE_DEPTH, 0.0);
+
+        tokio::spawn(run_migrations(
+            rx,
+            Arc::clone(&depth),
+            dataflow_state_handle,
+            authority,
+        ));
+
+        Self { tx, depth }
+    }
+
+    /// Add a migration to the end of the queue, returning a tracker for the migration and a future
+    /// which will resolve once it's finished running.
+    pub(super) fn enqueue(
+        &self,
+        spec: ExtendRecipeSpec<'static>,
+    ) -> (MigrationTracker, MigrationResult) {
+        let (done, result) = oneshot::channel();
+        let tracker = MigrationTracker::default();
+        let depth = self.depth.fetch_add(1, Ordering::AcqRel) + 1;
+        gauge!(recorded::CONTROLLER_MIGRATION_QUEUE_DEPTH, depth as f64);
+        debug!(%depth, "Enqueueing migration");
+
+        if let Err(mpsc::error::SendError(QueuedMigration { done, .. })) =
+            self.tx.send(QueuedMigration {
+                spec,
+                tracker: tracker.clone(),
+                done,
+            })
+        {
+            self.depth.fetch_sub(1, Ordering::AcqRel);
+            tracker.set_phase(MigrationPhase::Finished);
+            let _ = done.send(Err(internal_err!("Migration queue is no longer running")));
+        }
+
+        (tracker, result)
+    }
+}
+
+async fn run_migrations(
+    mut rx: mpsc::UnboundedReceiver<QueuedMigration>,
+    depth: Arc<AtomicUsize>,
+    dataflow_state_handle: Arc<DfStateHandle>,
+    authority: Arc<Authority>,
+) {
+    while let Some(QueuedMigration {
+        spec,
+        tracker,
+        done,
+    }) = rx.recv().await
+    {
+        let remaining = depth.fetch_sub(1, Ordering::AcqRel) - 1;
+        gauge!(recorded::CONTROLLER_MIGRATION_QUEUE_DEPTH, remaining as f64);
+        debug!(%remaining, "Running queued migration");
+
+        // Each migration runs in its own task, so that a panic while running one migration is
+        // reported as an error for that migration rather than taking down the whole queue
+        let dataflow_state_handle = Arc::clone(&dataflow_state_handle);
+        let authority = Arc::clone(&authority);
+        let task_tracker = tracker.clone();
+        let res = tokio::spawn(async move {
+            // Migrations cancelled while they were still queued are never run
+            task_tracker.check_cancelled()?;
+
+            let mut writer = dataflow_state_handle.write().await;
+            writer.as_mut().migration_tracker = Some(task_tracker);
+            let res = writer.as_mut().extend_recipe(spec, false).await;
+            writer.as_mut().migration_tracker = None;
+            res?;
+            dataflow_state_handle.commit(writer, &authority).await?;
+            Ok(())
+        })
+        .await
+        .unwrap_or_else(|e| Err(internal_err!("Migration task failed: {e}")));
+        tracker.set_phase(MigrationPhase::Finished);
+
+        if done.send(res).is_err() {
+            warn!("Requester of migration went away before it finished running");
+        }
+    }
+}
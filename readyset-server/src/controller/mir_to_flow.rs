@@ -12,9 +12,11 @@ use std::iter;
 
 use common::DfValue;
 use dataflow::node::Column as DfColumn;
+use dataflow::ops::grouped::approx_distinct::ApproxCountDistinct;
 use dataflow::ops::grouped::concat::GroupConcat;
 use dataflow::ops::join::{Join, JoinType};
 use dataflow::ops::project::Project;
+use dataflow::ops::topk::TopKMode;
 use dataflow::ops::Side;
 use dataflow::{node, ops, Expr as DfExpr, PostLookupAggregates, ReaderProcessing};
 use itertools::Itertools;
@@ -23,14 +25,15 @@ use mir::node::node_inner::MirNodeInner;
 use mir::node::{GroupedNodeType, ProjectExpr, ViewKeyColumn};
 use mir::query::MirQuery;
 use mir::{Column, DfNodeIndex, NodeIndex as MirNodeIndex};
-use nom_sql::{ColumnConstraint, ColumnSpecification, Expr, OrderType, Relation};
+use nom_sql::{ColumnConstraint, ColumnSpecification, DialectDisplay, Expr, OrderType, Relation};
 use petgraph::graph::NodeIndex;
 use petgraph::Direction;
 use readyset_client::internal::{Index, IndexType};
 use readyset_client::ViewPlaceholder;
+use readyset_data::dialect::SqlEngine;
 use readyset_data::{Collation, DfType, Dialect};
 use readyset_errors::{
-    internal, internal_err, invariant, invariant_eq, ReadySetError, ReadySetResult,
+    internal, internal_err, invariant, invariant_eq, unsupported_err, ReadySetError, ReadySetResult,
 };
 
 use crate::controller::Migration;
@@ -315,9 +318,14 @@ pub(super) fn mir_node_to_flow_parts(
                     ref order,
                     ref group_by,
                     limit,
+                    ..
                 } => {
                     invariant_eq!(ancestors.len(), 1);
                     let parent = ancestors[0];
+                    let topk_mode = match graph[mir_node].inner {
+                        MirNodeInner::TopK { mode, .. } => Some(mode),
+                        _ => None,
+                    };
                     Some(make_paginate_or_topk_node(
                         graph,
                         name,
@@ -326,7 +334,45 @@ pub(super) fn mir_node_to_flow_parts(
                         order,
                         group_by,
                         limit,
-                        matches!(graph[mir_node].inner, MirNodeInner::TopK { .. }),
+                        topk_mode,
+                        mig,
+                    )?)
+                }
+                MirNodeInner::Latest {
+                    ref group_by,
+                    ref over,
+                } => {
+                    invariant_eq!(ancestors.len(), 1);
+                    let parent = ancestors[0];
+                    Some(make_latest_node(
+                        graph,
+                        name,
+                        parent,
+                        &graph.columns(mir_node),
+                        group_by,
+                        over,
+                        mig,
+                    )?)
+                }
+                MirNodeInner::FullTextSearch {
+                    ref columns,
+                    ref terms,
+                    match_all,
+                    ref group_by,
+                    limit,
+                } => {
+                    invariant_eq!(ancestors.len(), 1);
+                    let parent = ancestors[0];
+                    Some(make_full_text_search_node(
+                        graph,
+                        name,
+                        parent,
+                        &graph.columns(mir_node),
+                        columns,
+                        terms,
+                        match_all,
+                        group_by,
+                        limit,
                         mig,
                     )?)
                 }
@@ -428,15 +474,10 @@ fn make_union_node(
 ) -> ReadySetResult<DfNodeIndex> {
     let mut emit_column_id: HashMap<NodeIndex, Vec<usize>> = HashMap::new();
 
-    let mut cols = Vec::with_capacity(
-        emit.first()
-            .ok_or_else(|| internal_err!("No emit columns"))?
-            .len(),
-    );
-
     // column_id_for_column doesn't take into consideration table aliases
     // which might cause improper ordering of columns in a union node
     // eg. Q6 in finkelstein.txt
+    let mut parents = Vec::with_capacity(ancestors.len());
     for (i, n) in ancestors.iter().enumerate() {
         let emit_cols = emit
             .get(i)
@@ -451,24 +492,89 @@ fn make_union_node(
             }
         })?;
 
-        // Union takes columns of first ancestor
-        if i == 0 {
-            let parent_cols = mig.dataflow_state.ingredients[ni.address()].columns();
-            cols = emit_cols
-                .iter()
-                .map(|i| {
-                    parent_cols
-                        .get(*i)
-                        .cloned()
-                        .ok_or_else(|| internal_err!("Invalid index"))
-                })
-                .collect::<ReadySetResult<Vec<_>>>()?;
-        }
+        let parent_cols = mig.dataflow_state.ingredients[ni.address()].columns();
+        let emitted = emit_cols
+            .iter()
+            .map(|i| {
+                parent_cols
+                    .get(*i)
+                    .cloned()
+                    .ok_or_else(|| internal_err!("Invalid index"))
+            })
+            .collect::<ReadySetResult<Vec<_>>>()?;
 
-        emit_column_id.insert(ni.address(), emit_cols);
+        parents.push((ni.address(), emit_cols, emitted));
+    }
+
+    // Union takes columns of first ancestor, with the types of all ancestors' columns unified so
+    // that every branch emits values of the same type
+    let mut cols = parents
+        .first()
+        .ok_or_else(|| internal_err!("No emit columns"))?
+        .2
+        .clone();
+    for (_, _, emitted) in parents.iter().skip(1) {
+        invariant_eq!(emitted.len(), cols.len());
+        for (col, other) in cols.iter_mut().zip(emitted) {
+            let ty = col.ty().unify(other.ty()).ok_or_else(|| {
+                unsupported_err!(
+                    "Cannot combine columns of types {} and {} in a UNION",
+                    col.ty(),
+                    other.ty()
+                )
+            })?;
+            *col = DfColumn::new(col.name().into(), ty, col.source().cloned());
+        }
     }
     set_names(&column_names(columns), &mut cols)?;
 
+    for (i, (parent, emit_cols, emitted)) in parents.into_iter().enumerate() {
+        if emitted
+            .iter()
+            .zip(&cols)
+            .all(|(c, unified)| c.ty().is_unknown() || c.ty() == unified.ty())
+        {
+            emit_column_id.insert(parent, emit_cols);
+            continue;
+        }
+
+        // Cast this branch's columns to the unified types before they reach the union
+        let exprs = emit_cols
+            .iter()
+            .zip(&emitted)
+            .zip(&cols)
+            .map(|((index, c), unified)| {
+                let column = DfExpr::Column {
+                    index: *index,
+                    ty: c.ty().clone(),
+                };
+                if c.ty().is_unknown() || c.ty() == unified.ty() {
+                    column
+                } else {
+                    DfExpr::Cast {
+                        expr: Box::new(column),
+                        ty: unified.ty().clone(),
+                        null_on_failure: false,
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        let project_cols = emitted
+            .iter()
+            .zip(&cols)
+            .map(|(c, unified)| {
+                DfColumn::new(c.name().into(), unified.ty().clone(), c.source().cloned())
+            })
+            .collect::<Vec<_>>();
+        let project_name = Relation {
+            schema: name.schema.clone(),
+            name: format!("{}_cast_{}", name.name, i).into(),
+        };
+        let project_len = exprs.len();
+        let project = mig.add_ingredient(project_name, project_cols, Project::new(parent, exprs));
+        emit_column_id.insert(project, (0..project_len).collect());
+    }
+
     let node = mig.add_ingredient(
         name,
         cols,
@@ -495,6 +601,7 @@ fn make_filter_node(
     let mut parent_cols = mig.dataflow_state.ingredients[parent_na.address()]
         .columns()
         .to_vec();
+    let conditions_ast = conditions.clone();
     let filter_conditions = lower_expression(
         graph,
         parent,
@@ -504,6 +611,19 @@ fn make_filter_node(
         mig.dialect,
     )?;
 
+    // MySQL treats any value as a boolean, but PostgreSQL requires filter conditions to actually
+    // be booleans
+    let condition_ty = filter_conditions.ty();
+    if mig.dialect.engine() == SqlEngine::PostgreSQL
+        && condition_ty.is_known()
+        && !condition_ty.is_bool()
+    {
+        invalid_query!(
+            "filter condition must be type boolean, not type {condition_ty} (in expression `{}`)",
+            Sensitive(&conditions_ast.display(mig.dialect.into()))
+        );
+    }
+
     set_names(&column_names(columns), &mut parent_cols)?;
 
     let node = mig.add_ingredient(
@@ -566,20 +686,49 @@ fn make_grouped_node(
         // to be an aggregation, however once we are in dataflow land the logic has not been
         // merged yet. For this reason, we need to pattern match for a groupconcat
         // aggregation before we pattern match for a generic aggregation.
-        GroupedNodeType::Aggregation(Aggregation::GroupConcat { separator: sep }) => {
-            let gc = GroupConcat::new(parent_na.address(), over_col_indx, group_col_indx, sep)?;
+        GroupedNodeType::Aggregation(Aggregation::GroupConcat {
+            separator: sep,
+            order_by,
+        }) => {
+            let order_by = order_by
+                .into_iter()
+                .map(|(col, ot)| Ok((graph.column_id_for_column(parent, &col)?, ot)))
+                .collect::<ReadySetResult<Vec<_>>>()?;
+            let gc = GroupConcat::new(
+                parent_na.address(),
+                over_col_indx,
+                group_col_indx,
+                sep,
+                order_by,
+            )?;
             let agg_col = make_agg_col(DfType::Text(/* TODO */ Collation::default()));
             cols.push(agg_col);
             set_names(&column_names(columns), &mut cols)?;
             mig.add_ingredient(name, cols, gc)
         }
-        GroupedNodeType::Aggregation(agg) => {
-            let grouped = agg.over(
+        // Like GROUP_CONCAT, APPROX_COUNT_DISTINCT keeps per-group state which is separate from
+        // the other aggregations in dataflow.
+        GroupedNodeType::Aggregation(Aggregation::ApproxCountDistinct { precision }) => {
+            let acd = ApproxCountDistinct::new(
                 parent_na.address(),
                 over_col_indx,
-                group_col_indx.as_slice(),
-                over_col_ty,
+                group_col_indx,
+                precision,
             )?;
+            let agg_col = make_agg_col(DfType::BigInt);
+            cols.push(agg_col);
+            set_names(&column_names(columns), &mut cols)?;
+            mig.add_ingredient(name, cols, acd)
+        }
+        GroupedNodeType::Aggregation(agg) => {
+            let grouped = agg
+                .map_columns(|col| graph.column_id_for_column(parent, &col))?
+                .over(
+                    parent_na.address(),
+                    over_col_indx,
+                    group_col_indx.as_slice(),
+                    over_col_ty,
+                )?;
             let agg_col = make_agg_col(grouped.output_col_type().or_ref(over_col_ty).clone());
             cols.push(agg_col);
             set_names(&column_names(columns), &mut cols)?;
@@ -878,6 +1027,7 @@ fn lower_expression(
     custom_types: &HashMap<Relation, DfType>,
     dialect: Dialect,
 ) -> ReadySetResult<DfExpr> {
+    let ast = expr.clone();
     DfExpr::lower(
         expr,
         dialect,
@@ -888,6 +1038,14 @@ fn lower_expression(
             custom_types,
         },
     )
+    .map_err(|e| match e {
+        // Point type errors at the (sub)expression of the query which they were found in
+        ReadySetError::InvalidQuery(msg) => ReadySetError::InvalidQuery(format!(
+            "{msg} (in expression `{}`)",
+            Sensitive(&ast.display(dialect.into()))
+        )),
+        e => e,
+    })
 }
 
 fn make_project_node(
@@ -1034,7 +1192,7 @@ fn make_paginate_or_topk_node(
     order: &Option<Vec<(Column, OrderType)>>,
     group_by: &[Column],
     limit: usize,
-    is_topk: bool,
+    topk_mode: Option<TopKMode>,
     mig: &mut Migration<'_>,
 ) -> ReadySetResult<DfNodeIndex> {
     let parent_na = graph.resolve_dataflow_node(parent).ok_or_else(|| {
@@ -1049,7 +1207,7 @@ fn make_paginate_or_topk_node(
     // set names using MIR columns to ensure aliases are used
     let column_names = column_names(columns);
     // create page_number column if this is a paginate node
-    if !is_topk {
+    if topk_mode.is_none() {
         #[allow(clippy::unwrap_used)] // column_names must be populated
         parent_cols.push(DfColumn::new(
             column_names.last().unwrap().into(),
@@ -1089,11 +1247,11 @@ fn make_paginate_or_topk_node(
     };
 
     // make the new operator and record its metadata
-    let na = if is_topk {
+    let na = if let Some(mode) = topk_mode {
         mig.add_ingredient(
             name,
             parent_cols,
-            ops::topk::TopK::new(parent_na.address(), cmp_rows, group_by_indx, limit),
+            ops::topk::TopK::new(parent_na.address(), cmp_rows, group_by_indx, limit, mode),
         )
     } else {
         mig.add_ingredient(
@@ -1105,6 +1263,104 @@ fn make_paginate_or_topk_node(
     Ok(DfNodeIndex::new(na))
 }
 
+fn make_latest_node(
+    graph: &MirGraph,
+    name: Relation,
+    parent: MirNodeIndex,
+    columns: &[Column],
+    group_by: &[Column],
+    over: &Column,
+    mig: &mut Migration<'_>,
+) -> ReadySetResult<DfNodeIndex> {
+    let parent_na = graph.resolve_dataflow_node(parent).ok_or_else(|| {
+        ReadySetError::MirNodeMustHaveDfNodeAssigned {
+            mir_node_index: parent.index(),
+        }
+    })?;
+    let mut parent_cols = mig.dataflow_state.ingredients[parent_na.address()]
+        .columns()
+        .to_vec();
+
+    // set names using MIR columns to ensure aliases are used
+    let column_names = column_names(columns);
+    set_names(&column_names, &mut parent_cols)?;
+
+    invariant!(
+        !group_by.is_empty(),
+        "need bogokey for Latest without group columns"
+    );
+
+    let group_by_indx = group_by
+        .iter()
+        .map(|c| graph.column_id_for_column(parent, c))
+        .collect::<ReadySetResult<Vec<_>>>()?;
+    let over_indx = graph.column_id_for_column(parent, over)?;
+
+    let na = mig.add_ingredient(
+        name,
+        parent_cols,
+        ops::latest::Latest::new(parent_na.address(), group_by_indx, over_indx),
+    );
+    Ok(DfNodeIndex::new(na))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn make_full_text_search_node(
+    graph: &MirGraph,
+    name: Relation,
+    parent: MirNodeIndex,
+    columns: &[Column],
+    text_columns: &[Column],
+    terms: &[String],
+    match_all: bool,
+    group_by: &[Column],
+    limit: Option<usize>,
+    mig: &mut Migration<'_>,
+) -> ReadySetResult<DfNodeIndex> {
+    let parent_na = graph.resolve_dataflow_node(parent).ok_or_else(|| {
+        ReadySetError::MirNodeMustHaveDfNodeAssigned {
+            mir_node_index: parent.index(),
+        }
+    })?;
+    let mut parent_cols = mig.dataflow_state.ingredients[parent_na.address()]
+        .columns()
+        .to_vec();
+
+    // set names using MIR columns to ensure aliases are used
+    let column_names = column_names(columns);
+    // create the score column
+    #[allow(clippy::unwrap_used)] // column_names must be populated
+    parent_cols.push(DfColumn::new(
+        column_names.last().unwrap().into(),
+        DfType::Double,
+        Some(name.clone()),
+    ));
+    set_names(&column_names, &mut parent_cols)?;
+
+    let text_column_indx = text_columns
+        .iter()
+        .map(|c| graph.column_id_for_column(parent, c))
+        .collect::<ReadySetResult<Vec<_>>>()?;
+    let group_by_indx = group_by
+        .iter()
+        .map(|c| graph.column_id_for_column(parent, c))
+        .collect::<ReadySetResult<Vec<_>>>()?;
+
+    let na = mig.add_ingredient(
+        name,
+        parent_cols,
+        ops::fulltext::FullTextSearch::new(
+            parent_na.address(),
+            text_column_indx,
+            terms.to_vec(),
+            match_all,
+            group_by_indx,
+            limit,
+        ),
+    );
+    Ok(DfNodeIndex::new(na))
+}
+
 fn make_reader_processing(
     graph: &MirGraph,
     parent: &MirNodeIndex,
@@ -1162,10 +1418,10 @@ fn materialize_leaf_node(
         }
     })?;
 
-    // we must add a new reader for this query. This also requires adding an identity node (at
-    // least currently), since a node can only have a single associated reader. However, the
-    // identity node exists at the MIR level, so we don't need to consider it here, as it has
-    // already been added.
+    // we must add a new reader for this query. The node may already have readers for other
+    // queries which look it up by different keys (see
+    // `SqlToMirConverter::add_leaf_to_existing_query`) - each reader is named after its query and
+    // has its own index, so there's no need for an identity node to separate them.
 
     // TODO(malte): consider the case when the projected columns need reordering
 
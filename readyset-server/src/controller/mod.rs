@@ -6,6 +6,7 @@ use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use anyhow::{format_err, Context};
+use database_utils::IgnoredColumns;
 use dataflow::node::{self, Column};
 use dataflow::prelude::ChannelCoordinator;
 use failpoint_macros::set_failpoint;
@@ -15,7 +16,7 @@ use metrics::{counter, gauge, histogram};
 use nom_sql::Relation;
 use readyset_client::consensus::{
     Authority, AuthorityControl, AuthorityWorkerHeartbeatResponse, CacheDDLRequest,
-    GetLeaderResult, WorkerDescriptor, WorkerId, WorkerSchedulingConfig,
+    GetLeaderResult, LabelSelector, WorkerDescriptor, WorkerId, WorkerSchedulingConfig,
 };
 #[cfg(feature = "failure_injection")]
 use readyset_client::failpoints;
@@ -47,14 +48,19 @@ use crate::worker::{WorkerRequest, WorkerRequestKind, WorkerRequestType};
 use crate::{Config, VolumeId};
 
 mod domain_handle;
+mod forecast;
 mod inner;
 mod keys;
 pub(crate) mod migrate; // crate viz for tests
+mod migration_queue;
 mod mir_to_flow;
+mod profiler;
 pub(crate) mod replication;
 pub(crate) mod schema;
 pub(crate) mod sql;
 mod state;
+pub(crate) mod view_eviction;
+mod worker_monitor;
 
 /// Time between leader state change checks without thread parking.
 const LEADER_STATE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
@@ -68,7 +74,22 @@ const WATCH_DURATION: Duration = Duration::from_secs(5);
 /// of all contained dataflow nodes.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct DomainPlacementRestriction {
+    /// The volume the worker must have, if any
     worker_volume: Option<VolumeId>,
+    /// The labels the worker must have
+    #[serde(default)]
+    label_selector: LabelSelector,
+}
+
+impl DomainPlacementRestriction {
+    /// Returns `true` if a domain with both this restriction and `other` could be placed onto
+    /// some worker
+    fn compatible_with(&self, other: &DomainPlacementRestriction) -> bool {
+        // A server can only have one worker_volume, as a result, both restrictions should require
+        // the same worker_volume.
+        self.worker_volume == other.worker_volume
+            && !self.label_selector.conflicts_with(&other.label_selector)
+    }
 }
 
 /// The key for a DomainPlacementRestriction for a dataflow node.
@@ -113,6 +134,18 @@ impl Debug for ControllerState {
     }
 }
 
+/// Parse the columns which are never replicated from the upstream database out of the given
+/// configuration. Invalid configurations are also rejected by the replicator, so we just warn here.
+fn ignored_columns(config: &Config) -> IgnoredColumns {
+    config
+        .replicator_config
+        .ignored_columns()
+        .unwrap_or_else(|error| {
+            warn!(%error, "Invalid --replication-columns-ignore, no columns will be ignored");
+            Default::default()
+        })
+}
+
 impl ControllerState {
     /// Initialize a new, empty [`ControllerState`] with the given configuration, and with the given
     /// value for the `permissive_writes` setting
@@ -134,7 +167,7 @@ impl ControllerState {
         let recipe = Recipe::with_config(
             crate::sql::Config {
                 reuse_type: config.reuse,
-                ..Default::default()
+                ignored_columns: ignored_columns(&config),
             },
             config.mir_config.clone(),
             permissive_writes,
@@ -153,6 +186,7 @@ impl ControllerState {
             HashMap::new(),
             cc,
             config.replication_strategy,
+            config.base_table_worker_selector.clone(),
         );
 
         Self {
@@ -165,6 +199,9 @@ impl ControllerState {
 #[derive(Clone)]
 pub struct Worker {
     healthy: bool,
+    /// Whether the worker has been drained via a request to `/drain_worker`, in which case no
+    /// domains are scheduled onto it until it re-registers
+    draining: bool,
     uri: Url,
     http: reqwest::Client,
     /// Configuration for how domains should be scheduled onto this worker
@@ -180,6 +217,7 @@ impl Worker {
     ) -> Self {
         Worker {
             healthy: true,
+            draining: false,
             uri: instance_uri,
             http: reqwest::Client::new(),
             domain_scheduling_config,
@@ -246,19 +284,23 @@ impl ReplicatorChannel {
 /// Channel used to notify the replication about controller events.
 /// This is the other way around communication from Replicator Channel
 pub struct ControllerChannel {
-    _sender: UnboundedSender<ControllerMessage>,
+    sender: UnboundedSender<ControllerMessage>,
     receiver: Option<UnboundedReceiver<ControllerMessage>>,
 }
 
 impl ControllerChannel {
     fn new() -> Self {
-        let (_sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
         Self {
-            _sender,
+            sender,
             receiver: Some(receiver),
         }
     }
 
+    fn sender(&self) -> UnboundedSender<ControllerMessage> {
+        self.sender.clone()
+    }
+
     fn receiver(&mut self) -> UnboundedReceiver<ControllerMessage> {
         self.receiver.take().unwrap()
     }
@@ -394,6 +436,10 @@ pub struct Controller {
     background_task_failed_rx: Receiver<ReadySetError>,
     /// Clone to send notifications that background tasks have failed
     background_task_failed_tx: Sender<ReadySetError>,
+    /// Receives workers which the leader's worker monitor has detected have failed
+    failed_workers_rx: UnboundedReceiver<Vec<WorkerIdentifier>>,
+    /// Clone to send notifications that workers have failed
+    failed_workers_tx: UnboundedSender<Vec<WorkerIdentifier>>,
     /// A `ControllerDescriptor` that describes this server instance.
     our_descriptor: ControllerDescriptor,
     /// The descriptor of the worker this controller's server is running.
@@ -437,6 +483,7 @@ impl Controller {
         // If we don't have an upstream, we allow permissive writes to base tables.
         let permissive_writes = config.replicator_config.upstream_db_url.is_none();
         let (background_task_failed_tx, background_task_failed_rx) = mpsc::channel(1);
+        let (failed_workers_tx, failed_workers_rx) = mpsc::unbounded_channel();
         Self {
             inner: Arc::new(LeaderHandle::new()),
             authority,
@@ -445,6 +492,8 @@ impl Controller {
             handle_rx,
             background_task_failed_tx,
             background_task_failed_rx,
+            failed_workers_tx,
+            failed_workers_rx,
             our_descriptor,
             worker_descriptor,
             config,
@@ -556,6 +605,10 @@ impl Controller {
                     self.config.replicator_config.clone(),
                     self.config.worker_request_timeout,
                     self.config.background_recovery_interval,
+                    self.config.worker_heartbeat_interval,
+                    self.config.worker_failure_timeout,
+                    self.failed_workers_tx.clone(),
+                    self.controller_channel.sender(),
                 );
                 self.leader_ready.store(false, Ordering::Release);
 
@@ -738,6 +791,9 @@ impl Controller {
                                 }).await {
                                     error!(%error, "Failed to persist stats in the Authority");
                                 }
+                                if let Some(leader) = self.inner.read().await.as_ref() {
+                                    leader.set_replication_healthy(true).await;
+                                }
                             },
                             ReplicatorMessage::RecoverableError(e) => {
                                 if let Err(error) = self.authority.update_persistent_stats(|stats| {
@@ -753,6 +809,9 @@ impl Controller {
                                 }).await {
                                     error!(%error, "Failed to persist stats in the Authority");
                                 }
+                                if let Some(leader) = self.inner.read().await.as_ref() {
+                                    leader.set_replication_healthy(false).await;
+                                }
                             },
                         },
                         _ => {
@@ -782,6 +841,13 @@ impl Controller {
                         return Err(err)
                     }
                 }
+                failed = self.failed_workers_rx.recv() => {
+                    if let Some(failed) = failed {
+                        if let Some(leader) = self.inner.write().await.as_mut() {
+                            leader.handle_failed_workers(failed).await?;
+                        }
+                    }
+                }
                 _ = self.shutdown_rx.recv() => {
                     info!("Controller shutting down after shutdown signal received");
                     break;
@@ -1041,6 +1107,8 @@ impl AuthorityLeaderElectionState {
                                 }
                                 state.dataflow_state.domain_config = self.config.domain_config.clone();
                                 state.dataflow_state.replication_strategy = self.config.replication_strategy;
+                                state.dataflow_state.base_table_worker_selector = self.config.base_table_worker_selector.clone();
+                                state.dataflow_state.recipe.set_ignored_columns(ignored_columns(&self.config));
                                 state.config = self.config.clone();
                                 Ok(state)
                             }
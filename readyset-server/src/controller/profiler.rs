@@ -0,0 +1,134 @@
+//! Periodic profiling of the data in base tables.
+//!
+//! The leader periodically samples up to [`PROFILE_SAMPLE_ROWS`] rows of every base table in the
+//! graph, and records a [`StateProfile`] of the approximate number of distinct values, number of
+//! NULLs, minimum and maximum values, and most frequent values of each of its columns in the
+//! [`TableProfiles`] shared by every copy of the dataflow state. Those profiles are used by the
+//! [`forecast`](super::forecast) module to estimate the size of the state of the nodes a migration
+//! would add, without having to sample every base table those nodes read from while planning the
+//! migration.
+
+use std::collections::HashMap;
+use std::sync::Weak;
+use std::time::{Duration, Instant};
+
+use dataflow::StateProfile;
+use nom_sql::Relation;
+use parking_lot::RwLock;
+use readyset_util::shutdown::ShutdownReceiver;
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{debug, warn};
+
+use crate::controller::state::DfStateHandle;
+
+/// The maximum number of rows of each base table to sample when profiling it
+pub(super) const PROFILE_SAMPLE_ROWS: usize = 10_000;
+
+/// The latest profile of each base table in the graph, keyed by the name of the table.
+///
+/// This isn't persisted along with the rest of the dataflow state, since it can always be rebuilt
+/// by profiling the tables again.
+#[derive(Debug, Default)]
+pub(super) struct TableProfiles(RwLock<HashMap<Relation, StateProfile>>);
+
+impl TableProfiles {
+    /// Returns the latest profile of the given table, if it's been profiled
+    pub(super) fn get(&self, table: &Relation) -> Option<StateProfile> {
+        self.0.read().get(table).cloned()
+    }
+
+    fn insert(&self, table: Relation, profile: StateProfile) {
+        self.0.write().insert(table, profile);
+    }
+
+    /// Forget the profiles of all tables for which `f` returns false
+    fn retain<F>(&self, mut f: F)
+    where
+        F: FnMut(&Relation) -> bool,
+    {
+        self.0.write().retain(|table, _| f(table));
+    }
+}
+
+/// Periodically profiles every base table in the graph, recording the results in the
+/// [`TableProfiles`] of the dataflow state.
+pub(super) struct TableProfiler {
+    interval: Duration,
+    /// The dataflow state of the leader that spawned the profiler. Held weakly so that the
+    /// profiler stops once that leader is dropped, for example after losing leadership.
+    dataflow_state_handle: Weak<DfStateHandle>,
+}
+
+impl TableProfiler {
+    pub(super) fn new(interval: Duration, dataflow_state_handle: Weak<DfStateHandle>) -> Self {
+        Self {
+            interval,
+            dataflow_state_handle,
+        }
+    }
+
+    /// Run the profiler until the leader that spawned it is dropped, or a shutdown signal is
+    /// received.
+    pub(super) async fn run(self, mut shutdown_rx: ShutdownReceiver) {
+        let mut interval = interval(self.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // The first tick completes immediately, and there aren't any tables to profile yet anyway
+        interval.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown_rx.recv() => break,
+            }
+
+            let Some(dataflow_state_handle) = self.dataflow_state_handle.upgrade() else {
+                break;
+            };
+            let start = Instant::now();
+            let tables = self.profile_tables(&dataflow_state_handle).await;
+            debug!(
+                tables,
+                ms = %start.elapsed().as_millis(),
+                "Finished profiling base tables"
+            );
+        }
+
+        debug!("Table profiler exiting");
+    }
+
+    /// Profile every base table in the graph, returning the number of tables profiled
+    async fn profile_tables(&self, dataflow_state_handle: &DfStateHandle) -> usize {
+        let tables = dataflow_state_handle.read().await.tables();
+        let mut profiled = 0;
+        for (table, ni) in &tables {
+            // Only hold the lock on the dataflow state while profiling a single table, so that we
+            // don't hold up migrations for longer than we have to
+            let ds = dataflow_state_handle.read().await;
+            if ds
+                .ingredients
+                .node_weight(*ni)
+                .map_or(true, |node| node.is_dropped())
+            {
+                // The table was dropped since we listed the tables
+                continue;
+            }
+            match ds.profile_table(*ni).await {
+                Ok(Some(profile)) => {
+                    ds.table_profiles.insert(table.clone(), profile);
+                    profiled += 1;
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    warn!(%error, table = %table.display_unquoted(), "Failed to profile base table")
+                }
+            }
+        }
+
+        dataflow_state_handle
+            .read()
+            .await
+            .table_profiles
+            .retain(|table| tables.contains_key(table));
+        profiled
+    }
+}
@@ -59,9 +59,18 @@ impl From<ReplicationOptions> for ReplicationStrategy {
     }
 }
 
+/// The number of replicas to run for domains containing readers which have been [marked as
+/// needing a warm standby][standby] - the primary replica, plus one standby.
+///
+/// [standby]: dataflow::node::special::Reader::standby
+const STANDBY_REPLICAS: usize = 2;
+
 impl ReplicationStrategy {
     /// Determine the number of times a domain with the given nodes should be replicated
     ///
+    /// Domains containing readers which need a warm standby are always replicated at least
+    /// [`STANDBY_REPLICAS`] times, regardless of the strategy.
+    ///
     /// # Invariants
     ///
     /// * Each of the nodes in `domain_nodes` must be present in `ingredients`
@@ -69,8 +78,15 @@ impl ReplicationStrategy {
     pub fn replicate_domain(&self, ingredients: &Graph, domain_nodes: &[NodeIndex]) -> usize {
         let has_reader = || domain_nodes.iter().any(|n| ingredients[*n].is_reader());
         let has_base = || domain_nodes.iter().any(|n| ingredients[*n].is_base());
+        let has_standby_reader = || {
+            domain_nodes.iter().any(|n| {
+                ingredients[*n]
+                    .as_reader()
+                    .map_or(false, |reader| reader.standby())
+            })
+        };
 
-        match *self {
+        let replicas = match *self {
             ReplicationStrategy::Never => 1,
             ReplicationStrategy::ReaderDomains(num_reader_replicas) => {
                 if has_reader() {
@@ -93,6 +109,17 @@ impl ReplicationStrategy {
                     num_non_base_replicas
                 }
             }
+        };
+
+        if replicas < STANDBY_REPLICAS && has_standby_reader() {
+            if has_base() {
+                warn!("Found domain with both a standby reader and a base, not replicating");
+                replicas
+            } else {
+                STANDBY_REPLICAS
+            }
+        } else {
+            replicas
         }
     }
 }
@@ -4,6 +4,7 @@ use dataflow::{PostLookupAggregate, PostLookupAggregateFunction, PostLookupAggre
 use mir::node::node_inner::MirNodeInner;
 use mir::node::ProjectExpr;
 use mir::{Column, NodeIndex};
+use nom_sql::analysis::visit_mut::{walk_expr, VisitorMut};
 use nom_sql::analysis::ReferredColumns;
 use nom_sql::FunctionExpr::*;
 use nom_sql::{self, DialectDisplay, Expr, FieldDefinitionExpr, Relation, SqlIdentifier};
@@ -127,6 +128,121 @@ pub(super) fn make_expressions_above_grouped(
     }
 }
 
+struct ReplaceGroupedExpressions<'a> {
+    qg: &'a QueryGraph,
+    projected_exprs: &'a HashMap<Expr, SqlIdentifier>,
+}
+
+impl<'ast, 'a> VisitorMut<'ast> for ReplaceGroupedExpressions<'a> {
+    type Error = !;
+
+    fn visit_expr(&mut self, expr: &'ast mut Expr) -> Result<(), Self::Error> {
+        if self.qg.group_by.contains(expr)
+            && let Some(alias) = self.projected_exprs.get(expr)
+        {
+            *expr = Expr::Column(nom_sql::Column {
+                name: alias.clone(),
+                table: None,
+            });
+            return Ok(());
+        }
+
+        walk_expr(self, expr)
+    }
+}
+
+/// Rewrite an expression which is evaluated *after* the grouped nodes for a query (such as an
+/// expression in the `SELECT` list or the `ORDER BY` clause) to refer to the columns that
+/// expressions in the `GROUP BY` clause were projected to by [`make_expressions_above_grouped`].
+///
+/// The columns those expressions are computed from aren't necessarily present after grouping, so
+/// without this, eg `SELECT DATE(created_at) AS d, count(*) FROM t GROUP BY DATE(created_at)` would
+/// fail to find the `created_at` column.
+pub(super) fn replace_grouped_expressions(
+    mut expr: Expr,
+    qg: &QueryGraph,
+    projected_exprs: &HashMap<Expr, SqlIdentifier>,
+) -> Expr {
+    let Ok(()) = ReplaceGroupedExpressions {
+        qg,
+        projected_exprs,
+    }
+    .visit_expr(&mut expr);
+    expr
+}
+
+/// Returns the columns that grouped nodes for the given query should group by: the columns and
+/// (projected) expressions in the `GROUP BY` clause, along with any parameter columns
+fn group_columns(
+    mir_converter: &SqlToMirConverter,
+    qg: &QueryGraph,
+    prev_node: NodeIndex,
+) -> Vec<Column> {
+    // Convert the GROUP BY exprs into column references
+    let group_by = qg
+        .group_by
+        .iter()
+        .map(|gb_expr| match gb_expr {
+            Expr::Column(c) => c.clone(),
+            expr => nom_sql::Column {
+                name: expr.display(nom_sql::Dialect::MySQL).to_string().into(),
+                table: None,
+            },
+        })
+        .collect::<Vec<_>>();
+
+    // get any parameter columns that aren't also in the group-by
+    // column set
+    let param_cols: Vec<_> = qg.relations.values().fold(vec![], |acc, rel| {
+        acc.into_iter()
+            .chain(
+                rel.parameters
+                    .iter()
+                    .map(|param| &param.col)
+                    .filter(|c| !group_by.contains(c)),
+            )
+            .collect()
+    });
+    // combine and dedup
+    #[allow(clippy::needless_collect)] // necessary to avoid cloning param_cols
+    let dedup_gb_cols: Vec<_> = group_by
+        .iter()
+        .filter(|gbc| !param_cols.contains(gbc))
+        .collect();
+    let gb_and_param_cols = dedup_gb_cols
+        .into_iter()
+        .chain(param_cols.into_iter())
+        .map(Column::from);
+
+    let mut have_parent_cols = HashSet::new();
+    // we cannot have duplicate columns at the data-flow level, as it confuses our
+    // migration analysis code.
+    gb_and_param_cols
+        .filter_map(|mut c| {
+            let pc = mir_converter
+                .columns(prev_node)
+                .iter()
+                .position(|pc| *pc == c);
+            if let Some(pc) = pc {
+                if !have_parent_cols.contains(&pc) {
+                    have_parent_cols.insert(pc);
+                    let pc = mir_converter.columns(prev_node)[pc].clone();
+                    if pc.name != c.name || pc.table != c.table {
+                        // remember the alias with the parent column
+                        c.aliases.push(pc);
+                    }
+                    Some(c)
+                } else {
+                    // we already have this column, so eliminate duplicate
+                    None
+                }
+            } else {
+                Some(c)
+            }
+        })
+        .collect()
+}
+
 pub(super) fn make_grouped(
     mir_converter: &mut SqlToMirConverter,
     query_name: &Relation,
@@ -139,75 +255,25 @@ pub(super) fn make_grouped(
     let mut agg_nodes: Vec<NodeIndex> = Vec::new();
 
     if qg.aggregates.is_empty() {
-        // Don't need to do anything if we don't have any aggregates
-        return Ok(vec![]);
+        if qg.group_by.is_empty() {
+            // Don't need to do anything if we don't have any aggregates or grouping
+            return Ok(vec![]);
+        }
+
+        // A GROUP BY without any aggregates just removes duplicate rows within each group
+        let group_cols = group_columns(mir_converter, qg, *prev_node);
+        let node = mir_converter.make_distinct_node(
+            query_name,
+            mir_converter.generate_label(&name),
+            *prev_node,
+            group_cols,
+        );
+        *prev_node = node;
+        return Ok(vec![node]);
     }
     for (function, alias) in &qg.aggregates {
         let name = mir_converter.generate_label(&name);
-
-        // Convert the GROUP BY exprs into column references
-        let group_by = qg
-            .group_by
-            .iter()
-            .map(|gb_expr| match gb_expr {
-                Expr::Column(c) => c.clone(),
-                expr => nom_sql::Column {
-                    name: expr.display(nom_sql::Dialect::MySQL).to_string().into(),
-                    table: None,
-                },
-            })
-            .collect::<Vec<_>>();
-
-        // get any parameter columns that aren't also in the group-by
-        // column set
-        let param_cols: Vec<_> = qg.relations.values().fold(vec![], |acc, rel| {
-            acc.into_iter()
-                .chain(
-                    rel.parameters
-                        .iter()
-                        .map(|param| &param.col)
-                        .filter(|c| !group_by.contains(c)),
-                )
-                .collect()
-        });
-        // combine and dedup
-        #[allow(clippy::needless_collect)] // necessary to avoid cloning param_cols
-        let dedup_gb_cols: Vec<_> = group_by
-            .iter()
-            .filter(|gbc| !param_cols.contains(gbc))
-            .collect();
-        let gb_and_param_cols = dedup_gb_cols
-            .into_iter()
-            .chain(param_cols.into_iter())
-            .map(Column::from);
-
-        let mut have_parent_cols = HashSet::new();
-        // we cannot have duplicate columns at the data-flow level, as it confuses our
-        // migration analysis code.
-        let group_cols = gb_and_param_cols
-            .filter_map(|mut c| {
-                let pc = mir_converter
-                    .columns(*prev_node)
-                    .iter()
-                    .position(|pc| *pc == c);
-                if let Some(pc) = pc {
-                    if !have_parent_cols.contains(&pc) {
-                        have_parent_cols.insert(pc);
-                        let pc = mir_converter.columns(*prev_node)[pc].clone();
-                        if pc.name != c.name || pc.table != c.table {
-                            // remember the alias with the parent column
-                            c.aliases.push(pc);
-                        }
-                        Some(c)
-                    } else {
-                        // we already have this column, so eliminate duplicate
-                        None
-                    }
-                } else {
-                    Some(c)
-                }
-            })
-            .collect();
+        let group_cols = group_columns(mir_converter, qg, *prev_node);
 
         let nodes: Vec<NodeIndex> = mir_converter.make_aggregate_node(
             query_name,
@@ -266,7 +332,7 @@ fn joinable_aggregate_nodes(
 ///
 /// This function is *not* responsible for determining whether the query *requires* post-lookup
 /// aggregation - that's the responsibility of the caller. This function will only return [`None`]
-/// if the query contains no aggregates.
+/// if the query contains no aggregates and no `GROUP BY` clause.
 pub(super) fn post_lookup_aggregates(
     query_graph: &QueryGraph,
     query_name: &Relation,
@@ -298,7 +364,8 @@ pub(super) fn post_lookup_aggregates(
         }));
     }
 
-    if query_graph.aggregates.is_empty() {
+    // A GROUP BY without any aggregates still needs to have duplicate rows removed for each group
+    if query_graph.aggregates.is_empty() && query_graph.group_by.is_empty() {
         return Ok(None);
     }
 
@@ -310,6 +377,11 @@ pub(super) fn post_lookup_aggregates(
                 Avg { .. } => {
                     unsupported!("Average is not supported as a post-lookup aggregate")
                 }
+                ApproxCountDistinct { .. } => {
+                    unsupported!(
+                        "APPROX_COUNT_DISTINCT is not supported as a post-lookup aggregate"
+                    )
+                }
                 Count { distinct, .. } if *distinct => {
                     // TODO(REA-4289)
                     unsupported!("COUNT(DISTINCT ...) is not supported as a post-lookup aggregate")
@@ -323,10 +395,15 @@ pub(super) fn post_lookup_aggregates(
                 Count { .. } | CountStar | Sum { .. } => PostLookupAggregateFunction::Sum,
                 Max(_) => PostLookupAggregateFunction::Max,
                 Min(_) => PostLookupAggregateFunction::Min,
+                GroupConcat {
+                    order_by: Some(_), ..
+                } => {
+                    unsupported!("GROUP_CONCAT(... ORDER BY ...) is not supported post-lookup")
+                }
                 GroupConcat { separator, .. } => PostLookupAggregateFunction::GroupConcat {
                     separator: separator.clone().unwrap_or_else(|| ",".to_owned()),
                 },
-                Call { .. } | Substring { .. } => continue,
+                Call { .. } | Substring { .. } | Match { .. } | Rank { .. } => continue,
             },
         });
     }
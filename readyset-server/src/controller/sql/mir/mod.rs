@@ -7,20 +7,22 @@ use ::serde::{Deserialize, Serialize};
 use catalog_tables::is_catalog_table;
 use common::IndexType;
 use dataflow::ops::grouped::aggregate::Aggregation;
+use dataflow::ops::grouped::approx_distinct;
+use dataflow::ops::topk::TopKMode;
 use dataflow::ops::union;
 use lazy_static::lazy_static;
 use mir::graph::MirGraph;
 use mir::node::node_inner::MirNodeInner;
 use mir::node::{GroupedNodeType, MirNode, ProjectExpr, ViewKeyColumn};
 use mir::query::{MirBase, MirQuery};
-use mir::DfNodeIndex;
-pub use mir::{Column, NodeIndex};
+pub use mir::{Column, NodeIndex, FULL_TEXT_SCORE_COL};
+use mir::{DfNodeIndex, BOGOKEY_COL};
 use nom_sql::analysis::ReferredColumns;
 use nom_sql::{
     BinaryOperator, CaseWhenBranch, ColumnSpecification, CompoundSelectOperator, CreateTableBody,
     DialectDisplay, Expr, FieldDefinitionExpr, FieldReference, FunctionExpr, InValue, LimitClause,
-    Literal, NonReplicatedRelation, OrderBy, OrderClause, OrderType, Relation, SelectStatement,
-    SqlIdentifier, TableKey, UnaryOperator,
+    Literal, NonReplicatedRelation, OrderBy, OrderClause, OrderType, RankFunction, Relation,
+    SelectStatement, SqlIdentifier, TableKey, UnaryOperator,
 };
 use petgraph::visit::Reversed;
 use petgraph::Direction;
@@ -36,11 +38,12 @@ use tracing::{debug, trace};
 use super::query_graph::{extract_limit_offset, JoinPredicate};
 use crate::controller::sql::mir::grouped::{
     make_expressions_above_grouped, make_grouped, make_predicates_above_grouped,
-    post_lookup_aggregates,
+    post_lookup_aggregates, replace_grouped_expressions,
 };
 use crate::controller::sql::mir::join::{make_cross_joins, make_joins};
 use crate::controller::sql::query_graph::{
-    to_query_graph, ExprColumn, OutputColumn, Pagination, QueryGraph,
+    to_query_graph, ExprColumn, OutputColumn, Pagination, QueryGraph, QueryGraphEdge, ViewKey,
+    WindowLimit,
 };
 use crate::controller::sql::query_signature::Signature;
 
@@ -86,6 +89,60 @@ fn value_columns_needed_for_predicates(
         .collect()
 }
 
+/// If the given query could share all of its nodes except for its leaf with another query which
+/// differs from it only in the columns it's looked up by, returns the query graph for the query
+/// with all of its parameters removed, to compare against the query graphs of other queries.
+///
+/// This is only the case for queries which are looked up by equality on columns that are all
+/// carried through to the leaf unchanged, and which don't do anything with the key (such as
+/// grouping, ordering, or paginating) that would make the nodes above the leaf depend on it.
+fn lookup_query_graph(query_graph: &QueryGraph, view_key: &ViewKey) -> Option<QueryGraph> {
+    let shareable = view_key.index_type == IndexType::HashMap
+        && !view_key.columns.is_empty()
+        && view_key
+            .columns
+            .iter()
+            .all(|(_, vp)| matches!(vp, ViewPlaceholder::OneToOne(_, BinaryOperator::Equal)))
+        && !query_graph.distinct
+        && !query_graph.is_correlated
+        && query_graph.aggregates.is_empty()
+        && query_graph.group_by.is_empty()
+        && query_graph.order.is_none()
+        && query_graph.pagination.is_none()
+        && query_graph.full_text_search.is_none()
+        && query_graph.default_row.is_none()
+        && query_graph
+            .relations
+            .values()
+            .all(|rel| rel.subgraph.is_none() && rel.parameters.iter().all(|p| p.expr.is_none()))
+        && query_graph.edges.values().all(|edge| match edge {
+            QueryGraphEdge::LeftJoin { params, .. } => params.is_empty(),
+            _ => true,
+        });
+
+    shareable.then(|| {
+        let mut query_graph = query_graph.clone();
+        for rel in query_graph.relations.values_mut() {
+            rel.parameters.clear();
+        }
+        query_graph
+    })
+}
+
+/// Returns the precision to use for an `APPROX_COUNT_DISTINCT` with the given (optional) precision
+/// argument, or an error if the precision is out of the supported range
+fn approx_count_distinct_precision(precision: Option<u8>) -> ReadySetResult<u8> {
+    let precision = precision.unwrap_or(approx_distinct::DEFAULT_PRECISION);
+    if !(approx_distinct::MIN_PRECISION..=approx_distinct::MAX_PRECISION).contains(&precision) {
+        invalid_query!(
+            "APPROX_COUNT_DISTINCT precision must be between {} and {}",
+            approx_distinct::MIN_PRECISION,
+            approx_distinct::MAX_PRECISION
+        );
+    }
+    Ok(precision)
+}
+
 /// The result of removing a relation from MIR.
 #[derive(Default)]
 pub struct MirRemovalResult {
@@ -182,6 +239,17 @@ pub(super) struct SqlToMirConverter {
     /// replicated (either due to lack of support, or because the user explicitly opted out from
     /// them being replicated)
     pub(in crate::controller::sql) non_replicated_relations: HashSet<NonReplicatedRelation>,
+
+    /// Query graphs of the cached queries which can have another lookup index added to their
+    /// leaf, with all their parameters removed. See [`lookup_query_graph`].
+    #[serde(default)]
+    pub(in crate::controller::sql) lookup_query_graphs: HashMap<Relation, QueryGraph>,
+
+    /// Map from the name of each cached query which other cached queries read from directly (see
+    /// [`resolve_stacked_query`][Self::resolve_stacked_query]) to the names of those queries, so
+    /// that they can be removed along with it.
+    #[serde(default)]
+    pub(in crate::controller::sql) stacked_queries: HashMap<Relation, HashSet<Relation>>,
 }
 
 impl SqlToMirConverter {
@@ -321,7 +389,7 @@ impl SqlToMirConverter {
                         })
                         .transpose()?,
                     limit,
-                    make_topk,
+                    make_topk.then_some(TopKMode::RowNumber),
                 )?
                 .last()
                 .unwrap();
@@ -401,7 +469,35 @@ impl SqlToMirConverter {
                     relation: name.display_unquoted().to_string(),
                 })?;
 
-        self.remove_dependent_nodes(leaf_mn)
+        // Cached queries stacked on top of this one don't descend from its leaf, so they have to
+        // be removed separately
+        let mut stacked_removal = MirRemovalResult::default();
+        for stacked in self.stacked_queries.remove(name).unwrap_or_default() {
+            if !self.relations.contains_key(&stacked) {
+                continue;
+            }
+            debug!(
+                name = %stacked.display_unquoted(),
+                stacked_on = %name.display_unquoted(),
+                "Removing query stacked on removed query"
+            );
+            let removal = self.remove_query(&stacked)?;
+            stacked_removal
+                .dataflow_nodes_to_remove
+                .extend(removal.dataflow_nodes_to_remove);
+            stacked_removal
+                .relations_removed
+                .extend(removal.relations_removed);
+        }
+
+        let mut mir_removal_result = self.remove_dependent_nodes(leaf_mn)?;
+        mir_removal_result
+            .dataflow_nodes_to_remove
+            .extend(stacked_removal.dataflow_nodes_to_remove);
+        mir_removal_result
+            .relations_removed
+            .extend(stacked_removal.relations_removed);
+        Ok(mir_removal_result)
     }
 
     /// Removes a base table, along with all the views/cached queries associated with it.
@@ -459,6 +555,66 @@ impl SqlToMirConverter {
         self.remove_dependent_nodes(*root)
     }
 
+    /// Returns the index of the (latest) column with the given name in the base table node for
+    /// `table_name`.
+    ///
+    /// Base table nodes keep the specifications of columns which have been dropped, so that column
+    /// indices line up with the columns of the dataflow base node.
+    pub(super) fn base_column_index(
+        &self,
+        table_name: &Relation,
+        column: &nom_sql::Column,
+    ) -> ReadySetResult<usize> {
+        let node = self
+            .get_relation(table_name)
+            .ok_or_else(|| self.table_not_found_err(table_name))?;
+        self.mir_graph
+            .column_id_for_column(node, &Column::from(column))
+    }
+
+    /// Add the column with the given specification to the end of the base table node for
+    /// `table_name`, returning its index. Any queries which already read from the base table are
+    /// left untouched.
+    pub(super) fn add_base_column(
+        &mut self,
+        table_name: &Relation,
+        spec: ColumnSpecification,
+    ) -> ReadySetResult<usize> {
+        let node = self
+            .get_relation(table_name)
+            .ok_or_else(|| self.table_not_found_err(table_name))?;
+        match &mut self.mir_graph[node].inner {
+            MirNodeInner::Base { column_specs, .. } => {
+                column_specs.push(spec);
+                Ok(column_specs.len() - 1)
+            }
+            _ => internal!("Node should be a base node!"),
+        }
+    }
+
+    /// Replace the specification of the column at the given index of the base table node for
+    /// `table_name`, for example after its type was changed. Any queries which already read from
+    /// the base table are left untouched.
+    pub(super) fn set_base_column_spec(
+        &mut self,
+        table_name: &Relation,
+        index: usize,
+        spec: ColumnSpecification,
+    ) -> ReadySetResult<()> {
+        let node = self
+            .get_relation(table_name)
+            .ok_or_else(|| self.table_not_found_err(table_name))?;
+        match &mut self.mir_graph[node].inner {
+            MirNodeInner::Base { column_specs, .. } => {
+                *column_specs
+                    .get_mut(index)
+                    .ok_or_else(|| internal_err!("Column {index} out of bounds"))? = spec;
+                Ok(())
+            }
+            _ => internal!("Node should be a base node!"),
+        }
+    }
+
     pub(super) fn make_mir_query(
         &mut self,
         query_name: Relation,
@@ -541,6 +697,10 @@ impl SqlToMirConverter {
         // Finally, remove the affected queries from the state.
         self.relations
             .retain(|name, _| !owners_to_remove.contains(name));
+        self.lookup_query_graphs
+            .retain(|name, _| !owners_to_remove.contains(name));
+        self.stacked_queries
+            .retain(|name, _| !owners_to_remove.contains(name));
         Ok(MirRemovalResult {
             dataflow_nodes_to_remove: df_to_remove,
             relations_removed: owners_to_remove,
@@ -935,10 +1095,69 @@ impl SqlToMirConverter {
             GroupConcat {
                 expr: box Expr::Column(col),
                 separator,
+                order_by,
+            } => {
+                let order_by = order_by
+                    .into_iter()
+                    .flat_map(|o| o.order_by)
+                    .map(
+                        |OrderBy {
+                             field,
+                             order_type,
+                             null_order,
+                         }| {
+                            let order_type = order_type.unwrap_or(OrderType::OrderAscending);
+                            if let Some(null_order) = null_order {
+                                if !null_order.is_default_for(order_type) {
+                                    unsupported!(
+                                        "Non-default NULLS FIRST/LAST is not yet supported"
+                                    );
+                                }
+                            }
+                            match field {
+                                FieldReference::Expr(Expr::Column(c)) => {
+                                    Ok((Column::from(c), order_type))
+                                }
+                                _ => unsupported!(
+                                    "Only columns are supported in the ORDER BY clause of \
+                                     GROUP_CONCAT"
+                                ),
+                            }
+                        },
+                    )
+                    .collect::<ReadySetResult<Vec<_>>>()?;
+
+                mknode(
+                    Column::from(col),
+                    GroupedNodeType::Aggregation(Aggregation::GroupConcat {
+                        separator: separator.unwrap_or_else(|| ",".to_owned()),
+                        order_by,
+                    }),
+                    false,
+                )
+            }
+            ApproxCountDistinct {
+                expr: box Expr::Column(col),
+                precision,
             } => mknode(
                 Column::from(col),
-                GroupedNodeType::Aggregation(Aggregation::GroupConcat {
-                    separator: separator.unwrap_or_else(|| ",".to_owned()),
+                GroupedNodeType::Aggregation(Aggregation::ApproxCountDistinct {
+                    precision: approx_count_distinct_precision(precision)?,
+                }),
+                false,
+            ),
+            ApproxCountDistinct {
+                ref expr,
+                precision,
+            } => mknode(
+                Column::named(
+                    projected_exprs
+                        .get(expr)
+                        .cloned()
+                        .ok_or_else(|| mk_error!(expr))?,
+                ),
+                GroupedNodeType::Aggregation(Aggregation::ApproxCountDistinct {
+                    precision: approx_count_distinct_precision(precision)?,
                 }),
                 false,
             ),
@@ -1283,11 +1502,11 @@ impl SqlToMirConverter {
         group_by: Vec<Column>,
         order: &Option<Vec<(Expr, OrderType)>>,
         limit: usize,
-        is_topk: bool,
+        topk_mode: Option<TopKMode>,
     ) -> ReadySetResult<Vec<NodeIndex>> {
-        if !self.config.allow_topk && is_topk {
+        if !self.config.allow_topk && topk_mode.is_some() {
             unsupported!("TopK is not supported");
-        } else if !self.config.allow_paginate && !is_topk {
+        } else if !self.config.allow_paginate && topk_mode.is_none() {
             unsupported!("Paginate is not supported");
         }
 
@@ -1351,13 +1570,14 @@ impl SqlToMirConverter {
         // make the new operator and record its metadata
         let paginate_node = self.add_query_node(
             query_name.clone(),
-            if is_topk {
+            if let Some(mode) = topk_mode {
                 MirNode::new(
                     name.into(),
                     MirNodeInner::TopK {
                         order,
                         group_by,
                         limit,
+                        mode,
                     },
                 )
             } else {
@@ -1785,6 +2005,22 @@ impl SqlToMirConverter {
         //  Reuse should just require that we add the query name to the "owners" hashset in the
         //  reused nodes if the node properties are identical.
 
+        let lookup_graph = if leaf_behavior.should_make_leaf() && anon_queries.is_empty() {
+            lookup_query_graph(query_graph, &query_graph.view_key(self.config())?)
+        } else {
+            None
+        };
+        if let Some(lookup_graph) = &lookup_graph {
+            if let Some(leaf) =
+                self.add_leaf_to_existing_query(query_name, query_graph, lookup_graph)?
+            {
+                self.relations.insert(query_name.clone(), leaf);
+                self.lookup_query_graphs
+                    .insert(query_name.clone(), lookup_graph.clone());
+                return Ok(leaf);
+            }
+        }
+
         // Canonical operator order: B-J-F-G-P-R
         // (Base, Join, Filter, GroupBy, Project, Reader)
         let leaf = {
@@ -1814,7 +2050,7 @@ impl SqlToMirConverter {
                     subquery_leaf
                 } else {
                     match self.get_relation(rel) {
-                        Some(node_idx) => node_idx,
+                        Some(node_idx) => self.resolve_stacked_query(query_name, rel, node_idx)?,
                         None => anon_queries
                             .get(rel)
                             .copied()
@@ -1993,6 +2229,50 @@ impl SqlToMirConverter {
                 prev_node = subquery_leaf;
             }
 
+            // Full-text search goes after all other predicates, so that it only has to index the
+            // rows that could actually be returned. If the query is only limited by the relevance
+            // of the search, the full-text search node can apply that limit itself.
+            let mut full_text_search_limited = false;
+            if let Some(fts) = &query_graph.full_text_search {
+                let limit = query_graph
+                    .pagination
+                    .as_ref()
+                    .filter(|p| {
+                        p.order.is_none()
+                            && p.offset.is_none()
+                            && query_graph.aggregates.is_empty()
+                            && query_graph.group_by.is_empty()
+                            && !query_graph.distinct
+                    })
+                    .map(|p| p.limit);
+                full_text_search_limited = limit.is_some();
+
+                prev_node = self.add_query_node(
+                    query_name.clone(),
+                    MirNode::new(
+                        format!(
+                            "q_{:x}_n{}_fts",
+                            query_graph.signature().hash,
+                            self.mir_graph.node_count()
+                        )
+                        .into(),
+                        MirNodeInner::FullTextSearch {
+                            columns: fts.columns.iter().map(Column::from).collect(),
+                            terms: fts.terms.clone(),
+                            match_all: fts.match_all,
+                            group_by: view_key
+                                .columns
+                                .iter()
+                                .filter(|(col, _)| col.name != *PAGE_NUMBER_COL)
+                                .map(|(col, _)| col.clone())
+                                .collect(),
+                            limit,
+                        },
+                    ),
+                    &[prev_node],
+                );
+            }
+
             // 8. Add function and grouped nodes
             let mut func_nodes: Vec<NodeIndex> = make_grouped(
                 self,
@@ -2021,11 +2301,100 @@ impl SqlToMirConverter {
             // 10. Get the final node
             let mut final_node = prev_node;
 
+            if let Some(WindowLimit {
+                function,
+                partition_by,
+                order,
+                limit,
+            }) = &query_graph.window_limit
+            {
+                let mut group_by = view_key
+                    .columns
+                    .iter()
+                    .map(|(col, _)| col.clone())
+                    .chain(partition_by.iter().map(Column::from))
+                    .collect::<Vec<_>>();
+                if group_by.is_empty() {
+                    // Dataflow needs a column to group by, so give every row the same group
+                    let parent_columns = self.mir_graph.columns(final_node);
+                    final_node = self.make_project_node(
+                        query_name,
+                        format!(
+                            "q_{:x}_n{}_bogo_project",
+                            query_graph.signature().hash,
+                            self.mir_graph.node_count()
+                        )
+                        .into(),
+                        final_node,
+                        parent_columns
+                            .into_iter()
+                            .map(ProjectExpr::Column)
+                            .chain(iter::once(ProjectExpr::Expr {
+                                expr: Expr::Literal(0.into()),
+                                alias: BOGOKEY_COL.clone(),
+                            }))
+                            .collect(),
+                    );
+                    func_nodes.push(final_node);
+                    group_by.push(Column::named(BOGOKEY_COL.clone()));
+                }
+
+                let name = format!(
+                    "q_{:x}_n{}",
+                    query_graph.signature().hash,
+                    self.mir_graph.node_count()
+                );
+                match (function, limit, order.as_deref()) {
+                    // `ROW_NUMBER() OVER (... ORDER BY ts DESC) = 1` keeps only the most recent
+                    // row per group, which doesn't need the state of a TopK
+                    (
+                        RankFunction::RowNumber,
+                        1,
+                        Some([(Expr::Column(over), OrderType::OrderDescending)]),
+                    ) => {
+                        let latest = self.add_query_node(
+                            query_name.clone(),
+                            MirNode::new(
+                                name.into(),
+                                MirNodeInner::Latest {
+                                    group_by,
+                                    over: Column::from(over),
+                                },
+                            ),
+                            &[final_node],
+                        );
+                        func_nodes.push(latest);
+                        final_node = latest;
+                    }
+                    _ => {
+                        let mode = match function {
+                            RankFunction::RowNumber => TopKMode::RowNumber,
+                            RankFunction::Rank => TopKMode::WithTies,
+                            RankFunction::DenseRank => TopKMode::DenseRank,
+                        };
+                        let topk_nodes = self.make_paginate_node(
+                            query_name,
+                            name.into(),
+                            final_node,
+                            group_by,
+                            order,
+                            *limit,
+                            Some(mode),
+                        )?;
+                        func_nodes.extend(topk_nodes.clone());
+                        final_node = *topk_nodes.last().unwrap();
+                    }
+                }
+            }
+
             if let Some(Pagination {
                 order,
                 limit,
                 offset,
-            }) = query_graph.pagination.as_ref()
+            }) = query_graph
+                .pagination
+                .as_ref()
+                .filter(|_| !full_text_search_limited)
             {
                 let make_topk = offset.is_none();
                 // view key will have the offset parameter if it exists. We must filter it out
@@ -2042,6 +2411,22 @@ impl SqlToMirConverter {
                     })
                     .collect();
 
+                let order = order.as_ref().map(|order| {
+                    order
+                        .iter()
+                        .map(|(expr, ot)| {
+                            (
+                                replace_grouped_expressions(
+                                    expr.clone(),
+                                    query_graph,
+                                    &expressions_above_grouped,
+                                ),
+                                *ot,
+                            )
+                        })
+                        .collect()
+                });
+
                 // Order by expression projections and either a topk or paginate node
                 let paginate_nodes = self.make_paginate_node(
                     query_name,
@@ -2053,9 +2438,9 @@ impl SqlToMirConverter {
                     .into(),
                     final_node,
                     group_by,
-                    order,
+                    &order,
                     *limit,
-                    make_topk,
+                    make_topk.then_some(TopKMode::RowNumber),
                 )?;
                 func_nodes.extend(paginate_nodes.clone());
                 final_node = *paginate_nodes.last().unwrap();
@@ -2113,7 +2498,11 @@ impl SqlToMirConverter {
                         if !already_computed.contains(oc) {
                             ProjectExpr::Expr {
                                 alias: ac.name.clone(),
-                                expr: ac.expression.clone(),
+                                expr: replace_grouped_expressions(
+                                    ac.expression.clone(),
+                                    query_graph,
+                                    &expressions_above_grouped,
+                                ),
                             }
                         } else {
                             ProjectExpr::Column(Column::named(&ac.name))
@@ -2136,12 +2525,35 @@ impl SqlToMirConverter {
                 .collect::<Vec<_>>();
 
             if leaf_behavior.should_make_leaf() {
+                let parameters = query_graph.parameters();
                 for (column, _) in &view_key.columns {
-                    if !emit
+                    if emit
                         .iter()
                         .any(|expr| matches!(expr, ProjectExpr::Column(c) if c == column))
                     {
-                        emit.push(ProjectExpr::Column(column.clone()))
+                        continue;
+                    }
+
+                    // Parameters on expressions rather than columns need to have the expression
+                    // projected out so that the leaf can be keyed on it
+                    let param_expr = parameters
+                        .iter()
+                        .find(|p| p.expr.is_some() && Column::from(p.col.clone()) == *column)
+                        .and_then(|p| p.expr.clone());
+                    match param_expr {
+                        Some(expr) => {
+                            let already_projected = emit.iter().any(|e| match e {
+                                ProjectExpr::Expr { alias, .. } => *alias == column.name,
+                                ProjectExpr::Column(_) => false,
+                            });
+                            if !already_projected {
+                                emit.push(ProjectExpr::Expr {
+                                    alias: column.name.clone(),
+                                    expr,
+                                })
+                            }
+                        }
+                        None => emit.push(ProjectExpr::Column(column.clone())),
                     }
                 }
             }
@@ -2238,22 +2650,45 @@ impl SqlToMirConverter {
                     project_order,
                 );
 
-                let aggregates = if view_key.index_type != IndexType::HashMap {
+                // Point lookups only need their results re-aggregated if they might be made for
+                // multiple keys at once, which is the case when the adapter has collapsed a
+                // parameterized IN list - and it only does that if post-lookup is allowed
+                let aggregates = if view_key.index_type != IndexType::HashMap
+                    || (self.config.allow_post_lookup
+                        && view_key
+                            .columns
+                            .iter()
+                            .any(|(_, vp)| matches!(vp, ViewPlaceholder::OneToOne(..))))
+                {
                     post_lookup_aggregates(query_graph, query_name)?
                 } else {
                     None
                 };
 
-                let order_by = query_graph
-                    .order
-                    .as_ref()
-                    .map(|order| order.iter().map(|(c, ot)| (Column::from(c), *ot)).collect());
+                let order_by = match &query_graph.order {
+                    Some(order) => {
+                        Some(order.iter().map(|(c, ot)| (Column::from(c), *ot)).collect())
+                    }
+                    // Without an explicit ORDER BY, full-text search results are returned in
+                    // order of relevance
+                    None if query_graph.full_text_search.is_some()
+                        && query_graph.aggregates.is_empty()
+                        && query_graph.group_by.is_empty() =>
+                    {
+                        Some(vec![(
+                            Column::named(FULL_TEXT_SCORE_COL.clone()),
+                            OrderType::OrderDescending,
+                        )])
+                    }
+                    None => None,
+                };
 
                 let limit = query_graph.pagination.as_ref().map(|p| p.limit);
 
-                if !self.config.allow_post_lookup
-                    && (aggregates.is_some() || order_by.is_some() || limit.is_some())
-                {
+                // ORDER BY on its own doesn't count as a post-lookup operation, since readers keep
+                // the rows for each key sorted according to the order annotated on the leaf, and
+                // results for multiple keys are merged lazily without ever re-sorting
+                if !self.config.allow_post_lookup && (aggregates.is_some() || limit.is_some()) {
                     unsupported!("Queries which perform operations post-lookup are not supported");
                 }
 
@@ -2318,10 +2753,156 @@ impl SqlToMirConverter {
         if leaf_behavior.should_register() {
             self.relations.insert(query_name.clone(), leaf);
         }
+        if let Some(lookup_graph) = lookup_graph {
+            self.lookup_query_graphs
+                .insert(query_name.clone(), lookup_graph);
+        }
 
         debug!(query_name = %query_name.display_unquoted(), "Added final MIR node for query");
 
         // finally, we output all the nodes we generated
         Ok(leaf)
     }
+
+    /// Look for an existing cached query whose query graph is identical to `lookup_graph` (see
+    /// [`lookup_query_graph`]), and if one exists, add a new leaf for `query_name` next to the
+    /// leaf of that query, keyed on the parameters of `query_graph`, rather than building a whole
+    /// new set of nodes for the query. Returns the index of the new leaf, if one was added.
+    ///
+    /// When lowered to dataflow, this adds another reader (with its own index) to the node the
+    /// existing query's reader reads from.
+    fn add_leaf_to_existing_query(
+        &mut self,
+        query_name: &Relation,
+        query_graph: &QueryGraph,
+        lookup_graph: &QueryGraph,
+    ) -> ReadySetResult<Option<NodeIndex>> {
+        let view_key = query_graph.view_key(self.config())?;
+        let Some((existing_query, existing_leaf, parent, returned_cols)) = self
+            .lookup_query_graphs
+            .iter()
+            .filter(|(name, qg)| *name != query_name && *qg == lookup_graph)
+            .find_map(|(name, _)| {
+                let leaf = self.get_relation(name)?;
+                let MirNodeInner::Leaf {
+                    lowered_to_df: true,
+                    returned_cols,
+                    ..
+                } = &self.mir_graph[leaf].inner
+                else {
+                    return None;
+                };
+                let parent = self
+                    .mir_graph
+                    .neighbors_directed(leaf, Direction::Incoming)
+                    .next()?;
+                // The parameters of the new query are only guaranteed to be carried through to
+                // the leaf of the existing query if it's keyed on them too, or if they're
+                // projected
+                view_key
+                    .columns
+                    .iter()
+                    .all(|(col, _)| self.mir_graph.column_id_for_column(parent, col).is_ok())
+                    .then(|| (name.clone(), leaf, parent, returned_cols.clone()))
+            })
+        else {
+            return Ok(None);
+        };
+
+        trace!(
+            query_name = %query_name.display_unquoted(),
+            existing_query = %existing_query.display_unquoted(),
+            "Adding leaf to existing query"
+        );
+
+        let shared_nodes = self
+            .mir_graph
+            .node_indices()
+            .filter(|&n| n != existing_leaf && self.mir_graph[n].is_owned_by(&existing_query))
+            .collect::<Vec<_>>();
+        for node in shared_nodes {
+            self.mir_graph[node].add_owner(query_name.clone());
+        }
+
+        Ok(Some(self.add_query_node(
+            query_name.clone(),
+            MirNode::new(
+                query_name.clone(),
+                MirNodeInner::Leaf {
+                    returned_cols,
+                    ..MirNodeInner::leaf(view_key.columns, view_key.index_type)
+                },
+            ),
+            &[parent],
+        )))
+    }
+
+    /// If the given relation, which is being read from by the query named `query_name`, is a
+    /// cached query, returns the node that the query should read from in its place - the parent of
+    /// the cached query's leaf. Otherwise, returns `node` unchanged.
+    ///
+    /// This allows cached queries to be stacked on top of other cached queries (eg a daily rollup
+    /// over an hourly rollup), sharing the dataflow nodes of the cached query they read from. Only
+    /// unparameterized cached queries which don't perform any operations post-lookup can be read
+    /// from this way, since otherwise the rows of the cached query aren't all materialized in
+    /// dataflow.
+    fn resolve_stacked_query(
+        &mut self,
+        query_name: &Relation,
+        relation: &Relation,
+        node: NodeIndex,
+    ) -> ReadySetResult<NodeIndex> {
+        let MirNodeInner::Leaf {
+            keys,
+            limit,
+            aggregates,
+            ..
+        } = &self.mir_graph[node].inner
+        else {
+            return Ok(node);
+        };
+
+        if keys
+            .iter()
+            .any(|(_, placeholder)| *placeholder != ViewPlaceholder::Generated)
+        {
+            unsupported!(
+                "Cannot read from parameterized cached query {}",
+                relation.display_unquoted()
+            );
+        }
+        if limit.is_some() || aggregates.is_some() {
+            unsupported!(
+                "Cannot read from cached query {}, which performs operations post-lookup",
+                relation.display_unquoted()
+            );
+        }
+
+        let parent = self
+            .mir_graph
+            .neighbors_directed(node, Direction::Incoming)
+            .next()
+            .ok_or_else(|| internal_err!("Leaf node must have a parent"))?;
+        trace!(
+            query_name = %query_name.display_unquoted(),
+            stacked_on = %relation.display_unquoted(),
+            "Stacking query on cached query"
+        );
+        self.stacked_queries
+            .entry(relation.clone())
+            .or_default()
+            .insert(query_name.clone());
+        Ok(parent)
+    }
+
+    /// Returns true if the given node is a leaf whose parents have all already been lowered to
+    /// dataflow, which is the case for leaves added by
+    /// [`add_leaf_to_existing_query`][Self::add_leaf_to_existing_query].
+    pub(super) fn leaf_parents_lowered(&self, leaf: NodeIndex) -> bool {
+        matches!(self.mir_graph[leaf].inner, MirNodeInner::Leaf { .. })
+            && self
+                .mir_graph
+                .neighbors_directed(leaf, Direction::Incoming)
+                .all(|parent| self.mir_graph[parent].df_node_index().is_some())
+    }
 }
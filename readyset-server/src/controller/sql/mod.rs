@@ -1,33 +1,46 @@
 use std::collections::{HashMap, HashSet};
 use std::str;
+use std::time::SystemTime;
 use std::vec::Vec;
 
 use ::mir::visualize::GraphViz;
-use ::mir::DfNodeIndex;
+use ::mir::{DfNodeIndex, RewriteOptions};
 use ::serde::{Deserialize, Serialize};
+use database_utils::IgnoredColumns;
+use dataflow::node::Column as DfColumn;
+use nom_sql::analysis::visit::{self, Visitor};
 use nom_sql::{
-    CompoundSelectOperator, CompoundSelectStatement, CreateTableBody, DialectDisplay,
-    FieldDefinitionExpr, NonReplicatedRelation, NotReplicatedReason, Relation, SelectSpecification,
-    SelectStatement, SqlIdentifier, SqlType, TableExpr,
+    AlterTableDefinition, AlterTableStatement, Column, ColumnConstraint, ColumnSpecification,
+    CompoundSelectOperator, CompoundSelectStatement, CreateIndexStatement, CreateTableBody,
+    DialectDisplay, DropIndexStatement, Expr, FieldDefinitionExpr, Literal, NonReplicatedRelation,
+    NotReplicatedReason, Relation, SelectSpecification, SelectStatement, SqlIdentifier, SqlType,
+    TableExpr, TableExprInner, TableKey,
 };
 use petgraph::graph::NodeIndex;
 use readyset_client::query::QueryId;
-use readyset_client::recipe::changelist::{AlterTypeChange, Change, PostgresTableMetadata};
-use readyset_client::recipe::ChangeList;
-use readyset_data::{DfType, Dialect, PgEnumMetadata};
+use readyset_client::recipe::changelist::{
+    AlterTypeChange, Change, CreateCache, PostgresTableMetadata,
+};
+use readyset_client::recipe::{ChangeList, QueryLogEntry, QueryRewrite, QuerySource};
+use readyset_client::ColumnProvenance;
+use readyset_data::{DfType, DfValue, Dialect, PgEnumMetadata};
 use readyset_errors::{
-    internal, internal_err, invalid_query_err, invariant, unsupported, ReadySetError,
+    internal, internal_err, invalid_query_err, invariant, invariant_eq, unsupported, ReadySetError,
     ReadySetResult,
 };
 use readyset_sql_passes::alias_removal::TableAliasRewrite;
-use readyset_sql_passes::{AliasRemoval, DetectUnsupportedPlaceholders, Rewrite, RewriteContext};
+use readyset_sql_passes::expr::scalar_optimize_expr;
+use readyset_sql_passes::{
+    AliasRemoval, DetectUnsupportedPlaceholders, RenameReferences, Rewrite, RewriteContext,
+};
 use readyset_util::redacted::Sensitive;
 use tracing::{debug, error, info, trace, warn};
 use vec1::Vec1;
 
 use self::mir::{LeafBehavior, NodeIndex as MirNodeIndex, SqlToMirConverter};
 use self::query_graph::to_query_graph;
-pub(crate) use self::recipe::{ExprId, Recipe, Schema};
+use self::query_log::QueryLog;
+pub(crate) use self::recipe::{ExprId, Recipe, RecipeVersions, Schema};
 use self::registry::ExprRegistry;
 use crate::controller::mir_to_flow::{mir_node_to_flow_parts, mir_query_to_flow_parts};
 pub(crate) use crate::controller::sql::registry::RecipeExpr;
@@ -37,6 +50,7 @@ use crate::ReuseConfigType;
 
 pub(crate) mod mir;
 mod query_graph;
+mod query_log;
 mod query_signature;
 mod recipe;
 mod registry;
@@ -46,12 +60,17 @@ mod registry;
 #[non_exhaustive]
 pub(crate) struct Config {
     pub(crate) reuse_type: Option<ReuseConfigType>,
+    /// Columns which are never replicated from the upstream database, and so can't be referenced
+    /// by queries
+    #[serde(default)]
+    pub(crate) ignored_columns: IgnoredColumns,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             reuse_type: Some(ReuseConfigType::Finkelstein),
+            ignored_columns: Default::default(),
         }
     }
 }
@@ -83,6 +102,25 @@ pub(crate) struct BaseSchema {
     pub pg_meta: Option<PostgresTableMetadata>,
 }
 
+/// An index on a table in the upstream database, recorded from a `CREATE INDEX` statement
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct IndexHint {
+    /// The name of the index, if it was given one
+    name: Option<SqlIdentifier>,
+    /// The names of the indexed columns, in order
+    columns: Vec<SqlIdentifier>,
+}
+
+/// Where the columns used by a compiled query come from, computed from the query's MIR when it's
+/// lowered to dataflow
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct QueryProvenance {
+    /// Where each output column of the query is derived from, in order
+    columns: Vec<ColumnProvenance>,
+    /// All the base table columns referenced anywhere in the query
+    referenced_base_columns: Vec<Column>,
+}
+
 /// Long-lived struct that holds information about the SQL queries (tables, views, and caches) that
 /// have been incorporated into the dataflow graph.
 ///
@@ -131,6 +169,29 @@ pub(crate) struct SqlIncorporator {
 
     /// Whether or to treat failed writes to base tables as no-ops
     permissive_writes: bool,
+
+    /// The log of installed cached queries, recording where each came from
+    #[serde(default)]
+    query_log: QueryLog,
+
+    /// The provenance of the columns of each query which has been compiled to dataflow
+    #[serde(default)]
+    provenance: HashMap<Relation, QueryProvenance>,
+
+    /// Indexes created in the upstream database via `CREATE INDEX`, keyed by the
+    /// (schema-qualified) name of the table they're on. Passed, along with any keys in the table's
+    /// `CREATE TABLE` statement, to base nodes as index hints when they're created.
+    #[serde(default)]
+    index_hints: HashMap<Relation, Vec<IndexHint>>,
+
+    /// Options for the MIR rewrite passes run on queries compiled to dataflow. Overridden for the
+    /// duration of adding individual caches, then reset to the default.
+    #[serde(skip)]
+    rewrite_options: RewriteOptions,
+
+    /// The names of the caches whose queries were compiled with predicate pushdown disabled
+    #[serde(default)]
+    predicate_pushdown_disabled: HashSet<Relation>,
 }
 
 impl SqlIncorporator {
@@ -223,12 +284,28 @@ impl SqlIncorporator {
             dialect,
         } = changelist;
 
-        for change in changes {
+        for change in order_stacked_caches(changes) {
             match change {
                 Change::CreateTable {
                     statement: mut cts,
                     pg_meta,
                 } => {
+                    if let Some(select) = cts.select.take() {
+                        let body = match cts.body {
+                            Ok(body) => body,
+                            Err(unparsed) => unsupported!(
+                                "CREATE TABLE {} body failed to parse: {}",
+                                cts.table.display_unquoted(),
+                                Sensitive(&unparsed)
+                            ),
+                        };
+                        cts.body = Ok(self.table_body_for_select(
+                            &cts.table,
+                            body,
+                            &select,
+                            &schema_search_path,
+                        )?);
+                    }
                     cts = self.rewrite(cts, &schema_search_path, dialect, None)?;
                     let body = match cts.body {
                         Ok(body) => body,
@@ -367,12 +444,48 @@ impl SqlIncorporator {
                     self.add_view(stmt.name, definition, schema_search_path.clone())?;
                 }
                 Change::CreateCache(cc) => {
-                    self.add_query(cc.name, *cc.statement, cc.always, &schema_search_path, mig)?;
+                    let received_sql = cc.statement.display(dialect.into()).to_string();
+                    mig.set_frontier_strategy(cc.frontier_strategy);
+                    mig.set_standby_readers(cc.standby);
+                    self.rewrite_options.push_filters_up = !cc.disable_predicate_pushdown;
+                    let res =
+                        self.add_query(cc.name, *cc.statement, cc.always, &schema_search_path, mig);
+                    mig.set_frontier_strategy(None);
+                    mig.set_standby_readers(false);
+                    self.rewrite_options = Default::default();
+                    let name = res?;
+                    self.record_installed_query(
+                        name,
+                        cc.source,
+                        cc.original_sql,
+                        received_sql,
+                        dialect,
+                    );
+                }
+                Change::AlterTable(mut stmt) => {
+                    // Other than renames and added or dropped columns (which only end up here
+                    // without an upstream database, since the replicator resnapshots tables
+                    // instead), the ALTER TABLE changes that can end up here (currently) are ones
+                    // that aren't relevant to ReadySet, so we can just ignore them.
+                    if let Some(first_schema) = schema_search_path.first() {
+                        if stmt.table.schema.is_none() {
+                            stmt.table.schema = Some(first_schema.clone())
+                        }
+                    }
+                    self.alter_columns(&stmt, mig)?;
+                    self.apply_renames(stmt, &schema_search_path, mig)?;
                 }
-                Change::AlterTable(_) => {
-                    // The only ALTER TABLE changes that can end up here (currently) are ones that
-                    // aren't relevant to ReadySet, so we can just ignore them.
+                Change::CreateIndex(mut stmt) => {
+                    if let Some(first_schema) = schema_search_path.first() {
+                        if stmt.table.schema.is_none() {
+                            stmt.table.schema = Some(first_schema.clone())
+                        }
+                    }
+                    let table = stmt.table.clone();
+                    self.add_index_hint(stmt);
+                    self.update_base_index_hints(&table, mig)?;
                 }
+                Change::DropIndex(stmt) => self.remove_index_hint(stmt, &schema_search_path),
                 Change::CreateType { mut name, ty } => {
                     if let Some(first_schema) = schema_search_path.first() {
                         if name.schema.is_none() {
@@ -470,6 +583,7 @@ impl SqlIncorporator {
                     } else {
                         self.remove_expression(&name, mig)?.is_some()
                     };
+                    self.index_hints.remove(&name);
 
                     if !removed && !if_exists {
                         error!(
@@ -539,6 +653,9 @@ impl SqlIncorporator {
             }
         }
 
+        let registry = &self.registry;
+        self.query_log.retain(|name| registry.get(name).is_some());
+
         Ok(())
     }
 
@@ -575,12 +692,604 @@ impl SqlIncorporator {
         pg_meta: Option<PostgresTableMetadata>,
         mig: &mut Migration<'_>,
     ) -> ReadySetResult<()> {
+        // Ignored columns are replicated as NULL, which would collapse all the rows of the table
+        // into one if the primary key included an ignored column
+        if let Some(ignored) = self.config.ignored_columns.for_table(&name) {
+            let ignored_key_column = body.keys.iter().flatten().find_map(|key| match key {
+                TableKey::PrimaryKey { columns, .. } => {
+                    columns.iter().find(|c| ignored.contains(&c.name))
+                }
+                _ => None,
+            });
+            if let Some(column) = ignored_key_column {
+                return Err(ReadySetError::TableError {
+                    table: name.clone(),
+                    source: Box::new(ReadySetError::Unsupported(format!(
+                        "Primary key column {} can't be ignored by --replication-columns-ignore",
+                        column.name
+                    ))),
+                });
+            }
+        }
+
         let (name, dataflow_idx) = self.add_base_via_mir(name, body, pg_meta, mig)?;
         self.remove_non_replicated_relation(&NonReplicatedRelation::new(name.clone()));
         self.leaf_addresses.insert(name, dataflow_idx);
         Ok(())
     }
 
+    /// Apply the table and column renames in the given `ALTER TABLE` statement, whose table must be
+    /// schema-qualified, ignoring any other definitions in the statement.
+    ///
+    /// The table is recreated under its new name and schema, so it has to be snapshotted again
+    /// afterwards. The views and caches which depend on the table are recreated with their
+    /// references to the table and its columns remapped to the new names, or dropped if that fails.
+    fn apply_renames(
+        &mut self,
+        stmt: AlterTableStatement,
+        schema_search_path: &[SqlIdentifier],
+        mig: &mut Migration<'_>,
+    ) -> ReadySetResult<()> {
+        let Ok(definitions) = stmt.definitions else {
+            return Ok(());
+        };
+        let mut new_name = stmt.table.clone();
+        let mut column_renames = HashMap::new();
+        for definition in definitions {
+            match definition {
+                AlterTableDefinition::RenameTable { new_name: name } => {
+                    new_name = Relation {
+                        schema: name.schema.or_else(|| stmt.table.schema.clone()),
+                        name: name.name,
+                    }
+                }
+                AlterTableDefinition::RenameColumn { name, new_name } => {
+                    column_renames.insert(name, new_name);
+                }
+                AlterTableDefinition::ChangeColumn { name, spec } if spec.column.name != name => {
+                    column_renames.insert(name, spec.column.name);
+                }
+                _ => {}
+            }
+        }
+        if new_name == stmt.table && column_renames.is_empty() {
+            return Ok(());
+        }
+
+        let Some(RecipeExpr::Table {
+            name: table,
+            body,
+            pg_meta,
+        }) = self.registry.get(&stmt.table).cloned()
+        else {
+            trace!(
+                table = %stmt.table.display_unquoted(),
+                "Ignoring rename of table that doesn't exist"
+            );
+            return Ok(());
+        };
+        info!(
+            table = %table.display_unquoted(),
+            new_name = %new_name.display_unquoted(),
+            ?column_renames,
+            "Renaming table or columns"
+        );
+
+        let body = body.rename_references(&table, &new_name, &column_renames);
+        let pg_meta = pg_meta.map(|meta| PostgresTableMetadata {
+            column_oids: meta
+                .column_oids
+                .into_iter()
+                .map(|(col, attnum)| (column_renames.get(&col).cloned().unwrap_or(col), attnum))
+                .collect(),
+            ..meta
+        });
+        if let Some(hints) = self.index_hints.remove(&table) {
+            let hints = hints
+                .into_iter()
+                .map(|hint| IndexHint {
+                    columns: hint
+                        .columns
+                        .into_iter()
+                        .map(|col| column_renames.get(&col).cloned().unwrap_or(col))
+                        .collect(),
+                    ..hint
+                })
+                .collect();
+            self.index_hints.insert(new_name.clone(), hints);
+        }
+
+        let dependents = self
+            .registry
+            .expressions_referencing_table(&table)
+            .cloned()
+            .collect::<Vec<_>>();
+        let predicate_pushdown_disabled = self.predicate_pushdown_disabled.clone();
+        for dependent in &dependents {
+            self.remove_expression(dependent.name(), mig)?;
+        }
+        self.remove_expression(&table, mig)?;
+        self.invalidate_queries_for_added_relation(&new_name, mig)?;
+        self.add_table(new_name.clone(), body.clone(), pg_meta.clone(), mig)?;
+        self.registry.add_query(RecipeExpr::Table {
+            name: new_name.clone(),
+            body,
+            pg_meta,
+        })?;
+
+        for view in self.uncompiled_views.values_mut() {
+            view.definition =
+                view.definition
+                    .clone()
+                    .rename_references(&table, &new_name, &column_renames);
+        }
+
+        // Views go first, since caches might read from them
+        let (views, caches): (Vec<_>, Vec<_>) = dependents
+            .into_iter()
+            .partition(|expr| matches!(expr, RecipeExpr::View { .. }));
+        for expr in views.into_iter().chain(caches) {
+            let name = expr.name().clone();
+            let res = match expr {
+                RecipeExpr::View { name, definition } => self.add_view(
+                    name,
+                    definition.rename_references(&table, &new_name, &column_renames),
+                    schema_search_path.to_vec(),
+                ),
+                RecipeExpr::Cache {
+                    name,
+                    statement,
+                    always,
+                    ..
+                } => {
+                    // Recompile the cache with the same options it was originally created with
+                    self.rewrite_options.push_filters_up =
+                        !predicate_pushdown_disabled.contains(&name);
+                    let res = self.add_query(
+                        Some(name),
+                        statement.rename_references(&table, &new_name, &column_renames),
+                        always,
+                        schema_search_path,
+                        mig,
+                    );
+                    self.rewrite_options = Default::default();
+                    res.map(|_| ())
+                }
+                RecipeExpr::Table { .. } => Ok(()),
+            };
+            if let Err(error) = res {
+                warn!(
+                    %error,
+                    name = %name.display_unquoted(),
+                    "Could not remap query after rename; dropping it"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply the columns added, dropped and changed by the given `ALTER TABLE` statement, whose
+    /// table must be schema-qualified, to the table in place, keeping its existing rows. Tables
+    /// which don't exist are ignored.
+    ///
+    /// Added columns go at the end of the table, and are backfilled for existing rows with their
+    /// `DEFAULT` value, which must be constant (or `NULL` if there isn't one). Dropped columns
+    /// can't be part of a primary or unique key. Changed columns (via MySQL's `CHANGE` or `MODIFY`)
+    /// take their new definition, but keep their name - renames are applied separately, by
+    /// [`Self::apply_renames`]. Values written after a column's type is changed are coerced to the
+    /// new type, but existing rows keep their values until the table is next snapshotted. Views and
+    /// caches which reference a dropped column or a column whose type changed are removed, but all
+    /// other queries reading from the table are left in place.
+    fn alter_columns(
+        &mut self,
+        stmt: &AlterTableStatement,
+        mig: &mut Migration<'_>,
+    ) -> ReadySetResult<()> {
+        let Ok(definitions) = &stmt.definitions else {
+            return Ok(());
+        };
+        if !definitions.iter().any(|def| {
+            matches!(
+                def,
+                AlterTableDefinition::AddColumn(_)
+                    | AlterTableDefinition::DropColumn { .. }
+                    | AlterTableDefinition::ChangeColumn { .. }
+            )
+        }) {
+            return Ok(());
+        }
+
+        let table = &stmt.table;
+        let Some(RecipeExpr::Table { mut body, .. }) = self.registry.get(table).cloned() else {
+            trace!(
+                table = %table.display_unquoted(),
+                "Ignoring ALTER TABLE of table that doesn't exist"
+            );
+            return Ok(());
+        };
+        let addr = *self
+            .leaf_addresses
+            .get(table)
+            .ok_or_else(|| self.mir_converter.table_not_found_err(table))?;
+
+        // Check all the default values up front, so we don't apply some of the changes before
+        // finding one we can't apply
+        for definition in definitions {
+            if let AlterTableDefinition::AddColumn(spec)
+            | AlterTableDefinition::ChangeColumn { spec, .. } = definition
+            {
+                constant_default(spec, mig.dialect)?;
+            }
+        }
+
+        for definition in definitions {
+            match definition {
+                AlterTableDefinition::AddColumn(spec) => {
+                    let mut spec = spec.clone();
+                    spec.column.table = Some(table.clone());
+                    if body
+                        .fields
+                        .iter()
+                        .any(|f| f.column.name == spec.column.name)
+                    {
+                        return Err(invalid_query_err!(
+                            "Column {} already exists in table {}",
+                            spec.column.name,
+                            table.display_unquoted()
+                        ));
+                    }
+
+                    let column = DfColumn::from_spec(spec.clone(), mig.dialect, |ty| {
+                        self.custom_types.get(&ty).cloned()
+                    })?;
+                    let default = match constant_default(&spec, mig.dialect)? {
+                        Some(lit) => {
+                            DfValue::try_from(&lit)?.coerce_to(column.ty(), &DfType::Unknown)?
+                        }
+                        None if spec.constraints.contains(&ColumnConstraint::NotNull) => {
+                            unsupported!(
+                                "Added NOT NULL column {} must have a default value",
+                                spec.column.name
+                            )
+                        }
+                        None => DfValue::None,
+                    };
+
+                    let idx = mig.add_column(addr, column, default)?;
+                    let mir_idx = self.mir_converter.add_base_column(table, spec.clone())?;
+                    invariant_eq!(idx, mir_idx);
+                    body.fields.push(spec);
+                }
+                AlterTableDefinition::DropColumn { name, .. } => {
+                    let Some(pos) = body.fields.iter().position(|f| f.column.name == *name) else {
+                        return Err(ReadySetError::NoSuchColumn(name.to_string()));
+                    };
+                    let column = body.fields[pos].column.clone();
+                    let in_column_key = body.fields[pos].constraints.iter().any(|c| {
+                        matches!(c, ColumnConstraint::PrimaryKey | ColumnConstraint::Unique)
+                    });
+                    if in_column_key
+                        || body.keys.iter().flatten().any(|key| match key {
+                            TableKey::PrimaryKey { columns, .. }
+                            | TableKey::UniqueKey { columns, .. } => {
+                                columns.iter().any(|c| c.name == *name)
+                            }
+                            _ => false,
+                        })
+                    {
+                        unsupported!("Can't drop column {name}, which is part of a key");
+                    }
+
+                    self.remove_queries_referencing_column(table, name, mig)?;
+
+                    let idx = self.mir_converter.base_column_index(table, &column)?;
+                    mig.drop_column(addr, idx)?;
+                    body.fields.remove(pos);
+                    body.keys = body.keys.map(|keys| {
+                        keys.into_iter()
+                            .filter(|key| match key {
+                                TableKey::Key { columns, .. }
+                                | TableKey::FulltextKey { columns, .. } => {
+                                    !columns.iter().any(|c| c.name == *name)
+                                }
+                                _ => true,
+                            })
+                            .collect()
+                    });
+                }
+                AlterTableDefinition::ChangeColumn { name, spec } => {
+                    let Some(pos) = body.fields.iter().position(|f| f.column.name == *name) else {
+                        return Err(ReadySetError::NoSuchColumn(name.to_string()));
+                    };
+                    let column = body.fields[pos].column.clone();
+                    let spec = ColumnSpecification {
+                        column: column.clone(),
+                        ..spec.clone()
+                    };
+
+                    if spec.sql_type != body.fields[pos].sql_type {
+                        self.remove_queries_referencing_column(table, name, mig)?;
+
+                        let ty = DfColumn::from_spec(spec.clone(), mig.dialect, |ty| {
+                            self.custom_types.get(&ty).cloned()
+                        })?
+                        .ty()
+                        .clone();
+                        let idx = self.mir_converter.base_column_index(table, &column)?;
+                        mig.set_column_type(addr, idx, ty)?;
+                        self.mir_converter
+                            .set_base_column_spec(table, idx, spec.clone())?;
+                    }
+                    body.fields[pos] = spec;
+                }
+                _ => {}
+            }
+        }
+
+        info!(table = %table.display_unquoted(), "Altered columns of table in place");
+        if let Some(schema) = self.base_schemas.get_mut(table) {
+            schema.statement = body.clone();
+        }
+        self.register_query(
+            table.clone(),
+            body.fields.iter().map(|f| f.column.name.clone()).collect(),
+        );
+        self.registry.update_table_body(table, body)
+    }
+
+    /// Remove all the views and caches which reference the column with the given name in the given
+    /// table
+    fn remove_queries_referencing_column(
+        &mut self,
+        table: &Relation,
+        column: &SqlIdentifier,
+        mig: &mut Migration<'_>,
+    ) -> ReadySetResult<()> {
+        // Detect references to the column by renaming it, which takes care of resolving aliases
+        // and unqualified columns
+        let renamed = HashMap::from([(column.clone(), SqlIdentifier::from(""))]);
+        let referencing = self
+            .registry
+            .expressions_referencing_table(table)
+            .filter(|expr| match expr {
+                RecipeExpr::View { definition, .. } => {
+                    definition.clone().rename_references(table, table, &renamed) != *definition
+                }
+                RecipeExpr::Cache { statement, .. } => {
+                    statement.clone().rename_references(table, table, &renamed) != *statement
+                }
+                RecipeExpr::Table { .. } => false,
+            })
+            .map(|expr| expr.name().clone())
+            .collect::<Vec<_>>();
+        for name in referencing {
+            debug!(
+                name = %name.display_unquoted(),
+                column = %column,
+                "Removing query referencing altered column"
+            );
+            self.remove_expression(&name, mig)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the body of the table created by a `CREATE TABLE ... AS SELECT` statement with the
+    /// given name, explicit column definitions and query: the explicitly defined columns, followed
+    /// by the columns selected by the query which weren't defined explicitly.
+    ///
+    /// The selected columns take their types and nullability from the columns of the tables they
+    /// were selected from, so the query can only select columns (or all the columns of a table)
+    /// from base tables. Note that this only determines the schema of the table: the rows returned
+    /// by the query have to be written to the table separately, for example by snapshotting it
+    /// from the upstream database.
+    fn table_body_for_select(
+        &self,
+        table: &Relation,
+        mut body: CreateTableBody,
+        select: &SelectStatement,
+        schema_search_path: &[SqlIdentifier],
+    ) -> ReadySetResult<CreateTableBody> {
+        if !select.ctes.is_empty() {
+            unsupported!("CREATE TABLE ... AS SELECT with common table expressions");
+        }
+
+        // The name each table in the query is referred to by, along with its columns
+        let mut tables = vec![];
+        for table_expr in select
+            .tables
+            .iter()
+            .chain(select.join.iter().flat_map(|join| join.right.table_exprs()))
+        {
+            let TableExprInner::Table(relation) = &table_expr.inner else {
+                unsupported!("CREATE TABLE ... AS SELECT from a subquery");
+            };
+            let schema = self
+                .base_schemas
+                .get(relation)
+                .or_else(|| {
+                    if relation.schema.is_some() {
+                        return None;
+                    }
+                    schema_search_path.iter().find_map(|schema| {
+                        self.base_schemas.get(&Relation {
+                            schema: Some(schema.clone()),
+                            name: relation.name.clone(),
+                        })
+                    })
+                })
+                .ok_or_else(|| self.mir_converter.table_not_found_err(relation))?;
+            let name = table_expr.alias.as_ref().unwrap_or(&relation.name);
+            tables.push((name, &schema.statement.fields));
+        }
+
+        let num_defined = body.fields.len();
+        let mut add_column =
+            |spec: &ColumnSpecification, name: &SqlIdentifier| -> ReadySetResult<()> {
+                match body.fields.iter().position(|f| f.column.name == *name) {
+                    // Explicitly defined columns take precedence over the selected ones
+                    Some(pos) if pos < num_defined => return Ok(()),
+                    Some(_) => {
+                        return Err(invalid_query_err!(
+                            "Duplicate column name {name} in CREATE TABLE ... AS SELECT"
+                        ))
+                    }
+                    None => {}
+                }
+                body.fields.push(ColumnSpecification {
+                    column: Column {
+                        name: name.clone(),
+                        table: Some(table.clone()),
+                    },
+                    sql_type: spec.sql_type.clone(),
+                    constraints: spec
+                        .constraints
+                        .iter()
+                        .filter(|c| matches!(c, ColumnConstraint::NotNull))
+                        .cloned()
+                        .collect(),
+                    comment: None,
+                });
+                Ok(())
+            };
+
+        for field in &select.fields {
+            match field {
+                FieldDefinitionExpr::All => {
+                    for spec in tables.iter().flat_map(|(_, fields)| fields.iter()) {
+                        add_column(spec, &spec.column.name)?;
+                    }
+                }
+                FieldDefinitionExpr::AllInTable(t) => {
+                    let fields = tables
+                        .iter()
+                        .find(|(name, _)| **name == t.name)
+                        .map(|(_, fields)| fields)
+                        .ok_or_else(|| self.mir_converter.table_not_found_err(t))?;
+                    for spec in fields.iter() {
+                        add_column(spec, &spec.column.name)?;
+                    }
+                }
+                FieldDefinitionExpr::Expr {
+                    expr: Expr::Column(column),
+                    alias,
+                } => {
+                    let spec = tables
+                        .iter()
+                        .filter(|(name, _)| {
+                            column.table.as_ref().map_or(true, |t| **name == t.name)
+                        })
+                        .find_map(|(_, fields)| {
+                            fields.iter().find(|f| f.column.name == column.name)
+                        })
+                        .ok_or_else(|| ReadySetError::NoSuchColumn(column.name.to_string()))?;
+                    add_column(spec, alias.as_ref().unwrap_or(&column.name))?;
+                }
+                FieldDefinitionExpr::Expr { expr, .. } => unsupported!(
+                    "CREATE TABLE ... AS SELECT can only select columns, not {}",
+                    expr.display(nom_sql::Dialect::MySQL)
+                ),
+            }
+        }
+
+        Ok(body)
+    }
+
+    /// Record the columns indexed by the given `CREATE INDEX` statement, whose table must be
+    /// schema-qualified, as an index hint for its table.
+    ///
+    /// This only records the hint; to apply it to the base node for a table that already exists,
+    /// see [`Self::update_base_index_hints`].
+    fn add_index_hint(&mut self, stmt: CreateIndexStatement) {
+        let hints = self.index_hints.entry(stmt.table).or_default();
+        if stmt.name.is_some() && hints.iter().any(|hint| hint.name == stmt.name) {
+            // The upstream database wouldn't have allowed creating an index with the same name
+            // unless it was `IF NOT EXISTS`, in which case the existing index is kept
+            return;
+        }
+        hints.push(IndexHint {
+            name: stmt.name,
+            columns: stmt.columns.into_iter().map(|c| c.name).collect(),
+        });
+    }
+
+    /// Replace the index hints of the existing base node for the table with the given name, if
+    /// any, with the ones currently recorded for that table
+    fn update_base_index_hints(
+        &self,
+        table: &Relation,
+        mig: &mut Migration<'_>,
+    ) -> ReadySetResult<()> {
+        let (Some(schema), Some(addr)) =
+            (self.base_schemas.get(table), self.leaf_addresses.get(table))
+        else {
+            return Ok(());
+        };
+        let index_hints = self.index_hints_for_table(table, &schema.statement);
+        mig.set_base_index_hints(*addr, index_hints)
+    }
+
+    /// Remove the index hint recorded for the index dropped by the given `DROP INDEX` statement, if
+    /// any.
+    ///
+    /// Existing base nodes keep any indexes added for the dropped index, since those indexes are
+    /// still valid (if possibly unused).
+    fn remove_index_hint(
+        &mut self,
+        stmt: DropIndexStatement,
+        schema_search_path: &[SqlIdentifier],
+    ) {
+        // In PostgreSQL, the index is in the same schema as its table
+        let schema = stmt
+            .name
+            .schema
+            .or_else(|| stmt.table.as_ref().and_then(|t| t.schema.clone()))
+            .or_else(|| schema_search_path.first().cloned());
+        for (table, hints) in &mut self.index_hints {
+            if table.schema != schema || stmt.table.as_ref().is_some_and(|t| t.name != table.name) {
+                continue;
+            }
+            hints.retain(|hint| hint.name.as_ref() != Some(&stmt.name.name));
+        }
+        self.index_hints.retain(|_, hints| !hints.is_empty());
+    }
+
+    /// Returns the sets of column indices to use as index hints for the base node for the table
+    /// with the given name and body: any non-primary keys in the table's body, followed by any
+    /// indexes recorded for the table via `CREATE INDEX`.
+    fn index_hints_for_table(&self, name: &Relation, body: &CreateTableBody) -> Vec<Vec<usize>> {
+        let key_columns = body
+            .keys
+            .iter()
+            .flatten()
+            .filter_map(|key| match key {
+                TableKey::UniqueKey { columns, .. } | TableKey::Key { columns, .. } => {
+                    Some(columns.iter().map(|c| &c.name).collect::<Vec<_>>())
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let hint_columns = self
+            .index_hints
+            .get(name)
+            .into_iter()
+            .flatten()
+            .map(|hint| hint.columns.iter().collect::<Vec<_>>());
+
+        key_columns
+            .into_iter()
+            .chain(hint_columns)
+            .filter_map(|columns| {
+                // Skip indexes on columns we don't know about (eg ignored columns)
+                columns
+                    .into_iter()
+                    .map(|col| body.fields.iter().position(|f| f.column.name == *col))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .filter(|columns| !columns.is_empty())
+            .collect()
+    }
+
     /// Add a new SQL VIEW, specified by the given `CREATE VIEW` statement, to the db
     fn add_view(
         &mut self,
@@ -601,6 +1310,73 @@ impl SqlIncorporator {
         Ok(())
     }
 
+    /// Record the query that was just installed under `name` in the query log, given what caused it
+    /// to be installed, the SQL text of the query before any rewrites (if it was rewritten before
+    /// being sent to the server), and the SQL text of the query as received by the server.
+    fn record_installed_query(
+        &mut self,
+        name: Relation,
+        source: QuerySource,
+        original_sql: Option<String>,
+        received_sql: String,
+        dialect: Dialect,
+    ) {
+        let Some(RecipeExpr::Cache { statement, .. }) = self.registry.get(&name) else {
+            return;
+        };
+        let installed_sql = statement.display(dialect.into()).to_string();
+
+        let mut rewrites = vec![];
+        if original_sql.is_some() {
+            rewrites.push(QueryRewrite::Adapter);
+        }
+        if installed_sql != received_sql {
+            rewrites.push(QueryRewrite::Server);
+        }
+        if let Some(target) = self.registry.resolve_alias(&name).filter(|n| **n != name) {
+            rewrites.push(QueryRewrite::AliasOf(target.clone()));
+        }
+        if let Some(caches) = self.registry.reused_caches(&name) {
+            rewrites.push(QueryRewrite::ReusesCaches(
+                caches.iter().map(|cache| cache.name().clone()).collect(),
+            ));
+        }
+
+        self.query_log.record(QueryLogEntry {
+            name,
+            source,
+            installed_at: SystemTime::now(),
+            original_sql: original_sql.unwrap_or(received_sql),
+            installed_sql,
+            rewrites,
+        });
+    }
+
+    /// Returns all the entries in the log of installed cached queries, in the order they were
+    /// installed
+    pub(crate) fn query_log(&self) -> Vec<QueryLogEntry> {
+        self.query_log.entries()
+    }
+
+    /// Returns where each output column of the compiled query with the given (non-aliased) name is
+    /// derived from, or `None` if no such query has been compiled to dataflow
+    pub(crate) fn column_provenance(&self, query: &Relation) -> Option<&[ColumnProvenance]> {
+        self.provenance.get(query).map(|p| p.columns.as_slice())
+    }
+
+    /// Returns the names of all the compiled queries which reference the given base table column,
+    /// and so would be affected by changes to it
+    pub(crate) fn queries_referencing_column(&self, column: &Column) -> Vec<Relation> {
+        let mut queries = self
+            .provenance
+            .iter()
+            .filter(|(_, p)| p.referenced_base_columns.contains(column))
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        queries.sort();
+        queries
+    }
+
     /// Add a new query to the graph, using the given `mig` to track changes.
     ///
     /// If `name` is provided, will use that as the name for the query to add, otherwise a unique
@@ -616,6 +1392,28 @@ impl SqlIncorporator {
         let name = name.unwrap_or_else(|| format!("q_{}", self.num_queries).into());
         let query_id = QueryId::from_select(&stmt, schema_search_path);
 
+        // An equivalent query which is already cached is reused as-is, so it has to have been
+        // compiled with the same options
+        let disable_predicate_pushdown = !self.rewrite_options.push_filters_up;
+        if let Some(existing) = self
+            .registry
+            .cache_with_query_id(query_id)
+            .and_then(|expr_id| self.registry.expression_name(expr_id))
+        {
+            if self.predicate_pushdown_disabled.contains(&existing) != disable_predicate_pushdown {
+                unsupported!(
+                    "Query is already cached as {}, which was created {} DISABLE PREDICATE \
+                     PUSHDOWN; drop that cache first to change the option",
+                    existing.display_unquoted(),
+                    if disable_predicate_pushdown {
+                        "without"
+                    } else {
+                        "with"
+                    }
+                );
+            }
+        }
+
         let mut invalidating_tables = vec![];
         let detect_placeholders_config =
             readyset_sql_passes::detect_unsupported_placeholders::Config {
@@ -687,6 +1485,9 @@ impl SqlIncorporator {
         if let Some(mir_query) = mir_query {
             let leaf = self.mir_to_dataflow(name.clone(), mir_query, mig)?;
             self.leaf_addresses.insert(name.clone(), leaf);
+            if disable_predicate_pushdown {
+                self.predicate_pushdown_disabled.insert(name.clone());
+            }
         }
 
         Ok(name)
@@ -947,6 +1748,11 @@ impl SqlIncorporator {
                 .ok_or_else(|| internal_err!("Base MIR nodes must have a Dataflow node assigned"))?
                 .address();
 
+        let index_hints = self.index_hints_for_table(&name, &statement);
+        if !index_hints.is_empty() {
+            mig.set_base_index_hints(dataflow_node, index_hints)?;
+        }
+
         self.base_schemas
             .insert(name.clone(), BaseSchema { statement, pg_meta });
 
@@ -1061,6 +1867,86 @@ impl SqlIncorporator {
         }
     }
 
+    /// Returns an error if the given (rewritten) query references any columns which are ignored by
+    /// the replicator, since those columns only ever contain NULL
+    fn check_ignored_columns(&self, stmt: &SelectStatement) -> ReadySetResult<()> {
+        if self.config.ignored_columns.is_empty() {
+            return Ok(());
+        }
+
+        #[derive(Default)]
+        struct ColumnReferences<'ast> {
+            columns: Vec<&'ast Column>,
+            aliases: HashMap<&'ast SqlIdentifier, &'ast Relation>,
+        }
+
+        impl<'ast> Visitor<'ast> for ColumnReferences<'ast> {
+            type Error = !;
+
+            fn visit_column(&mut self, column: &'ast Column) -> Result<(), Self::Error> {
+                self.columns.push(column);
+                Ok(())
+            }
+
+            fn visit_table_expr(&mut self, table_expr: &'ast TableExpr) -> Result<(), Self::Error> {
+                if let (TableExprInner::Table(table), Some(alias)) =
+                    (&table_expr.inner, &table_expr.alias)
+                {
+                    self.aliases.insert(alias, table);
+                }
+                visit::walk_table_expr(self, table_expr)
+            }
+        }
+
+        let mut references = ColumnReferences::default();
+        let Ok(()) = references.visit_select_statement(stmt);
+
+        for column in references.columns {
+            let Some(table) = &column.table else {
+                continue;
+            };
+            let table = match table.schema {
+                None => references
+                    .aliases
+                    .get(&table.name)
+                    .copied()
+                    .unwrap_or(table),
+                Some(_) => table,
+            };
+            if self.config.ignored_columns.contains(table, &column.name) {
+                return Err(ReadySetError::ColumnNotReplicated {
+                    table: table.display_unquoted().to_string(),
+                    column: column.name.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the fields to use to select all the columns of the given table which aren't ignored
+    /// by the replicator
+    fn replicated_fields(&self, table: &Relation) -> Vec<FieldDefinitionExpr> {
+        match (
+            self.config.ignored_columns.for_table(table),
+            self.base_schemas.get(table),
+        ) {
+            (Some(ignored), Some(BaseSchema { statement, .. })) => statement
+                .fields
+                .iter()
+                .filter(|field| !ignored.contains(&field.column.name))
+                .map(|field| FieldDefinitionExpr::Expr {
+                    expr: Expr::Column(Column {
+                        name: field.column.name.clone(),
+                        table: Some(table.clone()),
+                    }),
+                    alias: None,
+                })
+                .collect(),
+            _ => vec![FieldDefinitionExpr::All],
+        }
+    }
+
     /// Compile a select statement to MIR within the context of the given migration, but *not*
     /// handling errors caused by referencing uncompiled views.
     ///
@@ -1077,6 +1963,7 @@ impl SqlIncorporator {
         // FIXME(REA-2168): Use correct dialect.
         trace!(stmt = %stmt.display(nom_sql::Dialect::MySQL), "Adding select query");
         *stmt = self.rewrite(stmt.clone(), search_path, mig.dialect, invalidating_tables)?;
+        self.check_ignored_columns(stmt)?;
 
         self.num_queries += 1;
 
@@ -1091,8 +1978,8 @@ impl SqlIncorporator {
                     to_view, for_table, ..
                 } => {
                     let mut query = SelectStatement {
+                        fields: self.replicated_fields(&for_table),
                         tables: vec![TableExpr::from(for_table)],
-                        fields: vec![FieldDefinitionExpr::All],
                         ..Default::default()
                     };
                     let subquery_leaf = self.select_query_to_mir(
@@ -1193,17 +2080,31 @@ impl SqlIncorporator {
             qname: query_name.display_unquoted().to_string(),
             source: Box::new(e),
         };
+        // Queries which only added a leaf to the nodes of an existing query mustn't be rewritten,
+        // since rewriting them would modify the nodes of the other query
+        let shares_nodes = self.mir_converter.leaf_parents_lowered(mir_leaf);
         let mir_query = self
             .mir_converter
             .make_mir_query(query_name.clone(), mir_leaf);
 
         trace!(pre_opt_mir = %mir_query.to_graphviz());
-        let mut opt_mir = mir_query.rewrite().map_err(on_err)?;
+        let mut opt_mir = if shares_nodes {
+            mir_query
+        } else {
+            mir_query.rewrite(self.rewrite_options).map_err(on_err)?
+        };
         trace!(post_opt_mir = %opt_mir.to_graphviz());
 
         let df_leaf =
             mir_query_to_flow_parts(&mut opt_mir, &self.custom_types, mig).map_err(on_err)?;
         let fields = opt_mir.fields();
+        self.provenance.insert(
+            query_name.clone(),
+            QueryProvenance {
+                columns: opt_mir.column_provenance(),
+                referenced_base_columns: opt_mir.referenced_base_columns(),
+            },
+        );
 
         self.register_query(query_name, fields);
 
@@ -1223,6 +2124,8 @@ impl SqlIncorporator {
         let mut mir_removal_result = self.mir_converter.remove_query(query_name)?;
         self.process_removal(&mut mir_removal_result, mig);
         self.view_schemas.remove(query_name);
+        self.provenance.remove(query_name);
+        self.predicate_pushdown_disabled.remove(query_name);
         Ok(mir_removal_result)
     }
 
@@ -1250,9 +2153,23 @@ impl SqlIncorporator {
 
     fn process_removal(&mut self, removal_result: &mut MirRemovalResult, mig: &mut Migration<'_>) {
         for query in removal_result.relations_removed.iter() {
-            self.leaf_addresses.remove(query);
+            // If the node the query's reader reads from isn't being removed (because it's shared
+            // with another query which reads from it by a different key), the reader has to be
+            // removed on its own
+            if let Some(leaf) = self.leaf_addresses.remove(query) {
+                if !removal_result
+                    .dataflow_nodes_to_remove
+                    .contains(&DfNodeIndex::new(leaf))
+                {
+                    if let Some(reader) = mig.dataflow_state.find_reader_for(leaf, query, &None) {
+                        mig.changes.drop_node(reader);
+                    }
+                }
+            }
             self.registry.remove_expression(query);
             self.view_schemas.remove(query);
+            self.provenance.remove(query);
+            self.predicate_pushdown_disabled.remove(query);
         }
         // Sadly, we don't use `DfNodeIndex` for migrations/df state, so we need to map them
         // to `NodeIndex`.
@@ -1290,3 +2207,114 @@ impl SqlIncorporator {
         self.view_schemas.insert(query_name, fields);
     }
 }
+
+/// Returns the `DEFAULT` value of the given column, if it has one, or an unsupported error if that
+/// value isn't constant (eg `DEFAULT CURRENT_TIMESTAMP`), since we can't evaluate it for rows
+/// written by the upstream database.
+fn constant_default(
+    spec: &ColumnSpecification,
+    dialect: Dialect,
+) -> ReadySetResult<Option<Literal>> {
+    let Some(mut expr) = spec.constraints.iter().find_map(|c| match c {
+        ColumnConstraint::DefaultValue(expr) => Some(expr.clone()),
+        _ => None,
+    }) else {
+        return Ok(None);
+    };
+    scalar_optimize_expr(&mut expr, dialect);
+    match expr {
+        Expr::Literal(lit) => Ok(Some(lit)),
+        expr => unsupported!(
+            "Default value {} for column {} must be constant",
+            expr.display(dialect.into()),
+            spec.column.name
+        ),
+    }
+}
+
+/// Reorder the given changes so that each `CREATE CACHE` statement which reads from another cached
+/// query created later in the same changelist is applied after the statement creating that query,
+/// preserving the relative order of all other changes.
+///
+/// This allows cached queries which are stacked on top of each other (see
+/// `SqlToMirConverter::resolve_stacked_query`) to be created in a single changelist in any order.
+fn order_stacked_caches(changes: Vec<Change>) -> Vec<Change> {
+    #[derive(Default)]
+    struct TableReferences<'ast> {
+        tables: HashSet<&'ast SqlIdentifier>,
+    }
+
+    impl<'ast> Visitor<'ast> for TableReferences<'ast> {
+        type Error = !;
+
+        fn visit_table(&mut self, table: &'ast Relation) -> Result<(), Self::Error> {
+            self.tables.insert(&table.name);
+            Ok(())
+        }
+    }
+
+    let cache_names = changes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, change)| match change {
+            Change::CreateCache(CreateCache {
+                name: Some(name), ..
+            }) => Some((&name.name, i)),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+    if cache_names.is_empty() {
+        return changes;
+    }
+
+    // The indices of the caches created later in the changelist that each change reads from
+    let dependencies = changes
+        .iter()
+        .enumerate()
+        .map(|(i, change)| {
+            let Change::CreateCache(cc) = change else {
+                return vec![];
+            };
+            let mut references = TableReferences::default();
+            let Ok(()) = references.visit_select_statement(&cc.statement);
+            references
+                .tables
+                .into_iter()
+                .filter_map(|table| cache_names.get(table).copied())
+                .filter(|&dep| dep > i)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    if dependencies.iter().all(|deps| deps.is_empty()) {
+        return changes;
+    }
+
+    let mut order = Vec::with_capacity(changes.len());
+    let mut applied = HashSet::new();
+    let mut deferred = Vec::new();
+    for (i, deps) in dependencies.iter().enumerate() {
+        if !deps.iter().all(|dep| applied.contains(dep)) {
+            deferred.push(i);
+            continue;
+        }
+        order.push(i);
+        applied.insert(i);
+        // Applying this change may have unblocked some of the deferred ones
+        while let Some(pos) = deferred.iter().position(|&d: &usize| {
+            #[allow(clippy::indexing_slicing)] // deferred only contains indices into changes
+            dependencies[d].iter().all(|dep| applied.contains(dep))
+        }) {
+            let d = deferred.remove(pos);
+            order.push(d);
+            applied.insert(d);
+        }
+    }
+    // Dependencies only ever point forwards, so everything will have been applied by now
+    order.extend(deferred);
+
+    let mut changes = changes.into_iter().map(Some).collect::<Vec<_>>();
+    order
+        .into_iter()
+        .filter_map(|i| changes.get_mut(i).and_then(Option::take))
+        .collect()
+}
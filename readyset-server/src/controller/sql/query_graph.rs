@@ -7,13 +7,16 @@ use std::vec::Vec;
 use std::{iter, mem};
 
 use common::{DfValue, IndexType};
+use dataflow::ops::fulltext::tokenize;
+use itertools::Itertools;
+use nom_sql::analysis::visit::Visitor;
 use nom_sql::analysis::visit_mut::{walk_expr, VisitorMut};
 use nom_sql::analysis::ReferredColumns;
 use nom_sql::{
     BinaryOperator, Column, DialectDisplay, Expr, FieldDefinitionExpr, FieldReference,
     FunctionExpr, InValue, ItemPlaceholder, JoinConstraint, JoinOperator, JoinRightSide,
-    LimitClause, Literal, OrderBy, OrderType, Relation, SelectStatement, SqlIdentifier, TableExpr,
-    TableExprInner,
+    LimitClause, Literal, OrderBy, OrderType, RankFunction, Relation, SelectStatement,
+    SqlIdentifier, TableExpr, TableExprInner,
 };
 use readyset_client::{PlaceholderIdx, ViewPlaceholder};
 use readyset_errors::{
@@ -199,6 +202,10 @@ pub struct JoinPredicate {
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Parameter {
     pub col: Column,
+    /// If the placeholder is compared against an expression rather than directly against a
+    /// column, that expression. In that case, `col` is a column with no table named after the
+    /// expression, which is projected out right before the leaf.
+    pub expr: Option<Expr>,
     pub op: nom_sql::BinaryOperator,
     pub placeholder_idx: Option<PlaceholderIdx>,
 }
@@ -239,6 +246,33 @@ pub struct Pagination {
     pub offset: Option<ViewPlaceholder>,
 }
 
+/// A limit on the number of rows in each partition of a subquery, lowered from a comparison in the
+/// outer query against a ranking window function projected by the subquery, such as
+/// `ROW_NUMBER() OVER (PARTITION BY x ORDER BY y) AS rn ... WHERE rn <= 3`
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowLimit {
+    /// The function used to rank the rows in each partition
+    pub function: RankFunction,
+    /// The columns the rows are partitioned by
+    pub partition_by: Vec<Column>,
+    /// The order of the rows within each partition
+    pub order: Option<Vec<(Expr, OrderType)>>,
+    /// The maximum rank of the rows to keep in each partition
+    pub limit: usize,
+}
+
+/// A full-text search filter in a query, written either as MySQL's `MATCH (...) AGAINST (...)` or
+/// as PostgreSQL's `to_tsvector(...) @@ to_tsquery(...)`
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FullTextSearch {
+    /// The text columns being searched
+    pub columns: Vec<Column>,
+    /// The (tokenized) terms being searched for
+    pub terms: Vec<String>,
+    /// If true, rows must contain all of the search terms rather than any of them
+    pub match_all: bool,
+}
+
 /// Description of the lookup key for a view
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ViewKey {
@@ -290,6 +324,11 @@ pub struct QueryGraph {
     pub order: Option<Vec<(Column, OrderType)>>,
     /// The pagination (order, limit, offset) for the query, if any
     pub pagination: Option<Pagination>,
+    /// The limit on the number of rows in each partition of this query, if it's a subquery whose
+    /// rank is compared against a constant in the outer query
+    pub window_limit: Option<WindowLimit>,
+    /// The full-text search filter in the `WHERE` clause of the query, if any
+    pub full_text_search: Option<FullTextSearch>,
     /// True if the query is correlated (is a subquery that refers to columns in an outer query)
     pub is_correlated: bool,
 }
@@ -484,6 +523,8 @@ impl Hash for QueryGraph {
         self.having_predicates.hash(state);
         self.order.hash(state);
         self.pagination.hash(state);
+        self.window_limit.hash(state);
+        self.full_text_search.hash(state);
         self.is_correlated.hash(state);
     }
 }
@@ -513,6 +554,114 @@ where
     new_ces
 }
 
+/// Words ignored in the query of a MySQL `MATCH ... AGAINST` search, taken from InnoDB's default
+/// stopword list
+const MYSQL_FULL_TEXT_STOPWORDS: &[&str] = &[
+    "a", "about", "an", "are", "as", "at", "be", "by", "com", "de", "en", "for", "from", "how",
+    "i", "in", "is", "it", "la", "of", "on", "or", "that", "the", "this", "to", "was", "what",
+    "when", "where", "who", "will", "with", "und", "www",
+];
+
+/// The minimum length of a word in the query of a MySQL `MATCH ... AGAINST` search, matching
+/// InnoDB's default `innodb_ft_min_token_size`
+const MYSQL_FULL_TEXT_MIN_TOKEN_SIZE: usize = 3;
+
+/// Returns the string value of the given expression if it's a string literal
+fn string_literal(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Literal(Literal::String(s)) => Some(s),
+        _ => None,
+    }
+}
+
+/// If the given call is to a PostgreSQL text search function with the given name, returns the
+/// arguments to that function after checking that they select the `simple` text search
+/// configuration
+fn text_search_call_args<'a>(expr: &'a Expr, fname: &str) -> ReadySetResult<Option<&'a Expr>> {
+    let arguments = match expr {
+        Expr::Call(FunctionExpr::Call { name, arguments }) if name.eq_ignore_ascii_case(fname) => {
+            arguments
+        }
+        _ => return Ok(None),
+    };
+
+    match arguments.as_slice() {
+        [config, arg] if string_literal(config) == Some("simple") => Ok(Some(arg)),
+        [_, _] | [_] => unsupported!(
+            "Only the 'simple' text search configuration is supported in full-text search"
+        ),
+        _ => invalid_query!("Wrong number of arguments to {fname}"),
+    }
+}
+
+/// If the given conjunct of a `WHERE` clause is a full-text search, extract a description of that
+/// search.
+///
+/// Only searches for constant terms are supported - the query of a `MATCH ... AGAINST` search and
+/// the argument to `to_tsquery` must be string literals. In PostgreSQL only the `simple` text
+/// search configuration (which does no stemming) is supported, and the only `tsquery` operator
+/// supported is `&`.
+fn extract_full_text_search(expr: &Expr) -> ReadySetResult<Option<FullTextSearch>> {
+    match expr {
+        Expr::Call(FunctionExpr::Match { columns, query }) => {
+            let query = string_literal(query).ok_or_else(|| {
+                unsupported_err!("The query of MATCH ... AGAINST must be a string literal")
+            })?;
+            Ok(Some(FullTextSearch {
+                columns: columns.clone(),
+                terms: tokenize(query)
+                    .filter(|term| {
+                        term.chars().count() >= MYSQL_FULL_TEXT_MIN_TOKEN_SIZE
+                            && !MYSQL_FULL_TEXT_STOPWORDS.contains(&term.as_str())
+                    })
+                    .unique()
+                    .collect(),
+                match_all: false,
+            }))
+        }
+        Expr::BinaryOp {
+            lhs,
+            op: BinaryOperator::AtAt,
+            rhs,
+        } => {
+            let (vector, query) = match text_search_call_args(lhs, "to_tsvector")? {
+                Some(vector) => (vector, rhs),
+                None => (
+                    text_search_call_args(rhs, "to_tsvector")?.ok_or_else(|| {
+                        unsupported_err!("The '@@' operator must be applied to to_tsvector(...)")
+                    })?,
+                    lhs,
+                ),
+            };
+            let column = match vector {
+                Expr::Column(column) => column.clone(),
+                _ => unsupported!("to_tsvector(...) in full-text search must be given a column"),
+            };
+
+            let (query, plain) = if let Some(query) = text_search_call_args(query, "to_tsquery")? {
+                (query, false)
+            } else if let Some(query) = text_search_call_args(query, "plainto_tsquery")? {
+                (query, true)
+            } else {
+                unsupported!("'@@' must be applied to to_tsquery(...) or plainto_tsquery(...)")
+            };
+            let query = string_literal(query).ok_or_else(|| {
+                unsupported_err!("The argument to a tsquery function must be a string literal")
+            })?;
+            if !plain && query.contains(['|', '!', ':', '(', ')', '<', '>', '*']) {
+                unsupported!("Only the '&' operator is supported in to_tsquery(...)");
+            }
+
+            Ok(Some(FullTextSearch {
+                columns: vec![column],
+                terms: tokenize(query).unique().collect(),
+                match_all: true,
+            }))
+        }
+        _ => Ok(None),
+    }
+}
+
 // 1. Extract any predicates with placeholder parameters. We push these down to the edge nodes,
 //    since we cannot instantiate the parameters inside the data flow graph (except for
 //    non-materialized nodes).
@@ -623,16 +772,40 @@ fn classify_conditionals(
             } else if is_predicate(op) {
                 // atomic selection predicate
                 if let Expr::Literal(Literal::Placeholder(ref placeholder)) = **rhs {
-                    if let Expr::Column(ref lf) = **lhs {
-                        let idx = match placeholder {
-                            ItemPlaceholder::DollarNumber(idx) => Some(*idx as usize),
-                            _ => None,
-                        };
-                        params.push(Parameter {
+                    let idx = match placeholder {
+                        ItemPlaceholder::DollarNumber(idx) => Some(*idx as usize),
+                        _ => None,
+                    };
+                    match &**lhs {
+                        Expr::Column(lf) => params.push(Parameter {
                             col: lf.clone(),
+                            expr: None,
                             op: *op,
                             placeholder_idx: idx,
-                        });
+                        }),
+                        expr => {
+                            if *op != BinaryOperator::Equal {
+                                unsupported!(
+                                    "Only equality comparisons between placeholders and \
+                                     expressions are supported"
+                                );
+                            }
+                            if expr.referred_columns().next().is_none() {
+                                unsupported!(
+                                    "Expressions compared against placeholders must mention at \
+                                     least one column"
+                                );
+                            }
+                            params.push(Parameter {
+                                col: Column {
+                                    name: expr.display(nom_sql::Dialect::MySQL).to_string().into(),
+                                    table: None,
+                                },
+                                expr: Some(expr.clone()),
+                                op: *op,
+                                placeholder_idx: idx,
+                            });
+                        }
                     }
                 } else if let Expr::Column(Column {
                     table: Some(table), ..
@@ -645,7 +818,9 @@ fn classify_conditionals(
                     global.push(ce.clone());
                 }
             } else {
-                unsupported!("Arithmetic not supported here")
+                // Any other expression (eg `WHERE a + b`) is filtered on its truthiness, which
+                // can only be done globally
+                global.push(ce.clone());
             }
         }
         Expr::In {
@@ -698,7 +873,8 @@ fn classify_conditionals(
         | Expr::Cast { .. }
         | Expr::Array(_)
         | Expr::Row { .. }
-        | Expr::Variable(_) => global.push(ce.clone()),
+        | Expr::Variable(_)
+        | Expr::Interval { .. } => global.push(ce.clone()),
     }
     Ok(())
 }
@@ -835,6 +1011,206 @@ pub(crate) fn extract_limit_offset(
     Ok(Some((limit as _, offset)))
 }
 
+/// If `expr` compares `column` against a constant in a way which limits its value to at most some
+/// rank, returns that rank
+fn rank_limit(expr: &Expr, column: &Column) -> ReadySetResult<Option<usize>> {
+    let Expr::BinaryOp { lhs, op, rhs } = expr else {
+        return Ok(None);
+    };
+    let (op, value) = match (lhs.as_ref(), rhs.as_ref()) {
+        (Expr::Column(c), Expr::Literal(value)) if c == column => (*op, value),
+        // Equality is symmetric, so it's fine to leave it as-is if it's not an ordering comparison
+        (Expr::Literal(value), Expr::Column(c)) if c == column => {
+            (op.flip_ordering_comparison().unwrap_or_else(|op| op), value)
+        }
+        _ => return Ok(None),
+    };
+    let value = match value {
+        Literal::UnsignedInteger(val) => *val as i64,
+        Literal::Integer(val) => *val,
+        _ => return Ok(None),
+    };
+    let limit = match op {
+        BinaryOperator::LessOrEqual => value,
+        BinaryOperator::Less => value - 1,
+        BinaryOperator::Equal if value == 1 => value,
+        _ => return Ok(None),
+    };
+    if limit < 1 {
+        unsupported!("Ranks must be compared against a positive constant");
+    }
+    Ok(Some(limit as _))
+}
+
+/// Lower comparisons in the `WHERE` clause of `stmt` against ranking window functions projected by
+/// its subqueries into limits on the number of rows in each partition of those subqueries,
+/// removing both the comparison and the window function from the query. Returns the limits for
+/// each subquery, keyed by the alias of the subquery.
+fn extract_window_limits(
+    stmt: &mut SelectStatement,
+) -> ReadySetResult<HashMap<Relation, WindowLimit>> {
+    let mut window_limits = HashMap::new();
+    let mut rank_columns = vec![];
+    let mut conjuncts = split_conjunctions(stmt.where_clause.iter());
+
+    let subqueries = stmt
+        .tables
+        .iter_mut()
+        .chain(stmt.join.iter_mut().filter_map(|jc| match &mut jc.right {
+            JoinRightSide::Table(table_expr) => Some(table_expr),
+            JoinRightSide::Tables(_) => None,
+        }))
+        .filter_map(|table_expr| match &mut table_expr.inner {
+            TableExprInner::Subquery(sq) => Some((table_expr.alias.clone(), sq)),
+            TableExprInner::Table(_) => None,
+        });
+
+    for (alias, sq) in subqueries {
+        let mut rank_fields = sq.fields.iter().positions(|field| {
+            matches!(
+                field,
+                FieldDefinitionExpr::Expr {
+                    expr: Expr::Call(FunctionExpr::Rank { .. }),
+                    ..
+                }
+            )
+        });
+        let Some(idx) = rank_fields.next() else {
+            continue;
+        };
+        if rank_fields.next().is_some() {
+            unsupported!("Only one window function per subquery is supported");
+        }
+        if sq.limit_clause.limit().is_some() {
+            unsupported!("Window functions are not supported in subqueries with a LIMIT");
+        }
+
+        let rel = Relation::from(
+            alias.ok_or_else(|| invalid_query_err!("All subqueries must have an alias"))?,
+        );
+        let FieldDefinitionExpr::Expr {
+            expr:
+                Expr::Call(FunctionExpr::Rank {
+                    function,
+                    partition_by,
+                    order_by,
+                }),
+            alias: Some(rank_alias),
+        } = sq.fields.remove(idx)
+        else {
+            unsupported!("Window functions must be aliased");
+        };
+        let rank_column = Column {
+            name: rank_alias,
+            table: Some(rel.clone()),
+        };
+
+        let mut limit = None;
+        for (i, conjunct) in conjuncts.iter().enumerate() {
+            if let Some(l) = rank_limit(conjunct, &rank_column)? {
+                limit = Some((i, l));
+                break;
+            }
+        }
+        let Some((conjunct_idx, limit)) = limit else {
+            unsupported!(
+                "Window functions are only supported in a subquery whose rank is compared against \
+                 a constant in the outer query"
+            );
+        };
+        conjuncts.remove(conjunct_idx);
+
+        let partition_by = partition_by
+            .into_iter()
+            .map(|expr| match expr {
+                Expr::Column(col) => Ok(col),
+                _ => unsupported!("Only columns are supported in PARTITION BY"),
+            })
+            .collect::<ReadySetResult<_>>()?;
+        let order = order_by
+            .map(|o| {
+                o.order_by
+                    .into_iter()
+                    .map(
+                        |OrderBy {
+                             field,
+                             order_type,
+                             null_order,
+                         }| {
+                            let order_type = order_type.unwrap_or(OrderType::OrderAscending);
+                            if let Some(null_order) = null_order {
+                                if !null_order.is_default_for(order_type) {
+                                    unsupported!(
+                                        "Non-default NULLS FIRST/LAST is not yet supported"
+                                    );
+                                }
+                            }
+                            match field {
+                                FieldReference::Expr(expr) => Ok((expr, order_type)),
+                                FieldReference::Numeric(_) => unsupported!(
+                                    "Numeric field references are not supported in OVER clauses"
+                                ),
+                            }
+                        },
+                    )
+                    .collect::<ReadySetResult<_>>()
+            })
+            .transpose()?;
+
+        rank_columns.push(rank_column);
+        window_limits.insert(
+            rel,
+            WindowLimit {
+                function,
+                partition_by,
+                order,
+                limit,
+            },
+        );
+    }
+
+    if window_limits.is_empty() {
+        return Ok(window_limits);
+    }
+
+    stmt.where_clause = conjuncts.into_iter().reduce(|lhs, rhs| Expr::BinaryOp {
+        lhs: Box::new(lhs),
+        op: BinaryOperator::And,
+        rhs: Box::new(rhs),
+    });
+
+    // The ranks themselves aren't computed, so they can't be referenced anywhere else
+    struct ColumnReferences<'a> {
+        columns: &'a [Column],
+        found: Option<Column>,
+    }
+
+    impl<'ast, 'a> Visitor<'ast> for ColumnReferences<'a> {
+        type Error = !;
+
+        fn visit_column(&mut self, column: &'ast Column) -> Result<(), Self::Error> {
+            if self.columns.contains(column) {
+                self.found = Some(column.clone());
+            }
+            Ok(())
+        }
+    }
+
+    let mut references = ColumnReferences {
+        columns: &rank_columns,
+        found: None,
+    };
+    let Ok(()) = references.visit_select_statement(stmt);
+    if let Some(column) = references.found {
+        unsupported!(
+            "The rank {} can only be compared against a constant in the WHERE clause",
+            column.display_unquoted()
+        );
+    }
+
+    Ok(window_limits)
+}
+
 fn table_expr_name(table_expr: &TableExpr) -> ReadySetResult<Relation> {
     match &table_expr.inner {
         TableExprInner::Table(t) => Ok(t.clone()),
@@ -865,11 +1241,15 @@ fn default_row_for_select(st: &SelectStatement) -> Option<Vec<DfValue>> {
                     FunctionExpr::Avg { .. } => DfValue::None,
                     FunctionExpr::Count { .. } => DfValue::Int(0),
                     FunctionExpr::CountStar => DfValue::Int(0),
+                    FunctionExpr::ApproxCountDistinct { .. } => DfValue::Int(0),
                     FunctionExpr::Sum { .. } => DfValue::None,
                     FunctionExpr::Max(..) => DfValue::None,
                     FunctionExpr::Min(..) => DfValue::None,
                     FunctionExpr::GroupConcat { .. } => DfValue::None,
-                    FunctionExpr::Call { .. } | FunctionExpr::Substring { .. } => DfValue::None,
+                    FunctionExpr::Call { .. }
+                    | FunctionExpr::Substring { .. }
+                    | FunctionExpr::Match { .. }
+                    | FunctionExpr::Rank { .. } => DfValue::None,
                 },
                 _ => DfValue::None,
             })
@@ -878,7 +1258,7 @@ fn default_row_for_select(st: &SelectStatement) -> Option<Vec<DfValue>> {
 }
 
 #[allow(clippy::cognitive_complexity)]
-pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
+pub fn to_query_graph(mut stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
     // a handy closure for making new relation nodes
     let new_node = |rel: Relation,
                     preds: Vec<Expr>,
@@ -928,6 +1308,7 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
         })
     };
 
+    let mut window_limits = extract_window_limits(&mut stmt)?;
     let default_row = default_row_for_select(&stmt);
     let is_correlated = is_correlated(&stmt);
 
@@ -963,7 +1344,9 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
                 );
                 if let Entry::Vacant(e) = relations.entry(rel.clone()) {
                     let mut node = new_node(rel.clone(), vec![], &stmt.fields)?;
-                    node.subgraph = Some(Box::new(to_query_graph((**sq).clone())?));
+                    let mut subgraph = to_query_graph((**sq).clone())?;
+                    subgraph.window_limit = window_limits.remove(&rel);
+                    node.subgraph = Some(Box::new(subgraph));
                     e.insert(node);
                 } else {
                     invalid_query!(
@@ -1151,14 +1534,46 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
         }
     }
 
+    let mut full_text_search = None;
     if let Some(ref cond) = stmt.where_clause {
-        // Let's classify the predicates we have in the query
-        classify_conditionals(
-            cond,
-            &mut local_predicates,
-            &mut global_predicates,
-            &mut query_parameters,
-        )?;
+        for conjunct in split_conjunctions(iter::once(cond)) {
+            // Full-text searches get their own node, so pull them out before classifying the
+            // rest of the predicates
+            if let Some(fts) = extract_full_text_search(&conjunct)? {
+                if full_text_search.replace(fts).is_some() {
+                    unsupported!("Only one full-text search is supported per query");
+                }
+                continue;
+            }
+
+            // Let's classify the predicates we have in the query
+            classify_conditionals(
+                &conjunct,
+                &mut local_predicates,
+                &mut global_predicates,
+                &mut query_parameters,
+            )?;
+        }
+    }
+
+    if let Some(fts) = &full_text_search {
+        if relations.len() != 1 {
+            unsupported!("Full-text search is only supported in queries on a single table");
+        }
+        #[allow(clippy::unwrap_used)] // checked relations.len() == 1
+        let rel = relations.values_mut().next().unwrap();
+        for col in &fts.columns {
+            if col.table.as_ref() != Some(&rel.relation) {
+                invalid_query!(
+                    "Column {} in full-text search does not reference table {}",
+                    col.name,
+                    rel.relation.display_unquoted()
+                );
+            }
+            if !rel.columns.contains(col) {
+                rel.columns.push(col.clone());
+            }
+        }
     }
 
     for (_, ces) in local_predicates.iter_mut() {
@@ -1182,7 +1597,36 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
     //    implementing the query (unlike in a traditional query plan, where the predicates on
     //    parameters might be evaluated sooner).
     for param in query_parameters.into_iter() {
-        if let Some(table) = &param.col.table {
+        if let Some(expr) = &param.expr {
+            // Parameters on expressions are registered with the (single) table whose columns
+            // they mention, and all of those columns are carried through to the leaf so the
+            // expression can be projected there
+            let tables = expr
+                .referred_columns()
+                .filter_map(|c| c.table.as_ref())
+                .collect::<HashSet<_>>();
+            if tables.len() != 1 {
+                unsupported!(
+                    "Expressions compared against placeholders must mention columns in exactly \
+                     one table"
+                );
+            }
+            #[allow(clippy::unwrap_used)] // checked tables.len() == 1
+            let table = tables.into_iter().next().unwrap();
+            let rel = relations.get_mut(table).ok_or_else(|| {
+                invalid_query_err!(
+                    "Expression {} references non-existent table {}",
+                    param.col.name,
+                    table.display_unquoted()
+                )
+            })?;
+            for col in expr.referred_columns() {
+                if !rel.columns.contains(col) {
+                    rel.columns.push(col.clone());
+                }
+            }
+            rel.parameters.push(param.clone());
+        } else if let Some(table) = &param.col.table {
             let rel = relations.get_mut(table).ok_or_else(|| {
                 invalid_query_err!(
                     "Column {} references non-existent table {}",
@@ -1233,6 +1677,21 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
                             column: c.clone(),
                         });
                     }
+                    Expr::Call(FunctionExpr::Match { .. }) => {
+                        // The relevance score of a full-text search is computed by the full-text
+                        // search node itself, so it ends up as a column projected by that node
+                        match (&full_text_search, extract_full_text_search(expr)?) {
+                            (Some(fts), Some(selected)) if *fts == selected => {}
+                            _ => unsupported!(
+                                "MATCH ... AGAINST can only be selected if it is also used to \
+                                 filter the query"
+                            ),
+                        }
+                        columns.push(OutputColumn::Data {
+                            alias: alias.clone().unwrap_or(name),
+                            column: Column::from(mir::FULL_TEXT_SCORE_COL.clone()),
+                        })
+                    }
                     Expr::Call(function) if is_aggregate(function) => {
                         let agg_name = aggregates
                             .entry(function.clone())
@@ -1439,6 +1898,21 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
             .collect()
     };
 
+    if relations
+        .values()
+        .flat_map(|rel| &rel.parameters)
+        .any(|param| param.expr.is_some())
+        && (!aggregates.is_empty()
+            || !group_by.is_empty()
+            || pagination.is_some()
+            || full_text_search.is_some())
+    {
+        unsupported!(
+            "Comparing placeholders against expressions is not supported in queries with \
+             aggregates, GROUP BY, LIMIT, or full-text search"
+        );
+    }
+
     Ok(QueryGraph {
         distinct: stmt.distinct,
         relations,
@@ -1452,7 +1926,9 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
         global_predicates,
         having_predicates,
         pagination,
+        window_limit: None,
         order,
+        full_text_search,
         is_correlated,
     })
 }
@@ -1670,6 +2146,197 @@ mod tests {
         assert_eq!(qg.global_predicates, vec![Expr::Literal(1.into())])
     }
 
+    #[test]
+    fn is_null_local_predicates() {
+        let qg = make_query_graph("SELECT t.x FROM t WHERE t.x IS NULL AND t.y IS NOT NULL");
+        assert_eq!(
+            qg.relations.get(&"t".into()).unwrap().predicates,
+            vec![
+                Expr::BinaryOp {
+                    lhs: Box::new(Expr::Column("t.x".into())),
+                    op: BinaryOperator::Is,
+                    rhs: Box::new(Expr::Literal(Literal::Null))
+                },
+                Expr::BinaryOp {
+                    lhs: Box::new(Expr::Column("t.y".into())),
+                    op: BinaryOperator::IsNot,
+                    rhs: Box::new(Expr::Literal(Literal::Null))
+                },
+            ]
+        )
+    }
+
+    #[test]
+    fn mysql_full_text_search() {
+        let qg = make_query_graph(
+            "SELECT t.id FROM t WHERE MATCH (t.title, t.body) AGAINST ('the quick brown fox') \
+             AND t.x = 1",
+        );
+        assert_eq!(
+            qg.full_text_search,
+            Some(FullTextSearch {
+                columns: vec!["t.title".into(), "t.body".into()],
+                terms: vec!["quick".into(), "brown".into(), "fox".into()],
+                match_all: false,
+            })
+        );
+        let rel = qg.relations.get(&"t".into()).unwrap();
+        assert_eq!(
+            rel.predicates,
+            vec![Expr::BinaryOp {
+                lhs: Box::new(Expr::Column("t.x".into())),
+                op: BinaryOperator::Equal,
+                rhs: Box::new(Expr::Literal(1.into()))
+            }]
+        );
+        assert!(rel.columns.contains(&"t.title".into()));
+        assert!(rel.columns.contains(&"t.body".into()));
+    }
+
+    #[test]
+    fn postgres_full_text_search() {
+        let qg = make_query_graph(
+            "SELECT t.id FROM t \
+             WHERE to_tsvector('simple', t.body) @@ to_tsquery('simple', 'Quick & fox')",
+        );
+        assert_eq!(
+            qg.full_text_search,
+            Some(FullTextSearch {
+                columns: vec!["t.body".into()],
+                terms: vec!["quick".into(), "fox".into()],
+                match_all: true,
+            })
+        );
+        assert!(qg.relations.get(&"t".into()).unwrap().predicates.is_empty());
+    }
+
+    #[test]
+    fn full_text_search_without_terms() {
+        let qg = make_query_graph("SELECT t.id FROM t WHERE MATCH (t.body) AGAINST ('the a to')");
+        assert_eq!(qg.full_text_search.unwrap().terms, Vec::<String>::new());
+
+        let qg = make_query_graph(
+            "SELECT t.id FROM t WHERE to_tsvector('simple', t.body) @@ to_tsquery('simple', '&')",
+        );
+        assert_eq!(qg.full_text_search.unwrap().terms, Vec::<String>::new());
+    }
+
+    #[test]
+    fn selected_full_text_search_score() {
+        let qg = make_query_graph(
+            "SELECT t.id, MATCH (t.body) AGAINST ('fox') AS score FROM t \
+             WHERE MATCH (t.body) AGAINST ('fox')",
+        );
+        assert_eq!(
+            qg.columns,
+            vec![
+                OutputColumn::Data {
+                    alias: "id".into(),
+                    column: "t.id".into(),
+                },
+                OutputColumn::Data {
+                    alias: "score".into(),
+                    column: "__full_text_score".into(),
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn full_text_search_unsupported() {
+        for query in [
+            "SELECT t.id FROM t WHERE MATCH (t.body) AGAINST (t.title)",
+            "SELECT t.id FROM t \
+             WHERE to_tsvector('english', t.body) @@ to_tsquery('english', 'fox')",
+            "SELECT t.id FROM t \
+             WHERE to_tsvector('simple', t.body) @@ to_tsquery('simple', 'quick | fox')",
+            "SELECT t1.id FROM t1 JOIN t2 ON t1.id = t2.id WHERE MATCH (t1.body) AGAINST ('fox')",
+            "SELECT t.id, MATCH (t.body) AGAINST ('fox') FROM t",
+        ] {
+            let stmt = parse_select_statement(Dialect::MySQL, query).unwrap();
+            assert!(
+                to_query_graph(stmt).unwrap_err().is_unsupported(),
+                "{query} should be unsupported"
+            );
+        }
+    }
+
+    #[test]
+    fn window_limit() {
+        let qg = make_query_graph(
+            "SELECT sq.id, sq.x FROM (SELECT t.id, t.x, RANK() OVER (PARTITION BY t.x ORDER BY \
+             t.y DESC) AS rn FROM t) sq WHERE sq.rn <= 3 AND sq.x = ?",
+        );
+
+        assert!(qg.window_limit.is_none());
+        assert_eq!(qg.parameters().len(), 1);
+        let subquery_rel = qg.relations.get(&Relation::from("sq")).unwrap();
+        assert!(subquery_rel.predicates.is_empty());
+
+        let subgraph = subquery_rel.subgraph.as_ref().unwrap();
+        assert_eq!(subgraph.fields.len(), 2);
+        assert_eq!(
+            subgraph.window_limit,
+            Some(WindowLimit {
+                function: RankFunction::Rank,
+                partition_by: vec![Column::from("t.x")],
+                order: Some(vec![(
+                    Expr::Column("t.y".into()),
+                    OrderType::OrderDescending
+                )]),
+                limit: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn window_limit_comparisons() {
+        for (predicate, limit) in [
+            ("sq.rn <= 3", 3),
+            ("sq.rn < 3", 2),
+            ("sq.rn = 1", 1),
+            ("3 >= sq.rn", 3),
+        ] {
+            let qg = make_query_graph(&format!(
+                "SELECT sq.id FROM (SELECT t.id, ROW_NUMBER() OVER (ORDER BY t.y) AS rn FROM t) \
+                 sq WHERE {predicate}"
+            ));
+            let subgraph = qg.relations[&Relation::from("sq")]
+                .subgraph
+                .as_ref()
+                .unwrap();
+            assert_eq!(
+                subgraph.window_limit.as_ref().unwrap().limit,
+                limit,
+                "{predicate}"
+            );
+        }
+    }
+
+    #[test]
+    fn window_limit_unsupported() {
+        for query in [
+            "SELECT sq.id, sq.rn FROM (SELECT t.id, ROW_NUMBER() OVER (ORDER BY t.y) AS rn FROM t) \
+             sq WHERE sq.rn <= 3",
+            "SELECT sq.id FROM (SELECT t.id, ROW_NUMBER() OVER (ORDER BY t.y) AS rn FROM t) sq \
+             WHERE sq.id = 1",
+            "SELECT sq.id FROM (SELECT t.id, ROW_NUMBER() OVER (ORDER BY t.y) AS rn FROM t) sq \
+             WHERE sq.rn > 3",
+            "SELECT sq.id FROM (SELECT t.id, ROW_NUMBER() OVER (ORDER BY t.y) AS rn FROM t) sq \
+             WHERE sq.rn = 2",
+            "SELECT sq.id FROM (SELECT t.id, ROW_NUMBER() OVER (ORDER BY t.y) AS rn FROM t \
+             LIMIT 10) sq WHERE sq.rn <= 3",
+            "SELECT sq.id FROM (SELECT t.id, ROW_NUMBER() OVER (PARTITION BY t.x + 1 ORDER BY \
+             t.y) AS rn FROM t) sq WHERE sq.rn <= 3",
+        ] {
+            let stmt = parse_select_statement(Dialect::MySQL, query).unwrap();
+            assert!(
+                to_query_graph(stmt).unwrap_err().is_unsupported(),
+                "{query} should be unsupported"
+            );
+        }
+    }
+
     #[test]
     fn local_pred_in_join_condition() {
         let qg = make_query_graph("SELECT t1.x FROM t1 JOIN t2 ON t1.x = t2.x AND t2.y = 4");
@@ -1738,6 +2405,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn arithmetic_global_predicate() {
+        let qg = make_query_graph("SELECT t.x FROM t WHERE t.a + t.b");
+        assert_eq!(
+            qg.global_predicates,
+            vec![Expr::BinaryOp {
+                lhs: Box::new(Expr::Column("t.a".into())),
+                op: BinaryOperator::Add,
+                rhs: Box::new(Expr::Column("t.b".into()))
+            }]
+        )
+    }
+
+    #[test]
+    fn expression_parameter() {
+        let qg = make_query_graph("SELECT t.x FROM t WHERE t.a + t.b = $1");
+        let rel = qg.relations.get(&"t".into()).unwrap();
+        assert!(rel.columns.contains(&"t.a".into()));
+        assert!(rel.columns.contains(&"t.b".into()));
+        assert_eq!(rel.parameters.len(), 1);
+        assert_eq!(
+            rel.parameters[0].expr,
+            Some(Expr::BinaryOp {
+                lhs: Box::new(Expr::Column("t.a".into())),
+                op: BinaryOperator::Add,
+                rhs: Box::new(Expr::Column("t.b".into()))
+            })
+        );
+        assert_eq!(rel.parameters[0].col.table, None);
+    }
+
+    #[test]
+    fn expression_parameter_unsupported() {
+        for query in [
+            "SELECT t.x FROM t WHERE t.a + t.b > $1",
+            "SELECT t1.x FROM t1 JOIN t2 ON t1.x = t2.x WHERE t1.a + t2.b = $1",
+            "SELECT count(t.x) FROM t WHERE lower(t.a) = $1",
+            "SELECT t.x FROM t WHERE lower(t.a) = $1 ORDER BY t.x LIMIT 3",
+        ] {
+            let stmt = parse_select_statement(Dialect::MySQL, query).unwrap();
+            let err = to_query_graph(stmt).unwrap_err();
+            assert!(err.is_unsupported(), "{query}: {err}");
+        }
+    }
+
     mod view_key {
         use super::*;
 
@@ -1756,6 +2468,28 @@ mod tests {
             )
         }
 
+        #[test]
+        fn expression_key() {
+            let qg = make_query_graph("SELECT t.x FROM t WHERE lower(t.email) = $1");
+            let key = qg.view_key(&Default::default()).unwrap();
+            let param = qg.parameters()[0].clone();
+
+            assert_eq!(key.index_type, IndexType::HashMap);
+            assert_eq!(
+                key.columns,
+                vec![(
+                    mir::Column::named(
+                        param
+                            .expr
+                            .unwrap()
+                            .display(nom_sql::Dialect::MySQL)
+                            .to_string()
+                    ),
+                    ViewPlaceholder::OneToOne(1, BinaryOperator::Equal)
+                )]
+            )
+        }
+
         #[test]
         fn double_equality_same_column() {
             let qg = make_query_graph("SELECT t.x FROM t WHERE t.x = $1 AND t.x = $2");
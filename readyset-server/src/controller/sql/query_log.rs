@@ -0,0 +1,111 @@
+//! A log of the cached queries installed in the recipe, recording what caused each query to be
+//! installed, when, and the rewrite decisions made along the way, so that the set of queries cached
+//! by a long-running deployment remains auditable.
+
+use std::collections::HashMap;
+
+use nom_sql::Relation;
+use readyset_client::recipe::QueryLogEntry;
+use serde::{Deserialize, Serialize};
+
+/// The log of installed cached queries, indexed by query name.
+///
+/// Only queries which are currently installed are kept in the log - entries are discarded once
+/// their query is dropped.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct QueryLog {
+    entries: HashMap<Relation, QueryLogEntry>,
+}
+
+impl QueryLog {
+    /// Record that a query was installed.
+    ///
+    /// If the same query was already installed under the same name, for example because the cache
+    /// was re-created after the server restarted, the existing entry is kept, so that the log
+    /// reflects when and how the query was first installed.
+    pub(crate) fn record(&mut self, entry: QueryLogEntry) {
+        match self.entries.get(&entry.name) {
+            Some(existing) if existing.installed_sql == entry.installed_sql => {}
+            _ => {
+                self.entries.insert(entry.name.clone(), entry);
+            }
+        }
+    }
+
+    /// Discard the entries for all queries whose names don't satisfy `installed`
+    pub(crate) fn retain<F>(&mut self, mut installed: F)
+    where
+        F: FnMut(&Relation) -> bool,
+    {
+        self.entries.retain(|name, _| installed(name))
+    }
+
+    /// Returns all the entries in the log, in the order their queries were installed
+    pub(crate) fn entries(&self) -> Vec<QueryLogEntry> {
+        let mut entries = self.entries.values().cloned().collect::<Vec<_>>();
+        entries.sort_by(|e1, e2| {
+            e1.installed_at
+                .cmp(&e2.installed_at)
+                .then_with(|| e1.name.cmp(&e2.name))
+        });
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use readyset_client::recipe::QuerySource;
+
+    use super::*;
+
+    fn entry(name: &str, sql: &str, installed_at: SystemTime) -> QueryLogEntry {
+        QueryLogEntry {
+            name: name.into(),
+            source: QuerySource::Api,
+            installed_at,
+            original_sql: sql.to_owned(),
+            installed_sql: sql.to_owned(),
+            rewrites: vec![],
+        }
+    }
+
+    #[test]
+    fn keeps_first_installation() {
+        let start = SystemTime::now();
+        let mut log = QueryLog::default();
+        log.record(entry("q1", "SELECT 1", start));
+        log.record(entry("q2", "SELECT 2", start + Duration::from_secs(1)));
+        log.record(entry("q1", "SELECT 1", start + Duration::from_secs(2)));
+
+        let entries = log.entries();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|e| e.name.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["q1", "q2"]
+        );
+        assert_eq!(entries[0].installed_at, start);
+
+        // Replacing the query under the same name replaces the entry
+        log.record(entry("q1", "SELECT 3", start + Duration::from_secs(3)));
+        assert_eq!(
+            log.entries()
+                .iter()
+                .map(|e| e.name.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["q2", "q1"]
+        );
+    }
+
+    #[test]
+    fn discards_dropped_queries() {
+        let mut log = QueryLog::default();
+        log.record(entry("q1", "SELECT 1", SystemTime::now()));
+        log.record(entry("q2", "SELECT 2", SystemTime::now()));
+        log.retain(|name| name.name != "q1");
+        assert_eq!(log.entries().len(), 1);
+    }
+}
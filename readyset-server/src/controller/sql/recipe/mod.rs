@@ -1,5 +1,7 @@
+use std::collections::HashMap;
 use std::{fmt, str};
 
+use database_utils::IgnoredColumns;
 use nom_sql::{Relation, SelectStatement, SqlIdentifier};
 use petgraph::graph::NodeIndex;
 use readyset_client::recipe::changelist::ChangeList;
@@ -18,6 +20,10 @@ use super::BaseSchema;
 use crate::controller::sql::SqlIncorporator;
 use crate::controller::Migration;
 
+mod versions;
+
+pub(crate) use self::versions::RecipeVersions;
+
 /// Uniquely identifies an expression in the expression registry.
 #[derive(Clone, Copy, Default, Debug, Deserialize, Hash, Serialize, PartialEq, Eq)]
 #[repr(transparent)]
@@ -135,6 +141,12 @@ impl Recipe {
         self.inc.config = sql_config;
     }
 
+    /// Set the columns which are never replicated from the upstream database, and so can't be
+    /// referenced by queries
+    pub(crate) fn set_ignored_columns(&mut self, ignored_columns: IgnoredColumns) {
+        self.inc.config.ignored_columns = ignored_columns;
+    }
+
     /// Change the behavior of failed writes to base nodes
     /// If permissive writes is true, failed writes will be no-ops, else they will return errors
     pub(crate) fn set_permissive_writes(&mut self, permissive_writes: bool) {
@@ -150,6 +162,17 @@ impl Recipe {
         self.inc.registry.cache_names()
     }
 
+    /// Returns a map from the names of all views and caches in the recipe to their definitions
+    pub(in crate::controller) fn view_and_cache_expressions(
+        &self,
+    ) -> HashMap<Relation, RecipeExpr> {
+        self.inc
+            .registry
+            .views_and_caches()
+            .map(|expr| (expr.name().clone(), expr.clone()))
+            .collect()
+    }
+
     /// Obtains the `NodeIndex` for the node corresponding to a named query or a write type.
     pub(in crate::controller) fn node_addr_for(
         &self,
@@ -225,6 +248,7 @@ impl Recipe {
             server_supports_mixed_comparisons: self.mir_config().allow_mixed_comparisons,
             server_supports_pagination: self.mir_config().allow_paginate
                 && self.mir_config().allow_topk,
+            server_supports_post_lookup: self.mir_config().allow_post_lookup,
         }
     }
 }
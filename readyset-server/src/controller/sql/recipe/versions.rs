@@ -0,0 +1,322 @@
+//! Versioning of the set of views and caches in the recipe.
+//!
+//! A batch of view and cache additions and removals can be *staged* as a new version of the
+//! recipe, which validates the batch without applying it, and then later *activated*, which applies
+//! every change in the batch in a single migration. Each activated version records which views and
+//! caches it added and removed, so that the recipe can later be rolled back to any prior version.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::SystemTime;
+
+use nom_sql::{CreateViewStatement, Relation};
+use readyset_client::recipe::changelist::{Change, ChangeList};
+use readyset_client::recipe::{ActivatedRecipeVersion, RecipeVersion, RecipeVersionsInfo};
+use readyset_data::Dialect;
+use readyset_errors::{bad_request_err, ReadySetResult};
+use serde::{Deserialize, Serialize};
+
+use crate::controller::sql::RecipeExpr;
+
+/// The maximum number of activated versions to remember. Once more versions than this have been
+/// activated, the oldest versions can no longer be rolled back to.
+const MAX_ACTIVATED_VERSIONS: usize = 64;
+
+/// A version of the recipe which has been activated
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ActivatedVersion {
+    version: RecipeVersion,
+    activated_at: SystemTime,
+    /// The dialect of the changelist that was activated, used to restore removed expressions
+    dialect: Dialect,
+    /// The names of the views and caches added by activating this version
+    added: Vec<Relation>,
+    /// The definitions of the views and caches removed by activating this version
+    removed: Vec<RecipeExpr>,
+}
+
+/// Bookkeeping for the versions of the set of views and caches in the recipe.
+///
+/// This only records changes made via staged versions - views and caches added or removed in any
+/// other way are unaffected by activating or rolling back versions.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct RecipeVersions {
+    /// The most recently allocated version
+    last_version: RecipeVersion,
+    /// Versions which have been staged but not yet activated, in the order they were staged
+    staged: Vec<(RecipeVersion, ChangeList)>,
+    /// Versions which have been activated and not rolled back, oldest first
+    activated: VecDeque<ActivatedVersion>,
+    /// The version immediately preceding the oldest version in `activated`, which is the oldest
+    /// version that can still be rolled back to
+    base: RecipeVersion,
+}
+
+impl RecipeVersions {
+    /// Record `changelist` as a new staged version, and return that version.
+    ///
+    /// It's up to the caller to check that the changes in the changelist are valid.
+    pub(crate) fn stage(&mut self, changelist: ChangeList) -> RecipeVersion {
+        self.last_version = RecipeVersion(self.last_version.0 + 1);
+        self.staged.push((self.last_version, changelist));
+        self.last_version
+    }
+
+    /// Returns the changelist for the given staged version, or an error if the version hasn't been
+    /// staged
+    pub(crate) fn staged(&self, version: RecipeVersion) -> ReadySetResult<&ChangeList> {
+        self.staged
+            .iter()
+            .find(|(v, _)| *v == version)
+            .map(|(_, changelist)| changelist)
+            .ok_or_else(|| bad_request_err(format!("Recipe version {version} is not staged")))
+    }
+
+    /// Record that the given staged version has been activated, given the views and caches in the
+    /// recipe before and after activating it (as returned by
+    /// [`Recipe::view_and_cache_expressions`](super::Recipe::view_and_cache_expressions)).
+    pub(crate) fn record_activation(
+        &mut self,
+        version: RecipeVersion,
+        dialect: Dialect,
+        before: &HashMap<Relation, RecipeExpr>,
+        after: &HashMap<Relation, RecipeExpr>,
+    ) {
+        self.staged.retain(|(v, _)| *v != version);
+
+        // An expression which was replaced with a different definition under the same name counts
+        // as both removed and added
+        let added = after
+            .iter()
+            .filter(|(name, expr)| before.get(*name) != Some(*expr))
+            .map(|(name, _)| name.clone())
+            .collect();
+        let mut removed = before
+            .iter()
+            .filter(|(name, expr)| after.get(*name) != Some(*expr))
+            .map(|(_, expr)| expr.clone())
+            .collect::<Vec<_>>();
+        // Restore views before caches, since caches may read from views
+        removed.sort_by_key(|expr| matches!(expr, RecipeExpr::Cache { .. }));
+
+        self.activated.push_back(ActivatedVersion {
+            version,
+            activated_at: SystemTime::now(),
+            dialect,
+            added,
+            removed,
+        });
+        if self.activated.len() > MAX_ACTIVATED_VERSIONS {
+            if let Some(evicted) = self.activated.pop_front() {
+                self.base = evicted.version;
+            }
+        }
+    }
+
+    /// Build a changelist which undoes all the versions activated after `version`, newest first,
+    /// removing the views and caches they added and restoring the views and caches they removed.
+    ///
+    /// Returns an error if `version` can't be rolled back to.
+    pub(crate) fn rollback_changelist(&self, version: RecipeVersion) -> ReadySetResult<ChangeList> {
+        self.check_rollback_target(version)?;
+
+        let undone = self
+            .activated
+            .iter()
+            .rev()
+            .take_while(|activated| activated.version != version)
+            .collect::<Vec<_>>();
+        let dialect = undone
+            .first()
+            .map(|activated| activated.dialect)
+            .unwrap_or(Dialect::DEFAULT_MYSQL);
+
+        let mut changes = vec![];
+        for activated in undone {
+            changes.extend(activated.added.iter().map(|name| Change::Drop {
+                name: name.clone(),
+                if_exists: true,
+            }));
+            changes.extend(activated.removed.iter().cloned().filter_map(recreate));
+        }
+
+        Ok(ChangeList::from_changes(changes, dialect))
+    }
+
+    /// Record that the recipe has been rolled back to the given version, forgetting all versions
+    /// activated after it
+    pub(crate) fn record_rollback(&mut self, version: RecipeVersion) {
+        while self
+            .activated
+            .back()
+            .map_or(false, |activated| activated.version != version)
+        {
+            self.activated.pop_back();
+        }
+    }
+
+    /// Returns a description of the current state of the versions of the recipe
+    pub(crate) fn info(&self) -> RecipeVersionsInfo {
+        RecipeVersionsInfo {
+            active: self
+                .activated
+                .back()
+                .map_or(self.base, |activated| activated.version),
+            activated: self
+                .activated
+                .iter()
+                .map(|activated| ActivatedRecipeVersion {
+                    version: activated.version,
+                    activated_at: activated.activated_at,
+                    added: activated.added.clone(),
+                    removed: activated
+                        .removed
+                        .iter()
+                        .map(|expr| expr.name().clone())
+                        .collect(),
+                })
+                .collect(),
+            staged: self.staged.iter().map(|(v, _)| *v).collect(),
+        }
+    }
+
+    fn check_rollback_target(&self, version: RecipeVersion) -> ReadySetResult<()> {
+        if version == self.base || self.activated.iter().any(|a| a.version == version) {
+            Ok(())
+        } else if version < self.base {
+            Err(bad_request_err(format!(
+                "Recipe version {version} is too old to roll back to; the oldest version that can \
+                 be rolled back to is {}",
+                self.base
+            )))
+        } else {
+            Err(bad_request_err(format!(
+                "Recipe version {version} has not been activated"
+            )))
+        }
+    }
+}
+
+/// Build a [`Change`] which re-creates the given view or cache. Returns `None` for tables, which
+/// are never added or removed by recipe versions.
+fn recreate(expr: RecipeExpr) -> Option<Change> {
+    match expr {
+        RecipeExpr::Table { .. } => None,
+        RecipeExpr::View { name, definition } => Some(Change::CreateView(CreateViewStatement {
+            name,
+            or_replace: false,
+            fields: vec![],
+            definition: Ok(Box::new(definition)),
+        })),
+        RecipeExpr::Cache {
+            name,
+            statement,
+            always,
+            ..
+        } => Some(Change::create_cache(name, statement, always)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom_sql::SelectSpecification;
+
+    use super::*;
+
+    fn view(name: &'static str, query: &'static str) -> (Relation, RecipeExpr) {
+        (
+            name.into(),
+            RecipeExpr::View {
+                name: name.into(),
+                definition: SelectSpecification::Simple(
+                    nom_sql::parse_select_statement(nom_sql::Dialect::MySQL, query).unwrap(),
+                ),
+            },
+        )
+    }
+
+    fn dropped_names(changelist: &ChangeList) -> Vec<Relation> {
+        changelist
+            .changes
+            .iter()
+            .filter_map(|change| match change {
+                Change::Drop { name, .. } => Some(name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn created_names(changelist: &ChangeList) -> Vec<Relation> {
+        changelist
+            .changes
+            .iter()
+            .filter_map(|change| match change {
+                Change::CreateView(stmt) => Some(stmt.name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stage_and_activate() {
+        let mut versions = RecipeVersions::default();
+        let v1 = versions.stage(ChangeList::new(Dialect::DEFAULT_MYSQL));
+        let v2 = versions.stage(ChangeList::new(Dialect::DEFAULT_MYSQL));
+        assert_eq!(v1, RecipeVersion(1));
+        assert_eq!(v2, RecipeVersion(2));
+        versions.staged(v1).unwrap();
+        versions.staged(RecipeVersion(3)).unwrap_err();
+
+        let before = HashMap::new();
+        let after = HashMap::from([view("v", "SELECT * FROM t")]);
+        versions.record_activation(v1, Dialect::DEFAULT_MYSQL, &before, &after);
+
+        versions.staged(v1).unwrap_err();
+        let info = versions.info();
+        assert_eq!(info.active, v1);
+        assert_eq!(info.staged, vec![v2]);
+        assert_eq!(info.activated.len(), 1);
+        assert_eq!(info.activated[0].added, vec![Relation::from("v")]);
+        assert!(info.activated[0].removed.is_empty());
+    }
+
+    #[test]
+    fn rollback_undoes_newer_versions() {
+        let mut versions = RecipeVersions::default();
+        let s0 = HashMap::from([view("a", "SELECT * FROM t")]);
+        let s1 = HashMap::from([view("a", "SELECT * FROM t"), view("b", "SELECT * FROM t")]);
+        let s2 = HashMap::from([view("b", "SELECT * FROM t")]);
+
+        let v1 = versions.stage(ChangeList::new(Dialect::DEFAULT_MYSQL));
+        versions.record_activation(v1, Dialect::DEFAULT_MYSQL, &s0, &s1);
+        let v2 = versions.stage(ChangeList::new(Dialect::DEFAULT_MYSQL));
+        versions.record_activation(v2, Dialect::DEFAULT_MYSQL, &s1, &s2);
+
+        let to_v1 = versions.rollback_changelist(v1).unwrap();
+        assert!(dropped_names(&to_v1).is_empty());
+        assert_eq!(created_names(&to_v1), vec![Relation::from("a")]);
+
+        let to_v0 = versions.rollback_changelist(RecipeVersion(0)).unwrap();
+        assert_eq!(dropped_names(&to_v0), vec![Relation::from("b")]);
+        assert_eq!(created_names(&to_v0), vec![Relation::from("a")]);
+
+        versions.rollback_changelist(RecipeVersion(3)).unwrap_err();
+
+        versions.record_rollback(v1);
+        assert_eq!(versions.info().active, v1);
+        versions.rollback_changelist(v2).unwrap_err();
+    }
+
+    #[test]
+    fn replaced_view_is_restored() {
+        let mut versions = RecipeVersions::default();
+        let before = HashMap::from([view("v", "SELECT * FROM t")]);
+        let after = HashMap::from([view("v", "SELECT x FROM t")]);
+
+        let v1 = versions.stage(ChangeList::new(Dialect::DEFAULT_MYSQL));
+        versions.record_activation(v1, Dialect::DEFAULT_MYSQL, &before, &after);
+
+        let changelist = versions.rollback_changelist(RecipeVersion(0)).unwrap();
+        assert_eq!(dropped_names(&changelist), vec![Relation::from("v")]);
+        assert_eq!(created_names(&changelist), vec![Relation::from("v")]);
+    }
+}
@@ -380,7 +380,14 @@ impl ExprRegistry {
     ///   references a table that is not present in the registry.
     /// - The [`RecipeExpr`] has a name that is already being used by a different [`RecipeExpr`].
     pub(super) fn add_query(&mut self, expression: RecipeExpr) -> ReadySetResult<bool> {
-        let expr_id = (&expression).into();
+        let expr_id = match &expression {
+            // Caches for equivalent queries (which have the same QueryId, even if they were written
+            // differently) share a single expression
+            RecipeExpr::Cache { query_id, .. } => self
+                .cache_with_query_id(*query_id)
+                .unwrap_or_else(|| (&expression).into()),
+            _ => (&expression).into(),
+        };
         debug!(?expression, %expr_id, "Adding expression to the registry");
         // We always try adding the alias first, in case there's another table/query
         // with the same name already.
@@ -454,6 +461,17 @@ impl ExprRegistry {
             .map(|query_id| self.expressions[query_id].name())
     }
 
+    /// Returns the id of the expression for an existing cache of a query with the given
+    /// [`QueryId`], if any.
+    pub(super) fn cache_with_query_id(&self, query_id: QueryId) -> Option<ExprId> {
+        self.expressions
+            .iter()
+            .find_map(|(expr_id, expr)| match expr {
+                RecipeExpr::Cache { query_id: id, .. } if *id == query_id => Some(*expr_id),
+                _ => None,
+            })
+    }
+
     /// Returns an iterator over all *original names* for all caches in the recipe (not including
     /// aliases)
     pub(super) fn cache_names(&self) -> impl Iterator<Item = &Relation> + '_ {
@@ -463,6 +481,13 @@ impl ExprRegistry {
         })
     }
 
+    /// Returns an iterator over all [`RecipeExpr::View`]s and [`RecipeExpr::Cache`]s in the recipe
+    pub(super) fn views_and_caches(&self) -> impl Iterator<Item = &RecipeExpr> + '_ {
+        self.expressions
+            .values()
+            .filter(|expr| !matches!(expr, RecipeExpr::Table { .. }))
+    }
+
     /// Removes the [`RecipeExpr`] associated with the given name (or alias), if
     /// it exists, and all the [`RecipeExpr`]s that depend on it.
     /// Returns the removed [`RecipeExpr`] if it was present, or `None` otherwise.
@@ -575,6 +600,67 @@ impl ExprRegistry {
             .flatten()
     }
 
+    /// Returns an iterator over all the views and caches which directly reference the table with
+    /// the given name
+    pub(super) fn expressions_referencing_table(
+        &self,
+        table: &Relation,
+    ) -> impl Iterator<Item = &RecipeExpr> {
+        self.aliases
+            .get(table)
+            .and_then(|table_id| self.dependencies.get(table_id))
+            .into_iter()
+            .flatten()
+            .map(|dep| self.expressions.get(dep).expect("Documented invariant"))
+    }
+
+    /// Replace the body of the table with the given name or alias (eg after columns have been added
+    /// to or dropped from it in place), keeping all of its aliases and the expressions which depend
+    /// on it.
+    pub(super) fn update_table_body(
+        &mut self,
+        table: &Relation,
+        body: CreateTableBody,
+    ) -> ReadySetResult<()> {
+        let old_id = *self
+            .aliases
+            .get(table)
+            .ok_or_else(|| internal_err!("Table {} not found in the registry", table.name))?;
+        let expression = match self.expressions.remove(&old_id) {
+            Some(RecipeExpr::Table { name, pg_meta, .. }) => RecipeExpr::Table {
+                name,
+                body,
+                pg_meta,
+            },
+            Some(expression) => {
+                self.expressions.insert(old_id, expression);
+                return Err(internal_err!("{} is not a table", table.name));
+            }
+            None => return Err(internal_err!("Expression for {} not found", table.name)),
+        };
+        let new_id = ExprId::from(&expression);
+
+        for id in self.aliases.values_mut() {
+            if *id == old_id {
+                *id = new_id;
+            }
+        }
+        if let Some(deps) = self.dependencies.remove(&old_id) {
+            self.dependencies.insert(new_id, deps);
+        }
+        for deps in self.custom_type_dependencies.values_mut() {
+            deps.remove(&old_id);
+        }
+        for ty in expression.custom_type_references() {
+            if let Some(deps) = self.custom_type_dependencies.get_mut(ty) {
+                deps.insert(new_id);
+            }
+        }
+
+        self.expressions.insert(new_id, expression);
+        Ok(())
+    }
+
     /// Returns an iterator over a list of expressions that contain columns referencing the given
     /// custom type
     pub(super) fn expressions_referencing_custom_type(
@@ -829,6 +915,28 @@ mod tests {
             );
         }
 
+        #[test]
+        fn add_equivalent_cached_query() {
+            let mut registry = setup();
+
+            let expr = recipe_expr_cache(
+                "test_query2",
+                "SELECT t.col1 FROM test_table AS t WHERE t.col1 = ?",
+            );
+            assert!(registry.add_query(expr).unwrap());
+
+            // Differs only in the name of the table alias, so it's an alias for the first query
+            let expr = recipe_expr_cache(
+                "test_query3",
+                "select x.col1 from test_table as x where x.col1 = ?",
+            );
+            assert!(!registry.add_query(expr).unwrap());
+            assert_eq!(
+                registry.resolve_alias(&"test_query3".into()),
+                Some(&"test_query2".into())
+            );
+        }
+
         #[test]
         fn add_view() {
             let mut registry = setup();
@@ -983,6 +1091,31 @@ mod tests {
             assert!(registry.aliases.is_empty());
         }
 
+        #[test]
+        fn update_table_body() {
+            let mut registry = setup();
+            let name: Relation = "test_table".into();
+            let body = parse_create_table(
+                Dialect::MySQL,
+                "CREATE TABLE test_table (col1 INT, col2 INT);",
+            )
+            .unwrap()
+            .body
+            .unwrap();
+
+            registry.update_table_body(&name, body.clone()).unwrap();
+            match registry.get(&name).unwrap() {
+                RecipeExpr::Table { body: new_body, .. } => assert_eq!(*new_body, body),
+                _ => panic!("Expected a table"),
+            }
+            assert_eq!(registry.expressions_referencing_table(&name).count(), 2);
+
+            registry
+                .update_table_body(&"test_query".into(), body)
+                .unwrap_err();
+            assert!(registry.get(&"test_query".into()).is_some());
+        }
+
         #[test]
         fn len() {
             let registry = setup();
@@ -13,47 +13,62 @@
 //! to manipulate it in a thread-safe way.
 
 use std::cell;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::net::SocketAddr;
 use std::ops::Deref;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant, SystemTime};
 
 use array2::Array2;
 use common::{IndexPair, Tag};
+use dataflow::ops::join::JoinType;
 use dataflow::payload::EvictRequest;
-use dataflow::prelude::{ChannelCoordinator, DomainIndex, DomainNodes, Graph, NodeIndex};
+use dataflow::prelude::{
+    ChannelCoordinator, DomainIndex, DomainNodes, Graph, NodeIndex, NodeOperator,
+};
 use dataflow::{
-    BaseTableState, DomainBuilder, DomainConfig, DomainRequest, NodeMap, Packet,
-    PersistenceParameters, Sharding,
+    BaseTableState, DomainBuilder, DomainConfig, DomainRequest, DurabilityMode, NodeMap, Packet,
+    PersistenceParameters, Sharding, StateProfile,
 };
 use failpoint_macros::set_failpoint;
 use futures::stream::{self, FuturesUnordered, StreamExt, TryStreamExt};
 use futures::{FutureExt, TryFutureExt, TryStream};
 use metrics::{gauge, histogram};
-use nom_sql::{NonReplicatedRelation, Relation, SqlIdentifier};
-use petgraph::visit::{Bfs, IntoNodeReferences};
+use mir::BOGOKEY_COL;
+use nom_sql::{Column, ColumnConstraint, NonReplicatedRelation, Relation, SqlIdentifier, SqlType};
+use petgraph::visit::{Bfs, IntoNodeReferences, Reversed};
 use petgraph::Direction;
 use rand::Rng;
 use readyset_client::builders::{
     ReaderHandleBuilder, ReusedReaderHandleBuilder, TableBuilder, ViewBuilder,
 };
-use readyset_client::consensus::{Authority, AuthorityControl};
-use readyset_client::debug::info::{GraphInfo, MaterializationInfo, NodeSize};
+use readyset_client::consensus::{Authority, AuthorityControl, LabelSelector, ZONE_LABEL};
+use readyset_client::debug::info::{
+    GraphInfo, KeyTrace, MaterializationInfo, MigrationPlanSummary, NodeSize, NodeSizeForecast,
+    ReaderHotKeys, ReaderKeyTrace, ReaderMetrics, ReaderSlowReplay, ReplayPathInfo,
+    ReplayPathSegmentInfo, SlowReplay, ViewHotKeys, ViewMetrics, ViewReplayPaths,
+};
 use readyset_client::debug::stats::{DomainStats, GraphStats, NodeStats};
 #[cfg(feature = "failure_injection")]
 use readyset_client::failpoints;
 use readyset_client::internal::{MaterializationStatus, ReplicaAddress};
 use readyset_client::metrics::recorded;
 use readyset_client::recipe::changelist::{Change, ChangeList};
-use readyset_client::recipe::{CacheExpr, ExtendRecipeSpec};
+use readyset_client::recipe::{
+    CacheExpr, DeactivatedView, ExtendRecipeSpec, MigrationPhase, QueryLogEntry, RecipeVersion,
+    RecipeVersionsInfo,
+};
+use readyset_client::runtime_config::{RuntimeConfigChange, RuntimeConfigUpdate};
+use readyset_client::schema::{ColumnDescription, RelationSchema, SchemaRegistry};
 use readyset_client::{
-    PersistencePoint, SingleKeyEviction, TableReplicationStatus, TableStatus, ViewCreateRequest,
-    ViewFilter, ViewRequest, ViewSchema,
+    ColumnProvenance, KeyComparison, PersistencePoint, SchemaType, SingleKeyEviction,
+    TableReplicationStatus, TableStatus, ViewCreateRequest, ViewFilter, ViewRateLimit, ViewRequest,
+    ViewResultLimit, ViewSchema, ViewStaleReads,
 };
 use readyset_data::{DfValue, Dialect};
 use readyset_errors::{
-    internal, internal_err, invariant_eq, NodeType, ReadySetError, ReadySetResult,
+    bad_request_err, internal, internal_err, invalid_query_err, invariant_eq, unsupported,
+    NodeType, ReadySetError, ReadySetResult,
 };
 use replication_offset::{ReplicationOffset, ReplicationOffsets};
 use serde::de::DeserializeOwned;
@@ -62,14 +77,20 @@ use tokio::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard};
 use tracing::{debug, error, info, instrument, trace, warn};
 use vec1::{vec1, Vec1};
 
+use super::forecast::{BaseTableStats, Forecaster};
 use super::migrate::DomainSettings;
+use super::profiler::{self, TableProfiles};
 use super::replication::ReplicationStrategy;
 use super::sql::Recipe;
+use super::view_eviction::DeactivatedViews;
 use crate::controller::domain_handle::DomainHandle;
 use crate::controller::migrate::materialization::Materializations;
 use crate::controller::migrate::scheduling::Scheduler;
-use crate::controller::migrate::{routing, DomainMigrationMode, DomainMigrationPlan, Migration};
-use crate::controller::sql::{RecipeExpr, Schema};
+use crate::controller::migrate::{
+    routing, DomainMigrationMode, DomainMigrationPlan, Migration, MigrationObserver,
+};
+use crate::controller::migration_queue::MigrationTracker;
+use crate::controller::sql::{RecipeExpr, RecipeVersions, Schema};
 use crate::controller::{
     schema, ControllerState, DomainPlacementRestriction, NodeRestrictionKey, Worker,
     WorkerIdentifier,
@@ -86,6 +107,9 @@ pub(in crate::controller) use self::graphviz::Graphviz;
 /// for replication offsets)
 const CONCURRENT_REQUESTS: usize = 16;
 
+/// Maximum number of entries to retain in the audit log of runtime configuration changes
+const MAX_RUNTIME_CONFIG_CHANGES: usize = 256;
+
 /// This structure holds all the dataflow state.
 /// It's meant to be handled exclusively by the [`DfStateHandle`], which is the structure
 /// that guarantees thread-safe access to it.
@@ -102,6 +126,11 @@ pub struct DfState {
 
     pub(super) replication_strategy: ReplicationStrategy,
 
+    /// Label selector which workers must match for domains containing base tables to be scheduled
+    /// onto them
+    #[serde(default)]
+    pub(super) base_table_worker_selector: LabelSelector,
+
     /// Controls the persistence mode, and parameters related to persistence.
     ///
     /// Three modes are available:
@@ -116,8 +145,19 @@ pub struct DfState {
 
     /// Current recipe
     pub(super) recipe: Recipe,
+    /// Versions of the views and caches in the recipe which have been staged or activated
+    #[serde(default)]
+    recipe_versions: RecipeVersions,
+    /// Caches whose dataflow nodes have been removed to free memory, but which can be re-created
+    /// from their definitions
+    #[serde(default)]
+    deactivated_views: DeactivatedViews,
     /// Latest replication position for the schema if from replica or binlog
     schema_replication_offset: Option<ReplicationOffset>,
+    /// Latest replication positions for the schemas of each additional upstream database, keyed
+    /// by the namespace the upstream database is replicated into
+    #[serde(default)]
+    namespace_schema_replication_offsets: HashMap<SqlIdentifier, ReplicationOffset>,
     /// Placement restrictions for nodes and the domains they are placed into.
     #[serde(with = "serde_with::rust::hashmap_as_tuple_list")]
     pub(super) node_restrictions: HashMap<NodeRestrictionKey, DomainPlacementRestriction>,
@@ -140,6 +180,32 @@ pub struct DfState {
     pub(super) read_addrs: HashMap<WorkerIdentifier, SocketAddr>,
     #[serde(skip)]
     pub(super) workers: HashMap<WorkerIdentifier, Worker>,
+
+    /// Tracker for the migration currently being run against this state, if it was requested via
+    /// the leader's [`MigrationQueue`](crate::controller::migration_queue::MigrationQueue).
+    #[serde(skip)]
+    pub(super) migration_tracker: Option<MigrationTracker>,
+
+    /// Observer for the migration currently being applied to this state, if one was registered
+    /// via [`Migration::set_observer`].
+    #[serde(skip)]
+    pub(super) migration_observer: Option<Arc<dyn MigrationObserver>>,
+
+    /// Audit log of the updates made to the runtime configuration of the deployment, oldest
+    /// first. Bounded to [`MAX_RUNTIME_CONFIG_CHANGES`] entries.
+    #[serde(default)]
+    pub(super) runtime_config_changes: VecDeque<RuntimeConfigChange>,
+
+    /// Whether an operator has asked for replication to be paused via `/pause_replication`, and
+    /// hasn't resumed it since. Persisted so that replication stays paused across a change of
+    /// leader.
+    #[serde(default)]
+    pub(super) replication_paused: bool,
+
+    /// The latest profiles of the data in each base table, maintained by the
+    /// [`TableProfiler`](super::profiler::TableProfiler)
+    #[serde(skip)]
+    pub(super) table_profiles: Arc<TableProfiles>,
 }
 
 impl DfState {
@@ -157,6 +223,7 @@ impl DfState {
         node_restrictions: HashMap<NodeRestrictionKey, DomainPlacementRestriction>,
         channel_coordinator: Arc<ChannelCoordinator>,
         replication_strategy: ReplicationStrategy,
+        base_table_worker_selector: LabelSelector,
     ) -> Self {
         Self {
             ingredients,
@@ -167,7 +234,10 @@ impl DfState {
             persistence,
             materializations,
             recipe,
+            recipe_versions: Default::default(),
+            deactivated_views: Default::default(),
             schema_replication_offset,
+            namespace_schema_replication_offsets: Default::default(),
             node_restrictions,
             domains: Default::default(),
             domain_nodes: Default::default(),
@@ -176,6 +246,12 @@ impl DfState {
             workers: Default::default(),
             domain_node_index_pairs: Default::default(),
             replication_strategy,
+            base_table_worker_selector,
+            migration_tracker: None,
+            migration_observer: None,
+            runtime_config_changes: Default::default(),
+            replication_paused: false,
+            table_profiles: Default::default(),
         }
     }
 
@@ -301,6 +377,195 @@ impl DfState {
             .collect()
     }
 
+    /// Describe the schemas of every base table and view in the graph
+    pub(super) fn schema(&self) -> ReadySetResult<SchemaRegistry> {
+        let tables = self
+            .tables()
+            .into_iter()
+            .map(|(name, ni)| self.table_schema(name, ni))
+            .collect::<ReadySetResult<Vec<_>>>()?;
+
+        let mut views = BTreeMap::new();
+        for ni in self
+            .ingredients
+            .externals(petgraph::EdgeDirection::Outgoing)
+        {
+            #[allow(clippy::indexing_slicing)] // just came from self.ingredients
+            let node = &self.ingredients[ni];
+            // A view may have more than one reader, all of which have the same schema
+            if node.is_reader() && !views.contains_key(node.name()) {
+                views.insert(node.name().clone(), self.reader_schema(ni)?);
+            }
+        }
+
+        Ok(SchemaRegistry {
+            tables,
+            views: views.into_values().collect(),
+        })
+    }
+
+    /// Describe the schema of the base table with the given name and node index
+    fn table_schema(&self, name: Relation, ni: NodeIndex) -> ReadySetResult<RelationSchema> {
+        let node = self
+            .ingredients
+            .node_weight(ni)
+            .ok_or_else(|| ReadySetError::NodeNotFound { index: ni.index() })?;
+        let base = node
+            .get_base()
+            .ok_or_else(|| ReadySetError::InvalidNodeType {
+                node_index: node.local_addr().id(),
+                expected_type: NodeType::Base,
+            })?;
+
+        let dropped = base.get_dropped();
+        let columns = node
+            .columns()
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !dropped.contains_key(*i))
+            .map(|(_, col)| {
+                let declared = self.base_column_type(&name, col.name());
+                ColumnDescription {
+                    name: col.name().into(),
+                    sql_type: declared.as_ref().map(|(ty, _)| ty.clone()),
+                    df_type: col.ty().clone(),
+                    nullable: declared.map_or(true, |(_, nullable)| nullable),
+                }
+            })
+            .collect();
+        let key_columns = base
+            .primary_key()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|i| node.columns().get(*i))
+            .map(|col| col.name().into())
+            .collect();
+
+        Ok(RelationSchema {
+            name,
+            columns,
+            key_columns,
+        })
+    }
+
+    /// Describe the schema of the view served by the reader node with the given index
+    fn reader_schema(&self, reader_ni: NodeIndex) -> ReadySetResult<RelationSchema> {
+        let node =
+            self.ingredients
+                .node_weight(reader_ni)
+                .ok_or_else(|| ReadySetError::NodeNotFound {
+                    index: reader_ni.index(),
+                })?;
+        let reader = node
+            .as_reader()
+            .ok_or_else(|| ReadySetError::InvalidNodeType {
+                node_index: node.local_addr().id(),
+                expected_type: NodeType::Reader,
+            })?;
+
+        // Columns on the optional side of an outer join may be NULL regardless of their base
+        // column, so don't trust base columns' nullability for views containing outer joins
+        let has_outer_join = self.has_outer_join(reader_ni);
+        let columns = match self.view_schema(reader_ni)? {
+            Some(schema) => schema
+                .schema(SchemaType::ReturnedSchema)
+                .iter()
+                .map(|col| {
+                    let declared = col
+                        .base
+                        .as_ref()
+                        .and_then(|base| self.base_column_type(&base.table, base.column.as_str()));
+                    ColumnDescription {
+                        name: col.column.name.clone(),
+                        sql_type: declared.as_ref().map(|(ty, _)| ty.clone()),
+                        df_type: col.column_type.clone(),
+                        nullable: has_outer_join || declared.map_or(true, |(_, nullable)| nullable),
+                    }
+                })
+                .collect(),
+            None => reader
+                .reader_processing()
+                .post_processing
+                .returned_cols
+                .clone()
+                .unwrap_or_else(|| (0..node.columns().len()).collect())
+                .into_iter()
+                .filter_map(|i| node.columns().get(i))
+                .map(|col| ColumnDescription {
+                    name: col.name().into(),
+                    sql_type: None,
+                    df_type: col.ty().clone(),
+                    nullable: true,
+                })
+                .collect(),
+        };
+        // Views for queries without parameters are keyed by a bogokey, which isn't a column anyone
+        // can look them up by
+        let key_columns = reader
+            .mapping()
+            .iter()
+            .filter_map(|i| node.columns().get(*i))
+            .filter(|col| col.name() != BOGOKEY_COL.as_str())
+            .map(|col| col.name().into())
+            .collect();
+
+        Ok(RelationSchema {
+            name: node.name().clone(),
+            columns,
+            key_columns,
+        })
+    }
+
+    /// Returns the SQL type declared for the column with the given name in the base table with the
+    /// given name, along with whether the column may contain `NULL`, or `None` if there's no such
+    /// column
+    fn base_column_type(&self, table: &Relation, column: &str) -> Option<(SqlType, bool)> {
+        let Some(Schema::Table(schema)) = self.recipe.schema_for(table) else {
+            return None;
+        };
+        let spec = schema
+            .statement
+            .fields
+            .iter()
+            .find(|spec| spec.column.name == *column)?;
+
+        let in_primary_key = self
+            .recipe
+            .node_addr_for(table)
+            .ok()
+            .and_then(|ni| self.ingredients.node_weight(ni))
+            .and_then(|node| Some((node, node.get_base()?.primary_key()?)))
+            .map_or(false, |(node, key)| {
+                key.iter()
+                    .any(|i| node.columns().get(*i).map_or(false, |c| c.name() == column))
+            });
+        let nullable = !in_primary_key
+            && !spec
+                .constraints
+                .iter()
+                .any(|c| matches!(c, ColumnConstraint::NotNull | ColumnConstraint::PrimaryKey));
+
+        Some((spec.sql_type.clone(), nullable))
+    }
+
+    /// Returns `true` if the node with the given index, or any of its ancestors, is an outer join
+    fn has_outer_join(&self, ni: NodeIndex) -> bool {
+        let graph = Reversed(&self.ingredients);
+        let mut bfs = Bfs::new(graph, ni);
+        while let Some(n) = bfs.next(graph) {
+            if let Some(NodeOperator::Join(join)) = self
+                .ingredients
+                .node_weight(n)
+                .and_then(|n| n.as_internal())
+            {
+                if *join.kind() == JoinType::Left {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
     pub(super) fn view_names(
         &self,
         queries: Vec<ViewCreateRequest>,
@@ -909,7 +1174,10 @@ impl DfState {
                 .map(|domain| (domain, DomainRequest::RequestReplicationOffsets)),
         )
         .try_fold(
-            ReplicationOffsets::with_schema_offset(self.schema_replication_offset.clone()),
+            ReplicationOffsets {
+                namespace_schemas: self.namespace_schema_replication_offsets.clone(),
+                ..ReplicationOffsets::with_schema_offset(self.schema_replication_offset.clone())
+            },
             |mut acc, (domain, domain_offs)| async move {
                 for replica in domain_offs.into_cells() {
                     for (lni, offset) in replica {
@@ -1159,6 +1427,45 @@ impl DfState {
         Ok(res)
     }
 
+    /// Returns the total size in bytes of the materialized state of all the nodes in the graph
+    /// other than base tables
+    pub(super) async fn materialized_view_bytes(&self) -> ReadySetResult<u64> {
+        Ok(self
+            .node_sizes()
+            .await?
+            .into_iter()
+            .filter(|(ni, _)| {
+                self.ingredients
+                    .node_weight(*ni)
+                    .map_or(false, |node| !node.is_base())
+            })
+            .map(|(_, size)| size.bytes.0 as u64)
+            .sum())
+    }
+
+    /// Collect the usage counters for the reader of every cache in the recipe, summed across all
+    /// of their shards and replicas
+    pub(super) async fn cache_metrics(&self) -> ReadySetResult<HashMap<Relation, ReaderMetrics>> {
+        let mut res = HashMap::new();
+        for name in self.recipe.cache_names() {
+            let Some(reader) = self
+                .recipe
+                .node_addr_for(name)
+                .ok()
+                .and_then(|ni| self.find_reader_for(ni, name, &None))
+            else {
+                continue;
+            };
+            res.insert(name.clone(), self.reader_metrics(reader).await?);
+        }
+        Ok(res)
+    }
+
+    /// Returns the caches which have been deactivated and can be reactivated
+    pub(super) fn deactivated_views(&self) -> Vec<DeactivatedView> {
+        self.deactivated_views.info()
+    }
+
     // ** Modify operations **
 
     /// Perform a new query schema migration.
@@ -1174,6 +1481,9 @@ impl DfState {
     {
         debug!("starting migration");
         gauge!(recorded::CONTROLLER_MIGRATION_IN_PROGRESS, 1.0);
+        if let Some(tracker) = &self.migration_tracker {
+            tracker.set_phase(MigrationPhase::Lowering);
+        }
         let mut m = Migration::new(self, dialect);
         let r = f(&mut m)?;
         m.commit(dry_run).await?;
@@ -1291,12 +1601,23 @@ impl DfState {
                     #[allow(clippy::indexing_slicing)] // checked above
                     let node = &self.ingredients[*n];
 
-                    if node.is_base() && w.domain_scheduling_config.volume_id.is_some() {
+                    if node.is_base()
+                        && self.persistence.mode != DurabilityMode::MemoryOnly
+                        && w.domain_scheduling_config.volume_id.is_some()
+                    {
+                        // Persistent volumes are bound to a single zone, so keep the base table
+                        // in the zone of the worker it was placed on in addition to requiring
+                        // the same volume.
+                        let mut label_selector = self.base_table_worker_selector.clone();
+                        if let Some(zone) = w.domain_scheduling_config.labels.get(ZONE_LABEL) {
+                            label_selector.insert(ZONE_LABEL.to_owned(), zone.clone());
+                        }
                         new_domain_restrictions.push((
                             node.name().to_owned(),
                             shard,
                             DomainPlacementRestriction {
                                 worker_volume: w.domain_scheduling_config.volume_id.clone(),
+                                label_selector,
                             },
                         ));
                     }
@@ -1413,6 +1734,22 @@ impl DfState {
         self.schema_replication_offset = offset;
     }
 
+    pub(super) fn set_namespace_schema_replication_offset(
+        &mut self,
+        namespace: SqlIdentifier,
+        offset: Option<ReplicationOffset>,
+    ) {
+        match offset {
+            Some(offset) => {
+                self.namespace_schema_replication_offsets
+                    .insert(namespace, offset);
+            }
+            None => {
+                self.namespace_schema_replication_offsets.remove(&namespace);
+            }
+        }
+    }
+
     pub(super) async fn flush_partial(&mut self) -> ReadySetResult<u64> {
         // get statistics for current domain sizes
         // and evict all state from partial nodes
@@ -1527,6 +1864,654 @@ impl DfState {
         Ok(res)
     }
 
+    /// Report diagnostic information about how the given `key` is served by the view with the given
+    /// name: which replay paths would be used to fill a miss on the key, which nodes along those
+    /// paths hold state, whether the key is currently materialized, and timing information about
+    /// the most recent miss on the key.
+    pub(super) async fn trace_key(
+        &self,
+        view: &Relation,
+        key: Vec<DfValue>,
+    ) -> ReadySetResult<KeyTrace> {
+        let view_not_found = || ReadySetError::ViewNotFound(view.display_unquoted().to_string());
+        let ni = self
+            .recipe
+            .node_addr_for(view)
+            .ok()
+            .or_else(|| self.views().get(view).copied())
+            .ok_or_else(view_not_found)?;
+        let reader = self
+            .find_reader_for(ni, view, &None)
+            .ok_or_else(view_not_found)?;
+        let key = KeyComparison::Equal(
+            Vec1::try_from(key).map_err(|_| invalid_query_err!("Cannot trace an empty key"))?,
+        );
+
+        let replay_paths = self
+            .materializations
+            .paths
+            .get(&reader)
+            .into_iter()
+            .flat_map(|paths| paths.left_values())
+            .filter_map(|tag| self.replay_path_info(reader, *tag))
+            .collect();
+
+        #[allow(clippy::indexing_slicing)] // reader came from self.ingredients
+        let reader_node = &self.ingredients[reader];
+        let domain = reader_node.domain();
+        let traces = self
+            .domains
+            .get(&domain)
+            .ok_or_else(|| internal_err!("Domain {domain} for reader not found"))?
+            .send_to_healthy::<ReaderKeyTrace>(
+                DomainRequest::TraceReaderKey {
+                    node: reader_node.local_addr(),
+                    key,
+                },
+                &self.workers,
+            )
+            .await?
+            .into_cells()
+            .into_iter()
+            .flatten(/* Discard results from non-running domains */)
+            .collect::<Vec<_>>();
+
+        Ok(KeyTrace {
+            view: view.clone(),
+            reader,
+            domain,
+            partial: self.materializations.is_partial(reader),
+            materialized: traces.iter().any(|t| t.materialized),
+            replay_paths,
+            last_miss: traces
+                .iter()
+                .filter_map(|t| t.last_miss)
+                .min_by_key(|miss| miss.elapsed),
+        })
+    }
+
+    /// Report usage counters for the view with the given name: how many reads against it hit and
+    /// missed, how often keys were evicted from it, and how long it took to fill misses, summed
+    /// across all of the view's reader shards and replicas.
+    pub(super) async fn view_metrics(&self, view: &Relation) -> ReadySetResult<ViewMetrics> {
+        let view_not_found = || ReadySetError::ViewNotFound(view.display_unquoted().to_string());
+        let ni = self
+            .recipe
+            .node_addr_for(view)
+            .ok()
+            .or_else(|| self.views().get(view).copied())
+            .ok_or_else(view_not_found)?;
+        let reader = self
+            .find_reader_for(ni, view, &None)
+            .ok_or_else(view_not_found)?;
+
+        Ok(ViewMetrics {
+            view: view.clone(),
+            reader: self.reader_metrics(reader).await?,
+        })
+    }
+
+    /// Collect the usage counters for the given reader node, summed across all of its shards and
+    /// replicas
+    pub(super) async fn reader_metrics(&self, reader: NodeIndex) -> ReadySetResult<ReaderMetrics> {
+        #[allow(clippy::indexing_slicing)] // reader came from self.ingredients
+        let reader_node = &self.ingredients[reader];
+        let domain = reader_node.domain();
+        let mut metrics = ReaderMetrics::default();
+        for shard_metrics in self
+            .domains
+            .get(&domain)
+            .ok_or_else(|| internal_err!("Domain {domain} for reader not found"))?
+            .send_to_healthy::<ReaderMetrics>(
+                DomainRequest::ReaderMetrics {
+                    node: reader_node.local_addr(),
+                },
+                &self.workers,
+            )
+            .await?
+            .into_cells()
+            .into_iter()
+            .flatten(/* Discard results from non-running domains */)
+        {
+            metrics += shard_metrics;
+        }
+        Ok(metrics)
+    }
+
+    /// Report the replay paths which can be used to fill misses in the view with the given name,
+    /// the domains those paths traverse, and running totals of the rows and bytes replayed into
+    /// the view's reader.
+    ///
+    /// Since a miss in the reader can in turn miss in partially materialized nodes along the
+    /// reader's replay paths, the replay paths targeting those nodes are included too.
+    pub(super) async fn view_replay_paths(
+        &self,
+        view: &Relation,
+    ) -> ReadySetResult<ViewReplayPaths> {
+        let view_not_found = || ReadySetError::ViewNotFound(view.display_unquoted().to_string());
+        let ni = self
+            .recipe
+            .node_addr_for(view)
+            .ok()
+            .or_else(|| self.views().get(view).copied())
+            .ok_or_else(view_not_found)?;
+        let reader = self
+            .find_reader_for(ni, view, &None)
+            .ok_or_else(view_not_found)?;
+
+        let mut replay_paths = Vec::new();
+        let mut visited = HashSet::new();
+        let mut to_visit = VecDeque::from([reader]);
+        while let Some(target) = to_visit.pop_front() {
+            if !visited.insert(target) {
+                continue;
+            }
+            let mut tags = self
+                .materializations
+                .paths
+                .get(&target)
+                .into_iter()
+                .flat_map(|paths| paths.left_values().copied())
+                .collect::<Vec<_>>();
+            tags.sort();
+            for tag in tags {
+                let Some(path) = self.replay_path_info(target, tag) else {
+                    continue;
+                };
+                to_visit.extend(
+                    path.segments
+                        .iter()
+                        .filter(|segment| segment.node_index != target && segment.partial)
+                        .map(|segment| segment.node_index),
+                );
+                replay_paths.push(path);
+            }
+        }
+
+        let domains = replay_paths
+            .iter()
+            .flat_map(|path| path.segments.iter().map(|segment| segment.domain))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let metrics = self.reader_metrics(reader).await?;
+
+        Ok(ViewReplayPaths {
+            view: view.clone(),
+            reader,
+            replay_paths,
+            domains,
+            replays: metrics.replays,
+            replayed_rows: metrics.replayed_rows,
+            replayed_bytes: metrics.replayed_bytes,
+        })
+    }
+
+    /// Report the keys read most often from the view with the given name, combined across all of
+    /// the view's reader shards and replicas
+    pub(super) async fn view_hot_keys(&self, view: &Relation) -> ReadySetResult<ViewHotKeys> {
+        let view_not_found = || ReadySetError::ViewNotFound(view.display_unquoted().to_string());
+        let ni = self
+            .recipe
+            .node_addr_for(view)
+            .ok()
+            .or_else(|| self.views().get(view).copied())
+            .ok_or_else(view_not_found)?;
+        let reader = self
+            .find_reader_for(ni, view, &None)
+            .ok_or_else(view_not_found)?;
+
+        #[allow(clippy::indexing_slicing)] // reader came from self.ingredients
+        let reader_node = &self.ingredients[reader];
+        let domain = reader_node.domain();
+        let mut hot_keys = ReaderHotKeys::default();
+        for shard_hot_keys in self
+            .domains
+            .get(&domain)
+            .ok_or_else(|| internal_err!("Domain {domain} for reader not found"))?
+            .send_to_healthy::<ReaderHotKeys>(
+                DomainRequest::ReaderHotKeys {
+                    node: reader_node.local_addr(),
+                },
+                &self.workers,
+            )
+            .await?
+            .into_cells()
+            .into_iter()
+            .flatten(/* Discard results from non-running domains */)
+        {
+            hot_keys += shard_hot_keys;
+        }
+
+        Ok(ViewHotKeys {
+            view: view.clone(),
+            reader: hot_keys,
+        })
+    }
+
+    /// Replace the limits on the rate of reads against the view with the given name, applying them
+    /// to all running shards and replicas of the view's reader
+    pub(super) async fn set_view_rate_limit(
+        &mut self,
+        view: &Relation,
+        limit: ViewRateLimit,
+    ) -> ReadySetResult<()> {
+        limit.validate()?;
+
+        let view_not_found = || ReadySetError::ViewNotFound(view.display_unquoted().to_string());
+        let ni = self
+            .recipe
+            .node_addr_for(view)
+            .ok()
+            .or_else(|| self.views().get(view).copied())
+            .ok_or_else(view_not_found)?;
+        let reader = self
+            .find_reader_for(ni, view, &None)
+            .ok_or_else(view_not_found)?;
+
+        #[allow(clippy::indexing_slicing)] // reader came from self.ingredients
+        let reader_node = &mut self.ingredients[reader];
+        reader_node
+            .as_mut_reader()
+            .ok_or_else(|| internal_err!("find_reader_for returned a non-reader node"))?
+            .set_rate_limit(limit);
+
+        let domain = reader_node.domain();
+        let node = reader_node.local_addr();
+        self.domains
+            .get(&domain)
+            .ok_or_else(|| internal_err!("Domain {domain} for reader not found"))?
+            .send_to_healthy::<()>(
+                DomainRequest::SetReaderRateLimit { node, limit },
+                &self.workers,
+            )
+            .await?;
+
+        info!(view = %view.display_unquoted(), ?limit, "Set view rate limit");
+        Ok(())
+    }
+
+    /// Set how long the reader for the view with the given name retains the deltas applied to it
+    /// for as-of reads, applying it to all running shards and replicas of the reader
+    pub(super) async fn set_view_history_retention(
+        &mut self,
+        view: &Relation,
+        retention: Option<Duration>,
+    ) -> ReadySetResult<()> {
+        if retention == Some(Duration::ZERO) {
+            return Err(bad_request_err(
+                "history retention must be greater than zero",
+            ));
+        }
+
+        let view_not_found = || ReadySetError::ViewNotFound(view.display_unquoted().to_string());
+        let ni = self
+            .recipe
+            .node_addr_for(view)
+            .ok()
+            .or_else(|| self.views().get(view).copied())
+            .ok_or_else(view_not_found)?;
+        let reader = self
+            .find_reader_for(ni, view, &None)
+            .ok_or_else(view_not_found)?;
+
+        #[allow(clippy::indexing_slicing)] // reader came from self.ingredients
+        let reader_node = &mut self.ingredients[reader];
+        reader_node
+            .as_mut_reader()
+            .ok_or_else(|| internal_err!("find_reader_for returned a non-reader node"))?
+            .set_history_retention(retention);
+
+        let domain = reader_node.domain();
+        let node = reader_node.local_addr();
+        self.domains
+            .get(&domain)
+            .ok_or_else(|| internal_err!("Domain {domain} for reader not found"))?
+            .send_to_healthy::<()>(
+                DomainRequest::SetReaderHistoryRetention { node, retention },
+                &self.workers,
+            )
+            .await?;
+
+        info!(view = %view.display_unquoted(), ?retention, "Set view history retention");
+        Ok(())
+    }
+
+    /// Configure whether reads against the reader for the view with the given name may be answered
+    /// with possibly-stale values, applying it to all running shards and replicas of the reader
+    pub(super) async fn set_view_stale_reads(
+        &mut self,
+        view: &Relation,
+        stale_reads: ViewStaleReads,
+    ) -> ReadySetResult<()> {
+        stale_reads.validate()?;
+
+        let view_not_found = || ReadySetError::ViewNotFound(view.display_unquoted().to_string());
+        let ni = self
+            .recipe
+            .node_addr_for(view)
+            .ok()
+            .or_else(|| self.views().get(view).copied())
+            .ok_or_else(view_not_found)?;
+        let reader = self
+            .find_reader_for(ni, view, &None)
+            .ok_or_else(view_not_found)?;
+
+        #[allow(clippy::indexing_slicing)] // reader came from self.ingredients
+        let reader_node = &mut self.ingredients[reader];
+        reader_node
+            .as_mut_reader()
+            .ok_or_else(|| internal_err!("find_reader_for returned a non-reader node"))?
+            .set_stale_reads(stale_reads);
+
+        let domain = reader_node.domain();
+        let node = reader_node.local_addr();
+        self.domains
+            .get(&domain)
+            .ok_or_else(|| internal_err!("Domain {domain} for reader not found"))?
+            .send_to_healthy::<()>(
+                DomainRequest::SetReaderStaleReads { node, stale_reads },
+                &self.workers,
+            )
+            .await?;
+
+        info!(view = %view.display_unquoted(), ?stale_reads, "Set view stale reads");
+        Ok(())
+    }
+
+    /// Replace the limits on the size of results of reads against the reader for the view with
+    /// the given name, applying them to all running shards and replicas of the reader
+    pub(super) async fn set_view_result_limit(
+        &mut self,
+        view: &Relation,
+        result_limit: ViewResultLimit,
+    ) -> ReadySetResult<()> {
+        result_limit.validate()?;
+
+        let view_not_found = || ReadySetError::ViewNotFound(view.display_unquoted().to_string());
+        let ni = self
+            .recipe
+            .node_addr_for(view)
+            .ok()
+            .or_else(|| self.views().get(view).copied())
+            .ok_or_else(view_not_found)?;
+        let reader = self
+            .find_reader_for(ni, view, &None)
+            .ok_or_else(view_not_found)?;
+
+        #[allow(clippy::indexing_slicing)] // reader came from self.ingredients
+        let reader_node = &mut self.ingredients[reader];
+        reader_node
+            .as_mut_reader()
+            .ok_or_else(|| internal_err!("find_reader_for returned a non-reader node"))?
+            .set_result_limit(result_limit);
+
+        let domain = reader_node.domain();
+        let node = reader_node.local_addr();
+        self.domains
+            .get(&domain)
+            .ok_or_else(|| internal_err!("Domain {domain} for reader not found"))?
+            .send_to_healthy::<()>(
+                DomainRequest::SetReaderResultLimit { node, result_limit },
+                &self.workers,
+            )
+            .await?;
+
+        info!(view = %view.display_unquoted(), ?result_limit, "Set view result limit");
+        Ok(())
+    }
+
+    /// Set how long reads against the reader for the view with the given name wait for misses to
+    /// be filled by upqueries, applying it to all running shards and replicas of the reader
+    pub(super) async fn set_view_upquery_timeout(
+        &mut self,
+        view: &Relation,
+        timeout: Option<Duration>,
+    ) -> ReadySetResult<()> {
+        let view_not_found = || ReadySetError::ViewNotFound(view.display_unquoted().to_string());
+        let ni = self
+            .recipe
+            .node_addr_for(view)
+            .ok()
+            .or_else(|| self.views().get(view).copied())
+            .ok_or_else(view_not_found)?;
+        let reader = self
+            .find_reader_for(ni, view, &None)
+            .ok_or_else(view_not_found)?;
+
+        #[allow(clippy::indexing_slicing)] // reader came from self.ingredients
+        let reader_node = &mut self.ingredients[reader];
+        reader_node
+            .as_mut_reader()
+            .ok_or_else(|| internal_err!("find_reader_for returned a non-reader node"))?
+            .set_upquery_timeout(timeout);
+
+        let domain = reader_node.domain();
+        let node = reader_node.local_addr();
+        self.domains
+            .get(&domain)
+            .ok_or_else(|| internal_err!("Domain {domain} for reader not found"))?
+            .send_to_healthy::<()>(
+                DomainRequest::SetReaderUpqueryTimeout { node, timeout },
+                &self.workers,
+            )
+            .await?;
+
+        info!(view = %view.display_unquoted(), ?timeout, "Set view upquery timeout");
+        Ok(())
+    }
+
+    /// Replace the secondary indices maintained on the reader for the view with the given name
+    /// with indices on each of the given sets of columns, returning the index of the reader. Only
+    /// supported for fully materialized views.
+    ///
+    /// This only updates the reader in the graph; once the dataflow state has been committed, the
+    /// new indices should be sent to all running shards and replicas of the reader with
+    /// [`Self::send_reader_secondary_indices`].
+    pub(super) fn set_view_secondary_indices(
+        &mut self,
+        view: &Relation,
+        indices: Vec<Vec<SqlIdentifier>>,
+    ) -> ReadySetResult<NodeIndex> {
+        let view_not_found = || ReadySetError::ViewNotFound(view.display_unquoted().to_string());
+        let ni = self
+            .recipe
+            .node_addr_for(view)
+            .ok()
+            .or_else(|| self.views().get(view).copied())
+            .ok_or_else(view_not_found)?;
+        let reader = self
+            .find_reader_for(ni, view, &None)
+            .ok_or_else(view_not_found)?;
+        if self.materializations.is_partial(reader) {
+            unsupported!("Secondary indices are only supported on fully materialized views");
+        }
+
+        #[allow(clippy::indexing_slicing)] // reader came from self.ingredients
+        let reader_node = &mut self.ingredients[reader];
+        let indices = indices
+            .iter()
+            .map(|columns| {
+                if columns.is_empty() {
+                    return Err(bad_request_err("Secondary indices must have columns"));
+                }
+                columns
+                    .iter()
+                    .map(|column| {
+                        reader_node
+                            .columns()
+                            .iter()
+                            .position(|c| c.name() == column.as_str())
+                            .ok_or_else(|| ReadySetError::NoSuchColumn(column.to_string()))
+                    })
+                    .collect::<ReadySetResult<Vec<_>>>()
+            })
+            .collect::<ReadySetResult<Vec<_>>>()?;
+        reader_node
+            .as_mut_reader()
+            .ok_or_else(|| internal_err!("find_reader_for returned a non-reader node"))?
+            .set_secondary_indices(indices);
+
+        info!(view = %view.display_unquoted(), "Set view secondary indices");
+        Ok(reader)
+    }
+
+    /// Send the secondary indices recorded for the given reader node to all running shards and
+    /// replicas of that reader.
+    pub(super) async fn send_reader_secondary_indices(
+        &self,
+        reader: NodeIndex,
+    ) -> ReadySetResult<()> {
+        let reader_node = self
+            .ingredients
+            .node_weight(reader)
+            .ok_or_else(|| ReadySetError::NoSuchNode(reader.index()))?;
+        let indices = reader_node
+            .as_reader()
+            .ok_or_else(|| internal_err!("Node {} is not a reader", reader.index()))?
+            .secondary_indices()
+            .to_vec();
+
+        let domain = reader_node.domain();
+        let node = reader_node.local_addr();
+        self.domains
+            .get(&domain)
+            .ok_or_else(|| internal_err!("Domain {domain} for reader not found"))?
+            .send_to_healthy::<()>(
+                DomainRequest::SetReaderSecondaryIndices { node, indices },
+                &self.workers,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Describe the replay path identified by `tag` that targets the given node, if it exists
+    pub(super) fn replay_path_info(&self, target: NodeIndex, tag: Tag) -> Option<ReplayPathInfo> {
+        let (_, path) = self
+            .materializations
+            .paths
+            .get(&target)?
+            .get_by_left(&tag)?;
+        Some(ReplayPathInfo {
+            tag: u32::from(tag),
+            segments: path
+                .iter()
+                .map(|&ni| {
+                    #[allow(clippy::indexing_slicing)] // replay paths point at graph nodes
+                    let node = &self.ingredients[ni];
+                    ReplayPathSegmentInfo {
+                        node_index: ni,
+                        node_name: node.name().clone(),
+                        domain: node.domain(),
+                        materialized: node.is_reader()
+                            || self.materializations.indexes_for(ni).is_some(),
+                        partial: self.materializations.is_partial(ni),
+                    }
+                })
+                .collect(),
+        })
+    }
+
+    /// Collect the most recent replays to fill misses in readers which took longer than the
+    /// configured slow replay threshold from all domains, oldest first.
+    pub(super) async fn slow_replays(&self) -> ReadySetResult<Vec<SlowReplay>> {
+        let replays_per_domain: Vec<Array2<Option<Vec<ReaderSlowReplay>>>> = {
+            let domains = self.domains.keys().copied().collect::<Vec<_>>();
+
+            stream::iter(domains)
+                .map(move |domain| {
+                    #[allow(clippy::indexing_slicing)] // came from self.domains
+                    self.domains[&domain].send_to_healthy::<Vec<ReaderSlowReplay>>(
+                        DomainRequest::SlowReplays,
+                        &self.workers,
+                    )
+                })
+                .buffer_unordered(CONCURRENT_REQUESTS)
+        }
+        .try_collect()
+        .await?;
+
+        let mut res = replays_per_domain
+            .into_iter()
+            .flat_map(|per_shard| per_shard.into_cells().into_iter().flatten().flatten())
+            .map(|replay| SlowReplay {
+                path: self.replay_path_info(replay.reader, Tag::new(replay.tag)),
+                replay,
+            })
+            .collect::<Vec<_>>();
+        res.sort_by_key(|r| r.replay.completed_at);
+        Ok(res)
+    }
+
+    /// Apply the given (already validated) update to the domain configuration used for domains
+    /// created in the future, and record it in the audit log of runtime configuration changes.
+    ///
+    /// This doesn't change the configuration of any running workers or domains - once this state
+    /// has been committed, that's done by [`Self::propagate_runtime_config_update`].
+    pub(super) fn record_runtime_config_update(&mut self, update: RuntimeConfigUpdate) {
+        if let Some(timeout) = update.view_request_timeout {
+            self.domain_config.view_request_timeout = timeout;
+        }
+        if let Some(timeout) = update.table_request_timeout {
+            self.domain_config.table_request_timeout = timeout;
+        }
+        if let Some(verbose) = update.verbose_domain_metrics {
+            self.domain_config.verbose_metrics = verbose;
+        }
+        if let Some(aggressive) = update.aggressively_update_state_sizes {
+            self.domain_config.aggressively_update_state_sizes = aggressive;
+        }
+        if let Some(threshold) = update.slow_replay_threshold {
+            self.domain_config.slow_replay_threshold = threshold;
+        }
+        if let Some(log) = update.log_slow_replays {
+            self.domain_config.log_slow_replays = log;
+        }
+        if let Some(profile) = update.profile_replays {
+            self.domain_config.profile_replays = profile;
+        }
+
+        self.runtime_config_changes.push_back(RuntimeConfigChange {
+            applied_at: SystemTime::now(),
+            update,
+        });
+        if self.runtime_config_changes.len() > MAX_RUNTIME_CONFIG_CHANGES {
+            self.runtime_config_changes.pop_front();
+        }
+    }
+
+    /// Send the given update to the runtime configuration of the deployment to all workers and
+    /// domains
+    pub(super) async fn propagate_runtime_config_update(
+        &self,
+        update: &RuntimeConfigUpdate,
+    ) -> ReadySetResult<()> {
+        if update.affects_workers() {
+            for worker in self.workers.values() {
+                worker
+                    .rpc::<()>(WorkerRequestKind::UpdateRuntimeConfig(update.clone()))
+                    .await?;
+            }
+        }
+
+        if update.affects_domains() {
+            let domains = self.domains.keys().copied().collect::<Vec<_>>();
+            stream::iter(domains)
+                .map(|domain| {
+                    #[allow(clippy::indexing_slicing)] // came from self.domains
+                    self.domains[&domain].send_to_healthy::<()>(
+                        DomainRequest::UpdateRuntimeConfig(update.clone()),
+                        &self.workers,
+                    )
+                })
+                .buffer_unordered(CONCURRENT_REQUESTS)
+                .try_collect::<Vec<_>>()
+                .await?;
+        }
+
+        Ok(())
+    }
+
     /// Iterate over all nodes stored in `self::domain_nodes` to find the [`Domain`] that owns the
     /// given `node`.
     ///
@@ -1570,6 +2555,132 @@ impl DfState {
         }
     }
 
+    /// Perform all the planning for the migration required to apply `changelist`, including
+    /// lowering any new queries to MIR and dataflow, but without committing the migration, and
+    /// return a description of the changes it would make, including a forecast of the size of the
+    /// state of each node it would materialize (see the [`forecast`](super::forecast) module).
+    ///
+    /// This leaves the dataflow state describing a graph that doesn't match the domains that are
+    /// actually running, so must only be called on a copy of the dataflow state which is then
+    /// discarded.
+    pub(super) async fn plan_migration(
+        &mut self,
+        changelist: ChangeList,
+    ) -> ReadySetResult<MigrationPlanSummary> {
+        // Collect the sizes of existing nodes before we make any changes to the graph, so we can
+        // use them to estimate how much state the migration would add
+        let node_sizes = self.node_sizes().await?;
+        let first_new_node = NodeIndex::new(self.ingredients.node_count());
+
+        let mut recipe = self.recipe.clone();
+        let mut m = Migration::new(self, changelist.dialect);
+        recipe.activate(&mut m, changelist)?;
+        let plan = m.plan().map_err(|e| ReadySetError::MigrationPlanFailed {
+            source: Box::new(e),
+        })?;
+        let mut summary = plan.summarize(first_new_node, &node_sizes);
+
+        let materialized_nodes = summary
+            .added_nodes
+            .iter()
+            .filter(|node| node.materialized)
+            .map(|node| node.node_index)
+            .collect::<Vec<_>>();
+        let base_tables = self
+            .base_table_stats(&materialized_nodes, &node_sizes)
+            .await?;
+        let mut forecaster = Forecaster::new(&self.ingredients, &base_tables);
+        summary.size_forecasts = summary
+            .added_nodes
+            .iter()
+            .filter(|node| node.materialized)
+            .map(|node| NodeSizeForecast {
+                node_index: node.node_index,
+                node_name: node.node_name.clone(),
+                partial: node.partial,
+                estimated_size: forecaster.forecast(node.node_index),
+            })
+            .collect();
+
+        Ok(summary)
+    }
+
+    /// Collect the sizes of all the existing base tables that any of the given nodes read from,
+    /// along with profiles of the data in their columns, for use in forecasting the size of those
+    /// nodes. Tables which haven't been profiled by the
+    /// [`TableProfiler`](super::profiler::TableProfiler) yet are profiled now.
+    async fn base_table_stats(
+        &self,
+        nodes: &[NodeIndex],
+        node_sizes: &HashMap<NodeIndex, NodeSize>,
+    ) -> ReadySetResult<HashMap<NodeIndex, BaseTableStats>> {
+        let mut base_tables = HashMap::new();
+        let mut to_visit = nodes.to_vec();
+        let mut visited = HashSet::new();
+        while let Some(ni) = to_visit.pop() {
+            if !visited.insert(ni) {
+                continue;
+            }
+            #[allow(clippy::indexing_slicing)] // nodes come from the graph
+            let node = &self.ingredients[ni];
+            if !node.is_base() {
+                to_visit.extend(self.ingredients.neighbors_directed(ni, Direction::Incoming));
+                continue;
+            }
+            // Base tables added by the migration don't have any rows yet
+            let Some(size) = node_sizes.get(&ni) else {
+                continue;
+            };
+
+            let profile = match self.table_profiles.get(node.name()) {
+                Some(profile) => Some(profile),
+                None => self.profile_table(ni).await?,
+            };
+            base_tables.insert(
+                ni,
+                BaseTableStats {
+                    size: *size,
+                    profile,
+                },
+            );
+        }
+        Ok(base_tables)
+    }
+
+    /// Profile the data in a sample of the rows of the given base table, combining the profiles of
+    /// each of its shards. Returns `None` if the table's domain isn't running.
+    pub(super) async fn profile_table(
+        &self,
+        ni: NodeIndex,
+    ) -> ReadySetResult<Option<StateProfile>> {
+        let node = self
+            .ingredients
+            .node_weight(ni)
+            .ok_or_else(|| ReadySetError::NoSuchNode(ni.index()))?;
+        let Some(domain) = self.domains.get(&node.domain()) else {
+            return Ok(None);
+        };
+
+        let profiles = domain
+            .send_to_healthy_replicas::<StateProfile, _>(
+                DomainRequest::ProfileState {
+                    node: node.local_addr(),
+                    max_rows: profiler::PROFILE_SAMPLE_ROWS,
+                },
+                [0],
+                &self.workers,
+            )
+            .await?;
+        Ok(profiles
+            .into_cells()
+            .into_iter()
+            .flatten()
+            .reduce(|mut a, b| {
+                a.merge(b);
+                a
+            }))
+    }
+
     pub(super) async fn extend_recipe(
         &mut self,
         recipe_spec: ExtendRecipeSpec<'_>,
@@ -1577,10 +2688,11 @@ impl DfState {
     ) -> Result<(), ReadySetError> {
         set_failpoint!(failpoints::EXTEND_RECIPE);
         // Drop recipes from the replicator that we have already processed.
-        if let (Some(new), Some(current)) = (
-            &recipe_spec.replication_offset,
-            &self.schema_replication_offset,
-        ) {
+        let current_offset = match &recipe_spec.replication_namespace {
+            Some(namespace) => self.namespace_schema_replication_offsets.get(namespace),
+            None => self.schema_replication_offset.as_ref(),
+        };
+        if let (Some(new), Some(current)) = (&recipe_spec.replication_offset, current_offset) {
             if current >= new {
                 // no-op
                 return Ok(());
@@ -1590,8 +2702,24 @@ impl DfState {
         match self.apply_recipe(recipe_spec.changes, dry_run).await {
             Ok(x) => {
                 if let Some(offset) = &recipe_spec.replication_offset {
-                    debug!(%offset, "Updating schema replication offset");
-                    offset.try_max_into(&mut self.schema_replication_offset)?
+                    match &recipe_spec.replication_namespace {
+                        Some(namespace) => {
+                            debug!(%offset, %namespace, "Updating schema replication offset");
+                            let mut current = self
+                                .namespace_schema_replication_offsets
+                                .get(namespace)
+                                .cloned();
+                            offset.try_max_into(&mut current)?;
+                            self.set_namespace_schema_replication_offset(
+                                namespace.clone(),
+                                current,
+                            );
+                        }
+                        None => {
+                            debug!(%offset, "Updating schema replication offset");
+                            offset.try_max_into(&mut self.schema_replication_offset)?
+                        }
+                    }
                 }
 
                 Ok(x)
@@ -1600,6 +2728,109 @@ impl DfState {
         }
     }
 
+    /// Validate the given set of view and cache additions and removals by performing a dry-run
+    /// migration with them on a copy of the dataflow state, then stage them as a new version of the
+    /// recipe which can later be activated via [`Self::activate_recipe_version`].
+    pub(super) async fn stage_recipe_version(
+        &mut self,
+        changelist: ChangeList,
+    ) -> ReadySetResult<RecipeVersion> {
+        for change in &changelist.changes {
+            match change {
+                Change::CreateView(_) | Change::CreateCache(_) => {}
+                Change::Drop { name, .. } => {
+                    let mut name = name.clone();
+                    if name.schema.is_none() {
+                        name.schema = changelist.schema_search_path.first().cloned();
+                    }
+                    if let Some(Schema::Table(_)) = self.recipe.schema_for(&name) {
+                        return Err(bad_request_err(format!(
+                            "Cannot drop table {} in a recipe version",
+                            name.display_unquoted()
+                        )));
+                    }
+                }
+                _ => {
+                    return Err(bad_request_err(
+                        "Recipe versions may only create or drop views and caches",
+                    ))
+                }
+            }
+        }
+
+        let mut state_copy = self.clone();
+        state_copy.apply_recipe(changelist.clone(), true).await?;
+
+        let version = self.recipe_versions.stage(changelist);
+        info!(%version, "Staged recipe version");
+        Ok(version)
+    }
+
+    /// Apply all the changes in the given staged version of the recipe in a single migration
+    pub(super) async fn activate_recipe_version(
+        &mut self,
+        version: RecipeVersion,
+    ) -> ReadySetResult<()> {
+        let changelist = self.recipe_versions.staged(version)?.clone();
+        let dialect = changelist.dialect;
+
+        let before = self.recipe.view_and_cache_expressions();
+        self.apply_recipe(changelist, false).await?;
+        let after = self.recipe.view_and_cache_expressions();
+
+        self.recipe_versions
+            .record_activation(version, dialect, &before, &after);
+        info!(%version, "Activated recipe version");
+        Ok(())
+    }
+
+    /// Roll the recipe back to the given version in a single migration, removing the views and
+    /// caches added by all versions activated since then and restoring the views and caches they
+    /// removed
+    pub(super) async fn rollback_recipe_to(
+        &mut self,
+        version: RecipeVersion,
+    ) -> ReadySetResult<()> {
+        let changelist = self.recipe_versions.rollback_changelist(version)?;
+        if !changelist.changes.is_empty() {
+            self.apply_recipe(changelist, false).await?;
+        }
+
+        self.recipe_versions.record_rollback(version);
+        info!(%version, "Rolled back recipe");
+        Ok(())
+    }
+
+    pub(super) fn recipe_versions(&self) -> RecipeVersionsInfo {
+        self.recipe_versions.info()
+    }
+
+    /// Returns the log of all the cached queries currently installed in the recipe, in the order
+    /// they were installed
+    pub(super) fn list_queries(&self) -> Vec<QueryLogEntry> {
+        self.recipe.sql_inc().query_log()
+    }
+
+    /// Returns where each output column of the view or cached query with the given name is derived
+    /// from
+    pub(super) fn column_provenance(
+        &self,
+        view: &Relation,
+    ) -> ReadySetResult<Vec<ColumnProvenance>> {
+        let name = self.recipe.resolve_alias(view).unwrap_or(view);
+        self.recipe
+            .sql_inc()
+            .column_provenance(name)
+            .map(|columns| columns.to_vec())
+            .ok_or_else(|| ReadySetError::ViewNotFound(view.display_unquoted().to_string()))
+    }
+
+    /// Returns the names of all the views and cached queries which reference the given base table
+    /// column
+    pub(super) fn column_dependents(&self, column: &Column) -> Vec<Relation> {
+        self.recipe.sql_inc().queries_referencing_column(column)
+    }
+
     /// Return 1 if one or more expressions were removed, else return 0.
     /// Someday we may want to return # expressions (and aliases?) dropped.
     pub(super) async fn remove_query(&mut self, query_name: &Relation) -> ReadySetResult<u64> {
@@ -1624,7 +2855,87 @@ impl DfState {
         Ok(1)
     }
 
+    /// Deactivate the cache with the given name, removing its dataflow nodes but keeping its
+    /// definition so that it can be re-created via [`Self::reactivate_view`]. `hit_rate` is the
+    /// percentage of reads against the cache that hit before it was deactivated, if it was
+    /// deactivated automatically.
+    pub(super) async fn deactivate_view(
+        &mut self,
+        view: &Relation,
+        hit_rate: Option<u8>,
+    ) -> ReadySetResult<()> {
+        let view_not_found = || ReadySetError::ViewNotFound(view.display_unquoted().to_string());
+        let name = self
+            .recipe
+            .resolve_alias(view)
+            .ok_or_else(view_not_found)?
+            .clone();
+        let expr = match self.recipe.expression_by_alias(&name) {
+            Some(expr @ RecipeExpr::Cache { .. }) => expr,
+            Some(_) => {
+                return Err(bad_request_err(format!(
+                    "{} is not a cache, and can't be deactivated",
+                    view.display_unquoted()
+                )))
+            }
+            None => return Err(view_not_found()),
+        };
+
+        self.apply_recipe(
+            ChangeList::from_change(
+                Change::Drop {
+                    name,
+                    if_exists: false,
+                },
+                Dialect::DEFAULT_MYSQL,
+            ),
+            false,
+        )
+        .await?;
+        self.deactivated_views.insert(expr, hit_rate);
+        Ok(())
+    }
+
+    /// Re-create the deactivated cache with the given name from its definition, migrating it with
+    /// the given dialect
+    pub(super) async fn reactivate_view(
+        &mut self,
+        view: &Relation,
+        dialect: Dialect,
+    ) -> ReadySetResult<()> {
+        let Some(expr) = self.deactivated_views.get(view) else {
+            return Err(bad_request_err(format!(
+                "View {} is not deactivated",
+                view.display_unquoted()
+            )));
+        };
+        // The cache may have been re-created some other way since it was deactivated
+        if self.recipe.resolve_alias(view).is_none() {
+            let RecipeExpr::Cache {
+                name,
+                statement,
+                always,
+                ..
+            } = expr.clone()
+            else {
+                internal!(
+                    "Deactivated view {} is not a cache",
+                    view.display_unquoted()
+                );
+            };
+            self.apply_recipe(
+                ChangeList::from_change(Change::create_cache(name, statement, always), dialect),
+                false,
+            )
+            .await?;
+        }
+        self.deactivated_views.remove(view);
+        info!(view = %view.display_unquoted(), "Reactivated view");
+        Ok(())
+    }
+
     pub(super) async fn remove_all_queries(&mut self) -> ReadySetResult<()> {
+        self.deactivated_views.clear();
         let changes = self
             .recipe
             .cache_names()
@@ -0,0 +1,276 @@
+//! Automatic deactivation of entire low-value views.
+//!
+//! Key-level eviction frees memory one key at a time, but a cache which is rarely read, or whose
+//! reads mostly miss anyway, keeps paying for the state of all of its nodes. When enabled via
+//! [`ViewEvictionConfig`], the leader periodically checks the total size of the (non-base-table)
+//! materialized state in the graph, and while that exceeds the configured target, *deactivates*
+//! the cache with the lowest hit rate among those whose hit rate over the last interval fell below
+//! the configured threshold - one cache per interval, so that the effect of each deactivation on
+//! memory usage is measured before deciding on the next.
+//!
+//! Deactivating a cache removes its dataflow nodes, but keeps its definition in the
+//! [`DeactivatedViews`] of the dataflow state, so that it can later be cheaply re-created via
+//! [`DfState::reactivate_view`](super::state::DfState::reactivate_view) without the client having
+//! to know the original query.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, SystemTime};
+
+use metrics::counter;
+use nom_sql::Relation;
+use readyset_client::consensus::Authority;
+use readyset_client::debug::info::ReaderMetrics;
+use readyset_client::metrics::recorded;
+use readyset_client::recipe::DeactivatedView;
+use readyset_errors::ReadySetResult;
+use readyset_util::shutdown::ShutdownReceiver;
+use serde::{Deserialize, Serialize};
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{debug, info, warn};
+
+use crate::controller::sql::RecipeExpr;
+use crate::controller::state::DfStateHandle;
+
+/// Configuration for the automatic deactivation of caches with a low hit rate while memory is
+/// constrained
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewEvictionConfig {
+    /// Interval on which to check memory usage and the hit rates of caches
+    pub interval: Duration,
+    /// The total size, in bytes, of the materialized state of all the non-base-table nodes in the
+    /// graph above which caches may be deactivated
+    pub memory_target: u64,
+    /// Caches for which fewer than this percentage of reads hit during the last interval are
+    /// candidates for deactivation
+    pub min_hit_rate: u8,
+}
+
+/// A cache which has been deactivated, along with the definition it can be re-created from
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct DeactivatedEntry {
+    expr: RecipeExpr,
+    deactivated_at: SystemTime,
+    hit_rate: Option<u8>,
+}
+
+/// The caches which have been deactivated and not yet reactivated, keyed by name
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(super) struct DeactivatedViews(HashMap<Relation, DeactivatedEntry>);
+
+impl DeactivatedViews {
+    /// Record that the cache defined by `expr` was deactivated, after the given percentage of the
+    /// reads against it hit (if it was deactivated automatically)
+    pub(super) fn insert(&mut self, expr: RecipeExpr, hit_rate: Option<u8>) {
+        self.0.insert(
+            expr.name().clone(),
+            DeactivatedEntry {
+                expr,
+                deactivated_at: SystemTime::now(),
+                hit_rate,
+            },
+        );
+    }
+
+    /// Returns the definition of the deactivated cache with the given name, if any
+    pub(super) fn get(&self, name: &Relation) -> Option<&RecipeExpr> {
+        self.0.get(name).map(|entry| &entry.expr)
+    }
+
+    /// Forget the deactivated cache with the given name, returning whether it was deactivated
+    pub(super) fn remove(&mut self, name: &Relation) -> bool {
+        self.0.remove(name).is_some()
+    }
+
+    /// Forget all deactivated caches
+    pub(super) fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Describe all the deactivated caches, most recently deactivated first
+    pub(super) fn info(&self) -> Vec<DeactivatedView> {
+        let mut views = self
+            .0
+            .iter()
+            .map(|(name, entry)| DeactivatedView {
+                name: name.clone(),
+                deactivated_at: entry.deactivated_at,
+                hit_rate: entry.hit_rate,
+            })
+            .collect::<Vec<_>>();
+        views.sort_by(|v1, v2| {
+            v2.deactivated_at
+                .cmp(&v1.deactivated_at)
+                .then_with(|| v1.name.cmp(&v2.name))
+        });
+        views
+    }
+}
+
+/// Returns the number of reads and the percentage of them which hit between the two snapshots of a
+/// reader's counters. If the counters went backwards, the reader must have been re-created, so
+/// all of its reads happened since `prev`.
+fn reads_and_hit_rate(prev: &ReaderMetrics, current: &ReaderMetrics) -> (u64, u8) {
+    let (hits, misses) = if current.hits < prev.hits || current.misses < prev.misses {
+        (current.hits, current.misses)
+    } else {
+        (current.hits - prev.hits, current.misses - prev.misses)
+    };
+    let reads = hits + misses;
+    let hit_rate = if reads == 0 {
+        0
+    } else {
+        (hits * 100 / reads) as u8
+    };
+    (reads, hit_rate)
+}
+
+/// Periodically deactivates the cache with the lowest hit rate while the materialized state in
+/// the graph exceeds the configured memory target.
+pub(super) struct ViewEvictor {
+    config: ViewEvictionConfig,
+    /// The dataflow state of the leader that spawned the evictor. Held weakly so that the evictor
+    /// stops once that leader is dropped, for example after losing leadership.
+    dataflow_state_handle: Weak<DfStateHandle>,
+    authority: Arc<Authority>,
+    /// The counters of the reader of each cache as of the previous check, used to compute hit
+    /// rates over only the last interval. Caches which don't have an entry here yet were created
+    /// since the previous check, and aren't considered for deactivation until the next one.
+    last_metrics: HashMap<Relation, ReaderMetrics>,
+}
+
+impl ViewEvictor {
+    pub(super) fn new(
+        config: ViewEvictionConfig,
+        dataflow_state_handle: Weak<DfStateHandle>,
+        authority: Arc<Authority>,
+    ) -> Self {
+        Self {
+            config,
+            dataflow_state_handle,
+            authority,
+            last_metrics: HashMap::new(),
+        }
+    }
+
+    /// Run the evictor until the leader that spawned it is dropped, or a shutdown signal is
+    /// received.
+    pub(super) async fn run(mut self, mut shutdown_rx: ShutdownReceiver) {
+        let mut interval = interval(self.config.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown_rx.recv() => break,
+            }
+
+            let Some(dataflow_state_handle) = self.dataflow_state_handle.upgrade() else {
+                break;
+            };
+            if let Err(error) = self.check(&dataflow_state_handle).await {
+                warn!(%error, "Failed to check for views to deactivate");
+            }
+        }
+
+        debug!("View evictor exiting");
+    }
+
+    /// Check memory usage and the hit rates of all caches, and deactivate the cache with the
+    /// lowest hit rate if memory usage exceeds the target and any cache's hit rate is below the
+    /// threshold
+    async fn check(&mut self, dataflow_state_handle: &DfStateHandle) -> ReadySetResult<()> {
+        let (memory_used, metrics) = {
+            let ds = dataflow_state_handle.read().await;
+            (
+                ds.materialized_view_bytes().await?,
+                ds.cache_metrics().await?,
+            )
+        };
+
+        let mut candidate: Option<(Relation, u8, u64)> = None;
+        for (view, current) in &metrics {
+            let Some(prev) = self.last_metrics.get(view) else {
+                continue;
+            };
+            let (reads, hit_rate) = reads_and_hit_rate(prev, current);
+            if hit_rate >= self.config.min_hit_rate {
+                continue;
+            }
+            // Prefer the cache with the lowest hit rate, and the fewest reads among those
+            if candidate.as_ref().map_or(true, |(_, min_rate, min_reads)| {
+                (hit_rate, reads) < (*min_rate, *min_reads)
+            }) {
+                candidate = Some((view.clone(), hit_rate, reads));
+            }
+        }
+        self.last_metrics = metrics;
+
+        if memory_used <= self.config.memory_target {
+            return Ok(());
+        }
+        let Some((view, hit_rate, reads)) = candidate else {
+            debug!(
+                memory_used,
+                memory_target = self.config.memory_target,
+                "Memory usage exceeds target, but no views have a low enough hit rate to deactivate"
+            );
+            return Ok(());
+        };
+
+        let mut writer = dataflow_state_handle.write().await;
+        writer
+            .as_mut()
+            .deactivate_view(&view, Some(hit_rate))
+            .await?;
+        dataflow_state_handle
+            .commit(writer, &self.authority)
+            .await?;
+        self.last_metrics.remove(&view);
+
+        info!(
+            view = %view.display_unquoted(),
+            hit_rate,
+            reads,
+            memory_used,
+            memory_target = self.config.memory_target,
+            "Deactivated view with low hit rate to reduce memory usage"
+        );
+        counter!(recorded::CONTROLLER_VIEWS_DEACTIVATED, 1);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(hits: u64, misses: u64) -> ReaderMetrics {
+        ReaderMetrics {
+            hits,
+            misses,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn hit_rate_since_previous_check() {
+        assert_eq!(
+            reads_and_hit_rate(&metrics(100, 0), &metrics(101, 3)),
+            (4, 25)
+        );
+    }
+
+    #[test]
+    fn hit_rate_without_reads() {
+        assert_eq!(reads_and_hit_rate(&metrics(5, 5), &metrics(5, 5)), (0, 0));
+    }
+
+    #[test]
+    fn hit_rate_after_reader_recreated() {
+        assert_eq!(
+            reads_and_hit_rate(&metrics(100, 10), &metrics(1, 1)),
+            (2, 50)
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! Failure detection for workers, driven by heartbeats sent from the leader.
+//!
+//! Workers are primarily considered failed when their session with the authority expires, but a
+//! worker can keep its authority session alive while being unable to respond to requests from the
+//! leader (for example, if it's partitioned from the leader but not from the authority). To catch
+//! that case the leader also periodically pings every worker it knows about, and reports any
+//! worker which misses enough consecutive heartbeats to cover the configured failure timeout to the
+//! controller, which handles it the same way as a worker whose authority session expired - by
+//! removing the worker and rescheduling the domains that were running on it onto healthy workers.
+
+use std::collections::HashMap;
+use std::sync::Weak;
+use std::time::Duration;
+
+use futures::future::join_all;
+use readyset_util::shutdown::ShutdownReceiver;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::time::{interval, timeout, MissedTickBehavior};
+use tracing::{debug, warn};
+
+use crate::controller::state::DfStateHandle;
+use crate::controller::{Worker, WorkerIdentifier};
+use crate::worker::WorkerRequestKind;
+
+/// Configuration for the [`WorkerMonitor`]
+#[derive(Debug, Clone, Copy)]
+pub(super) struct WorkerMonitorConfig {
+    /// Interval on which to send a heartbeat to each worker
+    pub(super) heartbeat_interval: Duration,
+    /// Amount of time a worker can go without responding to heartbeats before it's considered
+    /// failed
+    pub(super) failure_timeout: Duration,
+}
+
+impl WorkerMonitorConfig {
+    /// The number of consecutive heartbeats a worker has to miss before it's considered failed.
+    ///
+    /// Counting missed heartbeats rather than measuring wall-clock time since the last successful
+    /// heartbeat means we never consider a worker failed just because the monitor itself was
+    /// unable to run (for example, while a migration holds the lock on the dataflow state).
+    fn max_missed_heartbeats(&self) -> u32 {
+        let interval = self.heartbeat_interval.as_millis().max(1);
+        (self.failure_timeout.as_millis().div_ceil(interval) as u32).max(1)
+    }
+}
+
+/// Sends heartbeats to all workers known to the leader, and reports workers which stop responding
+/// over a channel to the controller.
+pub(super) struct WorkerMonitor {
+    config: WorkerMonitorConfig,
+    /// The dataflow state of the leader that spawned the monitor. Held weakly so that the monitor
+    /// stops once that leader is dropped, for example after losing leadership.
+    dataflow_state_handle: Weak<DfStateHandle>,
+    failed_workers_tx: UnboundedSender<Vec<WorkerIdentifier>>,
+    /// Number of consecutive heartbeats each worker has missed
+    missed_heartbeats: HashMap<WorkerIdentifier, u32>,
+}
+
+impl WorkerMonitor {
+    pub(super) fn new(
+        config: WorkerMonitorConfig,
+        dataflow_state_handle: Weak<DfStateHandle>,
+        failed_workers_tx: UnboundedSender<Vec<WorkerIdentifier>>,
+    ) -> Self {
+        Self {
+            config,
+            dataflow_state_handle,
+            failed_workers_tx,
+            missed_heartbeats: Default::default(),
+        }
+    }
+
+    /// Run the monitor until the leader that spawned it is dropped, the controller stops listening
+    /// for failed workers, or a shutdown signal is received.
+    pub(super) async fn run(mut self, mut shutdown_rx: ShutdownReceiver) {
+        let mut interval = interval(self.config.heartbeat_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown_rx.recv() => break,
+            }
+
+            let Some(dataflow_state_handle) = self.dataflow_state_handle.upgrade() else {
+                break;
+            };
+            let workers = dataflow_state_handle.read().await.workers.clone();
+            drop(dataflow_state_handle);
+
+            let failed = self.check_workers(workers).await;
+            if !failed.is_empty() && self.failed_workers_tx.send(failed).is_err() {
+                break;
+            }
+        }
+
+        debug!("Worker monitor exiting");
+    }
+
+    /// Send a heartbeat to each of the given workers, returning the workers which have now missed
+    /// enough heartbeats to be considered failed.
+    async fn check_workers(
+        &mut self,
+        workers: HashMap<WorkerIdentifier, Worker>,
+    ) -> Vec<WorkerIdentifier> {
+        // Forget about workers which have already been removed
+        self.missed_heartbeats
+            .retain(|wi, _| workers.contains_key(wi));
+
+        let heartbeat_interval = self.config.heartbeat_interval;
+        let results = join_all(workers.into_iter().map(|(wi, worker)| async move {
+            let res = timeout(
+                heartbeat_interval,
+                worker.rpc::<()>(WorkerRequestKind::Ping),
+            )
+            .await;
+            (wi, res)
+        }))
+        .await;
+
+        let max_missed_heartbeats = self.config.max_missed_heartbeats();
+        let mut failed = vec![];
+        for (wi, res) in results {
+            let error = match res {
+                Ok(Ok(())) => {
+                    if self.missed_heartbeats.remove(&wi).is_some() {
+                        debug!(worker = %wi, "Worker is responding to heartbeats again");
+                    }
+                    continue;
+                }
+                Ok(Err(error)) => error.to_string(),
+                Err(_) => "heartbeat timed out".to_owned(),
+            };
+
+            let missed = self.missed_heartbeats.entry(wi.clone()).or_default();
+            *missed += 1;
+            warn!(worker = %wi, %error, missed = *missed, "Worker missed heartbeat");
+            if *missed >= max_missed_heartbeats {
+                failed.push(wi);
+            }
+        }
+
+        for wi in &failed {
+            warn!(
+                worker = %wi,
+                failure_timeout_seconds = self.config.failure_timeout.as_secs(),
+                "Worker has not responded to heartbeats within the failure timeout, \
+                 considering it failed"
+            );
+            self.missed_heartbeats.remove(wi);
+        }
+
+        failed
+    }
+}
@@ -0,0 +1,284 @@
+//! Liveness and readiness checks for the `/health/live` and `/health/ready` endpoints of the
+//! server's HTTP router, intended for use by orchestrators and load balancers.
+//!
+//! Both endpoints respond with a JSON [`HealthReport`], with a status code of 200 if all checks
+//! passed or 503 otherwise. The readiness checks are:
+//!
+//! * `server`: the server has finished starting up, and isn't shutting down
+//! * `authority`: the authority can be reached, and has a leader
+//! * `controller`: the worker has been registered with a controller, and the leader can be reached
+//!   and has finished snapshotting
+//! * `replication`: replication from the upstream database hasn't been stopped for longer than
+//!   [`Config::max_ready_replication_lag`](crate::Config::max_ready_replication_lag)
+//! * `domains`: none of the domain replicas assigned to this worker have exited
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use health_reporter::{HealthReporter, State};
+use hyper::header::CONTENT_TYPE;
+use hyper::http::response::Builder;
+use hyper::{Body, Response, StatusCode};
+use readyset_client::consensus::{Authority, AuthorityControl};
+use readyset_client::ReadySetHandle;
+use readyset_errors::{internal_err, ReadySetResult};
+use serde::Serialize;
+use tokio::sync::mpsc::Sender;
+use tokio::time::timeout;
+
+use crate::worker::{WorkerHealth, WorkerRequest, WorkerRequestKind};
+
+/// How long to wait for each dependency to respond to a health check
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The outcome of a health check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum CheckStatus {
+    Pass,
+    Fail,
+}
+
+/// The result of a single health check
+#[derive(Debug, Serialize)]
+pub(crate) struct CheckResult {
+    status: CheckStatus,
+    /// A human-readable description of the result
+    message: String,
+}
+
+impl CheckResult {
+    fn pass(message: impl Into<String>) -> Self {
+        Self {
+            status: CheckStatus::Pass,
+            message: message.into(),
+        }
+    }
+
+    fn fail(message: impl Into<String>) -> Self {
+        Self {
+            status: CheckStatus::Fail,
+            message: message.into(),
+        }
+    }
+}
+
+/// The results of a set of health checks, serialized as the body of the health check endpoints
+#[derive(Debug, Serialize)]
+pub(crate) struct HealthReport {
+    /// [`CheckStatus::Pass`] if all checks passed
+    status: CheckStatus,
+    checks: BTreeMap<&'static str, CheckResult>,
+}
+
+impl HealthReport {
+    fn new(checks: BTreeMap<&'static str, CheckResult>) -> Self {
+        let status = if checks.values().all(|c| c.status == CheckStatus::Pass) {
+            CheckStatus::Pass
+        } else {
+            CheckStatus::Fail
+        };
+        Self { status, checks }
+    }
+
+    /// Build a JSON response out of this report
+    #[allow(clippy::unwrap_used)] // serializing a map of strings and building with valid headers
+    pub(crate) fn into_response(self, res: Builder) -> Response<Body> {
+        let status = match self.status {
+            CheckStatus::Pass => StatusCode::OK,
+            CheckStatus::Fail => StatusCode::SERVICE_UNAVAILABLE,
+        };
+        let body = serde_json::to_vec(&self).unwrap();
+        res.status(status)
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    }
+}
+
+/// Request the health of the worker running in this server
+async fn worker_health(worker_tx: &Sender<WorkerRequest>) -> ReadySetResult<WorkerHealth> {
+    let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+    worker_tx
+        .send(WorkerRequest {
+            kind: WorkerRequestKind::HealthCheck,
+            done_tx,
+        })
+        .await
+        .map_err(|_| internal_err!("worker went away"))?;
+    let res = timeout(HEALTH_CHECK_TIMEOUT, done_rx)
+        .await
+        .map_err(|_| internal_err!("worker did not respond within {HEALTH_CHECK_TIMEOUT:?}"))?
+        .map_err(|_| internal_err!("worker went away"))??
+        .ok_or_else(|| internal_err!("worker returned an empty health check response"))?;
+    Ok(bincode::deserialize(&res)?)
+}
+
+fn check_server(health_reporter: &HealthReporter) -> CheckResult {
+    match health_reporter.state() {
+        State::Healthy => CheckResult::pass("server is healthy"),
+        state => CheckResult::fail(format!("server is in {state} state")),
+    }
+}
+
+/// Check that the worker running in this server is responsive, for the `/health/live` endpoint
+pub(crate) async fn liveness(
+    health_reporter: &HealthReporter,
+    worker_tx: &Sender<WorkerRequest>,
+) -> HealthReport {
+    let mut checks = BTreeMap::new();
+    // The server is still alive while it's starting up or shutting down
+    checks.insert(
+        "server",
+        match health_reporter.state() {
+            State::Unknown => CheckResult::fail("server is in unknown state"),
+            state => CheckResult::pass(format!("server is in {state} state")),
+        },
+    );
+    checks.insert(
+        "worker",
+        match worker_health(worker_tx).await {
+            Ok(_) => CheckResult::pass("worker is responsive"),
+            Err(error) => CheckResult::fail(error.to_string()),
+        },
+    );
+    HealthReport::new(checks)
+}
+
+/// Check that this server and everything it depends on is able to serve requests, for the
+/// `/health/ready` endpoint
+pub(crate) async fn readiness(
+    health_reporter: &HealthReporter,
+    worker_tx: &Sender<WorkerRequest>,
+    authority: &Arc<Authority>,
+    max_replication_lag: Duration,
+) -> HealthReport {
+    let leader = async {
+        timeout(HEALTH_CHECK_TIMEOUT, authority.get_leader())
+            .await
+            .unwrap_or_else(|_| {
+                Err(internal_err!(
+                    "no leader was found within {HEALTH_CHECK_TIMEOUT:?}"
+                ))
+            })
+    };
+    let (leader, worker) = tokio::join!(leader, worker_health(worker_tx));
+
+    let mut checks = BTreeMap::new();
+    checks.insert("server", check_server(health_reporter));
+
+    let leader_ready = match &leader {
+        Ok(leader) => {
+            checks.insert(
+                "authority",
+                CheckResult::pass(format!("leader is at {}", leader.controller_uri)),
+            );
+            let mut handle = ReadySetHandle::make_raw(
+                leader.controller_uri.clone(),
+                Some(HEALTH_CHECK_TIMEOUT),
+                None,
+            );
+            let leader_ready = async {
+                handle.ready().await?;
+                handle.leader_ready().await
+            };
+            Some(leader_ready.await)
+        }
+        Err(error) => {
+            checks.insert("authority", CheckResult::fail(error.to_string()));
+            None
+        }
+    };
+
+    let worker = match worker {
+        Ok(worker) => worker,
+        Err(error) => {
+            for check in ["controller", "replication", "domains"] {
+                checks.insert(check, CheckResult::fail(error.to_string()));
+            }
+            return HealthReport::new(checks);
+        }
+    };
+
+    checks.insert(
+        "controller",
+        match (&worker.controller_uri, leader_ready) {
+            (None, _) => CheckResult::fail("worker has not been registered with a controller"),
+            (Some(_), None) => CheckResult::fail("leader could not be found in the authority"),
+            (Some(_), Some(Ok(true))) => CheckResult::pass("leader is ready"),
+            (Some(_), Some(Ok(false))) => CheckResult::fail("leader has not finished snapshotting"),
+            (Some(_), Some(Err(error))) => {
+                CheckResult::fail(format!("leader could not be reached: {error}"))
+            }
+        },
+    );
+
+    checks.insert(
+        "replication",
+        match worker.replication_unhealthy_for {
+            None => CheckResult::pass("replication is healthy"),
+            Some(lag) if lag <= max_replication_lag => CheckResult::pass(format!(
+                "replication has been stopped for {}s",
+                lag.as_secs()
+            )),
+            Some(lag) => CheckResult::fail(format!(
+                "replication has been stopped for {}s, longer than the maximum of {}s",
+                lag.as_secs(),
+                max_replication_lag.as_secs()
+            )),
+        },
+    );
+
+    checks.insert(
+        "domains",
+        if worker.failed_domains.is_empty() {
+            CheckResult::pass(format!("{} domains running", worker.num_domains))
+        } else {
+            CheckResult::fail(format!(
+                "domains failed: {}",
+                worker
+                    .failed_domains
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        },
+    );
+
+    HealthReport::new(checks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_fails_if_any_check_fails() {
+        let report = HealthReport::new(BTreeMap::from([
+            ("server", CheckResult::pass("server is healthy")),
+            ("domains", CheckResult::fail("domains failed: 1.0.0")),
+        ]));
+        assert_eq!(report.status, CheckStatus::Fail);
+        let res = report.into_response(Response::builder());
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn report_serializes_to_json() {
+        let report = HealthReport::new(BTreeMap::from([(
+            "server",
+            CheckResult::pass("server is healthy"),
+        )]));
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "status": "pass",
+                "checks": {
+                    "server": { "status": "pass", "message": "server is healthy" }
+                }
+            })
+        );
+    }
+}
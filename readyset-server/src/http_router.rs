@@ -4,6 +4,7 @@ use std::net::{IpAddr, SocketAddr};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use anyhow::anyhow;
 use futures::TryFutureExt;
@@ -23,6 +24,7 @@ use tower::Service;
 use tracing::warn;
 
 use crate::controller::ControllerRequest;
+use crate::health_check;
 use crate::metrics::{get_global_recorder, Clear, RecorderType};
 use crate::worker::WorkerRequest;
 
@@ -45,6 +47,9 @@ pub struct NoriaServerHttpRouter {
     pub authority: Arc<Authority>,
     /// Used to record and report the servers current health.
     pub health_reporter: HealthReporter,
+    /// Amount of time replication can be stopped for before `/health/ready` reports the server as
+    /// not ready.
+    pub max_ready_replication_lag: Duration,
     /// Used to communicate externally that a failpoint request has been received and successfully
     /// handled.
     /// Most commonly used to block on further startup action if --wait-for-failpoint is supplied.
@@ -172,6 +177,30 @@ impl Service<Request<Body>> for NoriaServerHttpRouter {
                     Ok(res.unwrap())
                 })
             }
+            (&Method::GET, "/health/live") => {
+                let health_reporter = self.health_reporter.clone();
+                let worker_tx = self.worker_tx.clone();
+                Box::pin(async move {
+                    let report = health_check::liveness(&health_reporter, &worker_tx).await;
+                    Ok(report.into_response(res))
+                })
+            }
+            (&Method::GET, "/health/ready") => {
+                let health_reporter = self.health_reporter.clone();
+                let worker_tx = self.worker_tx.clone();
+                let authority = self.authority.clone();
+                let max_ready_replication_lag = self.max_ready_replication_lag;
+                Box::pin(async move {
+                    let report = health_check::readiness(
+                        &health_reporter,
+                        &worker_tx,
+                        &authority,
+                        max_ready_replication_lag,
+                    )
+                    .await;
+                    Ok(report.into_response(res))
+                })
+            }
             (&Method::POST, "/metrics_dump") => {
                 let render = get_global_recorder().and_then(|r| r.render(RecorderType::Noria));
                 let res = match render {
@@ -8,11 +8,12 @@
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 use std::{iter, thread};
 
 use chrono::NaiveDate;
 use common::Index;
+use database_utils::UpstreamConfig;
 use dataflow::node::special::Base;
 use dataflow::ops::grouped::aggregate::Aggregation;
 use dataflow::ops::identity::Identity;
@@ -24,17 +25,25 @@ use dataflow::utils::{dataflow_column, make_columns};
 use dataflow::{
     BinaryOperator, DurabilityMode, Expr as DfExpr, PersistenceParameters, ReaderProcessing,
 };
-use futures::{join, StreamExt};
+use futures::{join, StreamExt, TryStreamExt};
 use itertools::Itertools;
 use nom_sql::{
-    parse_create_table, parse_create_view, parse_query, parse_select_statement, OrderType,
-    Relation, SqlQuery,
+    parse_create_table, parse_create_view, parse_query, parse_select_statement, Column,
+    ColumnConstraint, OrderType, Relation, SqlIdentifier, SqlQuery, SqlType,
 };
 use readyset_client::consensus::{Authority, LocalAuthority, LocalAuthorityStore};
 use readyset_client::consistency::Timestamp;
+use readyset_client::debug::info::KeyCount;
 use readyset_client::internal::LocalNodeIndex;
 use readyset_client::recipe::changelist::{Change, ChangeList, CreateCache};
-use readyset_client::{KeyComparison, Modification, SchemaType, ViewPlaceholder, ViewQuery};
+use readyset_client::recipe::{MigrationStatus, QueryRewrite, QuerySource, RecipeVersion};
+use readyset_client::runtime_config::RuntimeConfigUpdate;
+use readyset_client::schema::RelationSchema;
+use readyset_client::status::ReplicationPauseState;
+use readyset_client::{
+    GraphvizOptions, KeyComparison, Modification, ReadySetHandle, SchemaType, ViewPlaceholder,
+    ViewQuery, ViewRateLimit, ViewResultLimit, ViewScanBatch, ViewStaleReads,
+};
 use readyset_data::{Bound, DfType, DfValue, Dialect, IntoBoundedRange};
 use readyset_errors::ReadySetError::{self, RpcFailed, SelectQueryCreationFailed};
 use readyset_util::eventually;
@@ -50,7 +59,8 @@ use vec1::vec1;
 
 use crate::controller::sql::SqlIncorporator;
 use crate::integration_utils::*;
-use crate::{get_col, Builder};
+use crate::manual::MigrationObserver;
+use crate::{get_col, Builder, FrontierStrategy, ViewEvictionConfig};
 
 #[tokio::test(flavor = "multi_thread")]
 async fn it_completes() {
@@ -289,6 +299,83 @@ async fn test_timestamp_propagation_multitable() {
     shutdown_tx.shutdown().await;
 }
 
+// Tests that reads against several views as of the same timestamp observe the same writes, even
+// if the views have since seen more writes.
+#[tokio::test(flavor = "multi_thread")]
+async fn consistent_read() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("consistent_read").await;
+
+    let a = g
+        .migrate(|mig| {
+            let a = mig.add_base(
+                "a",
+                make_columns(&["a", "b"]),
+                Base::new().with_primary_key([0]),
+            );
+
+            for (name, key) in [("by_b", 1), ("by_a", 0)] {
+                let mut emits = HashMap::new();
+                emits.insert(a, vec![0, 1]);
+                let u = Union::new(emits, union::DuplicateMode::UnionAll).unwrap();
+                let n = mig.add_ingredient(name, make_columns(&["a", "b"]), u);
+                mig.maintain_anonymous(n, &Index::hash_map(vec![key]));
+            }
+            a
+        })
+        .await;
+
+    for view in ["by_b", "by_a"] {
+        g.set_view_history_retention(&view.into(), Some(Duration::from_secs(60)))
+            .await
+            .unwrap();
+    }
+    let mut muta = g.table_by_index(a).await.unwrap();
+
+    muta.insert(vec![1.into(), 10.into()]).await.unwrap();
+    let t1 = timestamp(vec![(0, 1)]);
+    muta.update_timestamp(t1.clone()).await.unwrap();
+    muta.insert(vec![2.into(), 10.into()]).await.unwrap();
+    let t2 = timestamp(vec![(0, 2)]);
+    muta.update_timestamp(t2.clone()).await.unwrap();
+
+    let reads: Vec<(Relation, Vec<DfValue>)> = vec![
+        ("by_b".into(), vec![DfValue::from(10)]),
+        ("by_a".into(), vec![DfValue::from(2)]),
+    ];
+    async fn read(
+        g: &mut ReadySetHandle,
+        reads: &[(Relation, Vec<DfValue>)],
+        ticket: Option<Timestamp>,
+    ) -> Vec<Vec<Vec<DfValue>>> {
+        g.consistent_read(reads.to_vec(), ticket)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|res| {
+                let mut rows = res.into_vec();
+                rows.sort();
+                rows
+            })
+            .collect()
+    }
+
+    assert_eq!(
+        read(&mut g, &reads, Some(t1)).await,
+        vec![vec![vec![DfValue::from(1), DfValue::from(10)]], vec![]]
+    );
+    let latest = vec![
+        vec![
+            vec![DfValue::from(1), DfValue::from(10)],
+            vec![DfValue::from(2), DfValue::from(10)],
+        ],
+        vec![vec![DfValue::from(2), DfValue::from(10)]],
+    ];
+    assert_eq!(read(&mut g, &reads, Some(t2)).await, latest);
+    assert_eq!(read(&mut g, &reads, None).await, latest);
+
+    shutdown_tx.shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 #[ignore = "Ignoring sharded tests"]
 async fn sharded_shuffle() {
@@ -920,6 +1007,58 @@ async fn it_works_with_sql_recipe() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn create_table_as_select() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("create_table_as_select").await;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t1 (id int NOT NULL, x text, PRIMARY KEY(id));
+             CREATE TABLE t2 (y int) AS SELECT t1.*, t1.x AS z, y FROM t1 JOIN t3 ON t1.id = t3.id;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap_err();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t1 (id int NOT NULL, x text, PRIMARY KEY(id));
+             CREATE TABLE t2 (w bigint) AS SELECT t1.*, t1.x AS z, id AS w FROM t1;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let t2 = g.table("t2").await.unwrap();
+    assert_eq!(t2.columns(), &["w", "id", "x", "z"]);
+    let schema = t2.schema().unwrap();
+    assert_eq!(schema.fields[0].sql_type, SqlType::BigInt(None));
+    assert_eq!(schema.fields[1].sql_type, SqlType::Int(None));
+    assert_eq!(
+        schema.fields[1].constraints,
+        vec![ColumnConstraint::NotNull]
+    );
+    assert_eq!(schema.fields[3].sql_type, SqlType::Text);
+    // Keys aren't copied from the selected tables
+    assert!(schema.keys.is_none());
+
+    // Selecting anything other than columns isn't supported
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t3 AS SELECT id + 1 FROM t1;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn it_works_with_vote() {
     let (mut g, shutdown_tx) = start_simple_unsharded("it_works_with_vote").await;
@@ -1517,6 +1656,40 @@ async fn it_doesnt_recover_persisted_bases_with_wrong_volume_id() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn base_tables_only_scheduled_onto_workers_matching_selector() {
+    let sql = "CREATE TABLE Car (id int, price int, PRIMARY KEY(id));";
+
+    let mut g = Builder::for_tests();
+    g.set_zone("us-east-1a".into());
+    g.set_base_table_worker_selector("topology.kubernetes.io/zone=us-east-1a".parse().unwrap());
+    let (mut g, shutdown_tx) = g
+        .start(Arc::new(Authority::from(LocalAuthority::new())))
+        .await
+        .unwrap();
+    g.backend_ready().await;
+    g.extend_recipe(ChangeList::from_str(sql, Dialect::DEFAULT_MYSQL).unwrap())
+        .await
+        .unwrap();
+    g.table("Car").await.unwrap();
+    shutdown_tx.shutdown().await;
+
+    let mut g = Builder::for_tests();
+    g.set_zone("us-east-1b".into());
+    g.set_base_table_worker_selector("topology.kubernetes.io/zone=us-east-1a".parse().unwrap());
+    let (mut g, shutdown_tx) = g
+        .start(Arc::new(Authority::from(LocalAuthority::new())))
+        .await
+        .unwrap();
+    g.backend_ready().await;
+    g.extend_recipe(ChangeList::from_str(sql, Dialect::DEFAULT_MYSQL).unwrap())
+        .await
+        .unwrap();
+    // There is no worker in the selected zone to place the base table's domain on
+    g.table("Car").await.unwrap_err();
+    shutdown_tx.shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn mutator_churn() {
     let (mut g, shutdown_tx) = start_simple_unsharded("mutator_churn").await;
@@ -2317,6 +2490,95 @@ async fn add_columns() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn migration_observer() {
+    use std::sync::Mutex;
+
+    use petgraph::graph::NodeIndex;
+    use readyset_client::internal::DomainIndex;
+    use readyset_errors::ReadySetResult;
+
+    #[derive(Default)]
+    struct RecordingObserver {
+        domains_created: Mutex<Vec<DomainIndex>>,
+        result: Mutex<Option<ReadySetResult<()>>>,
+    }
+
+    impl MigrationObserver for RecordingObserver {
+        fn domain_created(&self, domain: DomainIndex, nodes: &[NodeIndex]) -> ReadySetResult<()> {
+            assert!(!nodes.is_empty());
+            self.domains_created.lock().unwrap().push(domain);
+            Ok(())
+        }
+
+        fn migration_finished(&self, result: &ReadySetResult<()>, _elapsed: Duration) {
+            *self.result.lock().unwrap() = Some(result.clone());
+        }
+    }
+
+    let (mut g, shutdown_tx) = start_simple_unsharded("migration_observer").await;
+    let observer = Arc::new(RecordingObserver::default());
+
+    let mig_observer = Arc::clone(&observer);
+    g.migrate(move |mig| {
+        mig.set_observer(mig_observer);
+        let a = mig.add_base("a", make_columns(&["a", "b"]), Base::default());
+        mig.maintain_anonymous(a, &Index::hash_map(vec![0]));
+    })
+    .await;
+
+    // One domain for the base table, and one for its reader
+    assert_eq!(observer.domains_created.lock().unwrap().len(), 2);
+    assert_eq!(*observer.result.lock().unwrap(), Some(Ok(())));
+
+    // Observers only observe the migration they were registered with
+    g.migrate(|mig| {
+        let b = mig.add_base("b", make_columns(&["a", "b"]), Base::default());
+        mig.maintain_anonymous(b, &Index::hash_map(vec![0]));
+    })
+    .await;
+    assert_eq!(observer.domains_created.lock().unwrap().len(), 2);
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn stable_node_ids() {
+    async fn node_ids(prefix: &str, recipe: &str) -> Vec<String> {
+        let (mut g, shutdown_tx) = start_simple_unsharded(prefix).await;
+        g.extend_recipe(ChangeList::from_str(recipe, Dialect::DEFAULT_MYSQL).unwrap())
+            .await
+            .unwrap();
+        let graphviz = g.graphviz(Default::default()).await.unwrap();
+        shutdown_tx.shutdown().await;
+
+        let re = regex::Regex::new("/ ([0-9a-f]{16})").unwrap();
+        re.captures_iter(&graphviz)
+            .map(|c| c[1].to_owned())
+            .collect()
+    }
+
+    let recipe = "CREATE TABLE t (id int, x int);
+         CREATE TABLE u (id int, t_id int);
+         CREATE CACHE q FROM SELECT t.x, u.id FROM t JOIN u ON t.id = u.t_id WHERE t.x = ?;";
+    let ids = node_ids("stable_node_ids_1", recipe).await;
+    assert!(!ids.is_empty());
+    assert!(ids.iter().all_unique());
+
+    // Adding an unrelated table first changes the indices of all the other nodes, but not their
+    // stable identifiers
+    let other_ids = node_ids(
+        "stable_node_ids_2",
+        &format!("CREATE TABLE other (a int);\n{recipe}"),
+    )
+    .await;
+    assert!(other_ids.iter().all_unique());
+    assert_eq!(other_ids.len(), ids.len() + 1);
+    for id in &ids {
+        assert!(other_ids.contains(id), "{id} not in {other_ids:?}");
+    }
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn migrate_added_columns() {
     let id: DfValue = "x".into();
@@ -2936,6 +3198,24 @@ async fn pkey_then_full_table_with_bogokey() {
         rows_with_bogokey
     );
 
+    // Reading the whole view should do the same, without needing to know about the bogokey
+    assert!(all_posts.is_unparameterized());
+    let pages = all_posts
+        .read_all(true, 3)
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(
+        pages.iter().map(Vec::len).collect::<Vec<_>>(),
+        vec![3, 3, 3, 1]
+    );
+    assert_eq!(pages.concat(), rows_with_bogokey);
+
+    // ...but isn't allowed for views that take parameters
+    assert!(!by_id.is_unparameterized());
+    assert!(by_id.read_all(true, 3).is_err());
+
     shutdown_tx.shutdown().await;
 }
 
@@ -4067,6 +4347,65 @@ async fn union_all_basic() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn union_all_mismatched_types() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("union_all_mismatched_types").await;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE small (id INT PRIMARY KEY, name VARCHAR(5));
+         CREATE TABLE big (id BIGINT PRIMARY KEY, name VARCHAR(10));
+         CREATE VIEW small_union_big AS (SELECT id, name FROM small) UNION ALL (SELECT id, name FROM big);
+         CREATE CACHE `query` FROM SELECT id, name FROM small_union_big;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut small = g.table("small").await.unwrap();
+    small.insert(vec![1.into(), "a".into()]).await.unwrap();
+    let mut big = g.table("big").await.unwrap();
+    big.insert(vec![(i64::MAX).into(), "bbbbbbbbbb".into()])
+        .await
+        .unwrap();
+
+    sleep().await;
+
+    let mut query = g.view("query").await.unwrap().into_reader_handle().unwrap();
+    let mut rows = query
+        .lookup(&[0.into()], true)
+        .await
+        .unwrap()
+        .into_vec()
+        .iter()
+        .map(|r| {
+            (
+                get_col!(query, r, "id", i64),
+                get_col!(query, r, "name", String),
+            )
+        })
+        .collect::<Vec<_>>();
+    rows.sort();
+    assert_eq!(
+        rows,
+        vec![(1, "a".to_owned()), (i64::MAX, "bbbbbbbbbb".to_owned())]
+    );
+
+    // Columns whose types can't be combined are rejected up front
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE CACHE `bad` FROM (SELECT id FROM small) UNION ALL (SELECT name FROM big);",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn between() {
     let (mut g, shutdown_tx) = start_simple_unsharded("between_query").await;
@@ -4215,12 +4554,18 @@ async fn topk_updates() {
 }
 
 #[tokio::test(flavor = "multi_thread")]
-async fn simple_pagination() {
-    let (mut g, shutdown_tx) = start_simple_unsharded("simple_pagination").await;
+async fn window_function_topk() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("window_function_topk").await;
     g.extend_recipe(
         ChangeList::from_str(
-            "CREATE TABLE t (x, y);
-         CREATE CACHE q FROM SELECT x, y FROM t WHERE y = $1 ORDER BY x ASC LIMIT 3 OFFSET $2;",
+            "CREATE TABLE posts (id INTEGER PRIMARY KEY, author INTEGER, score INTEGER);
+
+         CREATE CACHE top_posts FROM
+         SELECT sq.id, sq.author FROM (
+             SELECT posts.id, posts.author,
+             RANK() OVER (PARTITION BY posts.author ORDER BY posts.score DESC) AS r
+             FROM posts
+         ) sq WHERE sq.r <= 2;",
             Dialect::DEFAULT_MYSQL,
         )
         .unwrap(),
@@ -4228,67 +4573,202 @@ async fn simple_pagination() {
     .await
     .unwrap();
 
-    eprintln!("{}", g.graphviz(Default::default()).await.unwrap());
+    let mut posts = g.table("posts").await.unwrap();
+    let mut top_posts = g
+        .view("top_posts")
+        .await
+        .unwrap()
+        .into_reader_handle()
+        .unwrap();
 
-    let mut t = g.table("t").await.unwrap();
+    posts
+        .insert_many(vec![
+            vec![1.into(), 1.into(), 10.into()],
+            vec![2.into(), 1.into(), 9.into()],
+            vec![3.into(), 1.into(), 9.into()],
+            vec![4.into(), 1.into(), 5.into()],
+            vec![5.into(), 2.into(), 3.into()],
+        ])
+        .await
+        .unwrap();
 
-    t.insert_many(vec![
-        vec![DfValue::from(1), DfValue::from("a")],
-        vec![DfValue::from(2), DfValue::from("a")],
-        vec![DfValue::from(3), DfValue::from("a")],
-        vec![DfValue::from(4), DfValue::from("a")],
-        vec![DfValue::from(5), DfValue::from("a")],
-        vec![DfValue::from(6), DfValue::from("a")],
-        vec![DfValue::from(1), DfValue::from("b")],
-        vec![DfValue::from(2), DfValue::from("b")],
-        vec![DfValue::from(3), DfValue::from("b")],
-    ])
-    .await
-    .unwrap();
+    sleep().await;
 
-    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    let res = top_posts.lookup(&[0.into()], true).await.unwrap();
+    let mut rows: Vec<Vec<DfValue>> = res.into();
+    rows.sort();
+    // Both posts tied for second place are included
     assert_eq!(
-        q.key_map(),
-        &[
-            (
-                ViewPlaceholder::OneToOne(1, nom_sql::BinaryOperator::Equal),
-                1
-            ),
-            (
-                ViewPlaceholder::PageNumber {
-                    offset_placeholder: 2,
-                    limit: 3
-                },
-                2
-            )
+        rows,
+        vec![
+            vec![1.into(), 1.into()],
+            vec![2.into(), 1.into()],
+            vec![3.into(), 1.into()],
+            vec![5.into(), 2.into()],
         ]
     );
 
-    let mut a_page1: Vec<Vec<DfValue>> = q
-        .lookup(&["a".into(), 0.into()], true)
-        .await
-        .unwrap()
-        .into();
-    a_page1.sort();
+    posts.delete(vec![1.into()]).await.unwrap();
+
+    sleep().await;
+
+    let res = top_posts.lookup(&[0.into()], true).await.unwrap();
+    let mut rows: Vec<Vec<DfValue>> = res.into();
+    rows.sort();
+    // Post 4 is now ranked third, after the two posts tied for first place
     assert_eq!(
-        a_page1,
+        rows,
         vec![
-            vec![DfValue::from(1), DfValue::from("a")],
-            vec![DfValue::from(2), DfValue::from("a")],
-            vec![DfValue::from(3), DfValue::from("a")],
+            vec![2.into(), 1.into()],
+            vec![3.into(), 1.into()],
+            vec![5.into(), 2.into()],
         ]
     );
 
-    let mut a_page2: Vec<Vec<DfValue>> = q
-        .lookup(&["a".into(), 1.into()], true)
-        .await
-        .unwrap()
-        .into();
-    a_page2.sort();
-    assert_eq!(
-        a_page2,
-        vec![
-            vec![DfValue::from(4), DfValue::from("a")],
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn latest_row_per_key() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("latest_row_per_key").await;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE readings (id INTEGER PRIMARY KEY, sensor INTEGER, ts INTEGER, value INTEGER);
+
+         CREATE CACHE latest_readings FROM
+         SELECT sq.sensor, sq.value FROM (
+             SELECT readings.sensor, readings.value,
+             ROW_NUMBER() OVER (PARTITION BY readings.sensor ORDER BY readings.ts DESC) AS rn
+             FROM readings
+         ) sq WHERE sq.rn = 1;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let graphviz = g.graphviz(Default::default()).await.unwrap();
+    assert!(graphviz.contains('⧖'), "{graphviz}");
+
+    let mut readings = g.table("readings").await.unwrap();
+    let mut latest_readings = g
+        .view("latest_readings")
+        .await
+        .unwrap()
+        .into_reader_handle()
+        .unwrap();
+
+    readings
+        .insert_many(vec![
+            vec![1.into(), 1.into(), 1.into(), 10.into()],
+            vec![2.into(), 1.into(), 3.into(), 30.into()],
+            vec![3.into(), 1.into(), 2.into(), 20.into()],
+            vec![4.into(), 2.into(), 1.into(), 100.into()],
+        ])
+        .await
+        .unwrap();
+
+    sleep().await;
+
+    let res = latest_readings.lookup(&[0.into()], true).await.unwrap();
+    let mut rows: Vec<Vec<DfValue>> = res.into();
+    rows.sort();
+    assert_eq!(
+        rows,
+        vec![vec![1.into(), 30.into()], vec![2.into(), 100.into()]]
+    );
+
+    readings.delete(vec![2.into()]).await.unwrap();
+
+    sleep().await;
+
+    let res = latest_readings.lookup(&[0.into()], true).await.unwrap();
+    let mut rows: Vec<Vec<DfValue>> = res.into();
+    rows.sort();
+    assert_eq!(
+        rows,
+        vec![vec![1.into(), 20.into()], vec![2.into(), 100.into()]]
+    );
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn simple_pagination() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("simple_pagination").await;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x, y);
+         CREATE CACHE q FROM SELECT x, y FROM t WHERE y = $1 ORDER BY x ASC LIMIT 3 OFFSET $2;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    eprintln!("{}", g.graphviz(Default::default()).await.unwrap());
+
+    let mut t = g.table("t").await.unwrap();
+
+    t.insert_many(vec![
+        vec![DfValue::from(1), DfValue::from("a")],
+        vec![DfValue::from(2), DfValue::from("a")],
+        vec![DfValue::from(3), DfValue::from("a")],
+        vec![DfValue::from(4), DfValue::from("a")],
+        vec![DfValue::from(5), DfValue::from("a")],
+        vec![DfValue::from(6), DfValue::from("a")],
+        vec![DfValue::from(1), DfValue::from("b")],
+        vec![DfValue::from(2), DfValue::from("b")],
+        vec![DfValue::from(3), DfValue::from("b")],
+    ])
+    .await
+    .unwrap();
+
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    assert_eq!(
+        q.key_map(),
+        &[
+            (
+                ViewPlaceholder::OneToOne(1, nom_sql::BinaryOperator::Equal),
+                1
+            ),
+            (
+                ViewPlaceholder::PageNumber {
+                    offset_placeholder: 2,
+                    limit: 3
+                },
+                2
+            )
+        ]
+    );
+
+    let mut a_page1: Vec<Vec<DfValue>> = q
+        .lookup(&["a".into(), 0.into()], true)
+        .await
+        .unwrap()
+        .into();
+    a_page1.sort();
+    assert_eq!(
+        a_page1,
+        vec![
+            vec![DfValue::from(1), DfValue::from("a")],
+            vec![DfValue::from(2), DfValue::from("a")],
+            vec![DfValue::from(3), DfValue::from("a")],
+        ]
+    );
+
+    let mut a_page2: Vec<Vec<DfValue>> = q
+        .lookup(&["a".into(), 1.into()], true)
+        .await
+        .unwrap()
+        .into();
+    a_page2.sort();
+    assert_eq!(
+        a_page2,
+        vec![
+            vec![DfValue::from(4), DfValue::from("a")],
             vec![DfValue::from(5), DfValue::from("a")],
             vec![DfValue::from(6), DfValue::from("a")],
         ]
@@ -4351,6 +4831,50 @@ async fn correct_nested_view_schema() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn full_state_shared_between_shards() {
+    let mut b = Builder::for_tests();
+    b.disable_partial();
+    b.set_sharding(Some(2));
+    b.set_share_full_state_between_shards(true);
+    let (mut g, shutdown_tx) = b.start_local().await.unwrap();
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE votes (story int, user int);
+             CREATE CACHE vc FROM
+             SELECT story, COUNT(user) AS vc FROM votes WHERE story = ? GROUP BY story;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut votes = g.table("votes").await.unwrap();
+    let mut vc = g.view("vc").await.unwrap().into_reader_handle().unwrap();
+    for (story, user) in [(1, 1), (2, 1), (1, 2), (3, 1), (2, 2), (1, 3)] {
+        votes
+            .insert(vec![DfValue::from(story), DfValue::from(user)])
+            .await
+            .unwrap();
+    }
+    sleep().await;
+
+    // Both shards of the aggregate run on the one worker, so they share its state, but each only
+    // sees the groups routed to it
+    for (story, count) in [(1, 3), (2, 2), (3, 1)] {
+        assert_eq!(
+            vc.lookup(&[DfValue::from(story)], true)
+                .await
+                .unwrap()
+                .into_vec(),
+            vec![vec![DfValue::from(story), DfValue::from(count)]]
+        );
+    }
+
+    shutdown_tx.shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn join_column_projection() {
     let (mut g, shutdown_tx) = start_simple_unsharded("join_column_projection").await;
@@ -5246,6 +5770,10 @@ async fn post_read_ilike() {
             timestamp: None,
             limit: None,
             offset: None,
+            as_of: None,
+            result_limit: None,
+            secondary_index: None,
+            upquery_timeout: None,
         })
         .await
         .unwrap()
@@ -5816,117 +6344,419 @@ async fn join_simple_cte() {
     shutdown_tx.shutdown().await;
 }
 
-// multiple_aggregate_sum tests multiple aggregators of the same type, in this case sum(),
-// operating over different columns from the same table.
 #[tokio::test(flavor = "multi_thread")]
-async fn multiple_aggregate_sum() {
-    let (mut g, shutdown_tx) = start_simple_unsharded("multiple_aggregate").await;
+async fn chained_and_shared_ctes() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("chained_and_shared_ctes").await;
 
     g.extend_recipe(
-
-            ChangeList::from_str("CREATE TABLE test (number int, value1 int, value2 int);
-         CREATE CACHE multiagg FROM SELECT sum(value1) AS s1, sum(value2) as s2 FROM test GROUP BY number;", Dialect::DEFAULT_MYSQL)
+        ChangeList::from_str(
+            "CREATE TABLE t1 (id int, value int);
+         CREATE TABLE t2 (value int, name text);
+         CREATE CACHE chained_and_shared_ctes FROM
+         WITH big AS (SELECT id, value FROM t1 WHERE value > 1),
+              big_ids AS (SELECT id, value FROM big WHERE id > 0)
+         SELECT b1.value AS v1, b2.value AS v2, t2.name
+         FROM big_ids AS b1
+         JOIN big_ids AS b2 ON b1.id = b2.id
+         JOIN t2 ON t2.value = b2.value
+         WHERE b1.value = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
         .unwrap(),
     )
     .await
     .unwrap();
 
-    let mut t = g.table("test").await.unwrap();
-    let mut q = g
-        .view("multiagg")
+    let mut t1 = g.table("t1").await.unwrap();
+    let mut t2 = g.table("t2").await.unwrap();
+    let mut view = g
+        .view("chained_and_shared_ctes")
         .await
         .unwrap()
         .into_reader_handle()
         .unwrap();
 
-    t.insert_many(vec![
-        vec![
-            DfValue::from(1i32),
-            DfValue::from(1i32),
-            DfValue::from(5i32),
-        ],
-        vec![
-            DfValue::from(1i32),
-            DfValue::from(4i32),
-            DfValue::from(2i32),
-        ],
-        vec![
-            DfValue::from(2i32),
-            DfValue::from(5i32),
-            DfValue::from(7i32),
-        ],
-        vec![
-            DfValue::from(2i32),
-            DfValue::from(7i32),
-            DfValue::from(1i32),
-        ],
-        vec![
-            DfValue::from(3i32),
-            DfValue::from(1i32),
-            DfValue::from(3i32),
-        ],
+    t1.insert_many(vec![
+        vec![DfValue::from(1i32), DfValue::from(1i32)],
+        vec![DfValue::from(2i32), DfValue::from(4i32)],
     ])
     .await
     .unwrap();
 
-    sleep().await;
+    t2.insert_many(vec![
+        vec![DfValue::from(1i32), DfValue::from("one")],
+        vec![DfValue::from(4i32), DfValue::from("four")],
+    ])
+    .await
+    .unwrap();
 
-    let rows = q.lookup(&[0i32.into()], true).await.unwrap();
+    sleep().await;
 
-    let res = rows
-        .into_iter()
-        .map(|r| {
-            (
-                get_col!(q, r, "s1", Decimal).to_i32().unwrap(),
-                get_col!(q, r, "s2", Decimal).to_i32().unwrap(),
-            )
-        })
-        .sorted()
-        .collect::<Vec<(i32, i32)>>();
+    let res = view.lookup(&[4i32.into()], true).await.unwrap().into_vec();
+    assert_eq!(res.len(), 1);
+    assert_eq!(get_col!(view, res[0], "name"), &DfValue::from("four"));
 
-    assert_eq!(res, vec![(1, 3), (5, 7), (12, 8)]);
+    let res = view.lookup(&[1i32.into()], true).await.unwrap().into_vec();
+    assert!(res.is_empty());
 
     shutdown_tx.shutdown().await;
 }
 
-// multiple_aggregate_same_col tests multiple aggregators of different types operating on the same
-// column.
 #[tokio::test(flavor = "multi_thread")]
-async fn multiple_aggregate_same_col() {
-    let (mut g, shutdown_tx) = start_simple_unsharded("multiple_aggregate_same_col").await;
+async fn rename_table_and_column_remaps_caches() {
+    let (mut g, shutdown_tx) =
+        start_simple_unsharded("rename_table_and_column_remaps_caches").await;
 
-    g.extend_recipe(ChangeList::from_str(
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (id int, x int);
+             CREATE CACHE q FROM SELECT t.x FROM t WHERE t.id = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
 
-            "CREATE TABLE test (number int, value int);
-         CREATE CACHE multiaggsamecol FROM SELECT sum(value) AS s, avg(value) AS a FROM test GROUP BY number;", Dialect::DEFAULT_MYSQL)
+    g.extend_recipe(
+        ChangeList::from_str(
+            "ALTER TABLE t RENAME COLUMN x TO y;
+             RENAME TABLE t TO t2;",
+            Dialect::DEFAULT_MYSQL,
+        )
         .unwrap(),
     )
     .await
     .unwrap();
 
-    let mut t = g.table("test").await.unwrap();
-    let mut q = g
-        .view("multiaggsamecol")
-        .await
-        .unwrap()
-        .into_reader_handle()
-        .unwrap();
+    g.table("t").await.unwrap_err();
+    let mut t2 = g.table("t2").await.unwrap();
+    assert_eq!(t2.columns(), &["id", "y"]);
+    let mut view = g.view("q").await.unwrap().into_reader_handle().unwrap();
 
-    t.insert_many(vec![
-        vec![DfValue::from(1i32), DfValue::from(1i32)],
-        vec![DfValue::from(1i32), DfValue::from(4i32)],
-        vec![DfValue::from(2i32), DfValue::from(5i32)],
-        vec![DfValue::from(2i32), DfValue::from(7i32)],
-        vec![DfValue::from(3i32), DfValue::from(1i32)],
+    t2.insert_many(vec![
+        vec![DfValue::from(1i32), DfValue::from(10i32)],
+        vec![DfValue::from(2i32), DfValue::from(20i32)],
     ])
     .await
     .unwrap();
 
     sleep().await;
 
-    let rows = q.lookup(&[0i32.into()], true).await.unwrap();
+    let res = view.lookup(&[2i32.into()], true).await.unwrap().into_vec();
+    assert_eq!(res.len(), 1);
+    assert_eq!(get_col!(view, res[0], "y"), &DfValue::from(20i32));
 
-    let res = rows
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn add_and_drop_columns_in_place() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("add_and_drop_columns_in_place").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (id int, x int, y int, PRIMARY KEY (id));
+             CREATE CACHE by_id FROM SELECT t.x FROM t WHERE t.id = ?;
+             CREATE CACHE y_by_id FROM SELECT t.y FROM t WHERE t.id = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("t").await.unwrap();
+    t.insert(vec![
+        DfValue::from(1i32),
+        DfValue::from(10i32),
+        DfValue::from(100i32),
+    ])
+    .await
+    .unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "ALTER TABLE t ADD COLUMN z int DEFAULT 3;
+             CREATE CACHE z_by_id FROM SELECT t.z FROM t WHERE t.id = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("t").await.unwrap();
+    assert_eq!(t.columns(), &["id", "x", "y", "z"]);
+    t.insert(vec![
+        DfValue::from(2i32),
+        DfValue::from(20i32),
+        DfValue::from(200i32),
+        DfValue::from(4i32),
+    ])
+    .await
+    .unwrap();
+
+    sleep().await;
+
+    // Existing rows are backfilled with the default
+    let mut view = g
+        .view("z_by_id")
+        .await
+        .unwrap()
+        .into_reader_handle()
+        .unwrap();
+    let res = view.lookup(&[1i32.into()], true).await.unwrap().into_vec();
+    assert_eq!(get_col!(view, res[0], "z"), &DfValue::from(3i32));
+    let res = view.lookup(&[2i32.into()], true).await.unwrap().into_vec();
+    assert_eq!(get_col!(view, res[0], "z"), &DfValue::from(4i32));
+
+    g.extend_recipe(
+        ChangeList::from_str("ALTER TABLE t DROP COLUMN y;", Dialect::DEFAULT_MYSQL).unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // Caches referencing the dropped column are removed, but the others are kept
+    g.view("y_by_id").await.unwrap_err();
+    let mut t = g.table("t").await.unwrap();
+    assert_eq!(t.columns(), &["id", "x", "z"]);
+    t.insert(vec![
+        DfValue::from(3i32),
+        DfValue::from(30i32),
+        DfValue::from(5i32),
+    ])
+    .await
+    .unwrap();
+
+    sleep().await;
+
+    let mut view = g.view("by_id").await.unwrap().into_reader_handle().unwrap();
+    let res = view.lookup(&[3i32.into()], true).await.unwrap().into_vec();
+    assert_eq!(get_col!(view, res[0], "x"), &DfValue::from(30i32));
+
+    g.extend_recipe(
+        ChangeList::from_str("ALTER TABLE t DROP COLUMN id;", Dialect::DEFAULT_MYSQL).unwrap(),
+    )
+    .await
+    .unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn change_column_in_place() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("change_column_in_place").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (id int, x int, y int, PRIMARY KEY (id));
+             CREATE CACHE x_by_id FROM SELECT t.x FROM t WHERE t.id = ?;
+             CREATE CACHE y_by_id FROM SELECT t.y FROM t WHERE t.id = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str("ALTER TABLE t MODIFY y text;", Dialect::DEFAULT_MYSQL).unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // Caches referencing the column whose type changed are removed, but the others are kept
+    g.view("y_by_id").await.unwrap_err();
+    g.view("x_by_id").await.unwrap();
+    let t = g.table("t").await.unwrap();
+    assert_eq!(t.schema().unwrap().fields[2].sql_type, SqlType::Text);
+
+    // Changing the name of a column renames it
+    g.extend_recipe(
+        ChangeList::from_str("ALTER TABLE t CHANGE x z int;", Dialect::DEFAULT_MYSQL).unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("t").await.unwrap();
+    assert_eq!(t.columns(), &["id", "z", "y"]);
+    t.insert(vec![
+        DfValue::from(1i32),
+        DfValue::from(10i32),
+        DfValue::from("a"),
+    ])
+    .await
+    .unwrap();
+
+    sleep().await;
+
+    let mut view = g
+        .view("x_by_id")
+        .await
+        .unwrap()
+        .into_reader_handle()
+        .unwrap();
+    let res = view.lookup(&[1i32.into()], true).await.unwrap().into_vec();
+    assert_eq!(get_col!(view, res[0], "z"), &DfValue::from(10i32));
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn alter_columns_of_unknown_table_or_with_non_constant_default() {
+    let (mut g, shutdown_tx) =
+        start_simple_unsharded("alter_columns_of_unknown_table_or_with_non_constant_default").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (id int, x int, PRIMARY KEY (id));",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // Altering a table we don't know about is ignored
+    g.extend_recipe(
+        ChangeList::from_str(
+            "ALTER TABLE unknown ADD COLUMN y int;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // Non-constant defaults are rejected, without applying any of the other changes
+    g.extend_recipe(
+        ChangeList::from_str(
+            "ALTER TABLE t ADD COLUMN y int, ADD COLUMN z datetime DEFAULT CURRENT_TIMESTAMP;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap_err();
+
+    let t = g.table("t").await.unwrap();
+    assert_eq!(t.columns(), &["id", "x"]);
+
+    shutdown_tx.shutdown().await;
+}
+
+// multiple_aggregate_sum tests multiple aggregators of the same type, in this case sum(),
+// operating over different columns from the same table.
+#[tokio::test(flavor = "multi_thread")]
+async fn multiple_aggregate_sum() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("multiple_aggregate").await;
+
+    g.extend_recipe(
+
+            ChangeList::from_str("CREATE TABLE test (number int, value1 int, value2 int);
+         CREATE CACHE multiagg FROM SELECT sum(value1) AS s1, sum(value2) as s2 FROM test GROUP BY number;", Dialect::DEFAULT_MYSQL)
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("test").await.unwrap();
+    let mut q = g
+        .view("multiagg")
+        .await
+        .unwrap()
+        .into_reader_handle()
+        .unwrap();
+
+    t.insert_many(vec![
+        vec![
+            DfValue::from(1i32),
+            DfValue::from(1i32),
+            DfValue::from(5i32),
+        ],
+        vec![
+            DfValue::from(1i32),
+            DfValue::from(4i32),
+            DfValue::from(2i32),
+        ],
+        vec![
+            DfValue::from(2i32),
+            DfValue::from(5i32),
+            DfValue::from(7i32),
+        ],
+        vec![
+            DfValue::from(2i32),
+            DfValue::from(7i32),
+            DfValue::from(1i32),
+        ],
+        vec![
+            DfValue::from(3i32),
+            DfValue::from(1i32),
+            DfValue::from(3i32),
+        ],
+    ])
+    .await
+    .unwrap();
+
+    sleep().await;
+
+    let rows = q.lookup(&[0i32.into()], true).await.unwrap();
+
+    let res = rows
+        .into_iter()
+        .map(|r| {
+            (
+                get_col!(q, r, "s1", Decimal).to_i32().unwrap(),
+                get_col!(q, r, "s2", Decimal).to_i32().unwrap(),
+            )
+        })
+        .sorted()
+        .collect::<Vec<(i32, i32)>>();
+
+    assert_eq!(res, vec![(1, 3), (5, 7), (12, 8)]);
+
+    shutdown_tx.shutdown().await;
+}
+
+// multiple_aggregate_same_col tests multiple aggregators of different types operating on the same
+// column.
+#[tokio::test(flavor = "multi_thread")]
+async fn multiple_aggregate_same_col() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("multiple_aggregate_same_col").await;
+
+    g.extend_recipe(ChangeList::from_str(
+
+            "CREATE TABLE test (number int, value int);
+         CREATE CACHE multiaggsamecol FROM SELECT sum(value) AS s, avg(value) AS a FROM test GROUP BY number;", Dialect::DEFAULT_MYSQL)
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("test").await.unwrap();
+    let mut q = g
+        .view("multiaggsamecol")
+        .await
+        .unwrap()
+        .into_reader_handle()
+        .unwrap();
+
+    t.insert_many(vec![
+        vec![DfValue::from(1i32), DfValue::from(1i32)],
+        vec![DfValue::from(1i32), DfValue::from(4i32)],
+        vec![DfValue::from(2i32), DfValue::from(5i32)],
+        vec![DfValue::from(2i32), DfValue::from(7i32)],
+        vec![DfValue::from(3i32), DfValue::from(1i32)],
+    ])
+    .await
+    .unwrap();
+
+    sleep().await;
+
+    let rows = q.lookup(&[0i32.into()], true).await.unwrap();
+
+    let res = rows
         .into_iter()
         .map(|r| {
             (
@@ -7530,19 +8360,111 @@ async fn group_by_agg_col_multi() {
 }
 
 #[tokio::test(flavor = "multi_thread")]
-async fn group_by_agg_col_with_join() {
-    let (mut g, shutdown_tx) = start_simple_unsharded("group_by_agg_col_with_join").await;
-    let sql = "
-        # base tables
-        CREATE TABLE test (id int, number int, PRIMARY KEY(id));
-        CREATE TABLE test2 (test_id int, value int);
+async fn group_by_expression_without_aggregates() {
+    let (mut g, shutdown_tx) =
+        start_simple_unsharded("group_by_expression_without_aggregates").await;
 
-        # read queries
-        CREATE CACHE groupbyaggcolwithjoin FROM SELECT count(number) as c, avg(test2.value) AS a \
-                    FROM test \
-                    INNER JOIN test2 \
-                    ON (test.id = test2.test_id)
-                    GROUP BY number;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE test (name text, number int);
+         CREATE CACHE initials FROM
+         SELECT substring(name, 1, 1) AS initial FROM test GROUP BY substring(name, 1, 1);",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("test").await.unwrap();
+    let mut q = g
+        .view("initials")
+        .await
+        .unwrap()
+        .into_reader_handle()
+        .unwrap();
+
+    t.insert_many(vec![
+        vec![DfValue::from("apple"), DfValue::from(1i32)],
+        vec![DfValue::from("avocado"), DfValue::from(2i32)],
+        vec![DfValue::from("banana"), DfValue::from(3i32)],
+    ])
+    .await
+    .unwrap();
+
+    sleep().await;
+
+    let rows = q.lookup(&[0i32.into()], true).await.unwrap();
+    let res = rows
+        .into_iter()
+        .map(|r| get_col!(q, r, "initial", String))
+        .sorted()
+        .collect::<Vec<_>>();
+    assert_eq!(res, vec!["a", "b"]);
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn group_by_and_order_by_expression() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("group_by_and_order_by_expression").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE test (name text, number int);
+         CREATE CACHE initial_counts FROM
+         SELECT substring(name, 1, 1) AS initial, count(*) AS c FROM test
+         GROUP BY substring(name, 1, 1)
+         ORDER BY substring(name, 1, 1) DESC;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("test").await.unwrap();
+    let mut q = g
+        .view("initial_counts")
+        .await
+        .unwrap()
+        .into_reader_handle()
+        .unwrap();
+
+    t.insert_many(vec![
+        vec![DfValue::from("apple"), DfValue::from(1i32)],
+        vec![DfValue::from("avocado"), DfValue::from(2i32)],
+        vec![DfValue::from("banana"), DfValue::from(3i32)],
+    ])
+    .await
+    .unwrap();
+
+    sleep().await;
+
+    let rows = q.lookup(&[0i32.into()], true).await.unwrap();
+    let res = rows
+        .into_iter()
+        .map(|r| (get_col!(q, r, "initial", String), get_col!(q, r, "c", i32)))
+        .collect::<Vec<_>>();
+    assert_eq!(res, vec![("b".to_owned(), 1), ("a".to_owned(), 2)]);
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn group_by_agg_col_with_join() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("group_by_agg_col_with_join").await;
+    let sql = "
+        # base tables
+        CREATE TABLE test (id int, number int, PRIMARY KEY(id));
+        CREATE TABLE test2 (test_id int, value int);
+
+        # read queries
+        CREATE CACHE groupbyaggcolwithjoin FROM SELECT count(number) as c, avg(test2.value) AS a \
+                    FROM test \
+                    INNER JOIN test2 \
+                    ON (test.id = test2.test_id)
+                    GROUP BY number;
     ";
 
     g.extend_recipe(ChangeList::from_str(sql, Dialect::DEFAULT_MYSQL).unwrap())
@@ -8771,111 +9693,90 @@ async fn simple_dry_run() {
 }
 
 #[tokio::test(flavor = "multi_thread")]
-async fn simple_dry_run_unsupported() {
-    let (mut g, shutdown_tx) = start_simple_unsharded("simple_dry_run").await;
-    let query = "
-        # base tables
-        CREATE TABLE table_1 (column_1 INT);
-        CREATE CACHE t1 FROM SELECT * FROM table_1;
-    ";
-    g.extend_recipe(ChangeList::from_str(query, Dialect::DEFAULT_MYSQL).unwrap())
+async fn plan_migration() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("plan_migration").await;
+    g.extend_recipe(
+        ChangeList::from_str("CREATE TABLE t (x int, y int);", Dialect::DEFAULT_MYSQL).unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let plan = g
+        .plan_migration(
+            ChangeList::from_str(
+                "CREATE CACHE q FROM SELECT x, count(*) FROM t WHERE y = ? GROUP BY x",
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap(),
+        )
         .await
         .unwrap();
 
-    g.table("table_1").await.unwrap();
-    g.view("t1").await.unwrap();
+    let reader = plan
+        .added_nodes
+        .iter()
+        .find(|n| n.node_name == "q".into())
+        .unwrap();
+    assert!(reader.materialized);
+    assert!(plan
+        .new_domains
+        .iter()
+        .any(|d| d.nodes.contains(&reader.node_index)));
 
-    let unsupported_query = "CREATE CACHE FROM SELECT 1";
-    let res = g
-        .dry_run(ChangeList::from_str(unsupported_query, Dialect::DEFAULT_MYSQL).unwrap())
-        .await;
-    assert!(matches!(
-        res,
-        Err(RpcFailed {
-            source:
-                box SelectQueryCreationFailed {
-                    source: box ReadySetError::Unsupported(_),
-                    ..
-                },
-            ..
-        })
-    ));
+    // Nothing should actually have been added
+    g.view("q").await.unwrap_err();
 
     shutdown_tx.shutdown().await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
-async fn multiple_simultaneous_migrations() {
-    let (mut g, shutdown_tx) = start_simple_unsharded("multiple_simultaneous_migrations").await;
-    let mut g2 = g.clone();
-
-    g.extend_recipe(ChangeList::from_change(
-        Change::CreateTable {
-            statement: parse_create_table(nom_sql::Dialect::MySQL, "CREATE TABLE t (x int, y int)")
-                .unwrap(),
-            pg_meta: None,
-        },
-        Dialect::DEFAULT_MYSQL,
-    ))
+async fn plan_migration_forecasts_state_size() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("plan_migration_forecasts_state_size").await;
+    g.extend_recipe(
+        ChangeList::from_str("CREATE TABLE t (x int, y int);", Dialect::DEFAULT_MYSQL).unwrap(),
+    )
     .await
     .unwrap();
 
     let mut t = g.table("t").await.unwrap();
+    t.insert_many((0i32..100).map(|i| vec![DfValue::from(i % 10), DfValue::from(i)]))
+        .await
+        .unwrap();
+    sleep().await;
 
-    t.insert_many(vec![
-        vec![DfValue::from(1), DfValue::from(2)],
-        vec![DfValue::from(3), DfValue::from(4)],
-    ])
-    .await
-    .unwrap();
+    let plan = g
+        .plan_migration(
+            ChangeList::from_str(
+                "CREATE CACHE q FROM SELECT x, count(*) FROM t GROUP BY x",
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap(),
+        )
+        .await
+        .unwrap();
 
-    let (r1, r2) = join!(
-        g.extend_recipe(ChangeList::from_change(
-            Change::CreateCache(CreateCache {
-                name: Some("q1".into()),
-                statement: Box::new(
-                    parse_select_statement(nom_sql::Dialect::MySQL, "SELECT * FROM t WHERE x = ?")
-                        .unwrap()
-                ),
-                always: false,
-            }),
-            Dialect::DEFAULT_MYSQL
-        )),
-        g2.extend_recipe(ChangeList::from_change(
-            Change::CreateCache(CreateCache {
-                name: Some("q2".into()),
-                statement: Box::new(
-                    parse_select_statement(nom_sql::Dialect::MySQL, "SELECT * FROM t WHERE y = ?")
-                        .unwrap()
-                ),
-                always: false,
-            }),
-            Dialect::DEFAULT_MYSQL
-        ))
+    // The whole table fits in the cardinality sample, so we know exactly how many groups there are
+    let reader = plan
+        .size_forecasts
+        .iter()
+        .find(|f| f.node_name == "q".into())
+        .unwrap();
+    assert_eq!(
+        reader.estimated_size.key_count,
+        KeyCount::EstimatedRowCount(10)
     );
-    r1.unwrap();
-    r2.unwrap();
-
-    let mut q1 = g.view("q1").await.unwrap().into_reader_handle().unwrap();
-    let mut q2 = g.view("q1").await.unwrap().into_reader_handle().unwrap();
-
-    let res1 = q1.lookup(&[1.into()], true).await.unwrap().into_vec();
-    let res2 = q2.lookup(&[3.into()], true).await.unwrap().into_vec();
-
-    assert_eq!(res1, vec![vec![1.into(), 2.into()]]);
-    assert_eq!(res2, vec![vec![3.into(), 4.into()]]);
+    assert!(plan.forecast_state_bytes() > 0);
 
     shutdown_tx.shutdown().await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
-async fn drop_view() {
-    let (mut g, shutdown_tx) = start_simple_unsharded("drop_view").await;
-
+async fn stage_activate_and_rollback_recipe_version() {
+    let (mut g, shutdown_tx) =
+        start_simple_unsharded("stage_activate_and_rollback_recipe_version").await;
     g.extend_recipe(
         ChangeList::from_str(
-            "CREATE TABLE t1 (id int);
-         CREATE CACHE t1_view FROM SELECT * FROM t1;",
+            "CREATE TABLE t (x int, y int); CREATE CACHE q1 FROM SELECT x FROM t WHERE y = ?;",
             Dialect::DEFAULT_MYSQL,
         )
         .unwrap(),
@@ -8883,85 +9784,2120 @@ async fn drop_view() {
     .await
     .unwrap();
 
-    g.extend_recipe(
-        ChangeList::from_str(
-            "CREATE CACHE t1_select FROM SELECT * FROM t1_view",
-            Dialect::DEFAULT_MYSQL,
-        )
-        .unwrap(),
-    )
+    // Dropping tables can't be staged
+    g.stage_recipe_version(ChangeList::from_change(
+        Change::Drop {
+            name: "t".into(),
+            if_exists: false,
+        },
+        Dialect::DEFAULT_MYSQL,
+    ))
     .await
-    .unwrap();
-
-    g.extend_recipe(ChangeList::from_str("DROP CACHE t1_select;", Dialect::DEFAULT_MYSQL).unwrap())
-        .await
-        .unwrap();
-
-    g.extend_recipe(ChangeList::from_str("DROP VIEW t1_view;", Dialect::DEFAULT_MYSQL).unwrap())
-        .await
-        .unwrap();
+    .unwrap_err();
 
-    let select_from_view_res = g
-        .extend_recipe(
+    let version = g
+        .stage_recipe_version(
             ChangeList::from_str(
-                "CREATE CACHE t1_select FROM SELECT * FROM t1_view",
+                "CREATE CACHE q2 FROM SELECT y FROM t WHERE x = ?; DROP CACHE q1;",
                 Dialect::DEFAULT_MYSQL,
             )
             .unwrap(),
         )
-        .await;
-    let err = select_from_view_res.unwrap_err();
-    assert!(err.to_string().contains("t1_view"));
+        .await
+        .unwrap();
+    assert_eq!(g.recipe_versions().await.unwrap().staged, vec![version]);
+
+    // Staging doesn't change anything
+    g.view("q1").await.unwrap();
+    g.view("q2").await.unwrap_err();
+
+    g.activate_recipe_version(version).await.unwrap();
+    g.view("q1").await.unwrap_err();
+    g.view("q2").await.unwrap();
+    let versions = g.recipe_versions().await.unwrap();
+    assert_eq!(versions.active, version);
+    assert!(versions.staged.is_empty());
+
+    g.rollback_to(RecipeVersion(0)).await.unwrap();
+    g.view("q1").await.unwrap();
+    g.view("q2").await.unwrap_err();
+    assert_eq!(g.recipe_versions().await.unwrap().active, RecipeVersion(0));
+
+    // The rolled back version can no longer be rolled back to
+    g.rollback_to(version).await.unwrap_err();
 
     shutdown_tx.shutdown().await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
-async fn drop_view_schema_qualified() {
-    readyset_tracing::init_test_logging();
-    let (mut g, shutdown_tx) = start_simple_unsharded("drop_view_schema_qualified").await;
-
+async fn list_queries() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("list_queries").await;
     g.extend_recipe(
         ChangeList::from_str(
-            "CREATE TABLE public.t1 (id int);
-             CREATE VIEW public.t1_view AS SELECT * FROM public.t1;",
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q1 FROM SELECT x FROM t WHERE y = ?;
+             CREATE CACHE q2 FROM SELECT y FROM t WHERE x = ?;",
             Dialect::DEFAULT_MYSQL,
         )
         .unwrap(),
     )
     .await
     .unwrap();
-
     g.extend_recipe(
         ChangeList::from_str(
-            "CREATE CACHE t1_select FROM SELECT * FROM t1_view",
+            "CREATE CACHE q3 FROM SELECT x FROM t WHERE y = ?;",
             Dialect::DEFAULT_MYSQL,
         )
-        .unwrap()
-        .with_schema_search_path(vec!["public".into()]),
+        .unwrap(),
     )
     .await
     .unwrap();
 
-    g.extend_recipe(
-        ChangeList::from_str("DROP VIEW public.t1_view;", Dialect::DEFAULT_MYSQL).unwrap(),
-    )
-    .await
-    .unwrap();
+    let queries = g.list_queries().await.unwrap();
+    assert_eq!(
+        queries.iter().map(|q| q.name.clone()).collect::<Vec<_>>(),
+        vec!["q1".into(), "q2".into(), "q3".into()]
+    );
+    assert!(queries.iter().all(|q| q.source == QuerySource::Api));
+    assert!(!queries[0].rewrites.contains(&QueryRewrite::Adapter));
+    assert!(queries[2]
+        .rewrites
+        .contains(&QueryRewrite::AliasOf("q1".into())));
 
-    g.view("t1_select").await.unwrap_err();
+    g.extend_recipe(ChangeList::from_str("DROP CACHE q2;", Dialect::DEFAULT_MYSQL).unwrap())
+        .await
+        .unwrap();
+    let queries = g.list_queries().await.unwrap();
+    assert_eq!(
+        queries.iter().map(|q| q.name.clone()).collect::<Vec<_>>(),
+        vec!["q1".into(), "q3".into()]
+    );
 
     shutdown_tx.shutdown().await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
-async fn read_from_dropped_query() {
-    let (mut g, shutdown_tx) = start_simple_unsharded("read_from_dropped_query").await;
-
+async fn column_provenance() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("column_provenance").await;
     g.extend_recipe(
         ChangeList::from_str(
-            "CREATE TABLE t1 (id int);
-         CREATE CACHE q FROM SELECT id FROM t1;",
+            "CREATE TABLE users (id int primary key, name text);
+             CREATE TABLE posts (id int primary key, author_id int, score int);
+             CREATE TABLE tags (id int primary key, tag text);
+             CREATE CACHE q FROM
+                 SELECT users.name, SUM(posts.score) AS total
+                 FROM users JOIN posts ON users.id = posts.author_id
+                 WHERE users.id = ?
+                 GROUP BY users.name;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let provenance = g.column_provenance(&"q".into()).await.unwrap();
+    let column = |name: &str| {
+        provenance
+            .iter()
+            .find(|c| c.column == name)
+            .unwrap_or_else(|| panic!("no column {name} in {provenance:?}"))
+    };
+
+    let name = column("name");
+    assert_eq!(name.base_columns, vec![Column::from("users.name")]);
+    assert!(name.expressions.is_empty());
+
+    let total = column("total");
+    assert_eq!(total.base_columns, vec![Column::from("posts.score")]);
+    assert!(
+        total.expressions.iter().any(|e| e.starts_with("SUM(")),
+        "{:?}",
+        total.expressions
+    );
+
+    // Columns only used as join keys still count as dependencies of the query
+    assert_eq!(
+        g.column_dependents(&Column::from("posts.author_id"))
+            .await
+            .unwrap(),
+        vec![Relation::from("q")]
+    );
+    assert!(g
+        .column_dependents(&Column::from("tags.tag"))
+        .await
+        .unwrap()
+        .is_empty());
+
+    g.extend_recipe(ChangeList::from_str("DROP CACHE q;", Dialect::DEFAULT_MYSQL).unwrap())
+        .await
+        .unwrap();
+    g.column_provenance(&"q".into()).await.unwrap_err();
+    assert!(g
+        .column_dependents(&Column::from("posts.author_id"))
+        .await
+        .unwrap()
+        .is_empty());
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn simple_dry_run_unsupported() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("simple_dry_run").await;
+    let query = "
+        # base tables
+        CREATE TABLE table_1 (column_1 INT);
+        CREATE CACHE t1 FROM SELECT * FROM table_1;
+    ";
+    g.extend_recipe(ChangeList::from_str(query, Dialect::DEFAULT_MYSQL).unwrap())
+        .await
+        .unwrap();
+
+    g.table("table_1").await.unwrap();
+    g.view("t1").await.unwrap();
+
+    let unsupported_query = "CREATE CACHE FROM SELECT 1";
+    let res = g
+        .dry_run(ChangeList::from_str(unsupported_query, Dialect::DEFAULT_MYSQL).unwrap())
+        .await;
+    assert!(matches!(
+        res,
+        Err(RpcFailed {
+            source:
+                box SelectQueryCreationFailed {
+                    source: box ReadySetError::Unsupported(_),
+                    ..
+                },
+            ..
+        })
+    ));
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn per_query_frontier_strategy() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("per_query_frontier_strategy").await;
+
+    g.extend_recipe(ChangeList::from_change(
+        Change::CreateTable {
+            statement: parse_create_table(nom_sql::Dialect::MySQL, "CREATE TABLE t (x int, y int)")
+                .unwrap(),
+            pg_meta: None,
+        },
+        Dialect::DEFAULT_MYSQL,
+    ))
+    .await
+    .unwrap();
+
+    g.extend_recipe(ChangeList::from_changes(
+        vec![
+            Change::CreateCache(CreateCache {
+                name: Some("shallow".into()),
+                statement: Box::new(
+                    parse_select_statement(nom_sql::Dialect::MySQL, "SELECT * FROM t WHERE x = ?")
+                        .unwrap(),
+                ),
+                always: false,
+                frontier_strategy: Some(FrontierStrategy::Readers),
+                source: Default::default(),
+                original_sql: None,
+                disable_predicate_pushdown: false,
+                standby: false,
+            }),
+            Change::CreateCache(CreateCache {
+                name: Some("deep".into()),
+                statement: Box::new(
+                    parse_select_statement(nom_sql::Dialect::MySQL, "SELECT * FROM t WHERE y = ?")
+                        .unwrap(),
+                ),
+                always: false,
+                frontier_strategy: None,
+                source: Default::default(),
+                original_sql: None,
+                disable_predicate_pushdown: false,
+                standby: false,
+            }),
+        ],
+        Dialect::DEFAULT_MYSQL,
+    ))
+    .await
+    .unwrap();
+
+    let graphviz_for = |query: &str| GraphvizOptions {
+        for_query: Some(query.into()),
+        detailed: true,
+    };
+    let shallow = g.graphviz(graphviz_for("shallow")).await.unwrap();
+    let deep = g.graphviz(graphviz_for("deep")).await.unwrap();
+
+    // ◔ marks partial materializations beyond the frontier, ◕ ones that aren't
+    assert!(shallow.contains('◔'), "{shallow}");
+    assert!(!deep.contains('◔'), "{deep}");
+    assert!(deep.contains('◕'), "{deep}");
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn predicate_pushdown_with_deletes() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("predicate_pushdown_with_deletes").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t1 (id int, a int, b int);
+             CREATE TABLE t2 (id int, c int);",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // The filters on `t1` should be pushed through the union for the `OR` and into the left-hand
+    // side of the join for `pushed`, but not for `not_pushed`. The queries are written slightly
+    // differently (but are equivalent) so that the second one doesn't reuse the first.
+    g.extend_recipe(ChangeList::from_changes(
+        [
+            ("pushed", "t1.b > 0", false),
+            ("not_pushed", "t1.b >= 1", true),
+        ]
+        .into_iter()
+        .map(|(name, cond, disable_predicate_pushdown)| {
+            let query = format!(
+                "SELECT t1.id, t2.c FROM t1 LEFT JOIN t2 ON t1.id = t2.id \
+                     WHERE (t1.a = 1 OR t1.b = 2) AND {cond}"
+            );
+            Change::CreateCache(CreateCache {
+                name: Some(name.into()),
+                statement: Box::new(
+                    parse_select_statement(nom_sql::Dialect::MySQL, &query).unwrap(),
+                ),
+                always: false,
+                frontier_strategy: None,
+                source: Default::default(),
+                original_sql: None,
+                disable_predicate_pushdown,
+                standby: false,
+            })
+        })
+        .collect::<Vec<_>>(),
+        Dialect::DEFAULT_MYSQL,
+    ))
+    .await
+    .unwrap();
+
+    let mut t1 = g.table("t1").await.unwrap();
+    let mut t2 = g.table("t2").await.unwrap();
+    let mut pushed = g
+        .view("pushed")
+        .await
+        .unwrap()
+        .into_reader_handle()
+        .unwrap();
+    let mut not_pushed = g
+        .view("not_pushed")
+        .await
+        .unwrap()
+        .into_reader_handle()
+        .unwrap();
+
+    t1.insert_many(vec![
+        vec![DfValue::from(1), DfValue::from(1), DfValue::from(5)],
+        vec![DfValue::from(2), DfValue::from(3), DfValue::from(2)],
+        vec![DfValue::from(3), DfValue::from(3), DfValue::from(3)],
+        vec![DfValue::from(4), DfValue::from(1), DfValue::from(-1)],
+    ])
+    .await
+    .unwrap();
+    t2.insert_many(vec![
+        vec![DfValue::from(1), DfValue::from(10)],
+        vec![DfValue::from(2), DfValue::from(20)],
+    ])
+    .await
+    .unwrap();
+
+    sleep().await;
+
+    for view in [&mut pushed, &mut not_pushed] {
+        let mut res = view.lookup(&[0.into()], true).await.unwrap().into_vec();
+        res.sort();
+        assert_eq!(
+            res,
+            vec![
+                vec![DfValue::from(1), DfValue::from(10)],
+                vec![DfValue::from(2), DfValue::from(20)],
+            ]
+        );
+    }
+
+    // Deletes from either side of the join have to flow through the moved filters
+    t1.delete_row(vec![DfValue::from(1), DfValue::from(1), DfValue::from(5)])
+        .await
+        .unwrap();
+    t1.delete_row(vec![DfValue::from(4), DfValue::from(1), DfValue::from(-1)])
+        .await
+        .unwrap();
+    t2.delete_row(vec![DfValue::from(2), DfValue::from(20)])
+        .await
+        .unwrap();
+
+    sleep().await;
+
+    for view in [&mut pushed, &mut not_pushed] {
+        assert_eq!(
+            view.lookup(&[0.into()], true).await.unwrap().into_vec(),
+            vec![vec![DfValue::from(2), DfValue::None]]
+        );
+    }
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn predicate_pushdown_option_for_reused_query() {
+    let (mut g, shutdown_tx) =
+        start_simple_unsharded("predicate_pushdown_option_for_reused_query").await;
+
+    let query = "SELECT t1.id, t2.c FROM t1 LEFT JOIN t2 ON t1.id = t2.id WHERE t1.a = 1";
+    g.extend_recipe(
+        ChangeList::from_str(
+            format!(
+                "CREATE TABLE t1 (id int, a int);
+                 CREATE TABLE t2 (id int, c int);
+                 CREATE CACHE DISABLE PREDICATE PUSHDOWN q1 FROM {query};"
+            ),
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // The same query can't be cached again with a different option, since it'd reuse the
+    // existing cache
+    let err = g
+        .extend_recipe(
+            ChangeList::from_str(
+                format!("CREATE CACHE q2 FROM {query};"),
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap(),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("PREDICATE PUSHDOWN"), "{err}");
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            format!("CREATE CACHE DISABLE PREDICATE PUSHDOWN q2 FROM {query};"),
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn lookup_by_different_columns_shares_nodes() {
+    let (mut g, shutdown_tx) =
+        start_simple_unsharded("lookup_by_different_columns_shares_nodes").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (id int, a int, b int);
+             CREATE CACHE by_a FROM SELECT id, a, b FROM t WHERE a = ?;
+             CREATE CACHE by_b FROM SELECT id, a, b FROM t WHERE b = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // Both readers should read from the same node, rather than each query getting its own copy
+    let views = g.views().await.unwrap();
+    assert_eq!(views[&"by_a".into()], views[&"by_b".into()]);
+
+    let mut t = g.table("t").await.unwrap();
+    let mut by_a = g.view("by_a").await.unwrap().into_reader_handle().unwrap();
+    let mut by_b = g.view("by_b").await.unwrap().into_reader_handle().unwrap();
+
+    t.insert_many(vec![
+        vec![DfValue::from(1), DfValue::from(1), DfValue::from(2)],
+        vec![DfValue::from(2), DfValue::from(1), DfValue::from(3)],
+        vec![DfValue::from(3), DfValue::from(2), DfValue::from(3)],
+    ])
+    .await
+    .unwrap();
+
+    sleep().await;
+
+    let mut res = by_a.lookup(&[1.into()], true).await.unwrap().into_vec();
+    res.sort();
+    assert_eq!(
+        res,
+        vec![
+            vec![DfValue::from(1), DfValue::from(1), DfValue::from(2)],
+            vec![DfValue::from(2), DfValue::from(1), DfValue::from(3)],
+        ]
+    );
+    let mut res = by_b.lookup(&[3.into()], true).await.unwrap().into_vec();
+    res.sort();
+    assert_eq!(
+        res,
+        vec![
+            vec![DfValue::from(2), DfValue::from(1), DfValue::from(3)],
+            vec![DfValue::from(3), DfValue::from(2), DfValue::from(3)],
+        ]
+    );
+
+    // Dropping one of the queries should leave the other one working
+    g.extend_recipe(ChangeList::from_str("DROP CACHE by_a;", Dialect::DEFAULT_MYSQL).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(g.views().await.unwrap().len(), 1);
+
+    t.insert(vec![DfValue::from(4), DfValue::from(3), DfValue::from(3)])
+        .await
+        .unwrap();
+
+    sleep().await;
+
+    let mut res = by_b.lookup(&[3.into()], true).await.unwrap().into_vec();
+    res.sort();
+    assert_eq!(
+        res,
+        vec![
+            vec![DfValue::from(2), DfValue::from(1), DfValue::from(3)],
+            vec![DfValue::from(3), DfValue::from(2), DfValue::from(3)],
+            vec![DfValue::from(4), DfValue::from(3), DfValue::from(3)],
+        ]
+    );
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dropping_cache_prunes_domains_and_state() {
+    let (mut g, shutdown_tx) =
+        start_simple_unsharded("dropping_cache_prunes_domains_and_state").await;
+
+    g.extend_recipe(
+        ChangeList::from_str("CREATE TABLE t (a int, b int);", Dialect::DEFAULT_MYSQL).unwrap(),
+    )
+    .await
+    .unwrap();
+    let num_domains = g.domains().await.unwrap().len();
+    let num_materializations = g.materialization_info().await.unwrap().len();
+
+    let create_cache = "CREATE CACHE q FROM SELECT a, sum(b) FROM t WHERE a = ? GROUP BY a;";
+    g.extend_recipe(ChangeList::from_str(create_cache, Dialect::DEFAULT_MYSQL).unwrap())
+        .await
+        .unwrap();
+    assert!(g.domains().await.unwrap().len() > num_domains);
+    assert!(g.materialization_info().await.unwrap().len() > num_materializations);
+
+    g.extend_recipe(ChangeList::from_str("DROP CACHE q;", Dialect::DEFAULT_MYSQL).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(g.domains().await.unwrap().len(), num_domains);
+    assert_eq!(
+        g.materialization_info().await.unwrap().len(),
+        num_materializations
+    );
+
+    // Writes to the table shouldn't try to go to the removed domains
+    let mut t = g.table("t").await.unwrap();
+    t.insert(vec![DfValue::from(1), DfValue::from(2)])
+        .await
+        .unwrap();
+    sleep().await;
+
+    // And we should be able to create the cache again from scratch
+    g.extend_recipe(ChangeList::from_str(create_cache, Dialect::DEFAULT_MYSQL).unwrap())
+        .await
+        .unwrap();
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    t.insert(vec![DfValue::from(1), DfValue::from(3)])
+        .await
+        .unwrap();
+    sleep().await;
+
+    assert_eq!(
+        q.lookup(&[1.into()], true).await.unwrap().into_vec(),
+        vec![vec![DfValue::from(1), Decimal::from(5).into()]]
+    );
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn trace_key() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("trace_key").await;
+
+    g.extend_recipe(ChangeList::from_changes(
+        vec![
+            Change::CreateTable {
+                statement: parse_create_table(
+                    nom_sql::Dialect::MySQL,
+                    "CREATE TABLE t (x int, y int)",
+                )
+                .unwrap(),
+                pg_meta: None,
+            },
+            Change::CreateCache(CreateCache {
+                name: Some("q".into()),
+                statement: Box::new(
+                    parse_select_statement(nom_sql::Dialect::MySQL, "SELECT * FROM t WHERE x = ?")
+                        .unwrap(),
+                ),
+                always: false,
+                frontier_strategy: None,
+                source: Default::default(),
+                original_sql: None,
+                disable_predicate_pushdown: false,
+                standby: false,
+            }),
+        ],
+        Dialect::DEFAULT_MYSQL,
+    ))
+    .await
+    .unwrap();
+
+    let trace = g
+        .trace_key("q".into(), vec![DfValue::from(1)])
+        .await
+        .unwrap();
+    assert!(!trace.materialized);
+    assert!(trace.last_miss.is_none());
+    assert!(trace.partial);
+    assert!(!trace.replay_paths.is_empty());
+
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    q.lookup(&[DfValue::from(1)], true).await.unwrap();
+
+    let trace = g
+        .trace_key("q".into(), vec![DfValue::from(1)])
+        .await
+        .unwrap();
+    assert!(trace.materialized);
+    let last_miss = trace.last_miss.unwrap();
+    assert!(last_miss.replay_duration.is_some());
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn order_by_without_post_lookup() {
+    let mut builder = Builder::for_tests();
+    builder.set_persistence(get_persistence_params("order_by_without_post_lookup"));
+    builder.set_allow_post_lookup(false);
+    let (mut g, shutdown_tx) = builder.start_local().await.unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int, z int);
+             CREATE CACHE q FROM SELECT y, z FROM t WHERE x = ? ORDER BY z DESC, y;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // Queries that need work to be done after the lookup are still rejected
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE CACHE q_limit FROM SELECT y, z FROM t WHERE x = ? ORDER BY z LIMIT 2;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap_err();
+
+    let mut t = g.table("t").await.unwrap();
+    t.insert_many(vec![
+        vec![1.into(), 1.into(), 2.into()],
+        vec![1.into(), 2.into(), 3.into()],
+        vec![1.into(), 3.into(), 1.into()],
+        vec![1.into(), 0.into(), 2.into()],
+        vec![2.into(), 4.into(), 5.into()],
+    ])
+    .await
+    .unwrap();
+
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    eventually!(run_test: {
+        q.lookup(&[1.into()], true).await.unwrap().into_vec()
+    }, then_assert: |res| {
+        assert_eq!(
+            res,
+            vec![
+                vec![DfValue::from(2), DfValue::from(3)],
+                vec![DfValue::from(0), DfValue::from(2)],
+                vec![DfValue::from(1), DfValue::from(2)],
+                vec![DfValue::from(3), DfValue::from(1)],
+            ]
+        );
+    });
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn group_concat_order_by() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("group_concat_order_by").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y text, z int);
+             CREATE CACHE q FROM
+             SELECT x, GROUP_CONCAT(y ORDER BY z DESC SEPARATOR ', ') AS ys
+             FROM t WHERE x = ? GROUP BY x;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("t").await.unwrap();
+    t.insert_many(vec![
+        vec![1.into(), "a".into(), 1.into()],
+        vec![1.into(), "b".into(), 3.into()],
+        vec![1.into(), "c".into(), 2.into()],
+    ])
+    .await
+    .unwrap();
+
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    eventually!(run_test: {
+        q.lookup(&[1.into()], true).await.unwrap().into_vec()
+    }, then_assert: |res| {
+        assert_eq!(res, vec![vec![DfValue::from(1), DfValue::from("b, c, a")]]);
+    });
+
+    t.delete_row(vec![DfValue::from(1), DfValue::from("c"), DfValue::from(2)])
+        .await
+        .unwrap();
+    t.insert(vec![1.into(), "d".into(), 4.into()])
+        .await
+        .unwrap();
+
+    eventually!(run_test: {
+        q.lookup(&[1.into()], true).await.unwrap().into_vec()
+    }, then_assert: |res| {
+        assert_eq!(res, vec![vec![DfValue::from(1), DfValue::from("d, b, a")]]);
+    });
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn approx_count_distinct() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("approx_count_distinct").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q FROM
+             SELECT x, APPROX_COUNT_DISTINCT(y) AS ys
+             FROM t WHERE x = ? GROUP BY x;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("t").await.unwrap();
+    t.insert_many(vec![
+        vec![1.into(), 1.into()],
+        vec![1.into(), 2.into()],
+        vec![1.into(), 2.into()],
+        vec![1.into(), DfValue::None],
+        vec![1.into(), 3.into()],
+    ])
+    .await
+    .unwrap();
+
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    eventually!(run_test: {
+        q.lookup(&[1.into()], true).await.unwrap().into_vec()
+    }, then_assert: |res| {
+        assert_eq!(res, vec![vec![DfValue::from(1), DfValue::from(3)]]);
+    });
+
+    // Deleting the last row with a value rebuilds the sketch for the group without it
+    t.delete_row(vec![DfValue::from(1), DfValue::from(3)])
+        .await
+        .unwrap();
+
+    eventually!(run_test: {
+        q.lookup(&[1.into()], true).await.unwrap().into_vec()
+    }, then_assert: |res| {
+        assert_eq!(res, vec![vec![DfValue::from(1), DfValue::from(2)]]);
+    });
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn view_metrics() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("view_metrics").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q FROM SELECT * FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let metrics = g.view_metrics(&"q".into()).await.unwrap();
+    assert_eq!(metrics.reader, Default::default());
+    assert!(metrics.avg_replay_latency().is_none());
+
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    q.lookup(&[DfValue::from(1)], true).await.unwrap();
+    q.lookup(&[DfValue::from(1)], true).await.unwrap();
+
+    let metrics = g.view_metrics(&"q".into()).await.unwrap();
+    assert_eq!(metrics.reader.misses, 1);
+    assert_eq!(metrics.reader.hits, 1);
+    assert_eq!(metrics.reader.replays, 1);
+    assert!(metrics.avg_replay_latency().is_some());
+
+    g.view_metrics(&"nonexistent".into()).await.unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn view_replay_paths() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("view_replay_paths").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q FROM SELECT * FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("t").await.unwrap();
+    t.insert_many(vec![
+        vec![DfValue::from(1), DfValue::from(2)],
+        vec![DfValue::from(1), DfValue::from(3)],
+        vec![DfValue::from(2), DfValue::from(4)],
+    ])
+    .await
+    .unwrap();
+    sleep().await;
+
+    let paths = g.view_replay_paths(&"q".into()).await.unwrap();
+    assert!(!paths.replay_paths.is_empty());
+    assert_eq!(
+        paths.replay_paths[0].segments.last().unwrap().node_index,
+        paths.reader
+    );
+    assert!(!paths.domains.is_empty());
+    assert_eq!(paths.replays, 0);
+    assert_eq!(paths.replayed_rows, 0);
+
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    q.lookup(&[DfValue::from(1)], true).await.unwrap();
+
+    let paths = g.view_replay_paths(&"q".into()).await.unwrap();
+    assert_eq!(paths.replays, 1);
+    assert_eq!(paths.replayed_rows, 2);
+    assert!(paths.replayed_bytes > 0);
+
+    g.view_replay_paths(&"nonexistent".into())
+        .await
+        .unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn view_hot_keys() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("view_hot_keys").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q FROM SELECT * FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let hot_keys = g.view_hot_keys(&"q".into()).await.unwrap();
+    assert_eq!(hot_keys.reader.total_reads, 0);
+    assert!(hot_keys.reader.keys.is_empty());
+
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    for _ in 0..3 {
+        q.lookup(&[DfValue::from(1)], true).await.unwrap();
+    }
+    q.lookup(&[DfValue::from(2)], true).await.unwrap();
+
+    let hot_keys = g.view_hot_keys(&"q".into()).await.unwrap();
+    assert_eq!(hot_keys.reader.total_reads, 4);
+    let hottest = &hot_keys.reader.keys[0];
+    assert_eq!(hottest.key, KeyComparison::Equal(vec1![DfValue::from(1)]));
+    assert_eq!(hottest.reads, 3);
+    assert_eq!(hottest.misses, 1);
+
+    g.view_hot_keys(&"nonexistent".into()).await.unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn view_scan() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("view_scan").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (a int, b int);
+             CREATE CACHE q FROM SELECT a, b FROM t WHERE b > ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("t").await.unwrap();
+    t.insert_many((1..=5).map(|b| vec![DfValue::from(b * 10), DfValue::from(b)]))
+        .await
+        .unwrap();
+    t.insert(vec![DfValue::from(31), DfValue::from(3)])
+        .await
+        .unwrap();
+
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    // Fill all the keys in the reader
+    q.multi_lookup(
+        vec![KeyComparison::Range(vec1![DfValue::from(0)].range_from())],
+        true,
+    )
+    .await
+    .unwrap();
+
+    let keys = |batch: &ViewScanBatch| {
+        batch
+            .keys
+            .iter()
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>()
+    };
+
+    let batch = q.scan(None, 2).await.unwrap();
+    assert_eq!(
+        keys(&batch),
+        vec![vec![DfValue::from(1)], vec![DfValue::from(2)]]
+    );
+    assert_eq!(
+        batch.keys[0].1,
+        vec![vec![DfValue::from(10), DfValue::from(1)]]
+    );
+    assert_eq!(batch.next, Some(vec![DfValue::from(3)]));
+
+    let batch = q.scan(batch.next, 2).await.unwrap();
+    assert_eq!(
+        keys(&batch),
+        vec![vec![DfValue::from(3)], vec![DfValue::from(4)]]
+    );
+    assert_eq!(batch.keys[0].1.len(), 2);
+
+    let batch = q.scan(batch.next, 2).await.unwrap();
+    assert_eq!(keys(&batch), vec![vec![DfValue::from(5)]]);
+    assert_eq!(batch.next, None);
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn view_rate_limit() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("view_rate_limit").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q FROM SELECT * FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    q.lookup(&[DfValue::from(1)], true).await.unwrap();
+
+    // With no misses allowed, hits still succeed but misses are rejected
+    g.set_view_rate_limit(
+        &"q".into(),
+        ViewRateLimit {
+            max_requests_per_second: None,
+            max_concurrent_misses: Some(0),
+        },
+    )
+    .await
+    .unwrap();
+    q.lookup(&[DfValue::from(1)], true).await.unwrap();
+    let err = q.lookup(&[DfValue::from(2)], true).await.unwrap_err();
+    assert!(err.caused_by_view_throttled(), "{err}");
+
+    g.set_view_rate_limit(
+        &"q".into(),
+        ViewRateLimit {
+            max_requests_per_second: Some(1),
+            max_concurrent_misses: None,
+        },
+    )
+    .await
+    .unwrap();
+    q.lookup(&[DfValue::from(2)], true).await.unwrap();
+    let err = q.lookup(&[DfValue::from(2)], true).await.unwrap_err();
+    assert!(err.caused_by_view_throttled(), "{err}");
+
+    g.set_view_rate_limit(&"q".into(), ViewRateLimit::default())
+        .await
+        .unwrap();
+    q.lookup(&[DfValue::from(2)], true).await.unwrap();
+
+    g.set_view_rate_limit(
+        &"q".into(),
+        ViewRateLimit {
+            max_requests_per_second: Some(0),
+            max_concurrent_misses: None,
+        },
+    )
+    .await
+    .unwrap_err();
+    g.set_view_rate_limit(&"nonexistent".into(), ViewRateLimit::default())
+        .await
+        .unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn view_history_retention() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("view_history_retention").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q FROM SELECT * FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    let mut t = g.table("t").await.unwrap();
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+
+    let before_retention = SystemTime::now();
+    g.set_view_history_retention(&"q".into(), Some(Duration::from_secs(60)))
+        .await
+        .unwrap();
+
+    t.insert(vec![1.into(), 1.into()]).await.unwrap();
+    sleep().await;
+    assert_eq!(
+        q.lookup(&[1.into()], true).await.unwrap().into_vec(),
+        vec![vec![DfValue::from(1), DfValue::from(1)]]
+    );
+
+    let before_insert = SystemTime::now();
+    t.insert(vec![1.into(), 2.into()]).await.unwrap();
+    sleep().await;
+
+    let mut res = q.lookup(&[1.into()], true).await.unwrap().into_vec();
+    res.sort();
+    assert_eq!(
+        res,
+        vec![
+            vec![DfValue::from(1), DfValue::from(1)],
+            vec![DfValue::from(1), DfValue::from(2)]
+        ]
+    );
+    assert_eq!(
+        q.lookup_at(&[1.into()], before_insert)
+            .await
+            .unwrap()
+            .into_vec(),
+        vec![vec![DfValue::from(1), DfValue::from(1)]]
+    );
+
+    // History doesn't reach back before retention was enabled
+    let err = q
+        .lookup_at(&[1.into()], before_retention)
+        .await
+        .unwrap_err();
+    assert!(err.caused_by_view_history_unavailable(), "{err}");
+
+    g.set_view_history_retention(&"q".into(), None)
+        .await
+        .unwrap();
+    let err = q.lookup_at(&[1.into()], before_insert).await.unwrap_err();
+    assert!(err.caused_by_view_history_unavailable(), "{err}");
+
+    g.set_view_history_retention(&"q".into(), Some(Duration::ZERO))
+        .await
+        .unwrap_err();
+    g.set_view_history_retention(&"nonexistent".into(), None)
+        .await
+        .unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn view_stale_reads() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("view_stale_reads").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q FROM SELECT * FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    let mut t = g.table("t").await.unwrap();
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+
+    g.set_view_stale_reads(&"q".into(), ViewStaleReads::enabled())
+        .await
+        .unwrap();
+
+    t.insert(vec![1.into(), 1.into()]).await.unwrap();
+    sleep().await;
+    let res = q.lookup(&[1.into()], true).await.unwrap();
+    assert!(!res.total_stats().unwrap().stale);
+    assert_eq!(
+        res.into_vec(),
+        vec![vec![DfValue::from(1), DfValue::from(1)]]
+    );
+
+    let eviction = g.evict_single(None).await.unwrap().unwrap();
+    assert_eq!(eviction.key, vec![1.into()]);
+
+    // While the evicted key is being replayed, reads are answered with its last-known value
+    let res = q.lookup(&[1.into()], false).await.unwrap();
+    assert!(res.total_stats().unwrap().stale);
+    assert_eq!(
+        res.into_vec(),
+        vec![vec![DfValue::from(1), DfValue::from(1)]]
+    );
+
+    // Once the replay is done, reads are fresh again
+    sleep().await;
+    let res = q.lookup(&[1.into()], true).await.unwrap();
+    assert!(!res.total_stats().unwrap().stale);
+
+    // Keys which were never read still miss
+    assert!(matches!(
+        q.lookup(&[2.into()], false).await,
+        Err(ReadySetError::ReaderMissingKey)
+    ));
+
+    g.set_view_stale_reads(
+        &"q".into(),
+        ViewStaleReads {
+            enabled: true,
+            max_retained_keys: 0,
+        },
+    )
+    .await
+    .unwrap_err();
+    g.set_view_stale_reads(&"nonexistent".into(), ViewStaleReads::default())
+        .await
+        .unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn view_result_limit() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("view_result_limit").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q FROM SELECT * FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    let mut t = g.table("t").await.unwrap();
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+
+    for y in 0..3 {
+        t.insert(vec![1.into(), y.into()]).await.unwrap();
+    }
+    sleep().await;
+    let res = q.lookup(&[1.into()], true).await.unwrap();
+    assert!(!res.total_stats().unwrap().truncated);
+    assert_eq!(res.into_vec().len(), 3);
+
+    g.set_view_result_limit(
+        &"q".into(),
+        ViewResultLimit {
+            max_rows: Some(2),
+            max_bytes: None,
+        },
+    )
+    .await
+    .unwrap();
+    let res = q.lookup(&[1.into()], true).await.unwrap();
+    assert!(res.total_stats().unwrap().truncated);
+    assert_eq!(res.into_vec().len(), 2);
+
+    // Limits on a single read apply on top of the view's limits
+    let res = q
+        .raw_lookup(ViewQuery {
+            result_limit: Some(ViewResultLimit {
+                max_rows: Some(1),
+                max_bytes: None,
+            }),
+            ..(vec![KeyComparison::Equal(vec1![1.into()])], true).into()
+        })
+        .await
+        .unwrap();
+    assert!(res.total_stats().unwrap().truncated);
+    assert_eq!(res.into_vec().len(), 1);
+
+    g.set_view_result_limit(
+        &"q".into(),
+        ViewResultLimit {
+            max_rows: Some(0),
+            max_bytes: None,
+        },
+    )
+    .await
+    .unwrap_err();
+    g.set_view_result_limit(&"nonexistent".into(), ViewResultLimit::default())
+        .await
+        .unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn view_upquery_timeout() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("view_upquery_timeout").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q FROM SELECT * FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    let mut t = g.table("t").await.unwrap();
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+
+    for x in 0..3 {
+        t.insert(vec![x.into(), x.into()]).await.unwrap();
+    }
+    sleep().await;
+
+    g.set_view_upquery_timeout(&"q".into(), Some(Duration::from_secs(10)))
+        .await
+        .unwrap();
+    let res = q.lookup(&[0.into()], true).await.unwrap();
+    assert_eq!(
+        res.into_vec(),
+        vec![vec![DfValue::from(0), DfValue::from(0)]]
+    );
+
+    // A timeout passed with the read overrides the view's
+    let res = q
+        .lookup_with_timeout(&[1.into()], Duration::from_secs(10))
+        .await
+        .unwrap();
+    assert_eq!(
+        res.into_vec(),
+        vec![vec![DfValue::from(1), DfValue::from(1)]]
+    );
+
+    g.set_view_upquery_timeout(&"q".into(), None).await.unwrap();
+    let res = q.lookup(&[2.into()], true).await.unwrap();
+    assert_eq!(
+        res.into_vec(),
+        vec![vec![DfValue::from(2), DfValue::from(2)]]
+    );
+
+    g.set_view_upquery_timeout(&"nonexistent".into(), None)
+        .await
+        .unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn view_secondary_index() {
+    let (mut g, shutdown_tx) = {
+        let mut builder = Builder::for_tests();
+        builder.disable_partial();
+        builder.set_sharding(None);
+        builder.set_persistence(get_persistence_params("view_secondary_index"));
+        builder.start_local()
+    }
+    .await
+    .unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q FROM SELECT x, y FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    let mut t = g.table("t").await.unwrap();
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+
+    t.insert_many((1..=4).map(|x| vec![DfValue::from(x), DfValue::from(x % 2)]))
+        .await
+        .unwrap();
+    sleep().await;
+
+    // No index on y yet
+    q.lookup_by_index(&["y"], vec![vec1![0.into()]])
+        .await
+        .unwrap_err();
+
+    g.set_view_secondary_indices(&"q".into(), vec![vec!["y".into()]])
+        .await
+        .unwrap();
+    let mut res = q
+        .lookup_by_index(&["y"], vec![vec1![0.into()]])
+        .await
+        .unwrap()
+        .into_vec();
+    res.sort();
+    assert_eq!(
+        res,
+        vec![vec![2.into(), 0.into()], vec![4.into(), 0.into()]]
+    );
+
+    // The index is kept up to date with writes
+    t.delete_row(vec![DfValue::from(2), DfValue::from(0)])
+        .await
+        .unwrap();
+    t.insert(vec![DfValue::from(5), DfValue::from(0)])
+        .await
+        .unwrap();
+    sleep().await;
+    let mut res = q
+        .lookup_by_index(&["y"], vec![vec1![0.into()], vec1![1.into()]])
+        .await
+        .unwrap()
+        .into_vec();
+    res.sort();
+    assert_eq!(
+        res,
+        vec![
+            vec![1.into(), 1.into()],
+            vec![3.into(), 1.into()],
+            vec![4.into(), 0.into()],
+            vec![5.into(), 0.into()],
+        ]
+    );
+
+    g.set_view_secondary_indices(&"q".into(), vec![vec!["nonexistent".into()]])
+        .await
+        .unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn stacked_caches() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("stacked_caches").await;
+
+    // The cache reading from `hourly` comes first, so it has to be migrated after `hourly`
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE events (day int, hour int);
+             CREATE CACHE daily FROM
+                 SELECT day, count(*) AS hours FROM hourly WHERE day = ? GROUP BY day;
+             CREATE CACHE hourly FROM
+                 SELECT day, hour, count(*) AS events FROM events GROUP BY day, hour;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut events = g.table("events").await.unwrap();
+    events
+        .insert_many(
+            [(1, 1), (1, 1), (1, 2), (2, 5)]
+                .map(|(day, hour)| vec![DfValue::from(day), DfValue::from(hour)]),
+        )
+        .await
+        .unwrap();
+    sleep().await;
+
+    let mut daily = g.view("daily").await.unwrap().into_reader_handle().unwrap();
+    assert_eq!(
+        daily.lookup(&[1.into()], true).await.unwrap().into_vec(),
+        vec![vec![DfValue::from(1), DfValue::from(2)]]
+    );
+    assert_eq!(
+        daily.lookup(&[2.into()], true).await.unwrap().into_vec(),
+        vec![vec![DfValue::from(2), DfValue::from(1)]]
+    );
+
+    // Only unparameterized caches can be read from
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE CACHE by_day FROM SELECT hour FROM events WHERE day = ?;
+             CREATE CACHE over_by_day FROM SELECT count(*) FROM by_day;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap_err();
+
+    // Dropping a cache drops the caches stacked on top of it
+    g.extend_recipe(ChangeList::from_str("DROP CACHE hourly;", Dialect::DEFAULT_MYSQL).unwrap())
+        .await
+        .unwrap();
+    g.view("hourly").await.unwrap_err();
+    g.view("daily").await.unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn schema_registry() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("schema_registry").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int PRIMARY KEY, y int, z text NOT NULL);
+             CREATE TABLE u (x int, w int);
+             CREATE CACHE q FROM SELECT t.z, u.w FROM t LEFT JOIN u ON t.x = u.x WHERE t.y = ?;
+             CREATE CACHE r FROM SELECT z, y + 1 AS y1 FROM t WHERE x = ?;
+             CREATE CACHE s FROM SELECT z FROM t ORDER BY y LIMIT 3;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let schema = g.schema().await.unwrap();
+    let nullability = |relation: &RelationSchema| {
+        relation
+            .columns
+            .iter()
+            .map(|col| (col.name.to_string(), col.nullable))
+            .collect::<Vec<_>>()
+    };
+
+    let t = schema.table(&"t".into()).unwrap();
+    assert_eq!(t.key_columns, vec![SqlIdentifier::from("x")]);
+    assert_eq!(
+        nullability(t),
+        vec![("x".into(), false), ("y".into(), true), ("z".into(), false)]
+    );
+    assert_eq!(t.columns[0].sql_type, Some(SqlType::Int(None)));
+    assert!(schema.table(&"u".into()).unwrap().key_columns.is_empty());
+
+    // Computed columns have no declared type, and are always nullable
+    let r = schema.view(&"r".into()).unwrap();
+    assert_eq!(
+        nullability(r),
+        vec![("z".into(), false), ("y1".into(), true)]
+    );
+    assert_eq!(r.columns[0].sql_type, t.columns[2].sql_type);
+    assert_eq!(r.columns[1].sql_type, None);
+    assert_eq!(r.key_columns.len(), 1);
+
+    // Views without parameters have no key columns, even though they're keyed by a bogokey
+    let s = schema.view(&"s".into()).unwrap();
+    assert!(s.key_columns.is_empty());
+    assert_eq!(
+        s.columns
+            .iter()
+            .map(|col| col.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["z"]
+    );
+
+    // Columns of views with outer joins are always nullable
+    let q = schema.view(&"q".into()).unwrap();
+    assert!(q.columns.iter().all(|col| col.nullable));
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn slow_replays() {
+    let authority_store = Arc::new(LocalAuthorityStore::new());
+    let authority = Arc::new(Authority::from(LocalAuthority::new_with_store(
+        authority_store,
+    )));
+    let mut builder = Builder::for_tests();
+    builder.set_sharding(None);
+    builder.set_persistence(get_persistence_params("slow_replays"));
+    // Treat every replay as slow
+    builder.set_slow_replay_threshold(Some(Duration::ZERO));
+    let (mut g, shutdown_tx) = builder.start_local_custom(authority).await.unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q FROM SELECT * FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert!(g.slow_replays().await.unwrap().is_empty());
+
+    let mut t = g.table("t").await.unwrap();
+    t.insert(vec![DfValue::from(1), DfValue::from(2)])
+        .await
+        .unwrap();
+
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    eventually!(run_test: {
+        q.lookup(&[DfValue::from(1)], true).await.unwrap().into_vec()
+    }, then_assert: |rows| {
+        assert_eq!(rows, vec![vec![DfValue::from(1), DfValue::from(2)]])
+    });
+
+    let replays = g.slow_replays().await.unwrap();
+    assert_eq!(replays.len(), 1);
+    let replay = &replays[0];
+    assert_eq!(
+        replay.replay.key,
+        KeyComparison::Equal(vec1![DfValue::from(1)])
+    );
+    assert!(!replay.replay.domain_timings.is_empty());
+    let path = replay.path.as_ref().unwrap();
+    assert_eq!(path.tag, replay.replay.tag);
+    assert_eq!(
+        path.segments.last().unwrap().node_index,
+        replay.replay.reader
+    );
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn deactivate_low_hit_rate_views() {
+    let authority_store = Arc::new(LocalAuthorityStore::new());
+    let authority = Arc::new(Authority::from(LocalAuthority::new_with_store(
+        authority_store,
+    )));
+    let mut builder = Builder::for_tests();
+    builder.set_sharding(None);
+    builder.set_persistence(get_persistence_params("deactivate_low_hit_rate_views"));
+    // Any materialized state at all exceeds the memory target
+    builder.set_view_eviction(Some(ViewEvictionConfig {
+        interval: Duration::from_millis(200),
+        memory_target: 0,
+        min_hit_rate: 50,
+    }));
+    let (mut g, shutdown_tx) = builder.start_local_custom(authority).await.unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q FROM SELECT * FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("t").await.unwrap();
+    t.insert(vec![DfValue::from(1), DfValue::from(2)])
+        .await
+        .unwrap();
+
+    // Fill the reader, and then stop reading from it so that its hit rate drops to zero
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    eventually!(run_test: {
+        q.lookup(&[DfValue::from(1)], true).await.unwrap().into_vec()
+    }, then_assert: |rows| {
+        assert_eq!(rows, vec![vec![DfValue::from(1), DfValue::from(2)]])
+    });
+
+    eventually!(run_test: {
+        g.deactivated_views().await.unwrap()
+    }, then_assert: |deactivated| {
+        assert_eq!(deactivated.len(), 1);
+        assert_eq!(deactivated[0].name, Relation::from("q"));
+        assert_eq!(deactivated[0].hit_rate, Some(0));
+    });
+    g.view("q").await.unwrap_err();
+
+    g.reactivate_view(&"q".into(), Dialect::DEFAULT_MYSQL)
+        .await
+        .unwrap();
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    assert_eq!(
+        q.lookup(&[DfValue::from(1)], true)
+            .await
+            .unwrap()
+            .into_vec(),
+        vec![vec![DfValue::from(1), DfValue::from(2)]]
+    );
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn deactivate_and_reactivate_view() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("deactivate_and_reactivate_view").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE VIEW v AS SELECT * FROM t;
+             CREATE CACHE q FROM SELECT * FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    g.deactivate_view(&"v".into()).await.unwrap_err();
+    g.deactivate_view(&"nonexistent".into()).await.unwrap_err();
+    g.reactivate_view(&"q".into(), Dialect::DEFAULT_MYSQL)
+        .await
+        .unwrap_err();
+
+    g.deactivate_view(&"q".into()).await.unwrap();
+    let deactivated = g.deactivated_views().await.unwrap();
+    assert_eq!(deactivated.len(), 1);
+    assert_eq!(deactivated[0].name, Relation::from("q"));
+    assert_eq!(deactivated[0].hit_rate, None);
+    g.view("q").await.unwrap_err();
+
+    g.reactivate_view(&"q".into(), Dialect::DEFAULT_MYSQL)
+        .await
+        .unwrap();
+    assert!(g.deactivated_views().await.unwrap().is_empty());
+    g.view("q").await.unwrap();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn negative_entries() {
+    let authority_store = Arc::new(LocalAuthorityStore::new());
+    let authority = Arc::new(Authority::from(LocalAuthority::new_with_store(
+        authority_store,
+    )));
+    let mut builder = Builder::for_tests();
+    builder.set_sharding(None);
+    builder.set_persistence(get_persistence_params("negative_entries"));
+    builder.set_max_negative_entries(Some(1));
+    let (mut g, shutdown_tx) = builder.start_local_custom(authority).await.unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q FROM SELECT * FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("t").await.unwrap();
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+
+    // Keys which don't exist upstream are cached, but only up to the maximum
+    for x in [1, 2] {
+        let rows = q.lookup(&[DfValue::from(x)], true).await.unwrap();
+        assert!(rows.into_vec().is_empty());
+    }
+    eventually!(q.keys().await.unwrap() == vec![vec![DfValue::from(2)]]);
+
+    // Once a row is written to a key, it's no longer evicted as a negative entry
+    t.insert(vec![DfValue::from(2), DfValue::from(3)])
+        .await
+        .unwrap();
+    eventually!(run_test: {
+        q.lookup(&[DfValue::from(2)], true).await.unwrap().into_vec()
+    }, then_assert: |rows| {
+        assert_eq!(rows, vec![vec![DfValue::from(2), DfValue::from(3)]])
+    });
+    let rows = q.lookup(&[DfValue::from(3)], true).await.unwrap();
+    assert!(rows.into_vec().is_empty());
+
+    let mut keys = q.keys().await.unwrap();
+    keys.sort();
+    assert_eq!(keys, vec![vec![DfValue::from(2)], vec![DfValue::from(3)]]);
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn prioritized_replays() {
+    let mut builder = Builder::for_tests();
+    builder.set_sharding(None);
+    builder.set_persistence(get_persistence_params("prioritized_replays"));
+    builder.set_prioritize_replays(true);
+    builder.set_max_consecutive_replay_packets(1);
+    let (mut g, shutdown_tx) = builder.start_local().await.unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q FROM SELECT * FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("t").await.unwrap();
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+
+    // Replays which overtake queued writes must still observe every write to the keys they fill
+    for y in 0..10 {
+        t.insert_many((0..10).map(|x| vec![DfValue::from(x), DfValue::from(y)]))
+            .await
+            .unwrap();
+        let _ = q.lookup(&[DfValue::from(y)], false).await.unwrap();
+    }
+
+    for x in 0..10 {
+        eventually!(run_test: {
+            q.lookup(&[DfValue::from(x)], true).await.unwrap().into_vec()
+        }, then_assert: |rows| {
+            assert_eq!(rows.len(), 10)
+        });
+    }
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn update_runtime_config() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("update_runtime_config").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q FROM SELECT * FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // Invalid updates are rejected, and not recorded
+    g.update_runtime_config(RuntimeConfigUpdate {
+        memory_check_frequency: Some(Duration::ZERO),
+        ..Default::default()
+    })
+    .await
+    .unwrap_err();
+    assert!(g.runtime_config_changes().await.unwrap().is_empty());
+
+    let update = RuntimeConfigUpdate {
+        memory_limit: Some(0),
+        slow_replay_threshold: Some(Some(Duration::ZERO)),
+        ..Default::default()
+    };
+    g.update_runtime_config(update.clone()).await.unwrap();
+
+    let changes = g.runtime_config_changes().await.unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].update, update);
+
+    // The new slow replay threshold should have made it to the already-running domains
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    q.lookup(&[DfValue::from(1)], true).await.unwrap();
+    assert_eq!(g.slow_replays().await.unwrap().len(), 1);
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn runtime_config_changes_survive_restart() {
+    let authority_store = Arc::new(LocalAuthorityStore::new());
+    let authority = Arc::new(Authority::from(LocalAuthority::new_with_store(
+        authority_store.clone(),
+    )));
+
+    let update = RuntimeConfigUpdate {
+        slow_replay_threshold: Some(Some(Duration::ZERO)),
+        ..Default::default()
+    };
+
+    {
+        let (mut g, shutdown_tx) = Builder::for_tests().start(authority.clone()).await.unwrap();
+        g.backend_ready().await;
+        g.update_runtime_config(update.clone()).await.unwrap();
+
+        shutdown_tx.shutdown().await;
+        if let Authority::LocalAuthority(l) = authority.as_ref() {
+            l.delete_ephemeral();
+        }
+    }
+
+    let authority = Arc::new(Authority::from(LocalAuthority::new_with_store(
+        authority_store.clone(),
+    )));
+    let (mut g, shutdown_tx) = Builder::for_tests().start(authority.clone()).await.unwrap();
+    g.backend_ready().await;
+
+    let changes = g.runtime_config_changes().await.unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].update, update);
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pause_replication_without_upstream() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("pause_replication_without_upstream").await;
+
+    assert_eq!(
+        g.replication_pause_state().await.unwrap(),
+        ReplicationPauseState::Running
+    );
+    g.pause_replication().await.unwrap_err();
+    assert_eq!(
+        g.replication_pause_state().await.unwrap(),
+        ReplicationPauseState::Running
+    );
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn multiple_simultaneous_migrations() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("multiple_simultaneous_migrations").await;
+    let mut g2 = g.clone();
+
+    g.extend_recipe(ChangeList::from_change(
+        Change::CreateTable {
+            statement: parse_create_table(nom_sql::Dialect::MySQL, "CREATE TABLE t (x int, y int)")
+                .unwrap(),
+            pg_meta: None,
+        },
+        Dialect::DEFAULT_MYSQL,
+    ))
+    .await
+    .unwrap();
+
+    let mut t = g.table("t").await.unwrap();
+
+    t.insert_many(vec![
+        vec![DfValue::from(1), DfValue::from(2)],
+        vec![DfValue::from(3), DfValue::from(4)],
+    ])
+    .await
+    .unwrap();
+
+    let (r1, r2) = join!(
+        g.extend_recipe(ChangeList::from_change(
+            Change::CreateCache(CreateCache {
+                name: Some("q1".into()),
+                statement: Box::new(
+                    parse_select_statement(nom_sql::Dialect::MySQL, "SELECT * FROM t WHERE x = ?")
+                        .unwrap()
+                ),
+                always: false,
+                frontier_strategy: None,
+                source: Default::default(),
+                original_sql: None,
+                disable_predicate_pushdown: false,
+                standby: false,
+            }),
+            Dialect::DEFAULT_MYSQL
+        )),
+        g2.extend_recipe(ChangeList::from_change(
+            Change::CreateCache(CreateCache {
+                name: Some("q2".into()),
+                statement: Box::new(
+                    parse_select_statement(nom_sql::Dialect::MySQL, "SELECT * FROM t WHERE y = ?")
+                        .unwrap()
+                ),
+                always: false,
+                frontier_strategy: None,
+                source: Default::default(),
+                original_sql: None,
+                disable_predicate_pushdown: false,
+                standby: false,
+            }),
+            Dialect::DEFAULT_MYSQL
+        ))
+    );
+    r1.unwrap();
+    r2.unwrap();
+
+    let mut q1 = g.view("q1").await.unwrap().into_reader_handle().unwrap();
+    let mut q2 = g.view("q1").await.unwrap().into_reader_handle().unwrap();
+
+    let res1 = q1.lookup(&[1.into()], true).await.unwrap().into_vec();
+    let res2 = q2.lookup(&[3.into()], true).await.unwrap().into_vec();
+
+    assert_eq!(res1, vec![vec![1.into(), 2.into()]]);
+    assert_eq!(res2, vec![vec![3.into(), 4.into()]]);
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn simultaneous_migrations_fail_independently() {
+    let (mut g, shutdown_tx) =
+        start_simple_unsharded("simultaneous_migrations_fail_independently").await;
+    let mut g2 = g.clone();
+
+    g.extend_recipe(
+        ChangeList::from_str("CREATE TABLE t (x int, y int);", Dialect::DEFAULT_MYSQL).unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let (r1, r2) = join!(
+        g.extend_recipe(
+            ChangeList::from_str(
+                "CREATE CACHE q1 FROM SELECT * FROM nonexistent WHERE x = ?;",
+                Dialect::DEFAULT_MYSQL
+            )
+            .unwrap()
+        ),
+        g2.extend_recipe(
+            ChangeList::from_str(
+                "CREATE CACHE q2 FROM SELECT * FROM t WHERE y = ?;",
+                Dialect::DEFAULT_MYSQL
+            )
+            .unwrap()
+        )
+    );
+    r1.unwrap_err();
+    r2.unwrap();
+
+    let views = g.views().await.unwrap();
+    assert!(!views.contains_key(&Relation::from("q1")));
+    assert!(views.contains_key(&Relation::from("q2")));
+
+    let mut t = g.table("t").await.unwrap();
+    t.insert(vec![DfValue::from(1), DfValue::from(2)])
+        .await
+        .unwrap();
+    sleep().await;
+
+    let mut q2 = g.view("q2").await.unwrap().into_reader_handle().unwrap();
+    let res = q2.lookup(&[2.into()], true).await.unwrap().into_vec();
+    assert_eq!(res, vec![vec![1.into(), 2.into()]]);
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cancel_migration() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("cancel_migration").await;
+
+    g.extend_recipe(
+        ChangeList::from_str("CREATE TABLE t (x int, y int);", Dialect::DEFAULT_MYSQL).unwrap(),
+    )
+    .await
+    .unwrap();
+    let mut t = g.table("t").await.unwrap();
+    t.insert(vec![DfValue::from(1), DfValue::from(2)])
+        .await
+        .unwrap();
+
+    let migration_id = g
+        .extend_recipe_async(
+            ChangeList::from_str(
+                "CREATE CACHE q FROM SELECT * FROM t WHERE x = ?;",
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    g.cancel_migration(migration_id).await.unwrap();
+
+    let status = loop {
+        let status = g.migration_status(migration_id).await.unwrap();
+        if !status.is_pending() {
+            break status;
+        }
+        g.migration_progress(migration_id).await.unwrap();
+        sleep().await;
+    };
+
+    // The migration might have finished before we got around to cancelling it, but if it was
+    // cancelled the cache must not exist
+    match status {
+        MigrationStatus::Done => {
+            assert!(g.views().await.unwrap().contains_key(&Relation::from("q")))
+        }
+        MigrationStatus::Failed(ReadySetError::MigrationCancelled) => {
+            assert!(!g.views().await.unwrap().contains_key(&Relation::from("q")))
+        }
+        status => panic!("Unexpected migration status: {status}"),
+    }
+    g.migration_progress(migration_id).await.unwrap_err();
+
+    // Either way, the graph should still be usable
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE CACHE q2 FROM SELECT * FROM t WHERE y = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    let mut q2 = g.view("q2").await.unwrap().into_reader_handle().unwrap();
+    let res = q2.lookup(&[2.into()], true).await.unwrap().into_vec();
+    assert_eq!(res, vec![vec![1.into(), 2.into()]]);
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn drop_view() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("drop_view").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t1 (id int);
+         CREATE CACHE t1_view FROM SELECT * FROM t1;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE CACHE t1_select FROM SELECT * FROM t1_view",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    g.extend_recipe(ChangeList::from_str("DROP CACHE t1_select;", Dialect::DEFAULT_MYSQL).unwrap())
+        .await
+        .unwrap();
+
+    g.extend_recipe(ChangeList::from_str("DROP VIEW t1_view;", Dialect::DEFAULT_MYSQL).unwrap())
+        .await
+        .unwrap();
+
+    let select_from_view_res = g
+        .extend_recipe(
+            ChangeList::from_str(
+                "CREATE CACHE t1_select FROM SELECT * FROM t1_view",
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap(),
+        )
+        .await;
+    let err = select_from_view_res.unwrap_err();
+    assert!(err.to_string().contains("t1_view"));
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn drop_view_schema_qualified() {
+    readyset_tracing::init_test_logging();
+    let (mut g, shutdown_tx) = start_simple_unsharded("drop_view_schema_qualified").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE public.t1 (id int);
+             CREATE VIEW public.t1_view AS SELECT * FROM public.t1;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE CACHE t1_select FROM SELECT * FROM t1_view",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap()
+        .with_schema_search_path(vec!["public".into()]),
+    )
+    .await
+    .unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str("DROP VIEW public.t1_view;", Dialect::DEFAULT_MYSQL).unwrap(),
+    )
+    .await
+    .unwrap();
+
+    g.view("t1_select").await.unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn read_from_dropped_query() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("read_from_dropped_query").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t1 (id int);
+         CREATE CACHE q FROM SELECT id FROM t1;",
             Dialect::DEFAULT_MYSQL,
         )
         .unwrap(),
@@ -9397,3 +12333,150 @@ async fn evict_single_intermediate_state() {
 
     shutdown_tx.shutdown().await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn ignored_columns() {
+    let mut builder = Builder::for_tests();
+    builder.set_persistence(get_persistence_params("ignored_columns"));
+    builder.set_replicator_config(UpstreamConfig {
+        replication_columns_ignore: Some("public.users.ssn, public.accounts.ssn".to_owned().into()),
+        ..Default::default()
+    });
+    let (mut g, shutdown_tx) = builder.start_local().await.unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE public.users (id int PRIMARY KEY, name text, ssn text);",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE CACHE q FROM SELECT id, name FROM users WHERE id = ?",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap()
+        .with_schema_search_path(vec!["public".into()]),
+    )
+    .await
+    .unwrap();
+
+    for query in [
+        "SELECT id, ssn FROM users WHERE id = ?",
+        "SELECT u.id FROM users u WHERE u.ssn = ?",
+        "SELECT * FROM users WHERE id = ?",
+    ] {
+        let err = g
+            .extend_recipe(
+                ChangeList::from_str(format!("CREATE CACHE FROM {query}"), Dialect::DEFAULT_MYSQL)
+                    .unwrap()
+                    .with_schema_search_path(vec!["public".into()]),
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            err.any_cause(|e| matches!(e, ReadySetError::ColumnNotReplicated { .. })),
+            "{query}: {err}"
+        );
+    }
+
+    // Primary key columns can't be ignored
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE public.accounts (ssn text PRIMARY KEY, balance int);",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn recovery_status_with_healthy_workers() {
+    let mut builder = Builder::for_tests();
+    builder.set_persistence(get_persistence_params(
+        "recovery_status_with_healthy_workers",
+    ));
+    builder.set_worker_heartbeat_interval(Duration::from_millis(100));
+    builder.set_worker_failure_timeout(Duration::from_millis(500));
+    let (mut g, shutdown_tx) = builder.start_local().await.unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x int, y int);
+             CREATE CACHE q FROM SELECT x, y FROM t WHERE x = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // Give the worker monitor a chance to send enough heartbeats to consider the worker failed if
+    // it wasn't responding to them
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    let status = g.recovery_status().await.unwrap();
+    assert_eq!(status.failed_workers, 0);
+    assert!(status.total_domains > 0);
+    assert!(status.is_recovered(), "{status:?}");
+    assert!(!status.recovery_running);
+
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    q.lookup(&[1.into()], true).await.unwrap();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn drain_worker_and_resnapshot_table_errors() {
+    let (mut g, shutdown_tx) =
+        start_simple_unsharded("drain_worker_and_resnapshot_table_errors").await;
+    g.extend_recipe(
+        ChangeList::from_str("CREATE TABLE t (x int);", Dialect::DEFAULT_MYSQL).unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // There's no upstream database to snapshot the table from
+    g.resnapshot_table(&"t".into()).await.unwrap_err();
+    g.drain_worker(&"http://127.0.0.1:1/".parse().unwrap())
+        .await
+        .unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn health_check_endpoints() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("health_check_endpoints").await;
+    g.backend_ready().await;
+    let address = g.get_address().clone();
+
+    let live = reqwest::get(address.join("/health/live").unwrap())
+        .await
+        .unwrap();
+    assert!(live.status().is_success());
+
+    eventually!(run_test: {
+        let res = reqwest::get(address.join("/health/ready").unwrap())
+            .await
+            .unwrap();
+        (res.status(), res.json::<serde_json::Value>().await.unwrap())
+    }, then_assert: |(status, report)| {
+        assert!(status.is_success(), "{report}");
+        assert_eq!(report["status"], "pass");
+        for check in ["server", "authority", "controller", "replication", "domains"] {
+            assert_eq!(report["checks"][check]["status"], "pass", "{report}");
+        }
+    });
+
+    shutdown_tx.shutdown().await;
+}
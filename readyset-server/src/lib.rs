@@ -130,7 +130,10 @@
 //! This crate also provides `LocalAuthority`, which allows you to _embed_ a `noriad` worker, and
 //! not bother with setting up Consul or multiple workers. This provides no fault-tolerance and
 //! no multi-machine operations, but can be a convenient way to set things up for development and
-//! testing. See `Builder::build_local` or the `basic-recipe` example for details.
+//! testing. See `Builder::build_local` or the `basic-recipe` example for details. Without an
+//! upstream database to resnapshot from, the schemas of base tables can be evolved in place by
+//! extending the recipe with `ALTER TABLE ... ADD COLUMN` (with a constant `DEFAULT` used to
+//! backfill existing rows) and `ALTER TABLE ... DROP COLUMN`.
 //!
 //! # I'm a visual learner
 //!
@@ -405,6 +408,7 @@ mod builder;
 mod controller;
 mod coordination;
 mod handle;
+mod health_check;
 mod http_router;
 
 /// Utilities to create all server components.
@@ -432,6 +436,7 @@ use controller::migrate::materialization;
 pub use controller::migrate::materialization::FrontierStrategy;
 pub use controller::replication::{ReplicationOptions, ReplicationStrategy};
 use controller::sql;
+pub use controller::view_eviction::ViewEvictionConfig;
 use database_utils::UpstreamConfig;
 pub use dataflow::{DurabilityMode, PersistenceParameters};
 pub use petgraph::graph::NodeIndex;
@@ -446,7 +451,7 @@ pub mod manual {
     pub use dataflow::node::special::Base;
     pub use dataflow::ops;
 
-    pub use crate::controller::migrate::Migration;
+    pub use crate::controller::migrate::{Migration, MigrationObserver};
 }
 
 use std::net::{IpAddr, ToSocketAddrs};
@@ -454,8 +459,10 @@ use std::path::PathBuf;
 use std::time::Duration;
 
 use anyhow::anyhow;
+use clap::builder::NonEmptyStringValueParser;
 use clap::Args;
 use dataflow::DomainConfig;
+use readyset_client::consensus::{parse_labels, LabelSelector, WorkerLabels};
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 
@@ -480,6 +487,15 @@ pub struct Config {
     pub(crate) reuse: Option<ReuseConfigType>,
     /// If set to true (the default), failing tokio tasks will cause a full-process abort.
     pub(crate) abort_on_task_failure: bool,
+    /// If set to true, reads against the caches of a domain which has failed (eg by panicking) are
+    /// rejected with [`ViewUnavailable`](readyset_errors::ReadySetError::ViewUnavailable) until
+    /// the controller has recovered it, rather than waiting on upqueries the failed domain will
+    /// never answer.
+    ///
+    /// Either way, a failed domain never aborts the process, and the controller recovers just that
+    /// domain and the domains downstream of it, leaving all other caches serving.
+    #[serde(default)]
+    pub(crate) isolate_domain_panics: bool,
     /// Configuration for converting SQL to MIR
     pub(crate) mir_config: sql::mir::Config,
     #[serde(flatten)]
@@ -496,12 +512,61 @@ pub struct Config {
     /// Interval on which to automatically run recovery as long as there are unscheduled domains
     #[serde(default = "default_background_recovery_interval")]
     pub(crate) background_recovery_interval: Duration,
+    /// Interval on which the leader sends heartbeats to each worker to check that it's still
+    /// able to respond to requests
+    #[serde(default = "default_worker_heartbeat_interval")]
+    pub(crate) worker_heartbeat_interval: Duration,
+    /// Amount of time a worker can go without responding to the leader's heartbeats before the
+    /// leader considers it failed and recovers the domains that were running on it elsewhere
+    #[serde(default = "default_worker_failure_timeout")]
+    pub(crate) worker_failure_timeout: Duration,
+    /// Amount of time replication from the upstream database can be stopped for before the
+    /// `/health/ready` endpoint reports the server as not ready
+    #[serde(default = "default_max_ready_replication_lag")]
+    pub(crate) max_ready_replication_lag: Duration,
+    /// Amount of time to wait, when shutting down, for the domains run by this server to finish
+    /// handling the writes and replays they have in flight and persist their base table state
+    #[serde(default = "default_shutdown_deadline")]
+    pub(crate) shutdown_deadline: Duration,
+    /// Interval on which to profile the data in every base table, to estimate the size of the
+    /// state of nodes added by migrations
+    #[serde(default = "default_table_profile_interval")]
+    pub(crate) table_profile_interval: Duration,
+    /// Label selector which workers must match for domains containing base tables to be scheduled
+    /// onto them
+    #[serde(default)]
+    pub(crate) base_table_worker_selector: LabelSelector,
+    /// Configuration for automatically deactivating caches with a low hit rate while the
+    /// materialized state in the graph exceeds a memory target. If `None`, caches are never
+    /// deactivated automatically.
+    #[serde(default)]
+    pub(crate) view_eviction: Option<ViewEvictionConfig>,
 }
 
 fn default_background_recovery_interval() -> Duration {
     Duration::from_secs(20)
 }
 
+fn default_worker_heartbeat_interval() -> Duration {
+    Duration::from_secs(5)
+}
+
+fn default_worker_failure_timeout() -> Duration {
+    Duration::from_secs(30)
+}
+
+fn default_max_ready_replication_lag() -> Duration {
+    Duration::from_secs(30)
+}
+
+fn default_shutdown_deadline() -> Duration {
+    Duration::from_secs(15)
+}
+
+fn default_table_profile_interval() -> Duration {
+    Duration::from_secs(300)
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -519,11 +584,27 @@ impl Default for Config {
                 table_request_timeout: Duration::from_millis(1800000),
                 eviction_kind: dataflow::EvictionKind::Random,
                 verbose_metrics: false,
+                max_tracked_extremum_values:
+                    dataflow::ops::grouped::extremum::DEFAULT_MAX_TRACKED_EXTREMUM_VALUES,
+                slow_replay_threshold: None,
+                log_slow_replays: false,
+                negative_entry_ttl: None,
+                max_negative_entries: None,
+                prioritize_replays: false,
+                max_consecutive_replay_packets: dataflow::DEFAULT_MAX_CONSECUTIVE_REPLAY_PACKETS,
+                overload_queue_depth: None,
+                overload_lag: None,
+                orphaned_replay_timeout: None,
+                profile_replays: false,
+                record_pool_size: dataflow::DEFAULT_RECORD_POOL_SIZE,
+                operator_threads: 0,
+                share_full_state_between_shards: false,
             },
             persistence: Default::default(),
             min_workers: 1,
             reuse: None,
             abort_on_task_failure: true,
+            isolate_domain_panics: false,
             mir_config: Default::default(),
             replicator_statement_logging: false,
             replicator_config: Default::default(),
@@ -531,6 +612,13 @@ impl Default for Config {
             upquery_timeout: Duration::from_millis(5000),
             worker_request_timeout: Duration::from_millis(1800000),
             background_recovery_interval: default_background_recovery_interval(),
+            worker_heartbeat_interval: default_worker_heartbeat_interval(),
+            worker_failure_timeout: default_worker_failure_timeout(),
+            max_ready_replication_lag: default_max_ready_replication_lag(),
+            shutdown_deadline: default_shutdown_deadline(),
+            table_profile_interval: default_table_profile_interval(),
+            base_table_worker_selector: Default::default(),
+            view_eviction: None,
         }
     }
 }
@@ -604,10 +692,28 @@ pub struct WorkerOptions {
     #[arg(long, default_value = "0", env = "NORIA_SHARDS", hide = true)]
     pub shards: usize,
 
-    /// Volume associated with the server.
-    #[arg(long, env = "VOLUME_ID", hide = true)]
+    /// Identifier for the persistent volume the server stores base table data on, such as the
+    /// name of the Kubernetes PersistentVolumeClaim mounted at the storage directory. Once a base
+    /// table with durable state is scheduled onto a server with a volume, it is only ever
+    /// rescheduled onto a server with the same volume.
+    #[arg(long, env = "VOLUME_ID", hide = true, value_parser = NonEmptyStringValueParser::new())]
     pub volume_id: Option<VolumeId>,
 
+    /// Labels describing the server, as a comma-separated list of `key=value` pairs, which are
+    /// matched against the label selectors restricting where base tables can be scheduled.
+    #[arg(long, env = "WORKER_LABELS", hide = true, value_parser = parse_labels)]
+    pub worker_labels: Option<WorkerLabels>,
+
+    /// The availability zone the server runs in. Shorthand for setting the
+    /// `topology.kubernetes.io/zone` label in `--worker-labels`.
+    #[arg(long, env = "ZONE", hide = true)]
+    pub zone: Option<String>,
+
+    /// Only schedule domains containing base tables onto servers whose labels match this
+    /// comma-separated list of `key=value` pairs.
+    #[arg(long, env = "BASE_TABLE_WORKER_SELECTOR", hide = true)]
+    pub base_table_worker_selector: Option<LabelSelector>,
+
     /// Enable experimental support for TopK in dataflow.
     ///
     /// NOTE If enabled, this must be set for all ReadySet processes (both servers and adapters).
@@ -672,6 +778,90 @@ pub struct WorkerOptions {
     )]
     pub background_recovery_interval_seconds: u64,
 
+    /// Interval, in seconds, on which the leader sends heartbeats to each worker to check that
+    /// it's still able to respond to requests
+    #[arg(
+        long,
+        env = "WORKER_HEARTBEAT_INTERVAL_SECONDS",
+        default_value = "5",
+        hide = true
+    )]
+    pub worker_heartbeat_interval_seconds: u64,
+
+    /// Number of seconds a worker can go without responding to the leader's heartbeats before
+    /// it's considered failed, and the domains that were running on it are recovered on other
+    /// workers
+    #[arg(
+        long,
+        env = "WORKER_FAILURE_TIMEOUT_SECONDS",
+        default_value = "30",
+        hide = true
+    )]
+    pub worker_failure_timeout_seconds: u64,
+
+    /// Number of seconds replication from the upstream database can be stopped for before the
+    /// server's `/health/ready` endpoint reports it as not ready
+    #[arg(
+        long,
+        env = "MAX_READY_REPLICATION_LAG_SECONDS",
+        default_value = "30",
+        hide = true
+    )]
+    pub max_ready_replication_lag_seconds: u64,
+
+    /// Number of seconds to wait, when shutting down, for domains to finish handling the writes
+    /// and replays they have in flight and persist their base table state before exiting
+    #[arg(
+        long,
+        env = "SHUTDOWN_DEADLINE_SECONDS",
+        default_value = "15",
+        hide = true
+    )]
+    pub shutdown_deadline_seconds: u64,
+
+    /// Interval, in seconds, on which to profile the data in a sample of the rows of every base
+    /// table, to estimate the size of the state of the nodes added by migrations
+    #[arg(
+        long,
+        env = "TABLE_PROFILE_INTERVAL_SECONDS",
+        default_value = "300",
+        hide = true
+    )]
+    pub table_profile_interval_seconds: u64,
+
+    /// If set, the leader automatically deactivates caches whose hit rate is below
+    /// `--view-eviction-min-hit-rate` while the total size in bytes of the materialized state of
+    /// all nodes other than base tables exceeds this value, removing their dataflow nodes while
+    /// keeping their definitions so that they can be reactivated later
+    #[arg(long, env = "VIEW_EVICTION_MEMORY_TARGET", hide = true)]
+    pub view_eviction_memory_target: Option<u64>,
+
+    /// Percentage of reads against a cache which must hit for it not to be deactivated when
+    /// `--view-eviction-memory-target` is exceeded
+    #[arg(
+        long,
+        env = "VIEW_EVICTION_MIN_HIT_RATE",
+        default_value = "10",
+        value_parser = clap::value_parser!(u8).range(0..=100),
+        hide = true
+    )]
+    pub view_eviction_min_hit_rate: u8,
+
+    /// Interval, in seconds, on which to check memory usage and the hit rates of caches when
+    /// `--view-eviction-memory-target` is set
+    #[arg(
+        long,
+        env = "VIEW_EVICTION_INTERVAL_SECONDS",
+        default_value = "60",
+        hide = true
+    )]
+    pub view_eviction_interval_seconds: u64,
+
+    /// Reject reads against the caches of a domain which has failed (eg by panicking) until it's
+    /// been recovered, rather than letting them wait on upqueries which will never be answered
+    #[arg(long, env = "ISOLATE_DOMAIN_PANICS", hide = true)]
+    pub isolate_domain_panics: bool,
+
     /// Whether to emit verbose metrics for the domains on this worker. This should be used very
     /// sparingly, as the metrics emitted will have high label cardinality and can be quite
     /// expensive!
@@ -682,6 +872,94 @@ pub struct WorkerOptions {
         hide = true
     )]
     pub verbose_domain_metrics: bool,
+
+    /// The maximum number of distinct values to track for each group of a MIN or MAX aggregate.
+    /// Higher values use more memory, but make it less likely that deleting the current minimum or
+    /// maximum of a group requires re-reading every row in that group.
+    #[arg(
+        long,
+        env = "MAX_TRACKED_EXTREMUM_VALUES",
+        default_value = "32",
+        hide = true
+    )]
+    pub max_tracked_extremum_values: usize,
+
+    /// Record replays to fill misses in caches which take longer than this many milliseconds
+    /// (from the miss to the fill) in a log of slow replays, which can be retrieved from the
+    /// controller. If unset, slow replays are not tracked.
+    #[arg(long, env = "SLOW_REPLAY_THRESHOLD_MS", hide = true)]
+    pub slow_replay_threshold_ms: Option<u64>,
+
+    /// Also log replays which exceed `--slow-replay-threshold-ms` at the WARN level
+    #[arg(long, env = "LOG_SLOW_REPLAYS", hide = true)]
+    pub log_slow_replays: bool,
+
+    /// Record the time spent processing replays in each node along their replay paths in metrics,
+    /// aggregated by cache and node type
+    #[arg(long, env = "PROFILE_REPLAYS", hide = true)]
+    pub profile_replays: bool,
+
+    /// The maximum number of emptied record buffers each domain holds on to for reuse when
+    /// copying writes. Set to 0 to allocate a new buffer for every copy instead. Only has an
+    /// effect if ReadySet was built with the `record_pool` feature.
+    #[arg(long, env = "RECORD_POOL_SIZE", default_value = "64", hide = true)]
+    pub record_pool_size: usize,
+
+    /// The number of worker threads each domain starts to filter and project large packets in
+    /// parallel. Set to 0 or 1 to process all packets on the domain's own thread.
+    #[arg(long, env = "OPERATOR_THREADS", default_value = "0", hide = true)]
+    pub operator_threads: usize,
+
+    /// Store the rows of all the shards of a fully materialized node which run on the same worker
+    /// in a single state, rather than keeping a separate copy of the state for each shard
+    #[arg(long, env = "SHARE_FULL_STATE_BETWEEN_SHARDS", hide = true)]
+    pub share_full_state_between_shards: bool,
+
+    /// Evict keys in caches which were filled with no rows (because they don't exist upstream)
+    /// after this many seconds, unless rows have since been written to them. If unset, such keys
+    /// are only evicted when the cache is over its memory limit.
+    #[arg(long, env = "NEGATIVE_ENTRY_TTL_SECONDS", hide = true)]
+    pub negative_entry_ttl_seconds: Option<u64>,
+
+    /// The maximum number of keys which were filled with no rows to keep in each cache at once.
+    /// If unset, the number of such keys is only bounded by the cache's memory limit.
+    #[arg(long, env = "MAX_NEGATIVE_ENTRIES", hide = true)]
+    pub max_negative_entries: Option<usize>,
+
+    /// Handle upquery requests and partial replays in each domain ahead of queued writes, to keep
+    /// read latency low under write-heavy load
+    #[arg(long, env = "PRIORITIZE_REPLAYS", hide = true)]
+    pub prioritize_replays: bool,
+
+    /// If `--prioritize-replays` is set, the maximum number of replay packets each domain handles
+    /// in a row while writes are queued, after which a single write is handled
+    #[arg(
+        long,
+        env = "MAX_CONSECUTIVE_REPLAY_PACKETS",
+        default_value = "16",
+        hide = true
+    )]
+    pub max_consecutive_replay_packets: usize,
+
+    /// Consider a domain overloaded while it has at least this many packets queued, and reject
+    /// reads which would have to wait for it to fill a miss so that they can be sent to the
+    /// upstream database instead. If unset, queue depth isn't used to detect overload.
+    #[arg(long, env = "OVERLOAD_QUEUE_DEPTH", hide = true)]
+    pub overload_queue_depth: Option<usize>,
+
+    /// Consider a domain overloaded once it's had packets queued continuously for longer than this
+    /// many milliseconds, and reject reads which would have to wait for it to fill a miss so that
+    /// they can be sent to the upstream database instead. If unset, lag isn't used to detect
+    /// overload.
+    #[arg(long, env = "OVERLOAD_LAG_MS", hide = true)]
+    pub overload_lag_ms: Option<u64>,
+
+    /// Stop tracking replays to fill misses in caches which have been in flight for longer than
+    /// this many seconds without any reads still waiting for them, for example because the
+    /// clients which issued those reads disconnected. If unset, replays are tracked until they
+    /// complete.
+    #[arg(long, env = "ORPHANED_REPLAY_TIMEOUT_SECONDS", hide = true)]
+    pub orphaned_replay_timeout_seconds: Option<u64>,
 }
 
 impl WorkerOptions {
@@ -699,9 +977,7 @@ impl WorkerOptions {
     }
 }
 
-// TODO(justin): Change VolumeId type when we know this fixed size.
-/// Id associated with the worker server's volume.
-pub type VolumeId = String;
+pub use readyset_client::consensus::VolumeId;
 
 #[cfg(test)]
 mod tests {
@@ -216,6 +216,9 @@ fn main() -> anyhow::Result<()> {
     if let Some(volume_id) = &opts.worker_options.volume_id {
         info!(%volume_id);
     }
+    if let Some(zone) = &opts.worker_options.zone {
+        info!(%zone);
+    }
 
     let deployment_dir = opts
         .worker_options
@@ -233,6 +236,7 @@ fn main() -> anyhow::Result<()> {
         _ => opts.authority_address.clone(),
     };
 
+    let shutdown_deadline = Duration::from_secs(opts.worker_options.shutdown_deadline_seconds);
     let mut builder =
         Builder::from_worker_options(opts.worker_options, &opts.deployment, deployment_dir);
     builder.set_listen_addr(opts.address);
@@ -285,8 +289,9 @@ fn main() -> anyhow::Result<()> {
         }
     });
 
-    // Shut down the server gracefully.
-    rt.block_on(shutdown_tx.shutdown_timeout(Duration::from_secs(20)));
+    // Shut down the server gracefully, leaving some time after the domains have drained for
+    // everything else to shut down.
+    rt.block_on(shutdown_tx.shutdown_timeout(shutdown_deadline + Duration::from_secs(5)));
 
     // Attempt a graceful shutdown of the telemetry reporting system
     rt.block_on(async move {
@@ -118,6 +118,8 @@ async fn start_worker(
     memory_limit: Option<usize>,
     memory_check_frequency: Option<Duration>,
     shutdown_rx: ShutdownReceiver,
+    shutdown_deadline: Duration,
+    isolate_domain_panics: bool,
 ) -> Result<(), anyhow::Error> {
     set_failpoint!("start-worker");
     let worker = Worker::new(
@@ -128,6 +130,8 @@ async fn start_worker(
         memory_limit,
         memory_check_frequency,
         shutdown_rx,
+        shutdown_deadline,
+        isolate_domain_panics,
     )?;
 
     tokio::spawn(maybe_abort_on_panic!(abort_on_task_failure, worker.run()));
@@ -193,6 +197,7 @@ async fn start_request_router(
     worker_tx: Sender<WorkerRequest>,
     controller_tx: Sender<ControllerRequest>,
     abort_on_task_failure: bool,
+    max_ready_replication_lag: Duration,
     health_reporter: HealthReporter,
     failpoint_channel: Option<Arc<Sender<()>>>,
     shutdown_rx: ShutdownReceiver,
@@ -204,6 +209,7 @@ async fn start_request_router(
         controller_tx,
         authority: authority.clone(),
         health_reporter: health_reporter.clone(),
+        max_ready_replication_lag,
         failpoint_channel,
     };
 
@@ -270,6 +276,9 @@ pub(crate) async fn start_instance_inner(
 
     let Config {
         abort_on_task_failure,
+        isolate_domain_panics,
+        max_ready_replication_lag,
+        shutdown_deadline,
         ..
     } = config;
 
@@ -285,6 +294,7 @@ pub(crate) async fn start_instance_inner(
         worker_tx.clone(),
         controller_tx,
         abort_on_task_failure,
+        max_ready_replication_lag,
         health_reporter.clone(),
         tx,
         shutdown_rx.clone(),
@@ -305,6 +315,8 @@ pub(crate) async fn start_instance_inner(
         memory_limit,
         memory_check_frequency,
         shutdown_rx.clone(),
+        shutdown_deadline,
+        isolate_domain_panics,
     )
     .await?;
 
@@ -0,0 +1,115 @@
+//! Injection of faults into packets sent between domains, for use in chaos tests.
+//!
+//! Faults are stored in a process-wide registry (much like the one used by the [`fail`] crate for
+//! failpoints), which is configured by the controller via
+//! [`WorkerRequestKind::SetPacketFaults`](super::WorkerRequestKind::SetPacketFaults) and consulted
+//! by every domain replica running in the process right before it sends packets to other domains.
+
+use std::collections::VecDeque;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use dataflow::payload::PacketDiscriminants;
+use dataflow::Packet;
+use readyset_client::failpoints::{PacketFault, PacketFaultAction};
+use readyset_client::internal::ReplicaAddress;
+use tracing::debug;
+
+static PACKET_FAULTS: RwLock<Vec<PacketFault>> = RwLock::new(Vec::new());
+
+/// Replace the set of packet faults that are applied to packets sent by domains in this process
+pub(super) fn set_packet_faults(faults: Vec<PacketFault>) {
+    #[allow(clippy::unwrap_used)] // Only fails if the lock is poisoned
+    let mut packet_faults = PACKET_FAULTS.write().unwrap();
+    *packet_faults = faults;
+}
+
+/// Apply all configured packet faults to the given batch of packets being sent by the domain
+/// replica at `from`, dropping and duplicating packets in place.
+///
+/// Returns the longest delay configured for any of the packets in the batch, which the caller
+/// should wait for before sending the batch.
+pub(super) fn apply_packet_faults(
+    from: ReplicaAddress,
+    to_send: &mut [(ReplicaAddress, VecDeque<Packet>)],
+) -> Option<Duration> {
+    #[allow(clippy::unwrap_used)] // Only fails if the lock is poisoned
+    let faults = PACKET_FAULTS.read().unwrap();
+    if faults.is_empty() {
+        return None;
+    }
+
+    let mut delay: Option<Duration> = None;
+    for (to, packets) in to_send.iter_mut() {
+        let mut faulted = VecDeque::with_capacity(packets.len());
+        for packet in packets.drain(..) {
+            let packet_type: &'static str = PacketDiscriminants::from(&packet).into();
+            let Some(fault) = faults
+                .iter()
+                .find(|f| f.matches(packet_type, from.domain_index, to.domain_index))
+            else {
+                faulted.push_back(packet);
+                continue;
+            };
+
+            debug!(%from, %to, %packet_type, action = ?fault.action, "Injecting packet fault");
+            match fault.action {
+                PacketFaultAction::Drop => {}
+                PacketFaultAction::Delay(d) => {
+                    delay = Some(delay.map_or(d, |delay| delay.max(d)));
+                    faulted.push_back(packet);
+                }
+                PacketFaultAction::Duplicate => {
+                    faulted.push_back(packet.clone());
+                    faulted.push_back(packet);
+                }
+            }
+        }
+        *packets = faulted;
+    }
+
+    delay
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replica(domain_index: usize) -> ReplicaAddress {
+        ReplicaAddress {
+            domain_index: domain_index.into(),
+            shard: 0,
+            replica: 0,
+        }
+    }
+
+    #[test]
+    fn drop_and_duplicate() {
+        set_packet_faults(vec![
+            PacketFault {
+                packet_type: Some("Spin".into()),
+                from: Some(0.into()),
+                to: Some(1.into()),
+                action: PacketFaultAction::Drop,
+            },
+            PacketFault {
+                packet_type: None,
+                from: Some(0.into()),
+                to: Some(2.into()),
+                action: PacketFaultAction::Duplicate,
+            },
+        ]);
+
+        let mut to_send = vec![
+            (replica(1), VecDeque::from(vec![Packet::Spin])),
+            (replica(2), VecDeque::from(vec![Packet::Spin])),
+            (replica(3), VecDeque::from(vec![Packet::Spin])),
+        ];
+        let delay = apply_packet_faults(replica(0), &mut to_send);
+        set_packet_faults(vec![]);
+
+        assert_eq!(delay, None);
+        let lens = to_send.iter().map(|(_, ps)| ps.len()).collect::<Vec<_>>();
+        assert_eq!(lens, vec![0, 2, 1]);
+    }
+}
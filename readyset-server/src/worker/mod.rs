@@ -1,16 +1,16 @@
 use std::cmp;
 use std::collections::hash_map::Entry::{Occupied, Vacant};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::net::{IpAddr, SocketAddr};
 use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::task::{ready, Context, Poll};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use dataflow::payload::EvictRequest;
-use dataflow::{ChannelCoordinator, DomainBuilder, DomainRequest, Packet, Readers};
+use dataflow::{ChannelCoordinator, DomainBuilder, DomainRequest, Packet, Readers, SharedStates};
 use enum_kinds::EnumKind;
 use futures::stream::FuturesUnordered;
 use futures_util::future::TryFutureExt;
@@ -21,7 +21,8 @@ use pin_project::pin_project;
 use readyset_alloc::StdThreadBuildWrapper;
 use readyset_client::internal::ReplicaAddress;
 use readyset_client::metrics::recorded;
-use readyset_client::ReadySetHandle;
+use readyset_client::runtime_config::RuntimeConfigUpdate;
+use readyset_client::{ReaderAddress, ReadySetHandle};
 use readyset_errors::{internal_err, ReadySetError, ReadySetResult};
 use readyset_util::select;
 use readyset_util::shutdown::ShutdownReceiver;
@@ -40,6 +41,9 @@ use self::replica::Replica;
 use crate::coordination::{DomainDescriptor, RunDomainResponse};
 use crate::worker::replica::WrappedDomainRequest;
 
+#[cfg(feature = "failure_injection")]
+mod fault_injection;
+mod packet_lanes;
 /// Request handlers and utilities for reading from the ReadHandle of a
 /// left-right map associated with a reader node.
 pub mod readers;
@@ -96,6 +100,37 @@ pub enum WorkerRequestKind {
         /// The limit in bytes
         limit: Option<usize>,
     },
+
+    /// Apply the worker-level parts of an update to the runtime configuration of the deployment
+    UpdateRuntimeConfig(RuntimeConfigUpdate),
+
+    /// Inform the worker of whether replication from the upstream database is healthy, so that
+    /// readers with stale reads enabled can flag their results as stale while it isn't
+    SetReplicationHealthy(bool),
+
+    /// Replace the set of faults injected into packets sent between domains on this worker
+    #[cfg(feature = "failure_injection")]
+    SetPacketFaults(Vec<readyset_client::failpoints::PacketFault>),
+
+    /// Report the health of the worker, for the server's health check endpoints. Returns a
+    /// [`WorkerHealth`].
+    HealthCheck,
+}
+
+/// The health of a worker, as returned by [`WorkerRequestKind::HealthCheck`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerHealth {
+    /// The URI of the controller this worker was last informed of, or `None` if the worker hasn't
+    /// been registered with a controller yet
+    pub controller_uri: Option<Url>,
+    /// How long replication from the upstream database has been unhealthy for, or `None` if it's
+    /// healthy
+    pub replication_unhealthy_for: Option<Duration>,
+    /// The number of domain replicas running on this worker
+    pub num_domains: usize,
+    /// Domain replicas assigned to this worker whose tasks have exited without being killed by
+    /// the controller
+    pub failed_domains: Vec<ReplicaAddress>,
 }
 
 /// A request to a running ReadySet worker, containing a request kind and a completion channel.
@@ -149,6 +184,12 @@ pub struct DomainHandle {
     /// Can be used to send an abort signal to the domain
     /// aborts automatically when dropped
     abort: oneshot::Sender<()>,
+    /// Used to tell the domain to drain when the worker is shutting down. `None` once the signal
+    /// has been sent.
+    drain: Option<oneshot::Sender<()>>,
+    /// The addresses of the readers in the domain, which are marked unavailable if the domain
+    /// fails and the worker's `isolate_domain_panics` is set
+    readers: Vec<ReaderAddress>,
 }
 
 /// Long-lived struct for tracking the currently allocated heap memory used by the current process
@@ -196,6 +237,7 @@ fn log_domain_result(domain: ReplicaAddress, result: &Result<anyhow::Result<()>,
         Ok(Ok(())) => info!(%domain, "domain exited"),
         Ok(Err(error)) => error!(%domain, %error, "domain failed with an error"),
         Err(e) if e.is_cancelled() => info!(%domain, "domain future cancelled"),
+        Err(error) if error.is_panic() => error!(%domain, %error, "domain panicked"),
         Err(error) => error!(%domain, %error, "domain failed with an error"),
     }
 }
@@ -227,8 +269,22 @@ pub struct Worker {
 
     memory: MemoryTracker,
     is_evicting: Arc<AtomicBool>,
+    /// Whether replication from the upstream database is healthy, shared with all the domains run
+    /// by this worker and their readers
+    replication_healthy: Arc<AtomicBool>,
+    /// The states shared between the co-located shards of nodes run by this worker
+    shared_states: SharedStates,
+    /// The time at which replication last became unhealthy, if it's currently unhealthy
+    replication_unhealthy_since: Option<Instant>,
+    /// Domain replicas in `domains` whose tasks have exited without being killed
+    failed_domains: HashSet<ReplicaAddress>,
     domain_wait_queue: FuturesUnordered<FinishedDomain>,
     shutdown_rx: ShutdownReceiver,
+    /// How long to wait for domains to drain when shutting down (see [`Worker::drain_domains`])
+    shutdown_deadline: Duration,
+    /// Whether to reject reads against the readers of failed domains until they're recovered (see
+    /// [`Config::isolate_domain_panics`](crate::Config::isolate_domain_panics))
+    isolate_domain_panics: bool,
 }
 
 impl Worker {
@@ -240,6 +296,8 @@ impl Worker {
         memory_limit: Option<usize>,
         memory_check_frequency: Option<Duration>,
         shutdown_rx: ShutdownReceiver,
+        shutdown_deadline: Duration,
+        isolate_domain_panics: bool,
     ) -> anyhow::Result<Self> {
         Ok(Self {
             election_state: None,
@@ -255,8 +313,14 @@ impl Worker {
             domains: Default::default(),
             memory: MemoryTracker::new()?,
             is_evicting: Default::default(),
+            replication_healthy: Arc::new(AtomicBool::new(true)),
+            shared_states: Default::default(),
+            replication_unhealthy_since: None,
+            failed_domains: Default::default(),
             domain_wait_queue: Default::default(),
             shutdown_rx,
+            shutdown_deadline,
+            isolate_domain_panics,
         })
     }
 
@@ -293,6 +357,7 @@ impl Worker {
                 info!("controller requested that this worker clears its existing domains");
                 self.coord.clear();
                 self.domains.clear();
+                self.failed_domains.clear();
                 while let Some((result, domain)) = self.domain_wait_queue.next().await {
                     log_domain_result(domain, &result)
                 }
@@ -321,6 +386,19 @@ impl Worker {
                 let mut bind_external = bind_actual;
                 bind_external.set_ip(self.domain_external);
 
+                let readers = builder
+                    .nodes
+                    .values()
+                    .filter_map(|n| {
+                        let n = n.borrow();
+                        n.is_reader().then(|| ReaderAddress {
+                            node: n.global_addr(),
+                            name: n.name().clone(),
+                            shard: replica_addr.shard,
+                        })
+                    })
+                    .collect();
+
                 // this channel is used for async persistent state initialization.
                 // since domains can have at most one base table, there's no need to have a
                 // buffer with a size bigger than one.
@@ -331,6 +409,8 @@ impl Worker {
                     self.readers.clone(),
                     self.coord.clone(),
                     state_size.clone(),
+                    Arc::clone(&self.replication_healthy),
+                    self.shared_states.clone(),
                     init_state_tx,
                 );
 
@@ -340,6 +420,8 @@ impl Worker {
                 // this channel is used for domain requests; it has a buffer size of 1 to prevent
                 // flooding a domain with requests
                 let (req_tx, req_rx) = tokio::sync::mpsc::channel(1);
+                // this channel is used to tell the domain to drain when the worker shuts down
+                let (drain, drain_rx) = oneshot::channel();
 
                 // need to register the domain with the local channel coordinator.
                 // local first to ensure that we don't unnecessarily give away remote for a
@@ -358,6 +440,7 @@ impl Worker {
                     local_rx,
                     req_rx,
                     init_state_rx,
+                    drain_rx,
                     self.coord.clone(),
                 );
                 // Each domain is single threaded in nature, so we spawn each one in a separate
@@ -385,8 +468,16 @@ impl Worker {
                         runtime.shutdown_background();
                     })?;
 
-                self.domains
-                    .insert(replica_addr, DomainHandle { req_tx, abort });
+                self.domains.insert(
+                    replica_addr,
+                    DomainHandle {
+                        req_tx,
+                        abort,
+                        drain: Some(drain),
+                        readers,
+                    },
+                );
+                self.failed_domains.remove(&replica_addr);
 
                 self.domain_wait_queue
                     .push(FinishedDomain(jh, replica_addr));
@@ -399,6 +490,7 @@ impl Worker {
             }
             WorkerRequestKind::KillDomains(domains) => {
                 for addr in domains {
+                    self.failed_domains.remove(&addr);
                     match self.domains.remove(&addr) {
                         Some(domain) => {
                             info!(domain = %addr, "Aborting domain at request of controller");
@@ -447,6 +539,48 @@ impl Worker {
                 self.memory_limit = limit;
                 Ok(None)
             }
+            WorkerRequestKind::UpdateRuntimeConfig(update) => {
+                info!(?update, "updating runtime config");
+                if let Some(limit) = update.memory_limit {
+                    self.memory_limit = (limit > 0).then_some(limit);
+                }
+                if let Some(period) = update.memory_check_frequency {
+                    self.evict_interval = Some(tokio::time::interval(period));
+                }
+                Ok(None)
+            }
+            WorkerRequestKind::SetReplicationHealthy(healthy) => {
+                if self.replication_healthy.swap(healthy, Ordering::Relaxed) != healthy {
+                    info!(healthy, "replication health changed");
+                }
+                if healthy {
+                    self.replication_unhealthy_since = None;
+                } else {
+                    self.replication_unhealthy_since
+                        .get_or_insert_with(Instant::now);
+                }
+                Ok(None)
+            }
+            #[cfg(feature = "failure_injection")]
+            WorkerRequestKind::SetPacketFaults(faults) => {
+                info!(?faults, "setting packet faults");
+                fault_injection::set_packet_faults(faults);
+                Ok(None)
+            }
+            WorkerRequestKind::HealthCheck => {
+                let health = WorkerHealth {
+                    controller_uri: self
+                        .election_state
+                        .as_ref()
+                        .map(|state| state.controller_uri.clone()),
+                    replication_unhealthy_for: self
+                        .replication_unhealthy_since
+                        .map(|since| since.elapsed()),
+                    num_domains: self.domains.len(),
+                    failed_domains: self.failed_domains.iter().copied().collect(),
+                };
+                Ok(Some(bincode::serialize(&health)?))
+            }
         }
     }
 
@@ -460,6 +594,18 @@ impl Worker {
         if matches!(result, Err(e) if e.is_cancelled()) {
             return Ok(());
         }
+        if let Some(domain) = self.domains.get(&addr) {
+            self.failed_domains.insert(addr);
+            // Reject reads against the domain's readers until the controller has recovered it,
+            // rather than letting them wait on upqueries the domain will never answer
+            if self.isolate_domain_panics {
+                #[allow(clippy::unwrap_used)] // lock poisoning is unrecoverable
+                let readers = self.readers.lock().unwrap();
+                for reader in domain.readers.iter().filter_map(|addr| readers.get(addr)) {
+                    reader.mark_unavailable();
+                }
+            }
+        }
 
         self.election_state
             .as_mut()
@@ -469,6 +615,51 @@ impl Worker {
             .await
     }
 
+    /// Tell all the domains run by this worker to stop accepting writes, finish handling the
+    /// packets and replays they have in flight, and persist the state of their base tables, then
+    /// wait up to [`shutdown_deadline`](crate::Config::shutdown_deadline) for them to exit.
+    ///
+    /// Any domains which are still running once the deadline passes are aborted when the worker is
+    /// dropped.
+    async fn drain_domains(&mut self) {
+        let Self {
+            domains,
+            domain_wait_queue,
+            shutdown_deadline,
+            ..
+        } = self;
+
+        if domains.is_empty() {
+            return;
+        }
+
+        info!(
+            num_domains = domains.len(),
+            deadline = ?shutdown_deadline,
+            "Draining domains"
+        );
+        for domain in domains.values_mut() {
+            if let Some(drain) = domain.drain.take() {
+                let _ = drain.send(());
+            }
+        }
+
+        let drained = tokio::time::timeout(*shutdown_deadline, async {
+            while let Some((result, replica_address)) = domain_wait_queue.next().await {
+                domains.remove(&replica_address);
+                log_domain_result(replica_address, &result);
+            }
+        })
+        .await;
+
+        if drained.is_err() {
+            warn!(
+                remaining = ?domains.keys().collect::<Vec<_>>(),
+                "Domains did not drain before the shutdown deadline"
+            );
+        }
+    }
+
     /// Run the worker continuously, processing worker requests, heartbeats, and domain failures.
     ///
     /// This function returns if the worker request sender is dropped.
@@ -495,6 +686,7 @@ impl Worker {
                 }
                 _ = self.shutdown_rx.recv() => {
                     debug!("worker shutting down after shutdown signal received");
+                    self.drain_domains().await;
                     return;
                 }
                 _ = eviction => {
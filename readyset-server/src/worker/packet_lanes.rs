@@ -0,0 +1,387 @@
+//! Priority lanes for the packets handled by a domain's event loop.
+//!
+//! Under write-heavy load, replay pieces and upquery requests can end up queued behind large
+//! batches of writes, which delays the reads waiting on them. When replays are prioritized, the
+//! event loop handles packets in the replay lane ahead of packets in the bulk lane, subject to two
+//! constraints:
+//!
+//! * A partial replay piece is never handled ahead of a bulk packet which was queued before it and
+//!   which updates the same node (or which is an eviction or the end of a full replay), since
+//!   updates along a replay path must be handled in the order they were sent.
+//! * After [`Config::max_consecutive_replay_packets`][] replay packets are handled in a row while
+//!   bulk packets are waiting, a single bulk packet is handled, so that writes aren't starved.
+//!
+//! [`Config::max_consecutive_replay_packets`]: dataflow::DomainConfig::max_consecutive_replay_packets
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Instant;
+
+use dataflow::payload::ReplayPieceContext;
+use dataflow::Packet;
+use metrics::{register_histogram, Histogram};
+use readyset_client::internal::LocalNodeIndex;
+use readyset_client::metrics::recorded;
+use readyset_client::KeyComparison;
+
+/// A packet waiting in one of the lanes
+struct Queued {
+    /// The order in which the packet was queued, across both lanes
+    seq: u64,
+    queued_at: Instant,
+    packet: Packet,
+}
+
+/// Returns `true` if `packet` belongs in the replay lane
+fn is_replay(packet: &Packet) -> bool {
+    matches!(
+        packet,
+        Packet::RequestReaderReplay { .. }
+            | Packet::ReleaseReaderReplays { .. }
+            | Packet::RequestPartialReplay { .. }
+            | Packet::ReplayPiece {
+                context: ReplayPieceContext::Partial { .. },
+                ..
+            }
+    )
+}
+
+/// What a packet in the bulk lane must be ordered with respect to
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Ordering {
+    /// Partial replay pieces targeting the given node
+    Node(LocalNodeIndex),
+    /// All partial replay pieces
+    All,
+}
+
+impl Ordering {
+    fn of(packet: &Packet) -> Option<Self> {
+        match packet {
+            Packet::Message { link, .. } | Packet::ReplayPiece { link, .. } => {
+                Some(Ordering::Node(link.dst))
+            }
+            Packet::Timestamp {
+                link: Some(link), ..
+            } => Some(Ordering::Node(link.dst)),
+            Packet::Evict(_) | Packet::Finish { .. } => Some(Ordering::All),
+            _ => None,
+        }
+    }
+}
+
+/// The packets queued to be handled by a domain's event loop, split into lanes by priority
+pub(super) struct PacketLanes {
+    prioritize_replays: bool,
+    max_consecutive_replay_packets: usize,
+    next_seq: u64,
+    /// Upquery requests and partial replay pieces. Empty unless replays are prioritized.
+    replays: VecDeque<Queued>,
+    /// All other packets
+    bulk: VecDeque<Queued>,
+    /// The sequence numbers of the packets in `bulk` which partial replay pieces must be ordered
+    /// with respect to, oldest first
+    bulk_orderings: HashMap<Ordering, VecDeque<u64>>,
+    /// The number of packets handled from `replays` since a packet was last handled from `bulk`
+    consecutive_replay_packets: usize,
+    replay_wait_time: Histogram,
+    bulk_wait_time: Histogram,
+}
+
+impl PacketLanes {
+    pub(super) fn new(prioritize_replays: bool, max_consecutive_replay_packets: usize) -> Self {
+        Self {
+            prioritize_replays,
+            max_consecutive_replay_packets,
+            next_seq: 0,
+            replays: Default::default(),
+            bulk: Default::default(),
+            bulk_orderings: Default::default(),
+            consecutive_replay_packets: 0,
+            replay_wait_time: register_histogram!(
+                recorded::DOMAIN_PACKET_QUEUE_WAIT_TIME,
+                "priority" => "replay"
+            ),
+            bulk_wait_time: register_histogram!(
+                recorded::DOMAIN_PACKET_QUEUE_WAIT_TIME,
+                "priority" => "bulk"
+            ),
+        }
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.replays.len() + self.bulk.len()
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.replays.is_empty() && self.bulk.is_empty()
+    }
+
+    /// Queue `packet` in the lane for its priority
+    pub(super) fn push(&mut self, packet: Packet) {
+        let queued = Queued {
+            seq: self.next_seq,
+            queued_at: Instant::now(),
+            packet,
+        };
+        self.next_seq += 1;
+
+        if self.prioritize_replays && is_replay(&queued.packet) {
+            self.replays.push_back(queued);
+        } else {
+            if self.prioritize_replays {
+                if let Some(ordering) = Ordering::of(&queued.packet) {
+                    self.bulk_orderings
+                        .entry(ordering)
+                        .or_default()
+                        .push_back(queued.seq);
+                }
+            }
+            self.bulk.push_back(queued);
+        }
+    }
+
+    /// Returns `true` if the replay packet `replay` must wait for a bulk packet queued before it
+    fn must_wait(&self, replay: &Queued) -> bool {
+        let Packet::ReplayPiece { link, .. } = &replay.packet else {
+            // Upquery requests can always be handled out of order
+            return false;
+        };
+        [Ordering::Node(link.dst), Ordering::All]
+            .iter()
+            .filter_map(|ordering| self.bulk_orderings.get(ordering)?.front())
+            .any(|seq| *seq < replay.seq)
+    }
+
+    /// Take the next packet to handle, if any
+    pub(super) fn pop(&mut self) -> Option<Packet> {
+        let from_bulk = match (self.replays.front(), self.bulk.front()) {
+            (None, None) => return None,
+            (None, Some(_)) => true,
+            (Some(_), None) => false,
+            (Some(replay), Some(_)) => {
+                self.consecutive_replay_packets >= self.max_consecutive_replay_packets
+                    || self.must_wait(replay)
+            }
+        };
+
+        let queued = if from_bulk {
+            self.consecutive_replay_packets = 0;
+            let queued = self.bulk.pop_front()?;
+            if let Some(ordering) = Ordering::of(&queued.packet) {
+                if let Some(seqs) = self.bulk_orderings.get_mut(&ordering) {
+                    seqs.pop_front();
+                    if seqs.is_empty() {
+                        self.bulk_orderings.remove(&ordering);
+                    }
+                }
+            }
+            queued
+        } else {
+            if !self.bulk.is_empty() {
+                self.consecutive_replay_packets += 1;
+            }
+            self.replays.pop_front()?
+        };
+
+        if self.prioritize_replays {
+            let wait_time = queued.queued_at.elapsed().as_micros() as f64;
+            if from_bulk {
+                self.bulk_wait_time.record(wait_time);
+            } else {
+                self.replay_wait_time.record(wait_time);
+            }
+        }
+
+        Some(queued.packet)
+    }
+
+    /// Remove all queued requests to replay keys in the reader `node` by the columns `cols`,
+    /// adding their keys to `keys`, so that they can be batched into a single request. Requests
+    /// queued after a request to release replays into the reader are left in place, so that they
+    /// aren't deduplicated into the replays being released.
+    pub(super) fn take_reader_replay_keys(
+        &mut self,
+        node: LocalNodeIndex,
+        cols: &[usize],
+        keys: &mut HashSet<KeyComparison>,
+    ) {
+        let lane = if self.prioritize_replays {
+            &mut self.replays
+        } else {
+            &mut self.bulk
+        };
+        let mut released = false;
+        lane.retain_mut(|queued| match &mut queued.packet {
+            Packet::ReleaseReaderReplays { node: n, .. } if *n == node => {
+                released = true;
+                true
+            }
+            Packet::RequestReaderReplay {
+                node: n,
+                cols: c,
+                keys: k,
+                ..
+            } if !released && *n == node && c == cols => {
+                keys.extend(k.drain(..));
+                false
+            }
+            _ => true,
+        });
+    }
+}
+
+impl Extend<Packet> for PacketLanes {
+    fn extend<I: IntoIterator<Item = Packet>>(&mut self, packets: I) {
+        for packet in packets {
+            self.push(packet);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dataflow::prelude::*;
+
+    use super::*;
+
+    fn message(dst: u32) -> Packet {
+        Packet::Message {
+            link: Link::new(LocalNodeIndex::make(0), LocalNodeIndex::make(dst)),
+            data: Default::default(),
+            trace: None,
+        }
+    }
+
+    fn replay_piece(dst: u32) -> Packet {
+        Packet::ReplayPiece {
+            link: Link::new(LocalNodeIndex::make(0), LocalNodeIndex::make(dst)),
+            tag: Tag::new(0),
+            data: Default::default(),
+            context: ReplayPieceContext::Partial {
+                for_keys: Default::default(),
+                requesting_shard: 0,
+                requesting_replica: 0,
+                unishard: true,
+                timings: vec![],
+            },
+            cache_name: "q".into(),
+        }
+    }
+
+    fn reader_replay(keys: &[i32]) -> Packet {
+        Packet::RequestReaderReplay {
+            node: LocalNodeIndex::make(1),
+            cols: vec![0],
+            keys: keys
+                .iter()
+                .map(|k| KeyComparison::Equal(vec1![DfValue::from(*k)]))
+                .collect(),
+            cache_name: "q".into(),
+        }
+    }
+
+    fn dsts(lanes: &mut PacketLanes) -> Vec<(bool, u32)> {
+        std::iter::from_fn(|| lanes.pop())
+            .map(|packet| {
+                let dst = match &packet {
+                    Packet::Message { link, .. } | Packet::ReplayPiece { link, .. } => {
+                        link.dst.id() as u32
+                    }
+                    _ => u32::MAX,
+                };
+                (is_replay(&packet), dst)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fifo_unless_prioritized() {
+        let mut lanes = PacketLanes::new(false, 16);
+        lanes.extend([message(1), replay_piece(2), message(3)]);
+        assert_eq!(dsts(&mut lanes), vec![(false, 1), (true, 2), (false, 3)]);
+        assert!(lanes.is_empty());
+    }
+
+    #[test]
+    fn replays_preempt_writes_to_other_nodes() {
+        let mut lanes = PacketLanes::new(true, 16);
+        lanes.extend([message(1), message(1), replay_piece(2), message(2)]);
+        assert_eq!(
+            dsts(&mut lanes),
+            vec![(true, 2), (false, 1), (false, 1), (false, 2)]
+        );
+    }
+
+    #[test]
+    fn replays_wait_for_earlier_writes_to_the_same_node() {
+        let mut lanes = PacketLanes::new(true, 16);
+        lanes.extend([message(1), message(2), message(3), replay_piece(2)]);
+        assert_eq!(
+            dsts(&mut lanes),
+            vec![(false, 1), (false, 2), (true, 2), (false, 3)]
+        );
+    }
+
+    #[test]
+    fn writes_are_not_starved() {
+        let mut lanes = PacketLanes::new(true, 2);
+        lanes.extend([
+            message(1),
+            replay_piece(2),
+            replay_piece(2),
+            replay_piece(2),
+            message(1),
+        ]);
+        assert_eq!(
+            dsts(&mut lanes),
+            vec![(true, 2), (true, 2), (false, 1), (true, 2), (false, 1)]
+        );
+    }
+
+    #[test]
+    fn batches_reader_replays() {
+        let mut lanes = PacketLanes::new(true, 16);
+        lanes.extend([message(1), reader_replay(&[1]), reader_replay(&[2, 3])]);
+        let Some(Packet::RequestReaderReplay {
+            node, cols, keys, ..
+        }) = lanes.pop()
+        else {
+            panic!("expected the reader replay request first");
+        };
+        let mut keys: HashSet<_> = keys.into_iter().collect();
+        lanes.take_reader_replay_keys(node, &cols, &mut keys);
+        assert_eq!(keys.len(), 3);
+        assert_eq!(dsts(&mut lanes), vec![(false, 1)]);
+    }
+
+    #[test]
+    fn does_not_batch_reader_replays_past_releases() {
+        let mut lanes = PacketLanes::new(true, 16);
+        lanes.extend([
+            reader_replay(&[1]),
+            reader_replay(&[2]),
+            Packet::ReleaseReaderReplays {
+                node: LocalNodeIndex::make(1),
+                keys: vec![KeyComparison::Equal(vec1![DfValue::from(3)])],
+            },
+            reader_replay(&[3]),
+        ]);
+        let Some(Packet::RequestReaderReplay {
+            node, cols, keys, ..
+        }) = lanes.pop()
+        else {
+            panic!("expected the reader replay request first");
+        };
+        let mut keys: HashSet<_> = keys.into_iter().collect();
+        lanes.take_reader_replay_keys(node, &cols, &mut keys);
+        assert_eq!(keys.len(), 2);
+        assert!(matches!(
+            lanes.pop(),
+            Some(Packet::ReleaseReaderReplays { .. })
+        ));
+        assert!(matches!(
+            lanes.pop(),
+            Some(Packet::RequestReaderReplay { .. })
+        ));
+    }
+}
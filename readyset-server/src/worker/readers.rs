@@ -11,7 +11,8 @@ use async_bincode::AsyncBincodeStream;
 use bincode::Options;
 use dataflow::prelude::*;
 use dataflow::{
-    Expr as DfExpr, LookupError, ReaderMap, ReaderUpdatedNotifier, Readers, SingleReadHandle,
+    Expr as DfExpr, LookupError, MissPermit, ReaderMap, ReaderUpdatedNotifier, Readers,
+    ReplayWaitGuard, SingleReadHandle,
 };
 use failpoint_macros::set_failpoint;
 use futures::pin_mut;
@@ -21,12 +22,13 @@ use readyset_client::consistency::Timestamp;
 #[cfg(feature = "failure_injection")]
 use readyset_client::failpoints;
 use readyset_client::metrics::recorded;
-use readyset_client::results::ResultIterator;
+use readyset_client::results::{ResultIterator, Results};
 use readyset_client::{
-    KeyComparison, LookupResult, ReadQuery, ReadReply, ReadReplyStats, ReaderAddress, Tagged,
-    ViewQuery,
+    AsOf, KeyComparison, LookupResult, ReadQuery, ReadReply, ReadReplyStats, ReaderAddress, Tagged,
+    ViewQuery, ViewResultLimit, ViewScanBatch,
 };
-use readyset_errors::internal_err;
+use readyset_data::DfValue;
+use readyset_errors::{bad_request_err, internal_err, unsupported_err};
 use readyset_util::shutdown::ShutdownReceiver;
 use serde::ser::Serializer;
 use serde::Serialize;
@@ -59,10 +61,56 @@ pub enum ServerReadReplyBatch {
     Unserialized(ResultIterator),
 }
 
+/// Call `f` with each row of `rs` until the rows reach `limit`, returning `true` if any rows were
+/// left over. Bytes are measured by the rows' serialized size.
+fn for_each_within_limit<F>(rs: &mut ResultIterator, limit: ViewResultLimit, mut f: F) -> bool
+where
+    F: FnMut(&[DfValue]),
+{
+    let options = bincode::DefaultOptions::default();
+    let max_rows = limit.max_rows.unwrap_or(usize::MAX);
+    let mut rows = 0usize;
+    let mut bytes = 0usize;
+    while let Some(row) = rs.next() {
+        if rows == max_rows {
+            return true;
+        }
+        if let Some(max_bytes) = limit.max_bytes {
+            bytes += options.serialized_size(row).unwrap() as usize;
+            if bytes > max_bytes {
+                return true;
+            }
+        }
+        f(row);
+        rows += 1;
+    }
+    false
+}
+
 impl ServerReadReplyBatch {
+    /// Construct a [`ServerReadReplyBatch`] from a result set, left unserialized if `raw_result` is
+    /// set and serialized otherwise, cutting the results short if they exceed `limit`. Returns
+    /// whether the results were cut short.
+    fn new(mut rs: ResultIterator, raw_result: bool, limit: ViewResultLimit) -> (Self, bool) {
+        match (raw_result, limit.is_unlimited()) {
+            (false, _) => Self::serialize(rs, limit),
+            (true, true) => (Self::Unserialized(rs), false),
+            (true, false) => {
+                // Collect the rows within the limit, so that the rest of the result set doesn't
+                // have to be held on to
+                let mut rows = vec![];
+                let truncated =
+                    for_each_within_limit(&mut rs, limit, |row| rows.push(row.to_vec()));
+                let results = ResultIterator::owned(vec![Results::new(rows)]);
+                (Self::Unserialized(results), truncated)
+            }
+        }
+    }
+
     /// Construct a [`ServerReadReplyBatch`] by serializing a result set, and storing the serialized
-    /// bytes.
-    fn serialize(mut rs: ResultIterator) -> Self {
+    /// bytes, cutting the results short if they exceed `limit`. Returns whether the results were
+    /// cut short.
+    fn serialize(mut rs: ResultIterator, limit: ViewResultLimit) -> (Self, bool) {
         let mut v = Vec::with_capacity(16 * 1024);
 
         let options = bincode::DefaultOptions::default();
@@ -72,10 +120,10 @@ impl ServerReadReplyBatch {
         usize::MAX.serialize(&mut ser).unwrap(); // Prepend the maximum possible room for length encoding
 
         let mut n = 0usize;
-        while let Some(row) = rs.next() {
+        let truncated = for_each_within_limit(&mut rs, limit, |row| {
             row.serialize(&mut ser).unwrap();
             n += 1;
-        }
+        });
 
         let max_len_enc = options.serialized_size(&usize::MAX).unwrap();
         let len_enc = options.serialized_size(&n).unwrap();
@@ -85,10 +133,13 @@ impl ServerReadReplyBatch {
         // Now encode the proper length
         n.serialize(&mut ser).unwrap();
 
-        Self::Serialized {
-            serialized_data: v.into(),
-            skip_bytes,
-        }
+        (
+            Self::Serialized {
+                serialized_data: v.into(),
+                skip_bytes,
+            },
+            truncated,
+        )
     }
 
     /// Return this [`ServerReadReplyBatch`] as its unserialized [`ResultIterator`] if it is
@@ -179,6 +230,10 @@ impl ReadRequestHandler {
             filter,
             limit,
             offset,
+            as_of,
+            result_limit,
+            secondary_index,
+            upquery_timeout,
         } = query;
 
         macro_rules! reply_with_ok {
@@ -205,6 +260,95 @@ impl ReadRequestHandler {
             Err(e) => reply_with_error!(e),
         };
 
+        macro_rules! reply_with_throttled {
+            ($limit: expr) => {{
+                let limit = $limit;
+                metrics::increment_counter!(
+                    recorded::SERVER_VIEW_QUERY_THROTTLED,
+                    "cache_name" => target.name.display_unquoted().to_string(),
+                    "limit" => limit.to_string(),
+                );
+                reply_with_error!(ReadySetError::ViewThrottled {
+                    view: target.name.display_unquoted().to_string(),
+                    limit,
+                })
+            }};
+        }
+
+        if let Some(Err(e)) = result_limit.as_ref().map(ViewResultLimit::validate) {
+            reply_with_error!(e);
+        }
+        let result_limit = reader.result_limit().min(&result_limit.unwrap_or_default());
+
+        macro_rules! reply_with_results {
+            ($results: expr, $stats: expr) => {{
+                let results =
+                    ResultIterator::new($results, &reader.post_lookup, limit, offset, filter);
+
+                let (results, truncated) =
+                    ServerReadReplyBatch::new(results, raw_result, result_limit);
+                if truncated {
+                    record_truncated(&target);
+                }
+
+                reply_with_ok!(LookupResult::Results(
+                    vec![results],
+                    ReadReplyStats {
+                        truncated,
+                        ..$stats
+                    }
+                ));
+            }};
+        }
+
+        macro_rules! record_stale {
+            ($reason: expr) => {
+                metrics::increment_counter!(
+                    recorded::SERVER_VIEW_QUERY_STALE,
+                    "cache_name" => target.name.display_unquoted().to_string(),
+                    "reason" => $reason,
+                )
+            };
+        }
+
+        if let Err(limit) = reader.throttle().check_request() {
+            reply_with_throttled!(limit);
+        }
+
+        if let Some(columns) = secondary_index {
+            if as_of.is_some() {
+                reply_with_error!(unsupported_err!(
+                    "Historical reads are not supported against secondary indices"
+                ));
+            }
+            let keys = match key_comparisons
+                .iter()
+                .map(|k| match k {
+                    KeyComparison::Equal(key) => Ok(key.to_vec()),
+                    KeyComparison::Range(_) => Err(bad_request_err(
+                        "Secondary indices only support equality lookups",
+                    )),
+                })
+                .collect::<ReadySetResult<Vec<_>>>()
+            {
+                Ok(keys) => keys,
+                Err(e) => reply_with_error!(e),
+            };
+            // Secondary indices are only maintained on fully materialized readers, so lookups
+            // never miss
+            match reader.secondary_indices().lookup(&columns, &keys) {
+                Some(results) => {
+                    self.hit_ctr.increment(1);
+                    reader.stats().record_hit(&target.name);
+                    reply_with_results!(results, Default::default())
+                }
+                None => reply_with_error!(bad_request_err(format!(
+                    "No secondary index on columns {columns:?} of {}",
+                    target.name.display_unquoted()
+                ))),
+            }
+        }
+
         let consistency_miss = !has_sufficient_timestamp(reader, &timestamp);
 
         let (keys_to_replay, receiver) = match reader.get_multi_with_notifier(&key_comparisons) {
@@ -221,23 +365,86 @@ impl ReadRequestHandler {
                 // We hit on all keys, and there is no consistency miss, can return results
                 // immediately
                 self.hit_ctr.increment(1);
-
-                let results = ResultIterator::new(hit, &reader.post_lookup, limit, offset, filter);
-
-                let results = if raw_result {
-                    ServerReadReplyBatch::Unserialized(results)
-                } else {
-                    ServerReadReplyBatch::serialize(results)
+                reader.stats().record_hit(&target.name);
+                reader.stats().record_key_reads(&key_comparisons, []);
+
+                let hit = match &as_of {
+                    None => hit,
+                    Some(as_of) => match reader.rewind(hit, &key_comparisons, as_of) {
+                        Some(hit) => hit,
+                        None => reply_with_error!(ReadySetError::ViewHistoryUnavailable {
+                            view: target.name.display_unquoted().to_string(),
+                        }),
+                    },
                 };
 
-                reply_with_ok!(LookupResult::Results(
-                    vec![results],
-                    ReadReplyStats::default()
-                ));
+                // While replication is down, the reader may be arbitrarily far behind the upstream
+                // database, so flag the results as stale if the view asked us to
+                let stale = as_of.is_none()
+                    && reader.stale_values().is_enabled()
+                    && !reader.stale_values().replication_healthy();
+                if stale {
+                    record_stale!("replication_down");
+                }
+
+                reply_with_results!(
+                    hit,
+                    ReadReplyStats {
+                        stale,
+                        ..Default::default()
+                    }
+                );
             }
         };
 
         self.miss_ctr.increment(1);
+        reader.stats().record_miss(&target.name);
+        reader
+            .stats()
+            .record_key_reads(&key_comparisons, keys_to_replay.iter().map(AsRef::as_ref));
+
+        // If the view has stale reads enabled and we have last-known values for all the keys we
+        // missed on, answer with those instead of waiting for the keys to be replayed
+        if as_of.is_none() && !consistency_miss {
+            if let Some(stale) = reader.get_multi_stale(&key_comparisons) {
+                // Still trigger replays so that later reads see fresh values, as long as that
+                // doesn't exceed the limit on concurrent misses
+                if !keys_to_replay.is_empty() && reader.throttle().acquire_miss().is_ok() {
+                    reader.trigger(
+                        keys_to_replay.into_iter().map(|k| k.into_owned()),
+                        target.name.clone(),
+                    );
+                }
+
+                record_stale!("replaying");
+                reply_with_results!(
+                    stale,
+                    ReadReplyStats {
+                        stale: true,
+                        ..Default::default()
+                    }
+                );
+            }
+        }
+
+        // Don't wait on a domain which is too far behind to fill the miss in a timely fashion, so
+        // that the client can send the query upstream instead
+        if let Err(retry_after) = reader.throttle().check_overload() {
+            metrics::increment_counter!(
+                recorded::SERVER_VIEW_QUERY_OVERLOADED,
+                "cache_name" => target.name.display_unquoted().to_string(),
+            );
+            reply_with_error!(ReadySetError::Overloaded { retry_after });
+        }
+
+        // Reserve a slot to wait for the miss to be filled before triggering any replays, so that
+        // reads which exceed the limit on concurrent misses don't cause any evictions
+        let miss_permit = match reader.throttle().acquire_miss() {
+            Ok(permit) => permit,
+            Err(limit) => reply_with_throttled!(limit),
+        };
+
+        let waiting = reader.wait_for(keys_to_replay.iter().map(AsRef::as_ref));
 
         // Trigger backfills for all the keys we missed on, regardless of a consistency hit/miss
         if !keys_to_replay.is_empty() {
@@ -247,21 +454,31 @@ impl ReadRequestHandler {
             );
         }
 
+        // A timeout passed with the read takes precedence over the one configured for the view,
+        // which takes precedence over the server's default
+        let upquery_timeout = upquery_timeout
+            .or_else(|| reader.upquery_timeout())
+            .unwrap_or(self.upquery_timeout);
+        let first = time::Instant::now();
         let read = BlockingRead {
             tag,
             target,
             key_comparisons,
             truth: self.global_readers.clone(),
-            first: time::Instant::now(),
+            first,
             warned: false,
             limit,
             offset,
             filter,
             timestamp,
-            upquery_timeout: self.upquery_timeout,
+            as_of,
+            result_limit,
+            deadline: first + upquery_timeout,
             raw_result,
             receiver,
             eviction_epoch: reader.eviction_epoch(),
+            _miss_permit: miss_permit,
+            _waiting: waiting,
         };
 
         if !block {
@@ -298,6 +515,44 @@ impl ReadRequestHandler {
             v: ReadReply::Keys(reader.keys()),
         })
     }
+
+    fn handle_scan_query(
+        &mut self,
+        tag: u32,
+        target: &ReaderAddress,
+        start: Option<&[DfValue]>,
+        limit: usize,
+    ) -> Reply {
+        let batch = get_reader_from_cache(target, &mut self.readers_cache, &self.global_readers)
+            .and_then(|reader| {
+                // Read one more key than was asked for, to find the key the next scan starts at
+                let mut keys = reader.scan(start, limit.saturating_add(1))?;
+                let next = if keys.len() > limit {
+                    keys.pop().map(|(key, _)| key)
+                } else {
+                    None
+                };
+                let keys = keys
+                    .into_iter()
+                    .map(|(key, rows)| {
+                        let rows = ResultIterator::new(
+                            std::iter::once(rows).collect(),
+                            &reader.post_lookup,
+                            None,
+                            None,
+                            None,
+                        );
+                        (key, rows.into_vec())
+                    })
+                    .collect();
+                Ok(ViewScanBatch { keys, next })
+            });
+
+        Ok(Tagged {
+            tag,
+            v: ReadReply::Scan(batch),
+        })
+    }
 }
 
 impl Service<Tagged<ReadQuery>> for ReadRequestHandler {
@@ -329,6 +584,15 @@ impl Service<Tagged<ReadQuery>> for ReadRequestHandler {
                 let _g = span.enter();
                 CallResult::Immediate(self.handle_keys_query(tag, target))
             }
+            ReadQuery::Scan {
+                ref target,
+                ref start,
+                limit,
+            } => {
+                let span = readyset_tracing::child_span!(INFO, "scan_query");
+                let _g = span.enter();
+                CallResult::Immediate(self.handle_scan_query(tag, target, start.as_deref(), limit))
+            }
         };
 
         async {
@@ -350,8 +614,9 @@ pub async fn retry_misses(mut rx: UnboundedReceiver<(BlockingRead, Ack)>) {
         loop {
             if let Some(recv) = &mut pending.receiver {
                 // If a receiever is available (on miss) then we simply wait for a notification that
-                // a hole has been filled, then recheck
-                let _ = recv.recv().await;
+                // a hole has been filled, then recheck. Stop waiting at the read's deadline though,
+                // since the replays it's waiting on may never complete.
+                let _ = tokio::time::timeout_at(pending.deadline.into(), recv.recv()).await;
                 while !recv.is_empty() {
                     // This drains all the messages from the notifier so we don't get woken right up
                     // again
@@ -362,6 +627,12 @@ pub async fn retry_misses(mut rx: UnboundedReceiver<(BlockingRead, Ack)>) {
                 tokio::time::sleep(RETRY_TIMEOUT).await;
             }
 
+            // If the client which issued the read has gone away, stop waiting on its behalf so that
+            // the reader's domain can reclaim any replays no other reads are waiting for
+            if ack.as_ref().map_or(false, |ack| ack.is_closed()) {
+                break;
+            }
+
             if let Poll::Ready(res) = pending.check(&mut reader_cache) {
                 upquery_hist.record(pending.first.elapsed().as_micros() as f64);
                 if let Some(a) = ack {
@@ -436,11 +707,20 @@ pub(crate) async fn listen(
     }
 }
 
+/// Record that the results of a read against `target` were cut short by its result limit
+fn record_truncated(target: &ReaderAddress) {
+    metrics::increment_counter!(
+        recorded::SERVER_VIEW_QUERY_TRUNCATED,
+        "cache_name" => target.name.display_unquoted().to_string(),
+    );
+}
+
 /// Verifies that the timestamp in the reader node associated with the read handle, `reader`,
 /// has a greater timestamp than `timestamp`. A greater reader timestamp indicates the writes
-/// in the node include all of the writes associated with `timestamp`.
+/// in the node include all of the writes associated with `timestamp`. An empty timestamp, such as
+/// the ticket of a session which hasn't made any writes yet, is satisfied by any reader.
 fn has_sufficient_timestamp(reader: &SingleReadHandle, timestamp: &Option<Timestamp>) -> bool {
-    if timestamp.is_none() {
+    if timestamp.as_ref().map_or(true, |t| t.map.is_empty()) {
         return true;
     }
 
@@ -468,10 +748,21 @@ pub struct BlockingRead {
     first: time::Instant,
     warned: bool,
     timestamp: Option<Timestamp>,
-    upquery_timeout: Duration,
+    /// If set, the time to rewind the results of the read to once all keys hit
+    as_of: Option<AsOf>,
+    /// Limits on the size of the results of the read
+    result_limit: ViewResultLimit,
+    /// When to stop waiting for the keys which missed to be filled, and fail the read
+    deadline: time::Instant,
     raw_result: bool,
     receiver: Option<ReaderUpdatedNotifier>,
     eviction_epoch: usize,
+    /// Slot reserved against the reader's limit on concurrent misses, released once the read
+    /// completes
+    _miss_permit: MissPermit,
+    /// Registration of the read as waiting for the keys it missed on, released once the read
+    /// completes or is abandoned
+    _waiting: ReplayWaitGuard,
 }
 
 impl std::fmt::Debug for BlockingRead {
@@ -507,6 +798,20 @@ impl BlockingRead {
             Err(_) => return Poll::Ready(Err(ReadySetError::ServerShuttingDown)),
             Ok(hit) => {
                 // We hit on all keys, and there is no consistency miss, can return results
+                let hit = match &self.as_of {
+                    None => hit,
+                    Some(as_of) => match reader.rewind(hit, &self.key_comparisons, as_of) {
+                        Some(hit) => hit,
+                        None => {
+                            return Poll::Ready(Ok(Tagged {
+                                tag: self.tag,
+                                v: ReadReply::Normal(Err(ReadySetError::ViewHistoryUnavailable {
+                                    view: target.name.display_unquoted().to_string(),
+                                })),
+                            }))
+                        }
+                    },
+                };
                 let results = ResultIterator::new(
                     hit,
                     &reader.post_lookup,
@@ -515,17 +820,20 @@ impl BlockingRead {
                     self.filter.take(),
                 );
 
-                let results = if self.raw_result {
-                    ServerReadReplyBatch::Unserialized(results)
-                } else {
-                    ServerReadReplyBatch::serialize(results)
-                };
+                let (results, truncated) =
+                    ServerReadReplyBatch::new(results, self.raw_result, self.result_limit);
+                if truncated {
+                    record_truncated(target);
+                }
 
                 return Poll::Ready(Ok(Tagged {
                     tag: self.tag,
                     v: ReadReply::Normal(Ok(LookupResult::Results(
                         vec![results],
-                        ReadReplyStats::default(),
+                        ReadReplyStats {
+                            truncated,
+                            ..Default::default()
+                        },
                     ))),
                 }));
             }
@@ -537,6 +845,22 @@ impl BlockingRead {
             self.warned = true;
         }
 
+        if time::Instant::now() >= self.deadline {
+            metrics::increment_counter!(
+                recorded::SERVER_VIEW_QUERY_UPQUERY_TIMEOUT,
+                "cache_name" => target.name.display_unquoted().to_string(),
+            );
+            // The replays we're waiting on may be stuck, so make sure the next read which misses
+            // on these keys triggers them again (both here and in the reader's domain) rather
+            // than waiting on them too. Any keys which are filled in the meantime stay in the
+            // reader for later reads.
+            reader.abandon_replays(
+                still_waiting.into_iter().map(|k| k.into_owned()).collect(),
+                self.target.name.clone(),
+            );
+            return Poll::Ready(Err(ReadySetError::UpqueryTimeout));
+        }
+
         let cur_eviction_epoch = reader.eviction_epoch();
         // Only retrigger if there was an eviction since we last checked
         if cur_eviction_epoch > self.eviction_epoch {
@@ -552,11 +876,7 @@ impl BlockingRead {
             }
         }
 
-        if self.first.elapsed() > self.upquery_timeout {
-            Poll::Ready(Err(ReadySetError::UpqueryTimeout))
-        } else {
-            Poll::Pending
-        }
+        Poll::Pending
     }
 }
 
@@ -565,7 +885,7 @@ fn get_reader_from_cache<'a>(
     readers_cache: &'a mut ReaderMap,
     global_readers: &Readers,
 ) -> ReadySetResult<&'a mut SingleReadHandle> {
-    Ok(match readers_cache.entry(target.clone()) {
+    let reader = match readers_cache.entry(target.clone()) {
         Occupied(v) if !v.get().was_dropped() => v.into_mut(),
         // If the entry is Vacant _or_ the WriteHandle was dropped out from under us, we need to
         // refer to the global_readers.
@@ -584,13 +904,21 @@ fn get_reader_from_cache<'a>(
                 Some(reader) => entry.insert_entry(reader.clone()).into_mut(),
             }
         }
-    })
+    };
+    // Reads against the readers of a failed domain would only miss and wait for upqueries which
+    // are never answered, so reject them until the domain has been recovered
+    if reader.is_unavailable() {
+        return Err(ReadySetError::ViewUnavailable(
+            target.name.display_unquoted().to_string(),
+        ));
+    }
+    Ok(reader)
 }
 
 #[cfg(test)]
 mod readreply {
     use readyset_client::results::SharedResults;
-    use readyset_client::{LookupResult, ReadReply, ReadReplyStats, Tagged};
+    use readyset_client::{LookupResult, ReadReply, ReadReplyBatch, ReadReplyStats, Tagged};
     use readyset_data::DfValue;
     use readyset_errors::ReadySetError;
 
@@ -604,13 +932,17 @@ mod readreply {
                     data.iter()
                         .cloned()
                         .map(|d| {
-                            ServerReadReplyBatch::serialize(ResultIterator::new(
-                                [d].into(),
-                                &Default::default(),
-                                None,
-                                None,
-                                None,
-                            ))
+                            ServerReadReplyBatch::serialize(
+                                ResultIterator::new(
+                                    [d].into(),
+                                    &Default::default(),
+                                    None,
+                                    None,
+                                    None,
+                                ),
+                                ViewResultLimit::default(),
+                            )
+                            .0
                         })
                         .collect(),
                     ReadReplyStats::default(),
@@ -680,6 +1012,51 @@ mod readreply {
             .collect()
     }
 
+    fn serialize_limited(data: SharedResults, limit: ViewResultLimit) -> (Vec<Vec<DfValue>>, bool) {
+        let results = ResultIterator::new(data, &Default::default(), None, None, None);
+        let (batch, truncated) = ServerReadReplyBatch::serialize(results, limit);
+        let batch: ReadReplyBatch =
+            bincode::deserialize(&bincode::serialize(&batch).unwrap()).unwrap();
+        (batch.into(), truncated)
+    }
+
+    #[test]
+    fn serialize_within_limit() {
+        let data = rows_vec([[[DfValue::from(1)], [DfValue::from(2)], [DfValue::from(3)]]]);
+        let (rows, truncated) = serialize_limited(
+            data.clone(),
+            ViewResultLimit {
+                max_rows: Some(3),
+                max_bytes: None,
+            },
+        );
+        assert_eq!(rows.len(), 3);
+        assert!(!truncated);
+
+        let (rows, truncated) = serialize_limited(
+            data.clone(),
+            ViewResultLimit {
+                max_rows: Some(2),
+                max_bytes: None,
+            },
+        );
+        assert_eq!(rows, vec![vec![DfValue::from(1)], vec![DfValue::from(2)]]);
+        assert!(truncated);
+
+        let row_size = bincode::DefaultOptions::default()
+            .serialized_size(&[DfValue::from(1)][..])
+            .unwrap() as usize;
+        let (rows, truncated) = serialize_limited(
+            data,
+            ViewResultLimit {
+                max_rows: None,
+                max_bytes: Some(row_size),
+            },
+        );
+        assert_eq!(rows, vec![vec![DfValue::from(1)]]);
+        assert!(truncated);
+    }
+
     #[test]
     fn rtt_normal_one() {
         rtt_ok(rows_vec([[[DfValue::from(1)]]]));
@@ -768,13 +1145,17 @@ mod readreply {
                     data.iter()
                         .cloned()
                         .map(|d| {
-                            ServerReadReplyBatch::serialize(ResultIterator::new(
-                                [d].into(),
-                                &Default::default(),
-                                None,
-                                None,
-                                None,
-                            ))
+                            ServerReadReplyBatch::serialize(
+                                ResultIterator::new(
+                                    [d].into(),
+                                    &Default::default(),
+                                    None,
+                                    None,
+                                    None,
+                                ),
+                                ViewResultLimit::default(),
+                            )
+                            .0
                         })
                         .collect(),
                     ReadReplyStats::default(),
@@ -9,11 +9,14 @@ use anyhow::{self, Context as AnyhowContext};
 use dataflow::payload::{MaterializedState, SourceChannelIdentifier};
 use dataflow::prelude::Executor;
 use dataflow::{Domain, DomainReceiver, DomainRequest, DualTcpStream, Packet};
+use failpoint_macros::set_failpoint;
 use futures_util::sink::{Sink, SinkExt};
 use futures_util::stream::StreamExt;
 use futures_util::FutureExt;
+#[cfg(feature = "failure_injection")]
+use readyset_client::failpoints;
 use readyset_client::internal::ReplicaAddress;
-use readyset_client::{KeyComparison, PacketData, PacketPayload, Tagged, CONNECTION_FROM_BASE};
+use readyset_client::{PacketData, PacketPayload, Tagged, CONNECTION_FROM_BASE};
 use readyset_errors::ReadySetResult;
 use strawpoll::Strawpoll;
 use time::Duration;
@@ -23,8 +26,18 @@ use tokio::sync::{broadcast, mpsc, oneshot, Mutex};
 use tokio_stream::wrappers::IntervalStream;
 use tracing::{debug, error, info, info_span, instrument, trace, warn, Span};
 
+#[cfg(feature = "failure_injection")]
+use super::fault_injection;
+use super::packet_lanes::PacketLanes;
 use super::ChannelCoordinator;
 
+/// The maximum number of packets to receive, or to handle, in a single iteration of the event loop
+const MAX_PACKETS_PER_CALL: usize = 64;
+
+/// The maximum number of received packets to hold in [`PacketLanes`] before we stop receiving
+/// packets until some are handled
+const MAX_QUEUED_PACKETS: usize = 4 * MAX_PACKETS_PER_CALL;
+
 type Outputs =
     AHashMap<ReplicaAddress, Box<dyn Sink<Packet, Error = bincode::Error> + Send + Unpin>>;
 
@@ -62,6 +75,11 @@ pub struct Replica {
 
     init_state_reqs: mpsc::Receiver<MaterializedState>,
 
+    /// Signalled when the worker running this domain is shutting down, at which point we stop
+    /// accepting writes and exit once all in-flight packets and replays have been handled (see
+    /// [`Replica::run`])
+    drain: oneshot::Receiver<()>,
+
     /// Stores pending outgoing messages
     out: Outboxes,
 }
@@ -73,6 +91,7 @@ impl Replica {
         locals: DomainReceiver,
         requests: mpsc::Receiver<WrappedDomainRequest>,
         init_state_reqs: mpsc::Receiver<MaterializedState>,
+        drain: oneshot::Receiver<()>,
         cc: Arc<ChannelCoordinator>,
     ) -> Self {
         Replica {
@@ -84,6 +103,7 @@ impl Replica {
             refresh_sizes: IntervalStream::new(tokio::time::interval(Duration::from_millis(500))),
             requests,
             init_state_reqs,
+            drain,
         }
     }
 }
@@ -107,28 +127,6 @@ impl Executor for Outboxes {
     }
 }
 
-/// Merge multiple [`RequestReaderReplay`] packets into a single packet
-fn flatten_request_reader_replay(
-    n: readyset_client::internal::LocalNodeIndex,
-    c: &[usize],
-    unique_keys: &mut HashSet<KeyComparison>,
-    packets: &mut VecDeque<Packet>,
-) {
-    // Sadly no drain filter for VecDeque yet
-    let mut i = 0;
-    while i < packets.len() {
-        match packets.get_mut(i) {
-            Some(Packet::RequestReaderReplay {
-                node, cols, keys, ..
-            }) if *node == n && *cols == c => {
-                unique_keys.extend(keys.drain(..));
-                packets.remove(i);
-            }
-            _ => i += 1,
-        }
-    }
-}
-
 impl Replica {
     fn span(&self) -> Span {
         info_span!(
@@ -141,11 +139,11 @@ impl Replica {
     }
 
     /// Read the first byte of a connection to determine if it is from a base node, and convert
-    /// it to a DualTcpStream, returning a unique token for the connection together with the
-    /// upgraded connection
+    /// it to a DualTcpStream, returning a unique token for the connection and whether it is from a
+    /// base node together with the upgraded connection
     async fn handle_new_connection(
         mut stream: TcpStream,
-    ) -> Result<(u64, DualTcpStream), anyhow::Error> {
+    ) -> Result<(u64, bool, DualTcpStream), anyhow::Error> {
         let mut tag: u8 = 0;
         stream.read_exact(std::slice::from_mut(&mut tag)).await?;
         let is_base = tag == CONNECTION_FROM_BASE;
@@ -180,7 +178,7 @@ impl Replica {
             .into()
         };
 
-        Ok((token, tcp))
+        Ok((token, is_base, tcp))
     }
 
     /// Receive packets from local and remote connections
@@ -188,8 +186,6 @@ impl Replica {
         locals: &mut DomainReceiver,
         connections: &mut tokio_stream::StreamMap<u64, DualTcpStream>,
     ) -> ReadySetResult<Option<VecDeque<Packet>>> {
-        const MAX_PACKETS_PER_CALL: usize = 64;
-
         let mut packets = VecDeque::with_capacity(MAX_PACKETS_PER_CALL);
 
         // Read packets from either the local connection or the remote connections, tokio
@@ -233,16 +229,71 @@ impl Replica {
         Ok(Some(packets))
     }
 
+    /// Handle up to [`MAX_PACKETS_PER_CALL`] packets queued in `lanes`, in priority order
+    async fn handle_packets(
+        domain: &mut Domain,
+        out: &mut Outboxes,
+        connections: &mut tokio_stream::StreamMap<u64, DualTcpStream>,
+        lanes: &mut PacketLanes,
+        span: &Span,
+    ) -> Result<(), anyhow::Error> {
+        for _ in 0..MAX_PACKETS_PER_CALL {
+            let Some(mut packet) = lanes.pop() else {
+                break;
+            };
+            let ack = match &mut packet {
+                Packet::Timestamp {
+                    src: SourceChannelIdentifier { token, tag },
+                    ..
+                }
+                | Packet::Input {
+                    src: SourceChannelIdentifier { token, tag },
+                    ..
+                } => {
+                    // After processing we need to ack timestamp and input messages from base
+                    connections
+                        .iter_mut()
+                        .find(|(t, _)| *t == *token)
+                        .map(|(_, conn)| (*tag, conn))
+                }
+                Packet::RequestReaderReplay {
+                    node, cols, keys, ..
+                } => {
+                    // We want to batch multiple reader replay requests into a single call while
+                    // deduplicating non unique keys
+                    let mut unique_keys: HashSet<_> = keys.drain(..).collect();
+                    lanes.take_reader_replay_keys(*node, cols, &mut unique_keys);
+                    keys.extend(unique_keys.drain());
+                    None
+                }
+                _ => None,
+            };
+
+            span.in_scope(|| domain.handle_packet(packet, out))?;
+
+            if let Some((tag, conn)) = ack {
+                conn.send(Tagged { tag, v: () }).await?;
+            }
+        }
+        Ok(())
+    }
+
     /// Sends response packets asynchronously, the future takes ownership of a set of packets and
     /// their destination, therefore it can't be dropped before completion without risking some
     /// packets being lost
     #[instrument(level = "debug", name = "send_packets", skip_all)]
     async fn send_packets(
         to_send: Vec<(ReplicaAddress, VecDeque<Packet>)>,
+        delay: Option<Duration>,
         connections: &tokio::sync::Mutex<Outputs>,
         coord: &ChannelCoordinator,
         failed: &Mutex<HashSet<SocketAddr>>,
     ) -> ReadySetResult<()> {
+        if let Some(delay) = delay {
+            trace!(?delay, "Delaying sending packets");
+            tokio::time::sleep(delay).await;
+        }
+
         let mut lock = connections.lock().await;
 
         let connections = &mut *lock;
@@ -316,11 +367,19 @@ impl Replica {
     }
 
     /// Start the event loop for a Replica
+    ///
+    /// Once the replica's drain signal is received, the event loop stops accepting new connections
+    /// and closes all connections from base table clients, so that no more writes are received. It
+    /// then keeps handling packets until there are no packets left queued or waiting to be sent and
+    /// the domain has no replays in flight, at which point it flushes the persistent state of the
+    /// domain's base tables to disk and returns.
     pub async fn run(mut self) -> Result<(), anyhow::Error> {
         // Accepted TCP connections being upgraded
         let mut connection_preambles = futures::stream::FuturesUnordered::new();
         // Every established connection goes here
         let mut connections: tokio_stream::StreamMap<u64, DualTcpStream> = Default::default();
+        // The tokens of the connections in `connections` which are from base table clients
+        let mut base_connections = HashSet::new();
         // A cache of established connections to other Replicas we may send messages to
         // sadly have to use Mutex here to make it possible to pass a mutable reference to outputs
         // to an async function
@@ -346,9 +405,16 @@ impl Replica {
             requests,
             out,
             init_state_reqs,
+            drain,
         } = &mut self;
 
         let mut channel_changes = coord.subscribe();
+        let mut lanes = PacketLanes::new(
+            domain.prioritize_replays(),
+            domain.max_consecutive_replay_packets(),
+        );
+        let mut drain = drain.fuse();
+        let mut draining = false;
 
         loop {
             // we have three logical input sources: receives from local domains, receives from
@@ -358,7 +424,7 @@ impl Replica {
             // https://docs.rs/tokio/1.9.0/tokio/macro.select.html
             tokio::select! {
                 // Accept incoming connections
-                conn = incoming.accept() => {
+                conn = incoming.accept(), if !draining => {
                     let (conn, addr) = conn.context("listening")?;
                     span.in_scope(|| debug!(from = ?addr, "accepted new connection"));
                     connection_preambles.push(Self::handle_new_connection(conn));
@@ -366,14 +432,36 @@ impl Replica {
 
                 // Handle any connections that we accepted but still need to preprocess and convert to DualTcpStream
                 Some(established_conn) = connection_preambles.next() => {
-                    let (token, tcp) = match established_conn {
+                    let (token, is_base, tcp) = match established_conn {
                         Err(_) => continue, // Ignore the errors on unestablished connections, they don't matter
                         Ok(tcp) => tcp,
                     };
 
+                    if is_base {
+                        if draining {
+                            // Don't accept any more writes once we've started draining
+                            continue;
+                        }
+                        // Forget about base table connections which have since been closed
+                        base_connections.retain(|token| connections.contains_key(token));
+                        base_connections.insert(token);
+                    }
                     connections.insert(token, tcp);
                 },
 
+                // Start draining once the worker shuts down. If the sender is dropped instead, the
+                // domain is being aborted, and the runtime we're running on is about to be shut
+                // down
+                Ok(()) = &mut drain => {
+                    span.in_scope(|| info!("draining domain"));
+                    draining = true;
+                    // Close all connections from base table clients, so that we stop receiving
+                    // writes. Any writes we've already received are still handled below.
+                    for token in base_connections.drain() {
+                        connections.remove(&token);
+                    }
+                },
+
                 // Handle changes to the addresses of individual domain replicas
                 replica_addr = channel_changes.recv() => {
                     match replica_addr {
@@ -424,39 +512,22 @@ impl Replica {
                 },
 
                 // Handle incoming messages
-                packets = Self::receive_packets(locals, &mut connections) => match packets? {
+                packets = Self::receive_packets(locals, &mut connections), if lanes.len() < MAX_QUEUED_PACKETS => match packets? {
                     None => {
                         span.in_scope(|| warn!("local input stream ended"));
                         return Ok(())
                     },
-                    Some(mut packets) => {
-                        while let Some(mut packet) = packets.pop_front() {
-                            let ack = match &mut packet {
-                                Packet::Timestamp { src: SourceChannelIdentifier { token, tag }, .. } |
-                                Packet::Input { src: SourceChannelIdentifier { token, tag }, .. } => {
-                                    // After processing we need to ack timestamp and input messages from base
-                                    connections.iter_mut().find(|(t, _)| *t == *token).map(|(_, conn)| (*tag, conn))
-                                }
-                                Packet::RequestReaderReplay { node, cols, keys, .. } => {
-                                    // We want to batch multiple reader replay requests into a single call while
-                                    // deduplicating non unique keys
-                                    let mut unique_keys: HashSet<_> = keys.drain(..).collect();
-                                    flatten_request_reader_replay(*node, cols, &mut unique_keys, &mut packets);
-                                    keys.extend(unique_keys.drain());
-                                    None
-                                }
-                                _ => None,
-                            };
-
-                            span.in_scope(|| domain.handle_packet(packet, out))?;
-
-                            if let Some((tag, conn)) = ack {
-                                conn.send(Tagged { tag, v: () }).await?;
-                            }
-                        }
+                    Some(packets) => {
+                        lanes.extend(packets);
+                        Self::handle_packets(domain, out, &mut connections, &mut lanes, &span).await?;
                     },
                 },
 
+                // Handle packets left queued by previous iterations
+                _ = std::future::ready(()), if !lanes.is_empty() => {
+                    Self::handle_packets(domain, out, &mut connections, &mut lanes, &span).await?;
+                },
+
                 // Poll the send packets future and reissue if outstanding packets are present
                 Some(res) = send_packets.next() => res?,
 
@@ -467,10 +538,33 @@ impl Replica {
                 _ = tokio::time::sleep(domain.next_poll_duration().unwrap_or_else(|| Duration::from_secs(3600))) => domain.handle_timeout()?,
             }
 
+            domain.record_queued_packets(lanes.len());
+
             // Check if the previous batch of send packets is done, and issue a new batch if needed
             if send_packets.is_empty() && !out.domains.is_empty() {
-                let to_send: Vec<_> = out.domains.drain().collect();
-                send_packets.push(Self::send_packets(to_send, &outputs, coord, &failed));
+                set_failpoint!(failpoints::DOMAIN_SEND_PACKETS);
+                #[allow(unused_mut)]
+                let mut to_send: Vec<_> = out.domains.drain().collect();
+                #[allow(unused_mut)]
+                let mut delay = None;
+                #[cfg(feature = "failure_injection")]
+                {
+                    delay = fault_injection::apply_packet_faults(domain.address(), &mut to_send);
+                }
+                send_packets.push(Self::send_packets(to_send, delay, &outputs, coord, &failed));
+            }
+
+            if draining
+                && lanes.is_empty()
+                && out.domains.is_empty()
+                && send_packets.is_empty()
+                && !domain.has_in_flight_replays()
+            {
+                span.in_scope(|| {
+                    info!("domain drained, flushing persistent state");
+                    domain.flush_persistent_state()
+                })?;
+                return Ok(());
             }
         }
     }
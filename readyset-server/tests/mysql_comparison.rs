@@ -325,11 +325,12 @@ async fn check_query(
                             let s: &str = (&v).try_into().unwrap();
                             s.to_string()
                         }
-                        d @ DfValue::ByteArray(_) => d.to_string(),
+                        d @ (DfValue::ByteArray(_) | DfValue::Uuid(_)) => d.to_string(),
                         DfValue::TimestampTz(_)
                         | DfValue::Time(_)
                         // These types are PostgreSQL specific
                         | DfValue::BitVector(_)
+                        | DfValue::Interval(_)
                         | DfValue::PassThrough(_)
                         | DfValue::Array(_) => {
                             unimplemented!()
@@ -0,0 +1,413 @@
+//! Support for filtering on the current time in cached queries.
+//!
+//! Functions such as `NOW()` are non-deterministic, so they can't be evaluated inside the dataflow
+//! graph: any result materialized from them would be stale as soon as it was computed. Instead,
+//! range comparisons between a column and an expression involving the current time in the `WHERE`
+//! clause of the query, such as:
+//!
+//! ```sql
+//! SELECT * FROM posts WHERE created_at > NOW() - INTERVAL 7 DAY
+//! ```
+//!
+//! are rewritten into parameterized range comparisons (`created_at > ?`), and the value of the
+//! expression is computed in the adapter every time the query is executed, then passed to ReadySet
+//! as part of the lookup key. The current time is taken in UTC, truncated to whole seconds, and is
+//! the same for every occurrence of a current time function within a single execution.
+//!
+//! `BETWEEN` conditions with bounds depending on the current time are rewritten into a pair of
+//! range comparisons first. Equality comparisons against the current time are deliberately not
+//! rewritten, since they would look up a different key every second. Any use of the current time
+//! that isn't rewritten here is reported with an explanatory error by the
+//! [`DetectCurrentTime`](crate::DetectCurrentTime) pass when the query is migrated.
+
+use std::mem;
+
+use chrono::NaiveDateTime;
+use dataflow_expression::{Dialect, Expr as DataflowExpr, LowerContext};
+use nom_sql::analysis::visit::{self, Visitor};
+use nom_sql::analysis::visit_mut::{self, VisitorMut};
+use nom_sql::{
+    BinaryOperator, Column, Expr, FunctionExpr, ItemPlaceholder, Literal, Relation,
+    SelectStatement, SqlIdentifier,
+};
+use readyset_data::{DfType, DfValue};
+use readyset_errors::{internal, ReadySetResult};
+
+/// The names of the functions returning the current date and time
+const CURRENT_TIMESTAMP_FUNCTIONS: &[&str] = &[
+    "now",
+    "current_timestamp",
+    "localtimestamp",
+    "localtime",
+    "sysdate",
+    "utc_timestamp",
+];
+
+/// The names of the functions returning the current date
+const CURRENT_DATE_FUNCTIONS: &[&str] = &["current_date", "curdate", "utc_date"];
+
+/// Returns true if the function with the given name returns the current date or time
+pub(crate) fn is_current_time_function(name: &SqlIdentifier) -> bool {
+    let name = name.to_lowercase();
+    CURRENT_TIMESTAMP_FUNCTIONS.contains(&name.as_str())
+        || CURRENT_DATE_FUNCTIONS.contains(&name.as_str())
+}
+
+/// Visitor which determines whether an expression can be evaluated in the adapter given only the
+/// current time
+#[derive(Default)]
+struct AnalyzeCurrentTimeExprVisitor {
+    calls_current_time_function: bool,
+    contains_column_or_placeholder: bool,
+}
+
+impl<'ast> Visitor<'ast> for AnalyzeCurrentTimeExprVisitor {
+    type Error = !;
+
+    fn visit_column(&mut self, _column: &'ast Column) -> Result<(), Self::Error> {
+        self.contains_column_or_placeholder = true;
+        Ok(())
+    }
+
+    fn visit_literal(&mut self, literal: &'ast Literal) -> Result<(), Self::Error> {
+        if matches!(literal, Literal::Placeholder(_)) {
+            self.contains_column_or_placeholder = true;
+        }
+        Ok(())
+    }
+
+    fn visit_select_statement(
+        &mut self,
+        _select_statement: &'ast SelectStatement,
+    ) -> Result<(), Self::Error> {
+        // Subqueries can't be evaluated in the adapter
+        self.contains_column_or_placeholder = true;
+        Ok(())
+    }
+
+    fn visit_function_expr(
+        &mut self,
+        function_expr: &'ast FunctionExpr,
+    ) -> Result<(), Self::Error> {
+        if let FunctionExpr::Call { name, .. } = function_expr {
+            if is_current_time_function(name) {
+                self.calls_current_time_function = true;
+            }
+        }
+        visit::walk_function_expr(self, function_expr)
+    }
+}
+
+/// Returns true if the given expression depends on the current time, and nothing else that can't
+/// be evaluated in the adapter
+fn is_current_time_expr(expr: &Expr) -> bool {
+    let mut visitor = AnalyzeCurrentTimeExprVisitor::default();
+    let Ok(()) = visitor.visit_expr(expr);
+    visitor.calls_current_time_function && !visitor.contains_column_or_placeholder
+}
+
+#[derive(Default)]
+struct ParameterizeCurrentTimeVisitor {
+    out: Vec<(usize, Expr)>,
+    in_supported_position: bool,
+    param_index: usize,
+    query_depth: u8,
+}
+
+impl ParameterizeCurrentTimeVisitor {
+    fn replace_expr(&mut self, expr: &mut Expr) {
+        let expr = mem::replace(
+            expr,
+            Expr::Literal(Literal::Placeholder(ItemPlaceholder::QuestionMark)),
+        );
+        self.out.push((self.param_index, expr));
+        self.param_index += 1;
+    }
+}
+
+impl<'ast> VisitorMut<'ast> for ParameterizeCurrentTimeVisitor {
+    type Error = !;
+
+    fn visit_literal(&mut self, literal: &'ast mut Literal) -> Result<(), Self::Error> {
+        if matches!(literal, Literal::Placeholder(_)) {
+            self.param_index += 1;
+        }
+        Ok(())
+    }
+
+    fn visit_select_statement(
+        &mut self,
+        select_statement: &'ast mut SelectStatement,
+    ) -> Result<(), Self::Error> {
+        self.query_depth = self.query_depth.saturating_add(1);
+        visit_mut::walk_select_statement(self, select_statement)?;
+        self.query_depth = self.query_depth.saturating_sub(1);
+        Ok(())
+    }
+
+    fn visit_where_clause(&mut self, expression: &'ast mut Expr) -> Result<(), Self::Error> {
+        // Like any other parameter, we can only support the current time in the WHERE clause of the
+        // top-level query, not any subqueries it contains.
+        self.in_supported_position = self.query_depth <= 1;
+        self.visit_expr(expression)?;
+        self.in_supported_position = false;
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, expression: &'ast mut Expr) -> Result<(), Self::Error> {
+        if self.in_supported_position {
+            match expression {
+                Expr::BinaryOp {
+                    op: BinaryOperator::And,
+                    ..
+                } => return visit_mut::walk_expr(self, expression),
+                Expr::Between {
+                    operand: box Expr::Column(_),
+                    min,
+                    max,
+                    negated: false,
+                } if is_current_time_expr(min) || is_current_time_expr(max) => {
+                    // Desugar into a pair of range comparisons, so that the bounds which depend on
+                    // the current time can be parameterized
+                    let Expr::Between {
+                        operand, min, max, ..
+                    } = expression.take()
+                    else {
+                        unreachable!("Checked above")
+                    };
+                    *expression = Expr::BinaryOp {
+                        lhs: Box::new(Expr::BinaryOp {
+                            lhs: operand.clone(),
+                            op: BinaryOperator::GreaterOrEqual,
+                            rhs: min,
+                        }),
+                        op: BinaryOperator::And,
+                        rhs: Box::new(Expr::BinaryOp {
+                            lhs: operand,
+                            op: BinaryOperator::LessOrEqual,
+                            rhs: max,
+                        }),
+                    };
+                    return self.visit_expr(expression);
+                }
+                Expr::BinaryOp {
+                    lhs: box Expr::Column(_),
+                    op,
+                    rhs,
+                } if op.is_ordering_comparison() && is_current_time_expr(rhs) => {
+                    self.replace_expr(rhs);
+                    return Ok(());
+                }
+                Expr::BinaryOp {
+                    lhs,
+                    op,
+                    rhs: box Expr::Column(_),
+                } if op.is_ordering_comparison() && is_current_time_expr(lhs) => {
+                    // Flip the comparison, so that the column is on the left-hand side
+                    #[allow(clippy::unwrap_used)] // We checked it's an ordering comparison
+                    let flipped = op.flip_ordering_comparison().unwrap();
+                    *op = flipped;
+                    mem::swap(lhs, rhs);
+                    self.replace_expr(rhs);
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+
+        let was_supported = self.in_supported_position;
+        self.in_supported_position = false;
+        visit_mut::walk_expr(self, expression)?;
+        self.in_supported_position = was_supported;
+        Ok(())
+    }
+}
+
+/// Replace range comparisons in the `WHERE` clause of the given query between a column and an
+/// expression which depends on the current time with placeholders, returning a list of the
+/// positions of those placeholders along with the expressions they replaced.
+///
+/// The returned expressions can be evaluated for each execution of the query with
+/// [`eval_current_time_expr`].
+pub fn parameterize_current_time(query: &mut SelectStatement) -> Vec<(usize, Expr)> {
+    let mut visitor = ParameterizeCurrentTimeVisitor::default();
+    let Ok(()) = visitor.visit_select_statement(query);
+    visitor.out
+}
+
+/// Evaluate an expression returned by [`parameterize_current_time`] with the given value for the
+/// current time (as a local time in the session timezone), returning the literal value to use for
+/// its placeholder.
+pub fn eval_current_time_expr(
+    expr: &Expr,
+    now: NaiveDateTime,
+    dialect: Dialect,
+) -> ReadySetResult<Literal> {
+    struct SubstituteCurrentTimeVisitor {
+        now: NaiveDateTime,
+    }
+
+    impl<'ast> VisitorMut<'ast> for SubstituteCurrentTimeVisitor {
+        type Error = !;
+
+        fn visit_expr(&mut self, expression: &'ast mut Expr) -> Result<(), Self::Error> {
+            if let Expr::Call(FunctionExpr::Call { name, .. }) = expression {
+                let name = name.to_lowercase();
+                let format = if CURRENT_TIMESTAMP_FUNCTIONS.contains(&name.as_str()) {
+                    Some("%Y-%m-%d %H:%M:%S")
+                } else if CURRENT_DATE_FUNCTIONS.contains(&name.as_str()) {
+                    Some("%Y-%m-%d")
+                } else {
+                    None
+                };
+                if let Some(format) = format {
+                    *expression =
+                        Expr::Literal(Literal::String(self.now.format(format).to_string()));
+                    return Ok(());
+                }
+            }
+            visit_mut::walk_expr(self, expression)
+        }
+    }
+
+    #[derive(Clone)]
+    struct CurrentTimeLowerContext;
+    impl LowerContext for CurrentTimeLowerContext {
+        fn resolve_column(&self, _col: Column) -> ReadySetResult<(usize, DfType)> {
+            internal!("Current time expressions can't reference columns")
+        }
+
+        fn resolve_type(&self, _ty: Relation) -> Option<DfType> {
+            None
+        }
+    }
+
+    let mut expr = expr.clone();
+    let Ok(()) = SubstituteCurrentTimeVisitor { now }.visit_expr(&mut expr);
+    DataflowExpr::lower(expr, dialect, CurrentTimeLowerContext)?
+        .eval::<DfValue>(&[])?
+        .try_into()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use nom_sql::{parse_expr, parse_select_statement, DialectDisplay};
+
+    use super::*;
+
+    fn parameterize(
+        query: &str,
+        dialect: nom_sql::Dialect,
+    ) -> (SelectStatement, Vec<(usize, Expr)>) {
+        let mut query = parse_select_statement(dialect, query).unwrap();
+        let out = parameterize_current_time(&mut query);
+        (query, out)
+    }
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 3, 1)
+            .unwrap()
+            .and_hms_opt(12, 30, 15)
+            .unwrap()
+    }
+
+    #[test]
+    fn parameterize_range() {
+        let (query, params) = parameterize(
+            "SELECT * FROM t WHERE x = ? AND created_at > NOW() - INTERVAL 7 DAY",
+            nom_sql::Dialect::MySQL,
+        );
+        assert_eq!(
+            query,
+            parse_select_statement(
+                nom_sql::Dialect::MySQL,
+                "SELECT * FROM t WHERE x = ? AND created_at > ?"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            params,
+            vec![(
+                1,
+                parse_expr(nom_sql::Dialect::MySQL, "NOW() - INTERVAL 7 DAY").unwrap()
+            )]
+        );
+    }
+
+    #[test]
+    fn parameterize_flipped_range() {
+        let (query, params) = parameterize(
+            "SELECT * FROM t WHERE date_sub(now(), INTERVAL 1 HOUR) <= updated_at",
+            nom_sql::Dialect::MySQL,
+        );
+        assert_eq!(
+            query,
+            parse_select_statement(
+                nom_sql::Dialect::MySQL,
+                "SELECT * FROM t WHERE updated_at >= ?"
+            )
+            .unwrap()
+        );
+        assert_eq!(params.len(), 1);
+        assert_eq!(params[0].0, 0);
+    }
+
+    #[test]
+    fn parameterize_between() {
+        let (query, params) = parameterize(
+            "SELECT * FROM t WHERE x = 1 AND created_at BETWEEN NOW() - INTERVAL 1 DAY AND NOW()",
+            nom_sql::Dialect::MySQL,
+        );
+        assert_eq!(
+            query,
+            parse_select_statement(
+                nom_sql::Dialect::MySQL,
+                "SELECT * FROM t WHERE x = 1 AND (created_at >= ? AND created_at <= ?)"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            params.into_iter().map(|(i, _)| i).collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+    }
+
+    #[test]
+    fn unsupported_positions_left_alone() {
+        for query in [
+            "SELECT * FROM t WHERE created_at = NOW()",
+            "SELECT * FROM t WHERE created_at NOT BETWEEN NOW() - INTERVAL 1 DAY AND NOW()",
+            "SELECT * FROM t WHERE created_at > NOW() OR x = 1",
+            "SELECT * FROM t WHERE created_at > updated_at - INTERVAL 1 DAY",
+            "SELECT * FROM t WHERE created_at > NOW() - INTERVAL ? DAY",
+            "SELECT NOW() FROM t",
+            "SELECT * FROM t WHERE x IN (SELECT y FROM u WHERE created_at > NOW())",
+        ] {
+            let (rewritten, params) = parameterize(query, nom_sql::Dialect::MySQL);
+            assert!(
+                params.is_empty(),
+                "{query} rewritten to {}",
+                rewritten.display(nom_sql::Dialect::MySQL)
+            );
+        }
+    }
+
+    #[test]
+    fn eval_mysql() {
+        let expr = parse_expr(nom_sql::Dialect::MySQL, "NOW() - INTERVAL 7 DAY").unwrap();
+        assert_eq!(
+            eval_current_time_expr(&expr, now(), Dialect::DEFAULT_MYSQL).unwrap(),
+            Literal::String("2024-02-23 12:30:15".into())
+        );
+    }
+
+    #[test]
+    fn eval_postgres() {
+        let expr = parse_expr(nom_sql::Dialect::PostgreSQL, "now() - INTERVAL '1 month'").unwrap();
+        assert_eq!(
+            eval_current_time_expr(&expr, now(), Dialect::DEFAULT_POSTGRESQL).unwrap(),
+            Literal::String("2024-02-01 12:30:15.000000".into())
+        );
+    }
+}
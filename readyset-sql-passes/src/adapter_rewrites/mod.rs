@@ -1,19 +1,26 @@
 mod autoparameterize;
+mod current_time;
 
 use std::borrow::Cow;
 use std::cmp::max;
 use std::convert::{TryFrom, TryInto};
 use std::fmt::Debug;
+use std::hash::Hash;
 use std::{iter, mem};
 
 pub use autoparameterize::auto_parameterize_query;
+use chrono::Utc;
+pub(crate) use current_time::is_current_time_function;
+pub use current_time::{eval_current_time_expr, parameterize_current_time};
+use dataflow_expression::Dialect;
 use itertools::{Either, Itertools};
+use nom_sql::analysis::visit::Visitor;
 use nom_sql::analysis::visit_mut::{self, VisitorMut};
 use nom_sql::{
     BinaryOperator, DialectDisplay, Expr, InValue, ItemPlaceholder, LimitClause, Literal,
     SelectStatement,
 };
-use readyset_data::{DfType, DfValue};
+use readyset_data::{DfType, DfValue, Timezone};
 use readyset_errors::{
     internal_err, invalid_query_err, unsupported, ReadySetError, ReadySetResult,
 };
@@ -31,8 +38,12 @@ use tracing::trace;
 pub struct ProcessedQueryParams {
     reordered_placeholders: Option<Vec<usize>>,
     rewritten_in_conditions: Vec<RewrittenIn>,
+    current_time_parameters: Vec<(usize, Expr)>,
     auto_parameters: Vec<(usize, Literal)>,
     pagination_parameters: AdapterPaginationParams,
+    /// Whether the rewritten query still contains string literals which weren't replaced with
+    /// placeholders
+    has_string_literals: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -70,10 +81,17 @@ pub struct AdapterRewriteParams {
     /// flag is true, both equals and range parameters in supported positions will be
     /// autoparameterized during the adapter rewrite passes.
     pub server_supports_mixed_comparisons: bool,
+    /// The server can re-aggregate the results of a multi-key lookup at the reader. If this flag
+    /// is true, parameterized IN conditions are collapsed even in queries with aggregates or
+    /// DISTINCT, since the per-key results will be merged after the lookup.
+    pub server_supports_post_lookup: bool,
 }
 
 /// This rewrite pass accomplishes the following:
 /// - Remaps dollar sign placeholders so that they appear in order
+/// - Replaces expressions depending on the current time (such as `NOW() - INTERVAL 7 DAY`) that
+///   columns are range-compared against with placeholders, whose values are computed each time the
+///   query is executed
 /// - Replaces literals with placeholders when they can be used as lookup indices in the noria
 ///   dataflow representation of the query. Note that this pass may not replace all literals and is
 ///   therefore cannot guarantee that the rewritten query is free of user PII.
@@ -95,22 +113,35 @@ pub fn process_query(
         query.limit_clause.clone_from(&limit_clause);
     }
 
+    let current_time_parameters = current_time::parameterize_current_time(query);
     let auto_parameters =
         autoparameterize::auto_parameterize_query(query, params.server_supports_mixed_comparisons);
-    let rewritten_in_conditions = collapse_where_in(query)?;
+    let rewritten_in_conditions = collapse_where_in(query, params.server_supports_post_lookup)?;
     number_placeholders(query)?;
+    let has_string_literals = contains_string_literal(query);
     Ok(ProcessedQueryParams {
         reordered_placeholders,
         rewritten_in_conditions,
+        current_time_parameters,
         auto_parameters,
         pagination_parameters: AdapterPaginationParams {
             limit_clause,
             force_paginate_in_adapter,
         },
+        has_string_literals,
     })
 }
 
 impl ProcessedQueryParams {
+    /// Returns true if the rewritten query still contains string literals which weren't replaced
+    /// with placeholders.
+    ///
+    /// Such literals are compiled into the dataflow graph, where timestamps are interpreted in
+    /// UTC, so they can't be interpreted in the timezone of the session executing the query.
+    pub fn has_string_literals(&self) -> bool {
+        self.has_string_literals
+    }
+
     /// If the query has values for OFFSET or LIMIT, get their values, returning a tuple of `limit,
     /// offset`
     pub fn limit_offset_params(
@@ -180,9 +211,17 @@ impl ProcessedQueryParams {
         }
     }
 
-    pub fn make_keys<'param, T>(&self, params: &'param [T]) -> ReadySetResult<Vec<Cow<'param, [T]>>>
+    /// Make a list of lookup keys from the given list of user-provided parameters, evaluating any
+    /// expressions depending on the current time according to the given `dialect`, in the given
+    /// session `timezone`
+    pub fn make_keys<'param, T>(
+        &self,
+        params: &'param [T],
+        dialect: Dialect,
+        timezone: Timezone,
+    ) -> ReadySetResult<Vec<Cow<'param, [T]>>>
     where
-        T: Clone + TryFrom<Literal, Error = ReadySetError> + Debug + Default + PartialEq,
+        T: Clone + TryFrom<Literal, Error = ReadySetError> + Debug + Default + Eq + Hash,
     {
         let params = if let Some(order_map) = &self.reordered_placeholders {
             Cow::Owned(reorder_params(params, order_map)?)
@@ -210,10 +249,31 @@ impl ProcessedQueryParams {
             }
         }
 
-        if params.is_empty() && self.auto_parameters.is_empty() {
+        if params.is_empty()
+            && self.auto_parameters.is_empty()
+            && self.current_time_parameters.is_empty()
+        {
             return Ok(vec![]);
         }
 
+        // The current time parameters are numbered among the placeholders that were in the query
+        // before it was auto-parameterized, so they have to be spliced in first. Like any other
+        // parameter, the current time is given as a local time in the session timezone.
+        let now = timezone
+            .from_utc_datetime(&Utc::now().naive_utc())
+            .naive_local();
+        let current_time_parameters = self
+            .current_time_parameters
+            .iter()
+            .map(|(i, expr)| -> ReadySetResult<_> {
+                Ok((
+                    *i,
+                    current_time::eval_current_time_expr(expr, now, dialect)?.try_into()?,
+                ))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let params = splice_auto_parameters(params, &current_time_parameters);
+
         let auto_parameters = self
             .auto_parameters
             .clone()
@@ -221,20 +281,45 @@ impl ProcessedQueryParams {
             .map(|(i, lit)| -> ReadySetResult<_> { Ok((i, lit.try_into()?)) })
             .collect::<Result<Vec<_>, _>>()?;
 
-        let params = splice_auto_parameters(params, &auto_parameters);
+        let params = splice_auto_parameters(params.as_ref(), &auto_parameters);
 
         if self.rewritten_in_conditions.is_empty() {
             return Ok(vec![Cow::Owned(params.into_owned())]);
         }
 
+        // Repeated values in an IN list would otherwise look up (and return the rows for) the same
+        // key more than once
         Ok(
             explode_params(params.as_ref(), &self.rewritten_in_conditions)
+                .unique()
                 .map(|k| Cow::Owned(k.into_owned()))
                 .collect(),
         )
     }
 }
 
+/// Returns true if the given query contains any string literals
+fn contains_string_literal(query: &SelectStatement) -> bool {
+    struct StringLiteralVisitor {
+        found: bool,
+    }
+
+    impl<'ast> Visitor<'ast> for StringLiteralVisitor {
+        type Error = !;
+
+        fn visit_literal(&mut self, literal: &'ast Literal) -> Result<(), Self::Error> {
+            if matches!(literal, Literal::String(_)) {
+                self.found = true;
+            }
+            Ok(())
+        }
+    }
+
+    let mut visitor = StringLiteralVisitor { found: false };
+    let Ok(()) = visitor.visit_select_statement(query);
+    visitor.found
+}
+
 /// Information about a single parameterized IN condition that has been rewritten to an equality
 /// condition
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -349,7 +434,13 @@ impl<'ast> VisitorMut<'ast> for CollapseWhereInVisitor {
 ///
 /// Note that IN conditions without any placeholders will be left untouched, as these can be handled
 /// by regular filter nodes in dataflow
-fn collapse_where_in(query: &mut SelectStatement) -> ReadySetResult<Vec<RewrittenIn>> {
+///
+/// If `server_supports_post_lookup` is true, queries with aggregates or DISTINCT are collapsed as
+/// well, relying on the reader to merge the results for each of the exploded keys.
+fn collapse_where_in(
+    query: &mut SelectStatement,
+    server_supports_post_lookup: bool,
+) -> ReadySetResult<Vec<RewrittenIn>> {
     let mut res = vec![];
     let distinct = query.distinct;
     let has_aggregates = query.contains_aggregate_select();
@@ -362,8 +453,8 @@ fn collapse_where_in(query: &mut SelectStatement) -> ReadySetResult<Vec<Rewritte
         // When a `SELECT` statement contains aggregates, such as `SUM` or `COUNT` (or `DISTINCT`,
         // which is implemented via COUNT),  we can't use placeholders, as those will aggregate key
         // lookups into a multi row response, as opposed to a single row response required by
-        // aggregates. We could support this pretty easily, but for now it's not in-scope
-        if !res.is_empty() {
+        // aggregates - unless the server can re-aggregate those rows after the lookup
+        if !res.is_empty() && !server_supports_post_lookup {
             if has_aggregates {
                 unsupported!("Aggregates with parameterized IN are not supported");
             }
@@ -587,7 +678,7 @@ mod tests {
         #[test]
         fn collapsed_where_placeholders() {
             let mut q = parse_select_statement_mysql("SELECT * FROM x WHERE x.y IN (?, ?, ?)");
-            let rewritten = collapse_where_in(&mut q).unwrap();
+            let rewritten = collapse_where_in(&mut q, false).unwrap();
             assert_eq!(
                 rewritten,
                 vec![RewrittenIn {
@@ -601,7 +692,7 @@ mod tests {
             );
 
             let mut q = parse_select_statement_mysql("SELECT * FROM x WHERE y IN (?, ?, ?)");
-            let rewritten = collapse_where_in(&mut q).unwrap();
+            let rewritten = collapse_where_in(&mut q, false).unwrap();
             assert_eq!(
                 rewritten,
                 vec![RewrittenIn {
@@ -615,7 +706,7 @@ mod tests {
             );
 
             let mut q = parse_select_statement_mysql("SELECT * FROM x WHERE AVG(y) IN (?, ?, ?)");
-            let rewritten = collapse_where_in(&mut q).unwrap();
+            let rewritten = collapse_where_in(&mut q, false).unwrap();
             assert_eq!(
                 rewritten,
                 vec![RewrittenIn {
@@ -631,7 +722,7 @@ mod tests {
             let mut q = parse_select_statement_mysql(
                 "SELECT * FROM t WHERE x = ? AND y IN (?, ?, ?) OR z = ?",
             );
-            let rewritten = collapse_where_in(&mut q).unwrap();
+            let rewritten = collapse_where_in(&mut q, false).unwrap();
             assert_eq!(
                 rewritten,
                 vec![RewrittenIn {
@@ -647,7 +738,7 @@ mod tests {
             let mut q = parse_select_statement_mysql(
                 "SELECT * FROM t WHERE x IN (SELECT * FROM z WHERE a = ?) AND y IN (?, ?) OR z = ?",
             );
-            let rewritten = collapse_where_in(&mut q).unwrap();
+            let rewritten = collapse_where_in(&mut q, false).unwrap();
             assert_eq!(
                 rewritten,
                 vec![RewrittenIn {
@@ -665,7 +756,7 @@ mod tests {
             let mut q = parse_select_statement_mysql(
                 "SELECT * FROM t WHERE x IN (SELECT * FROM z WHERE b = ? AND a IN (?, ?)) OR z = ?",
             );
-            let rewritten = collapse_where_in(&mut q).unwrap();
+            let rewritten = collapse_where_in(&mut q, false).unwrap();
             assert_eq!(
                 rewritten,
                 vec![RewrittenIn {
@@ -684,7 +775,7 @@ mod tests {
         #[test]
         fn collapsed_where_literals() {
             let mut q = parse_select_statement_mysql("SELECT * FROM x WHERE x.y IN (1, 2, 3)");
-            assert_eq!(collapse_where_in(&mut q).unwrap(), vec![]);
+            assert_eq!(collapse_where_in(&mut q, false).unwrap(), vec![]);
             assert_eq!(
                 q,
                 parse_select_statement_mysql("SELECT * FROM x WHERE x.y IN (1, 2, 3)")
@@ -694,7 +785,7 @@ mod tests {
         #[test]
         fn collapsed_where_dollarsign_placeholders() {
             let mut q = parse_select_statement_mysql("SELECT * FROM x WHERE x.y IN ($1, $2, $3)");
-            let rewritten = collapse_where_in(&mut q).unwrap();
+            let rewritten = collapse_where_in(&mut q, false).unwrap();
             assert_eq!(
                 rewritten,
                 vec![RewrittenIn {
@@ -712,7 +803,7 @@ mod tests {
             );
 
             let mut q = parse_select_statement_postgres("SELECT * FROM x WHERE y IN ($1, $2, $3)");
-            let rewritten = collapse_where_in(&mut q).unwrap();
+            let rewritten = collapse_where_in(&mut q, false).unwrap();
             assert_eq!(
                 rewritten,
                 vec![RewrittenIn {
@@ -731,7 +822,7 @@ mod tests {
 
             let mut q =
                 parse_select_statement_postgres("SELECT * FROM x WHERE AVG(y) IN ($1, $2, $3)");
-            let rewritten = collapse_where_in(&mut q).unwrap();
+            let rewritten = collapse_where_in(&mut q, false).unwrap();
             assert_eq!(
                 rewritten,
                 vec![RewrittenIn {
@@ -751,7 +842,7 @@ mod tests {
             let mut q = parse_select_statement_postgres(
                 "SELECT * FROM t WHERE x = $1 AND y IN ($2, $3, $4) OR z = $5",
             );
-            let rewritten = collapse_where_in(&mut q).unwrap();
+            let rewritten = collapse_where_in(&mut q, false).unwrap();
             assert_eq!(
                 rewritten,
                 vec![RewrittenIn {
@@ -771,7 +862,7 @@ mod tests {
             let mut q = parse_select_statement_postgres(
             "SELECT * FROM t WHERE x IN (SELECT * FROM z WHERE a = $1) AND y IN ($2, $3) OR z = $4",
         );
-            let rewritten = collapse_where_in(&mut q).unwrap();
+            let rewritten = collapse_where_in(&mut q, false).unwrap();
             assert_eq!(
                 rewritten,
                 vec![RewrittenIn {
@@ -792,7 +883,7 @@ mod tests {
             let mut q = parse_select_statement_postgres(
             "SELECT * FROM t WHERE x IN (SELECT * FROM z WHERE b = $1 AND a IN ($2, $3)) OR z = $4",
         );
-            let rewritten = collapse_where_in(&mut q).unwrap();
+            let rewritten = collapse_where_in(&mut q, false).unwrap();
             assert_eq!(
                 rewritten,
                 vec![RewrittenIn {
@@ -815,7 +906,7 @@ mod tests {
         fn collapse_multiple_where_in() {
             let mut q =
                 parse_select_statement_mysql("SELECT * FROM t WHERE x IN (?,?) AND y IN (?,?)");
-            let rewritten = collapse_where_in(&mut q).unwrap();
+            let rewritten = collapse_where_in(&mut q, false).unwrap();
             assert_eq!(
                 rewritten,
                 vec![
@@ -942,6 +1033,7 @@ mod tests {
     }
 
     mod process_query {
+        use chrono::NaiveDateTime;
         use readyset_data::DfValue;
 
         use super::*;
@@ -949,6 +1041,7 @@ mod tests {
         const PARAMS: AdapterRewriteParams = AdapterRewriteParams {
             server_supports_pagination: false,
             server_supports_mixed_comparisons: false,
+            server_supports_post_lookup: false,
         };
 
         fn process_and_make_keys(
@@ -960,7 +1053,16 @@ mod tests {
             let processed = process_query(&mut query, PARAMS).unwrap();
             (
                 processed
-                    .make_keys(&params)
+                    .make_keys(
+                        &params,
+                        match dialect {
+                            nom_sql::Dialect::MySQL => readyset_data::Dialect::DEFAULT_MYSQL,
+                            nom_sql::Dialect::PostgreSQL => {
+                                readyset_data::Dialect::DEFAULT_POSTGRESQL
+                            }
+                        },
+                        Timezone::UTC,
+                    )
                     .unwrap()
                     .into_iter()
                     .map(|c| c.to_vec())
@@ -1068,6 +1170,64 @@ mod tests {
             assert_eq!(keys, vec![vec![4.into()]]);
         }
 
+        #[test]
+        fn current_time_params() {
+            let (keys, query) = process_and_make_keys_postgres(
+                "SELECT * FROM t WHERE x > 4 AND created_at > now() - INTERVAL '1 day'",
+                vec![],
+            );
+            assert_eq!(
+                query,
+                parse_select_statement_postgres("SELECT * FROM t WHERE x > $1 AND created_at > $2")
+            );
+            assert_eq!(keys.len(), 1);
+            assert_eq!(keys[0][0], 4.into());
+            assert!(
+                keys[0][1]
+                    .coerce_to(
+                        &DfType::Timestamp {
+                            subsecond_digits: 6
+                        },
+                        &DfType::Unknown
+                    )
+                    .is_ok(),
+                "{:?}",
+                keys[0][1]
+            );
+        }
+
+        #[test]
+        fn current_time_params_in_session_timezone() {
+            let mut query =
+                parse_select_statement_mysql("SELECT * FROM t WHERE created_at > NOW()");
+            let processed = process_query(&mut query, PARAMS).unwrap();
+            let now_in = |timezone: &str| {
+                let keys = processed
+                    .make_keys::<DfValue>(
+                        &[],
+                        readyset_data::Dialect::DEFAULT_MYSQL,
+                        timezone.parse().unwrap(),
+                    )
+                    .unwrap();
+                NaiveDateTime::try_from(
+                    &keys[0][0]
+                        .coerce_to(
+                            &DfType::DateTime {
+                                subsecond_digits: 0,
+                            },
+                            &DfType::Unknown,
+                        )
+                        .unwrap(),
+                )
+                .unwrap()
+            };
+
+            let utc = now_in("+00:00");
+            let local = now_in("+10:00");
+            let offset = (local - utc).num_minutes();
+            assert!((599..=601).contains(&offset), "{offset}");
+        }
+
         #[test]
         fn number_autoparam_number() {
             let (keys, query) = process_and_make_keys_postgres(
@@ -1153,6 +1313,52 @@ mod tests {
             );
         }
 
+        #[test]
+        fn where_in_with_repeated_values() {
+            let (keys, query) = process_and_make_keys_mysql(
+                "SELECT a FROM t WHERE b IN (?, ?, ?, ?)",
+                vec![1.into(), 2.into(), 1.into(), 2.into()],
+            );
+
+            assert_eq!(
+                query,
+                parse_select_statement_mysql("SELECT a FROM t WHERE b = ?")
+            );
+            assert_eq!(keys, vec![vec![1.into()], vec![2.into()]]);
+        }
+
+        #[test]
+        fn aggregate_where_in_with_post_lookup() {
+            let mut query =
+                parse_select_statement_mysql("SELECT sum(a) FROM t WHERE b IN (?, ?, ?)");
+            assert!(process_query(&mut query, PARAMS).is_err());
+
+            let mut query =
+                parse_select_statement_mysql("SELECT sum(a) FROM t WHERE b IN (?, ?, ?)");
+            let processed = process_query(
+                &mut query,
+                AdapterRewriteParams {
+                    server_supports_post_lookup: true,
+                    ..PARAMS
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                query,
+                parse_select_statement_mysql("SELECT sum(a) FROM t WHERE b = ?")
+            );
+            assert_eq!(
+                processed
+                    .make_keys::<DfValue>(
+                        &[1.into(), 2.into(), 3.into()],
+                        readyset_data::Dialect::DEFAULT_MYSQL
+                    )
+                    .unwrap()
+                    .len(),
+                3
+            );
+        }
+
         #[test]
         fn numbered_point_following_where_in() {
             let (keys, query) = process_and_make_keys_postgres(
@@ -1566,5 +1772,22 @@ mod tests {
                 vec![vec![1.into(), 1.into()], vec![1.into(), 2.into()]]
             );
         }
+
+        #[test]
+        fn has_string_literals() {
+            let process = |query: &str| {
+                process_query(&mut parse_select_statement_postgres(query), PARAMS).unwrap()
+            };
+
+            assert!(
+                !process("SELECT * FROM t WHERE created_at = '2024-01-01 00:00:00'")
+                    .has_string_literals()
+            );
+            assert!(!process("SELECT * FROM t WHERE x = $1").has_string_literals());
+            assert!(
+                process("SELECT * FROM t WHERE created_at > '2024-01-01' OR x = $1")
+                    .has_string_literals()
+            );
+        }
     }
 }
@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+use std::mem;
+
+use nom_sql::analysis::visit_mut::{self, walk_select_statement, VisitorMut};
+use nom_sql::{
+    Column, Dialect, DialectDisplay, Expr, FieldDefinitionExpr, FunctionExpr, JoinRightSide,
+    SelectStatement, SqlIdentifier, TableExprInner,
+};
+
+pub trait Canonicalize {
+    /// Rewrite this query into a canonical form, such that queries which differ only in ways that
+    /// can't change their results (the names given to table aliases, or the case of function names)
+    /// are rewritten to the same query.
+    ///
+    /// Table aliases are renamed to `__t0`, `__t1`, ... in the order they appear in the query, and
+    /// any field which doesn't already have an alias and isn't a plain column reference is given
+    /// an explicit alias equal to its original text, so that the names of the columns in the
+    /// query's result set are unchanged.
+    ///
+    /// The canonical form of a query is intended to be used to identify equivalent queries (eg by
+    /// hashing it), not to be executed.
+    fn canonicalize(&mut self);
+}
+
+#[derive(Default)]
+struct CanonicalizeVisitor {
+    /// The number of table aliases renamed so far
+    next_alias: usize,
+    /// Stack of maps from the tables that can be referenced in each enclosing SELECT statement to
+    /// the name they're referenced by after canonicalization
+    scopes: Vec<HashMap<SqlIdentifier, SqlIdentifier>>,
+}
+
+impl<'ast> VisitorMut<'ast> for CanonicalizeVisitor {
+    type Error = !;
+
+    fn visit_select_statement(
+        &mut self,
+        select_statement: &'ast mut SelectStatement,
+    ) -> Result<(), Self::Error> {
+        let mut scope = HashMap::new();
+        let join_table_exprs =
+            select_statement
+                .join
+                .iter_mut()
+                .flat_map(|join| match &mut join.right {
+                    JoinRightSide::Table(table) => vec![table],
+                    JoinRightSide::Tables(tables) => tables.iter_mut().collect(),
+                });
+        for table_expr in select_statement.tables.iter_mut().chain(join_table_exprs) {
+            match (&mut table_expr.alias, &table_expr.inner) {
+                (Some(alias), _) => {
+                    let canonical = SqlIdentifier::from(format!("__t{}", self.next_alias));
+                    self.next_alias += 1;
+                    scope.insert(mem::replace(alias, canonical.clone()), canonical);
+                }
+                (None, TableExprInner::Table(table)) => {
+                    // Unaliased tables shadow aliases with the same name in enclosing queries
+                    scope.insert(table.name.clone(), table.name.clone());
+                }
+                (None, TableExprInner::Subquery(_)) => {}
+            }
+        }
+
+        self.scopes.push(scope);
+        walk_select_statement(self, select_statement)?;
+        self.scopes.pop();
+
+        Ok(())
+    }
+
+    fn visit_field_definition_expr(
+        &mut self,
+        fde: &'ast mut FieldDefinitionExpr,
+    ) -> Result<(), Self::Error> {
+        // This runs before any columns within the expression are visited, so the alias is taken
+        // from the expression as it was originally written
+        if let FieldDefinitionExpr::Expr {
+            expr,
+            alias: alias @ None,
+        } = fde
+            && !matches!(expr, Expr::Column(_))
+        {
+            *alias = Some(expr.display(Dialect::MySQL).to_string().into());
+        }
+
+        visit_mut::walk_field_definition_expr(self, fde)
+    }
+
+    fn visit_function_expr(
+        &mut self,
+        function_expr: &'ast mut FunctionExpr,
+    ) -> Result<(), Self::Error> {
+        if let FunctionExpr::Call { name, .. } = function_expr {
+            *name = name.to_lowercase().into();
+        }
+
+        visit_mut::walk_function_expr(self, function_expr)
+    }
+
+    fn visit_column(&mut self, column: &'ast mut Column) -> Result<(), Self::Error> {
+        if let Some(table) = &mut column.table
+            && table.schema.is_none()
+            && let Some(canonical) = self
+                .scopes
+                .iter()
+                .rev()
+                .find_map(|scope| scope.get(&table.name))
+        {
+            table.name = canonical.clone();
+        }
+
+        Ok(())
+    }
+}
+
+impl Canonicalize for SelectStatement {
+    fn canonicalize(&mut self) {
+        let Ok(()) = CanonicalizeVisitor::default().visit_select_statement(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom_sql::{parse_query, SqlQuery};
+
+    use super::*;
+
+    fn canonical(dialect: Dialect, query: &str) -> SelectStatement {
+        let SqlQuery::Select(mut stmt) = parse_query(dialect, query).unwrap() else {
+            panic!("Expected a SELECT statement");
+        };
+        stmt.canonicalize();
+        stmt
+    }
+
+    #[test]
+    fn formatting_differences() {
+        assert_eq!(
+            canonical(Dialect::MySQL, "SELECT a, b FROM t WHERE a = ?"),
+            canonical(Dialect::MySQL, "select  a,b\n  from t where a=?"),
+        );
+    }
+
+    #[test]
+    fn table_aliases() {
+        assert_eq!(
+            canonical(
+                Dialect::MySQL,
+                "SELECT x.a, y.b FROM t AS x JOIN u AS y ON x.id = y.t_id WHERE x.a = ?"
+            ),
+            canonical(
+                Dialect::MySQL,
+                "SELECT t1.a, t2.b FROM t AS t1 JOIN u AS t2 ON t1.id = t2.t_id WHERE t1.a = ?"
+            ),
+        );
+        assert_eq!(
+            canonical(
+                Dialect::MySQL,
+                "SELECT x.a FROM t AS x JOIN t AS y ON x.id = y.parent_id"
+            ),
+            canonical(
+                Dialect::MySQL,
+                "SELECT p.a FROM t AS p JOIN t AS c ON p.id = c.parent_id"
+            ),
+        );
+        // Aliases are positional, so swapping which alias refers to which table is significant
+        assert_ne!(
+            canonical(
+                Dialect::MySQL,
+                "SELECT x.a FROM t AS x JOIN t AS y ON x.id = y.parent_id"
+            ),
+            canonical(
+                Dialect::MySQL,
+                "SELECT y.a FROM t AS x JOIN t AS y ON x.id = y.parent_id"
+            ),
+        );
+    }
+
+    #[test]
+    fn subquery_aliases() {
+        assert_eq!(
+            canonical(
+                Dialect::MySQL,
+                "SELECT sq.a FROM (SELECT x.a FROM t AS x) AS sq WHERE sq.a = ?"
+            ),
+            canonical(
+                Dialect::MySQL,
+                "SELECT q.a FROM (SELECT y.a FROM t AS y) AS q WHERE q.a = ?"
+            ),
+        );
+    }
+
+    #[test]
+    fn unaliased_tables_shadow_outer_aliases() {
+        assert_eq!(
+            canonical(
+                Dialect::MySQL,
+                "SELECT t.a FROM u AS t WHERE t.b IN (SELECT t.b FROM t)"
+            ),
+            canonical(
+                Dialect::MySQL,
+                "SELECT s.a FROM u AS s WHERE s.b IN (SELECT t.b FROM t)"
+            ),
+        );
+    }
+
+    #[test]
+    fn function_name_case() {
+        assert_eq!(
+            canonical(Dialect::PostgreSQL, "SELECT coalesce(a, b) AS c FROM t"),
+            canonical(Dialect::PostgreSQL, "SELECT COALESCE(a, b) AS c FROM t"),
+        );
+    }
+
+    #[test]
+    fn result_column_names_preserved() {
+        // These queries return columns with different names, so they aren't equivalent
+        assert_ne!(
+            canonical(Dialect::MySQL, "SELECT x.a + 1 FROM t AS x"),
+            canonical(Dialect::MySQL, "SELECT y.a + 1 FROM t AS y"),
+        );
+        assert_ne!(
+            canonical(Dialect::MySQL, "SELECT coalesce(a, b) FROM t"),
+            canonical(Dialect::MySQL, "SELECT COALESCE(a, b) FROM t"),
+        );
+        assert_eq!(
+            canonical(Dialect::MySQL, "SELECT x.a + 1 AS c FROM t AS x"),
+            canonical(Dialect::MySQL, "SELECT y.a + 1 AS c FROM t AS y"),
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use dataflow_expression::Dialect;
+use nom_sql::{ColumnConstraint, CreateTableOption, CreateTableStatement};
+use readyset_data::dialect::SqlEngine;
+
+pub trait DefaultCollation {
+    /// Make the default character set or collation of a MySQL table (specified via the
+    /// `DEFAULT CHARSET` or `COLLATE` table options) explicit on all text columns of the table
+    /// that don't specify their own character set or collation, so that the columns compare
+    /// strings the same way MySQL does.
+    fn propagate_default_collation(self, dialect: Dialect) -> Self;
+}
+
+impl DefaultCollation for CreateTableStatement {
+    fn propagate_default_collation(mut self, dialect: Dialect) -> Self {
+        if dialect.engine() != SqlEngine::MySQL {
+            return self;
+        }
+
+        let Ok(options) = &self.options else {
+            return self;
+        };
+        let collation = options.iter().find_map(|opt| match opt {
+            CreateTableOption::Collate(name) => Some(ColumnConstraint::Collation(name.to_string())),
+            _ => None,
+        });
+        let charset = options.iter().find_map(|opt| match opt {
+            CreateTableOption::Charset(name) => {
+                Some(ColumnConstraint::CharacterSet(name.to_string()))
+            }
+            _ => None,
+        });
+        let Some(default) = collation.or(charset) else {
+            return self;
+        };
+
+        if let Ok(body) = &mut self.body {
+            for field in &mut body.fields {
+                if field.sql_type.is_any_text()
+                    && !field.constraints.iter().any(|c| {
+                        matches!(
+                            c,
+                            ColumnConstraint::Collation(_) | ColumnConstraint::CharacterSet(_)
+                        )
+                    })
+                {
+                    field.constraints.push(default.clone());
+                }
+            }
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom_sql::{parse_create_table, Dialect as ParserDialect};
+
+    use super::*;
+
+    #[test]
+    fn table_collation() {
+        let orig = parse_create_table(
+            ParserDialect::MySQL,
+            "CREATE TABLE t (a int, b text, c varchar(10) COLLATE utf8mb4_bin, \
+             d char(1) CHARACTER SET latin1) DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_general_ci",
+        )
+        .unwrap();
+        let expected = parse_create_table(
+            ParserDialect::MySQL,
+            "CREATE TABLE t (a int, b text COLLATE utf8mb4_general_ci, \
+             c varchar(10) COLLATE utf8mb4_bin, d char(1) CHARACTER SET latin1) \
+             DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_general_ci",
+        )
+        .unwrap();
+        assert_eq!(
+            orig.propagate_default_collation(Dialect::DEFAULT_MYSQL),
+            expected
+        );
+    }
+
+    #[test]
+    fn table_charset() {
+        let orig = parse_create_table(
+            ParserDialect::MySQL,
+            "CREATE TABLE t (a text) DEFAULT CHARSET=latin1",
+        )
+        .unwrap();
+        let expected = parse_create_table(
+            ParserDialect::MySQL,
+            "CREATE TABLE t (a text CHARACTER SET latin1) DEFAULT CHARSET=latin1",
+        )
+        .unwrap();
+        assert_eq!(
+            orig.propagate_default_collation(Dialect::DEFAULT_MYSQL),
+            expected
+        );
+    }
+
+    #[test]
+    fn no_table_options() {
+        let orig = parse_create_table(ParserDialect::MySQL, "CREATE TABLE t (a text)").unwrap();
+        assert_eq!(
+            orig.clone()
+                .propagate_default_collation(Dialect::DEFAULT_MYSQL),
+            orig
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use nom_sql::analysis::visit::{self, Visitor};
+use nom_sql::{BinaryOperator, Expr, FunctionExpr, SelectStatement};
+use readyset_errors::{unsupported, ReadySetError, ReadySetResult};
+
+use crate::adapter_rewrites::is_current_time_function;
+
+pub trait DetectCurrentTime: Sized {
+    /// Return an unsupported error, describing how the query could be written instead, for any
+    /// use of a function returning the current time (such as `NOW()`) in this query.
+    ///
+    /// The only supported uses of the current time are the range comparisons against columns which
+    /// are replaced with placeholders by the adapter (see
+    /// [`parameterize_current_time`](crate::adapter_rewrites::parameterize_current_time)), so any
+    /// call that reaches the server is in a position that can't be rewritten.
+    fn detect_current_time(self) -> ReadySetResult<Self>;
+}
+
+#[derive(Default)]
+struct DetectCurrentTimeVisitor {
+    in_where_clause: bool,
+}
+
+impl<'ast> Visitor<'ast> for DetectCurrentTimeVisitor {
+    type Error = ReadySetError;
+
+    fn visit_where_clause(&mut self, expr: &'ast Expr) -> Result<(), Self::Error> {
+        let was_in_where_clause = self.in_where_clause;
+        self.in_where_clause = true;
+        self.visit_expr(expr)?;
+        self.in_where_clause = was_in_where_clause;
+        Ok(())
+    }
+
+    fn visit_select_statement(
+        &mut self,
+        select_statement: &'ast SelectStatement,
+    ) -> Result<(), Self::Error> {
+        let was_in_where_clause = self.in_where_clause;
+        self.in_where_clause = false;
+        visit::walk_select_statement(self, select_statement)?;
+        self.in_where_clause = was_in_where_clause;
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) -> Result<(), Self::Error> {
+        if let Expr::BinaryOp {
+            lhs,
+            op: BinaryOperator::Equal | BinaryOperator::NotEqual,
+            rhs,
+        } = expr
+        {
+            if [lhs, rhs].into_iter().any(|e| calls_current_time(e)) {
+                unsupported!(
+                    "Comparing for equality against the current time is not supported, since the \
+                     current time changes on every execution; use a range comparison instead \
+                     (eg `created_at > NOW() - INTERVAL 1 DAY`)"
+                );
+            }
+        }
+
+        visit::walk_expr(self, expr)
+    }
+
+    fn visit_function_expr(
+        &mut self,
+        function_expr: &'ast FunctionExpr,
+    ) -> Result<(), Self::Error> {
+        if let FunctionExpr::Call { name, .. } = function_expr {
+            if is_current_time_function(name) {
+                if self.in_where_clause {
+                    unsupported!(
+                        "{name}() is only supported in a range comparison against a column which \
+                         is part of the top-level AND of conditions in the WHERE clause (eg \
+                         `WHERE created_at > NOW() - INTERVAL 1 DAY`)"
+                    );
+                } else {
+                    unsupported!(
+                        "{name}() is only supported in the WHERE clause of a query, since its \
+                         result changes on every execution"
+                    );
+                }
+            }
+        }
+
+        visit::walk_function_expr(self, function_expr)
+    }
+}
+
+fn calls_current_time(expr: &Expr) -> bool {
+    struct CallsCurrentTimeVisitor(bool);
+
+    impl<'ast> Visitor<'ast> for CallsCurrentTimeVisitor {
+        type Error = !;
+
+        fn visit_function_expr(
+            &mut self,
+            function_expr: &'ast FunctionExpr,
+        ) -> Result<(), Self::Error> {
+            if let FunctionExpr::Call { name, .. } = function_expr {
+                self.0 |= is_current_time_function(name);
+            }
+            visit::walk_function_expr(self, function_expr)
+        }
+    }
+
+    let mut visitor = CallsCurrentTimeVisitor(false);
+    let Ok(()) = visitor.visit_expr(expr);
+    visitor.0
+}
+
+impl DetectCurrentTime for SelectStatement {
+    fn detect_current_time(self) -> ReadySetResult<Self> {
+        DetectCurrentTimeVisitor::default().visit_select_statement(&self)?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom_sql::{parse_select_statement, Dialect};
+
+    use super::*;
+
+    fn detect(query: &str) -> ReadySetResult<SelectStatement> {
+        parse_select_statement(Dialect::MySQL, query)
+            .unwrap()
+            .detect_current_time()
+    }
+
+    #[test]
+    fn no_current_time() {
+        detect("SELECT * FROM t WHERE created_at > ?").unwrap();
+    }
+
+    #[test]
+    fn equality() {
+        let err = detect("SELECT * FROM t WHERE a = NOW()").unwrap_err();
+        assert!(
+            matches!(&err, ReadySetError::Unsupported(msg) if msg.contains("equality")),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn not_rewritable_in_where_clause() {
+        let err = detect("SELECT * FROM t WHERE a > NOW() OR b = 1").unwrap_err();
+        assert!(
+            matches!(&err, ReadySetError::Unsupported(msg) if msg.contains("top-level AND")),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn outside_where_clause() {
+        for query in [
+            "SELECT a, NOW() FROM t",
+            "SELECT a FROM t ORDER BY coalesce(a, NOW())",
+            "SELECT * FROM t WHERE a IN (SELECT b FROM u GROUP BY b HAVING max(c) > NOW())",
+        ] {
+            let err = detect(query).unwrap_err();
+            assert!(
+                matches!(&err, ReadySetError::Unsupported(msg) if msg.contains("only supported in the WHERE clause")),
+                "{query}: {err}"
+            );
+        }
+    }
+}
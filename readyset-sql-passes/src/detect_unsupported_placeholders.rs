@@ -1,7 +1,8 @@
 use std::mem;
 
 use nom_sql::analysis::visit::{self, walk_expr, Visitor};
-use nom_sql::{BinaryOperator, Expr, ItemPlaceholder, Literal, SelectStatement};
+use nom_sql::analysis::{contains_aggregate, ReferredColumns};
+use nom_sql::{BinaryOperator, Expr, InValue, ItemPlaceholder, Literal, SelectStatement};
 use readyset_errors::{ReadySetError, ReadySetResult};
 use vec1::Vec1;
 
@@ -72,7 +73,7 @@ impl UnsupportedPlaceholderVisitor {
         if !self.config.allow_mixed_comparisons {
             match (lhs, rhs, op) {
                 (
-                    Expr::Column(_),
+                    _,
                     Expr::Literal(Literal::Placeholder(ItemPlaceholder::DollarNumber(n))),
                     BinaryOperator::Equal,
                 ) => self.context.equality_comparisons.push(*n),
@@ -125,8 +126,10 @@ impl<'ast> Visitor<'ast> for UnsupportedPlaceholderVisitor {
     /// We do nothing except record any placeholders in `Context::ordering_comparisons` or
     /// `Context::equality_comparisons` if we have:
     /// - a comparison with a `Expr::Column` on the left and a `Expr::Literal` on the right
-    /// - a `Expr::Between` with a `Expr::Column` as the operand and `Expr::Literal`s for the min
-    ///   and max expressions.
+    /// - an equality comparison with an expression over columns (see [`is_parameterizable_expr`])
+    ///   on the left and a `Expr::Literal` on the right
+    /// - a non-negated `Expr::Between` with a `Expr::Column` as the operand and `Expr::Literal`s
+    ///   for the min and max expressions.
     ///
     /// Otherwise, walk the expression and record any placeholder values we find in
     /// `Self::unsupported_placeholders`.
@@ -140,11 +143,16 @@ impl<'ast> Visitor<'ast> for UnsupportedPlaceholderVisitor {
         match expr {
             Expr::BinaryOp { lhs, rhs, op } => {
                 // The placeholder is supported if we have an equality or ordering comparison with a
-                // column on the left and placeholder on the right.
-                if !(matches!(**lhs, Expr::Column(_))
-                    && matches!(**rhs, Expr::Literal(_)) // no need to walk for any literal
-                    && (matches!(op, BinaryOperator::Equal) || op.is_ordering_comparison()))
-                {
+                // column on the left and placeholder on the right, or an equality comparison with
+                // an expression over columns on the left and placeholder on the right.
+                let supported_lhs = match **lhs {
+                    Expr::Column(_) => {
+                        matches!(op, BinaryOperator::Equal) || op.is_ordering_comparison()
+                    }
+                    _ => matches!(op, BinaryOperator::Equal) && is_parameterizable_expr(lhs),
+                };
+                // no need to walk for any literal
+                if !(supported_lhs && matches!(**rhs, Expr::Literal(_))) {
                     let Ok(_) = walk_expr(self, expr);
                 } else {
                     // Record placeholders in either Context::equality_comparisons or
@@ -153,15 +161,19 @@ impl<'ast> Visitor<'ast> for UnsupportedPlaceholderVisitor {
                 }
             }
             Expr::Between {
-                operand, min, max, ..
+                operand,
+                min,
+                max,
+                negated,
             } => {
-                // We only support placeholders in Expr::Between if the operand is a column.
+                // We only support placeholders in Expr::Between if the operand is a column and the
+                // BETWEEN isn't negated, since a negated BETWEEN can't be turned into a range key.
                 //
                 // If min and max are expressions that contain placeholders, rather than
                 // placeholders themselves, then the placeholders are not supported.
                 //
                 // We allow a mix of non-placeholder and placeholder literals in min and max.
-                if !matches!(**operand, Expr::Column(_)) {
+                if *negated || !matches!(**operand, Expr::Column(_)) {
                     let Ok(_) = walk_expr(self, expr);
                 } else {
                     if !matches!(**min, Expr::Literal(_)) {
@@ -216,6 +228,26 @@ impl<'ast> Visitor<'ast> for UnsupportedPlaceholderVisitor {
     }
 }
 
+/// Returns true if a placeholder can be compared for equality against the given expression, which
+/// is the case if the expression refers to at least one column and doesn't contain any
+/// placeholders, subqueries or aggregates.
+fn is_parameterizable_expr(expr: &Expr) -> bool {
+    expr.referred_columns().next().is_some()
+        && !contains_aggregate(expr)
+        && !expr.recursive_subexpressions().any(|e| {
+            matches!(
+                e,
+                Expr::Literal(Literal::Placeholder(_))
+                    | Expr::NestedSelect(_)
+                    | Expr::Exists(_)
+                    | Expr::In {
+                        rhs: InValue::Subquery(_),
+                        ..
+                    }
+            )
+        })
+}
+
 impl DetectUnsupportedPlaceholders for SelectStatement {
     fn detect_unsupported_placeholders(&self, config: Config) -> ReadySetResult<()> {
         let mut visitor = UnsupportedPlaceholderVisitor::new(config);
@@ -266,11 +298,25 @@ mod tests {
 
     #[test]
     fn extracts_placeholder_expr_comparison() {
-        let select = parse_select_statement("SELECT a FROM t WHERE b + $1 = 1 AND c + 1 = $2");
+        let select = parse_select_statement("SELECT a FROM t WHERE b + $1 = 1 AND c + 1 > $2");
         let res = select.detect_unsupported_placeholders(Config::default());
         extracts_placeholders(res, &[1, 2]);
     }
 
+    #[test]
+    fn ignores_supported_expr_equality() {
+        let select = parse_select_statement("SELECT a FROM t WHERE lower(b) = $1 AND c + d = $2");
+        let res = select.detect_unsupported_placeholders(Config::default());
+        extracts_placeholders(res, &[]);
+    }
+
+    #[test]
+    fn extracts_placeholder_compared_to_expr_without_columns() {
+        let select = parse_select_statement("SELECT a FROM t WHERE 1 + 1 = $1 AND b + $2 = $3");
+        let res = select.detect_unsupported_placeholders(Config::default());
+        extracts_placeholders(res, &[1, 2, 3]);
+    }
+
     #[test]
     fn extracts_placeholder_having_clause() {
         let select =
@@ -303,6 +349,13 @@ mod tests {
         extracts_placeholders(res, &[]);
     }
 
+    #[test]
+    fn extracts_negated_between() {
+        let select = parse_select_statement("SELECT a FROM t WHERE b NOT BETWEEN $1 AND $2");
+        let res = select.detect_unsupported_placeholders(Config::default());
+        extracts_placeholders(res, &[1, 2]);
+    }
+
     #[test]
     fn ignores_supported_limit_offset() {
         let select = parse_select_statement("SELECT a FROM t WHERE b = $1 LIMIT $2 OFFSET $3");
@@ -0,0 +1,311 @@
+use std::mem;
+
+use nom_sql::analysis::visit::{self, Visitor};
+use nom_sql::analysis::visit_mut::{self, VisitorMut};
+use nom_sql::{
+    CommonTableExpr, Relation, SelectStatement, SqlIdentifier, TableExpr, TableExprInner,
+};
+
+pub trait InlineCtes: Sized {
+    /// Recursively replace the reference to every common table expression in the given query
+    /// which is referenced exactly once with a subquery, and remove any common table expressions
+    /// which are never referenced. For example, the following query:
+    ///
+    /// ```sql
+    /// WITH max_val AS (SELECT max(value) AS v FROM t)
+    /// SELECT max_val.v FROM max_val
+    /// ```
+    ///
+    /// becomes:
+    ///
+    /// ```sql
+    /// SELECT max_val.v FROM (SELECT max(value) AS v FROM t) AS max_val
+    /// ```
+    ///
+    /// Common table expressions which are referenced more than once are kept, so that they can be
+    /// compiled once into a shared internal view. Since each of those is compiled separately from
+    /// the rest of the query, any earlier common table expressions they reference are copied into
+    /// their own `WITH` clause.
+    #[must_use]
+    fn inline_ctes(self) -> Self;
+}
+
+/// Returns true if the given table expression is an unqualified reference to `name`
+fn references(table_expr: &TableExpr, name: &SqlIdentifier) -> bool {
+    matches!(
+        &table_expr.inner,
+        TableExprInner::Table(Relation { schema: None, name: table }) if table == name
+    )
+}
+
+/// Returns the prefix of the CTEs of the given statement whose bodies can reference an outer
+/// relation called `name`, or `None` if `name` is not shadowed by any CTE of the statement.
+fn unshadowed_ctes<'a>(
+    ctes: &'a [CommonTableExpr],
+    name: &SqlIdentifier,
+) -> Option<&'a [CommonTableExpr]> {
+    ctes.iter()
+        .position(|cte| cte.name == *name)
+        .map(|pos| &ctes[..=pos])
+}
+
+struct CountReferencesVisitor<'a> {
+    name: &'a SqlIdentifier,
+    count: usize,
+}
+
+impl<'ast, 'a> Visitor<'ast> for CountReferencesVisitor<'a> {
+    type Error = !;
+
+    fn visit_select_statement(
+        &mut self,
+        select_statement: &'ast SelectStatement,
+    ) -> Result<(), Self::Error> {
+        match unshadowed_ctes(&select_statement.ctes, self.name) {
+            Some(ctes) => {
+                for cte in ctes {
+                    self.visit_select_statement(&cte.statement)?;
+                }
+                Ok(())
+            }
+            None => visit::walk_select_statement(self, select_statement),
+        }
+    }
+
+    fn visit_table_expr(&mut self, table_expr: &'ast TableExpr) -> Result<(), Self::Error> {
+        if references(table_expr, self.name) {
+            self.count += 1;
+        }
+        visit::walk_table_expr(self, table_expr)
+    }
+}
+
+fn count_references(stmt: &SelectStatement, name: &SqlIdentifier) -> usize {
+    let mut visitor = CountReferencesVisitor { name, count: 0 };
+    let Ok(()) = visitor.visit_select_statement(stmt);
+    visitor.count
+}
+
+struct InlineReferenceVisitor<'a> {
+    name: &'a SqlIdentifier,
+    statement: Option<SelectStatement>,
+}
+
+impl<'ast, 'a> VisitorMut<'ast> for InlineReferenceVisitor<'a> {
+    type Error = !;
+
+    fn visit_select_statement(
+        &mut self,
+        select_statement: &'ast mut SelectStatement,
+    ) -> Result<(), Self::Error> {
+        if self.statement.is_none() {
+            return Ok(());
+        }
+
+        if let Some(pos) = select_statement
+            .ctes
+            .iter()
+            .position(|cte| cte.name == *self.name)
+        {
+            for cte in &mut select_statement.ctes[..=pos] {
+                self.visit_select_statement(&mut cte.statement)?;
+            }
+            Ok(())
+        } else {
+            visit_mut::walk_select_statement(self, select_statement)
+        }
+    }
+
+    fn visit_table_expr(&mut self, table_expr: &'ast mut TableExpr) -> Result<(), Self::Error> {
+        if references(table_expr, self.name)
+            && let Some(statement) = self.statement.take()
+        {
+            table_expr.inner = TableExprInner::Subquery(Box::new(statement));
+            table_expr.alias.get_or_insert_with(|| self.name.clone());
+            return Ok(());
+        }
+        visit_mut::walk_table_expr(self, table_expr)
+    }
+}
+
+/// Inline the (only) reference to `cte` in the first of `stmts` which references it
+fn inline_reference<'a>(
+    stmts: impl IntoIterator<Item = &'a mut SelectStatement>,
+    cte: CommonTableExpr,
+) {
+    let mut visitor = InlineReferenceVisitor {
+        name: &cte.name,
+        statement: Some(cte.statement),
+    };
+    for stmt in stmts {
+        let Ok(()) = visitor.visit_select_statement(stmt);
+    }
+}
+
+fn inline_ctes_in_statement(stmt: &mut SelectStatement) {
+    let mut ctes = mem::take(&mut stmt.ctes);
+    // CTEs can only reference CTEs defined before them, so by going from last to first we know
+    // that by the time we get to any CTE all of the references to it are in place, including the
+    // ones in the bodies of later CTEs that were inlined into the query.
+    let mut kept: Vec<CommonTableExpr> = vec![];
+    while let Some(cte) = ctes.pop() {
+        let num_references = count_references(stmt, &cte.name)
+            + kept
+                .iter()
+                .map(|kept_cte| count_references(&kept_cte.statement, &cte.name))
+                .sum::<usize>();
+
+        match num_references {
+            0 => {}
+            1 => inline_reference(
+                std::iter::once(&mut *stmt)
+                    .chain(kept.iter_mut().map(|kept_cte| &mut kept_cte.statement)),
+                cte,
+            ),
+            _ => kept.push(cte),
+        }
+    }
+    kept.reverse();
+
+    // Each of the remaining CTEs gets compiled separately from the rest of the query, so give each
+    // one its own copy of the earlier CTEs it references
+    for i in 0..kept.len() {
+        let (earlier, rest) = kept.split_at_mut(i);
+        let statement = &mut rest[0].statement;
+        let dependencies = earlier
+            .iter()
+            .filter(|dep| count_references(statement, &dep.name) > 0)
+            .cloned()
+            .collect::<Vec<_>>();
+        if !dependencies.is_empty() {
+            statement.ctes.splice(0..0, dependencies);
+        }
+    }
+
+    stmt.ctes = kept;
+}
+
+struct InlineCtesVisitor;
+
+impl<'ast> VisitorMut<'ast> for InlineCtesVisitor {
+    type Error = !;
+
+    fn visit_select_statement(
+        &mut self,
+        select_statement: &'ast mut SelectStatement,
+    ) -> Result<(), Self::Error> {
+        if !select_statement.ctes.is_empty() {
+            inline_ctes_in_statement(select_statement);
+        }
+        visit_mut::walk_select_statement(self, select_statement)
+    }
+}
+
+impl InlineCtes for SelectStatement {
+    fn inline_ctes(mut self) -> Self {
+        let Ok(()) = InlineCtesVisitor.visit_select_statement(&mut self);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom_sql::{parse_select_statement, Dialect, DialectDisplay};
+
+    use super::*;
+
+    fn rewrites_to(input: &str, expected: &str) {
+        let input = parse_select_statement(Dialect::MySQL, input).unwrap();
+        let expected = parse_select_statement(Dialect::MySQL, expected).unwrap();
+        let result = input.inline_ctes();
+        assert_eq!(
+            result,
+            expected,
+            "\nExpected: {}\n     Got: {}",
+            expected.display(Dialect::MySQL),
+            result.display(Dialect::MySQL)
+        );
+    }
+
+    #[test]
+    fn single_reference() {
+        rewrites_to(
+            "WITH max_val AS (SELECT max(value) AS v FROM t) SELECT max_val.v FROM max_val",
+            "SELECT max_val.v FROM (SELECT max(value) AS v FROM t) AS max_val",
+        );
+    }
+
+    #[test]
+    fn single_aliased_reference_in_join() {
+        rewrites_to(
+            "WITH u AS (SELECT id, name FROM users) \
+             SELECT t.x, u2.name FROM t JOIN u AS u2 ON t.user_id = u2.id",
+            "SELECT t.x, u2.name FROM t JOIN (SELECT id, name FROM users) AS u2 \
+             ON t.user_id = u2.id",
+        );
+    }
+
+    #[test]
+    fn unreferenced() {
+        rewrites_to(
+            "WITH unused AS (SELECT a FROM t) SELECT b FROM t2",
+            "SELECT b FROM t2",
+        );
+    }
+
+    #[test]
+    fn reference_in_subquery() {
+        rewrites_to(
+            "WITH ids AS (SELECT id FROM t) SELECT a FROM t2 WHERE id IN (SELECT id FROM ids)",
+            "SELECT a FROM t2 WHERE id IN (SELECT id FROM (SELECT id FROM t) AS ids)",
+        );
+    }
+
+    #[test]
+    fn chained() {
+        rewrites_to(
+            "WITH a AS (SELECT x FROM t), b AS (SELECT x FROM a) SELECT x FROM b",
+            "SELECT x FROM (SELECT x FROM (SELECT x FROM t) AS a) AS b",
+        );
+    }
+
+    #[test]
+    fn multiple_references_kept() {
+        rewrites_to(
+            "WITH a AS (SELECT x FROM t) SELECT a1.x FROM a AS a1 JOIN a AS a2 ON a1.x = a2.x",
+            "WITH a AS (SELECT x FROM t) SELECT a1.x FROM a AS a1 JOIN a AS a2 ON a1.x = a2.x",
+        );
+    }
+
+    #[test]
+    fn kept_cte_gets_copy_of_dependencies() {
+        rewrites_to(
+            "WITH a AS (SELECT x FROM t), b AS (SELECT x FROM a) \
+             SELECT b1.x FROM b AS b1 JOIN b AS b2 ON b1.x = b2.x",
+            "WITH b AS (SELECT x FROM (SELECT x FROM t) AS a) \
+             SELECT b1.x FROM b AS b1 JOIN b AS b2 ON b1.x = b2.x",
+        );
+    }
+
+    #[test]
+    fn shared_dependency() {
+        rewrites_to(
+            "WITH a AS (SELECT x FROM t), b AS (SELECT x FROM a) \
+             SELECT b1.x FROM b AS b1 JOIN b AS b2 ON b1.x = b2.x JOIN a ON a.x = b1.x \
+             JOIN a AS a2 ON a2.x = b2.x",
+            "WITH a AS (SELECT x FROM t), b AS (SELECT x FROM (SELECT x FROM t) AS a) \
+             SELECT b1.x FROM b AS b1 JOIN b AS b2 ON b1.x = b2.x JOIN a ON a.x = b1.x \
+             JOIN a AS a2 ON a2.x = b2.x",
+        );
+    }
+
+    #[test]
+    fn shadowed_in_subquery() {
+        rewrites_to(
+            "WITH a AS (SELECT x FROM t) \
+             SELECT x FROM a WHERE x IN (WITH a AS (SELECT y FROM t2) SELECT y FROM a)",
+            "SELECT x FROM (SELECT x FROM t) AS a \
+             WHERE x IN (SELECT y FROM (SELECT y FROM t2) AS a)",
+        );
+    }
+}
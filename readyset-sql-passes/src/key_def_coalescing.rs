@@ -72,6 +72,7 @@ mod tests {
                 keys: None,
             }),
             options: Ok(vec![]),
+            select: None,
         };
 
         let ctq = q.coalesce_key_definitions();
@@ -11,16 +11,21 @@
 pub mod adapter_rewrites;
 pub mod alias_removal;
 pub mod anonymize;
+mod canonicalize;
 mod create_table_columns;
+mod default_collation;
+mod detect_current_time;
 mod detect_problematic_self_joins;
 pub mod detect_unsupported_placeholders;
 pub mod expr;
 mod implied_tables;
+mod inline_ctes;
 mod inline_literals;
 mod key_def_coalescing;
 mod normalize_topk_with_aggregate;
 mod order_limit_removal;
 mod remove_numeric_field_references;
+mod rename_references;
 mod resolve_schemas;
 mod rewrite_between;
 mod star_expansion;
@@ -39,16 +44,21 @@ use nom_sql::{
 use readyset_errors::ReadySetResult;
 
 pub use crate::alias_removal::AliasRemoval;
+pub use crate::canonicalize::Canonicalize;
 pub use crate::create_table_columns::CreateTableColumns;
+pub use crate::default_collation::DefaultCollation;
+pub use crate::detect_current_time::DetectCurrentTime;
 pub use crate::detect_problematic_self_joins::DetectProblematicSelfJoins;
 pub use crate::detect_unsupported_placeholders::DetectUnsupportedPlaceholders;
 pub use crate::expr::ScalarOptimizeExpressions;
 pub use crate::implied_tables::ImpliedTableExpansion;
+pub use crate::inline_ctes::InlineCtes;
 pub use crate::inline_literals::InlineLiterals;
 pub use crate::key_def_coalescing::KeyDefinitionCoalescing;
 pub use crate::normalize_topk_with_aggregate::NormalizeTopKWithAggregate;
 pub use crate::order_limit_removal::OrderLimitRemoval;
 pub use crate::remove_numeric_field_references::RemoveNumericFieldReferences;
+pub use crate::rename_references::RenameReferences;
 pub use crate::resolve_schemas::ResolveSchemas;
 pub use crate::rewrite_between::RewriteBetween;
 pub use crate::star_expansion::StarExpansion;
@@ -153,13 +163,16 @@ impl Rewrite for CreateTableStatement {
                 context.invalidating_tables.as_deref_mut(),
             )?
             .normalize_create_table_columns()
+            .propagate_default_collation(context.dialect)
             .coalesce_key_definitions())
     }
 }
 
 impl Rewrite for SelectStatement {
     fn rewrite(self, context: &mut RewriteContext) -> ReadySetResult<Self> {
-        self.rewrite_between()
+        self.inline_ctes()
+            .detect_current_time()?
+            .rewrite_between()
             .scalar_optimize_expressions(context.dialect)
             .strip_post_filters()
             .resolve_schemas(
@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+
+use nom_sql::analysis::visit_mut::{self, VisitorMut};
+use nom_sql::{
+    Column, CreateTableBody, Relation, SelectSpecification, SelectStatement, SqlIdentifier,
+    TableExprInner, TableKey,
+};
+
+use crate::outermost_table_exprs;
+
+/// The names by which the columns of the renamed table can be referred to within a single query
+/// (or `CREATE TABLE` body)
+struct Scope {
+    /// The name of the table itself, and any aliases given to it
+    names: Vec<Relation>,
+    /// Whether unqualified columns refer to the renamed table
+    unqualified: bool,
+}
+
+struct RenameReferencesVisitor<'a> {
+    table: &'a Relation,
+    new_table: &'a Relation,
+    columns: &'a HashMap<SqlIdentifier, SqlIdentifier>,
+    scopes: Vec<Scope>,
+}
+
+impl<'a> RenameReferencesVisitor<'a> {
+    fn is_renamed_table(&self, relation: &Relation) -> bool {
+        relation.name == self.table.name
+            && (relation.schema.is_none() || relation.schema == self.table.schema)
+    }
+
+    fn refers_to_renamed_table(&self, column: &Column) -> bool {
+        match &column.table {
+            // Qualified columns can refer to tables in any enclosing scope, eg in correlated
+            // subqueries
+            Some(table) => self.scopes.iter().any(|scope| {
+                scope.names.iter().any(|name| {
+                    name == table || (table.schema.is_none() && name.name == table.name)
+                })
+            }),
+            None => self.scopes.last().is_some_and(|scope| scope.unqualified),
+        }
+    }
+
+    fn visit_with_scope<F>(&mut self, scope: Scope, f: F) -> Result<(), !>
+    where
+        F: FnOnce(&mut Self) -> Result<(), !>,
+    {
+        self.scopes.push(scope);
+        let res = f(self);
+        self.scopes.pop();
+        res
+    }
+}
+
+impl<'ast, 'a> VisitorMut<'ast> for RenameReferencesVisitor<'a> {
+    type Error = !;
+
+    fn visit_table(&mut self, table: &'ast mut Relation) -> Result<(), Self::Error> {
+        if self.is_renamed_table(table) {
+            *table = if table.schema.is_none() && self.new_table.schema == self.table.schema {
+                self.new_table.name.clone().into()
+            } else {
+                self.new_table.clone()
+            };
+        }
+        Ok(())
+    }
+
+    fn visit_column(&mut self, column: &'ast mut Column) -> Result<(), Self::Error> {
+        // Qualifiers which don't refer to the renamed table (even if they happen to have the same
+        // name, eg as an alias for another table) are left alone
+        if self.refers_to_renamed_table(column) {
+            if let Some(new_name) = self.columns.get(&column.name) {
+                column.name = new_name.clone();
+            }
+            if let Some(table) = &mut column.table {
+                self.visit_table(table)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_table_key(&mut self, table_key: &'ast mut TableKey) -> Result<(), Self::Error> {
+        if let TableKey::ForeignKey {
+            columns,
+            target_table,
+            target_columns,
+            ..
+        } = table_key
+        {
+            for column in columns {
+                self.visit_column(column)?;
+            }
+            // The target columns of a foreign key only refer to the renamed table if the foreign
+            // key references the table itself
+            if self.is_renamed_table(target_table) {
+                for column in target_columns {
+                    self.visit_column(column)?;
+                }
+            }
+            return self.visit_table(target_table);
+        }
+        visit_mut::walk_table_key(self, table_key)
+    }
+
+    fn visit_select_statement(
+        &mut self,
+        select_statement: &'ast mut SelectStatement,
+    ) -> Result<(), Self::Error> {
+        let table_exprs = outermost_table_exprs(select_statement).collect::<Vec<_>>();
+        let names = table_exprs
+            .iter()
+            .filter_map(|table_expr| match &table_expr.inner {
+                TableExprInner::Table(table) if self.is_renamed_table(table) => Some(
+                    table_expr
+                        .alias
+                        .clone()
+                        .map(Relation::from)
+                        .unwrap_or_else(|| table.clone()),
+                ),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let scope = Scope {
+            unqualified: table_exprs.len() == 1 && names.len() == 1,
+            names,
+        };
+
+        self.visit_with_scope(scope, |visitor| {
+            visit_mut::walk_select_statement(visitor, select_statement)
+        })
+    }
+}
+
+pub trait RenameReferences: Sized {
+    /// Rename all references to `table` to `new_table`, and all references to columns of that
+    /// table which are keys in `columns` to the corresponding value. Unqualified column references
+    /// are only renamed if the renamed table is the only table in scope.
+    #[must_use]
+    fn rename_references(
+        self,
+        table: &Relation,
+        new_table: &Relation,
+        columns: &HashMap<SqlIdentifier, SqlIdentifier>,
+    ) -> Self;
+}
+
+fn visitor<'a>(
+    table: &'a Relation,
+    new_table: &'a Relation,
+    columns: &'a HashMap<SqlIdentifier, SqlIdentifier>,
+) -> RenameReferencesVisitor<'a> {
+    RenameReferencesVisitor {
+        table,
+        new_table,
+        columns,
+        scopes: vec![],
+    }
+}
+
+impl RenameReferences for SelectStatement {
+    fn rename_references(
+        mut self,
+        table: &Relation,
+        new_table: &Relation,
+        columns: &HashMap<SqlIdentifier, SqlIdentifier>,
+    ) -> Self {
+        let Ok(()) = visitor(table, new_table, columns).visit_select_statement(&mut self);
+        self
+    }
+}
+
+impl RenameReferences for SelectSpecification {
+    fn rename_references(
+        mut self,
+        table: &Relation,
+        new_table: &Relation,
+        columns: &HashMap<SqlIdentifier, SqlIdentifier>,
+    ) -> Self {
+        let mut visitor = visitor(table, new_table, columns);
+        let Ok(()) = match &mut self {
+            SelectSpecification::Compound(stmt) => visitor.visit_compound_select_statement(stmt),
+            SelectSpecification::Simple(stmt) => visitor.visit_select_statement(stmt),
+        };
+        self
+    }
+}
+
+impl RenameReferences for CreateTableBody {
+    fn rename_references(
+        mut self,
+        table: &Relation,
+        new_table: &Relation,
+        columns: &HashMap<SqlIdentifier, SqlIdentifier>,
+    ) -> Self {
+        let mut visitor = visitor(table, new_table, columns);
+        let scope = Scope {
+            names: vec![table.clone()],
+            unqualified: true,
+        };
+        let Ok(()) = visitor.visit_with_scope(scope, |visitor| {
+            for field in &mut self.fields {
+                visitor.visit_column_specification(field)?;
+            }
+            for key in self.keys.iter_mut().flatten() {
+                visitor.visit_table_key(key)?;
+            }
+            Ok(())
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom_sql::{parse_create_table, parse_select_statement, Dialect, DialectDisplay};
+
+    use super::*;
+
+    fn columns(renames: &[(&str, &str)]) -> HashMap<SqlIdentifier, SqlIdentifier> {
+        renames
+            .iter()
+            .map(|(old, new)| ((*old).into(), (*new).into()))
+            .collect()
+    }
+
+    fn rewrites_to(
+        input: &str,
+        expected: &str,
+        table: &str,
+        new_table: &str,
+        renames: &[(&str, &str)],
+    ) {
+        let input = parse_select_statement(Dialect::MySQL, input).unwrap();
+        let expected = parse_select_statement(Dialect::MySQL, expected).unwrap();
+        let result = input.rename_references(&table.into(), &new_table.into(), &columns(renames));
+        assert_eq!(
+            result,
+            expected,
+            "\nExpected: {}\n     Got: {}",
+            expected.display(Dialect::MySQL),
+            result.display(Dialect::MySQL)
+        );
+    }
+
+    #[test]
+    fn qualified_columns() {
+        rewrites_to(
+            "SELECT t.x, t.y, u.x FROM t JOIN u ON t.id = u.t_id WHERE t.x = ?",
+            "SELECT t.z, t.y, u.x FROM t JOIN u ON t.id = u.t_id WHERE t.z = ?",
+            "t",
+            "t",
+            &[("x", "z")],
+        );
+    }
+
+    #[test]
+    fn unqualified_columns_single_table() {
+        rewrites_to(
+            "SELECT x, y FROM t WHERE x = ?",
+            "SELECT z, y FROM t WHERE z = ?",
+            "t",
+            "t",
+            &[("x", "z")],
+        );
+    }
+
+    #[test]
+    fn unqualified_columns_multiple_tables() {
+        rewrites_to(
+            "SELECT x FROM t, u",
+            "SELECT x FROM t, u",
+            "t",
+            "t",
+            &[("x", "z")],
+        );
+    }
+
+    #[test]
+    fn unqualified_columns_in_subquery_on_other_table() {
+        rewrites_to(
+            "SELECT x FROM t WHERE y IN (SELECT x FROM u)",
+            "SELECT z FROM t WHERE y IN (SELECT x FROM u)",
+            "t",
+            "t",
+            &[("x", "z")],
+        );
+    }
+
+    #[test]
+    fn aliased_table() {
+        rewrites_to(
+            "SELECT t1.x, t2.x FROM t AS t1 JOIN t AS t2 ON t1.id = t2.parent_id",
+            "SELECT t1.z, t2.z FROM t AS t1 JOIN t AS t2 ON t1.id = t2.parent_id",
+            "t",
+            "t",
+            &[("x", "z")],
+        );
+    }
+
+    #[test]
+    fn table_rename() {
+        rewrites_to(
+            "SELECT t.x FROM t WHERE t.y IN (SELECT u.y FROM u WHERE u.x = t.x)",
+            "SELECT t2.x FROM t2 WHERE t2.y IN (SELECT u.y FROM u WHERE u.x = t2.x)",
+            "t",
+            "t2",
+            &[],
+        );
+    }
+
+    #[test]
+    fn table_and_column_rename_in_subquery() {
+        rewrites_to(
+            "SELECT u.a FROM u WHERE u.b IN (SELECT x FROM t)",
+            "SELECT u.a FROM u WHERE u.b IN (SELECT z FROM t2)",
+            "t",
+            "t2",
+            &[("x", "z")],
+        );
+    }
+
+    #[test]
+    fn create_table_body() {
+        let body = parse_create_table(
+            Dialect::MySQL,
+            "CREATE TABLE t (x int, y int, PRIMARY KEY (x), \
+             FOREIGN KEY (y) REFERENCES t (x), FOREIGN KEY (x) REFERENCES u (x))",
+        )
+        .unwrap()
+        .body
+        .unwrap();
+        let expected = parse_create_table(
+            Dialect::MySQL,
+            "CREATE TABLE t (z int, y int, PRIMARY KEY (z), \
+             FOREIGN KEY (y) REFERENCES t (z), FOREIGN KEY (z) REFERENCES u (x))",
+        )
+        .unwrap()
+        .body
+        .unwrap();
+        assert_eq!(
+            body.rename_references(&"t".into(), &"t".into(), &columns(&[("x", "z")])),
+            expected
+        );
+    }
+}
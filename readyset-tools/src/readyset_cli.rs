@@ -0,0 +1,235 @@
+#![warn(clippy::panic)]
+//! Command-line tool for administering a ReadySet deployment through the API of its current
+//! leader.
+
+use anyhow::bail;
+use clap::builder::NonEmptyStringValueParser;
+use clap::{Parser, Subcommand, ValueEnum};
+use nom_sql::{DialectDisplay, Relation};
+use readyset_client::consensus::AuthorityType;
+use readyset_client::metrics::client::MetricsClient;
+use readyset_client::recipe::ChangeList;
+use readyset_client::{GraphvizOptions, ReadySetHandle};
+use url::Url;
+
+#[derive(Parser)]
+#[command(name = "readyset-cli")]
+struct Options {
+    #[arg(short, long, env("AUTHORITY_ADDRESS"), default_value("127.0.0.1:8500"))]
+    authority_address: String,
+
+    #[arg(long, env("AUTHORITY"), default_value("consul"), value_parser = ["consul"])]
+    authority: AuthorityType,
+
+    #[arg(short, long, env("DEPLOYMENT"), value_parser = NonEmptyStringValueParser::new())]
+    deployment: String,
+
+    /// The SQL dialect used to parse and print queries
+    #[arg(long, env("DATABASE_TYPE"), value_enum, default_value = "mysql")]
+    database_type: DatabaseType,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DatabaseType {
+    #[value(name = "mysql")]
+    MySql,
+    #[value(name = "postgresql")]
+    PostgreSql,
+}
+
+impl DatabaseType {
+    fn nom_sql_dialect(self) -> nom_sql::Dialect {
+        match self {
+            DatabaseType::MySql => nom_sql::Dialect::MySQL,
+            DatabaseType::PostgreSql => nom_sql::Dialect::PostgreSQL,
+        }
+    }
+
+    fn dialect(self) -> readyset_data::Dialect {
+        match self {
+            DatabaseType::MySql => readyset_data::Dialect::DEFAULT_MYSQL,
+            DatabaseType::PostgreSql => readyset_data::Dialect::DEFAULT_POSTGRESQL,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List all caches, as the statements which would create them
+    ListCaches,
+
+    /// Create a cache for a SELECT query
+    CreateCache {
+        /// The name to give the cache. Defaults to a name generated from the query
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Always serve the query from the cache, even inside transactions
+        #[arg(long)]
+        always: bool,
+
+        /// Schemas to resolve unqualified table names in the query against, in order
+        #[arg(long, value_delimiter = ',')]
+        schema_search_path: Vec<String>,
+
+        /// The SELECT query to cache
+        query: String,
+    },
+
+    /// Drop a cache
+    DropCache {
+        /// The name of the cache to drop, optionally qualified with its schema
+        #[arg(value_parser = parse_relation)]
+        name: Relation,
+    },
+
+    /// Show the status of replication from the upstream database, and of each replicated table
+    ReplicationStatus,
+
+    /// Dump the current values of the metrics recorded by every server in the deployment, as JSON
+    Metrics,
+
+    /// Drop a base table and snapshot it again from scratch from the upstream database. Caches
+    /// which read from the table are dropped along with it.
+    ResnapshotTable {
+        /// The name of the table to resnapshot, optionally qualified with its schema
+        #[arg(value_parser = parse_relation)]
+        table: Relation,
+    },
+
+    /// Show the keys read most often from a cache, along with how many of their reads missed
+    HotKeys {
+        /// The name of the cache, optionally qualified with its schema
+        #[arg(value_parser = parse_relation)]
+        cache: Relation,
+    },
+
+    /// Move all domains off of a worker and stop scheduling domains onto it
+    DrainWorker {
+        /// The URI the worker registered with the controller, as listed by the controller's
+        /// `/workers` endpoint
+        worker: Url,
+    },
+
+    /// Print a graphviz description of the dataflow graph
+    Graph {
+        /// Only print the part of the graph for the cache with the given name
+        #[arg(long, value_parser = parse_relation)]
+        cache: Option<Relation>,
+
+        /// Print a simplified graph, with less information about each node
+        #[arg(long)]
+        simple: bool,
+    },
+}
+
+/// Parse a table or cache name, optionally qualified with its schema as `schema.name`
+fn parse_relation(s: &str) -> anyhow::Result<Relation> {
+    let (schema, name) = match s.split_once('.') {
+        Some((schema, name)) => (Some(schema), name),
+        None => (None, s),
+    };
+    if name.is_empty() || schema.map_or(false, str::is_empty) {
+        bail!("Invalid name: {s}");
+    }
+    Ok(Relation {
+        schema: schema.map(Into::into),
+        name: name.into(),
+    })
+}
+
+impl Options {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let authority = self
+            .authority
+            .to_authority(&self.authority_address, &self.deployment);
+
+        let mut handle: ReadySetHandle = ReadySetHandle::new(authority).await;
+        handle.ready().await?;
+
+        let dialect = self.database_type.nom_sql_dialect();
+        match self.command {
+            Command::ListCaches => {
+                for cache in handle.verbose_views().await? {
+                    println!("{};", cache.display(dialect));
+                }
+            }
+            Command::CreateCache {
+                name,
+                always,
+                schema_search_path,
+                query,
+            } => {
+                let statement = format!(
+                    "CREATE CACHE {}{}FROM {query}",
+                    if always { "ALWAYS " } else { "" },
+                    name.map(|name| format!("{name} ")).unwrap_or_default(),
+                );
+                let changes = ChangeList::from_str(statement, self.database_type.dialect())?
+                    .with_schema_search_path(
+                        schema_search_path.into_iter().map(Into::into).collect(),
+                    );
+                handle.extend_recipe(changes).await?;
+            }
+            Command::DropCache { name } => {
+                handle.remove_query(&name).await?;
+            }
+            Command::ReplicationStatus => {
+                let status: Vec<(String, String)> = handle.status().await?.into();
+                for (name, value) in status {
+                    println!("{name}: {value}");
+                }
+                println!(
+                    "Replication pause state: {:?}",
+                    handle.replication_pause_state().await?
+                );
+                for (table, status) in handle.table_statuses().await? {
+                    println!("{}: {}", table.display(dialect), status.replication_status);
+                }
+            }
+            Command::Metrics => {
+                let mut client = MetricsClient::new(handle)?;
+                for dump in client.get_metrics().await? {
+                    println!("{}: {}", dump.addr, serde_json::to_string(&dump.metrics)?);
+                }
+            }
+            Command::ResnapshotTable { table } => {
+                handle.resnapshot_table(&table).await?;
+            }
+            Command::HotKeys { cache } => {
+                let hot_keys = handle.view_hot_keys(&cache).await?;
+                println!("Total key reads: {}", hot_keys.reader.total_reads);
+                for hot_key in hot_keys.reader.keys {
+                    println!(
+                        "{:?}: {} reads (overcounted by up to {}), {} misses",
+                        hot_key.key, hot_key.reads, hot_key.overestimate, hot_key.misses
+                    );
+                }
+            }
+            Command::DrainWorker { worker } => {
+                let moved = handle.drain_worker(&worker).await?;
+                println!("Moved {moved} domain replicas off of {worker}");
+            }
+            Command::Graph { cache, simple } => {
+                let graph = handle
+                    .graphviz(GraphvizOptions {
+                        for_query: cache,
+                        detailed: !simple,
+                    })
+                    .await?;
+                println!("{graph}");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let options = Options::parse();
+    options.run().await
+}
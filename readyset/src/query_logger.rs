@@ -66,6 +66,7 @@ impl QueryLogger {
         const ADAPTER_REWRITE_PARAMS: AdapterRewriteParams = AdapterRewriteParams {
             server_supports_pagination: true,
             server_supports_mixed_comparisons: true,
+            server_supports_post_lookup: true,
         };
 
         SharedString::from(match query {
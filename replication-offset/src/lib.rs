@@ -10,7 +10,7 @@ use std::fmt;
 use std::hash::Hash;
 
 use mysql::MySqlPosition;
-use nom_sql::Relation;
+use nom_sql::{Relation, SqlIdentifier};
 use postgres::PostgresPosition;
 use readyset_errors::{internal_err, ReadySetError, ReadySetResult};
 use serde::{Deserialize, Serialize};
@@ -144,6 +144,15 @@ pub struct ReplicationOffsets {
     ///
     /// A table with [`None`] as its replication offset has not yet been snapshotted successfully
     pub tables: HashMap<Relation, Option<ReplicationOffset>>,
+
+    /// Replication offset for the schema of each additional upstream database, keyed by the
+    /// namespace its relations are replicated into.
+    ///
+    /// Offsets in different namespaces come from different replication logs, so these are never
+    /// compared with each other or with [`schema`](Self::schema) - a replicator only ever looks at
+    /// the offsets for its own upstream database.
+    #[serde(default)]
+    pub namespace_schemas: HashMap<SqlIdentifier, ReplicationOffset>,
 }
 
 impl ReplicationOffsets {
@@ -153,6 +162,7 @@ impl ReplicationOffsets {
         Self {
             schema,
             tables: HashMap::new(),
+            namespace_schemas: HashMap::new(),
         }
     }
 
@@ -312,6 +322,7 @@ mod tests {
                         ),
                     ),
                 ]),
+                ..Default::default()
             };
             let res: MySqlPosition = offsets.max_offset().unwrap().unwrap().try_into().unwrap();
             assert_eq!(res.binlog_file_name().to_string(), "test.00001");
@@ -344,6 +355,7 @@ mod tests {
                         ),
                     ),
                 ]),
+                ..Default::default()
             };
             let res = offsets.max_offset();
             res.unwrap_err();
@@ -371,6 +383,7 @@ mod tests {
                         ),
                     ),
                 ]),
+                ..Default::default()
             };
             let res = offsets.max_offset().unwrap();
             assert!(res.is_none());
@@ -395,6 +408,7 @@ mod tests {
                     ),
                     ("t2".into(), None),
                 ]),
+                ..Default::default()
             };
             let res = offsets.max_offset().unwrap();
             assert!(res.is_none());
@@ -0,0 +1,90 @@
+use readyset_client::{Modification, TableOperation};
+use readyset_data::DfValue;
+
+/// Replace the values of the columns at the given indices with `NULL` in all the given table
+/// operations, so that the values of columns ignored with --replication-columns-ignore are never
+/// written to ReadySet.
+///
+/// Keys are left as-is, since primary key columns can't be ignored.
+pub(crate) fn null_ignored_columns(actions: &mut [TableOperation], indices: &[usize]) {
+    let null_row = |row: &mut Vec<DfValue>| {
+        for &idx in indices {
+            if let Some(value) = row.get_mut(idx) {
+                *value = DfValue::None;
+            }
+        }
+    };
+    let null_update = |update: &mut Vec<Modification>| {
+        for &idx in indices {
+            if let Some(modification) = update.get_mut(idx) {
+                if !matches!(modification, Modification::None) {
+                    *modification = Modification::Set(DfValue::None);
+                }
+            }
+        }
+    };
+
+    for action in actions {
+        match action {
+            TableOperation::Insert(row) | TableOperation::DeleteRow { row } => null_row(row),
+            TableOperation::InsertOrUpdate { row, update } => {
+                null_row(row);
+                null_update(update);
+            }
+            TableOperation::Update { update, .. } => null_update(update),
+            TableOperation::DeleteByKey { .. }
+            | TableOperation::Truncate
+            | TableOperation::SetReplicationOffset(_)
+            | TableOperation::SetSnapshotMode(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn null_columns() {
+        let mut actions = vec![
+            TableOperation::Insert(vec![1.into(), "secret".into(), 2.into()]),
+            TableOperation::DeleteRow {
+                row: vec![1.into(), "secret".into(), 2.into()],
+            },
+            TableOperation::Update {
+                key: vec![1.into()],
+                update: vec![
+                    Modification::None,
+                    Modification::Set("secret".into()),
+                    Modification::Set(3.into()),
+                ],
+            },
+            TableOperation::DeleteByKey {
+                key: vec![1.into()],
+            },
+        ];
+
+        null_ignored_columns(&mut actions, &[1]);
+
+        assert_eq!(
+            actions,
+            vec![
+                TableOperation::Insert(vec![1.into(), DfValue::None, 2.into()]),
+                TableOperation::DeleteRow {
+                    row: vec![1.into(), DfValue::None, 2.into()],
+                },
+                TableOperation::Update {
+                    key: vec![1.into()],
+                    update: vec![
+                        Modification::None,
+                        Modification::Set(DfValue::None),
+                        Modification::Set(3.into()),
+                    ],
+                },
+                TableOperation::DeleteByKey {
+                    key: vec![1.into()],
+                },
+            ]
+        );
+    }
+}
@@ -7,16 +7,20 @@
     let_chains
 )]
 pub mod db_util;
+pub(crate) mod ignored_columns;
 pub(crate) mod mysql_connector;
 pub(crate) mod noria_adapter;
 pub(crate) mod postgres_connector;
+pub(crate) mod row_filter;
+pub(crate) mod table_dependencies;
 pub(crate) mod table_filter;
+pub(crate) mod value_normalizer;
 
 use std::time::Duration;
 
 use metrics::{register_gauge, Gauge};
 use nom_sql::Relation;
-pub use noria_adapter::{cleanup, NoriaAdapter};
+pub use noria_adapter::{cleanup, release_replication_slot, NoriaAdapter};
 use readyset_client::metrics::recorded;
 use readyset_errors::ReadySetError;
 pub use replication_offset::mysql::MySqlPosition;
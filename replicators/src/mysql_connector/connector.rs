@@ -1,8 +1,10 @@
 use std::convert::{TryFrom, TryInto};
 use std::io;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use binlog::consts::{BinlogChecksumAlg, EventType};
+use database_utils::DEFAULT_MYSQL_SERVER_ID;
 use metrics::counter;
 use mysql::binlog::events::StatusVarVal;
 use mysql::binlog::jsonb::{self, JsonbToJsonError};
@@ -18,12 +20,14 @@ use readyset_data::{DfValue, Dialect};
 use readyset_errors::{internal, internal_err, ReadySetError, ReadySetResult};
 use replication_offset::mysql::MySqlPosition;
 use replication_offset::ReplicationOffset;
-use tracing::{error, info, warn};
+use tracing::{error, info, trace, warn};
 
 use crate::noria_adapter::{Connector, ReplicationAction};
 
 const CHECKSUM_QUERY: &str = "SET @master_binlog_checksum='CRC32'";
-const DEFAULT_SERVER_ID: u32 = u32::MAX - 55;
+/// The number of heartbeat intervals we wait for an event or heartbeat from the server before
+/// presuming the binlog connection is dead
+const HEARTBEAT_TIMEOUT_INTERVALS: u32 = 2;
 
 /// A connector that connects to a MySQL server and starts reading binlogs from a given position.
 ///
@@ -39,6 +43,12 @@ const DEFAULT_SERVER_ID: u32 = u32::MAX - 55;
 /// * `REPLICATION CLIENT` - to use SHOW MASTER STATUS, SHOW SLAVE STATUS, and SHOW BINARY LOGS;
 ///
 /// The connector must also be assigned a unique `server_id` value
+///
+/// If configured with a heartbeat interval, the connector asks the server to send heartbeats while
+/// there are no new events in the binlog, so that it can tell an idle server apart from a dead
+/// connection. Managed databases such as RDS and Aurora silently drop idle connections and purge
+/// binlogs aggressively, so detecting a dead connection promptly allows us to reconnect before the
+/// binlog we're replicating from is purged.
 pub(crate) struct MySqlBinlogConnector {
     /// This is the underlying (regular) MySQL connection
     connection: mysql::Conn,
@@ -52,15 +62,21 @@ pub(crate) struct MySqlBinlogConnector {
     /// The GTID of the current transaction. Table modification events will have
     /// the current GTID attached if enabled in mysql.
     current_gtid: Option<u64>,
+    /// Whether the last action we returned was part of an upstream transaction which hasn't
+    /// committed yet
+    in_transaction: bool,
     /// Whether to log statements received by the connector
     enable_statement_logging: bool,
+    /// The interval at which the server sends heartbeats while there are no new events, if
+    /// heartbeats are enabled
+    heartbeat_interval: Option<Duration>,
 }
 
 impl MySqlBinlogConnector {
     /// The binlog replica must be assigned a unique `server_id` in the replica topology
     /// if one is not assigned we will use (u32::MAX - 55)
     fn server_id(&self) -> u32 {
-        self.server_id.unwrap_or(DEFAULT_SERVER_ID)
+        self.server_id.unwrap_or(DEFAULT_MYSQL_SERVER_ID)
     }
 
     /// In order to request a binlog, we must first register as a replica, and let the primary
@@ -69,6 +85,18 @@ impl MySqlBinlogConnector {
     async fn register_as_replica(&mut self) -> mysql::Result<()> {
         self.connection.query_drop(CHECKSUM_QUERY).await?;
 
+        if let Some(heartbeat_interval) = self.heartbeat_interval {
+            // The heartbeat period is read by the server's binlog dump thread, in nanoseconds.
+            // Versions from 8.0.26 on read `source_heartbeat_period`, earlier versions (and
+            // MariaDB) read `master_heartbeat_period`
+            let period = heartbeat_interval.as_nanos();
+            self.connection
+                .query_drop(format!(
+                    "SET @master_heartbeat_period = {period}, @source_heartbeat_period = {period}"
+                ))
+                .await?;
+        }
+
         let cmd = mysql_common::packets::ComRegisterSlave::new(self.server_id());
         self.connection.write_command(&cmd).await?;
         // Server will respond with OK.
@@ -101,6 +129,24 @@ impl MySqlBinlogConnector {
         Ok(())
     }
 
+    /// Returns whether the binlog file we're about to start replicating from still exists on the
+    /// server.
+    ///
+    /// Binlogs may be purged between taking a snapshot and starting replication, or while we're
+    /// disconnected, especially on managed databases which retain binlogs for as short a time as
+    /// possible by default.
+    async fn binlog_available(&mut self) -> mysql::Result<bool> {
+        let binlogs: Vec<Option<String>> = self
+            .connection
+            .query_map("SHOW BINARY LOGS", |row: mysql::Row| row.get(0))
+            .await?;
+        let filename = self.next_position.binlog_file_name().to_string();
+        Ok(binlogs
+            .into_iter()
+            .flatten()
+            .any(|binlog| binlog == filename))
+    }
+
     /// Compute the checksum of the event and compare to the supplied checksum
     fn validate_event_checksum(event: &binlog::events::Event) -> bool {
         if let Ok(Some(BinlogChecksumAlg::BINLOG_CHECKSUM_ALG_CRC32)) =
@@ -122,6 +168,7 @@ impl MySqlBinlogConnector {
         next_position: MySqlPosition,
         server_id: Option<u32>,
         enable_statement_logging: bool,
+        heartbeat_interval: Option<Duration>,
     ) -> ReadySetResult<Self> {
         let mut connector = MySqlBinlogConnector {
             connection: mysql::Conn::new(mysql_opts).await?,
@@ -129,9 +176,25 @@ impl MySqlBinlogConnector {
             server_id,
             next_position,
             current_gtid: None,
+            in_transaction: false,
             enable_statement_logging,
+            heartbeat_interval,
         };
 
+        match connector.binlog_available().await {
+            Ok(true) => {}
+            Ok(false) => {
+                warn!(
+                    next_position = %connector.next_position,
+                    "Binlog to resume replication from has been purged from the upstream database"
+                );
+                return Err(ReadySetError::FullResnapshotNeeded);
+            }
+            // Listing binlogs requires the REPLICATION CLIENT permission, which we can do without -
+            // if the binlog has been purged we'll find out when we request it
+            Err(error) => warn!(%error, "Could not check that binlog is available"),
+        }
+
         connector.register_as_replica().await?;
         let binlog_request = connector.request_binlog().await;
         match binlog_request {
@@ -160,7 +223,23 @@ impl MySqlBinlogConnector {
 
     /// Get the next raw binlog event
     async fn next_event(&mut self) -> mysql::Result<binlog::events::Event> {
-        let packet = self.connection.read_packet().await?;
+        let packet = match self.heartbeat_interval {
+            Some(heartbeat_interval) => {
+                let timeout = heartbeat_interval * HEARTBEAT_TIMEOUT_INTERVALS;
+                tokio::time::timeout(timeout, self.connection.read_packet())
+                    .await
+                    .map_err(|_| {
+                        io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            format!(
+                                "Received no binlog events or heartbeats from MySQL server in \
+                                 {timeout:?}, presuming connection is dead"
+                            ),
+                        )
+                    })??
+            }
+            None => self.connection.read_packet().await?,
+        };
         if let Some(first_byte) = packet.first() {
             // We should only see EOF/(254) if the mysql upstream has gone away or the
             // NON_BLOCKING SQL flag is set,
@@ -199,6 +278,17 @@ impl MySqlBinlogConnector {
         loop {
             let binlog_event = self.next_event().await?;
 
+            if matches!(
+                binlog_event.header().event_type(),
+                Ok(EventType::HEARTBEAT_EVENT)
+            ) {
+                // Sent by the server in place of events while it has nothing new to send, to let
+                // us know it's still there. Heartbeats aren't written to the binlog, so they
+                // don't move our position.
+                trace!(next_position = %self.next_position, "Received binlog heartbeat");
+                continue;
+            }
+
             if u64::from(binlog_event.header().log_pos()) < self.next_position.position
                 && self.next_position.position + u64::from(binlog_event.header().event_size())
                     > u64::from(u32::MAX)
@@ -245,6 +335,18 @@ impl MySqlBinlogConnector {
                         info!(target: "replicator_statement", "{:?}", ev);
                     }
 
+                    // Transactions on non-transactional storage engines (such as MyISAM) end with a
+                    // `COMMIT` query, rather than an XID_EVENT
+                    let query = ev.query();
+                    if query.eq_ignore_ascii_case("COMMIT")
+                        || query.eq_ignore_ascii_case("ROLLBACK")
+                    {
+                        if std::mem::take(&mut self.in_transaction) {
+                            return Ok((ReplicationAction::Commit, &self.next_position));
+                        }
+                        continue;
+                    }
+
                     let schema = match ev
                         .status_vars()
                         .get_status_var(binlog::consts::StatusVarKey::UpdatedDbNames)
@@ -326,6 +428,7 @@ impl MySqlBinlogConnector {
                         ));
                     }
 
+                    self.in_transaction = true;
                     return Ok((
                         ReplicationAction::TableAction {
                             table: Relation {
@@ -385,6 +488,7 @@ impl MySqlBinlogConnector {
                         ));
                     }
 
+                    self.in_transaction = true;
                     return Ok((
                         ReplicationAction::TableAction {
                             table: Relation {
@@ -429,6 +533,7 @@ impl MySqlBinlogConnector {
                         });
                     }
 
+                    self.in_transaction = true;
                     return Ok((
                         ReplicationAction::TableAction {
                             table: Relation {
@@ -467,17 +572,32 @@ impl MySqlBinlogConnector {
                     self.current_gtid = Some(ev.gno());
                 }
 
-                /*
-
-                EventType::ANONYMOUS_GTID_EVENT => {}
-
                 EventType::XID_EVENT => {
-                    // Generated for a commit of a transaction that modifies one or more tables of an XA-capable
-                    // storage engine. Normal transactions are implemented by sending a QUERY_EVENT containing a
-                    // BEGIN statement and a QUERY_EVENT containing a COMMIT statement
-                    // (or a ROLLBACK statement if the transaction is rolled back).
+                    // Generated for a commit of a transaction that modifies one or more tables of
+                    // an XA-capable storage engine, such as InnoDB. If we returned any of the
+                    // transaction's changes, let the caller know it's over, so that it can pause
+                    // replication between transactions.
+                    if self.enable_statement_logging {
+                        info!(target: "replicator_statement", "XID_EVENT");
+                    }
+                    if std::mem::take(&mut self.in_transaction) {
+                        return Ok((ReplicationAction::Commit, &self.next_position));
+                    }
+                }
+
+                ev @ EventType::ANONYMOUS_GTID_EVENT => {
+                    // Written in place of a GTID_EVENT for transactions which weren't assigned a
+                    // GTID, such as when `gtid_mode` is off (the default on RDS and Aurora), or
+                    // while it's being changed online. Make sure we don't attach the GTID of an
+                    // earlier transaction to this one.
+                    if self.enable_statement_logging {
+                        info!(target: "replicator_statement", "{:?}", ev);
+                    }
+                    self.current_gtid = None;
                 }
 
+                /*
+
                 EventType::START_EVENT_V3 // Old version of FORMAT_DESCRIPTION_EVENT
                 | EventType::FORMAT_DESCRIPTION_EVENT // A descriptor event that is written to the beginning of each binary log file. This event is used as of MySQL 5.0; it supersedes START_EVENT_V3.
                 | EventType::STOP_EVENT // Written when mysqld stops
@@ -642,7 +762,23 @@ impl Connector for MySqlBinlogConnector {
         _: &ReplicationOffset,
         until: Option<&ReplicationOffset>,
     ) -> ReadySetResult<(ReplicationAction, ReplicationOffset)> {
-        let (action, pos) = self.next_action_inner(until).await?;
+        let (action, pos) = match self.next_action_inner(until).await {
+            Ok(res) => res,
+            // The binlog we're replicating from was purged out from under us, which can happen if
+            // we fall far enough behind on a server with a short binlog retention period
+            Err(mysql_async::Error::Server(ref err))
+                if mysql_srv::ErrorKind::from(err.code)
+                    == mysql_srv::ErrorKind::ER_MASTER_FATAL_ERROR_READING_BINLOG =>
+            {
+                error!(error = %err, "Failed to read binlog");
+                return Err(ReadySetError::FullResnapshotNeeded);
+            }
+            Err(err) => return Err(err.into()),
+        };
         Ok((action, pos.into()))
     }
+
+    fn in_transaction(&self) -> bool {
+        self.in_transaction
+    }
 }
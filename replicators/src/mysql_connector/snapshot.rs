@@ -4,6 +4,7 @@ use std::fmt::{self, Display};
 use std::future;
 use std::time::Instant;
 
+use database_utils::{IgnoredColumns, ValueSizeCaps};
 use futures::future::TryFutureExt;
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
@@ -11,7 +12,9 @@ use itertools::Itertools;
 use mysql::prelude::Queryable;
 use mysql::{Transaction, TxOpts};
 use mysql_async as mysql;
-use nom_sql::{DialectDisplay, NonReplicatedRelation, NotReplicatedReason, Relation};
+use nom_sql::{
+    DialectDisplay, NonReplicatedRelation, NotReplicatedReason, Relation, SqlIdentifier,
+};
 use readyset_client::recipe::changelist::{Change, ChangeList};
 use readyset_data::Dialect;
 use readyset_errors::{internal_err, ReadySetResult};
@@ -22,7 +25,10 @@ use tracing::{debug, error, info, info_span, warn};
 use tracing_futures::Instrument;
 
 use crate::db_util::DatabaseSchemas;
+use crate::row_filter::RowFilters;
+use crate::table_dependencies::TableDependencies;
 use crate::table_filter::TableFilter;
+use crate::value_normalizer::ValueNormalizer;
 use crate::TablesSnapshottingGaugeGuard;
 
 const BATCH_SIZE: usize = 1000; // How many queries to buffer before pushing to ReadySet
@@ -50,6 +56,12 @@ pub(crate) struct MySqlReplicator {
     pub(crate) pool: mysql::Pool,
     /// Filters out the desired tables to snapshot and replicate
     pub(crate) table_filter: TableFilter,
+    /// Filters out the desired rows of each table to snapshot and replicate
+    pub(crate) row_filters: RowFilters,
+    /// Columns which are never snapshotted or replicated
+    pub(crate) ignored_columns: IgnoredColumns,
+    /// Caps on the size of the values of columns
+    pub(crate) value_size_caps: ValueSizeCaps,
 }
 
 /// Get the list of tables defined in the database
@@ -202,6 +214,7 @@ impl MySqlReplicator {
         }
 
         let mut bad_tables = Vec::new();
+        let mut dependencies = TableDependencies::default();
         // Process `CREATE TABLE` statements
         for (db, table) in replicated_tables.iter() {
             match create_for_table(&mut tx, db, table, TableKind::BaseTable)
@@ -218,6 +231,19 @@ impl MySqlReplicator {
                     future::ready(ChangeList::from_str(create_table, Dialect::DEFAULT_MYSQL))
                 })
                 .and_then(|changelist| {
+                    for change in changelist.changes() {
+                        if let Change::CreateTable { statement, .. } = change
+                            && let Ok(body) = &statement.body
+                        {
+                            dependencies.add_table(
+                                &Relation {
+                                    schema: Some(db.into()),
+                                    name: table.into(),
+                                },
+                                body.keys.iter().flatten(),
+                            );
+                        }
+                    }
                     noria.extend_recipe_no_leader_ready(
                         changelist.with_schema_search_path(vec![db.clone().into()]),
                     )
@@ -249,6 +275,7 @@ impl MySqlReplicator {
         bad_tables
             .into_iter()
             .for_each(|(db, table)| self.table_filter.deny_replication(&db, &table));
+        dependencies.warn_on_excluded_references(&self.table_filter, nom_sql::Dialect::MySQL);
 
         // We process all views, regardless of their schemas and the table filter, since a view can
         // exist that only selects from tables in other schemas.
@@ -303,7 +330,7 @@ impl MySqlReplicator {
             .set_schema_replication_offset(Some(&binlog_position.into()))
             .await?;
 
-        let table_list = replicated_tables
+        let mut table_list = replicated_tables
             .into_iter()
             // refilter to remove any bad tables that failed to extend recipe
             .filter(|(schema, table)| {
@@ -315,13 +342,24 @@ impl MySqlReplicator {
                 name: name.into(),
             })
             .collect::<Vec<_>>();
+        // Tables are snapshotted after the tables their foreign keys reference. `dump_tables` pops
+        // tables off the end of the list, so the referenced tables go last.
+        dependencies.sort_referenced_first(&mut table_list, |t| t);
+        table_list.reverse();
         Ok((tx, table_list))
     }
 
-    /// Call `SELECT * FROM table` and convert all rows into a ReadySet row
+    /// Call `SELECT * FROM table`, filtered by the table's row filter if it has one, and convert
+    /// all rows into a ReadySet row
     /// it may seem inefficient but apparently that is the correct way to
     /// replicate a table, and `mysqldump` and `debezium` do just that
-    pub(crate) async fn dump_table(&self, table: &Relation) -> mysql::Result<TableDumper> {
+    ///
+    /// If any of the given columns of the table are ignored, they're selected as `NULL` instead.
+    pub(crate) async fn dump_table(
+        &self,
+        table: &Relation,
+        columns: &[SqlIdentifier],
+    ) -> mysql::Result<TableDumper> {
         let mut tx = self
             .pool
             .start_transaction(tx_opts())
@@ -333,11 +371,39 @@ impl MySqlReplicator {
             .await
             .map_err(log_err);
 
-        let query_count = format!(
+        // `TIMESTAMP` values are returned in the session timezone, but are always replicated from
+        // the binlog in UTC, so make sure we snapshot them in UTC as well
+        tx.query_drop("SET SESSION time_zone = '+00:00'")
+            .await
+            .map_err(log_err)?;
+
+        let mut query_count = format!(
             "select count(*) from {}",
             table.display(nom_sql::Dialect::MySQL)
         );
-        let query = format!("select * from {}", table.display(nom_sql::Dialect::MySQL));
+        let fields = match self.ignored_columns.for_table(table) {
+            Some(ignored) => columns
+                .iter()
+                .map(|column| {
+                    let quoted = nom_sql::Dialect::MySQL.quote_identifier(column);
+                    if ignored.contains(column) {
+                        format!("NULL as {quoted}")
+                    } else {
+                        quoted.to_string()
+                    }
+                })
+                .join(", "),
+            None => "*".to_owned(),
+        };
+        let mut query = format!(
+            "select {fields} from {}",
+            table.display(nom_sql::Dialect::MySQL)
+        );
+        if let Some(row_filter) = self.row_filters.expr_for_table(table) {
+            let where_clause = format!(" where {}", row_filter.display(nom_sql::Dialect::MySQL));
+            query_count.push_str(&where_clause);
+            query.push_str(&where_clause);
+        }
         Ok(TableDumper {
             query_count,
             query,
@@ -377,10 +443,12 @@ impl MySqlReplicator {
     }
 
     /// Replicate a single table from the provided TableDumper and into ReadySet by
-    /// converting every MySQL row into ReadySet row and calling `insert_many` in batches
+    /// converting every MySQL row into ReadySet row, normalizing its values, and calling
+    /// `insert_many` in batches
     async fn replicate_table(
         mut dumper: TableDumper,
         mut table_mutator: readyset_client::Table,
+        normalizer: ValueNormalizer,
         snapshot_report_interval_secs: u16,
     ) -> ReadySetResult<()> {
         let mut cnt = 0;
@@ -406,7 +474,7 @@ impl MySqlReplicator {
         let snapshot_report_interval_secs = snapshot_report_interval_secs as u64;
 
         loop {
-            let row = match row_stream.next().await {
+            let mut row = match row_stream.next().await {
                 Ok(Some(row)) => row,
                 Ok(None) => break,
                 Err(err) if cnt == nrows => {
@@ -418,6 +486,7 @@ impl MySqlReplicator {
                 }
             };
 
+            normalizer.normalize_row(&mut row);
             rows.push(row);
             cnt += 1;
 
@@ -551,6 +620,9 @@ impl MySqlReplicator {
             "Snapshotting table",
             table = %table.display(nom_sql::Dialect::MySQL)
         );
+        let table_mutator = noria.table(table.clone()).instrument(span.clone()).await?;
+        let normalizer = ValueNormalizer::new(&table_mutator, &self.value_size_caps);
+
         span.in_scope(|| info!("Acquiring read lock"));
         let mut read_lock = self.lock_table(&table).await?;
         // We acquire the position for each table individually, since it changes from
@@ -558,22 +630,28 @@ impl MySqlReplicator {
         let repl_offset = ReplicationOffset::from(self.get_binlog_position().await?);
         span.in_scope(|| info!("Snapshotting table"));
 
-        let dumper = self.dump_table(&table).instrument(span.clone()).await?;
+        let dumper = self
+            .dump_table(&table, table_mutator.columns())
+            .instrument(span.clone())
+            .await?;
 
         // At this point we have a transaction that will see *that* table at *this* binlog
         // position, so we can drop the read lock
         read_lock.query_drop("UNLOCK TABLES").await?;
         span.in_scope(|| info!("Read lock released"));
 
-        let table_mutator = noria.table(table.clone()).instrument(span.clone()).await?;
-
         Ok(tokio::spawn(async move {
             (
                 table,
                 repl_offset,
-                Self::replicate_table(dumper, table_mutator, snapshot_report_interval_secs)
-                    .instrument(span)
-                    .await,
+                Self::replicate_table(
+                    dumper,
+                    table_mutator,
+                    normalizer,
+                    snapshot_report_interval_secs,
+                )
+                .instrument(span)
+                .await,
             )
         }))
     }
@@ -589,9 +667,9 @@ impl MySqlReplicator {
         let mut replication_tasks = FuturesUnordered::new();
         let mut compacting_tasks = FuturesUnordered::new();
 
-        // For each table we spawn a new task to parallelize the replication process, with a limit
-        // We pop front because we add the tables before the views, and the views depend on the
-        // tables. TODO: do we need to fully finish tables before views?
+        // For each table we spawn a new task to parallelize the replication process, with a limit.
+        // Tables are popped off the end of the list, which holds the tables referenced by foreign
+        // keys (see `load_recipe_with_meta_lock`)
         while let Some(table) = table_list.pop() {
             if replication_offsets.has_table(&table) {
                 info!(
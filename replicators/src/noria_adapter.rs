@@ -3,20 +3,24 @@ use std::mem;
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use database_utils::{DatabaseURL, UpstreamConfig};
+use database_utils::{
+    DatabaseURL, IgnoredColumns, UpstreamConfig, UpstreamTlsConnector, ValueSizeCaps,
+};
 use deadpool_postgres::{Manager, ManagerConfig, Pool, RecyclingMethod};
 use failpoint_macros::set_failpoint;
 use metrics::{counter, histogram};
 use mysql::prelude::Queryable;
-use mysql::{OptsBuilder, PoolConstraints, PoolOpts, SslOpts};
-use nom_sql::{DialectDisplay, NonReplicatedRelation, NotReplicatedReason, Relation};
-use postgres_native_tls::MakeTlsConnector;
-use postgres_protocol::escape::escape_literal;
+use mysql::{OptsBuilder, PoolConstraints, PoolOpts};
+use mysql_async as mysql;
+use nom_sql::{
+    AlterTableDefinition, DialectDisplay, NonReplicatedRelation, NotReplicatedReason, Relation,
+};
 use readyset_client::consistency::Timestamp;
 #[cfg(feature = "failure_injection")]
 use readyset_client::failpoints;
 use readyset_client::metrics::recorded::{self, SnapshotStatusTag};
 use readyset_client::recipe::changelist::{Change, ChangeList};
+use readyset_client::status::ReplicationPauseState;
 use readyset_client::utils::retry_with_exponential_backoff;
 use readyset_client::{ReadySetHandle, Table, TableOperation};
 use readyset_data::Dialect;
@@ -24,16 +28,22 @@ use readyset_errors::{internal_err, set_failpoint_return_err, ReadySetError, Rea
 use readyset_telemetry_reporter::{TelemetryBuilder, TelemetryEvent, TelemetrySender};
 use replication_offset::{ReplicationOffset, ReplicationOffsets};
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::sync::watch;
+use tokio_postgres as pgsql;
 use tracing::{debug, error, info, info_span, trace, warn, Instrument};
-use {mysql_async as mysql, tokio_postgres as pgsql};
 
 use crate::db_util::{CreateSchema, DatabaseSchemas};
+use crate::ignored_columns::null_ignored_columns;
 use crate::mysql_connector::{MySqlBinlogConnector, MySqlReplicator};
 use crate::postgres_connector::{
-    drop_publication, drop_readyset_schema, drop_replication_slot, PostgresReplicator,
-    PostgresWalConnector, PUBLICATION_NAME, REPLICATION_SLOT,
+    drop_publication, drop_readyset_schema, drop_replication_slot, publication_name,
+    replication_slot_name, resolve_publication, verify_replication_slot, PostgresReplicator,
+    PostgresWalConnector, SlotMonitor,
 };
+use crate::row_filter::{RowFilter, RowFilters};
+use crate::table_dependencies::warn_on_excluded_references;
 use crate::table_filter::TableFilter;
+use crate::value_normalizer::ValueNormalizer;
 use crate::{ControllerMessage, ReplicatorMessage};
 
 /// Time to wait for requests to coalesce between snapshotting. Useful for preventing a series of
@@ -58,6 +68,10 @@ pub(crate) enum ReplicationAction {
         changes: Vec<Change>,
     },
     LogPosition,
+    /// The end of an upstream transaction, all of whose changes have already been returned as
+    /// [`ReplicationAction::TableAction`]s. There's nothing to apply, but this gives the main loop
+    /// a chance to pause replication between transactions.
+    Commit,
 }
 
 #[async_trait]
@@ -78,6 +92,13 @@ pub(crate) trait Connector {
         last_pos: &ReplicationOffset,
         until: Option<&ReplicationOffset>,
     ) -> ReadySetResult<(ReplicationAction, ReplicationOffset)>;
+
+    /// Returns `true` if the last action returned by [`Connector::next_action`] was in the middle
+    /// of an upstream transaction, meaning we shouldn't stop replicating until the rest of that
+    /// transaction has been applied.
+    fn in_transaction(&self) -> bool {
+        false
+    }
 }
 
 /// Cleans up replication related assets on the upstream database as supplied by the
@@ -92,24 +113,10 @@ pub async fn cleanup(config: UpstreamConfig) -> ReadySetResult<()> {
             ReadySetError::UrlParseFailed(format!("Invalid URL supplied to --upstream-db-url: {e}"))
         })?
     {
-        let connector = {
-            let mut builder = native_tls::TlsConnector::builder();
-            if config.disable_upstream_ssl_verification {
-                builder.danger_accept_invalid_certs(true);
-            }
-            if let Some(root_cert) = config.get_root_cert().await {
-                builder.add_root_certificate(root_cert?);
-            }
-            builder.build().unwrap() // Never returns an error
-        };
-        let tls_connector = postgres_native_tls::MakeTlsConnector::new(connector);
+        let tls_connector = config.pg_tls_connector().await?;
 
-        let repl_slot_name = match &config.replication_server_id {
-            Some(server_id) => {
-                format!("{}_{}", REPLICATION_SLOT, server_id)
-            }
-            _ => REPLICATION_SLOT.to_string(),
-        };
+        let repl_slot_name = replication_slot_name(config.replication_server_id.as_ref());
+        let publication = publication_name(config.replication_server_id.as_ref());
         let resnapshot_slot_name = resnapshot_slot_name(&repl_slot_name);
 
         let dbname = options.get_dbname().ok_or_else(|| {
@@ -124,7 +131,7 @@ pub async fn cleanup(config: UpstreamConfig) -> ReadySetResult<()> {
         let (mut client, connection) = cleanup_opts.connect(tls_connector).await?;
         let _connection_handle = tokio::spawn(connection);
 
-        drop_publication(&mut client, &repl_slot_name).await?;
+        drop_publication(&mut client, &publication).await?;
         // Drop primary replication slot
         drop_replication_slot(&mut client, &repl_slot_name).await?;
         // Drop resnapshot replication slot (if exists, if does not we output debug message)
@@ -136,6 +143,47 @@ pub async fn cleanup(config: UpstreamConfig) -> ReadySetResult<()> {
     Ok(())
 }
 
+/// Drops the replication slot on the upstream database as supplied by the UpstreamConfig, if it's
+/// a postgres database, so that the upstream database doesn't retain WAL for it while we're not
+/// replicating. Must only be called once replication has stopped.
+pub async fn release_replication_slot(config: &UpstreamConfig) -> ReadySetResult<()> {
+    if let DatabaseURL::PostgreSQL(options) = config
+        .upstream_db_url
+        .as_ref()
+        .ok_or_else(|| internal_err!("Replication URL not supplied"))?
+        .parse()
+        .map_err(|e| {
+            ReadySetError::UrlParseFailed(format!("Invalid URL supplied to --upstream-db-url: {e}"))
+        })?
+    {
+        let tls_connector = config.pg_tls_connector().await?;
+        let repl_slot_name = replication_slot_name(config.replication_server_id.as_ref());
+
+        let (client, connection) = options.connect(tls_connector).await?;
+        let _connection_handle = tokio::spawn(connection);
+
+        info!(slot = %repl_slot_name, "Dropping replication slot");
+        // An active slot can't be dropped, and the upstream database may take a moment to notice
+        // that our replication connection has been closed
+        retry_with_exponential_backoff(
+            || async {
+                client
+                    .execute(
+                        "SELECT pg_drop_replication_slot(slot_name) FROM pg_replication_slots \
+                         WHERE slot_name = $1",
+                        &[&repl_slot_name],
+                    )
+                    .await
+            },
+            5,
+            Duration::from_millis(250),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
 pub fn resnapshot_slot_name(repl_slot_name: &String) -> String {
     format!("{}_{}", RESNAPSHOT_SLOT, repl_slot_name)
 }
@@ -160,6 +208,18 @@ pub struct NoriaAdapter {
     replication_offsets: ReplicationOffsets,
     /// Filters out changes we are not interested in
     table_filter: TableFilter,
+    /// Filters out rows we are not interested in
+    row_filters: RowFilters,
+    /// A map of row filters lowered against the current schema of each table, cached alongside
+    /// [`Self::mutator_map`]
+    row_filter_map: HashMap<Relation, Option<RowFilter>>,
+    /// Columns which are never replicated, and are written to ReadySet as NULL instead
+    ignored_columns: IgnoredColumns,
+    /// Caps on the size of the values of columns
+    value_size_caps: ValueSizeCaps,
+    /// A map of the value normalizers for the current schema of each table, cached alongside
+    /// [`Self::mutator_map`]
+    value_normalizer_map: HashMap<Relation, Option<ValueNormalizer>>,
     /// If the connector can partially resnapshot a database
     supports_resnapshot: bool,
 }
@@ -169,7 +229,8 @@ impl NoriaAdapter {
         noria: ReadySetHandle,
         mut config: UpstreamConfig,
         notification_channel: &UnboundedSender<ReplicatorMessage>,
-        controller_channel: &UnboundedReceiver<ControllerMessage>,
+        controller_channel: &mut UnboundedReceiver<ControllerMessage>,
+        pause_state: &watch::Sender<ReplicationPauseState>,
         telemetry_sender: TelemetrySender,
         server_startup: bool,
         enable_statement_logging: bool,
@@ -199,6 +260,7 @@ impl NoriaAdapter {
                     config,
                     notification_channel,
                     controller_channel,
+                    pause_state,
                     resnapshot,
                     &telemetry_sender,
                     enable_statement_logging,
@@ -209,17 +271,7 @@ impl NoriaAdapter {
             DatabaseURL::PostgreSQL(options) => {
                 let noria = noria.clone();
                 let config = config.clone();
-                let connector = {
-                    let mut builder = native_tls::TlsConnector::builder();
-                    if config.disable_upstream_ssl_verification {
-                        builder.danger_accept_invalid_certs(true);
-                    }
-                    if let Some(root_cert) = config.get_root_cert().await {
-                        builder.add_root_certificate(root_cert?);
-                    }
-                    builder.build().unwrap() // Never returns an error
-                };
-                let tls_connector = postgres_native_tls::MakeTlsConnector::new(connector);
+                let tls_connector = config.pg_tls_connector().await?;
                 let pool = pg_pool(
                     options.clone(),
                     config.replication_pool_size,
@@ -227,12 +279,7 @@ impl NoriaAdapter {
                 )
                 .await?;
 
-                let repl_slot_name = match &config.replication_server_id {
-                    Some(server_id) => {
-                        format!("{}_{}", REPLICATION_SLOT, server_id)
-                    }
-                    _ => REPLICATION_SLOT.to_string(),
-                };
+                let repl_slot_name = replication_slot_name(config.replication_server_id.as_ref());
 
                 NoriaAdapter::start_inner_postgres(
                     options,
@@ -240,6 +287,7 @@ impl NoriaAdapter {
                     config,
                     notification_channel,
                     controller_channel,
+                    pause_state,
                     resnapshot,
                     full_snapshot,
                     &telemetry_sender,
@@ -265,6 +313,7 @@ impl NoriaAdapter {
                     full_snapshot = true;
                     warn!(error=%err, "Restarting adapter after error encountered. Full resnapshot will be performed");
                 }
+                ReadySetError::ReplicationPaused => return Err(ReadySetError::ReplicationPaused),
 
                 err => {
                     warn!(error=%err, "Restarting adapter after error encountered");
@@ -291,7 +340,8 @@ impl NoriaAdapter {
         mut noria: ReadySetHandle,
         mut config: UpstreamConfig,
         notification_channel: &UnboundedSender<ReplicatorMessage>,
-        controller_channel: &UnboundedReceiver<ControllerMessage>,
+        controller_channel: &mut UnboundedReceiver<ControllerMessage>,
+        pause_state: &watch::Sender<ReplicationPauseState>,
         resnapshot: bool,
         telemetry_sender: &TelemetrySender,
         enable_statement_logging: bool,
@@ -299,8 +349,7 @@ impl NoriaAdapter {
     ) -> ReadySetResult<!> {
         use replication_offset::mysql::MySqlPosition;
 
-        if let Some(cert_path) = config.ssl_root_cert.clone() {
-            let ssl_opts = SslOpts::default().with_root_certs(vec![cert_path.into()]);
+        if let Some(ssl_opts) = config.mysql_ssl_opts() {
             mysql_options = OptsBuilder::from_opts(mysql_options)
                 .ssl_opts(ssl_opts)
                 .into();
@@ -325,22 +374,28 @@ impl NoriaAdapter {
             config.replication_tables_ignore.take(),
             mysql_options.db_name(),
         )?;
+        let row_filters = RowFilters::try_new(
+            nom_sql::Dialect::MySQL,
+            config.replication_row_filters.take(),
+            mysql_options.db_name(),
+        )?;
+        let ignored_columns = config.ignored_columns()?;
+        let value_size_caps = config.value_size_caps()?;
 
         let mut db_schemas = DatabaseSchemas::new();
 
         let pos = match (replication_offsets.max_offset()?, resnapshot) {
             (None, _) | (_, true) => {
                 let span = info_span!("taking database snapshot");
-                // The default min is already 10, so we keep that the same to reduce complexity
-                // overhead of too many flags.
                 // The only way PoolConstraints::new() can panic on unwrap is if min is not less
-                // than or equal to max, so we naively reset min if max is below 10.
-                let constraints = if config.replication_pool_size <= 10 {
-                    PoolConstraints::new(config.replication_pool_size, config.replication_pool_size)
-                        .unwrap()
-                } else {
-                    PoolConstraints::new(10, config.replication_pool_size).unwrap()
-                };
+                // than or equal to max, so we cap min at max.
+                let constraints = PoolConstraints::new(
+                    config
+                        .snapshot_pool_min_size
+                        .min(config.replication_pool_size),
+                    config.replication_pool_size,
+                )
+                .unwrap();
                 let pool_opts = PoolOpts::default().with_constraints(constraints);
                 let replicator_opts: mysql_async::Opts =
                     OptsBuilder::from_opts(mysql_options.clone())
@@ -361,6 +416,9 @@ impl NoriaAdapter {
                 let replicator = MySqlReplicator {
                     pool,
                     table_filter: table_filter.clone(),
+                    row_filters: row_filters.clone(),
+                    ignored_columns: ignored_columns.clone(),
+                    value_size_caps: value_size_caps.clone(),
                 };
 
                 let snapshot_start = Instant::now();
@@ -435,6 +493,13 @@ impl NoriaAdapter {
             (Some(pos), _) => pos.clone().try_into()?,
         };
 
+        let streaming_options = OptsBuilder::from_opts(mysql_options.clone()).tcp_keepalive(
+            config
+                .streaming_tcp_keepalive()
+                .map(|keepalive| keepalive.as_millis() as u32),
+        );
+        let heartbeat_interval = config.replication_heartbeat_interval();
+
         let server_id = config
             .replication_server_id
             .as_ref()
@@ -449,10 +514,11 @@ impl NoriaAdapter {
 
         let connector = Box::new(
             MySqlBinlogConnector::connect(
-                mysql_options.clone(),
+                streaming_options,
                 pos.clone(),
                 server_id,
                 enable_statement_logging,
+                heartbeat_interval,
             )
             .await?,
         );
@@ -464,6 +530,11 @@ impl NoriaAdapter {
             mutator_map: HashMap::new(),
             warned_missing_tables: HashSet::new(),
             table_filter,
+            row_filters,
+            row_filter_map: HashMap::new(),
+            ignored_columns,
+            value_size_caps,
+            value_normalizer_map: HashMap::new(),
             supports_resnapshot: true,
             dialect: Dialect::DEFAULT_MYSQL,
         };
@@ -487,6 +558,7 @@ impl NoriaAdapter {
                         Some(max),
                         notification_channel,
                         controller_channel,
+                        pause_state,
                     )
                     .await?;
             }
@@ -506,6 +578,7 @@ impl NoriaAdapter {
                 None,
                 notification_channel,
                 controller_channel,
+                pause_state,
             )
             .await?;
 
@@ -518,11 +591,12 @@ impl NoriaAdapter {
         mut noria: ReadySetHandle,
         mut config: UpstreamConfig,
         notification_channel: &UnboundedSender<ReplicatorMessage>,
-        controller_channel: &UnboundedReceiver<ControllerMessage>,
+        controller_channel: &mut UnboundedReceiver<ControllerMessage>,
+        pause_state: &watch::Sender<ReplicationPauseState>,
         resnapshot: bool,
         mut full_resnapshot: bool,
         telemetry_sender: &TelemetrySender,
-        tls_connector: MakeTlsConnector,
+        tls_connector: UpstreamTlsConnector,
         pool: deadpool_postgres::Pool,
         repl_slot_name: String,
         enable_statement_logging: bool,
@@ -559,6 +633,13 @@ impl NoriaAdapter {
             config.replication_tables_ignore.take(),
             None,
         )?;
+        let row_filters = RowFilters::try_new(
+            nom_sql::Dialect::PostgreSQL,
+            config.replication_row_filters.take(),
+            None,
+        )?;
+        let ignored_columns = config.ignored_columns()?;
+        let value_size_caps = config.value_size_caps()?;
 
         let (mut client, connection) = pgsql_opts.connect(tls_connector.clone()).await?;
         let _connection_handle = tokio::spawn(connection);
@@ -578,6 +659,8 @@ impl NoriaAdapter {
             })?;
 
         let max_parallel_snapshot_tables = config.max_parallel_snapshot_tables();
+        let slot_lag_warning_bytes = config.replication_slot_lag_warning_bytes();
+        let mut publication = publication_name(config.replication_server_id.as_ref());
         let mut connector = Box::new(
             PostgresWalConnector::connect(
                 pgsql_opts.clone(),
@@ -586,6 +669,7 @@ impl NoriaAdapter {
                 pos,
                 tls_connector.clone(),
                 &repl_slot_name,
+                &publication,
                 enable_statement_logging,
                 full_resnapshot,
                 noria.clone(),
@@ -599,22 +683,25 @@ impl NoriaAdapter {
         let replication_slot = if let Some(slot) = &connector.replication_slot {
             Some(slot.clone())
         } else {
-            let escaped_slot_name = escape_literal(&repl_slot_name);
-            let readyset_slot_exists = client
-                .query_one(
-                    &format!("SELECT EXISTS(SELECT 1 FROM pg_replication_slots WHERE slot_name={escaped_slot_name})"),
-                    &[],
-                )
-                .await
-                .and_then(|row| row.try_get::<_, bool>(0))
-                .map_err(|e| {
-                    ReadySetError::Internal(format!(
-                        "Unable to read replication slot from upstream database: {}",
-                        e
-                    ))
-                })?;
+            let readyset_slot_exists =
+                verify_replication_slot(&client, &repl_slot_name, dbname).await?;
+
+            // The publication may have been dropped, or this deployment may have been created with
+            // a replication server id by a version of ReadySet that didn't include it in the
+            // publication name
+            let publication_exists = match resolve_publication(&client, &publication).await? {
+                Some(existing) => {
+                    publication = existing;
+                    true
+                }
+                None => {
+                    warn!(%publication, "Publication does not exist, recreating it");
+                    connector.create_publication(&publication).await?;
+                    false
+                }
+            };
 
-            if readyset_slot_exists {
+            if readyset_slot_exists && publication_exists {
                 info!(%full_resnapshot, %resnapshot, pos=?pos, "readyset_slot_exists");
                 if full_resnapshot || resnapshot || pos.is_none() {
                     // This is not an initial connection but we need to resnapshot the latest
@@ -641,9 +728,12 @@ impl NoriaAdapter {
                 // If our primary replication slot doesn't already exist, we need to create one,
                 // which will require us to do a full resnapshot, since we'd have no way of
                 // replaying events between our current max offset and the consistent point of the
-                // new slot.
+                // new slot. The same goes if we just recreated the publication, since the existing
+                // slot can't decode changes that were written before the publication existed.
                 full_resnapshot = true;
 
+                connector.drop_replication_slot(&repl_slot_name).await?;
+
                 Some(
                     connector
                         .create_replication_slot(&repl_slot_name, true)
@@ -665,9 +755,16 @@ impl NoriaAdapter {
                 .and_then(|row| row.try_get::<_, String>(0))
                 .unwrap_or_else(|_| "unknown".to_owned());
 
-            let mut replicator =
-                PostgresReplicator::new(&mut client, pool, &mut noria, table_filter.clone())
-                    .await?;
+            let mut replicator = PostgresReplicator::new(
+                &mut client,
+                pool,
+                &mut noria,
+                table_filter.clone(),
+                row_filters.clone(),
+                ignored_columns.clone(),
+                value_size_caps.clone(),
+            )
+            .await?;
 
             let snapshot_result = replicator
                 .snapshot_to_noria(
@@ -722,9 +819,11 @@ impl NoriaAdapter {
         }
 
         connector
-            .start_replication(&repl_slot_name, PUBLICATION_NAME, version_num)
+            .start_replication(&repl_slot_name, &publication, version_num)
             .await?;
 
+        let _slot_monitor = SlotMonitor::spawn(client, repl_slot_name, slot_lag_warning_bytes);
+
         let replication_offsets = noria.replication_offsets().await?;
         trace!(?replication_offsets, "Loaded replication offsets");
         let mut min_pos = replication_offsets
@@ -743,6 +842,11 @@ impl NoriaAdapter {
             mutator_map: HashMap::new(),
             warned_missing_tables: HashSet::new(),
             table_filter,
+            row_filters,
+            row_filter_map: HashMap::new(),
+            ignored_columns,
+            value_size_caps,
+            value_normalizer_map: HashMap::new(),
             supports_resnapshot: true,
             dialect: Dialect::DEFAULT_POSTGRESQL,
         };
@@ -755,6 +859,7 @@ impl NoriaAdapter {
                     Some(max_pos),
                     notification_channel,
                     controller_channel,
+                    pause_state,
                 )
                 .await?;
         }
@@ -766,7 +871,13 @@ impl NoriaAdapter {
         info!("Streaming replication started");
 
         adapter
-            .main_loop(&mut min_pos, None, notification_channel, controller_channel)
+            .main_loop(
+                &mut min_pos,
+                None,
+                notification_channel,
+                controller_channel,
+                pause_state,
+            )
             .await?;
 
         unreachable!("`main_loop` will never stop with an Ok status if `until = None`");
@@ -788,7 +899,9 @@ impl NoriaAdapter {
                 let keep = self
                     .table_filter
                     .should_be_processed(schema.as_str(), statement.table.name.as_str())
-                    && statement.body.is_ok();
+                    // Tables created from a query will be resnapshotted, even if we can't parse
+                    // their column definitions
+                    && (statement.body.is_ok() || statement.select.is_some());
                 if !keep {
                     non_replicated_tables.push(Relation {
                         schema: Some(schema.clone().into()),
@@ -800,9 +913,48 @@ impl NoriaAdapter {
             Change::AlterTable(stmt) => self
                 .table_filter
                 .should_be_processed(schema.as_str(), stmt.table.name.as_str()),
+            Change::CreateIndex(stmt) => self
+                .table_filter
+                .should_be_processed(schema.as_str(), stmt.table.name.as_str()),
             _ => true,
         });
 
+        // Warn about any new foreign keys referencing tables outside the filtered scope
+        for change in changelist.changes() {
+            let (table, keys) = match change {
+                Change::CreateTable { statement, .. } => (
+                    &statement.table,
+                    statement
+                        .body
+                        .iter()
+                        .flat_map(|body| body.keys.iter().flatten())
+                        .collect::<Vec<_>>(),
+                ),
+                Change::AlterTable(stmt) => (
+                    &stmt.table,
+                    stmt.definitions
+                        .iter()
+                        .flatten()
+                        .filter_map(|def| match def {
+                            AlterTableDefinition::AddKey(key) => Some(key),
+                            _ => None,
+                        })
+                        .collect(),
+                ),
+                _ => continue,
+            };
+            let table = Relation {
+                schema: Some(
+                    table
+                        .schema
+                        .clone()
+                        .unwrap_or_else(|| schema.clone().into()),
+                ),
+                name: table.name.clone(),
+            };
+            warn_on_excluded_references(&table, keys, &self.table_filter, self.dialect.into());
+        }
+
         // Mark all tables that were filtered as non-replicated, too
         changelist
             .changes_mut()
@@ -814,6 +966,42 @@ impl NoriaAdapter {
             }));
 
         if self.supports_resnapshot && changelist.changes().any(Change::requires_resnapshot) {
+            // Apply any renames before resnapshotting, so that the views and caches which depend on
+            // the renamed tables or columns are remapped to the new names rather than dropped. The
+            // renamed tables are recreated without a replication offset, so they'll be snapshotted
+            // again along with any other changed tables.
+            let renames = changelist
+                .changes()
+                .filter(|change| change.is_rename())
+                .cloned()
+                .collect::<Vec<_>>();
+            if !renames.is_empty() {
+                for change in &renames {
+                    if let Change::AlterTable(stmt) = change {
+                        self.replication_offsets.tables.remove(&Relation {
+                            schema: Some(
+                                stmt.table
+                                    .schema
+                                    .clone()
+                                    .unwrap_or_else(|| schema.clone().into()),
+                            ),
+                            name: stmt.table.name.clone(),
+                        });
+                    }
+                }
+                self.clear_mutator_cache();
+                if let Err(error) = self
+                    .noria
+                    .extend_recipe(
+                        ChangeList::from_changes(renames, self.dialect)
+                            .with_schema_search_path(vec![schema.clone().into()]),
+                    )
+                    .await
+                {
+                    warn!(%error, "Error applying renames, dependent caches will be dropped");
+                }
+            }
+
             // In case we detect a DDL change that requires a full schema resnapshot exit the loop
             // with the proper status
             if let Some(pos) = self.replication_offsets.max_offset()?.cloned() {
@@ -927,7 +1115,24 @@ impl NoriaAdapter {
         txid: Option<u64>,
         pos: ReplicationOffset,
     ) -> ReadySetResult<()> {
-        // Send the rows as are
+        if let Some(row_filter) = self.row_filter_for_table(&table).await? {
+            actions = row_filter
+                .apply(actions)
+                .map_err(|e| ReadySetError::TableError {
+                    table: table.clone(),
+                    source: Box::new(e),
+                })?;
+        }
+
+        let ignored_columns = self.ignored_column_indices(&table).await?;
+        if !ignored_columns.is_empty() {
+            null_ignored_columns(&mut actions, &ignored_columns);
+        }
+
+        if let Some(normalizer) = self.value_normalizer_for_table(&table).await? {
+            normalizer.normalize_actions(&mut actions);
+        }
+
         let table_mutator = if let Some(table) = self.mutator_for_table(&table).await? {
             table
         } else {
@@ -978,6 +1183,7 @@ impl NoriaAdapter {
         // First check if we should skip this action due to insufficient log position or lack of
         // interest
         match &action {
+            ReplicationAction::Commit => return Ok(()),
             ReplicationAction::DdlChange { .. } | ReplicationAction::LogPosition => {
                 match &self.replication_offsets.schema {
                     Some(cur) if pos <= *cur => {
@@ -1034,6 +1240,7 @@ impl NoriaAdapter {
                 txid,
             } => self.handle_table_actions(table, actions, txid, pos).await,
             ReplicationAction::LogPosition => self.handle_log_position(pos).await,
+            ReplicationAction::Commit => Ok(()),
         }
     }
 
@@ -1044,7 +1251,8 @@ impl NoriaAdapter {
         position: &mut ReplicationOffset,
         until: Option<ReplicationOffset>,
         notification_channel: &UnboundedSender<ReplicatorMessage>,
-        _controller_channel: &UnboundedReceiver<ControllerMessage>,
+        controller_channel: &mut UnboundedReceiver<ControllerMessage>,
+        pause_state: &watch::Sender<ReplicationPauseState>,
     ) -> ReadySetResult<()> {
         // Notify the controller that we've started replication if we've entered the main (not
         // catchup) replication loop.
@@ -1053,6 +1261,7 @@ impl NoriaAdapter {
             let _ = notification_channel.send(ReplicatorMessage::ReplicationStarted);
         }
 
+        let mut pause_rx = pause_state.subscribe();
         loop {
             set_failpoint!(failpoints::UPSTREAM, |_| ReadySetResult::Err(
                 ReadySetError::ReplicationFailed(
@@ -1064,7 +1273,32 @@ impl NoriaAdapter {
                 return Ok(());
             }
 
-            let (action, pos) = match self.connector.next_action(position, until.as_ref()).await {
+            // We only pause, or handle messages from the controller, in the main (not catchup)
+            // replication loop, and never in the middle of an upstream transaction
+            let can_pause = until.is_none() && !self.connector.in_transaction();
+            let mut controller_message = None;
+            let next_action = tokio::select! {
+                biased;
+                _ = pause_rx.wait_for(|state| *state == ReplicationPauseState::Pausing),
+                    if can_pause => None,
+                Some(message) = controller_channel.recv(), if can_pause => {
+                    controller_message = Some(message);
+                    None
+                }
+                next_action = self.connector.next_action(position, until.as_ref()) => {
+                    Some(next_action)
+                }
+            };
+            let Some(next_action) = next_action else {
+                return match controller_message {
+                    Some(ControllerMessage::ResnapshotTable { table }) => {
+                        self.resnapshot_table(table, position).await
+                    }
+                    None => Self::pause(position, pause_state),
+                };
+            };
+
+            let (action, pos) = match next_action {
                 Ok(next_action) => next_action,
                 // In some cases, we may fail to replicate because of unsupported operations, stop
                 // replicating a table if we encounter this type of error.
@@ -1104,10 +1338,65 @@ impl NoriaAdapter {
         }
     }
 
+    /// Record that replication has paused at `position`, in response to a request to pause it,
+    /// then return [`ReadySetError::ReplicationPaused`] to stop replicating until it's resumed.
+    fn pause(
+        position: &ReplicationOffset,
+        pause_state: &watch::Sender<ReplicationPauseState>,
+    ) -> ReadySetResult<()> {
+        info!(%position, "Pausing replication");
+        // If replication was resumed before we got here, the controller will restart us right away
+        pause_state.send_if_modified(|state| {
+            if *state == ReplicationPauseState::Pausing {
+                *state = ReplicationPauseState::Paused {
+                    offset: position.clone(),
+                };
+                true
+            } else {
+                false
+            }
+        });
+        Err(ReadySetError::ReplicationPaused)
+    }
+
+    /// Drop `table` from ReadySet in response to a request from the controller, then return
+    /// [`ReadySetError::ResnapshotNeeded`] so that replication restarts and the table is recreated
+    /// and snapshotted again from scratch. Caches which read from the table are dropped along with
+    /// it.
+    async fn resnapshot_table(
+        &mut self,
+        table: Relation,
+        position: &ReplicationOffset,
+    ) -> ReadySetResult<()> {
+        info!(
+            table = %table.display(nom_sql::Dialect::PostgreSQL),
+            "Resnapshotting table"
+        );
+        // Forward all other tables to the current position, so that nothing is replayed to them
+        // once replication restarts
+        self.handle_log_position(position.clone()).await?;
+        self.replication_offsets.tables.remove(&table);
+        self.mutator_map.remove(&table);
+        self.row_filter_map.remove(&table);
+        self.value_normalizer_map.remove(&table);
+        self.noria
+            .extend_recipe(ChangeList::from_changes(
+                vec![Change::Drop {
+                    name: table,
+                    if_exists: true,
+                }],
+                self.dialect,
+            ))
+            .await?;
+        Err(ReadySetError::ResnapshotNeeded)
+    }
+
     /// When schema changes there is a risk the cached mutators will no longer be in sync
     /// and we need to drop them all
     fn clear_mutator_cache(&mut self) {
-        self.mutator_map.clear()
+        self.mutator_map.clear();
+        self.row_filter_map.clear();
+        self.value_normalizer_map.clear();
     }
 
     /// Get a mutator for a noria table from the cache if available, or fetch a new one
@@ -1126,6 +1415,72 @@ impl NoriaAdapter {
         }
     }
 
+    /// Get the row filter for a noria table from the cache if available, or lower it against the
+    /// schema of the table and cache it. Returns None if the table has no row filter, or doesn't
+    /// exist in noria.
+    async fn row_filter_for_table(
+        &mut self,
+        name: &Relation,
+    ) -> ReadySetResult<Option<&RowFilter>> {
+        if self.row_filters.expr_for_table(name).is_none() {
+            return Ok(None);
+        }
+
+        if !self.row_filter_map.contains_key(name) {
+            let schema = self
+                .mutator_for_table(name)
+                .await?
+                .and_then(|table| table.schema().cloned());
+            let row_filter = match schema {
+                Some(schema) => self
+                    .row_filters
+                    .filter_for_table(name, &schema, self.dialect)
+                    .map_err(|e| ReadySetError::TableError {
+                        table: name.clone(),
+                        source: Box::new(e),
+                    })?,
+                None => None,
+            };
+            self.row_filter_map.insert(name.clone(), row_filter);
+        }
+
+        Ok(self.row_filter_map.get(name).and_then(Option::as_ref))
+    }
+
+    /// Returns the indices of the columns of the given table which are ignored, and so must be
+    /// written to ReadySet as NULL
+    async fn ignored_column_indices(&mut self, name: &Relation) -> ReadySetResult<Vec<usize>> {
+        if self.ignored_columns.for_table(name).is_none() {
+            return Ok(vec![]);
+        }
+
+        let columns = match self.mutator_for_table(name).await? {
+            Some(table) => table.columns().to_vec(),
+            None => return Ok(vec![]),
+        };
+        Ok(self.ignored_columns.indices_for_table(name, &columns))
+    }
+
+    /// Get the value normalizer for a noria table from the cache if available, or construct it
+    /// from the schema of the table and cache it. Returns None if the normalizer would never change
+    /// any values, or if the table doesn't exist in noria.
+    async fn value_normalizer_for_table(
+        &mut self,
+        name: &Relation,
+    ) -> ReadySetResult<Option<&ValueNormalizer>> {
+        if !self.value_normalizer_map.contains_key(name) {
+            let value_size_caps = self.value_size_caps.clone();
+            let normalizer = self
+                .mutator_for_table(name)
+                .await?
+                .map(|table| ValueNormalizer::new(table, &value_size_caps))
+                .filter(|normalizer| !normalizer.is_noop());
+            self.value_normalizer_map.insert(name.clone(), normalizer);
+        }
+
+        Ok(self.value_normalizer_map.get(name).and_then(Option::as_ref))
+    }
+
     /// Remove the table referenced by the provided schema and table name from our base table and
     /// dataflow state (if any).
     async fn remove_table_from_readyset(&mut self, table: Relation) -> ReadySetResult<()> {
@@ -1135,6 +1490,8 @@ impl NoriaAdapter {
         );
         self.replication_offsets.tables.remove(&table);
         self.mutator_map.remove(&table);
+        self.row_filter_map.remove(&table);
+        self.value_normalizer_map.remove(&table);
         // Dropping the table cleans up any dataflow state that may have been made as well as
         // cleaning up the base table on disk.
         let changelist = ChangeList::from_changes(
@@ -1207,7 +1564,7 @@ impl NoriaAdapter {
 pub async fn pg_pool(
     config: pgsql::Config,
     pool_size: usize,
-    tls: MakeTlsConnector,
+    tls: UpstreamTlsConnector,
 ) -> Result<deadpool_postgres::Pool, deadpool_postgres::BuildError> {
     let mgr_config = ManagerConfig {
         recycling_method: RecyclingMethod::Verified,
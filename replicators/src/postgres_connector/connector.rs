@@ -1,13 +1,12 @@
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use database_utils::UpstreamConfig;
+use database_utils::{UpstreamConfig, UpstreamTlsConnector};
 #[cfg(feature = "failure_injection")]
 use failpoint_macros::set_failpoint;
 use futures::FutureExt;
 use nom_sql::Relation;
 use pgsql::SimpleQueryMessage;
-use postgres_native_tls::MakeTlsConnector;
 use postgres_protocol::escape::escape_literal;
 #[cfg(feature = "failure_injection")]
 use readyset_client::failpoints;
@@ -23,7 +22,6 @@ use tracing::{debug, error, info, trace, warn};
 
 use super::ddl_replication::setup_ddl_replication;
 use super::wal_reader::{WalEvent, WalReader};
-use super::PUBLICATION_NAME;
 use crate::db_util::error_is_slot_not_found;
 use crate::noria_adapter::{Connector, ReplicationAction};
 
@@ -119,8 +117,9 @@ impl PostgresWalConnector {
         dbname: S,
         config: UpstreamConfig,
         next_position: Option<Lsn>,
-        tls_connector: MakeTlsConnector,
+        tls_connector: UpstreamTlsConnector,
         repl_slot_name: &str,
+        publication: &str,
         enable_statement_logging: bool,
         full_resnapshot: bool,
         controller: ReadySetHandle,
@@ -129,6 +128,9 @@ impl PostgresWalConnector {
             setup_ddl_replication(pg_config.clone(), tls_connector.clone()).await?;
         }
         pg_config.dbname(dbname.as_ref()).set_replication_database();
+        if let Some(keepalive) = config.streaming_tcp_keepalive() {
+            pg_config.keepalives(true).keepalives_idle(keepalive);
+        }
 
         let (client, connection) = pg_config
             .connect(tls_connector)
@@ -167,14 +169,18 @@ impl PostgresWalConnector {
             //
             // Note that later on, this means we'll need to make sure we resnapshot *all* tables!
             connector
-                .create_publication_and_slot(repl_slot_name)
+                .create_publication_and_slot(repl_slot_name, publication)
                 .await?;
         }
 
         Ok(connector)
     }
 
-    async fn create_publication_and_slot(&mut self, repl_slot_name: &str) -> ReadySetResult<()> {
+    async fn create_publication_and_slot(
+        &mut self,
+        repl_slot_name: &str,
+        publication: &str,
+    ) -> ReadySetResult<()> {
         let system = self.identify_system().await?;
         debug!(
             id = %system.id,
@@ -183,7 +189,7 @@ impl PostgresWalConnector {
             dbname = ?system.dbname
         );
 
-        match self.create_publication(PUBLICATION_NAME).await {
+        match self.create_publication(publication).await {
             Ok(()) => {
                 // Created a new publication, everything is good
             }
@@ -271,7 +277,7 @@ impl PostgresWalConnector {
 
     /// Creates a new `PUBLICATION name FOR ALL TABLES`, to be able to receive WAL on that slot.
     /// The user must have superuser privileges for that to work.
-    async fn create_publication(&mut self, name: &str) -> ReadySetResult<()> {
+    pub(crate) async fn create_publication(&mut self, name: &str) -> ReadySetResult<()> {
         let query = format!("CREATE PUBLICATION {} FOR ALL TABLES", name);
         self.simple_query(&query).await.map_err(|e| {
             ReadySetError::ReplicationFailed(format!("Failed to create publication: {e}"))
@@ -333,15 +339,28 @@ impl PostgresWalConnector {
         set_failpoint_return_err!(failpoints::POSTGRES_START_REPLICATION);
 
         // Load the confirmed flush LSN for this replication slot so we can log it before starting
-        // replication, and get the WAL status so we can make sure our replication slot is healthy
-        let [confirmed_flush_lsn, wal_status] = self
-                .one_row_query::<2>(&format!(
-                    "SELECT confirmed_flush_lsn, wal_status FROM pg_replication_slots WHERE slot_name = {}",
+        // replication, and get the WAL status and active process so we can make sure our
+        // replication slot is healthy and not in use by anyone else
+        let [confirmed_flush_lsn, wal_status, active_pid] = self
+                .one_row_query::<3>(&format!(
+                    "SELECT confirmed_flush_lsn, wal_status, coalesce(active_pid::text, '') FROM pg_replication_slots WHERE slot_name = {}",
                     escape_literal(slot),
                 ))
                 .await?;
 
-        tracing::info!(?confirmed_flush_lsn, ?wal_status);
+        tracing::info!(?confirmed_flush_lsn, ?wal_status, ?active_pid);
+
+        if !active_pid.is_empty() {
+            // A replication slot can only be streamed from by one process at a time. This is
+            // usually the connection of a previous replicator that the upstream database hasn't
+            // cleaned up yet, in which case we'll succeed once we retry, but it may also be
+            // another deployment using the same slot name.
+            return Err(ReadySetError::ReplicationFailed(format!(
+                "Replication slot {slot} is in use by process {active_pid}. If another ReadySet \
+                 deployment replicates from this server, set --replication-server-id to a \
+                 distinct value for each deployment"
+            )));
+        }
 
         if wal_status == "unreserved" {
             // If the WAL status is "unreserved," it means Postgres has marked WAL files we need as
@@ -781,6 +800,15 @@ impl Connector for PostgresWalConnector {
             match event {
                 WalEvent::DdlEvent { ddl_event, lsn } => {
                     if actions.is_empty() {
+                        if ddl_event.requires_resnapshot() {
+                            // The rows inserted into the table were replicated before we knew
+                            // about the table, so the only way to get them is to snapshot it
+                            info!(
+                                schema = ddl_event.schema(),
+                                "Table created with rows in it, resnapshotting"
+                            );
+                            return Err(ReadySetError::ResnapshotNeeded);
+                        }
                         return Ok((
                             ReplicationAction::DdlChange {
                                 schema: ddl_event.schema().to_string(),
@@ -891,4 +919,8 @@ impl Connector for PostgresWalConnector {
             }
         }
     }
+
+    fn in_transaction(&self) -> bool {
+        self.in_transaction
+    }
 }
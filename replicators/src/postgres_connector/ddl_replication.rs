@@ -35,13 +35,16 @@
 //!   to construct a full `ALTER TABLE` statement, `ALTER TABLE` events are replicated as a `CREATE
 //!   TABLE` statement - ReadySet will then know that a `CREATE TABLE` statement for a table that
 //!   already exists should be treated as an alter table.
+//! * For `CREATE INDEX`, the event trigger looks up the names of the indexed columns in the catalog
+//!   rather than replicating the statement itself, since the columns are all we use the index for
+//!   (as an index hint for the table's base node). Indexes on expressions aren't replicated.
 //!
 //! [dialect]: nom_sql::Dialect
 
 use nom_sql::{
     parse_query, AlterTableStatement, Column, ColumnConstraint, ColumnSpecification,
-    CreateTableBody, CreateTableStatement, CreateViewStatement, Dialect, NonReplicatedRelation,
-    NotReplicatedReason, Relation, SqlQuery, SqlType, TableKey,
+    CreateIndexStatement, CreateTableBody, CreateTableStatement, CreateViewStatement, Dialect,
+    NonReplicatedRelation, NotReplicatedReason, Relation, SqlQuery, SqlType, TableKey,
 };
 use pgsql::tls::MakeTlsConnect;
 use readyset_client::recipe::changelist::{AlterTypeChange, Change, PostgresTableMetadata};
@@ -151,6 +154,10 @@ pub(crate) enum DdlEventData {
         name: String,
         columns: Vec<DdlCreateTableColumn>,
         constraints: Vec<DdlCreateTableConstraint>,
+        /// Whether the table was created with rows already in it (by `CREATE TABLE AS` or `SELECT
+        /// INTO`)
+        #[serde(default)]
+        populated: bool,
     },
     AddNonReplicatedTable {
         name: String,
@@ -161,6 +168,12 @@ pub(crate) enum DdlEventData {
         statement: Result<AlterTableStatement, String>,
     },
     CreateView(#[serde(deserialize_with = "parse_create_view_statement")] CreateViewStatement),
+    CreateIndex {
+        name: String,
+        table: String,
+        unique: bool,
+        columns: Vec<String>,
+    },
     Drop(String),
     CreateType {
         oid: u32,
@@ -195,6 +208,18 @@ where
 }
 
 impl DdlEvent {
+    /// Returns true if applying this event requires resnapshotting, because it created a table
+    /// along with rows that were written to the WAL before the event itself
+    pub(crate) fn requires_resnapshot(&self) -> bool {
+        matches!(
+            self.data,
+            DdlEventData::CreateTable {
+                populated: true,
+                ..
+            }
+        )
+    }
+
     /// Convert this [`DdlEvent`] into a SQL DDL statement that can be sent to ReadySet directly
     /// (using the ReadySet-native SQL dialect, not the postgresql dialect!)
     pub(crate) fn into_change(self) -> Change {
@@ -204,6 +229,7 @@ impl DdlEvent {
                 name,
                 columns,
                 constraints,
+                ..
             } => {
                 let table = Relation {
                     schema: Some(self.schema.into()),
@@ -256,6 +282,7 @@ impl DdlEvent {
                             table,
                             body: Ok(body),
                             options: Ok(vec![]),
+                            select: None,
                         },
                         pg_meta: Some(PostgresTableMetadata { oid, column_oids }),
                     },
@@ -295,6 +322,31 @@ impl DdlEvent {
                 Change::AlterTable(stmt)
             }
             DdlEventData::CreateView(stmt) => Change::CreateView(stmt),
+            DdlEventData::CreateIndex {
+                name,
+                table,
+                unique,
+                columns,
+            } => {
+                let table = Relation {
+                    schema: Some(self.schema.into()),
+                    name: table.into(),
+                };
+                Change::CreateIndex(CreateIndexStatement {
+                    name: Some(name.into()),
+                    columns: columns
+                        .into_iter()
+                        .map(|name| Column {
+                            name: name.into(),
+                            table: Some(table.clone()),
+                        })
+                        .collect(),
+                    table,
+                    unique,
+                    if_not_exists: false,
+                    index_type: None,
+                })
+            }
             DdlEventData::Drop(name) => Change::Drop {
                 name: name.into(),
                 if_exists: false,
@@ -524,7 +576,9 @@ mod tests {
                 name,
                 columns,
                 constraints,
+                populated,
             } => {
+                assert!(!populated);
                 assert_eq!(
                     oid,
                     client
@@ -578,6 +632,33 @@ mod tests {
         client.teardown().await;
     }
 
+    #[parallel_group(GROUP)]
+    #[tokio::test]
+    async fn create_table_as() {
+        let client = setup("create_table_as").await;
+
+        client
+            .simple_query("create table t1 (id integer primary key, value text)")
+            .await
+            .unwrap();
+        client
+            .simple_query("create table t2 as select id, value from t1")
+            .await
+            .unwrap();
+
+        let ddl = get_last_ddl(&client, "create_table_as").await.unwrap();
+        assert!(ddl.requires_resnapshot());
+        match ddl.data {
+            DdlEventData::CreateTable { name, columns, .. } => {
+                assert_eq!(name, "t2");
+                assert_eq!(columns.len(), 2);
+            }
+            _ => panic!(),
+        }
+
+        client.teardown().await;
+    }
+
     #[parallel_group(GROUP)]
     #[tokio::test]
     async fn create_table_with_reserved_keyword_as_name() {
@@ -778,6 +859,37 @@ mod tests {
         client.teardown().await;
     }
 
+    #[parallel_group(GROUP)]
+    #[tokio::test]
+    async fn create_index() {
+        let client = setup("create_index").await;
+        client
+            .simple_query("create table t (x int, y int, z int)")
+            .await
+            .unwrap();
+
+        let _ = get_last_ddl(&client, "create_index").await;
+
+        client
+            .simple_query("create unique index t_idx on t (y, x) include (z)")
+            .await
+            .unwrap();
+
+        let ddl = get_last_ddl(&client, "create_index").await.unwrap();
+        assert_eq!(ddl.schema, "public");
+        assert_eq!(
+            ddl.data,
+            DdlEventData::CreateIndex {
+                name: "t_idx".into(),
+                table: "t".into(),
+                unique: true,
+                columns: vec!["y".into(), "x".into()],
+            }
+        );
+
+        client.teardown().await;
+    }
+
     #[parallel_group(GROUP)]
     #[tokio::test]
     async fn drop_table() {
@@ -71,6 +71,9 @@ BEGIN
                 -- OID->json makes a string by default, so cast to bigint
                 'oid', cls.oid::bigint,
                 'name', cls.relname,
+                -- `CREATE TABLE AS` and `SELECT INTO` insert rows into the table before we
+                -- get to replicate its definition
+                'populated', TG_TAG IN ('CREATE TABLE AS', 'SELECT INTO'),
                 'columns', (
                     SELECT json_agg(json_build_object(
                         'attnum', attr.attnum,
@@ -181,6 +184,54 @@ END $$;
 
 ----
 
+CREATE OR REPLACE FUNCTION readyset.replicate_create_index()
+RETURNS event_trigger
+LANGUAGE plpgsql
+AS $$
+DECLARE
+    create_message text;
+BEGIN
+    SELECT
+    json_build_object(
+        'schema', object.schema_name,
+        'data', json_build_object('CreateIndex', json_build_object(
+            'name', cls_index.relname,
+            'table', cls_table.relname,
+            'unique', idx.indisunique,
+            'columns', (
+                SELECT json_agg(attr.attname ORDER BY k.ord)
+                FROM unnest(idx.indkey::int2[]) WITH ORDINALITY AS k(attnum, ord)
+                JOIN pg_catalog.pg_attribute attr
+                    ON attr.attrelid = idx.indrelid
+                    AND attr.attnum = k.attnum
+                -- Skip columns in the index's INCLUDE list
+                WHERE k.ord <= idx.indnkeyatts
+            )
+        ))
+    )
+    INTO create_message
+    FROM pg_event_trigger_ddl_commands() object
+    JOIN pg_catalog.pg_index idx ON idx.indexrelid = object.objid
+    JOIN pg_catalog.pg_class cls_index ON cls_index.oid = idx.indexrelid
+    JOIN pg_catalog.pg_class cls_table ON cls_table.oid = idx.indrelid
+    WHERE object.object_type = 'index'
+    AND object.schema_name != 'pg_temp'
+    -- Indexes on expressions can't be used as index hints
+    AND idx.indexprs IS NULL;
+
+    IF create_message IS NULL THEN
+        RETURN;
+    END IF;
+
+    IF readyset.is_pre14() THEN
+        UPDATE readyset.ddl_replication_log SET "ddl" = create_message;
+    ELSE
+        PERFORM pg_logical_emit_message(true, 'readyset', create_message);
+    END IF;
+END $$;
+
+----
+
 CREATE OR REPLACE FUNCTION readyset.replicate_create_view()
 RETURNS event_trigger
 LANGUAGE plpgsql
@@ -336,7 +387,7 @@ END $$;
 DROP EVENT TRIGGER IF EXISTS readyset_replicate_create_table;
 CREATE EVENT TRIGGER readyset_replicate_create_table
     ON ddl_command_end
-    WHEN TAG IN ('CREATE TABLE')
+    WHEN TAG IN ('CREATE TABLE', 'CREATE TABLE AS', 'SELECT INTO')
     EXECUTE PROCEDURE readyset.replicate_create_table();
 
 DROP EVENT TRIGGER IF EXISTS readyset_replicate_alter_table;
@@ -345,6 +396,12 @@ CREATE EVENT TRIGGER readyset_replicate_alter_table
     WHEN TAG IN ('ALTER TABLE')
     EXECUTE PROCEDURE readyset.replicate_alter_table();
 
+DROP EVENT TRIGGER IF EXISTS readyset_replicate_create_index;
+CREATE EVENT TRIGGER readyset_replicate_create_index
+    ON ddl_command_end
+    WHEN TAG IN ('CREATE INDEX')
+    EXECUTE PROCEDURE readyset.replicate_create_index();
+
 DROP event TRIGGER IF EXISTS readyset_replicate_create_view;
 CREATE EVENT TRIGGER readyset_replicate_create_view
     ON ddl_command_end
@@ -1,5 +1,6 @@
 mod connector;
 mod ddl_replication;
+mod slot;
 mod snapshot;
 mod wal;
 mod wal_reader;
@@ -7,6 +8,10 @@ mod wal_reader;
 pub use connector::{
     drop_publication, drop_readyset_schema, drop_replication_slot, PostgresWalConnector,
 };
+pub(crate) use slot::{
+    publication_name, replication_slot_name, resolve_publication, verify_replication_slot,
+    SlotMonitor,
+};
 pub use snapshot::PostgresReplicator;
 
 pub(crate) const REPLICATION_SLOT: &str = "readyset";
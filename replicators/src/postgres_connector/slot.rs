@@ -0,0 +1,213 @@
+//! Naming, verification and monitoring of the publication and replication slot we use to stream
+//! changes from an upstream postgres database.
+
+use std::time::Duration;
+
+use database_utils::ReplicationServerId;
+use metrics::gauge;
+use readyset_client::metrics::recorded;
+use readyset_errors::{ReadySetError, ReadySetResult};
+use tokio::task::JoinHandle;
+use tokio_postgres as pgsql;
+use tracing::{error, info, warn};
+
+use super::{PUBLICATION_NAME, REPLICATION_SLOT};
+
+/// The interval at which a [`SlotMonitor`] checks on our replication slot
+const SLOT_MONITOR_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Returns the name of the replication slot used by the deployment with the given server id
+pub(crate) fn replication_slot_name(server_id: Option<&ReplicationServerId>) -> String {
+    match server_id {
+        Some(server_id) => format!("{REPLICATION_SLOT}_{server_id}"),
+        None => REPLICATION_SLOT.to_string(),
+    }
+}
+
+/// Returns the name of the publication used by the deployment with the given server id
+pub(crate) fn publication_name(server_id: Option<&ReplicationServerId>) -> String {
+    match server_id {
+        Some(server_id) => format!("{PUBLICATION_NAME}_{server_id}"),
+        None => PUBLICATION_NAME.to_string(),
+    }
+}
+
+async fn publication_exists(client: &pgsql::Client, name: &str) -> ReadySetResult<bool> {
+    Ok(client
+        .query_one(
+            "SELECT EXISTS(SELECT 1 FROM pg_publication WHERE pubname = $1)",
+            &[&name],
+        )
+        .await?
+        .try_get(0)?)
+}
+
+/// Determines the publication to replicate from, given the name of the publication this
+/// deployment should use. Returns `None` if that publication doesn't exist.
+///
+/// Earlier versions of ReadySet used [`PUBLICATION_NAME`] regardless of the replication server
+/// id, so if only that publication exists we keep replicating from it, rather than forcing
+/// deployments that are upgrading to perform a full resnapshot.
+pub(crate) async fn resolve_publication(
+    client: &pgsql::Client,
+    publication: &str,
+) -> ReadySetResult<Option<String>> {
+    if publication_exists(client, publication).await? {
+        return Ok(Some(publication.to_owned()));
+    }
+
+    if publication != PUBLICATION_NAME && publication_exists(client, PUBLICATION_NAME).await? {
+        info!(
+            %publication,
+            legacy_publication = PUBLICATION_NAME,
+            "Publication does not exist; replicating from the publication created by an earlier \
+             version of ReadySet"
+        );
+        return Ok(Some(PUBLICATION_NAME.to_owned()));
+    }
+
+    Ok(None)
+}
+
+/// Checks that the replication slot with the given name, if it exists, was created by us for the
+/// given database. Returns whether the slot exists.
+///
+/// Replication slot names are shared by all databases on a postgres server, so two ReadySet
+/// deployments using the same replication server id would otherwise silently fight over the same
+/// slot.
+pub(crate) async fn verify_replication_slot(
+    client: &pgsql::Client,
+    slot: &str,
+    dbname: &str,
+) -> ReadySetResult<bool> {
+    let Some(row) = client
+        .query_opt(
+            "SELECT database::text, plugin::text FROM pg_replication_slots WHERE slot_name = $1",
+            &[&slot],
+        )
+        .await
+        .map_err(|e| {
+            ReadySetError::Internal(format!(
+                "Unable to read replication slot from upstream database: {}",
+                e
+            ))
+        })?
+    else {
+        return Ok(false);
+    };
+
+    let database: Option<String> = row.try_get(0)?;
+    let plugin: Option<String> = row.try_get(1)?;
+
+    if database.as_deref() != Some(dbname) || plugin.as_deref() != Some("pgoutput") {
+        return Err(ReadySetError::ReplicationFailed(format!(
+            "Replication slot {slot} belongs to database {} with output plugin {}, so it can't \
+             be used to replicate database {dbname}. Set --replication-server-id to a distinct \
+             value for each ReadySet deployment replicating from this server",
+            database.as_deref().unwrap_or("<none>"),
+            plugin.as_deref().unwrap_or("<none>"),
+        )));
+    }
+
+    Ok(true)
+}
+
+/// A background task that periodically checks how much WAL the upstream database is retaining for
+/// our replication slot. The amount is recorded in the
+/// [`recorded::REPLICATOR_SLOT_RETAINED_WAL_BYTES`] metric, and we warn if it exceeds the
+/// configured threshold or if the slot is at risk of being invalidated.
+///
+/// The task is aborted when the monitor is dropped.
+pub(crate) struct SlotMonitor(JoinHandle<()>);
+
+impl SlotMonitor {
+    /// Spawn a new monitor for the replication slot named `slot`, using `client` to query the
+    /// upstream database
+    pub(crate) fn spawn(
+        client: pgsql::Client,
+        slot: String,
+        lag_warning_bytes: Option<u64>,
+    ) -> Self {
+        Self(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SLOT_MONITOR_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(error) = check_slot(&client, &slot, lag_warning_bytes).await {
+                    warn!(%error, %slot, "Failed to check on replication slot");
+                }
+            }
+        }))
+    }
+}
+
+impl Drop for SlotMonitor {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+async fn check_slot(
+    client: &pgsql::Client,
+    slot: &str,
+    lag_warning_bytes: Option<u64>,
+) -> ReadySetResult<()> {
+    // Standbys don't have a current WAL insert position, so compare against the last position
+    // they replayed instead
+    let Some(row) = client
+        .query_opt(
+            "SELECT pg_wal_lsn_diff(
+                 CASE WHEN pg_is_in_recovery()
+                 THEN pg_last_wal_replay_lsn()
+                 ELSE pg_current_wal_lsn() END,
+                 restart_lsn
+             )::bigint,
+             wal_status
+             FROM pg_replication_slots WHERE slot_name = $1",
+            &[&slot],
+        )
+        .await?
+    else {
+        warn!(%slot, "Replication slot no longer exists");
+        return Ok(());
+    };
+
+    let retained_bytes: Option<i64> = row.try_get(0)?;
+    let wal_status: Option<String> = row.try_get(1)?;
+
+    if let Some(retained_bytes) = retained_bytes {
+        gauge!(
+            recorded::REPLICATOR_SLOT_RETAINED_WAL_BYTES,
+            retained_bytes as f64
+        );
+
+        if let Some(threshold) = lag_warning_bytes.filter(|threshold| {
+            u64::try_from(retained_bytes).is_ok_and(|retained| retained > *threshold)
+        }) {
+            warn!(
+                %slot,
+                %retained_bytes,
+                %threshold,
+                "The upstream database is retaining a large amount of WAL for our replication \
+                 slot. This usually means we aren't able to replicate changes as fast as they \
+                 are occurring"
+            );
+        }
+    }
+
+    match wal_status.as_deref() {
+        Some("unreserved") => warn!(
+            %slot,
+            "The upstream database has marked WAL files we need as being ready for deletion. If \
+             we don't catch up by the next checkpoint on the Postgres server, we'll be forced to \
+             perform a full resnapshot"
+        ),
+        Some("lost") => error!(
+            %slot,
+            "Our replication slot has become invalidated, so a full resnapshot will be performed \
+             when replication restarts"
+        ),
+        _ => {}
+    }
+
+    Ok(())
+}
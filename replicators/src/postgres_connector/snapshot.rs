@@ -5,13 +5,14 @@ use std::fmt::{self, Display};
 use std::future;
 use std::time::Instant;
 
+use database_utils::{IgnoredColumns, ValueSizeCaps};
 use failpoint_macros::set_failpoint;
 use futures::stream::FuturesUnordered;
 use futures::{pin_mut, StreamExt, TryFutureExt};
 use itertools::Itertools;
 use nom_sql::{
     parse_key_specification_string, parse_sql_type, Column, ColumnConstraint, ColumnSpecification,
-    CreateTableBody, CreateTableStatement, Dialect, DialectDisplay, NonReplicatedRelation,
+    CreateTableBody, CreateTableStatement, Dialect, DialectDisplay, Expr, NonReplicatedRelation,
     NotReplicatedReason, Relation, SqlIdentifier, TableKey,
 };
 use postgres_types::{accepts, FromSql, Kind, Type};
@@ -28,7 +29,10 @@ use tracing::{debug, info, info_span, trace, warn, Instrument};
 
 use super::connector::CreatedSlot;
 use crate::db_util::CreateSchema;
+use crate::row_filter::RowFilters;
+use crate::table_dependencies::TableDependencies;
 use crate::table_filter::TableFilter;
+use crate::value_normalizer::ValueNormalizer;
 use crate::TablesSnapshottingGaugeGuard;
 
 const BATCH_SIZE: usize = 1024; // How many queries to buffer before pushing to ReadySet
@@ -50,6 +54,12 @@ pub struct PostgresReplicator<'a> {
     pub(crate) noria: &'a mut readyset_client::ReadySetHandle,
     /// Filters out tables we are not interested in
     pub(crate) table_filter: TableFilter,
+    /// Filters out rows we are not interested in
+    pub(crate) row_filters: RowFilters,
+    /// Columns which are never snapshotted or replicated
+    pub(crate) ignored_columns: IgnoredColumns,
+    /// Caps on the size of the values of columns
+    pub(crate) value_size_caps: ValueSizeCaps,
 }
 
 #[derive(Debug)]
@@ -332,7 +342,7 @@ impl TableEntry {
         let query = r"
             SELECT c2.relname, pg_catalog.pg_get_constraintdef(con.oid, true), contype
             FROM pg_catalog.pg_class c, pg_catalog.pg_class c2, pg_catalog.pg_index i
-            LEFT JOIN pg_catalog.pg_constraint con ON (conrelid = i.indrelid AND conindid = i.indexrelid AND contype IN ('f','p','u'))
+            LEFT JOIN pg_catalog.pg_constraint con ON (conrelid = i.indrelid AND conindid = i.indexrelid AND contype IN ('p','u'))
             WHERE c.oid = $1
             AND c.oid = i.indrelid
             AND i.indexrelid = c2.oid
@@ -344,6 +354,41 @@ impl TableEntry {
         constraints.into_iter().map(TryInto::try_into).collect()
     }
 
+    /// Foreign keys aren't backed by an index on the referencing table, so they aren't returned by
+    /// [`Self::get_constraints`]. The referenced table is always returned fully qualified, since
+    /// `pg_get_constraintdef` omits the schema of tables on the search path.
+    async fn get_foreign_keys<'a>(
+        oid: u32,
+        transaction: &'a pgsql::Transaction<'a>,
+    ) -> Result<Vec<ConstraintEntry>, pgsql::Error> {
+        let query = r"
+            SELECT con.conname, pg_catalog.pg_get_constraintdef(con.oid, true), con.contype,
+                   n.nspname, c.relname
+            FROM pg_catalog.pg_constraint con
+            JOIN pg_catalog.pg_class c ON c.oid = con.confrelid
+            JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+            WHERE con.conrelid = $1
+            AND con.contype = 'f'
+            ORDER BY con.conname;
+            ";
+
+        let foreign_keys = transaction.query(query, &[&oid]).await?;
+        foreign_keys
+            .into_iter()
+            .map(|row| {
+                let referenced_table = Relation {
+                    schema: Some(row.try_get::<_, String>(3)?.into()),
+                    name: row.try_get::<_, String>(4)?.into(),
+                };
+                let mut entry = ConstraintEntry::try_from(row)?;
+                if let TableKey::ForeignKey { target_table, .. } = &mut entry.definition {
+                    *target_table = referenced_table;
+                }
+                Ok(entry)
+            })
+            .collect()
+    }
+
     async fn get_table<'a>(
         &self,
         transaction: &'a pgsql::Transaction<'a>,
@@ -360,7 +405,7 @@ impl TableEntry {
                 }
                 .context("when loading columns for the table")
             })?;
-        let constraints = Self::get_constraints(self.oid, transaction)
+        let mut constraints = Self::get_constraints(self.oid, transaction)
             .await
             .map_err(|e| {
                 ReadySetError::TableError {
@@ -372,6 +417,19 @@ impl TableEntry {
                 }
                 .context("when loading constraints")
             })?;
+        let foreign_keys = Self::get_foreign_keys(self.oid, transaction)
+            .await
+            .map_err(|e| {
+                ReadySetError::TableError {
+                    table: Relation {
+                        schema: Some(self.schema.clone().into()),
+                        name: self.name.clone().into(),
+                    },
+                    source: Box::new(ReadySetError::ReplicationFailed(e.to_string())),
+                }
+                .context("when loading foreign keys")
+            })?;
+        constraints.extend(foreign_keys);
 
         Ok(TableDescription {
             oid: self.oid,
@@ -471,6 +529,7 @@ impl TableDescription {
                     },
                 }),
                 options: Ok(vec![]),
+                select: None,
             },
         })
     }
@@ -482,6 +541,9 @@ impl TableDescription {
         mut noria_table: readyset_client::Table,
         snapshot_report_interval_secs: u16,
         wal_position: &ReplicationOffset,
+        row_filter: Option<&Expr>,
+        ignored_columns: &HashSet<SqlIdentifier>,
+        normalizer: &ValueNormalizer,
     ) -> ReadySetResult<()> {
         let mut cnt = 0;
 
@@ -503,12 +565,33 @@ impl TableDescription {
             .await?
             .try_get::<_, i64>("approximate_nrows")?;
 
-        // The most efficient way to copy an entire table is COPY BINARY
-        let query = format!(
-            "COPY \"{}\".\"{}\" TO stdout BINARY",
-            self.schema()?,
-            self.name.name
-        );
+        // The most efficient way to copy an entire table is COPY BINARY. If only some of the
+        // table's rows or columns are replicated, copy the result of a query selecting those rows
+        // instead, naming the columns explicitly so they line up with `type_map` below
+        let query = if row_filter.is_none() && ignored_columns.is_empty() {
+            format!(
+                "COPY \"{}\".\"{}\" TO stdout BINARY",
+                self.schema()?,
+                self.name.name
+            )
+        } else {
+            format!(
+                "COPY (SELECT {} FROM \"{}\".\"{}\"{}) TO stdout BINARY",
+                self.columns
+                    .iter()
+                    .map(|c| if ignored_columns.contains(c.name.as_str()) {
+                        format!("NULL AS \"{}\"", c.name)
+                    } else {
+                        format!("\"{}\"", c.name)
+                    })
+                    .join(", "),
+                self.schema()?,
+                self.name.name,
+                row_filter
+                    .map(|row_filter| format!(" WHERE {}", row_filter.display(Dialect::PostgreSQL)))
+                    .unwrap_or_default()
+            )
+        };
         let rows = transaction.copy_out(query.as_str()).await?;
 
         let type_map: Vec<_> = self.columns.iter().map(|c| c.pg_type.clone()).collect();
@@ -536,6 +619,10 @@ impl TableDescription {
                         (0..type_map.len())
                             .map(|i| row.try_get::<DfValue>(i))
                             .collect::<Result<Vec<_>, _>>()
+                            .map(|mut row| {
+                                normalizer.normalize_row(&mut row);
+                                row
+                            })
                             .map_err(|err| {
                                 ReadySetError::ReplicationFailed(format!(
                                     "Failed converting to DfValue, table: {}, row: {}, err: {}",
@@ -609,6 +696,9 @@ impl<'a> PostgresReplicator<'a> {
         pool: deadpool_postgres::Pool,
         noria: &'a mut readyset_client::ReadySetHandle,
         table_filter: TableFilter,
+        row_filters: RowFilters,
+        ignored_columns: IgnoredColumns,
+        value_size_caps: ValueSizeCaps,
     ) -> ReadySetResult<PostgresReplicator<'a>> {
         let transaction = Some(
             client
@@ -624,6 +714,9 @@ impl<'a> PostgresReplicator<'a> {
             pool,
             noria,
             table_filter,
+            row_filters,
+            ignored_columns,
+            value_size_caps,
         })
     }
 
@@ -635,6 +728,9 @@ impl<'a> PostgresReplicator<'a> {
         snapshot_report_interval_secs: u16,
         snapshot_name: String,
         wal_position: &ReplicationOffset,
+        row_filter: Option<Expr>,
+        ignored_columns: HashSet<SqlIdentifier>,
+        normalizer: ValueNormalizer,
     ) -> ReadySetResult<()> {
         let mut client = pool.get().await?;
 
@@ -656,6 +752,9 @@ impl<'a> PostgresReplicator<'a> {
                 noria_table,
                 snapshot_report_interval_secs,
                 wal_position,
+                row_filter.as_ref(),
+                &ignored_columns,
+                &normalizer,
             )
             .instrument(span.clone())
             .await
@@ -888,6 +987,14 @@ impl<'a> PostgresReplicator<'a> {
             .set_schema_replication_offset(Some(&wal_position))
             .await?;
 
+        // Record the foreign keys between the replicated tables, so that tables can be snapshotted
+        // after the tables they reference
+        let mut dependencies = TableDependencies::default();
+        for table in &tables {
+            dependencies.add_table(&table.name, table.constraints.iter().map(|c| &c.definition));
+        }
+        dependencies.warn_on_excluded_references(&self.table_filter, Dialect::PostgreSQL);
+
         let replication_offsets = self.noria.replication_offsets().await?;
 
         if !full_snapshot {
@@ -900,6 +1007,7 @@ impl<'a> PostgresReplicator<'a> {
                     )
                 });
         }
+        dependencies.sort_referenced_first(&mut tables, |t| &t.name);
 
         // Emit a no-op replication message: If there isn't any traffic since we started
         // snapshotting, we may be stuck 'catching up' because the lsn of a re-snapshot slot can be
@@ -931,6 +1039,13 @@ impl<'a> PostgresReplicator<'a> {
             let pool = self.pool.clone();
 
             let snapshot_name = replication_slot.snapshot_name.clone();
+            let row_filter = self.row_filters.expr_for_table(&table.name).cloned();
+            let ignored_columns = self
+                .ignored_columns
+                .for_table(&table.name)
+                .cloned()
+                .unwrap_or_default();
+            let normalizer = ValueNormalizer::new(&noria_table, &self.value_size_caps);
             let table = table.clone();
             if snapshotting_tables.len() >= max_parallel_snapshot_tables {
                 snapshotting_tables.next().await;
@@ -943,6 +1058,9 @@ impl<'a> PostgresReplicator<'a> {
                 snapshot_report_interval_secs,
                 snapshot_name,
                 &wal_position,
+                row_filter,
+                ignored_columns,
+                normalizer,
             ))
         }
 
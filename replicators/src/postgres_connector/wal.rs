@@ -61,6 +61,8 @@ pub enum TableErrorKind {
     TimeParseError(mysql_time::ConvertError),
     NumericParseError(NumericParseErrorKind),
     BitVectorParseError(String),
+    UuidParseError(String),
+    IntervalParseError(String),
     ArrayParseError,
     InvalidMapping(String),
     UnsupportedTypeConversion { type_oid: u32 },
@@ -5,7 +5,7 @@ use std::sync::Arc;
 use bit_vec::BitVec;
 use mysql_time::MySqlTime;
 use postgres_types::Kind;
-use readyset_data::{Array, Collation, DfType, DfValue, Dialect};
+use readyset_data::{Array, Collation, DfType, DfValue, Dialect, TimestampTz};
 use readyset_errors::ReadySetError;
 use replication_offset::postgres::{CommitLsn, Lsn};
 use rust_decimal::prelude::FromStr;
@@ -708,8 +708,21 @@ impl wal::TupleData {
                                 | PGType::BPCHAR
                                 | PGType::MACADDR
                                 | PGType::INET
-                                | PGType::UUID
                                 | PGType::NAME => DfValue::from(str.as_ref()),
+                                PGType::UUID => DfValue::from(str.as_ref())
+                                    .coerce_to(&DfType::Uuid, &DfType::Unknown)
+                                    .map_err(|e| WalError::TableError {
+                                        kind: TableErrorKind::UuidParseError(e.to_string()),
+                                        schema: relation.schema_name_lossy(),
+                                        table: relation.relation_name_lossy(),
+                                    })?,
+                                PGType::INTERVAL => DfValue::from(str.as_ref())
+                                    .coerce_to(&DfType::Interval, &DfType::Unknown)
+                                    .map_err(|e| WalError::TableError {
+                                        kind: TableErrorKind::IntervalParseError(e.to_string()),
+                                        schema: relation.schema_name_lossy(),
+                                        table: relation.relation_name_lossy(),
+                                    })?,
                                 // JSONB might rearrange the json value (like the order of the keys
                                 // in an object for example), vs
                                 // JSON that keeps the text as-is.
@@ -736,13 +749,19 @@ impl wal::TupleData {
                                     })?)
                                 }
                                 PGType::TIMESTAMPTZ => {
-                                    DfValue::TimestampTz(str.parse().map_err(|_| {
-                                        WalError::TableError {
+                                    let ts: TimestampTz =
+                                        str.parse().map_err(|_| WalError::TableError {
                                             kind: TableErrorKind::TimestampTzParseError,
                                             schema: relation.schema_name_lossy(),
                                             table: relation.relation_name_lossy(),
-                                        }
-                                    })?)
+                                        })?;
+                                    // The WAL renders timestamptz values in the timezone of the
+                                    // upstream server, but snapshotted values are always in UTC -
+                                    // normalize to UTC so values don't depend on where they came
+                                    // from.
+                                    DfValue::TimestampTz(
+                                        ts.to_chrono().naive_utc().and_utc().fixed_offset().into(),
+                                    )
                                 }
                                 PGType::BYTEA => {
                                     hex::decode(str.strip_prefix("\\x").unwrap_or(&str))
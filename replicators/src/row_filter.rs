@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+
+use dataflow_expression::{Dialect, Expr as DataflowExpr, LowerContext};
+use nom_locate::LocatedSpan;
+use nom_sql::{parse_expr, replicator_table_list, Column, CreateTableBody, Expr, Relation};
+use readyset_client::{Modification, TableOperation};
+use readyset_data::{DfType, DfValue};
+use readyset_errors::{ReadySetError, ReadySetResult};
+use readyset_util::redacted::RedactedString;
+
+/// A set of per-table row filters, configured with --replication-row-filters, which restrict the
+/// rows of a table that are snapshotted and replicated to those matching a `WHERE` clause.
+///
+/// During snapshotting the filter is added to the query used to read the table from the upstream
+/// database, and during replication each [`TableOperation`] is checked against the filter (see
+/// [`RowFilter::apply`]) before being sent to readyset-server.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RowFilters {
+    filters: HashMap<Relation, Expr>,
+}
+
+impl RowFilters {
+    /// Parse a list of row filters of the form `<table>:<expression>`, separated by semicolons.
+    /// Tables without a schema are assumed to be in `default_schema`.
+    pub(crate) fn try_new(
+        dialect: nom_sql::Dialect,
+        row_filters: Option<RedactedString>,
+        default_schema: Option<&str>,
+    ) -> ReadySetResult<RowFilters> {
+        let mut filters = HashMap::new();
+        let Some(row_filters) = row_filters else {
+            return Ok(RowFilters { filters });
+        };
+
+        for filter in row_filters.split(';').filter(|f| !f.trim().is_empty()) {
+            let (table, expr) = filter.split_once(':').ok_or_else(|| {
+                ReadySetError::ReplicationFailed(
+                    "Row filters must be of the form <table>:<expression>".to_string(),
+                )
+            })?;
+
+            let mut table =
+                match replicator_table_list(dialect)(LocatedSpan::new(table.trim().as_bytes())) {
+                    Ok((rem, tables)) if rem.is_empty() && tables.len() == 1 => {
+                        tables.into_iter().next().expect("Checked length above")
+                    }
+                    _ => {
+                        return Err(ReadySetError::ReplicationFailed(format!(
+                            "Unable to parse table in row filter {filter}"
+                        )))
+                    }
+                };
+            if table.schema.is_none() {
+                table.schema = Some(default_schema.map(Into::into).ok_or_else(|| {
+                    ReadySetError::ReplicationFailed(format!(
+                        "No database and no default database for table {}",
+                        table.name
+                    ))
+                })?);
+            }
+
+            let expr = parse_expr(dialect, expr).map_err(|e| {
+                ReadySetError::ReplicationFailed(format!(
+                    "Unable to parse expression in row filter {filter}: {e}"
+                ))
+            })?;
+
+            if filters.insert(table, expr).is_some() {
+                return Err(ReadySetError::ReplicationFailed(format!(
+                    "Duplicate row filter for table in {filter}"
+                )));
+            }
+        }
+
+        Ok(RowFilters { filters })
+    }
+
+    /// Returns the expression rows of the given table must match to be replicated, if any
+    pub(crate) fn expr_for_table(&self, table: &Relation) -> Option<&Expr> {
+        self.filters.get(table)
+    }
+
+    /// Returns the filter rows of the given table must match to be replicated, if any, lowered
+    /// against the given schema of the table
+    pub(crate) fn filter_for_table(
+        &self,
+        table: &Relation,
+        schema: &CreateTableBody,
+        dialect: Dialect,
+    ) -> ReadySetResult<Option<RowFilter>> {
+        let Some(expr) = self.filters.get(table) else {
+            return Ok(None);
+        };
+
+        #[derive(Clone)]
+        struct RowFilterLowerContext<'a> {
+            table: &'a Relation,
+            schema: &'a CreateTableBody,
+            dialect: Dialect,
+        }
+
+        impl<'a> LowerContext for RowFilterLowerContext<'a> {
+            fn resolve_column(&self, col: Column) -> ReadySetResult<(usize, DfType)> {
+                if col
+                    .table
+                    .as_ref()
+                    .is_some_and(|table| table.name != self.table.name)
+                {
+                    return Err(ReadySetError::ReplicationFailed(format!(
+                        "Row filter for table {} may only reference its own columns",
+                        self.table.name
+                    )));
+                }
+
+                self.schema
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .find(|(_, field)| field.column.name == col.name)
+                    .map(|(idx, field)| {
+                        let ty = DfType::from_sql_type(&field.sql_type, self.dialect, |_| None)
+                            .unwrap_or(DfType::Unknown);
+                        (idx, ty)
+                    })
+                    .ok_or_else(|| ReadySetError::NoSuchColumn(col.name.to_string()))
+            }
+
+            fn resolve_type(&self, _ty: Relation) -> Option<DfType> {
+                None
+            }
+        }
+
+        let expr = DataflowExpr::lower(
+            expr.clone(),
+            dialect,
+            RowFilterLowerContext {
+                table,
+                schema,
+                dialect,
+            },
+        )?;
+
+        Ok(Some(RowFilter(expr)))
+    }
+}
+
+/// A row filter for a single table, lowered so that it can be evaluated against the rows of that
+/// table
+#[derive(Debug, Clone)]
+pub(crate) struct RowFilter(DataflowExpr);
+
+impl RowFilter {
+    /// Returns true if the given row should be replicated
+    pub(crate) fn matches(&self, row: &[DfValue]) -> ReadySetResult<bool> {
+        Ok(self.0.eval(row)?.is_truthy())
+    }
+
+    /// Rewrite the given list of table operations so that only rows matching the filter are
+    /// written to the table:
+    ///
+    /// * Inserts and deletes of rows that don't match the filter are dropped, since those rows are
+    ///   never stored in the table.
+    /// * Updates that provide the full new row are turned into a delete of the old row, followed by
+    ///   an insert of the new row if it matches the filter, which handles rows moving into or out
+    ///   of the filter.
+    /// * Deletes by key, and updates which leave some columns unchanged, can't be checked against
+    ///   the filter, so they're passed through as-is.
+    pub(crate) fn apply(
+        &self,
+        actions: Vec<TableOperation>,
+    ) -> ReadySetResult<Vec<TableOperation>> {
+        let mut filtered = Vec::with_capacity(actions.len());
+        for action in actions {
+            match action {
+                TableOperation::Insert(row) => {
+                    if self.matches(&row)? {
+                        filtered.push(TableOperation::Insert(row))
+                    }
+                }
+                TableOperation::DeleteRow { row } => {
+                    if self.matches(&row)? {
+                        filtered.push(TableOperation::DeleteRow { row })
+                    }
+                }
+                TableOperation::InsertOrUpdate { row, update } => {
+                    if self.matches(&row)? {
+                        filtered.push(TableOperation::InsertOrUpdate { row, update })
+                    }
+                }
+                TableOperation::Update { update, key } => {
+                    let new_row = update
+                        .iter()
+                        .map(|m| match m {
+                            Modification::Set(value) => Some(value.clone()),
+                            _ => None,
+                        })
+                        .collect::<Option<Vec<_>>>();
+
+                    // The key may be changing, and the old row may or may not be in the table, so
+                    // replace it with the new row rather than updating it in place
+                    match new_row {
+                        Some(row) if self.matches(&row)? => {
+                            filtered.push(TableOperation::DeleteByKey { key });
+                            filtered.push(TableOperation::Insert(row));
+                        }
+                        Some(_) => filtered.push(TableOperation::DeleteByKey { key }),
+                        None => filtered.push(TableOperation::Update { update, key }),
+                    }
+                }
+                action @ (TableOperation::DeleteByKey { .. }
+                | TableOperation::Truncate
+                | TableOperation::SetReplicationOffset(_)
+                | TableOperation::SetSnapshotMode(_)) => filtered.push(action),
+            }
+        }
+
+        Ok(filtered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom_sql::parse_create_table;
+
+    use super::*;
+
+    fn filters() -> RowFilters {
+        RowFilters::try_new(
+            nom_sql::Dialect::MySQL,
+            Some(
+                "t1:tenant_id = 42; other.t2: deleted_at IS NULL"
+                    .to_string()
+                    .into(),
+            ),
+            Some("noria"),
+        )
+        .unwrap()
+    }
+
+    fn t1() -> Relation {
+        Relation {
+            schema: Some("noria".into()),
+            name: "t1".into(),
+        }
+    }
+
+    fn t1_filter() -> RowFilter {
+        let schema = parse_create_table(
+            nom_sql::Dialect::MySQL,
+            "CREATE TABLE t1 (id int PRIMARY KEY, tenant_id int)",
+        )
+        .unwrap()
+        .body
+        .unwrap();
+        filters()
+            .filter_for_table(&t1(), &schema, Dialect::DEFAULT_MYSQL)
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn parse_filters() {
+        let filters = filters();
+        assert!(filters.expr_for_table(&t1()).is_some());
+        assert!(filters
+            .expr_for_table(&Relation {
+                schema: Some("other".into()),
+                name: "t2".into(),
+            })
+            .is_some());
+        assert!(filters
+            .expr_for_table(&Relation {
+                schema: Some("noria".into()),
+                name: "t2".into(),
+            })
+            .is_none());
+    }
+
+    #[test]
+    fn invalid_filters() {
+        RowFilters::try_new(
+            nom_sql::Dialect::MySQL,
+            Some("t1 tenant_id = 42".to_string().into()),
+            Some("noria"),
+        )
+        .unwrap_err();
+        RowFilters::try_new(
+            nom_sql::Dialect::PostgreSQL,
+            Some("t1: tenant_id = 42".to_string().into()),
+            None,
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn apply_filter() {
+        let actions = vec![
+            TableOperation::Insert(vec![1.into(), 42.into()]),
+            TableOperation::Insert(vec![2.into(), 43.into()]),
+            TableOperation::DeleteRow {
+                row: vec![2.into(), 43.into()],
+            },
+            TableOperation::Update {
+                key: vec![1.into()],
+                update: vec![Modification::Set(1.into()), Modification::Set(43.into())],
+            },
+            TableOperation::Update {
+                key: vec![2.into()],
+                update: vec![Modification::Set(2.into()), Modification::Set(42.into())],
+            },
+            TableOperation::Update {
+                key: vec![3.into()],
+                update: vec![Modification::None, Modification::Set(43.into())],
+            },
+        ];
+
+        assert_eq!(
+            t1_filter().apply(actions).unwrap(),
+            vec![
+                TableOperation::Insert(vec![1.into(), 42.into()]),
+                TableOperation::DeleteByKey {
+                    key: vec![1.into()],
+                },
+                TableOperation::DeleteByKey {
+                    key: vec![2.into()],
+                },
+                TableOperation::Insert(vec![2.into(), 42.into()]),
+                TableOperation::Update {
+                    key: vec![3.into()],
+                    update: vec![Modification::None, Modification::Set(43.into())],
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use nom_sql::{Dialect, DialectDisplay, Relation, TableKey};
+use tracing::warn;
+
+use crate::table_filter::TableFilter;
+
+/// The graph of foreign key references between the base tables of the upstream database.
+///
+/// During snapshotting this is used to order tables so that every table is snapshotted after the
+/// tables its foreign keys reference, and to warn about replicated tables which reference tables
+/// that are excluded from replication by the [`TableFilter`] - queries joining those tables can't
+/// be cached.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TableDependencies {
+    /// Map from each table to the set of (other) tables referenced by its foreign keys
+    references: HashMap<Relation, BTreeSet<Relation>>,
+}
+
+/// Returns the tables referenced by the foreign keys in `keys`, qualifying any unqualified table
+/// names with the schema of `table`
+fn foreign_key_targets<'a>(
+    table: &'a Relation,
+    keys: impl IntoIterator<Item = &'a TableKey> + 'a,
+) -> impl Iterator<Item = Relation> + 'a {
+    keys.into_iter().filter_map(move |key| match key {
+        TableKey::ForeignKey { target_table, .. } => Some(Relation {
+            schema: target_table.schema.clone().or_else(|| table.schema.clone()),
+            name: target_table.name.clone(),
+        }),
+        _ => None,
+    })
+}
+
+/// Emit a warning if `target` is excluded from replication by `table_filter`
+fn warn_if_excluded(
+    table: &Relation,
+    target: &Relation,
+    table_filter: &TableFilter,
+    dialect: Dialect,
+) {
+    let Some(schema) = &target.schema else {
+        return;
+    };
+    if !table_filter.should_be_processed(schema.as_str(), target.name.as_str()) {
+        warn!(
+            table = %table.display(dialect),
+            referenced_table = %target.display(dialect),
+            "Table has a foreign key referencing a table which is excluded from replication; \
+             queries joining these tables will not be cached"
+        );
+    }
+}
+
+/// Emit a warning for each foreign key in `keys` on the (replicated) table `table` which
+/// references a table excluded from replication by `table_filter`
+pub(crate) fn warn_on_excluded_references<'a>(
+    table: &'a Relation,
+    keys: impl IntoIterator<Item = &'a TableKey> + 'a,
+    table_filter: &TableFilter,
+    dialect: Dialect,
+) {
+    for target in foreign_key_targets(table, keys) {
+        warn_if_excluded(table, &target, table_filter, dialect);
+    }
+}
+
+impl TableDependencies {
+    /// Record the foreign keys among the given keys of the table `table`
+    pub(crate) fn add_table<'a>(
+        &mut self,
+        table: &'a Relation,
+        keys: impl IntoIterator<Item = &'a TableKey> + 'a,
+    ) {
+        let targets = foreign_key_targets(table, keys)
+            .filter(|target| target != table)
+            .collect();
+        self.references.insert(table.clone(), targets);
+    }
+
+    /// Emit a warning for each foreign key of a table in the graph which is replicated according
+    /// to `table_filter`, but references a table which isn't
+    pub(crate) fn warn_on_excluded_references(&self, table_filter: &TableFilter, dialect: Dialect) {
+        for (table, targets) in &self.references {
+            let Some(schema) = &table.schema else {
+                continue;
+            };
+            if table_filter.should_be_processed(schema.as_str(), table.name.as_str()) {
+                for target in targets {
+                    warn_if_excluded(table, target, table_filter, dialect);
+                }
+            }
+        }
+    }
+
+    /// Sort `tables` so that each table comes after all of the tables it references, preserving
+    /// the existing order as much as possible otherwise. Foreign key cycles are broken arbitrarily
+    /// (in the order the tables appear in `tables`).
+    pub(crate) fn sort_referenced_first<T, F>(&self, tables: &mut Vec<T>, relation: F)
+    where
+        F: Fn(&T) -> &Relation,
+    {
+        let positions = tables
+            .iter()
+            .enumerate()
+            .map(|(pos, table)| (relation(table).clone(), pos))
+            .collect::<HashMap<_, _>>();
+
+        fn visit(
+            pos: usize,
+            names: &[Relation],
+            positions: &HashMap<Relation, usize>,
+            references: &HashMap<Relation, BTreeSet<Relation>>,
+            visited: &mut HashSet<usize>,
+            order: &mut Vec<usize>,
+        ) {
+            if !visited.insert(pos) {
+                return;
+            }
+            for target in references.get(&names[pos]).into_iter().flatten() {
+                if let Some(target_pos) = positions.get(target) {
+                    visit(*target_pos, names, positions, references, visited, order);
+                }
+            }
+            order.push(pos);
+        }
+
+        let names = tables
+            .iter()
+            .map(|t| relation(t).clone())
+            .collect::<Vec<_>>();
+        let mut visited = HashSet::new();
+        let mut order = Vec::with_capacity(tables.len());
+        for pos in 0..tables.len() {
+            visit(
+                pos,
+                &names,
+                &positions,
+                &self.references,
+                &mut visited,
+                &mut order,
+            );
+        }
+
+        let mut slots = tables.drain(..).map(Some).collect::<Vec<_>>();
+        tables.extend(order.into_iter().map(|pos| {
+            slots[pos]
+                .take()
+                .expect("Each table is visited exactly once")
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom_sql::parse_create_table;
+
+    use super::*;
+
+    fn rel(name: &str) -> Relation {
+        Relation {
+            schema: Some("public".into()),
+            name: name.into(),
+        }
+    }
+
+    fn dependencies(tables: &[&str]) -> TableDependencies {
+        let mut dependencies = TableDependencies::default();
+        for create_table in tables {
+            let stmt = parse_create_table(Dialect::PostgreSQL, create_table).unwrap();
+            let table = Relation {
+                schema: Some("public".into()),
+                name: stmt.table.name,
+            };
+            dependencies.add_table(&table, stmt.body.unwrap().keys.iter().flatten());
+        }
+        dependencies
+    }
+
+    #[test]
+    fn referenced_tables_first() {
+        let dependencies = dependencies(&[
+            "CREATE TABLE line_items (id int, order_id int, product_id int, \
+             FOREIGN KEY (order_id) REFERENCES orders (id), \
+             FOREIGN KEY (product_id) REFERENCES public.products (id))",
+            "CREATE TABLE orders (id int, customer_id int, \
+             FOREIGN KEY (customer_id) REFERENCES customers (id))",
+            "CREATE TABLE customers (id int)",
+            "CREATE TABLE products (id int)",
+            "CREATE TABLE unrelated (id int)",
+        ]);
+
+        let mut tables = vec![
+            rel("line_items"),
+            rel("unrelated"),
+            rel("orders"),
+            rel("products"),
+            rel("customers"),
+        ];
+        dependencies.sort_referenced_first(&mut tables, |t| t);
+        assert_eq!(
+            tables,
+            vec![
+                rel("customers"),
+                rel("orders"),
+                rel("products"),
+                rel("line_items"),
+                rel("unrelated"),
+            ]
+        );
+    }
+
+    #[test]
+    fn self_reference_and_cycles() {
+        let dependencies = dependencies(&[
+            "CREATE TABLE employees (id int, manager_id int, \
+             FOREIGN KEY (manager_id) REFERENCES employees (id))",
+            "CREATE TABLE a (id int, b_id int, FOREIGN KEY (b_id) REFERENCES b (id))",
+            "CREATE TABLE b (id int, a_id int, FOREIGN KEY (a_id) REFERENCES a (id))",
+        ]);
+
+        let mut tables = vec![rel("employees"), rel("a"), rel("b")];
+        dependencies.sort_referenced_first(&mut tables, |t| t);
+        assert_eq!(tables, vec![rel("employees"), rel("b"), rel("a")]);
+    }
+
+    #[test]
+    fn missing_referenced_table() {
+        let dependencies = dependencies(&[
+            "CREATE TABLE posts (id int, user_id int, \
+             FOREIGN KEY (user_id) REFERENCES users (id))",
+            "CREATE TABLE comments (id int, post_id int, \
+             FOREIGN KEY (post_id) REFERENCES posts (id))",
+        ]);
+
+        let mut tables = vec![rel("comments"), rel("posts")];
+        dependencies.sort_referenced_first(&mut tables, |t| t);
+        assert_eq!(tables, vec![rel("posts"), rel("comments")]);
+    }
+}
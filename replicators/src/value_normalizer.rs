@@ -0,0 +1,419 @@
+use database_utils::ValueSizeCaps;
+use encoding_rs::Encoding;
+use metrics::counter;
+use nom_sql::{ColumnConstraint, ColumnSpecification, Relation, SqlType};
+use readyset_client::metrics::recorded;
+use readyset_client::{Modification, TableOperation};
+use readyset_data::DfValue;
+use tracing::{error, warn};
+
+/// Normalizes the values of the columns of a table before they're written to ReadySet:
+///
+/// * Values of binary columns (`BLOB`, `BINARY`, `bytea`, ...) are always written as
+///   [`DfValue::ByteArray`], even if the upstream database sent them as text, so that they compare
+///   equal to values of the same column and are returned to clients as binary data
+/// * Values larger than the cap on the size of their column, configured with
+///   --replication-value-size-caps, are replaced with `NULL`
+/// * Values of text columns with a character set other than UTF-8 (such as MySQL's `latin1`) are
+///   decoded into UTF-8 when normalizing replicated [`TableOperation`]s. Values read while
+///   snapshotting have already been converted to UTF-8 by the upstream database, so are left as-is
+///   by [`normalize_row`]. Operations containing values that can't be decoded are quarantined:
+///   they're logged and skipped, rather than failing replication.
+///
+/// Keys are left as-is, since they identify rows that have already been normalized.
+///
+/// [`normalize_row`]: ValueNormalizer::normalize_row
+#[derive(Debug, Clone)]
+pub(crate) struct ValueNormalizer {
+    table: Relation,
+    /// The indices of the binary columns of the table
+    binary: Vec<usize>,
+    /// The indices of the columns of the table with a size cap, along with the cap in bytes
+    size_caps: Vec<(usize, usize)>,
+    /// The indices of the text columns of the table with a non-UTF-8 character set, along with the
+    /// encoding of that character set
+    charsets: Vec<(usize, &'static Encoding)>,
+}
+
+/// A value which could not be decoded from the character set of its column
+#[derive(Debug)]
+struct UndecodableValue {
+    column_index: usize,
+    encoding: &'static Encoding,
+}
+
+fn is_binary_type(ty: &SqlType) -> bool {
+    matches!(
+        ty,
+        SqlType::Blob
+            | SqlType::TinyBlob
+            | SqlType::MediumBlob
+            | SqlType::LongBlob
+            | SqlType::ByteArray
+            | SqlType::Binary(_)
+            | SqlType::VarBinary(_)
+            | SqlType::Geometry(_)
+    )
+}
+
+/// Returns the name of the MySQL character set of the given column, if it has one
+fn column_charset(spec: &ColumnSpecification) -> Option<&str> {
+    spec.constraints.iter().find_map(|c| match c {
+        ColumnConstraint::CharacterSet(charset) => Some(charset.as_str()),
+        // Collation names are always prefixed with the name of their character set
+        ColumnConstraint::Collation(collation) => collation.split('_').next(),
+        _ => None,
+    })
+}
+
+/// Returns the encoding of the MySQL character set with the given name, or `None` if values in
+/// the character set don't need to be decoded (because they're already valid UTF-8) or if the
+/// character set isn't supported.
+fn encoding_for_mysql_charset(charset: &str) -> Option<&'static Encoding> {
+    use encoding_rs::*;
+
+    match charset.to_ascii_lowercase().as_str() {
+        // MySQL's `latin1` is actually Windows-1252
+        "latin1" => Some(WINDOWS_1252),
+        "latin2" => Some(ISO_8859_2),
+        "latin5" => Some(WINDOWS_1254),
+        "latin7" => Some(ISO_8859_13),
+        "greek" => Some(ISO_8859_7),
+        "hebrew" => Some(ISO_8859_8),
+        "cp1250" => Some(WINDOWS_1250),
+        "cp1251" => Some(WINDOWS_1251),
+        "cp1256" => Some(WINDOWS_1256),
+        "cp1257" => Some(WINDOWS_1257),
+        "cp866" => Some(IBM866),
+        "koi8r" => Some(KOI8_R),
+        "koi8u" => Some(KOI8_U),
+        "sjis" | "cp932" => Some(SHIFT_JIS),
+        "ujis" | "eucjpms" => Some(EUC_JP),
+        "euckr" => Some(EUC_KR),
+        "gb2312" | "gbk" => Some(GBK),
+        "gb18030" => Some(GB18030),
+        "big5" => Some(BIG5),
+        "ucs2" | "utf16" => Some(UTF_16BE),
+        "utf16le" => Some(UTF_16LE),
+        _ => None,
+    }
+}
+
+/// Returns the size in bytes of a string or binary value, or `None` for values of any other type
+fn value_size(value: &DfValue) -> Option<usize> {
+    match value {
+        DfValue::Text(t) => Some(t.as_str().len()),
+        DfValue::TinyText(t) => Some(t.as_str().len()),
+        DfValue::ByteArray(b) => Some(b.len()),
+        _ => None,
+    }
+}
+
+impl ValueNormalizer {
+    /// Construct a new `ValueNormalizer` for the current schema of the given table
+    pub(crate) fn new(table: &readyset_client::Table, value_size_caps: &ValueSizeCaps) -> Self {
+        let name = table.table_name();
+        let columns = table.columns();
+        let binary = match table.schema() {
+            Some(schema) => columns
+                .iter()
+                .enumerate()
+                .filter(|(_, column)| {
+                    schema
+                        .fields
+                        .iter()
+                        .any(|f| &f.column.name == *column && is_binary_type(&f.sql_type))
+                })
+                .map(|(idx, _)| idx)
+                .collect(),
+            None => vec![],
+        };
+        let charsets = match table.schema() {
+            Some(schema) => columns
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, column)| {
+                    let spec = schema.fields.iter().find(|f| &f.column.name == column)?;
+                    if !spec.sql_type.is_any_text() {
+                        return None;
+                    }
+                    let charset = column_charset(spec)?;
+                    let encoding = encoding_for_mysql_charset(charset);
+                    if encoding.is_none()
+                        && !matches!(
+                            charset.to_ascii_lowercase().as_str(),
+                            "utf8" | "utf8mb3" | "utf8mb4" | "ascii" | "binary"
+                        )
+                    {
+                        warn!(
+                            table = %name.display_unquoted(),
+                            %column,
+                            %charset,
+                            "Unsupported character set, values will be replicated as-is"
+                        );
+                    }
+                    encoding.map(|encoding| (idx, encoding))
+                })
+                .collect(),
+            None => vec![],
+        };
+        let size_caps = match value_size_caps.for_table(name) {
+            Some(caps) => columns
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, column)| caps.get(column).map(|cap| (idx, *cap)))
+                .collect(),
+            None => vec![],
+        };
+
+        Self {
+            table: name.clone(),
+            binary,
+            size_caps,
+            charsets,
+        }
+    }
+
+    /// Returns true if this normalizer never changes any values
+    pub(crate) fn is_noop(&self) -> bool {
+        self.binary.is_empty() && self.size_caps.is_empty() && self.charsets.is_empty()
+    }
+
+    /// Decode the given value of a replicated row from the character set of its column, if any
+    fn decode_value(&self, idx: usize, value: &mut DfValue) -> Result<(), UndecodableValue> {
+        let Some((_, encoding)) = self.charsets.iter().find(|(i, _)| *i == idx) else {
+            return Ok(());
+        };
+        let Ok(bytes) = value.as_bytes() else {
+            return Ok(());
+        };
+        match encoding.decode_without_bom_handling_and_without_replacement(bytes) {
+            Some(s) => {
+                *value = DfValue::from(s.as_ref());
+                Ok(())
+            }
+            None => Err(UndecodableValue {
+                column_index: idx,
+                encoding,
+            }),
+        }
+    }
+
+    fn normalize_value(&self, idx: usize, value: &mut DfValue) {
+        if self.binary.contains(&idx) {
+            if let DfValue::Text(_) | DfValue::TinyText(_) = value {
+                if let Ok(bytes) = value.as_bytes() {
+                    *value = DfValue::ByteArray(bytes.to_vec().into());
+                }
+            }
+        }
+
+        if let Some((_, cap)) = self.size_caps.iter().find(|(i, _)| *i == idx) {
+            if let Some(size) = value_size(value).filter(|size| size > cap) {
+                warn!(
+                    table = %self.table.display_unquoted(),
+                    column_index = idx,
+                    size,
+                    cap,
+                    "Value is larger than the size cap of its column, replicating it as NULL"
+                );
+                counter!(recorded::REPLICATOR_OVERSIZED_VALUES, 1u64);
+                *value = DfValue::None;
+            }
+        }
+    }
+
+    /// Normalize the values of the given row of the table
+    pub(crate) fn normalize_row(&self, row: &mut [DfValue]) {
+        for (idx, value) in row.iter_mut().enumerate() {
+            self.normalize_value(idx, value);
+        }
+    }
+
+    /// Decode and normalize the values of the given replicated row of the table
+    fn normalize_replicated_row(&self, row: &mut [DfValue]) -> Result<(), UndecodableValue> {
+        for (idx, value) in row.iter_mut().enumerate() {
+            self.decode_value(idx, value)?;
+            self.normalize_value(idx, value);
+        }
+        Ok(())
+    }
+
+    /// Decode and normalize the values of the given replicated update to the table
+    fn normalize_update(&self, update: &mut [Modification]) -> Result<(), UndecodableValue> {
+        for (idx, modification) in update.iter_mut().enumerate() {
+            if let Modification::Set(value) = modification {
+                self.decode_value(idx, value)?;
+                self.normalize_value(idx, value);
+            }
+        }
+        Ok(())
+    }
+
+    /// Normalize the values of all the given replicated table operations, quarantining (logging
+    /// and removing) any operations containing values which can't be decoded from the character
+    /// set of their column
+    pub(crate) fn normalize_actions(&self, actions: &mut Vec<TableOperation>) {
+        actions.retain_mut(|action| {
+            let res = match action {
+                TableOperation::Insert(row) | TableOperation::DeleteRow { row } => {
+                    self.normalize_replicated_row(row)
+                }
+                TableOperation::InsertOrUpdate { row, update } => self
+                    .normalize_replicated_row(row)
+                    .and_then(|_| self.normalize_update(update)),
+                TableOperation::Update { update, .. } => self.normalize_update(update),
+                TableOperation::DeleteByKey { .. }
+                | TableOperation::Truncate
+                | TableOperation::SetReplicationOffset(_)
+                | TableOperation::SetSnapshotMode(_) => Ok(()),
+            };
+
+            match res {
+                Ok(()) => true,
+                Err(UndecodableValue {
+                    column_index,
+                    encoding,
+                }) => {
+                    error!(
+                        table = %self.table.display_unquoted(),
+                        column_index,
+                        encoding = encoding.name(),
+                        ?action,
+                        "Could not decode value from the character set of its column, skipping row"
+                    );
+                    counter!(recorded::REPLICATOR_QUARANTINED_ROWS, 1u64);
+                    false
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalizer() -> ValueNormalizer {
+        ValueNormalizer {
+            table: Relation {
+                schema: Some("s".into()),
+                name: "t".into(),
+            },
+            binary: vec![1],
+            size_caps: vec![(1, 4), (2, 3)],
+            charsets: vec![],
+        }
+    }
+
+    #[test]
+    fn binary_columns() {
+        let mut row = vec![1.into(), "ab".into(), "cd".into()];
+        normalizer().normalize_row(&mut row);
+        assert_eq!(
+            row,
+            vec![
+                1.into(),
+                DfValue::ByteArray(b"ab".to_vec().into()),
+                "cd".into()
+            ]
+        );
+    }
+
+    #[test]
+    fn size_caps() {
+        let mut actions = vec![
+            TableOperation::Insert(vec![
+                1.into(),
+                DfValue::ByteArray(vec![0; 5].into()),
+                "abc".into(),
+            ]),
+            TableOperation::Update {
+                key: vec![1.into()],
+                update: vec![
+                    Modification::None,
+                    Modification::Set(DfValue::ByteArray(vec![0; 4].into())),
+                    Modification::Set("abcd".into()),
+                ],
+            },
+        ];
+
+        normalizer().normalize_actions(&mut actions);
+
+        assert_eq!(
+            actions,
+            vec![
+                TableOperation::Insert(vec![1.into(), DfValue::None, "abc".into()]),
+                TableOperation::Update {
+                    key: vec![1.into()],
+                    update: vec![
+                        Modification::None,
+                        Modification::Set(DfValue::ByteArray(vec![0; 4].into())),
+                        Modification::Set(DfValue::None),
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_charsets() {
+        let normalizer = ValueNormalizer {
+            table: Relation {
+                schema: Some("s".into()),
+                name: "t".into(),
+            },
+            binary: vec![],
+            size_caps: vec![],
+            charsets: vec![
+                (1, encoding_for_mysql_charset("latin1").unwrap()),
+                (2, encoding_for_mysql_charset("sjis").unwrap()),
+            ],
+        };
+        let mut actions = vec![
+            TableOperation::Insert(vec![
+                1.into(),
+                DfValue::ByteArray(b"caf\xe9".to_vec().into()),
+                DfValue::ByteArray(b"\x82\xa0".to_vec().into()),
+            ]),
+            // Valid UTF-8, but still needs to be decoded as latin1
+            TableOperation::DeleteRow {
+                row: vec![2.into(), "é".into(), DfValue::None],
+            },
+            // Not valid Shift-JIS
+            TableOperation::Insert(vec![
+                3.into(),
+                "abc".into(),
+                DfValue::ByteArray(b"\x82".to_vec().into()),
+            ]),
+        ];
+
+        normalizer.normalize_actions(&mut actions);
+
+        assert_eq!(
+            actions,
+            vec![
+                TableOperation::Insert(vec![1.into(), "café".into(), "あ".into()]),
+                TableOperation::DeleteRow {
+                    row: vec![2.into(), "Ã©".into(), DfValue::None],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshot_rows_are_not_decoded() {
+        let normalizer = ValueNormalizer {
+            table: Relation {
+                schema: Some("s".into()),
+                name: "t".into(),
+            },
+            binary: vec![],
+            size_caps: vec![],
+            charsets: vec![(0, encoding_for_mysql_charset("latin1").unwrap())],
+        };
+        let mut row = vec!["café".into()];
+        normalizer.normalize_row(&mut row);
+        assert_eq!(row, vec![DfValue::from("café")]);
+    }
+}
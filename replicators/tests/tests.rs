@@ -13,6 +13,7 @@ use rand::distributions::Alphanumeric;
 use rand::{Rng, SeedableRng};
 use readyset_client::consensus::{Authority, LocalAuthority, LocalAuthorityStore};
 use readyset_client::recipe::changelist::{Change, ChangeList, CreateCache};
+use readyset_client::status::ReplicationPauseState;
 use readyset_client::ReadySetHandle;
 use readyset_data::{Collation, DfValue, Dialect, TinyText};
 use readyset_errors::{internal, ReadySetError, ReadySetResult};
@@ -24,6 +25,7 @@ use replicators::db_util::error_is_slot_not_found;
 use replicators::{ControllerMessage, NoriaAdapter, ReplicatorMessage};
 use test_utils::slow;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::sync::watch;
 use tokio::time::sleep;
 use tokio_postgres::error::SqlState;
 use tracing::{debug, error, trace};
@@ -368,6 +370,7 @@ impl TestHandle {
         let (controll_receiver, _controll_sender) = TestControllChannel::new();
         self.notification_channel = Some(receiver);
         runtime.spawn(async move {
+            let (pause_state, _) = watch::channel(ReplicationPauseState::Running);
             if let Err(error) = NoriaAdapter::start(
                 controller,
                 Config {
@@ -376,6 +379,7 @@ impl TestHandle {
                 },
                 sender,
                 controll_receiver,
+                &pause_state,
                 telemetry_sender,
                 server_startup,
                 false, // disable statement logging in tests
@@ -448,6 +452,11 @@ impl TestHandle {
                                 .unwrap(),
                         ),
                         always: false,
+                        frontier_strategy: None,
+                        source: Default::default(),
+                        original_sql: None,
+                        disable_predicate_pushdown: false,
+                        standby: false,
                     }),
                 ],
                 self.dialect,
@@ -743,6 +752,20 @@ async fn mysql_replication_resnapshot() {
     resnapshot_inner(&mysql_url()).await.unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+#[slow]
+async fn psql14_create_table_as_select() {
+    create_table_as_select_inner(&pgsql_url()).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+#[slow]
+async fn mysql_create_table_as_select() {
+    create_table_as_select_inner(&mysql_url()).await.unwrap();
+}
+
 #[tokio::test(flavor = "multi_thread")]
 #[serial_test::serial]
 #[slow]
@@ -1121,18 +1144,26 @@ async fn replication_skip_unparsable_inner(url: &str) -> ReadySetResult<()> {
     readyset_tracing::init_test_logging();
     let mut client = DbConnection::connect(url).await?;
 
+    // A column type that exists upstream, but that we can't parse
+    let unparsable_type = if url.starts_with("postgresql") {
+        "polygon"
+    } else {
+        "year"
+    };
+
     client
-        .query(
+        .query(&format!(
             "
-            DROP TABLE IF EXISTS t1 CASCADE; CREATE TABLE t1 (id polygon);
+            DROP TABLE IF EXISTS t1 CASCADE; CREATE TABLE t1 (id {unparsable_type});
             DROP TABLE IF EXISTS t2 CASCADE; CREATE TABLE t2 (id int);
             DROP VIEW IF EXISTS t1_view; CREATE VIEW t1_view AS SELECT * FROM t1;
             DROP VIEW IF EXISTS t2_view; CREATE VIEW t2_view AS SELECT * FROM t2;
             DROP VIEW IF EXISTS unparsable_view; CREATE VIEW unparsable_view AS SELECT * FROM (SELECT * FROM t2) sq;
             INSERT INTO t2 VALUES (1),(2),(3);
-            ",
-        )
-        .await.expect("failed to setup");
+            "
+        ))
+        .await
+        .expect("failed to setup");
 
     let (mut ctx, shutdown_tx) = TestHandle::start_noria(url.to_string(), None)
         .await
@@ -1163,13 +1194,13 @@ async fn replication_skip_unparsable_inner(url: &str) -> ReadySetResult<()> {
         .expect_err("Can't have view for nonexistent table");
 
     client
-        .query(
+        .query(&format!(
             "
-            DROP TABLE IF EXISTS t3 CASCADE; CREATE TABLE t3 (id polygon);
+            DROP TABLE IF EXISTS t3 CASCADE; CREATE TABLE t3 (id {unparsable_type});
             DROP VIEW IF EXISTS t3_view; CREATE VIEW t3_view AS SELECT * FROM t3;
             INSERT INTO t2 VALUES (4),(5),(6);
-            ",
-        )
+            "
+        ))
         .await
         .expect("query failed");
 
@@ -1614,6 +1645,126 @@ async fn mysql_enum_replication() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+#[slow]
+async fn mysql_latin1_replication() {
+    readyset_tracing::init_test_logging();
+    let url = &mysql_url();
+    let mut client = DbConnection::connect(url).await.unwrap();
+    client
+        .query(
+            "
+            DROP TABLE IF EXISTS `latin1_test` CASCADE;
+            DROP VIEW IF EXISTS latin1_test_view;
+            CREATE TABLE `latin1_test` (
+                id int NOT NULL PRIMARY KEY,
+                s varchar(255)
+            ) DEFAULT CHARSET=latin1;
+            CREATE VIEW latin1_test_view AS SELECT * FROM `latin1_test` ORDER BY id ASC;
+            INSERT INTO latin1_test VALUES (0, 'café')",
+        )
+        .await
+        .unwrap();
+
+    let (mut ctx, shutdown_tx) = TestHandle::start_noria(url.to_string(), None)
+        .await
+        .unwrap();
+    ctx.notification_channel
+        .as_mut()
+        .unwrap()
+        .snapshot_completed()
+        .await
+        .unwrap();
+
+    ctx.check_results(
+        "latin1_test_view",
+        "Snapshot",
+        &[&[DfValue::Int(0), DfValue::from("café")]],
+    )
+    .await
+    .unwrap();
+
+    client
+        .query("INSERT INTO latin1_test VALUES (1, 'naïve'), (2, 'Ã©')")
+        .await
+        .unwrap();
+
+    ctx.check_results(
+        "latin1_test_view",
+        "Replication",
+        &[
+            &[DfValue::Int(0), DfValue::from("café")],
+            &[DfValue::Int(1), DfValue::from("naïve")],
+            &[DfValue::Int(2), DfValue::from("Ã©")],
+        ],
+    )
+    .await
+    .unwrap();
+
+    client.stop().await;
+    ctx.stop().await;
+    shutdown_tx.shutdown().await;
+}
+
+/// Checks that the binlog connection isn't presumed dead while the upstream is idle for longer than
+/// the heartbeat timeout, as long as the upstream keeps sending heartbeats
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+#[slow]
+async fn mysql_replication_heartbeats() {
+    readyset_tracing::init_test_logging();
+    let url = &mysql_url();
+    let mut client = DbConnection::connect(url).await.unwrap();
+    client
+        .query(
+            "
+            DROP TABLE IF EXISTS heartbeat_test CASCADE;
+            DROP VIEW IF EXISTS heartbeat_test_view;
+            CREATE TABLE heartbeat_test (id int NOT NULL PRIMARY KEY);
+            CREATE VIEW heartbeat_test_view AS SELECT * FROM heartbeat_test ORDER BY id ASC;
+            INSERT INTO heartbeat_test VALUES (1);",
+        )
+        .await
+        .unwrap();
+
+    let config = Config {
+        replication_heartbeat_interval_secs: 1,
+        ..Default::default()
+    };
+    let (mut ctx, shutdown_tx) = TestHandle::start_noria(url.to_string(), Some(config))
+        .await
+        .unwrap();
+    ctx.notification_channel
+        .as_mut()
+        .unwrap()
+        .snapshot_completed()
+        .await
+        .unwrap();
+    ctx.check_results("heartbeat_test_view", "Snapshot", &[&[DfValue::Int(1)]])
+        .await
+        .unwrap();
+
+    // Stay idle for several heartbeat timeouts
+    tokio::time::sleep(Duration::from_secs(5)).await;
+
+    client
+        .query("INSERT INTO heartbeat_test VALUES (2)")
+        .await
+        .unwrap();
+    ctx.check_results(
+        "heartbeat_test_view",
+        "Replication",
+        &[&[DfValue::Int(1)], &[DfValue::Int(2)]],
+    )
+    .await
+    .unwrap();
+
+    client.stop().await;
+    ctx.stop().await;
+    shutdown_tx.shutdown().await;
+}
+
 async fn postgresql_ddl_replicate_drop_table_internal(url: &str) {
     readyset_tracing::init_test_logging();
     let mut client = DbConnection::connect(url).await.unwrap();
@@ -1662,6 +1813,66 @@ async fn postgresql_ddl_replicate_drop_table_internal(url: &str) {
     shutdown_tx.shutdown().await;
 }
 
+async fn create_table_as_select_inner(url: &str) -> ReadySetResult<()> {
+    let mut client = DbConnection::connect(url).await?;
+    client
+        .query(
+            "
+            DROP TABLE IF EXISTS ctas_src CASCADE;
+            DROP TABLE IF EXISTS ctas_dst CASCADE;
+            DROP VIEW IF EXISTS ctas_src_view;
+            DROP VIEW IF EXISTS ctas_dst_view;
+            CREATE TABLE ctas_src (id int, val varchar(255));
+            INSERT INTO ctas_src VALUES (1, 'a'), (2, 'b');",
+        )
+        .await?;
+
+    let (mut ctx, shutdown_tx) = TestHandle::start_noria(url.to_string(), None).await?;
+    ctx.notification_channel
+        .as_mut()
+        .unwrap()
+        .snapshot_completed()
+        .await
+        .unwrap();
+
+    client
+        .query("CREATE TABLE ctas_dst AS SELECT * FROM ctas_src")
+        .await?;
+    client
+        .query("INSERT INTO ctas_src SELECT id + 2, val FROM ctas_src")
+        .await?;
+    client
+        .query(
+            "CREATE VIEW ctas_src_view AS SELECT * FROM ctas_src;
+             CREATE VIEW ctas_dst_view AS SELECT * FROM ctas_dst;",
+        )
+        .await?;
+
+    ctx.check_results(
+        "ctas_dst_view",
+        "CREATE TABLE AS SELECT",
+        &[
+            &[DfValue::from(1), DfValue::from("a")],
+            &[DfValue::from(2), DfValue::from("b")],
+        ],
+    )
+    .await?;
+    ctx.check_results(
+        "ctas_src_view",
+        "INSERT ... SELECT",
+        &[
+            &[DfValue::from(1), DfValue::from("a")],
+            &[DfValue::from(2), DfValue::from("b")],
+            &[DfValue::from(3), DfValue::from("a")],
+            &[DfValue::from(4), DfValue::from("b")],
+        ],
+    )
+    .await?;
+
+    shutdown_tx.shutdown().await;
+    Ok(())
+}
+
 async fn postgresql_ddl_replicate_create_table_internal(url: &str) {
     readyset_tracing::init_test_logging();
     let mut client = DbConnection::connect(url).await.unwrap();
@@ -1735,6 +1946,11 @@ async fn postgresql_ddl_replicate_drop_view_internal(url: &str) {
             .unwrap(),
         ),
         always: false,
+        frontier_strategy: None,
+        source: Default::default(),
+        original_sql: None,
+        disable_predicate_pushdown: false,
+        standby: false,
     });
     ctx.noria
         .extend_recipe(ChangeList::from_change(
@@ -1816,6 +2032,11 @@ async fn postgresql_ddl_replicate_create_view_internal(url: &str) {
                     .unwrap()
                 ),
                 always: true,
+                frontier_strategy: None,
+                source: Default::default(),
+                original_sql: None,
+                disable_predicate_pushdown: false,
+                standby: false,
             }),
             Dialect::DEFAULT_POSTGRESQL
         ))
@@ -2390,6 +2611,154 @@ async fn postgresql_drop_nonexistent_replication_slot() {
     ));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+#[slow]
+async fn postgresql_recreate_dropped_publication() {
+    let url = pgsql_url();
+    let mut client = DbConnection::connect(&url).await.unwrap();
+    client
+        .query(
+            "DROP TABLE IF EXISTS t CASCADE;
+             CREATE TABLE t (x int PRIMARY KEY);
+             CREATE VIEW v AS SELECT x FROM t;
+             INSERT INTO t (x) values (1);",
+        )
+        .await
+        .unwrap();
+
+    let config = Config {
+        replication_server_id: Some(ReplicationServerId("recreate_publication".into())),
+        ..Default::default()
+    };
+    let (mut ctx, shutdown_tx) = TestHandle::start_noria(url.to_string(), Some(config.clone()))
+        .await
+        .unwrap();
+    ctx.notification_channel
+        .as_mut()
+        .unwrap()
+        .snapshot_completed()
+        .await
+        .unwrap();
+
+    ctx.check_results("v", "Snapshot", &[&[DfValue::from(1)]])
+        .await
+        .unwrap();
+
+    // Drop our publication while we aren't replicating, then make sure we recreate it and pick up
+    // the writes we missed by resnapshotting once we reconnect. The legacy publication is dropped
+    // too, since we'd otherwise fall back to replicating from it.
+    ctx.stop_repl().await;
+    client
+        .query(
+            "DROP PUBLICATION readyset_recreate_publication;
+             DROP PUBLICATION IF EXISTS readyset;
+             INSERT INTO t (x) values (2);",
+        )
+        .await
+        .unwrap();
+
+    ctx.start_repl(Some(config), TelemetrySender::new_no_op(), false)
+        .await
+        .unwrap();
+    ctx.check_results(
+        "v",
+        "Resnapshot",
+        &[&[DfValue::from(1)], &[DfValue::from(2)]],
+    )
+    .await
+    .unwrap();
+
+    client.query("INSERT INTO t (x) values (3)").await.unwrap();
+    ctx.check_results(
+        "v",
+        "Replication",
+        &[
+            &[DfValue::from(1)],
+            &[DfValue::from(2)],
+            &[DfValue::from(3)],
+        ],
+    )
+    .await
+    .unwrap();
+
+    client.stop().await;
+    ctx.stop().await;
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+#[slow]
+async fn postgresql_replication_row_filters() {
+    let url = pgsql_url();
+    let mut client = DbConnection::connect(&url).await.unwrap();
+    client
+        .query(
+            "DROP TABLE IF EXISTS t CASCADE;
+             CREATE TABLE t (id int PRIMARY KEY, tenant_id int);
+             CREATE VIEW v AS SELECT id FROM t;
+             INSERT INTO t (id, tenant_id) VALUES (1, 42), (2, 43);",
+        )
+        .await
+        .unwrap();
+
+    let config = Config {
+        replication_row_filters: Some("public.t:tenant_id = 42".to_string().into()),
+        ..Default::default()
+    };
+    let (mut ctx, shutdown_tx) = TestHandle::start_noria(url.to_string(), Some(config))
+        .await
+        .unwrap();
+    ctx.notification_channel
+        .as_mut()
+        .unwrap()
+        .snapshot_completed()
+        .await
+        .unwrap();
+
+    ctx.check_results("v", "Snapshot", &[&[DfValue::from(1)]])
+        .await
+        .unwrap();
+
+    // Rows moving into the filter are inserted, and rows that don't match it are ignored
+    client
+        .query(
+            "INSERT INTO t (id, tenant_id) VALUES (3, 42), (4, 43);
+             UPDATE t SET tenant_id = 42 WHERE id = 2;",
+        )
+        .await
+        .unwrap();
+    ctx.check_results(
+        "v",
+        "Replication",
+        &[
+            &[DfValue::from(1)],
+            &[DfValue::from(2)],
+            &[DfValue::from(3)],
+        ],
+    )
+    .await
+    .unwrap();
+
+    // Rows moving out of the filter are deleted
+    client
+        .query("UPDATE t SET tenant_id = 43 WHERE id = 1")
+        .await
+        .unwrap();
+    ctx.check_results(
+        "v",
+        "Replication",
+        &[&[DfValue::from(2)], &[DfValue::from(3)]],
+    )
+    .await
+    .unwrap();
+
+    client.stop().await;
+    ctx.stop().await;
+    shutdown_tx.shutdown().await;
+}
+
 /// Given a table, check that it has an associated TOAST table
 async fn postgresql_is_toasty(client: &tokio_postgres::client::Client, table: &str) -> bool {
     let res = client